@@ -1,3 +1,5 @@
+use crate::services::chat_export::{ChatExportFormat, ChatExportService};
+use crate::services::prompt_bundle::{ConflictStrategy, PromptBundleService, PromptTemplateBundle, TemplateImportReport};
 use crate::storage::ai::AIStore;
 use crate::storage::Database;
 use std::sync::Mutex;
@@ -93,3 +95,124 @@ pub fn get_prompt_template(
         .map_err(|e| format!("Failed to get template: {}", e))
 }
 
+
+/// Export one or more conversations as Markdown or JSON. Without ids, every
+/// conversation in the main listing is exported.
+#[tauri::command]
+pub fn export_conversations(
+    ids: Option<Vec<String>>,
+    format: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String, String> {
+    let format = ChatExportFormat::parse(&format)
+        .ok_or_else(|| format!("Unsupported export format: {}", format))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = AIStore::new(db_guard.conn.clone())
+        .map_err(|e| format!("Failed to initialize AIStore: {}", e))?;
+    let ids = match ids {
+        Some(ids) => ids,
+        None => store.list_conversations()
+            .map_err(|e| format!("Failed to list conversations: {}", e))?
+            .into_iter()
+            .map(|c| c.id)
+            .collect(),
+    };
+    ChatExportService::export(&store, &ids, format)
+        .map_err(|e| format!("Failed to export conversations: {}", e))
+}
+
+#[tauri::command]
+pub fn archive_conversation(
+    id: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<bool, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = AIStore::new(db_guard.conn.clone())
+        .map_err(|e| format!("Failed to initialize AIStore: {}", e))?;
+    store.archive_conversation(&id)
+        .map_err(|e| format!("Failed to archive conversation: {}", e))
+}
+
+/// Archive conversations with no activity in the last `older_than_days` days.
+#[tauri::command]
+pub fn archive_old_conversations(
+    older_than_days: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<usize, String> {
+    if older_than_days < 1 {
+        return Err("older_than_days must be at least 1".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = AIStore::new(db_guard.conn.clone())
+        .map_err(|e| format!("Failed to initialize AIStore: {}", e))?;
+    let cutoff = chrono::Utc::now().timestamp() - older_than_days * 86_400;
+    store.archive_conversations_before(cutoff)
+        .map_err(|e| format!("Failed to archive conversations: {}", e))
+}
+
+#[tauri::command]
+pub fn list_archived_conversations(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::ai::ArchivedConversation>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = AIStore::new(db_guard.conn.clone())
+        .map_err(|e| format!("Failed to initialize AIStore: {}", e))?;
+    store.list_archived_conversations()
+        .map_err(|e| format!("Failed to list archived conversations: {}", e))
+}
+
+#[tauri::command]
+pub fn restore_conversation(
+    id: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<bool, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = AIStore::new(db_guard.conn.clone())
+        .map_err(|e| format!("Failed to initialize AIStore: {}", e))?;
+    store.restore_conversation(&id)
+        .map_err(|e| format!("Failed to restore conversation: {}", e))
+}
+
+/// Import a template bundle from a file path or URL. Name conflicts are
+/// resolved by `on_conflict`: "keep_newer" (default), "overwrite", "skip" or "rename".
+#[tauri::command]
+pub async fn import_prompt_templates(
+    path_or_url: String,
+    on_conflict: Option<ConflictStrategy>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<TemplateImportReport, String> {
+    let bundle = PromptBundleService::load(&path_or_url)
+        .await
+        .map_err(|e| format!("Failed to load template bundle: {}", e))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = AIStore::new(db_guard.conn.clone())
+        .map_err(|e| format!("Failed to initialize AIStore: {}", e))?;
+    PromptBundleService::import(&store, &bundle, on_conflict.unwrap_or(ConflictStrategy::KeepNewer))
+        .map_err(|e| format!("Failed to import templates: {}", e))
+}
+
+/// Export templates as a shareable bundle. Without ids, all templates are exported.
+/// When `path` is given the bundle is also written there.
+#[tauri::command]
+pub fn export_prompt_templates(
+    ids: Option<Vec<i64>>,
+    bundle_name: Option<String>,
+    author: Option<String>,
+    path: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<PromptTemplateBundle, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = AIStore::new(db_guard.conn.clone())
+        .map_err(|e| format!("Failed to initialize AIStore: {}", e))?;
+    let bundle = PromptBundleService::export(&store, ids.as_deref(), bundle_name, author)
+        .map_err(|e| format!("Failed to export templates: {}", e))?;
+
+    if let Some(path) = path {
+        let json = serde_json::to_string_pretty(&bundle)
+            .map_err(|e| format!("Failed to serialize bundle: {}", e))?;
+        std::fs::write(&path, json)
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+
+    Ok(bundle)
+}
@@ -43,6 +43,44 @@ pub fn get_statistics(
         .map_err(|e| format!("Failed to get statistics: {}", e))
 }
 
+/// Commands slower than `threshold_ms` (default: the slow-command
+/// threshold) over the last `hours_back` hours, grouped by name.
+#[tauri::command]
+pub fn get_slow_commands(
+    threshold_ms: Option<f64>,
+    hours_back: Option<i64>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::SlowCommand>, String> {
+    use crate::services::command_trace::{CONFIG_SLOW_MS, DEFAULT_SLOW_MS};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let threshold = match threshold_ms {
+        Some(t) => t.max(0.0),
+        None => db_guard
+            .get_config(CONFIG_SLOW_MS)
+            .map_err(|e| format!("Failed to read slow command threshold: {}", e))?
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(DEFAULT_SLOW_MS),
+    };
+    let since = chrono::Utc::now().timestamp() - hours_back.unwrap_or(24).max(1) * 3600;
+    let store = AnalyticsStore::new(db_guard.conn.clone());
+    store.slow_commands(threshold, since, limit.unwrap_or(50).clamp(1, 500))
+        .map_err(|e| format!("Failed to get slow commands: {}", e))
+}
+
+/// Commands at least this slow are reported live over the WebSocket.
+#[tauri::command]
+pub fn set_slow_command_threshold(
+    threshold_ms: f64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    db_guard
+        .set_config(crate::services::command_trace::CONFIG_SLOW_MS, &threshold_ms.max(1.0).to_string())
+        .map_err(|e| format!("Failed to save slow command threshold: {}", e))
+}
+
 #[tauri::command]
 pub fn test_analytics_collection(
     db: State<'_, Mutex<Database>>,
@@ -0,0 +1,101 @@
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::backup::{
+    BackupEntry, BackupReport, BackupService, CONFIG_DESTINATION, CONFIG_INTERVAL_HOURS, CONFIG_KEEP_DAILY,
+    CONFIG_KEEP_WEEKLY, CONFIG_WEBDAV_URL, CONFIG_WEBDAV_USER, WEBDAV_KEY_PROVIDER,
+};
+use crate::storage::Database;
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+fn db_arc(db: &State<'_, Mutex<Database>>) -> Result<Arc<Mutex<Database>>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BackupSettings {
+    pub destination: Option<String>, // "webdav", "s3", or empty to disable
+    pub webdav_url: Option<String>,
+    pub webdav_user: Option<String>,
+    pub webdav_password: Option<String>,
+    pub interval_hours: Option<i64>, // 0 disables scheduled backups
+    pub keep_daily: Option<i64>,
+    pub keep_weekly: Option<i64>,
+}
+
+#[tauri::command]
+pub fn set_backup_settings(
+    settings: BackupSettings,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mut values: Vec<(&str, String)> = Vec::new();
+    if let Some(destination) = settings.destination {
+        values.push((CONFIG_DESTINATION, destination));
+    }
+    if let Some(url) = settings.webdav_url {
+        values.push((CONFIG_WEBDAV_URL, url.trim().to_string()));
+    }
+    if let Some(user) = settings.webdav_user {
+        values.push((CONFIG_WEBDAV_USER, user));
+    }
+    if let Some(hours) = settings.interval_hours {
+        values.push((CONFIG_INTERVAL_HOURS, hours.max(0).to_string()));
+    }
+    if let Some(days) = settings.keep_daily {
+        values.push((CONFIG_KEEP_DAILY, days.max(1).to_string()));
+    }
+    if let Some(weeks) = settings.keep_weekly {
+        values.push((CONFIG_KEEP_WEEKLY, weeks.max(0).to_string()));
+    }
+    for (key, value) in values {
+        db_guard.set_config(key, &value)
+            .map_err(|e| format!("Failed to save backup settings: {}", e))?;
+    }
+    if let Some(password) = settings.webdav_password {
+        if password.is_empty() {
+            api_key_manager.delete_key(WEBDAV_KEY_PROVIDER)
+        } else {
+            api_key_manager.store_key(WEBDAV_KEY_PROVIDER, &password)
+        }
+        .map_err(|e| format!("Failed to save WebDAV password: {}", e))?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn run_backup_now(
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<BackupReport, String> {
+    let db_arc = db_arc(&db)?;
+    BackupService::run_backup(&db_arc, &api_key_manager)
+        .await
+        .map_err(|e| format!("Backup failed: {}", e))
+}
+
+#[tauri::command]
+pub async fn list_remote_backups(
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<Vec<BackupEntry>, String> {
+    let db_arc = db_arc(&db)?;
+    BackupService::list_backups(&db_arc, &api_key_manager)
+        .await
+        .map_err(|e| format!("Failed to list backups: {}", e))
+}
+
+/// Download a backup from the destination. It is applied the next time the app starts.
+#[tauri::command]
+pub async fn restore_backup_from_remote(
+    name: String,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<(), String> {
+    let db_arc = db_arc(&db)?;
+    BackupService::restore(&db_arc, &api_key_manager, &name)
+        .await
+        .map_err(|e| format!("Failed to restore backup: {}", e))
+}
@@ -0,0 +1,120 @@
+use crate::services::benchmark_tracker::{
+    self, BenchmarkTracker, BenchmarkTrend, CONFIG_BASELINE_WINDOW, CONFIG_THRESHOLD_PERCENT, CONFIG_WATCH_DIR,
+};
+use crate::storage::benchmarks::{BenchmarkRegression, BenchmarkResult, BenchmarkStore, NewBenchmarkResult};
+use crate::storage::Database;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+fn db_arc(db: &State<'_, Mutex<Database>>) -> Result<Arc<Mutex<Database>>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() })))
+}
+
+/// Record benchmark results (e.g. pushed from CI) and alert on regressions.
+#[tauri::command]
+pub async fn record_benchmark_results(
+    results: Vec<NewBenchmarkResult>,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<BenchmarkRegression>, String> {
+    if results.iter().any(|r| r.name.trim().is_empty() || !r.value.is_finite()) {
+        return Err("Benchmark results need a name and a finite value".to_string());
+    }
+    let db_arc = db_arc(&db)?;
+    let regressions = BenchmarkTracker::ingest(&db_arc, &results)
+        .map_err(|e| format!("Failed to record benchmark results: {}", e))?;
+    BenchmarkTracker::notify_regressions(&app, &regressions).await;
+    Ok(regressions)
+}
+
+/// Import `cargo bench` output or a JSON array of results.
+#[tauri::command]
+pub async fn import_benchmark_output(
+    suite: String,
+    content: String,
+    context: Option<String>,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<BenchmarkRegression>, String> {
+    let results = benchmark_tracker::parse_bench_output(&suite, &content, context.as_deref())?;
+    let db_arc = db_arc(&db)?;
+    let regressions = BenchmarkTracker::ingest(&db_arc, &results)
+        .map_err(|e| format!("Failed to import benchmark output: {}", e))?;
+    BenchmarkTracker::notify_regressions(&app, &regressions).await;
+    Ok(regressions)
+}
+
+#[tauri::command]
+pub fn list_benchmark_results(
+    suite: Option<String>,
+    name: Option<String>,
+    metric: Option<String>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = BenchmarkStore::new(db_guard.conn.clone());
+    store.list_results(suite.as_deref(), name.as_deref(), metric.as_deref(), limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to list benchmark results: {}", e))
+}
+
+#[tauri::command]
+pub fn get_benchmark_trends(
+    suite: Option<String>,
+    points: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<BenchmarkTrend>, String> {
+    let db_arc = db_arc(&db)?;
+    BenchmarkTracker::get_trends(&db_arc, suite.as_deref(), points.unwrap_or(30))
+        .map_err(|e| format!("Failed to compute benchmark trends: {}", e))
+}
+
+#[tauri::command]
+pub fn list_benchmark_regressions(
+    unacknowledged_only: Option<bool>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<BenchmarkRegression>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = BenchmarkStore::new(db_guard.conn.clone());
+    store.list_regressions(unacknowledged_only.unwrap_or(false), limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to list benchmark regressions: {}", e))
+}
+
+#[tauri::command]
+pub fn acknowledge_benchmark_regression(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = BenchmarkStore::new(db_guard.conn.clone());
+    store.acknowledge_regression(id)
+        .map_err(|e| format!("Failed to acknowledge regression: {}", e))
+}
+
+#[tauri::command]
+pub fn set_benchmark_settings(
+    threshold_percent: Option<f64>,
+    baseline_window: Option<i64>,
+    watch_dir: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    if let Some(threshold) = threshold_percent {
+        if !threshold.is_finite() || threshold <= 0.0 {
+            return Err("Threshold must be a positive percentage".to_string());
+        }
+        db_guard.set_config(CONFIG_THRESHOLD_PERCENT, &threshold.to_string())
+            .map_err(|e| format!("Failed to save threshold: {}", e))?;
+    }
+    if let Some(window) = baseline_window {
+        db_guard.set_config(CONFIG_BASELINE_WINDOW, &window.max(1).to_string())
+            .map_err(|e| format!("Failed to save baseline window: {}", e))?;
+    }
+    if let Some(dir) = watch_dir {
+        db_guard.set_config(CONFIG_WATCH_DIR, dir.trim())
+            .map_err(|e| format!("Failed to save watch directory: {}", e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::services::clipboard_monitor::{self, CONFIG_ENABLED};
+use crate::storage::clipboard::{ClipboardEntry, ClipboardStore};
+use crate::storage::Database;
+use std::sync::Mutex;
+use tauri::State;
+
+#[tauri::command]
+pub fn set_clipboard_history_enabled(
+    enabled: bool,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    db_guard.set_config(CONFIG_ENABLED, if enabled { "true" } else { "false" })
+        .map_err(|e| format!("Failed to update clipboard setting: {}", e))
+}
+
+#[tauri::command]
+pub fn get_clipboard_history(
+    limit: Option<i64>,
+    include_sensitive: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<ClipboardEntry>, String> {
+    let limit = limit.unwrap_or(100).max(1).min(1000);
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ClipboardStore::new(db_guard.conn.clone());
+    store.list_entries(limit, include_sensitive.unwrap_or(true))
+        .map_err(|e| format!("Failed to get clipboard history: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_clipboard_entry(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ClipboardStore::new(db_guard.conn.clone());
+    store.delete_entry(id)
+        .map_err(|e| format!("Failed to delete clipboard entry: {}", e))
+}
+
+/// Purge stored history and optionally wipe the live clipboard as well.
+#[tauri::command]
+pub async fn purge_clipboard_history(
+    before_ts: Option<i64>,
+    sensitive_only: Option<bool>,
+    clear_clipboard: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<usize, String> {
+    let deleted = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        let store = ClipboardStore::new(db_guard.conn.clone());
+        store.purge(before_ts, sensitive_only.unwrap_or(false))
+            .map_err(|e| format!("Failed to purge clipboard history: {}", e))?
+    };
+
+    if clear_clipboard.unwrap_or(false) {
+        clipboard_monitor::clear_clipboard().await?;
+    }
+
+    Ok(deleted)
+}
+
+/// Check arbitrary text against the sensitive-data patterns without storing it.
+#[tauri::command]
+pub fn detect_clipboard_secrets(text: String) -> Result<Vec<String>, String> {
+    Ok(clipboard_monitor::detect_sensitive(&text))
+}
@@ -16,3 +16,84 @@ pub fn set_config(key: String, value: String, db: State<'_, Mutex<Database>>) ->
         .map_err(|e| format!("Failed to set config: {}", e))
 }
 
+
+#[derive(serde::Serialize)]
+pub struct StartupProfileStatus {
+    /// Profile the app was launched with
+    pub active: crate::services::startup_profile::StartupProfile,
+    /// Profile the next launch will use
+    pub configured: crate::services::startup_profile::StartupProfile,
+    pub started: Vec<crate::services::startup_profile::Subsystem>,
+}
+
+#[tauri::command]
+pub fn get_startup_profile(
+    db: State<'_, Mutex<Database>>,
+    subsystems: State<'_, crate::services::startup_profile::Subsystems>,
+) -> Result<StartupProfileStatus, String> {
+    use crate::services::startup_profile::StartupProfile;
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(StartupProfileStatus {
+        active: subsystems.profile().clone(),
+        configured: StartupProfile::load(&db_guard),
+        started: subsystems.started(),
+    })
+}
+
+/// Choose "full" or "lite" plus per-subsystem overrides such as
+/// `{"ollama": true}`. Takes effect on the next launch.
+#[tauri::command]
+pub fn set_startup_profile(
+    profile: String,
+    subsystems: Option<serde_json::Value>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    use crate::services::startup_profile::{CONFIG_PROFILE, CONFIG_SUBSYSTEMS};
+
+    if profile != "full" && profile != "lite" {
+        return Err(format!("Unknown startup profile: {}", profile));
+    }
+    if subsystems.as_ref().is_some_and(|v| !v.is_object()) {
+        return Err("Subsystem overrides must be an object".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let updates: Vec<(&str, String)> = vec![
+        (CONFIG_PROFILE, profile),
+        (CONFIG_SUBSYSTEMS, subsystems.map(|v| v.to_string()).unwrap_or_default()),
+    ];
+    for (key, value) in updates {
+        db_guard
+            .set_config(key, &value)
+            .map_err(|e| format!("Failed to save startup profile: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Timezone and notification quiet hours used for schedules, rules, daily
+/// buckets and displayed times.
+#[tauri::command]
+pub fn get_time_preferences(
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::timezone::TimePreferences, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(crate::services::timezone::TimePreferences::load(&db_guard))
+}
+
+/// Workflow schedules pick up a new timezone on their next reload.
+#[tauri::command]
+pub fn set_time_preferences(
+    preferences: crate::services::timezone::TimePreferences,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    preferences
+        .save(&db_guard)
+        .map_err(|e| format!("Failed to save time preferences: {}", e))
+}
+
+/// IANA timezone names for the preference picker.
+#[tauri::command]
+pub fn list_timezones() -> Vec<&'static str> {
+    chrono_tz::TZ_VARIANTS.iter().map(|tz| tz.name()).collect()
+}
@@ -0,0 +1,58 @@
+use crate::services::crash_reporter::{CrashReport, CrashReporter};
+use crate::storage::Database;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Local crash reports, newest first.
+#[tauri::command]
+pub fn list_crash_reports(limit: Option<usize>) -> Result<Vec<CrashReport>, String> {
+    let mut reports = CrashReporter::list().map_err(|e| format!("Failed to list crash reports: {}", e))?;
+    reports.truncate(limit.unwrap_or(50));
+    Ok(reports)
+}
+
+#[tauri::command]
+pub fn get_crash_report(id: String) -> Result<Option<CrashReport>, String> {
+    CrashReporter::get(&id).map_err(|e| format!("Failed to read crash report: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_crash_report(id: String) -> Result<bool, String> {
+    CrashReporter::delete(&id).map_err(|e| format!("Failed to delete crash report: {}", e))
+}
+
+/// Send a report to the configured endpoint. Requires consent.
+#[tauri::command]
+pub async fn submit_crash_report(id: String, db: State<'_, Mutex<Database>>) -> Result<CrashReport, String> {
+    let database = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        Database { conn: db_guard.conn.clone() }
+    };
+    CrashReporter::submit(&database, &id)
+        .await
+        .map_err(|e| format!("Failed to submit crash report: {}", e))
+}
+
+#[tauri::command]
+pub fn set_crash_report_settings(
+    consent: Option<bool>,
+    endpoint: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    use crate::services::crash_reporter::{CONFIG_CONSENT, CONFIG_ENDPOINT};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mut updates: Vec<(&str, String)> = Vec::new();
+    if let Some(consent) = consent {
+        updates.push((CONFIG_CONSENT, consent.to_string()));
+    }
+    if let Some(endpoint) = endpoint {
+        updates.push((CONFIG_ENDPOINT, endpoint.trim().to_string()));
+    }
+    for (key, value) in updates {
+        db_guard
+            .set_config(key, &value)
+            .map_err(|e| format!("Failed to save crash report settings: {}", e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use crate::services::dashboard_layouts::{DashboardBundle, DashboardImportReport, DashboardLayoutService};
+use crate::storage::dashboards::{DashboardLayout, DashboardStore, DashboardWidget};
+use crate::storage::Database;
+use std::sync::Mutex;
+use tauri::State;
+
+#[tauri::command]
+pub fn list_dashboard_layouts(db: State<'_, Mutex<Database>>) -> Result<Vec<DashboardLayout>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    DashboardStore::new(db_guard.conn.clone())
+        .list_layouts()
+        .map_err(|e| format!("Failed to list dashboard layouts: {}", e))
+}
+
+#[tauri::command]
+pub fn get_dashboard_layout(id: i64, db: State<'_, Mutex<Database>>) -> Result<Option<DashboardLayout>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    DashboardStore::new(db_guard.conn.clone())
+        .get_layout(id)
+        .map_err(|e| format!("Failed to get dashboard layout: {}", e))
+}
+
+#[tauri::command]
+pub fn create_dashboard_layout(
+    name: String,
+    description: Option<String>,
+    widgets: Vec<DashboardWidget>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    if name.trim().is_empty() {
+        return Err("Layout name cannot be empty".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    DashboardStore::new(db_guard.conn.clone())
+        .create_layout(name.trim(), description.as_deref(), &widgets)
+        .map_err(|e| format!("Failed to create dashboard layout: {}", e))
+}
+
+#[tauri::command]
+pub fn update_dashboard_layout(
+    id: i64,
+    name: Option<String>,
+    description: Option<String>,
+    widgets: Option<Vec<DashboardWidget>>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let name = name.map(|n| n.trim().to_string());
+    if name.as_deref() == Some("") {
+        return Err("Layout name cannot be empty".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    DashboardStore::new(db_guard.conn.clone())
+        .update_layout(id, name.as_deref(), description.as_deref(), widgets.as_deref())
+        .map_err(|e| format!("Failed to update dashboard layout: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_dashboard_layout(id: i64, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    DashboardStore::new(db_guard.conn.clone())
+        .delete_layout(id)
+        .map_err(|e| format!("Failed to delete dashboard layout: {}", e))
+}
+
+/// Make a layout the one the dashboard opens with.
+#[tauri::command]
+pub fn set_default_dashboard_layout(id: i64, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    DashboardStore::new(db_guard.conn.clone())
+        .set_default_layout(id)
+        .map_err(|e| format!("Failed to set default dashboard layout: {}", e))
+}
+
+/// Export layouts as a shareable bundle. Without ids, all layouts are exported.
+/// When `path` is given the bundle is also written there.
+#[tauri::command]
+pub fn export_dashboard_layouts(
+    ids: Option<Vec<i64>>,
+    path: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<DashboardBundle, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = DashboardStore::new(db_guard.conn.clone());
+    let bundle = DashboardLayoutService::export(&store, ids.as_deref())
+        .map_err(|e| format!("Failed to export dashboard layouts: {}", e))?;
+
+    if let Some(path) = path {
+        let json = serde_json::to_string_pretty(&bundle)
+            .map_err(|e| format!("Failed to serialize bundle: {}", e))?;
+        std::fs::write(&path, json)
+            .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+
+    Ok(bundle)
+}
+
+/// Import a layout bundle from a file. Layouts named like an existing one
+/// replace it with `overwrite`, and are otherwise imported under a new name.
+#[tauri::command]
+pub fn import_dashboard_layouts(
+    path: String,
+    overwrite: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<DashboardImportReport, String> {
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let bundle = DashboardLayoutService::parse(&content)
+        .map_err(|e| format!("Failed to load dashboard layouts: {}", e))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = DashboardStore::new(db_guard.conn.clone());
+    DashboardLayoutService::import(&store, &bundle, overwrite.unwrap_or(false))
+        .map_err(|e| format!("Failed to import dashboard layouts: {}", e))
+}
@@ -70,3 +70,65 @@ pub fn export_data(
     }
 }
 
+
+/// Export recent events, their entities and article folders as interlinked
+/// Markdown notes into an Obsidian vault. The folder is remembered and kept
+/// in sync hourly; pass no path to re-export into the remembered one.
+#[tauri::command]
+pub fn export_research_vault(
+    vault_path: Option<String>,
+    days: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::vault_export::VaultExportReport, String> {
+    use crate::services::vault_export::{VaultExporter, CONFIG_VAULT_PATH, DEFAULT_DAYS};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let path = match vault_path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => path,
+        None => VaultExporter::vault_path(&db_guard).ok_or_else(|| "No vault folder chosen".to_string())?,
+    };
+    let report = VaultExporter::export(&db_guard, std::path::Path::new(&path), days.unwrap_or(DEFAULT_DAYS))
+        .map_err(|e| format!("Failed to export vault: {}", e))?;
+    db_guard
+        .set_config(CONFIG_VAULT_PATH, &path)
+        .map_err(|e| format!("Failed to save vault folder: {}", e))?;
+    Ok(report)
+}
+
+/// Stop keeping the vault in sync. Exported notes are left in place.
+#[tauri::command]
+pub fn disable_research_vault_sync(db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    db_guard
+        .set_config(crate::services::vault_export::CONFIG_VAULT_PATH, "")
+        .map_err(|e| format!("Failed to clear vault folder: {}", e))
+}
+
+/// Cite articles, and the evidence of events, as BibTeX (`bibtex`) or
+/// Zotero RDF (`zotero`). With `archive_dir` the stored article text is
+/// saved there and referenced from each citation.
+#[tauri::command]
+pub fn export_citations(
+    article_ids: Vec<i64>,
+    event_ids: Option<Vec<i64>>,
+    format: String,
+    archive_dir: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String, String> {
+    use crate::services::citation_export::{CitationExport, CitationFormat};
+
+    let format = CitationFormat::parse(&format)
+        .ok_or_else(|| "Invalid format. Use 'bibtex' or 'zotero'".to_string())?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let citations = CitationExport::collect(
+        &db_guard,
+        &article_ids,
+        &event_ids.unwrap_or_default(),
+        archive_dir.as_deref().filter(|d| !d.trim().is_empty()).map(std::path::Path::new),
+    )
+    .map_err(|e| format!("Failed to collect citations: {}", e))?;
+    if citations.is_empty() {
+        return Err("No articles found to cite".to_string());
+    }
+    Ok(CitationExport::render(&citations, format))
+}
@@ -0,0 +1,59 @@
+use crate::services::db_housekeeping::{DbHousekeeping, HousekeepingReport, HousekeepingSettings};
+use crate::storage::db_housekeeping::{DbHousekeepingStore, HousekeepingRecord};
+use crate::storage::Database;
+use std::sync::Mutex;
+use tauri::State;
+
+#[tauri::command]
+pub fn get_db_housekeeping_settings(
+    db: State<'_, Mutex<Database>>,
+) -> Result<HousekeepingSettings, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(HousekeepingSettings::load(&db_guard))
+}
+
+#[tauri::command]
+pub fn set_db_housekeeping_settings(
+    settings: HousekeepingSettings,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    settings
+        .save(&db_guard)
+        .map_err(|e| format!("Failed to save housekeeping settings: {}", e))
+}
+
+/// The most recent weekly report, if one has run.
+#[tauri::command]
+pub fn get_db_housekeeping_report(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<HousekeepingReport>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    DbHousekeeping::latest(&db_guard).map_err(|e| format!("Failed to read housekeeping report: {}", e))
+}
+
+/// Measure the database now instead of waiting for the weekly run.
+#[tauri::command]
+pub async fn run_db_housekeeping_now(
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<HousekeepingReport, String> {
+    let report = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        DbHousekeeping::run(&db_guard).map_err(|e| format!("Failed to build housekeeping report: {}", e))?
+    };
+    DbHousekeeping::notify(&app, &report).await;
+    Ok(report)
+}
+
+/// Past reports, newest first, for charting size over time.
+#[tauri::command]
+pub fn list_db_housekeeping_reports(
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<HousekeepingRecord>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    DbHousekeepingStore::new(db_guard.conn.clone())
+        .list_reports(limit.unwrap_or(12))
+        .map_err(|e| format!("Failed to list housekeeping reports: {}", e))
+}
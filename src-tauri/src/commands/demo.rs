@@ -0,0 +1,37 @@
+use crate::services::demo_data::{DemoCleanup, DemoData};
+use crate::services::job_manager::{JobClass, JobManager};
+use crate::storage::{Database, Job};
+use std::sync::Mutex;
+use tauri::State;
+
+/// Generate synthetic feeds, articles, events, prices and portfolios in a
+/// background job. The same `seed`, `scale` (1-20) and `anchor_ts` always
+/// produce the same data.
+#[tauri::command]
+pub fn seed_demo_data(
+    scale: Option<u32>,
+    seed: Option<u64>,
+    anchor_ts: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+    jobs: State<'_, JobManager>,
+) -> Result<Job, String> {
+    let conn = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        db_guard.conn.clone()
+    };
+    let scale = scale.unwrap_or(1);
+    let seed = seed.unwrap_or(42);
+    let params = serde_json::json!({ "scale": scale, "seed": seed, "anchor_ts": anchor_ts });
+    jobs.spawn("seed_demo_data", JobClass::Database, params, move |ctx| async move {
+        let summary = DemoData::seed(conn, scale, seed, anchor_ts, Some(&ctx))?;
+        Ok(serde_json::to_value(summary)?)
+    })
+    .map_err(|e| format!("Failed to start demo data job: {}", e))
+}
+
+/// Remove all generated demo data.
+#[tauri::command]
+pub fn clear_demo_data(db: State<'_, Mutex<Database>>) -> Result<DemoCleanup, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    DemoData::clear(&db_guard.conn).map_err(|e| format!("Failed to clear demo data: {}", e))
+}
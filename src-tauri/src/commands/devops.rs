@@ -1,4 +1,10 @@
-use crate::storage::devops::DevOpsStore;
+use crate::services::remote_hosts::RemoteHostService;
+use crate::services::ssh_tunnels::{TunnelInfo, TunnelManager, TunnelStatus};
+use crate::services::health_checker::{CheckResult, HealthChecker};
+use crate::services::watchdog::{LoopStatus, Watchdog};
+use crate::storage::devops::{DevOpsStore, HealthCheckRun, UptimeStats};
+use crate::storage::remote_hosts::{NewRemoteHost, RemoteHost, RemoteHostAction, RemoteHostStore};
+use crate::storage::ssh_tunnels::{NewSshTunnel, SshTunnelStore};
 use crate::storage::Database;
 use std::sync::{Arc, Mutex};
 use tauri::State;
@@ -157,7 +163,7 @@ pub fn fix_health_check_urls(
 pub async fn check_health_check(
     name: String,
     db: State<'_, Mutex<Database>>,
-) -> Result<(), String> {
+) -> Result<CheckResult, String> {
     let db_arc = Arc::new(Mutex::new(Database {
         conn: {
             let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
@@ -165,7 +171,206 @@ pub async fn check_health_check(
         },
     }));
     
-    crate::services::health_checker::HealthChecker::check_health_check(&name, &db_arc)
+    HealthChecker::check_health_check(&name, &db_arc)
         .await
         .map_err(|e| format!("Failed to check health check: {}", e))
 }
+
+/// Recorded outcomes of a check, newest first.
+#[tauri::command]
+pub fn get_health_check_history(
+    name: String,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<HealthCheckRun>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    DevOpsStore::new(db_guard.conn.clone())
+        .list_health_check_history(&name, limit.unwrap_or(100).clamp(1, 5000))
+        .map_err(|e| format!("Failed to get health check history: {}", e))
+}
+
+/// Uptime over the last `hours` (default 24) of one check, or all checks.
+#[tauri::command]
+pub fn get_health_check_uptime(
+    name: Option<String>,
+    hours: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<UptimeStats>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    HealthChecker::uptime(&db_guard, name.as_deref(), hours.unwrap_or(24))
+        .map_err(|e| format!("Failed to get health check uptime: {}", e))
+}
+
+/// Last heartbeat of every watched background loop.
+#[tauri::command]
+pub fn get_watchdog_status(db: State<'_, Mutex<Database>>) -> Result<Vec<LoopStatus>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let missed = Watchdog::missed_intervals(&db_guard);
+    Ok(Watchdog::status(chrono::Utc::now().timestamp(), missed))
+}
+
+fn db_arc(db: &State<'_, Mutex<Database>>) -> Result<Arc<Mutex<Database>>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() })))
+}
+
+#[tauri::command]
+pub fn list_remote_hosts(db: State<'_, Mutex<Database>>) -> Result<Vec<RemoteHost>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    RemoteHostStore::new(db_guard.conn.clone())
+        .list_hosts()
+        .map_err(|e| format!("Failed to list remote hosts: {}", e))
+}
+
+#[tauri::command]
+pub fn create_remote_host(host: NewRemoteHost, db: State<'_, Mutex<Database>>) -> Result<i64, String> {
+    RemoteHostService::validate(&host).map_err(|e| format!("Invalid remote host: {}", e))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    RemoteHostStore::new(db_guard.conn.clone())
+        .create_host(&host)
+        .map_err(|e| format!("Failed to create remote host: {}", e))
+}
+
+#[tauri::command]
+pub fn update_remote_host(id: i64, host: NewRemoteHost, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    RemoteHostService::validate(&host).map_err(|e| format!("Invalid remote host: {}", e))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    RemoteHostStore::new(db_guard.conn.clone())
+        .update_host(id, &host)
+        .map_err(|e| format!("Failed to update remote host: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_remote_host(id: i64, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    RemoteHostStore::new(db_guard.conn.clone())
+        .delete_host(id)
+        .map_err(|e| format!("Failed to delete remote host: {}", e))
+}
+
+/// Send a wake-on-LAN magic packet to a host.
+#[tauri::command]
+pub async fn wake_remote_host(
+    id: i64,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<RemoteHostAction, String> {
+    RemoteHostService::wake(&db_arc(&db)?, &app, id)
+        .await
+        .map_err(|e| format!("Failed to wake remote host: {}", e))
+}
+
+#[tauri::command]
+pub async fn ping_remote_host(
+    id: i64,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<RemoteHostAction, String> {
+    RemoteHostService::ping(&db_arc(&db)?, &app, id)
+        .await
+        .map_err(|e| format!("Failed to ping remote host: {}", e))
+}
+
+/// Run a command on a host over ssh. The host needs key-based auth.
+#[tauri::command]
+pub async fn run_remote_host_command(
+    id: i64,
+    command: String,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<RemoteHostAction, String> {
+    RemoteHostService::ssh(&db_arc(&db)?, &app, id, &command)
+        .await
+        .map_err(|e| format!("Failed to run remote command: {}", e))
+}
+
+/// Logged wake, ping and SSH actions, newest first.
+#[tauri::command]
+pub fn list_remote_host_actions(
+    host_id: Option<i64>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<RemoteHostAction>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    RemoteHostStore::new(db_guard.conn.clone())
+        .list_actions(host_id, limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to list remote host actions: {}", e))
+}
+
+/// Configured tunnels with the status of the running ones.
+#[tauri::command]
+pub fn list_ssh_tunnels(db: State<'_, Mutex<Database>>) -> Result<Vec<TunnelInfo>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let tunnels = SshTunnelStore::new(db_guard.conn.clone())
+        .list_tunnels()
+        .map_err(|e| format!("Failed to list SSH tunnels: {}", e))?;
+    Ok(tunnels
+        .into_iter()
+        .map(|tunnel| TunnelInfo { status: TunnelManager::status(tunnel.id), tunnel })
+        .collect())
+}
+
+#[tauri::command]
+pub fn create_ssh_tunnel(tunnel: NewSshTunnel, db: State<'_, Mutex<Database>>) -> Result<i64, String> {
+    TunnelManager::validate(&tunnel).map_err(|e| format!("Invalid SSH tunnel: {}", e))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    SshTunnelStore::new(db_guard.conn.clone())
+        .create_tunnel(&tunnel)
+        .map_err(|e| format!("Failed to create SSH tunnel: {}", e))
+}
+
+/// Update a tunnel, restarting it when it's running.
+#[tauri::command]
+pub fn update_ssh_tunnel(
+    id: i64,
+    tunnel: NewSshTunnel,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    TunnelManager::validate(&tunnel).map_err(|e| format!("Invalid SSH tunnel: {}", e))?;
+    let updated = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        let store = SshTunnelStore::new(db_guard.conn.clone());
+        store.update_tunnel(id, &tunnel)
+            .map_err(|e| format!("Failed to update SSH tunnel: {}", e))?;
+        store.get_tunnel(id)
+            .map_err(|e| format!("Failed to get SSH tunnel: {}", e))?
+    };
+    if let Some(updated) = updated {
+        if TunnelManager::stop(id) {
+            TunnelManager::start(updated, db_arc(&db)?, app);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_ssh_tunnel(id: i64, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    TunnelManager::stop(id);
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    SshTunnelStore::new(db_guard.conn.clone())
+        .delete_tunnel(id)
+        .map_err(|e| format!("Failed to delete SSH tunnel: {}", e))
+}
+
+#[tauri::command]
+pub fn start_ssh_tunnel(
+    id: i64,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<TunnelStatus>, String> {
+    let tunnel = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        SshTunnelStore::new(db_guard.conn.clone())
+            .get_tunnel(id)
+            .map_err(|e| format!("Failed to get SSH tunnel: {}", e))?
+            .ok_or_else(|| format!("SSH tunnel {} not found", id))?
+    };
+    TunnelManager::start(tunnel, db_arc(&db)?, app);
+    Ok(TunnelManager::status(id))
+}
+
+#[tauri::command]
+pub fn stop_ssh_tunnel(id: i64) -> Result<bool, String> {
+    Ok(TunnelManager::stop(id))
+}
@@ -0,0 +1,53 @@
+use crate::providers::disk_usage::{DiskScanOptions, DiskScanResult, DiskUsageProvider};
+use std::sync::Mutex;
+use tauri::State;
+
+#[tauri::command]
+pub async fn scan_disk_usage(
+    roots: Vec<String>,
+    exclusions: Option<Vec<String>>,
+    max_depth: Option<usize>,
+    top_n: Option<usize>,
+    full_rescan: Option<bool>,
+    app: tauri::AppHandle,
+    provider: State<'_, Mutex<DiskUsageProvider>>,
+) -> Result<DiskScanResult, String> {
+    // Clone out of the mutex so a long scan never blocks other callers
+    let provider = {
+        let provider_guard = provider.lock().map_err(|e| format!("Provider lock error: {}", e))?;
+        provider_guard.clone()
+    };
+
+    let defaults = DiskScanOptions::default();
+    let options = DiskScanOptions {
+        roots,
+        exclusions: exclusions.unwrap_or(defaults.exclusions),
+        max_depth: max_depth.unwrap_or(defaults.max_depth).min(16),
+        top_n: top_n.unwrap_or(defaults.top_n).max(1).min(1000),
+        full_rescan: full_rescan.unwrap_or(false),
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        provider.scan(&options, &|progress| {
+            let _ = crate::services::window_router::emit(&app, serde_json::json!({
+                "type": "disk-usage-progress",
+                "data": progress,
+                "timestamp": chrono::Utc::now().timestamp_millis()
+            }));
+        })
+    })
+    .await
+    .map_err(|e| format!("Disk scan task failed: {}", e))?;
+
+    result.map_err(|e| format!("Failed to scan disk usage: {}", e))
+}
+
+#[tauri::command]
+pub fn clear_disk_usage_cache(
+    provider: State<'_, Mutex<DiskUsageProvider>>,
+) -> Result<usize, String> {
+    let provider_guard = provider.lock().map_err(|e| format!("Provider lock error: {}", e))?;
+    let cleared = provider_guard.cached_dir_count();
+    provider_guard.clear_cache();
+    Ok(cleared)
+}
@@ -0,0 +1,110 @@
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::document_ingest::{DocumentIngest, IngestResult};
+use crate::services::watch_folders::PIPELINE_DOCUMENTS;
+use crate::storage::{Database, Document, DocumentEntity, DocumentStore, WatchFolderStore};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+#[derive(Debug, Serialize)]
+pub struct DocumentDetail {
+    pub document: Document,
+    pub text: String,
+    pub entities: Vec<DocumentEntity>,
+}
+
+/// Ingest a PDF, DOCX or text file: extract its text (OCR for scanned
+/// PDFs), entities and embeddings, and make it searchable.
+#[tauri::command]
+pub async fn ingest_document(
+    path: String,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<IngestResult, String> {
+    let embedder = crate::commands::memory::embedding_service(&api_key_manager, &db);
+    DocumentIngest::ingest(&db, &embedder, std::path::Path::new(&path))
+        .await
+        .map_err(|e| format!("Failed to ingest document: {}", e))
+}
+
+#[tauri::command]
+pub fn list_documents(
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<Document>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = DocumentStore::new(db_guard.conn.clone());
+    store.list_documents(limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to list documents: {}", e))
+}
+
+#[tauri::command]
+pub fn get_document(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<DocumentDetail>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = DocumentStore::new(db_guard.conn.clone());
+    let Some(document) = store.get_document(id).map_err(|e| format!("Failed to get document: {}", e))? else {
+        return Ok(None);
+    };
+    let text = store.get_document_text(id)
+        .map_err(|e| format!("Failed to get document text: {}", e))?
+        .unwrap_or_default();
+    let entities = store.get_entities(id)
+        .map_err(|e| format!("Failed to get document entities: {}", e))?;
+    Ok(Some(DocumentDetail { document, text, entities }))
+}
+
+#[tauri::command]
+pub fn delete_document(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = DocumentStore::new(db_guard.conn.clone());
+    store.delete_document(id)
+        .map_err(|e| format!("Failed to delete document: {}", e))
+}
+
+/// Folder whose new PDF/DOCX/text files are ingested automatically; None stops watching.
+/// Shorthand for a single enabled documents watch folder.
+#[tauri::command]
+pub fn set_document_watch_folder(
+    path: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    if let Some(path) = &path {
+        if !std::path::Path::new(path).is_dir() {
+            return Err(format!("Not a folder: {}", path));
+        }
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = WatchFolderStore::new(db_guard.conn.clone());
+    let folders = store.list_folders().map_err(|e| format!("Failed to list watch folders: {}", e))?;
+    for folder in folders.iter().filter(|f| f.pipeline == PIPELINE_DOCUMENTS && Some(&f.path) != path.as_ref()) {
+        store.set_enabled(folder.id, false)
+            .map_err(|e| format!("Failed to save watch folder: {}", e))?;
+    }
+    let Some(path) = path else { return Ok(()) };
+    let saved = match folders.iter().find(|f| f.pipeline == PIPELINE_DOCUMENTS && f.path == path) {
+        Some(folder) => store.set_enabled(folder.id, true),
+        None => store.create_folder(&path, PIPELINE_DOCUMENTS, &serde_json::json!({})).map(|_| ()),
+    };
+    saved.map_err(|e| format!("Failed to save watch folder: {}", e))
+}
+
+#[tauri::command]
+pub fn get_document_watch_folder(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<String>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let folders = WatchFolderStore::new(db_guard.conn.clone())
+        .list_folders()
+        .map_err(|e| format!("Failed to list watch folders: {}", e))?;
+    Ok(folders
+        .into_iter()
+        .find(|f| f.pipeline == PIPELINE_DOCUMENTS && f.enabled)
+        .map(|f| f.path)
+        .or_else(|| DocumentIngest::watch_folder(&db_guard).map(|p| p.display().to_string())))
+}
@@ -4,7 +4,7 @@ use crate::providers::economic_calendar::EconomicCalendarProvider;
 use crate::services::api_key_manager::APIKeyManager;
 use crate::storage::Database;
 use chrono::{DateTime, Utc};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use tauri::State;
 
 #[tauri::command]
@@ -163,3 +163,50 @@ pub async fn sync_economic_events(
     
     Ok(synced_count)
 }
+
+/// Upcoming economic events, earnings for watchlist tickers and snoozed-alert
+/// wake times as an ICS calendar. `kinds` picks among "economic", "earnings"
+/// and "snoozed_alerts" (all by default); the range defaults to the next 30 days.
+#[tauri::command]
+pub async fn export_calendar_ics(
+    range: Option<crate::services::calendar_ics::CalendarRange>,
+    kinds: Option<Vec<String>>,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<String, String> {
+    use crate::services::calendar_ics::CalendarIcs;
+
+    let kinds = CalendarIcs::parse_kinds(kinds.as_deref()).map_err(|e| e.to_string())?;
+    CalendarIcs::build(&db, Some(api_key_manager.inner().as_ref()), &range.unwrap_or_default(), &kinds)
+        .await
+        .map_err(|e| format!("Failed to export calendar: {}", e))
+}
+
+/// Turn the calendar feed served by the local script bridge on or off.
+/// Returns the feed URL, with a fresh token each time it is enabled.
+#[tauri::command]
+pub fn set_calendar_feed_enabled(
+    enabled: bool,
+    db: State<'_, Mutex<Database>>,
+    script_bridge: State<'_, crate::services::ScriptBridgeServer>,
+) -> Result<Option<String>, String> {
+    use crate::services::calendar_ics::{CalendarIcs, CONFIG_FEED_TOKEN};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let token = if enabled { uuid::Uuid::new_v4().simple().to_string() } else { String::new() };
+    db_guard
+        .set_config(CONFIG_FEED_TOKEN, &token)
+        .map_err(|e| format!("Failed to update calendar feed: {}", e))?;
+    Ok(enabled.then(|| CalendarIcs::feed_url(script_bridge.get_port(), &token)))
+}
+
+#[tauri::command]
+pub fn get_calendar_feed_url(
+    db: State<'_, Mutex<Database>>,
+    script_bridge: State<'_, crate::services::ScriptBridgeServer>,
+) -> Result<Option<String>, String> {
+    use crate::services::calendar_ics::CalendarIcs;
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(CalendarIcs::feed_token(&db_guard).map(|token| CalendarIcs::feed_url(script_bridge.get_port(), &token)))
+}
@@ -8,6 +8,7 @@ pub async fn generate_embedding(
     text: String,
     _dimension: Option<usize>,
     api_key_manager: State<'_, Arc<APIKeyManager>>,
+    db: State<'_, std::sync::Mutex<crate::storage::Database>>,
 ) -> Result<Vec<f32>, String> {
     let mut service = EmbeddingService::new();
     
@@ -15,6 +16,10 @@ pub async fn generate_embedding(
     if let Ok(Some(openai_key)) = api_key_manager.get_key_optional("openai") {
         service.set_openai_key(openai_key);
     }
+    if let Ok(db_guard) = db.lock() {
+        service.set_meter(crate::services::llm_metering::LlmMeter::new(db_guard.conn.clone()));
+        service.set_privacy_filter(crate::services::redaction::PrivacyFilter::new(db_guard.conn.clone()));
+    }
     
     let embedding = service.generate(&text).await
         .map_err(|e| format!("Failed to generate embedding: {}", e))?;
@@ -0,0 +1,55 @@
+use crate::providers::fx::FxRate;
+use crate::services::fx::{Conversion, FxService};
+use crate::storage::Database;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+fn db_arc(db: &State<'_, Mutex<Database>>) -> Result<Arc<Mutex<Database>>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() })))
+}
+
+/// Rate for 1 `base` in `quote` on `date` (YYYY-MM-DD), or the latest when omitted.
+#[tauri::command]
+pub async fn get_fx_rate(
+    base: String,
+    quote: String,
+    date: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<FxRate, String> {
+    let db_arc = db_arc(&db)?;
+    FxService::get_rate(&db_arc, &base, &quote, date.as_deref())
+        .await
+        .map_err(|e| format!("Failed to get FX rate: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_fx_history(
+    base: String,
+    quote: String,
+    from: String,
+    to: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<FxRate>, String> {
+    if from > to {
+        return Err("Start date must not be after end date".to_string());
+    }
+    let db_arc = db_arc(&db)?;
+    FxService::get_history(&db_arc, &base, &quote, &from, &to)
+        .await
+        .map_err(|e| format!("Failed to get FX history: {}", e))
+}
+
+#[tauri::command]
+pub async fn convert_currency(
+    amount: f64,
+    from: String,
+    to: String,
+    date: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Conversion, String> {
+    let db_arc = db_arc(&db)?;
+    FxService::convert(&db_arc, amount, &from, &to, date.as_deref())
+        .await
+        .map_err(|e| format!("Failed to convert currency: {}", e))
+}
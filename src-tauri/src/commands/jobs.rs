@@ -0,0 +1,88 @@
+use crate::services::job_manager::{JobClass, JobManager};
+use crate::storage::{Database, Job};
+use serde_json::Value;
+use std::sync::Mutex;
+use tauri::{Manager, State};
+
+/// Jobs newest first; `status` filters to queued, running, succeeded,
+/// failed, cancelled or interrupted.
+#[tauri::command]
+pub fn list_jobs(
+    status: Option<String>,
+    limit: Option<i64>,
+    jobs: State<'_, JobManager>,
+) -> Result<Vec<Job>, String> {
+    jobs.list(status.as_deref(), limit.unwrap_or(100).clamp(1, 1000))
+        .map_err(|e| format!("Failed to list jobs: {}", e))
+}
+
+#[tauri::command]
+pub fn get_job(id: String, jobs: State<'_, JobManager>) -> Result<Option<Job>, String> {
+    jobs.get(&id).map_err(|e| format!("Failed to get job: {}", e))
+}
+
+/// Returns false when the job already finished.
+#[tauri::command]
+pub fn cancel_job(id: String, jobs: State<'_, JobManager>) -> Result<bool, String> {
+    Ok(jobs.cancel(&id))
+}
+
+/// Start one of the built-in background jobs: fetch_feeds,
+/// rebuild_events {days_back}, rebuild_search_index {from_ts} or
+/// export_vault {vault_path, days}. Progress arrives as `job-progress`
+/// WebSocket messages.
+#[tauri::command]
+pub fn start_job(
+    kind: String,
+    params: Option<Value>,
+    app: tauri::AppHandle,
+    jobs: State<'_, JobManager>,
+) -> Result<Job, String> {
+    let params = params.unwrap_or_else(|| serde_json::json!({}));
+    let job = match kind.as_str() {
+        "fetch_feeds" => jobs.spawn("fetch_feeds", JobClass::Network, params, move |ctx| async move {
+            let db = app.state::<Mutex<Database>>();
+            let items = crate::commands::osint::fetch_feeds(&app, db.inner(), Some(&ctx))
+                .await
+                .map_err(anyhow::Error::msg)?;
+            Ok(serde_json::json!({ "items": items }))
+        }),
+        "rebuild_events" => {
+            let days_back = params.get("days_back").and_then(|v| v.as_i64()).unwrap_or(14).clamp(1, 365);
+            jobs.spawn("rebuild_events", JobClass::Database, params, move |ctx| async move {
+                let db = app.state::<Mutex<Database>>();
+                let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+                let count = crate::commands::temporal::rebuild_events(&app, &db_guard, days_back, Some(&ctx))
+                    .map_err(anyhow::Error::msg)?;
+                Ok(serde_json::json!({ "touched_events": count }))
+            })
+        }
+        "rebuild_search_index" => {
+            let from_ts = params.get("from_ts").and_then(|v| v.as_i64());
+            jobs.spawn("rebuild_search_index", JobClass::Database, params, move |_ctx| async move {
+                let db = app.state::<Mutex<Database>>();
+                let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+                let count = crate::storage::TemporalStore::new(db_guard.conn.clone()).rebuild_search_index(from_ts)?;
+                Ok(serde_json::json!({ "indexed_docs": count }))
+            })
+        }
+        "export_vault" => {
+            use crate::services::vault_export::{VaultExporter, DEFAULT_DAYS};
+
+            let days = params.get("days").and_then(|v| v.as_i64()).unwrap_or(DEFAULT_DAYS);
+            let path = params.get("vault_path").and_then(|v| v.as_str()).map(|p| p.to_string());
+            jobs.spawn("export_vault", JobClass::Compute, params, move |ctx| async move {
+                let db = app.state::<Mutex<Database>>();
+                let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+                let path = path
+                    .or_else(|| VaultExporter::vault_path(&db_guard))
+                    .ok_or_else(|| anyhow::anyhow!("No vault folder chosen"))?;
+                ctx.progress(0.0, format!("Exporting to {}", path));
+                let report = VaultExporter::export(&db_guard, std::path::Path::new(&path), days)?;
+                Ok(serde_json::to_value(report)?)
+            })
+        }
+        other => return Err(format!("Unknown job kind: {}", other)),
+    };
+    job.map_err(|e| format!("Failed to start job: {}", e))
+}
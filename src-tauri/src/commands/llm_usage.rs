@@ -0,0 +1,203 @@
+use crate::services::llm_metering::{BudgetStatus, LlmMeter};
+use crate::services::llm_router::RoutingPolicy;
+use crate::services::redaction::{RedactionSettings, Redactor};
+use crate::storage::llm_usage::{CloudEgressRecord, LlmRouteRecord, LlmRouteSummary, LlmUsageRow, LlmUsageStore};
+use crate::storage::Database;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmUsageReport {
+    pub rows: Vec<LlmUsageRow>,
+    pub total_requests: i64,
+    pub total_tokens_in: i64,
+    pub total_tokens_out: i64,
+    pub total_cost_usd: f64,
+    pub budgets: Vec<BudgetStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmRoutingReport {
+    pub since: i64,
+    pub rows: Vec<LlmRouteSummary>,
+    pub local_calls: i64,
+    pub cloud_calls: i64,
+    pub fallbacks: i64,
+    pub failures: i64,
+    pub total_cost_usd: f64,
+}
+
+/// Daily usage per provider/model. Defaults to the current month.
+#[tauri::command]
+pub fn get_llm_usage(
+    from_day: Option<String>,
+    to_day: Option<String>,
+    provider: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<LlmUsageReport, String> {
+    let now = chrono::Utc::now();
+    let from_day = from_day.unwrap_or_else(|| now.format("%Y-%m-01").to_string());
+    let to_day = to_day.unwrap_or_else(|| now.format("%Y-%m-%d").to_string());
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = LlmUsageStore::new(db_guard.conn.clone());
+    let meter = LlmMeter::new(db_guard.conn.clone());
+
+    let rows = store.get_usage(&from_day, &to_day, provider.as_deref())
+        .map_err(|e| format!("Failed to get LLM usage: {}", e))?;
+    let mut budgets = Vec::new();
+    for budget in store.list_budgets().map_err(|e| format!("Failed to list budgets: {}", e))? {
+        if let Some(status) = meter.budget_status(&budget.provider)
+            .map_err(|e| format!("Failed to get budget status: {}", e))?
+        {
+            budgets.push(status);
+        }
+    }
+
+    Ok(LlmUsageReport {
+        total_requests: rows.iter().map(|r| r.requests).sum(),
+        total_tokens_in: rows.iter().map(|r| r.tokens_in).sum(),
+        total_tokens_out: rows.iter().map(|r| r.tokens_out).sum(),
+        total_cost_usd: rows.iter().map(|r| r.cost_usd).sum(),
+        rows,
+        budgets,
+    })
+}
+
+/// Set a monthly cap for a provider. `action` is "warn" (log once per month) or
+/// "block" (refuse further calls until next month).
+#[tauri::command]
+pub fn set_llm_budget(
+    provider: String,
+    monthly_limit_usd: Option<f64>,
+    monthly_token_limit: Option<i64>,
+    action: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let action = action.unwrap_or_else(|| "warn".to_string());
+    if action != "warn" && action != "block" {
+        return Err(format!("Invalid budget action: {}", action));
+    }
+    if monthly_limit_usd.is_none() && monthly_token_limit.is_none() {
+        return Err("Set a cost limit, a token limit, or both".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = LlmUsageStore::new(db_guard.conn.clone());
+    store.set_budget(&provider, monthly_limit_usd, monthly_token_limit, &action)
+        .map_err(|e| format!("Failed to set budget: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_llm_budget(
+    provider: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = LlmUsageStore::new(db_guard.conn.clone());
+    store.delete_budget(&provider)
+        .map_err(|e| format!("Failed to delete budget: {}", e))
+}
+
+#[tauri::command]
+pub fn get_redaction_settings(
+    db: State<'_, Mutex<Database>>,
+) -> Result<RedactionSettings, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    RedactionSettings::load(&db_guard)
+        .map_err(|e| format!("Failed to load redaction settings: {}", e))
+}
+
+#[tauri::command]
+pub fn set_redaction_settings(
+    settings: RedactionSettings,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    settings.validate().map_err(|e| e.to_string())?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    settings.save(&db_guard)
+        .map_err(|e| format!("Failed to save redaction settings: {}", e))
+}
+
+/// What `text` would look like when sent to a cloud provider.
+#[tauri::command]
+pub fn preview_redaction(
+    text: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let settings = RedactionSettings::load(&db_guard)
+        .map_err(|e| format!("Failed to load redaction settings: {}", e))?;
+    Ok(Redactor::new(settings).redact(&text))
+}
+
+/// Audit of requests sent to cloud providers, newest first.
+#[tauri::command]
+pub fn list_cloud_egress(
+    provider: Option<String>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<CloudEgressRecord>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    LlmUsageStore::new(db_guard.conn.clone())
+        .list_egress(provider.as_deref(), limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to list cloud egress: {}", e))
+}
+
+#[tauri::command]
+pub fn get_llm_routing_policy(
+    db: State<'_, Mutex<Database>>,
+) -> Result<RoutingPolicy, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    RoutingPolicy::load(&db_guard)
+        .map_err(|e| format!("Failed to load routing policy: {}", e))
+}
+
+#[tauri::command]
+pub fn set_llm_routing_policy(
+    policy: RoutingPolicy,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    policy.validate().map_err(|e| e.to_string())?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    policy.save(&db_guard)
+        .map_err(|e| format!("Failed to save routing policy: {}", e))
+}
+
+/// Where routed calls went over the last `days` (default 30) and what they
+/// cost.
+#[tauri::command]
+pub fn get_llm_routing_report(
+    days: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<LlmRoutingReport, String> {
+    let since = chrono::Utc::now().timestamp() - days.unwrap_or(30).max(1) * 86_400;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let rows = LlmUsageStore::new(db_guard.conn.clone())
+        .route_summary(since)
+        .map_err(|e| format!("Failed to get routing report: {}", e))?;
+    let calls_to = |target: &str| -> i64 { rows.iter().filter(|r| r.target == target).map(|r| r.calls - r.failures).sum() };
+
+    Ok(LlmRoutingReport {
+        since,
+        local_calls: calls_to("local"),
+        cloud_calls: calls_to("cloud"),
+        fallbacks: rows.iter().map(|r| r.fallbacks).sum(),
+        failures: rows.iter().map(|r| r.failures).sum(),
+        total_cost_usd: rows.iter().map(|r| r.cost_usd).sum(),
+        rows,
+    })
+}
+
+/// Routing decisions, newest first.
+#[tauri::command]
+pub fn list_llm_routes(
+    task: Option<String>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<LlmRouteRecord>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    LlmUsageStore::new(db_guard.conn.clone())
+        .list_routes(task.as_deref(), limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to list LLM routes: {}", e))
+}
@@ -0,0 +1,87 @@
+use crate::services::market_calendar::{Exchange, Holiday, MarketCalendar, MarketStatus, HOLIDAY_YEARS};
+use crate::storage::market_data::MarketDataStore;
+use crate::storage::Database;
+use chrono::Datelike;
+use std::sync::Mutex;
+use tauri::State;
+
+fn resolve_exchange(exchange: Option<&str>, ticker: Option<&str>) -> Result<&'static Exchange, String> {
+    match (exchange, ticker) {
+        (Some(code), _) => MarketCalendar::exchange(code).ok_or_else(|| format!("Unknown exchange: {}", code)),
+        (None, Some(ticker)) => Ok(MarketCalendar::exchange_for_ticker(ticker)),
+        (None, None) => Ok(&MarketCalendar::exchanges()[0]),
+    }
+}
+
+#[tauri::command]
+pub fn list_market_exchanges() -> Result<Vec<Exchange>, String> {
+    Ok(MarketCalendar::exchanges().to_vec())
+}
+
+/// Current session of an exchange, or of the exchange a ticker trades on.
+#[tauri::command]
+pub fn get_market_status(
+    exchange: Option<String>,
+    ticker: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<MarketStatus, String> {
+    let exchange = resolve_exchange(exchange.as_deref(), ticker.as_deref())?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let calendar = MarketCalendar::load(db_guard.conn.clone());
+    Ok(calendar.status(exchange, chrono::Utc::now().timestamp()))
+}
+
+/// Built-in and custom holidays of an exchange for a year (default: this year).
+#[tauri::command]
+pub fn list_market_holidays(
+    exchange: String,
+    year: Option<i32>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<Holiday>, String> {
+    let exchange = resolve_exchange(Some(&exchange), None)?;
+    let year = year.unwrap_or_else(|| chrono::Utc::now().year());
+    if !HOLIDAY_YEARS.contains(&year) {
+        return Err(format!(
+            "Year must be between {} and {}",
+            HOLIDAY_YEARS.start(),
+            HOLIDAY_YEARS.end()
+        ));
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let calendar = MarketCalendar::load(db_guard.conn.clone());
+    Ok(calendar.holidays(exchange, year))
+}
+
+/// Add a holiday, or an early close when `early_close` ("HH:MM" local) is set.
+#[tauri::command]
+pub fn add_market_holiday(
+    exchange: String,
+    date: String,
+    name: String,
+    early_close: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let exchange = resolve_exchange(Some(&exchange), None)?;
+    chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date (expected YYYY-MM-DD): {}", date))?;
+    if let Some(time) = early_close.as_deref() {
+        chrono::NaiveTime::parse_from_str(time, "%H:%M")
+            .map_err(|_| format!("Invalid early close (expected HH:MM): {}", time))?;
+    }
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    MarketDataStore::new(db_guard.conn.clone())
+        .upsert_market_holiday(exchange.code, &date, name.trim(), early_close.as_deref())
+        .map_err(|e| format!("Failed to add market holiday: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_market_holiday(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<bool, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    MarketDataStore::new(db_guard.conn.clone())
+        .delete_market_holiday(id)
+        .map_err(|e| format!("Failed to delete market holiday: {}", e))
+}
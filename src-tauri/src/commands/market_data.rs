@@ -1,10 +1,16 @@
 use crate::providers::market_data::MarketDataManager;
-use crate::storage::market_data::{MarketDataStore, MarketPrice, PriceHistory};
+use crate::storage::market_data::{ChartAnnotation, Fundamentals, MarketDataStore, MarketPrice, PriceHistory};
 use crate::storage::Database;
+use crate::storage::response_cache::ResponseCacheStore;
 use crate::services::market_data_stream::MarketDataStreamer;
-use crate::services::market_cache::MarketDataCache;
+use crate::services::market_cache::{CacheEntryInfo, CacheInvalidation, CacheTtls, MarketCacheStats, MarketDataCache};
 use crate::services::rate_limiter::RateLimiter;
+use crate::services::response_cache::{Cached, ResponseCache};
 use crate::services::api_key_manager::APIKeyManager;
+use crate::services::fundamentals::FundamentalsService;
+use crate::services::market_calendar::MarketCalendar;
+use crate::services::market_signals::{MarketSignalsService, TickerSignals};
+use crate::services::symbol_search::{SymbolSearchResult, SymbolSearchService};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use tauri::State;
@@ -21,65 +27,83 @@ pub struct ChartDataPoint {
 
 #[tauri::command]
 pub async fn get_market_price(
+    app: tauri::AppHandle,
     ticker: String,
     db: State<'_, Mutex<Database>>,
     cache: State<'_, Mutex<MarketDataCache>>,
     api_key_manager: State<'_, Arc<APIKeyManager>>,
+    rate_limiter: State<'_, Mutex<RateLimiter>>,
 ) -> Result<Option<MarketPrice>, String> {
+    let conn = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        db_guard.conn.clone()
+    };
+    let calendar = MarketCalendar::load(conn.clone());
+
     // Try in-memory cache first
     if let Ok(cache_guard) = cache.lock() {
-        if let Some(price) = cache_guard.get_price(&ticker) {
+        if let Some(mut price) = cache_guard.get_price(&ticker) {
+            calendar.annotate(&mut price);
             return Ok(Some(price));
         }
     }
     
-    let conn = {
-        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
-        db_guard.conn.clone()
-    };
-    let store = MarketDataStore::new(conn);
+    let store = MarketDataStore::new(conn.clone());
     
     // Try database cache
-    if let Ok(Some(price)) = store.get_price(&ticker) {
+    if let Ok(Some(mut price)) = store.get_price(&ticker) {
         let now = chrono::Utc::now().timestamp();
-        if now - price.timestamp < 60 {
+        let quote_ttl = cache.lock().map(|c| c.ttls().quote_secs as i64).unwrap_or(60);
+        if now - price.timestamp < quote_ttl {
             // Update in-memory cache
             if let Ok(cache_guard) = cache.lock() {
-                cache_guard.set_price(ticker.clone(), price.clone());
+                cache_guard.set_price(ticker.clone(), price.clone(), None);
             }
+            calendar.annotate(&mut price);
             return Ok(Some(price));
         }
     }
 
-    // Fetch from provider
+    // Fetch from provider, or serve its last response while that refreshes
     let manager = MarketDataManager::new(Some(api_key_manager.inner().as_ref()));
-    // Rate limiter is optional - pass None to avoid holding lock across await
-    match manager.get_price(&ticker, None).await {
-        Ok(price_data) => {
-            let price = MarketPrice {
+    // Clone out of the mutex so the lock isn't held across the fetch; clones share state
+    let limiter = rate_limiter.lock()
+        .map_err(|e| format!("Rate limiter lock error: {}", e))?
+        .clone();
+    let quote_ttl = cache.lock().map(|c| c.ttls().quote_secs as i64).unwrap_or(60);
+    let fetch_ticker = ticker.clone();
+    let fetched = ResponseCache::new(conn.clone(), Some(app))
+        .get_or_fetch(&format!("quote:{}", ticker.to_uppercase()), "quote", quote_ttl, move || async move {
+            let price = manager.get_price(&fetch_ticker, Some(&limiter)).await?;
+            Ok::<_, anyhow::Error>((price, manager.last_provider()))
+        })
+        .await;
+    match fetched {
+        Ok(Cached { value: price_data, provider, stale, .. }) => {
+            let mut price = MarketPrice {
                 ticker: price_data.ticker.clone(),
                 price: price_data.price,
                 change: price_data.change,
                 change_percent: price_data.change_percent,
                 volume: price_data.volume,
                 timestamp: price_data.timestamp,
+                session: None,
             };
             
-            // Cache in memory
-            if let Ok(cache_guard) = cache.lock() {
-                cache_guard.set_price(ticker.clone(), price.clone());
-            }
-            
-            // Cache in database
-            let conn = {
-                let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
-                db_guard.conn.clone()
-            };
-            let store = MarketDataStore::new(conn);
-            if let Err(e) = store.upsert_price(&price) {
-                eprintln!("Failed to cache price in database: {}", e);
+            // A stale response stays out of the caches so the next request sees the refresh
+            if !stale {
+                // Cache in memory
+                if let Ok(cache_guard) = cache.lock() {
+                    cache_guard.set_price(ticker.clone(), price.clone(), provider.as_deref());
+                }
+
+                // Cache in database
+                if let Err(e) = store.upsert_price(&price) {
+                    eprintln!("Failed to cache price in database: {}", e);
+                }
             }
             
+            calendar.annotate(&mut price);
             Ok(Some(price))
         }
         Err(e) => Err(format!("Failed to fetch price: {}", e)),
@@ -93,9 +117,12 @@ pub async fn get_market_prices(
     streamer: State<'_, Mutex<MarketDataStreamer>>,
     cache: State<'_, Mutex<MarketDataCache>>,
     api_key_manager: State<'_, Arc<APIKeyManager>>,
+    rate_limiter: State<'_, Mutex<RateLimiter>>,
 ) -> Result<Vec<MarketPrice>, String> {
     let mut result_map: std::collections::HashMap<String, MarketPrice> = std::collections::HashMap::new();
     let mut to_fetch: Vec<String> = Vec::new();
+    // Expired quotes, shown for tickers that can't be refreshed
+    let mut stale: std::collections::HashMap<String, MarketPrice> = std::collections::HashMap::new();
     
     // Check in-memory cache first
     if let Ok(cache_guard) = cache.lock() {
@@ -120,18 +147,20 @@ pub async fn get_market_prices(
         
         let cached = store.get_prices(&to_fetch).unwrap_or_default();
         let now = chrono::Utc::now().timestamp();
+        let quote_ttl = cache.lock().map(|c| c.ttls().quote_secs as i64).unwrap_or(60);
         
         let mut still_to_fetch = Vec::new();
         for price in cached {
-            if now - price.timestamp < 60 {
+            if now - price.timestamp < quote_ttl {
                 // Fresh cache
                 result_map.insert(price.ticker.clone(), price.clone());
                 // Update in-memory cache
                 if let Ok(cache_guard) = cache.lock() {
-                    cache_guard.set_price(price.ticker.clone(), price);
+                    cache_guard.set_price(price.ticker.clone(), price, None);
                 }
             } else {
                 still_to_fetch.push(price.ticker.clone());
+                stale.insert(price.ticker.clone(), price);
             }
         }
         
@@ -147,41 +176,53 @@ pub async fn get_market_prices(
     // Fetch missing/expired prices
     if !to_fetch.is_empty() {
         let manager = MarketDataManager::new(Some(api_key_manager.inner().as_ref()));
-        // Rate limiter is optional - pass None to avoid holding lock across await
-        if let Ok(prices) = manager.get_prices(&to_fetch, None).await {
-            for price_data in prices {
-                let price = MarketPrice {
-                    ticker: price_data.ticker.clone(),
-                    price: price_data.price,
-                    change: price_data.change,
-                    change_percent: price_data.change_percent,
-                    volume: price_data.volume,
-                    timestamp: price_data.timestamp,
-                };
-                
-                // Cache in memory
-                if let Ok(cache_guard) = cache.lock() {
-                    cache_guard.set_price(price.ticker.clone(), price.clone());
-                }
-                
-                // Cache in database
-                let conn = {
-                    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
-                    db_guard.conn.clone()
-                };
-                let store = MarketDataStore::new(conn);
-                if let Err(e) = store.upsert_price(&price) {
-                    eprintln!("Failed to cache price: {}", e);
-                }
-                
-                // Push to streamer and subscribe ticker
-                if let Ok(streamer_guard) = streamer.lock() {
-                    streamer_guard.update_price(price.clone());
-                    streamer_guard.subscribe(vec![price.ticker.clone()]);
+        // Clone out of the mutex so the lock isn't held across the fetch; clones share state
+        let limiter = rate_limiter.lock()
+            .map_err(|e| format!("Rate limiter lock error: {}", e))?
+            .clone();
+        for fetched in manager.get_prices_each(&to_fetch, Some(&limiter)).await {
+            let (price_data, provider) = match fetched {
+                Ok(sourced) => (sourced.price, sourced.provider),
+                Err(e) => {
+                    eprintln!("No price for {}", e);
+                    if let Some(price) = stale.remove(&e.ticker) {
+                        result_map.insert(e.ticker, price);
+                    }
+                    continue;
                 }
-                
-                result_map.insert(price.ticker.clone(), price);
+            };
+            let price = MarketPrice {
+                ticker: price_data.ticker.clone(),
+                price: price_data.price,
+                change: price_data.change,
+                change_percent: price_data.change_percent,
+                volume: price_data.volume,
+                timestamp: price_data.timestamp,
+                session: None,
+            };
+            
+            // Cache in memory
+            if let Ok(cache_guard) = cache.lock() {
+                cache_guard.set_price(price.ticker.clone(), price.clone(), Some(&provider));
+            }
+            
+            // Cache in database
+            let conn = {
+                let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+                db_guard.conn.clone()
+            };
+            let store = MarketDataStore::new(conn);
+            if let Err(e) = store.upsert_price(&price) {
+                eprintln!("Failed to cache price: {}", e);
             }
+            
+            // Push to streamer and subscribe ticker
+            if let Ok(streamer_guard) = streamer.lock() {
+                streamer_guard.update_price(price.clone());
+                streamer_guard.subscribe(vec![price.ticker.clone()]);
+            }
+            
+            result_map.insert(price.ticker.clone(), price);
         }
     }
 
@@ -191,51 +232,233 @@ pub async fn get_market_prices(
     }
 
     // Return prices in order requested
+    let calendar = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        MarketCalendar::load(db_guard.conn.clone())
+    };
     let result: Vec<MarketPrice> = tickers
         .iter()
         .filter_map(|t| result_map.get(t).cloned())
+        .map(|mut price| {
+            calendar.annotate(&mut price);
+            price
+        })
         .collect();
 
     Ok(result)
 }
 
+/// Points returned by `get_chart_data` when the caller doesn't set `max_points`.
+const DEFAULT_CHART_MAX_POINTS: usize = 2000;
+const MAX_CHART_MAX_POINTS: usize = 20000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartEventMarker {
+    pub event_id: i64,
+    pub time: i64, // timestamp of the bar the event falls in
+    pub start_ts: i64,
+    pub title: String,
+    pub event_type: String,
+    pub severity: f64,
+    pub sentiment_score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartAnnotationMarker {
+    pub annotation_id: i64,
+    pub time: i64, // timestamp of the bar the annotation falls in
+    pub timestamp: i64,
+    pub kind: String,
+    pub text: String,
+    pub event_id: Option<i64>,
+    pub alert_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartData {
+    pub ticker: String,
+    pub interval: String,
+    pub points: Vec<ChartDataPoint>,
+    pub markers: Vec<ChartEventMarker>,
+    pub annotations: Vec<ChartAnnotationMarker>,
+    pub source_points: usize,
+    pub downsampled: bool,
+}
+
+/// OHLCV for a ticker, downsampled to at most `max_points` (2000 by default)
+/// and annotated with linked temporal events and pinned chart annotations. `mode` is "ohlc" (bucketed
+/// candles, the default) or "lttb" (shape-preserving for line charts).
 #[tauri::command]
 pub async fn get_chart_data(
+    app: tauri::AppHandle,
     ticker: String,
     from_ts: i64,
     to_ts: i64,
     interval: String,
+    max_points: Option<usize>,
+    mode: Option<String>,
     db: State<'_, Mutex<Database>>,
     cache: State<'_, Mutex<MarketDataCache>>,
     api_key_manager: State<'_, Arc<APIKeyManager>>,
-) -> Result<Vec<ChartDataPoint>, String> {
+    rate_limiter: State<'_, Mutex<RateLimiter>>,
+) -> Result<ChartData, String> {
+    use crate::services::chart_downsampling::{bucket_ohlcv, lttb};
+    use crate::storage::temporal::TemporalStore;
+
+    let mode = mode.unwrap_or_else(|| "ohlc".to_string());
+    if mode != "ohlc" && mode != "lttb" {
+        return Err(format!("Unknown downsampling mode: {}", mode));
+    }
+    let max_points = max_points.unwrap_or(DEFAULT_CHART_MAX_POINTS).clamp(3, MAX_CHART_MAX_POINTS);
+
+    let mut history = match cached_chart_history(&ticker, from_ts, to_ts, &db, &cache)? {
+        Some(history) => history,
+        None => {
+            // Fetch from provider, or serve its last response while that refreshes
+            let manager = MarketDataManager::new(Some(api_key_manager.inner().as_ref()));
+            // Clone out of the mutex so the lock isn't held across the fetch; clones share state
+            let limiter = rate_limiter.lock()
+                .map_err(|e| format!("Rate limiter lock error: {}", e))?
+                .clone();
+            let conn = {
+                let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+                db_guard.conn.clone()
+            };
+            let history_ttl = cache.lock().map(|c| c.ttls().history_secs as i64).unwrap_or(3600);
+            let key = format!("history:{}:{}:{}:{}", ticker.to_uppercase(), from_ts, to_ts, interval);
+            let (fetch_ticker, fetch_interval) = (ticker.clone(), interval.clone());
+            let Cached { value: ohlcv_data, provider, stale, .. } = ResponseCache::new(conn.clone(), Some(app))
+                .get_or_fetch(&key, "history", history_ttl, move || async move {
+                    let history = manager.get_history(&fetch_ticker, from_ts, to_ts, &fetch_interval, Some(&limiter)).await?;
+                    Ok::<_, anyhow::Error>((history, manager.last_provider()))
+                })
+                .await
+                .map_err(|e| format!("Failed to fetch chart data: {}", e))?;
+
+            // Cache in memory, unless stale so the next request sees the refresh
+            if !stale {
+                if let Ok(cache_guard) = cache.lock() {
+                    cache_guard.set_history(ticker.clone(), from_ts, to_ts, ohlcv_data.clone(), provider.as_deref());
+                }
+            }
+
+            // Cache in database
+            let store = MarketDataStore::new(conn);
+            for data in &ohlcv_data {
+                let history = PriceHistory {
+                    id: 0,
+                    ticker: ticker.clone(),
+                    timestamp: data.timestamp,
+                    open: data.open,
+                    high: data.high,
+                    low: data.low,
+                    close: data.close,
+                    volume: data.volume,
+                };
+
+                if let Err(e) = store.insert_price_history(&history) {
+                    eprintln!("Failed to cache history: {}", e);
+                }
+            }
+
+            ohlcv_data
+        }
+    };
+    history.sort_by_key(|d| d.timestamp);
+    let source_points = history.len();
+    let sampled = if mode == "lttb" { lttb(&history, max_points) } else { bucket_ohlcv(&history, max_points) };
+
+    let (events, annotations) = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        let events = TemporalStore::new(db_guard.conn.clone())
+            .list_events_for_ticker(&ticker, from_ts, to_ts, 500)
+            .map_err(|e| format!("Failed to load chart events: {}", e))?;
+        let annotations = MarketDataStore::new(db_guard.conn.clone())
+            .list_chart_annotations(&ticker, from_ts, to_ts)
+            .map_err(|e| format!("Failed to load chart annotations: {}", e))?;
+        (events, annotations)
+    };
+    let markers = events
+        .into_iter()
+        .filter_map(|event| {
+            // Snap to the last bar at or before the event so the UI can place it;
+            // events before the first bar are off the chart and dropped
+            let index = sampled.partition_point(|p| p.timestamp <= event.start_ts);
+            let bar = sampled.get(index.checked_sub(1)?)?;
+            Some(ChartEventMarker {
+                event_id: event.id,
+                time: bar.timestamp,
+                start_ts: event.start_ts,
+                title: event.title,
+                event_type: event.event_type,
+                severity: event.severity,
+                sentiment_score: event.sentiment_score,
+            })
+        })
+        .collect();
+    let annotations = annotations
+        .into_iter()
+        .filter_map(|annotation| {
+            let index = sampled.partition_point(|p| p.timestamp <= annotation.timestamp);
+            let bar = sampled.get(index.checked_sub(1)?)?;
+            Some(ChartAnnotationMarker {
+                annotation_id: annotation.id,
+                time: bar.timestamp,
+                timestamp: annotation.timestamp,
+                kind: annotation.kind,
+                text: annotation.text,
+                event_id: annotation.event_id,
+                alert_id: annotation.alert_id,
+            })
+        })
+        .collect();
+
+    Ok(ChartData {
+        ticker,
+        interval,
+        downsampled: sampled.len() < source_points,
+        points: sampled
+            .into_iter()
+            .map(|d| ChartDataPoint {
+                time: d.timestamp,
+                open: d.open,
+                high: d.high,
+                low: d.low,
+                close: d.close,
+                volume: d.volume,
+            })
+            .collect(),
+        markers,
+        annotations,
+        source_points,
+    })
+}
+
+/// Raw bars from the in-memory cache, else the local database.
+fn cached_chart_history(
+    ticker: &str,
+    from_ts: i64,
+    to_ts: i64,
+    db: &State<'_, Mutex<Database>>,
+    cache: &State<'_, Mutex<MarketDataCache>>,
+) -> Result<Option<Vec<crate::providers::market_data::OHLCVData>>, String> {
     use crate::providers::market_data::OHLCVData;
-    
+
     // Try in-memory cache first
     if let Ok(cache_guard) = cache.lock() {
-        if let Some(history) = cache_guard.get_history(&ticker, from_ts, to_ts) {
-            return Ok(history
-                .into_iter()
-                .map(|d| ChartDataPoint {
-                    time: d.timestamp,
-                    open: d.open,
-                    high: d.high,
-                    low: d.low,
-                    close: d.close,
-                    volume: d.volume,
-                })
-                .collect());
+        if let Some(history) = cache_guard.get_history(ticker, from_ts, to_ts) {
+            return Ok(Some(history));
         }
     }
-    
+
     let conn = {
         let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
         db_guard.conn.clone()
     };
     let store = MarketDataStore::new(conn);
-    
-    // Try database cache
-    if let Ok(history) = store.get_price_history(&ticker, from_ts, to_ts, Some(10000)) {
+
+    // Try database cache; the whole range, since downsampling happens afterwards
+    if let Ok(history) = store.get_price_history_range(ticker, from_ts, to_ts) {
         if !history.is_empty() {
             let ohlcv_data: Vec<OHLCVData> = history
                 .into_iter()
@@ -248,73 +471,17 @@ pub async fn get_chart_data(
                     volume: h.volume,
                 })
                 .collect();
-            
-            // Cache in memory
-            if let Ok(cache_guard) = cache.lock() {
-                cache_guard.set_history(ticker.clone(), from_ts, to_ts, ohlcv_data.clone());
-            }
-            
-            return Ok(ohlcv_data
-                .into_iter()
-                .map(|d| ChartDataPoint {
-                    time: d.timestamp,
-                    open: d.open,
-                    high: d.high,
-                    low: d.low,
-                    close: d.close,
-                    volume: d.volume,
-                })
-                .collect());
-        }
-    }
 
-    // Fetch from provider
-    let manager = MarketDataManager::new(Some(api_key_manager.inner().as_ref()));
-    // Rate limiter is optional - pass None to avoid holding lock across await
-    match manager.get_history(&ticker, from_ts, to_ts, &interval, None).await {
-        Ok(ohlcv_data) => {
             // Cache in memory
             if let Ok(cache_guard) = cache.lock() {
-                cache_guard.set_history(ticker.clone(), from_ts, to_ts, ohlcv_data.clone());
-            }
-            
-            // Cache in database
-            let conn = {
-                let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
-                db_guard.conn.clone()
-            };
-            let store = MarketDataStore::new(conn);
-            for data in &ohlcv_data {
-                let history = PriceHistory {
-                    id: 0,
-                    ticker: ticker.clone(),
-                    timestamp: data.timestamp,
-                    open: data.open,
-                    high: data.high,
-                    low: data.low,
-                    close: data.close,
-                    volume: data.volume,
-                };
-                
-                if let Err(e) = store.insert_price_history(&history) {
-                    eprintln!("Failed to cache history: {}", e);
-                }
+                cache_guard.set_history(ticker.to_string(), from_ts, to_ts, ohlcv_data.clone(), None);
             }
 
-            Ok(ohlcv_data
-                .into_iter()
-                .map(|d| ChartDataPoint {
-                    time: d.timestamp,
-                    open: d.open,
-                    high: d.high,
-                    low: d.low,
-                    close: d.close,
-                    volume: d.volume,
-                })
-                .collect())
+            return Ok(Some(ohlcv_data));
         }
-        Err(e) => Err(format!("Failed to fetch chart data: {}", e)),
     }
+
+    Ok(None)
 }
 
 #[tauri::command]
@@ -329,20 +496,353 @@ pub fn get_events_for_chart(
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
     let store = TemporalStore::new(db_guard.conn.clone());
     
-    // Get events in time range
-    // For now, filter by ticker in title/summary (will be enhanced later)
-    let events = store
-        .list_events(1000, Some(from_ts), Some(to_ts))
-        .map_err(|e| format!("Failed to list events: {}", e))?;
-    
-    // Filter events that mention the ticker
-    let filtered: Vec<_> = events
-        .into_iter()
-        .filter(|e| {
-            e.title.to_uppercase().contains(&ticker.to_uppercase())
-                || e.summary.to_uppercase().contains(&ticker.to_uppercase())
-        })
-        .collect();
-    
-    Ok(filtered)
+    store
+        .list_events_for_ticker(&ticker, from_ts, to_ts, 1000)
+        .map_err(|e| format!("Failed to list events: {}", e))
+}
+
+#[tauri::command]
+pub fn list_chart_annotations(
+    ticker: String,
+    from_ts: i64,
+    to_ts: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<ChartAnnotation>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    MarketDataStore::new(db_guard.conn.clone())
+        .list_chart_annotations(&ticker, from_ts, to_ts)
+        .map_err(|e| format!("Failed to list chart annotations: {}", e))
+}
+
+#[tauri::command]
+pub fn add_chart_annotation(
+    ticker: String,
+    timestamp: i64,
+    text: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    if ticker.trim().is_empty() {
+        return Err("Ticker must not be empty".to_string());
+    }
+    if text.trim().is_empty() {
+        return Err("Annotation text must not be empty".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    MarketDataStore::new(db_guard.conn.clone())
+        .add_chart_annotation(&ticker, timestamp, text.trim())
+        .map_err(|e| format!("Failed to add chart annotation: {}", e))
+}
+
+/// Pin an alert (when `alert_id` is set) or an event to a ticker's chart.
+#[tauri::command]
+pub fn pin_chart_marker(
+    ticker: String,
+    event_id: Option<i64>,
+    alert_id: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    use crate::services::chart_annotations::ChartAnnotationService;
+    use crate::storage::temporal::TemporalStore;
+
+    if ticker.trim().is_empty() {
+        return Err("Ticker must not be empty".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let temporal_store = TemporalStore::new(db_guard.conn.clone());
+    let market_data_store = MarketDataStore::new(db_guard.conn.clone());
+    let pinned = match (alert_id, event_id) {
+        (Some(alert_id), _) => ChartAnnotationService::pin_alert(&temporal_store, &market_data_store, &ticker, alert_id),
+        (None, Some(event_id)) => ChartAnnotationService::pin_event(&temporal_store, &market_data_store, &ticker, event_id),
+        (None, None) => return Err("Either an event or an alert is required".to_string()),
+    };
+    pinned.map_err(|e| format!("Failed to pin chart marker: {}", e))
+}
+
+#[tauri::command]
+pub fn update_chart_annotation(
+    id: i64,
+    text: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<bool, String> {
+    if text.trim().is_empty() {
+        return Err("Annotation text must not be empty".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    MarketDataStore::new(db_guard.conn.clone())
+        .update_chart_annotation(id, text.trim())
+        .map_err(|e| format!("Failed to update chart annotation: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_chart_annotation(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<bool, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    MarketDataStore::new(db_guard.conn.clone())
+        .delete_chart_annotation(id)
+        .map_err(|e| format!("Failed to delete chart annotation: {}", e))
+}
+
+#[tauri::command]
+pub fn get_market_cache_stats(
+    cache: State<'_, Mutex<MarketDataCache>>,
+) -> Result<MarketCacheStats, String> {
+    let cache_guard = cache.lock().map_err(|e| format!("Cache lock error: {}", e))?;
+    Ok(cache_guard.stats())
+}
+
+#[tauri::command]
+pub fn list_market_cache_entries(
+    cache: State<'_, Mutex<MarketDataCache>>,
+) -> Result<Vec<CacheEntryInfo>, String> {
+    let cache_guard = cache.lock().map_err(|e| format!("Cache lock error: {}", e))?;
+    Ok(cache_guard.entries())
+}
+
+/// Drop cached entries by ticker, provider and/or kind ("quote", "history",
+/// "fundamentals"). With no filters the whole cache is cleared. Returns the
+/// number of entries removed.
+#[tauri::command]
+pub fn invalidate_market_cache(
+    ticker: Option<String>,
+    provider: Option<String>,
+    kind: Option<String>,
+    db: State<'_, Mutex<Database>>,
+    cache: State<'_, Mutex<MarketDataCache>>,
+) -> Result<usize, String> {
+    if let Some(k) = kind.as_deref() {
+        if !matches!(k, "quote" | "history" | "fundamentals") {
+            return Err(format!("Unknown cache kind: {}", k));
+        }
+    }
+    // Persisted provider responses would otherwise be served right back
+    let persisted = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        ResponseCacheStore::new(db_guard.conn.clone())
+            .clear(kind.as_deref(), ticker.as_deref(), provider.as_deref())
+            .map_err(|e| format!("Failed to clear cached responses: {}", e))?
+    };
+    let cache_guard = cache.lock().map_err(|e| format!("Cache lock error: {}", e))?;
+    Ok(persisted + cache_guard.invalidate(&CacheInvalidation { ticker, provider, kind }))
+}
+
+#[tauri::command]
+pub fn get_market_cache_ttls(
+    cache: State<'_, Mutex<MarketDataCache>>,
+) -> Result<CacheTtls, String> {
+    let cache_guard = cache.lock().map_err(|e| format!("Cache lock error: {}", e))?;
+    Ok(cache_guard.ttls())
+}
+
+#[tauri::command]
+pub fn set_market_cache_ttls(
+    ttls: CacheTtls,
+    db: State<'_, Mutex<Database>>,
+    cache: State<'_, Mutex<MarketDataCache>>,
+) -> Result<(), String> {
+    if ttls.quote_secs == 0 || ttls.history_secs == 0 || ttls.fundamentals_secs == 0 {
+        return Err("Cache TTLs must be at least one second".to_string());
+    }
+    {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        ttls.save(&db_guard)
+            .map_err(|e| format!("Failed to save cache TTLs: {}", e))?;
+    }
+    let cache_guard = cache.lock().map_err(|e| format!("Cache lock error: {}", e))?;
+    cache_guard.set_ttls(ttls);
+    Ok(())
+}
+
+/// Stored fundamentals for a ticker, fetched from the providers when missing,
+/// older than the cache TTL, or when `refresh` is set.
+#[tauri::command]
+pub async fn get_fundamentals(
+    ticker: String,
+    refresh: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+    cache: State<'_, Mutex<MarketDataCache>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+    rate_limiter: State<'_, Mutex<RateLimiter>>,
+) -> Result<Fundamentals, String> {
+    let ticker = ticker.trim().to_uppercase();
+    let cache = cache.lock()
+        .map_err(|e| format!("Cache lock error: {}", e))?
+        .clone();
+
+    if !refresh.unwrap_or(false) {
+        if let Some(cached) = cache.get_fundamentals(&ticker) {
+            if let Ok(fundamentals) = serde_json::from_value::<Fundamentals>(cached) {
+                return Ok(fundamentals);
+            }
+        }
+        let stored = {
+            let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+            MarketDataStore::new(db_guard.conn.clone())
+                .get_fundamentals(&ticker)
+                .map_err(|e| format!("Failed to load fundamentals: {}", e))?
+        };
+        if let Some(fundamentals) = stored {
+            let age = chrono::Utc::now().timestamp() - fundamentals.fetched_at;
+            if age < cache.ttls().fundamentals_secs as i64 {
+                if let Ok(value) = serde_json::to_value(&fundamentals) {
+                    cache.set_fundamentals(&ticker, value, None);
+                }
+                return Ok(fundamentals);
+            }
+        }
+    }
+
+    let db_arc = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() }))
+    };
+    let limiter = rate_limiter.lock()
+        .map_err(|e| format!("Rate limiter lock error: {}", e))?
+        .clone();
+    FundamentalsService::fetch(&db_arc, api_key_manager.inner().as_ref(), Some(&limiter), Some(&cache), &ticker)
+        .await
+        .map_err(|e| format!("Failed to fetch fundamentals: {}", e))
+}
+
+/// Stored short interest and analyst rating history of a ticker; fetched
+/// from the providers first when `refresh` is set.
+#[tauri::command]
+pub async fn get_market_signals(
+    ticker: String,
+    refresh: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+    rate_limiter: State<'_, Mutex<RateLimiter>>,
+) -> Result<TickerSignals, String> {
+    let db_arc = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() }))
+    };
+    if refresh.unwrap_or(false) {
+        let limiter = rate_limiter.lock()
+            .map_err(|e| format!("Rate limiter lock error: {}", e))?
+            .clone();
+        return MarketSignalsService::fetch(&db_arc, api_key_manager.inner().as_ref(), Some(&limiter), &ticker)
+            .await
+            .map_err(|e| format!("Failed to fetch market signals: {}", e));
+    }
+    let conn = db_arc.lock().map_err(|e| format!("Database lock error: {}", e))?.conn.clone();
+    MarketSignalsService::signals(conn, &ticker)
+        .map_err(|e| format!("Failed to load market signals: {}", e))
+}
+
+/// Refresh short interest and ratings now: the given tickers, or every stale
+/// watchlist ticker when none are given. Returns how many were updated.
+#[tauri::command]
+pub async fn refresh_market_signals(
+    tickers: Option<Vec<String>>,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+    rate_limiter: State<'_, Mutex<RateLimiter>>,
+) -> Result<usize, String> {
+    let db_arc = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() }))
+    };
+    let limiter = rate_limiter.lock()
+        .map_err(|e| format!("Rate limiter lock error: {}", e))?
+        .clone();
+    let api_key_manager = api_key_manager.inner().as_ref();
+
+    match tickers {
+        Some(tickers) => {
+            let mut refreshed = 0;
+            for ticker in tickers {
+                match MarketSignalsService::fetch(&db_arc, api_key_manager, Some(&limiter), &ticker).await {
+                    Ok(_) => refreshed += 1,
+                    Err(e) => eprintln!("Failed to refresh market signals for {}: {}", ticker, e),
+                }
+            }
+            Ok(refreshed)
+        }
+        None => MarketSignalsService::refresh_watchlist(&db_arc, api_key_manager, Some(&limiter))
+            .await
+            .map_err(|e| format!("Failed to refresh market signals: {}", e)),
+    }
+}
+
+/// Symbol autocomplete for watchlist and portfolio forms: local tickers and
+/// cached provider results, ranked. Providers are queried at most once a
+/// week per query unless `refresh` is set.
+#[tauri::command]
+pub async fn search_symbols(
+    query: String,
+    limit: Option<usize>,
+    refresh: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+    rate_limiter: State<'_, Mutex<RateLimiter>>,
+) -> Result<Vec<SymbolSearchResult>, String> {
+    let db_arc = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() }))
+    };
+    let limiter = rate_limiter.lock()
+        .map_err(|e| format!("Rate limiter lock error: {}", e))?
+        .clone();
+    SymbolSearchService::search(
+        &db_arc,
+        api_key_manager.inner().as_ref(),
+        Some(&limiter),
+        &query,
+        limit.unwrap_or(10).clamp(1, 50),
+        refresh.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| format!("Failed to search symbols: {}", e))
+}
+
+#[tauri::command]
+pub fn list_fundamentals(
+    sector: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<Fundamentals>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    MarketDataStore::new(db_guard.conn.clone())
+        .list_fundamentals(sector.as_deref())
+        .map_err(|e| format!("Failed to list fundamentals: {}", e))
+}
+
+/// Refresh fundamentals now: the given tickers, or every stale held/alerted
+/// ticker when none are given. Returns how many were updated.
+#[tauri::command]
+pub async fn refresh_fundamentals(
+    tickers: Option<Vec<String>>,
+    db: State<'_, Mutex<Database>>,
+    cache: State<'_, Mutex<MarketDataCache>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+    rate_limiter: State<'_, Mutex<RateLimiter>>,
+) -> Result<usize, String> {
+    let db_arc = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() }))
+    };
+    let cache = cache.lock()
+        .map_err(|e| format!("Cache lock error: {}", e))?
+        .clone();
+    let limiter = rate_limiter.lock()
+        .map_err(|e| format!("Rate limiter lock error: {}", e))?
+        .clone();
+
+    match tickers {
+        Some(tickers) => {
+            let mut refreshed = 0;
+            for ticker in tickers {
+                match FundamentalsService::fetch(&db_arc, api_key_manager.inner().as_ref(), Some(&limiter), Some(&cache), &ticker).await {
+                    Ok(_) => refreshed += 1,
+                    Err(e) => eprintln!("Failed to refresh fundamentals for {}: {}", ticker, e),
+                }
+            }
+            Ok(refreshed)
+        }
+        None => FundamentalsService::refresh_stale(&db_arc, api_key_manager.inner().as_ref(), Some(&limiter), Some(&cache))
+            .await
+            .map_err(|e| format!("Failed to refresh fundamentals: {}", e)),
+    }
 }
@@ -0,0 +1,128 @@
+use crate::commands::ollama::OllamaState;
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::conversation_memory::{ConversationMemoryService, ScoredMemory, CONFIG_MEMORY_ENABLED};
+use crate::services::embeddings::EmbeddingService;
+use crate::services::llm_metering::LlmMeter;
+use crate::services::redaction::PrivacyFilter;
+use crate::storage::memories::{Memory, MemoryStore};
+use crate::storage::Database;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+pub(crate) fn embedding_service(api_key_manager: &APIKeyManager, db: &Mutex<Database>) -> EmbeddingService {
+    let mut service = EmbeddingService::new();
+    if let Ok(Some(openai_key)) = api_key_manager.get_key_optional("openai") {
+        service.set_openai_key(openai_key);
+    }
+    if let Ok(db_guard) = db.lock() {
+        service.set_meter(LlmMeter::new(db_guard.conn.clone()));
+        service.set_privacy_filter(PrivacyFilter::new(db_guard.conn.clone()));
+        service.load_image_config(&db_guard);
+    }
+    service
+}
+
+#[tauri::command]
+pub fn set_memory_enabled(
+    enabled: bool,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    db_guard.set_config(CONFIG_MEMORY_ENABLED, if enabled { "true" } else { "false" })
+        .map_err(|e| format!("Failed to save memory setting: {}", e))
+}
+
+/// Extract durable facts from a conversation into memory. Does nothing unless memory is enabled.
+#[tauri::command]
+pub async fn extract_conversation_memories(
+    conversation_id: String,
+    model: String,
+    db: State<'_, Mutex<Database>>,
+    ollama: State<'_, OllamaState>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<Vec<Memory>, String> {
+    if !ConversationMemoryService::is_enabled(&db).map_err(|e| e.to_string())? {
+        return Ok(Vec::new());
+    }
+    let embedder = embedding_service(&api_key_manager, &db);
+    let provider = ollama.read().await;
+    ConversationMemoryService::extract_from_conversation(&db, &provider, &embedder, &model, &conversation_id)
+        .await
+        .map_err(|e| format!("Failed to extract memories: {}", e))
+}
+
+#[tauri::command]
+pub fn list_memories(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<Memory>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = MemoryStore::new(db_guard.conn.clone());
+    store.list_memories()
+        .map_err(|e| format!("Failed to list memories: {}", e))
+}
+
+#[tauri::command]
+pub async fn add_memory(
+    content: String,
+    kind: Option<String>,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<i64, String> {
+    if content.trim().is_empty() {
+        return Err("Memory content must not be empty".to_string());
+    }
+    let embedding = embedding_service(&api_key_manager, &db)
+        .generate(content.trim())
+        .await
+        .map_err(|e| format!("Failed to embed memory: {}", e))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = MemoryStore::new(db_guard.conn.clone());
+    store.add_memory(content.trim(), kind.as_deref().unwrap_or("fact"), None, &embedding)
+        .map_err(|e| format!("Failed to add memory: {}", e))
+}
+
+#[tauri::command]
+pub async fn update_memory(
+    id: i64,
+    content: String,
+    kind: Option<String>,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<(), String> {
+    if content.trim().is_empty() {
+        return Err("Memory content must not be empty".to_string());
+    }
+    let embedding = embedding_service(&api_key_manager, &db)
+        .generate(content.trim())
+        .await
+        .map_err(|e| format!("Failed to embed memory: {}", e))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = MemoryStore::new(db_guard.conn.clone());
+    store.update_memory(id, content.trim(), kind.as_deref(), &embedding)
+        .map_err(|e| format!("Failed to update memory: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_memory(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = MemoryStore::new(db_guard.conn.clone());
+    store.delete_memory(id)
+        .map_err(|e| format!("Failed to delete memory: {}", e))
+}
+
+/// Memories relevant to `query`, best match first.
+#[tauri::command]
+pub async fn search_memories(
+    query: String,
+    limit: Option<usize>,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<Vec<ScoredMemory>, String> {
+    let embedder = embedding_service(&api_key_manager, &db);
+    ConversationMemoryService::find_relevant(&db, &embedder, &query, limit.unwrap_or(5))
+        .await
+        .map_err(|e| format!("Failed to search memories: {}", e))
+}
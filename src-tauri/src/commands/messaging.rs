@@ -3,7 +3,7 @@ use crate::storage::Database;
 use crate::ws::WsServer;
 use serde_json::Value;
 use std::sync::Mutex;
-use tauri::{State, Emitter};
+use tauri::State;
 
 #[tauri::command]
 pub fn messaging_create_conversation(
@@ -52,7 +52,7 @@ pub fn send_message(
         let _ = ws_guard.publish("messaging", msg.clone());
         
         // Also emit Tauri event for frontend
-        let _ = app.emit("ws-message", serde_json::json!({
+        let _ = crate::services::window_router::emit(&app, serde_json::json!({
             "type": "message",
             "data": message,
             "timestamp": chrono::Utc::now().timestamp_millis(),
@@ -1,5 +1,5 @@
 use crate::storage::migration_tracking::MigrationTracker;
-use crate::storage::Database;
+use crate::storage::{Database, SchemaIntegrity, SchemaReport};
 use std::sync::Mutex;
 use tauri::State;
 
@@ -23,3 +23,22 @@ pub fn get_latest_migration_version(
         .map_err(|e| format!("Failed to get latest version: {}", e))
 }
 
+
+/// Compare the database with the schema the stores expect, without changes.
+#[tauri::command]
+pub fn check_schema_integrity(db: State<'_, Mutex<Database>>) -> Result<SchemaReport, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    SchemaIntegrity::verify(&db_guard.conn)
+        .map_err(|e| format!("Failed to check schema: {}", e))
+}
+
+/// Apply safe repairs, plus a rebuild of each table named in `rebuild`.
+#[tauri::command]
+pub fn repair_schema(
+    rebuild: Option<Vec<String>>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<SchemaReport, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    SchemaIntegrity::repair(&db_guard.conn, &rebuild.unwrap_or_default())
+        .map_err(|e| format!("Failed to repair schema: {}", e))
+}
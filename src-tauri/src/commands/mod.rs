@@ -31,6 +31,33 @@ pub mod global_search;
 pub mod notifications;
 pub mod data_export;
 pub mod price_alerts;
+pub mod disk_usage;
+pub mod clipboard;
+pub mod time_tracking;
+pub mod benchmarks;
+pub mod memory;
+pub mod llm_usage;
+pub mod watch_folders;
+pub mod statement_import;
+pub mod db_housekeeping;
+pub mod reports;
+pub mod profiles;
+pub mod sync;
+pub mod backup;
+pub mod object_storage;
+pub mod fx;
+pub mod scenarios;
+pub mod documents;
+pub mod transcripts;
+pub mod jobs;
+pub mod windows;
+pub mod palette;
+pub mod updates;
+pub mod crash_reports;
+pub mod demo;
+pub mod market_calendar;
+pub mod dashboards;
+pub mod tags;
 
 // Re-exports are not needed - commands are registered directly in lib.rs
 
@@ -0,0 +1,114 @@
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::chat_export::{ChatExportFormat, ChatExportService};
+use crate::services::object_storage::{
+    ObjectStorageService, ACCESS_KEY_PROVIDER, ARCHIVES_PREFIX, CONFIG_BUCKET, CONFIG_ENDPOINT, CONFIG_PREFIX,
+    CONFIG_REGION, REPORTS_PREFIX, SECRET_KEY_PROVIDER,
+};
+use crate::storage::ai::AIStore;
+use crate::storage::Database;
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+fn db_arc(db: &State<'_, Mutex<Database>>) -> Result<Arc<Mutex<Database>>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ObjectStorageSettings {
+    pub endpoint: Option<String>, // e.g. http://minio.local:9000, empty to disable
+    pub bucket: Option<String>,
+    pub region: Option<String>,
+    pub prefix: Option<String>,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+}
+
+#[tauri::command]
+pub fn set_object_storage_settings(
+    settings: ObjectStorageSettings,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<(), String> {
+    if let Some(endpoint) = settings.endpoint.as_deref().map(str::trim) {
+        if !endpoint.is_empty() && !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            return Err("Endpoint must start with http:// or https://".to_string());
+        }
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    for (key, value) in [
+        (CONFIG_ENDPOINT, settings.endpoint),
+        (CONFIG_BUCKET, settings.bucket),
+        (CONFIG_REGION, settings.region),
+        (CONFIG_PREFIX, settings.prefix),
+    ] {
+        if let Some(value) = value {
+            db_guard.set_config(key, value.trim())
+                .map_err(|e| format!("Failed to save object storage settings: {}", e))?;
+        }
+    }
+    for (provider, value) in [
+        (ACCESS_KEY_PROVIDER, settings.access_key_id),
+        (SECRET_KEY_PROVIDER, settings.secret_access_key),
+    ] {
+        if let Some(value) = value {
+            api_key_manager.store_key(provider, &value)
+                .map_err(|e| format!("Failed to save object storage credentials: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Store a generated report or export (e.g. the output of `export_data`) in the bucket.
+/// Returns the object key.
+#[tauri::command]
+pub async fn upload_report_artifact(
+    name: String,
+    content: String,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<String, String> {
+    if name.is_empty() || name.contains('/') || name.contains('\\') {
+        return Err("Artifact name must be a plain file name".to_string());
+    }
+    let db_arc = db_arc(&db)?;
+    let subprefix = format!("{}/{}", REPORTS_PREFIX, chrono::Utc::now().format("%Y-%m-%d"));
+    ObjectStorageService::upload_bytes(&db_arc, &api_key_manager, &subprefix, &name, content.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to upload report: {}", e))
+}
+
+/// Push archived conversations to the bucket as one JSON document. Without
+/// ids, every archived conversation is included. Returns the object key.
+#[tauri::command]
+pub async fn upload_conversation_archive(
+    ids: Option<Vec<String>>,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<String, String> {
+    let content = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        let store = AIStore::new(db_guard.conn.clone())
+            .map_err(|e| format!("Failed to initialize AIStore: {}", e))?;
+        let ids = match ids {
+            Some(ids) => ids,
+            None => store.list_archived_conversations()
+                .map_err(|e| format!("Failed to list archived conversations: {}", e))?
+                .into_iter()
+                .map(|c| c.id)
+                .collect(),
+        };
+        if ids.is_empty() {
+            return Err("No archived conversations to upload".to_string());
+        }
+        ChatExportService::export(&store, &ids, ChatExportFormat::Json)
+            .map_err(|e| format!("Failed to export conversations: {}", e))?
+    };
+
+    let db_arc = db_arc(&db)?;
+    let name = format!("conversations-{}.json", chrono::Utc::now().format("%Y%m%dT%H%M%SZ"));
+    ObjectStorageService::upload_bytes(&db_arc, &api_key_manager, ARCHIVES_PREFIX, &name, content.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to upload archive: {}", e))
+}
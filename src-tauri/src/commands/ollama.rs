@@ -1,9 +1,12 @@
 use crate::providers::ollama::{OllamaProvider, OllamaModel, ChatMessage};
+use crate::services::context_window::{ContextManager, ContextUsage};
+use crate::services::model_benchmark::{ModelBenchmark, QualityTier};
+use crate::services::ollama_lifecycle::{LifecycleSettings, ModelLifecycle, ModelLoadState};
+use crate::storage::model_benchmarks::{ModelBenchmarkStore, ModelBenchmarkSummary};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tauri::State;
-use notify::{Watcher, RecommendedWatcher, RecursiveMode, Event, EventKind};
 
 // Global Ollama provider state
 pub type OllamaState = Arc<RwLock<OllamaProvider>>;
@@ -50,14 +53,47 @@ pub async fn load_model_from_file(
         .map_err(|e| format!("Failed to load model: {}", e))
 }
 
+/// With a `conversation_id`, older turns that would overflow the model's
+/// context window are replaced by the conversation's rolling summary, and
+/// the resulting usage is reported as a `context-usage` event.
 #[tauri::command]
 pub async fn chat_with_ollama(
     model: String,
     messages: Vec<ChatMessage>,
+    conversation_id: Option<String>,
     ollama: State<'_, OllamaState>,
+    db: State<'_, std::sync::Mutex<crate::storage::Database>>,
+    api_key_manager: State<'_, Arc<crate::services::api_key_manager::APIKeyManager>>,
+    app: tauri::AppHandle,
 ) -> Result<String, String> {
+    use crate::services::conversation_memory::ConversationMemoryService;
+
+    let mut messages = messages;
+    if let Some(conversation_id) = conversation_id.as_deref() {
+        let provider = ollama.read().await;
+        let (fitted, usage) = ContextManager::fit(&db, &provider, &model, conversation_id, messages)
+            .await
+            .map_err(|e| format!("Failed to fit conversation into context: {}", e))?;
+        messages = fitted;
+        let _ = crate::services::window_router::emit(&app, serde_json::json!({
+            "type": "context-usage",
+            "data": { "conversation_id": conversation_id, "usage": usage },
+            "timestamp": chrono::Utc::now().timestamp_millis(),
+        }));
+    }
+    if ConversationMemoryService::is_enabled(&db).unwrap_or(false) {
+        let embedder = crate::commands::memory::embedding_service(&api_key_manager, &db);
+        if let Err(e) = ConversationMemoryService::inject(&db, &embedder, &mut messages, 5).await {
+            eprintln!("Warning: Failed to inject memories into chat: {}", e);
+        }
+    }
+
+    let meter = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        crate::services::llm_metering::LlmMeter::new(db_guard.conn.clone())
+    };
     let provider = ollama.read().await;
-    provider.chat(&model, messages).await
+    meter.ollama_chat(&provider, &model, messages).await
         .map_err(|e| format!("Failed to chat with Ollama: {}", e))
 }
 
@@ -83,42 +119,90 @@ pub async fn get_models_folder_path(
     Ok(provider.get_models_folder().to_string_lossy().to_string())
 }
 
-// Start watching the models folder for new files
-pub async fn start_models_folder_watcher(
-    ollama: Arc<RwLock<OllamaProvider>>,
-) -> Result<RecommendedWatcher, String> {
-    // Get the models folder path
-    let models_folder = {
-        let provider = ollama.read().await;
-        provider.get_models_folder().to_path_buf()
+/// Benchmark installed models (or just `models`) on the standard prompt set.
+#[tauri::command]
+pub async fn run_ollama_benchmark(
+    models: Option<Vec<String>>,
+    ollama: State<'_, OllamaState>,
+    db: State<'_, std::sync::Mutex<crate::storage::Database>>,
+) -> Result<Vec<ModelBenchmarkSummary>, String> {
+    let store = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        ModelBenchmarkStore::new(db_guard.conn.clone())
     };
-    
-    let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
-        match res {
-            Ok(event) => {
-                if let EventKind::Create(_) | EventKind::Modify(_) = event.kind {
-                    // New file added or modified
-                    if let Some(path) = event.paths.first() {
-                        // Check if it's a model file
-                        if let Some(ext) = path.extension() {
-                            let ext_str = ext.to_string_lossy().to_lowercase();
-                            if matches!(ext_str.as_str(), "gguf" | "bin" | "safetensors" | "pt" | "pth") {
-                                println!("New model file detected: {:?}", path);
-                                // In a real implementation, you might want to emit a Tauri event here
-                                // or trigger model loading automatically
-                            }
-                        }
-                    }
-                }
-            }
-            Err(e) => eprintln!("Watch error: {:?}", e),
-        }
-    })
-    .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+    let provider = ollama.read().await;
+    ModelBenchmark::run(&provider, &store, models).await
+        .map_err(|e| format!("Failed to benchmark models: {}", e))
+}
+
+/// Each model's latest benchmark results, per task.
+#[tauri::command]
+pub async fn list_ollama_benchmarks(
+    task: Option<String>,
+    db: State<'_, std::sync::Mutex<crate::storage::Database>>,
+) -> Result<Vec<ModelBenchmarkSummary>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ModelBenchmarkStore::new(db_guard.conn.clone())
+        .list_summaries(task.as_deref())
+        .map_err(|e| format!("Failed to list benchmarks: {}", e))
+}
+
+/// The fastest benchmarked model meeting `min_tier` (basic|standard|high) for a task.
+#[tauri::command]
+pub async fn select_ollama_model(
+    task: String,
+    min_tier: String,
+    db: State<'_, std::sync::Mutex<crate::storage::Database>>,
+) -> Result<Option<String>, String> {
+    let tier = QualityTier::parse(&min_tier).map_err(|e| e.to_string())?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ModelBenchmark::select(&ModelBenchmarkStore::new(db_guard.conn.clone()), &task, tier)
+        .map_err(|e| format!("Failed to select model: {}", e))
+}
+
+/// How much of the model's context window `messages` take, after the
+/// conversation's summary (if any) replaces the turns it covers.
+#[tauri::command]
+pub async fn get_context_usage(
+    model: String,
+    messages: Vec<ChatMessage>,
+    conversation_id: Option<String>,
+    ollama: State<'_, OllamaState>,
+    db: State<'_, std::sync::Mutex<crate::storage::Database>>,
+) -> Result<ContextUsage, String> {
+    let provider = ollama.read().await;
+    ContextManager::usage(&db, &provider, &model, conversation_id.as_deref(), &messages)
+        .await
+        .map_err(|e| format!("Failed to compute context usage: {}", e))
+}
+
+/// Load state, queue depth and memory of the models seen this session.
+#[tauri::command]
+pub fn get_ollama_model_states() -> Result<Vec<ModelLoadState>, String> {
+    Ok(ModelLifecycle::states())
+}
 
-    watcher
-        .watch(&models_folder, RecursiveMode::Recursive)
-        .map_err(|e| format!("Failed to watch models folder: {}", e))?;
+#[tauri::command]
+pub fn get_ollama_lifecycle_settings(
+    db: State<'_, std::sync::Mutex<crate::storage::Database>>,
+) -> Result<LifecycleSettings, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    LifecycleSettings::load(&db_guard)
+        .map_err(|e| format!("Failed to load Ollama lifecycle settings: {}", e))
+}
 
-    Ok(watcher)
+#[tauri::command]
+pub fn set_ollama_lifecycle_settings(
+    settings: LifecycleSettings,
+    db: State<'_, std::sync::Mutex<crate::storage::Database>>,
+) -> Result<(), String> {
+    if settings.concurrency == 0 {
+        return Err("Concurrency must be at least 1".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    settings.save(&db_guard)
+        .map_err(|e| format!("Failed to save Ollama lifecycle settings: {}", e))?;
+    ModelLifecycle::reload();
+    Ok(())
 }
+
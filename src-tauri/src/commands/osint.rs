@@ -2,7 +2,7 @@ use crate::storage::osint::OSINTStore;
 use crate::storage::Database;
 use crate::storage::temporal::TemporalStore;
 use std::sync::Mutex;
-use tauri::{Emitter, State};
+use tauri::State;
 use rusqlite::params;
 
 #[tauri::command]
@@ -33,6 +33,37 @@ pub fn update_rss_feed(
         .map_err(|e| format!("Failed to update feed: {}", e))
 }
 
+/// Set the language a feed publishes in (ISO 639-1, e.g. "de"), or clear it
+/// to detect each article's language instead.
+#[tauri::command]
+pub fn set_rss_feed_language(
+    id: i64,
+    language: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let language = language.map(|l| l.trim().to_lowercase()).filter(|l| !l.is_empty());
+    if let Some(language) = language.as_deref().filter(|l| !crate::storage::search_index::is_language_code(l)) {
+        return Err(format!("Not an ISO 639-1 language code: {}", language));
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = OSINTStore::new(db_guard.conn.clone());
+    store.set_feed_language(id, language.as_deref())
+        .map_err(|e| format!("Failed to set feed language: {}", e))
+}
+
+/// Tag up to `limit` articles that have no language yet. Returns how many
+/// were processed; call again until it returns 0.
+#[tauri::command]
+pub fn detect_article_languages(
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<usize, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = OSINTStore::new(db_guard.conn.clone());
+    store.detect_item_languages(limit.unwrap_or(1000).clamp(1, 10_000))
+        .map_err(|e| format!("Failed to detect article languages: {}", e))
+}
+
 #[tauri::command]
 pub fn delete_rss_feed(
     id: i64,
@@ -133,6 +164,238 @@ pub fn create_entity_relationship(
         .map_err(|e| format!("Failed to create relationship: {}", e))
 }
 
+#[tauri::command]
+pub fn list_iocs(
+    ioc_type: Option<String>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::osint::Ioc>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = OSINTStore::new(db_guard.conn.clone());
+    store.list_iocs(ioc_type.as_deref(), limit.unwrap_or(500))
+        .map_err(|e| format!("Failed to list IOCs: {}", e))
+}
+
+/// Add an IOC, optionally citing the article it was found in. The type is
+/// detected from the value when not given.
+#[tauri::command]
+pub fn create_ioc(
+    value: String,
+    ioc_type: Option<String>,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+    article_id: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let value = value.trim().to_string();
+    let ioc_type = match ioc_type {
+        Some(t) => t,
+        None => crate::services::stix::classify_ioc(&value)
+            .ok_or_else(|| format!("Could not detect the IOC type of '{}'", value))?
+            .to_string(),
+    };
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = OSINTStore::new(db_guard.conn.clone());
+    let id = store
+        .upsert_ioc(&crate::storage::osint::NewIoc {
+            ioc_type,
+            value,
+            description,
+            source: "manual".to_string(),
+            tags: tags.unwrap_or_default(),
+            confidence: 0.5,
+        })
+        .map_err(|e| format!("Failed to save IOC: {}", e))?;
+    if let Some(article_id) = article_id {
+        store.link_ioc_article(id, article_id)
+            .map_err(|e| format!("Failed to link IOC to article: {}", e))?;
+    }
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn delete_ioc(id: i64, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = OSINTStore::new(db_guard.conn.clone());
+    store.delete_ioc(id)
+        .map_err(|e| format!("Failed to delete IOC: {}", e))
+}
+
+/// STIX 2.1 bundle of entities, IOCs and supporting articles as JSON.
+/// Without ids, everything is exported.
+#[tauri::command]
+pub fn export_stix_bundle(
+    entity_ids: Option<Vec<i64>>,
+    ioc_ids: Option<Vec<i64>>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let bundle = crate::services::stix::StixService::export(&db_guard, entity_ids.as_deref(), ioc_ids.as_deref())
+        .map_err(|e| format!("Failed to export STIX bundle: {}", e))?;
+    serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to serialize STIX bundle: {}", e))
+}
+
+#[tauri::command]
+pub fn import_stix_bundle(
+    bundle: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::stix::StixImportReport, String> {
+    let bundle: serde_json::Value = serde_json::from_str(&bundle)
+        .map_err(|e| format!("Invalid STIX JSON: {}", e))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    crate::services::stix::StixService::import(&db_guard, &bundle, "stix")
+        .map_err(|e| format!("Failed to import STIX bundle: {}", e))
+}
+
+#[tauri::command]
+pub fn get_misp_config(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<crate::services::misp::MispConfig>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    crate::services::misp::MispService::config(&db_guard)
+        .map_err(|e| format!("Failed to read MISP config: {}", e))
+}
+
+/// MISP instance settings; None disconnects. The API key is stored
+/// separately through the API key manager under "misp".
+#[tauri::command]
+pub fn set_misp_config(
+    config: Option<crate::services::misp::MispConfig>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let value = match config {
+        Some(config) => {
+            if !config.base_url.starts_with("http://") && !config.base_url.starts_with("https://") {
+                return Err(format!("Invalid MISP URL: {}", config.base_url));
+            }
+            serde_json::to_string(&config).map_err(|e| format!("Failed to serialize config: {}", e))?
+        }
+        None => String::new(),
+    };
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    db_guard
+        .set_config(crate::services::misp::CONFIG_MISP, &value)
+        .map_err(|e| format!("Failed to save MISP config: {}", e))
+}
+
+/// Push IOCs (as one MISP event) and temporal events (one MISP event each).
+#[tauri::command]
+pub async fn push_to_misp(
+    ioc_ids: Option<Vec<i64>>,
+    event_ids: Option<Vec<i64>>,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, std::sync::Arc<crate::services::api_key_manager::APIKeyManager>>,
+) -> Result<crate::services::misp::MispPushReport, String> {
+    crate::services::misp::MispService::push(
+        &db,
+        &api_key_manager,
+        &ioc_ids.unwrap_or_default(),
+        &event_ids.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| format!("Failed to push to MISP: {}", e))
+}
+
+#[tauri::command]
+pub async fn pull_from_misp(
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, std::sync::Arc<crate::services::api_key_manager::APIKeyManager>>,
+) -> Result<crate::services::misp::MispPullReport, String> {
+    crate::services::misp::MispService::pull(&db, &api_key_manager)
+        .await
+        .map_err(|e| format!("Failed to pull from MISP: {}", e))
+}
+
+#[tauri::command]
+pub fn get_misp_sync_status(
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::misp::MispSyncStatus, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    crate::services::misp::MispService::status(&db_guard)
+        .map_err(|e| format!("Failed to read MISP sync status: {}", e))
+}
+
+/// OCR the images of one article now. Also runs when the scheduled pass is disabled.
+#[tauri::command]
+pub async fn ocr_article_images(
+    article_id: i64,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::ArticleImageText>, String> {
+    let db_arc = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        std::sync::Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() }))
+    };
+    crate::services::article_ocr::ArticleOcr::process_article(&db_arc, &app, article_id)
+        .await
+        .map_err(|e| format!("Failed to OCR article images: {}", e))
+}
+
+/// Related coverage for the article reader: other articles ranked by a blend
+/// of embedding similarity, shared entities and shared events.
+#[tauri::command]
+pub async fn get_related_articles(
+    article_id: i64,
+    limit: Option<usize>,
+    api_key_manager: State<'_, std::sync::Arc<crate::services::api_key_manager::APIKeyManager>>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::services::related_articles::RelatedArticle>, String> {
+    use crate::services::embeddings::EmbeddingService;
+
+    let limit = limit.unwrap_or(10).clamp(1, 50);
+    let mut embedder = EmbeddingService::new();
+    if let Ok(Some(openai_key)) = api_key_manager.get_key_optional("openai") {
+        embedder.set_openai_key(openai_key);
+    }
+    let conn = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        embedder.set_meter(crate::services::llm_metering::LlmMeter::new(db_guard.conn.clone()));
+        embedder.set_privacy_filter(crate::services::redaction::PrivacyFilter::new(db_guard.conn.clone()));
+        db_guard.conn.clone()
+    };
+    crate::services::related_articles::RelatedArticles::find(conn, Some(&embedder), article_id, limit)
+        .await
+        .map_err(|e| format!("Failed to find related articles: {}", e))
+}
+
+#[tauri::command]
+pub fn get_article_image_text(
+    article_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::ArticleImageText>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = OSINTStore::new(db_guard.conn.clone());
+    store
+        .get_article_image_text(article_id)
+        .map_err(|e| format!("Failed to get article image text: {}", e))
+}
+
+/// `vision_model` empty means tesseract.
+#[tauri::command]
+pub fn set_article_ocr_settings(
+    enabled: Option<bool>,
+    vision_model: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    use crate::services::article_ocr::{CONFIG_ENABLED, CONFIG_VISION_MODEL};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mut updates: Vec<(&str, String)> = Vec::new();
+    if let Some(enabled) = enabled {
+        updates.push((CONFIG_ENABLED, enabled.to_string()));
+    }
+    if let Some(model) = vision_model {
+        updates.push((CONFIG_VISION_MODEL, model.trim().to_string()));
+    }
+    for (key, value) in updates {
+        db_guard
+            .set_config(key, &value)
+            .map_err(|e| format!("Failed to save {}: {}", key, e))?;
+    }
+    Ok(())
+}
+
 // Helper function to extract article content from HTML
 async fn fetch_full_article_content(url: &str) -> Option<String> {
     use scraper::{Html, Selector};
@@ -217,6 +480,17 @@ async fn fetch_full_article_content(url: &str) -> Option<String> {
 pub async fn fetch_rss_feeds(
     app: tauri::AppHandle,
     db: State<'_, Mutex<Database>>,
+) -> Result<usize, String> {
+    fetch_feeds(&app, &db, None).await
+}
+
+/// Fetch every enabled feed and rebuild events and the search index.
+/// Run as a job, progress is reported per feed and cancellation is
+/// honoured between feeds.
+pub(crate) async fn fetch_feeds(
+    app: &tauri::AppHandle,
+    db: &Mutex<Database>,
+    job: Option<&crate::services::job_manager::JobContext>,
 ) -> Result<usize, String> {
     use crate::storage::osint::OSINTStore;
     use rss::Channel;
@@ -235,6 +509,7 @@ pub async fn fetch_rss_feeds(
     if enabled_feeds.is_empty() {
         return Ok(0);
     }
+    let feed_count = enabled_feeds.len();
     
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
@@ -245,10 +520,18 @@ pub async fn fetch_rss_feeds(
     let now = chrono::Utc::now().timestamp();
     
     // Collect all items first, then save them
-    let mut items_to_save: Vec<(i64, String, String, String, i64)> = Vec::new();
+    let mut items_to_save: Vec<(i64, String, String, String, i64, Option<String>)> = Vec::new();
     let mut feeds_to_update: Vec<i64> = Vec::new();
+    // (feed, status, items, error) per feed for the data quality monitor
+    let mut fetch_log: Vec<(i64, &str, i64, Option<String>)> = Vec::new();
     
-    for feed in enabled_feeds {
+    for (index, feed) in enabled_feeds.into_iter().enumerate() {
+        if let Some(job) = job {
+            if job.is_cancelled() {
+                return Err("Feed fetch cancelled".to_string());
+            }
+            job.progress(index as f64 / feed_count as f64, format!("Fetching {}", feed.name));
+        }
         match client.get(&feed.url).send().await {
             Ok(response) => {
                 if response.status().is_success() {
@@ -318,10 +601,17 @@ pub async fn fetch_rss_feeds(
                                             .map(|dt| dt.timestamp())
                                             .unwrap_or(now);
                                         
-                                        items_to_save.push((feed.id, title, content, link, published_at));
+                                        // Podcast episodes, transcribed later if enabled
+                                        let audio_url = item
+                                            .enclosure()
+                                            .filter(|e| e.mime_type().starts_with("audio/"))
+                                            .map(|e| e.url().to_string());
+
+                                        items_to_save.push((feed.id, title, content, link, published_at, audio_url));
                                         items_saved += 1;
                                     }
                                     
+                                    fetch_log.push((feed.id, "ok", items_saved as i64, None));
                                     if items_saved > 0 {
                                         feeds_to_update.push(feed.id);
                                         total_items += items_saved;
@@ -329,31 +619,39 @@ pub async fn fetch_rss_feeds(
                                 }
                                 Err(e) => {
                                     eprintln!("Failed to parse RSS feed {}: {}", feed.name, e);
+                                    fetch_log.push((feed.id, "parse_error", 0, Some(e.to_string())));
                                 }
                             }
                         }
                         Err(e) => {
                             eprintln!("Failed to read RSS feed {}: {}", feed.name, e);
+                            fetch_log.push((feed.id, "fetch_error", 0, Some(e.to_string())));
                         }
                     }
                 } else {
                     eprintln!("Failed to fetch RSS feed {}: HTTP {}", feed.name, response.status());
+                    fetch_log.push((feed.id, "http_error", 0, Some(format!("HTTP {}", response.status()))));
                 }
             }
             Err(e) => {
                 eprintln!("Failed to fetch RSS feed {}: {}", feed.name, e);
+                fetch_log.push((feed.id, "fetch_error", 0, Some(e.to_string())));
             }
         }
     }
     
     // Now save all items to database (with lock)
-    {
+    let flagged_feeds = {
         let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
         let store = OSINTStore::new(db_guard.conn.clone());
         let temporal = TemporalStore::new(db_guard.conn.clone());
+        let transcripts = crate::storage::TranscriptStore::new(db_guard.conn.clone());
         
-        for (feed_id, title, description, link, published_at) in items_to_save {
+        for (feed_id, title, description, link, published_at, audio_url) in items_to_save {
             if let Ok(article_id) = store.save_rss_item(feed_id, &title, &description, &link, published_at) {
+                if let Some(audio_url) = audio_url {
+                    let _ = transcripts.queue_episode(article_id, &audio_url);
+                }
                 // Lightweight entity extraction on ingest
                 let text = format!("{} {}", title, description);
                 let entities = extract_entities_enhanced(&text);
@@ -366,27 +664,82 @@ pub async fn fetch_rss_feeds(
         for feed_id in feeds_to_update {
             let _ = store.update_feed_last_fetch(feed_id);
         }
+        for (feed_id, status, items, error) in &fetch_log {
+            if let Err(e) = store.record_feed_fetch(*feed_id, status, *items, error.as_deref()) {
+                eprintln!("Failed to record fetch of feed {}: {}", feed_id, e);
+            }
+        }
 
         // Rebuild temporal events + search index (MVP)
         let _ = temporal.rebuild_events_mvp(30);
+        let volume_settings = crate::services::news_volume::VolumeAnomalySettings::load(&db_guard);
+        if let Err(e) = crate::services::news_volume::NewsVolumeDetector::detect(&store, &temporal, &volume_settings, 2) {
+            eprintln!("News volume anomaly detection failed: {}", e);
+        }
+        let divergence_settings = crate::services::sentiment_divergence::DivergenceSettings::load(&db_guard);
+        if let Err(e) = crate::services::sentiment_divergence::SentimentDivergenceDetector::detect(&store, &temporal, &divergence_settings) {
+            eprintln!("Sentiment divergence detection failed: {}", e);
+        }
+        if let Err(e) = crate::services::story_lifecycle::StoryLifecycle::update(&temporal, 30) {
+            eprintln!("Story lifecycle update failed: {}", e);
+        }
+        if let Err(e) = crate::services::corroboration::CorroborationScorer::update(&temporal, 30) {
+            eprintln!("Corroboration scoring failed: {}", e);
+        }
+        let summary_settings = crate::services::event_summaries::SummaryRefreshSettings::load(&db_guard);
+        if let Err(e) = crate::services::event_summaries::EventSummaries::update(&temporal, &summary_settings, 30) {
+            eprintln!("Event summary refresh failed: {}", e);
+        }
         let _ = temporal.rebuild_search_index(Some(chrono::Utc::now().timestamp() - 30 * 24 * 3600));
         if let Ok(created_alerts) = temporal.evaluate_alert_rules_mvp(30, 500) {
+            let market_data_store = crate::storage::market_data::MarketDataStore::new(db_guard.conn.clone());
+            if let Err(e) = crate::services::chart_annotations::ChartAnnotationService::record_alerts(&temporal, &market_data_store, &created_alerts) {
+                eprintln!("Failed to record chart markers for alerts: {}", e);
+            }
             for alert in created_alerts {
-                let _ = app.emit(
-                    "ws-message",
-                    serde_json::json!({
-                        "type": "temporal-alert",
-                        "data": alert,
-                        "timestamp": chrono::Utc::now().timestamp_millis()
-                    }),
-                );
+                let _ = crate::services::window_router::emit(app, serde_json::json!({
+                    "type": "temporal-alert",
+                    "data": &alert,
+                    "timestamp": chrono::Utc::now().timestamp_millis()
+                }));
+                crate::ws::publish_alert(app, "temporal", serde_json::json!(alert));
             }
         }
-    }
+
+        let quality_settings = crate::services::data_quality::DataQualitySettings::load(&db_guard);
+        if quality_settings.alerts_enabled {
+            crate::services::data_quality::DataQualityMonitor::flag_changes(&store, &quality_settings).unwrap_or_else(|e| {
+                eprintln!("Data quality check failed: {}", e);
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        }
+    };
+    crate::services::data_quality::DataQualityMonitor::notify(app, &flagged_feeds).await;
     
     Ok(total_items)
 }
 
+/// Per-feed fetch failures, empty and duplicate items and staleness over
+/// the configured window (or `window_days`), unhealthy feeds first.
+#[tauri::command]
+pub fn get_data_quality_report(
+    window_days: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::data_quality::DataQualityReport, String> {
+    use crate::services::data_quality::{DataQualityMonitor, DataQualitySettings};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mut settings = DataQualitySettings::load(&db_guard);
+    if let Some(days) = window_days {
+        settings.window_days = days.clamp(1, 365);
+    }
+    let store = OSINTStore::new(db_guard.conn.clone());
+    DataQualityMonitor::report(&store, &settings)
+        .map_err(|e| format!("Failed to build data quality report: {}", e))
+}
+
 #[tauri::command]
 pub fn get_rss_item(
     id: i64,
@@ -432,6 +785,8 @@ pub fn toggle_article_saved(
         .map_err(|e| format!("Failed to toggle saved: {}", e))
 }
 
+/// Folders remain for older clients; filing an article also tags it with
+/// the folder's name.
 #[tauri::command]
 pub fn set_article_folder(
     id: i64,
@@ -441,7 +796,17 @@ pub fn set_article_folder(
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
     let store = OSINTStore::new(db_guard.conn.clone());
     store.set_folder(id, folder_id)
-        .map_err(|e| format!("Failed to set folder: {}", e))
+        .map_err(|e| format!("Failed to set folder: {}", e))?;
+    if let Some(fid) = folder_id {
+        let folders = store.list_folders()
+            .map_err(|e| format!("Failed to list folders: {}", e))?;
+        if let Some(folder) = folders.iter().find(|f| f.id == fid) {
+            crate::storage::TagStore::new(db_guard.conn.clone())
+                .attach("article", id, &folder.name)
+                .map_err(|e| format!("Failed to tag article: {}", e))?;
+        }
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -477,18 +842,58 @@ pub fn delete_article_folder(
         .map_err(|e| format!("Failed to delete folder: {}", e))
 }
 
+/// How many read articles a cleanup would remove, defaulting to the
+/// configured age.
+#[tauri::command]
+pub fn preview_article_cleanup(
+    older_than_days: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::storage::osint::ArticleCleanupPreview, String> {
+    use crate::services::article_cleanup::ArticleCleanup;
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let days = older_than_days
+        .or_else(|| ArticleCleanup::configured_days(&db_guard))
+        .ok_or_else(|| "No article age given or configured".to_string())?;
+    let store = OSINTStore::new(db_guard.conn.clone());
+    ArticleCleanup::preview(&store, days)
+        .map_err(|e| format!("Failed to preview article cleanup: {}", e))
+}
+
+/// Archive or delete (`mode`) read, non-favorite articles older than
+/// `older_than_days` that no event cites, defaulting to the configured
+/// cleanup. Returns the number removed.
+#[tauri::command]
+pub fn cleanup_read_articles(
+    older_than_days: Option<i64>,
+    mode: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<usize, String> {
+    use crate::services::article_cleanup::ArticleCleanup;
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let days = older_than_days
+        .or_else(|| ArticleCleanup::configured_days(&db_guard))
+        .ok_or_else(|| "No article age given or configured".to_string())?;
+    let mode = mode.unwrap_or_else(|| ArticleCleanup::configured_mode(&db_guard));
+    let store = OSINTStore::new(db_guard.conn.clone());
+    ArticleCleanup::apply(&store, days, &mode)
+        .map_err(|e| format!("Failed to clean up articles: {}", e))
+}
+
 #[tauri::command]
 pub fn get_filtered_articles(
     favorite: Option<bool>,
     saved: Option<bool>,
     read: Option<bool>,
     folder_id: Option<i64>,
+    tag: Option<String>,
     limit: i32,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Vec<crate::storage::osint::RSSItem>, String> {
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
     let store = OSINTStore::new(db_guard.conn.clone());
-    store.get_items_by_filter(favorite, saved, read, folder_id, limit)
+    store.get_items_by_filter(favorite, saved, read, folder_id, tag.as_deref(), limit)
         .map_err(|e| format!("Failed to get filtered articles: {}", e))
 }
 
@@ -539,6 +944,34 @@ pub async fn extract_entities_from_article(
     }
 }
 
+/// Re-run entity extraction over articles published in [from_ts, to_ts)
+/// (default: the last 90 days) as a cancellable background job,
+/// `batch_size` articles at a time.
+#[tauri::command]
+pub fn reextract_entities(
+    from_ts: Option<i64>,
+    to_ts: Option<i64>,
+    batch_size: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+    jobs: State<'_, crate::services::job_manager::JobManager>,
+) -> Result<crate::storage::Job, String> {
+    use crate::services::entity_reextraction::{EntityReextraction, DEFAULT_BATCH_SIZE};
+
+    let conn = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        db_guard.conn.clone()
+    };
+    let to_ts = to_ts.unwrap_or_else(|| chrono::Utc::now().timestamp() + 1);
+    let from_ts = from_ts.unwrap_or(to_ts - 90 * 24 * 3600);
+    let batch_size = batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
+    let params = serde_json::json!({ "from_ts": from_ts, "to_ts": to_ts, "batch_size": batch_size });
+    jobs.spawn("reextract_entities", crate::services::job_manager::JobClass::Database, params, move |ctx| async move {
+        let summary = EntityReextraction::run(conn, from_ts, to_ts, batch_size, Some(&ctx)).await?;
+        Ok(serde_json::to_value(summary)?)
+    })
+    .map_err(|e| format!("Failed to start re-extraction job: {}", e))
+}
+
 #[tauri::command]
 pub async fn fetch_full_article(
     article_id: i64,
@@ -573,7 +1006,7 @@ pub async fn fetch_full_article(
     }
 }
 
-fn extract_entities_enhanced(text: &str) -> Vec<(String, String, f64, String)> {
+pub(crate) fn extract_entities_enhanced(text: &str) -> Vec<(String, String, f64, String)> {
     let mut entities = Vec::new();
     let lower = text.to_lowercase();
     let words: Vec<&str> = text.split_whitespace().collect();
@@ -0,0 +1,40 @@
+use crate::services::command_palette::{CommandPalette, PaletteItem};
+use crate::storage::{Database, PaletteStore};
+use std::sync::Mutex;
+use tauri::State;
+
+/// Ranked palette entries for `query`; an empty query lists recent items first.
+#[tauri::command]
+pub fn search_palette(
+    query: String,
+    limit: Option<usize>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<PaletteItem>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    CommandPalette::search(&db_guard, &query, limit.unwrap_or(20).clamp(1, 200))
+        .map_err(|e| format!("Failed to search palette: {}", e))
+}
+
+/// Remember that an event, entity, project etc. was opened so the palette
+/// offers it again.
+#[tauri::command]
+pub fn record_palette_visit(
+    kind: String,
+    target_id: String,
+    title: String,
+    subtitle: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    PaletteStore::new(db_guard.conn.clone())
+        .record_visit(&kind, &target_id, &title, subtitle.as_deref())
+        .map_err(|e| format!("Failed to record visit: {}", e))
+}
+
+#[tauri::command]
+pub fn clear_palette_recents(db: State<'_, Mutex<Database>>) -> Result<usize, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    PaletteStore::new(db_guard.conn.clone())
+        .clear()
+        .map_err(|e| format!("Failed to clear recents: {}", e))
+}
@@ -1,12 +1,20 @@
-use crate::storage::portfolio::{PortfolioStore, Portfolio, Holding, Transaction};
+use crate::storage::portfolio::{PortfolioStore, Portfolio, Holding, Transaction, TargetAllocation};
 use crate::storage::market_data::MarketDataStore;
 use crate::storage::portfolio_performance::PortfolioPerformanceStore;
 use crate::services::portfolio_analyzer::PortfolioAnalyzer;
+use crate::services::rebalancer::{DriftReport, RebalanceAdvisor, RebalanceOptions, RebalancePlan};
+use crate::services::tax_lots::{LotMethod, TaxLotService, TaxReport, TaxReportFormat};
 use crate::storage::Database;
 use std::collections::HashMap;
-use std::sync::Mutex;
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
 use tauri::State;
 
+fn db_arc(db: &State<'_, Mutex<Database>>) -> Result<Arc<Mutex<Database>>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() })))
+}
+
 #[tauri::command]
 pub fn create_portfolio(
     name: String,
@@ -49,15 +57,163 @@ pub fn add_holding(
     quantity: f64,
     purchase_price: f64,
     purchase_date: i64,
+    currency: Option<String>,
     db: State<'_, Mutex<Database>>,
 ) -> Result<i64, String> {
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
     let store = PortfolioStore::new(db_guard.conn.clone());
+    let currency = currency.filter(|c| !c.trim().is_empty()).unwrap_or_else(|| "USD".to_string());
     store
-        .add_holding(portfolio_id, &ticker, quantity, purchase_price, purchase_date)
+        .add_holding(portfolio_id, &ticker, quantity, purchase_price, purchase_date, currency.trim())
         .map_err(|e| format!("Failed to add holding: {}", e))
 }
 
+#[tauri::command]
+pub fn set_portfolio_base_currency(
+    portfolio_id: i64,
+    currency: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let currency = currency.trim();
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("Invalid currency code: {}", currency));
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    PortfolioStore::new(db_guard.conn.clone())
+        .set_base_currency(portfolio_id, currency)
+        .map_err(|e| format!("Failed to set base currency: {}", e))
+}
+
+#[tauri::command]
+pub fn set_holding_currency(
+    holding_id: i64,
+    currency: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let currency = currency.trim();
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("Invalid currency code: {}", currency));
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    PortfolioStore::new(db_guard.conn.clone())
+        .set_holding_currency(holding_id, currency)
+        .map_err(|e| format!("Failed to set holding currency: {}", e))
+}
+
+/// Realized gains for sales in `tax_year`, matched by `method` (fifo, lifo or
+/// hifo; fifo by default) and converted to `currency` at each trade date's rate.
+#[tauri::command]
+pub async fn get_realized_gains(
+    portfolio_id: i64,
+    tax_year: i32,
+    method: Option<String>,
+    currency: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<TaxReport, String> {
+    let method = LotMethod::parse(method.as_deref().unwrap_or("fifo")).map_err(|e| e.to_string())?;
+    let db_arc = db_arc(&db)?;
+    TaxLotService::realized_gains(&db_arc, portfolio_id, tax_year, method, currency.as_deref())
+        .await
+        .map_err(|e| format!("Failed to compute realized gains: {}", e))
+}
+
+/// Realized gains as CSV laid out for `format` ("form_8949" or "german_kap").
+#[tauri::command]
+pub async fn export_tax_report(
+    portfolio_id: i64,
+    tax_year: i32,
+    format: String,
+    method: Option<String>,
+    currency: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String, String> {
+    let format = TaxReportFormat::parse(&format).map_err(|e| e.to_string())?;
+    let method = LotMethod::parse(method.as_deref().unwrap_or("fifo")).map_err(|e| e.to_string())?;
+    let db_arc = db_arc(&db)?;
+    let report = TaxLotService::realized_gains(&db_arc, portfolio_id, tax_year, method, currency.as_deref())
+        .await
+        .map_err(|e| format!("Failed to compute realized gains: {}", e))?;
+    Ok(TaxLotService::to_csv(&report, format))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TargetAllocationInput {
+    pub target_type: String, // ticker|sector
+    pub target: String,
+    pub weight_percent: f64,
+}
+
+/// Replace the portfolio's target allocations. All targets must be of the same
+/// type and sum to at most 100%; any remainder is meant to stay in cash.
+#[tauri::command]
+pub fn set_target_allocations(
+    portfolio_id: i64,
+    targets: Vec<TargetAllocationInput>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    if let Some(first) = targets.first() {
+        if first.target_type != "ticker" && first.target_type != "sector" {
+            return Err(format!("Invalid target type: {}", first.target_type));
+        }
+        if targets.iter().any(|t| t.target_type != first.target_type) {
+            return Err("Targets must all be tickers or all be sectors".to_string());
+        }
+    }
+    if targets.iter().any(|t| t.target.trim().is_empty() || t.weight_percent < 0.0) {
+        return Err("Targets need a name and a non-negative weight".to_string());
+    }
+    let total: f64 = targets.iter().map(|t| t.weight_percent).sum();
+    if total > 100.0 + 1e-6 {
+        return Err(format!("Target weights sum to {:.2}%, more than 100%", total));
+    }
+
+    let targets: Vec<(String, String, f64)> = targets
+        .into_iter()
+        .map(|t| (t.target_type, t.target.trim().to_string(), t.weight_percent))
+        .collect();
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    PortfolioStore::new(db_guard.conn.clone())
+        .set_target_allocations(portfolio_id, &targets)
+        .map_err(|e| format!("Failed to set target allocations: {}", e))
+}
+
+#[tauri::command]
+pub fn get_target_allocations(
+    portfolio_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<TargetAllocation>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    PortfolioStore::new(db_guard.conn.clone())
+        .list_target_allocations(portfolio_id)
+        .map_err(|e| format!("Failed to get target allocations: {}", e))
+}
+
+#[tauri::command]
+pub fn get_allocation_drift(
+    portfolio_id: i64,
+    cash: Option<f64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<DriftReport, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let portfolio_store = PortfolioStore::new(db_guard.conn.clone());
+    let market_data_store = MarketDataStore::new(db_guard.conn.clone());
+    RebalanceAdvisor::drift(&portfolio_store, &market_data_store, portfolio_id, cash.unwrap_or(0.0))
+        .map_err(|e| format!("Failed to compute allocation drift: {}", e))
+}
+
+#[tauri::command]
+pub fn suggest_rebalance_trades(
+    portfolio_id: i64,
+    options: Option<RebalanceOptions>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<RebalancePlan, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let portfolio_store = PortfolioStore::new(db_guard.conn.clone());
+    let market_data_store = MarketDataStore::new(db_guard.conn.clone());
+    RebalanceAdvisor::suggest_trades(&portfolio_store, &market_data_store, portfolio_id, &options.unwrap_or_default())
+        .map_err(|e| format!("Failed to suggest rebalance trades: {}", e))
+}
+
 #[tauri::command]
 pub fn list_holdings(
     portfolio_id: i64,
@@ -151,6 +307,40 @@ pub fn get_portfolio_performance_metrics(
     .map_err(|e| format!("Failed to calculate performance metrics: {}", e))
 }
 
+/// Historical VaR/CVaR and holdings correlation, using the configured
+/// confidence levels and lookback window.
+#[tauri::command]
+pub fn get_portfolio_risk(
+    portfolio_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::portfolio_analyzer::PortfolioRisk, String> {
+    use crate::services::portfolio_analyzer::{
+        CONFIG_RISK_CONFIDENCE_LEVELS, CONFIG_RISK_LOOKBACK_DAYS, DEFAULT_RISK_CONFIDENCE_LEVELS,
+        DEFAULT_RISK_LOOKBACK_DAYS,
+    };
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let confidence_levels: Vec<f64> = db_guard
+        .get_config(CONFIG_RISK_CONFIDENCE_LEVELS)
+        .ok()
+        .flatten()
+        .map(|v| v.split(',').filter_map(|c| c.trim().parse().ok()).collect::<Vec<f64>>())
+        .filter(|levels| !levels.is_empty())
+        .unwrap_or_else(|| DEFAULT_RISK_CONFIDENCE_LEVELS.to_vec());
+    let lookback_days = db_guard
+        .get_config(CONFIG_RISK_LOOKBACK_DAYS)
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse().ok())
+        .filter(|d: &i64| *d > 1)
+        .unwrap_or(DEFAULT_RISK_LOOKBACK_DAYS);
+
+    let portfolio_store = PortfolioStore::new(db_guard.conn.clone());
+    let market_data_store = MarketDataStore::new(db_guard.conn.clone());
+    PortfolioAnalyzer::calculate_risk(&portfolio_store, &market_data_store, portfolio_id, &confidence_levels, lookback_days)
+        .map_err(|e| format!("Failed to calculate portfolio risk: {}", e))
+}
+
 #[tauri::command]
 pub fn get_portfolio_impact(
     portfolio_id: i64,
@@ -218,3 +408,36 @@ pub fn delete_portfolio(
         .delete_portfolio(id)
         .map_err(|e| format!("Failed to delete portfolio: {}", e))
 }
+
+/// Answer a question like "why is my portfolio down today?" from today's price
+/// moves, news and events for held tickers. Uses `model`, or the configured
+/// analyst model when omitted.
+#[tauri::command]
+pub async fn ask_analyst(
+    question: String,
+    portfolio_id: Option<i64>,
+    model: Option<String>,
+    db: State<'_, Mutex<Database>>,
+    ollama: State<'_, crate::commands::ollama::OllamaState>,
+    api_key_manager: State<'_, std::sync::Arc<crate::services::api_key_manager::APIKeyManager>>,
+) -> Result<crate::services::portfolio_qa::AnalystAnswer, String> {
+    if question.trim().is_empty() {
+        return Err("Question must not be empty".to_string());
+    }
+    let model = match model {
+        Some(m) if !m.trim().is_empty() => m,
+        _ => {
+            let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+            db_guard
+                .get_config(crate::services::event_analyst::CONFIG_MODEL)
+                .map_err(|e| format!("Failed to read analyst model: {}", e))?
+                .filter(|m| !m.is_empty())
+                .ok_or_else(|| "No model given and no analyst model configured".to_string())?
+        }
+    };
+
+    let provider = ollama.read().await;
+    crate::services::portfolio_qa::PortfolioQAService::ask(&db, &provider, &api_key_manager, &model, question.trim(), portfolio_id)
+        .await
+        .map_err(|e| format!("Failed to answer question: {}", e))
+}
@@ -8,6 +8,7 @@ pub fn create_price_alert(
     ticker: String,
     condition: String,
     target_price: f64,
+    regular_hours_only: Option<bool>,
     db: State<'_, Mutex<Database>>,
 ) -> Result<i64, String> {
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
@@ -19,7 +20,7 @@ pub fn create_price_alert(
         return Err(format!("Invalid condition. Must be one of: {}", valid_conditions.join(", ")));
     }
     
-    store.create_alert(&ticker.to_uppercase(), &condition, target_price)
+    store.create_alert(&ticker.to_uppercase(), &condition, target_price, regular_hours_only.unwrap_or(false))
         .map_err(|e| format!("Failed to create alert: {}", e))
 }
 
@@ -54,6 +55,7 @@ pub fn update_price_alert(
     condition: Option<String>,
     target_price: Option<f64>,
     enabled: Option<bool>,
+    regular_hours_only: Option<bool>,
     db: State<'_, Mutex<Database>>,
 ) -> Result<(), String> {
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
@@ -67,7 +69,7 @@ pub fn update_price_alert(
         }
     }
     
-    store.update_alert(id, condition.as_deref(), target_price, enabled)
+    store.update_alert(id, condition.as_deref(), target_price, enabled, regular_hours_only)
         .map_err(|e| format!("Failed to update alert: {}", e))
 }
 
@@ -1,4 +1,7 @@
 use crate::providers::ProcessProvider;
+use crate::services::process_guardian::ProcessGuardian;
+use crate::storage::process_policies::{NewProcessPolicy, ProcessPolicy, ProcessPolicyAction, ProcessPolicyStore};
+use crate::storage::Database;
 use std::sync::Mutex;
 use tauri::State;
 
@@ -21,3 +24,52 @@ pub fn kill_process(pid: u32, provider: State<'_, Mutex<ProcessProvider>>) -> Re
     provider_guard.kill_process(pid)
 }
 
+
+#[tauri::command]
+pub fn list_process_policies(db: State<'_, Mutex<Database>>) -> Result<Vec<ProcessPolicy>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ProcessPolicyStore::new(db_guard.conn.clone())
+        .list_policies(false)
+        .map_err(|e| format!("Failed to list process policies: {}", e))
+}
+
+/// Create a resource limit policy. New policies default to dry-run, which
+/// records and alerts on what would be done without acting.
+#[tauri::command]
+pub fn create_process_policy(policy: NewProcessPolicy, db: State<'_, Mutex<Database>>) -> Result<i64, String> {
+    ProcessGuardian::validate(&policy).map_err(|e| format!("Invalid process policy: {}", e))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ProcessPolicyStore::new(db_guard.conn.clone())
+        .create_policy(&policy)
+        .map_err(|e| format!("Failed to create process policy: {}", e))
+}
+
+#[tauri::command]
+pub fn update_process_policy(id: i64, policy: NewProcessPolicy, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    ProcessGuardian::validate(&policy).map_err(|e| format!("Invalid process policy: {}", e))?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ProcessPolicyStore::new(db_guard.conn.clone())
+        .update_policy(id, &policy)
+        .map_err(|e| format!("Failed to update process policy: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_process_policy(id: i64, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ProcessPolicyStore::new(db_guard.conn.clone())
+        .delete_policy(id)
+        .map_err(|e| format!("Failed to delete process policy: {}", e))
+}
+
+/// What policies did (or in dry-run would have done), newest first.
+#[tauri::command]
+pub fn list_process_policy_actions(
+    policy_id: Option<i64>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<ProcessPolicyAction>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ProcessPolicyStore::new(db_guard.conn.clone())
+        .list_actions(policy_id, limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to list process policy actions: {}", e))
+}
@@ -0,0 +1,104 @@
+use crate::services::profile_manager::{ProfileExport, ProfileManager, ProfileSwitch};
+use crate::storage::profiles::{Profile, ProfileStore};
+use crate::storage::Database;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+fn db_arc(db: &State<'_, Mutex<Database>>) -> Result<Arc<Mutex<Database>>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() })))
+}
+
+#[tauri::command]
+pub fn list_profiles(db: State<'_, Mutex<Database>>) -> Result<Vec<Profile>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ProfileStore::new(db_guard.conn.clone())
+        .list_profiles()
+        .map_err(|e| format!("Failed to list profiles: {}", e))
+}
+
+/// Create a profile. With `share_articles` false it only sees feeds added while it is active.
+#[tauri::command]
+pub fn create_profile(
+    name: String,
+    share_articles: Option<bool>,
+    pin: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    if name.trim().is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    let id = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        ProfileStore::new(db_guard.conn.clone())
+            .create_profile(name.trim(), share_articles.unwrap_or(true))
+            .map_err(|e| format!("Failed to create profile: {}", e))?
+    };
+    if let Some(pin) = pin.filter(|p| !p.is_empty()) {
+        ProfileManager::set_pin(&db_arc(&db)?, id, &pin, None)
+            .map_err(|e| format!("Failed to set profile PIN: {}", e))?;
+    }
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn update_profile(
+    id: i64,
+    name: Option<String>,
+    share_articles: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ProfileStore::new(db_guard.conn.clone())
+        .update_profile(id, name.as_deref(), share_articles)
+        .map_err(|e| format!("Failed to update profile: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_profile(id: i64, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ProfileStore::new(db_guard.conn.clone())
+        .delete_profile(id)
+        .map_err(|e| format!("Failed to delete profile: {}", e))
+}
+
+#[tauri::command]
+pub fn set_profile_pin(
+    id: i64,
+    pin: String,
+    current_pin: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    if pin.is_empty() {
+        return Err("PIN cannot be empty".to_string());
+    }
+    ProfileManager::set_pin(&db_arc(&db)?, id, &pin, current_pin.as_deref())
+        .map_err(|e| format!("Failed to set profile PIN: {}", e))
+}
+
+/// Switch the active profile, verifying its PIN if it has one.
+#[tauri::command]
+pub fn switch_profile(
+    id: i64,
+    pin: Option<String>,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<ProfileSwitch, String> {
+    let switched = ProfileManager::switch(&db_arc(&db)?, id, pin.as_deref())
+        .map_err(|e| format!("Failed to switch profile: {}", e))?;
+
+    let _ = crate::services::window_router::emit(&app, serde_json::json!({
+        "type": "profile-switched",
+        "data": switched.profile,
+        "timestamp": chrono::Utc::now().timestamp_millis(),
+    }));
+
+    Ok(switched)
+}
+
+/// Export a profile's watchlists, alert rules and chat history as JSON.
+#[tauri::command]
+pub fn export_profile(id: i64, db: State<'_, Mutex<Database>>) -> Result<ProfileExport, String> {
+    ProfileManager::export(&db_arc(&db)?, id)
+        .map_err(|e| format!("Failed to export profile: {}", e))
+}
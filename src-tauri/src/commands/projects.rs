@@ -1,4 +1,6 @@
-use crate::storage::projects::ProjectStore;
+use crate::providers::git::GitProvider;
+use crate::storage::projects::{ProjectRepoLink, ProjectStore, ProjectTask, TaskBoardColumn};
+use crate::storage::tags::TagStore;
 use crate::storage::Database;
 use std::sync::Mutex;
 use tauri::State;
@@ -32,11 +34,12 @@ pub fn update_project(
 #[tauri::command]
 pub fn list_projects(
     project_type: Option<String>,
+    tag: Option<String>,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Vec<crate::storage::projects::Project>, String> {
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
     let store = ProjectStore::new(db_guard.conn.clone());
-    store.list_projects(project_type.as_deref())
+    store.list_projects(project_type.as_deref(), tag.as_deref())
         .map_err(|e| format!("Failed to list projects: {}", e))
 }
 
@@ -59,6 +62,220 @@ pub fn delete_project(
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
     let store = ProjectStore::new(db_guard.conn.clone());
     store.delete_project(id)
-        .map_err(|e| format!("Failed to delete project: {}", e))
+        .map_err(|e| format!("Failed to delete project: {}", e))?;
+    TagStore::new(db_guard.conn.clone())
+        .detach_all("project", id)
+        .map_err(|e| format!("Failed to remove project tags: {}", e))
 }
 
+
+#[tauri::command]
+pub async fn link_project_repo(
+    project_id: i64,
+    repo_path: String,
+    unpushed_alert_days: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::providers::git::GitRepoStatus, String> {
+    let git = GitProvider::new();
+    let status = git.get_status(&repo_path).await
+        .map_err(|e| format!("Failed to read repository: {}", e))?;
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    if store.get_project(project_id).map_err(|e| format!("Failed to get project: {}", e))?.is_none() {
+        return Err(format!("Project {} not found", project_id));
+    }
+    store.link_repo(project_id, &repo_path, unpushed_alert_days.unwrap_or(3).max(1))
+        .map_err(|e| format!("Failed to link repository: {}", e))?;
+
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn unlink_project_repo(
+    project_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.unlink_repo(project_id)
+        .map_err(|e| format!("Failed to unlink repository: {}", e))
+}
+
+#[tauri::command]
+pub fn list_project_repos(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<ProjectRepoLink>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.list_repo_links()
+        .map_err(|e| format!("Failed to list project repositories: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_project_git_status(
+    project_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::providers::git::GitRepoStatus, String> {
+    let link = linked_repo(project_id, &db)?;
+    GitProvider::new().get_status(&link.repo_path).await
+        .map_err(|e| format!("Failed to get git status: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_project_recent_commits(
+    project_id: i64,
+    limit: Option<usize>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::providers::git::GitCommit>, String> {
+    let link = linked_repo(project_id, &db)?;
+    GitProvider::new().get_recent_commits(&link.repo_path, limit.unwrap_or(20).min(200)).await
+        .map_err(|e| format!("Failed to get recent commits: {}", e))
+}
+
+fn linked_repo(project_id: i64, db: &State<'_, Mutex<Database>>) -> Result<ProjectRepoLink, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.get_repo_link(project_id)
+        .map_err(|e| format!("Failed to get repository link: {}", e))?
+        .ok_or_else(|| format!("Project {} has no linked repository", project_id))
+}
+
+#[tauri::command]
+pub fn create_project_task(
+    project_id: i64,
+    title: String,
+    description: Option<String>,
+    status: Option<String>,
+    priority: Option<String>,
+    due_at: Option<i64>,
+    tags: Option<Vec<String>>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.create_task(
+        project_id,
+        &title,
+        description.as_deref(),
+        status.as_deref().unwrap_or("todo"),
+        priority.as_deref().unwrap_or("medium"),
+        due_at,
+        &tags.unwrap_or_default(),
+    )
+    .map_err(|e| format!("Failed to create task: {}", e))
+}
+
+#[tauri::command]
+pub fn update_project_task(
+    id: i64,
+    title: Option<String>,
+    description: Option<String>,
+    priority: Option<String>,
+    due_at: Option<i64>,
+    clear_due_at: Option<bool>,
+    tags: Option<Vec<String>>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let due_at = if clear_due_at.unwrap_or(false) {
+        Some(None)
+    } else {
+        due_at.map(Some)
+    };
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.update_task(
+        id,
+        title.as_deref(),
+        description.as_deref(),
+        priority.as_deref(),
+        due_at,
+        tags.as_deref(),
+    )
+    .map_err(|e| format!("Failed to update task: {}", e))
+}
+
+#[tauri::command]
+pub fn move_project_task(
+    id: i64,
+    status: String,
+    position: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.move_task(id, &status, position)
+        .map_err(|e| format!("Failed to move task: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_project_task(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.delete_task(id)
+        .map_err(|e| format!("Failed to delete task: {}", e))
+}
+
+#[tauri::command]
+pub fn get_project_task(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<ProjectTask>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.get_task(id)
+        .map_err(|e| format!("Failed to get task: {}", e))
+}
+
+#[tauri::command]
+pub fn list_project_tasks(
+    project_id: i64,
+    status: Option<String>,
+    tag: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<ProjectTask>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.list_tasks(project_id, status.as_deref(), tag.as_deref())
+        .map_err(|e| format!("Failed to list tasks: {}", e))
+}
+
+#[tauri::command]
+pub fn get_project_board(
+    project_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<TaskBoardColumn>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.get_board(project_id)
+        .map_err(|e| format!("Failed to get project board: {}", e))
+}
+
+#[tauri::command]
+pub fn link_project_task(
+    task_id: i64,
+    target_type: String,
+    target_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.link_task(task_id, &target_type, target_id)
+        .map_err(|e| format!("Failed to link task: {}", e))
+}
+
+#[tauri::command]
+pub fn unlink_project_task(
+    task_id: i64,
+    target_type: String,
+    target_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = ProjectStore::new(db_guard.conn.clone());
+    store.unlink_task(task_id, &target_type, target_id)
+        .map_err(|e| format!("Failed to unlink task: {}", e))
+}
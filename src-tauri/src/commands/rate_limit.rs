@@ -37,7 +37,7 @@ pub fn get_rate_limit_bucket(
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
     let store = RateLimitStore::new(db_guard.conn.clone())
         .map_err(|e| format!("Failed to initialize store: {}", e))?;
-    store.get_bucket(&name)
+    store.peek_bucket(&name)
         .map_err(|e| format!("Failed to get bucket: {}", e))
 }
 
@@ -0,0 +1,114 @@
+use crate::services::report_builder::{ReportBuilder, ReportResult, ReportTable};
+use crate::storage::saved_reports::{NewSavedReport, ReportSpec, SavedReport, SavedReportStore};
+use crate::storage::Database;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Tables and columns a report spec may reference.
+#[tauri::command]
+pub fn list_report_tables(db: State<'_, Mutex<Database>>) -> Result<Vec<ReportTable>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db_guard.conn.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ReportBuilder::tables(&conn).map_err(|e| format!("Failed to list report tables: {}", e))
+}
+
+#[tauri::command]
+pub fn list_saved_reports(db: State<'_, Mutex<Database>>) -> Result<Vec<SavedReport>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    SavedReportStore::new(db_guard.conn.clone())
+        .list_reports()
+        .map_err(|e| format!("Failed to list reports: {}", e))
+}
+
+/// Create a report, or update it when `id` is given.
+#[tauri::command]
+pub fn save_report(
+    id: Option<i64>,
+    report: NewSavedReport,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    {
+        let conn = db_guard.conn.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        ReportBuilder::validate(&conn, &report).map_err(|e| format!("Invalid report: {}", e))?;
+    }
+    SavedReportStore::new(db_guard.conn.clone())
+        .save_report(id, &report)
+        .map_err(|e| format!("Failed to save report: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_saved_report(id: i64, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    SavedReportStore::new(db_guard.conn.clone())
+        .delete_report(id)
+        .map_err(|e| format!("Failed to delete report: {}", e))
+}
+
+/// Run a saved report by `id`, or an unsaved `spec` for previewing.
+#[tauri::command]
+pub fn run_report(
+    id: Option<i64>,
+    spec: Option<ReportSpec>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<ReportResult, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let spec = resolve_spec(&db_guard, id, spec)?;
+    let conn = db_guard.conn.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ReportBuilder::run(&conn, &spec).map_err(|e| format!("Failed to run report: {}", e))
+}
+
+/// Run a report and return it as CSV or JSON text for saving client-side.
+#[tauri::command]
+pub fn export_report(
+    id: Option<i64>,
+    spec: Option<ReportSpec>,
+    format: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let spec = resolve_spec(&db_guard, id, spec)?;
+    let result = {
+        let conn = db_guard.conn.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        ReportBuilder::run(&conn, &spec).map_err(|e| format!("Failed to run report: {}", e))?
+    };
+    ReportBuilder::export(&result, &format).map_err(|e| format!("Failed to export report: {}", e))
+}
+
+/// Write a saved report to its export folder now. Returns the file path.
+#[tauri::command]
+pub fn run_saved_report_now(id: i64, db: State<'_, Mutex<Database>>) -> Result<String, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = SavedReportStore::new(db_guard.conn.clone());
+    let report = store
+        .get_report(id)
+        .map_err(|e| format!("Failed to load report: {}", e))?
+        .ok_or_else(|| format!("Report {} not found", id))?;
+
+    let now = chrono::Utc::now().timestamp();
+    match ReportBuilder::run_to_file(&db_guard, &report) {
+        Ok(path) => {
+            let path = path.display().to_string();
+            store
+                .record_run(id, now, Some(&path), None)
+                .map_err(|e| format!("Failed to record report run: {}", e))?;
+            Ok(path)
+        }
+        Err(e) => {
+            let _ = store.record_run(id, now, None, Some(&e.to_string()));
+            Err(format!("Failed to export report: {}", e))
+        }
+    }
+}
+
+fn resolve_spec(db: &Database, id: Option<i64>, spec: Option<ReportSpec>) -> Result<ReportSpec, String> {
+    match (id, spec) {
+        (_, Some(spec)) => Ok(spec),
+        (Some(id), None) => SavedReportStore::new(db.conn.clone())
+            .get_report(id)
+            .map_err(|e| format!("Failed to load report: {}", e))?
+            .map(|r| r.spec)
+            .ok_or_else(|| format!("Report {} not found", id)),
+        (None, None) => Err("Either a report id or a spec is required".to_string()),
+    }
+}
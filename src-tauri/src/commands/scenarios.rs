@@ -0,0 +1,113 @@
+use crate::services::stress_test::{ScenarioResult, StressTestEngine};
+use crate::storage::market_data::MarketDataStore;
+use crate::storage::portfolio::PortfolioStore;
+use crate::storage::scenarios::{ScenarioRun, ScenarioShock, ScenarioStore, StressScenario};
+use crate::storage::Database;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Shocks from `shocks`, or parsed from shorthand `text` like "tech -15%, rates +50bp".
+fn resolve_shocks(shocks: Option<Vec<ScenarioShock>>, text: Option<String>) -> Result<Vec<ScenarioShock>, String> {
+    match (shocks, text) {
+        (Some(shocks), _) if !shocks.is_empty() => Ok(shocks),
+        (_, Some(text)) => StressTestEngine::parse_shocks(&text).map_err(|e| format!("Invalid scenario: {}", e)),
+        _ => Err("A scenario needs shocks or a shorthand description".to_string()),
+    }
+}
+
+#[tauri::command]
+pub fn parse_scenario_shocks(text: String) -> Result<Vec<ScenarioShock>, String> {
+    StressTestEngine::parse_shocks(&text).map_err(|e| format!("Invalid scenario: {}", e))
+}
+
+#[tauri::command]
+pub fn save_stress_scenario(
+    name: String,
+    description: Option<String>,
+    shocks: Option<Vec<ScenarioShock>>,
+    text: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    if name.trim().is_empty() {
+        return Err("Scenario name must not be empty".to_string());
+    }
+    let description = description.or_else(|| text.clone());
+    let shocks = resolve_shocks(shocks, text)?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ScenarioStore::new(db_guard.conn.clone())
+        .save_scenario(name.trim(), description.as_deref(), &shocks)
+        .map_err(|e| format!("Failed to save scenario: {}", e))
+}
+
+#[tauri::command]
+pub fn list_stress_scenarios(db: State<'_, Mutex<Database>>) -> Result<Vec<StressScenario>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ScenarioStore::new(db_guard.conn.clone())
+        .list_scenarios()
+        .map_err(|e| format!("Failed to list scenarios: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_stress_scenario(id: i64, db: State<'_, Mutex<Database>>) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ScenarioStore::new(db_guard.conn.clone())
+        .delete_scenario(id)
+        .map_err(|e| format!("Failed to delete scenario: {}", e))
+}
+
+/// Run a saved scenario against a portfolio and keep the result for comparison.
+#[tauri::command]
+pub fn run_stress_scenario(
+    scenario_id: i64,
+    portfolio_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<ScenarioResult, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let scenario_store = ScenarioStore::new(db_guard.conn.clone());
+    let scenario = scenario_store
+        .get_scenario(scenario_id)
+        .map_err(|e| format!("Failed to load scenario: {}", e))?
+        .ok_or_else(|| format!("Scenario {} not found", scenario_id))?;
+
+    let portfolio_store = PortfolioStore::new(db_guard.conn.clone());
+    let market_data_store = MarketDataStore::new(db_guard.conn.clone());
+    let result = StressTestEngine::run(&portfolio_store, &market_data_store, portfolio_id, Some(scenario_id), &scenario.shocks)
+        .map_err(|e| format!("Failed to run scenario: {}", e))?;
+
+    let snapshot = serde_json::to_value(&result).map_err(|e| format!("Failed to serialize result: {}", e))?;
+    scenario_store
+        .record_run(scenario_id, portfolio_id, result.portfolio_value, result.total_impact, result.impact_percent, &snapshot)
+        .map_err(|e| format!("Failed to record scenario run: {}", e))?;
+
+    Ok(result)
+}
+
+/// Try shocks without saving them.
+#[tauri::command]
+pub fn preview_stress_scenario(
+    portfolio_id: i64,
+    shocks: Option<Vec<ScenarioShock>>,
+    text: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<ScenarioResult, String> {
+    let shocks = resolve_shocks(shocks, text)?;
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let portfolio_store = PortfolioStore::new(db_guard.conn.clone());
+    let market_data_store = MarketDataStore::new(db_guard.conn.clone());
+    StressTestEngine::run(&portfolio_store, &market_data_store, portfolio_id, None, &shocks)
+        .map_err(|e| format!("Failed to run scenario: {}", e))
+}
+
+/// Earlier results of a scenario for a portfolio, newest first.
+#[tauri::command]
+pub fn get_scenario_runs(
+    scenario_id: i64,
+    portfolio_id: i64,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<ScenarioRun>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    ScenarioStore::new(db_guard.conn.clone())
+        .list_runs(scenario_id, portfolio_id, limit)
+        .map_err(|e| format!("Failed to list scenario runs: {}", e))
+}
@@ -0,0 +1,150 @@
+use crate::services::statement_import::{ImportPreview, StatementImporter};
+use crate::storage::{Database, MappingProfile, StatementImport, StatementImportStore, StatementMapping};
+use std::sync::Mutex;
+use tauri::State;
+
+#[tauri::command]
+pub fn list_statement_mapping_profiles(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<MappingProfile>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    StatementImportStore::new(db_guard.conn.clone())
+        .list_profiles()
+        .map_err(|e| format!("Failed to list mapping profiles: {}", e))
+}
+
+/// Saves under `name`, replacing an existing profile of that name.
+#[tauri::command]
+pub fn save_statement_mapping_profile(
+    name: String,
+    institution: Option<String>,
+    mapping: StatementMapping,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    if name.trim().is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    StatementImportStore::new(db_guard.conn.clone())
+        .save_profile(name.trim(), institution.as_deref(), &mapping)
+        .map_err(|e| format!("Failed to save mapping profile: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_statement_mapping_profile(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    StatementImportStore::new(db_guard.conn.clone())
+        .delete_profile(id)
+        .map_err(|e| format!("Failed to delete mapping profile: {}", e))
+}
+
+/// JSON to share the profile with other installations.
+#[tauri::command]
+pub fn export_statement_mapping_profile(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let profile = StatementImportStore::new(db_guard.conn.clone())
+        .get_profile(id)
+        .map_err(|e| format!("Failed to get mapping profile: {}", e))?
+        .ok_or_else(|| format!("Mapping profile {} not found", id))?;
+    StatementImporter::export_profile(&profile).map_err(|e| format!("Failed to export mapping profile: {}", e))
+}
+
+#[tauri::command]
+pub fn import_statement_mapping_profile(
+    json: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    StatementImporter::import_profile(&StatementImportStore::new(db_guard.conn.clone()), &json)
+        .map_err(|e| format!("Failed to import mapping profile: {}", e))
+}
+
+/// A starting mapping guessed from the file's header, to edit into a profile.
+#[tauri::command]
+pub fn detect_statement_mapping(text: String) -> Result<StatementMapping, String> {
+    StatementImporter::detect_mapping(&text).map_err(|e| format!("Failed to detect mapping: {}", e))
+}
+
+/// Parse and check a statement without importing it. `mapping` wins over
+/// `profile_id`; with neither, columns are detected from the header.
+#[tauri::command]
+pub fn preview_statement_import(
+    portfolio_id: i64,
+    text: String,
+    profile_id: Option<i64>,
+    mapping: Option<StatementMapping>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<ImportPreview, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mapping = StatementImporter::resolve_mapping(
+        &StatementImportStore::new(db_guard.conn.clone()),
+        &text,
+        profile_id,
+        mapping,
+    )
+    .map_err(|e| format!("Failed to resolve mapping: {}", e))?;
+    StatementImporter::preview(&db_guard.conn, portfolio_id, &text, mapping)
+        .map_err(|e| format!("Failed to preview statement: {}", e))
+}
+
+/// Import the new, valid rows. Rows matching existing transactions are
+/// skipped; invalid rows abort the import unless `skip_invalid`.
+#[tauri::command]
+pub fn commit_statement_import(
+    portfolio_id: i64,
+    file_name: String,
+    text: String,
+    profile_id: Option<i64>,
+    mapping: Option<StatementMapping>,
+    skip_invalid: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<StatementImport, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mapping = StatementImporter::resolve_mapping(
+        &StatementImportStore::new(db_guard.conn.clone()),
+        &text,
+        profile_id,
+        mapping,
+    )
+    .map_err(|e| format!("Failed to resolve mapping: {}", e))?;
+    StatementImporter::commit(
+        &db_guard.conn,
+        portfolio_id,
+        profile_id,
+        &file_name,
+        &text,
+        mapping,
+        skip_invalid.unwrap_or(false),
+    )
+    .map_err(|e| format!("Failed to import statement: {}", e))
+}
+
+#[tauri::command]
+pub fn list_statement_imports(
+    portfolio_id: Option<i64>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<StatementImport>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    StatementImportStore::new(db_guard.conn.clone())
+        .list_imports(portfolio_id, limit.unwrap_or(50))
+        .map_err(|e| format!("Failed to list statement imports: {}", e))
+}
+
+/// Remove the transactions an import added. Returns how many.
+#[tauri::command]
+pub fn undo_statement_import(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<usize, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    StatementImportStore::new(db_guard.conn.clone())
+        .undo_import(id)
+        .map_err(|e| format!("Failed to undo statement import: {}", e))
+}
@@ -1,8 +1,9 @@
 use crate::services::{NewsAggregator, SentimentAnalyzer};
+use crate::services::response_cache::ResponseCache;
 use crate::services::api_key_manager::APIKeyManager;
 use crate::storage::{Database, StockNewsItem, StockNewsStore, StockTicker};
 use std::sync::{Arc, Mutex};
-use tauri::{Emitter, State};
+use tauri::State;
 
 #[tauri::command]
 pub fn get_stock_tickers(
@@ -77,14 +78,18 @@ pub async fn refresh_stock_news(
     api_key_manager: State<'_, Arc<APIKeyManager>>,
 ) -> Result<usize, String> {
     // Get store
-    let store = {
+    let (store, response_cache) = {
         let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
-        Arc::new(Mutex::new(StockNewsStore::new(db_guard.conn.clone())))
+        (
+            Arc::new(Mutex::new(StockNewsStore::new(db_guard.conn.clone()))),
+            ResponseCache::new(db_guard.conn.clone(), Some(app.clone())),
+        )
     };
 
     // Create aggregator with API key manager
     let aggregator = NewsAggregator::new_with_api_keys(store, Some(&*api_key_manager))
-        .map_err(|e| format!("Failed to create news aggregator: {}", e))?;
+        .map_err(|e| format!("Failed to create news aggregator: {}", e))?
+        .with_response_cache(response_cache);
 
     // Fetch news
     let symbols = tickers.unwrap_or_default();
@@ -98,14 +103,11 @@ pub async fn refresh_stock_news(
     // Emit events for new items
     if count > 0 {
         for item in &items {
-            let _ = app.emit(
-                "ws-message",
-                serde_json::json!({
-                    "type": "stock-news",
-                    "data": item,
-                    "timestamp": chrono::Utc::now().timestamp_millis()
-                }),
-            );
+            let _ = crate::services::window_router::emit(&app, serde_json::json!({
+                "type": "stock-news",
+                "data": item,
+                "timestamp": chrono::Utc::now().timestamp_millis()
+            }));
         }
     }
 
@@ -0,0 +1,66 @@
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::sync_client::{
+    SyncClient, SyncReport, SyncStatus, CONFIG_SYNC_INTERVAL_MINUTES, CONFIG_SYNC_URL, SYNC_KEY_PROVIDER,
+};
+use crate::storage::Database;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+fn db_arc(db: &State<'_, Mutex<Database>>) -> Result<Arc<Mutex<Database>>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() })))
+}
+
+/// Configure the self-hosted sync server. An empty URL disables sync; an
+/// interval of 0 means sync only runs when requested.
+#[tauri::command]
+pub fn set_sync_settings(
+    server_url: String,
+    interval_minutes: Option<i64>,
+    token: Option<String>,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<(), String> {
+    let trimmed = server_url.trim();
+    if !trimmed.is_empty() && !trimmed.starts_with("http://") && !trimmed.starts_with("https://") {
+        return Err("Sync server URL must start with http:// or https://".to_string());
+    }
+    {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        db_guard.set_config(CONFIG_SYNC_URL, trimmed)
+            .map_err(|e| format!("Failed to save sync settings: {}", e))?;
+        if let Some(minutes) = interval_minutes {
+            db_guard.set_config(CONFIG_SYNC_INTERVAL_MINUTES, &minutes.max(0).to_string())
+                .map_err(|e| format!("Failed to save sync settings: {}", e))?;
+        }
+    }
+    if let Some(token) = token {
+        if token.is_empty() {
+            api_key_manager.delete_key(SYNC_KEY_PROVIDER)
+        } else {
+            api_key_manager.store_key(SYNC_KEY_PROVIDER, &token)
+        }
+        .map_err(|e| format!("Failed to save sync token: {}", e))?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_sync_status(
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<SyncStatus, String> {
+    SyncClient::status(&db_arc(&db)?, &api_key_manager)
+        .map_err(|e| format!("Failed to get sync status: {}", e))
+}
+
+#[tauri::command]
+pub async fn sync_now(
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+) -> Result<SyncReport, String> {
+    let db_arc = db_arc(&db)?;
+    SyncClient::sync_once(&db_arc, &api_key_manager)
+        .await
+        .map_err(|e| format!("Sync failed: {}", e))
+}
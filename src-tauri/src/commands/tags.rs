@@ -0,0 +1,113 @@
+use crate::storage::{Database, Tag, TagStore, TagUsage};
+use std::sync::Mutex;
+use tauri::State;
+
+#[tauri::command]
+pub fn list_tags(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<Tag>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TagStore::new(db_guard.conn.clone())
+        .list_tags()
+        .map_err(|e| format!("Failed to list tags: {}", e))
+}
+
+#[tauri::command]
+pub fn create_tag(
+    name: String,
+    color: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TagStore::new(db_guard.conn.clone())
+        .create_tag(&name, color.as_deref())
+        .map_err(|e| format!("Failed to create tag: {}", e))
+}
+
+#[tauri::command]
+pub fn update_tag(
+    id: i64,
+    name: Option<String>,
+    color: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TagStore::new(db_guard.conn.clone())
+        .update_tag(id, name.as_deref(), color.as_deref())
+        .map_err(|e| format!("Failed to update tag: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_tag(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TagStore::new(db_guard.conn.clone())
+        .delete_tag(id)
+        .map_err(|e| format!("Failed to delete tag: {}", e))
+}
+
+/// Tag an article, event, alert, project or note by tag name, creating the
+/// tag on first use. Returns the tag's id.
+#[tauri::command]
+pub fn attach_tag(
+    object_type: String,
+    object_id: i64,
+    tag: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TagStore::new(db_guard.conn.clone())
+        .attach(&object_type, object_id, &tag)
+        .map_err(|e| format!("Failed to attach tag: {}", e))
+}
+
+#[tauri::command]
+pub fn detach_tag(
+    object_type: String,
+    object_id: i64,
+    tag_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TagStore::new(db_guard.conn.clone())
+        .detach(&object_type, object_id, tag_id)
+        .map_err(|e| format!("Failed to detach tag: {}", e))
+}
+
+#[tauri::command]
+pub fn get_object_tags(
+    object_type: String,
+    object_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<Tag>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TagStore::new(db_guard.conn.clone())
+        .tags_for(&object_type, object_id)
+        .map_err(|e| format!("Failed to get tags: {}", e))
+}
+
+/// Ids of the objects of one type carrying a tag, e.g. to filter notes kept
+/// outside the database.
+#[tauri::command]
+pub fn list_tagged_objects(
+    object_type: String,
+    tag: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<i64>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TagStore::new(db_guard.conn.clone())
+        .tagged_objects(&object_type, &tag)
+        .map_err(|e| format!("Failed to list tagged objects: {}", e))
+}
+
+#[tauri::command]
+pub fn get_tag_usage_stats(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<TagUsage>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TagStore::new(db_guard.conn.clone())
+        .usage_stats()
+        .map_err(|e| format!("Failed to get tag usage stats: {}", e))
+}
@@ -1,22 +1,27 @@
-use crate::storage::temporal::TemporalStore;
+use crate::services::alert_rule_library::{AlertRuleLibrary, ImportReport, RuleExport, RuleTemplate};
+use crate::services::threshold_tuner::ThresholdTuner;
+use crate::storage::temporal::{RuleTuning, TemporalStore, ThresholdAdjustment, ThresholdBounds};
 use crate::storage::Database;
 use serde_json::Value;
 use std::sync::Mutex;
-use tauri::{Emitter, State};
+use tauri::State;
 
 #[tauri::command]
 pub fn temporal_list_events(
     limit: Option<i64>,
     from_ts: Option<i64>,
     to_ts: Option<i64>,
+    tag: Option<String>,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Vec<crate::storage::temporal::TemporalEvent>, String> {
     let limit = limit.unwrap_or(200).max(1).min(2000);
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
     let store = TemporalStore::new(db_guard.conn.clone());
-    store
-        .list_events(limit, from_ts, to_ts)
-        .map_err(|e| format!("Failed to list events: {}", e))
+    match tag {
+        Some(tag) => store.list_tagged_events(&tag, limit, from_ts, to_ts),
+        None => store.list_events(limit, from_ts, to_ts),
+    }
+    .map_err(|e| format!("Failed to list events: {}", e))
 }
 
 #[tauri::command]
@@ -31,6 +36,53 @@ pub fn temporal_get_event(
         .map_err(|e| format!("Failed to get event: {}", e))
 }
 
+/// Events currently in any of the given lifecycle states, e.g. ["emerging", "developing"]
+/// for stories developing now.
+#[tauri::command]
+pub fn temporal_list_events_by_state(
+    states: Vec<String>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::temporal::TemporalEvent>, String> {
+    use crate::services::story_lifecycle::{StoryLifecycle, STATES};
+
+    let states: Vec<String> = states.iter().map(|s| s.trim().to_lowercase()).collect();
+    if let Some(unknown) = states.iter().find(|s| !StoryLifecycle::is_state(s)) {
+        return Err(format!("Unknown lifecycle state '{}', expected one of {}", unknown, STATES.join(", ")));
+    }
+    let limit = limit.unwrap_or(200).clamp(1, 2000);
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .list_events_by_state(&states, limit)
+        .map_err(|e| format!("Failed to list events: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_get_event_transitions(
+    event_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::temporal::LifecycleTransition>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .list_lifecycle_transitions(event_id)
+        .map_err(|e| format!("Failed to list lifecycle transitions: {}", e))
+}
+
+/// Reclassify recent events from their evidence arrival rate. Returns the transitions made.
+#[tauri::command]
+pub fn temporal_update_lifecycles(
+    days_back: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::temporal::LifecycleTransition>, String> {
+    let days_back = days_back.unwrap_or(14).clamp(1, 365);
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    crate::services::story_lifecycle::StoryLifecycle::update(&store, days_back)
+        .map_err(|e| format!("Failed to update lifecycles: {}", e))
+}
+
 #[tauri::command]
 pub fn temporal_list_event_evidence(
     event_id: i64,
@@ -43,6 +95,99 @@ pub fn temporal_list_event_evidence(
         .map_err(|e| format!("Failed to list evidence: {}", e))
 }
 
+/// An event's summaries as the story developed, newest first.
+#[tauri::command]
+pub fn temporal_list_summary_revisions(
+    event_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::temporal::SummaryRevision>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .list_summary_revisions(event_id)
+        .map_err(|e| format!("Failed to list summary revisions: {}", e))
+}
+
+/// Rewrite an event's summary from its current evidence now.
+#[tauri::command]
+pub fn temporal_refresh_event_summary(
+    event_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<crate::storage::temporal::SummaryRevision>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    let settings = crate::services::event_summaries::SummaryRefreshSettings::load(&db_guard);
+    crate::services::event_summaries::EventSummaries::refresh(&store, event_id, &settings)
+        .map_err(|e| format!("Failed to refresh event summary: {}", e))
+}
+
+/// Archive events that ended more than `older_than_months` ago, defaulting
+/// to the configured retention. Returns the number archived.
+#[tauri::command]
+pub fn temporal_archive_events(
+    older_than_months: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<usize, String> {
+    use crate::services::event_retention::EventRetention;
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let months = older_than_months
+        .or_else(|| EventRetention::retention_months(&db_guard))
+        .ok_or_else(|| "No retention period given or configured".to_string())?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    EventRetention::apply(&store, months)
+        .map_err(|e| format!("Failed to archive events: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_list_archived_events(
+    query: Option<String>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::temporal::ArchivedEvent>, String> {
+    let limit = limit.unwrap_or(200).clamp(1, 2000);
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .list_archived_events(query.as_deref(), limit)
+        .map_err(|e| format!("Failed to list archived events: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_get_archived_event(
+    event_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<crate::storage::temporal::ArchivedEventDetail>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .get_archived_event(event_id)
+        .map_err(|e| format!("Failed to read archived event: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_restore_event(
+    event_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<bool, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .restore_event(event_id)
+        .map_err(|e| format!("Failed to restore event: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_get_storage_usage(
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::storage::temporal::EventStorageUsage, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .storage_usage()
+        .map_err(|e| format!("Failed to read storage usage: {}", e))
+}
+
 #[tauri::command]
 pub fn temporal_rebuild_events_mvp(
     days_back: Option<i64>,
@@ -51,33 +196,75 @@ pub fn temporal_rebuild_events_mvp(
 ) -> Result<i64, String> {
     let days_back = days_back.unwrap_or(14).max(1).min(365);
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    rebuild_events(&app, &db_guard, days_back, None)
+}
+
+/// Rebuild events and their derived scores, then evaluate alert rules.
+/// Run as a job, cancellation is honoured between the steps.
+pub(crate) fn rebuild_events(
+    app: &tauri::AppHandle,
+    db_guard: &Database,
+    days_back: i64,
+    job: Option<&crate::services::job_manager::JobContext>,
+) -> Result<i64, String> {
+    let step = |progress: f64, message: &str| -> Result<(), String> {
+        if let Some(job) = job {
+            if job.is_cancelled() {
+                return Err("Event rebuild cancelled".to_string());
+            }
+            job.progress(progress, message);
+        }
+        Ok(())
+    };
+
     let store = TemporalStore::new(db_guard.conn.clone());
+    step(0.0, "Forming events")?;
     let count = store
         .rebuild_events_mvp(days_back)
         .map_err(|e| format!("Failed to rebuild events: {}", e))?;
+    step(0.5, "Scoring events")?;
+    let volume_settings = crate::services::news_volume::VolumeAnomalySettings::load(db_guard);
+    let osint_store = crate::storage::osint::OSINTStore::new(db_guard.conn.clone());
+    if let Err(e) = crate::services::news_volume::NewsVolumeDetector::detect(&osint_store, &store, &volume_settings, 2) {
+        eprintln!("News volume anomaly detection failed: {}", e);
+    }
+    let divergence_settings = crate::services::sentiment_divergence::DivergenceSettings::load(db_guard);
+    if let Err(e) = crate::services::sentiment_divergence::SentimentDivergenceDetector::detect(&osint_store, &store, &divergence_settings) {
+        eprintln!("Sentiment divergence detection failed: {}", e);
+    }
+    if let Err(e) = crate::services::story_lifecycle::StoryLifecycle::update(&store, days_back) {
+        eprintln!("Story lifecycle update failed: {}", e);
+    }
+    if let Err(e) = crate::services::corroboration::CorroborationScorer::update(&store, days_back) {
+        eprintln!("Corroboration scoring failed: {}", e);
+    }
+    let summary_settings = crate::services::event_summaries::SummaryRefreshSettings::load(db_guard);
+    if let Err(e) = crate::services::event_summaries::EventSummaries::update(&store, &summary_settings, days_back) {
+        eprintln!("Event summary refresh failed: {}", e);
+    }
 
     // Evaluate rules and emit newly created alerts
+    step(0.8, "Evaluating alert rules")?;
     if let Ok(created_alerts) = store.evaluate_alert_rules_mvp(days_back, 500) {
+        let market_data_store = crate::storage::market_data::MarketDataStore::new(db_guard.conn.clone());
+        if let Err(e) = crate::services::chart_annotations::ChartAnnotationService::record_alerts(&store, &market_data_store, &created_alerts) {
+            eprintln!("Failed to record chart markers for alerts: {}", e);
+        }
         for alert in created_alerts {
-            let _ = app.emit(
-                "ws-message",
-                serde_json::json!({
-                    "type": "temporal-alert",
-                    "data": alert,
-                    "timestamp": chrono::Utc::now().timestamp_millis()
-                }),
-            );
+            let _ = crate::services::window_router::emit(app, serde_json::json!({
+                "type": "temporal-alert",
+                "data": &alert,
+                "timestamp": chrono::Utc::now().timestamp_millis()
+            }));
+            crate::ws::publish_alert(app, "temporal", serde_json::json!(alert));
         }
     }
 
-    let _ = app.emit(
-        "ws-message",
-        serde_json::json!({
-            "type": "temporal-job-status",
-            "data": { "job": "rebuild-events-mvp", "touched_events": count, "days_back": days_back },
-            "timestamp": chrono::Utc::now().timestamp_millis()
-        }),
-    );
+    let _ = crate::services::window_router::emit(&app, serde_json::json!({
+        "type": "temporal-job-status",
+        "data": { "job": "rebuild-events-mvp", "touched_events": count, "days_back": days_back },
+        "timestamp": chrono::Utc::now().timestamp_millis()
+    }));
 
     Ok(count)
 }
@@ -94,14 +281,92 @@ pub fn temporal_rebuild_search_index(
         .rebuild_search_index(from_ts)
         .map_err(|e| format!("Failed to rebuild search index: {}", e))?;
 
-    let _ = app.emit(
-        "ws-message",
-        serde_json::json!({
-            "type": "temporal-job-status",
-            "data": { "job": "rebuild-search-index", "indexed_docs": count },
-            "timestamp": chrono::Utc::now().timestamp_millis()
-        }),
-    );
+    let _ = crate::services::window_router::emit(&app, serde_json::json!({
+        "type": "temporal-job-status",
+        "data": { "job": "rebuild-search-index", "indexed_docs": count },
+        "timestamp": chrono::Utc::now().timestamp_millis()
+    }));
+
+    Ok(count)
+}
+
+#[tauri::command]
+pub fn get_search_index_settings(
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::storage::search_index::SearchIndexSettings, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    crate::storage::search_index::SearchIndexSettings::load(&db_guard)
+        .map_err(|e| format!("Failed to load search index settings: {}", e))
+}
+
+/// Languages whose articles may form events; empty allows every language.
+#[tauri::command]
+pub fn get_event_languages(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<String>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db_guard.conn.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    Ok(crate::storage::search_index::event_languages(&conn))
+}
+
+/// Restrict event formation to articles in `languages` (ISO 639-1 codes).
+/// Articles whose language is unknown still form events. Applies from the
+/// next rebuild.
+#[tauri::command]
+pub fn set_event_languages(
+    languages: Vec<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    use crate::storage::search_index::{is_language_code, CONFIG_EVENT_LANGUAGES};
+
+    let languages: Vec<String> = languages.iter().map(|l| l.trim().to_lowercase()).filter(|l| !l.is_empty()).collect();
+    if let Some(language) = languages.iter().find(|l| !is_language_code(l)) {
+        return Err(format!("Not an ISO 639-1 language code: {}", language));
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    db_guard
+        .set_config(CONFIG_EVENT_LANGUAGES, &languages.join(","))
+        .map_err(|e| format!("Failed to save event languages: {}", e))
+}
+
+/// Save tokenizer and stop-word settings. Stop words apply to the next
+/// search; tokenizer and trigram changes need `reindex_search`.
+#[tauri::command]
+pub fn set_search_index_settings(
+    settings: crate::storage::search_index::SearchIndexSettings,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    settings
+        .save(&db_guard)
+        .map_err(|e| format!("Failed to save search index settings: {}", e))
+}
+
+/// Recreate the search index with the saved tokenizer settings and fill it
+/// again. Returns the number of indexed documents.
+#[tauri::command]
+pub fn reindex_search(
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    use crate::storage::search_index::{recreate_index, SearchIndexSettings};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let settings = SearchIndexSettings::load(&db_guard)
+        .map_err(|e| format!("Failed to load search index settings: {}", e))?;
+    {
+        let conn = db_guard.conn.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        recreate_index(&conn, &settings).map_err(|e| format!("Failed to recreate search index: {}", e))?;
+    }
+    let count = TemporalStore::new(db_guard.conn.clone())
+        .rebuild_search_index(None)
+        .map_err(|e| format!("Failed to rebuild search index: {}", e))?;
+
+    let _ = crate::services::window_router::emit(&app, serde_json::json!({
+        "type": "temporal-job-status",
+        "data": { "job": "reindex-search", "indexed_docs": count, "tokenizer": settings.tokenize_option() },
+        "timestamp": chrono::Utc::now().timestamp_millis()
+    }));
 
     Ok(count)
 }
@@ -161,6 +426,21 @@ pub fn temporal_add_watchlist_item(
         .map_err(|e| format!("Failed to add watchlist item: {}", e))
 }
 
+/// Per-item events, alerts and feedback for a watchlist over the last `days`
+/// days, with suggestions to retire or reweight items.
+#[tauri::command]
+pub fn get_watchlist_analytics(
+    watchlist_id: i64,
+    days: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::watchlist_analytics::WatchlistAnalytics, String> {
+    let days = days.unwrap_or(90).clamp(1, 365);
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    crate::services::watchlist_analytics::WatchlistAnalyzer::analyze(&store, watchlist_id, days)
+        .map_err(|e| format!("Failed to analyze watchlist: {}", e))
+}
+
 #[tauri::command]
 pub fn temporal_list_watchlist_items(
     watchlist_id: i64,
@@ -202,19 +482,178 @@ pub fn temporal_list_alert_rules(
         .map_err(|e| format!("Failed to list alert rules: {}", e))
 }
 
+/// Export rules (all when `rule_ids` is unset) with their watchlists referenced by name.
+#[tauri::command]
+pub fn temporal_export_alert_rules(
+    rule_ids: Option<Vec<i64>>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<RuleExport, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    AlertRuleLibrary::export(&store, rule_ids.as_deref())
+        .map_err(|e| format!("Failed to export alert rules: {}", e))
+}
+
+/// Import exported rules. Watchlists missing here are created from the
+/// export only with `create_missing_watchlists`; otherwise their rules are skipped.
+#[tauri::command]
+pub fn temporal_import_alert_rules(
+    export: RuleExport,
+    create_missing_watchlists: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<ImportReport, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    AlertRuleLibrary::import(&store, &export, create_missing_watchlists.unwrap_or(false))
+        .map_err(|e| format!("Failed to import alert rules: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_list_alert_rule_templates() -> Result<Vec<RuleTemplate>, String> {
+    Ok(AlertRuleLibrary::templates())
+}
+
+/// Create a rule from a gallery template; `params` maps each template parameter key to its value.
+#[tauri::command]
+pub fn temporal_create_alert_rule_from_template(
+    template_id: String,
+    params: Value,
+    name: Option<String>,
+    watchlist_id: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let params = params.as_object().cloned().unwrap_or_default();
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    AlertRuleLibrary::instantiate(&store, &template_id, &params, name.as_deref(), watchlist_id)
+        .map_err(|e| format!("Failed to create alert rule from template: {}", e))
+}
+
+/// Set a rule's per-channel message templates, keyed by channel name or
+/// "default". `None` goes back to the untemplated message.
+#[tauri::command]
+pub fn temporal_set_alert_rule_templates(
+    rule_id: i64,
+    templates: Option<Value>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    use crate::services::alert_templates::MessageTemplate;
+
+    if let Some(t) = &templates {
+        serde_json::from_value::<std::collections::HashMap<String, MessageTemplate>>(t.clone())
+            .map_err(|e| format!("Invalid message templates: {}", e))?;
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .set_alert_rule_templates(rule_id, templates.as_ref())
+        .map_err(|e| format!("Failed to set alert rule templates: {}", e))
+}
+
+/// Render the message a rule would send on `channel`. Uses `template` when
+/// given (an unsaved draft), otherwise the rule's own. Fills it from
+/// `alert_id`, else the rule's latest alert, else sample values.
+#[tauri::command]
+pub fn temporal_preview_alert_message(
+    rule_id: i64,
+    channel: String,
+    alert_id: Option<i64>,
+    template: Option<crate::services::alert_templates::MessageTemplate>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::alert_templates::RenderedMessage, String> {
+    use crate::services::alert_templates::AlertTemplates;
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    let rule = store
+        .list_alert_rules()
+        .map_err(|e| format!("Failed to list alert rules: {}", e))?
+        .into_iter()
+        .find(|r| r.id == rule_id)
+        .ok_or_else(|| format!("Alert rule {} not found", rule_id))?;
+    let alerts = store
+        .list_alerts(1000, None, None)
+        .map_err(|e| format!("Failed to list alerts: {}", e))?;
+    let alert = match alert_id {
+        Some(id) => Some(
+            alerts
+                .iter()
+                .find(|a| a.id == id)
+                .ok_or_else(|| format!("Alert {} not found", id))?,
+        ),
+        None => alerts.iter().filter(|a| a.rule_id == rule_id).max_by_key(|a| a.fired_at),
+    };
+
+    let variables = match alert {
+        Some(alert) => AlertTemplates::variables(alert),
+        None => AlertTemplates::sample_variables(&rule),
+    };
+    let template = template.or_else(|| AlertTemplates::for_channel(&rule, &channel));
+    Ok(AlertTemplates::render(&channel, template.as_ref(), &variables))
+}
+
+/// Run a synthetic event through alert rules and trace every condition.
+/// Tests `rule_json` when given (an unsaved draft), otherwise the rule
+/// `rule_id`, otherwise every rule of the active profile.
+#[tauri::command]
+pub fn temporal_test_rules(
+    event: crate::services::rule_sandbox::SyntheticEvent,
+    rule_id: Option<i64>,
+    rule_json: Option<Value>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::services::rule_sandbox::RuleTestResult>, String> {
+    use crate::services::rule_sandbox::RuleSandbox;
+
+    let rules = match rule_json {
+        Some(rule_json) => vec![RuleSandbox::draft_rule(rule_json)],
+        None => {
+            let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+            let store = TemporalStore::new(db_guard.conn.clone());
+            let rules = store
+                .list_alert_rules()
+                .map_err(|e| format!("Failed to list alert rules: {}", e))?;
+            match rule_id {
+                Some(id) => {
+                    let rule = rules
+                        .into_iter()
+                        .find(|r| r.id == id)
+                        .ok_or_else(|| format!("Alert rule {} not found", id))?;
+                    vec![rule]
+                }
+                None => rules,
+            }
+        }
+    };
+    let timezone = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        crate::services::timezone::TimePreferences::load(&db_guard).timezone
+    };
+    let rules: Vec<_> = rules
+        .into_iter()
+        .map(|mut rule| {
+            rule.rule_json = crate::services::alert_rule_engine::AlertRuleEngine::with_timezone(&rule.rule_json, &timezone);
+            rule
+        })
+        .collect();
+    Ok(RuleSandbox::run(&rules, &event))
+}
+
 #[tauri::command]
 pub fn temporal_list_alerts(
     limit: Option<i64>,
     from_ts: Option<i64>,
     to_ts: Option<i64>,
+    tag: Option<String>,
     db: State<'_, Mutex<Database>>,
 ) -> Result<Vec<crate::storage::temporal::Alert>, String> {
     let limit = limit.unwrap_or(200).max(1).min(2000);
     let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
     let store = TemporalStore::new(db_guard.conn.clone());
-    store
-        .list_alerts(limit, from_ts, to_ts)
-        .map_err(|e| format!("Failed to list alerts: {}", e))
+    match tag {
+        Some(tag) => store.list_tagged_alerts(&tag, limit, from_ts, to_ts),
+        None => store.list_alerts(limit, from_ts, to_ts),
+    }
+    .map_err(|e| format!("Failed to list alerts: {}", e))
 }
 
 #[tauri::command]
@@ -255,6 +694,109 @@ pub fn temporal_resolve_alert(
         .map_err(|e| format!("Failed to resolve alert: {}", e))
 }
 
+/// Mute an entity or raise its score thresholds, for all rules or one.
+#[tauri::command]
+pub fn temporal_create_entity_override(
+    entry: crate::storage::temporal::NewEntityAlertOverride,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .create_entity_override(&entry)
+        .map_err(|e| format!("Failed to create entity override: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_list_entity_overrides(
+    include_expired: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::temporal::EntityAlertOverride>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .list_entity_overrides(include_expired.unwrap_or(false))
+        .map_err(|e| format!("Failed to list entity overrides: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_delete_entity_override(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .delete_entity_override(id)
+        .map_err(|e| format!("Failed to delete entity override: {}", e))
+}
+
+/// Turn weekly threshold auto-tuning of a rule on or off. `bounds` maps
+/// severity|volume|sentiment to the {min, max} tuning must stay within.
+#[tauri::command]
+pub fn temporal_set_rule_tuning(
+    rule_id: i64,
+    enabled: bool,
+    bounds: std::collections::HashMap<String, ThresholdBounds>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .set_rule_tuning(rule_id, enabled, &bounds)
+        .map_err(|e| format!("Failed to set rule tuning: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_get_rule_tuning(
+    rule_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<RuleTuning>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .get_rule_tuning(rule_id)
+        .map_err(|e| format!("Failed to get rule tuning: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_list_threshold_adjustments(
+    rule_id: Option<i64>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<ThresholdAdjustment>, String> {
+    let limit = limit.unwrap_or(100).max(1).min(1000);
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .list_threshold_adjustments(rule_id, limit)
+        .map_err(|e| format!("Failed to list threshold adjustments: {}", e))
+}
+
+#[tauri::command]
+pub fn temporal_revert_threshold_adjustment(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<ThresholdAdjustment, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .revert_threshold_adjustment(id)
+        .map_err(|e| format!("Failed to revert threshold adjustment: {}", e))
+}
+
+/// Tune one rule now instead of waiting for the weekly run.
+#[tauri::command]
+pub fn temporal_tune_rule_now(
+    rule_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<ThresholdAdjustment>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    ThresholdTuner::tune_rule(&store, rule_id, chrono::Utc::now().timestamp())
+        .map_err(|e| format!("Failed to tune alert rule: {}", e))
+}
+
 #[tauri::command]
 pub fn temporal_run_backtest_mvp(
     from_ts: i64,
@@ -268,6 +810,111 @@ pub fn temporal_run_backtest_mvp(
         .map_err(|e| format!("Failed to run backtest: {}", e))
 }
 
+/// Score recent daily article counts per entity and create "volume_anomaly"
+/// events for spikes. Returns the ids of new events.
+#[tauri::command]
+pub fn temporal_detect_volume_anomalies(
+    days: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<i64>, String> {
+    use crate::services::news_volume::{NewsVolumeDetector, VolumeAnomalySettings};
+
+    let days = days.unwrap_or(2).clamp(1, 90);
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let settings = VolumeAnomalySettings::load(&db_guard);
+    let osint_store = crate::storage::osint::OSINTStore::new(db_guard.conn.clone());
+    let store = TemporalStore::new(db_guard.conn.clone());
+    NewsVolumeDetector::detect(&osint_store, &store, &settings, days)
+        .map_err(|e| format!("Failed to detect volume anomalies: {}", e))
+}
+
+/// Daily article counts, baseline and anomaly events for one entity.
+#[tauri::command]
+pub fn temporal_get_entity_volume_profile(
+    entity: String,
+    days: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::news_volume::EntityVolumeProfile, String> {
+    use crate::services::news_volume::{NewsVolumeDetector, VolumeAnomalySettings};
+
+    if entity.trim().is_empty() {
+        return Err("Entity must not be empty".to_string());
+    }
+    let days = days.unwrap_or(30).clamp(1, 365);
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let settings = VolumeAnomalySettings::load(&db_guard);
+    let osint_store = crate::storage::osint::OSINTStore::new(db_guard.conn.clone());
+    let store = TemporalStore::new(db_guard.conn.clone());
+    let mut profile = NewsVolumeDetector::entity_profile(&osint_store, &store, &settings, entity.trim(), days)
+        .map_err(|e| format!("Failed to build volume profile: {}", e))?;
+    profile.market_signals = crate::services::market_signals::MarketSignalsService::signals_for_entity(
+        db_guard.conn.clone(),
+        entity.trim(),
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("Failed to load market signals for {}: {}", entity.trim(), e);
+        None
+    });
+    Ok(profile)
+}
+
+/// Per-source sentiment of entities covered by several sources over the
+/// last `hours`, widest disagreement first. Includes entities below the
+/// divergence threshold.
+#[tauri::command]
+pub fn temporal_get_sentiment_divergence(
+    entity: Option<String>,
+    hours: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::services::sentiment_divergence::EntityDivergence>, String> {
+    use crate::services::sentiment_divergence::{DivergenceSettings, SentimentDivergenceDetector};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mut settings = DivergenceSettings::load(&db_guard);
+    if let Some(hours) = hours {
+        settings.window_hours = hours.clamp(1, 24 * 90);
+    }
+    let osint_store = crate::storage::osint::OSINTStore::new(db_guard.conn.clone());
+    let entity = entity.as_deref().map(str::trim).filter(|e| !e.is_empty());
+    SentimentDivergenceDetector::analyze(&osint_store, &settings, entity, chrono::Utc::now().timestamp())
+        .map_err(|e| format!("Failed to analyze sentiment divergence: {}", e))
+}
+
+/// How `ticker` moved 1h/1d/1w after events about it (or about `entity`),
+/// averaged by event type and sentiment.
+#[tauri::command]
+pub fn temporal_event_price_report(
+    query: crate::services::event_price_correlation::EventPriceQuery,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::event_price_correlation::EventPriceReport, String> {
+    if query.ticker.trim().is_empty() {
+        return Err("Ticker must not be empty".to_string());
+    }
+    if query.from_ts > query.to_ts {
+        return Err("Start must not be after end".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let temporal_store = TemporalStore::new(db_guard.conn.clone());
+    let market_data_store = crate::storage::market_data::MarketDataStore::new(db_guard.conn.clone());
+    crate::services::event_price_correlation::EventPriceAnalyzer::analyze(&temporal_store, &market_data_store, &query)
+        .map_err(|e| format!("Failed to build event price report: {}", e))
+}
+
+/// An alert with its rule, event, top evidence, matched watchlist items and
+/// price moves of related tickers, for the alert detail view.
+#[tauri::command]
+pub fn get_alert_context(
+    alert_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::services::alert_context::AlertContext, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let temporal_store = TemporalStore::new(db_guard.conn.clone());
+    let market_data_store = crate::storage::market_data::MarketDataStore::new(db_guard.conn.clone());
+    crate::services::alert_context::AlertContextService::build(&temporal_store, &market_data_store, alert_id)
+        .map_err(|e| format!("Failed to load alert context: {}", e))?
+        .ok_or_else(|| format!("Alert {} not found", alert_id))
+}
+
 #[tauri::command]
 pub fn temporal_get_entity_graph_mvp(
     days_back: Option<i64>,
@@ -324,14 +971,11 @@ pub fn temporal_compute_feature_mvp(
         .compute_feature_mvp(feature_id, days_back)
         .map_err(|e| format!("Failed to compute feature: {}", e))?;
 
-    let _ = app.emit(
-        "ws-message",
-        serde_json::json!({
-            "type": "temporal-job-status",
-            "data": { "job": "compute-feature-mvp", "feature_id": feature_id, "inserted": inserted, "days_back": days_back },
-            "timestamp": chrono::Utc::now().timestamp_millis()
-        }),
-    );
+    let _ = crate::services::window_router::emit(&app, serde_json::json!({
+        "type": "temporal-job-status",
+        "data": { "job": "compute-feature-mvp", "feature_id": feature_id, "inserted": inserted, "days_back": days_back },
+        "timestamp": chrono::Utc::now().timestamp_millis()
+    }));
 
     Ok(inserted)
 }
@@ -383,10 +1027,12 @@ pub async fn escalate_alert(
     alert_id: i64,
     escalation_level: i32,
     channel: String,
+    idempotency_key: Option<String>,
     db: State<'_, Mutex<Database>>,
     app: tauri::AppHandle,
 ) -> Result<i64, String> {
-    use crate::services::alert_escalator::AlertEscalator;
+    use crate::services::notification_outbox::NotificationOutbox;
+    use crate::storage::OutboxStore;
     
     // Clone connection before dropping guard to avoid holding across await
     let conn = {
@@ -408,10 +1054,6 @@ pub async fn escalate_alert(
         .find(|r| r.id == alert.rule_id)
         .ok_or_else(|| format!("Rule {} not found", alert.rule_id))?;
     
-    // Create escalation record
-    let escalation_id = store.create_escalation(alert_id, escalation_level, &channel)
-        .map_err(|e| format!("Failed to create escalation: {}", e))?;
-    
     // Get level config for this escalation level
     let level_config = rule.escalation_config.as_ref()
         .and_then(|config| {
@@ -421,28 +1063,38 @@ pub async fn escalate_alert(
                 .cloned()
         });
     
-    // Clone data needed for async call to avoid holding references across await
-    let alert_clone = alert.clone();
-    let channel_clone = channel.clone();
     let level_config_value = level_config.unwrap_or(serde_json::json!({}));
-    
-    // Send escalation
-    if let Err(e) = AlertEscalator::send_escalation(
-        &store,
-        escalation_id,
-        &channel_clone,
-        &alert_clone,
-        &level_config_value,
-        Some(app),
-    ).await {
+
+    // Manual escalations are deliberate repeats, so each click gets its own
+    // key unless the caller retries with the one it used before
+    let key = idempotency_key.unwrap_or_else(|| format!("manual:{}", uuid::Uuid::new_v4()));
+    let outbox = OutboxStore::new(store.conn.clone());
+    let queued = outbox
+        .enqueue_escalation(
+            &key,
+            alert_id,
+            escalation_level,
+            &channel,
+            &NotificationOutbox::escalation_payload(alert, rule, &level_config_value),
+        )
+        .map_err(|e| format!("Failed to queue escalation: {}", e))?;
+    let message = match queued {
+        Some(message) => message,
+        None => {
+            let existing = outbox
+                .find_by_key(&key)
+                .map_err(|e| format!("Failed to queue escalation: {}", e))?
+                .ok_or_else(|| format!("Escalation {} not found", key))?;
+            return Ok(existing.escalation_id.unwrap_or_default());
+        }
+    };
+    let escalation_id = message.escalation_id.unwrap_or_default();
+
+    // Failed sends stay queued for the retry worker
+    if let Err(e) = NotificationOutbox::deliver(store.conn.clone(), &message, Some(app)).await {
         eprintln!("Failed to send escalation: {}", e);
-        store.mark_escalation_sent(escalation_id, Some(&format!("{}", e)))
-            .map_err(|e| format!("Failed to mark escalation: {}", e))?;
-    } else {
-        store.mark_escalation_sent(escalation_id, None)
-            .map_err(|e| format!("Failed to mark escalation: {}", e))?;
     }
-    
+
     Ok(escalation_id)
 }
 
@@ -458,4 +1110,249 @@ pub fn get_alert_escalation_history(
         .map_err(|e| format!("Failed to get escalation history: {}", e))
 }
 
+/// Alert notifications in the outbox, newest first; `status` is pending,
+/// sending, sent or dead.
+#[tauri::command]
+pub fn list_notification_outbox(
+    status: Option<String>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::OutboxMessage>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    crate::storage::OutboxStore::new(db_guard.conn.clone())
+        .list_messages(status.as_deref(), limit.unwrap_or(100).clamp(1, 1000))
+        .map_err(|e| format!("Failed to list outbox: {}", e))
+}
+
+/// Notifications that ran out of delivery attempts.
+#[tauri::command]
+pub fn list_dead_letters(
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::OutboxMessage>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    crate::storage::OutboxStore::new(db_guard.conn.clone())
+        .list_messages(Some("dead"), limit.unwrap_or(100).clamp(1, 1000))
+        .map_err(|e| format!("Failed to list dead letters: {}", e))
+}
+
+/// Queue a dead-lettered notification again with fresh attempts.
+#[tauri::command]
+pub fn retry_dead_letter(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<bool, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    crate::storage::OutboxStore::new(db_guard.conn.clone())
+        .requeue(id)
+        .map_err(|e| format!("Failed to requeue notification: {}", e))
+}
+
+#[tauri::command]
+pub fn get_issue_tracker_config(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<crate::services::issue_tracker::IssueTrackerConfig>, String> {
+    use crate::services::issue_tracker::IssueTracker;
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    IssueTracker::config(&db_guard).map_err(|e| format!("Failed to read issue tracker config: {}", e))
+}
+
+/// Jira or Linear settings. API tokens are stored separately through the
+/// API key manager under "jira" or "linear".
+#[tauri::command]
+pub fn set_issue_tracker_config(
+    config: Option<crate::services::issue_tracker::IssueTrackerConfig>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    use crate::services::issue_tracker::{CONFIG_ISSUE_TRACKER, PROVIDER_JIRA, PROVIDER_LINEAR};
+
+    let value = match config {
+        Some(config) => {
+            if config.provider != PROVIDER_JIRA && config.provider != PROVIDER_LINEAR {
+                return Err(format!("Unknown issue tracker: {}", config.provider));
+            }
+            serde_json::to_string(&config).map_err(|e| format!("Failed to serialize config: {}", e))?
+        }
+        None => String::new(),
+    };
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    db_guard
+        .set_config(CONFIG_ISSUE_TRACKER, &value)
+        .map_err(|e| format!("Failed to save issue tracker config: {}", e))
+}
+
+/// Create a Jira/Linear issue from an alert and link it to the alert.
+#[tauri::command]
+pub async fn create_alert_issue(
+    alert_id: i64,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, std::sync::Arc<crate::services::api_key_manager::APIKeyManager>>,
+) -> Result<crate::storage::temporal::AlertIssue, String> {
+    use crate::services::issue_tracker::IssueTracker;
+
+    let (store, config) = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        let config = IssueTracker::config(&db_guard)
+            .map_err(|e| format!("Failed to read issue tracker config: {}", e))?
+            .ok_or_else(|| "Issue tracker not configured".to_string())?;
+        (TemporalStore::new(db_guard.conn.clone()), config)
+    };
+    let alert = store
+        .list_alerts(1000, None, None)
+        .map_err(|e| format!("Failed to list alerts: {}", e))?
+        .into_iter()
+        .find(|a| a.id == alert_id)
+        .ok_or_else(|| format!("Alert {} not found", alert_id))?;
+    let rule = store
+        .list_alert_rules_for(None)
+        .map_err(|e| format!("Failed to list rules: {}", e))?
+        .into_iter()
+        .find(|r| r.id == alert.rule_id);
+    let config = IssueTracker::config_for_rule(&config, rule.as_ref()).map_err(|e| e.to_string())?;
+
+    IssueTracker::create_issue(&store, &config, &api_key_manager, &alert)
+        .await
+        .map_err(|e| format!("Failed to create issue: {}", e))
+}
+
+#[tauri::command]
+pub fn get_alert_issue(
+    alert_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<crate::storage::temporal::AlertIssue>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .get_alert_issue(alert_id)
+        .map_err(|e| format!("Failed to get alert issue: {}", e))
+}
+
+/// Refresh linked issue statuses now instead of waiting for the scheduler.
+#[tauri::command]
+pub async fn sync_alert_issues(
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, std::sync::Arc<crate::services::api_key_manager::APIKeyManager>>,
+) -> Result<crate::services::issue_tracker::IssueSyncReport, String> {
+    use crate::services::issue_tracker::IssueTracker;
 
+    let (store, config) = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        let config = IssueTracker::config(&db_guard)
+            .map_err(|e| format!("Failed to read issue tracker config: {}", e))?
+            .ok_or_else(|| "Issue tracker not configured".to_string())?;
+        (TemporalStore::new(db_guard.conn.clone()), config)
+    };
+    IssueTracker::sync(&store, &config, &api_key_manager)
+        .await
+        .map_err(|e| format!("Failed to sync issues: {}", e))
+}
+
+/// Send a test message through an alert channel with the given escalation
+/// level config. Failures come back in the result, not as an error.
+#[tauri::command]
+pub async fn test_alert_channel(
+    channel: String,
+    config: serde_json::Value,
+    app: tauri::AppHandle,
+) -> Result<crate::services::alert_channels::ChannelTestResult, String> {
+    use crate::services::alert_channels::AlertChannelSender;
+
+    Ok(AlertChannelSender::test_channel(&channel, &config, Some(app)).await)
+}
+
+
+
+#[tauri::command]
+pub fn temporal_get_event_commentary(
+    event_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<crate::storage::temporal::EventCommentary>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TemporalStore::new(db_guard.conn.clone());
+    store
+        .get_event_commentary(event_id)
+        .map_err(|e| format!("Failed to get event commentary: {}", e))
+}
+
+/// Run the analyst now instead of waiting for the next scheduled pass.
+#[tauri::command]
+pub async fn temporal_run_event_analyst(
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::temporal::EventCommentary>, String> {
+    let db_arc = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        std::sync::Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() }))
+    };
+    crate::services::event_analyst::EventAnalyst::run_once(&db_arc, &app)
+        .await
+        .map_err(|e| format!("Failed to run event analyst: {}", e))
+}
+
+/// Analyzed events from the last `hours_back` hours, newest first.
+#[tauri::command]
+pub fn temporal_get_analyst_briefing(
+    hours_back: Option<i64>,
+    date: Option<String>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<Value>, String> {
+    use crate::services::timezone::{self, TimePreferences};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let tz = TimePreferences::load(&db_guard).tz();
+    // `date` (YYYY-MM-DD) is a calendar day in the user's timezone
+    let (from_ts, to_ts) = match date {
+        Some(date) => {
+            let day = chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date {}: {}", date, e))?;
+            let next = day.succ_opt().ok_or_else(|| format!("Invalid date {}", date))?;
+            (timezone::day_start(day, tz), Some(timezone::day_start(next, tz)))
+        }
+        None => (chrono::Utc::now().timestamp() - hours_back.unwrap_or(24).max(1) * 3600, None),
+    };
+    let store = TemporalStore::new(db_guard.conn.clone());
+    let entries = store
+        .list_commented_events(from_ts, to_ts, limit.unwrap_or(20).max(1).min(200))
+        .map_err(|e| format!("Failed to build briefing: {}", e))?;
+    Ok(entries
+        .into_iter()
+        .map(|(event, commentary)| {
+            let local_time = timezone::local_datetime(event.start_ts, tz).format("%Y-%m-%d %H:%M %Z").to_string();
+            serde_json::json!({ "event": event, "commentary": commentary, "local_time": local_time })
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn temporal_set_event_analyst_settings(
+    enabled: Option<bool>,
+    model: Option<String>,
+    min_severity: Option<f64>,
+    min_volume: Option<f64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    use crate::services::event_analyst::{CONFIG_ENABLED, CONFIG_MIN_SEVERITY, CONFIG_MIN_VOLUME, CONFIG_MODEL};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mut updates: Vec<(&str, String)> = Vec::new();
+    if let Some(enabled) = enabled {
+        updates.push((CONFIG_ENABLED, enabled.to_string()));
+    }
+    if let Some(model) = model {
+        updates.push((CONFIG_MODEL, model.trim().to_string()));
+    }
+    if let Some(severity) = min_severity {
+        updates.push((CONFIG_MIN_SEVERITY, severity.clamp(0.0, 1.0).to_string()));
+    }
+    if let Some(volume) = min_volume {
+        updates.push((CONFIG_MIN_VOLUME, volume.max(1.0).to_string()));
+    }
+    for (key, value) in updates {
+        db_guard
+            .set_config(key, &value)
+            .map_err(|e| format!("Failed to save {}: {}", key, e))?;
+    }
+    Ok(())
+}
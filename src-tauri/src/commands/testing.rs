@@ -1,6 +1,8 @@
-use crate::storage::testing::TestingStore;
+use crate::services::test_report_parser::TestReportFormat;
+use crate::services::test_runner::{TestRunner, FLAKY_MIN_FLIPS, FLAKY_WINDOW};
+use crate::storage::testing::{TestCommand, TestingStore};
 use crate::storage::Database;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use tauri::State;
 
 #[tauri::command]
@@ -99,3 +101,160 @@ pub fn get_suite_stats(
         .map_err(|e| format!("Failed to get suite stats: {}", e))
 }
 
+
+/// Set the command `run_test_suite` executes for a suite. Pass no program to clear it.
+#[tauri::command]
+pub fn set_test_suite_command(
+    suite_id: i64,
+    program: Option<String>,
+    args: Option<Vec<String>>,
+    working_dir: Option<String>,
+    report_path: Option<String>,
+    format: Option<String>,
+    timeout_secs: Option<u64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    parse_format(format.as_deref())?;
+    let command = program
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .map(|program| TestCommand {
+            program,
+            args: args.unwrap_or_default(),
+            working_dir,
+            report_path,
+            format,
+            timeout_secs,
+        });
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TestingStore::new(db_guard.conn.clone())
+        .set_suite_command(suite_id, command.as_ref())
+        .map_err(|e| format!("Failed to set test command: {}", e))
+}
+
+#[tauri::command]
+pub fn get_test_suite_command(
+    suite_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<TestCommand>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    TestingStore::new(db_guard.conn.clone())
+        .get_suite_command(suite_id)
+        .map_err(|e| format!("Failed to get test command: {}", e))
+}
+
+/// Run the suite's configured command and ingest its JUnit XML, LLVM lit or
+/// cargo test JSON output as a new run.
+#[tauri::command]
+pub async fn run_test_suite(
+    suite_id: i64,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::storage::testing::TestRun, String> {
+    let db_arc = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() }))
+    };
+
+    let run = TestRunner::run_suite(db_arc, suite_id)
+        .await
+        .map_err(|e| format!("Failed to run test suite: {}", e))?;
+
+    let _ = crate::services::window_router::emit(&app, serde_json::json!({
+        "type": "test-run-complete",
+        "data": run,
+        "timestamp": chrono::Utc::now().timestamp_millis()
+    }));
+
+    Ok(run)
+}
+
+#[tauri::command]
+pub fn import_test_report(
+    suite_id: i64,
+    content: String,
+    format: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<crate::storage::testing::TestRun, String> {
+    let format = parse_format(format.as_deref())?;
+    let db_arc = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        Arc::new(Mutex::new(Database { conn: db_guard.conn.clone() }))
+    };
+    TestRunner::import(&db_arc, suite_id, &content, format)
+        .map_err(|e| format!("Failed to import test report: {}", e))
+}
+
+#[tauri::command]
+pub fn list_test_runs(
+    suite_id: i64,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::testing::TestRun>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TestingStore::new(db_guard.conn.clone());
+    store.list_test_runs(suite_id, limit.unwrap_or(50).max(1).min(500))
+        .map_err(|e| format!("Failed to list test runs: {}", e))
+}
+
+fn parse_format(format: Option<&str>) -> Result<Option<TestReportFormat>, String> {
+    match format {
+        None | Some("auto") => Ok(None),
+        Some(f) => TestReportFormat::parse(f)
+            .map(Some)
+            .ok_or_else(|| format!("Unknown report format: {}. Use 'junit', 'llvm', 'cargo' or 'auto'", f)),
+    }
+}
+
+#[tauri::command]
+pub fn detect_flaky_tests(
+    suite_id: i64,
+    window: Option<usize>,
+    min_flips: Option<usize>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::testing::FlakyTest>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TestingStore::new(db_guard.conn.clone());
+    store.detect_flaky_tests(
+        suite_id,
+        window.unwrap_or(FLAKY_WINDOW).max(2).min(200),
+        min_flips.unwrap_or(FLAKY_MIN_FLIPS).max(1),
+    )
+    .map_err(|e| format!("Failed to detect flaky tests: {}", e))
+}
+
+#[tauri::command]
+pub fn quarantine_test(
+    suite_id: i64,
+    test_name: String,
+    reason: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TestingStore::new(db_guard.conn.clone());
+    store.quarantine_test(suite_id, &test_name, reason.as_deref(), false)
+        .map_err(|e| format!("Failed to quarantine test: {}", e))
+}
+
+#[tauri::command]
+pub fn unquarantine_test(
+    suite_id: i64,
+    test_name: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TestingStore::new(db_guard.conn.clone());
+    store.unquarantine_test(suite_id, &test_name)
+        .map_err(|e| format!("Failed to remove test from quarantine: {}", e))
+}
+
+#[tauri::command]
+pub fn list_quarantined_tests(
+    suite_id: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<crate::storage::testing::QuarantinedTest>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TestingStore::new(db_guard.conn.clone());
+    store.list_quarantined(suite_id)
+        .map_err(|e| format!("Failed to list quarantined tests: {}", e))
+}
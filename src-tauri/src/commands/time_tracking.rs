@@ -0,0 +1,187 @@
+use crate::services::data_export::DataExportService;
+use crate::services::time_tracker::{CONFIG_AUTO_ENABLED, CONFIG_IDLE_MINUTES};
+use crate::storage::time_tracking::{AutoTrackingRule, TimeEntry, TimeSummaryRow, TimeTrackingStore};
+use crate::storage::Database;
+use std::sync::Mutex;
+use tauri::State;
+
+#[tauri::command]
+pub fn start_time_tracking(
+    project_id: i64,
+    note: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let project_store = crate::storage::ProjectStore::new(db_guard.conn.clone());
+    if project_store.get_project(project_id).map_err(|e| format!("Failed to get project: {}", e))?.is_none() {
+        return Err(format!("Project {} not found", project_id));
+    }
+    let store = TimeTrackingStore::new(db_guard.conn.clone());
+    store.start_timer(project_id, "manual", note.as_deref(), chrono::Utc::now().timestamp())
+        .map_err(|e| format!("Failed to start timer: {}", e))
+}
+
+#[tauri::command]
+pub fn stop_time_tracking(
+    project_id: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<usize, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TimeTrackingStore::new(db_guard.conn.clone());
+    store.stop_timers(project_id, None, chrono::Utc::now().timestamp(), "manual")
+        .map_err(|e| format!("Failed to stop timer: {}", e))
+}
+
+#[tauri::command]
+pub fn get_active_timers(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<TimeEntry>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TimeTrackingStore::new(db_guard.conn.clone());
+    store.list_running()
+        .map_err(|e| format!("Failed to get active timers: {}", e))
+}
+
+#[tauri::command]
+pub fn list_time_entries(
+    project_id: Option<i64>,
+    from_ts: Option<i64>,
+    to_ts: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<TimeEntry>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TimeTrackingStore::new(db_guard.conn.clone());
+    store.list_entries(project_id, from_ts, to_ts)
+        .map_err(|e| format!("Failed to list time entries: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_time_entry(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TimeTrackingStore::new(db_guard.conn.clone());
+    store.delete_entry(id)
+        .map_err(|e| format!("Failed to delete time entry: {}", e))
+}
+
+#[tauri::command]
+pub fn get_time_summary(
+    period: String,
+    from_ts: Option<i64>,
+    to_ts: Option<i64>,
+    project_id: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<TimeSummaryRow>, String> {
+    let now = chrono::Utc::now().timestamp();
+    let default_span = if period == "weekly" { 28 * 86_400 } else { 7 * 86_400 };
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TimeTrackingStore::new(db_guard.conn.clone());
+    store.summarize(&period, from_ts.unwrap_or(now - default_span), to_ts.unwrap_or(now), project_id)
+        .map_err(|e| format!("Failed to summarize time entries: {}", e))
+}
+
+/// CSV of time entries for invoicing, one row per entry.
+#[tauri::command]
+pub fn export_time_entries_csv(
+    project_id: Option<i64>,
+    from_ts: Option<i64>,
+    to_ts: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<String, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TimeTrackingStore::new(db_guard.conn.clone());
+    let project_store = crate::storage::ProjectStore::new(db_guard.conn.clone());
+
+    let mut entries = store.list_entries(project_id, from_ts, to_ts)
+        .map_err(|e| format!("Failed to list time entries: {}", e))?;
+    entries.reverse(); // Chronological order reads better on an invoice
+
+    let project_names: std::collections::HashMap<i64, String> = project_store
+        .list_projects(None, None)
+        .map_err(|e| format!("Failed to list projects: {}", e))?
+        .into_iter()
+        .map(|p| (p.id, p.name))
+        .collect();
+
+    let format_ts = |ts: i64| {
+        chrono::DateTime::from_timestamp(ts, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default()
+    };
+
+    let rows: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|e| {
+            serde_json::json!({
+                "project": project_names.get(&e.project_id).cloned().unwrap_or_else(|| format!("Project {}", e.project_id)),
+                "date": format_ts(e.started_at).chars().take(10).collect::<String>(),
+                "started_at": format_ts(e.started_at),
+                "ended_at": e.ended_at.map(format_ts).unwrap_or_default(),
+                "hours": (e.duration_secs as f64 / 3600.0 * 100.0).round() / 100.0,
+                "source": e.source,
+                "note": e.note.clone().unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    Ok(DataExportService::export_to_csv(
+        &rows,
+        &["project", "date", "started_at", "ended_at", "hours", "source", "note"],
+    ))
+}
+
+#[tauri::command]
+pub fn add_time_tracking_rule(
+    project_id: i64,
+    app_pattern: String,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    if app_pattern.trim().is_empty() {
+        return Err("App pattern must not be empty".to_string());
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TimeTrackingStore::new(db_guard.conn.clone());
+    store.add_rule(project_id, app_pattern.trim())
+        .map_err(|e| format!("Failed to add tracking rule: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_time_tracking_rule(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TimeTrackingStore::new(db_guard.conn.clone());
+    store.delete_rule(id)
+        .map_err(|e| format!("Failed to delete tracking rule: {}", e))
+}
+
+#[tauri::command]
+pub fn list_time_tracking_rules(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<AutoTrackingRule>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TimeTrackingStore::new(db_guard.conn.clone());
+    store.list_rules()
+        .map_err(|e| format!("Failed to list tracking rules: {}", e))
+}
+
+#[tauri::command]
+pub fn set_time_tracking_settings(
+    idle_minutes: Option<i64>,
+    auto_tracking: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    if let Some(minutes) = idle_minutes {
+        db_guard.set_config(CONFIG_IDLE_MINUTES, &minutes.max(1).to_string())
+            .map_err(|e| format!("Failed to save idle threshold: {}", e))?;
+    }
+    if let Some(enabled) = auto_tracking {
+        db_guard.set_config(CONFIG_AUTO_ENABLED, if enabled { "true" } else { "false" })
+            .map_err(|e| format!("Failed to save auto tracking setting: {}", e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use crate::services::transcription::{
+    Transcriber, TranscriptionResult, CONFIG_FFMPEG_PATH, CONFIG_LANGUAGE, CONFIG_PODCASTS_ENABLED,
+    CONFIG_WHISPER_MODEL,
+};
+use crate::storage::{Database, Transcript, TranscriptSegment, TranscriptStore};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::State;
+
+#[derive(Debug, Serialize)]
+pub struct TranscriptDetail {
+    pub transcript: Transcript,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+/// Transcribe a local audio file with whisper and make it searchable.
+#[tauri::command]
+pub async fn transcribe_audio(
+    path: String,
+    title: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<TranscriptionResult, String> {
+    let path = std::path::Path::new(&path);
+    if !Transcriber::is_audio(path) {
+        return Err(format!("Unsupported audio type: {}", path.display()));
+    }
+    Transcriber::transcribe_file(&db, path, title, None, None)
+        .await
+        .map_err(|e| format!("Failed to transcribe audio: {}", e))
+}
+
+/// Transcribe the audio enclosure of an RSS item now instead of waiting
+/// for the background pass.
+#[tauri::command]
+pub async fn transcribe_podcast_episode(
+    article_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<TranscriptionResult, String> {
+    Transcriber::transcribe_episode(&db, article_id)
+        .await
+        .map_err(|e| format!("Failed to transcribe episode: {}", e))
+}
+
+#[tauri::command]
+pub fn list_transcripts(
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<Transcript>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TranscriptStore::new(db_guard.conn.clone());
+    store.list_transcripts(limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to list transcripts: {}", e))
+}
+
+#[tauri::command]
+pub fn get_transcript(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Option<TranscriptDetail>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TranscriptStore::new(db_guard.conn.clone());
+    let Some(transcript) = store.get_transcript(id).map_err(|e| format!("Failed to get transcript: {}", e))? else {
+        return Ok(None);
+    };
+    let segments = store.get_segments(id)
+        .map_err(|e| format!("Failed to get transcript segments: {}", e))?;
+    Ok(Some(TranscriptDetail { transcript, segments }))
+}
+
+#[tauri::command]
+pub fn delete_transcript(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = TranscriptStore::new(db_guard.conn.clone());
+    store.delete_transcript(id)
+        .map_err(|e| format!("Failed to delete transcript: {}", e))
+}
+
+/// Empty strings clear a setting; an unset whisper model disables transcription.
+#[tauri::command]
+pub fn set_transcription_settings(
+    whisper_model_path: Option<String>,
+    language: Option<String>,
+    ffmpeg_path: Option<String>,
+    podcasts_enabled: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    if let Some(path) = whisper_model_path.as_deref().filter(|p| !p.trim().is_empty()) {
+        if !std::path::Path::new(path).is_file() {
+            return Err(format!("Whisper model not found: {}", path));
+        }
+    }
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mut updates: Vec<(&str, String)> = Vec::new();
+    if let Some(path) = whisper_model_path {
+        updates.push((CONFIG_WHISPER_MODEL, path.trim().to_string()));
+    }
+    if let Some(language) = language {
+        updates.push((CONFIG_LANGUAGE, language.trim().to_lowercase()));
+    }
+    if let Some(ffmpeg) = ffmpeg_path {
+        updates.push((CONFIG_FFMPEG_PATH, ffmpeg.trim().to_string()));
+    }
+    if let Some(enabled) = podcasts_enabled {
+        updates.push((CONFIG_PODCASTS_ENABLED, enabled.to_string()));
+    }
+    for (key, value) in updates {
+        db_guard
+            .set_config(key, &value)
+            .map_err(|e| format!("Failed to save {}: {}", key, e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::services::updater::{PreviousInstall, UpdateInfo, Updater};
+use crate::storage::Database;
+use std::sync::Mutex;
+use tauri::State;
+
+/// Compare the running version with the release feed and return its notes.
+#[tauri::command]
+pub async fn check_for_updates(
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<UpdateInfo, String> {
+    Updater::check(db.inner(), &app)
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))
+}
+
+/// Download, verify and install the latest release. The running version is
+/// kept for `rollback_update`; `restart` relaunches into the new one.
+#[tauri::command]
+pub async fn apply_update(
+    restart: Option<bool>,
+    app: tauri::AppHandle,
+    db: State<'_, Mutex<Database>>,
+) -> Result<UpdateInfo, String> {
+    let info = Updater::apply(db.inner(), &app)
+        .await
+        .map_err(|e| format!("Failed to apply update: {}", e))?;
+    if restart.unwrap_or(false) {
+        app.restart();
+    }
+    Ok(info)
+}
+
+#[tauri::command]
+pub fn rollback_update(restart: Option<bool>, app: tauri::AppHandle) -> Result<PreviousInstall, String> {
+    let previous = Updater::rollback(&app).map_err(|e| format!("Failed to roll back: {}", e))?;
+    if restart.unwrap_or(false) {
+        app.restart();
+    }
+    Ok(previous)
+}
+
+/// The version an update replaced, if it can still be rolled back to.
+#[tauri::command]
+pub fn get_previous_version(app: tauri::AppHandle) -> Result<Option<PreviousInstall>, String> {
+    Updater::previous(&app).map_err(|e| format!("Failed to read previous version: {}", e))
+}
+
+#[tauri::command]
+pub fn set_update_settings(
+    feed_url: Option<String>,
+    auto_check: Option<bool>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    use crate::services::updater::{CONFIG_AUTO_CHECK, CONFIG_FEED_URL};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mut updates: Vec<(&str, String)> = Vec::new();
+    if let Some(url) = feed_url {
+        updates.push((CONFIG_FEED_URL, url.trim().to_string()));
+    }
+    if let Some(auto_check) = auto_check {
+        updates.push((CONFIG_AUTO_CHECK, auto_check.to_string()));
+    }
+    for (key, value) in updates {
+        db_guard
+            .set_config(key, &value)
+            .map_err(|e| format!("Failed to save update settings: {}", e))?;
+    }
+    Ok(())
+}
@@ -34,3 +34,96 @@ pub fn cleanup_expired_vectors(db: State<'_, Mutex<Database>>) -> Result<usize,
     store.delete_expired()
         .map_err(|e| format!("Failed to cleanup expired vectors: {}", e))
 }
+
+#[derive(Debug, serde::Serialize)]
+pub struct SimilarImage {
+    pub id: String,
+    pub source: String, // file path or URL
+    pub similarity: f32,
+    pub metadata: serde_json::Value,
+}
+
+fn image_embedder(db: &Mutex<Database>) -> Result<crate::services::embeddings::EmbeddingService, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let mut embedder = crate::services::embeddings::EmbeddingService::new();
+    embedder.load_image_config(&db_guard);
+    Ok(embedder)
+}
+
+/// Add an image file to the reverse image index.
+#[tauri::command]
+pub async fn index_image(path: String, db: State<'_, Mutex<Database>>) -> Result<String, String> {
+    let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let embedder = image_embedder(&db)?;
+    let embedding = embedder.generate_image(&bytes)
+        .await
+        .map_err(|e| format!("Failed to embed image: {}", e))?;
+    let id = crate::storage::vector_store::image_document_id(&bytes);
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = VectorStore::new(db_guard.conn.clone());
+    store.index_image(&id, &path, embedder.image_model(), embedding, serde_json::json!({ "kind": "file" }))
+        .map_err(|e| format!("Failed to index image: {}", e))?;
+    Ok(id)
+}
+
+/// Reverse image lookup against indexed article images and screenshots.
+/// The query image is a local file (`path`) or downloaded (`url`).
+#[tauri::command]
+pub async fn search_similar_images(
+    path: Option<String>,
+    url: Option<String>,
+    limit: Option<i32>,
+    min_similarity: Option<f32>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<SimilarImage>, String> {
+    let bytes = match (path, url) {
+        (Some(path), _) => std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?,
+        (None, Some(url)) => {
+            let client = reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(20))
+                .build()
+                .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            crate::services::article_ocr::fetch_image(&client, &url)
+                .await
+                .map_err(|e| format!("Failed to download {}: {}", url, e))?
+                .0
+        }
+        (None, None) => return Err("Either path or url is required".to_string()),
+    };
+    let embedder = image_embedder(&db)?;
+    let embedding = embedder.generate_image(&bytes)
+        .await
+        .map_err(|e| format!("Failed to embed image: {}", e))?;
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = VectorStore::new(db_guard.conn.clone());
+    let results = store
+        .search_similar_images(embedder.image_model(), &embedding, limit.unwrap_or(20).clamp(1, 200), min_similarity.unwrap_or(0.7))
+        .map_err(|e| format!("Failed to search images: {}", e))?;
+    Ok(results
+        .into_iter()
+        .map(|(doc, similarity)| SimilarImage {
+            id: doc.id,
+            source: doc.content,
+            similarity,
+            metadata: doc.metadata,
+        })
+        .collect())
+}
+
+/// CLIP server for image embeddings; None goes back to the local fingerprint.
+/// Images indexed with another model have to be re-indexed to be found.
+#[tauri::command]
+pub fn set_image_embedding_model(
+    base_url: Option<String>,
+    model: Option<String>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    use crate::services::embeddings::{CONFIG_IMAGE_EMBEDDING_MODEL, CONFIG_IMAGE_EMBEDDING_URL};
+
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    db_guard.set_config(CONFIG_IMAGE_EMBEDDING_URL, base_url.as_deref().unwrap_or("").trim())
+        .map_err(|e| format!("Failed to save image embedding URL: {}", e))?;
+    db_guard.set_config(CONFIG_IMAGE_EMBEDDING_MODEL, model.as_deref().unwrap_or("").trim())
+        .map_err(|e| format!("Failed to save image embedding model: {}", e))
+}
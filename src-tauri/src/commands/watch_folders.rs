@@ -0,0 +1,123 @@
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::watch_folders::{WatchFolders, WatchScanReport};
+use crate::storage::{Database, WatchFolder, WatchFolderFile, WatchFolderStore};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+#[tauri::command]
+pub fn list_watch_folders(
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<WatchFolder>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    WatchFolderStore::new(db_guard.conn.clone())
+        .list_folders()
+        .map_err(|e| format!("Failed to list watch folders: {}", e))
+}
+
+/// Watch `path` with `pipeline` (documents|portfolio_csv|json_events|ollama_models).
+/// portfolio_csv needs `{"portfolio_id": ...}` in `options`, plus an optional
+/// `"profile_id"` naming the statement mapping profile to import with.
+#[tauri::command]
+pub fn create_watch_folder(
+    path: String,
+    pipeline: String,
+    options: Option<serde_json::Value>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<i64, String> {
+    let options = options.unwrap_or_else(|| serde_json::json!({}));
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    WatchFolders::validate(&db_guard, &path, &pipeline, &options).map_err(|e| e.to_string())?;
+    WatchFolderStore::new(db_guard.conn.clone())
+        .create_folder(&path, &pipeline, &options)
+        .map_err(|e| format!("Failed to create watch folder: {}", e))
+}
+
+#[tauri::command]
+pub fn update_watch_folder(
+    id: i64,
+    path: String,
+    options: serde_json::Value,
+    enabled: bool,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    let store = WatchFolderStore::new(db_guard.conn.clone());
+    let folder = store
+        .get_folder(id)
+        .map_err(|e| format!("Failed to get watch folder: {}", e))?
+        .ok_or_else(|| format!("Watch folder {} not found", id))?;
+    WatchFolders::validate(&db_guard, &path, &folder.pipeline, &options).map_err(|e| e.to_string())?;
+    store
+        .update_folder(id, &path, &options, enabled)
+        .map_err(|e| format!("Failed to update watch folder: {}", e))
+}
+
+#[tauri::command]
+pub fn set_watch_folder_enabled(
+    id: i64,
+    enabled: bool,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    WatchFolderStore::new(db_guard.conn.clone())
+        .set_enabled(id, enabled)
+        .map_err(|e| format!("Failed to update watch folder: {}", e))
+}
+
+#[tauri::command]
+pub fn delete_watch_folder(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    WatchFolderStore::new(db_guard.conn.clone())
+        .delete_folder(id)
+        .map_err(|e| format!("Failed to delete watch folder: {}", e))
+}
+
+/// Files a folder has handled, newest first; `status` is processed|duplicate|failed.
+#[tauri::command]
+pub fn list_watch_folder_files(
+    folder_id: i64,
+    status: Option<String>,
+    limit: Option<i64>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<Vec<WatchFolderFile>, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    WatchFolderStore::new(db_guard.conn.clone())
+        .list_files(folder_id, status.as_deref(), limit.unwrap_or(100))
+        .map_err(|e| format!("Failed to list watch folder files: {}", e))
+}
+
+/// Forget a folder's failed files so the next scan retries them.
+#[tauri::command]
+pub fn retry_failed_watch_files(
+    folder_id: i64,
+    db: State<'_, Mutex<Database>>,
+) -> Result<usize, String> {
+    let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+    WatchFolderStore::new(db_guard.conn.clone())
+        .clear_failed(folder_id)
+        .map_err(|e| format!("Failed to reset failed files: {}", e))
+}
+
+/// Scan a folder now instead of waiting for the next scheduled scan.
+#[tauri::command]
+pub async fn scan_watch_folder(
+    id: i64,
+    db: State<'_, Mutex<Database>>,
+    api_key_manager: State<'_, Arc<APIKeyManager>>,
+    app: tauri::AppHandle,
+) -> Result<WatchScanReport, String> {
+    let folder = {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        WatchFolderStore::new(db_guard.conn.clone())
+            .get_folder(id)
+            .map_err(|e| format!("Failed to get watch folder: {}", e))?
+            .ok_or_else(|| format!("Watch folder {} not found", id))?
+    };
+    WatchFolders::scan(&db, &api_key_manager, Some(&app), &folder, &mut HashSet::new())
+        .await
+        .map_err(|e| format!("Failed to scan watch folder: {}", e))
+}
@@ -0,0 +1,59 @@
+use crate::services::window_router::WindowRouter;
+use crate::storage::windows::AppWindow;
+use tauri::State;
+
+fn valid_label(label: &str) -> bool {
+    !label.is_empty()
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '/' | ':'))
+}
+
+/// Open (or focus) a detached window showing `route`, e.g. an alerts
+/// window with `topics: ["price-alert", "alert-*"]`. Backend messages are
+/// only delivered to windows subscribed to their type.
+#[tauri::command]
+pub fn open_window(
+    label: String,
+    route: String,
+    title: Option<String>,
+    topics: Option<Vec<String>>,
+    app: tauri::AppHandle,
+    router: State<'_, WindowRouter>,
+) -> Result<AppWindow, String> {
+    if !valid_label(&label) {
+        return Err(format!("Invalid window label: {}", label));
+    }
+    let title = title.unwrap_or_else(|| format!("MINA - {}", label));
+    router
+        .open(&app, &label, &route, &title, topics.unwrap_or_default())
+        .map_err(|e| format!("Failed to open window: {}", e))
+}
+
+#[tauri::command]
+pub fn close_window(
+    label: String,
+    app: tauri::AppHandle,
+    router: State<'_, WindowRouter>,
+) -> Result<(), String> {
+    router
+        .close(&app, &label)
+        .map_err(|e| format!("Failed to close window: {}", e))
+}
+
+#[tauri::command]
+pub fn set_window_topics(
+    label: String,
+    topics: Vec<String>,
+    router: State<'_, WindowRouter>,
+) -> Result<AppWindow, String> {
+    router
+        .set_topics(&label, topics)
+        .map_err(|e| format!("Failed to set window topics: {}", e))
+}
+
+/// Known windows with their saved layout; `open` ones are restored at launch.
+#[tauri::command]
+pub fn list_windows(router: State<'_, WindowRouter>) -> Result<Vec<AppWindow>, String> {
+    Ok(router.list())
+}
@@ -1,6 +1,8 @@
-use crate::ws::WsServer;
-use std::sync::Mutex;
-use tauri::{State, Manager, Emitter};
+use crate::storage::Database;
+use crate::ws::{BroadcastIntervals, WsEncoding, WsEnvelope, WsMessage, WsServer};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{State, Manager};
 use uuid::Uuid;
 
 #[tauri::command]
@@ -32,10 +34,48 @@ pub fn publish_ws_message(
     server_guard.publish(&topic, msg)
 }
 
-/// Connect to WebSocket server and return connection ID
+/// The frontend event for a delivered message, with the sequence number and
+/// topic clients use to ask for replays.
+fn event_json(envelope: &WsEnvelope) -> serde_json::Value {
+    let (message_type, data) = match &envelope.message {
+        WsMessage::SystemMetrics(metrics) => ("system-metrics", serde_json::json!(metrics)),
+        WsMessage::ProcessUpdate(process) => ("process-update", serde_json::json!(process)),
+        WsMessage::ProcessBatch(processes) => ("process-batch", serde_json::json!(processes)),
+        WsMessage::NetworkUpdate(network) => ("network-update", serde_json::json!(network)),
+        WsMessage::Error(error) => ("error", serde_json::json!(error)),
+        WsMessage::ConfigUpdate { key, value } => ("config-update", serde_json::json!({ "key": key, "value": value })),
+        WsMessage::StockNews(news) => ("stock-news", serde_json::json!(news)),
+        WsMessage::StockNewsBatch(news_batch) => ("stock-news-batch", serde_json::json!(news_batch)),
+        WsMessage::MarketData(data) => ("market-data", serde_json::json!(data)),
+        WsMessage::MarketDataBatch(data_batch) => ("market-data-batch", serde_json::json!(data_batch)),
+        WsMessage::Message(msg) => ("message", serde_json::json!(msg)),
+        WsMessage::MessageTyping { conversation_id, sender } => {
+            ("message-typing", serde_json::json!({ "conversation_id": conversation_id, "sender": sender }))
+        }
+        WsMessage::OllamaModelState(state) => ("ollama-model-state", serde_json::json!(state)),
+        WsMessage::Alert(alert) => ("alert", alert.clone()),
+        WsMessage::Ping => ("ping", serde_json::json!(null)),
+        WsMessage::Pong => ("pong", serde_json::json!(null)),
+    };
+    serde_json::json!({
+        "type": message_type,
+        "data": data,
+        "seq": envelope.seq,
+        "topic": envelope.topic,
+        "timestamp": envelope.timestamp,
+    })
+}
+
+/// Connect to WebSocket server and return connection ID.
+///
+/// With `batch_ms` (or an `encoding` other than JSON, which batches every
+/// 250ms) messages are coalesced and delivered as one `ws-batch` event per
+/// window, its `data` encoded as requested.
 #[tauri::command]
 pub fn ws_connect(
     topics: Vec<String>,
+    encoding: Option<WsEncoding>,
+    batch_ms: Option<u64>,
     server: State<'_, Mutex<WsServer>>,
     app: tauri::AppHandle,
 ) -> Result<String, String> {
@@ -47,108 +87,19 @@ pub fn ws_connect(
     // Spawn a task to listen to messages from this connection and forward them to the frontend
     let app_handle = app.clone();
     let conn_id = connection_id.clone();
+    let encoding = encoding.unwrap_or_default();
+    let batch_ms = batch_ms.or((encoding != WsEncoding::Json).then_some(crate::ws::DEFAULT_BATCH_MS));
+    if let Some(batch_ms) = batch_ms {
+        tauri::async_runtime::spawn(forward_batches(app_handle, conn_id, receiver, encoding, batch_ms));
+        return Ok(connection_id);
+    }
     tauri::async_runtime::spawn(async move {
         let mut rx = receiver;
         loop {
             match rx.recv().await {
-                Ok(msg) => {
-                    // Convert WsMessage to the format expected by frontend
-                    let event_data = match msg {
-                        crate::ws::WsMessage::SystemMetrics(metrics) => {
-                            serde_json::json!({
-                                "type": "system-metrics",
-                                "data": metrics,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::ProcessUpdate(process) => {
-                            serde_json::json!({
-                                "type": "process-update",
-                                "data": process,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::NetworkUpdate(network) => {
-                            serde_json::json!({
-                                "type": "network-update",
-                                "data": network,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::Error(error) => {
-                            serde_json::json!({
-                                "type": "error",
-                                "data": error,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::ConfigUpdate { key, value } => {
-                            serde_json::json!({
-                                "type": "config-update",
-                                "data": { "key": key, "value": value },
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::StockNews(news) => {
-                            serde_json::json!({
-                                "type": "stock-news",
-                                "data": news,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::StockNewsBatch(news_batch) => {
-                            serde_json::json!({
-                                "type": "stock-news-batch",
-                                "data": news_batch,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::MarketData(data) => {
-                            serde_json::json!({
-                                "type": "market-data",
-                                "data": data,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::MarketDataBatch(data_batch) => {
-                            serde_json::json!({
-                                "type": "market-data-batch",
-                                "data": data_batch,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::Message(msg) => {
-                            serde_json::json!({
-                                "type": "message",
-                                "data": msg,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::MessageTyping { conversation_id, sender } => {
-                            serde_json::json!({
-                                "type": "message-typing",
-                                "data": { "conversation_id": conversation_id, "sender": sender },
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::Ping => {
-                            serde_json::json!({
-                                "type": "ping",
-                                "data": null,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                        crate::ws::WsMessage::Pong => {
-                            serde_json::json!({
-                                "type": "pong",
-                                "data": null,
-                                "timestamp": chrono::Utc::now().timestamp_millis(),
-                            })
-                        }
-                    };
-                    
+                Ok(envelope) => {
                     // Emit the message to the frontend
-                    let _ = app_handle.emit("ws-message", event_data);
+                    let _ = crate::services::window_router::emit(&app_handle, event_json(&envelope));
                 }
                 Err(tokio::sync::broadcast::error::RecvError::Closed) => {
                     // Channel closed, connection is being removed
@@ -165,6 +116,58 @@ pub fn ws_connect(
     Ok(connection_id)
 }
 
+/// Forward a connection's messages to the frontend in coalesced batches.
+/// Whatever is pending when the channel closes still goes out.
+async fn forward_batches(
+    app: tauri::AppHandle,
+    conn_id: String,
+    mut rx: tokio::sync::broadcast::Receiver<WsEnvelope>,
+    encoding: WsEncoding,
+    batch_ms: u64,
+) {
+    let mut pending: Vec<WsEnvelope> = Vec::new();
+    let mut window = tokio::time::interval(tokio::time::Duration::from_millis(batch_ms.max(10)));
+    loop {
+        tokio::select! {
+            received = rx.recv() => match received {
+                Ok(envelope) => pending.push(envelope),
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                    flush_batch(&app, &conn_id, &mut pending, encoding);
+                    break;
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    eprintln!("WebSocket connection {} lagged, skipped {} messages", conn_id, skipped);
+                }
+            },
+            _ = window.tick() => flush_batch(&app, &conn_id, &mut pending, encoding),
+        }
+    }
+}
+
+fn flush_batch(app: &tauri::AppHandle, conn_id: &str, pending: &mut Vec<WsEnvelope>, encoding: WsEncoding) {
+    if pending.is_empty() {
+        return;
+    }
+    let events: Vec<serde_json::Value> = crate::ws::coalesce(std::mem::take(pending))
+        .iter()
+        .map(event_json)
+        .collect();
+    let count = events.len();
+    match encoding.encode(events) {
+        Ok(data) => {
+            let _ = crate::services::window_router::emit(app, serde_json::json!({
+                "type": "ws-batch",
+                "connection_id": conn_id,
+                "encoding": encoding.as_str(),
+                "count": count,
+                "data": data,
+                "timestamp": chrono::Utc::now().timestamp_millis(),
+            }));
+        }
+        Err(e) => eprintln!("WebSocket connection {} batch dropped: {}", conn_id, e),
+    }
+}
+
 /// Subscribe to topics for an existing connection
 #[tauri::command]
 pub fn ws_subscribe(
@@ -225,3 +228,55 @@ pub fn ws_disconnect(
     Ok(())
 }
 
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WsReplayEvents {
+    /// Frontend events, oldest first
+    pub messages: Vec<serde_json::Value>,
+    pub latest_seq: u64,
+    /// False when some missed messages were already dropped from history;
+    /// the client should then refetch state instead
+    pub complete: bool,
+}
+
+/// Messages broadcast on `topics` after `since_seq`, so a reconnecting
+/// client can catch up without a full refetch.
+#[tauri::command]
+pub fn ws_replay(
+    topics: Vec<String>,
+    since_seq: u64,
+    server: State<'_, Mutex<WsServer>>,
+) -> Result<WsReplayEvents, String> {
+    let server_guard = server.lock().map_err(|e| format!("Server lock error: {}", e))?;
+    let replay = server_guard.replay(&topics, since_seq)
+        .map_err(|e| format!("Failed to replay messages: {}", e))?;
+    Ok(WsReplayEvents {
+        messages: replay.messages.iter().map(event_json).collect(),
+        latest_seq: replay.latest_seq,
+        complete: replay.complete,
+    })
+}
+
+#[tauri::command]
+pub fn get_ws_broadcast_intervals(
+    server: State<'_, Mutex<Arc<WsServer>>>,
+) -> Result<BroadcastIntervals, String> {
+    let server_guard = server.lock().map_err(|e| format!("Server lock error: {}", e))?;
+    Ok(server_guard.broadcast_intervals())
+}
+
+/// Change how often each data family is pushed. Applies to the running
+/// broadcast loops right away and is kept for the next launch.
+#[tauri::command]
+pub fn set_ws_broadcast_intervals(
+    intervals: BroadcastIntervals,
+    server: State<'_, Mutex<Arc<WsServer>>>,
+    db: State<'_, Mutex<Database>>,
+) -> Result<(), String> {
+    {
+        let db_guard = db.lock().map_err(|e| format!("Database lock error: {}", e))?;
+        intervals.save(&db_guard)?;
+    }
+    let server_guard = server.lock().map_err(|e| format!("Server lock error: {}", e))?;
+    server_guard.set_broadcast_intervals(intervals)
+}
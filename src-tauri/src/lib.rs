@@ -9,13 +9,14 @@ mod ws;
 mod utils;
 mod services;
 mod data;
+#[cfg(test)]
+mod test_support;
 
 use storage::Database;
 use storage::{RateLimitStore, TestingStore, AnalyticsStore, VectorStore, AIStore, AutomationStore, DevOpsStore, OSINTStore, TemporalStore, ProjectStore, MigrationTracker, StockNewsStore};
-use providers::{SystemProvider, NetworkProvider, ProcessProvider, HomebrewProvider, SystemUtilsProvider, OllamaProvider};
+use providers::{SystemProvider, NetworkProvider, ProcessProvider, HomebrewProvider, SystemUtilsProvider, DiskUsageProvider};
 use ws::WsServer;
 use std::path::PathBuf;
-use tokio::sync::RwLock;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,6 +90,8 @@ fn save_error(
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    services::crash_reporter::CrashReporter::install();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .setup(|app| {
@@ -98,6 +101,7 @@ pub fn run() {
             let db = match Database::new(app.handle()) {
                 Ok(db) => {
                     eprintln!("MINA: Database initialized successfully");
+                    services::crash_reporter::CrashReporter::attach_database(db.conn.clone());
                     db
                 }
                 Err(e) => {
@@ -111,11 +115,11 @@ pub fn run() {
             eprintln!("MINA: Initializing stores...");
             
             eprintln!("MINA: Initializing RateLimitStore...");
-            RateLimitStore::new(db.conn.clone())
+            let rate_limit_store = Arc::new(RateLimitStore::new(db.conn.clone())
                 .map_err(|e| {
                     eprintln!("MINA: Failed to initialize RateLimitStore: {}", e);
                     e
-                })?;
+                })?);
             eprintln!("MINA: RateLimitStore initialized");
             
             eprintln!("MINA: Initializing TestingStore...");
@@ -142,6 +146,9 @@ pub fn run() {
             let _ = VectorStore::new(db.conn.clone());
             eprintln!("MINA: VectorStore initialized");
             
+            // Profiles first: the AI, OSINT and temporal stores scope queries by them
+            let _ = storage::ProfileStore::new(db.conn.clone());
+
             eprintln!("MINA: Initializing AIStore...");
             if let Err(e) = AIStore::new(db.conn.clone()) {
                 eprintln!("WARNING: Failed to initialize AIStore: {}", e);
@@ -202,29 +209,20 @@ pub fn run() {
             }
             eprintln!("MINA: DevOpsStore initialized");
             
-            // Start health check service (HTTP endpoints for Database and Redis)
-            eprintln!("MINA: Starting health check service...");
-            let health_check_service = crate::services::HealthCheckService::new(5433);
-            let health_service_for_spawn = health_check_service.clone();
-            tauri::async_runtime::spawn(async move {
-                if let Err(e) = health_service_for_spawn.start().await {
-                    eprintln!("WARNING: Failed to start health check service: {}", e);
-                    eprintln!("Note: Database and Redis health checks will not be available via HTTP");
-                } else {
-                    eprintln!("MINA: Health check service started on port 5433");
-                    eprintln!("MINA: Database health check available at http://127.0.0.1:5433/health/database");
-                    eprintln!("MINA: Redis health check available at http://127.0.0.1:5433/health/redis");
-                }
-            });
-            app.manage(health_check_service);
-            
-            // Start health check monitoring
-            eprintln!("MINA: Starting health check monitoring...");
-            let db_for_health_checks = Arc::new(Mutex::new(Database {
+            // Health check service (HTTP endpoints for Database and Redis); started
+            // with the other optional subsystems below
+            let health_check_service = crate::services::HealthCheckService::new(
+                crate::services::health_check_service::PORT,
+                Arc::new(Mutex::new(Database { conn: db.conn.clone() })),
+            );
+            app.manage(health_check_service.clone());
+
+            // Clipboard history is opt-in; the monitor idles until enabled in config
+            let _ = storage::ClipboardStore::new(db.conn.clone());
+            let db_for_clipboard = Arc::new(Mutex::new(Database {
                 conn: db.conn.clone(),
             }));
-            crate::services::health_checker::HealthChecker::start_checking(db_for_health_checks);
-            eprintln!("MINA: Health check monitoring started");
+            crate::services::clipboard_monitor::ClipboardMonitor::start_monitoring(db_for_clipboard, app_handle.clone());
             
             eprintln!("MINA: Initializing OSINTStore...");
             // Initialize OSINTStore - this will create default feeds if needed
@@ -235,10 +233,48 @@ pub fn run() {
             eprintln!("MINA: Initializing TemporalStore...");
             let _ = TemporalStore::new(db.conn.clone());
             eprintln!("MINA: TemporalStore initialized");
+
+            // FX rates back multi-currency valuation and fx_close features
+            let _ = storage::FxStore::new(db.conn.clone());
             
             eprintln!("MINA: Initializing ProjectStore...");
             let _ = ProjectStore::new(db.conn.clone());
             eprintln!("MINA: ProjectStore initialized");
+
+            // After OSINTStore, so existing article folders carry over as tags
+            let _ = storage::TagStore::new(db.conn.clone());
+
+            let db_for_repo_monitor = Arc::new(Mutex::new(Database {
+                conn: db.conn.clone(),
+            }));
+            crate::services::repo_monitor::RepoMonitor::start_checking(db_for_repo_monitor, app_handle.clone());
+
+            let db_for_task_reminders = Arc::new(Mutex::new(Database {
+                conn: db.conn.clone(),
+            }));
+            crate::services::task_reminder::TaskReminder::start_checking(db_for_task_reminders, app_handle.clone());
+
+            let _ = storage::TimeTrackingStore::new(db.conn.clone());
+            let db_for_time_tracking = Arc::new(Mutex::new(Database {
+                conn: db.conn.clone(),
+            }));
+            crate::services::time_tracker::TimeTracker::start_tracking(db_for_time_tracking, app_handle.clone());
+
+            let _ = storage::BenchmarkStore::new(db.conn.clone());
+            let db_for_benchmarks = Arc::new(Mutex::new(Database {
+                conn: db.conn.clone(),
+            }));
+            crate::services::benchmark_tracker::BenchmarkTracker::start_watching(db_for_benchmarks, app_handle.clone());
+
+            let db_for_event_analyst = Arc::new(Mutex::new(Database {
+                conn: db.conn.clone(),
+            }));
+            crate::services::event_analyst::EventAnalyst::start_scheduler(db_for_event_analyst, app_handle.clone());
+
+            let db_for_article_ocr = Arc::new(Mutex::new(Database {
+                conn: db.conn.clone(),
+            }));
+            crate::services::article_ocr::ArticleOcr::start_scheduler(db_for_article_ocr, app_handle.clone());
             
             eprintln!("MINA: Initializing MigrationTracker...");
             let _ = MigrationTracker::new(db.conn.clone());
@@ -263,13 +299,50 @@ pub fn run() {
                 eprintln!("Warning: Failed to seed initial data: {}", e);
             }
             
+            // Databases from older builds can miss tables or columns; fix what
+            // ALTER TABLE can and report the rest for a guided rebuild
+            match storage::SchemaIntegrity::repair(&db.conn, &[]) {
+                Ok(report) => {
+                    let unresolved = report.unresolved();
+                    eprintln!(
+                        "MINA: Schema check: {} tables, {} drifts, {} unresolved",
+                        report.checked_tables,
+                        report.drifts.len(),
+                        unresolved.len()
+                    );
+                    for drift in unresolved {
+                        let message = format!(
+                            "{}{}: {} (needs {})",
+                            drift.table,
+                            drift.column.as_deref().map(|c| format!(".{}", c)).unwrap_or_default(),
+                            drift.kind,
+                            drift.repair
+                        );
+                        eprintln!("WARNING: Schema drift {}", message);
+                        let _ = db.save_error("Schema Drift", &message, drift.error.as_deref(), Some("SchemaIntegrity"), "warning");
+                    }
+                }
+                Err(e) => eprintln!("WARNING: Schema check failed: {}", e),
+            }
+
             eprintln!("MINA: Stores initialized");
             
+            // Startup profile decides which optional subsystems start now
+            let startup_profile = services::startup_profile::StartupProfile::load(&db);
+            eprintln!("MINA: Startup profile: {}", startup_profile.name);
+
             // Clone connections before moving db
             let db_conn_for_escalation = db.conn.clone();
-            let db_conn_for_streaming = db.conn.clone();
             let db_conn_for_price_alerts = db.conn.clone();
             
+            // Time every command from here on
+            app.manage(services::command_trace::CommandTracer::start(app.handle().clone(), db.conn.clone()));
+            app.manage(services::job_manager::JobManager::new(app.handle().clone(), db.conn.clone()));
+            // Route backend events to the windows subscribed to them
+            app.manage(services::window_router::WindowRouter::new(db.conn.clone()));
+            // Push store changes to the UI as invalidation events
+            services::change_feed::ChangeFeed::start(app.handle().clone());
+
             // Now manage the database (before it's used elsewhere)
             app.manage(Mutex::new(db));
             
@@ -284,32 +357,38 @@ pub fn run() {
             app.manage(std::sync::Mutex::new(ProcessProvider::new()));
             app.manage(tokio::sync::Mutex::new(HomebrewProvider::new()));
             app.manage(std::sync::Mutex::new(SystemUtilsProvider::new()));
+            app.manage(std::sync::Mutex::new(DiskUsageProvider::new()));
             
-            // Initialize Ollama provider
-            eprintln!("MINA: Initializing Ollama provider...");
-            if let Ok(app_data_dir) = app.path().app_data_dir() {
-                let models_folder = app_data_dir.join("models");
-                if let Err(e) = std::fs::create_dir_all(&models_folder) {
-                    eprintln!("WARNING: Failed to create models folder: {}", e);
-                } else {
-                    let ollama_provider = OllamaProvider::new(models_folder);
-                    let ollama_state = Arc::new(RwLock::new(ollama_provider));
-                    app.manage(ollama_state);
+            // Models folder for the Ollama provider
+            let models_folder = match app.path().app_data_dir() {
+                Ok(app_data_dir) => {
+                    let models_folder = app_data_dir.join("models");
+                    match std::fs::create_dir_all(&models_folder) {
+                        Ok(()) => Some(models_folder),
+                        Err(e) => {
+                            eprintln!("WARNING: Failed to create models folder: {}", e);
+                            None
+                        }
+                    }
                 }
-            } else {
-                eprintln!("WARNING: Failed to get app data dir for Ollama");
-            }
+                Err(_) => {
+                    eprintln!("WARNING: Failed to get app data dir for Ollama");
+                    None
+                }
+            };
             
             // Initialize WebSocket server
             eprintln!("MINA: Initializing WebSocket server...");
             let ws_server = Arc::new(WsServer::new());
-            ws_server.start_broadcast(app.handle().clone());
+            if let Err(e) = ws_server.set_broadcast_intervals(ws::BroadcastIntervals::load(&Database { conn: db_conn_for_price_alerts.clone() })) {
+                eprintln!("WARNING: Ignoring saved WebSocket broadcast intervals: {}", e);
+            }
             app.manage(Mutex::new(ws_server.clone()));
             
             // Initialize rate limiter
             eprintln!("MINA: Initializing rate limiter...");
-            let mut rate_limiter = services::rate_limiter::RateLimiter::new();
-            // Register rate limits for providers
+            let mut rate_limiter = services::rate_limiter::RateLimiter::with_store(rate_limit_store);
+            // Register default rate limits for providers (persisted buckets keep any edits)
             rate_limiter.register_limit("Yahoo Finance".to_string(), 100, 60); // 100 requests per minute
             rate_limiter.register_limit("Alpha Vantage".to_string(), 5, 60); // 5 requests per minute (free tier)
             rate_limiter.register_limit("Polygon.io".to_string(), 5, 60); // 5 requests per minute (free tier)
@@ -328,8 +407,16 @@ pub fn run() {
             
             // Initialize market data cache
             eprintln!("MINA: Initializing market data cache...");
-            let market_cache = services::market_cache::MarketDataCache::new();
+            let cache_ttls = services::market_cache::CacheTtls::load(&Database {
+                conn: db_conn_for_price_alerts.clone(),
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("WARNING: Failed to load market cache TTLs: {}", e);
+                services::market_cache::CacheTtls::default()
+            });
+            let market_cache = services::market_cache::MarketDataCache::with_ttls(cache_ttls);
             let cache_for_cleanup = Arc::new(market_cache.clone());
+            let cache_for_fundamentals = market_cache.clone();
             app.manage(Mutex::new(market_cache));
             
             // Start cache cleanup task
@@ -357,38 +444,158 @@ pub fn run() {
             // Initialize market data streamer
             eprintln!("MINA: Initializing market data streamer...");
             let market_streamer = Arc::new(services::market_data_stream::MarketDataStreamer::new(ws_server.clone()));
-            market_streamer.start_batching(app.handle().clone());
-            
-            // Use cloned connection for fetching loop
-            let db_for_streaming = Arc::new(Mutex::new(Database {
-                conn: db_conn_for_streaming,
-            }));
-            market_streamer.start_fetching_loop(Some(api_key_manager.clone()), db_for_streaming);
-            
-            app.manage(Mutex::new(market_streamer));
+            app.manage(Mutex::new(market_streamer.clone()));
             
             // Start alert escalation checker
             eprintln!("MINA: Starting alert escalation checker...");
+            services::notification_outbox::NotificationOutbox::start_worker(
+                db_for_escalation.clone(),
+                app.handle().clone(),
+            );
+            {
+                let (db, handle) = (db_for_escalation.clone(), app.handle().clone());
+                services::watchdog::Watchdog::on_restart("notification_outbox", move || {
+                    services::notification_outbox::NotificationOutbox::start_worker(db.clone(), handle.clone())
+                });
+            }
             services::alert_escalation_checker::AlertEscalationChecker::start_periodic_checks(
                 db_for_escalation,
                 app.handle().clone(),
             );
             let rate_limiter_for_alerts = Arc::new(Mutex::new((*rate_limiter_arc).clone()));
-            let db_for_price_alerts = Arc::new(Mutex::new(Database {
-                conn: db_conn_for_price_alerts,
+            let db_for_sync = Arc::new(Mutex::new(Database {
+                conn: db_conn_for_price_alerts.clone(),
             }));
-            
-            // Start price alert checker
-            eprintln!("MINA: Starting price alert checker...");
-            // Start price alert checker with event bus integration
-            services::price_alert_checker::PriceAlertChecker::start_checking(
-                db_for_price_alerts,
-                ws_server.clone(),
+            services::sync_client::SyncClient::start_scheduler(
+                db_for_sync,
+                api_key_manager.clone(),
+                app.handle().clone(),
+            );
+            let db_for_backups = Arc::new(Mutex::new(Database {
+                conn: db_conn_for_price_alerts.clone(),
+            }));
+            services::backup::BackupService::start_scheduler(
+                db_for_backups,
+                api_key_manager.clone(),
+                app.handle().clone(),
+            );
+
+            // Archive temporal events past the configured retention
+            services::event_retention::EventRetention::start_scheduler(Arc::new(Mutex::new(Database {
+                conn: db_conn_for_price_alerts.clone(),
+            })));
+
+            // Archive or delete old read articles when configured
+            services::article_cleanup::ArticleCleanup::start_scheduler(Arc::new(Mutex::new(Database {
+                conn: db_conn_for_price_alerts.clone(),
+            })));
+
+            // Move auto-tuned alert thresholds by their helpful/unhelpful labels
+            services::threshold_tuner::ThresholdTuner::start_scheduler(Arc::new(Mutex::new(Database {
+                conn: db_conn_for_price_alerts.clone(),
+            })));
+
+            // Weekly report on table sizes and growth
+            services::db_housekeeping::DbHousekeeping::start_scheduler(
+                Arc::new(Mutex::new(Database {
+                    conn: db_conn_for_price_alerts.clone(),
+                })),
+                app.handle().clone(),
+            );
+
+            // Export scheduled saved reports
+            services::report_builder::ReportBuilder::start_scheduler(Arc::new(Mutex::new(Database {
+                conn: db_conn_for_price_alerts.clone(),
+            })));
+
+            // Restart or alert on background loops that stop heartbeating
+            services::watchdog::Watchdog::start(
+                Arc::new(Mutex::new(Database {
+                    conn: db_conn_for_price_alerts.clone(),
+                })),
+                app.handle().clone(),
+            );
+
+            // Resolve alerts whose Jira/Linear issue was closed
+            services::issue_tracker::IssueTracker::start_scheduler(
+                Arc::new(Mutex::new(Database {
+                    conn: db_conn_for_price_alerts.clone(),
+                })),
+                api_key_manager.clone(),
+            );
+
+            // Pull MISP attributes on the configured interval
+            services::misp::MispService::start_scheduler(
+                Arc::new(Mutex::new(Database {
+                    conn: db_conn_for_price_alerts.clone(),
+                })),
                 api_key_manager.clone(),
-                rate_limiter_for_alerts,
+            );
+
+            // Feed files dropped into watch folders through their pipelines
+            services::watch_folders::WatchFolders::start_scheduler(
+                Arc::new(Mutex::new(Database {
+                    conn: db_conn_for_price_alerts.clone(),
+                })),
+                api_key_manager.clone(),
+                app.handle().clone(),
+            );
+
+            // Transcribe queued podcast episodes
+            services::transcription::Transcriber::start_scheduler(Arc::new(Mutex::new(Database {
+                conn: db_conn_for_price_alerts.clone(),
+            })));
+
+            // Keep the exported Obsidian vault in sync
+            services::vault_export::VaultExporter::start_scheduler(Arc::new(Mutex::new(Database {
+                conn: db_conn_for_price_alerts.clone(),
+            })));
+
+            // Crash reports left by earlier runs, when the user opted in
+            services::crash_reporter::CrashReporter::submit_pending(db_conn_for_price_alerts.clone());
+
+            // Enforce process resource limit policies
+            services::process_guardian::ProcessGuardian::start(
+                Arc::new(Mutex::new(Database {
+                    conn: db_conn_for_price_alerts.clone(),
+                })),
+                app.handle().clone(),
+            );
+
+            // Open the SSH tunnels marked to connect at startup
+            services::ssh_tunnels::TunnelManager::start_all(
+                Arc::new(Mutex::new(Database {
+                    conn: db_conn_for_price_alerts.clone(),
+                })),
                 app.handle().clone(),
-                Some(event_bus.clone()),
             );
+
+            // Daily release feed check, when enabled
+            services::updater::Updater::start_scheduler(
+                Arc::new(Mutex::new(Database {
+                    conn: db_conn_for_price_alerts.clone(),
+                })),
+                app.handle().clone(),
+            );
+
+            // Market data, health checks, Ollama and WS broadcast start now or
+            // on first use, depending on the startup profile
+            let subsystems = services::startup_profile::Subsystems::new(
+                startup_profile,
+                services::startup_profile::SubsystemDeps {
+                    conn: db_conn_for_price_alerts,
+                    ws_server: ws_server.clone(),
+                    market_streamer,
+                    api_key_manager: api_key_manager.clone(),
+                    rate_limiter: rate_limiter_for_alerts,
+                    market_cache: cache_for_fundamentals,
+                    event_bus: event_bus.clone(),
+                    health_check_service,
+                    models_folder,
+                },
+            );
+            subsystems.start_enabled(app.handle());
+            app.manage(subsystems);
             
             eprintln!("MINA: Setup complete, showing window...");
             
@@ -409,24 +616,37 @@ pub fn run() {
                 eprintln!("ERROR: Main window not found after setup!");
                 eprintln!("MINA: Available windows: {:?}", app.webview_windows().keys().collect::<Vec<_>>());
             }
+
+            // Saved layout and detached windows from the last session
+            app.state::<services::window_router::WindowRouter>().restore(app.handle());
             
             Ok(())
         })
         .on_window_event(|window, event| {
-            // Handle window events
-            if let tauri::WindowEvent::CloseRequested { .. } = event {
-                // Allow window to close
+            // Persist layout changes and windows closed by the user
+            if let Some(router) = window.try_state::<services::window_router::WindowRouter>() {
+                router.handle_event(window, event);
             }
         })
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(services::command_trace::instrument(services::startup_profile::lazy_init(tauri::generate_handler![
             commands::system::get_system_metrics,
             commands::network::get_network_interfaces,
             commands::network::get_network_connections,
             commands::process::get_processes,
             commands::process::get_process,
             commands::process::kill_process,
+            commands::process::list_process_policies,
+            commands::process::create_process_policy,
+            commands::process::update_process_policy,
+            commands::process::delete_process_policy,
+            commands::process::list_process_policy_actions,
             commands::config::get_config,
             commands::config::set_config,
+            commands::config::get_startup_profile,
+            commands::config::set_startup_profile,
+            commands::config::get_time_preferences,
+            commands::config::set_time_preferences,
+            commands::config::list_timezones,
             commands::ws::get_ws_connection_count,
             commands::ws::get_ws_topics,
             commands::ws::publish_ws_message,
@@ -435,6 +655,9 @@ pub fn run() {
             commands::ws::ws_unsubscribe,
             commands::ws::ws_get_connection_status,
             commands::ws::ws_disconnect,
+            commands::ws::ws_replay,
+            commands::ws::get_ws_broadcast_intervals,
+            commands::ws::set_ws_broadcast_intervals,
             commands::auth::set_pin,
             commands::auth::verify_pin,
             commands::auth::create_session,
@@ -453,10 +676,43 @@ pub fn run() {
             commands::vector_store::list_collections,
             commands::vector_store::get_collection_stats,
             commands::vector_store::cleanup_expired_vectors,
+            commands::vector_store::index_image,
+            commands::vector_store::search_similar_images,
+            commands::vector_store::set_image_embedding_model,
             commands::analytics::save_metric,
             commands::analytics::get_metrics,
             commands::analytics::get_statistics,
+            commands::analytics::get_slow_commands,
+            commands::analytics::set_slow_command_threshold,
             commands::analytics::test_analytics_collection,
+            commands::jobs::list_jobs,
+            commands::jobs::get_job,
+            commands::jobs::cancel_job,
+            commands::jobs::start_job,
+            commands::windows::open_window,
+            commands::windows::close_window,
+            commands::windows::set_window_topics,
+            commands::windows::list_windows,
+            commands::palette::search_palette,
+            commands::palette::record_palette_visit,
+            commands::palette::clear_palette_recents,
+            commands::updates::check_for_updates,
+            commands::updates::apply_update,
+            commands::updates::rollback_update,
+            commands::updates::get_previous_version,
+            commands::updates::set_update_settings,
+            commands::crash_reports::list_crash_reports,
+            commands::crash_reports::get_crash_report,
+            commands::crash_reports::delete_crash_report,
+            commands::crash_reports::submit_crash_report,
+            commands::crash_reports::set_crash_report_settings,
+            commands::demo::seed_demo_data,
+            commands::demo::clear_demo_data,
+            commands::market_calendar::list_market_exchanges,
+            commands::market_calendar::get_market_status,
+            commands::market_calendar::list_market_holidays,
+            commands::market_calendar::add_market_holiday,
+            commands::market_calendar::delete_market_holiday,
             commands::rate_limit::create_rate_limit_bucket,
             commands::rate_limit::list_rate_limit_buckets,
             commands::rate_limit::get_rate_limit_bucket,
@@ -464,9 +720,18 @@ pub fn run() {
             commands::rate_limit::refill_rate_limit_bucket,
             commands::migration::list_migrations,
             commands::migration::get_latest_migration_version,
+            commands::migration::check_schema_integrity,
+            commands::migration::repair_schema,
             commands::system_utils::get_disk_info,
             commands::system_utils::get_system_info,
             commands::system_utils::prevent_sleep,
+            commands::disk_usage::scan_disk_usage,
+            commands::disk_usage::clear_disk_usage_cache,
+            commands::clipboard::set_clipboard_history_enabled,
+            commands::clipboard::get_clipboard_history,
+            commands::clipboard::delete_clipboard_entry,
+            commands::clipboard::purge_clipboard_history,
+            commands::clipboard::detect_clipboard_secrets,
             commands::vector_search::search_vectors,
             commands::embeddings::generate_embedding,
             commands::ai::create_conversation,
@@ -476,6 +741,77 @@ pub fn run() {
             commands::ai::create_prompt_template,
             commands::ai::list_prompt_templates,
             commands::ai::get_prompt_template,
+            commands::ai::export_conversations,
+            commands::ai::archive_conversation,
+            commands::ai::archive_old_conversations,
+            commands::ai::list_archived_conversations,
+            commands::ai::restore_conversation,
+            commands::ai::import_prompt_templates,
+            commands::ai::export_prompt_templates,
+            commands::memory::set_memory_enabled,
+            commands::memory::extract_conversation_memories,
+            commands::memory::list_memories,
+            commands::memory::add_memory,
+            commands::memory::update_memory,
+            commands::memory::delete_memory,
+            commands::memory::search_memories,
+            commands::llm_usage::get_llm_usage,
+            commands::llm_usage::set_llm_budget,
+            commands::llm_usage::delete_llm_budget,
+            commands::llm_usage::get_redaction_settings,
+            commands::llm_usage::set_redaction_settings,
+            commands::llm_usage::preview_redaction,
+            commands::llm_usage::list_cloud_egress,
+            commands::llm_usage::get_llm_routing_policy,
+            commands::llm_usage::set_llm_routing_policy,
+            commands::llm_usage::get_llm_routing_report,
+            commands::llm_usage::list_llm_routes,
+            commands::profiles::list_profiles,
+            commands::profiles::create_profile,
+            commands::profiles::update_profile,
+            commands::profiles::delete_profile,
+            commands::profiles::set_profile_pin,
+            commands::profiles::switch_profile,
+            commands::profiles::export_profile,
+            commands::sync::set_sync_settings,
+            commands::sync::get_sync_status,
+            commands::sync::sync_now,
+            commands::backup::set_backup_settings,
+            commands::backup::run_backup_now,
+            commands::backup::list_remote_backups,
+            commands::backup::restore_backup_from_remote,
+            commands::object_storage::set_object_storage_settings,
+            commands::object_storage::upload_report_artifact,
+            commands::object_storage::upload_conversation_archive,
+            commands::market_data::get_market_cache_stats,
+            commands::market_data::list_market_cache_entries,
+            commands::market_data::invalidate_market_cache,
+            commands::market_data::get_market_cache_ttls,
+            commands::market_data::set_market_cache_ttls,
+            commands::market_data::get_fundamentals,
+            commands::market_data::list_fundamentals,
+            commands::market_data::refresh_fundamentals,
+            commands::market_data::search_symbols,
+            commands::market_data::get_market_signals,
+            commands::market_data::refresh_market_signals,
+            commands::fx::get_fx_rate,
+            commands::fx::get_fx_history,
+            commands::fx::convert_currency,
+            commands::portfolio::set_portfolio_base_currency,
+            commands::portfolio::set_holding_currency,
+            commands::portfolio::get_realized_gains,
+            commands::portfolio::export_tax_report,
+            commands::portfolio::set_target_allocations,
+            commands::portfolio::get_target_allocations,
+            commands::portfolio::get_allocation_drift,
+            commands::portfolio::suggest_rebalance_trades,
+            commands::scenarios::parse_scenario_shocks,
+            commands::scenarios::save_stress_scenario,
+            commands::scenarios::list_stress_scenarios,
+            commands::scenarios::delete_stress_scenario,
+            commands::scenarios::run_stress_scenario,
+            commands::scenarios::preview_stress_scenario,
+            commands::scenarios::get_scenario_runs,
             commands::automation::create_script,
             commands::automation::list_scripts,
             commands::automation::get_script,
@@ -495,20 +831,95 @@ pub fn run() {
             commands::devops::init_default_health_checks,
             commands::devops::fix_health_check_urls,
             commands::devops::check_health_check,
+            commands::devops::get_health_check_history,
+            commands::devops::get_health_check_uptime,
+            commands::devops::get_watchdog_status,
             commands::devops::create_alert,
             commands::devops::list_alerts,
             commands::devops::resolve_alert,
             commands::devops::save_prometheus_metric,
             commands::devops::get_prometheus_metrics,
+            commands::devops::list_remote_hosts,
+            commands::devops::create_remote_host,
+            commands::devops::update_remote_host,
+            commands::devops::delete_remote_host,
+            commands::devops::wake_remote_host,
+            commands::devops::ping_remote_host,
+            commands::devops::run_remote_host_command,
+            commands::devops::list_remote_host_actions,
+            commands::devops::list_ssh_tunnels,
+            commands::devops::create_ssh_tunnel,
+            commands::devops::update_ssh_tunnel,
+            commands::devops::delete_ssh_tunnel,
+            commands::devops::start_ssh_tunnel,
+            commands::devops::stop_ssh_tunnel,
             commands::osint::create_rss_feed,
             commands::osint::list_rss_feeds,
             commands::osint::update_rss_feed,
+            commands::osint::set_rss_feed_language,
+            commands::osint::detect_article_languages,
             commands::osint::delete_rss_feed,
             commands::osint::save_rss_item,
+            commands::osint::get_data_quality_report,
             commands::osint::get_recent_rss_items,
             commands::osint::create_entity,
             commands::osint::list_entities,
             commands::osint::create_entity_relationship,
+            commands::osint::list_iocs,
+            commands::osint::create_ioc,
+            commands::osint::delete_ioc,
+            commands::osint::export_stix_bundle,
+            commands::osint::import_stix_bundle,
+            commands::osint::get_misp_config,
+            commands::osint::set_misp_config,
+            commands::osint::push_to_misp,
+            commands::osint::pull_from_misp,
+            commands::osint::get_misp_sync_status,
+            commands::osint::ocr_article_images,
+            commands::osint::get_article_image_text,
+            commands::osint::set_article_ocr_settings,
+            commands::documents::ingest_document,
+            commands::documents::list_documents,
+            commands::documents::get_document,
+            commands::documents::delete_document,
+            commands::documents::set_document_watch_folder,
+            commands::documents::get_document_watch_folder,
+            commands::watch_folders::list_watch_folders,
+            commands::watch_folders::create_watch_folder,
+            commands::watch_folders::update_watch_folder,
+            commands::watch_folders::set_watch_folder_enabled,
+            commands::watch_folders::delete_watch_folder,
+            commands::watch_folders::list_watch_folder_files,
+            commands::watch_folders::retry_failed_watch_files,
+            commands::watch_folders::scan_watch_folder,
+            commands::statement_import::list_statement_mapping_profiles,
+            commands::statement_import::save_statement_mapping_profile,
+            commands::statement_import::delete_statement_mapping_profile,
+            commands::statement_import::export_statement_mapping_profile,
+            commands::statement_import::import_statement_mapping_profile,
+            commands::statement_import::detect_statement_mapping,
+            commands::statement_import::preview_statement_import,
+            commands::statement_import::commit_statement_import,
+            commands::statement_import::list_statement_imports,
+            commands::statement_import::undo_statement_import,
+            commands::db_housekeeping::get_db_housekeeping_settings,
+            commands::db_housekeeping::set_db_housekeeping_settings,
+            commands::db_housekeeping::get_db_housekeeping_report,
+            commands::db_housekeeping::run_db_housekeeping_now,
+            commands::db_housekeeping::list_db_housekeeping_reports,
+            commands::reports::list_report_tables,
+            commands::reports::list_saved_reports,
+            commands::reports::save_report,
+            commands::reports::delete_saved_report,
+            commands::reports::run_report,
+            commands::reports::export_report,
+            commands::reports::run_saved_report_now,
+            commands::transcripts::transcribe_audio,
+            commands::transcripts::transcribe_podcast_episode,
+            commands::transcripts::list_transcripts,
+            commands::transcripts::get_transcript,
+            commands::transcripts::delete_transcript,
+            commands::transcripts::set_transcription_settings,
             commands::osint::fetch_rss_feeds,
             commands::osint::get_rss_item,
             commands::osint::mark_article_read,
@@ -518,28 +929,77 @@ pub fn run() {
             commands::osint::create_article_folder,
             commands::osint::list_article_folders,
             commands::osint::delete_article_folder,
+            commands::osint::preview_article_cleanup,
+            commands::osint::cleanup_read_articles,
+            commands::osint::get_related_articles,
+            commands::tags::list_tags,
+            commands::tags::create_tag,
+            commands::tags::update_tag,
+            commands::tags::delete_tag,
+            commands::tags::attach_tag,
+            commands::tags::detach_tag,
+            commands::tags::get_object_tags,
+            commands::tags::list_tagged_objects,
+            commands::tags::get_tag_usage_stats,
             commands::osint::get_filtered_articles,
             commands::osint::get_article_entities,
             commands::osint::extract_entities_from_article,
+            commands::osint::reextract_entities,
             commands::osint::fetch_full_article,
             commands::temporal::temporal_list_events,
             commands::temporal::temporal_get_event,
+            commands::temporal::temporal_list_events_by_state,
+            commands::temporal::temporal_get_event_transitions,
+            commands::temporal::temporal_update_lifecycles,
             commands::temporal::temporal_list_event_evidence,
+            commands::temporal::temporal_list_summary_revisions,
+            commands::temporal::temporal_refresh_event_summary,
+            commands::temporal::temporal_archive_events,
+            commands::temporal::temporal_list_archived_events,
+            commands::temporal::temporal_get_archived_event,
+            commands::temporal::temporal_restore_event,
+            commands::temporal::temporal_get_storage_usage,
             commands::temporal::temporal_rebuild_events_mvp,
             commands::temporal::temporal_rebuild_search_index,
+            commands::temporal::get_event_languages,
+            commands::temporal::set_event_languages,
+            commands::temporal::get_search_index_settings,
+            commands::temporal::set_search_index_settings,
+            commands::temporal::reindex_search,
             commands::temporal::temporal_search,
             commands::temporal::temporal_list_watchlists,
             commands::temporal::temporal_create_watchlist,
             commands::temporal::temporal_add_watchlist_item,
+            commands::temporal::get_watchlist_analytics,
             commands::temporal::temporal_list_watchlist_items,
             commands::temporal::temporal_create_alert_rule,
             commands::temporal::temporal_list_alert_rules,
+            commands::temporal::temporal_export_alert_rules,
+            commands::temporal::temporal_import_alert_rules,
+            commands::temporal::temporal_list_alert_rule_templates,
+            commands::temporal::temporal_create_alert_rule_from_template,
+            commands::temporal::temporal_set_alert_rule_templates,
+            commands::temporal::temporal_preview_alert_message,
+            commands::temporal::temporal_test_rules,
             commands::temporal::temporal_list_alerts,
             commands::temporal::temporal_ack_alert,
             commands::temporal::temporal_snooze_alert,
             commands::temporal::temporal_resolve_alert,
+            commands::temporal::temporal_create_entity_override,
+            commands::temporal::temporal_list_entity_overrides,
+            commands::temporal::temporal_delete_entity_override,
+            commands::temporal::temporal_set_rule_tuning,
+            commands::temporal::temporal_get_rule_tuning,
+            commands::temporal::temporal_list_threshold_adjustments,
+            commands::temporal::temporal_revert_threshold_adjustment,
+            commands::temporal::temporal_tune_rule_now,
             commands::temporal::temporal_run_backtest_mvp,
             commands::temporal::temporal_get_entity_graph_mvp,
+            commands::temporal::temporal_event_price_report,
+            commands::temporal::get_alert_context,
+            commands::temporal::temporal_detect_volume_anomalies,
+            commands::temporal::temporal_get_entity_volume_profile,
+            commands::temporal::temporal_get_sentiment_divergence,
             commands::temporal::temporal_create_feature_definition,
             commands::temporal::temporal_list_feature_definitions,
             commands::temporal::temporal_compute_feature_mvp,
@@ -548,16 +1008,70 @@ pub fn run() {
             commands::temporal::temporal_get_alert_label,
             commands::temporal::escalate_alert,
             commands::temporal::get_alert_escalation_history,
+            commands::temporal::list_notification_outbox,
+            commands::temporal::list_dead_letters,
+            commands::temporal::retry_dead_letter,
+            commands::temporal::test_alert_channel,
+            commands::temporal::get_issue_tracker_config,
+            commands::temporal::set_issue_tracker_config,
+            commands::temporal::create_alert_issue,
+            commands::temporal::get_alert_issue,
+            commands::temporal::sync_alert_issues,
+            commands::temporal::temporal_get_event_commentary,
+            commands::temporal::temporal_run_event_analyst,
+            commands::temporal::temporal_get_analyst_briefing,
+            commands::temporal::temporal_set_event_analyst_settings,
             commands::testing::create_test_suite,
             commands::testing::list_test_suites,
             commands::testing::save_test_result,
             commands::testing::get_suite_results,
             commands::testing::get_suite_stats,
+            commands::testing::set_test_suite_command,
+            commands::testing::get_test_suite_command,
+            commands::testing::run_test_suite,
+            commands::testing::import_test_report,
+            commands::testing::list_test_runs,
+            commands::testing::detect_flaky_tests,
+            commands::testing::quarantine_test,
+            commands::testing::unquarantine_test,
+            commands::testing::list_quarantined_tests,
+            commands::benchmarks::record_benchmark_results,
+            commands::benchmarks::import_benchmark_output,
+            commands::benchmarks::list_benchmark_results,
+            commands::benchmarks::get_benchmark_trends,
+            commands::benchmarks::list_benchmark_regressions,
+            commands::benchmarks::acknowledge_benchmark_regression,
+            commands::benchmarks::set_benchmark_settings,
             commands::projects::create_project,
             commands::projects::update_project,
             commands::projects::list_projects,
             commands::projects::get_project,
             commands::projects::delete_project,
+            commands::projects::link_project_repo,
+            commands::projects::unlink_project_repo,
+            commands::projects::list_project_repos,
+            commands::projects::get_project_git_status,
+            commands::projects::get_project_recent_commits,
+            commands::projects::create_project_task,
+            commands::projects::update_project_task,
+            commands::projects::move_project_task,
+            commands::projects::delete_project_task,
+            commands::projects::get_project_task,
+            commands::projects::list_project_tasks,
+            commands::projects::get_project_board,
+            commands::projects::link_project_task,
+            commands::projects::unlink_project_task,
+            commands::time_tracking::start_time_tracking,
+            commands::time_tracking::stop_time_tracking,
+            commands::time_tracking::get_active_timers,
+            commands::time_tracking::list_time_entries,
+            commands::time_tracking::delete_time_entry,
+            commands::time_tracking::get_time_summary,
+            commands::time_tracking::export_time_entries_csv,
+            commands::time_tracking::add_time_tracking_rule,
+            commands::time_tracking::delete_time_tracking_rule,
+            commands::time_tracking::list_time_tracking_rules,
+            commands::time_tracking::set_time_tracking_settings,
             commands::ollama::check_ollama_status,
             commands::ollama::list_ollama_models,
             commands::ollama::get_ollama_model_info,
@@ -565,6 +1079,13 @@ pub fn run() {
             commands::ollama::chat_with_ollama,
             commands::ollama::scan_models_folder,
             commands::ollama::get_models_folder_path,
+            commands::ollama::run_ollama_benchmark,
+            commands::ollama::list_ollama_benchmarks,
+            commands::ollama::select_ollama_model,
+            commands::ollama::get_context_usage,
+            commands::ollama::get_ollama_model_states,
+            commands::ollama::get_ollama_lifecycle_settings,
+            commands::ollama::set_ollama_lifecycle_settings,
             commands::stock_news::get_stock_tickers,
             commands::stock_news::get_stock_news,
             commands::stock_news::search_stock_news,
@@ -578,6 +1099,11 @@ pub fn run() {
             commands::market_data::get_market_prices,
             commands::market_data::get_chart_data,
             commands::market_data::get_events_for_chart,
+            commands::market_data::list_chart_annotations,
+            commands::market_data::add_chart_annotation,
+            commands::market_data::pin_chart_marker,
+            commands::market_data::update_chart_annotation,
+            commands::market_data::delete_chart_annotation,
             commands::portfolio::create_portfolio,
             commands::portfolio::list_portfolios,
             commands::portfolio::get_portfolio,
@@ -588,10 +1114,12 @@ pub fn run() {
             commands::portfolio::delete_holding,
             commands::portfolio::get_portfolio_value,
             commands::portfolio::get_portfolio_performance_metrics,
+            commands::portfolio::get_portfolio_risk,
             commands::portfolio::get_portfolio_impact,
             commands::portfolio::add_transaction,
             commands::portfolio::list_transactions,
             commands::portfolio::delete_portfolio,
+            commands::portfolio::ask_analyst,
             commands::economic_calendar::create_economic_event,
             commands::economic_calendar::list_economic_events,
             commands::economic_calendar::get_economic_event,
@@ -599,6 +1127,9 @@ pub fn run() {
             commands::economic_calendar::record_event_outcome,
             commands::economic_calendar::get_event_impact_history,
             commands::economic_calendar::sync_economic_events,
+            commands::economic_calendar::export_calendar_ics,
+            commands::economic_calendar::set_calendar_feed_enabled,
+            commands::economic_calendar::get_calendar_feed_url,
             commands::messaging::messaging_create_conversation,
             commands::messaging::messaging_list_conversations,
             commands::messaging::send_message,
@@ -620,7 +1151,18 @@ pub fn run() {
             commands::notifications::send_alert_notification,
             commands::notifications::send_price_alert_notification,
             commands::data_export::export_data,
+            commands::data_export::export_research_vault,
+            commands::data_export::disable_research_vault_sync,
+            commands::data_export::export_citations,
             commands::grid_layouts::list_grid_layout_templates,
+            commands::dashboards::list_dashboard_layouts,
+            commands::dashboards::get_dashboard_layout,
+            commands::dashboards::create_dashboard_layout,
+            commands::dashboards::update_dashboard_layout,
+            commands::dashboards::delete_dashboard_layout,
+            commands::dashboards::set_default_dashboard_layout,
+            commands::dashboards::export_dashboard_layouts,
+            commands::dashboards::import_dashboard_layouts,
             commands::price_alerts::create_price_alert,
             commands::price_alerts::list_price_alerts,
             commands::price_alerts::get_price_alert,
@@ -628,7 +1170,7 @@ pub fn run() {
             commands::price_alerts::delete_price_alert,
             get_recent_errors,
             save_error
-        ])
+        ])))
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,27 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub mod webdav;
+pub mod s3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteFile {
+    pub name: String,
+    pub size: Option<u64>,
+    pub modified: Option<i64>,
+}
+
+/// Somewhere backups and other artifacts can be pushed to and fetched back from.
+#[async_trait]
+pub trait BackupDestination: Send + Sync {
+    fn get_name(&self) -> &str;
+    async fn upload(&self, name: &str, source: &Path) -> Result<()>;
+    async fn download(&self, name: &str, dest: &Path) -> Result<()>;
+    async fn list(&self) -> Result<Vec<RemoteFile>>;
+    async fn delete(&self, name: &str) -> Result<()>;
+}
+
+pub use webdav::WebDavDestination;
+pub use s3::S3Destination;
@@ -0,0 +1,321 @@
+use super::{BackupDestination, RemoteFile};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use regex::Regex;
+use reqwest::{Client, Method};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use tokio::io::AsyncReadExt;
+
+/// Files above this size are sent with multipart upload.
+const MULTIPART_THRESHOLD: u64 = 16 * 1024 * 1024;
+/// S3 requires parts of at least 5 MiB (except the last one).
+const PART_SIZE: usize = 8 * 1024 * 1024;
+const EMPTY_PAYLOAD_HASH: &str = "e3b0c44298fc1c149afbfc4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+/// An S3-compatible bucket (AWS, MinIO, ...), addressed path-style so it
+/// works with self-hosted endpoints without wildcard DNS.
+pub struct S3Destination {
+    client: Client,
+    endpoint: String,
+    bucket: String,
+    region: String,
+    prefix: String,
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+impl S3Destination {
+    pub fn new(
+        endpoint: &str,
+        bucket: &str,
+        region: &str,
+        prefix: &str,
+        access_key_id: String,
+        secret_access_key: String,
+    ) -> Self {
+        let prefix = prefix.trim_matches('/');
+        S3Destination {
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(600))
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            bucket: bucket.to_string(),
+            region: if region.is_empty() { "us-east-1".to_string() } else { region.to_string() },
+            prefix: if prefix.is_empty() { String::new() } else { format!("{}/", prefix) },
+            access_key_id,
+            secret_access_key,
+        }
+    }
+
+    /// Same bucket and credentials, with `sub` appended to the key prefix.
+    pub fn with_subprefix(&self, sub: &str) -> Self {
+        S3Destination {
+            client: self.client.clone(),
+            endpoint: self.endpoint.clone(),
+            bucket: self.bucket.clone(),
+            region: self.region.clone(),
+            prefix: format!("{}{}/", self.prefix, sub.trim_matches('/')),
+            access_key_id: self.access_key_id.clone(),
+            secret_access_key: self.secret_access_key.clone(),
+        }
+    }
+
+    pub fn key_for(&self, name: &str) -> String {
+        format!("{}{}", self.prefix, name)
+    }
+
+    fn host(&self) -> &str {
+        self.endpoint
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+    }
+
+    /// Send a SigV4-signed request. `key` of None addresses the bucket itself.
+    async fn send(
+        &self,
+        method: Method,
+        key: Option<&str>,
+        query: &[(&str, &str)],
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response> {
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_stamp = now.format("%Y%m%d").to_string();
+
+        let canonical_uri = match key {
+            Some(k) => format!("/{}/{}", uri_encode(&self.bucket, false), uri_encode(k, false)),
+            None => format!("/{}", uri_encode(&self.bucket, false)),
+        };
+        let mut sorted_query: Vec<(String, String)> = query
+            .iter()
+            .map(|(k, v)| (uri_encode(k, true), uri_encode(v, true)))
+            .collect();
+        sorted_query.sort();
+        let canonical_query = sorted_query
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let payload_hash = if body.is_empty() { EMPTY_PAYLOAD_HASH.to_string() } else { sha256_hex(&body) };
+        let canonical_headers = format!(
+            "host:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n",
+            self.host(), payload_hash, amz_date
+        );
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method.as_str(), canonical_uri, canonical_query, canonical_headers, signed_headers, payload_hash
+        );
+
+        let credential_scope = format!("{}/{}/s3/aws4_request", date_stamp, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date, credential_scope, sha256_hex(canonical_request.as_bytes())
+        );
+
+        let k_date = hmac_sha256(format!("AWS4{}", self.secret_access_key).as_bytes(), date_stamp.as_bytes())?;
+        let k_region = hmac_sha256(&k_date, self.region.as_bytes())?;
+        let k_service = hmac_sha256(&k_region, b"s3")?;
+        let k_signing = hmac_sha256(&k_service, b"aws4_request")?;
+        let signature = hex(&hmac_sha256(&k_signing, string_to_sign.as_bytes())?);
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, credential_scope, signed_headers, signature
+        );
+
+        let url = if canonical_query.is_empty() {
+            format!("{}{}", self.endpoint, canonical_uri)
+        } else {
+            format!("{}{}?{}", self.endpoint, canonical_uri, canonical_query)
+        };
+        let response = self.client
+            .request(method, url)
+            .header("Authorization", authorization)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .body(body)
+            .send()
+            .await
+            .context("Failed to reach object storage")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Object storage error: {} - {}", status, error_text);
+        }
+        Ok(response)
+    }
+
+    async fn upload_multipart(&self, key: &str, source: &Path) -> Result<()> {
+        let response = self.send(Method::POST, Some(key), &[("uploads", "")], Vec::new()).await?;
+        let body = response.text().await?;
+        let upload_id = Regex::new(r"<UploadId>(.*?)</UploadId>")?
+            .captures(&body)
+            .map(|c| c[1].to_string())
+            .ok_or_else(|| anyhow::anyhow!("Object storage did not return an upload id"))?;
+
+        match self.upload_parts(key, &upload_id, source).await {
+            Ok(etags) => {
+                let parts: String = etags
+                    .iter()
+                    .enumerate()
+                    .map(|(i, etag)| format!("<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>", i + 1, etag))
+                    .collect();
+                let complete = format!("<CompleteMultipartUpload>{}</CompleteMultipartUpload>", parts);
+                self.send(Method::POST, Some(key), &[("uploadId", upload_id.as_str())], complete.into_bytes()).await?;
+                Ok(())
+            }
+            Err(e) => {
+                // Abort so the bucket isn't left holding orphaned parts
+                let _ = self.send(Method::DELETE, Some(key), &[("uploadId", upload_id.as_str())], Vec::new()).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn upload_parts(&self, key: &str, upload_id: &str, source: &Path) -> Result<Vec<String>> {
+        let mut file = tokio::fs::File::open(source)
+            .await
+            .with_context(|| format!("Failed to open {}", source.display()))?;
+        let mut etags = Vec::new();
+        loop {
+            let mut chunk = Vec::with_capacity(PART_SIZE);
+            (&mut file).take(PART_SIZE as u64).read_to_end(&mut chunk).await?;
+            if chunk.is_empty() {
+                break;
+            }
+            let part_number = (etags.len() + 1).to_string();
+            let response = self
+                .send(Method::PUT, Some(key), &[("partNumber", part_number.as_str()), ("uploadId", upload_id)], chunk)
+                .await?;
+            let etag = response
+                .headers()
+                .get("ETag")
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| anyhow::anyhow!("Part {} upload returned no ETag", part_number))?
+                .to_string();
+            etags.push(etag);
+        }
+        Ok(etags)
+    }
+}
+
+#[async_trait]
+impl BackupDestination for S3Destination {
+    fn get_name(&self) -> &str {
+        "s3"
+    }
+
+    async fn upload(&self, name: &str, source: &Path) -> Result<()> {
+        let key = self.key_for(name);
+        let size = tokio::fs::metadata(source).await?.len();
+        if size > MULTIPART_THRESHOLD {
+            return self.upload_multipart(&key, source).await;
+        }
+        let data = tokio::fs::read(source)
+            .await
+            .with_context(|| format!("Failed to read {}", source.display()))?;
+        self.send(Method::PUT, Some(&key), &[], data).await?;
+        Ok(())
+    }
+
+    async fn download(&self, name: &str, dest: &Path) -> Result<()> {
+        let response = self.send(Method::GET, Some(&self.key_for(name)), &[], Vec::new()).await?;
+        let bytes = response.bytes().await?;
+        tokio::fs::write(dest, &bytes)
+            .await
+            .with_context(|| format!("Failed to write {}", dest.display()))?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<RemoteFile>> {
+        let contents_re = Regex::new(r"(?s)<Contents>(.*?)</Contents>")?;
+        let key_re = Regex::new(r"<Key>(.*?)</Key>")?;
+        let size_re = Regex::new(r"<Size>(\d+)</Size>")?;
+        let modified_re = Regex::new(r"<LastModified>(.*?)</LastModified>")?;
+        let truncated_re = Regex::new(r"<IsTruncated>true</IsTruncated>")?;
+        let token_re = Regex::new(r"<NextContinuationToken>(.*?)</NextContinuationToken>")?;
+
+        let mut files = Vec::new();
+        let mut continuation: Option<String> = None;
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", self.prefix.as_str())];
+            if let Some(token) = &continuation {
+                query.push(("continuation-token", token.as_str()));
+            }
+            let body = self.send(Method::GET, None, &query, Vec::new()).await?.text().await?;
+
+            for block in contents_re.captures_iter(&body) {
+                let block = &block[1];
+                let key = match key_re.captures(block) {
+                    Some(c) => c[1].replace("&amp;", "&"),
+                    None => continue,
+                };
+                let name = key.strip_prefix(&self.prefix).unwrap_or(&key);
+                // Objects in deeper "folders" belong to other subsystems
+                if name.is_empty() || name.contains('/') {
+                    continue;
+                }
+                files.push(RemoteFile {
+                    name: name.to_string(),
+                    size: size_re.captures(block).and_then(|c| c[1].parse().ok()),
+                    modified: modified_re
+                        .captures(block)
+                        .and_then(|c| chrono::DateTime::parse_from_rfc3339(&c[1]).ok())
+                        .map(|d| d.timestamp()),
+                });
+            }
+
+            continuation = if truncated_re.is_match(&body) {
+                token_re.captures(&body).map(|c| c[1].to_string())
+            } else {
+                None
+            };
+            if continuation.is_none() {
+                break;
+            }
+        }
+        Ok(files)
+    }
+
+    async fn delete(&self, name: &str) -> Result<()> {
+        self.send(Method::DELETE, Some(&self.key_for(name)), &[], Vec::new()).await?;
+        Ok(())
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key)
+        .map_err(|e| anyhow::anyhow!("HMAC error: {}", e))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// SigV4 URI encoding: everything but unreserved characters, and `/` too
+/// unless it separates path segments.
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(byte as char),
+            b'/' if !encode_slash => out.push('/'),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
@@ -0,0 +1,139 @@
+use super::{BackupDestination, RemoteFile};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use regex::Regex;
+use reqwest::{Client, Method, StatusCode};
+use std::path::Path;
+
+/// A WebDAV folder, e.g. `https://cloud.example.com/remote.php/dav/files/me/mina-backups`
+/// on Nextcloud. Credentials are sent as HTTP basic auth (use an app password).
+pub struct WebDavDestination {
+    client: Client,
+    base_url: String,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl WebDavDestination {
+    pub fn new(base_url: &str, username: Option<String>, password: Option<String>) -> Self {
+        WebDavDestination {
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(600))
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            base_url: format!("{}/", base_url.trim_end_matches('/')),
+            username,
+            password,
+        }
+    }
+
+    fn request(&self, method: Method, name: &str) -> reqwest::RequestBuilder {
+        let builder = self.client.request(method, format!("{}{}", self.base_url, name));
+        match &self.username {
+            Some(user) => builder.basic_auth(user, self.password.as_deref()),
+            None => builder,
+        }
+    }
+
+    /// Create the target folder; servers answer 405 when it already exists.
+    async fn ensure_folder(&self) -> Result<()> {
+        let mkcol = Method::from_bytes(b"MKCOL").context("Invalid WebDAV method")?;
+        let response = self.request(mkcol, "").send().await.context("Failed to reach WebDAV server")?;
+        match response.status() {
+            s if s.is_success() || s == StatusCode::METHOD_NOT_ALLOWED => Ok(()),
+            s => Err(anyhow::anyhow!("WebDAV MKCOL failed: {}", s)),
+        }
+    }
+}
+
+#[async_trait]
+impl BackupDestination for WebDavDestination {
+    fn get_name(&self) -> &str {
+        "webdav"
+    }
+
+    async fn upload(&self, name: &str, source: &Path) -> Result<()> {
+        self.ensure_folder().await?;
+        let data = tokio::fs::read(source)
+            .await
+            .with_context(|| format!("Failed to read {}", source.display()))?;
+        let response = self.request(Method::PUT, name).body(data).send().await
+            .context("Failed to upload to WebDAV server")?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("WebDAV upload of {} failed: {}", name, response.status()));
+        }
+        Ok(())
+    }
+
+    async fn download(&self, name: &str, dest: &Path) -> Result<()> {
+        let response = self.request(Method::GET, name).send().await
+            .context("Failed to download from WebDAV server")?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("WebDAV download of {} failed: {}", name, response.status()));
+        }
+        let bytes = response.bytes().await?;
+        tokio::fs::write(dest, &bytes)
+            .await
+            .with_context(|| format!("Failed to write {}", dest.display()))?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<RemoteFile>> {
+        let propfind = Method::from_bytes(b"PROPFIND").context("Invalid WebDAV method")?;
+        let response = self.request(propfind, "")
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml")
+            .body(r#"<?xml version="1.0"?><d:propfind xmlns:d="DAV:"><d:prop><d:getcontentlength/><d:getlastmodified/></d:prop></d:propfind>"#)
+            .send()
+            .await
+            .context("Failed to list WebDAV folder")?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("WebDAV PROPFIND failed: {}", response.status()));
+        }
+        parse_propfind(&response.text().await?)
+    }
+
+    async fn delete(&self, name: &str) -> Result<()> {
+        let response = self.request(Method::DELETE, name).send().await
+            .context("Failed to delete from WebDAV server")?;
+        if !response.status().is_success() && response.status() != StatusCode::NOT_FOUND {
+            return Err(anyhow::anyhow!("WebDAV delete of {} failed: {}", name, response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Pull file entries out of a multistatus response. Namespace prefixes vary
+/// between servers (`d:`, `D:`, none), so they're matched loosely.
+fn parse_propfind(xml: &str) -> Result<Vec<RemoteFile>> {
+    let response_re = Regex::new(r"(?s)<(?:\w+:)?response\b.*?</(?:\w+:)?response>")?;
+    let href_re = Regex::new(r"(?s)<(?:\w+:)?href>(.*?)</(?:\w+:)?href>")?;
+    let length_re = Regex::new(r"(?s)<(?:\w+:)?getcontentlength>(\d+)</(?:\w+:)?getcontentlength>")?;
+    let modified_re = Regex::new(r"(?s)<(?:\w+:)?getlastmodified>(.*?)</(?:\w+:)?getlastmodified>")?;
+
+    let mut files = Vec::new();
+    for block in response_re.find_iter(xml) {
+        let block = block.as_str();
+        let href = match href_re.captures(block) {
+            Some(caps) => caps[1].trim().to_string(),
+            None => continue,
+        };
+        // The folder itself and sub-folders end with a slash
+        if href.ends_with('/') {
+            continue;
+        }
+        let name = href.rsplit('/').next().unwrap_or(&href).replace("%20", " ");
+        files.push(RemoteFile {
+            name,
+            size: length_re.captures(block).and_then(|c| c[1].parse().ok()),
+            modified: modified_re
+                .captures(block)
+                .and_then(|c| chrono::DateTime::parse_from_rfc2822(c[1].trim()).ok())
+                .map(|d| d.timestamp()),
+        });
+    }
+    Ok(files)
+}
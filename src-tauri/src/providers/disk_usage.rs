@@ -0,0 +1,359 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskUsageNode {
+    pub path: String,
+    pub name: String,
+    pub size: u64,
+    pub file_count: u64,
+    pub is_dir: bool,
+    pub children: Vec<DiskUsageNode>,
+    pub truncated_children: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskScanOptions {
+    pub roots: Vec<String>,
+    pub exclusions: Vec<String>,
+    pub max_depth: usize,
+    pub top_n: usize,
+    pub full_rescan: bool,
+}
+
+impl Default for DiskScanOptions {
+    fn default() -> Self {
+        DiskScanOptions {
+            roots: Vec::new(),
+            exclusions: Vec::new(),
+            max_depth: 3,
+            top_n: 50,
+            full_rescan: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskScanProgress {
+    pub root: String,
+    pub current_path: String,
+    pub entries_scanned: u64,
+    pub bytes_scanned: u64,
+    pub cached_dirs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskScanResult {
+    pub roots: Vec<DiskUsageNode>,
+    pub total_size: u64,
+    pub entries_scanned: u64,
+    pub cached_dirs: u64,
+    pub duration_ms: u64,
+    pub errors: Vec<String>,
+}
+
+/// Direct contents of a directory as seen on the last scan. Reused as long as
+/// the directory's mtime is unchanged, which skips re-reading every file's
+/// metadata on incremental rescans. Cached unfiltered, so a rescan with other
+/// exclusions still sees everything.
+#[derive(Debug, Clone)]
+struct CachedDir {
+    mtime: SystemTime,
+    files: Vec<(String, u64)>,
+    subdirs: Vec<String>,
+}
+
+struct ScanState<'a> {
+    options: &'a DiskScanOptions,
+    root: String,
+    entries_scanned: u64,
+    bytes_scanned: u64,
+    cached_dirs: u64,
+    errors: Vec<String>,
+    on_progress: &'a dyn Fn(DiskScanProgress),
+}
+
+const PROGRESS_EVERY: u64 = 500;
+const MAX_REPORTED_ERRORS: usize = 100;
+
+#[derive(Clone)]
+pub struct DiskUsageProvider {
+    cache: Arc<Mutex<HashMap<PathBuf, CachedDir>>>,
+}
+
+impl DiskUsageProvider {
+    pub fn new() -> Self {
+        DiskUsageProvider {
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Walk every root and return a size-aggregated tree. Directory listings are
+    /// cached between scans; unchanged directories are not re-stat'ed unless
+    /// `full_rescan` is set. Note that a file growing in place does not bump its
+    /// parent directory's mtime, so a full rescan is needed to pick that up.
+    pub fn scan(&self, options: &DiskScanOptions, on_progress: &dyn Fn(DiskScanProgress)) -> Result<DiskScanResult, String> {
+        if options.roots.is_empty() {
+            return Err("No directories to scan".to_string());
+        }
+
+        if options.full_rescan {
+            self.clear_cache();
+        }
+
+        let started = std::time::Instant::now();
+        let mut roots = Vec::new();
+        let mut entries_scanned = 0u64;
+        let mut cached_dirs = 0u64;
+        let mut errors = Vec::new();
+
+        for root in &options.roots {
+            let root_path = PathBuf::from(root);
+            let meta = std::fs::symlink_metadata(&root_path)
+                .map_err(|e| format!("Cannot read {}: {}", root, e))?;
+            if !meta.is_dir() {
+                return Err(format!("{} is not a directory", root));
+            }
+
+            let mut state = ScanState {
+                options,
+                root: root.clone(),
+                entries_scanned: 0,
+                bytes_scanned: 0,
+                cached_dirs: 0,
+                errors: Vec::new(),
+                on_progress,
+            };
+            let node = self.scan_dir(&root_path, 0, &mut state);
+
+            on_progress(DiskScanProgress {
+                root: root.clone(),
+                current_path: root.clone(),
+                entries_scanned: state.entries_scanned,
+                bytes_scanned: state.bytes_scanned,
+                cached_dirs: state.cached_dirs,
+            });
+
+            entries_scanned += state.entries_scanned;
+            cached_dirs += state.cached_dirs;
+            errors.extend(state.errors);
+            roots.push(node);
+        }
+
+        roots.sort_by(|a, b| b.size.cmp(&a.size));
+        let total_size = roots.iter().map(|r| r.size).sum();
+        errors.truncate(MAX_REPORTED_ERRORS);
+
+        Ok(DiskScanResult {
+            roots,
+            total_size,
+            entries_scanned,
+            cached_dirs,
+            duration_ms: started.elapsed().as_millis() as u64,
+            errors,
+        })
+    }
+
+    pub fn clear_cache(&self) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.clear();
+        }
+    }
+
+    pub fn cached_dir_count(&self) -> usize {
+        self.cache.lock().map(|c| c.len()).unwrap_or(0)
+    }
+
+    fn scan_dir(&self, path: &Path, depth: usize, state: &mut ScanState<'_>) -> DiskUsageNode {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+
+        let mut node = DiskUsageNode {
+            path: path.to_string_lossy().to_string(),
+            name,
+            size: 0,
+            file_count: 0,
+            is_dir: true,
+            children: Vec::new(),
+            truncated_children: 0,
+        };
+
+        let listing = match self.list_dir(path, state) {
+            Ok(listing) => listing,
+            Err(e) => {
+                state.errors.push(format!("{}: {}", path.display(), e));
+                return node;
+            }
+        };
+
+        let keep_children = depth < state.options.max_depth;
+
+        for (file_name, size) in &listing.files {
+            node.size += size;
+            node.file_count += 1;
+            if keep_children {
+                node.children.push(DiskUsageNode {
+                    path: path.join(file_name).to_string_lossy().to_string(),
+                    name: file_name.clone(),
+                    size: *size,
+                    file_count: 1,
+                    is_dir: false,
+                    children: Vec::new(),
+                    truncated_children: 0,
+                });
+            }
+        }
+
+        for dir_name in &listing.subdirs {
+            let child = self.scan_dir(&path.join(dir_name), depth + 1, state);
+            node.size += child.size;
+            node.file_count += child.file_count;
+            if keep_children {
+                node.children.push(child);
+            }
+        }
+
+        node.children.sort_by(|a, b| b.size.cmp(&a.size));
+        if node.children.len() > state.options.top_n {
+            node.truncated_children = node.children.len() - state.options.top_n;
+            node.children.truncate(state.options.top_n);
+        }
+
+        node
+    }
+
+    /// The directory's contents minus this scan's exclusions.
+    fn list_dir(&self, path: &Path, state: &mut ScanState<'_>) -> Result<CachedDir, String> {
+        let mut listing = self.read_dir_cached(path, state)?;
+        let exclusions = &state.options.exclusions;
+        listing.files.retain(|(name, _)| !is_excluded(name, &path.join(name), exclusions));
+        listing.subdirs.retain(|name| !is_excluded(name, &path.join(name), exclusions));
+        self.record_entries(path, &listing, state);
+        Ok(listing)
+    }
+
+    fn read_dir_cached(&self, path: &Path, state: &mut ScanState<'_>) -> Result<CachedDir, String> {
+        let mtime = std::fs::symlink_metadata(path)
+            .and_then(|m| m.modified())
+            .map_err(|e| e.to_string())?;
+
+        if let Ok(cache) = self.cache.lock() {
+            if let Some(cached) = cache.get(path) {
+                if cached.mtime == mtime {
+                    state.cached_dirs += 1;
+                    return Ok(cached.clone());
+                }
+            }
+        }
+
+        let mut files = Vec::new();
+        let mut subdirs = Vec::new();
+
+        for entry in std::fs::read_dir(path).map_err(|e| e.to_string())? {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    state.errors.push(format!("{}: {}", path.display(), e));
+                    continue;
+                }
+            };
+            let entry_name = entry.file_name().to_string_lossy().to_string();
+            let entry_path = entry.path();
+
+            // symlink_metadata so links are never followed out of the scanned tree
+            let meta = match std::fs::symlink_metadata(&entry_path) {
+                Ok(meta) => meta,
+                Err(e) => {
+                    state.errors.push(format!("{}: {}", entry_path.display(), e));
+                    continue;
+                }
+            };
+
+            if meta.is_dir() {
+                subdirs.push(entry_name);
+            } else if meta.is_file() {
+                files.push((entry_name, meta.len()));
+            }
+        }
+
+        let listing = CachedDir { mtime, files, subdirs };
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(path.to_path_buf(), listing.clone());
+        }
+        Ok(listing)
+    }
+
+    fn record_entries(&self, path: &Path, listing: &CachedDir, state: &mut ScanState<'_>) {
+        let before = state.entries_scanned;
+        state.entries_scanned += (listing.files.len() + listing.subdirs.len()) as u64;
+        state.bytes_scanned += listing.files.iter().map(|(_, size)| size).sum::<u64>();
+
+        if state.entries_scanned / PROGRESS_EVERY != before / PROGRESS_EVERY {
+            (state.on_progress)(DiskScanProgress {
+                root: state.root.clone(),
+                current_path: path.to_string_lossy().to_string(),
+                entries_scanned: state.entries_scanned,
+                bytes_scanned: state.bytes_scanned,
+                cached_dirs: state.cached_dirs,
+            });
+        }
+    }
+}
+
+impl Default for DiskUsageProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Exclusion patterns: `*.ext` matches by suffix, anything containing a path
+/// separator matches as a path prefix, everything else matches the entry name.
+fn is_excluded(name: &str, path: &Path, exclusions: &[String]) -> bool {
+    exclusions.iter().any(|pattern| {
+        if let Some(suffix) = pattern.strip_prefix('*') {
+            name.ends_with(suffix)
+        } else if pattern.contains('/') || pattern.contains('\\') {
+            path.starts_with(pattern)
+        } else {
+            name == pattern
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rescan_applies_changed_exclusions_to_cached_dirs() {
+        let root = std::env::temp_dir().join(format!("mina-disk-usage-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("node_modules")).unwrap();
+        std::fs::write(root.join("node_modules").join("dep.js"), vec![0u8; 300]).unwrap();
+        std::fs::write(root.join("main.rs"), vec![0u8; 100]).unwrap();
+
+        let provider = DiskUsageProvider::new();
+        let mut options = DiskScanOptions {
+            roots: vec![root.to_string_lossy().to_string()],
+            exclusions: vec!["node_modules".to_string()],
+            ..DiskScanOptions::default()
+        };
+        let first = provider.scan(&options, &|_| {}).unwrap();
+        assert_eq!(first.total_size, 100);
+
+        options.exclusions.clear();
+        let second = provider.scan(&options, &|_| {}).unwrap();
+        assert!(second.cached_dirs > 0);
+        assert_eq!(second.total_size, 400);
+
+        options.exclusions = vec!["*.rs".to_string()];
+        let third = provider.scan(&options, &|_| {}).unwrap();
+        assert_eq!(third.total_size, 300);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
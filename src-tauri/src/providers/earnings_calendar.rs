@@ -0,0 +1,104 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EarningsDate {
+    pub symbol: String,
+    pub date: chrono::NaiveDate,
+    pub hour: Option<String>, // bmo (before market open), amc (after market close), dmh
+    pub eps_estimate: Option<f64>,
+    pub revenue_estimate: Option<f64>,
+    pub quarter: Option<i64>,
+    pub year: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FinnhubEarningsResponse {
+    #[serde(rename = "earningsCalendar", default)]
+    earnings_calendar: Vec<FinnhubEarning>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FinnhubEarning {
+    symbol: String,
+    date: String,
+    hour: Option<String>,
+    #[serde(rename = "epsEstimate")]
+    eps_estimate: Option<f64>,
+    #[serde(rename = "revenueEstimate")]
+    revenue_estimate: Option<f64>,
+    quarter: Option<i64>,
+    year: Option<i64>,
+}
+
+/// Upcoming earnings dates from Finnhub's earnings calendar.
+pub struct EarningsCalendarProvider {
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+impl EarningsCalendarProvider {
+    pub fn new(api_key: Option<String>) -> Self {
+        EarningsCalendarProvider {
+            api_key,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.api_key.is_some()
+    }
+
+    /// Earnings dates of `symbols` between the two dates, one request per symbol.
+    pub async fn fetch_earnings(
+        &self,
+        symbols: &[String],
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+    ) -> Result<Vec<EarningsDate>> {
+        let api_key = self.api_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Finnhub API key not configured"))?;
+
+        let mut result = Vec::new();
+        for symbol in symbols {
+            let url = format!(
+                "https://finnhub.io/api/v1/calendar/earnings?from={}&to={}&symbol={}&token={}",
+                from_date.format("%Y-%m-%d"),
+                to_date.format("%Y-%m-%d"),
+                urlencoding::encode(symbol),
+                api_key
+            );
+
+            let response = self.client
+                .get(&url)
+                .send()
+                .await
+                .context("Failed to send Finnhub earnings request")?;
+
+            if !response.status().is_success() {
+                anyhow::bail!("Finnhub earnings API error: {}", response.status());
+            }
+
+            let body: FinnhubEarningsResponse = response.json().await
+                .context("Failed to parse Finnhub earnings response")?;
+
+            for earning in body.earnings_calendar {
+                let Ok(date) = chrono::NaiveDate::parse_from_str(&earning.date, "%Y-%m-%d") else {
+                    continue;
+                };
+                result.push(EarningsDate {
+                    symbol: earning.symbol,
+                    date,
+                    hour: earning.hour.filter(|h| !h.is_empty()),
+                    eps_estimate: earning.eps_estimate,
+                    revenue_estimate: earning.revenue_estimate,
+                    quarter: earning.quarter,
+                    year: earning.year,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+}
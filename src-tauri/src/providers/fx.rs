@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// One daily reference rate: 1 `base` = `rate` `quote`. Dates are YYYY-MM-DD.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FxRate {
+    pub base: String,
+    pub quote: String,
+    pub date: String,
+    pub rate: f64,
+}
+
+#[async_trait]
+pub trait FxProvider: Send + Sync {
+    /// Rate on `date`, or the latest published rate when None. Providers that
+    /// skip weekends and holidays return the last rate before `date`.
+    async fn get_rate(&self, base: &str, quote: &str, date: Option<&str>) -> Result<FxRate>;
+    /// Daily rates between `from` and `to` inclusive, oldest first.
+    async fn get_history(&self, base: &str, quote: &str, from: &str, to: &str) -> Result<Vec<FxRate>>;
+    fn get_name(&self) -> &str;
+}
+
+#[derive(Debug, Deserialize)]
+struct FrankfurterSingle {
+    date: String,
+    rates: HashMap<String, f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FrankfurterSeries {
+    rates: HashMap<String, HashMap<String, f64>>,
+}
+
+/// ECB euro foreign exchange reference rates, served by the free
+/// frankfurter.app API (no key, one fixing per business day).
+pub struct EcbProvider {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl EcbProvider {
+    pub fn new() -> Self {
+        EcbProvider {
+            client: reqwest::Client::new(),
+            base_url: "https://api.frankfurter.app".to_string(),
+        }
+    }
+
+    async fn fetch<T: serde::de::DeserializeOwned>(&self, path: &str, base: &str, quote: &str) -> Result<T> {
+        let url = format!("{}/{}?from={}&to={}", self.base_url, path, base, quote);
+        let response = self.client
+            .get(&url)
+            .timeout(std::time::Duration::from_secs(20))
+            .send()
+            .await
+            .context("Failed to reach ECB rates API")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("ECB rates API error: {}", response.status());
+        }
+
+        response.json().await.context("Failed to parse ECB rates response")
+    }
+}
+
+impl Default for EcbProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FxProvider for EcbProvider {
+    async fn get_rate(&self, base: &str, quote: &str, date: Option<&str>) -> Result<FxRate> {
+        let single: FrankfurterSingle = self.fetch(date.unwrap_or("latest"), base, quote).await?;
+        let rate = single.rates
+            .get(quote)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("No {}/{} rate published", base, quote))?;
+        Ok(FxRate {
+            base: base.to_string(),
+            quote: quote.to_string(),
+            date: single.date,
+            rate,
+        })
+    }
+
+    async fn get_history(&self, base: &str, quote: &str, from: &str, to: &str) -> Result<Vec<FxRate>> {
+        let series: FrankfurterSeries = self.fetch(&format!("{}..{}", from, to), base, quote).await?;
+        let mut rates: Vec<FxRate> = series.rates
+            .into_iter()
+            .filter_map(|(date, rates)| {
+                rates.get(quote).map(|rate| FxRate {
+                    base: base.to_string(),
+                    quote: quote.to_string(),
+                    date,
+                    rate: *rate,
+                })
+            })
+            .collect();
+        rates.sort_by(|a, b| a.date.cmp(&b.date));
+        Ok(rates)
+    }
+
+    fn get_name(&self) -> &str {
+        "ECB"
+    }
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tokio::process::Command;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitRepoStatus {
+    pub repo_path: String,
+    pub branch: String,
+    pub upstream: Option<String>,
+    pub dirty: bool,
+    pub changed_files: usize,
+    pub ahead: Option<u32>,
+    pub behind: Option<u32>,
+    pub oldest_unpushed_at: Option<i64>,
+    pub last_commit_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub timestamp: i64,
+    pub subject: String,
+}
+
+pub struct GitProvider;
+
+impl GitProvider {
+    pub fn new() -> Self {
+        GitProvider
+    }
+
+    /// Check that `path` is inside a git work tree.
+    pub async fn is_repo(&self, path: &str) -> bool {
+        Self::run_git(path, &["rev-parse", "--is-inside-work-tree"])
+            .await
+            .map(|out| out.trim() == "true")
+            .unwrap_or(false)
+    }
+
+    pub async fn get_status(&self, path: &str) -> Result<GitRepoStatus, String> {
+        if !Path::new(path).is_dir() {
+            return Err(format!("{} is not a directory", path));
+        }
+        if !self.is_repo(path).await {
+            return Err(format!("{} is not a git repository", path));
+        }
+
+        let branch = Self::run_git(path, &["rev-parse", "--abbrev-ref", "HEAD"])
+            .await
+            .map(|out| out.trim().to_string())
+            .unwrap_or_else(|_| "HEAD".to_string());
+
+        let porcelain = Self::run_git(path, &["status", "--porcelain"]).await?;
+        let changed_files = porcelain.lines().filter(|l| !l.trim().is_empty()).count();
+
+        // No upstream is common for local-only branches; leave ahead/behind unknown
+        let upstream = Self::run_git(path, &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{upstream}"])
+            .await
+            .ok()
+            .map(|out| out.trim().to_string())
+            .filter(|s| !s.is_empty());
+
+        let (ahead, behind, oldest_unpushed_at) = if upstream.is_some() {
+            let counts = Self::run_git(path, &["rev-list", "--left-right", "--count", "@{upstream}...HEAD"]).await?;
+            let mut parts = counts.split_whitespace();
+            let behind = parts.next().and_then(|s| s.parse::<u32>().ok());
+            let ahead = parts.next().and_then(|s| s.parse::<u32>().ok());
+
+            let oldest = if ahead.unwrap_or(0) > 0 {
+                Self::run_git(path, &["log", "@{upstream}..HEAD", "--format=%ct"])
+                    .await
+                    .ok()
+                    .and_then(|out| out.lines().filter_map(|l| l.trim().parse::<i64>().ok()).min())
+            } else {
+                None
+            };
+            (ahead, behind, oldest)
+        } else {
+            (None, None, None)
+        };
+
+        let last_commit_at = Self::run_git(path, &["log", "-1", "--format=%ct"])
+            .await
+            .ok()
+            .and_then(|out| out.trim().parse::<i64>().ok());
+
+        Ok(GitRepoStatus {
+            repo_path: path.to_string(),
+            branch,
+            upstream,
+            dirty: changed_files > 0,
+            changed_files,
+            ahead,
+            behind,
+            oldest_unpushed_at,
+            last_commit_at,
+        })
+    }
+
+    pub async fn get_recent_commits(&self, path: &str, limit: usize) -> Result<Vec<GitCommit>, String> {
+        let limit_arg = format!("-n{}", limit.max(1));
+        let output = Self::run_git(path, &["log", &limit_arg, "--format=%H%x1f%an%x1f%ct%x1f%s"]).await?;
+
+        let commits = output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\u{1f}');
+                let hash = fields.next()?.to_string();
+                let author = fields.next()?.to_string();
+                let timestamp = fields.next()?.parse::<i64>().ok()?;
+                let subject = fields.next().unwrap_or("").to_string();
+                Some(GitCommit {
+                    short_hash: hash.chars().take(7).collect(),
+                    hash,
+                    author,
+                    timestamp,
+                    subject,
+                })
+            })
+            .collect();
+
+        Ok(commits)
+    }
+
+    async fn run_git(path: &str, args: &[&str]) -> Result<String, String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(path)
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| format!("Failed to execute git: {}", e))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+impl Default for GitProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::providers::market_data::{MarketDataProvider, MarketPriceData, OHLCVData};
+use crate::providers::market_data::{normalize_sector, FundamentalsData, MarketDataProvider, MarketPriceData, OHLCVData, PriceError, PriceResult, SymbolMatch};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 
@@ -83,23 +83,22 @@ impl MarketDataProvider for AlphaVantageProvider {
         }
     }
 
-    async fn get_prices(&self, tickers: &[String]) -> Result<Vec<MarketPriceData>> {
+    async fn get_prices(&self, tickers: &[String]) -> Vec<PriceResult> {
         // Alpha Vantage free tier only allows 5 calls per minute
         // So we'll fetch sequentially with delays
         let mut results = Vec::new();
         for ticker in tickers {
-            match self.get_price(ticker).await {
-                Ok(price) => results.push(price),
-                Err(e) => {
-                    eprintln!("Failed to fetch {} from Alpha Vantage: {}", ticker, e);
-                }
-            }
+            results.push(self.get_price(ticker).await.map_err(|e| PriceError::new(ticker, e)));
             // Rate limit: 1 request per 12 seconds (5 per minute)
             if tickers.len() > 1 {
                 tokio::time::sleep(tokio::time::Duration::from_secs(12)).await;
             }
         }
-        Ok(results)
+        results
+    }
+
+    fn max_batch_size(&self) -> usize {
+        5
     }
 
     async fn get_history(
@@ -199,6 +198,100 @@ impl MarketDataProvider for AlphaVantageProvider {
         }
     }
 
+    async fn get_fundamentals(&self, ticker: &str) -> Result<FundamentalsData> {
+        let url = format!(
+            "https://www.alphavantage.co/query?function=OVERVIEW&symbol={}&apikey={}",
+            ticker, self.api_key
+        );
+
+        let response = self.client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to send Alpha Vantage overview request")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Alpha Vantage API error: {}", response.status());
+        }
+
+        let json: serde_json::Value = response.json().await
+            .context("Failed to parse Alpha Vantage response")?;
+
+        if let Some(note) = json.get("Note").and_then(|v| v.as_str()) {
+            anyhow::bail!("Alpha Vantage API limit: {}", note);
+        }
+        // Unknown symbols come back as an empty object
+        if json.get("Symbol").is_none() {
+            anyhow::bail!("Alpha Vantage has no overview for {}", ticker);
+        }
+
+        // All values are strings, with "None" or "-" for missing data
+        let number = |field: &str| {
+            json.get(field)
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse::<f64>().ok())
+        };
+        let text = |field: &str| json.get(field).and_then(|v| v.as_str());
+
+        Ok(FundamentalsData {
+            ticker: ticker.to_string(),
+            name: text("Name").map(|s| s.to_string()),
+            sector: text("Sector").and_then(normalize_sector),
+            industry: text("Industry").and_then(normalize_sector),
+            pe_ratio: number("PERatio"),
+            eps: number("EPS"),
+            market_cap: number("MarketCapitalization"),
+            dividend_yield: number("DividendYield"),
+        })
+    }
+
+    async fn search_symbols(&self, query: &str) -> Result<Vec<SymbolMatch>> {
+        let response = self.client
+            .get("https://www.alphavantage.co/query")
+            .query(&[("function", "SYMBOL_SEARCH"), ("keywords", query), ("apikey", self.api_key.as_str())])
+            .send()
+            .await
+            .context("Failed to send Alpha Vantage symbol search request")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Alpha Vantage API error: {}", response.status());
+        }
+
+        let json: serde_json::Value = response.json().await
+            .context("Failed to parse Alpha Vantage response")?;
+
+        if let Some(note) = json.get("Note").and_then(|v| v.as_str()) {
+            anyhow::bail!("Alpha Vantage API limit: {}", note);
+        }
+        if let Some(error) = json.get("Error Message").and_then(|v| v.as_str()) {
+            anyhow::bail!("Alpha Vantage error: {}", error);
+        }
+
+        let matches = json
+            .get("bestMatches")
+            .and_then(|m| m.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Invalid Alpha Vantage symbol search format"))?;
+
+        let text = |m: &serde_json::Value, field: &str| {
+            m.get(field).and_then(|v| v.as_str()).map(|s| s.to_string())
+        };
+        Ok(matches
+            .iter()
+            .filter_map(|m| {
+                let symbol = text(m, "1. symbol")?;
+                Some(SymbolMatch {
+                    name: text(m, "2. name").unwrap_or_else(|| symbol.clone()),
+                    exchange: None,
+                    asset_type: text(m, "3. type"),
+                    region: text(m, "4. region"),
+                    currency: text(m, "8. currency"),
+                    provider: self.get_name().to_string(),
+                    symbol,
+                })
+            })
+            .collect())
+    }
+
     fn get_name(&self) -> &str {
         "Alpha Vantage"
     }
@@ -9,6 +9,7 @@ pub use polygon::PolygonProvider;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OHLCVData {
@@ -30,10 +31,124 @@ pub struct MarketPriceData {
     pub timestamp: i64,
 }
 
+/// Why one symbol of a batch has no price.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceError {
+    pub ticker: String,
+    pub message: String,
+}
+
+impl PriceError {
+    pub fn new(ticker: &str, error: impl std::fmt::Display) -> Self {
+        PriceError { ticker: ticker.to_string(), message: error.to_string() }
+    }
+}
+
+impl std::fmt::Display for PriceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.ticker, self.message)
+    }
+}
+
+impl std::error::Error for PriceError {}
+
+/// Outcome of one symbol in a batch price request.
+pub type PriceResult = std::result::Result<MarketPriceData, PriceError>;
+
+/// A price together with the provider that served it.
+#[derive(Debug, Clone)]
+pub struct SourcedPrice {
+    pub price: MarketPriceData,
+    pub provider: String,
+}
+
+/// Company fundamentals. Dividend yield is a fraction (0.012 = 1.2%).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundamentalsData {
+    pub ticker: String,
+    pub name: Option<String>,
+    pub sector: Option<String>,
+    pub industry: Option<String>,
+    pub pe_ratio: Option<f64>,
+    pub eps: Option<f64>,
+    pub market_cap: Option<f64>,
+    pub dividend_yield: Option<f64>,
+}
+
+/// One result of a provider's symbol lookup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolMatch {
+    pub symbol: String,
+    pub name: String,
+    pub exchange: Option<String>,
+    pub asset_type: Option<String>, // Equity, ETF, Fund, Index, ...
+    pub region: Option<String>,
+    pub currency: Option<String>,
+    pub provider: String,
+}
+
+/// Shares sold short as of one settlement date.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortInterestData {
+    pub ticker: String,
+    pub report_ts: i64,
+    pub shares_short: i64,
+    pub short_percent_float: Option<f64>, // fraction, 0.05 = 5%
+    pub days_to_cover: Option<f64>,
+}
+
+/// One analyst rating action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingChangeData {
+    pub ticker: String,
+    pub firm: String,
+    pub action: String, // upgrade|downgrade|initiate|maintain|reiterate
+    pub from_grade: Option<String>,
+    pub to_grade: Option<String>,
+    pub rated_at: i64,
+}
+
+/// Providers report sectors in different casing ("TECHNOLOGY" vs "Technology");
+/// normalize so allocation and rules group them together.
+pub fn normalize_sector(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("none") {
+        return None;
+    }
+    if value.chars().any(|c| c.is_lowercase()) {
+        return Some(value.to_string());
+    }
+    Some(
+        value
+            .split(' ')
+            .map(|word| {
+                let lower = word.to_lowercase();
+                let mut chars = lower.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
 #[async_trait]
 pub trait MarketDataProvider: Send + Sync {
     async fn get_price(&self, ticker: &str) -> Result<MarketPriceData>;
-    async fn get_prices(&self, tickers: &[String]) -> Result<Vec<MarketPriceData>>;
+    /// One result per ticker, in order; a failing symbol doesn't fail the others.
+    async fn get_prices(&self, tickers: &[String]) -> Vec<PriceResult>;
+    /// Most tickers the manager passes to one `get_prices` call.
+    fn max_batch_size(&self) -> usize {
+        25
+    }
+    /// HTTP requests one `get_prices` call for `tickers` symbols makes, which
+    /// is what the rate limiter is charged. One per ticker unless the provider
+    /// has a batch endpoint.
+    fn requests_for_batch(&self, tickers: usize) -> usize {
+        tickers
+    }
     async fn get_history(
         &self,
         ticker: &str,
@@ -41,12 +156,25 @@ pub trait MarketDataProvider: Send + Sync {
         to_ts: i64,
         interval: &str,
     ) -> Result<Vec<OHLCVData>>;
+    async fn get_fundamentals(&self, _ticker: &str) -> Result<FundamentalsData> {
+        anyhow::bail!("{} does not provide fundamentals", self.get_name())
+    }
+    async fn search_symbols(&self, _query: &str) -> Result<Vec<SymbolMatch>> {
+        anyhow::bail!("{} does not provide symbol search", self.get_name())
+    }
+    async fn get_short_interest(&self, _ticker: &str) -> Result<Vec<ShortInterestData>> {
+        anyhow::bail!("{} does not provide short interest", self.get_name())
+    }
+    async fn get_rating_changes(&self, _ticker: &str) -> Result<Vec<RatingChangeData>> {
+        anyhow::bail!("{} does not provide analyst ratings", self.get_name())
+    }
     fn get_name(&self) -> &str;
 }
 
 pub struct MarketDataManager {
     providers: Vec<Box<dyn MarketDataProvider>>,
     default_provider: usize,
+    last_provider: std::sync::Mutex<Option<String>>,
 }
 
 impl MarketDataManager {
@@ -73,6 +201,18 @@ impl MarketDataManager {
         MarketDataManager {
             providers,
             default_provider: 0, // Yahoo Finance is default
+            last_provider: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Name of the provider that served the most recent successful request.
+    pub fn last_provider(&self) -> Option<String> {
+        self.last_provider.lock().ok().and_then(|p| p.clone())
+    }
+
+    fn served_by(&self, provider_name: &str) {
+        if let Ok(mut last) = self.last_provider.lock() {
+            *last = Some(provider_name.to_string());
         }
     }
 
@@ -80,13 +220,11 @@ impl MarketDataManager {
         // Try default provider first
         let provider_name = self.providers[self.default_provider].get_name();
         if let Some(limiter) = rate_limiter {
-            limiter.wait_if_needed(provider_name).await;
+            limiter.acquire(provider_name).await;
         }
         match self.providers[self.default_provider].get_price(ticker).await {
             Ok(price) => {
-                if let Some(limiter) = rate_limiter {
-                    limiter.record_request(provider_name);
-                }
+                self.served_by(provider_name);
                 Ok(price)
             },
             Err(e) => {
@@ -95,12 +233,10 @@ impl MarketDataManager {
                 for provider in &self.providers {
                     let provider_name = provider.get_name();
                     if let Some(limiter) = rate_limiter {
-                        limiter.wait_if_needed(provider_name).await;
+                        limiter.acquire(provider_name).await;
                     }
                     if let Ok(price) = provider.get_price(ticker).await {
-                        if let Some(limiter) = rate_limiter {
-                            limiter.record_request(provider_name);
-                        }
+                        self.served_by(provider_name);
                         return Ok(price);
                     }
                 }
@@ -109,37 +245,85 @@ impl MarketDataManager {
         }
     }
 
+    /// Every price that could be fetched, in request order. Fails only
+    /// when not a single ticker could be priced.
     pub async fn get_prices(&self, tickers: &[String], rate_limiter: Option<&crate::services::rate_limiter::RateLimiter>) -> Result<Vec<MarketPriceData>> {
-        // Try default provider first
-        let provider_name = self.providers[self.default_provider].get_name();
-        if let Some(limiter) = rate_limiter {
-            limiter.wait_if_needed(provider_name).await;
+        let mut prices = Vec::new();
+        let mut last_error = None;
+        for result in self.get_prices_each(tickers, rate_limiter).await {
+            match result {
+                Ok(sourced) => prices.push(sourced.price),
+                Err(e) => {
+                    eprintln!("No price for {}", e);
+                    last_error = Some(e);
+                }
+            }
         }
-        match self.providers[self.default_provider].get_prices(tickers).await {
-            Ok(prices) => {
-                if let Some(limiter) = rate_limiter {
-                    limiter.record_request(provider_name);
+        match last_error {
+            Some(e) if prices.is_empty() => Err(e.into()),
+            _ => Ok(prices),
+        }
+    }
+
+    /// One result per ticker, in request order. Tickers go to the default
+    /// provider in chunks of its batch size; whatever it couldn't price is
+    /// asked of the other providers, so one delisted symbol only fails itself.
+    pub async fn get_prices_each(&self, tickers: &[String], rate_limiter: Option<&crate::services::rate_limiter::RateLimiter>) -> Vec<std::result::Result<SourcedPrice, PriceError>> {
+        let mut prices: HashMap<String, SourcedPrice> = HashMap::new();
+        let mut errors: HashMap<String, Vec<String>> = HashMap::new();
+        let order = std::iter::once(self.default_provider).chain((0..self.providers.len()).filter(|i| *i != self.default_provider));
+
+        for index in order {
+            let mut pending: Vec<String> = Vec::new();
+            for ticker in tickers {
+                if !prices.contains_key(&ticker.to_uppercase()) && !pending.contains(ticker) {
+                    pending.push(ticker.clone());
                 }
-                Ok(prices)
-            },
-            Err(e) => {
-                eprintln!("Default provider failed: {}, trying fallback", e);
-                // Try other providers
-                for provider in &self.providers {
-                    let provider_name = provider.get_name();
-                    if let Some(limiter) = rate_limiter {
-                        limiter.wait_if_needed(provider_name).await;
+            }
+            if pending.is_empty() {
+                break;
+            }
+
+            let provider = &self.providers[index];
+            let provider_name = provider.get_name();
+            for chunk in pending.chunks(provider.max_batch_size().max(1)) {
+                if let Some(limiter) = rate_limiter {
+                    for _ in 0..provider.requests_for_batch(chunk.len()) {
+                        limiter.acquire(provider_name).await;
                     }
-                    if let Ok(prices) = provider.get_prices(tickers).await {
-                        if let Some(limiter) = rate_limiter {
-                            limiter.record_request(provider_name);
+                }
+                let results = provider.get_prices(chunk).await;
+                for result in results {
+                    match result {
+                        Ok(price) => {
+                            self.served_by(provider_name);
+                            prices.insert(price.ticker.to_uppercase(), SourcedPrice { price, provider: provider_name.to_string() });
                         }
-                        return Ok(prices);
+                        Err(e) => errors
+                            .entry(e.ticker.to_uppercase())
+                            .or_default()
+                            .push(format!("{}: {}", provider_name, e.message)),
                     }
                 }
-                Err(e)
             }
         }
+
+        tickers
+            .iter()
+            .map(|ticker| {
+                let key = ticker.to_uppercase();
+                match prices.get(&key) {
+                    Some(price) => Ok(price.clone()),
+                    None => Err(PriceError {
+                        ticker: ticker.clone(),
+                        message: errors
+                            .get(&key)
+                            .map(|e| e.join("; "))
+                            .unwrap_or_else(|| "No provider returned a price".to_string()),
+                    }),
+                }
+            })
+            .collect()
     }
 
     pub async fn get_history(
@@ -153,16 +337,14 @@ impl MarketDataManager {
         // Try default provider first
         let provider_name = self.providers[self.default_provider].get_name();
         if let Some(limiter) = rate_limiter {
-            limiter.wait_if_needed(provider_name).await;
+            limiter.acquire(provider_name).await;
         }
         match self.providers[self.default_provider]
             .get_history(ticker, from_ts, to_ts, interval)
             .await
         {
             Ok(history) => {
-                if let Some(limiter) = rate_limiter {
-                    limiter.record_request(provider_name);
-                }
+                self.served_by(provider_name);
                 Ok(history)
             },
             Err(e) => {
@@ -171,12 +353,10 @@ impl MarketDataManager {
                 for provider in &self.providers {
                     let provider_name = provider.get_name();
                     if let Some(limiter) = rate_limiter {
-                        limiter.wait_if_needed(provider_name).await;
+                        limiter.acquire(provider_name).await;
                     }
                     if let Ok(history) = provider.get_history(ticker, from_ts, to_ts, interval).await {
-                        if let Some(limiter) = rate_limiter {
-                            limiter.record_request(provider_name);
-                        }
+                        self.served_by(provider_name);
                         return Ok(history);
                     }
                 }
@@ -184,5 +364,178 @@ impl MarketDataManager {
             }
         }
     }
+
+    pub async fn get_fundamentals(&self, ticker: &str, rate_limiter: Option<&crate::services::rate_limiter::RateLimiter>) -> Result<FundamentalsData> {
+        let mut last_error = None;
+        for provider in &self.providers {
+            let provider_name = provider.get_name();
+            if let Some(limiter) = rate_limiter {
+                limiter.acquire(provider_name).await;
+            }
+            match provider.get_fundamentals(ticker).await {
+                Ok(fundamentals) => {
+                    self.served_by(provider_name);
+                    return Ok(fundamentals);
+                }
+                Err(e) => {
+                    eprintln!("{} fundamentals failed for {}: {}", provider_name, ticker, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No provider available for fundamentals")))
+    }
+
+    /// Ask every provider that supports symbol search and merge the results,
+    /// first provider first. Fails only when no provider answered.
+    pub async fn search_symbols(&self, query: &str, rate_limiter: Option<&crate::services::rate_limiter::RateLimiter>) -> Result<Vec<SymbolMatch>> {
+        let mut matches: Vec<SymbolMatch> = Vec::new();
+        let mut answered = false;
+        let mut last_error = None;
+        for provider in &self.providers {
+            let provider_name = provider.get_name();
+            if let Some(limiter) = rate_limiter {
+                limiter.acquire(provider_name).await;
+            }
+            match provider.search_symbols(query).await {
+                Ok(found) => {
+                    answered = true;
+                    for m in found {
+                        if !matches.iter().any(|existing| existing.symbol.eq_ignore_ascii_case(&m.symbol)) {
+                            matches.push(m);
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{} symbol search failed for {}: {}", provider_name, query, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        if !answered {
+            return Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No provider available for symbol search")));
+        }
+        Ok(matches)
+    }
+
+    pub async fn get_short_interest(&self, ticker: &str, rate_limiter: Option<&crate::services::rate_limiter::RateLimiter>) -> Result<Vec<ShortInterestData>> {
+        let mut last_error = None;
+        for provider in &self.providers {
+            let provider_name = provider.get_name();
+            if let Some(limiter) = rate_limiter {
+                limiter.acquire(provider_name).await;
+            }
+            match provider.get_short_interest(ticker).await {
+                Ok(reports) => {
+                    self.served_by(provider_name);
+                    return Ok(reports);
+                }
+                Err(e) => {
+                    eprintln!("{} short interest failed for {}: {}", provider_name, ticker, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No provider available for short interest")))
+    }
+
+    pub async fn get_rating_changes(&self, ticker: &str, rate_limiter: Option<&crate::services::rate_limiter::RateLimiter>) -> Result<Vec<RatingChangeData>> {
+        let mut last_error = None;
+        for provider in &self.providers {
+            let provider_name = provider.get_name();
+            if let Some(limiter) = rate_limiter {
+                limiter.acquire(provider_name).await;
+            }
+            match provider.get_rating_changes(ticker).await {
+                Ok(changes) => {
+                    self.served_by(provider_name);
+                    return Ok(changes);
+                }
+                Err(e) => {
+                    eprintln!("{} analyst ratings failed for {}: {}", provider_name, ticker, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No provider available for analyst ratings")))
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Prices the tickers it knows, fails the rest, and records batch sizes.
+    struct FakeProvider {
+        name: &'static str,
+        known: Vec<&'static str>,
+        batches: std::sync::Arc<std::sync::Mutex<Vec<usize>>>,
+    }
+
+    #[async_trait]
+    impl MarketDataProvider for FakeProvider {
+        async fn get_price(&self, ticker: &str) -> Result<MarketPriceData> {
+            if !self.known.contains(&ticker) {
+                anyhow::bail!("unknown symbol");
+            }
+            Ok(MarketPriceData { ticker: ticker.to_string(), price: 1.0, change: 0.0, change_percent: 0.0, volume: 0, timestamp: 0 })
+        }
+
+        async fn get_prices(&self, tickers: &[String]) -> Vec<PriceResult> {
+            self.batches.lock().unwrap().push(tickers.len());
+            let mut results = Vec::new();
+            for ticker in tickers {
+                results.push(self.get_price(ticker).await.map_err(|e| PriceError::new(ticker, e)));
+            }
+            results
+        }
+
+        fn max_batch_size(&self) -> usize {
+            2
+        }
+
+        async fn get_history(&self, _ticker: &str, _from_ts: i64, _to_ts: i64, _interval: &str) -> Result<Vec<OHLCVData>> {
+            Ok(Vec::new())
+        }
+
+        fn get_name(&self) -> &str {
+            self.name
+        }
+    }
+
+    fn fake(name: &'static str, known: Vec<&'static str>, batches: &std::sync::Arc<std::sync::Mutex<Vec<usize>>>) -> Box<dyn MarketDataProvider> {
+        Box::new(FakeProvider { name, known, batches: batches.clone() })
+    }
+
+    #[tokio::test]
+    async fn one_bad_symbol_only_fails_itself() {
+        let primary_batches = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let backup_batches = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let manager = MarketDataManager {
+            providers: vec![
+                fake("primary", vec!["AAPL", "MSFT", "NVDA"], &primary_batches),
+                fake("backup", vec!["SAP"], &backup_batches),
+            ],
+            default_provider: 0,
+            last_provider: std::sync::Mutex::new(None),
+        };
+        let tickers: Vec<String> = ["AAPL", "DELISTED", "MSFT", "SAP", "NVDA"].iter().map(|t| t.to_string()).collect();
+
+        let results = manager.get_prices_each(&tickers, None).await;
+        let priced: Vec<bool> = results.iter().map(|r| r.is_ok()).collect();
+        assert_eq!(priced, vec![true, false, true, true, true]);
+        assert_eq!(results[0].as_ref().unwrap().provider, "primary");
+        assert_eq!(results[3].as_ref().unwrap().provider, "backup");
+        let error = results[1].as_ref().unwrap_err();
+        assert_eq!(error.ticker, "DELISTED");
+        assert!(error.message.contains("primary") && error.message.contains("backup"));
+
+        // Five tickers in chunks of two, then only the two misses go to the backup
+        assert_eq!(*primary_batches.lock().unwrap(), vec![2, 2, 1]);
+        assert_eq!(*backup_batches.lock().unwrap(), vec![2]);
+
+        let prices = manager.get_prices(&tickers, None).await.unwrap();
+        assert_eq!(prices.len(), 4);
+        assert!(manager.get_prices(&["GONE".to_string()], None).await.is_err());
+    }
+}
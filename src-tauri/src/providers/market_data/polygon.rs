@@ -1,4 +1,4 @@
-use crate::providers::market_data::{MarketDataProvider, MarketPriceData, OHLCVData};
+use crate::providers::market_data::{MarketDataProvider, MarketPriceData, OHLCVData, PriceError, PriceResult};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 
@@ -84,18 +84,13 @@ impl MarketDataProvider for PolygonProvider {
         }
     }
 
-    async fn get_prices(&self, tickers: &[String]) -> Result<Vec<MarketPriceData>> {
+    async fn get_prices(&self, tickers: &[String]) -> Vec<PriceResult> {
         // Polygon allows batch requests, but we'll do individual for simplicity
         let mut results = Vec::new();
         for ticker in tickers {
-            match self.get_price(ticker).await {
-                Ok(price) => results.push(price),
-                Err(e) => {
-                    eprintln!("Failed to fetch {} from Polygon: {}", ticker, e);
-                }
-            }
+            results.push(self.get_price(ticker).await.map_err(|e| PriceError::new(ticker, e)));
         }
-        Ok(results)
+        results
     }
 
     async fn get_history(
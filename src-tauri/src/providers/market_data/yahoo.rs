@@ -1,4 +1,7 @@
-use crate::providers::market_data::{MarketDataProvider, MarketPriceData, OHLCVData};
+use crate::providers::market_data::{
+    normalize_sector, FundamentalsData, MarketDataProvider, MarketPriceData, OHLCVData, PriceError, PriceResult, RatingChangeData,
+    ShortInterestData, SymbolMatch,
+};
 use anyhow::Result;
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -28,6 +31,32 @@ impl YahooFinanceProvider {
         true
     }
 
+    /// The first quoteSummary result for `ticker` with the given modules.
+    async fn quote_summary(&self, ticker: &str, modules: &str) -> Result<serde_json::Value> {
+        let url = format!(
+            "https://query2.finance.yahoo.com/v10/finance/quoteSummary/{}?modules={}",
+            ticker, modules
+        );
+
+        let response = self.client
+            .get(&url)
+            .header("User-Agent", "Mozilla/5.0")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to fetch {}: {}", modules, response.status());
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        json.get("quoteSummary")
+            .and_then(|q| q.get("result"))
+            .and_then(|r| r.as_array())
+            .and_then(|r| r.first())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Invalid Yahoo Finance {} response", modules))
+    }
+
     async fn fetch_yahoo_quote(&self, ticker: &str) -> Result<MarketPriceData> {
         let url = format!("https://query1.finance.yahoo.com/v8/finance/chart/{}", ticker);
         
@@ -82,17 +111,12 @@ impl MarketDataProvider for YahooFinanceProvider {
         self.fetch_yahoo_quote(ticker).await
     }
 
-    async fn get_prices(&self, tickers: &[String]) -> Result<Vec<MarketPriceData>> {
+    async fn get_prices(&self, tickers: &[String]) -> Vec<PriceResult> {
         let mut results = Vec::new();
         for ticker in tickers {
-            match self.get_price(ticker).await {
-                Ok(price) => results.push(price),
-                Err(e) => {
-                    eprintln!("Failed to fetch {} from Yahoo: {}", ticker, e);
-                }
-            }
+            results.push(self.get_price(ticker).await.map_err(|e| PriceError::new(ticker, e)));
         }
-        Ok(results)
+        results
     }
 
     async fn get_history(
@@ -169,6 +193,167 @@ impl MarketDataProvider for YahooFinanceProvider {
         anyhow::bail!("Invalid history response format")
     }
 
+    async fn get_fundamentals(&self, ticker: &str) -> Result<FundamentalsData> {
+        let url = format!(
+            "https://query2.finance.yahoo.com/v10/finance/quoteSummary/{}?modules=price,summaryDetail,defaultKeyStatistics,assetProfile",
+            ticker
+        );
+
+        let response = self.client
+            .get(&url)
+            .header("User-Agent", "Mozilla/5.0")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to fetch fundamentals: {}", response.status());
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        let result = json
+            .get("quoteSummary")
+            .and_then(|q| q.get("result"))
+            .and_then(|r| r.as_array())
+            .and_then(|r| r.first())
+            .ok_or_else(|| anyhow::anyhow!("Invalid Yahoo Finance fundamentals response"))?;
+
+        // Numeric fields come as { "raw": 12.3, "fmt": "12.30" }
+        let raw = |module: &str, field: &str| {
+            result.get(module)
+                .and_then(|m| m.get(field))
+                .and_then(|v| v.get("raw"))
+                .and_then(|v| v.as_f64())
+        };
+        let text = |module: &str, field: &str| {
+            result.get(module)
+                .and_then(|m| m.get(field))
+                .and_then(|v| v.as_str())
+        };
+
+        Ok(FundamentalsData {
+            ticker: ticker.to_string(),
+            name: text("price", "longName").or_else(|| text("price", "shortName")).map(|s| s.to_string()),
+            sector: text("assetProfile", "sector").and_then(normalize_sector),
+            industry: text("assetProfile", "industry").and_then(normalize_sector),
+            pe_ratio: raw("summaryDetail", "trailingPE"),
+            eps: raw("defaultKeyStatistics", "trailingEps"),
+            market_cap: raw("price", "marketCap").or_else(|| raw("summaryDetail", "marketCap")),
+            dividend_yield: raw("summaryDetail", "dividendYield"),
+        })
+    }
+
+    async fn get_short_interest(&self, ticker: &str) -> Result<Vec<ShortInterestData>> {
+        let result = self.quote_summary(ticker, "defaultKeyStatistics").await?;
+        let raw = |field: &str| {
+            result.get("defaultKeyStatistics")
+                .and_then(|m| m.get(field))
+                .and_then(|v| v.get("raw"))
+                .and_then(|v| v.as_f64())
+        };
+
+        // Yahoo reports the latest settlement and the one before it
+        let mut reports = Vec::new();
+        if let (Some(shares), Some(date)) = (raw("sharesShortPriorMonth"), raw("sharesShortPreviousMonthDate")) {
+            reports.push(ShortInterestData {
+                ticker: ticker.to_string(),
+                report_ts: date as i64,
+                shares_short: shares as i64,
+                short_percent_float: None,
+                days_to_cover: None,
+            });
+        }
+        if let (Some(shares), Some(date)) = (raw("sharesShort"), raw("dateShortInterest")) {
+            reports.push(ShortInterestData {
+                ticker: ticker.to_string(),
+                report_ts: date as i64,
+                shares_short: shares as i64,
+                short_percent_float: raw("shortPercentOfFloat"),
+                days_to_cover: raw("shortRatio"),
+            });
+        }
+        if reports.is_empty() {
+            anyhow::bail!("Yahoo Finance has no short interest for {}", ticker);
+        }
+        Ok(reports)
+    }
+
+    async fn get_rating_changes(&self, ticker: &str) -> Result<Vec<RatingChangeData>> {
+        let result = self.quote_summary(ticker, "upgradeDowngradeHistory").await?;
+        let history = result
+            .get("upgradeDowngradeHistory")
+            .and_then(|m| m.get("history"))
+            .and_then(|h| h.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Yahoo Finance has no analyst ratings for {}", ticker))?;
+
+        let text = |entry: &serde_json::Value, field: &str| {
+            entry.get(field)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        Ok(history
+            .iter()
+            .filter_map(|entry| {
+                let action = match text(entry, "action")?.as_str() {
+                    "up" => "upgrade",
+                    "down" => "downgrade",
+                    "init" => "initiate",
+                    "main" => "maintain",
+                    "reit" => "reiterate",
+                    _ => return None,
+                };
+                Some(RatingChangeData {
+                    ticker: ticker.to_string(),
+                    firm: text(entry, "firm")?,
+                    action: action.to_string(),
+                    from_grade: text(entry, "fromGrade"),
+                    to_grade: text(entry, "toGrade"),
+                    rated_at: entry.get("epochGradeDate").and_then(|v| v.as_i64())?,
+                })
+            })
+            .collect())
+    }
+
+    async fn search_symbols(&self, query: &str) -> Result<Vec<SymbolMatch>> {
+        let response = self.client
+            .get("https://query2.finance.yahoo.com/v1/finance/search")
+            .query(&[("q", query), ("quotesCount", "15"), ("newsCount", "0")])
+            .header("User-Agent", "Mozilla/5.0")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to search symbols: {}", response.status());
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        let quotes = json
+            .get("quotes")
+            .and_then(|q| q.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Invalid Yahoo Finance search response"))?;
+
+        let text = |quote: &serde_json::Value, field: &str| {
+            quote.get(field).and_then(|v| v.as_str()).map(|s| s.to_string())
+        };
+        Ok(quotes
+            .iter()
+            // News and other non-Yahoo results carry no quote data
+            .filter(|q| q.get("isYahooFinance").and_then(|v| v.as_bool()).unwrap_or(true))
+            .filter_map(|q| {
+                let symbol = text(q, "symbol")?;
+                Some(SymbolMatch {
+                    name: text(q, "longname").or_else(|| text(q, "shortname")).unwrap_or_else(|| symbol.clone()),
+                    exchange: text(q, "exchDisp").or_else(|| text(q, "exchange")),
+                    asset_type: text(q, "typeDisp").or_else(|| text(q, "quoteType")),
+                    region: None,
+                    currency: None,
+                    provider: self.get_name().to_string(),
+                    symbol,
+                })
+            })
+            .collect())
+    }
+
     fn get_name(&self) -> &str {
         "Yahoo Finance"
     }
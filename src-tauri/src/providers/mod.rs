@@ -4,9 +4,15 @@ pub mod process;
 pub mod homebrew;
 pub mod system_utils;
 pub mod ollama;
+pub mod openai;
 pub mod news;
 pub mod market_data;
 pub mod economic_calendar;
+pub mod disk_usage;
+pub mod git;
+pub mod backup;
+pub mod fx;
+pub mod earnings_calendar;
 
 pub use system::SystemProvider;
 pub use network::NetworkProvider;
@@ -14,4 +20,5 @@ pub use process::ProcessProvider;
 pub use homebrew::HomebrewProvider;
 pub use system_utils::SystemUtilsProvider;
 pub use ollama::{OllamaProvider, OllamaModel, ChatMessage};
+pub use disk_usage::DiskUsageProvider;
 
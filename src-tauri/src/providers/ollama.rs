@@ -34,6 +34,9 @@ struct ChatRequest {
     model: String,
     messages: Vec<ChatMessage>,
     stream: bool,
+    /// "json" or a JSON schema the output is constrained to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -46,6 +49,44 @@ pub struct ChatMessage {
 struct ChatResponse {
     message: Option<ChatMessageResponse>,
     done: bool,
+    prompt_eval_count: Option<i64>,
+    eval_count: Option<i64>,
+    /// Durations in nanoseconds
+    total_duration: Option<u64>,
+    load_duration: Option<u64>,
+    eval_duration: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ChatUsage {
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+}
+
+/// Token counts and timings Ollama reports for one chat.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ChatStats {
+    pub usage: ChatUsage,
+    pub total_ms: u64,
+    /// Time spent loading the model into memory, 0 when it was loaded
+    pub load_ms: u64,
+    /// Time spent generating the completion
+    pub eval_ms: u64,
+}
+
+impl ChatStats {
+    pub fn tokens_per_sec(&self) -> Option<f64> {
+        (self.eval_ms > 0).then(|| self.usage.completion_tokens as f64 * 1000.0 / self.eval_ms as f64)
+    }
+}
+
+/// A model currently loaded by Ollama.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningModel {
+    pub name: String,
+    pub size: u64,
+    #[serde(default)]
+    pub size_vram: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -150,6 +191,41 @@ impl OllamaProvider {
         Ok(info)
     }
 
+    /// The context window a model runs with: `num_ctx` from its Modelfile
+    /// when set, and the model's trained context length, if Ollama reports
+    /// them.
+    pub async fn context_window(&self, model_name: &str) -> Result<(Option<u64>, Option<u64>)> {
+        let url = format!("{}/api/show", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .json(&serde_json::json!({ "name": model_name }))
+            .send()
+            .await
+            .context("Failed to connect to Ollama")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Ollama API returned error: {}", response.status());
+        }
+
+        let show: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse model info")?;
+        let num_ctx = show["parameters"].as_str().and_then(|params| {
+            params.lines().find_map(|line| {
+                let mut parts = line.split_whitespace();
+                (parts.next() == Some("num_ctx")).then(|| parts.next()?.parse().ok()).flatten()
+            })
+        });
+        let trained = show["model_info"].as_object().and_then(|info| {
+            info.iter()
+                .find(|(key, _)| key.ends_with(".context_length"))
+                .and_then(|(_, value)| value.as_u64())
+        });
+        Ok((num_ctx, trained))
+    }
+
     pub async fn load_model_from_file(&self, model_path: &Path) -> Result<String> {
         // For HuggingFace models, we need to import them into Ollama
         // This requires the model to be in GGUF format or compatible format
@@ -183,12 +259,43 @@ impl OllamaProvider {
         model: &str,
         messages: Vec<ChatMessage>,
     ) -> Result<String> {
+        Ok(self.chat_with_usage(model, messages).await?.0)
+    }
+
+    /// Chat and also return the token counts Ollama reports.
+    pub async fn chat_with_usage(
+        &self,
+        model: &str,
+        messages: Vec<ChatMessage>,
+    ) -> Result<(String, ChatUsage)> {
+        let (content, stats) = self.chat_with_stats(model, messages).await?;
+        Ok((content, stats.usage))
+    }
+
+    /// Chat and also return the token counts and timings Ollama reports.
+    pub async fn chat_with_stats(
+        &self,
+        model: &str,
+        messages: Vec<ChatMessage>,
+    ) -> Result<(String, ChatStats)> {
+        self.chat_with_format(model, messages, None).await
+    }
+
+    /// Chat with sampling constrained to `format`, a JSON schema (or
+    /// "json" for any JSON), when given.
+    pub async fn chat_with_format(
+        &self,
+        model: &str,
+        messages: Vec<ChatMessage>,
+        format: Option<serde_json::Value>,
+    ) -> Result<(String, ChatStats)> {
         let url = format!("{}/api/chat", self.base_url);
         
         let request = ChatRequest {
             model: model.to_string(),
             messages: messages.clone(),
             stream: false,
+            format,
         };
 
         let response = self
@@ -211,10 +318,100 @@ impl OllamaProvider {
             .await
             .context("Failed to parse Ollama chat response")?;
 
-        Ok(chat_response
+        let nanos_to_ms = |ns: Option<u64>| ns.unwrap_or(0) / 1_000_000;
+        let stats = ChatStats {
+            usage: ChatUsage {
+                prompt_tokens: chat_response.prompt_eval_count.unwrap_or(0),
+                completion_tokens: chat_response.eval_count.unwrap_or(0),
+            },
+            total_ms: nanos_to_ms(chat_response.total_duration),
+            load_ms: nanos_to_ms(chat_response.load_duration),
+            eval_ms: nanos_to_ms(chat_response.eval_duration),
+        };
+        let content = chat_response
             .message
             .map(|m| m.content)
-            .unwrap_or_else(|| "No response from model".to_string()))
+            .unwrap_or_else(|| "No response from model".to_string());
+        Ok((content, stats))
+    }
+
+    /// Load a model into memory without generating anything, keeping it
+    /// loaded for `keep_alive` (e.g. "10m") after its last use.
+    pub async fn preload_model(&self, model: &str, keep_alive: &str) -> Result<()> {
+        let url = format!("{}/api/generate", self.base_url);
+        let response = self
+            .client
+            .post(&url)
+            .json(&serde_json::json!({ "model": model, "keep_alive": keep_alive }))
+            .send()
+            .await
+            .context("Failed to connect to Ollama")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Ollama API returned error: {}", response.status());
+        }
+        Ok(())
+    }
+
+    /// Models Ollama currently has loaded, with their memory footprint.
+    pub async fn running_models(&self) -> Result<Vec<RunningModel>> {
+        let url = format!("{}/api/ps", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to connect to Ollama")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Ollama API returned error: {}", response.status());
+        }
+
+        #[derive(Deserialize)]
+        struct PsResponse {
+            #[serde(default)]
+            models: Vec<RunningModel>,
+        }
+        let ps: PsResponse = response
+            .json()
+            .await
+            .context("Failed to parse Ollama response")?;
+        Ok(ps.models)
+    }
+
+    /// One-shot generation with images attached, for vision models such as
+    /// llava. Images are raw file bytes.
+    pub async fn generate_with_images(&self, model: &str, prompt: &str, images: &[Vec<u8>]) -> Result<String> {
+        use base64::Engine;
+
+        let url = format!("{}/api/generate", self.base_url);
+        let encoded: Vec<String> = images
+            .iter()
+            .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes))
+            .collect();
+        let response = self
+            .client
+            .post(&url)
+            .json(&serde_json::json!({
+                "model": model,
+                "prompt": prompt,
+                "images": encoded,
+                "stream": false,
+            }))
+            .send()
+            .await
+            .context("Failed to send generate request to Ollama")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Ollama API error: {} - {}", status, error_text);
+        }
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse Ollama generate response")?;
+        Ok(body["response"].as_str().unwrap_or_default().trim().to_string())
     }
 
 
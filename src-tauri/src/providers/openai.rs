@@ -0,0 +1,84 @@
+use crate::providers::ollama::{ChatMessage, ChatUsage};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: &'a [ChatMessage],
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatCompletionChoice>,
+    #[serde(default)]
+    usage: Option<CompletionUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChoice {
+    message: ChatMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionUsage {
+    prompt_tokens: i64,
+    completion_tokens: i64,
+}
+
+/// Chat completions against OpenAI or any server speaking its API.
+pub struct OpenAIChatProvider {
+    base_url: String,
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl OpenAIChatProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            base_url: OPENAI_BASE_URL.to_string(),
+            api_key,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub async fn chat(&self, model: &str, messages: &[ChatMessage]) -> Result<(String, ChatUsage)> {
+        let response = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url))
+            .bearer_auth(&self.api_key)
+            .json(&ChatCompletionRequest { model, messages })
+            .send()
+            .await
+            .context("Failed to send chat request to OpenAI")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("OpenAI API error: {} - {}", status, error_text);
+        }
+
+        let completion: ChatCompletionResponse = response
+            .json()
+            .await
+            .context("Failed to parse OpenAI chat response")?;
+        let usage = completion
+            .usage
+            .map(|u| ChatUsage { prompt_tokens: u.prompt_tokens, completion_tokens: u.completion_tokens })
+            .unwrap_or_default();
+        let content = completion
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.content)
+            .context("OpenAI returned no choices")?;
+        Ok((content, usage))
+    }
+}
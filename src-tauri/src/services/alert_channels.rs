@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use lettre::{
@@ -7,6 +8,43 @@ use lettre::{
     AsyncSmtpTransport, AsyncTransport, Tokio1Executor,
 };
 
+/// Longest provider response kept in a delivery status.
+const MAX_RESPONSE_CHARS: usize = 500;
+
+/// What the provider answered to a send.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeliveryStatus {
+    pub smtp_code: Option<u16>,
+    pub http_status: Option<u16>,
+    pub response: Option<String>,
+}
+
+/// A send the provider answered but rejected.
+#[derive(Debug)]
+pub struct ChannelError {
+    pub status: DeliveryStatus,
+    pub message: String,
+}
+
+impl std::fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ChannelError {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelTestResult {
+    pub channel: String,
+    pub success: bool,
+    pub smtp_code: Option<u16>,
+    pub http_status: Option<u16>,
+    pub response: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
+
 pub struct AlertChannelSender;
 
 impl AlertChannelSender {
@@ -17,73 +55,94 @@ impl AlertChannelSender {
         alert_message: &str,
         recipient: &str,
         config: Option<&Value>,
-    ) -> Result<()> {
-        // Check if SMTP config is available
+    ) -> Result<DeliveryStatus> {
         if let Some(config) = config {
-            if let (Some(smtp_host), Some(smtp_port), Some(smtp_user), Some(smtp_pass)) = (
-                config.get("smtp_host").and_then(|v| v.as_str()),
-                config.get("smtp_port").and_then(|v| v.as_u64()),
-                config.get("smtp_user").and_then(|v| v.as_str()),
-                config.get("smtp_pass").and_then(|v| v.as_str()),
-            ) {
-                // Parse recipient email
-                let to_email: Mailbox = recipient
-                    .parse()
-                    .context(format!("Invalid recipient email address: {}", recipient))?;
-                
-                // Parse from email (use smtp_user or default)
-                let from_email: Mailbox = smtp_user
-                    .parse()
-                    .context(format!("Invalid sender email address: {}", smtp_user))?;
-                
-                // Build email message
-                let email = Message::builder()
-                    .from(from_email)
-                    .to(to_email)
-                    .subject(alert_title)
-                    .singlepart(
-                        SinglePart::builder()
-                            .header(ContentType::TEXT_PLAIN)
-                            .body(alert_message.to_string()),
-                    )
-                    .context("Failed to build email message")?;
-                
-                // Create SMTP transport
-                let smtp_port_u16 = smtp_port.min(u16::MAX as u64) as u16;
-                
-                // Determine if we need TLS (check config or default to STARTTLS)
-                let use_tls = config
-                    .get("use_tls")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(true);
-                
-                let mailer = if use_tls {
-                    AsyncSmtpTransport::<Tokio1Executor>::relay(smtp_host)
-                        .context(format!("Failed to create SMTP relay for {}", smtp_host))?
-                        .port(smtp_port_u16)
-                        .credentials(Credentials::new(smtp_user.to_string(), smtp_pass.to_string()))
-                        .build()
-                } else {
-                    // For non-TLS (not recommended but supported)
-                    AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(smtp_host)
-                        .port(smtp_port_u16)
-                        .credentials(Credentials::new(smtp_user.to_string(), smtp_pass.to_string()))
-                        .build()
-                };
-                
-                // Send email
-                mailer
-                    .send(email)
-                    .await
-                    .context("Failed to send email via SMTP")?;
-                
-                return Ok(());
+            if let Some(status) = Self::send_smtp(alert_title, alert_message, recipient, config).await? {
+                return Ok(status);
             }
         }
         
         // Fallback: log email if no SMTP config
         eprintln!("[EMAIL] No SMTP config provided. To: {}, Subject: {}, Body: {}", recipient, alert_title, alert_message);
-        Ok(())
+        Ok(DeliveryStatus::default())
+    }
+
+    /// Send through the configured SMTP server. `None` when the config lacks SMTP settings.
+    async fn send_smtp(
+        alert_title: &str,
+        alert_message: &str,
+        recipient: &str,
+        config: &Value,
+    ) -> Result<Option<DeliveryStatus>> {
+        if let (Some(smtp_host), Some(smtp_port), Some(smtp_user), Some(smtp_pass)) = (
+            config.get("smtp_host").and_then(|v| v.as_str()),
+            config.get("smtp_port").and_then(|v| v.as_u64()),
+            config.get("smtp_user").and_then(|v| v.as_str()),
+            config.get("smtp_pass").and_then(|v| v.as_str()),
+        ) {
+            // Parse recipient email
+            let to_email: Mailbox = recipient
+                .parse()
+                .context(format!("Invalid recipient email address: {}", recipient))?;
+            
+            // Parse from email (use smtp_user or default)
+            let from_email: Mailbox = smtp_user
+                .parse()
+                .context(format!("Invalid sender email address: {}", smtp_user))?;
+            
+            // Build email message
+            let email = Message::builder()
+                .from(from_email)
+                .to(to_email)
+                .subject(alert_title)
+                .singlepart(
+                    SinglePart::builder()
+                        .header(ContentType::TEXT_PLAIN)
+                        .body(alert_message.to_string()),
+                )
+                .context("Failed to build email message")?;
+            
+            // Create SMTP transport
+            let smtp_port_u16 = smtp_port.min(u16::MAX as u64) as u16;
+            
+            // Determine if we need TLS (check config or default to STARTTLS)
+            let use_tls = config
+                .get("use_tls")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            
+            let mailer = if use_tls {
+                AsyncSmtpTransport::<Tokio1Executor>::relay(smtp_host)
+                    .context(format!("Failed to create SMTP relay for {}", smtp_host))?
+                    .port(smtp_port_u16)
+                    .credentials(Credentials::new(smtp_user.to_string(), smtp_pass.to_string()))
+                    .build()
+            } else {
+                // For non-TLS (not recommended but supported)
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(smtp_host)
+                    .port(smtp_port_u16)
+                    .credentials(Credentials::new(smtp_user.to_string(), smtp_pass.to_string()))
+                    .build()
+            };
+            
+            // Send email
+            return match mailer.send(email).await {
+                Ok(response) => Ok(Some(DeliveryStatus {
+                    smtp_code: response.code().to_string().parse().ok(),
+                    http_status: None,
+                    response: Some(response.message().collect::<Vec<_>>().join(" ")),
+                })),
+                Err(e) => Err(ChannelError {
+                    status: DeliveryStatus {
+                        smtp_code: e.status().and_then(|code| code.to_string().parse().ok()),
+                        ..Default::default()
+                    },
+                    message: format!("Failed to send email via SMTP: {}", e),
+                }
+                .into()),
+            };
+        }
+        Ok(None)
     }
 
     /// Send alert via SMS
@@ -92,72 +151,85 @@ impl AlertChannelSender {
         alert_message: &str,
         recipient: &str,
         config: Option<&Value>,
-    ) -> Result<()> {
-        // Check for Twilio or other SMS provider config
+    ) -> Result<DeliveryStatus> {
         if let Some(config) = config {
-            if let (Some(api_key), Some(api_secret), Some(from_number)) = (
-                config.get("api_key").and_then(|v| v.as_str()),
-                config.get("api_secret").and_then(|v| v.as_str()),
-                config.get("from_number").and_then(|v| v.as_str()),
-            ) {
-                // Try Twilio first
-                if let Some(twilio_account_sid) = config.get("twilio_account_sid").and_then(|v| v.as_str()) {
-                    return Self::send_twilio_sms(
-                        twilio_account_sid,
-                        api_key,
-                        api_secret,
-                        from_number,
-                        recipient,
-                        alert_message,
-                    ).await;
-                }
-                
-                // Try AWS SNS
-                if let Some(aws_region) = config.get("aws_region").and_then(|v| v.as_str()) {
-                    if let Some(aws_access_key_id) = config.get("aws_access_key_id").and_then(|v| v.as_str()) {
-                        if let Some(aws_secret_access_key) = config.get("aws_secret_access_key").and_then(|v| v.as_str()) {
-                            return Self::send_aws_sns_sms(
-                                aws_region,
-                                aws_access_key_id,
-                                aws_secret_access_key,
-                                from_number,
-                                recipient,
-                                alert_message,
-                            ).await;
-                        }
+            if let Some(status) = Self::send_sms_via_provider(alert_message, recipient, config).await? {
+                return Ok(status);
+            }
+        }
+        
+        // Fallback: log SMS
+        eprintln!("[SMS] To: {}, Message: {}", recipient, alert_message);
+        Ok(DeliveryStatus::default())
+    }
+
+    /// Send through the first SMS provider the config has credentials for.
+    /// `None` when it has none.
+    async fn send_sms_via_provider(
+        alert_message: &str,
+        recipient: &str,
+        config: &Value,
+    ) -> Result<Option<DeliveryStatus>> {
+        // Check for Twilio or other SMS provider config
+        if let (Some(api_key), Some(api_secret), Some(from_number)) = (
+            config.get("api_key").and_then(|v| v.as_str()),
+            config.get("api_secret").and_then(|v| v.as_str()),
+            config.get("from_number").and_then(|v| v.as_str()),
+        ) {
+            // Try Twilio first
+            if let Some(twilio_account_sid) = config.get("twilio_account_sid").and_then(|v| v.as_str()) {
+                return Self::send_twilio_sms(
+                    twilio_account_sid,
+                    api_key,
+                    api_secret,
+                    from_number,
+                    recipient,
+                    alert_message,
+                ).await.map(Some);
+            }
+            
+            // Try AWS SNS
+            if let Some(aws_region) = config.get("aws_region").and_then(|v| v.as_str()) {
+                if let Some(aws_access_key_id) = config.get("aws_access_key_id").and_then(|v| v.as_str()) {
+                    if let Some(aws_secret_access_key) = config.get("aws_secret_access_key").and_then(|v| v.as_str()) {
+                        return Self::send_aws_sns_sms(
+                            aws_region,
+                            aws_access_key_id,
+                            aws_secret_access_key,
+                            from_number,
+                            recipient,
+                            alert_message,
+                        ).await.map(Some);
                     }
                 }
-                
-                // Try Vonage
-                if config.get("vonage_api_key").and_then(|v| v.as_str()).is_some() {
-                    if let Some(vonage_api_key) = config.get("vonage_api_key").and_then(|v| v.as_str()) {
-                        if let Some(vonage_api_secret) = config.get("vonage_api_secret").and_then(|v| v.as_str()) {
-                            return Self::send_vonage_sms(
-                                vonage_api_key,
-                                vonage_api_secret,
-                                from_number,
-                                recipient,
-                                alert_message,
-                            ).await;
-                        }
+            }
+            
+            // Try Vonage
+            if config.get("vonage_api_key").and_then(|v| v.as_str()).is_some() {
+                if let Some(vonage_api_key) = config.get("vonage_api_key").and_then(|v| v.as_str()) {
+                    if let Some(vonage_api_secret) = config.get("vonage_api_secret").and_then(|v| v.as_str()) {
+                        return Self::send_vonage_sms(
+                            vonage_api_key,
+                            vonage_api_secret,
+                            from_number,
+                            recipient,
+                            alert_message,
+                        ).await.map(Some);
                     }
                 }
-                
-                // Try MessageBird
-                if let Some(messagebird_api_key) = config.get("messagebird_api_key").and_then(|v| v.as_str()) {
-                    return Self::send_messagebird_sms(
-                        messagebird_api_key,
-                        from_number,
-                        recipient,
-                        alert_message,
-                    ).await;
-                }
+            }
+            
+            // Try MessageBird
+            if let Some(messagebird_api_key) = config.get("messagebird_api_key").and_then(|v| v.as_str()) {
+                return Self::send_messagebird_sms(
+                    messagebird_api_key,
+                    from_number,
+                    recipient,
+                    alert_message,
+                ).await.map(Some);
             }
         }
-        
-        // Fallback: log SMS
-        eprintln!("[SMS] To: {}, Message: {}", recipient, alert_message);
-        Ok(())
+        Ok(None)
     }
 
     async fn send_twilio_sms(
@@ -167,7 +239,7 @@ impl AlertChannelSender {
         from: &str,
         to: &str,
         message: &str,
-    ) -> Result<()> {
+    ) -> Result<DeliveryStatus> {
         let client = reqwest::Client::new();
         let url = format!("https://api.twilio.com/2010-04-01/Accounts/{}/Messages.json", account_sid);
         
@@ -184,13 +256,7 @@ impl AlertChannelSender {
             .await
             .context("Failed to send Twilio SMS request")?;
         
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Twilio API error: {} - {}", status, error_text);
-        }
-        
-        Ok(())
+        check_response("Twilio API error", response).await
     }
 
     async fn send_aws_sns_sms(
@@ -200,7 +266,7 @@ impl AlertChannelSender {
         from: &str,
         to: &str,
         message: &str,
-    ) -> Result<()> {
+    ) -> Result<DeliveryStatus> {
         use chrono::Utc;
         use hmac::{Hmac, Mac};
         use sha2::{Sha256, Digest};
@@ -320,13 +386,7 @@ impl AlertChannelSender {
             .await
             .context("Failed to send AWS SNS SMS request")?;
         
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("AWS SNS API error: {} - {}", status, error_text);
-        }
-        
-        Ok(())
+        check_response("AWS SNS API error", response).await
     }
 
     async fn send_vonage_sms(
@@ -335,7 +395,7 @@ impl AlertChannelSender {
         from: &str,
         to: &str,
         message: &str,
-    ) -> Result<()> {
+    ) -> Result<DeliveryStatus> {
         let client = reqwest::Client::new();
         let url = "https://rest.nexmo.com/sms/json";
         
@@ -353,14 +413,10 @@ impl AlertChannelSender {
             .await
             .context("Failed to send Vonage SMS request")?;
         
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Vonage API error: {} - {}", status, error_text);
-        }
+        let delivery = check_response("Vonage API error", response).await?;
         
         // Check response body for Vonage-specific error codes
-        let response_body: serde_json::Value = response.json().await
+        let response_body: serde_json::Value = serde_json::from_str(delivery.response.as_deref().unwrap_or_default())
             .context("Failed to parse Vonage response")?;
         
         if let Some(messages) = response_body.get("messages").and_then(|v| v.as_array()) {
@@ -370,13 +426,17 @@ impl AlertChannelSender {
                         let error_text = first_msg.get("error-text")
                             .and_then(|v| v.as_str())
                             .unwrap_or("Unknown error");
-                        anyhow::bail!("Vonage SMS error (status {}): {}", status, error_text);
+                        return Err(ChannelError {
+                            status: delivery.clone(),
+                            message: format!("Vonage SMS error (status {}): {}", status, error_text),
+                        }
+                        .into());
                     }
                 }
             }
         }
         
-        Ok(())
+        Ok(delivery)
     }
 
     async fn send_messagebird_sms(
@@ -384,7 +444,7 @@ impl AlertChannelSender {
         from: &str,
         to: &str,
         message: &str,
-    ) -> Result<()> {
+    ) -> Result<DeliveryStatus> {
         let client = reqwest::Client::new();
         let url = "https://rest.messagebird.com/messages";
         
@@ -403,13 +463,7 @@ impl AlertChannelSender {
             .await
             .context("Failed to send MessageBird SMS request")?;
         
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("MessageBird API error: {} - {}", status, error_text);
-        }
-        
-        Ok(())
+        check_response("MessageBird API error", response).await
     }
 
     /// Send alert via webhook
@@ -418,7 +472,7 @@ impl AlertChannelSender {
         alert_data: &Value,
         webhook_url: &str,
         config: Option<&Value>,
-    ) -> Result<()> {
+    ) -> Result<DeliveryStatus> {
         let client = reqwest::Client::new();
         
         // Build request body
@@ -453,13 +507,7 @@ impl AlertChannelSender {
             .await
             .context("Failed to send webhook request")?;
         
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Webhook error: {} - {}", status, error_text);
-        }
-        
-        Ok(())
+        check_response("Webhook error", response).await
     }
 
     /// Send alert via push notification (desktop)
@@ -468,7 +516,7 @@ impl AlertChannelSender {
         alert_title: &str,
         alert_message: &str,
         app: Option<tauri::AppHandle>,
-    ) -> Result<()> {
+    ) -> Result<DeliveryStatus> {
         if let Some(app_handle) = app {
             // Use desktop notification service
             use crate::services::desktop_notifications::DesktopNotificationService;
@@ -485,7 +533,160 @@ impl AlertChannelSender {
             eprintln!("[PUSH] Title: {}, Message: {}", alert_title, alert_message);
         }
         
-        Ok(())
+        Ok(DeliveryStatus::default())
+    }
+
+    /// Send alert to a Slack incoming webhook
+    pub async fn send_slack(
+        alert_title: &str,
+        alert_message: &str,
+        webhook_url: &str,
+    ) -> Result<DeliveryStatus> {
+        let body = serde_json::json!({
+            "text": format!("*{}*\n{}", alert_title, alert_message),
+        });
+        
+        let response = reqwest::Client::new()
+            .post(webhook_url)
+            .json(&body)
+            .send()
+            .await
+            .context("Failed to send Slack webhook request")?;
+        
+        check_response("Slack error", response).await
+    }
+
+    /// Send alert through a Telegram bot
+    pub async fn send_telegram(
+        alert_title: &str,
+        alert_message: &str,
+        bot_token: &str,
+        chat_id: &str,
+    ) -> Result<DeliveryStatus> {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", bot_token);
+        let body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": format!("{}\n\n{}", alert_title, alert_message),
+        });
+        
+        let response = reqwest::Client::new()
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .context("Failed to send Telegram request")?;
+        
+        check_response("Telegram API error", response).await
+    }
+
+    /// Send a test message through `channel` using an escalation level config
+    /// (the same keys `AlertEscalator::send_escalation` reads). Unlike a real
+    /// escalation, missing credentials fail instead of falling back to the log.
+    pub async fn test_channel(channel: &str, config: &Value, app: Option<tauri::AppHandle>) -> ChannelTestResult {
+        let started = std::time::Instant::now();
+        let result = Self::send_test(channel, config, app).await;
+        let duration_ms = started.elapsed().as_millis() as i64;
+
+        match result {
+            Ok(status) => ChannelTestResult {
+                channel: channel.to_string(),
+                success: true,
+                smtp_code: status.smtp_code,
+                http_status: status.http_status,
+                response: status.response.map(|r| truncate(&r)),
+                error: None,
+                duration_ms,
+            },
+            Err(e) => {
+                let status = e
+                    .downcast_ref::<ChannelError>()
+                    .map(|c| c.status.clone())
+                    .unwrap_or_default();
+                ChannelTestResult {
+                    channel: channel.to_string(),
+                    success: false,
+                    smtp_code: status.smtp_code,
+                    http_status: status.http_status,
+                    response: status.response.map(|r| truncate(&r)),
+                    error: Some(format!("{:#}", e)),
+                    duration_ms,
+                }
+            }
+        }
+    }
+
+    async fn send_test(channel: &str, config: &Value, app: Option<tauri::AppHandle>) -> Result<DeliveryStatus> {
+        let title = "MINA test alert";
+        let message = format!(
+            "Test message sent {} to check the {} alert channel.",
+            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+            channel
+        );
+        let required = |key: &str| {
+            config
+                .get(key)
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.trim().is_empty())
+                .ok_or_else(|| anyhow::anyhow!("{} not configured", key))
+        };
+
+        match channel {
+            "email" => {
+                let recipient = required("email")?;
+                let smtp = config
+                    .get("email_config")
+                    .ok_or_else(|| anyhow::anyhow!("email_config not configured"))?;
+                Self::send_smtp(title, &message, recipient, smtp)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("email_config needs smtp_host, smtp_port, smtp_user and smtp_pass"))
+            }
+            "sms" => {
+                let recipient = required("phone")?;
+                let provider = config
+                    .get("sms_config")
+                    .ok_or_else(|| anyhow::anyhow!("sms_config not configured"))?;
+                Self::send_sms_via_provider(&message, recipient, provider)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("sms_config has no complete SMS provider credentials"))
+            }
+            "webhook" => {
+                let url = required("webhook_url")?;
+                let data = serde_json::json!({ "title": title, "message": message, "test": true });
+                Self::send_webhook(0, &data, url, config.get("webhook_config")).await
+            }
+            "slack" => Self::send_slack(title, &message, required("slack_webhook_url")?).await,
+            "telegram" => {
+                Self::send_telegram(title, &message, required("telegram_bot_token")?, required("telegram_chat_id")?).await
+            }
+            "push" => {
+                let app = app.ok_or_else(|| anyhow::anyhow!("No app handle for desktop notifications"))?;
+                Self::send_push(0, title, &message, Some(app)).await
+            }
+            _ => anyhow::bail!("Unknown channel: {}", channel),
+        }
     }
 }
 
+/// Turn a non-success HTTP response into a `ChannelError` carrying its status.
+async fn check_response(error_prefix: &str, response: reqwest::Response) -> Result<DeliveryStatus> {
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    let delivery = DeliveryStatus {
+        smtp_code: None,
+        http_status: Some(status.as_u16()),
+        response: Some(text.clone()),
+    };
+    if !status.is_success() {
+        return Err(ChannelError {
+            status: delivery,
+            message: format!("{}: {} - {}", error_prefix, status, text),
+        }
+        .into());
+    }
+    Ok(delivery)
+}
+
+fn truncate(text: &str) -> String {
+    text.chars().take(MAX_RESPONSE_CHARS).collect()
+}
+
@@ -0,0 +1,202 @@
+use crate::services::watchlist_analytics::item_matches;
+use crate::storage::market_data::{MarketDataStore, MarketPrice, PriceHistory};
+use crate::storage::temporal::{Alert, AlertRule, EventCommentary, EvidenceSnippet, TemporalEvent, TemporalStore, WatchlistItem};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+const MAX_EVIDENCE: i64 = 5;
+const MAX_TICKERS: usize = 8;
+/// Entities checked against known tickers; the rest are rarely companies.
+const MAX_ENTITIES: usize = 20;
+/// How far before the event to look for the close it's measured from.
+const PRICE_LOOKBACK_SECS: i64 = 7 * 86_400;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchlistMatch {
+    pub watchlist_id: i64,
+    pub watchlist_name: String,
+    pub item: WatchlistItem,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TickerMove {
+    pub ticker: String,
+    /// Latest stored quote, with its daily change
+    pub price: Option<MarketPrice>,
+    /// Percent change from the last close before the event started to now
+    pub move_since_event: Option<f64>,
+}
+
+/// Everything the alert detail view shows, in one call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertContext {
+    pub alert: Alert,
+    pub rule: Option<AlertRule>,
+    pub event: Option<TemporalEvent>,
+    pub commentary: Option<EventCommentary>,
+    pub evidence: Vec<EvidenceSnippet>,
+    pub watchlist_matches: Vec<WatchlistMatch>,
+    pub tickers: Vec<TickerMove>,
+}
+
+pub struct AlertContextService;
+
+impl AlertContextService {
+    /// None when the alert doesn't exist. The rule or event may have been
+    /// deleted since the alert fired; their parts are then empty.
+    pub fn build(temporal_store: &TemporalStore, market_data_store: &MarketDataStore, alert_id: i64) -> Result<Option<AlertContext>> {
+        let alert = match temporal_store.get_alert(alert_id)? {
+            Some(alert) => alert,
+            None => return Ok(None),
+        };
+        let rule = temporal_store.get_alert_rule(alert.rule_id)?;
+        let event = match alert.event_id {
+            Some(event_id) => temporal_store.get_event(event_id)?,
+            None => None,
+        };
+
+        let mut context = AlertContext {
+            alert,
+            rule,
+            event: None,
+            commentary: None,
+            evidence: Vec::new(),
+            watchlist_matches: Vec::new(),
+            tickers: Vec::new(),
+        };
+        let event = match event {
+            Some(event) => event,
+            None => return Ok(Some(context)),
+        };
+
+        context.commentary = temporal_store.get_event_commentary(event.id)?;
+        context.evidence = temporal_store.evidence_snippets(event.id, MAX_EVIDENCE)?;
+        let match_context = temporal_store.event_match_context(event.id)?.unwrap_or_default();
+        for watchlist in temporal_store.list_watchlists()? {
+            for item in temporal_store.list_watchlist_items(watchlist.id)? {
+                if item.enabled && item_matches(&item, &match_context) {
+                    context.watchlist_matches.push(WatchlistMatch {
+                        watchlist_id: watchlist.id,
+                        watchlist_name: watchlist.name.clone(),
+                        item,
+                    });
+                }
+            }
+        }
+
+        let tickers = related_tickers(market_data_store, context.commentary.as_ref(), &match_context.entities)?;
+        let now = chrono::Utc::now().timestamp();
+        for ticker in tickers {
+            let price = market_data_store.get_price(&ticker)?;
+            let bars = market_data_store.get_price_history_range(&ticker, event.start_ts - PRICE_LOOKBACK_SECS, now)?;
+            let latest = price.as_ref().map(|p| p.price).or_else(|| bars.last().map(|b| b.close));
+            context.tickers.push(TickerMove {
+                move_since_event: latest.and_then(|latest| move_since(&bars, event.start_ts, latest)),
+                ticker,
+                price,
+            });
+        }
+
+        context.event = Some(event);
+        Ok(Some(context))
+    }
+}
+
+/// Tickers an event is about: the ones the analyst commentary named first,
+/// then entities that resolve to a known ticker.
+pub fn related_tickers(
+    market_data_store: &MarketDataStore,
+    commentary: Option<&EventCommentary>,
+    entities: &[String],
+) -> Result<Vec<String>> {
+    let mut tickers: Vec<String> = Vec::new();
+    let named = commentary.iter().flat_map(|c| c.affected_tickers.iter());
+    for ticker in named.map(|t| t.trim().to_uppercase()).filter(|t| !t.is_empty()) {
+        if !tickers.contains(&ticker) {
+            tickers.push(ticker);
+        }
+    }
+    for entity in entities.iter().take(MAX_ENTITIES) {
+        if let Some(ticker) = market_data_store.resolve_ticker(entity)? {
+            let ticker = ticker.to_uppercase();
+            if !tickers.contains(&ticker) {
+                tickers.push(ticker);
+            }
+        }
+    }
+    tickers.truncate(MAX_TICKERS);
+    Ok(tickers)
+}
+
+/// Percent change from the last close at or before `start_ts` to `latest`.
+fn move_since(bars: &[PriceHistory], start_ts: i64, latest: f64) -> Option<f64> {
+    let before = bars.partition_point(|b| b.timestamp <= start_ts).checked_sub(1)?;
+    let base = bars[before].close;
+    if base <= 0.0 {
+        return None;
+    }
+    Some((latest / base - 1.0) * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{alert, alert_rule, article, event, new_event, price, test_db, FIXTURE_TS};
+
+    #[test]
+    fn context_bundles_rule_event_evidence_and_watchlist_matches() {
+        let db = test_db();
+        let article_id = article(&db, "Reuters", "Chipmaker recall", "A recall at the chipmaker");
+        let stored = event(&db, &new_event("Chipmaker recall"), &[article_id]);
+        let rule_id = alert_rule(&db, "Recalls", serde_json::json!({ "any": [{ "type": "contains_keyword", "keyword": "recall" }] }));
+        let alert_id = alert(&db, rule_id, Some(stored.id));
+
+        let temporal_store = TemporalStore::new(db.conn.clone());
+        let watchlist_id = temporal_store.create_watchlist("Supply chain").unwrap();
+        temporal_store.add_watchlist_item(watchlist_id, "keyword", "recall", 1.0, true).unwrap();
+        temporal_store.add_watchlist_item(watchlist_id, "keyword", "merger", 1.0, true).unwrap();
+        temporal_store.save_event_commentary(&EventCommentary {
+            event_id: stored.id,
+            model: "test".to_string(),
+            what_happened: String::new(),
+            why_it_matters: String::new(),
+            affected_tickers: vec!["nvda".to_string()],
+            created_at: FIXTURE_TS,
+        }).unwrap();
+        price(&db, "NVDA", 110.0);
+
+        let market_data_store = MarketDataStore::new(db.conn.clone());
+        let context = AlertContextService::build(&temporal_store, &market_data_store, alert_id)
+            .unwrap()
+            .expect("alert exists");
+
+        assert_eq!(context.rule.map(|r| r.name), Some("Recalls".to_string()));
+        assert_eq!(context.event.map(|e| e.id), Some(stored.id));
+        assert_eq!(context.evidence.len(), 1);
+        assert_eq!(context.evidence[0].source, "Reuters");
+        let matched: Vec<&str> = context.watchlist_matches.iter().map(|m| m.item.value.as_str()).collect();
+        assert_eq!(matched, vec!["recall"]);
+        assert_eq!(context.tickers.len(), 1);
+        assert_eq!(context.tickers[0].ticker, "NVDA");
+        assert_eq!(context.tickers[0].price.as_ref().map(|p| p.price), Some(110.0));
+
+        assert!(AlertContextService::build(&temporal_store, &market_data_store, alert_id + 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn move_is_measured_from_the_close_before_the_event() {
+        let bar = |timestamp: i64, close: f64| PriceHistory {
+            id: 0,
+            ticker: "NVDA".to_string(),
+            timestamp,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 0,
+        };
+        let bars = vec![bar(100, 90.0), bar(200, 100.0), bar(300, 120.0)];
+        assert_eq!(move_since(&bars, 250, 110.0), Some(10.0));
+        assert_eq!(move_since(&bars, 50, 110.0), None);
+    }
+}
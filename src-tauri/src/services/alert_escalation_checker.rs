@@ -15,9 +15,13 @@ impl AlertEscalationChecker {
     ) {
         tauri::async_runtime::spawn(async move {
             let mut interval = interval(Duration::from_secs(60)); // Check every minute
+            let heartbeat = crate::services::watchdog::Watchdog::register("alert_escalations", Duration::from_secs(60));
 
             loop {
                 interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
 
                 if let Err(e) = Self::check_pending_escalations(&db, &app) {
                     eprintln!("Error checking pending escalations: {}", e);
@@ -32,7 +36,8 @@ impl AlertEscalationChecker {
         
         // Get all unacknowledged alerts
         let alerts = store.list_alerts(1000, None, None)?;
-        let rules = store.list_alert_rules()?;
+        // Alerts fire for every profile, not just the one on screen
+        let rules = store.list_alert_rules_for(None)?;
         
         let rules_map: std::collections::HashMap<i64, &AlertRule> = rules
             .iter()
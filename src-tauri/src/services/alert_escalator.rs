@@ -1,5 +1,8 @@
 use crate::storage::temporal::{TemporalStore, Alert, AlertRule, AlertEscalation};
 use crate::services::alert_channels::AlertChannelSender;
+use crate::services::alert_templates::AlertTemplates;
+use crate::services::notification_outbox::NotificationOutbox;
+use crate::storage::OutboxStore;
 use anyhow::Result;
 use serde_json::Value;
 
@@ -27,18 +30,21 @@ impl AlertEscalator {
                         if let Some(channels) = level_config.get("channels").and_then(|v| v.as_array()) {
                             for channel_value in channels {
                                 if let Some(channel) = channel_value.as_str() {
-                                    let escalation_id = store.create_escalation(
+                                    // Queued before sending so a crash can't lose it; the
+                                    // key keeps overlapping checks from sending twice
+                                    let queued = OutboxStore::new(store.conn.clone()).enqueue_escalation(
+                                        &NotificationOutbox::escalation_key(alert.id, escalation_level, channel),
                                         alert.id,
                                         escalation_level,
                                         channel,
+                                        &NotificationOutbox::escalation_payload(alert, rule, level_config),
                                     )?;
-                                    
-                                    // Try to send escalation
-                                    if let Err(e) = Self::send_escalation(store, escalation_id, channel, alert, level_config, app.clone()).await {
+                                    let Some(message) = queued else { continue };
+                                    let escalation_id = message.escalation_id.unwrap_or_default();
+
+                                    // Failed sends stay queued for the retry worker
+                                    if let Err(e) = NotificationOutbox::deliver(store.conn.clone(), &message, app.clone()).await {
                                         eprintln!("Failed to send escalation: {}", e);
-                                        store.mark_escalation_sent(escalation_id, Some(&format!("{}", e)))?;
-                                    } else {
-                                        store.mark_escalation_sent(escalation_id, None)?;
                                     }
                                     
                                     if let Ok(escalation) = store.get_alert_escalations(alert.id) {
@@ -94,23 +100,20 @@ impl AlertEscalator {
         _escalation_id: i64,
         channel: &str,
         alert: &Alert,
+        rule: &AlertRule,
         level_config: &Value,
         app: Option<tauri::AppHandle>,
     ) -> Result<()> {
-        // Alert payload is already a Value
-        let alert_payload = &alert.payload_json;
-        
-        let alert_title = alert_payload.get("title")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Alert")
-            .to_string();
-        
-        let alert_message = alert_payload.get("message")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| {
-                serde_json::to_string(alert_payload).unwrap_or_else(|_| "Alert triggered".to_string())
-            });
+        let template = AlertTemplates::for_channel(rule, channel);
+        let rendered = AlertTemplates::render(channel, template.as_ref(), &AlertTemplates::variables(alert));
+        let alert_title = rendered.subject;
+        let alert_message = rendered.body;
+
+        // Webhooks get the payload, plus the rendered message when templated
+        let mut alert_payload = alert.payload_json.clone();
+        if template.is_some() && alert_payload.is_object() {
+            alert_payload["rendered"] = serde_json::json!({ "subject": alert_title, "body": alert_message });
+        }
         
         match channel {
             "email" => {
@@ -158,9 +161,27 @@ impl AlertEscalator {
                     app,
                 ).await
             }
+            "slack" => {
+                let url = level_config.get("slack_webhook_url")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Slack webhook URL not configured"))?;
+                
+                AlertChannelSender::send_slack(&alert_title, &alert_message, url).await
+            }
+            "telegram" => {
+                let bot_token = level_config.get("telegram_bot_token")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Telegram bot token not configured"))?;
+                let chat_id = level_config.get("telegram_chat_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Telegram chat ID not configured"))?;
+                
+                AlertChannelSender::send_telegram(&alert_title, &alert_message, bot_token, chat_id).await
+            }
             _ => {
                 anyhow::bail!("Unknown channel: {}", channel)
             }
         }
+        .map(|_| ())
     }
 }
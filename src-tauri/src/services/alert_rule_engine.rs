@@ -1,12 +1,85 @@
-use crate::storage::temporal::TemporalEvent;
+use crate::storage::temporal::{EntityAlertOverride, TemporalEvent};
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashSet;
 use regex::Regex;
-use chrono::{TimeZone, Utc, Datelike, Timelike};
+use chrono::{DateTime, Utc, Datelike, Timelike};
+use chrono_tz::Tz;
+use crate::services::timezone::{local_datetime, parse_timezone};
+
+/// How one condition or logic group evaluated against an event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConditionTrace {
+    pub condition_type: String, // condition type, or the group operator (AND/OR/NOT/XOR, any/all)
+    pub matched: bool,
+    /// The condition as written; None for groups
+    pub condition: Option<Value>,
+    /// Keywords, entities, sources or states that made the condition match
+    pub matched_values: Vec<String>,
+    /// The event's score a score condition was compared against
+    pub observed: Option<f64>,
+    pub error: Option<String>,
+    pub children: Vec<ConditionTrace>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleTrace {
+    pub matched: bool,
+    /// Set when the rule couldn't be evaluated, e.g. a missing field or bad regex
+    pub error: Option<String>,
+    pub conditions: Vec<ConditionTrace>,
+}
+
+impl RuleTrace {
+    /// Matched leaf conditions, depth first.
+    pub fn matched_leaves(&self) -> Vec<&ConditionTrace> {
+        fn collect<'a>(trace: &'a ConditionTrace, out: &mut Vec<&'a ConditionTrace>) {
+            if trace.condition.is_some() {
+                if trace.matched {
+                    out.push(trace);
+                }
+            } else {
+                for child in &trace.children {
+                    collect(child, out);
+                }
+            }
+        }
+        let mut out = Vec::new();
+        for condition in &self.conditions {
+            collect(condition, &mut out);
+        }
+        out
+    }
+
+    /// Distinct keywords, entities and sources that matched, for highlighting.
+    pub fn highlights(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.matched_leaves()
+            .into_iter()
+            .flat_map(|c| c.matched_values.iter().cloned())
+            .filter(|v| seen.insert(v.to_lowercase()))
+            .collect()
+    }
+
+    /// One line per matched condition, e.g. "contains_keyword: merger" or "severity: 0.82".
+    pub fn reasons(&self) -> Vec<String> {
+        self.matched_leaves()
+            .into_iter()
+            .map(|c| match (c.matched_values.is_empty(), c.observed) {
+                (false, _) => format!("{}: {}", c.condition_type, c.matched_values.join(", ")),
+                (true, Some(score)) => format!("{}: {:.2}", c.condition_type, score),
+                (true, None) => c.condition_type.clone(),
+            })
+            .collect()
+    }
+}
 
 pub struct AlertRuleEngine;
 
+/// Event scores an entity override can raise the minimum of
+pub const OVERRIDE_SCORES: &[&str] = &["severity", "volume", "novelty", "confidence", "corroborated_by"];
+
 impl AlertRuleEngine {
     /// Enhanced rule matching with support for complex conditions
     pub fn rule_matches(
@@ -44,6 +117,255 @@ impl AlertRuleEngine {
         Ok(any_pass && all_pass)
     }
 
+    /// Why an alert from `rule_id` on this event is held back by an entity
+    /// override, if it is: a mute on a mentioned entity, or a raised
+    /// threshold the event's scores don't reach. `overrides` must already
+    /// exclude expired entries.
+    pub fn entity_override_reason(
+        overrides: &[EntityAlertOverride],
+        rule_id: i64,
+        entities_lower: &HashSet<String>,
+        event: &TemporalEvent,
+    ) -> Option<String> {
+        overrides
+            .iter()
+            .filter(|o| (o.rule_id.is_none() || o.rule_id == Some(rule_id)) && entities_lower.contains(&o.entity))
+            .find_map(|o| match o.action.as_str() {
+                "mute" => Some(format!("{} is muted", o.entity)),
+                "raise_threshold" => o.min_scores.iter().find_map(|(name, min)| {
+                    let score = Self::override_score(event, name)?;
+                    (score < *min).then(|| format!("{} {:.2} below the {:.2} required for {}", name, score, min, o.entity))
+                }),
+                _ => None,
+            })
+    }
+
+    fn override_score(event: &TemporalEvent, name: &str) -> Option<f64> {
+        match name {
+            "severity" => Some(event.severity),
+            "volume" => Some(event.volume_score),
+            "novelty" => Some(event.novelty_score),
+            "confidence" => Some(event.confidence),
+            "corroborated_by" => Some(event.corroborated_by as f64),
+            _ => None,
+        }
+    }
+
+    /// Copy of the rule with a timezone on every time_of_day/day_of_week
+    /// condition that doesn't name its own: the rule's top-level
+    /// "timezone" if set, otherwise `default_tz` (the global preference).
+    pub fn with_timezone(rule_json: &Value, default_tz: &str) -> Value {
+        fn fill(value: &mut Value, tz: &str) {
+            match value {
+                Value::Object(map) => {
+                    let is_time = matches!(map.get("type").and_then(|t| t.as_str()), Some("time_of_day" | "day_of_week"));
+                    if is_time && !map.contains_key("timezone") {
+                        map.insert("timezone".to_string(), Value::String(tz.to_string()));
+                    }
+                    map.values_mut().for_each(|child| fill(child, tz));
+                }
+                Value::Array(items) => items.iter_mut().for_each(|item| fill(item, tz)),
+                _ => {}
+            }
+        }
+
+        let tz = rule_json.get("timezone").and_then(|v| v.as_str()).unwrap_or(default_tz).to_string();
+        let mut rule = rule_json.clone();
+        fill(&mut rule, &tz);
+        rule
+    }
+
+    /// Whether any condition in the rule, nested groups included, is of `condition_type`.
+    pub fn has_condition(rule_json: &Value, condition_type: &str) -> bool {
+        match rule_json {
+            Value::Object(map) => {
+                map.get("type").and_then(|t| t.as_str()) == Some(condition_type)
+                    || map.values().any(|child| Self::has_condition(child, condition_type))
+            }
+            Value::Array(items) => items.iter().any(|item| Self::has_condition(item, condition_type)),
+            _ => false,
+        }
+    }
+
+    /// Evaluate a rule like `rule_matches`, recording the outcome of every
+    /// condition so it can be shown why a rule did or didn't match.
+    pub fn explain(
+        rule_json: &Value,
+        haystack_lower: &str,
+        entities_lower: &HashSet<String>,
+        sources_lower: &HashSet<String>,
+        event: &TemporalEvent,
+    ) -> RuleTrace {
+        let (matched, error) = match Self::rule_matches(rule_json, haystack_lower, entities_lower, sources_lower, event) {
+            Ok(m) => (m, None),
+            Err(e) => (false, Some(e.to_string())),
+        };
+
+        let conditions = if let Some(logic) = rule_json.get("logic") {
+            vec![Self::trace_group(logic, haystack_lower, entities_lower, sources_lower, event)]
+        } else {
+            ["any", "all"]
+                .iter()
+                .filter_map(|key| {
+                    let conds = rule_json.get(*key).and_then(|v| v.as_array())?;
+                    let children: Vec<ConditionTrace> = conds
+                        .iter()
+                        .map(|c| Self::trace_condition(c, haystack_lower, entities_lower, sources_lower, event))
+                        .collect();
+                    let matched = if *key == "any" {
+                        children.is_empty() || children.iter().any(|c| c.matched)
+                    } else {
+                        children.iter().all(|c| c.matched)
+                    };
+                    Some(Self::group_trace(key, matched, children))
+                })
+                .collect()
+        };
+
+        RuleTrace { matched, error, conditions }
+    }
+
+    fn trace_group(
+        logic: &Value,
+        haystack_lower: &str,
+        entities_lower: &HashSet<String>,
+        sources_lower: &HashSet<String>,
+        event: &TemporalEvent,
+    ) -> ConditionTrace {
+        let op = logic.get("operator").and_then(|v| v.as_str()).unwrap_or("AND").to_uppercase();
+        let members: Vec<&Value> = if op == "NOT" {
+            logic.get("condition").into_iter().collect()
+        } else {
+            logic.get("conditions").and_then(|v| v.as_array()).map(|a| a.iter().collect()).unwrap_or_default()
+        };
+        let children: Vec<ConditionTrace> = members
+            .into_iter()
+            .map(|c| {
+                if c.get("logic").is_some() {
+                    Self::trace_group(c, haystack_lower, entities_lower, sources_lower, event)
+                } else {
+                    Self::trace_condition(c, haystack_lower, entities_lower, sources_lower, event)
+                }
+            })
+            .collect();
+
+        let matched = match op.as_str() {
+            "AND" => logic.get("conditions").is_some_and(|v| v.is_array()) && children.iter().all(|c| c.matched),
+            "OR" => children.iter().any(|c| c.matched),
+            "NOT" => children.first().map(|c| !c.matched && c.error.is_none()).unwrap_or(false),
+            "XOR" => children.iter().filter(|c| c.matched).count() == 1,
+            _ => false,
+        };
+        Self::group_trace(&op, matched, children)
+    }
+
+    fn group_trace(op: &str, matched: bool, children: Vec<ConditionTrace>) -> ConditionTrace {
+        ConditionTrace {
+            condition_type: op.to_string(),
+            matched,
+            condition: None,
+            matched_values: Vec::new(),
+            observed: None,
+            error: None,
+            children,
+        }
+    }
+
+    fn trace_condition(
+        cond: &Value,
+        haystack_lower: &str,
+        entities_lower: &HashSet<String>,
+        sources_lower: &HashSet<String>,
+        event: &TemporalEvent,
+    ) -> ConditionTrace {
+        let condition_type = cond.get("type").and_then(|v| v.as_str()).unwrap_or("").to_string();
+        let (matched, error) = match Self::condition_matches(cond, haystack_lower, entities_lower, sources_lower, event) {
+            Ok(m) => (m, None),
+            Err(e) => (false, Some(e.to_string())),
+        };
+        ConditionTrace {
+            matched_values: if matched {
+                Self::matched_values(&condition_type, cond, haystack_lower, entities_lower, sources_lower, event)
+            } else {
+                Vec::new()
+            },
+            observed: Self::observed_score(&condition_type, event, entities_lower),
+            condition_type,
+            matched,
+            condition: Some(cond.clone()),
+            error,
+            children: Vec::new(),
+        }
+    }
+
+    /// What a matched condition matched on, for highlighting.
+    fn matched_values(
+        condition_type: &str,
+        cond: &Value,
+        haystack_lower: &str,
+        entities_lower: &HashSet<String>,
+        sources_lower: &HashSet<String>,
+        event: &TemporalEvent,
+    ) -> Vec<String> {
+        let field = |key: &str| cond.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+        let contained = |key: &str, set: &HashSet<String>, prefix: &str| -> Vec<String> {
+            cond.get(key)
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str())
+                        .filter(|v| set.contains(&format!("{}{}", prefix, v.to_lowercase())))
+                        .map(|v| v.to_string())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        match condition_type {
+            "contains_keyword" => field("keyword").into_iter().collect(),
+            "contains_regex" => field("pattern")
+                .and_then(|p| Regex::new(&p).ok())
+                .and_then(|re| re.find(haystack_lower).map(|m| m.as_str().to_string()))
+                .into_iter()
+                .collect(),
+            "starts_with" => field("prefix").into_iter().collect(),
+            "ends_with" => field("suffix").into_iter().collect(),
+            "mentions_entity" => field("entity").into_iter().collect(),
+            "mentions_any_entity" | "mentions_all_entities" => contained("entities", entities_lower, ""),
+            "co_mention" => field("entityA").into_iter().chain(field("entityB")).collect(),
+            "sector_in" => contained("sectors", entities_lower, "sector:"),
+            "industry_in" => contained("industries", entities_lower, "industry:"),
+            "source_in" => contained("sources", sources_lower, ""),
+            "language_in" => contained("languages", entities_lower, "language:"),
+            "event_type" | "event_type_in" => vec![event.event_type.clone()],
+            "sentiment_divergence" => field("entity").into_iter().collect(),
+            "lifecycle_state" | "lifecycle_state_in" | "lifecycle_transition" => vec![event.lifecycle_state.clone()],
+            "rating_downgrade" | "rating_upgrade" => Self::rating_tickers(condition_type, cond, entities_lower),
+            "short_interest_change_pct" => Self::short_interest_changes(cond, entities_lower)
+                .into_iter()
+                .map(|(ticker, pct)| format!("{}: {:+.1}%", ticker, pct))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn observed_score(condition_type: &str, event: &TemporalEvent, entities_lower: &HashSet<String>) -> Option<f64> {
+        match condition_type {
+            "sentiment" | "sentiment_below" | "sentiment_above" => Some(event.sentiment_score),
+            "volume" | "volume_spike" => Some(event.volume_score),
+            "novelty" | "novelty_above" => Some(event.novelty_score),
+            "severity" => Some(event.severity),
+            "confidence" => Some(event.confidence),
+            "corroborated_by" => Some(event.corroborated_by as f64),
+            "sentiment_divergence" => Some(event.severity * 2.0),
+            "short_interest_change_pct" => Self::short_interest_changes(&Value::Null, entities_lower)
+                .into_iter()
+                .map(|(_, pct)| pct)
+                .max_by(|a, b| a.abs().total_cmp(&b.abs())),
+            _ => None,
+        }
+    }
+
     fn evaluate_logic_group(
         logic: &Value,
         haystack_lower: &str,
@@ -192,6 +514,27 @@ impl AlertRuleEngine {
                 Ok(!a.is_empty() && !b.is_empty() && entities_lower.contains(&a) && entities_lower.contains(&b))
             }
             
+            // Sector conditions. Entities that resolve to a ticker or company with
+            // stored fundamentals contribute "sector:<name>" and "industry:<name>".
+            "sector_in" => {
+                if let Some(sectors) = cond.get("sectors").and_then(|v| v.as_array()) {
+                    Ok(sectors.iter()
+                        .filter_map(|v| v.as_str())
+                        .any(|s| entities_lower.contains(&format!("sector:{}", s.to_lowercase()))))
+                } else {
+                    Err(anyhow::anyhow!("Missing sectors array"))
+                }
+            }
+            "industry_in" => {
+                if let Some(industries) = cond.get("industries").and_then(|v| v.as_array()) {
+                    Ok(industries.iter()
+                        .filter_map(|v| v.as_str())
+                        .any(|i| entities_lower.contains(&format!("industry:{}", i.to_lowercase()))))
+                } else {
+                    Err(anyhow::anyhow!("Missing industries array"))
+                }
+            }
+
             // Source conditions
             "source_in" => {
                 if let Some(arr) = cond.get("sources").and_then(|v| v.as_array()) {
@@ -202,6 +545,16 @@ impl AlertRuleEngine {
                     Err(anyhow::anyhow!("Missing sources array"))
                 }
             }
+            // Languages of the event's articles arrive as "language:<code>" entities
+            "language_in" => {
+                if let Some(languages) = cond.get("languages").and_then(|v| v.as_array()) {
+                    Ok(languages.iter()
+                        .filter_map(|v| v.as_str())
+                        .any(|l| entities_lower.contains(&format!("language:{}", l.to_lowercase()))))
+                } else {
+                    Err(anyhow::anyhow!("Missing languages array"))
+                }
+            }
             "source_not_in" => {
                 if let Some(arr) = cond.get("sources").and_then(|v| v.as_array()) {
                     Ok(!arr.iter()
@@ -248,6 +601,14 @@ impl AlertRuleEngine {
                     cond.get("value").and_then(|v| v.as_f64())
                 )
             }
+            // Independent sources (distinct domains) reporting the event
+            "corroborated_by" => {
+                Self::compare_score(
+                    event.corroborated_by as f64,
+                    cond.get("operator").and_then(|v| v.as_str()).unwrap_or(">="),
+                    cond.get("value").and_then(|v| v.as_f64())
+                )
+            }
             
             // Legacy aliases for backward compatibility
             "sentiment_below" => {
@@ -281,10 +642,7 @@ impl AlertRuleEngine {
                     cond.get("start_hour").and_then(|v| v.as_i64()),
                     cond.get("end_hour").and_then(|v| v.as_i64()),
                 ) {
-                    let event_time = Utc.timestamp_opt(event.start_ts, 0)
-                        .single()
-                        .ok_or_else(|| anyhow::anyhow!("Invalid timestamp"))?;
-                    let hour = event_time.naive_utc().hour() as i64;
+                    let hour = condition_local_time(cond, event.start_ts)?.hour() as i64;
                     Ok((hour >= start_hour && hour <= end_hour) || (start_hour > end_hour && (hour >= start_hour || hour <= end_hour)))
                 } else {
                     Err(anyhow::anyhow!("Missing start_hour or end_hour"))
@@ -292,11 +650,7 @@ impl AlertRuleEngine {
             }
             "day_of_week" => {
                 if let Some(days) = cond.get("days").and_then(|v| v.as_array()) {
-                    let event_time = Utc.timestamp_opt(event.start_ts, 0)
-                        .single()
-                        .ok_or_else(|| anyhow::anyhow!("Invalid timestamp"))?;
-                    let naive = event_time.naive_utc();
-                    let weekday = naive.weekday().number_from_monday() as i64;
+                    let weekday = condition_local_time(cond, event.start_ts)?.weekday().number_from_monday() as i64;
                     Ok(days.iter()
                         .filter_map(|v| v.as_i64())
                         .any(|d| d == weekday))
@@ -331,7 +685,79 @@ impl AlertRuleEngine {
                     Err(anyhow::anyhow!("Missing types array"))
                 }
             }
+
+            // Story lifecycle conditions
+            "lifecycle_state" => {
+                cond.get("state")
+                    .and_then(|v| v.as_str())
+                    .map(|s| Ok(event.lifecycle_state.eq_ignore_ascii_case(s)))
+                    .unwrap_or(Err(anyhow::anyhow!("Missing state")))
+            }
+            "lifecycle_state_in" => {
+                if let Some(states) = cond.get("states").and_then(|v| v.as_array()) {
+                    Ok(states.iter()
+                        .filter_map(|v| v.as_str())
+                        .any(|s| event.lifecycle_state.eq_ignore_ascii_case(s)))
+                } else {
+                    Err(anyhow::anyhow!("Missing states array"))
+                }
+            }
+            "lifecycle_transition" => {
+                // Only the move into `to` (optionally from `from`) within the last
+                // `within_hours` matches, so the rule fires on the transition rather
+                // than for as long as the event stays in that state.
+                let to = cond.get("to")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Missing to"))?;
+                let within_hours = cond.get("within_hours").and_then(|v| v.as_i64()).unwrap_or(6);
+                let from_matches = match cond.get("from").and_then(|v| v.as_str()) {
+                    Some(from) => event.previous_lifecycle_state
+                        .as_deref()
+                        .map(|p| p.eq_ignore_ascii_case(from))
+                        .unwrap_or(false),
+                    None => true,
+                };
+                let recent = event.lifecycle_changed_at
+                    .map(|ts| ts >= Utc::now().timestamp() - within_hours * 3600)
+                    .unwrap_or(false);
+                Ok(event.lifecycle_state.eq_ignore_ascii_case(to) && from_matches && recent)
+            }
             
+            // Sources disagreeing in tone about an entity, optionally a given one.
+            // Divergence events carry the spread as severity * 2.
+            "sentiment_divergence" => {
+                if event.event_type != crate::services::sentiment_divergence::EVENT_TYPE {
+                    return Ok(false);
+                }
+                let min_spread = cond.get("min_spread").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let entity_matches = cond
+                    .get("entity")
+                    .and_then(|v| v.as_str())
+                    .map(|e| entities_lower.contains(&e.to_lowercase()))
+                    .unwrap_or(true);
+                Ok(entity_matches && event.severity * 2.0 >= min_spread)
+            }
+
+            // Market signals. Entities that resolve to a ticker contribute
+            // "rating:<upgrade|downgrade>:<ticker>" for recent rating changes and
+            // "short_interest_change_pct:<ticker>:<pct>" from the last two reports.
+            "rating_downgrade" | "rating_upgrade" => {
+                Ok(!Self::rating_tickers(t, cond, entities_lower).is_empty())
+            }
+            "short_interest_change_pct" => {
+                let operator = cond.get("operator").and_then(|v| v.as_str()).unwrap_or(">=");
+                let threshold = cond.get("value").and_then(|v| v.as_f64());
+                if threshold.is_none() {
+                    return Err(anyhow::anyhow!("Missing threshold value"));
+                }
+                for (_, pct) in Self::short_interest_changes(cond, entities_lower) {
+                    if Self::compare_score(pct, operator, threshold)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+
             _ => {
                 // Unknown condition type - log warning but don't fail
                 eprintln!("Warning: Unknown condition type: {}", t);
@@ -340,6 +766,38 @@ impl AlertRuleEngine {
         }
     }
 
+    /// Tickers with a recent rating change of the condition's direction,
+    /// limited to the condition's `ticker` when set.
+    fn rating_tickers(condition_type: &str, cond: &Value, entities_lower: &HashSet<String>) -> Vec<String> {
+        let prefix = if condition_type == "rating_upgrade" { "rating:upgrade:" } else { "rating:downgrade:" };
+        let wanted = cond.get("ticker").and_then(|v| v.as_str()).map(|t| t.to_lowercase());
+        let mut tickers: Vec<String> = entities_lower
+            .iter()
+            .filter_map(|e| e.strip_prefix(prefix))
+            .filter(|ticker| wanted.as_deref().map(|w| w == *ticker).unwrap_or(true))
+            .map(|ticker| ticker.to_uppercase())
+            .collect();
+        tickers.sort();
+        tickers
+    }
+
+    /// (ticker, change in percent) per ticker with two short interest reports.
+    fn short_interest_changes(cond: &Value, entities_lower: &HashSet<String>) -> Vec<(String, f64)> {
+        let wanted = cond.get("ticker").and_then(|v| v.as_str()).map(|t| t.to_lowercase());
+        let mut changes: Vec<(String, f64)> = entities_lower
+            .iter()
+            .filter_map(|e| e.strip_prefix("short_interest_change_pct:"))
+            .filter_map(|rest| {
+                let (ticker, pct) = rest.rsplit_once(':')?;
+                Some((ticker.to_string(), pct.parse::<f64>().ok()?))
+            })
+            .filter(|(ticker, _)| wanted.as_deref().map(|w| w == ticker).unwrap_or(true))
+            .map(|(ticker, pct)| (ticker.to_uppercase(), pct))
+            .collect();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+        changes
+    }
+
     fn compare_score(score: f64, operator: &str, threshold: Option<f64>) -> Result<bool> {
         let threshold = threshold.ok_or_else(|| anyhow::anyhow!("Missing threshold value"))?;
         
@@ -355,3 +813,218 @@ impl AlertRuleEngine {
     }
 }
 
+/// The event's start in the condition's "timezone", UTC when it has none.
+fn condition_local_time(cond: &Value, ts: i64) -> Result<DateTime<Tz>> {
+    let tz = match cond.get("timezone").and_then(|v| v.as_str()) {
+        Some(name) => parse_timezone(name)?,
+        None => Tz::UTC,
+    };
+    Ok(local_datetime(ts, tz))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, assert_golden};
+    use serde_json::json;
+
+    #[test]
+    fn finds_nested_conditions() {
+        let rule = json!({ "logic": { "operator": "AND", "conditions": [
+            { "type": "severity", "operator": ">=", "value": 0.5 },
+            { "logic": { "operator": "NOT", "condition": { "type": "lifecycle_transition", "to": "resolved" } } }
+        ]}});
+        assert!(AlertRuleEngine::has_condition(&rule, "lifecycle_transition"));
+        assert!(!AlertRuleEngine::has_condition(&rule, "lifecycle_state"));
+        assert!(AlertRuleEngine::has_condition(&json!({ "any": [{ "type": "lifecycle_state", "state": "escalating" }] }), "lifecycle_state"));
+    }
+
+    #[test]
+    fn rule_traces_match_golden() {
+        let db = test_support::test_db();
+        let mut fixture = test_support::new_event("Acme Corp agrees merger with Globex");
+        fixture.summary = "Regulators review the deal".to_string();
+        fixture.event_type = "merger".to_string();
+        fixture.severity = 0.8;
+        fixture.sentiment_score = 0.3;
+        let event = test_support::event(&db, &fixture, &[]);
+
+        let haystack = format!("{} {}", event.title.to_lowercase(), event.summary.to_lowercase());
+        let entities: HashSet<String> = ["acme corp", "globex", "sector:technology"].iter().map(|s| s.to_string()).collect();
+        let sources: HashSet<String> = ["reuters".to_string()].into_iter().collect();
+
+        let cases = [
+            ("keyword_any", json!({ "any": [
+                { "type": "contains_keyword", "keyword": "merger" },
+                { "type": "contains_keyword", "keyword": "bankruptcy" }
+            ]})),
+            ("entities_and_severity", json!({ "all": [
+                { "type": "mentions_all_entities", "entities": ["Acme Corp", "Globex"] },
+                { "type": "severity", "operator": ">=", "value": 0.75 }
+            ]})),
+            ("sector_or_sentiment", json!({ "logic": { "operator": "OR", "conditions": [
+                { "type": "sector_in", "sectors": ["Energy"] },
+                { "type": "sentiment", "operator": "<", "value": 0.0 }
+            ]}})),
+            ("source_xor_sentiment", json!({ "logic": { "operator": "XOR", "conditions": [
+                { "type": "source_in", "sources": ["Reuters"] },
+                { "type": "sentiment", "operator": ">=", "value": 0.5 }
+            ]}})),
+            ("invalid_regex_in_any", json!({ "any": [{ "type": "contains_regex", "pattern": "(unclosed" }] })),
+            ("missing_keyword_in_logic", json!({ "logic": { "operator": "AND", "conditions": [{ "type": "contains_keyword" }] } })),
+        ];
+
+        let traces: Vec<serde_json::Value> = cases
+            .iter()
+            .map(|(name, rule)| {
+                let trace = AlertRuleEngine::explain(rule, &haystack, &entities, &sources, &event);
+                json!({
+                    "name": name,
+                    "matched": trace.matched,
+                    "error": trace.error,
+                    "reasons": trace.reasons(),
+                    "highlights": trace.highlights(),
+                })
+            })
+            .collect();
+        assert_golden("rule_engine", &traces);
+    }
+
+    #[test]
+    fn language_conditions_read_article_languages() {
+        let db = test_support::test_db();
+        let event = test_support::event(&db, &test_support::new_event("Zinsentscheid der EZB"), &[]);
+        let entities: HashSet<String> = ["ezb", "language:de"].iter().map(|s| s.to_string()).collect();
+        let sources = HashSet::new();
+        let matches = |rule: serde_json::Value| {
+            AlertRuleEngine::rule_matches(&rule, "zinsentscheid der ezb", &entities, &sources, &event).unwrap()
+        };
+
+        assert!(matches(json!({ "all": [{ "type": "language_in", "languages": ["de", "fr"] }] })));
+        assert!(!matches(json!({ "all": [{ "type": "language_in", "languages": ["EN"] }] })));
+        assert!(AlertRuleEngine::condition_matches(
+            &json!({ "type": "language_in" }), "", &entities, &sources, &event
+        ).is_err());
+    }
+
+    #[test]
+    fn time_conditions_use_rule_or_global_timezone() {
+        let db = test_support::test_db();
+        let mut fixture = test_support::new_event("Late session selloff");
+        // Friday 2024-06-21 23:30 in Berlin, 21:30 UTC
+        fixture.start_ts = 1_719_005_400;
+        let event = test_support::event(&db, &fixture, &[]);
+        let (entities, sources) = (HashSet::new(), HashSet::new());
+        let late = json!({ "all": [{ "type": "time_of_day", "start_hour": 23, "end_hour": 23 }] });
+        let matches = |rule: &serde_json::Value, tz: &str| {
+            let rule = AlertRuleEngine::with_timezone(rule, tz);
+            AlertRuleEngine::rule_matches(&rule, "", &entities, &sources, &event).unwrap()
+        };
+
+        assert!(!matches(&late, "UTC"));
+        assert!(matches(&late, "Europe/Berlin"));
+        let mut overridden = late.clone();
+        overridden["timezone"] = json!("America/New_York");
+        assert!(!matches(&overridden, "Europe/Berlin"));
+        // Still Friday in New York, already Saturday in Tokyo
+        let friday = json!({ "logic": { "operator": "AND", "conditions": [{ "type": "day_of_week", "days": [5] }] } });
+        assert!(matches(&friday, "America/New_York"));
+        assert!(!matches(&friday, "Asia/Tokyo"));
+    }
+
+    #[test]
+    fn sentiment_divergence_condition_reads_divergence_events() {
+        let db = test_support::test_db();
+        let mut fixture = test_support::new_event("Sources disagree on Acme");
+        fixture.event_type = crate::services::sentiment_divergence::EVENT_TYPE.to_string();
+        fixture.severity = 0.2;
+        let divergence = test_support::event(&db, &fixture, &[]);
+        let news = test_support::event(&db, &test_support::new_event("Acme results"), &[]);
+        let entities: HashSet<String> = ["acme".to_string()].into_iter().collect();
+        let sources = HashSet::new();
+        let matches = |rule: serde_json::Value, event: &TemporalEvent| {
+            AlertRuleEngine::rule_matches(&rule, "", &entities, &sources, event).unwrap()
+        };
+
+        let any = json!({ "all": [{ "type": "sentiment_divergence" }] });
+        assert!(matches(any.clone(), &divergence));
+        assert!(!matches(any, &news));
+        assert!(matches(json!({ "all": [{ "type": "sentiment_divergence", "entity": "ACME", "min_spread": 0.4 }] }), &divergence));
+        assert!(!matches(json!({ "all": [{ "type": "sentiment_divergence", "min_spread": 0.5 }] }), &divergence));
+        assert!(!matches(json!({ "all": [{ "type": "sentiment_divergence", "entity": "Globex" }] }), &divergence));
+    }
+
+    #[test]
+    fn market_signal_conditions_read_resolved_entities() {
+        let db = test_support::test_db();
+        let event = test_support::event(&db, &test_support::new_event("Acme cut to sell"), &[]);
+        let entities: HashSet<String> = ["acme", "rating:downgrade:acme", "short_interest_change_pct:acme:25.00"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let sources = HashSet::new();
+        let matches = |rule: serde_json::Value| {
+            AlertRuleEngine::rule_matches(&rule, "acme cut to sell", &entities, &sources, &event).unwrap()
+        };
+
+        assert!(matches(json!({ "all": [{ "type": "rating_downgrade" }] })));
+        assert!(matches(json!({ "all": [{ "type": "rating_downgrade", "ticker": "ACME" }] })));
+        assert!(!matches(json!({ "all": [{ "type": "rating_downgrade", "ticker": "GLBX" }] })));
+        assert!(!matches(json!({ "all": [{ "type": "rating_upgrade" }] })));
+        assert!(matches(json!({ "all": [{ "type": "short_interest_change_pct", "operator": ">", "value": 20 }] })));
+        assert!(!matches(json!({ "all": [{ "type": "short_interest_change_pct", "operator": ">", "value": 30 }] })));
+
+        let trace = AlertRuleEngine::explain(
+            &json!({ "all": [{ "type": "short_interest_change_pct", "operator": ">", "value": 20 }] }),
+            "acme cut to sell",
+            &entities,
+            &sources,
+            &event,
+        );
+        assert_eq!(trace.reasons(), vec!["short_interest_change_pct: ACME: +25.0%"]);
+    }
+
+    #[test]
+    fn entity_overrides_mute_or_raise_thresholds() {
+        use crate::storage::temporal::{NewEntityAlertOverride, TemporalStore};
+
+        let db = test_support::test_db();
+        let rule_id = test_support::alert_rule(&db, "Meme stocks", json!({ "any": [] }));
+        let other_rule = test_support::alert_rule(&db, "Everything", json!({ "any": [] }));
+        let mut fixture = test_support::new_event("GME squeeze again");
+        fixture.severity = 0.6;
+        let event = test_support::event(&db, &fixture, &[]);
+        let entities: HashSet<String> = ["gamestop".to_string()].into_iter().collect();
+        let store = TemporalStore::new(db.conn.clone());
+        let new_override = |action: &str, rule_id: Option<i64>, min_scores: &[(&str, f64)]| NewEntityAlertOverride {
+            entity: "GameStop".to_string(),
+            rule_id,
+            action: action.to_string(),
+            min_scores: min_scores.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+            reason: None,
+            days: Some(3),
+        };
+
+        store.create_entity_override(&new_override("raise_threshold", None, &[("severity", 0.8)])).unwrap();
+        let overrides = store.list_entity_overrides(false).unwrap();
+        assert_eq!(
+            AlertRuleEngine::entity_override_reason(&overrides, rule_id, &entities, &event).as_deref(),
+            Some("severity 0.60 below the 0.80 required for gamestop")
+        );
+        assert!(AlertRuleEngine::entity_override_reason(&overrides, rule_id, &HashSet::new(), &event).is_none());
+
+        store.create_entity_override(&new_override("mute", Some(other_rule), &[])).unwrap();
+        let overrides = store.list_entity_overrides(false).unwrap();
+        assert_eq!(
+            AlertRuleEngine::entity_override_reason(&overrides, other_rule, &entities, &event).as_deref(),
+            Some("gamestop is muted")
+        );
+        let mut severe = event.clone();
+        severe.severity = 0.9;
+        assert!(AlertRuleEngine::entity_override_reason(&overrides, rule_id, &entities, &severe).is_none());
+
+        assert!(store.create_entity_override(&new_override("raise_threshold", None, &[("hype", 1.0)])).is_err());
+        assert!(store.create_entity_override(&new_override("ignore", None, &[])).is_err());
+    }
+}
@@ -0,0 +1,421 @@
+use crate::storage::temporal::TemporalStore;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Bumped when the export layout changes incompatibly
+pub const EXPORT_VERSION: i64 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedWatchlistItem {
+    pub item_type: String,
+    pub value: String,
+    pub weight: f64,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedWatchlist {
+    pub name: String,
+    pub items: Vec<ExportedWatchlistItem>,
+}
+
+/// A rule as exported. The watchlist is referenced by name, since ids
+/// differ between installs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedRule {
+    pub name: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub watchlist: Option<String>,
+    pub rule_json: Value,
+    #[serde(default)]
+    pub schedule: Option<String>,
+    #[serde(default)]
+    pub escalation_config: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleExport {
+    pub version: i64,
+    pub exported_at: i64,
+    pub rules: Vec<ExportedRule>,
+    /// Watchlists the rules refer to, so an import can recreate them
+    #[serde(default)]
+    pub watchlists: Vec<ExportedWatchlist>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedRule {
+    pub id: i64,
+    /// Differs from the exported name when that was already taken
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedRule {
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub imported: Vec<ImportedRule>,
+    pub skipped: Vec<SkippedRule>,
+    pub created_watchlists: Vec<String>,
+}
+
+/// One value a template asks for before it becomes a rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateParam {
+    pub key: String,
+    pub prompt: String,
+    pub kind: String, // list|number|text
+    pub required: bool,
+    pub default: Option<Value>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// A starting point in the gallery. String values "{{key}}" in `rule_json`
+/// are replaced with the parameter, keeping its type (a list stays a list).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub params: Vec<TemplateParam>,
+    pub rule_json: Value,
+}
+
+fn param(key: &str, prompt: &str, kind: &str, default: Option<Value>) -> TemplateParam {
+    let score = kind == "number";
+    TemplateParam {
+        key: key.to_string(),
+        prompt: prompt.to_string(),
+        kind: kind.to_string(),
+        required: default.is_none(),
+        default,
+        min: score.then_some(0.0),
+        max: score.then_some(1.0),
+    }
+}
+
+/// Export, import and the built-in template gallery for alert rules.
+pub struct AlertRuleLibrary;
+
+impl AlertRuleLibrary {
+    /// Rules of the active profile, all or those in `rule_ids`, with the
+    /// watchlists they use.
+    pub fn export(store: &TemporalStore, rule_ids: Option<&[i64]>) -> Result<RuleExport> {
+        let watchlists = store.list_watchlists()?;
+        let watchlist_name = |id: i64| watchlists.iter().find(|w| w.id == id).map(|w| w.name.clone());
+
+        let mut rules = Vec::new();
+        let mut used = Vec::new();
+        for rule in store.list_alert_rules()? {
+            if rule_ids.is_some_and(|ids| !ids.contains(&rule.id)) {
+                continue;
+            }
+            let watchlist = rule.watchlist_id.and_then(watchlist_name);
+            if let (Some(id), Some(_)) = (rule.watchlist_id, &watchlist) {
+                if !used.contains(&id) {
+                    used.push(id);
+                }
+            }
+            rules.push(ExportedRule {
+                name: rule.name,
+                enabled: rule.enabled,
+                watchlist,
+                rule_json: rule.rule_json,
+                schedule: rule.schedule,
+                escalation_config: rule.escalation_config,
+            });
+        }
+        if let Some(ids) = rule_ids {
+            if rules.len() < ids.len() {
+                anyhow::bail!("{} of the requested rules were not found", ids.len() - rules.len());
+            }
+        }
+
+        let mut exported_watchlists = Vec::new();
+        for id in used {
+            let Some(name) = watchlist_name(id) else { continue };
+            let items = store
+                .list_watchlist_items(id)?
+                .into_iter()
+                .map(|i| ExportedWatchlistItem { item_type: i.item_type, value: i.value, weight: i.weight, enabled: i.enabled })
+                .collect();
+            exported_watchlists.push(ExportedWatchlist { name, items });
+        }
+
+        Ok(RuleExport {
+            version: EXPORT_VERSION,
+            exported_at: chrono::Utc::now().timestamp(),
+            rules,
+            watchlists: exported_watchlists,
+        })
+    }
+
+    /// Create the exported rules in the active profile. Watchlists are
+    /// matched by name; missing ones are created from the export when
+    /// `create_missing_watchlists` is set, otherwise their rules are skipped
+    /// rather than imported unscoped.
+    pub fn import(store: &TemporalStore, export: &RuleExport, create_missing_watchlists: bool) -> Result<ImportReport> {
+        if export.version > EXPORT_VERSION {
+            anyhow::bail!("Export version {} is newer than supported ({})", export.version, EXPORT_VERSION);
+        }
+        let mut report = ImportReport::default();
+        let mut watchlists: Vec<(String, i64)> = store.list_watchlists()?.into_iter().map(|w| (w.name, w.id)).collect();
+        let mut names: Vec<String> = store.list_alert_rules()?.into_iter().map(|r| r.name).collect();
+
+        for rule in &export.rules {
+            if let Err(e) = validate_rule_json(&rule.rule_json) {
+                report.skipped.push(SkippedRule { name: rule.name.clone(), reason: e.to_string() });
+                continue;
+            }
+
+            let watchlist_id = match &rule.watchlist {
+                None => None,
+                Some(name) => match watchlists.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)) {
+                    Some((_, id)) => Some(*id),
+                    None if create_missing_watchlists => {
+                        let id = store.create_watchlist(name)?;
+                        if let Some(exported) = export.watchlists.iter().find(|w| w.name.eq_ignore_ascii_case(name)) {
+                            for item in &exported.items {
+                                store.add_watchlist_item(id, &item.item_type, &item.value, item.weight, item.enabled)?;
+                            }
+                        }
+                        watchlists.push((name.clone(), id));
+                        report.created_watchlists.push(name.clone());
+                        Some(id)
+                    }
+                    None => {
+                        report.skipped.push(SkippedRule {
+                            name: rule.name.clone(),
+                            reason: format!("Watchlist '{}' does not exist", name),
+                        });
+                        continue;
+                    }
+                },
+            };
+
+            let name = unique_name(&rule.name, &names);
+            let id = store.create_alert_rule(
+                &name,
+                rule.enabled,
+                watchlist_id,
+                &rule.rule_json,
+                rule.schedule.as_deref(),
+                rule.escalation_config.as_ref(),
+            )?;
+            names.push(name.clone());
+            report.imported.push(ImportedRule { id, name });
+        }
+        Ok(report)
+    }
+
+    /// The built-in gallery.
+    pub fn templates() -> Vec<RuleTemplate> {
+        vec![
+            RuleTemplate {
+                id: "earnings_surprise".to_string(),
+                name: "Earnings surprise watch".to_string(),
+                description: "Results, guidance changes and estimate beats or misses for the companies you follow.".to_string(),
+                params: vec![
+                    param("companies", "Companies or tickers to watch", "list", None),
+                    param("min_severity", "Minimum severity (0-1)", "number", Some(json!(0.5))),
+                ],
+                rule_json: json!({ "logic": { "operator": "AND", "conditions": [
+                    { "type": "mentions_any_entity", "entities": "{{companies}}" },
+                    { "type": "contains_regex", "pattern": "earnings|guidance|quarterly results|(beat|miss(ed)?|top(ped)?) (estimates|expectations|forecasts)|profit warning" },
+                    { "type": "severity", "operator": ">=", "value": "{{min_severity}}" }
+                ] } }),
+            },
+            RuleTemplate {
+                id: "regulatory_action".to_string(),
+                name: "Regulatory action watch".to_string(),
+                description: "Investigations, fines, lawsuits and sanctions against the companies you follow.".to_string(),
+                params: vec![
+                    param("companies", "Companies or tickers to watch", "list", None),
+                    param("min_severity", "Minimum severity (0-1)", "number", Some(json!(0.4))),
+                ],
+                rule_json: json!({ "logic": { "operator": "AND", "conditions": [
+                    { "type": "mentions_any_entity", "entities": "{{companies}}" },
+                    { "type": "contains_regex", "pattern": "\\b(regulators?|antitrust|investigation|probe|fined?|penalty|lawsuit|sued|sanctions?|subpoena|consent decree)\\b" },
+                    { "type": "severity", "operator": ">=", "value": "{{min_severity}}" }
+                ] } }),
+            },
+            RuleTemplate {
+                id: "supply_chain_disruption".to_string(),
+                name: "Supply-chain disruption".to_string(),
+                description: "Shortages, stoppages and logistics trouble at suppliers, ports or regions you depend on.".to_string(),
+                params: vec![
+                    param("suppliers", "Suppliers, ports or regions to watch", "list", None),
+                    param("min_severity", "Minimum severity (0-1)", "number", Some(json!(0.5))),
+                ],
+                rule_json: json!({ "logic": { "operator": "AND", "conditions": [
+                    { "type": "mentions_any_entity", "entities": "{{suppliers}}" },
+                    { "type": "contains_regex", "pattern": "shortage|supply chain|disruption|factory fire|port (closure|congestion)|strike|recall|(halt|suspend)(ed|s)? production|export (ban|controls?)" },
+                    { "type": "severity", "operator": ">=", "value": "{{min_severity}}" }
+                ] } }),
+            },
+        ]
+    }
+
+    /// The template's rule JSON with `params` filled in. Missing optional
+    /// parameters take their defaults; lists may be given as arrays or
+    /// comma-separated text.
+    pub fn render(template_id: &str, params: &Map<String, Value>) -> Result<Value> {
+        let template = Self::templates()
+            .into_iter()
+            .find(|t| t.id == template_id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown rule template: {}", template_id))?;
+
+        let mut values = Map::new();
+        for p in &template.params {
+            let value = match params.get(&p.key).filter(|v| !v.is_null()).or(p.default.as_ref()) {
+                Some(v) => coerce_param(p, v)?,
+                None => anyhow::bail!("{} is required", p.prompt),
+            };
+            values.insert(p.key.clone(), value);
+        }
+        Ok(substitute(&template.rule_json, &values))
+    }
+
+    /// Create a rule from a template. `name` defaults to the template's.
+    pub fn instantiate(
+        store: &TemporalStore,
+        template_id: &str,
+        params: &Map<String, Value>,
+        name: Option<&str>,
+        watchlist_id: Option<i64>,
+    ) -> Result<i64> {
+        let rule_json = Self::render(template_id, params)?;
+        let default_name = Self::templates().into_iter().find(|t| t.id == template_id).map(|t| t.name).unwrap_or_default();
+        let name = name.map(str::trim).filter(|n| !n.is_empty()).unwrap_or(&default_name);
+        store.create_alert_rule(name, true, watchlist_id, &rule_json, None, None)
+    }
+}
+
+fn coerce_param(p: &TemplateParam, value: &Value) -> Result<Value> {
+    match p.kind.as_str() {
+        "list" => {
+            let items: Vec<String> = match value {
+                Value::Array(items) => items.iter().filter_map(|v| v.as_str()).map(|s| s.trim().to_string()).collect(),
+                Value::String(s) => s.split(',').map(|s| s.trim().to_string()).collect(),
+                _ => anyhow::bail!("{} must be a list", p.prompt),
+            };
+            let items: Vec<String> = items.into_iter().filter(|s| !s.is_empty()).collect();
+            if items.is_empty() && p.required {
+                anyhow::bail!("{} needs at least one value", p.prompt);
+            }
+            Ok(json!(items))
+        }
+        "number" => {
+            let n = value
+                .as_f64()
+                .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+                .ok_or_else(|| anyhow::anyhow!("{} must be a number", p.prompt))?;
+            if p.min.is_some_and(|min| n < min) || p.max.is_some_and(|max| n > max) {
+                anyhow::bail!("{} must be between {} and {}", p.prompt, p.min.unwrap_or(f64::MIN), p.max.unwrap_or(f64::MAX));
+            }
+            Ok(json!(n))
+        }
+        _ => match value.as_str().map(str::trim) {
+            Some(s) if !s.is_empty() || !p.required => Ok(json!(s)),
+            _ => anyhow::bail!("{} must be text", p.prompt),
+        },
+    }
+}
+
+fn substitute(value: &Value, params: &Map<String, Value>) -> Value {
+    match value {
+        Value::String(s) => s
+            .strip_prefix("{{")
+            .and_then(|s| s.strip_suffix("}}"))
+            .and_then(|key| params.get(key.trim()))
+            .cloned()
+            .unwrap_or_else(|| value.clone()),
+        Value::Array(items) => Value::Array(items.iter().map(|v| substitute(v, params)).collect()),
+        Value::Object(map) => Value::Object(map.iter().map(|(k, v)| (k.clone(), substitute(v, params))).collect()),
+        _ => value.clone(),
+    }
+}
+
+/// Rules are `{ "logic": {...} }` or the legacy `{ "any": [...], "all": [...] }`.
+fn validate_rule_json(rule_json: &Value) -> Result<()> {
+    let is_rule = rule_json.get("logic").is_some_and(|l| l.is_object())
+        || rule_json.get("any").is_some_and(|a| a.is_array())
+        || rule_json.get("all").is_some_and(|a| a.is_array());
+    if !is_rule {
+        anyhow::bail!("Not an alert rule: expected a logic group or any/all conditions");
+    }
+    Ok(())
+}
+
+/// `name`, or "name (imported)", "name (imported 2)", ... if taken.
+fn unique_name(name: &str, taken: &[String]) -> String {
+    let free = |candidate: &str| !taken.iter().any(|t| t.eq_ignore_ascii_case(candidate));
+    if free(name) {
+        return name.to_string();
+    }
+    let mut candidate = format!("{} (imported)", name);
+    let mut n = 2;
+    while !free(&candidate) {
+        candidate = format!("{} (imported {})", name, n);
+        n += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn rules_round_trip_with_watchlists_by_name() {
+        let (source_db, target_db) = (test_support::test_db(), test_support::test_db());
+        let source = TemporalStore::new(source_db.conn.clone());
+        let watchlist = source.create_watchlist("Semis").unwrap();
+        source.add_watchlist_item(watchlist, "entity", "nvidia", 1.0, true).unwrap();
+        let rule = json!({ "any": [{ "type": "contains_keyword", "keyword": "export" }] });
+        source.create_alert_rule("Chip exports", true, Some(watchlist), &rule, None, None).unwrap();
+        let export = AlertRuleLibrary::export(&source, None).unwrap();
+        assert_eq!(export.rules[0].watchlist.as_deref(), Some("Semis"));
+
+        let target = TemporalStore::new(target_db.conn.clone());
+        target.create_alert_rule("Chip exports", true, None, &rule, None, None).unwrap();
+        // Without the watchlist the rule is skipped, not imported unscoped
+        let report = AlertRuleLibrary::import(&target, &export, false).unwrap();
+        assert_eq!((report.imported.len(), report.skipped.len()), (0, 1));
+
+        let report = AlertRuleLibrary::import(&target, &export, true).unwrap();
+        assert_eq!(report.created_watchlists, vec!["Semis".to_string()]);
+        assert_eq!(report.imported[0].name, "Chip exports (imported)");
+        let imported = target.get_alert_rule(report.imported[0].id).unwrap().unwrap();
+        let semis = target.list_watchlists().unwrap().into_iter().find(|w| w.name == "Semis").unwrap();
+        assert_eq!(imported.watchlist_id, Some(semis.id));
+        assert_eq!(target.list_watchlist_items(semis.id).unwrap()[0].value, "nvidia");
+    }
+
+    #[test]
+    fn templates_render_with_typed_params() {
+        let params = json!({ "companies": "Apple, , TSMC" });
+        let rule = AlertRuleLibrary::render("earnings_surprise", params.as_object().unwrap()).unwrap();
+        let conditions = &rule["logic"]["conditions"];
+        assert_eq!(conditions[0]["entities"], json!(["Apple", "TSMC"]));
+        assert_eq!(conditions[2]["value"], json!(0.5));
+        assert!(validate_rule_json(&rule).is_ok());
+
+        assert!(AlertRuleLibrary::render("earnings_surprise", &Map::new()).is_err());
+        let too_high = json!({ "companies": ["Apple"], "min_severity": 3 });
+        assert!(AlertRuleLibrary::render("earnings_surprise", too_high.as_object().unwrap()).is_err());
+        for template in AlertRuleLibrary::templates() {
+            for p in &template.params {
+                assert!(template.rule_json.to_string().contains(&format!("{{{{{}}}}}", p.key)), "{} unused in {}", p.key, template.id);
+            }
+        }
+    }
+}
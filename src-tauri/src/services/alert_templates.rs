@@ -0,0 +1,166 @@
+use crate::services::prompt_bundle::{missing_variables, render_template};
+use crate::storage::temporal::{Alert, AlertRule};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Subject and body for one channel, in prompt template syntax (`{{event.title}}`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageTemplate {
+    #[serde(default)]
+    pub subject: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedMessage {
+    pub channel: String,
+    pub subject: String,
+    pub body: String,
+    /// False when no template applies and the untemplated message was used
+    pub templated: bool,
+    pub missing_variables: Vec<String>,
+}
+
+pub struct AlertTemplates;
+
+impl AlertTemplates {
+    /// The rule's template for `channel`: `templates.<channel>` of its
+    /// escalation config, else `templates.default`.
+    pub fn for_channel(rule: &AlertRule, channel: &str) -> Option<MessageTemplate> {
+        let templates = rule.escalation_config.as_ref()?.get("templates")?;
+        templates
+            .get(channel)
+            .or_else(|| templates.get("default"))
+            .and_then(|t| serde_json::from_value(t.clone()).ok())
+    }
+
+    /// Template variables of a fired alert: its payload (rule, event, scores,
+    /// lifecycle, corroboration, commentary, links) plus `alert.*` and
+    /// `reasons`, the matched conditions joined with "; ".
+    pub fn variables(alert: &Alert) -> Value {
+        let mut vars = match &alert.payload_json {
+            Value::Object(_) => alert.payload_json.clone(),
+            _ => serde_json::json!({}),
+        };
+        let fired_at = chrono::DateTime::from_timestamp(alert.fired_at, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string());
+        vars["alert"] = serde_json::json!({
+            "id": alert.id,
+            "status": alert.status,
+            "fired_at": fired_at,
+        });
+        let reasons: Vec<&str> = alert
+            .payload_json
+            .get("explanation")
+            .and_then(|e| e.get("reasons"))
+            .and_then(|r| r.as_array())
+            .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        vars["reasons"] = Value::String(reasons.join("; "));
+        vars
+    }
+
+    /// Stand-in variables for previewing a rule that hasn't fired yet.
+    pub fn sample_variables(rule: &AlertRule) -> Value {
+        let now = chrono::Utc::now().timestamp();
+        serde_json::json!({
+            "rule": { "id": rule.id, "name": rule.name },
+            "alert": {
+                "id": 0,
+                "status": "new",
+                "fired_at": chrono::Utc::now().format("%Y-%m-%d %H:%M UTC").to_string(),
+            },
+            "event": { "id": 0, "title": "Sample event title", "start_ts": now, "end_ts": now },
+            "scores": { "sentiment": -0.4, "novelty": 0.8, "volume": 0.6, "severity": 0.7, "confidence": 0.9 },
+            "lifecycle": { "state": "developing", "previous": "emerging", "changed_at": now },
+            "corroboration": { "sources": 3, "corroborated_by": 2, "reliability_tiers": 2, "time_to_second_source": 600 },
+            "commentary": { "what_happened": "Sample summary.", "why_it_matters": "Sample impact." },
+            "links": { "articles": ["https://example.com/article"] },
+            "reasons": "matched sample condition",
+        })
+    }
+
+    /// Render the message for `channel`. Without a template this is the
+    /// payload's title and message (or the payload itself) with the
+    /// analyst commentary appended.
+    pub fn render(channel: &str, template: Option<&MessageTemplate>, variables: &Value) -> RenderedMessage {
+        let fallback_subject = variables
+            .get("title")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Alert")
+            .to_string();
+        let (subject, body, missing) = match template {
+            Some(t) => {
+                let mut missing = Vec::new();
+                let subject = match &t.subject {
+                    Some(s) => {
+                        missing.extend(missing_variables(s, variables));
+                        render_template(s, variables)
+                    }
+                    None => fallback_subject,
+                };
+                let body = match &t.body {
+                    Some(b) => {
+                        missing.extend(missing_variables(b, variables));
+                        render_template(b, variables)
+                    }
+                    None => fallback_body(variables),
+                };
+                missing.sort();
+                missing.dedup();
+                (subject, body, missing)
+            }
+            None => (fallback_subject, fallback_body(variables), Vec::new()),
+        };
+
+        RenderedMessage {
+            channel: channel.to_string(),
+            subject,
+            body,
+            templated: template.is_some(),
+            missing_variables: missing,
+        }
+    }
+}
+
+fn fallback_body(variables: &Value) -> String {
+    let message = variables
+        .get("message")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| serde_json::to_string(variables).unwrap_or_else(|_| "Alert triggered".to_string()));
+
+    // Analyst commentary, when the event has been analyzed
+    match variables
+        .get("commentary")
+        .and_then(|c| c.get("why_it_matters"))
+        .and_then(|v| v.as_str())
+    {
+        Some(why) => format!("{}\n\nWhy it matters: {}", message, why),
+        None => message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_channel_template_with_dotted_variables() {
+        let template = MessageTemplate {
+            subject: Some("[{{rule.name}}] {{event.title}}".to_string()),
+            body: Some("Severity {{scores.severity}}\n{{links.articles}}\n{{event.missing}}".to_string()),
+        };
+        let vars = serde_json::json!({
+            "rule": { "name": "Chips" },
+            "event": { "title": "Export rules tightened" },
+            "scores": { "severity": 0.75 },
+            "links": { "articles": ["https://a.example/1", "https://b.example/2"] },
+        });
+        let rendered = AlertTemplates::render("email", Some(&template), &vars);
+        assert_eq!(rendered.subject, "[Chips] Export rules tightened");
+        assert_eq!(rendered.body, "Severity 0.75\nhttps://a.example/1, https://b.example/2\n");
+        assert_eq!(rendered.missing_variables, vec!["event.missing".to_string()]);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::storage::osint::{ArticleCleanupPreview, OSINTStore};
+use crate::storage::Database;
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+
+/// Read articles older than this many days are cleaned up daily. Unset or 0 keeps everything.
+pub const CONFIG_CLEANUP_DAYS: &str = "article_cleanup_days";
+/// "archive" (default) keeps a compressed copy, "delete" drops articles outright.
+pub const CONFIG_CLEANUP_MODE: &str = "article_cleanup_mode";
+
+pub const MODES: [&str; 2] = ["archive", "delete"];
+const DAY_SECS: i64 = 86400;
+const CHECK_INTERVAL_SECS: u64 = 24 * 3600;
+
+pub struct ArticleCleanup;
+
+impl ArticleCleanup {
+    pub fn configured_days(db: &Database) -> Option<i64> {
+        db.get_config(CONFIG_CLEANUP_DAYS)
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .filter(|d| *d > 0)
+    }
+
+    pub fn configured_mode(db: &Database) -> String {
+        db.get_config(CONFIG_CLEANUP_MODE)
+            .ok()
+            .flatten()
+            .map(|m| m.trim().to_lowercase())
+            .filter(|m| MODES.contains(&m.as_str()))
+            .unwrap_or_else(|| MODES[0].to_string())
+    }
+
+    fn cutoff(days: i64) -> Result<i64> {
+        if days < 1 {
+            anyhow::bail!("Articles must be at least 1 day old to clean up");
+        }
+        Ok(chrono::Utc::now().timestamp() - days * DAY_SECS)
+    }
+
+    pub fn preview(store: &OSINTStore, days: i64) -> Result<ArticleCleanupPreview> {
+        store.preview_article_cleanup(Self::cutoff(days)?)
+    }
+
+    /// Archive or delete read, non-favorite articles older than `days`.
+    /// Returns the number removed.
+    pub fn apply(store: &OSINTStore, days: i64, mode: &str) -> Result<usize> {
+        if !MODES.contains(&mode) {
+            anyhow::bail!("Unknown cleanup mode {} (use {})", mode, MODES.join(" or "));
+        }
+        store.cleanup_read_articles(Self::cutoff(days)?, mode == "delete")
+    }
+
+    /// Apply the configured cleanup, if any.
+    pub fn apply_configured(db: &Arc<Mutex<Database>>) -> Result<usize> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        match Self::configured_days(&db_guard) {
+            Some(days) => Self::apply(&OSINTStore::new(db_guard.conn.clone()), days, &Self::configured_mode(&db_guard)),
+            None => Ok(0),
+        }
+    }
+
+    pub fn start_scheduler(db: Arc<Mutex<Database>>) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+            let heartbeat = crate::services::watchdog::Watchdog::register("article_cleanup", tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+
+            loop {
+                interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
+                match Self::apply_configured(&db) {
+                    Ok(0) => {}
+                    Ok(n) => eprintln!("Cleaned up {} read articles", n),
+                    Err(e) => eprintln!("Article cleanup failed: {}", e),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+    use rusqlite::params;
+
+    #[test]
+    fn only_old_read_unkept_articles_are_cleaned_up() {
+        let db = test_support::test_db();
+        let store = OSINTStore::new(db.conn.clone());
+        let old = test_support::FIXTURE_TS - 30 * DAY_SECS;
+        let mut ids = Vec::new();
+        for title in ["Plain", "Favorite", "Unread", "Evidence", "Recent"] {
+            let id = test_support::article(&db, "Wire", title, "Body text");
+            ids.push(id);
+            let published_at = if title == "Recent" { chrono::Utc::now().timestamp() } else { old };
+            let read = if title == "Unread" { 0 } else { 1 };
+            let favorite = if title == "Favorite" { 1 } else { 0 };
+            db.conn
+                .lock()
+                .unwrap()
+                .execute(
+                    "UPDATE rss_items SET published_at = ?1, read = ?2, favorite = ?3 WHERE id = ?4",
+                    params![published_at, read, favorite, id],
+                )
+                .unwrap();
+        }
+        test_support::event(&db, &test_support::new_event("Cited"), &[ids[3]]);
+
+        let preview = ArticleCleanup::preview(&store, 7).unwrap();
+        assert_eq!(preview.count, 1);
+        assert_eq!(preview.oldest_published_at, Some(old));
+        assert!(ArticleCleanup::apply(&store, 7, "shred").is_err());
+
+        assert_eq!(ArticleCleanup::apply(&store, 7, "archive").unwrap(), 1);
+        let conn = db.conn.lock().unwrap();
+        let remaining: i64 = conn.query_row("SELECT COUNT(*) FROM rss_items", [], |row| row.get(0)).unwrap();
+        let archived: i64 = conn
+            .query_row("SELECT id FROM rss_item_archive", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 4);
+        assert_eq!(archived, ids[0]);
+    }
+}
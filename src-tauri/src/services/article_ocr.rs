@@ -0,0 +1,297 @@
+use crate::commands::ollama::OllamaState;
+use crate::services::embeddings::EmbeddingService;
+use crate::services::ocr::OcrEngine;
+use crate::storage::vector_store::image_document_id;
+use crate::storage::{ArticleImageText, Database, OSINTStore, VectorStore};
+use anyhow::{Context, Result};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+pub const CONFIG_ENABLED: &str = "article_ocr_enabled";
+/// Ollama vision model (e.g. "llava"). When set it reads images instead of tesseract.
+pub const CONFIG_VISION_MODEL: &str = "article_ocr_vision_model";
+
+const MAX_ARTICLES_PER_RUN: i64 = 10;
+const MAX_IMAGES_PER_ARTICLE: usize = 8;
+const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+/// Less than this is usually a logo or OCR noise from a photo
+const MIN_TEXT_CHARS: usize = 12;
+
+const VISION_PROMPT: &str = "Transcribe all readable text in this image, including chart titles, axis labels, \
+numbers and table cells. Reply with the text only. If there is no text, reply with nothing.";
+
+enum Reader {
+    Tesseract(OcrEngine),
+    Vision(OllamaState, String),
+}
+
+impl Reader {
+    fn engine_name(&self) -> String {
+        match self {
+            Reader::Tesseract(_) => "tesseract".to_string(),
+            Reader::Vision(_, model) => model.clone(),
+        }
+    }
+
+    async fn read(&self, bytes: Vec<u8>, extension: &str) -> Result<String> {
+        match self {
+            Reader::Tesseract(engine) => {
+                let engine = engine.clone();
+                let path = std::env::temp_dir().join(format!("mina-img-{}.{}", uuid::Uuid::new_v4(), extension));
+                std::fs::write(&path, &bytes)?;
+                let owned = path.clone();
+                let text = tokio::task::spawn_blocking(move || engine.image_text(&owned)).await;
+                let _ = std::fs::remove_file(&path);
+                text.context("OCR task panicked")?
+            }
+            Reader::Vision(ollama, model) => {
+                let provider = ollama.read().await;
+                provider.generate_with_images(model, VISION_PROMPT, &[bytes]).await
+            }
+        }
+    }
+}
+
+/// Optional OCR pass over images embedded in saved articles. Recognized text
+/// is stored per image and appended to the article's search index entry, so
+/// charts and screenshots of statements can be found by their contents.
+/// The downloaded images are also added to the reverse image index.
+pub struct ArticleOcr;
+
+impl ArticleOcr {
+    pub fn start_scheduler(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30 * 60));
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = Self::run_once(&db, &app).await {
+                    eprintln!("Error running article image OCR: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Process saved articles not yet OCR'd, if enabled. Returns how many
+    /// images yielded text.
+    pub async fn run_once(db: &Arc<Mutex<Database>>, app: &AppHandle) -> Result<usize> {
+        let (enabled, conn, embedder) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            (
+                db_guard.get_config(CONFIG_ENABLED)?.map(|v| v == "true").unwrap_or(false),
+                db_guard.conn.clone(),
+                image_embedder(&db_guard),
+            )
+        };
+        if !enabled {
+            return Ok(0);
+        }
+        let reader = match Self::reader(db, app)? {
+            Some(r) => r,
+            None => return Ok(0),
+        };
+
+        let store = OSINTStore::new(conn.clone());
+        let vectors = VectorStore::new(conn);
+        let mut found = 0;
+        for (id, url, content) in store.articles_pending_ocr(MAX_ARTICLES_PER_RUN)? {
+            match Self::process(&store, &vectors, &embedder, &reader, id, &url, &content).await {
+                Ok(texts) => found += texts.len(),
+                Err(e) => eprintln!("Failed to OCR images of article {}: {}", id, e),
+            }
+        }
+        Ok(found)
+    }
+
+    /// OCR the images of one article now, regardless of the enabled flag or
+    /// earlier runs.
+    pub async fn process_article(db: &Arc<Mutex<Database>>, app: &AppHandle, article_id: i64) -> Result<Vec<ArticleImageText>> {
+        let (conn, embedder) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            (db_guard.conn.clone(), image_embedder(&db_guard))
+        };
+        let reader = Self::reader(db, app)?
+            .ok_or_else(|| anyhow::anyhow!("No OCR engine available: install tesseract or set a vision model"))?;
+        let store = OSINTStore::new(conn.clone());
+        let vectors = VectorStore::new(conn);
+        let article = store
+            .get_item(article_id)?
+            .ok_or_else(|| anyhow::anyhow!("Article {} not found", article_id))?;
+        Self::process(&store, &vectors, &embedder, &reader, article.id, &article.url, &article.content).await
+    }
+
+    /// The configured vision model when Ollama is running, otherwise tesseract.
+    fn reader(db: &Arc<Mutex<Database>>, app: &AppHandle) -> Result<Option<Reader>> {
+        let (vision_model, engine) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            (
+                db_guard.get_config(CONFIG_VISION_MODEL)?.filter(|m| !m.trim().is_empty()),
+                OcrEngine::from_config(&db_guard),
+            )
+        };
+        if let Some(model) = vision_model {
+            if let Some(state) = app.try_state::<OllamaState>() {
+                return Ok(Some(Reader::Vision(state.inner().clone(), model)));
+            }
+        }
+        Ok(engine.is_available().then_some(Reader::Tesseract(engine)))
+    }
+
+    async fn process(
+        store: &OSINTStore,
+        vectors: &VectorStore,
+        embedder: &EmbeddingService,
+        reader: &Reader,
+        article_id: i64,
+        article_url: &str,
+        content: &str,
+    ) -> Result<Vec<ArticleImageText>> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(20))
+            .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
+            .build()?;
+        let urls = image_urls(content, article_url);
+        let engine = reader.engine_name();
+
+        let mut texts = Vec::new();
+        for url in urls.iter().take(MAX_IMAGES_PER_ARTICLE) {
+            let (bytes, extension) = match fetch_image(&client, url).await {
+                Ok(image) => image,
+                Err(e) => {
+                    eprintln!("Skipping image {}: {}", url, e);
+                    continue;
+                }
+            };
+            let indexed = match embedder.generate_image(&bytes).await {
+                Ok(embedding) => vectors.index_image(
+                    &image_document_id(&bytes),
+                    url,
+                    embedder.image_model(),
+                    embedding,
+                    serde_json::json!({ "kind": "article_image", "article_id": article_id }),
+                ),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = indexed {
+                eprintln!("Failed to index image {}: {}", url, e);
+            }
+
+            let text = match reader.read(bytes, extension).await {
+                Ok(t) => t.split_whitespace().collect::<Vec<_>>().join(" "),
+                Err(e) => {
+                    eprintln!("OCR failed for {}: {}", url, e);
+                    continue;
+                }
+            };
+            if text.chars().count() < MIN_TEXT_CHARS {
+                continue;
+            }
+            texts.push(ArticleImageText {
+                article_id,
+                image_url: url.clone(),
+                text,
+                engine: engine.clone(),
+                processed_at: chrono::Utc::now().timestamp(),
+            });
+        }
+
+        store.save_article_image_text(article_id, urls.len().min(MAX_IMAGES_PER_ARTICLE) as i64, &texts)?;
+        Ok(texts)
+    }
+}
+
+fn image_embedder(db: &Database) -> EmbeddingService {
+    let mut embedder = EmbeddingService::new();
+    embedder.load_image_config(db);
+    embedder
+}
+
+/// Download an image, returning its bytes and a file extension for its type.
+pub(crate) async fn fetch_image(client: &reqwest::Client, url: &str) -> Result<(Vec<u8>, &'static str)> {
+    let response = client.get(url).send().await?.error_for_status()?;
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_lowercase();
+    let extension = match content_type.split(';').next().unwrap_or_default().trim() {
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/tiff" => "tiff",
+        "image/bmp" => "bmp",
+        other => anyhow::bail!("unsupported content type '{}'", other),
+    };
+    if response.content_length().is_some_and(|len| len as usize > MAX_IMAGE_BYTES) {
+        anyhow::bail!("image larger than {} bytes", MAX_IMAGE_BYTES);
+    }
+    let bytes = response.bytes().await?;
+    if bytes.len() > MAX_IMAGE_BYTES {
+        anyhow::bail!("image larger than {} bytes", MAX_IMAGE_BYTES);
+    }
+    Ok((bytes.to_vec(), extension))
+}
+
+/// Absolute, de-duplicated `<img src>` URLs in article HTML. Inline data
+/// URIs and SVGs (no raster text to read) are skipped.
+fn image_urls(html: &str, base_url: &str) -> Vec<String> {
+    let document = scraper::Html::parse_fragment(html);
+    let selector = scraper::Selector::parse("img[src]").expect("valid selector");
+    let base = reqwest::Url::parse(base_url).ok();
+
+    let mut urls: Vec<String> = Vec::new();
+    for img in document.select(&selector) {
+        let src = img.value().attr("src").unwrap_or_default().trim();
+        if src.is_empty() || src.starts_with("data:") {
+            continue;
+        }
+        let resolved = match &base {
+            Some(base) => base.join(src).ok(),
+            None => reqwest::Url::parse(src).ok(),
+        };
+        let Some(url) = resolved else { continue };
+        if !matches!(url.scheme(), "http" | "https") || url.path().to_lowercase().ends_with(".svg") {
+            continue;
+        }
+        let url = url.to_string();
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_urls_resolves_and_filters() {
+        let html = r#"<p>Q3 results</p>
+            <img src="/charts/revenue.png" alt="chart">
+            <img src="https://cdn.example.com/shot.jpg">
+            <img src="/charts/revenue.png">
+            <img src="data:image/png;base64,AAAA">
+            <img src="logo.svg">"#;
+        let urls = image_urls(html, "https://news.example.com/markets/article-1");
+        assert_eq!(
+            urls,
+            vec![
+                "https://news.example.com/charts/revenue.png".to_string(),
+                "https://cdn.example.com/shot.jpg".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn image_urls_without_base_keeps_absolute_only() {
+        let html = r#"<img src="/relative.png"><img src="http://example.com/a.png">"#;
+        assert_eq!(image_urls(html, ""), vec!["http://example.com/a.png".to_string()]);
+    }
+}
@@ -0,0 +1,323 @@
+use crate::providers::backup::{BackupDestination, RemoteFile, WebDavDestination};
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::object_storage::{ObjectStorageService, BACKUPS_PREFIX};
+use crate::storage::Database;
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+
+pub const CONFIG_DESTINATION: &str = "backup_destination"; // "webdav" or "s3"
+pub const CONFIG_WEBDAV_URL: &str = "backup_webdav_url";
+pub const CONFIG_WEBDAV_USER: &str = "backup_webdav_user";
+pub const CONFIG_INTERVAL_HOURS: &str = "backup_interval_hours";
+pub const CONFIG_KEEP_DAILY: &str = "backup_keep_daily";
+pub const CONFIG_KEEP_WEEKLY: &str = "backup_keep_weekly";
+const CONFIG_LAST_RUN: &str = "backup_last_run_at";
+
+/// The WebDAV password (a Nextcloud app password) lives in APIKeyManager.
+pub const WEBDAV_KEY_PROVIDER: &str = "webdav";
+
+const DEFAULT_KEEP_DAILY: usize = 7;
+const DEFAULT_KEEP_WEEKLY: usize = 4;
+const BACKUP_PREFIX: &str = "mina-backup-";
+const BACKUP_SUFFIX: &str = ".db.gz";
+const BACKUP_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupReport {
+    pub file_name: String,
+    pub size_bytes: u64,
+    pub destination: String,
+    pub rotated_out: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupEntry {
+    pub name: String,
+    pub created_at: i64,
+    pub size: Option<u64>,
+}
+
+pub struct BackupService;
+
+impl BackupService {
+    /// Build the configured destination, or None when backups aren't set up.
+    pub fn destination(db: &Database, api_key_manager: &APIKeyManager) -> Result<Option<Box<dyn BackupDestination>>> {
+        match db.get_config(CONFIG_DESTINATION)?.as_deref() {
+            Some("webdav") => {
+                let url = db.get_config(CONFIG_WEBDAV_URL)?
+                    .filter(|u| !u.trim().is_empty())
+                    .ok_or_else(|| anyhow::anyhow!("WebDAV URL is not configured"))?;
+                let user = db.get_config(CONFIG_WEBDAV_USER)?.filter(|u| !u.is_empty());
+                let password = api_key_manager.get_key_optional(WEBDAV_KEY_PROVIDER)?;
+                Ok(Some(Box::new(WebDavDestination::new(&url, user, password))))
+            }
+            Some("s3") => {
+                let bucket = ObjectStorageService::bucket(db, api_key_manager)?
+                    .ok_or_else(|| anyhow::anyhow!("Object storage is not configured"))?;
+                Ok(Some(Box::new(bucket.with_subprefix(BACKUPS_PREFIX))))
+            }
+            Some("") | None => Ok(None),
+            Some(other) => Err(anyhow::anyhow!("Unknown backup destination: {}", other)),
+        }
+    }
+
+    /// Snapshot the database, push it to the destination and rotate old backups.
+    pub async fn run_backup(db: &Arc<Mutex<Database>>, api_key_manager: &APIKeyManager) -> Result<BackupReport> {
+        let (destination, keep_daily, keep_weekly, snapshot) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let destination = Self::destination(&db_guard, api_key_manager)?
+                .ok_or_else(|| anyhow::anyhow!("No backup destination configured"))?;
+            let keep_daily = db_guard.get_config(CONFIG_KEEP_DAILY)?
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_KEEP_DAILY);
+            let keep_weekly = db_guard.get_config(CONFIG_KEEP_WEEKLY)?
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_KEEP_WEEKLY);
+            (destination, keep_daily, keep_weekly, Self::create_snapshot(&db_guard)?)
+        };
+
+        let file_name = snapshot
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let size_bytes = std::fs::metadata(&snapshot).map(|m| m.len()).unwrap_or(0);
+        let uploaded = destination.upload(&file_name, &snapshot).await;
+        let _ = std::fs::remove_file(&snapshot);
+        uploaded?;
+
+        let backups = parse_backups(&destination.list().await?);
+        let mut rotated_out = Vec::new();
+        for name in select_expired(&backups, keep_daily, keep_weekly) {
+            match destination.delete(&name).await {
+                Ok(()) => rotated_out.push(name),
+                Err(e) => eprintln!("Failed to rotate out backup {}: {}", name, e),
+            }
+        }
+
+        {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            db_guard.set_config(CONFIG_LAST_RUN, &chrono::Utc::now().timestamp().to_string())?;
+        }
+
+        Ok(BackupReport {
+            file_name,
+            size_bytes,
+            destination: destination.get_name().to_string(),
+            rotated_out,
+        })
+    }
+
+    pub async fn list_backups(db: &Arc<Mutex<Database>>, api_key_manager: &APIKeyManager) -> Result<Vec<BackupEntry>> {
+        let destination = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            Self::destination(&db_guard, api_key_manager)?
+                .ok_or_else(|| anyhow::anyhow!("No backup destination configured"))?
+        };
+        Ok(parse_backups(&destination.list().await?))
+    }
+
+    /// Download a backup and stage it; it replaces the database on the next launch.
+    pub async fn restore(db: &Arc<Mutex<Database>>, api_key_manager: &APIKeyManager, name: &str) -> Result<()> {
+        if backup_time(name).is_none() {
+            return Err(anyhow::anyhow!("{} is not a mina backup", name));
+        }
+        let destination = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            Self::destination(&db_guard, api_key_manager)?
+                .ok_or_else(|| anyhow::anyhow!("No backup destination configured"))?
+        };
+
+        let download = std::env::temp_dir().join(name);
+        destination.download(name, &download).await?;
+        let compressed = std::fs::read(&download)?;
+        let _ = std::fs::remove_file(&download);
+
+        let mut decoder = flate2::read::GzDecoder::new(compressed.as_slice());
+        let mut restored = Vec::new();
+        std::io::Read::read_to_end(&mut decoder, &mut restored).context("Backup is not valid gzip")?;
+        if !restored.starts_with(b"SQLite format 3\0") {
+            return Err(anyhow::anyhow!("Backup does not contain a SQLite database"));
+        }
+
+        std::fs::write(Database::staged_restore_path()?, restored)
+            .context("Failed to stage restored database")?;
+        Ok(())
+    }
+
+    /// Run backups on the configured interval. An interval of 0 (the default) disables them.
+    pub fn start_scheduler(db: Arc<Mutex<Database>>, api_key_manager: Arc<APIKeyManager>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(300));
+
+            loop {
+                interval.tick().await;
+
+                let due = match db.lock() {
+                    Ok(db_guard) => {
+                        let hours: i64 = db_guard.get_config(CONFIG_INTERVAL_HOURS).ok().flatten()
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or(0);
+                        let last_run: i64 = db_guard.get_config(CONFIG_LAST_RUN).ok().flatten()
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or(0);
+                        hours > 0 && chrono::Utc::now().timestamp() - last_run >= hours * 3600
+                    }
+                    Err(_) => false,
+                };
+                if !due {
+                    continue;
+                }
+
+                match Self::run_backup(&db, &api_key_manager).await {
+                    Ok(report) => {
+                        let _ = crate::services::window_router::emit(&app, serde_json::json!({
+                            "type": "backup-completed",
+                            "data": report,
+                            "timestamp": chrono::Utc::now().timestamp_millis(),
+                        }));
+                    }
+                    Err(e) => {
+                        eprintln!("Scheduled backup failed: {}", e);
+                        if let Ok(db_guard) = db.lock() {
+                            let _ = db_guard.save_error("Backup Failure", &e.to_string(), None, Some("BackupService"), "error");
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Consistent copy of the live database via `VACUUM INTO`, gzip-compressed.
+    fn create_snapshot(db: &Database) -> Result<std::path::PathBuf> {
+        let stamp = chrono::Utc::now().format(BACKUP_TIME_FORMAT).to_string();
+        let dir = std::env::temp_dir();
+        let raw = dir.join(format!("{}{}.db", BACKUP_PREFIX, stamp));
+        let packed = dir.join(format!("{}{}{}", BACKUP_PREFIX, stamp, BACKUP_SUFFIX));
+        let _ = std::fs::remove_file(&raw);
+
+        {
+            let conn = db.conn.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            conn.execute("VACUUM INTO ?1", [raw.to_string_lossy().to_string()])
+                .context("Failed to snapshot database")?;
+        }
+
+        let data = std::fs::read(&raw)?;
+        let _ = std::fs::remove_file(&raw);
+        let mut encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(&packed)?,
+            flate2::Compression::default(),
+        );
+        encoder.write_all(&data)?;
+        encoder.finish()?;
+        Ok(packed)
+    }
+}
+
+fn backup_time(name: &str) -> Option<i64> {
+    let stamp = name.strip_prefix(BACKUP_PREFIX)?.strip_suffix(BACKUP_SUFFIX)?;
+    NaiveDateTime::parse_from_str(stamp, BACKUP_TIME_FORMAT)
+        .ok()
+        .map(|t| t.and_utc().timestamp())
+}
+
+/// Our backups among the remote files, newest first. Other files are left alone.
+fn parse_backups(files: &[RemoteFile]) -> Vec<BackupEntry> {
+    let mut backups: Vec<BackupEntry> = files
+        .iter()
+        .filter_map(|f| {
+            backup_time(&f.name).map(|created_at| BackupEntry {
+                name: f.name.clone(),
+                created_at,
+                size: f.size,
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    backups
+}
+
+/// Grandfather-father style rotation: keep the newest backup of each of the
+/// last `keep_daily` days and of each of the last `keep_weekly` ISO weeks.
+/// `backups` must be sorted newest first.
+fn select_expired(backups: &[BackupEntry], keep_daily: usize, keep_weekly: usize) -> Vec<String> {
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+    let mut expired = Vec::new();
+
+    for backup in backups {
+        let date = match chrono::DateTime::from_timestamp(backup.created_at, 0) {
+            Some(d) => d.date_naive(),
+            None => continue,
+        };
+        let week = (date.iso_week().year(), date.iso_week().week());
+
+        let mut keep = false;
+        if !days.contains(&date) && days.len() < keep_daily {
+            days.insert(date);
+            keep = true;
+        }
+        if !weeks.contains(&week) && weeks.len() < keep_weekly {
+            weeks.insert(week);
+            keep = true;
+        }
+        if !keep {
+            expired.push(backup.name.clone());
+        }
+    }
+    expired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(stamp: &str) -> BackupEntry {
+        let name = format!("{}{}{}", BACKUP_PREFIX, stamp, BACKUP_SUFFIX);
+        BackupEntry { created_at: backup_time(&name).unwrap(), name, size: None }
+    }
+
+    #[test]
+    fn test_select_expired_keeps_daily_and_weekly() {
+        // Two backups a day for 40 days, newest first
+        let mut backups = Vec::new();
+        let start = NaiveDateTime::parse_from_str("20261015T120000Z", BACKUP_TIME_FORMAT).unwrap();
+        for day in 0..40 {
+            for hour in [12, 0] {
+                let t = start - chrono::Duration::days(day) - chrono::Duration::hours(12 - hour);
+                backups.push(entry(&t.format(BACKUP_TIME_FORMAT).to_string()));
+            }
+        }
+
+        let expired = select_expired(&backups, 7, 4);
+        let kept: Vec<&BackupEntry> = backups.iter().filter(|b| !expired.contains(&b.name)).collect();
+
+        // 7 daily backups, plus the newest backup of the 4 weeks not already covered
+        assert_eq!(kept[0].name, backups[0].name);
+        assert!(kept.len() >= 7 && kept.len() <= 11);
+        let days: HashSet<_> = kept
+            .iter()
+            .map(|b| chrono::DateTime::from_timestamp(b.created_at, 0).unwrap().date_naive())
+            .collect();
+        assert_eq!(days.len(), kept.len());
+        assert!(!expired.contains(&backups[0].name));
+        assert!(expired.contains(&backups[1].name)); // second backup of the newest day
+    }
+
+    #[test]
+    fn test_backup_time_ignores_foreign_files() {
+        assert!(backup_time("mina-backup-20261015T031500Z.db.gz").is_some());
+        assert!(backup_time("notes.txt").is_none());
+        assert!(backup_time("mina-backup-latest.db.gz").is_none());
+    }
+}
@@ -0,0 +1,318 @@
+use crate::storage::benchmarks::{BenchmarkRegression, BenchmarkSeries, BenchmarkStore, NewBenchmarkResult};
+use crate::storage::Database;
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+
+pub const CONFIG_THRESHOLD_PERCENT: &str = "benchmark_regression_threshold_percent";
+pub const CONFIG_BASELINE_WINDOW: &str = "benchmark_baseline_window";
+pub const CONFIG_WATCH_DIR: &str = "benchmark_watch_dir";
+const CONFIG_WATCH_LAST_MTIME: &str = "benchmark_watch_last_mtime";
+
+const DEFAULT_THRESHOLD_PERCENT: f64 = 10.0;
+const DEFAULT_BASELINE_WINDOW: i64 = 10;
+/// Fewer previous samples than this and the baseline is too noisy to alert on.
+const MIN_BASELINE_SAMPLES: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkTrend {
+    pub series: BenchmarkSeries,
+    pub latest: f64,
+    pub baseline: Option<f64>,
+    pub change_percent: Option<f64>, // Positive means worse
+    pub slope_per_run: f64,
+    pub direction: String, // "improving", "regressing", "stable"
+    pub points: Vec<(i64, f64)>, // (recorded_at, value), oldest first
+}
+
+pub struct BenchmarkTracker;
+
+impl BenchmarkTracker {
+    /// Store results and compare each one against the rolling median of its
+    /// previous samples. Returns the regressions that crossed the threshold.
+    pub fn ingest(db: &Arc<Mutex<Database>>, results: &[NewBenchmarkResult]) -> Result<Vec<BenchmarkRegression>> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let threshold = db_guard.get_config(CONFIG_THRESHOLD_PERCENT)?
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(DEFAULT_THRESHOLD_PERCENT);
+        let window = db_guard.get_config(CONFIG_BASELINE_WINDOW)?
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(DEFAULT_BASELINE_WINDOW)
+            .max(1);
+        let store = BenchmarkStore::new(db_guard.conn.clone());
+
+        let mut regressions = Vec::new();
+        for result in results {
+            let id = store.record_result(result)?;
+            let previous = store.previous_values(&result.suite, &result.name, &result.metric, id, window)?;
+            if previous.len() < MIN_BASELINE_SAMPLES {
+                continue;
+            }
+
+            let baseline = median(&previous);
+            if let Some(change) = change_percent(baseline, result.value, result.lower_is_better) {
+                if change > threshold {
+                    regressions.push(store.create_regression(id, result, baseline, change, threshold)?);
+                }
+            }
+        }
+
+        Ok(regressions)
+    }
+
+    pub async fn notify_regressions(app: &AppHandle, regressions: &[BenchmarkRegression]) {
+        for regression in regressions {
+            let _ = crate::services::window_router::emit(app, serde_json::json!({
+                "type": "benchmark-regression",
+                "data": regression,
+                "timestamp": chrono::Utc::now().timestamp_millis(),
+            }));
+
+            use crate::services::desktop_notifications::{DesktopNotificationService, NotificationOptions};
+            let _ = DesktopNotificationService::send(app, NotificationOptions {
+                title: format!("Benchmark regression: {}", regression.name),
+                body: format!(
+                    "{} {} is {:.1}% worse than its baseline ({:.2} vs {:.2})",
+                    regression.suite,
+                    regression.metric,
+                    regression.change_percent,
+                    regression.value,
+                    regression.baseline
+                ),
+                icon: Some("alert".to_string()),
+                sound: None,
+                tag: Some(format!("benchmark-{}-{}", regression.suite, regression.name)),
+                data: serde_json::to_value(regression).ok(),
+            }).await;
+        }
+    }
+
+    pub fn get_trends(db: &Arc<Mutex<Database>>, suite: Option<&str>, points: i64) -> Result<Vec<BenchmarkTrend>> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let window = db_guard.get_config(CONFIG_BASELINE_WINDOW)?
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(DEFAULT_BASELINE_WINDOW)
+            .max(1);
+        let threshold = db_guard.get_config(CONFIG_THRESHOLD_PERCENT)?
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(DEFAULT_THRESHOLD_PERCENT);
+        let store = BenchmarkStore::new(db_guard.conn.clone());
+
+        let mut trends = Vec::new();
+        for series in store.list_series(suite)? {
+            let mut results = store.list_results(
+                Some(series.suite.as_str()),
+                Some(series.name.as_str()),
+                Some(series.metric.as_str()),
+                points.max(window + 1),
+            )?;
+            results.reverse();
+            let values: Vec<f64> = results.iter().map(|r| r.value).collect();
+            let latest = match values.last() {
+                Some(v) => *v,
+                None => continue,
+            };
+
+            let previous: Vec<f64> = values[..values.len() - 1]
+                .iter()
+                .rev()
+                .take(window as usize)
+                .copied()
+                .collect();
+            let baseline = if previous.len() >= MIN_BASELINE_SAMPLES { Some(median(&previous)) } else { None };
+            let change = baseline.and_then(|b| change_percent(b, latest, series.lower_is_better));
+
+            // The threshold keeps run-to-run noise from flipping the label
+            let direction = match change {
+                Some(c) if c > threshold => "regressing",
+                Some(c) if c < -threshold => "improving",
+                _ => "stable",
+            };
+
+            let slope = slope(&values);
+            let skip = results.len().saturating_sub(points.max(1) as usize);
+            trends.push(BenchmarkTrend {
+                latest,
+                baseline,
+                change_percent: change,
+                slope_per_run: slope,
+                direction: direction.to_string(),
+                points: results.iter().skip(skip).map(|r| (r.recorded_at, r.value)).collect(),
+                series,
+            });
+        }
+
+        Ok(trends)
+    }
+
+    /// Poll the configured directory for new benchmark output files.
+    pub fn start_watching(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+
+            loop {
+                interval.tick().await;
+
+                match Self::scan_watch_dir(&db) {
+                    Ok(regressions) if !regressions.is_empty() => {
+                        Self::notify_regressions(&app, &regressions).await;
+                    }
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Error ingesting benchmark files: {}", e),
+                }
+            }
+        });
+    }
+
+    fn scan_watch_dir(db: &Arc<Mutex<Database>>) -> Result<Vec<BenchmarkRegression>> {
+        let (dir, last_mtime) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let dir = match db_guard.get_config(CONFIG_WATCH_DIR)? {
+                Some(d) if !d.trim().is_empty() => d,
+                _ => return Ok(Vec::new()),
+            };
+            let last_mtime = db_guard.get_config(CONFIG_WATCH_LAST_MTIME)?
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or(0);
+            (dir, last_mtime)
+        };
+
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&dir)?.flatten() {
+            let path = entry.path();
+            let is_report = matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("json") | Some("txt") | Some("log")
+            );
+            if !is_report {
+                continue;
+            }
+            let mtime = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0);
+            if mtime > last_mtime {
+                files.push((mtime, path));
+            }
+        }
+        files.sort();
+
+        let mut regressions = Vec::new();
+        let mut newest = last_mtime;
+        for (mtime, path) in files {
+            let suite = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("default")
+                .to_string();
+            match std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| parse_bench_output(&suite, &content, None))
+            {
+                Ok(results) => regressions.extend(Self::ingest(db, &results)?),
+                Err(e) => eprintln!("Skipping benchmark file {}: {}", path.display(), e),
+            }
+            newest = newest.max(mtime);
+        }
+
+        if newest > last_mtime {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            db_guard.set_config(CONFIG_WATCH_LAST_MTIME, &newest.to_string())?;
+        }
+
+        Ok(regressions)
+    }
+}
+
+/// Parse either a JSON array of results or libtest `cargo bench` output
+/// (`test parse ... bench:   1,234 ns/iter (+/- 56)`).
+pub fn parse_bench_output(suite: &str, content: &str, context: Option<&str>) -> Result<Vec<NewBenchmarkResult>, String> {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('[') {
+        let mut results: Vec<serde_json::Value> = serde_json::from_str(trimmed)
+            .map_err(|e| format!("Invalid benchmark JSON: {}", e))?;
+        // Suite and context come from the caller unless the entry sets them
+        for entry in results.iter_mut() {
+            if let Some(obj) = entry.as_object_mut() {
+                obj.entry("suite").or_insert_with(|| serde_json::json!(suite));
+                if let Some(ctx) = context {
+                    obj.entry("context").or_insert_with(|| serde_json::json!(ctx));
+                }
+            }
+        }
+        return results
+            .into_iter()
+            .map(|v| serde_json::from_value(v).map_err(|e| format!("Invalid benchmark entry: {}", e)))
+            .collect();
+    }
+
+    let line_re = Regex::new(r"(?m)^test (\S+)\s+\.\.\. bench:\s+([\d,.]+) (\S+)")
+        .map_err(|e| e.to_string())?;
+    let results: Vec<NewBenchmarkResult> = line_re
+        .captures_iter(content)
+        .filter_map(|caps| {
+            let value = caps[2].replace(',', "").parse::<f64>().ok()?;
+            Some(NewBenchmarkResult {
+                suite: suite.to_string(),
+                name: caps[1].to_string(),
+                metric: "time".to_string(),
+                value,
+                unit: caps[3].to_string(),
+                lower_is_better: true,
+                context: context.map(|c| c.to_string()),
+            })
+        })
+        .collect();
+
+    if results.is_empty() {
+        return Err("No benchmark results found".to_string());
+    }
+    Ok(results)
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Percentage by which `value` is worse than `baseline`; negative when better.
+fn change_percent(baseline: f64, value: f64, lower_is_better: bool) -> Option<f64> {
+    if baseline == 0.0 {
+        return None;
+    }
+    let delta = if lower_is_better { value - baseline } else { baseline - value };
+    Some(delta / baseline.abs() * 100.0)
+}
+
+/// Least-squares slope of the values against their run index.
+fn slope(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let mut num = 0.0;
+    let mut den = 0.0;
+    for (i, y) in values.iter().enumerate() {
+        let dx = i as f64 - mean_x;
+        num += dx * (y - mean_y);
+        den += dx * dx;
+    }
+    if den == 0.0 { 0.0 } else { num / den }
+}
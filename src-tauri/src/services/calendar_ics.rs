@@ -0,0 +1,331 @@
+use crate::providers::earnings_calendar::{EarningsCalendarProvider, EarningsDate};
+use crate::services::api_key_manager::APIKeyManager;
+use crate::storage::economic_calendar::EconomicCalendarStore;
+use crate::storage::market_data::MarketDataStore;
+use crate::storage::temporal::TemporalStore;
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+/// Token the served feed requires. Unset means the feed is off.
+pub const CONFIG_FEED_TOKEN: &str = "calendar_ics_feed_token";
+
+pub const KIND_ECONOMIC: &str = "economic";
+pub const KIND_EARNINGS: &str = "earnings";
+pub const KIND_SNOOZED_ALERTS: &str = "snoozed_alerts";
+pub const KINDS: [&str; 3] = [KIND_ECONOMIC, KIND_EARNINGS, KIND_SNOOZED_ALERTS];
+
+pub const DEFAULT_RANGE_DAYS: i64 = 30;
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Defaults to the next `DEFAULT_RANGE_DAYS` days.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalendarRange {
+    #[serde(default)]
+    pub from_ts: Option<i64>,
+    #[serde(default)]
+    pub to_ts: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryStart {
+    At(i64),
+    AllDay(chrono::NaiveDate),
+}
+
+#[derive(Debug, Clone)]
+pub struct CalendarEntry {
+    pub uid: String,
+    pub start: EntryStart,
+    pub summary: String,
+    pub description: Option<String>,
+}
+
+pub struct CalendarIcs;
+
+impl CalendarIcs {
+    /// Validated kinds; none given means all.
+    pub fn parse_kinds(kinds: Option<&[String]>) -> Result<Vec<&'static str>> {
+        let Some(kinds) = kinds.filter(|k| !k.is_empty()) else {
+            return Ok(KINDS.to_vec());
+        };
+        kinds
+            .iter()
+            .map(|k| {
+                KINDS
+                    .iter()
+                    .find(|known| known.eq_ignore_ascii_case(k.trim()))
+                    .copied()
+                    .ok_or_else(|| anyhow::anyhow!("Unknown calendar kind: {} (expected one of {})", k, KINDS.join(", ")))
+            })
+            .collect()
+    }
+
+    /// Build the calendar. Earnings need a Finnhub key; without one they are left out.
+    pub async fn build(
+        db: &Mutex<Database>,
+        api_key_manager: Option<&APIKeyManager>,
+        range: &CalendarRange,
+        kinds: &[&str],
+    ) -> Result<String> {
+        let now = chrono::Utc::now().timestamp();
+        let from_ts = range.from_ts.unwrap_or(now);
+        let to_ts = range.to_ts.unwrap_or(from_ts + DEFAULT_RANGE_DAYS * 86400);
+        if to_ts < from_ts {
+            anyhow::bail!("Calendar range ends before it starts");
+        }
+
+        let (mut entries, tickers) = {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let entries = Self::local_entries(&db_guard, from_ts, to_ts, kinds)?;
+            let tickers = if kinds.contains(&KIND_EARNINGS) {
+                Self::watchlist_tickers(&db_guard)?
+            } else {
+                Vec::new()
+            };
+            (entries, tickers)
+        };
+
+        if !tickers.is_empty() {
+            let api_key = api_key_manager.and_then(|mgr| mgr.get_key_optional("finnhub").ok().flatten());
+            let provider = EarningsCalendarProvider::new(api_key);
+            if provider.is_configured() {
+                let from = chrono::DateTime::from_timestamp(from_ts, 0).unwrap_or_default();
+                let to = chrono::DateTime::from_timestamp(to_ts, 0).unwrap_or_default();
+                entries.extend(earnings_entries(&provider.fetch_earnings(&tickers, from, to).await?));
+            }
+        }
+
+        Ok(to_ics(&entries, now))
+    }
+
+    /// Economic events and snoozed-alert wake times in the range.
+    fn local_entries(db: &Database, from_ts: i64, to_ts: i64, kinds: &[&str]) -> Result<Vec<CalendarEntry>> {
+        let mut entries = Vec::new();
+
+        if kinds.contains(&KIND_ECONOMIC) {
+            let store = EconomicCalendarStore::new(db.conn.clone());
+            for event in store.list_events(from_ts, to_ts, None, None)? {
+                let mut details = vec![format!("{} · {}", event.country, event.event_type)];
+                if let Some(forecast) = event.forecast_value {
+                    details.push(format!("Forecast: {}", forecast));
+                }
+                if let Some(previous) = event.previous_value {
+                    details.push(format!("Previous: {}", previous));
+                }
+                details.push(format!("Impact: {:.1}", event.impact_score));
+                entries.push(CalendarEntry {
+                    uid: format!("economic-{}@mina", event.id),
+                    start: EntryStart::At(event.scheduled_at),
+                    summary: format!("{} ({})", event.name, event.country),
+                    description: Some(details.join("\n")),
+                });
+            }
+        }
+
+        if kinds.contains(&KIND_SNOOZED_ALERTS) {
+            let store = TemporalStore::new(db.conn.clone());
+            for alert in store.list_alerts(2000, None, None)? {
+                let Some(until) = alert.snoozed_until else { continue };
+                if alert.status != "snoozed" || until < from_ts || until > to_ts {
+                    continue;
+                }
+                let title = alert
+                    .payload_json
+                    .get("event")
+                    .and_then(|e| e.get("title"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("Alert");
+                let rule = alert
+                    .payload_json
+                    .get("rule")
+                    .and_then(|r| r.get("name"))
+                    .and_then(|v| v.as_str());
+                entries.push(CalendarEntry {
+                    uid: format!("alert-{}-{}@mina", alert.id, until),
+                    start: EntryStart::At(until),
+                    summary: format!("Snoozed alert: {}", title),
+                    description: rule.map(|r| format!("Rule: {}", r)),
+                });
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Tickers behind the entities on watchlists: names and symbols known
+    /// from stored fundamentals, plus values written like a ticker ("AAPL").
+    pub fn watchlist_tickers(db: &Database) -> Result<Vec<String>> {
+        let temporal = TemporalStore::new(db.conn.clone());
+        let fundamentals = MarketDataStore::new(db.conn.clone()).list_fundamentals(None)?;
+
+        let mut tickers = BTreeSet::new();
+        for watchlist in temporal.list_watchlists()? {
+            for item in temporal.list_watchlist_items(watchlist.id)? {
+                if !item.enabled || item.item_type != "entity" {
+                    continue;
+                }
+                let value = item.value.trim();
+                let known = fundamentals.iter().find(|f| {
+                    f.ticker.eq_ignore_ascii_case(value)
+                        || f.name.as_deref().map(|n| n.eq_ignore_ascii_case(value)).unwrap_or(false)
+                });
+                match known {
+                    Some(f) => {
+                        tickers.insert(f.ticker.to_uppercase());
+                    }
+                    None if looks_like_ticker(value) => {
+                        tickers.insert(value.to_string());
+                    }
+                    None => {}
+                }
+            }
+        }
+        Ok(tickers.into_iter().collect())
+    }
+
+    pub fn feed_token(db: &Database) -> Option<String> {
+        db.get_config(CONFIG_FEED_TOKEN).ok().flatten().filter(|t| !t.is_empty())
+    }
+
+    /// Feed URL on the local script bridge server.
+    pub fn feed_url(port: u16, token: &str) -> String {
+        format!("http://127.0.0.1:{}/calendar.ics?token={}", port, token)
+    }
+}
+
+fn looks_like_ticker(value: &str) -> bool {
+    let (symbol, suffix) = value.split_once('.').unwrap_or((value, ""));
+    (1..=5).contains(&symbol.len())
+        && symbol.chars().all(|c| c.is_ascii_uppercase())
+        && suffix.len() <= 2
+        && suffix.chars().all(|c| c.is_ascii_uppercase())
+}
+
+fn earnings_entries(earnings: &[EarningsDate]) -> Vec<CalendarEntry> {
+    earnings
+        .iter()
+        .map(|e| {
+            let timing = match e.hour.as_deref() {
+                Some("bmo") => " (before open)",
+                Some("amc") => " (after close)",
+                _ => "",
+            };
+            let mut details = Vec::new();
+            if let (Some(q), Some(y)) = (e.quarter, e.year) {
+                details.push(format!("Q{} {}", q, y));
+            }
+            if let Some(eps) = e.eps_estimate {
+                details.push(format!("EPS estimate: {:.2}", eps));
+            }
+            if let Some(revenue) = e.revenue_estimate {
+                details.push(format!("Revenue estimate: {:.0}", revenue));
+            }
+            CalendarEntry {
+                uid: format!("earnings-{}-{}@mina", e.symbol, e.date.format("%Y%m%d")),
+                start: EntryStart::AllDay(e.date),
+                summary: format!("{} earnings{}", e.symbol, timing),
+                description: (!details.is_empty()).then(|| details.join("\n")),
+            }
+        })
+        .collect()
+}
+
+/// RFC 5545 calendar. Timed entries are 15-minute UTC events.
+pub fn to_ics(entries: &[CalendarEntry], now: i64) -> String {
+    let stamp = format_utc(now);
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//MINA//Calendar Export//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:MINA".to_string(),
+    ];
+    for entry in entries {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", entry.uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        match &entry.start {
+            EntryStart::At(ts) => {
+                lines.push(format!("DTSTART:{}", format_utc(*ts)));
+                lines.push(format!("DTEND:{}", format_utc(ts + 15 * 60)));
+            }
+            EntryStart::AllDay(date) => {
+                lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+                if let Some(next) = date.succ_opt() {
+                    lines.push(format!("DTEND;VALUE=DATE:{}", next.format("%Y%m%d")));
+                }
+            }
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&entry.summary)));
+        if let Some(description) = &entry.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|l| fold_line(l)).collect::<Vec<_>>().join("\r\n") + "\r\n"
+}
+
+fn format_utc(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .unwrap_or_default()
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Split lines longer than 75 octets, continuing with a leading space,
+/// without breaking a UTF-8 character.
+fn fold_line(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_and_folds_long_lines() {
+        let entry = CalendarEntry {
+            uid: "economic-1@mina".to_string(),
+            start: EntryStart::At(0),
+            summary: format!("CPI, core; {}", "x".repeat(80)),
+            description: None,
+        };
+        let ics = to_ics(&[entry], 0);
+        assert!(ics.contains("DTSTART:19700101T000000Z\r\n"));
+        assert!(ics.contains("SUMMARY:CPI\\, core\\; "));
+        assert!(ics.lines().all(|l| l.trim_end_matches('\r').len() <= MAX_LINE_OCTETS));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn recognizes_ticker_symbols() {
+        assert!(looks_like_ticker("AAPL"));
+        assert!(looks_like_ticker("BRK.B"));
+        assert!(!looks_like_ticker("Nvidia"));
+        assert!(!looks_like_ticker("NVIDIAX"));
+    }
+}
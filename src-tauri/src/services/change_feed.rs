@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Mutex, OnceLock};
+use tauri::AppHandle;
+
+/// How often pending changes are flushed to the UI.
+const FLUSH_INTERVAL_MS: u64 = 250;
+/// More changed ids than this per entity and op collapse into "refetch all".
+const MAX_IDS: usize = 500;
+
+pub const ARTICLES: &str = "articles";
+pub const EVENTS: &str = "events";
+pub const ALERTS: &str = "alerts";
+pub const MARKET_PRICES: &str = "market-prices";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Default)]
+struct Pending {
+    ids: BTreeSet<i64>,
+    keys: BTreeSet<String>,
+    /// Too many changes to list; the UI should refetch
+    all: bool,
+}
+
+impl Pending {
+    fn overflowed(&mut self) {
+        if self.ids.len() + self.keys.len() > MAX_IDS {
+            self.ids.clear();
+            self.keys.clear();
+            self.all = true;
+        }
+    }
+}
+
+/// One `invalidate-<entity>` message: which rows of an entity changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Invalidation {
+    pub entity: String,
+    pub op: ChangeOp,
+    pub ids: Vec<i64>,
+    /// Changed rows keyed by something other than an id, e.g. tickers
+    pub keys: Vec<String>,
+    pub all: bool,
+}
+
+/// Set once the flusher runs; changes recorded before that (or in tests)
+/// are dropped, since nothing would deliver them.
+static PENDING: OnceLock<Mutex<BTreeMap<(String, ChangeOp), Pending>>> = OnceLock::new();
+
+/// Coalesces store mutations into fine-grained `ws-message` invalidation
+/// events, so lists update the rows that changed instead of polling.
+pub struct ChangeFeed;
+
+impl ChangeFeed {
+    /// Start delivering changes to the windows subscribed to
+    /// `invalidate-<entity>`.
+    pub fn start(app: AppHandle) {
+        if PENDING.set(Mutex::new(BTreeMap::new())).is_err() {
+            return;
+        }
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(FLUSH_INTERVAL_MS));
+            loop {
+                interval.tick().await;
+                for invalidation in Self::drain() {
+                    let _ = crate::services::window_router::emit(&app, serde_json::json!({
+                        "type": format!("invalidate-{}", invalidation.entity),
+                        "data": invalidation,
+                        "timestamp": chrono::Utc::now().timestamp_millis(),
+                    }));
+                }
+            }
+        });
+    }
+
+    /// Record changed rows by id.
+    pub fn ids(entity: &str, op: ChangeOp, ids: impl IntoIterator<Item = i64>) {
+        Self::record(entity, op, |pending| pending.ids.extend(ids));
+    }
+
+    /// Record changed rows by key, for entities without an id.
+    pub fn keys<S: Into<String>>(entity: &str, op: ChangeOp, keys: impl IntoIterator<Item = S>) {
+        Self::record(entity, op, |pending| pending.keys.extend(keys.into_iter().map(Into::into)));
+    }
+
+    /// Record that an entity changed too broadly to list, e.g. a rebuild.
+    pub fn all(entity: &str, op: ChangeOp) {
+        Self::record(entity, op, |pending| pending.all = true);
+    }
+
+    fn record(entity: &str, op: ChangeOp, apply: impl FnOnce(&mut Pending)) {
+        let Some(pending) = PENDING.get() else { return };
+        let Ok(mut pending) = pending.lock() else { return };
+        let entry = pending.entry((entity.to_string(), op)).or_default();
+        if !entry.all {
+            apply(entry);
+            entry.overflowed();
+        }
+    }
+
+    fn drain() -> Vec<Invalidation> {
+        let Some(pending) = PENDING.get() else { return Vec::new() };
+        let Ok(mut pending) = pending.lock() else { return Vec::new() };
+        std::mem::take(&mut *pending)
+            .into_iter()
+            .map(|((entity, op), p)| to_invalidation(entity, op, p))
+            .collect()
+    }
+}
+
+fn to_invalidation(entity: String, op: ChangeOp, pending: Pending) -> Invalidation {
+    Invalidation {
+        entity,
+        op,
+        ids: pending.ids.into_iter().collect(),
+        keys: pending.keys.into_iter().collect(),
+        all: pending.all,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_changes_dedupe_and_collapse_when_too_many() {
+        let mut pending = Pending::default();
+        pending.ids.extend([3, 1, 3]);
+        pending.overflowed();
+        let invalidation = to_invalidation(ARTICLES.to_string(), ChangeOp::Insert, pending);
+        assert_eq!(invalidation.ids, vec![1, 3]);
+        assert!(!invalidation.all);
+
+        let mut pending = Pending::default();
+        pending.keys.extend((0..=MAX_IDS).map(|i| format!("T{}", i)));
+        pending.overflowed();
+        let invalidation = to_invalidation(MARKET_PRICES.to_string(), ChangeOp::Update, pending);
+        assert!(invalidation.all);
+        assert!(invalidation.keys.is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::services::alert_context::related_tickers;
+use crate::storage::market_data::MarketDataStore;
+use crate::storage::temporal::{Alert, TemporalEvent, TemporalStore};
+use anyhow::Result;
+
+/// Event and alert markers on price charts.
+pub struct ChartAnnotationService;
+
+impl ChartAnnotationService {
+    /// Pin newly fired alerts to the charts of the tickers their event is
+    /// about, at the time the event started. Returns the markers written.
+    pub fn record_alerts(temporal_store: &TemporalStore, market_data_store: &MarketDataStore, alerts: &[Alert]) -> Result<usize> {
+        let mut written = 0;
+        for alert in alerts {
+            let event = match alert.event_id {
+                Some(event_id) => temporal_store.get_event(event_id)?,
+                None => None,
+            };
+            let event = match event {
+                Some(event) => event,
+                None => continue,
+            };
+            let commentary = temporal_store.get_event_commentary(event.id)?;
+            let entities = temporal_store
+                .event_match_context(event.id)?
+                .map(|c| c.entities)
+                .unwrap_or_default();
+            let text = alert_text(alert, &event);
+            for ticker in related_tickers(market_data_store, commentary.as_ref(), &entities)? {
+                market_data_store.upsert_chart_marker(&ticker, event.start_ts, &text, Some(event.id), Some(alert.id))?;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+
+    /// Pin an event to a ticker's chart at its start.
+    pub fn pin_event(temporal_store: &TemporalStore, market_data_store: &MarketDataStore, ticker: &str, event_id: i64) -> Result<i64> {
+        let event = temporal_store
+            .get_event(event_id)?
+            .ok_or_else(|| anyhow::anyhow!("Event {} not found", event_id))?;
+        market_data_store.upsert_chart_marker(ticker, event.start_ts, &event.title, Some(event.id), None)
+    }
+
+    /// Pin an alert to a ticker's chart, at its event's start when it has
+    /// one and otherwise when it fired.
+    pub fn pin_alert(temporal_store: &TemporalStore, market_data_store: &MarketDataStore, ticker: &str, alert_id: i64) -> Result<i64> {
+        let alert = temporal_store
+            .get_alert(alert_id)?
+            .ok_or_else(|| anyhow::anyhow!("Alert {} not found", alert_id))?;
+        let event = match alert.event_id {
+            Some(event_id) => temporal_store.get_event(event_id)?,
+            None => None,
+        };
+        let (timestamp, text) = match &event {
+            Some(event) => (event.start_ts, alert_text(&alert, event)),
+            None => (alert.fired_at, rule_name(&alert).unwrap_or_else(|| format!("Alert {}", alert.id))),
+        };
+        market_data_store.upsert_chart_marker(ticker, timestamp, &text, alert.event_id, Some(alert.id))
+    }
+}
+
+fn rule_name(alert: &Alert) -> Option<String> {
+    alert.payload_json.pointer("/rule/name").and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+/// "<rule name>: <event title>", or just the title when the payload has no rule.
+fn alert_text(alert: &Alert, event: &TemporalEvent) -> String {
+    match rule_name(alert) {
+        Some(name) => format!("{}: {}", name, event.title),
+        None => event.title.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::temporal::EventCommentary;
+    use crate::test_support::{alert, alert_rule, article, event, new_event, test_db, FIXTURE_TS};
+
+    #[test]
+    fn fired_alerts_become_markers_once_per_ticker() {
+        let db = test_db();
+        let article_id = article(&db, "Reuters", "Chipmaker recall", "A recall at the chipmaker");
+        let stored = event(&db, &new_event("Chipmaker recall"), &[article_id]);
+        let rule_id = alert_rule(&db, "Recalls", serde_json::json!({ "any": [{ "type": "contains_keyword", "keyword": "recall" }] }));
+        let alert_id = alert(&db, rule_id, Some(stored.id));
+
+        let temporal_store = TemporalStore::new(db.conn.clone());
+        temporal_store.save_event_commentary(&EventCommentary {
+            event_id: stored.id,
+            model: "test".to_string(),
+            what_happened: String::new(),
+            why_it_matters: String::new(),
+            affected_tickers: vec!["NVDA".to_string(), "TSM".to_string()],
+            created_at: FIXTURE_TS,
+        }).unwrap();
+        let market_data_store = MarketDataStore::new(db.conn.clone());
+        market_data_store.add_chart_annotation("nvda", FIXTURE_TS + 60, "Earnings call").unwrap();
+
+        let alerts = vec![temporal_store.get_alert(alert_id).unwrap().unwrap()];
+        assert_eq!(ChartAnnotationService::record_alerts(&temporal_store, &market_data_store, &alerts).unwrap(), 2);
+        ChartAnnotationService::record_alerts(&temporal_store, &market_data_store, &alerts).unwrap();
+
+        let annotations = market_data_store.list_chart_annotations("NVDA", FIXTURE_TS - 60, FIXTURE_TS + 3600).unwrap();
+        let kinds: Vec<&str> = annotations.iter().map(|a| a.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["alert", "user"]);
+        assert_eq!(annotations[0].alert_id, Some(alert_id));
+        assert_eq!(annotations[0].timestamp, stored.start_ts);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::providers::market_data::OHLCVData;
+
+/// Merge consecutive bars into at most `max_points` candles, keeping the
+/// first open, highest high, lowest low, last close and summed volume.
+pub fn bucket_ohlcv(data: &[OHLCVData], max_points: usize) -> Vec<OHLCVData> {
+    if max_points == 0 || data.len() <= max_points {
+        return data.to_vec();
+    }
+
+    let bucket_size = data.len().div_ceil(max_points);
+    data.chunks(bucket_size)
+        .map(|bucket| {
+            let first = &bucket[0];
+            let last = &bucket[bucket.len() - 1];
+            OHLCVData {
+                timestamp: first.timestamp,
+                open: first.open,
+                high: bucket.iter().map(|b| b.high).fold(f64::MIN, f64::max),
+                low: bucket.iter().map(|b| b.low).fold(f64::MAX, f64::min),
+                close: last.close,
+                volume: bucket.iter().map(|b| b.volume).sum(),
+            }
+        })
+        .collect()
+}
+
+/// Largest-Triangle-Three-Buckets on closing prices: keeps the bars that best
+/// preserve the visual shape of a line chart. First and last bars are kept.
+pub fn lttb(data: &[OHLCVData], max_points: usize) -> Vec<OHLCVData> {
+    if max_points < 3 || data.len() <= max_points {
+        return data.to_vec();
+    }
+
+    let mut sampled = Vec::with_capacity(max_points);
+    sampled.push(data[0].clone());
+
+    // Middle points are split into max_points - 2 buckets
+    let every = (data.len() - 2) as f64 / (max_points - 2) as f64;
+    let mut a = 0usize;
+
+    for i in 0..(max_points - 2) {
+        let bucket_start = (i as f64 * every) as usize + 1;
+        let bucket_end = (((i + 1) as f64 * every) as usize + 1).min(data.len() - 1);
+
+        // Average of the next bucket is the third triangle vertex
+        let next_start = bucket_end;
+        let next_end = (((i + 2) as f64 * every) as usize + 1).min(data.len());
+        let next = &data[next_start..next_end.max(next_start + 1)];
+        let avg_x = next.iter().map(|p| p.timestamp as f64).sum::<f64>() / next.len() as f64;
+        let avg_y = next.iter().map(|p| p.close).sum::<f64>() / next.len() as f64;
+
+        let (ax, ay) = (data[a].timestamp as f64, data[a].close);
+        let mut best = bucket_start;
+        let mut best_area = -1.0;
+        for (j, point) in data.iter().enumerate().take(bucket_end).skip(bucket_start) {
+            let area = ((ax - avg_x) * (point.close - ay) - (ax - point.timestamp as f64) * (avg_y - ay)).abs();
+            if area > best_area {
+                best_area = area;
+                best = j;
+            }
+        }
+
+        sampled.push(data[best].clone());
+        a = best;
+    }
+
+    sampled.push(data[data.len() - 1].clone());
+    sampled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(n: usize) -> Vec<OHLCVData> {
+        (0..n)
+            .map(|i| {
+                let close = (i as f64 / 10.0).sin() * 10.0 + 100.0;
+                OHLCVData { timestamp: i as i64 * 60, open: close, high: close + 1.0, low: close - 1.0, close, volume: 10 }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bucket_preserves_range_and_volume() {
+        let data = series(1000);
+        let sampled = bucket_ohlcv(&data, 100);
+        assert!(sampled.len() <= 100);
+        assert_eq!(sampled.iter().map(|b| b.volume).sum::<i64>(), 10_000);
+        let max_high = data.iter().map(|b| b.high).fold(f64::MIN, f64::max);
+        assert_eq!(sampled.iter().map(|b| b.high).fold(f64::MIN, f64::max), max_high);
+    }
+
+    #[test]
+    fn lttb_keeps_endpoints_and_size() {
+        let data = series(5000);
+        let sampled = lttb(&data, 500);
+        assert_eq!(sampled.len(), 500);
+        assert_eq!(sampled[0].timestamp, data[0].timestamp);
+        assert_eq!(sampled[499].timestamp, data[4999].timestamp);
+        assert!(sampled.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+    }
+}
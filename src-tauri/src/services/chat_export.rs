@@ -0,0 +1,92 @@
+use crate::storage::ai::{AIStore, ChatMessage, Conversation};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ChatExportFormat {
+    Markdown,
+    Json,
+}
+
+impl ChatExportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format.to_lowercase().as_str() {
+            "markdown" | "md" => Some(ChatExportFormat::Markdown),
+            "json" => Some(ChatExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+pub struct ChatExportService;
+
+impl ChatExportService {
+    /// Export conversations by id, looking in the archive for any that were archived.
+    /// Unknown ids are skipped.
+    pub fn export(store: &AIStore, ids: &[String], format: ChatExportFormat) -> Result<String> {
+        let mut conversations = Vec::new();
+        for id in ids {
+            if let Some(conversation) = store.get_conversation(id)? {
+                let messages = store.get_messages(id)?;
+                conversations.push((conversation, messages));
+            } else if let Some(archived) = store.get_archived_conversation(id)? {
+                conversations.push(archived);
+            }
+        }
+
+        match format {
+            ChatExportFormat::Json => {
+                let data: Vec<serde_json::Value> = conversations
+                    .iter()
+                    .map(|(conversation, messages)| {
+                        serde_json::json!({
+                            "conversation": conversation,
+                            "messages": messages,
+                        })
+                    })
+                    .collect();
+                // A single conversation exports as an object, bulk as an array
+                if data.len() == 1 {
+                    Ok(serde_json::to_string_pretty(&data[0])?)
+                } else {
+                    Ok(serde_json::to_string_pretty(&data)?)
+                }
+            }
+            ChatExportFormat::Markdown => Ok(conversations
+                .iter()
+                .map(|(conversation, messages)| to_markdown(conversation, messages))
+                .collect::<Vec<_>>()
+                .join("\n---\n\n")),
+        }
+    }
+}
+
+fn to_markdown(conversation: &Conversation, messages: &[ChatMessage]) -> String {
+    let format_ts = |ts: i64| {
+        chrono::DateTime::from_timestamp(ts, 0)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_default()
+    };
+
+    let mut md = format!("# {}\n\n", conversation.title);
+    md.push_str(&format!("- Created: {}\n", format_ts(conversation.created_at)));
+    md.push_str(&format!("- Updated: {}\n", format_ts(conversation.updated_at)));
+    if let Some(model) = &conversation.model {
+        md.push_str(&format!("- Model: {}\n", model));
+    }
+    md.push('\n');
+
+    for message in messages {
+        let role = match message.role.as_str() {
+            "user" => "User",
+            "assistant" => "Assistant",
+            "system" => "System",
+            other => other,
+        };
+        md.push_str(&format!("## {} · {}\n\n", role, format_ts(message.timestamp)));
+        md.push_str(message.content.trim());
+        md.push_str("\n\n");
+    }
+
+    md
+}
@@ -0,0 +1,274 @@
+use crate::storage::{Database, OSINTStore, TemporalStore};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CitationFormat {
+    BibTex,
+    ZoteroRdf,
+}
+
+impl CitationFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format.to_lowercase().as_str() {
+            "bibtex" | "bib" => Some(CitationFormat::BibTex),
+            "zotero" | "rdf" | "zotero_rdf" => Some(CitationFormat::ZoteroRdf),
+            _ => None,
+        }
+    }
+}
+
+/// One cited article.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Citation {
+    pub key: String,
+    pub article_id: i64,
+    pub title: String,
+    pub source: String,
+    pub url: String,
+    pub published_at: i64,
+    /// When the article was fetched, i.e. the access date
+    pub accessed_at: i64,
+    /// Local copy of the stored article text, when archiving was requested
+    pub archive_path: Option<String>,
+}
+
+pub struct CitationExport;
+
+impl CitationExport {
+    /// Citations for the given articles plus the evidence articles of the
+    /// given events, in that order and without duplicates. With
+    /// `archive_dir`, each article's stored text is written there as HTML
+    /// and referenced from its citation.
+    pub fn collect(
+        db: &Database,
+        article_ids: &[i64],
+        event_ids: &[i64],
+        archive_dir: Option<&Path>,
+    ) -> Result<Vec<Citation>> {
+        let osint = OSINTStore::new(db.conn.clone());
+        let temporal = TemporalStore::new(db.conn.clone());
+
+        let mut ids: Vec<i64> = article_ids.to_vec();
+        for event_id in event_ids {
+            ids.extend(temporal.list_event_evidence(*event_id)?.into_iter().map(|e| e.rss_item_id));
+        }
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+
+        let feeds: HashMap<i64, String> = osint.list_feeds()?.into_iter().map(|f| (f.id, f.name)).collect();
+        if let Some(dir) = archive_dir {
+            std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+
+        let mut keys = HashSet::new();
+        let mut citations = Vec::new();
+        for id in ids {
+            let Some(item) = osint.get_item(id)? else { continue };
+            let base = citation_key(&item.title, item.published_at);
+            let mut key = base.clone();
+            let mut suffix = b'a';
+            while !keys.insert(key.clone()) {
+                key = format!("{}{}", base, suffix as char);
+                suffix = suffix.saturating_add(1);
+            }
+
+            let archive_path = match archive_dir {
+                Some(dir) => {
+                    let path = dir.join(format!("{}.html", key));
+                    std::fs::write(&path, archive_html(&item.title, &item.url, &item.content))
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    Some(path.display().to_string())
+                }
+                None => None,
+            };
+
+            citations.push(Citation {
+                key,
+                article_id: item.id,
+                title: item.title.trim().to_string(),
+                source: feeds.get(&item.feed_id).cloned().unwrap_or_default(),
+                url: item.url,
+                published_at: item.published_at,
+                accessed_at: item.fetched_at,
+                archive_path,
+            });
+        }
+        Ok(citations)
+    }
+
+    pub fn render(citations: &[Citation], format: CitationFormat) -> String {
+        match format {
+            CitationFormat::BibTex => to_bibtex(citations),
+            CitationFormat::ZoteroRdf => to_zotero_rdf(citations),
+        }
+    }
+}
+
+/// `firstword` + year, e.g. `reuters2024`, lowercase ASCII.
+fn citation_key(title: &str, published_at: i64) -> String {
+    let word: String = title
+        .split_whitespace()
+        .map(|w| w.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>())
+        .find(|w| w.len() > 3)
+        .unwrap_or_else(|| "article".to_string())
+        .to_lowercase();
+    format!("{}{}", word, format_ts(published_at, "%Y"))
+}
+
+fn format_ts(ts: i64, fmt: &str) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format(fmt).to_string())
+        .unwrap_or_default()
+}
+
+/// Escape BibTeX special characters in a field value.
+fn bibtex_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            c if c.is_control() => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// biblatex `@online` entries; `file` is what JabRef and Zotero read for attachments.
+fn to_bibtex(citations: &[Citation]) -> String {
+    let mut out = String::new();
+    for c in citations {
+        out.push_str(&format!("@online{{{},\n", c.key));
+        // Double braces keep the title's capitalization
+        out.push_str(&format!("  title = {{{{{}}}}},\n", bibtex_escape(&c.title)));
+        if !c.source.is_empty() {
+            out.push_str(&format!("  organization = {{{}}},\n", bibtex_escape(&c.source)));
+        }
+        out.push_str(&format!("  date = {{{}}},\n", format_ts(c.published_at, "%Y-%m-%d")));
+        out.push_str(&format!("  url = {{{}}},\n", c.url));
+        out.push_str(&format!("  urldate = {{{}}},\n", format_ts(c.accessed_at, "%Y-%m-%d")));
+        if let Some(path) = &c.archive_path {
+            out.push_str(&format!("  file = {{Archived copy:{}:text/html}},\n", path));
+        }
+        out.push_str("}\n\n");
+    }
+    out
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Zotero RDF with one `webpage` item per article and its archived copy as
+/// an attachment, importable through Zotero's File > Import.
+fn to_zotero_rdf(citations: &[Citation]) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <rdf:RDF\n \
+         xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\"\n \
+         xmlns:z=\"http://www.zotero.org/namespaces/export#\"\n \
+         xmlns:dc=\"http://purl.org/dc/elements/1.1/\"\n \
+         xmlns:dcterms=\"http://purl.org/dc/terms/\"\n \
+         xmlns:link=\"http://purl.org/rss/1.0/modules/link/\">\n",
+    );
+    for c in citations {
+        let url = xml_escape(&c.url);
+        out.push_str(&format!("    <rdf:Description rdf:about=\"{}\">\n", url));
+        out.push_str("        <z:itemType>webpage</z:itemType>\n");
+        if !c.source.is_empty() {
+            out.push_str(&format!(
+                "        <dcterms:isPartOf>\n            <z:Website><dc:title>{}</dc:title></z:Website>\n        </dcterms:isPartOf>\n",
+                xml_escape(&c.source)
+            ));
+        }
+        if c.archive_path.is_some() {
+            out.push_str(&format!("        <link:link rdf:resource=\"#{}_archive\"/>\n", c.key));
+        }
+        out.push_str(&format!("        <dc:title>{}</dc:title>\n", xml_escape(&c.title)));
+        out.push_str(&format!("        <dc:date>{}</dc:date>\n", format_ts(c.published_at, "%Y-%m-%d")));
+        out.push_str(&format!(
+            "        <dc:identifier>\n            <dcterms:URI><rdf:value>{}</rdf:value></dcterms:URI>\n        </dc:identifier>\n",
+            url
+        ));
+        out.push_str(&format!(
+            "        <dcterms:dateSubmitted>{}</dcterms:dateSubmitted>\n",
+            format_ts(c.accessed_at, "%Y-%m-%d %H:%M:%S")
+        ));
+        out.push_str("    </rdf:Description>\n");
+
+        if let Some(path) = &c.archive_path {
+            out.push_str(&format!("    <z:Attachment rdf:about=\"#{}_archive\">\n", c.key));
+            out.push_str("        <z:itemType>attachment</z:itemType>\n");
+            out.push_str(&format!("        <rdf:resource rdf:resource=\"{}\"/>\n", xml_escape(path)));
+            out.push_str(&format!(
+                "        <dc:identifier>\n            <dcterms:URI><rdf:value>{}</rdf:value></dcterms:URI>\n        </dc:identifier>\n",
+                url
+            ));
+            out.push_str("        <dc:title>Archived copy</dc:title>\n");
+            out.push_str("        <link:type>text/html</link:type>\n");
+            out.push_str("    </z:Attachment>\n");
+        }
+    }
+    out.push_str("</rdf:RDF>\n");
+    out
+}
+
+fn archive_html(title: &str, url: &str, content: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{}</title></head>\n<body>\n<h1>{}</h1>\n<p><a href=\"{}\">{}</a></p>\n{}\n</body>\n</html>\n",
+        xml_escape(title),
+        xml_escape(title),
+        xml_escape(url),
+        xml_escape(url),
+        content
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn citation() -> Citation {
+        Citation {
+            key: "tariffs2024".to_string(),
+            article_id: 1,
+            title: "Tariffs & {chips}: 50% up".to_string(),
+            source: "Wire <News>".to_string(),
+            url: "https://example.com/a?b=1&c=2".to_string(),
+            published_at: 1_717_200_000,
+            accessed_at: 1_717_286_400,
+            archive_path: Some("/tmp/tariffs2024.html".to_string()),
+        }
+    }
+
+    #[test]
+    fn bibtex_escapes_fields_and_references_archive() {
+        assert_eq!(citation_key("The Tariffs are back", 1_717_200_000), "tariffs2024");
+        let bib = to_bibtex(&[citation()]);
+        assert!(bib.starts_with("@online{tariffs2024,\n"));
+        assert!(bib.contains("title = {{Tariffs \\& \\{chips\\}: 50\\% up}}"));
+        assert!(bib.contains("urldate = {2024-06-02}"));
+        assert!(bib.contains("file = {Archived copy:/tmp/tariffs2024.html:text/html}"));
+    }
+
+    #[test]
+    fn zotero_rdf_escapes_xml() {
+        let rdf = to_zotero_rdf(&[citation()]);
+        assert!(rdf.contains("<rdf:Description rdf:about=\"https://example.com/a?b=1&amp;c=2\">"));
+        assert!(rdf.contains("<z:Website><dc:title>Wire &lt;News&gt;</dc:title></z:Website>"));
+        assert!(rdf.contains("<z:Attachment rdf:about=\"#tariffs2024_archive\">"));
+    }
+}
@@ -0,0 +1,264 @@
+use crate::storage::clipboard::ClipboardStore;
+use crate::storage::Database;
+use anyhow::Result;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::AppHandle;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+pub const CONFIG_ENABLED: &str = "clipboard_history_enabled";
+pub const CONFIG_LINGER_SECS: &str = "clipboard_sensitive_linger_secs";
+pub const CONFIG_MAX_ENTRIES: &str = "clipboard_history_max_entries";
+
+const DEFAULT_LINGER_SECS: i64 = 60;
+const DEFAULT_MAX_ENTRIES: i64 = 500;
+const MAX_CONTENT_CHARS: usize = 20_000;
+
+/// (kind, pattern) pairs used to flag clipboard content that looks like a secret.
+const SENSITIVE_PATTERNS: &[(&str, &str)] = &[
+    ("private_key", r"-----BEGIN (?:RSA |EC |DSA |OPENSSH |PGP |ENCRYPTED )?PRIVATE KEY(?: BLOCK)?-----"),
+    ("aws_access_key", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+    ("github_token", r"\bgh[pousr]_[A-Za-z0-9]{36,}\b"),
+    ("openai_key", r"\bsk-(?:proj-)?[A-Za-z0-9_-]{20,}\b"),
+    ("anthropic_key", r"\bsk-ant-[A-Za-z0-9_-]{20,}\b"),
+    ("slack_token", r"\bxox[abprs]-[A-Za-z0-9-]{10,}\b"),
+    ("google_api_key", r"\bAIza[0-9A-Za-z_-]{35}\b"),
+    ("jwt", r"\beyJ[A-Za-z0-9_-]{10,}\.eyJ[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,}\b"),
+    ("assigned_secret", r#"(?i)\b(?:api[_-]?key|secret|token|passw(?:or)?d)\b\s*[:=]\s*["']?[^\s"']{8,}"#),
+];
+
+fn sensitive_regexes() -> &'static Vec<(&'static str, Regex)> {
+    static REGEXES: OnceLock<Vec<(&'static str, Regex)>> = OnceLock::new();
+    REGEXES.get_or_init(|| {
+        SENSITIVE_PATTERNS
+            .iter()
+            .filter_map(|(kind, pattern)| match Regex::new(pattern) {
+                Ok(re) => Some((*kind, re)),
+                Err(e) => {
+                    eprintln!("WARNING: Invalid clipboard pattern {}: {}", kind, e);
+                    None
+                }
+            })
+            .collect()
+    })
+}
+
+/// Return the kinds of secrets found in `text` (empty when nothing matched).
+pub fn detect_sensitive(text: &str) -> Vec<String> {
+    sensitive_regexes()
+        .iter()
+        .filter(|(_, re)| re.is_match(text))
+        .map(|(kind, _)| kind.to_string())
+        .collect()
+}
+
+/// Replace every detected secret with a `[REDACTED:<kind>]` marker.
+pub fn redact_sensitive(text: &str) -> String {
+    let mut redacted = text.to_string();
+    for (kind, re) in sensitive_regexes() {
+        redacted = re.replace_all(&redacted, format!("[REDACTED:{}]", kind).as_str()).to_string();
+    }
+    redacted
+}
+
+fn hash_content(text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(text.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+pub struct ClipboardMonitor;
+
+impl ClipboardMonitor {
+    /// Poll the system clipboard while history is enabled in config. Disabled by default.
+    pub fn start_monitoring(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(2));
+            let mut last_hash: Option<String> = None;
+            // When the current clipboard content is sensitive: (first seen, already warned)
+            let mut sensitive_since: Option<(i64, bool)> = None;
+
+            loop {
+                interval.tick().await;
+
+                let (enabled, linger_secs, max_entries) = match Self::load_settings(&db) {
+                    Ok(settings) => settings,
+                    Err(e) => {
+                        eprintln!("Error loading clipboard settings: {}", e);
+                        continue;
+                    }
+                };
+                if !enabled {
+                    last_hash = None;
+                    sensitive_since = None;
+                    continue;
+                }
+
+                let text = match read_clipboard().await {
+                    Some(text) if !text.trim().is_empty() => text,
+                    _ => {
+                        last_hash = None;
+                        sensitive_since = None;
+                        continue;
+                    }
+                };
+
+                let hash = hash_content(&text);
+                let now = chrono::Utc::now().timestamp();
+
+                if last_hash.as_deref() != Some(hash.as_str()) {
+                    last_hash = Some(hash.clone());
+                    let kinds = detect_sensitive(&text);
+                    sensitive_since = if kinds.is_empty() { None } else { Some((now, false)) };
+
+                    if let Err(e) = Self::record(&db, &text, &hash, &kinds, max_entries) {
+                        eprintln!("Error recording clipboard entry: {}", e);
+                    }
+                    continue;
+                }
+
+                if let Some((since, warned)) = sensitive_since {
+                    if !warned && now - since >= linger_secs {
+                        sensitive_since = Some((since, true));
+                        Self::warn_lingering(&app, &text, now - since).await;
+                    }
+                }
+            }
+        });
+    }
+
+    fn load_settings(db: &Arc<Mutex<Database>>) -> Result<(bool, i64, i64)> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let enabled = db_guard.get_config(CONFIG_ENABLED)?
+            .map(|v| v == "true")
+            .unwrap_or(false);
+        let linger_secs = db_guard.get_config(CONFIG_LINGER_SECS)?
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(DEFAULT_LINGER_SECS)
+            .max(5);
+        let max_entries = db_guard.get_config(CONFIG_MAX_ENTRIES)?
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(DEFAULT_MAX_ENTRIES)
+            .max(1);
+        Ok((enabled, linger_secs, max_entries))
+    }
+
+    fn record(db: &Arc<Mutex<Database>>, text: &str, hash: &str, kinds: &[String], max_entries: i64) -> Result<()> {
+        let conn = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            db_guard.conn.clone()
+        };
+        let store = ClipboardStore::new(conn);
+
+        // Secrets are never written to disk in clear text, nor as a hash that
+        // could be checked against guesses: sensitive entries go by their redacted text
+        let (stored, stored_hash) = if kinds.is_empty() {
+            (text.to_string(), hash.to_string())
+        } else {
+            let redacted = redact_sensitive(text);
+            let redacted_hash = format!("redacted:{}", hash_content(&redacted));
+            (redacted, redacted_hash)
+        };
+        let stored: String = stored.chars().take(MAX_CONTENT_CHARS).collect();
+
+        store.record_entry(&stored, &stored_hash, kinds, text.chars().count())?;
+        store.trim(max_entries)?;
+        Ok(())
+    }
+
+    async fn warn_lingering(app: &AppHandle, text: &str, age_secs: i64) {
+        let kinds = detect_sensitive(text);
+        let _ = crate::services::window_router::emit(app, serde_json::json!({
+            "type": "clipboard-sensitive-warning",
+            "data": { "kinds": kinds, "age_secs": age_secs },
+            "timestamp": chrono::Utc::now().timestamp_millis()
+        }));
+
+        use crate::services::desktop_notifications::{DesktopNotificationService, NotificationOptions};
+        let _ = DesktopNotificationService::send(
+            app,
+            NotificationOptions {
+                title: "Sensitive data on clipboard".to_string(),
+                body: format!(
+                    "Clipboard has held what looks like a {} for {}s. Consider clearing it.",
+                    kinds.join(", "),
+                    age_secs
+                ),
+                icon: Some("alert".to_string()),
+                sound: Some("default".to_string()),
+                tag: Some("clipboard-sensitive".to_string()),
+                data: Some(serde_json::json!({ "type": "clipboard_sensitive", "kinds": kinds })),
+            },
+        )
+        .await;
+    }
+}
+
+/// Platform clipboard tools, in order of preference.
+fn clipboard_tools(read: bool) -> Vec<(&'static str, Vec<&'static str>)> {
+    if cfg!(target_os = "macos") {
+        if read {
+            vec![("pbpaste", vec![])]
+        } else {
+            vec![("pbcopy", vec![])]
+        }
+    } else if cfg!(target_os = "windows") {
+        if read {
+            vec![("powershell", vec!["-NoProfile", "-Command", "Get-Clipboard -Raw"])]
+        } else {
+            vec![("powershell", vec!["-NoProfile", "-Command", "Set-Clipboard -Value $null"])]
+        }
+    } else if read {
+        vec![
+            ("wl-paste", vec!["--no-newline"]),
+            ("xclip", vec!["-selection", "clipboard", "-o"]),
+            ("xsel", vec!["--clipboard", "--output"]),
+        ]
+    } else {
+        vec![
+            ("wl-copy", vec!["--clear"]),
+            ("xclip", vec!["-selection", "clipboard", "-i"]),
+            ("xsel", vec!["--clipboard", "--clear"]),
+        ]
+    }
+}
+
+/// Read the current clipboard text using the platform's CLI tools.
+pub async fn read_clipboard() -> Option<String> {
+    for (program, args) in clipboard_tools(true) {
+        if let Ok(output) = Command::new(program).args(&args).output().await {
+            if output.status.success() {
+                return Some(String::from_utf8_lossy(&output.stdout).to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Overwrite the clipboard with an empty string.
+pub async fn clear_clipboard() -> Result<(), String> {
+    for (program, args) in clipboard_tools(false) {
+        let child = Command::new(program)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn();
+        if let Ok(mut child) = child {
+            // Dropping stdin after an empty write leaves the clipboard empty
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = stdin.write_all(b"").await;
+            }
+            if let Ok(status) = child.wait().await {
+                if status.success() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    Err("No clipboard tool available to clear the clipboard".to_string())
+}
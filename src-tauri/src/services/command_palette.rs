@@ -0,0 +1,268 @@
+use crate::storage::{AutomationStore, Database, PaletteStore, ProjectStore};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const RECENT_LIMIT: i64 = 100;
+
+/// One palette entry. Navigation entries carry a `route`, actions a Tauri
+/// `command` with its `args`; recents carry the id the UI opens them by.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaletteItem {
+    pub kind: String, // action|navigate|script|workflow|project|event|entity|...
+    pub id: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub route: Option<String>,
+    pub command: Option<String>,
+    pub args: Option<serde_json::Value>,
+    pub score: i64,
+}
+
+/// (route, title) of every top-level view.
+const ROUTES: &[(&str, &str)] = &[
+    ("/", "Hub"),
+    ("/system-monitor", "System Monitor"),
+    ("/network", "Network"),
+    ("/ai", "AI Chat"),
+    ("/devops", "DevOps"),
+    ("/automation", "Automation"),
+    ("/packages", "Packages"),
+    ("/reality", "Reality Timeline Studio"),
+    ("/vector-store", "Vector Store"),
+    ("/security", "Security Center"),
+    ("/utilities", "System Utilities"),
+    ("/create", "Create"),
+    ("/testing", "Testing Center"),
+    ("/config", "Configuration"),
+    ("/migration", "Migrations"),
+    ("/websocket", "WebSocket Monitor"),
+    ("/errors", "Errors"),
+    ("/rate-limit", "Rate Limits"),
+    ("/vector-search", "Vector Search"),
+    ("/analytics", "Analytics"),
+    ("/grid", "Grid Layout"),
+    ("/stock-news", "Stock News"),
+    ("/market-data", "Market Data"),
+    ("/portfolio", "Portfolio"),
+    ("/economic-calendar", "Economic Calendar"),
+    ("/chart-studio", "Chart Studio"),
+    ("/messaging", "Messaging"),
+    ("/sentiment", "Sentiment Analysis"),
+    ("/docs", "Documentation"),
+];
+
+/// (id, title, command, args) of actions that run a backend command.
+fn actions() -> Vec<(&'static str, &'static str, &'static str, serde_json::Value)> {
+    vec![
+        ("fetch-feeds", "Fetch RSS feeds", "start_job", serde_json::json!({ "kind": "fetch_feeds" })),
+        ("rebuild-events", "Rebuild events", "start_job", serde_json::json!({ "kind": "rebuild_events" })),
+        ("rebuild-search-index", "Rebuild search index", "start_job", serde_json::json!({ "kind": "rebuild_search_index" })),
+        ("export-vault", "Export Obsidian vault", "start_job", serde_json::json!({ "kind": "export_vault" })),
+        ("open-alerts-window", "Open alerts window", "open_window", serde_json::json!({
+            "label": "alerts",
+            "route": "/reality",
+            "title": "MINA - Alerts",
+            "topics": ["price-alert", "alert-*", "desktop-notification"],
+        })),
+        ("list-jobs", "Show background jobs", "list_jobs", serde_json::json!({})),
+        ("slow-commands", "Show slow commands", "get_slow_commands", serde_json::json!({})),
+    ]
+}
+
+/// Searchable registry behind the UI command palette: views, actions,
+/// scripts, workflows, projects and recently opened items, ranked the same
+/// way for every caller.
+pub struct CommandPalette;
+
+impl CommandPalette {
+    pub fn search(db: &Database, query: &str, limit: usize) -> Result<Vec<PaletteItem>> {
+        let query = query.trim();
+        let now = chrono::Utc::now().timestamp();
+        let mut candidates: Vec<(PaletteItem, i64)> = Vec::new();
+
+        for (route, title) in ROUTES {
+            candidates.push((item("navigate", route, title, Some(format!("Go to {}", route)), Some(route), None), 0));
+        }
+        for (id, title, command, args) in actions() {
+            let mut action = item("action", id, title, None, None, Some(command));
+            action.args = Some(args);
+            candidates.push((action, 0));
+        }
+
+        if let Ok(store) = AutomationStore::new(db.conn.clone()) {
+            for script in store.list_scripts()? {
+                candidates.push((
+                    item(
+                        "script",
+                        &script.id.to_string(),
+                        &script.name,
+                        Some(script.language.clone()),
+                        Some("/automation"),
+                        None,
+                    ),
+                    0,
+                ));
+            }
+            for workflow in store.list_workflows()? {
+                candidates.push((
+                    item(
+                        "workflow",
+                        &workflow.id.to_string(),
+                        &workflow.name,
+                        workflow.description.clone(),
+                        Some("/automation"),
+                        None,
+                    ),
+                    0,
+                ));
+            }
+        }
+        for project in ProjectStore::new(db.conn.clone()).list_projects(None, None)? {
+            candidates.push((
+                item(
+                    "project",
+                    &project.id.to_string(),
+                    &project.name,
+                    Some(project.project_type.clone()),
+                    Some("/create"),
+                    None,
+                ),
+                0,
+            ));
+        }
+
+        // Recents boost the matching entry, or stand alone for events and
+        // entities that have no listing of their own here
+        let mut index: HashMap<(String, String), usize> = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, (item, _))| ((item.kind.clone(), item.id.clone()), i))
+            .collect();
+        for recent in PaletteStore::new(db.conn.clone()).list_recent(RECENT_LIMIT)? {
+            let boost = recency_boost(now - recent.visited_at) + recent.visit_count.min(10);
+            let key = (recent.kind.clone(), recent.target_id.clone());
+            match index.get(&key) {
+                Some(&i) => candidates[i].1 += boost,
+                None => {
+                    index.insert(key, candidates.len());
+                    candidates.push((
+                        item(&recent.kind, &recent.target_id, &recent.title, recent.subtitle.clone(), None, None),
+                        boost,
+                    ));
+                }
+            }
+        }
+
+        let mut results: Vec<PaletteItem> = candidates
+            .into_iter()
+            .filter_map(|(mut item, boost)| {
+                let text_score = if query.is_empty() {
+                    0
+                } else {
+                    let title = fuzzy_score(query, &item.title);
+                    let subtitle = item.subtitle.as_deref().and_then(|s| fuzzy_score(query, s)).map(|s| s - 10);
+                    title.max(subtitle)?
+                };
+                item.score = text_score + boost;
+                Some(item)
+            })
+            .collect();
+        results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+        results.truncate(limit);
+        Ok(results)
+    }
+}
+
+fn item(
+    kind: &str,
+    id: &str,
+    title: &str,
+    subtitle: Option<String>,
+    route: Option<&str>,
+    command: Option<&str>,
+) -> PaletteItem {
+    PaletteItem {
+        kind: kind.to_string(),
+        id: id.to_string(),
+        title: title.to_string(),
+        subtitle,
+        route: route.map(|r| r.to_string()),
+        command: command.map(|c| c.to_string()),
+        args: None,
+        score: 0,
+    }
+}
+
+fn recency_boost(age_secs: i64) -> i64 {
+    match age_secs {
+        a if a < 3600 => 20,
+        a if a < 86_400 => 12,
+        a if a < 7 * 86_400 => 6,
+        _ => 2,
+    }
+}
+
+/// Subsequence match of `query` in `text`, case-insensitive. Consecutive
+/// characters, word starts and prefixes score higher; None when not all
+/// query characters appear in order.
+pub(crate) fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let mut score = 0i64;
+    let mut matched = 0usize;
+    let mut last: Option<usize> = None;
+    for (i, c) in text.iter().enumerate() {
+        if matched == query.len() {
+            break;
+        }
+        if *c != query[matched] {
+            continue;
+        }
+        score += 1;
+        if i > 0 && last == Some(i - 1) {
+            score += 5;
+        }
+        if i == 0 || !text[i - 1].is_alphanumeric() {
+            score += 8;
+        }
+        last = Some(i);
+        matched += 1;
+    }
+    if matched < query.len() {
+        return None;
+    }
+    if text.starts_with(&query) {
+        score += 20;
+    }
+    // Prefer shorter texts for the same match
+    score -= (text.len().saturating_sub(query.len()) / 8) as i64;
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_requires_all_chars_in_order() {
+        assert!(fuzzy_score("pfm", "Portfolio Manager").is_some());
+        assert!(fuzzy_score("mfp", "Portfolio Manager").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn fuzzy_prefers_prefix_and_word_starts() {
+        let prefix = fuzzy_score("port", "Portfolio").unwrap();
+        let inner = fuzzy_score("port", "Import tools").unwrap();
+        assert!(prefix > inner);
+
+        let initials = fuzzy_score("sm", "System Monitor").unwrap();
+        let scattered = fuzzy_score("sm", "Messaging").unwrap_or(i64::MIN);
+        assert!(initials > scattered);
+    }
+}
@@ -125,13 +125,17 @@ impl CommandRegistry {
             let limit = args.get("limit").and_then(|v| v.as_i64());
             let from_ts = args.get("from_ts").and_then(|v| v.as_i64());
             let to_ts = args.get("to_ts").and_then(|v| v.as_i64());
+            let tag = args.get("tag").and_then(|v| v.as_str());
             let db = app.try_state::<Mutex<Database>>()
                 .ok_or_else(|| anyhow::anyhow!("Database not found in app state"))?;
             let db_guard = db.lock()
                 .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
             let store = crate::storage::TemporalStore::new(db_guard.conn.clone());
-            let result = store.list_events(limit.unwrap_or(200), from_ts, to_ts)
-                .map_err(|e| anyhow::anyhow!("{}", e))?;
+            let result = match tag {
+                Some(tag) => store.list_tagged_events(tag, limit.unwrap_or(200), from_ts, to_ts),
+                None => store.list_events(limit.unwrap_or(200), from_ts, to_ts),
+            }
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
             Ok(serde_json::to_value(result)?)
         });
         
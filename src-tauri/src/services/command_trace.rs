@@ -0,0 +1,172 @@
+use crate::storage::{AnalyticsStore, Database};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc;
+
+pub const METRIC_TYPE: &str = "command_duration";
+/// Commands at least this slow (ms) are pushed to the UI as `slow-command`.
+pub const CONFIG_SLOW_MS: &str = "command_trace_slow_ms";
+
+pub const DEFAULT_SLOW_MS: f64 = 250.0;
+const FLUSH_INTERVAL_SECS: u64 = 5;
+const RETENTION_SECS: i64 = 7 * 86_400;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandSample {
+    pub command: String,
+    pub args_bytes: usize,
+    pub duration_ms: f64,
+    /// Estimated wait for the database connection, 0 when it was free at
+    /// dispatch
+    pub db_wait_ms: f64,
+    pub timestamp: i64,
+}
+
+/// Records how long every Tauri command takes. Samples are queued and
+/// written in batches so tracing never adds a database write to the
+/// command itself.
+///
+/// Sync commands run on the main thread and are timed to completion, which
+/// is what shows up as UI jank. Async commands are timed until they hand off
+/// to the runtime.
+#[derive(Clone)]
+pub struct CommandTracer {
+    tx: mpsc::UnboundedSender<CommandSample>,
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl CommandTracer {
+    pub fn start(app: AppHandle, conn: Arc<Mutex<Connection>>) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<CommandSample>();
+        let writer_conn = conn.clone();
+
+        tauri::async_runtime::spawn(async move {
+            let store = AnalyticsStore::new(writer_conn.clone());
+            let mut interval = tokio::time::interval(Duration::from_secs(FLUSH_INTERVAL_SECS));
+            let mut last_prune = 0i64;
+
+            loop {
+                interval.tick().await;
+
+                let mut batch = Vec::new();
+                while let Ok(sample) = rx.try_recv() {
+                    batch.push(sample);
+                }
+                if batch.is_empty() {
+                    continue;
+                }
+
+                let slow_ms = Database { conn: writer_conn.clone() }
+                    .get_config(CONFIG_SLOW_MS)
+                    .ok()
+                    .flatten()
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .unwrap_or(DEFAULT_SLOW_MS);
+                for sample in batch.iter().filter(|s| s.duration_ms >= slow_ms) {
+                    let _ = crate::services::window_router::emit(&app, serde_json::json!({
+                        "type": "slow-command",
+                        "data": sample,
+                        "timestamp": chrono::Utc::now().timestamp_millis(),
+                    }));
+                }
+
+                let rows: Vec<(i64, f64, Option<String>)> = batch
+                    .iter()
+                    .map(|s| {
+                        let metadata = serde_json::json!({
+                            "command": s.command,
+                            "args_bytes": s.args_bytes,
+                            "db_wait_ms": s.db_wait_ms,
+                        });
+                        (s.timestamp, s.duration_ms, Some(metadata.to_string()))
+                    })
+                    .collect();
+                if let Err(e) = store.save_metrics(METRIC_TYPE, &rows) {
+                    eprintln!("Failed to save command timings: {}", e);
+                }
+
+                let now = chrono::Utc::now().timestamp();
+                if now - last_prune > 3600 {
+                    let _ = store.delete_metrics_before(METRIC_TYPE, now - RETENTION_SECS);
+                    last_prune = now;
+                }
+            }
+        });
+
+        CommandTracer { tx, conn }
+    }
+
+    fn record(&self, command: String, args_bytes: usize, started: Instant, db_busy: bool) {
+        let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+        let sample = CommandSample {
+            command,
+            args_bytes,
+            duration_ms,
+            db_wait_ms: 0.0,
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        if !db_busy {
+            let _ = self.tx.send(sample);
+            return;
+        }
+
+        // Wait for the connection off the main thread to see how long it
+        // stays busy; only done when it was locked at dispatch
+        let tracer = self.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let waited = match tracer.conn.lock() {
+                Ok(_guard) => started.elapsed(),
+                Err(_) => Duration::ZERO,
+            };
+            let _ = tracer.tx.send(CommandSample {
+                db_wait_ms: (waited.as_secs_f64() * 1000.0 - sample.duration_ms).max(0.0),
+                ..sample
+            });
+        });
+    }
+}
+
+/// Wrap the generated command handler so each invocation is timed.
+pub fn instrument<F>(handler: F) -> impl Fn(Invoke<tauri::Wry>) -> bool + Send + Sync + 'static
+where
+    F: Fn(Invoke<tauri::Wry>) -> bool + Send + Sync + 'static,
+{
+    move |invoke: Invoke<tauri::Wry>| {
+        let webview = invoke.message.webview();
+        let Some(tracer) = webview.try_state::<CommandTracer>().map(|t| t.inner().clone()) else {
+            return handler(invoke);
+        };
+
+        let command = invoke.message.command().to_string();
+        let args_bytes = payload_size(invoke.message.payload());
+        let db_busy = matches!(tracer.conn.try_lock(), Err(std::sync::TryLockError::WouldBlock));
+        let started = Instant::now();
+
+        let handled = handler(invoke);
+        tracer.record(command, args_bytes, started, db_busy);
+        handled
+    }
+}
+
+fn payload_size(payload: &InvokeBody) -> usize {
+    match payload {
+        InvokeBody::Json(value) => serde_json::to_vec(value).map(|v| v.len()).unwrap_or(0),
+        InvokeBody::Raw(bytes) => bytes.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_size_counts_serialized_json() {
+        let body = InvokeBody::Json(serde_json::json!({ "limit": 10 }));
+        assert_eq!(payload_size(&body), r#"{"limit":10}"#.len());
+        assert_eq!(payload_size(&InvokeBody::Raw(vec![0; 42])), 42);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::providers::ollama::{ChatMessage, OllamaProvider};
+use crate::services::llm_metering::LlmMeter;
+use crate::storage::ai::AIStore;
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Ollama's window when the Modelfile doesn't set `num_ctx`; prompts past
+/// it are silently truncated.
+const DEFAULT_NUM_CTX: u64 = 4096;
+/// Rough average for English text with the tokenizers Ollama models use.
+const CHARS_PER_TOKEN: u64 = 4;
+/// Role markers and separators the chat template adds per message.
+const MESSAGE_OVERHEAD_TOKENS: u64 = 4;
+/// Share of the window the prompt may fill; the rest is left for the answer.
+const PROMPT_BUDGET: f64 = 0.75;
+/// The latest turns are always sent verbatim.
+const KEEP_RECENT: usize = 6;
+
+const SUMMARY_PROMPT: &str = "You maintain a running summary of a conversation between a user and an assistant. \
+Given the previous summary (if any) and the next turns, write an updated summary in at most 200 words. \
+Keep names, numbers, decisions, open questions and the user's stated goals; drop pleasantries. \
+Respond with only the summary.";
+
+/// How full a model's context window is for a conversation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextUsage {
+    pub model: String,
+    pub context_length: u64,
+    /// Tokens the prompt may use before answers get cut short
+    pub budget_tokens: u64,
+    pub used_tokens: u64,
+    /// Of the whole window; over 100 means the prompt gets truncated
+    pub percent: f64,
+    pub summarized_messages: usize,
+    pub summary_tokens: u64,
+}
+
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(CHARS_PER_TOKEN)
+}
+
+pub fn count_tokens(messages: &[ChatMessage]) -> u64 {
+    messages
+        .iter()
+        .map(|m| estimate_tokens(&m.content) + MESSAGE_OVERHEAD_TOKENS)
+        .sum()
+}
+
+/// How many leading turns to summarize so the rest fits in `budget`,
+/// given the tokens of every turn and of what's always sent (system
+/// prompt and summary). Never less than `summarized`, and never so many
+/// that fewer than KEEP_RECENT turns remain.
+fn cut_point(turn_tokens: &[u64], summarized: usize, fixed_tokens: u64, budget: u64) -> usize {
+    let last = turn_tokens.len().saturating_sub(KEEP_RECENT).max(summarized);
+    let mut cut = summarized;
+    let mut total = fixed_tokens + turn_tokens[summarized.min(turn_tokens.len())..].iter().sum::<u64>();
+    while total > budget && cut < last {
+        total -= turn_tokens[cut];
+        cut += 1;
+    }
+    cut
+}
+
+static WINDOWS: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+
+/// Counts tokens against each model's context window and folds the
+/// oldest turns of long chats into a rolling summary stored with the
+/// conversation, so they don't fall off the end of the window unnoticed.
+pub struct ContextManager;
+
+impl ContextManager {
+    /// The window a model actually runs with, cached per model.
+    pub async fn context_length(provider: &OllamaProvider, model: &str) -> u64 {
+        if let Some(length) = WINDOWS.get().and_then(|w| w.lock().ok()?.get(model).copied()) {
+            return length;
+        }
+        let length = match provider.context_window(model).await {
+            Ok((Some(num_ctx), _)) => num_ctx,
+            Ok((None, Some(trained))) => trained.min(DEFAULT_NUM_CTX),
+            Ok((None, None)) => DEFAULT_NUM_CTX,
+            // Not cached, so the next call retries
+            Err(_) => return DEFAULT_NUM_CTX,
+        };
+        if let Ok(mut windows) = WINDOWS.get_or_init(|| Mutex::new(HashMap::new())).lock() {
+            windows.insert(model.to_string(), length);
+        }
+        length
+    }
+
+    /// Tokens a prompt may use with `model` and still leave room for the answer.
+    pub async fn prompt_budget(provider: &OllamaProvider, model: &str) -> u64 {
+        (Self::context_length(provider, model).await as f64 * PROMPT_BUDGET) as u64
+    }
+
+    /// Usage without summarizing anything, applying the conversation's
+    /// stored summary when there is one.
+    pub async fn usage(
+        db: &Mutex<Database>,
+        provider: &OllamaProvider,
+        model: &str,
+        conversation_id: Option<&str>,
+        messages: &[ChatMessage],
+    ) -> Result<ContextUsage> {
+        let context_length = Self::context_length(provider, model).await;
+        let (summary, summarized) = match conversation_id {
+            Some(id) => Self::stored_summary(db, id, messages)?,
+            None => (None, 0),
+        };
+        let prompt = compose(messages, summary.as_deref(), summarized);
+        Ok(usage(model, context_length, &prompt, summarized, summary.as_deref()))
+    }
+
+    /// The messages to send for a conversation: its system prompt, the
+    /// summary of older turns and the latest turns, summarizing more of
+    /// them first when the prompt would overflow the window.
+    pub async fn fit(
+        db: &Mutex<Database>,
+        provider: &OllamaProvider,
+        model: &str,
+        conversation_id: &str,
+        messages: Vec<ChatMessage>,
+    ) -> Result<(Vec<ChatMessage>, ContextUsage)> {
+        let context_length = Self::context_length(provider, model).await;
+        let budget = (context_length as f64 * PROMPT_BUDGET) as u64;
+        let (mut summary, mut summarized) = Self::stored_summary(db, conversation_id, &messages)?;
+
+        let (system, turns): (Vec<&ChatMessage>, Vec<&ChatMessage>) = messages.iter().partition(|m| m.role == "system");
+        let turn_tokens: Vec<u64> = turns.iter().map(|m| count_tokens(std::slice::from_ref(*m))).collect();
+        let system_tokens: u64 = system.iter().map(|m| count_tokens(std::slice::from_ref(*m))).sum();
+        // The new summary's size isn't known yet; assume the prompt's cap
+        let fixed_tokens = system_tokens + 300;
+        let cut = cut_point(&turn_tokens, summarized, fixed_tokens, budget);
+
+        if cut > summarized {
+            let (meter, store) = {
+                let db_guard = db.lock()
+                    .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+                (LlmMeter::new(db_guard.conn.clone()), AIStore::new(db_guard.conn.clone())?)
+            };
+            let transcript = turns[summarized..cut]
+                .iter()
+                .map(|m| format!("{}: {}", m.role, m.content))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let input = match &summary {
+                Some(previous) => format!("Previous summary:\n{}\n\nNext turns:\n{}", previous, transcript),
+                None => format!("Next turns:\n{}", transcript),
+            };
+            let updated = meter
+                .ollama_chat(provider, model, vec![
+                    ChatMessage { role: "system".to_string(), content: SUMMARY_PROMPT.to_string() },
+                    ChatMessage { role: "user".to_string(), content: input },
+                ])
+                .await?;
+            store.save_summary(conversation_id, updated.trim(), cut as i64, Some(model))?;
+            summary = Some(updated.trim().to_string());
+            summarized = cut;
+        }
+
+        let prompt = compose(&messages, summary.as_deref(), summarized);
+        let usage = usage(model, context_length, &prompt, summarized, summary.as_deref());
+        Ok((prompt, usage))
+    }
+
+    /// The stored summary, unless the conversation no longer has the turns
+    /// it covers (e.g. it was edited or cleared).
+    fn stored_summary(db: &Mutex<Database>, conversation_id: &str, messages: &[ChatMessage]) -> Result<(Option<String>, usize)> {
+        let stored = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            AIStore::new(db_guard.conn.clone())?.get_summary(conversation_id)?
+        };
+        let turns = messages.iter().filter(|m| m.role != "system").count();
+        Ok(match stored {
+            Some(s) if (s.summarized_messages as usize) <= turns => (Some(s.summary), s.summarized_messages as usize),
+            _ => (None, 0),
+        })
+    }
+}
+
+/// System messages, then the summary, then the turns it doesn't cover.
+fn compose(messages: &[ChatMessage], summary: Option<&str>, summarized: usize) -> Vec<ChatMessage> {
+    let mut prompt: Vec<ChatMessage> = messages.iter().filter(|m| m.role == "system").cloned().collect();
+    if let Some(summary) = summary.filter(|_| summarized > 0) {
+        prompt.push(ChatMessage {
+            role: "system".to_string(),
+            content: format!("Summary of the earlier conversation:\n{}", summary),
+        });
+    }
+    prompt.extend(messages.iter().filter(|m| m.role != "system").skip(summarized).cloned());
+    prompt
+}
+
+fn usage(model: &str, context_length: u64, prompt: &[ChatMessage], summarized: usize, summary: Option<&str>) -> ContextUsage {
+    let used_tokens = count_tokens(prompt);
+    ContextUsage {
+        model: model.to_string(),
+        context_length,
+        budget_tokens: (context_length as f64 * PROMPT_BUDGET) as u64,
+        used_tokens,
+        percent: used_tokens as f64 * 100.0 / context_length.max(1) as f64,
+        summarized_messages: summarized,
+        summary_tokens: summary.filter(|_| summarized > 0).map(estimate_tokens).unwrap_or(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_turns_are_cut_until_the_rest_fits() {
+        let turns = vec![500, 500, 500, 500, 100, 100, 100, 100, 100, 100];
+        // Fits as is
+        assert_eq!(cut_point(&turns, 0, 200, 10_000), 0);
+        // 2,800 tokens into 1,500: the four long turns go
+        assert_eq!(cut_point(&turns, 0, 200, 1_500), 3);
+        assert_eq!(cut_point(&turns, 0, 200, 900), 4);
+        // The latest turns stay even when they don't fit
+        assert_eq!(cut_point(&turns, 0, 200, 100), 4);
+        // Already summarized turns stay summarized
+        assert_eq!(cut_point(&turns, 4, 200, 10_000), 4);
+
+        let messages = vec![
+            ChatMessage { role: "system".to_string(), content: "Be brief.".to_string() },
+            ChatMessage { role: "user".to_string(), content: "one".to_string() },
+            ChatMessage { role: "assistant".to_string(), content: "two".to_string() },
+            ChatMessage { role: "user".to_string(), content: "three".to_string() },
+        ];
+        let prompt = compose(&messages, Some("counted to two"), 2);
+        let contents: Vec<&str> = prompt.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["Be brief.", "Summary of the earlier conversation:\ncounted to two", "three"]);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::providers::ollama::{ChatMessage, OllamaProvider};
+use crate::services::embeddings::EmbeddingService;
+use crate::services::llm_metering::LlmMeter;
+use crate::services::model_benchmark::{ModelBenchmark, TASK_EXTRACTION};
+use crate::services::structured_output::StructuredOutput;
+use crate::storage::memories::{Memory, MemoryStore};
+use crate::storage::vector_store::cosine_similarity;
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+pub const CONFIG_MEMORY_ENABLED: &str = "ai_memory_enabled";
+
+/// New memories this similar to an existing one are treated as duplicates.
+const DUPLICATE_SIMILARITY: f32 = 0.92;
+const MIN_RELEVANCE: f32 = 0.3;
+
+const EXTRACTION_PROMPT: &str = "You extract durable facts and preferences about the user from a conversation. \
+Only include information that will still be useful in future conversations (e.g. their job, tools they use, \
+portfolio focus, formatting preferences). Ignore one-off questions and anything the assistant said about itself. \
+Respond with only a JSON array of objects like {\"content\": \"...\", \"kind\": \"fact\" | \"preference\"}. \
+Respond with [] if there is nothing worth remembering.";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedMemory {
+    pub content: String,
+    #[serde(default = "default_kind")]
+    pub kind: String,
+}
+
+fn default_kind() -> String {
+    "fact".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoredMemory {
+    pub memory: Memory,
+    pub score: f32,
+}
+
+pub struct ConversationMemoryService;
+
+impl ConversationMemoryService {
+    pub fn is_enabled(db: &Mutex<Database>) -> Result<bool> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        Ok(db_guard.get_config(CONFIG_MEMORY_ENABLED)?
+            .map(|v| v == "true")
+            .unwrap_or(false))
+    }
+
+    /// Ask the model for durable facts in a conversation and store the new ones.
+    /// Returns the memories that were added.
+    pub async fn extract_from_conversation(
+        db: &Mutex<Database>,
+        ollama: &OllamaProvider,
+        embedder: &EmbeddingService,
+        model: &str,
+        conversation_id: &str,
+    ) -> Result<Vec<Memory>> {
+        let (store, meter, transcript, conn) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let ai_store = crate::storage::AIStore::new(db_guard.conn.clone())?;
+            let transcript = ai_store
+                .get_messages(conversation_id)?
+                .iter()
+                .map(|m| format!("{}: {}", m.role, m.content))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            (
+                MemoryStore::new(db_guard.conn.clone()),
+                LlmMeter::new(db_guard.conn.clone()),
+                transcript,
+                db_guard.conn.clone(),
+            )
+        };
+        if transcript.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let model = &ModelBenchmark::resolve(&conn, TASK_EXTRACTION, model)?;
+        let extracted: Vec<ExtractedMemory> = StructuredOutput::ollama_chat(
+            &meter,
+            ollama,
+            model,
+            vec![
+                ChatMessage { role: "system".to_string(), content: EXTRACTION_PROMPT.to_string() },
+                ChatMessage { role: "user".to_string(), content: transcript },
+            ],
+            &extraction_schema(),
+        )
+        .await?;
+
+        let mut existing = store.list_memories()?;
+        let mut added = Vec::new();
+        for candidate in extracted {
+            let content = candidate.content.trim();
+            if content.is_empty() {
+                continue;
+            }
+            let embedding = embedder.generate(content).await?;
+            let duplicate = existing
+                .iter()
+                .any(|m| cosine_similarity(&m.embedding, &embedding) >= DUPLICATE_SIMILARITY);
+            if duplicate {
+                continue;
+            }
+
+            let kind = if candidate.kind == "preference" { "preference" } else { "fact" };
+            let id = store.add_memory(content, kind, Some(conversation_id), &embedding)?;
+            if let Some(memory) = store.get_memory(id)? {
+                existing.push(memory.clone());
+                added.push(memory);
+            }
+        }
+
+        Ok(added)
+    }
+
+    pub async fn find_relevant(
+        db: &Mutex<Database>,
+        embedder: &EmbeddingService,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<ScoredMemory>> {
+        let store = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            MemoryStore::new(db_guard.conn.clone())
+        };
+        let memories = store.list_memories()?;
+        if memories.is_empty() || query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query_embedding = embedder.generate(query).await?;
+        let mut scored: Vec<ScoredMemory> = memories
+            .into_iter()
+            .map(|memory| ScoredMemory {
+                score: cosine_similarity(&query_embedding, &memory.embedding),
+                memory,
+            })
+            .filter(|s| s.score >= MIN_RELEVANCE)
+            .collect();
+        scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+
+        let ids: Vec<i64> = scored.iter().map(|s| s.memory.id).collect();
+        store.mark_used(&ids)?;
+        Ok(scored)
+    }
+
+    /// Add relevant memories to the system prompt of a chat, based on the last user message.
+    pub async fn inject(
+        db: &Mutex<Database>,
+        embedder: &EmbeddingService,
+        messages: &mut Vec<ChatMessage>,
+        limit: usize,
+    ) -> Result<()> {
+        let query = match messages.iter().rev().find(|m| m.role == "user") {
+            Some(m) => m.content.clone(),
+            None => return Ok(()),
+        };
+        let relevant = Self::find_relevant(db, embedder, &query, limit).await?;
+        if relevant.is_empty() {
+            return Ok(());
+        }
+
+        let section = format_memory_section(&relevant);
+        match messages.iter_mut().find(|m| m.role == "system") {
+            Some(system) => {
+                system.content = format!("{}\n\n{}", system.content, section);
+            }
+            None => messages.insert(0, ChatMessage { role: "system".to_string(), content: section }),
+        }
+        Ok(())
+    }
+}
+
+pub fn format_memory_section(memories: &[ScoredMemory]) -> String {
+    let mut section = String::from("Things you know about the user from earlier conversations:\n");
+    for scored in memories {
+        section.push_str(&format!("- {}\n", scored.memory.content));
+    }
+    section
+}
+
+fn extraction_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "required": ["content"],
+            "properties": {
+                "content": { "type": "string" },
+                "kind": { "enum": ["fact", "preference"] }
+            }
+        }
+    })
+}
@@ -0,0 +1,115 @@
+use crate::storage::temporal::{Corroboration, EvidenceSource, TemporalStore};
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+
+pub struct CorroborationScorer;
+
+impl CorroborationScorer {
+    /// Recompute corroboration for events ending in the last `days_back` days.
+    /// Returns the number of events updated.
+    pub fn update(temporal_store: &TemporalStore, days_back: i64) -> Result<usize> {
+        let since = chrono::Utc::now().timestamp() - days_back * 24 * 3600;
+        let mut by_event: BTreeMap<i64, Vec<EvidenceSource>> = BTreeMap::new();
+        for source in temporal_store.evidence_sources(since)? {
+            by_event.entry(source.event_id).or_default().push(source);
+        }
+
+        for (event_id, sources) in &by_event {
+            temporal_store.set_corroboration(*event_id, &corroboration(sources))?;
+        }
+        Ok(by_event.len())
+    }
+}
+
+/// Independent sources are distinct article domains, so several feeds of
+/// one outlet count once. Reliability tiers are high (>= 0.8), medium
+/// (>= 0.5) and low.
+pub fn corroboration(sources: &[EvidenceSource]) -> Corroboration {
+    let feeds: HashSet<i64> = sources.iter().map(|s| s.feed_id).collect();
+    let tiers: HashSet<&str> = sources.iter().map(|s| reliability_tier(s.reliability)).collect();
+
+    let mut ordered: Vec<&EvidenceSource> = sources.iter().collect();
+    ordered.sort_by_key(|s| s.published_at);
+    let mut domains: HashSet<String> = HashSet::new();
+    let mut time_to_second_source = None;
+    for source in &ordered {
+        domains.insert(source_domain(source));
+        if domains.len() == 2 && time_to_second_source.is_none() {
+            time_to_second_source = Some(source.published_at - ordered[0].published_at);
+        }
+    }
+
+    Corroboration {
+        source_count: feeds.len() as i64,
+        corroborated_by: domains.len() as i64,
+        reliability_tier_count: tiers.len() as i64,
+        time_to_second_source,
+    }
+}
+
+fn reliability_tier(reliability: f64) -> &'static str {
+    if reliability >= 0.8 {
+        "high"
+    } else if reliability >= 0.5 {
+        "medium"
+    } else {
+        "low"
+    }
+}
+
+fn source_domain(source: &EvidenceSource) -> String {
+    domain_of(&source.article_url)
+        .or_else(|| domain_of(&source.feed_url))
+        .unwrap_or_else(|| format!("feed:{}", source.feed_id))
+}
+
+/// Host of a URL without port or a leading "www.".
+pub fn domain_of(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map(|(_, r)| r).unwrap_or(url);
+    let host = rest.split(['/', '?', '#']).next()?;
+    let host = host.rsplit_once('@').map(|(_, h)| h).unwrap_or(host);
+    let host = host.split(':').next()?.trim().to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    if host.is_empty() || !host.contains('.') {
+        None
+    } else {
+        Some(host.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(feed_id: i64, url: &str, reliability: f64, published_at: i64) -> EvidenceSource {
+        EvidenceSource {
+            event_id: 1,
+            feed_id,
+            article_url: url.to_string(),
+            feed_url: String::new(),
+            reliability,
+            published_at,
+        }
+    }
+
+    #[test]
+    fn feeds_of_one_outlet_count_once() {
+        let sources = vec![
+            source(1, "https://www.reuters.com/world/a", 0.9, 100),
+            source(2, "https://reuters.com/business/b", 0.9, 200),
+            source(3, "http://apnews.com:443/article/c", 0.6, 700),
+        ];
+        let c = corroboration(&sources);
+        assert_eq!(c.source_count, 3);
+        assert_eq!(c.corroborated_by, 2);
+        assert_eq!(c.reliability_tier_count, 2);
+        assert_eq!(c.time_to_second_source, Some(600));
+    }
+
+    #[test]
+    fn single_source_has_no_second_source_time() {
+        let c = corroboration(&[source(1, "https://example.com/x", 0.3, 100)]);
+        assert_eq!(c.corroborated_by, 1);
+        assert_eq!(c.time_to_second_source, None);
+    }
+}
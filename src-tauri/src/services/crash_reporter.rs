@@ -0,0 +1,300 @@
+use crate::storage::Database;
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// "true" once the user agreed to send crash reports.
+pub const CONFIG_CONSENT: &str = "crash_reports_consent";
+/// Self-hosted endpoint reports are POSTed to as JSON.
+pub const CONFIG_ENDPOINT: &str = "crash_reports_endpoint";
+
+const REPORTS_DIR: &str = "crash-reports";
+const MAX_REPORTS: usize = 50;
+const LOG_TAIL_LINES: i32 = 20;
+const RECENT_TASK_ERRORS: usize = 20;
+const REDACTED: &str = "<redacted>";
+/// Config keys whose values may identify the user or grant access.
+const SENSITIVE_KEY_PARTS: [&str; 10] = [
+    "key", "token", "secret", "password", "url", "email", "user", "path", "host", "folder",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: String,
+    pub kind: String, // panic|task_error
+    pub message: String,
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: Option<String>,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub created_at: i64,
+    pub log_tail: Vec<String>,
+    /// Config with identifying values redacted
+    pub config: BTreeMap<String, String>,
+    pub submitted_at: Option<i64>,
+}
+
+/// Set once the database is open so reports can include config and the
+/// error log; panics before that still get a report without them.
+static DATABASE: OnceLock<Arc<Mutex<Connection>>> = OnceLock::new();
+static TASK_ERRORS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Writes crash reports for panics and failed background tasks as JSON
+/// files in the app data folder, where they survive the crash itself.
+pub struct CrashReporter;
+
+impl CrashReporter {
+    /// Install the panic hook. Call first thing so setup panics are caught.
+    pub fn install() {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Unknown panic".to_string());
+            let location = info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+            let report = build_report(
+                "panic",
+                &message,
+                location,
+                Some(std::backtrace::Backtrace::force_capture().to_string()),
+            );
+            if let Err(e) = write_report(&report) {
+                eprintln!("Failed to write crash report: {}", e);
+            }
+            previous(info);
+        }));
+    }
+
+    pub fn attach_database(conn: Arc<Mutex<Connection>>) {
+        let _ = DATABASE.set(conn);
+    }
+
+    /// Record a background task that failed without panicking.
+    pub fn capture_task_error(task: &str, error: &anyhow::Error) {
+        let message = format!("{}: {:#}", task, error);
+        if let Ok(mut recent) = TASK_ERRORS.lock() {
+            if recent.len() == RECENT_TASK_ERRORS {
+                recent.pop_front();
+            }
+            recent.push_back(format!("[{}] {}", chrono::Utc::now().to_rfc3339(), anonymize(&message)));
+        }
+        let report = build_report("task_error", &message, None, None);
+        if let Err(e) = write_report(&report) {
+            eprintln!("Failed to write crash report: {}", e);
+        }
+    }
+
+    /// Newest first.
+    pub fn list() -> Result<Vec<CrashReport>> {
+        let dir = reports_dir()?;
+        let mut reports: Vec<CrashReport> = std::fs::read_dir(&dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "json"))
+            .filter_map(|p| std::fs::read(&p).ok())
+            .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+            .collect();
+        reports.sort_by(|a: &CrashReport, b| b.created_at.cmp(&a.created_at).then_with(|| b.id.cmp(&a.id)));
+        Ok(reports)
+    }
+
+    pub fn get(id: &str) -> Result<Option<CrashReport>> {
+        let path = report_path(id)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&std::fs::read(path)?)?))
+    }
+
+    pub fn delete(id: &str) -> Result<bool> {
+        let path = report_path(id)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(path)?;
+        Ok(true)
+    }
+
+    /// Send one report to the configured endpoint. Refuses without consent.
+    pub async fn submit(db: &Database, id: &str) -> Result<CrashReport> {
+        let endpoint = Self::endpoint_with_consent(db)?
+            .ok_or_else(|| anyhow::anyhow!("Crash report submission needs consent and an endpoint"))?;
+        let mut report = Self::get(id)?.ok_or_else(|| anyhow::anyhow!("Crash report {} not found", id))?;
+
+        reqwest::Client::new()
+            .post(&endpoint)
+            .timeout(std::time::Duration::from_secs(30))
+            .json(&report)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        report.submitted_at = Some(chrono::Utc::now().timestamp());
+        write_report(&report)?;
+        Ok(report)
+    }
+
+    /// Send reports not submitted yet, once at startup, when the user has
+    /// consented.
+    pub fn submit_pending(conn: Arc<Mutex<Connection>>) {
+        tauri::async_runtime::spawn(async move {
+            let db = Database { conn };
+            if !matches!(Self::endpoint_with_consent(&db), Ok(Some(_))) {
+                return;
+            }
+            let Ok(reports) = Self::list() else { return };
+            for report in reports.into_iter().filter(|r| r.submitted_at.is_none()) {
+                if let Err(e) = Self::submit(&db, &report.id).await {
+                    eprintln!("Failed to submit crash report {}: {}", report.id, e);
+                    return;
+                }
+            }
+        });
+    }
+
+    fn endpoint_with_consent(db: &Database) -> Result<Option<String>> {
+        let consent = db.get_config(CONFIG_CONSENT)?.is_some_and(|v| v == "true");
+        let endpoint = db.get_config(CONFIG_ENDPOINT)?.filter(|e| !e.trim().is_empty());
+        Ok(endpoint.filter(|_| consent))
+    }
+}
+
+fn build_report(kind: &str, message: &str, location: Option<String>, backtrace: Option<String>) -> CrashReport {
+    let now = chrono::Utc::now();
+    let (log_tail, config) = database_snapshot();
+    CrashReport {
+        id: format!("{}-{}", now.format("%Y%m%dT%H%M%S"), &uuid::Uuid::new_v4().simple().to_string()[..8]),
+        kind: kind.to_string(),
+        message: anonymize(message),
+        location,
+        thread: std::thread::current().name().map(|n| n.to_string()),
+        backtrace: backtrace.map(|b| anonymize(&b)),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        created_at: now.timestamp(),
+        log_tail,
+        config,
+        submitted_at: None,
+    }
+}
+
+/// Recent error log lines and redacted config. Uses try_lock: the panicking
+/// thread may be the one holding the connection.
+fn database_snapshot() -> (Vec<String>, BTreeMap<String, String>) {
+    let mut log_tail = Vec::new();
+    let mut config = BTreeMap::new();
+
+    if let Some(conn) = DATABASE.get() {
+        if let Ok(conn) = conn.try_lock() {
+            if let Ok(mut stmt) = conn.prepare(
+                "SELECT created_at, severity, error_type, message FROM errors ORDER BY created_at DESC LIMIT ?1",
+            ) {
+                if let Ok(rows) = stmt.query_map([LOG_TAIL_LINES], |row| {
+                    Ok(format!(
+                        "[{}] {} {}: {}",
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?
+                    ))
+                }) {
+                    log_tail.extend(rows.filter_map(|r| r.ok()).map(|line| anonymize(&line)));
+                    log_tail.reverse();
+                }
+            }
+            if let Ok(mut stmt) = conn.prepare("SELECT key, value FROM config ORDER BY key") {
+                if let Ok(rows) = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))) {
+                    for (key, value) in rows.filter_map(|r| r.ok()) {
+                        let value = if is_sensitive_key(&key) { REDACTED.to_string() } else { anonymize(&value) };
+                        config.insert(key, value);
+                    }
+                }
+            }
+        }
+    }
+    if let Ok(recent) = TASK_ERRORS.lock() {
+        log_tail.extend(recent.iter().cloned());
+    }
+    (log_tail, config)
+}
+
+fn reports_dir() -> Result<PathBuf> {
+    let dir = Database::data_dir()?.join(REPORTS_DIR);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn report_path(id: &str) -> Result<PathBuf> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        anyhow::bail!("Invalid crash report id: {}", id);
+    }
+    Ok(reports_dir()?.join(format!("{}.json", id)))
+}
+
+fn write_report(report: &CrashReport) -> Result<()> {
+    let path = report_path(&report.id)?;
+    std::fs::write(&path, serde_json::to_vec_pretty(report)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    // Keep the newest reports only
+    let dir = reports_dir()?;
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "json"))
+        .collect();
+    if files.len() > MAX_REPORTS {
+        // Ids start with the timestamp, so names sort by age
+        files.sort();
+        for old in &files[..files.len() - MAX_REPORTS] {
+            let _ = std::fs::remove_file(old);
+        }
+    }
+    Ok(())
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SENSITIVE_KEY_PARTS.iter().any(|part| key.contains(part))
+}
+
+/// Replace the home directory so paths don't carry the user name.
+fn anonymize(text: &str) -> String {
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).unwrap_or_default();
+    if home.len() > 1 {
+        text.replace(&home, "~")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sensitive_keys_are_detected() {
+        assert!(is_sensitive_key("update_public_key"));
+        assert!(is_sensitive_key("backup_webdav_url"));
+        assert!(is_sensitive_key("whisper_model_path"));
+        assert!(!is_sensitive_key("startup_profile"));
+        assert!(!is_sensitive_key("command_trace_slow_ms"));
+    }
+
+    #[test]
+    fn report_ids_are_validated() {
+        assert!(report_path("../../etc/passwd").is_err());
+        assert!(report_path("").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use crate::storage::dashboards::{DashboardStore, DashboardWidget};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+pub const BUNDLE_FORMAT: &str = "mina-dashboard-layouts";
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Shareable collection of dashboard layouts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardBundle {
+    pub format: String,
+    pub format_version: u32,
+    #[serde(default)]
+    pub exported_at: Option<i64>,
+    pub layouts: Vec<BundledLayout>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledLayout {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub widgets: Vec<DashboardWidget>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DashboardImportReport {
+    pub imported: Vec<String>,
+    pub updated: Vec<String>,
+    pub skipped: Vec<String>,
+    pub renamed: Vec<(String, String)>,
+}
+
+pub struct DashboardLayoutService;
+
+impl DashboardLayoutService {
+    /// Without ids, all layouts are exported.
+    pub fn export(store: &DashboardStore, ids: Option<&[i64]>) -> Result<DashboardBundle> {
+        let layouts = match ids {
+            Some(ids) => {
+                let mut layouts = Vec::new();
+                for id in ids {
+                    if let Some(layout) = store.get_layout(*id)? {
+                        layouts.push(layout);
+                    }
+                }
+                layouts
+            }
+            None => store.list_layouts()?,
+        };
+
+        Ok(DashboardBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            format_version: BUNDLE_FORMAT_VERSION,
+            exported_at: Some(chrono::Utc::now().timestamp()),
+            layouts: layouts
+                .into_iter()
+                .map(|l| BundledLayout { name: l.name, description: l.description, widgets: l.widgets })
+                .collect(),
+        })
+    }
+
+    pub fn parse(content: &str) -> Result<DashboardBundle> {
+        let bundle: DashboardBundle = serde_json::from_str(content)
+            .context("Invalid dashboard layout bundle")?;
+        if bundle.format != BUNDLE_FORMAT {
+            anyhow::bail!("Unsupported bundle format: {}", bundle.format);
+        }
+        if bundle.format_version > BUNDLE_FORMAT_VERSION {
+            anyhow::bail!(
+                "Bundle format version {} is newer than supported version {}",
+                bundle.format_version,
+                BUNDLE_FORMAT_VERSION
+            );
+        }
+        Ok(bundle)
+    }
+
+    /// Layouts named like an existing one replace it with `overwrite`, and
+    /// are otherwise imported under a new name, e.g. "Trading (2)".
+    pub fn import(store: &DashboardStore, bundle: &DashboardBundle, overwrite: bool) -> Result<DashboardImportReport> {
+        let mut report = DashboardImportReport::default();
+
+        for incoming in &bundle.layouts {
+            let name = incoming.name.trim();
+            if name.is_empty() {
+                report.skipped.push(incoming.name.clone());
+                continue;
+            }
+            let description = incoming.description.as_deref();
+
+            match store.get_layout_by_name(name)? {
+                None => {
+                    store.create_layout(name, description, &incoming.widgets)?;
+                    report.imported.push(name.to_string());
+                }
+                Some(existing) if overwrite => {
+                    store.update_layout(existing.id, None, description, Some(&incoming.widgets))?;
+                    report.updated.push(name.to_string());
+                }
+                Some(_) => {
+                    let renamed = Self::free_name(store, name)?;
+                    store.create_layout(&renamed, description, &incoming.widgets)?;
+                    report.renamed.push((name.to_string(), renamed));
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn free_name(store: &DashboardStore, name: &str) -> Result<String> {
+        for n in 2.. {
+            let candidate = format!("{} ({})", name, n);
+            if store.get_layout_by_name(&candidate)?.is_none() {
+                return Ok(candidate);
+            }
+        }
+        unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_db;
+
+    fn widget(kind: &str) -> DashboardWidget {
+        DashboardWidget {
+            id: kind.to_string(),
+            kind: kind.to_string(),
+            title: None,
+            x: 0,
+            y: 0,
+            w: 4,
+            h: 3,
+            config: serde_json::json!({ "limit": 10 }),
+        }
+    }
+
+    #[test]
+    fn exported_layouts_import_under_new_names_unless_overwritten() {
+        let db = test_db();
+        let store = DashboardStore::new(db.conn.clone());
+        let id = store.create_layout("Monitoring", None, &[widget("alerts_by_rule"), widget("system_metrics")]).unwrap();
+
+        let json = serde_json::to_string(&DashboardLayoutService::export(&store, None).unwrap()).unwrap();
+        let bundle = DashboardLayoutService::parse(&json).unwrap();
+
+        let report = DashboardLayoutService::import(&store, &bundle, false).unwrap();
+        assert_eq!(report.renamed, vec![("Monitoring".to_string(), "Monitoring (2)".to_string())]);
+        let copy = store.get_layout_by_name("Monitoring (2)").unwrap().unwrap();
+        assert_eq!(copy.widgets, store.get_layout(id).unwrap().unwrap().widgets);
+
+        store.update_layout(id, None, None, Some(&[widget("watchlist_movers")])).unwrap();
+        let report = DashboardLayoutService::import(&store, &bundle, true).unwrap();
+        assert_eq!(report.updated, vec!["Monitoring".to_string()]);
+        assert_eq!(store.get_layout(id).unwrap().unwrap().widgets.len(), 2);
+    }
+}
@@ -0,0 +1,241 @@
+use crate::storage::osint::{FeedQualityCounts, OSINTStore, RSSFeed};
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+pub const CONFIG_WINDOW_DAYS: &str = "data_quality_window_days";
+/// Enabled feeds without a new item for this many days are stale.
+pub const CONFIG_STALE_DAYS: &str = "data_quality_stale_days";
+pub const CONFIG_MAX_FAILURE_RATE: &str = "data_quality_max_failure_rate";
+pub const CONFIG_MAX_EMPTY_RATIO: &str = "data_quality_max_empty_ratio";
+pub const CONFIG_MAX_DUPLICATE_RATE: &str = "data_quality_max_duplicate_rate";
+/// "true" to notify when a feed's issues change after a fetch.
+pub const CONFIG_ALERTS_ENABLED: &str = "data_quality_alerts_enabled";
+
+const DAY: i64 = 86400;
+/// Fewer fetches or items than this say too little to flag a rate.
+const MIN_FETCHES: i64 = 3;
+const MIN_ITEMS: i64 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataQualitySettings {
+    pub window_days: i64,
+    pub stale_days: i64,
+    pub max_failure_rate: f64,
+    pub max_empty_ratio: f64,
+    pub max_duplicate_rate: f64,
+    pub alerts_enabled: bool,
+}
+
+impl Default for DataQualitySettings {
+    fn default() -> Self {
+        DataQualitySettings {
+            window_days: 7,
+            stale_days: 7,
+            max_failure_rate: 0.5,
+            max_empty_ratio: 0.5,
+            max_duplicate_rate: 0.5,
+            alerts_enabled: false,
+        }
+    }
+}
+
+impl DataQualitySettings {
+    pub fn load(db: &Database) -> Self {
+        let defaults = Self::default();
+        let get = |key: &str| db.get_config(key).ok().flatten();
+        DataQualitySettings {
+            window_days: get(CONFIG_WINDOW_DAYS).and_then(|v| v.parse().ok()).unwrap_or(defaults.window_days).max(1),
+            stale_days: get(CONFIG_STALE_DAYS).and_then(|v| v.parse().ok()).unwrap_or(defaults.stale_days).max(1),
+            max_failure_rate: get(CONFIG_MAX_FAILURE_RATE).and_then(|v| v.parse().ok()).unwrap_or(defaults.max_failure_rate),
+            max_empty_ratio: get(CONFIG_MAX_EMPTY_RATIO).and_then(|v| v.parse().ok()).unwrap_or(defaults.max_empty_ratio),
+            max_duplicate_rate: get(CONFIG_MAX_DUPLICATE_RATE).and_then(|v| v.parse().ok()).unwrap_or(defaults.max_duplicate_rate),
+            alerts_enabled: get(CONFIG_ALERTS_ENABLED).map(|v| v == "true").unwrap_or(defaults.alerts_enabled),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedQuality {
+    pub feed_id: i64,
+    pub name: String,
+    pub url: String,
+    pub enabled: bool,
+    pub fetches: i64,
+    pub fetch_failure_rate: Option<f64>,
+    pub parse_failure_rate: Option<f64>,
+    pub last_error: Option<String>,
+    pub items: i64,
+    pub empty_ratio: Option<f64>,
+    pub duplicate_rate: Option<f64>,
+    pub last_item_at: Option<i64>,
+    /// fetch_failures|parse_failures|empty_content|duplicates|stale
+    pub issues: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataQualityReport {
+    pub settings: DataQualitySettings,
+    pub generated_at: i64,
+    pub unhealthy_feeds: usize,
+    /// Feeds with issues first
+    pub feeds: Vec<FeedQuality>,
+}
+
+/// Watches the RSS pipeline for sources that fail, go quiet or deliver
+/// junk, so they're noticed before they starve event detection.
+pub struct DataQualityMonitor;
+
+impl DataQualityMonitor {
+    pub fn report(store: &OSINTStore, settings: &DataQualitySettings) -> Result<DataQualityReport> {
+        let now = chrono::Utc::now().timestamp();
+        let mut counts: HashMap<i64, FeedQualityCounts> = store
+            .feed_quality_counts(now - settings.window_days * DAY)?
+            .into_iter()
+            .map(|c| (c.feed_id, c))
+            .collect();
+
+        let mut feeds: Vec<FeedQuality> = store
+            .list_feeds()?
+            .into_iter()
+            .map(|feed| {
+                let counts = counts.remove(&feed.id).unwrap_or_default();
+                assess(&feed, &counts, settings, now)
+            })
+            .collect();
+        feeds.sort_by(|a, b| b.issues.len().cmp(&a.issues.len()).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(DataQualityReport {
+            settings: settings.clone(),
+            generated_at: now,
+            unhealthy_feeds: feeds.iter().filter(|f| !f.issues.is_empty()).count(),
+            feeds,
+        })
+    }
+
+    /// Feeds whose issues changed since they were last flagged, remembering
+    /// the new issues. Feeds that recovered are cleared silently.
+    pub fn flag_changes(store: &OSINTStore, settings: &DataQualitySettings) -> Result<Vec<FeedQuality>> {
+        let report = Self::report(store, settings)?;
+        let mut changed = Vec::new();
+        for feed in report.feeds {
+            let issues = (!feed.issues.is_empty()).then(|| feed.issues.join(","));
+            if store.feed_quality_flag(feed.feed_id)? == issues {
+                continue;
+            }
+            store.set_feed_quality_flag(feed.feed_id, issues.as_deref())?;
+            if issues.is_some() {
+                changed.push(feed);
+            }
+        }
+        Ok(changed)
+    }
+
+    pub async fn notify(app: &AppHandle, feeds: &[FeedQuality]) {
+        use crate::services::desktop_notifications::{DesktopNotificationService, NotificationOptions};
+
+        for feed in feeds {
+            let payload = serde_json::to_value(feed).unwrap_or_default();
+            let _ = crate::services::window_router::emit(app, serde_json::json!({
+                "type": "feed-quality-issue",
+                "data": payload,
+                "timestamp": chrono::Utc::now().timestamp_millis(),
+            }));
+            let _ = DesktopNotificationService::send(app, NotificationOptions {
+                title: format!("Feed needs attention: {}", feed.name),
+                body: format!("Issues: {}", feed.issues.join(", ").replace('_', " ")),
+                icon: Some("alert".to_string()),
+                sound: None,
+                tag: Some(format!("feed-quality-{}", feed.feed_id)),
+                data: Some(payload),
+            }).await;
+        }
+    }
+}
+
+fn assess(feed: &RSSFeed, counts: &FeedQualityCounts, settings: &DataQualitySettings, now: i64) -> FeedQuality {
+    let rate = |part: i64, whole: i64, min: i64| (whole >= min).then(|| part as f64 / whole as f64);
+    let fetch_failure_rate = rate(counts.fetch_failures, counts.fetches, MIN_FETCHES);
+    let parse_failure_rate = rate(counts.parse_failures, counts.fetches, MIN_FETCHES);
+    let empty_ratio = rate(counts.empty_items, counts.items, MIN_ITEMS);
+    let duplicate_rate = rate(counts.duplicate_items, counts.items, MIN_ITEMS);
+
+    let mut issues = Vec::new();
+    if feed.enabled {
+        let exceeds = |value: Option<f64>, max: f64| value.map(|v| v >= max).unwrap_or(false);
+        if exceeds(fetch_failure_rate, settings.max_failure_rate) {
+            issues.push("fetch_failures".to_string());
+        }
+        if exceeds(parse_failure_rate, settings.max_failure_rate) {
+            issues.push("parse_failures".to_string());
+        }
+        if exceeds(empty_ratio, settings.max_empty_ratio) {
+            issues.push("empty_content".to_string());
+        }
+        if exceeds(duplicate_rate, settings.max_duplicate_rate) {
+            issues.push("duplicates".to_string());
+        }
+        // A feed added recently hasn't had the chance to deliver yet
+        let quiet_since = counts.last_item_at.unwrap_or(feed.created_at);
+        if now - quiet_since >= settings.stale_days * DAY {
+            issues.push("stale".to_string());
+        }
+    }
+
+    FeedQuality {
+        feed_id: feed.id,
+        name: feed.name.clone(),
+        url: feed.url.clone(),
+        enabled: feed.enabled,
+        fetches: counts.fetches,
+        fetch_failure_rate,
+        parse_failure_rate,
+        last_error: counts.last_error.clone(),
+        items: counts.items,
+        empty_ratio,
+        duplicate_rate,
+        last_item_at: counts.last_item_at,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{article, feed, test_db};
+
+    fn issues(report: &DataQualityReport, name: &str) -> Vec<String> {
+        report.feeds.iter().find(|f| f.name == name).map(|f| f.issues.clone()).unwrap()
+    }
+
+    #[test]
+    fn failing_empty_and_quiet_feeds_are_flagged() {
+        let db = test_db();
+        let store = OSINTStore::new(db.conn.clone());
+        let broken = feed(&db, "Broken");
+        for _ in 0..3 {
+            store.record_feed_fetch(broken, "parse_error", 0, Some("unexpected end of file")).unwrap();
+        }
+        for i in 0..5 {
+            article(&db, "Thin", &format!("Story {}", i), "");
+        }
+        article(&db, "Healthy", "Central bank holds rates", "<p>Full text</p>");
+        let quiet = article(&db, "Quiet", "Last post", "<p>Goodbye</p>");
+        db.conn.lock().unwrap()
+            .execute("UPDATE rss_items SET fetched_at = fetched_at - 30 * 86400 WHERE id = ?1", [quiet])
+            .unwrap();
+
+        let settings = DataQualitySettings::default();
+        let report = DataQualityMonitor::report(&store, &settings).unwrap();
+        assert_eq!(issues(&report, "Broken"), vec!["parse_failures"]);
+        assert_eq!(issues(&report, "Thin"), vec!["empty_content"]);
+        assert_eq!(issues(&report, "Quiet"), vec!["stale"]);
+        assert!(issues(&report, "Healthy").is_empty());
+
+        // Only changes are reported, so a second check stays quiet
+        assert_eq!(DataQualityMonitor::flag_changes(&store, &settings).unwrap().len(), 3);
+        assert!(DataQualityMonitor::flag_changes(&store, &settings).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,376 @@
+use crate::storage::db_housekeeping::{DbHousekeepingStore, DbMeasurement, ObjectSize};
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+
+/// "false" turns the weekly report off.
+pub const CONFIG_ENABLED: &str = "db_housekeeping_enabled";
+/// Database size in MB the report warns about, and counts down to.
+pub const CONFIG_WARN_MB: &str = "db_housekeeping_warn_mb";
+const CONFIG_LAST_RUN: &str = "db_housekeeping_last_run_at";
+
+const DAY: i64 = 86400;
+const MB: f64 = 1_048_576.0;
+const RUN_INTERVAL_SECS: i64 = 7 * DAY;
+const CHECK_INTERVAL_SECS: u64 = 3600;
+/// About a year of weekly reports
+const KEEP_REPORTS: i64 = 52;
+/// Growth is measured against the oldest report in this window
+const GROWTH_WINDOW_DAYS: i64 = 28;
+const TOP_N: usize = 10;
+/// Indexes and free lists smaller than this aren't worth a rebuild
+const MIN_RECLAIM_BYTES: i64 = 1 << 20;
+const BLOAT_RATIO: f64 = 0.3;
+/// Warn about the size limit when it's this close at the current rate
+const WARN_HORIZON_DAYS: i64 = 90;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HousekeepingSettings {
+    pub enabled: bool,
+    pub warn_mb: i64,
+}
+
+impl Default for HousekeepingSettings {
+    fn default() -> Self {
+        HousekeepingSettings { enabled: true, warn_mb: 1024 }
+    }
+}
+
+impl HousekeepingSettings {
+    pub fn load(db: &Database) -> Self {
+        let defaults = Self::default();
+        let get = |key: &str| db.get_config(key).ok().flatten();
+        HousekeepingSettings {
+            enabled: get(CONFIG_ENABLED).map(|v| v != "false").unwrap_or(defaults.enabled),
+            warn_mb: get(CONFIG_WARN_MB).and_then(|v| v.parse().ok()).unwrap_or(defaults.warn_mb).max(1),
+        }
+    }
+
+    pub fn save(&self, db: &Database) -> Result<()> {
+        if self.warn_mb < 1 {
+            anyhow::bail!("Warning size must be at least 1 MB");
+        }
+        db.set_config(CONFIG_ENABLED, if self.enabled { "true" } else { "false" })?;
+        db.set_config(CONFIG_WARN_MB, &self.warn_mb.to_string())?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexBloat {
+    pub name: String,
+    pub table_name: String,
+    pub bytes: i64,
+    pub unused_bytes: i64,
+    pub unused_ratio: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableGrowth {
+    pub table: String,
+    pub rows: Option<i64>,
+    pub rows_per_day: f64,
+    pub bytes_per_day: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HousekeepingReport {
+    pub created_at: i64,
+    pub total_bytes: i64,
+    pub freelist_bytes: i64,
+    pub warn_bytes: i64,
+    /// Every table and index
+    pub objects: Vec<ObjectSize>,
+    /// Tables with their indexes, biggest first (by rows without dbstat)
+    pub largest: Vec<ObjectSize>,
+    pub bloated_indexes: Vec<IndexBloat>,
+    /// When the report growth is measured against was taken
+    pub baseline_at: Option<i64>,
+    /// Fastest growing tables first
+    pub growth: Vec<TableGrowth>,
+    pub bytes_per_day: Option<f64>,
+    pub projected_bytes_30d: Option<i64>,
+    pub projected_bytes_90d: Option<i64>,
+    /// Days until `warn_bytes` at the current rate; 0 once past it
+    pub days_until_warn: Option<i64>,
+    pub recommendations: Vec<String>,
+}
+
+fn mb(bytes: i64) -> String {
+    format!("{:.1} MB", bytes as f64 / MB)
+}
+
+/// Weekly report on what the database holds and how fast it grows, so
+/// retention can be tuned before it gets large.
+pub struct DbHousekeeping;
+
+impl DbHousekeeping {
+    /// Measure, compare with the growth baseline, and keep the report.
+    pub fn run(db: &Database) -> Result<HousekeepingReport> {
+        let settings = HousekeepingSettings::load(db);
+        let store = DbHousekeepingStore::new(db.conn.clone());
+        let now = chrono::Utc::now().timestamp();
+
+        let measurement = store.measure()?;
+        let baseline = store
+            .earliest_report_since(now - GROWTH_WINDOW_DAYS * DAY)?
+            .and_then(|r| serde_json::from_value::<HousekeepingReport>(r.report).ok());
+        let report = build_report(measurement, baseline.as_ref(), settings.warn_mb * 1_048_576, now);
+
+        store.save_report(now, report.total_bytes, &serde_json::to_value(&report)?)?;
+        store.prune_reports(KEEP_REPORTS)?;
+        db.set_config(CONFIG_LAST_RUN, &now.to_string())?;
+        Ok(report)
+    }
+
+    pub fn latest(db: &Database) -> Result<Option<HousekeepingReport>> {
+        Ok(DbHousekeepingStore::new(db.conn.clone())
+            .latest_report()?
+            .and_then(|r| serde_json::from_value(r.report).ok()))
+    }
+
+    pub async fn notify(app: &AppHandle, report: &HousekeepingReport) {
+        use crate::services::desktop_notifications::{DesktopNotificationService, NotificationOptions};
+
+        let payload = serde_json::to_value(report).unwrap_or_default();
+        let _ = crate::services::window_router::emit(app, serde_json::json!({
+            "type": "db-housekeeping-report",
+            "data": payload,
+            "timestamp": chrono::Utc::now().timestamp_millis(),
+        }));
+
+        let mut body = match report.bytes_per_day {
+            Some(rate) => format!("{}, growing {} per week.", mb(report.total_bytes), mb((rate * 7.0) as i64)),
+            None => format!("{}.", mb(report.total_bytes)),
+        };
+        if let Some(first) = report.recommendations.first() {
+            body.push(' ');
+            body.push_str(first);
+        }
+        let _ = DesktopNotificationService::send(app, NotificationOptions {
+            title: "Weekly database report".to_string(),
+            body,
+            icon: Some(if report.recommendations.is_empty() { "info" } else { "alert" }.to_string()),
+            sound: None,
+            tag: Some("db-housekeeping".to_string()),
+            data: Some(serde_json::json!({ "type": "db-housekeeping" })),
+        }).await;
+    }
+
+    pub fn start_scheduler(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+            let heartbeat = crate::services::watchdog::Watchdog::register("db_housekeeping", tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+
+            loop {
+                interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
+
+                let result = match db.lock() {
+                    Ok(db_guard) => {
+                        let last_run: i64 = db_guard.get_config(CONFIG_LAST_RUN).ok().flatten()
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or(0);
+                        let due = HousekeepingSettings::load(&db_guard).enabled
+                            && chrono::Utc::now().timestamp() - last_run >= RUN_INTERVAL_SECS;
+                        if !due {
+                            continue;
+                        }
+                        Self::run(&db_guard)
+                    }
+                    Err(_) => continue,
+                };
+
+                match result {
+                    Ok(report) => Self::notify(&app, &report).await,
+                    Err(e) => eprintln!("Database housekeeping report failed: {}", e),
+                }
+            }
+        });
+    }
+}
+
+fn build_report(
+    measurement: DbMeasurement,
+    baseline: Option<&HousekeepingReport>,
+    warn_bytes: i64,
+    now: i64,
+) -> HousekeepingReport {
+    let DbMeasurement { total_bytes, freelist_bytes, objects } = measurement;
+
+    // Tables with their indexes' bytes folded in
+    let mut largest: Vec<ObjectSize> = objects.iter().filter(|o| o.kind == "table").cloned().collect();
+    for table in &mut largest {
+        let index_bytes: Option<i64> = objects
+            .iter()
+            .filter(|o| o.kind == "index" && o.table_name == table.name)
+            .map(|o| o.bytes)
+            .sum();
+        table.bytes = table.bytes.zip(index_bytes).map(|(t, i)| t + i).or(table.bytes);
+    }
+    largest.sort_by_key(|t| std::cmp::Reverse((t.bytes.unwrap_or(0), t.rows.unwrap_or(0))));
+    largest.truncate(TOP_N);
+
+    let mut bloated_indexes: Vec<IndexBloat> = objects
+        .iter()
+        .filter(|o| o.kind == "index")
+        .filter_map(|o| {
+            let (bytes, unused_bytes) = (o.bytes?, o.unused_bytes?);
+            let unused_ratio = unused_bytes as f64 / bytes.max(1) as f64;
+            (bytes >= MIN_RECLAIM_BYTES && unused_ratio >= BLOAT_RATIO).then(|| IndexBloat {
+                name: o.name.clone(),
+                table_name: o.table_name.clone(),
+                bytes,
+                unused_bytes,
+                unused_ratio,
+            })
+        })
+        .collect();
+    bloated_indexes.sort_by_key(|i| std::cmp::Reverse(i.unused_bytes));
+
+    // Growth needs at least a day between measurements to mean anything
+    let baseline = baseline.filter(|b| now - b.created_at >= DAY);
+    let mut growth = Vec::new();
+    let mut bytes_per_day = None;
+    if let Some(base) = baseline {
+        let days = (now - base.created_at) as f64 / DAY as f64;
+        bytes_per_day = Some((total_bytes - base.total_bytes) as f64 / days);
+        let before: HashMap<&str, &ObjectSize> = base.objects.iter().map(|o| (o.name.as_str(), o)).collect();
+        for table in objects.iter().filter(|o| o.kind == "table") {
+            let Some(old) = before.get(table.name.as_str()) else { continue };
+            let rows_per_day = (table.rows.unwrap_or(0) - old.rows.unwrap_or(0)) as f64 / days;
+            let table_bytes_per_day = table.bytes.zip(old.bytes).map(|(new, old)| (new - old) as f64 / days);
+            if rows_per_day > 0.0 || table_bytes_per_day.is_some_and(|b| b > 0.0) {
+                growth.push(TableGrowth {
+                    table: table.name.clone(),
+                    rows: table.rows,
+                    rows_per_day,
+                    bytes_per_day: table_bytes_per_day,
+                });
+            }
+        }
+        growth.sort_by(|a, b| {
+            b.bytes_per_day
+                .unwrap_or(0.0)
+                .total_cmp(&a.bytes_per_day.unwrap_or(0.0))
+                .then(b.rows_per_day.total_cmp(&a.rows_per_day))
+        });
+        growth.truncate(TOP_N);
+    }
+
+    let project = |days: i64| bytes_per_day.map(|rate| total_bytes + (rate.max(0.0) * days as f64) as i64);
+    let days_until_warn = if total_bytes >= warn_bytes {
+        Some(0)
+    } else {
+        bytes_per_day
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| ((warn_bytes - total_bytes) as f64 / rate).ceil() as i64)
+    };
+
+    let mut recommendations = Vec::new();
+    match days_until_warn {
+        Some(0) => recommendations.push(format!(
+            "The database is past the {} warning size; tighten retention on the largest tables.",
+            mb(warn_bytes)
+        )),
+        Some(days) if days <= WARN_HORIZON_DAYS => recommendations.push(format!(
+            "At the current rate the database reaches {} in about {} days.",
+            mb(warn_bytes),
+            days
+        )),
+        _ => {}
+    }
+    if let Some(fastest) = growth.first() {
+        if days_until_warn.is_some_and(|d| d <= WARN_HORIZON_DAYS) {
+            recommendations.push(format!("{} grows fastest ({} rows/day).", fastest.table, fastest.rows_per_day.round()));
+        }
+    }
+    if freelist_bytes >= MIN_RECLAIM_BYTES && freelist_bytes * 10 >= total_bytes {
+        recommendations.push(format!("{} is free pages left by deletes; VACUUM would return it to disk.", mb(freelist_bytes)));
+    }
+    if !bloated_indexes.is_empty() {
+        let reclaimable: i64 = bloated_indexes.iter().map(|i| i.unused_bytes).sum();
+        recommendations.push(format!(
+            "{} indexes are mostly empty pages ({} unused); REINDEX would compact them.",
+            bloated_indexes.len(),
+            mb(reclaimable)
+        ));
+    }
+
+    HousekeepingReport {
+        created_at: now,
+        total_bytes,
+        freelist_bytes,
+        warn_bytes,
+        objects,
+        largest,
+        bloated_indexes,
+        baseline_at: baseline.map(|b| b.created_at),
+        growth,
+        bytes_per_day,
+        projected_bytes_30d: project(30),
+        projected_bytes_90d: project(90),
+        days_until_warn,
+        recommendations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(name: &str, kind: &str, table_name: &str, rows: Option<i64>, bytes: i64, unused: i64) -> ObjectSize {
+        ObjectSize {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            table_name: table_name.to_string(),
+            rows,
+            bytes: Some(bytes),
+            unused_bytes: Some(unused),
+        }
+    }
+
+    #[test]
+    fn projects_growth_and_flags_bloat() {
+        let mib = 1_048_576;
+        let first = build_report(
+            DbMeasurement {
+                total_bytes: 100 * mib,
+                freelist_bytes: 0,
+                objects: vec![object("articles", "table", "articles", Some(1000), 80 * mib, 0)],
+            },
+            None,
+            1024 * mib,
+            0,
+        );
+        assert_eq!((first.bytes_per_day, first.days_until_warn), (None, None));
+
+        let report = build_report(
+            DbMeasurement {
+                total_bytes: 170 * mib,
+                freelist_bytes: 20 * mib,
+                objects: vec![
+                    object("articles", "table", "articles", Some(8000), 140 * mib, 0),
+                    object("idx_articles_ts", "index", "articles", None, 4 * mib, 2 * mib),
+                    object("config", "table", "config", Some(10), 4096, 0),
+                ],
+            },
+            Some(&first),
+            1024 * mib,
+            7 * DAY,
+        );
+        assert_eq!(report.bytes_per_day, Some(10.0 * mib as f64));
+        assert_eq!(report.projected_bytes_30d, Some(470 * mib));
+        assert_eq!(report.days_until_warn, Some(86));
+        assert_eq!(report.largest[0].bytes, Some(144 * mib));
+        assert_eq!(report.growth.len(), 1);
+        assert_eq!(report.growth[0].rows_per_day, 1000.0);
+        assert_eq!(report.bloated_indexes[0].name, "idx_articles_ts");
+        assert_eq!(report.recommendations.len(), 4);
+    }
+}
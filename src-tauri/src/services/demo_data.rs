@@ -0,0 +1,346 @@
+use crate::services::job_manager::JobContext;
+use crate::storage::temporal::NewTemporalEvent;
+use crate::storage::{MarketDataStore, MarketPrice, OSINTStore, PortfolioStore, PriceHistory, TemporalStore};
+use anyhow::Result;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+pub const MAX_SCALE: u32 = 20;
+const DAY: i64 = 86_400;
+const HISTORY_DAYS: i64 = 90;
+const NEWS_DAYS: i64 = 14;
+/// Demo feeds live under the reserved .invalid TLD and are created disabled,
+/// so the feed fetcher never contacts them.
+const FEED_DOMAIN: &str = "demo.invalid";
+const PORTFOLIO_PREFIX: &str = "Demo Portfolio";
+
+/// (ticker, company, sector) of the fictional companies the demo covers.
+const COMPANIES: &[(&str, &str, &str)] = &[
+    ("ACMR", "Acme Robotics", "Technology"),
+    ("GLBX", "Globex Energy", "Energy"),
+    ("INTK", "Initech Software", "Technology"),
+    ("UMBR", "Umbrella Pharma", "Healthcare"),
+    ("SOYL", "Soylent Foods", "Consumer Staples"),
+    ("STRK", "Stark Industries", "Industrials"),
+    ("WAYN", "Wayne Enterprises", "Industrials"),
+    ("CYBD", "Cyberdyne Systems", "Technology"),
+    ("TYRL", "Tyrell Bio", "Healthcare"),
+    ("OSCP", "Oscorp Materials", "Materials"),
+    ("MSSV", "Massive Dynamic", "Technology"),
+    ("HOOL", "Hooli Media", "Communication Services"),
+];
+
+const FEEDS: &[(&str, &str)] = &[
+    ("wire", "Demo Wire"),
+    ("markets", "Demo Markets Daily"),
+    ("tech", "Demo Tech Journal"),
+    ("energy", "Demo Energy Report"),
+    ("health", "Demo Health Brief"),
+    ("regulators", "Demo Regulatory Watch"),
+];
+
+/// (headline, event type, sentiment sign); `{a}` and `{b}` are companies.
+const STORIES: &[(&str, &str, f64)] = &[
+    ("{a} beats earnings expectations on strong growth", "earnings", 1.0),
+    ("{a} misses quarterly estimates as costs rise", "earnings", -1.0),
+    ("{a} agrees to acquire {b}", "merger", 1.0),
+    ("{a} recalls products after safety concern", "regulatory", -1.0),
+    ("Regulators sue {a} over disclosures", "regulatory", -1.0),
+    ("{a} launches new product line", "product", 1.0),
+    ("{a} announces layoffs amid restructuring", "labor", -1.0),
+    ("{a} raises full-year guidance", "earnings", 1.0),
+    ("{a} and {b} sign supply partnership", "partnership", 1.0),
+    ("{a} warns of supply chain disruption", "operations", -1.0),
+];
+
+const ANGLES: &[&str] = &["", "Report: ", "Update: ", "Analysts react as ", "Live: "];
+
+const BACKGROUND: &[&str] = &[
+    "Markets drift ahead of central bank meeting",
+    "Bond yields edge higher in quiet trading",
+    "Commodity prices mixed as dollar steadies",
+    "Investors weigh outlook for consumer spending",
+    "Volatility eases after a busy week of earnings",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemoSummary {
+    pub seed: u64,
+    pub scale: u32,
+    pub anchor_ts: i64,
+    pub feeds: usize,
+    pub articles: usize,
+    pub events: usize,
+    pub price_points: usize,
+    pub portfolios: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemoCleanup {
+    pub feeds: usize,
+    pub articles: usize,
+    pub events: usize,
+    pub price_points: usize,
+    pub portfolios: usize,
+}
+
+/// Deterministic synthetic data for demos, load profiling and bug reports.
+/// The same seed, scale and anchor always produce the same rows; running it
+/// twice updates them in place rather than duplicating them.
+pub struct DemoData;
+
+impl DemoData {
+    /// `anchor_ts` is the "now" the data is generated around; it defaults to
+    /// the start of the current UTC day so demo data looks recent.
+    pub fn seed(
+        conn: Arc<Mutex<Connection>>,
+        scale: u32,
+        seed: u64,
+        anchor_ts: Option<i64>,
+        ctx: Option<&JobContext>,
+    ) -> Result<DemoSummary> {
+        let scale = scale.clamp(1, MAX_SCALE);
+        let anchor = anchor_ts.unwrap_or_else(|| {
+            let now = chrono::Utc::now().timestamp();
+            now - now.rem_euclid(DAY)
+        });
+        let mut rng = StdRng::seed_from_u64(seed);
+        let osint = OSINTStore::new(conn.clone());
+        let temporal = TemporalStore::new(conn.clone());
+        let market = MarketDataStore::new(conn.clone());
+        let portfolios = PortfolioStore::new(conn.clone());
+
+        let report = |progress: f64, message: &str| {
+            if let Some(ctx) = ctx {
+                ctx.progress(progress, message);
+            }
+        };
+        let check_cancelled = || ctx.map(|c| c.check_cancelled()).unwrap_or(Ok(()));
+
+        report(0.0, "Creating feeds");
+        let mut feed_ids = Vec::new();
+        for (slug, name) in FEEDS {
+            let id = osint.create_feed(&format!("https://{}.{}/feed", slug, FEED_DOMAIN), name, Some(rng.gen_range(0.4..0.95)))?;
+            osint.update_feed(id, None, None, None, Some(false))?;
+            feed_ids.push((id, *slug));
+        }
+
+        let event_count = 20 * scale as usize;
+        let mut articles = 0usize;
+        let mut article_seq = 0usize;
+        let mut next_article = |rng: &mut StdRng, title: &str, content: &str, published_at: i64| -> Result<i64> {
+            let (feed_id, slug) = feed_ids[rng.gen_range(0..feed_ids.len())];
+            article_seq += 1;
+            let url = format!("https://{}.{}/{}/{}", slug, FEED_DOMAIN, seed, article_seq);
+            articles += 1;
+            osint.save_rss_item(feed_id, title, content, &url, published_at)
+        };
+
+        for i in 0..event_count {
+            if i % 10 == 0 {
+                check_cancelled()?;
+                report(0.05 + 0.55 * i as f64 / event_count as f64, "Generating stories");
+            }
+            let (headline, event_type, sign) = STORIES[rng.gen_range(0..STORIES.len())];
+            let a = COMPANIES[rng.gen_range(0..COMPANIES.len())];
+            let b = loop {
+                let b = COMPANIES[rng.gen_range(0..COMPANIES.len())];
+                if b.0 != a.0 {
+                    break b;
+                }
+            };
+            let headline = headline.replace("{a}", a.1).replace("{b}", b.1);
+            let start = anchor - rng.gen_range(0..NEWS_DAYS * DAY);
+            let summary = format!(
+                "{} ({}) in the {} sector. {}",
+                a.1,
+                a.0,
+                a.2.to_lowercase(),
+                if sign > 0.0 { "Investors see a positive surprise." } else { "Investors voice concern about the outlook." }
+            );
+
+            let mut evidence = Vec::new();
+            let mut last = start;
+            for _ in 0..rng.gen_range(3..=10) {
+                let published = start + rng.gen_range(0..36 * 3600);
+                last = last.max(published);
+                let title = format!("{}{}", ANGLES[rng.gen_range(0..ANGLES.len())], headline);
+                evidence.push(next_article(&mut rng, &title, &summary, published)?);
+            }
+
+            temporal.upsert_event(
+                &NewTemporalEvent {
+                    title: headline,
+                    summary,
+                    start_ts: start,
+                    end_ts: last,
+                    event_type: event_type.to_string(),
+                    confidence: round2(rng.gen_range(0.4..0.95)),
+                    severity: round2(rng.gen_range(0.2..0.95)),
+                    novelty_score: round2(rng.gen_range(0.1..0.9)),
+                    volume_score: round2(evidence.len() as f64 / 10.0),
+                    sentiment_score: round2(sign * rng.gen_range(0.2..0.8)),
+                    cluster_key: format!("demo:{}:{}", seed, i),
+                },
+                &evidence,
+            )?;
+        }
+
+        // Articles that belong to no story
+        for _ in 0..50 * scale as usize {
+            let title = BACKGROUND[rng.gen_range(0..BACKGROUND.len())];
+            let published = anchor - rng.gen_range(0..NEWS_DAYS * DAY);
+            next_article(&mut rng, title, "Background market coverage.", published)?;
+        }
+
+        check_cancelled()?;
+        report(0.65, "Generating prices");
+        let mut price_points = 0usize;
+        let mut last_close = Vec::new();
+        for (ticker, _, _) in COMPANIES {
+            let mut close: f64 = rng.gen_range(20.0..400.0);
+            let mut previous = close;
+            for day in (0..HISTORY_DAYS).rev() {
+                previous = close;
+                let open = close;
+                close = (close * (1.0 + rng.gen_range(-0.03..0.032))).max(1.0);
+                market.insert_price_history(&PriceHistory {
+                    id: 0,
+                    ticker: ticker.to_string(),
+                    timestamp: anchor - day * DAY,
+                    open: round2(open),
+                    high: round2(open.max(close) * (1.0 + rng.gen_range(0.0..0.01))),
+                    low: round2(open.min(close) * (1.0 - rng.gen_range(0.0..0.01))),
+                    close: round2(close),
+                    volume: rng.gen_range(100_000..5_000_000),
+                })?;
+                price_points += 1;
+            }
+            market.upsert_price(&MarketPrice {
+                ticker: ticker.to_string(),
+                price: round2(close),
+                change: round2(close - previous),
+                change_percent: round2((close - previous) / previous * 100.0),
+                volume: rng.gen_range(100_000..5_000_000),
+                timestamp: anchor,
+                session: None,
+            })?;
+            last_close.push(close);
+        }
+
+        check_cancelled()?;
+        report(0.9, "Generating portfolios");
+        let existing: Vec<String> = portfolios.list_portfolios()?.into_iter().map(|p| p.name).collect();
+        let portfolio_count = scale as usize;
+        for n in 1..=portfolio_count {
+            let name = format!("{} {} (seed {})", PORTFOLIO_PREFIX, n, seed);
+            let holdings = rng.gen_range(4..=8);
+            // Draw holdings even when the portfolio exists so later output
+            // doesn't depend on what was already there
+            let picks: Vec<(usize, f64, f64)> = (0..holdings)
+                .map(|_| {
+                    let idx = rng.gen_range(0..COMPANIES.len());
+                    (idx, rng.gen_range(1..200) as f64, rng.gen_range(0.7..1.2))
+                })
+                .collect();
+            if existing.contains(&name) {
+                continue;
+            }
+            let id = portfolios.create_portfolio(&name)?;
+            for (idx, quantity, cost_factor) in picks {
+                let purchase_price = round2(last_close[idx] * cost_factor);
+                portfolios.add_holding(id, COMPANIES[idx].0, quantity, purchase_price, anchor - 60 * DAY, "USD")?;
+            }
+        }
+
+        Ok(DemoSummary {
+            seed,
+            scale,
+            anchor_ts: anchor,
+            feeds: FEEDS.len(),
+            articles,
+            events: event_count,
+            price_points,
+            portfolios: portfolio_count,
+        })
+    }
+
+    /// Remove everything `seed` created, for any seed.
+    pub fn clear(conn: &Arc<Mutex<Connection>>) -> Result<DemoCleanup> {
+        let mut conn = conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        let feed_pattern = format!("https://%.{}/feed", FEED_DOMAIN);
+        let portfolio_pattern = format!("{} %", PORTFOLIO_PREFIX);
+
+        tx.execute(
+            "DELETE FROM temporal_event_evidence WHERE event_id IN
+                (SELECT id FROM temporal_events WHERE cluster_key LIKE 'demo:%')",
+            [],
+        )?;
+        let events = tx.execute("DELETE FROM temporal_events WHERE cluster_key LIKE 'demo:%'", [])?;
+        let articles = tx.execute(
+            "DELETE FROM rss_items WHERE feed_id IN (SELECT id FROM rss_feeds WHERE url LIKE ?1)",
+            params![feed_pattern],
+        )?;
+        let feeds = tx.execute("DELETE FROM rss_feeds WHERE url LIKE ?1", params![feed_pattern])?;
+        tx.execute(
+            "DELETE FROM holdings WHERE portfolio_id IN (SELECT id FROM portfolios WHERE name LIKE ?1)",
+            params![portfolio_pattern],
+        )?;
+        let portfolios = tx.execute("DELETE FROM portfolios WHERE name LIKE ?1", params![portfolio_pattern])?;
+        let mut price_points = 0;
+        for (ticker, _, _) in COMPANIES {
+            price_points += tx.execute("DELETE FROM price_history WHERE ticker = ?1", params![ticker])?;
+            tx.execute("DELETE FROM market_prices WHERE ticker = ?1", params![ticker])?;
+        }
+        tx.commit()?;
+
+        Ok(DemoCleanup { feeds, articles, events, price_points, portfolios })
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn snapshot(conn: &Arc<Mutex<Connection>>) -> Vec<String> {
+        let conn = conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT title || '|' || start_ts || '|' || severity || '|' || sentiment_score
+                 FROM temporal_events WHERE cluster_key LIKE 'demo:%' ORDER BY cluster_key",
+            )
+            .unwrap();
+        let rows = stmt.query_map([], |row| row.get(0)).unwrap();
+        rows.map(|r| r.unwrap()).collect()
+    }
+
+    #[test]
+    fn same_seed_gives_same_data_and_clear_removes_it() {
+        let first = test_support::test_db();
+        let second = test_support::test_db();
+        let anchor = Some(test_support::FIXTURE_TS);
+        let summary = DemoData::seed(first.conn.clone(), 1, 7, anchor, None).unwrap();
+        DemoData::seed(second.conn.clone(), 1, 7, anchor, None).unwrap();
+        assert_eq!(snapshot(&first.conn), snapshot(&second.conn));
+        assert_eq!(summary.events, 20);
+
+        // Re-seeding updates in place
+        DemoData::seed(first.conn.clone(), 1, 7, anchor, None).unwrap();
+        assert_eq!(snapshot(&first.conn).len(), 20);
+
+        let cleanup = DemoData::clear(&first.conn).unwrap();
+        assert_eq!(cleanup.events, 20);
+        assert_eq!(cleanup.portfolios, 1);
+        assert!(snapshot(&first.conn).is_empty());
+    }
+}
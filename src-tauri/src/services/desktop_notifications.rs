@@ -1,6 +1,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager, Emitter};
+use crate::services::timezone::TimePreferences;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationOptions {
@@ -15,11 +16,22 @@ pub struct NotificationOptions {
 pub struct DesktopNotificationService;
 
 impl DesktopNotificationService {
-    /// Send a desktop notification
+    /// Send a desktop notification. Nothing is shown during the configured
+    /// quiet hours; whatever triggered it is still recorded in the app.
     pub async fn send(
         app: &AppHandle,
         options: NotificationOptions,
     ) -> Result<()> {
+        if let Some(db) = app.try_state::<std::sync::Mutex<crate::storage::Database>>() {
+            let quiet = db
+                .lock()
+                .map(|db| TimePreferences::load(&db).is_quiet(chrono::Utc::now().timestamp()))
+                .unwrap_or(false);
+            if quiet {
+                return Ok(());
+            }
+        }
+
         // Try to use Tauri's notification plugin if available
         // Otherwise, fall back to emitting an event for the frontend to handle
         
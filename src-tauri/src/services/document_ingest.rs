@@ -0,0 +1,352 @@
+use crate::services::embeddings::EmbeddingService;
+use crate::services::ocr::OcrEngine;
+use crate::storage::{Database, DocumentStore, NewDocument, VectorDocument, VectorStore};
+use crate::storage::vector_store::image_document_id;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// The document watch folder before watch folders had pipelines; moved to
+/// a documents watch folder on startup.
+pub const CONFIG_WATCH_FOLDER: &str = "document_watch_folder";
+pub const VECTOR_COLLECTION: &str = "documents";
+
+const SUPPORTED_EXTENSIONS: [&str; 5] = ["pdf", "docx", "txt", "md", "markdown"];
+/// Screenshots, read with OCR
+const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "tif", "tiff", "bmp"];
+/// Below this many characters a PDF is treated as a scan and OCRed
+const MIN_TEXT_CHARS: usize = 200;
+const CHUNK_CHARS: usize = 1500;
+const MAX_CHUNKS: usize = 64;
+/// Entity extraction looks at the start of long documents only
+const MAX_ENTITY_TEXT_CHARS: usize = 100_000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IngestResult {
+    pub document_id: i64,
+    pub title: String,
+    /// The file was ingested before; nothing was changed
+    pub duplicate: bool,
+    pub char_count: usize,
+    pub page_count: Option<i64>,
+    pub ocr_used: bool,
+    pub entities: usize,
+    pub chunks_embedded: usize,
+    pub embedding_error: Option<String>,
+}
+
+struct Extracted {
+    text: String,
+    mime_type: &'static str,
+    page_count: Option<i64>,
+    ocr_used: bool,
+}
+
+pub struct DocumentIngest;
+
+impl DocumentIngest {
+    pub fn is_supported(path: &Path) -> bool {
+        is_image(path)
+            || path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| SUPPORTED_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+    }
+
+    /// Extract a file's text, store it as a document with its entities and
+    /// index it for full-text and semantic search. Embedding failures are
+    /// reported in the result rather than failing the ingest.
+    pub async fn ingest(db: &Mutex<Database>, embedder: &EmbeddingService, path: &Path) -> Result<IngestResult> {
+        if !Self::is_supported(path) {
+            anyhow::bail!("Unsupported document type: {}", path.display());
+        }
+        let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let file_hash = format!("{:x}", Sha256::digest(&bytes));
+        let title = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Untitled".to_string());
+
+        let ocr = {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let store = DocumentStore::new(db_guard.conn.clone());
+            if let Some(document_id) = store.find_by_hash(&file_hash)? {
+                return Ok(IngestResult {
+                    document_id,
+                    title,
+                    duplicate: true,
+                    ..Default::default()
+                });
+            }
+            OcrEngine::from_config(&db_guard)
+        };
+
+        if is_image(path) {
+            // Screenshots also go into the reverse image index
+            let vectors = {
+                let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+                VectorStore::new(db_guard.conn.clone())
+            };
+            let indexed = match embedder.generate_image(&bytes).await {
+                Ok(embedding) => vectors.index_image(
+                    &image_document_id(&bytes),
+                    &path.display().to_string(),
+                    embedder.image_model(),
+                    embedding,
+                    serde_json::json!({ "kind": "screenshot", "title": title }),
+                ),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = indexed {
+                eprintln!("Failed to index image {}: {}", path.display(), e);
+            }
+        }
+
+        let owned_path = path.to_path_buf();
+        let extracted = tokio::task::spawn_blocking(move || extract_text(&owned_path, &bytes, &ocr))
+            .await
+            .context("Text extraction task failed")??;
+        if extracted.text.trim().is_empty() {
+            anyhow::bail!("No text found in {}", path.display());
+        }
+
+        let entity_text: String = extracted.text.chars().take(MAX_ENTITY_TEXT_CHARS).collect();
+        let mut entities: Vec<(String, String, f64)> = crate::commands::osint::extract_entities_enhanced(&entity_text)
+            .into_iter()
+            .map(|(entity_type, name, confidence, _context)| (entity_type, name, confidence))
+            .collect();
+        let mut seen = HashSet::new();
+        entities.retain(|(t, n, _)| seen.insert((t.clone(), n.to_lowercase())));
+
+        let (document_id, vectors) = {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let store = DocumentStore::new(db_guard.conn.clone());
+            let document_id = store.insert_document(&NewDocument {
+                title: title.clone(),
+                file_path: path.display().to_string(),
+                file_hash,
+                mime_type: extracted.mime_type.to_string(),
+                size_bytes: std::fs::metadata(path).map(|m| m.len() as i64).unwrap_or(0),
+                page_count: extracted.page_count,
+                ocr_used: extracted.ocr_used,
+                content: extracted.text.clone(),
+            })?;
+            store.save_entities(document_id, &entities)?;
+            (document_id, VectorStore::new(db_guard.conn.clone()))
+        };
+
+        let mut result = IngestResult {
+            document_id,
+            title: title.clone(),
+            duplicate: false,
+            char_count: extracted.text.chars().count(),
+            page_count: extracted.page_count,
+            ocr_used: extracted.ocr_used,
+            entities: entities.len(),
+            chunks_embedded: 0,
+            embedding_error: None,
+        };
+
+        vectors.create_collection(VECTOR_COLLECTION, embedder.dimension() as i32)?;
+        let now = chrono::Utc::now().timestamp();
+        for (index, chunk) in chunk_text(&extracted.text, CHUNK_CHARS).into_iter().take(MAX_CHUNKS).enumerate() {
+            let embedding = match embedder.generate(&chunk).await {
+                Ok(embedding) => embedding,
+                Err(e) => {
+                    result.embedding_error = Some(e.to_string());
+                    break;
+                }
+            };
+            vectors.insert_document(&VectorDocument {
+                id: format!("document:{}:{}", document_id, index),
+                collection: VECTOR_COLLECTION.to_string(),
+                content: chunk,
+                embedding,
+                metadata: serde_json::json!({
+                    "document_id": document_id,
+                    "chunk": index,
+                    "title": title,
+                    "file_path": path.display().to_string(),
+                }),
+                created_at: now,
+                expires_at: None,
+            })?;
+            result.chunks_embedded += 1;
+        }
+
+        Ok(result)
+    }
+
+    pub fn watch_folder(db: &Database) -> Option<PathBuf> {
+        db.get_config(CONFIG_WATCH_FOLDER)
+            .ok()
+            .flatten()
+            .filter(|p| !p.trim().is_empty())
+            .map(PathBuf::from)
+    }
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+fn extract_text(path: &Path, bytes: &[u8], ocr: &OcrEngine) -> Result<Extracted> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "pdf" => {
+            let text = pdf_extract::extract_text_from_mem(bytes).unwrap_or_default();
+            if text.trim().chars().count() >= MIN_TEXT_CHARS {
+                return Ok(Extracted {
+                    text: normalize_whitespace(&text),
+                    mime_type: "application/pdf",
+                    page_count: None,
+                    ocr_used: false,
+                });
+            }
+            // No usable text layer, most likely a scan
+            let (text, pages) = ocr.pdf_text(path).context("PDF has no text layer and OCR failed")?;
+            Ok(Extracted {
+                text: normalize_whitespace(&text),
+                mime_type: "application/pdf",
+                page_count: Some(pages),
+                ocr_used: true,
+            })
+        }
+        "png" | "jpg" | "jpeg" | "tif" | "tiff" | "bmp" => Ok(Extracted {
+            text: normalize_whitespace(&ocr.image_text(path).context("OCR failed")?),
+            mime_type: match extension.as_str() {
+                "png" => "image/png",
+                "tif" | "tiff" => "image/tiff",
+                "bmp" => "image/bmp",
+                _ => "image/jpeg",
+            },
+            page_count: None,
+            ocr_used: true,
+        }),
+        "docx" => Ok(Extracted {
+            text: docx_text(bytes)?,
+            mime_type: "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            page_count: None,
+            ocr_used: false,
+        }),
+        _ => Ok(Extracted {
+            text: String::from_utf8_lossy(bytes).to_string(),
+            mime_type: if extension == "txt" { "text/plain" } else { "text/markdown" },
+            page_count: None,
+            ocr_used: false,
+        }),
+    }
+}
+
+/// Paragraph text of a DOCX, read from word/document.xml.
+fn docx_text(bytes: &[u8]) -> Result<String> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).context("Not a valid DOCX file")?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .context("DOCX has no word/document.xml")?
+        .read_to_string(&mut xml)?;
+    Ok(docx_xml_text(&xml))
+}
+
+fn docx_xml_text(xml: &str) -> String {
+    let mut out = String::with_capacity(xml.len() / 4);
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&unescape_xml(&rest[..start]));
+        let Some(end) = rest[start..].find('>') else { break };
+        let tag = &rest[start + 1..start + end];
+        let name = tag.trim_start_matches('/').split([' ', '/']).next().unwrap_or_default();
+        match name {
+            "w:p" if tag.starts_with('/') => out.push('\n'),
+            "w:tab" => out.push('\t'),
+            "w:br" | "w:cr" => out.push('\n'),
+            _ => {}
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.trim().to_string()
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Collapse runs of spaces and blank lines left by PDF extraction.
+fn normalize_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank_lines = 0;
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out.trim().to_string()
+}
+
+/// Split on paragraph boundaries into chunks of about `max_chars`.
+fn chunk_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        if !current.is_empty() && current.chars().count() + paragraph.chars().count() > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if paragraph.chars().count() > max_chars {
+            let chars: Vec<char> = paragraph.chars().collect();
+            for piece in chars.chunks(max_chars) {
+                chunks.push(piece.iter().collect());
+            }
+            continue;
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(paragraph);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_docx_paragraphs() {
+        let xml = r#"<w:document><w:body><w:p><w:r><w:t>Q3 &amp; outlook</w:t></w:r></w:p><w:p><w:r><w:t xml:space="preserve">Revenue</w:t><w:tab/><w:t>up</w:t></w:r></w:p></w:body></w:document>"#;
+        assert_eq!(docx_xml_text(xml), "Q3 & outlook\nRevenue\tup");
+    }
+
+    #[test]
+    fn chunks_on_paragraphs() {
+        let text = format!("{}\n\n{}\n\n{}", "a".repeat(40), "b".repeat(40), "c".repeat(120));
+        let chunks = chunk_text(&text, 100);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0], format!("{}\n\n{}", "a".repeat(40), "b".repeat(40)));
+        assert_eq!(chunks[1], "c".repeat(100));
+    }
+}
@@ -12,6 +12,13 @@ struct OpenAIEmbeddingRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct OpenAIEmbeddingResponse {
     data: Vec<OpenAIEmbeddingData>,
+    #[serde(default)]
+    usage: Option<OpenAIUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OpenAIUsage {
+    prompt_tokens: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +26,18 @@ struct OpenAIEmbeddingData {
     embedding: Vec<f32>,
 }
 
+/// Base URL of an OpenAI-compatible embeddings server hosting a CLIP model
+/// (e.g. infinity or LocalAI). Unset means the local image fingerprint.
+pub const CONFIG_IMAGE_EMBEDDING_URL: &str = "image_embedding_url";
+pub const CONFIG_IMAGE_EMBEDDING_MODEL: &str = "image_embedding_model";
+
+const DEFAULT_IMAGE_MODEL: &str = "openai/clip-vit-base-patch32";
+const LOCAL_IMAGE_MODEL: &str = "local-fingerprint";
+/// Side of the grayscale thumbnail in the local fingerprint
+const FINGERPRINT_SIDE: u32 = 16;
+/// Bins per RGB channel in the local fingerprint's colour histogram
+const HISTOGRAM_BINS: usize = 4;
+
 /// Embedding service that supports multiple providers
 pub struct EmbeddingService {
     openai_client: Option<reqwest::Client>,
@@ -26,6 +45,10 @@ pub struct EmbeddingService {
     openai_base_url: String,
     cache: Arc<Mutex<HashMap<String, Vec<f32>>>>,
     dimension: usize,
+    meter: Option<crate::services::llm_metering::LlmMeter>,
+    privacy: Option<crate::services::redaction::PrivacyFilter>,
+    image_base_url: Option<String>,
+    image_model: String,
 }
 
 impl EmbeddingService {
@@ -36,6 +59,10 @@ impl EmbeddingService {
             openai_base_url: "https://api.openai.com/v1".to_string(),
             cache: Arc::new(Mutex::new(HashMap::new())),
             dimension: 1536, // OpenAI text-embedding-3-small dimension
+            meter: None,
+            privacy: None,
+            image_base_url: None,
+            image_model: LOCAL_IMAGE_MODEL.to_string(),
         }
     }
 
@@ -44,6 +71,86 @@ impl EmbeddingService {
         self.openai_api_key = Some(api_key);
     }
 
+    /// Record OpenAI usage and enforce its budget
+    pub fn set_meter(&mut self, meter: crate::services::llm_metering::LlmMeter) {
+        self.meter = Some(meter);
+    }
+
+    /// Redact text sent to OpenAI and audit it
+    pub fn set_privacy_filter(&mut self, filter: crate::services::redaction::PrivacyFilter) {
+        self.privacy = Some(filter);
+    }
+
+    /// Use a CLIP model for image embeddings
+    pub fn set_image_model(&mut self, base_url: String, model: Option<String>) {
+        self.image_base_url = Some(base_url.trim_end_matches('/').to_string());
+        self.image_model = model.unwrap_or_else(|| DEFAULT_IMAGE_MODEL.to_string());
+    }
+
+    /// Apply the image embedding settings from the config table
+    pub fn load_image_config(&mut self, db: &crate::storage::Database) {
+        let get = |key| db.get_config(key).ok().flatten().filter(|v: &String| !v.trim().is_empty());
+        if let Some(url) = get(CONFIG_IMAGE_EMBEDDING_URL) {
+            self.set_image_model(url, get(CONFIG_IMAGE_EMBEDDING_MODEL));
+        }
+    }
+
+    /// Name of the model behind `generate_image`. Embeddings from different
+    /// models aren't comparable, so it's stored alongside each vector.
+    pub fn image_model(&self) -> &str {
+        &self.image_model
+    }
+
+    /// Embed an encoded image (PNG, JPEG, ...). Unlike text there is no
+    /// fallback when the CLIP server fails: mixing the two vector spaces
+    /// in one collection would make every comparison meaningless.
+    pub async fn generate_image(&self, bytes: &[u8]) -> Result<Vec<f32>> {
+        match &self.image_base_url {
+            Some(base_url) => self.generate_clip(base_url, bytes).await,
+            None => image_fingerprint(bytes),
+        }
+    }
+
+    async fn generate_clip(&self, base_url: &str, bytes: &[u8]) -> Result<Vec<f32>> {
+        use base64::Engine;
+
+        let client = self.openai_client.as_ref()
+            .context("HTTP client not initialized")?;
+        let mime = image::guess_format(bytes).map(|f| f.to_mime_type()).unwrap_or("image/png");
+        let data_uri = format!(
+            "data:{};base64,{}",
+            mime,
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        );
+        let response = client
+            .post(&format!("{}/embeddings", base_url))
+            .json(&serde_json::json!({
+                "model": self.image_model,
+                "input": [data_uri],
+                "modality": "image",
+            }))
+            .send()
+            .await
+            .context("Failed to send image embedding request")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("Image embedding API error ({}): {}", status, error_text);
+        }
+
+        let embedding_response: OpenAIEmbeddingResponse = response
+            .json()
+            .await
+            .context("Failed to parse image embedding response")?;
+        embedding_response
+            .data
+            .into_iter()
+            .next()
+            .map(|d| d.embedding)
+            .context("No embedding data in image embedding response")
+    }
+
     /// Generate embedding for text, trying OpenAI first, then falling back to local
     pub async fn generate(&self, text: &str) -> Result<Vec<f32>> {
         // Check cache first
@@ -87,9 +194,19 @@ impl EmbeddingService {
         let client = self.openai_client.as_ref()
             .context("OpenAI client not initialized")?;
 
+        if let Some(meter) = &self.meter {
+            meter.check_budget("openai")?;
+        }
+        let started = std::time::Instant::now();
+
+        let model = "text-embedding-3-small";
+        let text = match &self.privacy {
+            Some(filter) => filter.prepare("openai", model, "embedding", text)?.0,
+            None => text.to_string(),
+        };
         let request = OpenAIEmbeddingRequest {
-            model: "text-embedding-3-small".to_string(),
-            input: vec![text.to_string()],
+            model: model.to_string(),
+            input: vec![text],
         };
 
         let response = client
@@ -112,6 +229,14 @@ impl EmbeddingService {
             .await
             .context("Failed to parse OpenAI response")?;
 
+        if let Some(meter) = &self.meter {
+            let tokens = embedding_response.usage.as_ref().map(|u| u.prompt_tokens).unwrap_or(0);
+            let latency_ms = started.elapsed().as_millis() as i64;
+            if let Err(e) = meter.record("openai", &request.model, tokens, 0, latency_ms) {
+                eprintln!("Warning: Failed to record LLM usage: {}", e);
+            }
+        }
+
         if let Some(data) = embedding_response.data.first() {
             Ok(data.embedding.clone())
         } else {
@@ -246,9 +371,79 @@ impl EmbeddingService {
     }
 }
 
+/// Local image embedding without a model: a grayscale thumbnail for layout
+/// plus a coarse colour histogram. Good at finding the same chart or
+/// screenshot again after resizing or recompression, not at semantics.
+fn image_fingerprint(bytes: &[u8]) -> Result<Vec<f32>> {
+    let image = image::load_from_memory(bytes).context("Failed to decode image")?;
+
+    let thumbnail = image
+        .resize_exact(FINGERPRINT_SIDE, FINGERPRINT_SIDE, image::imageops::FilterType::Triangle)
+        .to_luma8();
+    let mut layout: Vec<f32> = thumbnail.pixels().map(|p| p.0[0] as f32 / 255.0).collect();
+    let mean = layout.iter().sum::<f32>() / layout.len() as f32;
+    for v in &mut layout {
+        *v -= mean;
+    }
+
+    let mut colours = vec![0.0f32; HISTOGRAM_BINS * HISTOGRAM_BINS * HISTOGRAM_BINS];
+    let small = image.thumbnail(64, 64).to_rgb8();
+    let bin = |c: u8| (c as usize * HISTOGRAM_BINS) / 256;
+    for p in small.pixels() {
+        let [r, g, b] = p.0;
+        colours[(bin(r) * HISTOGRAM_BINS + bin(g)) * HISTOGRAM_BINS + bin(b)] += 1.0;
+    }
+
+    // Normalize each part on its own so neither dominates the cosine
+    for part in [&mut layout, &mut colours] {
+        let norm: f32 = part.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for v in part.iter_mut() {
+                *v /= norm;
+            }
+        }
+    }
+    layout.extend(colours);
+    for v in &mut layout {
+        *v /= std::f32::consts::SQRT_2;
+    }
+    Ok(layout)
+}
+
 impl Default for EmbeddingService {
     fn default() -> Self {
         Self::new()
     }
 }
 
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::vector_store::cosine_similarity;
+
+    fn png(width: u32, height: u32, pixel: impl Fn(u32, u32) -> [u8; 3]) -> Vec<u8> {
+        let img = image::RgbImage::from_fn(width, height, |x, y| image::Rgb(pixel(x, y)));
+        let mut out = std::io::Cursor::new(Vec::new());
+        img.write_to(&mut out, image::ImageFormat::Png).unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn fingerprint_matches_resized_copy() {
+        let chart = |x: u32, y: u32| if y > 60 - x / 4 { [20, 90, 200] } else { [255, 255, 255] };
+        let original = image_fingerprint(&png(240, 120, |x, y| chart(x, y))).unwrap();
+        let resized = image_fingerprint(&png(120, 60, |x, y| chart(x * 2, y * 2))).unwrap();
+        let other = image_fingerprint(&png(240, 120, |x, _| if x < 120 { [200, 30, 30] } else { [0, 0, 0] })).unwrap();
+
+        assert_eq!(original.len(), (FINGERPRINT_SIDE * FINGERPRINT_SIDE) as usize + HISTOGRAM_BINS.pow(3));
+        assert!(cosine_similarity(&original, &resized) > 0.95);
+        assert!(cosine_similarity(&original, &other) < cosine_similarity(&original, &resized));
+    }
+
+    #[test]
+    fn fingerprint_rejects_non_images() {
+        assert!(image_fingerprint(b"not an image").is_err());
+    }
+}
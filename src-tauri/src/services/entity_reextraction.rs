@@ -0,0 +1,119 @@
+use crate::services::job_manager::JobContext;
+use crate::storage::osint::OSINTStore;
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+pub const DEFAULT_BATCH_SIZE: i64 = 100;
+const MAX_BATCH_SIZE: i64 = 1000;
+/// Pause between batches so UI queries get the database in between
+const BATCH_PAUSE_MS: u64 = 200;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReextractionSummary {
+    pub articles: usize,
+    pub batches: usize,
+    pub entities_removed: usize,
+    pub entities_added: usize,
+}
+
+/// Runs the current entity extractor again over already stored articles,
+/// replacing what earlier versions extracted.
+pub struct EntityReextraction;
+
+impl EntityReextraction {
+    /// Re-extract articles published in [from_ts, to_ts), `batch_size` at a
+    /// time. Each article is swapped in its own transaction, so cancelling
+    /// leaves every article either fully old or fully new.
+    pub async fn run(
+        conn: Arc<Mutex<Connection>>,
+        from_ts: i64,
+        to_ts: i64,
+        batch_size: i64,
+        ctx: Option<&JobContext>,
+    ) -> Result<ReextractionSummary> {
+        if to_ts <= from_ts {
+            anyhow::bail!("to_ts must be after from_ts");
+        }
+        let batch_size = batch_size.clamp(1, MAX_BATCH_SIZE);
+        let store = OSINTStore::new(conn);
+        let total = store.count_articles_between(from_ts, to_ts)?;
+        let mut summary = ReextractionSummary::default();
+        let mut after_id = 0;
+
+        loop {
+            if let Some(ctx) = ctx {
+                ctx.check_cancelled()?;
+                ctx.progress(
+                    summary.articles as f64 / total.max(1) as f64,
+                    format!("Re-extracted {} of {} articles", summary.articles, total),
+                );
+            }
+
+            let batch = store.articles_after(from_ts, to_ts, after_id, batch_size)?;
+            let Some((last_id, _, _)) = batch.last() else {
+                break;
+            };
+            after_id = *last_id;
+            Self::reextract_batch(&store, &batch, &mut summary)?;
+
+            if (batch.len() as i64) < batch_size {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(BATCH_PAUSE_MS)).await;
+        }
+        Ok(summary)
+    }
+
+    fn reextract_batch(
+        store: &OSINTStore,
+        articles: &[(i64, String, String)],
+        summary: &mut ReextractionSummary,
+    ) -> Result<()> {
+        for (article_id, title, content) in articles {
+            let entities = crate::commands::osint::extract_entities_enhanced(&format!("{} {}", title, content));
+            let (removed, added) = store.replace_extracted_entities(*article_id, &entities)?;
+            summary.articles += 1;
+            summary.entities_removed += removed;
+            summary.entities_added += added;
+        }
+        summary.batches += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[tokio::test]
+    async fn superseded_entities_are_replaced_in_range_only() {
+        let db = test_support::test_db();
+        let store = OSINTStore::new(db.conn.clone());
+        let feed = test_support::feed(&db, "Wire");
+        let old = store.save_rss_item(feed, "Talks in Germany", "Leaders met in Germany.", "https://example.com/old", 1_000).unwrap();
+        let recent: Vec<i64> = (0..3)
+            .map(|i| {
+                let url = format!("https://example.com/recent/{}", i);
+                store.save_rss_item(feed, "Talks in Germany", "Leaders met in Germany.", &url, 10_000 + i).unwrap()
+            })
+            .collect();
+        for id in std::iter::once(old).chain(recent.iter().copied()) {
+            store.save_extracted_entity(id, "person", "Stale Name", 0.4, None).unwrap();
+        }
+
+        let summary = EntityReextraction::run(db.conn.clone(), 5_000, 20_000, 2, None).await.unwrap();
+        assert_eq!((summary.articles, summary.batches, summary.entities_removed), (3, 2, 3));
+
+        let names = |id: i64| -> Vec<String> {
+            store.get_entities_for_article(id).unwrap().into_iter().map(|e| e.name).collect()
+        };
+        for id in recent {
+            assert!(names(id).contains(&"germany".to_string()));
+            assert!(!names(id).contains(&"Stale Name".to_string()));
+        }
+        assert_eq!(names(old), vec!["Stale Name".to_string()]);
+    }
+}
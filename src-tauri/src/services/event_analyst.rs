@@ -0,0 +1,190 @@
+use crate::commands::ollama::OllamaState;
+use crate::providers::ollama::ChatMessage;
+use crate::services::llm_metering::LlmMeter;
+use crate::services::model_benchmark::{ModelBenchmark, TASK_SUMMARIZATION};
+use crate::services::structured_output::StructuredOutput;
+use crate::storage::temporal::{EventCommentary, TemporalEvent, TemporalStore};
+use crate::storage::Database;
+use anyhow::Result;
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+pub const CONFIG_ENABLED: &str = "event_analyst_enabled";
+pub const CONFIG_MODEL: &str = "event_analyst_model";
+pub const CONFIG_MIN_SEVERITY: &str = "event_analyst_min_severity";
+pub const CONFIG_MIN_VOLUME: &str = "event_analyst_min_volume";
+
+const DEFAULT_MIN_SEVERITY: f64 = 0.7;
+const DEFAULT_MIN_VOLUME: f64 = 5.0;
+const MAX_EVENTS_PER_RUN: i64 = 5;
+const LOOKBACK_SECS: i64 = 2 * 86_400;
+
+const ANALYST_PROMPT: &str = "You are a concise markets analyst. Given an event detected from news coverage and \
+the user's watchlist, respond with only a JSON object: {\"what_happened\": \"1-2 sentences\", \
+\"why_it_matters\": \"1-3 sentences on market impact\", \"affected_tickers\": [\"...\"]}. \
+Only list tickers or names from the watchlist that the event plausibly affects.";
+
+#[derive(Debug, Deserialize)]
+struct AnalystResponse {
+    what_happened: String,
+    why_it_matters: String,
+    #[serde(default)]
+    affected_tickers: Vec<String>,
+}
+
+fn analyst_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "required": ["what_happened", "why_it_matters", "affected_tickers"],
+        "properties": {
+            "what_happened": { "type": "string", "minLength": 1 },
+            "why_it_matters": { "type": "string", "minLength": 1 },
+            "affected_tickers": { "type": "array", "items": { "type": "string" } }
+        }
+    })
+}
+
+pub struct EventAnalyst;
+
+impl EventAnalyst {
+    /// Every 15 minutes, write commentary for new high-severity events when enabled.
+    pub fn start_scheduler(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(15 * 60));
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = Self::run_once(&db, &app).await {
+                    eprintln!("Error generating event commentary: {}", e);
+                }
+            }
+        });
+    }
+
+    pub async fn run_once(db: &Arc<Mutex<Database>>, app: &AppHandle) -> Result<Vec<EventCommentary>> {
+        let (enabled, model, min_severity, min_volume, conn) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            (
+                db_guard.get_config(CONFIG_ENABLED)?.map(|v| v == "true").unwrap_or(false),
+                db_guard.get_config(CONFIG_MODEL)?,
+                db_guard.get_config(CONFIG_MIN_SEVERITY)?
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .unwrap_or(DEFAULT_MIN_SEVERITY),
+                db_guard.get_config(CONFIG_MIN_VOLUME)?
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .unwrap_or(DEFAULT_MIN_VOLUME),
+                db_guard.conn.clone(),
+            )
+        };
+        let model = match (enabled, model) {
+            (true, Some(m)) if !m.is_empty() => ModelBenchmark::resolve(&conn, TASK_SUMMARIZATION, &m)?,
+            _ => return Ok(Vec::new()),
+        };
+        let ollama = match app.try_state::<OllamaState>() {
+            Some(state) => state.inner().clone(),
+            None => return Ok(Vec::new()),
+        };
+
+        let store = TemporalStore::new(conn.clone());
+        let from_ts = chrono::Utc::now().timestamp() - LOOKBACK_SECS;
+        let events = store.list_events_needing_commentary(from_ts, min_severity, min_volume, MAX_EVENTS_PER_RUN)?;
+        if events.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let watchlist = watchlist_values(&store)?;
+        let meter = LlmMeter::new(conn);
+        let mut written = Vec::new();
+
+        for event in events {
+            let provider = ollama.read().await;
+            let commentary = match Self::analyze(&meter, &provider, &model, &store, &event, &watchlist).await {
+                Ok(c) => c,
+                Err(e) => {
+                    eprintln!("Failed to analyze event {}: {}", event.id, e);
+                    continue;
+                }
+            };
+            drop(provider);
+
+            store.save_event_commentary(&commentary)?;
+            let _ = crate::services::window_router::emit(app, serde_json::json!({
+                "type": "event-commentary",
+                "data": { "event": event, "commentary": commentary },
+                "timestamp": chrono::Utc::now().timestamp_millis(),
+            }));
+            written.push(commentary);
+        }
+
+        Ok(written)
+    }
+
+    async fn analyze(
+        meter: &LlmMeter,
+        provider: &crate::providers::ollama::OllamaProvider,
+        model: &str,
+        store: &TemporalStore,
+        event: &TemporalEvent,
+        watchlist: &[String],
+    ) -> Result<EventCommentary> {
+        let snippets: Vec<String> = store
+            .list_event_evidence(event.id)?
+            .into_iter()
+            .filter_map(|e| e.snippet)
+            .take(5)
+            .collect();
+
+        let prompt = format!(
+            "Event: {}\nSummary: {}\nArticles: {:.0}, sentiment {:.2}\nEvidence:\n- {}\n\nWatchlist: {}",
+            event.title,
+            event.summary,
+            event.volume_score,
+            event.sentiment_score,
+            snippets.join("\n- "),
+            if watchlist.is_empty() { "(empty)".to_string() } else { watchlist.join(", ") },
+        );
+
+        let parsed: AnalystResponse = StructuredOutput::ollama_chat(
+            meter,
+            provider,
+            model,
+            vec![
+                ChatMessage { role: "system".to_string(), content: ANALYST_PROMPT.to_string() },
+                ChatMessage { role: "user".to_string(), content: prompt },
+            ],
+            &analyst_schema(),
+        )
+        .await?;
+
+        // Keep only watchlist entries; models like to invent tickers
+        let affected_tickers = parsed
+            .affected_tickers
+            .into_iter()
+            .filter_map(|t| watchlist.iter().find(|w| w.eq_ignore_ascii_case(t.trim())).cloned())
+            .collect();
+
+        Ok(EventCommentary {
+            event_id: event.id,
+            model: model.to_string(),
+            what_happened: parsed.what_happened.trim().to_string(),
+            why_it_matters: parsed.why_it_matters.trim().to_string(),
+            affected_tickers,
+            created_at: chrono::Utc::now().timestamp(),
+        })
+    }
+}
+
+fn watchlist_values(store: &TemporalStore) -> Result<Vec<String>> {
+    let mut values = Vec::new();
+    for watchlist in store.list_watchlists()? {
+        for item in store.list_watchlist_items(watchlist.id)? {
+            if item.enabled && (item.item_type == "entity" || item.item_type == "keyword") && !values.contains(&item.value) {
+                values.push(item.value);
+            }
+        }
+    }
+    Ok(values)
+}
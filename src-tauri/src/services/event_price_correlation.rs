@@ -0,0 +1,189 @@
+use crate::storage::market_data::{MarketDataStore, PriceHistory};
+use crate::storage::temporal::TemporalStore;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Horizons the price reaction is measured over, in seconds.
+const HORIZONS: [(&str, i64); 3] = [("1h", 3600), ("1d", 86400), ("1w", 7 * 86400)];
+const DEFAULT_LARGE_MOVE_PERCENT: f64 = 5.0;
+const MAX_EVENTS: i64 = 2000;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventPriceQuery {
+    /// Ticker whose prices are measured
+    pub ticker: String,
+    /// Entity the events are about; defaults to the ticker
+    #[serde(default)]
+    pub entity: Option<String>,
+    pub from_ts: i64,
+    pub to_ts: i64,
+    /// Absolute percent move that counts as large (5% by default)
+    #[serde(default)]
+    pub large_move_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventPriceMove {
+    pub event_id: i64,
+    pub title: String,
+    pub event_type: String,
+    pub sentiment_score: f64,
+    pub start_ts: i64,
+    /// Percent change from the last close before the event; None when the
+    /// stored bars are too coarse or don't reach that far
+    pub move_1h: Option<f64>,
+    pub move_1d: Option<f64>,
+    pub move_1w: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveGroupStats {
+    pub group: String,
+    pub events: usize,
+    pub avg_move_1h: Option<f64>,
+    pub avg_move_1d: Option<f64>,
+    pub avg_move_1w: Option<f64>,
+    pub avg_abs_move_1d: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventPriceReport {
+    pub ticker: String,
+    pub entity: String,
+    pub events_analyzed: usize,
+    pub events_without_prices: usize,
+    pub by_event_type: Vec<MoveGroupStats>,
+    pub by_sentiment: Vec<MoveGroupStats>,
+    /// Events followed by a move of at least `large_move_percent` at any horizon, largest first
+    pub large_moves: Vec<EventPriceMove>,
+    pub large_move_percent: f64,
+    pub moves: Vec<EventPriceMove>,
+}
+
+pub struct EventPriceAnalyzer;
+
+impl EventPriceAnalyzer {
+    pub fn analyze(
+        temporal_store: &TemporalStore,
+        market_data_store: &MarketDataStore,
+        query: &EventPriceQuery,
+    ) -> Result<EventPriceReport> {
+        let ticker = query.ticker.trim().to_uppercase();
+        let entity = query.entity.clone().filter(|e| !e.trim().is_empty()).unwrap_or_else(|| ticker.clone());
+        let large_move_percent = query.large_move_percent.unwrap_or(DEFAULT_LARGE_MOVE_PERCENT).abs();
+
+        let events = temporal_store.list_events_for_ticker(&entity, query.from_ts, query.to_ts, MAX_EVENTS)?;
+        // Prices need to extend a week past the last event for the 1w horizon
+        let bars = market_data_store.get_price_history_range(&ticker, query.from_ts - 7 * 86400, query.to_ts + 7 * 86400)?;
+
+        let mut moves = Vec::new();
+        let mut events_without_prices = 0;
+        for event in events {
+            let horizon_moves: Vec<Option<f64>> = HORIZONS
+                .iter()
+                .map(|(_, secs)| move_after(&bars, event.start_ts, *secs))
+                .collect();
+            if horizon_moves.iter().all(|m| m.is_none()) {
+                events_without_prices += 1;
+                continue;
+            }
+            moves.push(EventPriceMove {
+                event_id: event.id,
+                title: event.title,
+                event_type: event.event_type,
+                sentiment_score: event.sentiment_score,
+                start_ts: event.start_ts,
+                move_1h: horizon_moves[0],
+                move_1d: horizon_moves[1],
+                move_1w: horizon_moves[2],
+            });
+        }
+
+        let by_event_type = group_stats(&moves, |m| m.event_type.clone());
+        let by_sentiment = group_stats(&moves, |m| sentiment_bucket(m.sentiment_score).to_string());
+
+        let mut large_moves: Vec<EventPriceMove> = moves
+            .iter()
+            .filter(|m| largest_abs_move(m) >= large_move_percent)
+            .cloned()
+            .collect();
+        large_moves.sort_by(|a, b| largest_abs_move(b).partial_cmp(&largest_abs_move(a)).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(EventPriceReport {
+            ticker,
+            entity,
+            events_analyzed: moves.len(),
+            events_without_prices,
+            by_event_type,
+            by_sentiment,
+            large_moves,
+            large_move_percent,
+            moves,
+        })
+    }
+}
+
+/// Percent change from the last close at or before `start_ts` to the last
+/// close at or before `start_ts + horizon`. None when both fall on the same
+/// bar (bars coarser than the horizon) or the data ends before the horizon.
+fn move_after(bars: &[PriceHistory], start_ts: i64, horizon: i64) -> Option<f64> {
+    let before = bars.partition_point(|b| b.timestamp <= start_ts).checked_sub(1)?;
+    let target_ts = start_ts + horizon;
+    if bars.last()?.timestamp < target_ts {
+        return None;
+    }
+    let after = bars.partition_point(|b| b.timestamp <= target_ts).checked_sub(1)?;
+    if after <= before || bars[before].close <= 0.0 {
+        return None;
+    }
+    Some((bars[after].close / bars[before].close - 1.0) * 100.0)
+}
+
+fn sentiment_bucket(score: f64) -> &'static str {
+    if score > 0.2 {
+        "positive"
+    } else if score < -0.2 {
+        "negative"
+    } else {
+        "neutral"
+    }
+}
+
+fn largest_abs_move(m: &EventPriceMove) -> f64 {
+    [m.move_1h, m.move_1d, m.move_1w]
+        .iter()
+        .flatten()
+        .map(|v| v.abs())
+        .fold(0.0, f64::max)
+}
+
+fn group_stats(moves: &[EventPriceMove], key: impl Fn(&EventPriceMove) -> String) -> Vec<MoveGroupStats> {
+    let mut groups: BTreeMap<String, Vec<&EventPriceMove>> = BTreeMap::new();
+    for m in moves {
+        groups.entry(key(m)).or_default().push(m);
+    }
+
+    let mut stats: Vec<MoveGroupStats> = groups
+        .into_iter()
+        .map(|(group, members)| MoveGroupStats {
+            events: members.len(),
+            avg_move_1h: average(members.iter().filter_map(|m| m.move_1h)),
+            avg_move_1d: average(members.iter().filter_map(|m| m.move_1d)),
+            avg_move_1w: average(members.iter().filter_map(|m| m.move_1w)),
+            avg_abs_move_1d: average(members.iter().filter_map(|m| m.move_1d.map(f64::abs))),
+            group,
+        })
+        .collect();
+    stats.sort_by(|a, b| b.events.cmp(&a.events));
+    stats
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0usize), |(s, c), v| (s + v, c + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count as f64)
+    }
+}
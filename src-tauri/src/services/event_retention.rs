@@ -0,0 +1,73 @@
+use crate::storage::temporal::TemporalStore;
+use crate::storage::Database;
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+
+/// Events that ended more than this many months ago are archived. Unset or 0 keeps everything.
+pub const CONFIG_RETENTION_MONTHS: &str = "temporal_retention_months";
+
+/// Event building re-clusters the last 30 days of articles, so anything
+/// younger than this would just be rebuilt after archiving.
+pub const MIN_RETENTION_MONTHS: i64 = 2;
+const MONTH_SECS: i64 = 30 * 86400;
+const BATCH_SIZE: i64 = 500;
+const CHECK_INTERVAL_SECS: u64 = 24 * 3600;
+
+pub struct EventRetention;
+
+impl EventRetention {
+    pub fn retention_months(db: &Database) -> Option<i64> {
+        db.get_config(CONFIG_RETENTION_MONTHS)
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .filter(|m| *m > 0)
+    }
+
+    /// Archive every event that ended more than `months` ago, in batches.
+    /// Returns the number archived.
+    pub fn apply(store: &TemporalStore, months: i64) -> Result<usize> {
+        if months < MIN_RETENTION_MONTHS {
+            anyhow::bail!("Retention must be at least {} months", MIN_RETENTION_MONTHS);
+        }
+        let cutoff = chrono::Utc::now().timestamp() - months * MONTH_SECS;
+
+        let mut total = 0;
+        loop {
+            let archived = store.archive_events_before(cutoff, BATCH_SIZE)?;
+            total += archived;
+            if (archived as i64) < BATCH_SIZE {
+                return Ok(total);
+            }
+        }
+    }
+
+    /// Apply the configured retention, if any.
+    pub fn apply_configured(db: &Arc<Mutex<Database>>) -> Result<usize> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        match Self::retention_months(&db_guard) {
+            Some(months) => Self::apply(&TemporalStore::new(db_guard.conn.clone()), months),
+            None => Ok(0),
+        }
+    }
+
+    pub fn start_scheduler(db: Arc<Mutex<Database>>) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+            let heartbeat = crate::services::watchdog::Watchdog::register("event_retention", tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+
+            loop {
+                interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
+                match Self::apply_configured(&db) {
+                    Ok(0) => {}
+                    Ok(n) => eprintln!("Archived {} temporal events past retention", n),
+                    Err(e) => eprintln!("Event retention failed: {}", e),
+                }
+            }
+        });
+    }
+}
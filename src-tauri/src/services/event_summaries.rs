@@ -0,0 +1,223 @@
+use crate::storage::temporal::{SummaryCandidate, SummaryEvidence, SummaryRevision, TemporalStore};
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// New evidence articles needed before a summary is rewritten.
+pub const CONFIG_MIN_NEW_EVIDENCE: &str = "event_summary_min_new_evidence";
+/// Severity change that rewrites a summary regardless of evidence count.
+pub const CONFIG_SEVERITY_DELTA: &str = "event_summary_severity_delta";
+
+const LEAD_CHARS: usize = 240;
+const HEADLINE_CHARS: usize = 90;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryRefreshSettings {
+    pub min_new_evidence: i64,
+    /// New evidence must also be at least this share of what was
+    /// summarized, so large stories aren't rewritten for every article
+    pub growth_ratio: f64,
+    pub severity_delta: f64,
+    /// Articles a summary is built from
+    pub max_articles: usize,
+}
+
+impl Default for SummaryRefreshSettings {
+    fn default() -> Self {
+        SummaryRefreshSettings {
+            min_new_evidence: 3,
+            growth_ratio: 0.5,
+            severity_delta: 0.2,
+            max_articles: 4,
+        }
+    }
+}
+
+impl SummaryRefreshSettings {
+    pub fn load(db: &Database) -> Self {
+        let defaults = Self::default();
+        let get = |key: &str| db.get_config(key).ok().flatten();
+        SummaryRefreshSettings {
+            min_new_evidence: get(CONFIG_MIN_NEW_EVIDENCE).and_then(|v| v.parse().ok()).unwrap_or(defaults.min_new_evidence).max(1),
+            severity_delta: get(CONFIG_SEVERITY_DELTA).and_then(|v| v.parse().ok()).unwrap_or(defaults.severity_delta),
+            ..defaults
+        }
+    }
+}
+
+pub struct EventSummaries;
+
+impl EventSummaries {
+    /// Rewrite the summaries of news events ending in the last `days_back`
+    /// days that gained significant evidence since they were last written.
+    pub fn update(
+        temporal_store: &TemporalStore,
+        settings: &SummaryRefreshSettings,
+        days_back: i64,
+    ) -> Result<Vec<SummaryRevision>> {
+        let since = chrono::Utc::now().timestamp() - days_back * 24 * 3600;
+        let mut revisions = Vec::new();
+        for candidate in temporal_store.summary_candidates(since)? {
+            if let Some(reason) = refresh_reason(&candidate, settings) {
+                if let Some(revision) = Self::regenerate(temporal_store, &candidate, settings, &reason)? {
+                    revisions.push(revision);
+                }
+            }
+        }
+        Ok(revisions)
+    }
+
+    /// Rewrite one event's summary now, whether or not it is due.
+    pub fn refresh(
+        temporal_store: &TemporalStore,
+        event_id: i64,
+        settings: &SummaryRefreshSettings,
+    ) -> Result<Option<SummaryRevision>> {
+        let event = temporal_store
+            .get_event(event_id)?
+            .ok_or_else(|| anyhow::anyhow!("Event {} not found", event_id))?;
+        let evidence_count = temporal_store.list_event_evidence(event_id)?.len() as i64;
+        let candidate = SummaryCandidate {
+            event_id,
+            severity: event.severity,
+            evidence_count,
+            summarized_evidence_count: None,
+            summarized_severity: None,
+        };
+        Self::regenerate(temporal_store, &candidate, settings, "manual")
+    }
+
+    fn regenerate(
+        temporal_store: &TemporalStore,
+        candidate: &SummaryCandidate,
+        settings: &SummaryRefreshSettings,
+        reason: &str,
+    ) -> Result<Option<SummaryRevision>> {
+        let evidence = temporal_store.summary_evidence(candidate.event_id)?;
+        let Some(summary) = compose_summary(&evidence, settings.max_articles) else {
+            return Ok(None);
+        };
+        temporal_store
+            .record_summary_revision(candidate.event_id, &summary, candidate.evidence_count, candidate.severity, reason)
+            .map(Some)
+    }
+}
+
+/// Why the event's summary is due for a rewrite, if it is. Before the
+/// first rewrite the summary reflects only the first article.
+pub fn refresh_reason(candidate: &SummaryCandidate, settings: &SummaryRefreshSettings) -> Option<String> {
+    let summarized = candidate.summarized_evidence_count.unwrap_or(1);
+    let new_evidence = candidate.evidence_count - summarized;
+    let needed = settings
+        .min_new_evidence
+        .max((summarized as f64 * settings.growth_ratio).ceil() as i64);
+    if new_evidence >= needed {
+        return Some(format!("{} new articles", new_evidence));
+    }
+
+    let severity_change = candidate.summarized_severity.map(|s| candidate.severity - s).unwrap_or(0.0);
+    if severity_change.abs() >= settings.severity_delta {
+        return Some(format!("severity {:+.2}", severity_change));
+    }
+    None
+}
+
+/// Weight of an article for the summary: its evidence weight, scaled by
+/// the reliability of its feed and favouring later coverage so the
+/// summary follows the story as it develops.
+fn article_score(article: &SummaryEvidence, first_ts: i64, last_ts: i64) -> f64 {
+    let span = (last_ts - first_ts).max(1) as f64;
+    let recency = 0.5 + 0.5 * (article.published_at - first_ts) as f64 / span;
+    article.weight * (0.5 + article.reliability) * recency
+}
+
+/// Summary led by the highest-weighted article, followed by the headlines
+/// of the next ones (one per feed) in the order they were published.
+pub fn compose_summary(evidence: &[SummaryEvidence], max_articles: usize) -> Option<String> {
+    let first_ts = evidence.iter().map(|a| a.published_at).min()?;
+    let last_ts = evidence.iter().map(|a| a.published_at).max()?;
+
+    let mut ranked: Vec<(&SummaryEvidence, f64)> =
+        evidence.iter().map(|a| (a, article_score(a, first_ts, last_ts))).collect();
+    ranked.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.0.published_at.cmp(&a.0.published_at))
+    });
+
+    let mut feeds = HashSet::new();
+    let mut selected: Vec<&SummaryEvidence> = Vec::new();
+    for (article, _) in ranked {
+        if selected.len() >= max_articles.max(1) {
+            break;
+        }
+        if feeds.insert(article.feed_id) {
+            selected.push(article);
+        }
+    }
+
+    let lead = selected.first()?;
+    let body = lead.content.replace(['\n', '\r'], " ");
+    let mut summary = format!("{} — {}", clip(&lead.title, HEADLINE_CHARS), clip(body.trim(), LEAD_CHARS));
+
+    let mut developments: Vec<&SummaryEvidence> = selected[1..].to_vec();
+    developments.sort_by_key(|a| a.published_at);
+    if !developments.is_empty() {
+        let headlines: Vec<String> = developments
+            .iter()
+            .map(|a| format!("{} ({})", clip(&a.title, HEADLINE_CHARS), a.source))
+            .collect();
+        summary.push_str(&format!(" Also: {}.", headlines.join("; ")));
+    }
+
+    let sources: HashSet<i64> = evidence.iter().map(|a| a.feed_id).collect();
+    summary.push_str(&format!(" [{} articles, {} sources]", evidence.len(), sources.len()));
+    Some(summary)
+}
+
+fn clip(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(max_chars).collect::<String>().trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn summaries_are_rewritten_once_enough_evidence_arrives() {
+        let db = test_support::test_db();
+        let store = TemporalStore::new(db.conn.clone());
+        let settings = SummaryRefreshSettings::default();
+        let first = test_support::article(&db, "Reuters", "Acme explores sale", "Acme Corp is exploring a sale.");
+        let mut fixture = test_support::new_event("Acme sale");
+        fixture.summary = "Acme explores sale — Acme Corp is exploring a sale.".to_string();
+        let event = test_support::event(&db, &fixture, &[first]);
+
+        assert!(EventSummaries::update(&store, &settings, 36500).unwrap().is_empty());
+
+        let more: Vec<i64> = [("Bloomberg", "Globex bids for Acme"), ("FT", "Acme board weighs Globex bid"), ("AP", "Acme agrees to Globex deal")]
+            .iter()
+            .map(|(source, title)| test_support::article(&db, source, title, "Details of the deal."))
+            .collect();
+        test_support::event(&db, &fixture, &more);
+
+        let updated = EventSummaries::update(&store, &settings, 36500).unwrap();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].reason, "3 new articles");
+        assert!(updated[0].summary.starts_with("Acme explores sale — Acme Corp is exploring a sale. Also: "));
+        assert!(updated[0].summary.ends_with("[4 articles, 4 sources]"));
+        assert_eq!(store.get_event(event.id).unwrap().unwrap().summary, updated[0].summary);
+        // The original summary is kept as the first revision
+        let revisions = store.list_summary_revisions(event.id).unwrap();
+        assert_eq!(revisions.iter().map(|r| r.revision).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(revisions[1].summary, fixture.summary);
+        // Nothing new since the rewrite
+        assert!(EventSummaries::update(&store, &settings, 36500).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::providers::market_data::MarketDataManager;
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::market_cache::MarketDataCache;
+use crate::services::rate_limiter::RateLimiter;
+use crate::storage::market_data::{Fundamentals, MarketDataStore};
+use crate::storage::Database;
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+
+/// How often held and alerted tickers get fresh fundamentals. 0 disables the refresh.
+pub const CONFIG_REFRESH_HOURS: &str = "fundamentals_refresh_hours";
+const DEFAULT_REFRESH_HOURS: i64 = 24;
+
+pub struct FundamentalsService;
+
+impl FundamentalsService {
+    /// Fetch fundamentals for one ticker from the providers and store them.
+    pub async fn fetch(
+        db: &Arc<Mutex<Database>>,
+        api_key_manager: &APIKeyManager,
+        rate_limiter: Option<&RateLimiter>,
+        cache: Option<&MarketDataCache>,
+        ticker: &str,
+    ) -> Result<Fundamentals> {
+        let ticker = ticker.trim().to_uppercase();
+        let manager = MarketDataManager::new(Some(api_key_manager));
+        let data = manager.get_fundamentals(&ticker, rate_limiter).await?;
+
+        let fundamentals = Fundamentals {
+            ticker: ticker.clone(),
+            name: data.name,
+            sector: data.sector,
+            industry: data.industry,
+            pe_ratio: data.pe_ratio,
+            eps: data.eps,
+            market_cap: data.market_cap,
+            dividend_yield: data.dividend_yield,
+            provider: manager.last_provider(),
+            fetched_at: chrono::Utc::now().timestamp(),
+        };
+
+        let conn = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            db_guard.conn.clone()
+        };
+        MarketDataStore::new(conn).upsert_fundamentals(&fundamentals)?;
+
+        if let Some(cache) = cache {
+            cache.set_fundamentals(&ticker, serde_json::to_value(&fundamentals)?, fundamentals.provider.as_deref());
+        }
+
+        Ok(fundamentals)
+    }
+
+    /// Refresh every held or alerted ticker whose fundamentals are older than
+    /// the configured interval. Returns how many were updated.
+    pub async fn refresh_stale(
+        db: &Arc<Mutex<Database>>,
+        api_key_manager: &APIKeyManager,
+        rate_limiter: Option<&RateLimiter>,
+        cache: Option<&MarketDataCache>,
+    ) -> Result<usize> {
+        let tickers = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let hours = Self::refresh_hours(&db_guard);
+            if hours <= 0 {
+                return Ok(0);
+            }
+            MarketDataStore::new(db_guard.conn.clone()).tickers_needing_fundamentals(hours * 3600)?
+        };
+
+        let mut refreshed = 0;
+        for ticker in tickers {
+            match Self::fetch(db, api_key_manager, rate_limiter, cache, &ticker).await {
+                Ok(_) => refreshed += 1,
+                Err(e) => eprintln!("Failed to refresh fundamentals for {}: {}", ticker, e),
+            }
+        }
+        Ok(refreshed)
+    }
+
+    pub fn start_scheduler(
+        db: Arc<Mutex<Database>>,
+        api_key_manager: Arc<APIKeyManager>,
+        rate_limiter: Arc<Mutex<RateLimiter>>,
+        cache: MarketDataCache,
+    ) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+
+            loop {
+                interval.tick().await;
+
+                let limiter = match rate_limiter.lock() {
+                    Ok(guard) => guard.clone(),
+                    Err(_) => continue,
+                };
+                match Self::refresh_stale(&db, &api_key_manager, Some(&limiter), Some(&cache)).await {
+                    Ok(n) if n > 0 => eprintln!("Refreshed fundamentals for {} tickers", n),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Fundamentals refresh failed: {}", e),
+                }
+            }
+        });
+    }
+
+    fn refresh_hours(db: &Database) -> i64 {
+        db.get_config(CONFIG_REFRESH_HOURS)
+            .ok()
+            .flatten()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_REFRESH_HOURS)
+    }
+}
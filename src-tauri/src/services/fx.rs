@@ -0,0 +1,139 @@
+use crate::providers::fx::{EcbProvider, FxProvider, FxRate};
+use crate::storage::fx::FxStore;
+use crate::storage::portfolio::PortfolioStore;
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Latest rates are re-fetched after this long; reference rates publish once a day.
+const LATEST_MAX_AGE_SECS: i64 = 6 * 3600;
+/// A stored rate this many days before the requested date still counts (weekends, holidays).
+const MAX_GAP_DAYS: i64 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conversion {
+    pub amount: f64,
+    pub from: String,
+    pub to: String,
+    pub rate: f64,
+    pub rate_date: String,
+    pub converted: f64,
+}
+
+pub struct FxService;
+
+impl FxService {
+    fn provider() -> Box<dyn FxProvider> {
+        Box::new(EcbProvider::new())
+    }
+
+    fn store(db: &Arc<Mutex<Database>>) -> Result<FxStore> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        Ok(FxStore::new(db_guard.conn.clone()))
+    }
+
+    /// Rate on `date` (YYYY-MM-DD) or the latest when None, from the local
+    /// cache when it's fresh enough, otherwise from the provider.
+    pub async fn get_rate(db: &Arc<Mutex<Database>>, base: &str, quote: &str, date: Option<&str>) -> Result<FxRate> {
+        let base = base.trim().to_uppercase();
+        let quote = quote.trim().to_uppercase();
+        let store = Self::store(db)?;
+
+        if let Some(cached) = store.get_rate(&base, &quote, date)? {
+            let usable = match date {
+                Some(d) => days_between(&cached.date, d).map(|gap| gap <= MAX_GAP_DAYS).unwrap_or(false),
+                None => store
+                    .last_fetched_at(&base, &quote)?
+                    .map(|at| chrono::Utc::now().timestamp() - at < LATEST_MAX_AGE_SECS)
+                    .unwrap_or(false),
+            };
+            if usable {
+                return Ok(cached);
+            }
+        }
+
+        let provider = Self::provider();
+        let rate = provider.get_rate(&base, &quote, date).await?;
+        store.upsert_rates(std::slice::from_ref(&rate), provider.get_name())?;
+        Ok(rate)
+    }
+
+    /// Daily rates between two dates, fetching from the provider when the
+    /// local series doesn't cover the range.
+    pub async fn get_history(db: &Arc<Mutex<Database>>, base: &str, quote: &str, from: &str, to: &str) -> Result<Vec<FxRate>> {
+        let base = base.trim().to_uppercase();
+        let quote = quote.trim().to_uppercase();
+        let store = Self::store(db)?;
+
+        let cached = store.get_history(&base, &quote, from, to)?;
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let effective_to = if to > today.as_str() { today.as_str() } else { to };
+        let covered = match (cached.first(), cached.last()) {
+            (Some(first), Some(last)) => {
+                days_between(from, &first.date).map(|gap| gap <= MAX_GAP_DAYS).unwrap_or(false)
+                    && days_between(&last.date, effective_to).map(|gap| gap <= MAX_GAP_DAYS).unwrap_or(false)
+            }
+            _ => false,
+        };
+        if covered {
+            return Ok(cached);
+        }
+
+        let provider = Self::provider();
+        let rates = provider.get_history(&base, &quote, from, to).await?;
+        store.upsert_rates(&rates, provider.get_name())?;
+        Ok(rates)
+    }
+
+    pub async fn convert(db: &Arc<Mutex<Database>>, amount: f64, from: &str, to: &str, date: Option<&str>) -> Result<Conversion> {
+        let rate = Self::get_rate(db, from, to, date).await?;
+        Ok(Conversion {
+            amount,
+            from: rate.base.clone(),
+            to: rate.quote.clone(),
+            rate: rate.rate,
+            rate_date: rate.date,
+            converted: amount * rate.rate,
+        })
+    }
+
+    /// Fetch today's rates for every holding currency that differs from its
+    /// portfolio's base currency, so valuation can run from the local cache.
+    pub async fn refresh_portfolio_pairs(db: &Arc<Mutex<Database>>) -> Result<usize> {
+        let pairs = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            PortfolioStore::new(db_guard.conn.clone()).currency_pairs()?
+        };
+
+        let mut refreshed = 0;
+        for (from, to) in pairs {
+            match Self::get_rate(db, &from, &to, None).await {
+                Ok(_) => refreshed += 1,
+                Err(e) => eprintln!("Failed to refresh {}/{} rate: {}", from, to, e),
+            }
+        }
+        Ok(refreshed)
+    }
+
+    pub fn start_scheduler(db: Arc<Mutex<Database>>) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(LATEST_MAX_AGE_SECS as u64));
+
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::refresh_portfolio_pairs(&db).await {
+                    eprintln!("FX refresh failed: {}", e);
+                }
+            }
+        });
+    }
+}
+
+fn days_between(earlier: &str, later: &str) -> Option<i64> {
+    let a = chrono::NaiveDate::parse_from_str(earlier, "%Y-%m-%d").ok()?;
+    let b = chrono::NaiveDate::parse_from_str(later, "%Y-%m-%d").ok()?;
+    Some((b - a).num_days().abs())
+}
@@ -1,4 +1,4 @@
-use crate::storage::{Database, StockNewsStore, TemporalStore, VectorStore};
+use crate::storage::{Database, StockNewsStore, TemporalStore, TranscriptStore, VectorStore};
 use crate::services::embeddings::EmbeddingService;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -23,6 +23,8 @@ pub enum SearchSource {
     Alert,
     Watchlist,
     Command,
+    Document,
+    Transcript,
 }
 
 pub struct GlobalSearchService;
@@ -84,17 +86,36 @@ impl GlobalSearchService {
         let store = TemporalStore::new(db_guard.conn.clone());
         
         let results = store.search(query, limit as i64)?;
+        let transcripts = TranscriptStore::new(db_guard.conn.clone());
         
-        Ok(results.into_iter().map(|r| {
+        Ok(results.into_iter().map(|mut r| {
             let title = r.get("title").and_then(|v| v.as_str()).unwrap_or("Event").to_string();
             let snippet = r.get("snippet").and_then(|v| v.as_str()).unwrap_or("").to_string();
             let doc_id = r.get("doc_id").and_then(|v| v.as_i64()).unwrap_or(0);
+            // Ingested documents share the index with articles and events
+            let (id, source) = match r.get("doc_type").and_then(|v| v.as_str()) {
+                Some("document") => (format!("document:{}", doc_id), SearchSource::Document),
+                // Point at the moment in the recording, for jump-to-timestamp
+                Some("transcript_segment") => match transcripts.get_segment(doc_id).ok().flatten() {
+                    Some(segment) => {
+                        r["transcript_id"] = serde_json::json!(segment.transcript_id);
+                        r["start_ms"] = serde_json::json!(segment.start_ms);
+                        r["end_ms"] = serde_json::json!(segment.end_ms);
+                        (
+                            format!("transcript:{}@{}", segment.transcript_id, segment.start_ms),
+                            SearchSource::Transcript,
+                        )
+                    }
+                    None => (format!("transcript_segment:{}", doc_id), SearchSource::Transcript),
+                },
+                _ => (format!("temporal:{}", doc_id), SearchSource::TemporalEvent),
+            };
             
             SearchResult {
-                id: format!("temporal:{}", doc_id),
+                id,
                 title,
                 snippet,
-                source: SearchSource::TemporalEvent,
+                source,
                 relevance: 0.8, // Temporal search already has relevance scoring
                 metadata: r,
             }
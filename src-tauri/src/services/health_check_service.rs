@@ -1,32 +1,54 @@
+use crate::services::health_checker::{CheckResult, HealthChecker};
+use crate::storage::devops::DevOpsStore;
+use crate::storage::Database;
 use anyhow::{Context, Result};
 use serde_json::json;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
 use tokio::net::TcpListener;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
 
+pub const PORT: u16 = 5433;
+
+/// Uptime in endpoint responses covers this many hours
+const UPTIME_HOURS: i64 = 24;
+
+/// Default connection strings - can be configured via environment variables
+pub(crate) fn database_url() -> String {
+    std::env::var("DATABASE_URL").unwrap_or_else(|_| "postgresql://localhost:5432/postgres".to_string())
+}
+
+pub(crate) fn redis_url() -> String {
+    std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string())
+}
+
+/// URL of the "database" or "redis" endpoint
+pub(crate) fn endpoint_url(kind: &str) -> String {
+    format!("http://127.0.0.1:{}/health/{}", PORT, kind)
+}
+
+/// Whether `url` points at this service's endpoint for `kind`, under any
+/// local host name.
+pub(crate) fn is_service_endpoint(url: &str, kind: &str) -> bool {
+    let url = url.trim().trim_end_matches('/').to_lowercase();
+    ["127.0.0.1", "localhost"]
+        .iter()
+        .any(|host| url == format!("http://{}:{}/health/{}", host, PORT, kind))
+}
+
 /// HTTP server that provides health check endpoints for Database and Redis
-/// This wraps native protocol checks (PostgreSQL, Redis) into HTTP endpoints
+/// This wraps native protocol checks (PostgreSQL, Redis) into HTTP endpoints.
+/// Checks run through `HealthChecker`, the same as the Tauri commands, and
+/// are recorded in the check's history.
 #[derive(Clone)]
 pub struct HealthCheckService {
     port: u16,
-    database_url: String,
-    redis_url: String,
+    db: Arc<Mutex<Database>>,
 }
 
 impl HealthCheckService {
-    pub fn new(port: u16) -> Self {
-        // Default connection strings - can be configured via environment variables
-        let database_url = std::env::var("DATABASE_URL")
-            .unwrap_or_else(|_| "postgresql://localhost:5432/postgres".to_string());
-        let redis_url = std::env::var("REDIS_URL")
-            .unwrap_or_else(|_| "redis://localhost:6379".to_string());
-        
-        HealthCheckService {
-            port,
-            database_url,
-            redis_url,
-        }
+    pub fn new(port: u16, db: Arc<Mutex<Database>>) -> Self {
+        HealthCheckService { port, db }
     }
 
     pub async fn start(&self) -> Result<()> {
@@ -212,61 +234,21 @@ impl HealthCheckService {
         // Handle GET requests
         if method == "GET" {
             let (status_code, body) = if path == "/health" || path == "/health/" {
-                // Overall health endpoint
+                // Overall health endpoint: the stored status of every check,
+                // as list_health_checks reports it
                 (200, json!({
                     "status": "healthy",
                     "service": "MINA Health Check Service",
                     "endpoints": {
                         "database": format!("http://127.0.0.1:{}/health/database", service.port),
                         "redis": format!("http://127.0.0.1:{}/health/redis", service.port),
-                    }
+                    },
+                    "checks": service.checks_summary().unwrap_or_default(),
                 }))
             } else if path == "/health/database" {
-                // Database health check
-                match Self::check_database(&service.database_url).await {
-                    Ok((healthy, message, response_time)) => {
-                        let status_code = if healthy { 200 } else { 503 };
-                        (status_code, json!({
-                            "status": if healthy { "healthy" } else { "unhealthy" },
-                            "service": "PostgreSQL Database",
-                            "message": message,
-                            "response_time_ms": response_time,
-                            "timestamp": chrono::Utc::now().to_rfc3339(),
-                        }))
-                    }
-                    Err(e) => {
-                        (503, json!({
-                            "status": "unhealthy",
-                            "service": "PostgreSQL Database",
-                            "message": format!("Health check failed: {}", e),
-                            "error": e.to_string(),
-                            "timestamp": chrono::Utc::now().to_rfc3339(),
-                        }))
-                    }
-                }
+                service.endpoint_response("database", "Database", "PostgreSQL Database").await
             } else if path == "/health/redis" {
-                // Redis health check
-                match Self::check_redis(&service.redis_url).await {
-                    Ok((healthy, message, response_time)) => {
-                        let status_code = if healthy { 200 } else { 503 };
-                        (status_code, json!({
-                            "status": if healthy { "healthy" } else { "unhealthy" },
-                            "service": "Redis",
-                            "message": message,
-                            "response_time_ms": response_time,
-                            "timestamp": chrono::Utc::now().to_rfc3339(),
-                        }))
-                    }
-                    Err(e) => {
-                        (503, json!({
-                            "status": "unhealthy",
-                            "service": "Redis",
-                            "message": format!("Health check failed: {}", e),
-                            "error": e.to_string(),
-                            "timestamp": chrono::Utc::now().to_rfc3339(),
-                        }))
-                    }
-                }
+                service.endpoint_response("redis", "Redis", "Redis").await
             } else {
                 // 404 for unknown paths
                 (404, json!({
@@ -299,116 +281,56 @@ impl HealthCheckService {
         Ok(())
     }
 
-    async fn check_database(url: &str) -> Result<(bool, String, u64)> {
-        let start = Instant::now();
-        
-        // Try to connect to PostgreSQL with a short timeout
-        match tokio::time::timeout(
-            std::time::Duration::from_secs(5),
-            tokio_postgres::connect(url, tokio_postgres::NoTls)
-        ).await {
-            Ok(Ok((client, connection))) => {
-                // Spawn connection task to handle the connection
-                tauri::async_runtime::spawn(async move {
-                    if let Err(e) = connection.await {
-                        eprintln!("Database connection error: {}", e);
-                    }
-                });
-                
-                // Try a simple query
-                match tokio::time::timeout(
-                    std::time::Duration::from_secs(2),
-                    client.query_one("SELECT 1", &[])
-                ).await {
-                    Ok(Ok(_)) => {
-                        let response_time = start.elapsed().as_millis() as u64;
-                        Ok((true, "Database is healthy and responding".to_string(), response_time))
-                    }
-                    Ok(Err(e)) => {
-                        let response_time = start.elapsed().as_millis() as u64;
-                        Ok((false, format!("Database query failed: {}", e), response_time))
-                    }
-                    Err(_) => {
-                        let response_time = start.elapsed().as_millis() as u64;
-                        Ok((false, "Database query timeout".to_string(), response_time))
-                    }
-                }
-            }
-            Ok(Err(e)) => {
-                let response_time = start.elapsed().as_millis() as u64;
-                let error_msg = if e.to_string().contains("Connection refused") {
-                    // Try to start PostgreSQL automatically
-                    let _ = Self::try_start_postgres().await;
-                    "Database connection refused: Attempted to start PostgreSQL service. Please wait a moment and check again."
-                } else if e.to_string().contains("timeout") {
-                    "Database connection timeout: PostgreSQL is not responding."
-                } else {
-                    &format!("Database connection failed: {}", e)
-                };
-                Ok((false, error_msg.to_string(), response_time))
-            }
-            Err(_) => {
-                let response_time = start.elapsed().as_millis() as u64;
-                Ok((false, "Database connection timeout: PostgreSQL did not respond within 5 seconds".to_string(), response_time))
-            }
-        }
+    /// Every configured check with its stored status and uptime.
+    fn checks_summary(&self) -> Result<Vec<serde_json::Value>> {
+        let db_guard = self.db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let checks = DevOpsStore::new(db_guard.conn.clone()).list_health_checks()?;
+        let uptime = HealthChecker::uptime(&db_guard, None, UPTIME_HOURS)?;
+        Ok(checks
+            .into_iter()
+            .map(|check| {
+                let stats = uptime.iter().find(|u| u.name == check.name);
+                json!({ "check": check, "uptime": stats })
+            })
+            .collect())
     }
 
-    async fn check_redis(url: &str) -> Result<(bool, String, u64)> {
-        let start = Instant::now();
-        
-        // Parse Redis URL and create client (synchronous operation)
-        let client = match redis::Client::open(url) {
-            Ok(client) => client,
+    async fn endpoint_response(&self, kind: &str, default_name: &str, label: &str) -> (u16, serde_json::Value) {
+        let result = match HealthChecker::check_service_endpoint(kind, default_name, &self.db).await {
+            Ok(result) => result,
             Err(e) => {
-                let response_time = start.elapsed().as_millis() as u64;
-                return Ok((false, format!("Failed to create Redis client: {}", e), response_time));
+                return (503, json!({
+                    "status": "unhealthy",
+                    "service": label,
+                    "message": format!("Health check failed: {}", e),
+                    "error": e.to_string(),
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                }));
             }
         };
-        
-        // Try to get a connection and ping
-        match tokio::time::timeout(
-            std::time::Duration::from_secs(5),
-            client.get_async_connection()
-        ).await {
-            Ok(Ok(mut conn)) => {
-                // Try PING command
-                match tokio::time::timeout(
-                    std::time::Duration::from_secs(2),
-                    redis::cmd("PING").query_async::<_, String>(&mut conn)
-                ).await {
-                    Ok(Ok(_)) => {
-                        let response_time = start.elapsed().as_millis() as u64;
-                        Ok((true, "Redis is healthy and responding".to_string(), response_time))
-                    }
-                    Ok(Err(e)) => {
-                        let response_time = start.elapsed().as_millis() as u64;
-                        Ok((false, format!("Redis PING failed: {}", e), response_time))
-                    }
-                    Err(_) => {
-                        let response_time = start.elapsed().as_millis() as u64;
-                        Ok((false, "Redis PING timeout".to_string(), response_time))
-                    }
-                }
-            }
-            Ok(Err(e)) => {
-                let response_time = start.elapsed().as_millis() as u64;
-                let error_msg = if e.to_string().contains("Connection refused") {
-                    // Try to start Redis automatically
-                    let _ = Self::try_start_redis().await;
-                    "Redis connection refused: Attempted to start Redis service. Please wait a moment and check again."
-                } else if e.to_string().contains("timeout") {
-                    "Redis connection timeout: Redis is not responding."
-                } else {
-                    &format!("Redis connection failed: {}", e)
-                };
-                Ok((false, error_msg.to_string(), response_time))
-            }
-            Err(_) => {
-                let response_time = start.elapsed().as_millis() as u64;
-                Ok((false, "Redis connection timeout: Redis did not respond within 5 seconds".to_string(), response_time))
-            }
-        }
+        let uptime = self.db.lock().ok().and_then(|db| {
+            HealthChecker::uptime(&db, Some(&result.name), UPTIME_HOURS).ok()?.into_iter().next()
+        });
+        (if result.is_healthy() { 200 } else { 503 }, Self::result_json(&result, label, uptime))
+    }
+
+    fn result_json(
+        result: &CheckResult,
+        label: &str,
+        uptime: Option<crate::storage::devops::UptimeStats>,
+    ) -> serde_json::Value {
+        json!({
+            "status": result.status,
+            "service": label,
+            "check": result.name,
+            "message": result.message,
+            "response_time_ms": result.response_time,
+            "timestamp": chrono::DateTime::from_timestamp(result.checked_at, 0)
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default(),
+            "uptime": uptime,
+        })
     }
 
     pub fn get_port(&self) -> u16 {
@@ -416,7 +338,7 @@ impl HealthCheckService {
     }
 
     /// Try to start PostgreSQL service
-    async fn try_start_postgres() -> Result<(), String> {
+    pub(crate) async fn try_start_postgres() -> Result<(), String> {
         if !Self::is_homebrew_available() {
             return Err("Homebrew not available".to_string());
         }
@@ -436,7 +358,7 @@ impl HealthCheckService {
     }
 
     /// Try to start Redis service
-    async fn try_start_redis() -> Result<(), String> {
+    pub(crate) async fn try_start_redis() -> Result<(), String> {
         if !Self::is_homebrew_available() {
             return Err("Homebrew not available".to_string());
         }
@@ -1,9 +1,41 @@
-use crate::storage::devops::DevOpsStore;
+use crate::services::health_check_service::{self, HealthCheckService};
+use crate::services::ssh_tunnels::TunnelManager;
+use crate::storage::devops::{DevOpsStore, HealthCheck, UptimeStats};
 use crate::storage::Database;
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
+/// Per-check history is kept this long
+const HISTORY_RETENTION_SECS: i64 = 30 * 86400;
+
+/// Outcome of one check. The scheduler, the Tauri commands and the HTTP
+/// endpoints all produce it through `HealthChecker`, so they agree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: String, // "healthy", "unhealthy"
+    pub message: String,
+    pub response_time: i64, // milliseconds
+    pub checked_at: i64,
+}
+
+impl CheckResult {
+    pub fn is_healthy(&self) -> bool {
+        self.status == "healthy"
+    }
+}
+
+/// How a check's URL is probed. The HTTP service's own /health/database
+/// and /health/redis URLs resolve to the native probes they wrap, so the
+/// scheduler never calls back into the HTTP server.
+enum Probe {
+    Postgres(String),
+    Redis(String),
+    Http(String),
+}
+
 pub struct HealthChecker;
 
 impl HealthChecker {
@@ -29,6 +61,8 @@ impl HealthChecker {
             let db_guard = db.lock()
                 .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
             let store = DevOpsStore::new(db_guard.conn.clone());
+            store.prune_health_check_history(chrono::Utc::now().timestamp() - HISTORY_RETENTION_SECS)
+                .context("Failed to prune health check history")?;
             store.list_health_checks()
                 .context("Failed to list health checks")?
         };
@@ -38,48 +72,142 @@ impl HealthChecker {
         for check in checks {
             let db_clone = db.clone();
             let task = tokio::spawn(async move {
-                Self::check_single_health_check(&check.name, &check.url, &db_clone).await
+                Self::run(&check, &db_clone).await
             });
             tasks.push(task);
         }
 
         // Wait for all checks to complete
         for task in tasks {
-            if let Err(e) = task.await {
-                eprintln!("Health check task error: {}", e);
+            match task.await {
+                Ok(Err(e)) => eprintln!("Health check error: {}", e),
+                Err(e) => eprintln!("Health check task error: {}", e),
+                Ok(Ok(_)) => {}
             }
         }
 
         Ok(())
     }
 
-    /// Check a single health check URL and update its status
-    async fn check_single_health_check(
+    /// Probe a check and record the outcome as its status and in its history
+    pub async fn run(check: &HealthCheck, db: &Arc<Mutex<Database>>) -> Result<CheckResult> {
+        let result = Self::probe(&check.name, &check.url).await;
+
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let store = DevOpsStore::new(db_guard.conn.clone());
+        let error = (!result.is_healthy()).then_some(result.message.as_str());
+        store.record_health_check(&check.name, &result.status, Some(result.response_time), error, result.checked_at)
+            .context(format!("Failed to update health check: {}", check.name))?;
+
+        Ok(result)
+    }
+
+    /// Manually trigger a health check for a specific check
+    pub async fn check_health_check(
         name: &str,
-        url: &str,
         db: &Arc<Mutex<Database>>,
-    ) -> Result<()> {
+    ) -> Result<CheckResult> {
+        let check = Self::find(name, db)?
+            .ok_or_else(|| anyhow::anyhow!("Health check not found: {}", name))?;
+        Self::run(&check, db).await
+    }
+
+    /// Run the configured check behind one of the HTTP service's endpoints
+    /// ("database" or "redis"). Without one, the endpoint is probed under
+    /// `default_name` but nothing is recorded.
+    pub async fn check_service_endpoint(
+        kind: &str,
+        default_name: &str,
+        db: &Arc<Mutex<Database>>,
+    ) -> Result<CheckResult> {
+        let configured = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            DevOpsStore::new(db_guard.conn.clone())
+                .list_health_checks()
+                .context("Failed to list health checks")?
+                .into_iter()
+                .find(|c| health_check_service::is_service_endpoint(&c.url, kind))
+        };
+
+        match configured {
+            Some(check) => Self::run(&check, db).await,
+            None => Ok(Self::probe(default_name, &health_check_service::endpoint_url(kind)).await),
+        }
+    }
+
+    /// Uptime of one check, or of every check, over the last `hours`.
+    pub fn uptime(db: &Database, name: Option<&str>, hours: i64) -> Result<Vec<UptimeStats>> {
+        let store = DevOpsStore::new(db.conn.clone());
+        let since = chrono::Utc::now().timestamp() - hours.max(1) * 3600;
+        let names: Vec<String> = match name {
+            Some(name) => vec![name.to_string()],
+            None => store.list_health_checks()?.into_iter().map(|c| c.name).collect(),
+        };
+        names.iter().map(|name| store.health_check_uptime(name, since)).collect()
+    }
+
+    fn find(name: &str, db: &Arc<Mutex<Database>>) -> Result<Option<HealthCheck>> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let store = DevOpsStore::new(db_guard.conn.clone());
+        let checks = store.list_health_checks()
+            .context("Failed to list health checks")?;
+        Ok(checks.into_iter().find(|c| c.name == name))
+    }
+
+    fn resolve(name: &str, url: &str) -> Probe {
+        let lower = url.trim().to_lowercase();
+        if health_check_service::is_service_endpoint(url, "database") {
+            Probe::Postgres(TunnelManager::route_url("postgres", &health_check_service::database_url()))
+        } else if health_check_service::is_service_endpoint(url, "redis") {
+            Probe::Redis(TunnelManager::route_url("redis", &health_check_service::redis_url()))
+        } else if lower.starts_with("postgres://") || lower.starts_with("postgresql://") {
+            Probe::Postgres(TunnelManager::route_health_check(name, url))
+        } else if lower.starts_with("redis://") || lower.starts_with("rediss://") {
+            Probe::Redis(TunnelManager::route_health_check(name, url))
+        } else {
+            // Remote services behind an SSH tunnel are checked through it
+            Probe::Http(TunnelManager::route_health_check(name, url))
+        }
+    }
+
+    /// Probe a URL without recording anything
+    pub async fn probe(name: &str, url: &str) -> CheckResult {
         let start_time = Instant::now();
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .context("Failed to create HTTP client")?;
+        let (healthy, message) = match Self::resolve(name, url) {
+            Probe::Postgres(url) => Self::probe_postgres(&url).await,
+            Probe::Redis(url) => Self::probe_redis(&url).await,
+            Probe::Http(url) => Self::probe_http(&url).await,
+        };
 
-        let result = client
-            .get(url)
-            .send()
-            .await;
+        CheckResult {
+            name: name.to_string(),
+            status: if healthy { "healthy" } else { "unhealthy" }.to_string(),
+            message,
+            response_time: start_time.elapsed().as_millis() as i64,
+            checked_at: chrono::Utc::now().timestamp(),
+        }
+    }
 
-        let response_time = start_time.elapsed().as_millis() as i64;
+    async fn probe_http(url: &str) -> (bool, String) {
+        let client = match reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+        {
+            Ok(client) => client,
+            Err(e) => return (false, format!("Failed to create HTTP client: {}", e)),
+        };
 
-        let (status, error) = match result {
+        match client.get(url).send().await {
             Ok(response) => {
                 if response.status().is_success() {
-                    ("healthy", None)
+                    (true, format!("HTTP {}", response.status().as_u16()))
                 } else {
                     (
-                        "unhealthy",
-                        Some(format!("HTTP {}: {}", response.status(), response.status().canonical_reason().unwrap_or("Unknown")))
+                        false,
+                        format!("HTTP {}: {}", response.status(), response.status().canonical_reason().unwrap_or("Unknown"))
                     )
                 }
             }
@@ -100,7 +228,7 @@ impl HealthChecker {
                     };
                     format!("Connection refused: {} may not be running. Attempting to start if available...", service_name)
                 } else if e.is_timeout() {
-                    format!("Request timeout: Service did not respond within 10 seconds")
+                    "Request timeout: Service did not respond within 10 seconds".to_string()
                 } else if e.is_request() {
                     format!("Invalid request: {}", e)
                 } else if e.is_decode() {
@@ -110,9 +238,9 @@ impl HealthChecker {
                     let error_str = e.to_string();
                     if error_str.contains("error sending request") {
                         if url.contains(":5432") {
-                            format!("Cannot connect: PostgreSQL database (port 5432) does not expose HTTP endpoints. Use a database health check API instead.")
+                            "Cannot connect: PostgreSQL database (port 5432) does not expose HTTP endpoints. Use a postgres:// URL instead.".to_string()
                         } else if url.contains(":6379") {
-                            format!("Cannot connect: Redis (port 6379) does not expose HTTP endpoints. Use a Redis health check API instead.")
+                            "Cannot connect: Redis (port 6379) does not expose HTTP endpoints. Use a redis:// URL instead.".to_string()
                         } else {
                             format!("Connection failed: {}", error_str)
                         }
@@ -120,47 +248,94 @@ impl HealthChecker {
                         format!("Request failed: {}", error_str)
                     }
                 };
-                (
-                    "unhealthy",
-                    Some(error_msg)
-                )
+                (false, error_msg)
             }
-        };
+        }
+    }
 
-        // Update the health check status
-        let db_guard = db.lock()
-            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
-        let store = DevOpsStore::new(db_guard.conn.clone());
-        store.update_health_check(name, status, Some(response_time), error.as_deref())
-            .context(format!("Failed to update health check: {}", name))?;
+    async fn probe_postgres(url: &str) -> (bool, String) {
+        // Try to connect to PostgreSQL with a short timeout
+        match tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            tokio_postgres::connect(url, tokio_postgres::NoTls)
+        ).await {
+            Ok(Ok((client, connection))) => {
+                // Spawn connection task to handle the connection
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = connection.await {
+                        eprintln!("Database connection error: {}", e);
+                    }
+                });
 
-        Ok(())
+                // Try a simple query
+                match tokio::time::timeout(
+                    std::time::Duration::from_secs(2),
+                    client.query_one("SELECT 1", &[])
+                ).await {
+                    Ok(Ok(_)) => (true, "Database is healthy and responding".to_string()),
+                    Ok(Err(e)) => (false, format!("Database query failed: {}", e)),
+                    Err(_) => (false, "Database query timeout".to_string()),
+                }
+            }
+            Ok(Err(e)) => {
+                let error_msg = if e.to_string().contains("Connection refused") {
+                    // Try to start PostgreSQL automatically
+                    let _ = HealthCheckService::try_start_postgres().await;
+                    "Database connection refused: Attempted to start PostgreSQL service. Please wait a moment and check again.".to_string()
+                } else if e.to_string().contains("timeout") {
+                    "Database connection timeout: PostgreSQL is not responding.".to_string()
+                } else {
+                    format!("Database connection failed: {}", e)
+                };
+                (false, error_msg)
+            }
+            Err(_) => (false, "Database connection timeout: PostgreSQL did not respond within 5 seconds".to_string()),
+        }
     }
 
-    /// Manually trigger a health check for a specific check
-    pub async fn check_health_check(
-        name: &str,
-        db: &Arc<Mutex<Database>>,
-    ) -> Result<()> {
-        // Get the health check
-        let check = {
-            let db_guard = db.lock()
-                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
-            let store = DevOpsStore::new(db_guard.conn.clone());
-            let checks = store.list_health_checks()
-                .context("Failed to list health checks")?;
-            checks.into_iter()
-                .find(|c| c.name == name)
-                .ok_or_else(|| anyhow::anyhow!("Health check not found: {}", name))?
+    async fn probe_redis(url: &str) -> (bool, String) {
+        // Parse Redis URL and create client (synchronous operation)
+        let client = match redis::Client::open(url) {
+            Ok(client) => client,
+            Err(e) => return (false, format!("Failed to create Redis client: {}", e)),
         };
 
-        Self::check_single_health_check(&check.name, &check.url, db).await
+        // Try to get a connection and ping
+        match tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            client.get_async_connection()
+        ).await {
+            Ok(Ok(mut conn)) => {
+                // Try PING command
+                match tokio::time::timeout(
+                    std::time::Duration::from_secs(2),
+                    redis::cmd("PING").query_async::<_, String>(&mut conn)
+                ).await {
+                    Ok(Ok(_)) => (true, "Redis is healthy and responding".to_string()),
+                    Ok(Err(e)) => (false, format!("Redis PING failed: {}", e)),
+                    Err(_) => (false, "Redis PING timeout".to_string()),
+                }
+            }
+            Ok(Err(e)) => {
+                let error_msg = if e.to_string().contains("Connection refused") {
+                    // Try to start Redis automatically
+                    let _ = HealthCheckService::try_start_redis().await;
+                    "Redis connection refused: Attempted to start Redis service. Please wait a moment and check again.".to_string()
+                } else if e.to_string().contains("timeout") {
+                    "Redis connection timeout: Redis is not responding.".to_string()
+                } else {
+                    format!("Redis connection failed: {}", e)
+                };
+                (false, error_msg)
+            }
+            Err(_) => (false, "Redis connection timeout: Redis did not respond within 5 seconds".to_string()),
+        }
     }
 
     /// Try to start Elasticsearch service via Homebrew
     async fn try_start_elasticsearch() -> Result<(), String> {
         use tokio::process::Command;
-        
+
         // Get Homebrew path
         fn get_brew_path() -> String {
             for brew_path in &["/opt/homebrew/bin/brew", "/usr/local/bin/brew", "brew"] {
@@ -175,7 +350,7 @@ impl HealthChecker {
         }
 
         let brew_path = get_brew_path();
-        
+
         // Check if Homebrew is available
         if !std::process::Command::new(&brew_path)
             .arg("--version")
@@ -185,7 +360,7 @@ impl HealthChecker {
         }
 
         let elasticsearch_services = vec!["elasticsearch", "elasticsearch-full"];
-        
+
         for service in elasticsearch_services {
             // Check if installed
             let installed = match Command::new(&brew_path)
@@ -208,8 +383,8 @@ impl HealthChecker {
                         if let Ok(stdout) = std::str::from_utf8(&output.stdout) {
                             stdout.lines().any(|line| {
                                 let parts: Vec<&str> = line.split_whitespace().collect();
-                                parts.len() >= 2 
-                                    && parts[0] == service 
+                                parts.len() >= 2
+                                    && parts[0] == service
                                     && (parts[1] == "started" || parts[1] == "running")
                             })
                         } else {
@@ -235,7 +410,7 @@ impl HealthChecker {
                 }
             }
         }
-        
+
         Err("Elasticsearch service not found".to_string())
     }
 }
@@ -0,0 +1,367 @@
+use crate::services::alert_templates::{AlertTemplates, MessageTemplate};
+use crate::services::api_key_manager::APIKeyManager;
+use crate::storage::temporal::{Alert, AlertIssue, AlertRule, TemporalStore};
+use crate::storage::Database;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// Issue tracker settings as JSON. A rule can override any of them under
+/// "issue_tracker" in its escalation config.
+pub const CONFIG_ISSUE_TRACKER: &str = "issue_tracker_config";
+
+pub const PROVIDER_JIRA: &str = "jira";
+pub const PROVIDER_LINEAR: &str = "linear";
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+const SYNC_INTERVAL_SECS: u64 = 15 * 60;
+
+const DEFAULT_SUBJECT: &str = "{{rule.name}}: {{event.title}}";
+const DEFAULT_BODY: &str = "Alert {{alert.id}} fired {{alert.fired_at}}.\n\n\
+Why: {{reasons}}\n\
+Severity {{scores.severity}} · novelty {{scores.novelty}} · sentiment {{scores.sentiment}}\n\
+Corroborated by {{corroboration.corroborated_by}} sources\n\n\
+Articles: {{links.articles}}";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueTrackerConfig {
+    pub provider: String, // jira|linear
+    /// Jira site, e.g. https://acme.atlassian.net
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Jira account the API token belongs to
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Jira project key or Linear team ID
+    pub project: String,
+    /// Jira issue type, "Task" by default
+    #[serde(default)]
+    pub issue_type: Option<String>,
+    /// Jira label names or Linear label IDs
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub template: Option<MessageTemplate>,
+    #[serde(default = "default_resolve_on_close")]
+    pub resolve_on_close: bool,
+}
+
+fn default_resolve_on_close() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssueSyncReport {
+    pub checked: usize,
+    pub closed: usize,
+    pub resolved_alerts: usize,
+    pub errors: Vec<String>,
+}
+
+struct RemoteStatus {
+    name: String,
+    closed: bool,
+}
+
+pub struct IssueTracker;
+
+impl IssueTracker {
+    pub fn config(db: &Database) -> Result<Option<IssueTrackerConfig>> {
+        match db.get_config(CONFIG_ISSUE_TRACKER)? {
+            Some(raw) if !raw.trim().is_empty() => Ok(Some(
+                serde_json::from_str(&raw).context("Invalid issue tracker config")?,
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    /// The global config with the rule's "issue_tracker" overrides applied.
+    pub fn config_for_rule(base: &IssueTrackerConfig, rule: Option<&AlertRule>) -> Result<IssueTrackerConfig> {
+        let overrides = rule
+            .and_then(|r| r.escalation_config.as_ref())
+            .and_then(|c| c.get("issue_tracker"))
+            .and_then(|v| v.as_object());
+        let Some(overrides) = overrides else {
+            return Ok(base.clone());
+        };
+        let mut merged = serde_json::to_value(base)?;
+        for (key, value) in overrides {
+            merged[key] = value.clone();
+        }
+        serde_json::from_value(merged).context("Invalid issue tracker override on rule")
+    }
+
+    /// Create an issue for the alert and link it. Fails if one already exists.
+    pub async fn create_issue(
+        store: &TemporalStore,
+        config: &IssueTrackerConfig,
+        api_key_manager: &APIKeyManager,
+        alert: &Alert,
+    ) -> Result<AlertIssue> {
+        if let Some(existing) = store.get_alert_issue(alert.id)? {
+            anyhow::bail!("Alert {} already has issue {}", alert.id, existing.issue_key);
+        }
+
+        let default_template = MessageTemplate {
+            subject: Some(DEFAULT_SUBJECT.to_string()),
+            body: Some(DEFAULT_BODY.to_string()),
+        };
+        let template = config.template.as_ref().unwrap_or(&default_template);
+        let message = AlertTemplates::render(&config.provider, Some(template), &AlertTemplates::variables(alert));
+
+        let token = api_key_manager
+            .get_key_optional(&config.provider)?
+            .ok_or_else(|| anyhow::anyhow!("No API token stored for {}", config.provider))?;
+        let client = reqwest::Client::new();
+
+        let mut issue = match config.provider.as_str() {
+            PROVIDER_JIRA => create_jira_issue(&client, config, &token, &message.subject, &message.body).await?,
+            PROVIDER_LINEAR => create_linear_issue(&client, config, &token, &message.subject, &message.body).await?,
+            other => anyhow::bail!("Unknown issue tracker: {}", other),
+        };
+        issue.alert_id = alert.id;
+        store.save_alert_issue(&issue)?;
+        Ok(issue)
+    }
+
+    /// Refresh the status of every open issue and resolve alerts whose issue closed.
+    pub async fn sync(
+        store: &TemporalStore,
+        config: &IssueTrackerConfig,
+        api_key_manager: &APIKeyManager,
+    ) -> Result<IssueSyncReport> {
+        let client = reqwest::Client::new();
+        let mut report = IssueSyncReport::default();
+
+        for issue in store.list_open_alert_issues()? {
+            let token = match api_key_manager.get_key_optional(&issue.provider)? {
+                Some(token) => token,
+                None => {
+                    report.errors.push(format!("{}: no API token stored for {}", issue.issue_key, issue.provider));
+                    continue;
+                }
+            };
+            let status = match issue.provider.as_str() {
+                PROVIDER_JIRA => jira_status(&client, config, &token, &issue.issue_key).await,
+                PROVIDER_LINEAR => linear_status(&client, &token, &issue.issue_id).await,
+                other => Err(anyhow::anyhow!("Unknown issue tracker: {}", other)),
+            };
+            report.checked += 1;
+
+            match status {
+                Ok(status) => {
+                    store.update_alert_issue_status(issue.alert_id, &status.name, status.closed)?;
+                    if status.closed {
+                        report.closed += 1;
+                        if config.resolve_on_close {
+                            store.update_alert_status(issue.alert_id, "resolved", None)?;
+                            report.resolved_alerts += 1;
+                        }
+                    }
+                }
+                Err(e) => report.errors.push(format!("{}: {}", issue.issue_key, e)),
+            }
+        }
+
+        Ok(report)
+    }
+
+    pub fn start_scheduler(db: Arc<Mutex<Database>>, api_key_manager: Arc<APIKeyManager>) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(SYNC_INTERVAL_SECS));
+
+            loop {
+                interval.tick().await;
+                let (store, config) = {
+                    let db_guard = match db.lock() {
+                        Ok(guard) => guard,
+                        Err(_) => continue,
+                    };
+                    match Self::config(&db_guard) {
+                        Ok(Some(config)) => (TemporalStore::new(db_guard.conn.clone()), config),
+                        Ok(None) => continue,
+                        Err(e) => {
+                            eprintln!("Issue tracker sync skipped: {}", e);
+                            continue;
+                        }
+                    }
+                };
+                match Self::sync(&store, &config, &api_key_manager).await {
+                    Ok(report) if report.closed > 0 => {
+                        eprintln!("Issue sync: {} issues closed, {} alerts resolved", report.closed, report.resolved_alerts)
+                    }
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Issue tracker sync failed: {}", e),
+                }
+            }
+        });
+    }
+}
+
+fn jira_base(config: &IssueTrackerConfig) -> Result<String> {
+    config
+        .base_url
+        .as_deref()
+        .map(|u| u.trim_end_matches('/').to_string())
+        .filter(|u| !u.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Jira base_url not configured"))
+}
+
+fn jira_email(config: &IssueTrackerConfig) -> Result<&str> {
+    config
+        .email
+        .as_deref()
+        .filter(|e| !e.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Jira account email not configured"))
+}
+
+async fn create_jira_issue(
+    client: &reqwest::Client,
+    config: &IssueTrackerConfig,
+    token: &str,
+    summary: &str,
+    description: &str,
+) -> Result<AlertIssue> {
+    let base = jira_base(config)?;
+    // Jira Cloud v3 takes descriptions in Atlassian Document Format
+    let paragraphs: Vec<Value> = description
+        .split("\n\n")
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| serde_json::json!({ "type": "paragraph", "content": [{ "type": "text", "text": p }] }))
+        .collect();
+    let body = serde_json::json!({
+        "fields": {
+            "project": { "key": config.project },
+            "summary": summary.chars().take(255).collect::<String>(),
+            "description": { "type": "doc", "version": 1, "content": paragraphs },
+            "issuetype": { "name": config.issue_type.as_deref().unwrap_or("Task") },
+            "labels": config.labels,
+        }
+    });
+
+    let response = client
+        .post(format!("{}/rest/api/3/issue", base))
+        .basic_auth(jira_email(config)?, Some(token))
+        .json(&body)
+        .send()
+        .await
+        .context("Failed to send Jira request")?;
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        anyhow::bail!("Jira API error: {} - {}", status, error_text);
+    }
+    let created: Value = response.json().await.context("Failed to parse Jira response")?;
+    let key = created.get("key").and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("Jira response has no issue key"))?;
+
+    Ok(AlertIssue {
+        alert_id: 0,
+        provider: PROVIDER_JIRA.to_string(),
+        issue_id: created.get("id").and_then(|v| v.as_str()).unwrap_or(key).to_string(),
+        issue_key: key.to_string(),
+        issue_url: Some(format!("{}/browse/{}", base, key)),
+        status: None,
+        closed: false,
+        created_at: chrono::Utc::now().timestamp(),
+        synced_at: None,
+    })
+}
+
+async fn jira_status(
+    client: &reqwest::Client,
+    config: &IssueTrackerConfig,
+    token: &str,
+    key: &str,
+) -> Result<RemoteStatus> {
+    let response = client
+        .get(format!("{}/rest/api/3/issue/{}?fields=status", jira_base(config)?, key))
+        .basic_auth(jira_email(config)?, Some(token))
+        .send()
+        .await
+        .context("Failed to send Jira request")?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("Jira API error: {}", status);
+    }
+    let issue: Value = response.json().await.context("Failed to parse Jira response")?;
+    let issue_status = &issue["fields"]["status"];
+    Ok(RemoteStatus {
+        name: issue_status["name"].as_str().unwrap_or("unknown").to_string(),
+        closed: issue_status["statusCategory"]["key"].as_str() == Some("done"),
+    })
+}
+
+async fn linear_request(client: &reqwest::Client, token: &str, query: &str, variables: Value) -> Result<Value> {
+    let response = client
+        .post(LINEAR_API_URL)
+        .header("Authorization", token)
+        .json(&serde_json::json!({ "query": query, "variables": variables }))
+        .send()
+        .await
+        .context("Failed to send Linear request")?;
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        anyhow::bail!("Linear API error: {} - {}", status, error_text);
+    }
+    let body: Value = response.json().await.context("Failed to parse Linear response")?;
+    if let Some(message) = body["errors"][0]["message"].as_str() {
+        anyhow::bail!("Linear API error: {}", message);
+    }
+    Ok(body["data"].clone())
+}
+
+async fn create_linear_issue(
+    client: &reqwest::Client,
+    config: &IssueTrackerConfig,
+    token: &str,
+    title: &str,
+    description: &str,
+) -> Result<AlertIssue> {
+    let data = linear_request(
+        client,
+        token,
+        "mutation($input: IssueCreateInput!) { issueCreate(input: $input) { success issue { id identifier url state { name } } } }",
+        serde_json::json!({
+            "input": {
+                "teamId": config.project,
+                "title": title,
+                "description": description,
+                "labelIds": config.labels,
+            }
+        }),
+    )
+    .await?;
+    let issue = &data["issueCreate"]["issue"];
+    let (Some(id), Some(identifier)) = (issue["id"].as_str(), issue["identifier"].as_str()) else {
+        anyhow::bail!("Linear did not return the created issue");
+    };
+
+    Ok(AlertIssue {
+        alert_id: 0,
+        provider: PROVIDER_LINEAR.to_string(),
+        issue_id: id.to_string(),
+        issue_key: identifier.to_string(),
+        issue_url: issue["url"].as_str().map(|s| s.to_string()),
+        status: issue["state"]["name"].as_str().map(|s| s.to_string()),
+        closed: false,
+        created_at: chrono::Utc::now().timestamp(),
+        synced_at: None,
+    })
+}
+
+async fn linear_status(client: &reqwest::Client, token: &str, issue_id: &str) -> Result<RemoteStatus> {
+    let data = linear_request(
+        client,
+        token,
+        "query($id: String!) { issue(id: $id) { state { name type } } }",
+        serde_json::json!({ "id": issue_id }),
+    )
+    .await?;
+    let state = &data["issue"]["state"];
+    Ok(RemoteStatus {
+        name: state["name"].as_str().unwrap_or("unknown").to_string(),
+        closed: matches!(state["type"].as_str(), Some("completed") | Some("canceled")),
+    })
+}
@@ -0,0 +1,242 @@
+use crate::storage::{Job, JobStore};
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+use tokio::sync::Semaphore;
+
+const HISTORY_RETENTION_SECS: i64 = 30 * 86_400;
+
+/// Jobs of one class share a concurrency limit, so a burst of feed
+/// fetches can't starve event rebuilds and vice versa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobClass {
+    /// Mostly waiting on remote APIs and feeds
+    Network,
+    /// CPU heavy: embeddings, OCR, transcription, exports
+    Compute,
+    /// Long write transactions, one at a time
+    Database,
+}
+
+impl JobClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobClass::Network => "network",
+            JobClass::Compute => "compute",
+            JobClass::Database => "database",
+        }
+    }
+
+    fn concurrency(&self) -> usize {
+        match self {
+            JobClass::Network => 4,
+            JobClass::Compute => 2,
+            JobClass::Database => 1,
+        }
+    }
+}
+
+/// Handed to a running job to report progress and notice cancellation.
+/// Cancellation is cooperative: long loops should check `is_cancelled`.
+#[derive(Clone)]
+pub struct JobContext {
+    id: String,
+    cancelled: Arc<AtomicBool>,
+    manager: JobManager,
+}
+
+impl JobContext {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Error out of the job if it was cancelled.
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.is_cancelled() {
+            anyhow::bail!("Job cancelled");
+        }
+        Ok(())
+    }
+
+    /// `progress` is the done fraction, 0..1.
+    pub fn progress(&self, progress: f64, message: impl Into<String>) {
+        let message = message.into();
+        self.manager.update(&self.id, |job| {
+            job.progress = progress.clamp(0.0, 1.0);
+            job.message = Some(message);
+        });
+    }
+}
+
+struct ActiveJob {
+    job: Job,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Central queue for background work. Every job is persisted with its
+/// status and progress, and each change is pushed to the UI as a
+/// `job-progress` WebSocket message.
+#[derive(Clone)]
+pub struct JobManager {
+    app: AppHandle,
+    store: Arc<JobStore>,
+    active: Arc<Mutex<HashMap<String, ActiveJob>>>,
+    limits: Arc<HashMap<JobClass, Arc<Semaphore>>>,
+}
+
+impl JobManager {
+    pub fn new(app: AppHandle, conn: Arc<Mutex<Connection>>) -> Self {
+        let store = JobStore::new(conn);
+        if let Err(e) = store.mark_interrupted() {
+            eprintln!("Failed to mark interrupted jobs: {}", e);
+        }
+        let _ = store.delete_finished_before(chrono::Utc::now().timestamp() - HISTORY_RETENTION_SECS);
+
+        let limits = [JobClass::Network, JobClass::Compute, JobClass::Database]
+            .into_iter()
+            .map(|class| (class, Arc::new(Semaphore::new(class.concurrency()))))
+            .collect();
+        JobManager {
+            app,
+            store: Arc::new(store),
+            active: Arc::new(Mutex::new(HashMap::new())),
+            limits: Arc::new(limits),
+        }
+    }
+
+    /// Queue a job. A job with the same name that is still queued or
+    /// running is returned instead of starting a second one.
+    pub fn spawn<F, Fut>(&self, name: &str, class: JobClass, params: serde_json::Value, run: F) -> Result<Job>
+    where
+        F: FnOnce(JobContext) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<serde_json::Value>> + Send + 'static,
+    {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let job = {
+            let mut active = self.active.lock()
+                .map_err(|e| anyhow::anyhow!("Job registry lock error: {}", e))?;
+            if let Some(existing) = active.values().find(|a| a.job.name == name) {
+                return Ok(existing.job.clone());
+            }
+            let job = Job {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: name.to_string(),
+                class: class.as_str().to_string(),
+                status: "queued".to_string(),
+                progress: 0.0,
+                message: None,
+                params,
+                result: None,
+                error: None,
+                created_at: chrono::Utc::now().timestamp(),
+                started_at: None,
+                finished_at: None,
+            };
+            active.insert(job.id.clone(), ActiveJob { job: job.clone(), cancelled: cancelled.clone() });
+            job
+        };
+        self.persist(&job);
+
+        let manager = self.clone();
+        let semaphore = self.limits[&class].clone();
+        let ctx = JobContext { id: job.id.clone(), cancelled: cancelled.clone(), manager: self.clone() };
+        tauri::async_runtime::spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            if cancelled.load(Ordering::Relaxed) {
+                manager.finish(&ctx.id, Err(anyhow::anyhow!("Job cancelled")), true);
+                return;
+            }
+            manager.update(&ctx.id, |job| {
+                job.status = "running".to_string();
+                job.started_at = Some(chrono::Utc::now().timestamp());
+            });
+
+            let id = ctx.id.clone();
+            let result = run(ctx).await;
+            manager.finish(&id, result, cancelled.load(Ordering::Relaxed));
+        });
+
+        Ok(job)
+    }
+
+    /// Ask a queued or running job to stop. Returns false when it isn't active.
+    pub fn cancel(&self, id: &str) -> bool {
+        let Ok(active) = self.active.lock() else { return false };
+        match active.get(id) {
+            Some(entry) => {
+                entry.cancelled.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Result<Option<Job>> {
+        if let Ok(active) = self.active.lock() {
+            if let Some(entry) = active.get(id) {
+                return Ok(Some(entry.job.clone()));
+            }
+        }
+        self.store.get_job(id)
+    }
+
+    /// Active jobs and history, newest first.
+    pub fn list(&self, status: Option<&str>, limit: i64) -> Result<Vec<Job>> {
+        self.store.list_jobs(status, limit)
+    }
+
+    fn update(&self, id: &str, apply: impl FnOnce(&mut Job)) {
+        let job = {
+            let Ok(mut active) = self.active.lock() else { return };
+            let Some(entry) = active.get_mut(id) else { return };
+            apply(&mut entry.job);
+            entry.job.clone()
+        };
+        self.persist(&job);
+    }
+
+    fn finish(&self, id: &str, result: Result<serde_json::Value>, cancelled: bool) {
+        let job = {
+            let Ok(mut active) = self.active.lock() else { return };
+            let Some(mut entry) = active.remove(id) else { return };
+            let job = &mut entry.job;
+            job.finished_at = Some(chrono::Utc::now().timestamp());
+            match result {
+                Ok(value) => {
+                    job.status = "succeeded".to_string();
+                    job.progress = 1.0;
+                    job.result = Some(value);
+                }
+                Err(_) if cancelled => job.status = "cancelled".to_string(),
+                Err(e) => {
+                    crate::services::crash_reporter::CrashReporter::capture_task_error(&job.name, &e);
+                    job.status = "failed".to_string();
+                    job.error = Some(e.to_string());
+                }
+            }
+            entry.job
+        };
+        self.persist(&job);
+    }
+
+    fn persist(&self, job: &Job) {
+        if let Err(e) = self.store.save_job(job) {
+            eprintln!("Failed to save job {}: {}", job.id, e);
+        }
+        let _ = crate::services::window_router::emit(&self.app, serde_json::json!({
+            "type": "job-progress",
+            "data": job,
+            "timestamp": chrono::Utc::now().timestamp_millis(),
+        }));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::providers::ollama::{ChatMessage, ChatUsage, OllamaProvider};
+use crate::providers::openai::OpenAIChatProvider;
+use crate::services::redaction::PrivacyFilter;
+use crate::services::ollama_lifecycle::ModelLifecycle;
+use crate::storage::llm_usage::LlmUsageStore;
+use crate::storage::Database;
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// USD per million (input, output) tokens. Matched by model-name prefix; local
+/// providers such as Ollama are free.
+const MODEL_PRICES: &[(&str, f64, f64)] = &[
+    ("text-embedding-3-small", 0.02, 0.0),
+    ("text-embedding-3-large", 0.13, 0.0),
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gpt-4.1", 2.00, 8.00),
+    ("claude-3-5-haiku", 0.80, 4.00),
+    ("claude-3-5-sonnet", 3.00, 15.00),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetStatus {
+    pub provider: String,
+    pub month: String,
+    pub spent_usd: f64,
+    pub tokens: i64,
+    pub monthly_limit_usd: Option<f64>,
+    pub monthly_token_limit: Option<i64>,
+    pub action: String,
+    pub exceeded: bool,
+}
+
+pub fn estimate_cost(provider: &str, model: &str, tokens_in: i64, tokens_out: i64) -> f64 {
+    if provider == "ollama" {
+        return 0.0;
+    }
+    MODEL_PRICES
+        .iter()
+        .find(|(prefix, _, _)| model.starts_with(prefix))
+        .map(|(_, input, output)| (tokens_in as f64 * input + tokens_out as f64 * output) / 1_000_000.0)
+        .unwrap_or(0.0)
+}
+
+#[derive(Clone)]
+pub struct LlmMeter {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl LlmMeter {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        LlmMeter { conn }
+    }
+
+    fn current_month() -> String {
+        chrono::Utc::now().format("%Y-%m").to_string()
+    }
+
+    pub fn budget_status(&self, provider: &str) -> Result<Option<BudgetStatus>> {
+        let store = LlmUsageStore::new(self.conn.clone());
+        let budget = match store.get_budget(provider)? {
+            Some(b) => b,
+            None => return Ok(None),
+        };
+        let month = Self::current_month();
+        let (spent, tokens) = store.month_totals(provider, &month)?;
+        let exceeded = budget.monthly_limit_usd.map(|l| spent >= l).unwrap_or(false)
+            || budget.monthly_token_limit.map(|l| tokens >= l).unwrap_or(false);
+
+        Ok(Some(BudgetStatus {
+            provider: provider.to_string(),
+            month,
+            spent_usd: spent,
+            tokens,
+            monthly_limit_usd: budget.monthly_limit_usd,
+            monthly_token_limit: budget.monthly_token_limit,
+            action: budget.action,
+            exceeded,
+        }))
+    }
+
+    /// Fail if the provider's monthly budget is exhausted and set to block.
+    pub fn check_budget(&self, provider: &str) -> Result<()> {
+        if let Some(status) = self.budget_status(provider)? {
+            if status.exceeded && status.action == "block" {
+                anyhow::bail!(
+                    "Monthly LLM budget for {} exceeded (${:.2} / {} tokens this month)",
+                    provider,
+                    status.spent_usd,
+                    status.tokens
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Record one call and, the first time a "warn" budget is crossed each
+    /// month, log it to the error dashboard.
+    pub fn record(&self, provider: &str, model: &str, tokens_in: i64, tokens_out: i64, latency_ms: i64) -> Result<()> {
+        let store = LlmUsageStore::new(self.conn.clone());
+        let cost = estimate_cost(provider, model, tokens_in, tokens_out);
+        store.record_usage(provider, model, tokens_in, tokens_out, latency_ms, cost)?;
+
+        if let Some(status) = self.budget_status(provider)? {
+            let already_warned = store
+                .get_budget(provider)?
+                .and_then(|b| b.warned_month)
+                .map(|m| m == status.month)
+                .unwrap_or(false);
+            if status.exceeded && !already_warned {
+                let db = Database { conn: self.conn.clone() };
+                let _ = db.save_error(
+                    "LLM Budget Exceeded",
+                    &format!(
+                        "{} usage this month is ${:.2} / {} tokens, over the configured budget",
+                        provider, status.spent_usd, status.tokens
+                    ),
+                    None,
+                    Some("LlmMeter"),
+                    "warning",
+                );
+                store.mark_budget_warned(provider, &status.month)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Ollama chat with budget check and metering.
+    pub async fn ollama_chat(&self, ollama: &OllamaProvider, model: &str, messages: Vec<ChatMessage>) -> Result<String> {
+        self.ollama_chat_with_format(ollama, model, messages, None).await
+    }
+
+    /// Ollama chat constrained to a JSON schema, with budget check and metering.
+    pub async fn ollama_chat_with_format(
+        &self,
+        ollama: &OllamaProvider,
+        model: &str,
+        messages: Vec<ChatMessage>,
+        format: Option<serde_json::Value>,
+    ) -> Result<String> {
+        Ok(self.ollama_chat_metered(ollama, model, messages, format).await?.0)
+    }
+
+    /// Ollama chat with budget check and metering, returning the token counts.
+    pub async fn ollama_chat_with_usage(
+        &self,
+        ollama: &OllamaProvider,
+        model: &str,
+        messages: Vec<ChatMessage>,
+    ) -> Result<(String, ChatUsage)> {
+        self.ollama_chat_metered(ollama, model, messages, None).await
+    }
+
+    async fn ollama_chat_metered(
+        &self,
+        ollama: &OllamaProvider,
+        model: &str,
+        messages: Vec<ChatMessage>,
+        format: Option<serde_json::Value>,
+    ) -> Result<(String, ChatUsage)> {
+        self.check_budget("ollama")?;
+        let _permit = ModelLifecycle::acquire(model).await?;
+        let started = std::time::Instant::now();
+        let (content, stats) = ollama.chat_with_format(model, messages, format).await?;
+        let usage = stats.usage;
+        let latency_ms = started.elapsed().as_millis() as i64;
+        if let Err(e) = self.record("ollama", model, usage.prompt_tokens, usage.completion_tokens, latency_ms) {
+            eprintln!("Warning: Failed to record LLM usage: {}", e);
+        }
+        Ok((content, usage))
+    }
+
+    /// Cloud chat with budget check and metering. Messages are redacted on
+    /// the way out and the placeholders restored in the answer.
+    pub async fn cloud_chat(
+        &self,
+        provider: &str,
+        cloud: &OpenAIChatProvider,
+        model: &str,
+        messages: &[ChatMessage],
+        purpose: &str,
+    ) -> Result<(String, ChatUsage)> {
+        self.check_budget(provider)?;
+        let (sent, redactor) = PrivacyFilter::new(self.conn.clone()).prepare_messages(provider, model, purpose, messages)?;
+        let started = std::time::Instant::now();
+        let (content, usage) = cloud.chat(model, &sent).await?;
+        let latency_ms = started.elapsed().as_millis() as i64;
+        if let Err(e) = self.record(provider, model, usage.prompt_tokens, usage.completion_tokens, latency_ms) {
+            eprintln!("Warning: Failed to record LLM usage: {}", e);
+        }
+        Ok((redactor.restore(&content), usage))
+    }
+}
@@ -0,0 +1,326 @@
+use crate::providers::ollama::{ChatMessage, ChatUsage, OllamaProvider};
+use crate::providers::openai::OpenAIChatProvider;
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::context_window::{count_tokens, ContextManager};
+use crate::services::llm_metering::{estimate_cost, LlmMeter};
+use crate::services::model_benchmark::QualityTier;
+use crate::storage::llm_usage::{LlmRouteRecord, LlmUsageStore};
+use crate::storage::Database;
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+pub const CONFIG_ENABLED: &str = "llm_routing_enabled";
+pub const CONFIG_CLOUD_PROVIDER: &str = "llm_routing_cloud_provider";
+pub const CONFIG_CLOUD_MODEL: &str = "llm_routing_cloud_model";
+pub const CONFIG_CLOUD_BASE_URL: &str = "llm_routing_cloud_base_url";
+pub const CONFIG_LONG_CONTEXT_TOKENS: &str = "llm_routing_long_context_tokens";
+pub const CONFIG_OVERRIDES: &str = "llm_routing_overrides";
+pub const CONFIG_FALLBACK: &str = "llm_routing_fallback";
+
+pub const TASK_PORTFOLIO_QA: &str = "portfolio_qa";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RouteTarget {
+    Local,
+    Cloud,
+}
+
+impl RouteTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteTarget::Local => "local",
+            RouteTarget::Cloud => "cloud",
+        }
+    }
+
+    fn other(&self) -> Self {
+        match self {
+            RouteTarget::Local => RouteTarget::Cloud,
+            RouteTarget::Cloud => RouteTarget::Local,
+        }
+    }
+}
+
+/// Which calls leave the machine. Disabled, everything runs on Ollama as
+/// before.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutingPolicy {
+    pub enabled: bool,
+    /// API key provider name; the API has to be OpenAI-compatible
+    pub cloud_provider: String,
+    pub cloud_model: String,
+    pub cloud_base_url: Option<String>,
+    /// Prompts longer than this, or than the local model's window, go to the cloud
+    pub long_context_tokens: u64,
+    /// Task type to target, overriding length and quality
+    pub overrides: BTreeMap<String, RouteTarget>,
+    /// Retry on the other target when the routed one fails
+    pub fallback: bool,
+}
+
+impl Default for RoutingPolicy {
+    fn default() -> Self {
+        RoutingPolicy {
+            enabled: false,
+            cloud_provider: "openai".to_string(),
+            cloud_model: "gpt-4o-mini".to_string(),
+            cloud_base_url: None,
+            long_context_tokens: 6000,
+            overrides: BTreeMap::new(),
+            fallback: true,
+        }
+    }
+}
+
+impl RoutingPolicy {
+    pub fn load(db: &Database) -> Result<Self> {
+        let defaults = Self::default();
+        Ok(RoutingPolicy {
+            enabled: db.get_config(CONFIG_ENABLED)?.map(|v| v == "true").unwrap_or(defaults.enabled),
+            cloud_provider: db.get_config(CONFIG_CLOUD_PROVIDER)?
+                .filter(|v| !v.trim().is_empty())
+                .unwrap_or(defaults.cloud_provider),
+            cloud_model: db.get_config(CONFIG_CLOUD_MODEL)?
+                .filter(|v| !v.trim().is_empty())
+                .unwrap_or(defaults.cloud_model),
+            cloud_base_url: db.get_config(CONFIG_CLOUD_BASE_URL)?.filter(|v| !v.trim().is_empty()),
+            long_context_tokens: db.get_config(CONFIG_LONG_CONTEXT_TOKENS)?
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.long_context_tokens),
+            overrides: db.get_config(CONFIG_OVERRIDES)?
+                .and_then(|v| serde_json::from_str(&v).ok())
+                .unwrap_or(defaults.overrides),
+            fallback: db.get_config(CONFIG_FALLBACK)?.map(|v| v == "true").unwrap_or(defaults.fallback),
+        })
+    }
+
+    pub fn save(&self, db: &Database) -> Result<()> {
+        db.set_config(CONFIG_ENABLED, if self.enabled { "true" } else { "false" })?;
+        db.set_config(CONFIG_CLOUD_PROVIDER, &self.cloud_provider)?;
+        db.set_config(CONFIG_CLOUD_MODEL, &self.cloud_model)?;
+        db.set_config(CONFIG_CLOUD_BASE_URL, self.cloud_base_url.as_deref().unwrap_or(""))?;
+        db.set_config(CONFIG_LONG_CONTEXT_TOKENS, &self.long_context_tokens.to_string())?;
+        db.set_config(CONFIG_OVERRIDES, &serde_json::to_string(&self.overrides)?)?;
+        db.set_config(CONFIG_FALLBACK, if self.fallback { "true" } else { "false" })?;
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.cloud_provider.trim().is_empty() || self.cloud_model.trim().is_empty() {
+            anyhow::bail!("Cloud provider and model must be set");
+        }
+        if self.long_context_tokens == 0 {
+            anyhow::bail!("Long-context threshold must be positive");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteDecision {
+    pub target: RouteTarget,
+    pub reason: String,
+}
+
+/// Where a call goes: overrides first, then long prompts and high-quality
+/// tasks to the cloud, and everything else stays local. Without a cloud
+/// API key nothing leaves the machine.
+fn decide(
+    policy: &RoutingPolicy,
+    task: &str,
+    tier: QualityTier,
+    prompt_tokens: u64,
+    local_budget: u64,
+    cloud_available: bool,
+) -> RouteDecision {
+    let route = |target, reason: &str| RouteDecision { target, reason: reason.to_string() };
+    if !policy.enabled {
+        return route(RouteTarget::Local, "routing disabled");
+    }
+    let wanted = match policy.overrides.get(task) {
+        Some(target) => route(*target, "task override"),
+        None if prompt_tokens > policy.long_context_tokens.min(local_budget) => route(RouteTarget::Cloud, "long context"),
+        None if tier == QualityTier::High => route(RouteTarget::Cloud, "high quality"),
+        None => route(RouteTarget::Local, "short task"),
+    };
+    if wanted.target == RouteTarget::Cloud && !cloud_available {
+        return route(RouteTarget::Local, "no cloud API key");
+    }
+    wanted
+}
+
+/// A routed call's answer and where it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutedResponse {
+    pub content: String,
+    pub target: RouteTarget,
+    pub provider: String,
+    pub model: String,
+    pub reason: String,
+    pub fell_back: bool,
+    pub cost_usd: f64,
+}
+
+/// Sends LLM calls to local Ollama or the configured cloud model per the
+/// routing policy, falls back to the other on failure, and logs every
+/// decision for the routing report.
+pub struct LlmRouter {
+    conn: Arc<Mutex<Connection>>,
+    policy: RoutingPolicy,
+    cloud: Option<OpenAIChatProvider>,
+}
+
+impl LlmRouter {
+    pub fn new(conn: Arc<Mutex<Connection>>, api_key_manager: &APIKeyManager) -> Result<Self> {
+        let policy = RoutingPolicy::load(&Database { conn: conn.clone() })?;
+        let key = if policy.enabled {
+            api_key_manager.get_key_optional(&policy.cloud_provider)?
+        } else {
+            None
+        };
+        let cloud = key.map(|key| {
+            let provider = OpenAIChatProvider::new(key);
+            match &policy.cloud_base_url {
+                Some(url) => provider.with_base_url(url),
+                None => provider,
+            }
+        });
+        Ok(LlmRouter { conn, policy, cloud })
+    }
+
+    pub async fn chat(
+        &self,
+        ollama: &OllamaProvider,
+        task: &str,
+        tier: QualityTier,
+        local_model: &str,
+        messages: Vec<ChatMessage>,
+    ) -> Result<RoutedResponse> {
+        let local_budget = ContextManager::prompt_budget(ollama, local_model).await;
+        let decision = decide(&self.policy, task, tier, count_tokens(&messages), local_budget, self.cloud.is_some());
+
+        let first = self.call(ollama, decision.target, task, local_model, &messages).await;
+        self.log(task, decision.target, local_model, &decision.reason, false, &first);
+        let error = match first {
+            Ok((content, usage)) => return Ok(self.response(decision.target, local_model, &decision.reason, false, content, usage)),
+            Err(e) => e,
+        };
+
+        let other = decision.target.other();
+        if !self.policy.enabled || !self.policy.fallback || (other == RouteTarget::Cloud && self.cloud.is_none()) {
+            return Err(error);
+        }
+        let reason = format!("{} failed: {}", decision.target.as_str(), error);
+        let second = self.call(ollama, other, task, local_model, &messages).await;
+        self.log(task, other, local_model, &reason, true, &second);
+        let (content, usage) = second?;
+        Ok(self.response(other, local_model, &reason, true, content, usage))
+    }
+
+    fn provider_and_model<'a>(&'a self, target: RouteTarget, local_model: &'a str) -> (&'a str, &'a str) {
+        match target {
+            RouteTarget::Local => ("ollama", local_model),
+            RouteTarget::Cloud => (&self.policy.cloud_provider, &self.policy.cloud_model),
+        }
+    }
+
+    async fn call(
+        &self,
+        ollama: &OllamaProvider,
+        target: RouteTarget,
+        task: &str,
+        local_model: &str,
+        messages: &[ChatMessage],
+    ) -> Result<(String, ChatUsage)> {
+        let meter = LlmMeter::new(self.conn.clone());
+        match (target, &self.cloud) {
+            (RouteTarget::Local, _) => meter.ollama_chat_with_usage(ollama, local_model, messages.to_vec()).await,
+            (RouteTarget::Cloud, Some(cloud)) => {
+                meter.cloud_chat(&self.policy.cloud_provider, cloud, &self.policy.cloud_model, messages, task).await
+            }
+            (RouteTarget::Cloud, None) => anyhow::bail!("No API key for {}", self.policy.cloud_provider),
+        }
+    }
+
+    fn response(
+        &self,
+        target: RouteTarget,
+        local_model: &str,
+        reason: &str,
+        fell_back: bool,
+        content: String,
+        usage: ChatUsage,
+    ) -> RoutedResponse {
+        let (provider, model) = self.provider_and_model(target, local_model);
+        RoutedResponse {
+            content,
+            target,
+            provider: provider.to_string(),
+            model: model.to_string(),
+            reason: reason.to_string(),
+            fell_back,
+            cost_usd: estimate_cost(provider, model, usage.prompt_tokens, usage.completion_tokens),
+        }
+    }
+
+    fn log(
+        &self,
+        task: &str,
+        target: RouteTarget,
+        local_model: &str,
+        reason: &str,
+        fell_back: bool,
+        outcome: &Result<(String, ChatUsage)>,
+    ) {
+        let (provider, model) = self.provider_and_model(target, local_model);
+        let usage = outcome.as_ref().map(|(_, usage)| *usage).unwrap_or_default();
+        let record = LlmRouteRecord {
+            id: 0,
+            task: task.to_string(),
+            target: target.as_str().to_string(),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            reason: reason.to_string(),
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            cost_usd: estimate_cost(provider, model, usage.prompt_tokens, usage.completion_tokens),
+            fell_back,
+            success: outcome.is_ok(),
+            error: outcome.as_ref().err().map(|e| e.to_string()),
+            created_at: chrono::Utc::now().timestamp(),
+        };
+        if let Err(e) = LlmUsageStore::new(self.conn.clone()).record_route(&record) {
+            eprintln!("Warning: Failed to record LLM route: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_by_override_length_and_quality() {
+        let mut policy = RoutingPolicy { enabled: true, ..RoutingPolicy::default() };
+        let target = |policy: &RoutingPolicy, task, tier, tokens, cloud| decide(policy, task, tier, tokens, 3000, cloud).target;
+
+        assert_eq!(target(&policy, "summarization", QualityTier::Basic, 500, true), RouteTarget::Local);
+        // Over the local window before the configured threshold
+        assert_eq!(target(&policy, "summarization", QualityTier::Basic, 4000, true), RouteTarget::Cloud);
+        assert_eq!(target(&policy, "portfolio_qa", QualityTier::High, 500, true), RouteTarget::Cloud);
+        // Nothing leaves without a key
+        assert_eq!(target(&policy, "portfolio_qa", QualityTier::High, 500, false), RouteTarget::Local);
+
+        policy.overrides.insert("portfolio_qa".to_string(), RouteTarget::Local);
+        policy.overrides.insert("extraction".to_string(), RouteTarget::Cloud);
+        assert_eq!(target(&policy, "portfolio_qa", QualityTier::High, 9000, true), RouteTarget::Local);
+        assert_eq!(target(&policy, "extraction", QualityTier::Basic, 10, true), RouteTarget::Cloud);
+
+        policy.enabled = false;
+        assert_eq!(decide(&policy, "extraction", QualityTier::High, 9000, 3000, true).reason, "routing disabled");
+    }
+}
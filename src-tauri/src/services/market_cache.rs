@@ -1,85 +1,310 @@
 use crate::storage::market_data::MarketPrice;
+use crate::storage::Database;
 use crate::providers::market_data::OHLCVData;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
 
+pub const CONFIG_QUOTE_TTL: &str = "market_cache_quote_ttl_secs";
+pub const CONFIG_HISTORY_TTL: &str = "market_cache_history_ttl_secs";
+pub const CONFIG_FUNDAMENTALS_TTL: &str = "market_cache_fundamentals_ttl_secs";
+
+/// Cache lifetimes per data type, in seconds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CacheTtls {
+    pub quote_secs: u64,
+    pub history_secs: u64,
+    pub fundamentals_secs: u64,
+}
+
+impl Default for CacheTtls {
+    fn default() -> Self {
+        CacheTtls {
+            quote_secs: 60,        // 1 minute for prices
+            history_secs: 3600,    // 1 hour for history
+            fundamentals_secs: 86400, // fundamentals change with filings, a day is plenty
+        }
+    }
+}
+
+impl CacheTtls {
+    /// Configured TTLs, falling back to defaults for anything unset or invalid.
+    pub fn load(db: &Database) -> Result<Self> {
+        let defaults = Self::default();
+        let read = |key: &str, default: u64| -> Result<u64> {
+            Ok(db.get_config(key)?
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(default))
+        };
+        Ok(CacheTtls {
+            quote_secs: read(CONFIG_QUOTE_TTL, defaults.quote_secs)?,
+            history_secs: read(CONFIG_HISTORY_TTL, defaults.history_secs)?,
+            fundamentals_secs: read(CONFIG_FUNDAMENTALS_TTL, defaults.fundamentals_secs)?,
+        })
+    }
+
+    pub fn save(&self, db: &Database) -> Result<()> {
+        db.set_config(CONFIG_QUOTE_TTL, &self.quote_secs.to_string())?;
+        db.set_config(CONFIG_HISTORY_TTL, &self.history_secs.to_string())?;
+        db.set_config(CONFIG_FUNDAMENTALS_TTL, &self.fundamentals_secs.to_string())?;
+        Ok(())
+    }
+}
+
+struct CacheEntry<T> {
+    value: T,
+    ticker: String,
+    provider: Option<String>,
+    stored_at: Instant,
+}
+
+impl<T> CacheEntry<T> {
+    fn new(ticker: &str, provider: Option<&str>, value: T) -> Self {
+        CacheEntry {
+            value,
+            ticker: ticker.to_uppercase(),
+            provider: provider.map(|p| p.to_string()),
+            stored_at: Instant::now(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Counters {
+    fn record(&self, hit: bool) {
+        if hit {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// How far entries are into their TTL: fresh (< 50%), aging (50-100%), expired
+/// (past TTL but not yet swept by cleanup).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StalenessDistribution {
+    pub fresh: usize,
+    pub aging: usize,
+    pub expired: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheTypeStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_rate: f64,
+    pub approx_bytes: usize,
+    pub ttl_secs: u64,
+    pub staleness: StalenessDistribution,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketCacheStats {
+    pub quote: CacheTypeStats,
+    pub history: CacheTypeStats,
+    pub fundamentals: CacheTypeStats,
+    pub approx_bytes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntryInfo {
+    pub kind: String,
+    pub key: String,
+    pub ticker: String,
+    pub provider: Option<String>,
+    pub age_secs: u64,
+    pub expires_in_secs: i64,
+}
+
+/// Which entries to drop. Unset fields match everything; `kind` is one of
+/// "quote", "history" or "fundamentals".
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheInvalidation {
+    pub ticker: Option<String>,
+    pub provider: Option<String>,
+    pub kind: Option<String>,
+}
+
+impl CacheInvalidation {
+    fn matches<T>(&self, kind: &str, entry: &CacheEntry<T>) -> bool {
+        let kind_matches = self.kind.as_deref().map(|k| k == kind).unwrap_or(true);
+        let ticker_matches = self.ticker.as_deref()
+            .map(|t| entry.ticker.eq_ignore_ascii_case(t))
+            .unwrap_or(true);
+        let provider_matches = match (self.provider.as_deref(), entry.provider.as_deref()) {
+            (None, _) => true,
+            (Some(wanted), Some(provider)) => provider.eq_ignore_ascii_case(wanted),
+            (Some(_), None) => false,
+        };
+        kind_matches && ticker_matches && provider_matches
+    }
+}
+
 pub struct MarketDataCache {
-    prices: Arc<Mutex<HashMap<String, (MarketPrice, Instant)>>>,
-    history: Arc<Mutex<HashMap<String, (Vec<OHLCVData>, Instant)>>>,
-    price_ttl: Duration,
-    history_ttl: Duration,
+    prices: Arc<Mutex<HashMap<String, CacheEntry<MarketPrice>>>>,
+    history: Arc<Mutex<HashMap<String, CacheEntry<Vec<OHLCVData>>>>>,
+    fundamentals: Arc<Mutex<HashMap<String, CacheEntry<serde_json::Value>>>>,
+    ttls: Arc<RwLock<CacheTtls>>,
+    price_counters: Arc<Counters>,
+    history_counters: Arc<Counters>,
+    fundamentals_counters: Arc<Counters>,
 }
 
 impl MarketDataCache {
     pub fn new() -> Self {
+        Self::with_ttls(CacheTtls::default())
+    }
+
+    pub fn with_ttls(ttls: CacheTtls) -> Self {
         MarketDataCache {
             prices: Arc::new(Mutex::new(HashMap::new())),
             history: Arc::new(Mutex::new(HashMap::new())),
-            price_ttl: Duration::from_secs(60), // 1 minute for prices
-            history_ttl: Duration::from_secs(3600), // 1 hour for history
+            fundamentals: Arc::new(Mutex::new(HashMap::new())),
+            ttls: Arc::new(RwLock::new(ttls)),
+            price_counters: Arc::new(Counters::default()),
+            history_counters: Arc::new(Counters::default()),
+            fundamentals_counters: Arc::new(Counters::default()),
+        }
+    }
+
+    pub fn ttls(&self) -> CacheTtls {
+        self.ttls.read().map(|t| *t).unwrap_or_default()
+    }
+
+    /// Change TTLs for this cache and all its clones. Applies to existing entries too.
+    pub fn set_ttls(&self, ttls: CacheTtls) {
+        if let Ok(mut current) = self.ttls.write() {
+            *current = ttls;
         }
     }
 
+    fn price_ttl(&self) -> Duration {
+        Duration::from_secs(self.ttls().quote_secs)
+    }
+
+    fn history_ttl(&self) -> Duration {
+        Duration::from_secs(self.ttls().history_secs)
+    }
+
+    fn fundamentals_ttl(&self) -> Duration {
+        Duration::from_secs(self.ttls().fundamentals_secs)
+    }
+
     /// Get cached price if available and fresh
     pub fn get_price(&self, ticker: &str) -> Option<MarketPrice> {
+        let ttl = self.price_ttl();
         let cache = self.prices.lock().ok()?;
-        if let Some((price, timestamp)) = cache.get(ticker) {
-            if timestamp.elapsed() < self.price_ttl {
-                return Some(price.clone());
-            }
-        }
-        None
+        let found = cache
+            .get(ticker)
+            .filter(|entry| entry.stored_at.elapsed() < ttl)
+            .map(|entry| entry.value.clone());
+        self.price_counters.record(found.is_some());
+        found
     }
 
-    /// Set price in cache
-    pub fn set_price(&self, ticker: String, price: MarketPrice) {
+    /// Set price in cache. `provider` is None for prices loaded from the database.
+    pub fn set_price(&self, ticker: String, price: MarketPrice, provider: Option<&str>) {
         if let Ok(mut cache) = self.prices.lock() {
-            cache.insert(ticker, (price, Instant::now()));
+            let entry = CacheEntry::new(&ticker, provider, price);
+            cache.insert(ticker, entry);
         }
     }
 
     /// Get cached history if available and fresh
     pub fn get_history(&self, ticker: &str, from_ts: i64, to_ts: i64) -> Option<Vec<OHLCVData>> {
+        let ttl = self.history_ttl();
         let cache = self.history.lock().ok()?;
         let cache_key = format!("{}:{}:{}", ticker, from_ts, to_ts);
-        if let Some((history, timestamp)) = cache.get(&cache_key) {
-            if timestamp.elapsed() < self.history_ttl {
+        let found = cache
+            .get(&cache_key)
+            .filter(|entry| entry.stored_at.elapsed() < ttl)
+            .map(|entry| {
                 // Filter history to requested time range
-                let filtered: Vec<OHLCVData> = history
+                entry.value
                     .iter()
                     .filter(|d| d.timestamp >= from_ts && d.timestamp <= to_ts)
                     .cloned()
-                    .collect();
-                if !filtered.is_empty() {
-                    return Some(filtered);
-                }
-            }
-        }
-        None
+                    .collect::<Vec<OHLCVData>>()
+            })
+            .filter(|filtered| !filtered.is_empty());
+        self.history_counters.record(found.is_some());
+        found
     }
 
     /// Set history in cache
-    pub fn set_history(&self, ticker: String, from_ts: i64, to_ts: i64, history: Vec<OHLCVData>) {
+    pub fn set_history(&self, ticker: String, from_ts: i64, to_ts: i64, history: Vec<OHLCVData>, provider: Option<&str>) {
         if let Ok(mut cache) = self.history.lock() {
             let cache_key = format!("{}:{}:{}", ticker, from_ts, to_ts);
-            cache.insert(cache_key, (history, Instant::now()));
+            cache.insert(cache_key, CacheEntry::new(&ticker, provider, history));
+        }
+    }
+
+    /// Get cached fundamentals if available and fresh
+    pub fn get_fundamentals(&self, ticker: &str) -> Option<serde_json::Value> {
+        let ttl = self.fundamentals_ttl();
+        let cache = self.fundamentals.lock().ok()?;
+        let found = cache
+            .get(&ticker.to_uppercase())
+            .filter(|entry| entry.stored_at.elapsed() < ttl)
+            .map(|entry| entry.value.clone());
+        self.fundamentals_counters.record(found.is_some());
+        found
+    }
+
+    /// Set fundamentals in cache
+    pub fn set_fundamentals(&self, ticker: &str, fundamentals: serde_json::Value, provider: Option<&str>) {
+        if let Ok(mut cache) = self.fundamentals.lock() {
+            cache.insert(ticker.to_uppercase(), CacheEntry::new(ticker, provider, fundamentals));
         }
     }
 
     /// Clear expired entries
     pub fn cleanup(&self) {
-        let now = Instant::now();
-        
-        // Cleanup prices
+        let price_ttl = self.price_ttl();
+        let history_ttl = self.history_ttl();
+        let fundamentals_ttl = self.fundamentals_ttl();
+
         if let Ok(mut cache) = self.prices.lock() {
-            cache.retain(|_, (_, timestamp)| now.duration_since(*timestamp) < self.price_ttl);
+            cache.retain(|_, entry| entry.stored_at.elapsed() < price_ttl);
         }
-        
-        // Cleanup history
         if let Ok(mut cache) = self.history.lock() {
-            cache.retain(|_, (_, timestamp)| now.duration_since(*timestamp) < self.history_ttl);
+            cache.retain(|_, entry| entry.stored_at.elapsed() < history_ttl);
+        }
+        if let Ok(mut cache) = self.fundamentals.lock() {
+            cache.retain(|_, entry| entry.stored_at.elapsed() < fundamentals_ttl);
+        }
+    }
+
+    /// Drop entries matching the filter. Returns how many were removed.
+    pub fn invalidate(&self, filter: &CacheInvalidation) -> usize {
+        let mut removed = 0;
+        if let Ok(mut cache) = self.prices.lock() {
+            let before = cache.len();
+            cache.retain(|_, entry| !filter.matches("quote", entry));
+            removed += before - cache.len();
+        }
+        if let Ok(mut cache) = self.history.lock() {
+            let before = cache.len();
+            cache.retain(|_, entry| !filter.matches("history", entry));
+            removed += before - cache.len();
+        }
+        if let Ok(mut cache) = self.fundamentals.lock() {
+            let before = cache.len();
+            cache.retain(|_, entry| !filter.matches("fundamentals", entry));
+            removed += before - cache.len();
         }
+        removed
     }
 
     /// Clear all cache
@@ -90,13 +315,111 @@ impl MarketDataCache {
         if let Ok(mut cache) = self.history.lock() {
             cache.clear();
         }
+        if let Ok(mut cache) = self.fundamentals.lock() {
+            cache.clear();
+        }
+    }
+
+    /// Get cache statistics. Byte counts are estimates of the payload, not allocator usage.
+    pub fn stats(&self) -> MarketCacheStats {
+        let ttls = self.ttls();
+        let quote = self.prices.lock()
+            .map(|c| type_stats(&*c, &self.price_counters, ttls.quote_secs, |key, p| {
+                key.len() + p.ticker.len() + std::mem::size_of::<MarketPrice>()
+            }))
+            .unwrap_or_else(|_| empty_stats(ttls.quote_secs));
+        let history = self.history.lock()
+            .map(|c| type_stats(&*c, &self.history_counters, ttls.history_secs, |key, h| {
+                key.len() + h.len() * std::mem::size_of::<OHLCVData>()
+            }))
+            .unwrap_or_else(|_| empty_stats(ttls.history_secs));
+        let fundamentals = self.fundamentals.lock()
+            .map(|c| type_stats(&*c, &self.fundamentals_counters, ttls.fundamentals_secs, |key, v| {
+                key.len() + v.to_string().len()
+            }))
+            .unwrap_or_else(|_| empty_stats(ttls.fundamentals_secs));
+
+        MarketCacheStats {
+            approx_bytes: quote.approx_bytes + history.approx_bytes + fundamentals.approx_bytes,
+            quote,
+            history,
+            fundamentals,
+        }
+    }
+
+    /// Every cached entry with its age, newest first.
+    pub fn entries(&self) -> Vec<CacheEntryInfo> {
+        let ttls = self.ttls();
+        let mut entries = Vec::new();
+        if let Ok(cache) = self.prices.lock() {
+            entries.extend(cache.iter().map(|(k, e)| entry_info("quote", k, e, ttls.quote_secs)));
+        }
+        if let Ok(cache) = self.history.lock() {
+            entries.extend(cache.iter().map(|(k, e)| entry_info("history", k, e, ttls.history_secs)));
+        }
+        if let Ok(cache) = self.fundamentals.lock() {
+            entries.extend(cache.iter().map(|(k, e)| entry_info("fundamentals", k, e, ttls.fundamentals_secs)));
+        }
+        entries.sort_by_key(|e| e.age_secs);
+        entries
+    }
+}
+
+fn type_stats<T>(
+    cache: &HashMap<String, CacheEntry<T>>,
+    counters: &Counters,
+    ttl_secs: u64,
+    size_of: impl Fn(&str, &T) -> usize,
+) -> CacheTypeStats {
+    let mut staleness = StalenessDistribution::default();
+    let mut approx_bytes = 0;
+    for (key, entry) in cache {
+        let age = entry.stored_at.elapsed().as_secs_f64();
+        let ttl = ttl_secs as f64;
+        if age >= ttl {
+            staleness.expired += 1;
+        } else if age >= ttl / 2.0 {
+            staleness.aging += 1;
+        } else {
+            staleness.fresh += 1;
+        }
+        approx_bytes += size_of(key, &entry.value);
     }
 
-    /// Get cache statistics
-    pub fn stats(&self) -> (usize, usize) {
-        let price_count = self.prices.lock().map(|c| c.len()).unwrap_or(0);
-        let history_count = self.history.lock().map(|c| c.len()).unwrap_or(0);
-        (price_count, history_count)
+    let hits = counters.hits.load(Ordering::Relaxed);
+    let misses = counters.misses.load(Ordering::Relaxed);
+    CacheTypeStats {
+        entries: cache.len(),
+        hits,
+        misses,
+        hit_rate: if hits + misses > 0 { hits as f64 / (hits + misses) as f64 } else { 0.0 },
+        approx_bytes,
+        ttl_secs,
+        staleness,
+    }
+}
+
+fn empty_stats(ttl_secs: u64) -> CacheTypeStats {
+    CacheTypeStats {
+        entries: 0,
+        hits: 0,
+        misses: 0,
+        hit_rate: 0.0,
+        approx_bytes: 0,
+        ttl_secs,
+        staleness: StalenessDistribution::default(),
+    }
+}
+
+fn entry_info<T>(kind: &str, key: &str, entry: &CacheEntry<T>, ttl_secs: u64) -> CacheEntryInfo {
+    let age_secs = entry.stored_at.elapsed().as_secs();
+    CacheEntryInfo {
+        kind: kind.to_string(),
+        key: key.to_string(),
+        ticker: entry.ticker.clone(),
+        provider: entry.provider.clone(),
+        age_secs,
+        expires_in_secs: ttl_secs as i64 - age_secs as i64,
     }
 }
 
@@ -111,9 +434,11 @@ impl Clone for MarketDataCache {
         MarketDataCache {
             prices: self.prices.clone(),
             history: self.history.clone(),
-            price_ttl: self.price_ttl,
-            history_ttl: self.history_ttl,
+            fundamentals: self.fundamentals.clone(),
+            ttls: self.ttls.clone(),
+            price_counters: self.price_counters.clone(),
+            history_counters: self.history_counters.clone(),
+            fundamentals_counters: self.fundamentals_counters.clone(),
         }
     }
 }
-
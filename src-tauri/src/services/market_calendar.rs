@@ -0,0 +1,451 @@
+use crate::storage::market_data::{MarketDataStore, MarketHoliday, MarketPrice};
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketSession {
+    PreMarket,
+    Regular,
+    PostMarket,
+    Closed,
+}
+
+impl MarketSession {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketSession::PreMarket => "pre_market",
+            MarketSession::Regular => "regular",
+            MarketSession::PostMarket => "post_market",
+            MarketSession::Closed => "closed",
+        }
+    }
+}
+
+/// Trading hours in minutes after local midnight. Extended sessions run
+/// from `pre_open` to `open` and from `close` to `post_close`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Exchange {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub timezone: Tz,
+    pub pre_open: u16,
+    pub open: u16,
+    pub close: u16,
+    pub post_close: u16,
+    pub trades_weekends: bool,
+}
+
+const fn hm(hours: u16, minutes: u16) -> u16 {
+    hours * 60 + minutes
+}
+
+const EXCHANGES: [Exchange; 8] = [
+    Exchange { code: "US", name: "NYSE / Nasdaq", timezone: chrono_tz::America::New_York, pre_open: hm(4, 0), open: hm(9, 30), close: hm(16, 0), post_close: hm(20, 0), trades_weekends: false },
+    Exchange { code: "XETRA", name: "Xetra / Frankfurt", timezone: chrono_tz::Europe::Berlin, pre_open: hm(8, 0), open: hm(9, 0), close: hm(17, 30), post_close: hm(22, 0), trades_weekends: false },
+    Exchange { code: "LSE", name: "London Stock Exchange", timezone: chrono_tz::Europe::London, pre_open: hm(7, 0), open: hm(8, 0), close: hm(16, 30), post_close: hm(17, 15), trades_weekends: false },
+    Exchange { code: "TSX", name: "Toronto Stock Exchange", timezone: chrono_tz::America::Toronto, pre_open: hm(7, 0), open: hm(9, 30), close: hm(16, 0), post_close: hm(17, 0), trades_weekends: false },
+    // Lunch breaks are not modelled; the session counts as regular
+    Exchange { code: "JPX", name: "Tokyo Stock Exchange", timezone: chrono_tz::Asia::Tokyo, pre_open: hm(8, 0), open: hm(9, 0), close: hm(15, 30), post_close: hm(15, 30), trades_weekends: false },
+    Exchange { code: "HKEX", name: "Hong Kong Stock Exchange", timezone: chrono_tz::Asia::Hong_Kong, pre_open: hm(9, 0), open: hm(9, 30), close: hm(16, 0), post_close: hm(16, 10), trades_weekends: false },
+    Exchange { code: "FX", name: "Foreign exchange", timezone: chrono_tz::UTC, pre_open: 0, open: 0, close: hm(24, 0), post_close: hm(24, 0), trades_weekends: false },
+    Exchange { code: "CRYPTO", name: "Crypto", timezone: chrono_tz::UTC, pre_open: 0, open: 0, close: hm(24, 0), post_close: hm(24, 0), trades_weekends: true },
+];
+
+/// Yahoo-style ticker suffixes and the exchange they trade on.
+const SUFFIXES: [(&str, &str); 9] = [
+    (".DE", "XETRA"),
+    (".F", "XETRA"),
+    (".L", "LSE"),
+    (".TO", "TSX"),
+    (".V", "TSX"),
+    (".T", "JPX"),
+    (".HK", "HKEX"),
+    ("=X", "FX"),
+    ("=F", "US"),
+];
+const CRYPTO_QUOTES: [&str; 4] = ["-USD", "-USDT", "-EUR", "-BTC"];
+/// Years holiday rules can be computed for
+pub const HOLIDAY_YEARS: std::ops::RangeInclusive<i32> = 1900..=2200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holiday {
+    pub exchange: String,
+    pub date: NaiveDate,
+    pub name: String,
+    /// Local minutes after midnight the regular session ends early, or
+    /// None when the market is closed all day
+    pub early_close: Option<u16>,
+    pub custom: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketStatus {
+    pub exchange: String,
+    pub name: String,
+    pub timezone: String,
+    pub session: MarketSession,
+    pub local_time: String,
+    pub holiday: Option<String>,
+    pub next_open: Option<i64>,
+}
+
+/// Exchange trading hours with built-in holiday rules plus user-added
+/// holidays and early closes from the database.
+#[derive(Debug, Clone, Default)]
+pub struct MarketCalendar {
+    custom: Vec<MarketHoliday>,
+}
+
+impl MarketCalendar {
+    /// Calendar with the user's holidays. Falls back to the built-in rules
+    /// when they can't be read.
+    pub fn load(conn: Arc<Mutex<Connection>>) -> Self {
+        match MarketDataStore::new(conn).list_market_holidays(None) {
+            Ok(custom) => MarketCalendar { custom },
+            Err(e) => {
+                eprintln!("Failed to load market holidays: {}", e);
+                MarketCalendar::default()
+            }
+        }
+    }
+
+    pub fn exchanges() -> &'static [Exchange] {
+        &EXCHANGES
+    }
+
+    pub fn exchange(code: &str) -> Option<&'static Exchange> {
+        EXCHANGES.iter().find(|e| e.code.eq_ignore_ascii_case(code))
+    }
+
+    /// Exchange a ticker trades on, from its suffix; US when unknown.
+    pub fn exchange_for_ticker(ticker: &str) -> &'static Exchange {
+        let ticker = ticker.trim().to_uppercase();
+        let code = if CRYPTO_QUOTES.iter().any(|q| ticker.ends_with(q)) {
+            "CRYPTO"
+        } else {
+            SUFFIXES
+                .iter()
+                .find(|(suffix, _)| ticker.ends_with(suffix))
+                .map(|(_, code)| *code)
+                .unwrap_or("US")
+        };
+        Self::exchange(code).unwrap_or(&EXCHANGES[0])
+    }
+
+    /// Holidays and early closes for one year, custom entries overriding
+    /// built-in ones on the same date.
+    pub fn holidays(&self, exchange: &Exchange, year: i32) -> Vec<Holiday> {
+        let mut holidays: Vec<Holiday> = self
+            .custom
+            .iter()
+            .filter(|h| h.exchange.eq_ignore_ascii_case(exchange.code))
+            .filter_map(|h| {
+                let date = NaiveDate::parse_from_str(&h.date, "%Y-%m-%d").ok()?;
+                (date.year() == year).then(|| Holiday {
+                    exchange: exchange.code.to_string(),
+                    date,
+                    name: h.name.clone(),
+                    early_close: h.early_close.as_deref().and_then(parse_hhmm),
+                    custom: true,
+                })
+            })
+            .collect();
+        for (date, name, early_close) in builtin_holidays(exchange.code, year) {
+            if !holidays.iter().any(|h| h.date == date) {
+                holidays.push(Holiday {
+                    exchange: exchange.code.to_string(),
+                    date,
+                    name: name.to_string(),
+                    early_close,
+                    custom: false,
+                });
+            }
+        }
+        holidays.sort_by_key(|h| h.date);
+        holidays
+    }
+
+    pub fn holiday(&self, exchange: &Exchange, date: NaiveDate) -> Option<Holiday> {
+        self.holidays(exchange, date.year()).into_iter().find(|h| h.date == date)
+    }
+
+    pub fn session_at(&self, exchange: &Exchange, ts: i64) -> MarketSession {
+        let Some(local) = exchange.timezone.timestamp_opt(ts, 0).single() else {
+            return MarketSession::Closed;
+        };
+        let date = local.date_naive();
+        if !exchange.trades_weekends && matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return MarketSession::Closed;
+        }
+        let mut close = exchange.close;
+        let mut post_close = exchange.post_close;
+        if let Some(holiday) = self.holiday(exchange, date) {
+            match holiday.early_close {
+                Some(early) => {
+                    close = close.min(early);
+                    post_close = post_close.min(early + (exchange.post_close - exchange.close));
+                }
+                None => return MarketSession::Closed,
+            }
+        }
+
+        let minute = (local.hour() * 60 + local.minute()) as u16;
+        if minute >= exchange.open && minute < close {
+            MarketSession::Regular
+        } else if minute >= exchange.pre_open && minute < exchange.open {
+            MarketSession::PreMarket
+        } else if minute >= close && minute < post_close {
+            MarketSession::PostMarket
+        } else {
+            MarketSession::Closed
+        }
+    }
+
+    pub fn session_for_ticker(&self, ticker: &str, ts: i64) -> MarketSession {
+        self.session_at(Self::exchange_for_ticker(ticker), ts)
+    }
+
+    /// Start of the next regular session after `ts`, within two weeks.
+    pub fn next_open(&self, exchange: &Exchange, ts: i64) -> Option<i64> {
+        let local = exchange.timezone.timestamp_opt(ts, 0).single()?;
+        let mut date = local.date_naive();
+        for _ in 0..14 {
+            let open = date.and_hms_opt((exchange.open / 60) as u32, (exchange.open % 60) as u32, 0)?;
+            if let Some(open_ts) = exchange.timezone.from_local_datetime(&open).earliest().map(|dt| dt.timestamp()) {
+                if open_ts > ts && self.session_at(exchange, open_ts) == MarketSession::Regular {
+                    return Some(open_ts);
+                }
+            }
+            date += Duration::days(1);
+        }
+        None
+    }
+
+    pub fn status(&self, exchange: &Exchange, ts: i64) -> MarketStatus {
+        let local = exchange.timezone.timestamp_opt(ts, 0).single();
+        let session = self.session_at(exchange, ts);
+        MarketStatus {
+            exchange: exchange.code.to_string(),
+            name: exchange.name.to_string(),
+            timezone: exchange.timezone.name().to_string(),
+            session,
+            local_time: local.map(|l| l.format("%Y-%m-%d %H:%M %Z").to_string()).unwrap_or_default(),
+            holiday: local.and_then(|l| self.holiday(exchange, l.date_naive())).map(|h| h.name),
+            next_open: if session == MarketSession::Regular { None } else { self.next_open(exchange, ts) },
+        }
+    }
+
+    /// Mark a quote with the session its market is in now.
+    pub fn annotate(&self, price: &mut MarketPrice) {
+        let now = Utc::now().timestamp();
+        price.session = Some(self.session_for_ticker(&price.ticker, now).as_str().to_string());
+    }
+}
+
+fn parse_hhmm(value: &str) -> Option<u16> {
+    let (hours, minutes) = value.split_once(':')?;
+    let (hours, minutes): (u16, u16) = (hours.parse().ok()?, minutes.parse().ok()?);
+    (hours < 24 && minutes < 60).then(|| hm(hours, minutes))
+}
+
+/// Easter Sunday (anonymous Gregorian algorithm).
+fn easter(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let (b, c) = (year / 100, year % 100);
+    let (d, e) = (b / 4, b % 4);
+    let g = (b - (b + 8) / 25 + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let (i, k) = (c / 4, c % 4);
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let n = h + l - 7 * m + 114;
+    NaiveDate::from_ymd_opt(year, (n / 31) as u32, (n % 31 + 1) as u32).expect("valid Easter date")
+}
+
+/// The `n`th `weekday` of a month; n = -1 for the last one.
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: i32) -> NaiveDate {
+    if n > 0 {
+        NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8).expect("valid weekday of month")
+    } else {
+        let mut date = NaiveDate::from_ymd_opt(year, month + 1, 1)
+            .unwrap_or_else(|| NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap())
+            - Duration::days(1);
+        while date.weekday() != weekday {
+            date -= Duration::days(1);
+        }
+        date
+    }
+}
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("valid date")
+}
+
+/// Weekend holidays move to the nearest weekday (US) or the next Monday (UK).
+fn observed(date: NaiveDate, saturday_to_friday: bool) -> Option<NaiveDate> {
+    match date.weekday() {
+        Weekday::Sat if saturday_to_friday => Some(date - Duration::days(1)),
+        Weekday::Sat => Some(date + Duration::days(2)),
+        Weekday::Sun => Some(date + Duration::days(1)),
+        _ => Some(date),
+    }
+}
+
+fn builtin_holidays(code: &str, year: i32) -> Vec<(NaiveDate, &'static str, Option<u16>)> {
+    let mut days: Vec<(Option<NaiveDate>, &'static str, Option<u16>)> = Vec::new();
+    let easter = easter(year);
+    match code {
+        "US" => {
+            // NYSE doesn't close on Dec 31 when New Year falls on a Saturday
+            let new_year = ymd(year, 1, 1);
+            let new_year = match new_year.weekday() {
+                Weekday::Sat => None,
+                _ => observed(new_year, false),
+            };
+            days.push((new_year, "New Year's Day", None));
+            days.push((Some(nth_weekday(year, 1, Weekday::Mon, 3)), "Martin Luther King Jr. Day", None));
+            days.push((Some(nth_weekday(year, 2, Weekday::Mon, 3)), "Washington's Birthday", None));
+            days.push((Some(easter - Duration::days(2)), "Good Friday", None));
+            days.push((Some(nth_weekday(year, 5, Weekday::Mon, -1)), "Memorial Day", None));
+            if year >= 2022 {
+                days.push((observed(ymd(year, 6, 19), true), "Juneteenth", None));
+            }
+            let independence = observed(ymd(year, 7, 4), true);
+            days.push((independence, "Independence Day", None));
+            let thanksgiving = nth_weekday(year, 11, Weekday::Thu, 4);
+            days.push((Some(nth_weekday(year, 9, Weekday::Mon, 1)), "Labor Day", None));
+            days.push((Some(thanksgiving), "Thanksgiving Day", None));
+            days.push((observed(ymd(year, 12, 25), true), "Christmas Day", None));
+
+            let early = Some(hm(13, 0));
+            let july_3 = ymd(year, 7, 3);
+            if independence != Some(july_3) && is_weekday(july_3) {
+                days.push((Some(july_3), "Independence Day (early close)", early));
+            }
+            days.push((Some(thanksgiving + Duration::days(1)), "Day after Thanksgiving (early close)", early));
+            let christmas_eve = ymd(year, 12, 24);
+            if is_weekday(christmas_eve) && observed(ymd(year, 12, 25), true) != Some(christmas_eve) {
+                days.push((Some(christmas_eve), "Christmas Eve (early close)", early));
+            }
+        }
+        "XETRA" => {
+            days.push((Some(ymd(year, 1, 1)), "New Year's Day", None));
+            days.push((Some(easter - Duration::days(2)), "Good Friday", None));
+            days.push((Some(easter + Duration::days(1)), "Easter Monday", None));
+            days.push((Some(ymd(year, 5, 1)), "Labour Day", None));
+            days.push((Some(ymd(year, 12, 24)), "Christmas Eve", None));
+            days.push((Some(ymd(year, 12, 25)), "Christmas Day", None));
+            days.push((Some(ymd(year, 12, 26)), "Boxing Day", None));
+            days.push((Some(ymd(year, 12, 31)), "New Year's Eve", None));
+        }
+        "LSE" => {
+            days.push((observed(ymd(year, 1, 1), false), "New Year's Day", None));
+            days.push((Some(easter - Duration::days(2)), "Good Friday", None));
+            days.push((Some(easter + Duration::days(1)), "Easter Monday", None));
+            days.push((Some(nth_weekday(year, 5, Weekday::Mon, 1)), "Early May Bank Holiday", None));
+            days.push((Some(nth_weekday(year, 5, Weekday::Mon, -1)), "Spring Bank Holiday", None));
+            days.push((Some(nth_weekday(year, 8, Weekday::Mon, -1)), "Summer Bank Holiday", None));
+            // Christmas on a Saturday moves to Monday and Boxing Day to Tuesday
+            let christmas = ymd(year, 12, 25);
+            let (christmas_obs, boxing_obs) = match christmas.weekday() {
+                Weekday::Fri => (christmas, christmas + Duration::days(3)),
+                Weekday::Sat => (christmas + Duration::days(2), christmas + Duration::days(3)),
+                Weekday::Sun => (christmas + Duration::days(2), christmas + Duration::days(1)),
+                _ => (christmas, christmas + Duration::days(1)),
+            };
+            days.push((Some(christmas_obs), "Christmas Day", None));
+            days.push((Some(boxing_obs), "Boxing Day", None));
+            for (day, name) in [(24, "Christmas Eve (early close)"), (31, "New Year's Eve (early close)")] {
+                let date = ymd(year, 12, day);
+                if is_weekday(date) {
+                    days.push((Some(date), name, Some(hm(12, 30))));
+                }
+            }
+        }
+        _ => {}
+    }
+    days.into_iter()
+        .filter_map(|(date, name, early_close)| date.map(|d| (d, name, early_close)))
+        .filter(|(date, _, _)| is_weekday(*date))
+        .collect()
+}
+
+fn is_weekday(date: NaiveDate) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(year: i32, month: u32, day: u32, hour: u32, minute: u32, tz: Tz) -> i64 {
+        tz.with_ymd_and_hms(year, month, day, hour, minute, 0).single().unwrap().timestamp()
+    }
+
+    #[test]
+    fn us_holidays_follow_nyse_rules() {
+        let calendar = MarketCalendar::default();
+        let us = MarketCalendar::exchange("US").unwrap();
+        let closed: Vec<String> = calendar
+            .holidays(us, 2024)
+            .into_iter()
+            .filter(|h| h.early_close.is_none())
+            .map(|h| h.date.format("%m-%d").to_string())
+            .collect();
+        assert_eq!(
+            closed,
+            vec!["01-01", "01-15", "02-19", "03-29", "05-27", "06-19", "07-04", "09-02", "11-28", "12-25"]
+        );
+        // Independence Day on a Saturday is observed on Friday
+        assert!(calendar.holiday(us, ymd(2026, 7, 3)).is_some_and(|h| h.early_close.is_none()));
+    }
+
+    #[test]
+    fn sessions_use_exchange_local_time() {
+        let calendar = MarketCalendar::default();
+        let ny = chrono_tz::America::New_York;
+        let us = MarketCalendar::exchange("US").unwrap();
+        assert_eq!(calendar.session_at(us, ts(2024, 3, 28, 8, 0, ny)), MarketSession::PreMarket);
+        assert_eq!(calendar.session_at(us, ts(2024, 3, 28, 10, 0, ny)), MarketSession::Regular);
+        assert_eq!(calendar.session_at(us, ts(2024, 3, 28, 17, 0, ny)), MarketSession::PostMarket);
+        assert_eq!(calendar.session_at(us, ts(2024, 3, 29, 10, 0, ny)), MarketSession::Closed);
+        // Early close the day after Thanksgiving
+        assert_eq!(calendar.session_at(us, ts(2024, 11, 29, 13, 30, ny)), MarketSession::PostMarket);
+
+        let crypto = MarketCalendar::exchange_for_ticker("BTC-USD");
+        assert_eq!(calendar.session_at(crypto, ts(2024, 3, 30, 3, 0, ny)), MarketSession::Regular);
+        assert_eq!(MarketCalendar::exchange_for_ticker("BRK-B").code, "US");
+        assert_eq!(MarketCalendar::exchange_for_ticker("sap.de").code, "XETRA");
+    }
+
+    #[test]
+    fn next_open_skips_weekends_and_holidays() {
+        let calendar = MarketCalendar::default();
+        let ny = chrono_tz::America::New_York;
+        let us = MarketCalendar::exchange("US").unwrap();
+        // Thursday evening before Good Friday: next open is Monday
+        assert_eq!(calendar.next_open(us, ts(2024, 3, 28, 18, 0, ny)), Some(ts(2024, 4, 1, 9, 30, ny)));
+    }
+
+    #[test]
+    fn custom_holidays_override_builtin_ones() {
+        let calendar = MarketCalendar {
+            custom: vec![MarketHoliday {
+                id: 1,
+                exchange: "US".to_string(),
+                date: "2025-01-09".to_string(),
+                name: "National Day of Mourning".to_string(),
+                early_close: None,
+                created_at: 0,
+            }],
+        };
+        let us = MarketCalendar::exchange("US").unwrap();
+        let ny = chrono_tz::America::New_York;
+        assert_eq!(calendar.session_at(us, ts(2025, 1, 9, 11, 0, ny)), MarketSession::Closed);
+        assert_eq!(calendar.status(us, ts(2025, 1, 9, 11, 0, ny)).holiday.as_deref(), Some("National Day of Mourning"));
+    }
+}
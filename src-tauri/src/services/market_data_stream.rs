@@ -1,12 +1,13 @@
 use crate::storage::market_data::MarketPrice;
 use crate::ws::{WsMessage, WsServer};
 use crate::providers::market_data::MarketDataManager;
+use crate::services::market_calendar::{MarketCalendar, MarketSession};
+use crate::services::watchdog::Watchdog;
 use crate::storage::market_data::MarketDataStore;
 use crate::storage::Database;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tokio::time::{interval, Duration};
-use tauri::Emitter;
 
 pub struct MarketDataStreamer {
     ws_server: Arc<WsServer>,
@@ -63,10 +64,26 @@ impl MarketDataStreamer {
         let subscribers = self.subscribers.clone();
 
         tauri::async_runtime::spawn(async move {
-            let mut interval = interval(Duration::from_millis(100)); // Batch every 100ms
+            let mut interval = interval(Duration::from_millis(crate::ws::TICK_MS));
+            let mut last_sent = None;
+            let heartbeat = Watchdog::register("market_data_batching", Duration::from_millis(crate::ws::TICK_MS));
 
             loop {
                 interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
+
+                // Flush at the market data interval, slower while every pending quote's market is closed
+                let every = ws_server.broadcast_intervals();
+                let (empty, any_open) = match pending_updates.lock() {
+                    Ok(pending) => (pending.is_empty(), pending.values().any(|p| p.session.as_deref() != Some("closed"))),
+                    Err(_) => (true, true),
+                };
+                let every_ms = if any_open { every.market_data_ms } else { every.market_data_closed_ms };
+                if empty || !crate::ws::due(&mut last_sent, every_ms) {
+                    continue;
+                }
 
                 // Get pending updates
                 let updates: Vec<MarketPrice> = {
@@ -100,7 +117,7 @@ impl MarketDataStreamer {
                     let _ = ws_server.publish("market-data", msg.clone());
 
                     // Also emit Tauri event for frontend
-                    let _ = app.emit("ws-message", serde_json::json!({
+                    let _ = crate::services::window_router::emit(&app, serde_json::json!({
                         "type": "market-data-batch",
                         "data": updates,
                         "timestamp": chrono::Utc::now().timestamp_millis(),
@@ -126,9 +143,13 @@ impl MarketDataStreamer {
             let mut interval = interval(Duration::from_secs(5)); // Fetch every 5 seconds
             let manager = MarketDataManager::new(api_key_manager.as_ref().map(|m| m.as_ref()));
             let last_fetch_time = Arc::new(Mutex::new(std::collections::HashMap::<String, i64>::new()));
+            let heartbeat = Watchdog::register("market_data_fetching", Duration::from_secs(5));
 
             loop {
                 interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
 
                 // Get subscribed tickers
                 let tickers_to_fetch: Vec<String> = {
@@ -142,6 +163,15 @@ impl MarketDataStreamer {
                 };
 
                 if !tickers_to_fetch.is_empty() {
+                    let calendar = {
+                        let db_guard = db.lock()
+                            .map_err(|e| {
+                                eprintln!("Failed to lock database: {}", e);
+                                e
+                            })
+                            .unwrap_or_else(|_| panic!("Database mutex poisoned"));
+                        MarketCalendar::load(db_guard.conn.clone())
+                    };
                     // Rate limit: only fetch if last fetch was > 1 second ago
                     let now = chrono::Utc::now().timestamp();
                     let tickers_to_fetch_now: Vec<String> = {
@@ -155,6 +185,10 @@ impl MarketDataStreamer {
                             .iter()
                             .filter(|ticker| {
                                 let last = lft.get(*ticker).copied().unwrap_or(0);
+                                // Closed markets don't move; fetch once for a starting quote
+                                if last > 0 && calendar.session_for_ticker(ticker, now) == MarketSession::Closed {
+                                    return false;
+                                }
                                 now - last >= 1 // At least 1 second between fetches per ticker
                             })
                             .cloned()
@@ -190,13 +224,14 @@ impl MarketDataStreamer {
                             let store = MarketDataStore::new(conn);
 
                             for price_data in prices {
-                                let price = MarketPrice {
+                                let mut price = MarketPrice {
                                     ticker: price_data.ticker.clone(),
                                     price: price_data.price,
                                     change: price_data.change,
                                     change_percent: price_data.change_percent,
                                     volume: price_data.volume,
                                     timestamp: price_data.timestamp,
+                                    session: None,
                                 };
 
                                 // Cache in database
@@ -205,6 +240,7 @@ impl MarketDataStreamer {
                                 }
 
                                 // Push to streamer for batching
+                                calendar.annotate(&mut price);
                                 {
                                     let mut pending = pending_updates.lock()
                                         .map_err(|e| {
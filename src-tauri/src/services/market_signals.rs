@@ -0,0 +1,174 @@
+use crate::providers::market_data::MarketDataManager;
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::rate_limiter::RateLimiter;
+use crate::storage::market_data::{short_interest_change_pct, AnalystRating, MarketDataStore, ShortInterest};
+use crate::storage::Database;
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// How often watchlist tickers get fresh short interest and ratings. 0 disables the refresh.
+pub const CONFIG_REFRESH_HOURS: &str = "market_signals_refresh_hours";
+const DEFAULT_REFRESH_HOURS: i64 = 24;
+const HISTORY_LIMIT: i64 = 24;
+
+/// Short interest and analyst rating history of one ticker, for its dossier.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TickerSignals {
+    pub ticker: String,
+    pub short_interest: Vec<ShortInterest>,
+    /// Change between the two latest short interest reports, in percent
+    pub short_interest_change_pct: Option<f64>,
+    pub rating_changes: Vec<AnalystRating>,
+}
+
+pub struct MarketSignalsService;
+
+impl MarketSignalsService {
+    /// Fetch short interest and rating changes for one ticker and store them.
+    /// Fails only when neither could be fetched.
+    pub async fn fetch(
+        db: &Arc<Mutex<Database>>,
+        api_key_manager: &APIKeyManager,
+        rate_limiter: Option<&RateLimiter>,
+        ticker: &str,
+    ) -> Result<TickerSignals> {
+        let ticker = ticker.trim().to_uppercase();
+        let manager = MarketDataManager::new(Some(api_key_manager));
+        let now = chrono::Utc::now().timestamp();
+
+        let short_interest = manager.get_short_interest(&ticker, rate_limiter).await;
+        let short_provider = manager.last_provider();
+        let ratings = manager.get_rating_changes(&ticker, rate_limiter).await;
+        let ratings_provider = manager.last_provider();
+
+        let conn = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            db_guard.conn.clone()
+        };
+        let store = MarketDataStore::new(conn.clone());
+
+        let (short_interest, ratings) = match (short_interest, ratings) {
+            (Err(e), Err(_)) => return Err(e),
+            both => both,
+        };
+        if let Ok(reports) = short_interest {
+            let reports: Vec<ShortInterest> = reports
+                .into_iter()
+                .map(|r| ShortInterest {
+                    ticker: ticker.clone(),
+                    report_ts: r.report_ts,
+                    shares_short: r.shares_short,
+                    short_percent_float: r.short_percent_float,
+                    days_to_cover: r.days_to_cover,
+                    provider: short_provider.clone(),
+                    fetched_at: now,
+                })
+                .collect();
+            store.upsert_short_interest(&reports)?;
+        }
+        if let Ok(changes) = ratings {
+            let changes: Vec<AnalystRating> = changes
+                .into_iter()
+                .map(|c| AnalystRating {
+                    id: 0,
+                    ticker: ticker.clone(),
+                    firm: c.firm,
+                    action: c.action,
+                    from_grade: c.from_grade,
+                    to_grade: c.to_grade,
+                    rated_at: c.rated_at,
+                    provider: ratings_provider.clone(),
+                    fetched_at: now,
+                })
+                .collect();
+            store.insert_analyst_ratings(&changes)?;
+        }
+        store.record_signal_fetch(&ticker, now)?;
+
+        Self::signals(conn, &ticker)
+    }
+
+    /// Refresh every watchlist ticker whose signals are older than the
+    /// configured interval. Returns how many were updated.
+    pub async fn refresh_watchlist(
+        db: &Arc<Mutex<Database>>,
+        api_key_manager: &APIKeyManager,
+        rate_limiter: Option<&RateLimiter>,
+    ) -> Result<usize> {
+        let tickers = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let hours = Self::refresh_hours(&db_guard);
+            if hours <= 0 {
+                return Ok(0);
+            }
+            MarketDataStore::new(db_guard.conn.clone()).watchlist_tickers_needing_signals(hours * 3600)?
+        };
+
+        let mut refreshed = 0;
+        for ticker in tickers {
+            match Self::fetch(db, api_key_manager, rate_limiter, &ticker).await {
+                Ok(_) => refreshed += 1,
+                Err(e) => eprintln!("Failed to refresh market signals for {}: {}", ticker, e),
+            }
+        }
+        Ok(refreshed)
+    }
+
+    pub fn start_scheduler(
+        db: Arc<Mutex<Database>>,
+        api_key_manager: Arc<APIKeyManager>,
+        rate_limiter: Arc<Mutex<RateLimiter>>,
+    ) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+
+            loop {
+                interval.tick().await;
+
+                let limiter = match rate_limiter.lock() {
+                    Ok(guard) => guard.clone(),
+                    Err(_) => continue,
+                };
+                match Self::refresh_watchlist(&db, &api_key_manager, Some(&limiter)).await {
+                    Ok(n) if n > 0 => eprintln!("Refreshed market signals for {} tickers", n),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Market signals refresh failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Stored history of one ticker.
+    pub fn signals(conn: Arc<Mutex<Connection>>, ticker: &str) -> Result<TickerSignals> {
+        let store = MarketDataStore::new(conn);
+        let ticker = ticker.trim().to_uppercase();
+        let short_interest = store.list_short_interest(&ticker, HISTORY_LIMIT)?;
+        Ok(TickerSignals {
+            short_interest_change_pct: short_interest_change_pct(&short_interest),
+            rating_changes: store.list_analyst_ratings(&ticker, None, HISTORY_LIMIT)?,
+            short_interest,
+            ticker,
+        })
+    }
+
+    /// Signals of the ticker an entity resolves to, when there are any.
+    pub fn signals_for_entity(conn: Arc<Mutex<Connection>>, entity: &str) -> Result<Option<TickerSignals>> {
+        let Some(ticker) = MarketDataStore::new(conn.clone()).resolve_ticker(entity)? else {
+            return Ok(None);
+        };
+        let signals = Self::signals(conn, &ticker)?;
+        Ok((!signals.short_interest.is_empty() || !signals.rating_changes.is_empty()).then_some(signals))
+    }
+
+    fn refresh_hours(db: &Database) -> i64 {
+        db.get_config(CONFIG_REFRESH_HOURS)
+            .ok()
+            .flatten()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_REFRESH_HOURS)
+    }
+}
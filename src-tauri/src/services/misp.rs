@@ -0,0 +1,460 @@
+use crate::services::api_key_manager::APIKeyManager;
+use crate::storage::osint::Ioc;
+use crate::storage::temporal::TemporalStore;
+use crate::storage::{Database, NewIoc, OSINTStore};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// MISP instance settings as JSON. The API key is stored through the API
+/// key manager under "misp".
+pub const CONFIG_MISP: &str = "misp_config";
+/// Outcome of the last push and pull, as JSON.
+const CONFIG_SYNC_STATUS: &str = "misp_sync_status";
+pub const API_KEY_PROVIDER: &str = "misp";
+/// IOCs pulled from MISP carry this source.
+pub const SOURCE: &str = "misp";
+
+const SCHEDULER_TICK_SECS: u64 = 15 * 60;
+const MAX_PULL_ATTRIBUTES: usize = 5000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MispConfig {
+    pub base_url: String,
+    /// MISP distribution level of pushed events, 0 (your organisation only) by default
+    #[serde(default)]
+    pub distribution: u8,
+    /// Local tag to MISP tag, e.g. "apt" -> "misp-galaxy:threat-actor=\"APT28\"".
+    /// Pulled tags are mapped back; unmapped tags pass through as is.
+    #[serde(default)]
+    pub tag_map: HashMap<String, String>,
+    /// Only pull attributes carrying one of these MISP tags
+    #[serde(default)]
+    pub pull_tags: Vec<String>,
+    #[serde(default = "default_pull_days")]
+    pub pull_days: i64,
+    /// Pull automatically every this many minutes; manual only when unset
+    #[serde(default)]
+    pub pull_interval_minutes: Option<i64>,
+}
+
+fn default_pull_days() -> i64 {
+    7
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MispSyncStatus {
+    pub configured: bool,
+    pub base_url: Option<String>,
+    pub last_push_at: Option<i64>,
+    pub last_pushed_events: Vec<String>,
+    pub last_pull_at: Option<i64>,
+    pub last_pulled: usize,
+    pub last_error: Option<String>,
+    /// IOCs in the local store that came from MISP
+    pub local_iocs: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MispPushReport {
+    /// IDs of the created MISP events
+    pub events: Vec<String>,
+    pub attributes: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MispPullReport {
+    pub attributes: usize,
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+pub struct MispService;
+
+impl MispService {
+    pub fn config(db: &Database) -> Result<Option<MispConfig>> {
+        match db.get_config(CONFIG_MISP)? {
+            Some(raw) if !raw.trim().is_empty() => {
+                Ok(Some(serde_json::from_str(&raw).context("Invalid MISP config")?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    pub fn status(db: &Database) -> Result<MispSyncStatus> {
+        let mut status: MispSyncStatus = db
+            .get_config(CONFIG_SYNC_STATUS)?
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        let config = Self::config(db)?;
+        status.configured = config.is_some();
+        status.base_url = config.map(|c| c.base_url);
+        status.local_iocs = OSINTStore::new(db.conn.clone()).count_iocs(Some(SOURCE))?;
+        Ok(status)
+    }
+
+    fn update_status(db: &Mutex<Database>, update: impl FnOnce(&mut MispSyncStatus)) -> Result<()> {
+        let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let mut status = Self::status(&db_guard)?;
+        update(&mut status);
+        db_guard.set_config(CONFIG_SYNC_STATUS, &serde_json::to_string(&status)?)
+    }
+
+    /// Push IOCs as one MISP event and each temporal event as its own MISP
+    /// event, with its evidence links and the IOCs found in its articles.
+    pub async fn push(
+        db: &Mutex<Database>,
+        api_key_manager: &APIKeyManager,
+        ioc_ids: &[i64],
+        event_ids: &[i64],
+    ) -> Result<MispPushReport> {
+        let (config, events) = {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let config = Self::config(&db_guard)?.ok_or_else(|| anyhow::anyhow!("MISP is not configured"))?;
+            let events = build_events(&db_guard, &config, ioc_ids, event_ids)?;
+            (config, events)
+        };
+        if events.is_empty() {
+            anyhow::bail!("Nothing to push");
+        }
+        let client = MispClient::new(&config, api_key_manager)?;
+
+        let mut report = MispPushReport::default();
+        let mut result = Ok(());
+        for event in &events {
+            match client.add_event(event).await {
+                Ok(id) => {
+                    report.attributes += event["Event"]["Attribute"].as_array().map_or(0, |a| a.len());
+                    report.events.push(id);
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let error = result.as_ref().err().map(|e| e.to_string());
+        Self::update_status(db, |status| {
+            status.last_push_at = Some(now);
+            status.last_pushed_events = report.events.clone();
+            status.last_error = error;
+        })?;
+        result.map(|_| report)
+    }
+
+    /// Import recent MISP attributes that map to an IOC type into the local
+    /// IOC store, translating tags through the tag map.
+    pub async fn pull(db: &Mutex<Database>, api_key_manager: &APIKeyManager) -> Result<MispPullReport> {
+        let config = {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            Self::config(&db_guard)?.ok_or_else(|| anyhow::anyhow!("MISP is not configured"))?
+        };
+        let client = MispClient::new(&config, api_key_manager)?;
+        let attributes = match client.search_attributes(&config).await {
+            Ok(attributes) => attributes,
+            Err(e) => {
+                let message = e.to_string();
+                Self::update_status(db, |status| status.last_error = Some(message))?;
+                return Err(e);
+            }
+        };
+
+        let reverse_tags: HashMap<&str, &str> =
+            config.tag_map.iter().map(|(local, remote)| (remote.as_str(), local.as_str())).collect();
+        let mut report = MispPullReport {
+            attributes: attributes.len(),
+            ..Default::default()
+        };
+        {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let store = OSINTStore::new(db_guard.conn.clone());
+            for attribute in &attributes {
+                let Some(ioc) = ioc_from_attribute(attribute, &reverse_tags) else {
+                    report.skipped += 1;
+                    continue;
+                };
+                store.upsert_ioc(&ioc)?;
+                report.imported += 1;
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        Self::update_status(db, |status| {
+            status.last_pull_at = Some(now);
+            status.last_pulled = report.imported;
+            status.last_error = None;
+        })?;
+        Ok(report)
+    }
+
+    /// Pull on the configured interval.
+    pub fn start_scheduler(db: Arc<Mutex<Database>>, api_key_manager: Arc<APIKeyManager>) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(SCHEDULER_TICK_SECS));
+
+            loop {
+                interval.tick().await;
+                let due = {
+                    let db_guard = match db.lock() {
+                        Ok(guard) => guard,
+                        Err(_) => continue,
+                    };
+                    let interval_minutes = match Self::config(&db_guard) {
+                        Ok(Some(config)) => config.pull_interval_minutes.filter(|m| *m > 0),
+                        _ => None,
+                    };
+                    let last_pull = Self::status(&db_guard).ok().and_then(|s| s.last_pull_at).unwrap_or(0);
+                    interval_minutes
+                        .is_some_and(|m| chrono::Utc::now().timestamp() - last_pull >= m * 60)
+                };
+                if !due {
+                    continue;
+                }
+                match Self::pull(&db, &api_key_manager).await {
+                    Ok(report) if report.imported > 0 => eprintln!("MISP pull: {} IOCs imported", report.imported),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("MISP pull failed: {}", e),
+                }
+            }
+        });
+    }
+}
+
+struct MispClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+impl MispClient {
+    fn new(config: &MispConfig, api_key_manager: &APIKeyManager) -> Result<Self> {
+        let base_url = config.base_url.trim().trim_end_matches('/').to_string();
+        if base_url.is_empty() {
+            anyhow::bail!("MISP base_url not configured");
+        }
+        let api_key = api_key_manager
+            .get_key_optional(API_KEY_PROVIDER)?
+            .ok_or_else(|| anyhow::anyhow!("No MISP API key stored"))?;
+        Ok(MispClient {
+            client: reqwest::Client::new(),
+            base_url,
+            api_key,
+        })
+    }
+
+    async fn post(&self, path: &str, body: &Value) -> Result<Value> {
+        let response = self
+            .client
+            .post(format!("{}{}", self.base_url, path))
+            .header("Authorization", &self.api_key)
+            .header("Accept", "application/json")
+            .json(body)
+            .send()
+            .await
+            .context("Failed to send MISP request")?;
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            anyhow::bail!("MISP API error: {} - {}", status, error_text);
+        }
+        response.json().await.context("Failed to parse MISP response")
+    }
+
+    /// Create an event; returns its MISP ID.
+    async fn add_event(&self, event: &Value) -> Result<String> {
+        let created = self.post("/events/add", event).await?;
+        created["Event"]["id"]
+            .as_str()
+            .map(|id| id.to_string())
+            .ok_or_else(|| anyhow::anyhow!("MISP did not return the created event"))
+    }
+
+    async fn search_attributes(&self, config: &MispConfig) -> Result<Vec<Value>> {
+        let mut query = json!({
+            "returnFormat": "json",
+            "last": format!("{}d", config.pull_days.max(1)),
+            "to_ids": true,
+            "limit": MAX_PULL_ATTRIBUTES,
+            "includeEventTags": true,
+        });
+        if !config.pull_tags.is_empty() {
+            query["tags"] = json!(config.pull_tags);
+        }
+        let body = self.post("/attributes/restSearch", &query).await?;
+        Ok(body["response"]["Attribute"].as_array().cloned().unwrap_or_default())
+    }
+}
+
+fn build_events(db: &Database, config: &MispConfig, ioc_ids: &[i64], event_ids: &[i64]) -> Result<Vec<Value>> {
+    let osint = OSINTStore::new(db.conn.clone());
+    let temporal = TemporalStore::new(db.conn.clone());
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    let mut events = Vec::new();
+
+    let mut iocs = Vec::new();
+    for id in ioc_ids {
+        if let Some(ioc) = osint.get_ioc(*id)? {
+            iocs.push(ioc);
+        }
+    }
+    if !iocs.is_empty() {
+        let attributes: Vec<Value> = iocs.iter().filter_map(|i| ioc_attribute(i, config)).collect();
+        events.push(misp_event(config, &format!("MINA indicators {}", today), &today, attributes));
+    }
+
+    for event_id in event_ids {
+        let Some(event) = temporal.get_event(*event_id)? else { continue };
+        let mut attributes = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for evidence in temporal.list_event_evidence(event.id)? {
+            let Some(article) = osint.get_item(evidence.rss_item_id)? else { continue };
+            attributes.push(json!({
+                "type": "link",
+                "category": "External analysis",
+                "value": article.url,
+                "comment": article.title,
+                "to_ids": false,
+            }));
+            for ioc in osint.iocs_for_article(article.id)? {
+                if seen.insert(ioc.id) {
+                    attributes.extend(ioc_attribute(&ioc, config));
+                }
+            }
+        }
+        let date = chrono::DateTime::from_timestamp(event.start_ts, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| today.clone());
+        let mut misp = misp_event(config, &event.title, &date, attributes);
+        if !event.summary.trim().is_empty() {
+            if let Some(attributes) = misp["Event"]["Attribute"].as_array_mut() {
+                attributes.push(json!({
+                    "type": "text",
+                    "category": "Other",
+                    "value": event.summary,
+                    "comment": "MINA event summary",
+                    "to_ids": false,
+                }));
+            }
+        }
+        events.push(misp);
+    }
+    Ok(events)
+}
+
+fn misp_event(config: &MispConfig, info: &str, date: &str, attributes: Vec<Value>) -> Value {
+    json!({
+        "Event": {
+            "info": info,
+            "date": date,
+            "distribution": config.distribution,
+            "threat_level_id": 4, // undefined
+            "analysis": 0,        // initial
+            "Attribute": attributes,
+        }
+    })
+}
+
+fn ioc_attribute(ioc: &Ioc, config: &MispConfig) -> Option<Value> {
+    let (attribute_type, category) = match ioc.ioc_type.as_str() {
+        "ipv4" | "ipv6" => ("ip-dst", "Network activity"),
+        "domain" => ("domain", "Network activity"),
+        "url" => ("url", "Network activity"),
+        "email" => ("email-src", "Payload delivery"),
+        "md5" => ("md5", "Payload delivery"),
+        "sha1" => ("sha1", "Payload delivery"),
+        "sha256" => ("sha256", "Payload delivery"),
+        _ => return None,
+    };
+    let tags: Vec<Value> = ioc
+        .tags
+        .iter()
+        .map(|t| json!({ "name": config.tag_map.get(t).unwrap_or(t) }))
+        .collect();
+    Some(json!({
+        "type": attribute_type,
+        "category": category,
+        "value": ioc.value,
+        "comment": ioc.description.clone().unwrap_or_default(),
+        "to_ids": true,
+        "Tag": tags,
+    }))
+}
+
+/// The local IOC for a MISP attribute, if its type maps to one.
+fn ioc_from_attribute(attribute: &Value, reverse_tags: &HashMap<&str, &str>) -> Option<NewIoc> {
+    let attribute_type = attribute["type"].as_str()?;
+    let raw = attribute["value"].as_str()?.trim();
+    // Composite types carry "filename|hash" or "ip|port"
+    let (ioc_type, value) = match attribute_type {
+        "ip-dst" | "ip-src" => {
+            let ioc_type = if raw.contains(':') { "ipv6" } else { "ipv4" };
+            (ioc_type, raw)
+        }
+        "ip-dst|port" | "ip-src|port" => {
+            let ip = raw.split('|').next()?;
+            (if ip.contains(':') { "ipv6" } else { "ipv4" }, ip)
+        }
+        "domain" | "hostname" => ("domain", raw),
+        "domain|ip" => ("domain", raw.split('|').next()?),
+        "url" | "uri" => ("url", raw),
+        "email" | "email-src" | "email-dst" => ("email", raw),
+        "md5" | "sha1" | "sha256" => (attribute_type, raw),
+        "filename|md5" => ("md5", raw.split('|').nth(1)?),
+        "filename|sha1" => ("sha1", raw.split('|').nth(1)?),
+        "filename|sha256" => ("sha256", raw.split('|').nth(1)?),
+        _ => return None,
+    };
+    if value.is_empty() {
+        return None;
+    }
+
+    let mut tags: Vec<String> = Vec::new();
+    for tag in attribute["Tag"].as_array().into_iter().flatten() {
+        if let Some(name) = tag["name"].as_str() {
+            let local = reverse_tags.get(name).copied().unwrap_or(name).to_string();
+            if !tags.contains(&local) {
+                tags.push(local);
+            }
+        }
+    }
+    let comment = attribute["comment"].as_str().filter(|c| !c.trim().is_empty());
+    let event_info = attribute["Event"]["info"].as_str();
+
+    Some(NewIoc {
+        ioc_type: ioc_type.to_string(),
+        value: value.to_string(),
+        description: comment.or(event_info).map(|d| d.to_string()),
+        source: SOURCE.to_string(),
+        tags,
+        confidence: 0.7,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_attributes_and_tags_to_iocs() {
+        let reverse: HashMap<&str, &str> = [("tlp:amber", "amber")].into_iter().collect();
+        let attribute = json!({
+            "type": "filename|sha256",
+            "value": format!("dropper.exe|{}", "b".repeat(64)),
+            "comment": "",
+            "Event": { "info": "Phishing wave" },
+            "Tag": [{ "name": "tlp:amber" }, { "name": "phishing" }],
+        });
+        let ioc = ioc_from_attribute(&attribute, &reverse).unwrap();
+        assert_eq!(ioc.ioc_type, "sha256");
+        assert_eq!(ioc.value, "b".repeat(64));
+        assert_eq!(ioc.tags, vec!["amber".to_string(), "phishing".to_string()]);
+        assert_eq!(ioc.description.as_deref(), Some("Phishing wave"));
+
+        assert!(ioc_from_attribute(&json!({ "type": "text", "value": "x" }), &reverse).is_none());
+    }
+}
@@ -27,6 +27,87 @@ pub mod script_bridge;
 pub mod health_checker;
 pub mod health_check_service;
 pub mod analytics_collector;
+pub mod clipboard_monitor;
+pub mod repo_monitor;
+pub mod task_reminder;
+pub mod time_tracker;
+pub mod test_report_parser;
+pub mod test_runner;
+pub mod benchmark_tracker;
+pub mod chat_export;
+pub mod prompt_bundle;
+pub mod dashboard_layouts;
+pub mod process_guardian;
+pub mod remote_hosts;
+pub mod ssh_tunnels;
+pub mod model_benchmark;
+pub mod ollama_lifecycle;
+pub mod context_window;
+pub mod structured_output;
+pub mod redaction;
+pub mod llm_router;
+pub mod watch_folders;
+pub mod statement_import;
+pub mod db_housekeeping;
+pub mod timezone;
+pub mod report_builder;
+pub mod event_summaries;
+pub mod sentiment_divergence;
+pub mod watchdog;
+pub mod entity_reextraction;
+pub mod response_cache;
+pub mod alert_rule_library;
+pub mod threshold_tuner;
+pub mod conversation_memory;
+pub mod llm_metering;
+pub mod event_analyst;
+pub mod portfolio_qa;
+pub mod profile_manager;
+pub mod sync_client;
+pub mod backup;
+pub mod object_storage;
+pub mod fundamentals;
+pub mod fx;
+pub mod tax_lots;
+pub mod rebalancer;
+pub mod stress_test;
+pub mod chart_downsampling;
+pub mod event_price_correlation;
+pub mod news_volume;
+pub mod story_lifecycle;
+pub mod corroboration;
+pub mod event_retention;
+pub mod article_cleanup;
+pub mod related_articles;
+pub mod watchlist_analytics;
+pub mod rule_sandbox;
+pub mod alert_templates;
+pub mod calendar_ics;
+pub mod issue_tracker;
+pub mod vault_export;
+pub mod citation_export;
+pub mod stix;
+pub mod misp;
+pub mod ocr;
+pub mod document_ingest;
+pub mod article_ocr;
+pub mod transcription;
+pub mod command_trace;
+pub mod job_manager;
+pub mod notification_outbox;
+pub mod startup_profile;
+pub mod window_router;
+pub mod command_palette;
+pub mod updater;
+pub mod crash_reporter;
+pub mod demo_data;
+pub mod symbol_search;
+pub mod market_calendar;
+pub mod market_signals;
+pub mod alert_context;
+pub mod chart_annotations;
+pub mod data_quality;
+pub mod change_feed;
 
 pub use event_bridge::EventBridge;
 pub use command_dispatcher::CommandDispatcher;
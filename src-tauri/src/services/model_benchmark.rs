@@ -0,0 +1,286 @@
+use crate::providers::ollama::{ChatMessage, OllamaProvider};
+use crate::services::ollama_lifecycle::ModelLifecycle;
+use crate::storage::model_benchmarks::{ModelBenchmarkResult, ModelBenchmarkStore, ModelBenchmarkSummary};
+use crate::storage::Database;
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Model name that services resolve to the benchmark winner for their task.
+pub const AUTO_MODEL: &str = "auto";
+/// Quality tier "auto" has to meet; "standard" when unset.
+pub const CONFIG_MIN_TIER: &str = "model_auto_min_tier";
+
+pub const TASK_SUMMARIZATION: &str = "summarization";
+pub const TASK_EXTRACTION: &str = "extraction";
+pub const TASK_GENERAL: &str = "general";
+
+/// A standard prompt with a cheap check of whether the answer is usable.
+struct BenchmarkPrompt {
+    id: &'static str,
+    task: &'static str,
+    system: &'static str,
+    prompt: &'static str,
+    check: fn(&str) -> bool,
+}
+
+/// The JSON value in a response, ignoring any prose around it.
+fn json_in(response: &str) -> Option<serde_json::Value> {
+    let start = response.find(['{', '['])?;
+    let end = response.rfind(['}', ']'])?;
+    serde_json::from_str(response.get(start..=end)?).ok()
+}
+
+const PROMPTS: &[BenchmarkPrompt] = &[
+    BenchmarkPrompt {
+        id: "summarize-rates",
+        task: TASK_SUMMARIZATION,
+        system: "Summarize the text in one sentence.",
+        prompt: "The central bank held its benchmark interest rate at 4.5% on Wednesday, as expected, but \
+signalled that two cuts were likely before the end of the year as inflation cooled for a third straight \
+month. Bond yields fell after the announcement and the currency weakened against the dollar.",
+        check: |r| {
+            let r = r.to_lowercase();
+            r.len() < 400 && r.contains("rate") && (r.contains("cut") || r.contains("held") || r.contains("hold"))
+        },
+    },
+    BenchmarkPrompt {
+        id: "summarize-earnings",
+        task: TASK_SUMMARIZATION,
+        system: "Summarize the text in one sentence.",
+        prompt: "Shares of the chipmaker jumped 12% in after-hours trading after it reported quarterly revenue \
+of $35 billion, ahead of analyst estimates of $33 billion, driven by record demand for data-center \
+processors. The company also raised its full-year guidance.",
+        check: |r| {
+            let r = r.to_lowercase();
+            r.len() < 400 && (r.contains("revenue") || r.contains("guidance") || r.contains("demand"))
+        },
+    },
+    BenchmarkPrompt {
+        id: "extract-entities",
+        task: TASK_EXTRACTION,
+        system: "Extract the organizations and people named in the text. Respond with only a JSON array of strings.",
+        prompt: "Apple chief executive Tim Cook met Nvidia's Jensen Huang in Santa Clara on Tuesday to discuss \
+chip supply, according to Reuters.",
+        check: |r| {
+            let Some(serde_json::Value::Array(items)) = json_in(r) else { return false };
+            let names: Vec<String> = items.iter().filter_map(|i| i.as_str()).map(|s| s.to_lowercase()).collect();
+            ["apple", "nvidia", "tim cook"].iter().all(|want| names.iter().any(|n| n.contains(want)))
+        },
+    },
+    BenchmarkPrompt {
+        id: "extract-facts",
+        task: TASK_EXTRACTION,
+        system: "Respond with only a JSON object: {\"ticker\": \"...\", \"price\": number, \"direction\": \"up\"|\"down\"}.",
+        prompt: "Tesla (TSLA) closed down 4% at $212.40 after deliveries missed estimates.",
+        check: |r| {
+            let Some(value) = json_in(r) else { return false };
+            value["ticker"].as_str().is_some_and(|t| t.eq_ignore_ascii_case("TSLA"))
+                && value["price"].as_f64().is_some_and(|p| (p - 212.40).abs() < 0.01)
+                && value["direction"].as_str() == Some("down")
+        },
+    },
+    BenchmarkPrompt {
+        id: "general-arithmetic",
+        task: TASK_GENERAL,
+        system: "Answer with only the number.",
+        prompt: "What is 17 multiplied by 23?",
+        check: |r| r.contains("391"),
+    },
+];
+
+/// How reliably a model has to pass a task's benchmark prompts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QualityTier {
+    Basic,
+    Standard,
+    High,
+}
+
+impl QualityTier {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "basic" => Ok(QualityTier::Basic),
+            "standard" => Ok(QualityTier::Standard),
+            "high" => Ok(QualityTier::High),
+            other => anyhow::bail!("Unknown quality tier: {}", other),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QualityTier::Basic => "basic",
+            QualityTier::Standard => "standard",
+            QualityTier::High => "high",
+        }
+    }
+
+    /// Share of the task's prompts a model has to pass.
+    fn min_quality(&self) -> f64 {
+        match self {
+            QualityTier::Basic => 0.0,
+            QualityTier::Standard => 0.5,
+            QualityTier::High => 1.0,
+        }
+    }
+}
+
+/// The fastest model meeting `tier` for `task`. Models whose latest run
+/// errored on every prompt have no speed and are never picked.
+fn fastest(summaries: &[ModelBenchmarkSummary], task: &str, tier: QualityTier) -> Option<String> {
+    summaries
+        .iter()
+        .filter(|s| s.task == task && s.quality >= tier.min_quality())
+        .filter_map(|s| s.avg_tokens_per_sec.map(|tps| (s, tps)))
+        .max_by(|(a, a_tps), (b, b_tps)| {
+            a_tps.total_cmp(b_tps).then(b.avg_latency_ms.total_cmp(&a.avg_latency_ms))
+        })
+        .map(|(s, _)| s.model.clone())
+}
+
+/// Benchmarks installed Ollama models on a standard prompt set, and picks
+/// the fastest one that's good enough for a task.
+pub struct ModelBenchmark;
+
+impl ModelBenchmark {
+    /// Run every prompt against each model (all installed models when
+    /// `models` is None), storing one result per prompt.
+    pub async fn run(
+        provider: &OllamaProvider,
+        store: &ModelBenchmarkStore,
+        models: Option<Vec<String>>,
+    ) -> Result<Vec<ModelBenchmarkSummary>> {
+        let models = match models {
+            Some(models) if !models.is_empty() => models,
+            _ => provider.list_models().await?.into_iter().map(|m| m.name).collect(),
+        };
+        if models.is_empty() {
+            anyhow::bail!("No Ollama models installed");
+        }
+
+        let run_id = uuid::Uuid::new_v4().to_string();
+        for model in &models {
+            let mut memory_bytes = None;
+            for prompt in PROMPTS {
+                // Queued like any other request, so runs don't compete with chats
+                let permit = ModelLifecycle::acquire(model).await?;
+                let started = Instant::now();
+                let response = provider
+                    .chat_with_stats(model, vec![
+                        ChatMessage { role: "system".to_string(), content: prompt.system.to_string() },
+                        ChatMessage { role: "user".to_string(), content: prompt.prompt.to_string() },
+                    ])
+                    .await;
+                let elapsed_ms = started.elapsed().as_millis() as i64;
+                drop(permit);
+
+                // The model is loaded after its first answer
+                if memory_bytes.is_none() && response.is_ok() {
+                    memory_bytes = provider
+                        .running_models()
+                        .await
+                        .ok()
+                        .and_then(|running| running.into_iter().find(|m| &m.name == model))
+                        .map(|m| m.size as i64);
+                }
+
+                let mut result = ModelBenchmarkResult {
+                    id: 0,
+                    run_id: run_id.clone(),
+                    model: model.clone(),
+                    prompt_id: prompt.id.to_string(),
+                    task: prompt.task.to_string(),
+                    tokens_per_sec: None,
+                    latency_ms: elapsed_ms,
+                    load_ms: 0,
+                    completion_tokens: 0,
+                    memory_bytes,
+                    passed: false,
+                    error: None,
+                    created_at: chrono::Utc::now().timestamp(),
+                };
+                match response {
+                    Ok((content, stats)) => {
+                        result.tokens_per_sec = stats.tokens_per_sec();
+                        result.load_ms = stats.load_ms as i64;
+                        result.latency_ms = (elapsed_ms - result.load_ms).max(0);
+                        result.completion_tokens = stats.usage.completion_tokens;
+                        result.passed = (prompt.check)(&content);
+                    }
+                    Err(e) => result.error = Some(e.to_string()),
+                }
+                store.record_result(&result)?;
+            }
+        }
+
+        Ok(store
+            .list_summaries(None)?
+            .into_iter()
+            .filter(|s| s.run_id == run_id)
+            .collect())
+    }
+
+    pub fn select(store: &ModelBenchmarkStore, task: &str, tier: QualityTier) -> Result<Option<String>> {
+        Ok(fastest(&store.list_summaries(Some(task))?, task, tier))
+    }
+
+    /// The model to use for `task`: `model` itself, or the benchmark winner
+    /// at the configured tier when it's "auto".
+    pub fn resolve(conn: &Arc<Mutex<Connection>>, task: &str, model: &str) -> Result<String> {
+        if model != AUTO_MODEL {
+            return Ok(model.to_string());
+        }
+        let tier = Database { conn: conn.clone() }
+            .get_config(CONFIG_MIN_TIER)?
+            .as_deref()
+            .map(QualityTier::parse)
+            .transpose()?
+            .unwrap_or(QualityTier::Standard);
+        Self::select(&ModelBenchmarkStore::new(conn.clone()), task, tier)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "No benchmarked model meets the {} tier for {}; run the model benchmark first",
+                tier.as_str(),
+                task
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(model: &str, quality: f64, tokens_per_sec: Option<f64>) -> ModelBenchmarkSummary {
+        ModelBenchmarkSummary {
+            model: model.to_string(),
+            task: TASK_SUMMARIZATION.to_string(),
+            run_id: "run".to_string(),
+            prompts: 2,
+            quality,
+            avg_tokens_per_sec: tokens_per_sec,
+            avg_latency_ms: 1_000.0,
+            memory_bytes: None,
+            benchmarked_at: 0,
+        }
+    }
+
+    #[test]
+    fn picks_the_fastest_model_meeting_the_tier() {
+        let summaries = vec![
+            summary("tiny", 0.5, Some(120.0)),
+            summary("medium", 1.0, Some(45.0)),
+            summary("large", 1.0, Some(20.0)),
+            summary("broken", 1.0, None),
+        ];
+        assert_eq!(fastest(&summaries, TASK_SUMMARIZATION, QualityTier::Basic).as_deref(), Some("tiny"));
+        assert_eq!(fastest(&summaries, TASK_SUMMARIZATION, QualityTier::High).as_deref(), Some("medium"));
+        assert_eq!(fastest(&summaries, TASK_EXTRACTION, QualityTier::Basic), None);
+
+        assert!(PROMPTS.iter().find(|p| p.id == "extract-entities").is_some_and(|p| {
+            (p.check)("Sure: [\"Apple\", \"Tim Cook\", \"Nvidia\", \"Jensen Huang\"]") && !(p.check)("Apple, Nvidia")
+        }));
+    }
+}
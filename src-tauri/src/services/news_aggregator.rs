@@ -4,16 +4,21 @@ use crate::providers::news::{
 };
 use crate::services::{TickerMatcher, SentimentAnalyzer};
 use crate::services::api_key_manager::APIKeyManager;
+use crate::services::response_cache::ResponseCache;
 use crate::storage::{StockNewsItem, StockNewsStore};
 use anyhow::Result;
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
+
+/// How long a provider's full news response is served before it is refreshed
+const NEWS_CACHE_TTL_SECS: i64 = 300;
 
 pub struct NewsAggregator {
-    providers: Vec<Box<dyn NewsProvider>>,
+    providers: Vec<Arc<dyn NewsProvider>>,
     ticker_matcher: Arc<Mutex<TickerMatcher>>,
     store: Arc<Mutex<StockNewsStore>>,
+    response_cache: Option<ResponseCache>,
 }
 
 impl NewsAggregator {
@@ -22,17 +27,17 @@ impl NewsAggregator {
     }
 
     pub fn new_with_api_keys(store: Arc<Mutex<StockNewsStore>>, api_key_manager: Option<&APIKeyManager>) -> Result<Self> {
-        let mut providers: Vec<Box<dyn NewsProvider>> = vec![
-            Box::new(BloombergRSS::new()),
-            Box::new(ReutersRSS::new()),
-            Box::new(OtherFinancialRSS::new()),
+        let mut providers: Vec<Arc<dyn NewsProvider>> = vec![
+            Arc::new(BloombergRSS::new()),
+            Arc::new(ReutersRSS::new()),
+            Arc::new(OtherFinancialRSS::new()),
         ];
 
         // Add API-based providers if API keys are available
         if let Some(key_mgr) = api_key_manager {
-            providers.push(Box::new(NewsAPIProvider::new(Some(key_mgr))));
-            providers.push(Box::new(AlphaVantageNewsProvider::new(Some(key_mgr))));
-            providers.push(Box::new(FinnhubNewsProvider::new(Some(key_mgr))));
+            providers.push(Arc::new(NewsAPIProvider::new(Some(key_mgr))));
+            providers.push(Arc::new(AlphaVantageNewsProvider::new(Some(key_mgr))));
+            providers.push(Arc::new(FinnhubNewsProvider::new(Some(key_mgr))));
         }
 
         // Initialize ticker matcher
@@ -46,9 +51,16 @@ impl NewsAggregator {
             providers,
             ticker_matcher: Arc::new(Mutex::new(ticker_matcher)),
             store,
+            response_cache: None,
         })
     }
 
+    /// Serve full fetches from `cache`, refreshing stale responses in the background.
+    pub fn with_response_cache(mut self, cache: ResponseCache) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
     /// Fetch news from all providers
     pub async fn fetch_all_news(&self, symbols: &[String], since: Option<i64>) -> Result<Vec<StockNewsItem>> {
         let mut all_items = Vec::new();
@@ -56,13 +68,32 @@ impl NewsAggregator {
         // Fetch from all providers in parallel
         let mut futures = Vec::new();
         for provider in &self.providers {
+            let provider = provider.clone();
             let provider_name = provider.get_name().to_string();
             let symbols_clone = symbols.to_vec();
             let since_clone = since;
+            // Incremental fetches are always live
+            let cache = self.response_cache.clone().filter(|_| since.is_none());
             
             // Create a future for each provider
             let future = async move {
-                match provider.fetch_news(&symbols_clone, since_clone).await {
+                let fetched = match cache {
+                    Some(cache) => {
+                        let mut key_symbols = symbols_clone.clone();
+                        key_symbols.sort();
+                        let key = format!("news:{}:{}", provider_name, key_symbols.join(","));
+                        let fetch_provider = provider.clone();
+                        cache
+                            .get_or_fetch(&key, "news", NEWS_CACHE_TTL_SECS, move || async move {
+                                let items = fetch_provider.fetch_news(&symbols_clone, None).await?;
+                                Ok::<_, anyhow::Error>((items, Some(fetch_provider.get_name().to_string())))
+                            })
+                            .await
+                            .map(|cached| cached.value)
+                    }
+                    None => provider.fetch_news(&symbols_clone, since_clone).await,
+                };
+                match fetched {
                     Ok(items) => {
                         eprintln!("Fetched {} items from {}", items.len(), provider_name);
                         Ok(items)
@@ -223,26 +254,20 @@ impl NewsAggregator {
                         // Emit events for new news items
                         if !new_items.is_empty() {
                             for item in &new_items {
-                                let _ = app.emit(
-                                    "ws-message",
-                                    serde_json::json!({
-                                        "type": "stock-news",
-                                        "data": item,
-                                        "timestamp": chrono::Utc::now().timestamp_millis()
-                                    }),
-                                );
+                                let _ = crate::services::window_router::emit(&app, serde_json::json!({
+                                    "type": "stock-news",
+                                    "data": item,
+                                    "timestamp": chrono::Utc::now().timestamp_millis()
+                                }));
                             }
 
                             // Also emit batch event
                             if new_items.len() <= 10 {
-                                let _ = app.emit(
-                                    "ws-message",
-                                    serde_json::json!({
-                                        "type": "stock-news-batch",
-                                        "data": new_items,
-                                        "timestamp": chrono::Utc::now().timestamp_millis()
-                                    }),
-                                );
+                                let _ = crate::services::window_router::emit(&app, serde_json::json!({
+                                    "type": "stock-news-batch",
+                                    "data": new_items,
+                                    "timestamp": chrono::Utc::now().timestamp_millis()
+                                }));
                             }
                         }
 
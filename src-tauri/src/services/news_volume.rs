@@ -0,0 +1,202 @@
+use crate::storage::osint::{EntityDayCount, OSINTStore};
+use crate::storage::temporal::{NewTemporalEvent, TemporalEvent, TemporalStore};
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const CONFIG_Z_THRESHOLD: &str = "news_volume_z_threshold";
+pub const CONFIG_BASELINE_DAYS: &str = "news_volume_baseline_days";
+/// Days with fewer articles than this never count as anomalies, however quiet the baseline.
+pub const CONFIG_MIN_ARTICLES: &str = "news_volume_min_articles";
+
+pub const EVENT_TYPE: &str = "volume_anomaly";
+const DAY: i64 = 86400;
+/// Keeps a perfectly flat baseline from turning one extra article into an infinite z-score.
+const MIN_STD: f64 = 1.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeAnomalySettings {
+    pub z_threshold: f64,
+    pub baseline_days: i64,
+    pub min_articles: i64,
+}
+
+impl Default for VolumeAnomalySettings {
+    fn default() -> Self {
+        VolumeAnomalySettings {
+            z_threshold: 3.0,
+            baseline_days: 28,
+            min_articles: 3,
+        }
+    }
+}
+
+impl VolumeAnomalySettings {
+    pub fn load(db: &Database) -> Self {
+        let defaults = Self::default();
+        let get = |key: &str| db.get_config(key).ok().flatten();
+        VolumeAnomalySettings {
+            z_threshold: get(CONFIG_Z_THRESHOLD).and_then(|v| v.parse().ok()).unwrap_or(defaults.z_threshold),
+            baseline_days: get(CONFIG_BASELINE_DAYS).and_then(|v| v.parse().ok()).unwrap_or(defaults.baseline_days).max(3),
+            min_articles: get(CONFIG_MIN_ARTICLES).and_then(|v| v.parse().ok()).unwrap_or(defaults.min_articles),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyVolume {
+    pub day_start: i64,
+    pub count: i64,
+    pub baseline_mean: f64,
+    pub baseline_std: f64,
+    pub z_score: f64,
+}
+
+/// Article volume history of one entity, for its dossier view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityVolumeProfile {
+    pub entity: String,
+    pub settings: VolumeAnomalySettings,
+    pub days: Vec<DailyVolume>,
+    pub anomalies: Vec<TemporalEvent>,
+    /// Short interest and analyst ratings when the entity resolves to a ticker
+    #[serde(default)]
+    pub market_signals: Option<crate::services::market_signals::TickerSignals>,
+}
+
+pub struct NewsVolumeDetector;
+
+impl NewsVolumeDetector {
+    /// Score the last `days_to_check` days of every entity against its rolling
+    /// baseline and upsert a "volume_anomaly" event for each day over the
+    /// threshold. Returns the ids of newly created events.
+    pub fn detect(
+        osint_store: &OSINTStore,
+        temporal_store: &TemporalStore,
+        settings: &VolumeAnomalySettings,
+        days_to_check: i64,
+    ) -> Result<Vec<i64>> {
+        let today = chrono::Utc::now().timestamp().div_euclid(DAY) * DAY;
+        let check_from = today - (days_to_check.max(1) - 1) * DAY;
+        let counts = osint_store.daily_entity_counts(check_from - settings.baseline_days * DAY, None)?;
+
+        let mut created = Vec::new();
+        for (entity, series) in by_entity(counts) {
+            for day in scored_days(&series, settings.baseline_days, check_from, today) {
+                if day.count < settings.min_articles || day.z_score < settings.z_threshold {
+                    continue;
+                }
+                let evidence = osint_store.article_ids_for_entity(&entity, day.day_start, day.day_start + DAY)?;
+                let (id, is_new) = temporal_store.upsert_event(&anomaly_event(&entity, &day, settings), &evidence)?;
+                if is_new {
+                    created.push(id);
+                }
+            }
+        }
+        Ok(created)
+    }
+
+    pub fn entity_profile(
+        osint_store: &OSINTStore,
+        temporal_store: &TemporalStore,
+        settings: &VolumeAnomalySettings,
+        entity: &str,
+        days: i64,
+    ) -> Result<EntityVolumeProfile> {
+        let today = chrono::Utc::now().timestamp().div_euclid(DAY) * DAY;
+        let from = today - (days.max(1) - 1) * DAY;
+        let counts = osint_store.daily_entity_counts(from - settings.baseline_days * DAY, Some(entity))?;
+        let series: BTreeMap<i64, i64> = counts.iter().map(|c| (c.day_start, c.count)).collect();
+        let display = counts.first().map(|c| c.entity.clone()).unwrap_or_else(|| entity.to_string());
+
+        let anomalies = temporal_store
+            .list_events_for_ticker(entity, from, today + DAY, 500)?
+            .into_iter()
+            .filter(|e| e.event_type == EVENT_TYPE)
+            .collect();
+
+        Ok(EntityVolumeProfile {
+            entity: display,
+            settings: settings.clone(),
+            days: scored_days(&series, settings.baseline_days, from, today),
+            anomalies,
+            market_signals: None,
+        })
+    }
+}
+
+fn by_entity(counts: Vec<EntityDayCount>) -> HashMap<String, BTreeMap<i64, i64>> {
+    let mut series: HashMap<String, BTreeMap<i64, i64>> = HashMap::new();
+    for c in counts {
+        series.entry(c.entity).or_default().insert(c.day_start, c.count);
+    }
+    series
+}
+
+/// Each day in [from, to] scored against the `baseline_days` before it, with
+/// days without articles counted as zero.
+fn scored_days(series: &BTreeMap<i64, i64>, baseline_days: i64, from: i64, to: i64) -> Vec<DailyVolume> {
+    let mut days = Vec::new();
+    let mut day = from;
+    while day <= to {
+        let baseline: Vec<f64> = (1..=baseline_days)
+            .map(|back| *series.get(&(day - back * DAY)).unwrap_or(&0) as f64)
+            .collect();
+        let mean = baseline.iter().sum::<f64>() / baseline.len() as f64;
+        let std = (baseline.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / baseline.len() as f64).sqrt();
+        let count = *series.get(&day).unwrap_or(&0);
+
+        days.push(DailyVolume {
+            day_start: day,
+            count,
+            baseline_mean: mean,
+            baseline_std: std,
+            z_score: (count as f64 - mean) / std.max(MIN_STD),
+        });
+        day += DAY;
+    }
+    days
+}
+
+fn anomaly_event(entity: &str, day: &DailyVolume, settings: &VolumeAnomalySettings) -> NewTemporalEvent {
+    let date = chrono::DateTime::from_timestamp(day.day_start, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    NewTemporalEvent {
+        title: format!("Unusual news volume: {}", entity),
+        summary: format!(
+            "{} articles mentioned {} on {}, against a {}-day average of {:.1} (z-score {:.1})",
+            day.count, entity, date, settings.baseline_days, day.baseline_mean, day.z_score
+        ),
+        start_ts: day.day_start,
+        end_ts: day.day_start + DAY - 1,
+        event_type: EVENT_TYPE.to_string(),
+        confidence: (day.z_score / (settings.z_threshold * 2.0)).clamp(0.5, 1.0),
+        severity: (day.z_score / (settings.z_threshold * 2.0)).clamp(0.0, 1.0),
+        novelty_score: 0.0,
+        volume_score: day.z_score,
+        sentiment_score: 0.0,
+        cluster_key: format!("{}|{}|{}", EVENT_TYPE, date, entity.to_lowercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spike_scores_above_flat_baseline() {
+        let mut series = BTreeMap::new();
+        for d in 0..28 {
+            series.insert(d * DAY, 2);
+        }
+        series.insert(28 * DAY, 12);
+
+        let days = scored_days(&series, 28, 28 * DAY, 28 * DAY);
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].baseline_mean, 2.0);
+        // Flat baseline has zero spread, so the floor applies
+        assert_eq!(days[0].z_score, 10.0);
+    }
+}
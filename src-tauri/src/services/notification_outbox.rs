@@ -0,0 +1,167 @@
+use crate::services::alert_escalator::AlertEscalator;
+use crate::storage::temporal::{Alert, AlertRule, TemporalStore};
+use crate::storage::{Database, OutboxMessage, OutboxStore};
+use anyhow::Result;
+use rusqlite::Connection;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use tokio::time::{interval, Duration};
+
+/// Attempts before a message is dead-lettered.
+pub const MAX_ATTEMPTS: i32 = 8;
+const BASE_BACKOFF_SECS: i64 = 30;
+const MAX_BACKOFF_SECS: i64 = 3600;
+const BATCH_SIZE: i64 = 20;
+const SENT_RETENTION_SECS: i64 = 30 * 86_400;
+
+/// Delivers queued alert notifications with at-least-once semantics and
+/// exponential backoff between attempts.
+pub struct NotificationOutbox;
+
+impl NotificationOutbox {
+    /// Snapshot of everything needed to send later, so a delivery doesn't
+    /// depend on the rule still looking the way it did when it fired.
+    pub fn escalation_payload(alert: &Alert, rule: &AlertRule, level_config: &Value) -> Value {
+        serde_json::json!({
+            "alert": alert,
+            "rule": rule,
+            "level_config": level_config,
+        })
+    }
+
+    /// Idempotency key for an automatic escalation; one send per alert,
+    /// level and channel no matter how often the checker runs.
+    pub fn escalation_key(alert_id: i64, escalation_level: i32, channel: &str) -> String {
+        format!("escalation:{}:{}:{}", alert_id, escalation_level, channel)
+    }
+
+    /// Try to send one message now. Failures are rescheduled; the error is
+    /// returned so callers can report it.
+    pub async fn deliver(
+        conn: Arc<Mutex<Connection>>,
+        message: &OutboxMessage,
+        app: Option<tauri::AppHandle>,
+    ) -> Result<()> {
+        let outbox = OutboxStore::new(conn.clone());
+        if !outbox.claim(message.id)? {
+            return Ok(());
+        }
+        let attempts = message.attempts + 1;
+
+        let parsed = serde_json::from_value::<Alert>(message.payload["alert"].clone()).and_then(|alert| {
+            serde_json::from_value::<AlertRule>(message.payload["rule"].clone()).map(|rule| (alert, rule))
+        });
+        let result = match parsed {
+            Ok((alert, rule)) => {
+                let store = TemporalStore::new(conn.clone());
+                AlertEscalator::send_escalation(
+                    &store,
+                    message.escalation_id.unwrap_or_default(),
+                    &message.channel,
+                    &alert,
+                    &rule,
+                    &message.payload["level_config"],
+                    app,
+                )
+                .await
+            }
+            Err(e) => Err(anyhow::anyhow!("Invalid outbox payload: {}", e)),
+        };
+
+        let store = TemporalStore::new(conn);
+        match result {
+            Ok(()) => {
+                outbox.mark_sent(message.id)?;
+                if let Some(escalation_id) = message.escalation_id {
+                    store.mark_escalation_sent(escalation_id, None)?;
+                }
+                Ok(())
+            }
+            Err(e) => {
+                let error = e.to_string();
+                let next = (attempts < MAX_ATTEMPTS)
+                    .then(|| chrono::Utc::now().timestamp() + backoff_secs(attempts));
+                outbox.mark_failed(message.id, &error, next)?;
+                if next.is_none() {
+                    if let Some(escalation_id) = message.escalation_id {
+                        store.mark_escalation_sent(escalation_id, Some(&error))?;
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Resume anything a crash left in flight, then retry due messages
+    /// every 15 seconds.
+    pub fn start_worker(db: Arc<Mutex<Database>>, app: tauri::AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let conn = match db.lock() {
+                Ok(db_guard) => db_guard.conn.clone(),
+                Err(e) => {
+                    eprintln!("Notification outbox not started: {}", e);
+                    return;
+                }
+            };
+            let outbox = OutboxStore::new(conn.clone());
+            match outbox.reset_in_flight() {
+                Ok(n) if n > 0 => eprintln!("MINA: Requeued {} interrupted notifications", n),
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to requeue interrupted notifications: {}", e),
+            }
+
+            let mut interval = interval(Duration::from_secs(15));
+            let mut last_prune = 0i64;
+            let heartbeat = crate::services::watchdog::Watchdog::register("notification_outbox", Duration::from_secs(15));
+
+            loop {
+                interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
+
+                let now = chrono::Utc::now().timestamp();
+                let due = match outbox.due_messages(now, BATCH_SIZE) {
+                    Ok(due) => due,
+                    Err(e) => {
+                        eprintln!("Failed to read notification outbox: {}", e);
+                        continue;
+                    }
+                };
+                for message in due {
+                    if let Err(e) = Self::deliver(conn.clone(), &message, Some(app.clone())).await {
+                        eprintln!(
+                            "Notification {} via {} failed (attempt {}): {}",
+                            message.id, message.channel, message.attempts + 1, e
+                        );
+                    }
+                }
+
+                if now - last_prune > 3600 {
+                    let _ = outbox.delete_sent_before(now - SENT_RETENTION_SECS);
+                    last_prune = now;
+                }
+            }
+        });
+    }
+}
+
+/// Delay before the next attempt: 30s, 1m, 2m, ... capped at an hour.
+fn backoff_secs(attempts: i32) -> i64 {
+    let exp = (attempts - 1).clamp(0, 16) as u32;
+    (BASE_BACKOFF_SECS * 2i64.pow(exp)).min(MAX_BACKOFF_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        assert_eq!(backoff_secs(1), 30);
+        assert_eq!(backoff_secs(2), 60);
+        assert_eq!(backoff_secs(4), 240);
+        assert_eq!(backoff_secs(8), MAX_BACKOFF_SECS);
+        assert_eq!(backoff_secs(40), MAX_BACKOFF_SECS);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::providers::backup::{BackupDestination, S3Destination};
+use crate::services::api_key_manager::APIKeyManager;
+use crate::storage::Database;
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+
+pub const CONFIG_ENDPOINT: &str = "object_storage_endpoint";
+pub const CONFIG_BUCKET: &str = "object_storage_bucket";
+pub const CONFIG_REGION: &str = "object_storage_region";
+pub const CONFIG_PREFIX: &str = "object_storage_prefix";
+
+/// Credentials are kept in APIKeyManager under these providers.
+pub const ACCESS_KEY_PROVIDER: &str = "s3_access_key_id";
+pub const SECRET_KEY_PROVIDER: &str = "s3_secret_access_key";
+
+/// Key prefixes per subsystem, below the configured prefix.
+pub const REPORTS_PREFIX: &str = "reports";
+pub const ARCHIVES_PREFIX: &str = "archives";
+pub const BACKUPS_PREFIX: &str = "backups";
+
+pub struct ObjectStorageService;
+
+impl ObjectStorageService {
+    /// The configured bucket, or None when object storage isn't set up.
+    pub fn bucket(db: &Database, api_key_manager: &APIKeyManager) -> Result<Option<S3Destination>> {
+        let endpoint = match db.get_config(CONFIG_ENDPOINT)?.filter(|e| !e.trim().is_empty()) {
+            Some(e) => e,
+            None => return Ok(None),
+        };
+        let bucket = db.get_config(CONFIG_BUCKET)?
+            .filter(|b| !b.trim().is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Object storage bucket is not configured"))?;
+        let region = db.get_config(CONFIG_REGION)?.unwrap_or_default();
+        let prefix = db.get_config(CONFIG_PREFIX)?.unwrap_or_default();
+        let access_key_id = api_key_manager.get_key(ACCESS_KEY_PROVIDER)?;
+        let secret_access_key = api_key_manager.get_key(SECRET_KEY_PROVIDER)?;
+
+        Ok(Some(S3Destination::new(&endpoint, &bucket, &region, &prefix, access_key_id, secret_access_key)))
+    }
+
+    /// Upload in-memory content (a report, an export) under a subsystem prefix.
+    /// Returns the object key.
+    pub async fn upload_bytes(
+        db: &Arc<Mutex<Database>>,
+        api_key_manager: &APIKeyManager,
+        subprefix: &str,
+        name: &str,
+        content: &[u8],
+    ) -> Result<String> {
+        let temp = std::env::temp_dir().join(format!("mina-upload-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&temp, content)?;
+        let result = Self::upload_file(db, api_key_manager, subprefix, name, &temp).await;
+        let _ = std::fs::remove_file(&temp);
+        result
+    }
+
+    /// Upload a file under a subsystem prefix; large files go multipart. Returns the object key.
+    pub async fn upload_file(
+        db: &Arc<Mutex<Database>>,
+        api_key_manager: &APIKeyManager,
+        subprefix: &str,
+        name: &str,
+        path: &std::path::Path,
+    ) -> Result<String> {
+        let bucket = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            Self::bucket(&db_guard, api_key_manager)?
+                .ok_or_else(|| anyhow::anyhow!("Object storage is not configured"))?
+                .with_subprefix(subprefix)
+        };
+        bucket.upload(name, path).await?;
+        Ok(bucket.key_for(name))
+    }
+}
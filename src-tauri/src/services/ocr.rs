@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Tesseract binary, when it isn't on PATH.
+pub const CONFIG_TESSERACT_PATH: &str = "ocr_tesseract_path";
+/// Tesseract language codes joined with "+", e.g. "eng+deu".
+pub const CONFIG_OCR_LANGUAGES: &str = "ocr_languages";
+
+const DEFAULT_TESSERACT: &str = "tesseract";
+const DEFAULT_LANGUAGES: &str = "eng";
+/// Rendering resolution for scanned PDF pages
+const PDF_RENDER_DPI: &str = "300";
+
+/// Text recognition through the tesseract CLI. PDFs are rendered to images
+/// with poppler's `pdftoppm` first.
+#[derive(Debug, Clone)]
+pub struct OcrEngine {
+    tesseract: String,
+    languages: String,
+}
+
+impl Default for OcrEngine {
+    fn default() -> Self {
+        OcrEngine {
+            tesseract: DEFAULT_TESSERACT.to_string(),
+            languages: DEFAULT_LANGUAGES.to_string(),
+        }
+    }
+}
+
+impl OcrEngine {
+    pub fn from_config(db: &crate::storage::Database) -> Self {
+        let get = |key| db.get_config(key).ok().flatten().filter(|v: &String| !v.trim().is_empty());
+        OcrEngine {
+            tesseract: get(CONFIG_TESSERACT_PATH).unwrap_or_else(|| DEFAULT_TESSERACT.to_string()),
+            languages: get(CONFIG_OCR_LANGUAGES).unwrap_or_else(|| DEFAULT_LANGUAGES.to_string()),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        Command::new(&self.tesseract).arg("--version").output().is_ok_and(|o| o.status.success())
+    }
+
+    /// Recognized text of one image file.
+    pub fn image_text(&self, path: &Path) -> Result<String> {
+        let output = Command::new(&self.tesseract)
+            .arg(path)
+            .arg("stdout")
+            .args(["-l", &self.languages])
+            .output()
+            .with_context(|| format!("Failed to run {} (is tesseract installed?)", self.tesseract))?;
+        if !output.status.success() {
+            anyhow::bail!("tesseract failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Recognized text of every page of a PDF, pages separated by form
+    /// feeds, and the page count.
+    pub fn pdf_text(&self, path: &Path) -> Result<(String, i64)> {
+        let dir = std::env::temp_dir().join(format!("mina-ocr-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)?;
+        let result = self.pdf_text_in(path, &dir);
+        let _ = std::fs::remove_dir_all(&dir);
+        result
+    }
+
+    fn pdf_text_in(&self, path: &Path, dir: &Path) -> Result<(String, i64)> {
+        let output = Command::new("pdftoppm")
+            .args(["-r", PDF_RENDER_DPI, "-png"])
+            .arg(path)
+            .arg(dir.join("page"))
+            .output()
+            .context("Failed to run pdftoppm (is poppler installed?)")?;
+        if !output.status.success() {
+            anyhow::bail!("pdftoppm failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        let mut pages: Vec<_> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "png"))
+            .collect();
+        // pdftoppm zero-pads page numbers, so names sort in page order
+        pages.sort();
+
+        let mut texts = Vec::with_capacity(pages.len());
+        for page in &pages {
+            texts.push(self.image_text(page)?);
+        }
+        Ok((texts.join("\n\x0c"), pages.len() as i64))
+    }
+}
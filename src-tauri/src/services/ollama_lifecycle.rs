@@ -0,0 +1,340 @@
+use crate::commands::ollama::OllamaState;
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Manager};
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+
+pub const CONFIG_WARM_MODELS: &str = "ollama_warm_models";
+pub const CONFIG_CONCURRENCY: &str = "ollama_model_concurrency";
+
+/// WS topic load state changes are published on.
+pub const WS_TOPIC: &str = "ollama-models";
+
+const CHECK_INTERVAL_SECS: u64 = 60;
+/// Long enough that a warm model never expires between checks.
+const WARM_KEEP_ALIVE: &str = "10m";
+
+/// Which models to keep loaded and how many requests each model serves at
+/// once; the rest wait in its queue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LifecycleSettings {
+    pub warm_models: Vec<String>,
+    pub concurrency: usize,
+}
+
+impl Default for LifecycleSettings {
+    fn default() -> Self {
+        LifecycleSettings {
+            warm_models: Vec::new(),
+            concurrency: 1,
+        }
+    }
+}
+
+impl LifecycleSettings {
+    pub fn load(db: &Database) -> Result<Self> {
+        let defaults = Self::default();
+        Ok(LifecycleSettings {
+            warm_models: db
+                .get_config(CONFIG_WARM_MODELS)?
+                .map(|v| v.split(',').map(|m| m.trim().to_string()).filter(|m| !m.is_empty()).collect())
+                .unwrap_or(defaults.warm_models),
+            concurrency: db
+                .get_config(CONFIG_CONCURRENCY)?
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.concurrency),
+        })
+    }
+
+    pub fn save(&self, db: &Database) -> Result<()> {
+        db.set_config(CONFIG_WARM_MODELS, &self.warm_models.join(","))?;
+        db.set_config(CONFIG_CONCURRENCY, &self.concurrency.to_string())?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelLoadState {
+    pub model: String,
+    pub state: String, // cold|loading|loaded
+    pub keep_warm: bool,
+    /// Requests being served
+    pub active: usize,
+    /// Requests waiting for a slot
+    pub queued: usize,
+    pub memory_bytes: Option<u64>,
+    pub last_used: Option<i64>,
+    pub last_error: Option<String>,
+    pub updated_at: i64,
+}
+
+impl ModelLoadState {
+    fn new(model: &str) -> Self {
+        ModelLoadState {
+            model: model.to_string(),
+            state: "cold".to_string(),
+            keep_warm: false,
+            active: 0,
+            queued: 0,
+            memory_bytes: None,
+            last_used: None,
+            last_error: None,
+            updated_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+struct Slot {
+    semaphore: Arc<Semaphore>,
+    concurrency: usize,
+    state: ModelLoadState,
+}
+
+#[derive(Default)]
+struct Models {
+    concurrency: Option<usize>,
+    slots: HashMap<String, Slot>,
+}
+
+impl Models {
+    fn slot(&mut self, model: &str) -> &mut Slot {
+        let concurrency = self.concurrency.unwrap_or(1);
+        self.slots.entry(model.to_string()).or_insert_with(|| Slot {
+            semaphore: Arc::new(Semaphore::new(concurrency)),
+            concurrency,
+            state: ModelLoadState::new(model),
+        })
+    }
+
+    /// A queue's semaphore can't shrink, so a changed limit gets a fresh
+    /// one; requests already admitted finish on the old one.
+    fn set_concurrency(&mut self, concurrency: usize) {
+        self.concurrency = Some(concurrency);
+        for slot in self.slots.values_mut() {
+            if slot.concurrency != concurrency {
+                slot.semaphore = Arc::new(Semaphore::new(concurrency));
+                slot.concurrency = concurrency;
+            }
+        }
+    }
+}
+
+static MODELS: OnceLock<Mutex<Models>> = OnceLock::new();
+/// Set once the manager runs; state changes before that aren't reported.
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static WAKE: OnceLock<Notify> = OnceLock::new();
+
+fn models() -> &'static Mutex<Models> {
+    MODELS.get_or_init(|| Mutex::new(Models::default()))
+}
+
+/// Update a model's state and report it when anything changed.
+fn update(model: &str, change: impl FnOnce(&mut ModelLoadState)) {
+    let changed = {
+        let Ok(mut models) = models().lock() else { return };
+        let state = &mut models.slot(model).state;
+        let before = state.clone();
+        change(state);
+        if *state == before {
+            return;
+        }
+        state.updated_at = chrono::Utc::now().timestamp();
+        state.clone()
+    };
+    if let Some(app) = APP.get() {
+        report(app, changed);
+    }
+}
+
+fn report(app: &AppHandle, state: ModelLoadState) {
+    if let Some(server) = app.try_state::<Mutex<Arc<crate::ws::WsServer>>>() {
+        if let Ok(server) = server.lock() {
+            let _ = server.publish(WS_TOPIC, crate::ws::WsMessage::OllamaModelState(state.clone()));
+        }
+    }
+    let _ = crate::services::window_router::emit(app, serde_json::json!({
+        "type": "ollama-model-state",
+        "data": state,
+        "timestamp": chrono::Utc::now().timestamp_millis(),
+    }));
+}
+
+/// A slot in a model's queue, held for the duration of one request.
+pub struct ModelPermit {
+    model: String,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Drop for ModelPermit {
+    fn drop(&mut self) {
+        update(&self.model, |state| {
+            state.active = state.active.saturating_sub(1);
+            state.last_used = Some(chrono::Utc::now().timestamp());
+            // Answering loaded it
+            if state.state == "loading" {
+                state.state = "loaded".to_string();
+            }
+        });
+    }
+}
+
+/// Keeps selected Ollama models loaded and queues requests per model, so
+/// chats don't stall on model loads and parallel requests don't thrash
+/// memory.
+pub struct ModelLifecycle;
+
+impl ModelLifecycle {
+    pub fn start(db: Arc<Mutex<Database>>, app: AppHandle, ollama: OllamaState) {
+        if APP.set(app).is_err() {
+            return;
+        }
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = WAKE.get_or_init(Notify::new).notified() => {}
+                }
+                let settings = {
+                    let Ok(db_guard) = db.lock() else { continue };
+                    LifecycleSettings::load(&db_guard).unwrap_or_default()
+                };
+                Self::apply(&settings);
+                let provider = ollama.read().await;
+                Self::keep_warm(&provider, &settings).await;
+            }
+        });
+    }
+
+    /// Pick up changed settings right away instead of at the next check.
+    pub fn reload() {
+        WAKE.get_or_init(Notify::new).notify_one();
+    }
+
+    /// Wait for a free slot in `model`'s queue.
+    pub async fn acquire(model: &str) -> Result<ModelPermit> {
+        let semaphore = {
+            let mut models = models()
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Model queue lock poisoned: {}", e))?;
+            models.slot(model).semaphore.clone()
+        };
+        update(model, |state| state.queued += 1);
+        let permit = semaphore.acquire_owned().await;
+        update(model, |state| {
+            state.queued = state.queued.saturating_sub(1);
+            if permit.is_ok() {
+                state.active += 1;
+                if state.state == "cold" {
+                    state.state = "loading".to_string();
+                }
+            }
+        });
+        Ok(ModelPermit {
+            model: model.to_string(),
+            _permit: permit?,
+        })
+    }
+
+    pub fn states() -> Vec<ModelLoadState> {
+        let mut states: Vec<ModelLoadState> = models()
+            .lock()
+            .map(|models| models.slots.values().map(|s| s.state.clone()).collect())
+            .unwrap_or_default();
+        states.sort_by(|a, b| a.model.cmp(&b.model));
+        states
+    }
+
+    fn apply(settings: &LifecycleSettings) {
+        let known: Vec<String> = {
+            let Ok(mut models) = models().lock() else { return };
+            models.set_concurrency(settings.concurrency);
+            for model in &settings.warm_models {
+                models.slot(model);
+            }
+            models.slots.keys().cloned().collect()
+        };
+        for model in known {
+            let keep_warm = settings.warm_models.contains(&model);
+            update(&model, |state| state.keep_warm = keep_warm);
+        }
+    }
+
+    /// Sync load state with what Ollama has loaded, and load warm models
+    /// that aren't.
+    async fn keep_warm(provider: &crate::providers::ollama::OllamaProvider, settings: &LifecycleSettings) {
+        let running = match provider.running_models().await {
+            Ok(running) => running,
+            Err(e) => {
+                for model in &settings.warm_models {
+                    let error = e.to_string();
+                    update(model, |state| state.last_error = Some(error));
+                }
+                return;
+            }
+        };
+
+        let known: Vec<String> = match models().lock() {
+            Ok(models) => models.slots.keys().cloned().collect(),
+            Err(_) => return,
+        };
+        for model in &known {
+            let loaded = running.iter().find(|m| &m.name == model);
+            update(model, |state| {
+                state.memory_bytes = loaded.map(|m| m.size);
+                if loaded.is_some() {
+                    state.state = "loaded".to_string();
+                } else if state.active == 0 {
+                    state.state = "cold".to_string();
+                }
+            });
+        }
+
+        for model in &settings.warm_models {
+            // Refreshing keep_alive on loaded models keeps them from expiring
+            let was_loaded = running.iter().any(|m| &m.name == model);
+            if !was_loaded {
+                update(model, |state| state.state = "loading".to_string());
+            }
+            let result = provider.preload_model(model, WARM_KEEP_ALIVE).await;
+            update(model, |state| match &result {
+                Ok(()) => {
+                    state.state = "loaded".to_string();
+                    state.last_error = None;
+                }
+                Err(e) => {
+                    if !was_loaded && state.active == 0 {
+                        state.state = "cold".to_string();
+                    }
+                    state.last_error = Some(e.to_string());
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn requests_queue_behind_the_concurrency_limit() {
+        let model = "queue-test:latest";
+        let first = ModelLifecycle::acquire(model).await.unwrap();
+
+        let waiting = tokio::spawn(async move { ModelLifecycle::acquire(model).await.map(|_| ()) });
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+        let state = ModelLifecycle::states().into_iter().find(|s| s.model == model).unwrap();
+        assert_eq!((state.active, state.queued, state.state.as_str()), (1, 1, "loading"));
+
+        drop(first);
+        waiting.await.unwrap().unwrap();
+        let state = ModelLifecycle::states().into_iter().find(|s| s.model == model).unwrap();
+        assert_eq!((state.active, state.queued, state.state.as_str()), (0, 0, "loaded"));
+        assert!(state.last_used.is_some());
+    }
+}
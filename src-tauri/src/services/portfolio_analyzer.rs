@@ -1,8 +1,17 @@
 use crate::storage::portfolio::PortfolioStore;
 use crate::storage::market_data::MarketDataStore;
 use crate::storage::portfolio_performance::PortfolioPerformanceStore;
+use crate::storage::fx::FxStore;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Comma-separated VaR confidence levels, e.g. "0.95,0.99".
+pub const CONFIG_RISK_CONFIDENCE_LEVELS: &str = "portfolio_risk_confidence_levels";
+/// Calendar days of price history used for VaR and correlations.
+pub const CONFIG_RISK_LOOKBACK_DAYS: &str = "portfolio_risk_lookback_days";
+pub const DEFAULT_RISK_CONFIDENCE_LEVELS: [f64; 2] = [0.95, 0.99];
+pub const DEFAULT_RISK_LOOKBACK_DAYS: i64 = 365;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortfolioValue {
@@ -11,6 +20,8 @@ pub struct PortfolioValue {
     pub total_gain: f64,
     pub total_gain_percent: f64,
     pub holdings: Vec<HoldingValue>,
+    pub base_currency: String,
+    pub missing_fx_pairs: Vec<String>, // pairs without a cached rate, valued at 1:1
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,11 +68,13 @@ pub struct PerformanceSnapshot {
 pub struct HoldingValue {
     pub ticker: String,
     pub quantity: f64,
-    pub cost_basis: f64,
-    pub current_value: f64,
+    pub cost_basis: f64, // in the portfolio's base currency
+    pub current_value: f64, // in the portfolio's base currency
     pub gain: f64,
     pub gain_percent: f64,
-    pub current_price: f64,
+    pub current_price: f64, // in the holding's own currency
+    pub currency: String,
+    pub fx_rate: f64, // holding currency -> base currency
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,6 +96,44 @@ pub struct HoldingImpact {
     pub impact_percent: f64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceShockImpact {
+    pub portfolio_value: f64,
+    pub base_currency: String,
+    pub total_impact: f64,
+    pub impact_percent: f64,
+    pub affected_holdings: Vec<HoldingImpact>,
+}
+
+/// One-day historical VaR and CVaR (expected shortfall), as positive losses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VarEstimate {
+    pub confidence: f64,
+    pub var_percent: f64,
+    pub var_amount: f64,
+    pub cvar_percent: f64,
+    pub cvar_amount: f64,
+}
+
+/// Pairwise correlation of daily returns; None where two tickers share too few days.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationMatrix {
+    pub tickers: Vec<String>,
+    pub values: Vec<Vec<Option<f64>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioRisk {
+    pub portfolio_id: i64,
+    pub base_currency: String,
+    pub portfolio_value: f64,
+    pub lookback_days: i64,
+    pub observations: usize, // days of portfolio returns behind the VaR figures
+    pub var: Vec<VarEstimate>,
+    pub correlation: CorrelationMatrix,
+    pub missing_history: Vec<String>, // holdings left out for lack of cached prices
+}
+
 pub struct PortfolioAnalyzer;
 
 impl PortfolioAnalyzer {
@@ -92,12 +143,29 @@ impl PortfolioAnalyzer {
         portfolio_id: i64,
     ) -> Result<PortfolioValue> {
         let holdings = portfolio_store.list_holdings(portfolio_id)?;
+        let base_currency = portfolio_store.get_portfolio(portfolio_id)?
+            .map(|p| p.base_currency)
+            .unwrap_or_else(|| "USD".to_string());
+        let fx_store = FxStore::new(portfolio_store.conn.clone());
         let mut total_value = 0.0;
         let mut total_cost = 0.0;
         let mut holding_values = Vec::new();
+        let mut missing_fx_pairs: Vec<String> = Vec::new();
 
         for holding in &holdings {
-            let cost_basis = holding.quantity * holding.purchase_price;
+            // Convert at today's cached rate; FxService keeps held pairs fresh
+            let fx_rate = match fx_store.get_rate(&holding.currency, &base_currency, None)? {
+                Some(rate) => rate.rate,
+                None => {
+                    let pair = format!("{}/{}", holding.currency, base_currency);
+                    if !missing_fx_pairs.contains(&pair) {
+                        missing_fx_pairs.push(pair);
+                    }
+                    1.0
+                }
+            };
+
+            let cost_basis = holding.quantity * holding.purchase_price * fx_rate;
             total_cost += cost_basis;
 
             // Get current price
@@ -107,7 +175,7 @@ impl PortfolioAnalyzer {
                 holding.purchase_price // Fallback to purchase price if no current price
             };
 
-            let current_value = holding.quantity * current_price;
+            let current_value = holding.quantity * current_price * fx_rate;
             total_value += current_value;
 
             let gain = current_value - cost_basis;
@@ -125,6 +193,8 @@ impl PortfolioAnalyzer {
                 gain,
                 gain_percent,
                 current_price,
+                currency: holding.currency.clone(),
+                fx_rate,
             });
         }
 
@@ -141,6 +211,8 @@ impl PortfolioAnalyzer {
             total_gain,
             total_gain_percent,
             holdings: holding_values,
+            base_currency,
+            missing_fx_pairs,
         })
     }
 
@@ -151,24 +223,34 @@ impl PortfolioAnalyzer {
         event_id: i64,
         price_changes: &std::collections::HashMap<String, f64>, // ticker -> price change percent
     ) -> Result<ImpactAnalysis> {
-        let holdings = portfolio_store.list_holdings(portfolio_id)?;
+        let shock = Self::analyze_price_changes(portfolio_store, market_data_store, portfolio_id, price_changes)?;
+
+        Ok(ImpactAnalysis {
+            portfolio_id,
+            event_id,
+            total_impact: shock.total_impact,
+            impact_percent: shock.impact_percent,
+            affected_holdings: shock.affected_holdings,
+        })
+    }
+
+    /// Impact of per-ticker percent moves on the portfolio, in its base currency.
+    pub fn analyze_price_changes(
+        portfolio_store: &PortfolioStore,
+        market_data_store: &MarketDataStore,
+        portfolio_id: i64,
+        price_changes: &std::collections::HashMap<String, f64>, // ticker -> price change percent
+    ) -> Result<PriceShockImpact> {
+        let portfolio_value = Self::calculate_portfolio_value(portfolio_store, market_data_store, portfolio_id)?;
         let mut total_impact = 0.0;
         let mut affected_holdings = Vec::new();
 
-        for holding in &holdings {
+        for holding in &portfolio_value.holdings {
             if let Some(price_change_percent) = price_changes.get(&holding.ticker) {
-                let current_price = if let Ok(Some(price)) = market_data_store.get_price(&holding.ticker) {
-                    price.price
-                } else {
-                    holding.purchase_price
-                };
-
-                let price_change = current_price * (price_change_percent / 100.0);
-                let new_value = holding.quantity * (current_price + price_change);
-                let current_value = holding.quantity * current_price;
-                let impact = new_value - current_value;
-                let impact_percent = if current_value > 0.0 {
-                    (impact / current_value) * 100.0
+                let price_change = holding.current_price * (price_change_percent / 100.0);
+                let impact = holding.current_value * (price_change_percent / 100.0);
+                let impact_percent = if holding.current_value > 0.0 {
+                    (impact / holding.current_value) * 100.0
                 } else {
                     0.0
                 };
@@ -186,16 +268,15 @@ impl PortfolioAnalyzer {
             }
         }
 
-        let portfolio_value = Self::calculate_portfolio_value(portfolio_store, market_data_store, portfolio_id)?;
         let impact_percent = if portfolio_value.total_value > 0.0 {
             (total_impact / portfolio_value.total_value) * 100.0
         } else {
             0.0
         };
 
-        Ok(ImpactAnalysis {
-            portfolio_id,
-            event_id,
+        Ok(PriceShockImpact {
+            portfolio_value: portfolio_value.total_value,
+            base_currency: portfolio_value.base_currency,
             total_impact,
             impact_percent,
             affected_holdings,
@@ -279,8 +360,17 @@ impl PortfolioAnalyzer {
             })
             .collect();
 
-        // Sector allocation (placeholder - would need sector data)
-        let sector_allocation = std::collections::HashMap::new();
+        // Sector allocation from stored fundamentals; tickers without one count as "Unknown"
+        let tickers: Vec<String> = portfolio_value.holdings.iter().map(|h| h.ticker.clone()).collect();
+        let sectors = market_data_store.get_sectors(&tickers)?;
+        let mut sector_allocation: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
+        if portfolio_value.total_value > 0.0 {
+            for holding in &portfolio_value.holdings {
+                let sector = sectors.get(&holding.ticker).cloned().unwrap_or_else(|| "Unknown".to_string());
+                *sector_allocation.entry(sector).or_insert(0.0) +=
+                    (holding.current_value / portfolio_value.total_value) * 100.0;
+            }
+        }
 
         // Beta and Alpha (placeholder - would need benchmark data)
         let beta = None;
@@ -403,4 +493,183 @@ impl PortfolioAnalyzer {
 
         Ok(Some(max_drawdown))
     }
+
+    /// Historical VaR/CVaR of the current holdings, replaying cached daily
+    /// returns with today's weights, plus the holdings' correlation matrix.
+    pub fn calculate_risk(
+        portfolio_store: &PortfolioStore,
+        market_data_store: &MarketDataStore,
+        portfolio_id: i64,
+        confidence_levels: &[f64],
+        lookback_days: i64,
+    ) -> Result<PortfolioRisk> {
+        let value = Self::calculate_portfolio_value(portfolio_store, market_data_store, portfolio_id)?;
+        let now = chrono::Utc::now().timestamp();
+        let from_ts = now - lookback_days * 86400;
+
+        let mut tickers = Vec::new();
+        let mut series = Vec::new();
+        let mut weights = Vec::new();
+        let mut missing_history = Vec::new();
+        for holding in &value.holdings {
+            let history = market_data_store.get_price_history(&holding.ticker, from_ts, now, Some(10000))?;
+            let returns = daily_returns(&history);
+            if returns.len() < 2 {
+                missing_history.push(holding.ticker.clone());
+                continue;
+            }
+            tickers.push(holding.ticker.clone());
+            series.push(returns);
+            weights.push(holding.current_value);
+        }
+
+        // Portfolio returns on days every included holding traded
+        let weight_total: f64 = weights.iter().sum();
+        let mut portfolio_returns = Vec::new();
+        if let Some(first) = series.first() {
+            for day in first.keys() {
+                let day_returns: Option<Vec<f64>> = series.iter().map(|s| s.get(day).copied()).collect();
+                if let Some(day_returns) = day_returns {
+                    if weight_total > 0.0 {
+                        let r: f64 = day_returns.iter().zip(&weights).map(|(r, w)| r * w / weight_total).sum();
+                        portfolio_returns.push(r);
+                    }
+                }
+            }
+        }
+
+        let var = confidence_levels
+            .iter()
+            .filter(|c| **c > 0.0 && **c < 1.0)
+            .filter_map(|c| historical_var(&portfolio_returns, *c).map(|(var, cvar)| VarEstimate {
+                confidence: *c,
+                var_percent: var * 100.0,
+                var_amount: var * weight_total,
+                cvar_percent: cvar * 100.0,
+                cvar_amount: cvar * weight_total,
+            }))
+            .collect();
+
+        let values: Vec<Vec<Option<f64>>> = series
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                series
+                    .iter()
+                    .enumerate()
+                    .map(|(j, b)| if i == j { Some(1.0) } else { correlation(a, b) })
+                    .collect()
+            })
+            .collect();
+
+        Ok(PortfolioRisk {
+            portfolio_id,
+            base_currency: value.base_currency,
+            portfolio_value: value.total_value,
+            lookback_days,
+            observations: portfolio_returns.len(),
+            var,
+            correlation: CorrelationMatrix { tickers, values },
+            missing_history,
+        })
+    }
+}
+
+/// Close-to-close returns keyed by UTC day, using each day's last close.
+fn daily_returns(history: &[crate::storage::market_data::PriceHistory]) -> BTreeMap<i64, f64> {
+    let mut closes: BTreeMap<i64, f64> = BTreeMap::new();
+    for bar in history {
+        closes.insert(bar.timestamp.div_euclid(86400), bar.close);
+    }
+
+    let mut returns = BTreeMap::new();
+    let mut prev: Option<f64> = None;
+    for (day, close) in closes {
+        if let Some(p) = prev {
+            if p > 0.0 {
+                returns.insert(day, close / p - 1.0);
+            }
+        }
+        prev = Some(close);
+    }
+    returns
+}
+
+/// (VaR, CVaR) as positive fractional losses at `confidence`.
+fn historical_var(returns: &[f64], confidence: f64) -> Option<(f64, f64)> {
+    if returns.is_empty() {
+        return None;
+    }
+    let mut sorted = returns.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let cutoff = (((1.0 - confidence) * sorted.len() as f64).floor() as usize).min(sorted.len() - 1);
+    let tail = &sorted[..=cutoff];
+    let var = (-sorted[cutoff]).max(0.0);
+    let cvar = (-(tail.iter().sum::<f64>() / tail.len() as f64)).max(0.0);
+    Some((var, cvar))
+}
+
+fn correlation(a: &BTreeMap<i64, f64>, b: &BTreeMap<i64, f64>) -> Option<f64> {
+    let pairs: Vec<(f64, f64)> = a.iter().filter_map(|(day, x)| b.get(day).map(|y| (*x, *y))).collect();
+    if pairs.len() < 3 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+    let cov: f64 = pairs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let var_x: f64 = pairs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let var_y: f64 = pairs.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+    if var_x <= 0.0 || var_y <= 0.0 {
+        return None;
+    }
+    Some(cov / (var_x.sqrt() * var_y.sqrt()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, assert_golden, round4};
+    use serde_json::json;
+
+    #[test]
+    fn portfolio_value_matches_golden() {
+        let db = test_support::test_db();
+        let id = test_support::portfolio(&db, "Core", &[("AAPL", 10.0, 100.0), ("MSFT", 5.0, 200.0)]);
+        // MSFT has no quote and is valued at its purchase price
+        test_support::price(&db, "AAPL", 110.0);
+
+        let value = PortfolioAnalyzer::calculate_portfolio_value(
+            &PortfolioStore::new(db.conn.clone()),
+            &MarketDataStore::new(db.conn.clone()),
+            id,
+        )
+        .unwrap();
+        let holdings: Vec<serde_json::Value> = value
+            .holdings
+            .iter()
+            .map(|h| {
+                json!({
+                    "ticker": h.ticker,
+                    "current_price": round4(h.current_price),
+                    "current_value": round4(h.current_value),
+                    "cost_basis": round4(h.cost_basis),
+                    "gain_percent": round4(h.gain_percent),
+                })
+            })
+            .collect();
+        assert_golden(
+            "portfolio_value",
+            &json!({
+                "total_value": round4(value.total_value),
+                "total_cost": round4(value.total_cost),
+                "total_gain": round4(value.total_gain),
+                "total_gain_percent": round4(value.total_gain_percent),
+                "base_currency": value.base_currency,
+                "missing_fx_pairs": value.missing_fx_pairs,
+                "holdings": holdings,
+            }),
+        );
+    }
 }
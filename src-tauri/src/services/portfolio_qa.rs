@@ -0,0 +1,220 @@
+use crate::providers::ollama::{ChatMessage, OllamaProvider};
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::llm_router::{LlmRouter, TASK_PORTFOLIO_QA};
+use crate::services::model_benchmark::QualityTier;
+use crate::storage::{Database, MarketDataStore, PortfolioStore, StockNewsStore, TemporalStore};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const MAX_EVENTS: usize = 10;
+const MAX_NEWS: i32 = 15;
+
+const QA_PROMPT: &str = "You are a portfolio analyst. Answer the user's question using only the data provided. \
+Cite every claim with the bracketed ids from the data, e.g. [P:AAPL], [E2], [N5]. \
+If the data doesn't explain something, say so instead of guessing. Keep the answer under 200 words.";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingMove {
+    pub ticker: String,
+    pub quantity: f64,
+    pub price: Option<f64>,
+    pub change_percent: Option<f64>,
+    pub day_change_value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Citation {
+    pub id: String, // "P:AAPL", "E1", "N3"
+    pub kind: String, // "price", "event", "news"
+    pub ref_id: Option<i64>,
+    pub ticker: Option<String>,
+    pub title: String,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalystAnswer {
+    pub question: String,
+    pub answer: String,
+    pub citations: Vec<Citation>,
+    pub moves: Vec<HoldingMove>,
+    pub portfolio_day_change: f64,
+    pub portfolio_day_change_percent: f64,
+}
+
+struct AnalystContext {
+    moves: Vec<HoldingMove>,
+    day_change: f64,
+    day_change_percent: f64,
+    citations: Vec<Citation>,
+}
+
+pub struct PortfolioQAService;
+
+impl PortfolioQAService {
+    pub async fn ask(
+        db: &Mutex<Database>,
+        ollama: &OllamaProvider,
+        api_key_manager: &APIKeyManager,
+        model: &str,
+        question: &str,
+        portfolio_id: Option<i64>,
+    ) -> Result<AnalystAnswer> {
+        let (context, router) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            (Self::gather(&db_guard, portfolio_id)?, LlmRouter::new(db_guard.conn.clone(), api_key_manager)?)
+        };
+
+        let mut data = format!(
+            "Portfolio change today: {:+.2} ({:+.2}%)\n\nSources:\n",
+            context.day_change, context.day_change_percent
+        );
+        for citation in &context.citations {
+            data.push_str(&format!(
+                "[{}] {}{}\n",
+                citation.id,
+                citation.title,
+                citation.detail.as_ref().map(|d| format!(" — {}", d)).unwrap_or_default()
+            ));
+        }
+
+        let response = router
+            .chat(ollama, TASK_PORTFOLIO_QA, QualityTier::Standard, model, vec![
+                ChatMessage { role: "system".to_string(), content: QA_PROMPT.to_string() },
+                ChatMessage {
+                    role: "user".to_string(),
+                    content: format!("Data:\n{}\nQuestion: {}", data, question),
+                },
+            ])
+            .await?
+            .content;
+
+        // Only return the sources the answer actually cites
+        let cited: Vec<Citation> = context
+            .citations
+            .iter()
+            .filter(|c| response.contains(&format!("[{}]", c.id)))
+            .cloned()
+            .collect();
+
+        Ok(AnalystAnswer {
+            question: question.to_string(),
+            answer: response.trim().to_string(),
+            citations: if cited.is_empty() { context.citations } else { cited },
+            moves: context.moves,
+            portfolio_day_change: context.day_change,
+            portfolio_day_change_percent: context.day_change_percent,
+        })
+    }
+
+    fn gather(db: &Database, portfolio_id: Option<i64>) -> Result<AnalystContext> {
+        let portfolio_store = PortfolioStore::new(db.conn.clone());
+        let market_store = MarketDataStore::new(db.conn.clone());
+        let news_store = StockNewsStore::new(db.conn.clone());
+        let temporal_store = TemporalStore::new(db.conn.clone());
+
+        let portfolio_ids: Vec<i64> = match portfolio_id {
+            Some(id) => vec![id],
+            None => portfolio_store.list_portfolios()?.into_iter().map(|p| p.id).collect(),
+        };
+
+        // Quantity per ticker across the selected portfolios
+        let mut quantities: HashMap<String, f64> = HashMap::new();
+        for id in portfolio_ids {
+            for holding in portfolio_store.list_holdings(id)? {
+                *quantities.entry(holding.ticker.to_uppercase()).or_insert(0.0) += holding.quantity;
+            }
+        }
+        let mut tickers: Vec<String> = quantities.keys().cloned().collect();
+        tickers.sort();
+
+        let mut moves = Vec::new();
+        let mut citations = Vec::new();
+        let mut day_change = 0.0;
+        let mut previous_value = 0.0;
+        for ticker in &tickers {
+            let quantity = quantities[ticker];
+            let price = market_store.get_price(ticker)?;
+            let day_change_value = price.as_ref().map(|p| p.change * quantity).unwrap_or(0.0);
+            if let Some(p) = &price {
+                day_change += day_change_value;
+                previous_value += (p.price - p.change) * quantity;
+                citations.push(Citation {
+                    id: format!("P:{}", ticker),
+                    kind: "price".to_string(),
+                    ref_id: None,
+                    ticker: Some(ticker.clone()),
+                    title: format!("{} {:+.2}% to {:.2}", ticker, p.change_percent, p.price),
+                    detail: Some(format!("position {:+.2} ({} shares)", day_change_value, quantity)),
+                });
+            }
+            moves.push(HoldingMove {
+                ticker: ticker.clone(),
+                quantity,
+                price: price.as_ref().map(|p| p.price),
+                change_percent: price.as_ref().map(|p| p.change_percent),
+                day_change_value,
+            });
+        }
+        moves.sort_by(|a, b| a.day_change_value.partial_cmp(&b.day_change_value).unwrap_or(std::cmp::Ordering::Equal));
+
+        let since = chrono::Utc::now().timestamp() - 86_400;
+
+        if !tickers.is_empty() {
+            let news = news_store.get_news(Some(tickers.clone()), MAX_NEWS, Some(since))?;
+            for (i, item) in news.iter().enumerate() {
+                citations.push(Citation {
+                    id: format!("N{}", i + 1),
+                    kind: "news".to_string(),
+                    ref_id: Some(item.id),
+                    ticker: item.tickers.first().cloned(),
+                    title: item.title.clone(),
+                    detail: Some(format!(
+                        "{} ({}){}",
+                        item.source,
+                        item.tickers.join(", "),
+                        item.sentiment.map(|s| format!(", sentiment {:.2}", s)).unwrap_or_default()
+                    )),
+                });
+            }
+        }
+
+        let mut event_count = 0;
+        for event in temporal_store.list_events(200, Some(since), None)? {
+            if event_count >= MAX_EVENTS {
+                break;
+            }
+            let commentary = temporal_store.get_event_commentary(event.id)?;
+            let text = format!("{} {}", event.title, event.summary).to_uppercase();
+            let matched = tickers.iter().find(|t| {
+                text.split(|c: char| !c.is_alphanumeric()).any(|w| w == t.as_str())
+                    || commentary.as_ref().map(|c| c.affected_tickers.iter().any(|a| a.eq_ignore_ascii_case(t))).unwrap_or(false)
+            });
+            if let Some(ticker) = matched {
+                event_count += 1;
+                citations.push(Citation {
+                    id: format!("E{}", event_count),
+                    kind: "event".to_string(),
+                    ref_id: Some(event.id),
+                    ticker: Some(ticker.clone()),
+                    title: event.title.clone(),
+                    detail: Some(
+                        commentary
+                            .map(|c| c.why_it_matters)
+                            .unwrap_or_else(|| event.summary.clone()),
+                    ),
+                });
+            }
+        }
+
+        Ok(AnalystContext {
+            moves,
+            day_change,
+            day_change_percent: if previous_value > 0.0 { day_change / previous_value * 100.0 } else { 0.0 },
+            citations,
+        })
+    }
+}
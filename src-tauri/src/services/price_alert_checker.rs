@@ -3,6 +3,7 @@ use crate::storage::Database;
 use crate::providers::market_data::MarketDataManager;
 use crate::services::api_key_manager::APIKeyManager;
 use crate::services::rate_limiter::RateLimiter;
+use crate::services::market_calendar::{MarketCalendar, MarketSession};
 use crate::services::automation_event_bus::AutomationEventBus;
 use crate::ws::WsServer;
 use anyhow::Result;
@@ -23,9 +24,13 @@ impl PriceAlertChecker {
     ) {
         tauri::async_runtime::spawn(async move {
             let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30)); // Check every 30 seconds
+            let heartbeat = crate::services::watchdog::Watchdog::register("price_alert_checker", tokio::time::Duration::from_secs(30));
 
             loop {
                 interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
 
                 if let Err(e) = Self::check_alerts(&db, &ws_server, &api_key_manager, &rate_limiter, &app, event_bus.as_ref()).await {
                     eprintln!("Error checking price alerts: {}", e);
@@ -48,6 +53,7 @@ impl PriceAlertChecker {
                 .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
             db_guard.conn.clone()
         };
+        let calendar = MarketCalendar::load(conn.clone());
         let store = PriceAlertStore::new(conn);
 
         // Get all enabled, non-triggered alerts
@@ -70,11 +76,28 @@ impl PriceAlertChecker {
 
         // Create market manager for this check
         let market_manager = MarketDataManager::new(Some(&**api_key_manager));
+        // Clones share the persisted buckets, so no lock is held across awaits
+        let limiter = rate_limiter.lock()
+            .map_err(|e| anyhow::anyhow!("Rate limiter lock poisoned: {}", e))?
+            .clone();
 
         // Check each ticker
-        for (ticker, alerts_for_ticker) in ticker_alerts {
-            // Get current price - rate limiter is optional, pass None to avoid holding lock across await
-            let price_result = market_manager.get_price(&ticker, None).await;
+        let now = chrono::Utc::now().timestamp();
+        for (ticker, mut alerts_for_ticker) in ticker_alerts {
+            let session = calendar.session_for_ticker(&ticker, now);
+            // Prices don't move while the market is closed; check once for a baseline
+            if session == MarketSession::Closed && alerts_for_ticker.iter().all(|a| a.current_price.is_some()) {
+                continue;
+            }
+            // Regular-hours alerts sit out pre/post-market moves entirely
+            if session != MarketSession::Regular {
+                alerts_for_ticker.retain(|a| !a.regular_hours_only);
+                if alerts_for_ticker.is_empty() {
+                    continue;
+                }
+            }
+
+            let price_result = market_manager.get_price(&ticker, Some(&limiter)).await;
             
             if let Ok(price_data) = price_result {
                 let current_price = price_data.price;
@@ -143,11 +166,12 @@ impl PriceAlertChecker {
                         let _ = app.emit("price-alert-triggered", &alert_message);
                         
                         // Also emit via ws-message for consistency
-                        let _ = app.emit("ws-message", serde_json::json!({
+                        let _ = crate::services::window_router::emit(app, serde_json::json!({
                             "type": "price-alert",
                             "data": alert_message,
                             "timestamp": chrono::Utc::now().timestamp_millis(),
                         }));
+                        crate::ws::publish_alert(&app, "price", serde_json::json!(alert_message));
                         
                         // Forward to AutomationEventBus
                         if let Some(bus) = event_bus {
@@ -0,0 +1,294 @@
+use crate::storage::process_policies::{NewProcessPolicy, ProcessPolicy, ProcessPolicyAction, ProcessPolicyStore};
+use crate::storage::Database;
+use anyhow::Result;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use sysinfo::System;
+use tauri::AppHandle;
+
+const CHECK_INTERVAL_SECS: u64 = 15;
+
+/// A process over a policy's limits right now.
+struct Breach {
+    pid: u32,
+    name: String,
+    rss_bytes: u64,
+    cpu_percent: f32,
+    reason: String,
+}
+
+/// Why a process is over a policy's limits, or None when it's within them.
+fn breach_reason(policy: &ProcessPolicy, rss_bytes: u64, cpu_percent: f32) -> Option<String> {
+    let mut reasons = Vec::new();
+    if let Some(max) = policy.max_rss_bytes {
+        if rss_bytes > max {
+            reasons.push(format!("RSS {} MB > {} MB", rss_bytes / 1_048_576, max / 1_048_576));
+        }
+    }
+    if let Some(max) = policy.max_cpu_percent {
+        if cpu_percent > max {
+            reasons.push(format!("CPU {:.0}% > {:.0}%", cpu_percent, max));
+        }
+    }
+    (!reasons.is_empty()).then(|| reasons.join(", "))
+}
+
+/// Remembers since when each (policy, pid) has been over its limits, so a
+/// policy fires only after a sustained breach, and once per breach.
+#[derive(Default)]
+struct BreachTracker {
+    since: HashMap<(i64, u32), i64>,
+    acted: HashSet<(i64, u32)>,
+}
+
+impl BreachTracker {
+    /// Record this check's breaches and return the ones now due for action.
+    fn observe(&mut self, policy: &ProcessPolicy, breaching: &[u32], now: i64) -> Vec<u32> {
+        let current: HashSet<(i64, u32)> = breaching.iter().map(|pid| (policy.id, *pid)).collect();
+        // Processes back under the limits (or gone) start over
+        self.since.retain(|key, _| key.0 != policy.id || current.contains(key));
+        self.acted.retain(|key| key.0 != policy.id || current.contains(key));
+
+        let mut due = Vec::new();
+        for key in current {
+            let since = *self.since.entry(key).or_insert(now);
+            if now - since >= policy.duration_secs && self.acted.insert(key) {
+                due.push(key.1);
+            }
+        }
+        due.sort_unstable();
+        due
+    }
+
+    fn forget_policies_except(&mut self, policy_ids: &HashSet<i64>) {
+        self.since.retain(|key, _| policy_ids.contains(&key.0));
+        self.acted.retain(|key| policy_ids.contains(&key.0));
+    }
+}
+
+/// Enforces resource limits on local processes: kills or renices processes
+/// that stay over a policy's memory or CPU limit, or only reports them when
+/// the policy is in dry-run mode.
+pub struct ProcessGuardian;
+
+impl ProcessGuardian {
+    pub fn validate(policy: &NewProcessPolicy) -> Result<()> {
+        if policy.name.trim().is_empty() {
+            anyhow::bail!("Policy name cannot be empty");
+        }
+        Regex::new(&policy.pattern).map_err(|e| anyhow::anyhow!("Invalid pattern: {}", e))?;
+        if policy.max_rss_bytes.is_none() && policy.max_cpu_percent.is_none() {
+            anyhow::bail!("Set a memory or CPU limit");
+        }
+        if policy.duration_secs < 0 {
+            anyhow::bail!("Duration cannot be negative");
+        }
+        if !["kill", "nice", "notify"].contains(&policy.action.as_str()) {
+            anyhow::bail!("Unknown policy action: {}", policy.action);
+        }
+        Ok(())
+    }
+
+    pub fn start(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut system = System::new();
+            let mut tracker = BreachTracker::default();
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+
+            loop {
+                interval.tick().await;
+                let actions = match Self::check(&db, &mut system, &mut tracker) {
+                    Ok(actions) => actions,
+                    Err(e) => {
+                        eprintln!("Process guardian check failed: {}", e);
+                        continue;
+                    }
+                };
+                for action in &actions {
+                    Self::notify(&app, action).await;
+                }
+            }
+        });
+    }
+
+    fn check(db: &Arc<Mutex<Database>>, system: &mut System, tracker: &mut BreachTracker) -> Result<Vec<ProcessPolicyAction>> {
+        let conn = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            db_guard.conn.clone()
+        };
+        let store = ProcessPolicyStore::new(conn);
+        let policies = store.list_policies(true)?;
+        tracker.forget_policies_except(&policies.iter().map(|p| p.id).collect());
+        if policies.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // CPU usage is measured between refreshes, so the system is kept
+        // across checks
+        system.refresh_processes();
+        let now = chrono::Utc::now().timestamp();
+        let own_pid = std::process::id();
+        let mut actions = Vec::new();
+
+        for policy in &policies {
+            let pattern = match Regex::new(&format!("(?i){}", policy.pattern)) {
+                Ok(pattern) => pattern,
+                Err(e) => {
+                    eprintln!("Process policy '{}' has an invalid pattern: {}", policy.name, e);
+                    continue;
+                }
+            };
+
+            let breaches: Vec<Breach> = system
+                .processes()
+                .iter()
+                .filter(|(pid, process)| pid.as_u32() != own_pid && pattern.is_match(process.name()))
+                .filter_map(|(pid, process)| {
+                    let reason = breach_reason(policy, process.memory(), process.cpu_usage())?;
+                    Some(Breach {
+                        pid: pid.as_u32(),
+                        name: process.name().to_string(),
+                        rss_bytes: process.memory(),
+                        cpu_percent: process.cpu_usage(),
+                        reason,
+                    })
+                })
+                .collect();
+            let pids: Vec<u32> = breaches.iter().map(|b| b.pid).collect();
+
+            for pid in tracker.observe(policy, &pids, now) {
+                let Some(breach) = breaches.iter().find(|b| b.pid == pid) else { continue };
+                let result = if policy.dry_run {
+                    Ok(())
+                } else {
+                    Self::enforce(system, policy, pid)
+                };
+                let action = ProcessPolicyAction {
+                    id: 0,
+                    policy_id: policy.id,
+                    policy_name: policy.name.clone(),
+                    pid,
+                    process_name: breach.name.clone(),
+                    action: policy.action.clone(),
+                    reason: breach.reason.clone(),
+                    rss_bytes: breach.rss_bytes,
+                    cpu_percent: breach.cpu_percent,
+                    dry_run: policy.dry_run,
+                    success: result.is_ok(),
+                    error: result.err().map(|e| e.to_string()),
+                    created_at: now,
+                };
+                let id = store.record_action(&action)?;
+                actions.push(ProcessPolicyAction { id, ..action });
+            }
+        }
+
+        Ok(actions)
+    }
+
+    fn enforce(system: &System, policy: &ProcessPolicy, pid: u32) -> Result<()> {
+        match policy.action.as_str() {
+            "kill" => {
+                let process = system
+                    .process(sysinfo::Pid::from_u32(pid))
+                    .ok_or_else(|| anyhow::anyhow!("Process {} exited", pid))?;
+                if !process.kill() {
+                    anyhow::bail!("Failed to kill process {}", pid);
+                }
+                Ok(())
+            }
+            "nice" => renice(pid, policy.nice_level),
+            "notify" => Ok(()),
+            other => anyhow::bail!("Unknown policy action: {}", other),
+        }
+    }
+
+    async fn notify(app: &AppHandle, action: &ProcessPolicyAction) {
+        use crate::services::desktop_notifications::{DesktopNotificationService, NotificationOptions};
+
+        let payload = serde_json::to_value(action).unwrap_or_default();
+        let _ = crate::services::window_router::emit(app, serde_json::json!({
+            "type": "process-policy-triggered",
+            "data": payload,
+            "timestamp": chrono::Utc::now().timestamp_millis(),
+        }));
+
+        let verb = match (action.action.as_str(), action.dry_run) {
+            ("kill", false) => "Killed",
+            ("kill", true) => "Would kill",
+            ("nice", false) => "Reniced",
+            ("nice", true) => "Would renice",
+            _ => "Over limit:",
+        };
+        let outcome = match &action.error {
+            Some(error) => format!(" (failed: {})", error),
+            None => String::new(),
+        };
+        let _ = DesktopNotificationService::send(app, NotificationOptions {
+            title: format!("Process policy: {}", action.policy_name),
+            body: format!("{} {} ({}): {}{}", verb, action.process_name, action.pid, action.reason, outcome),
+            icon: Some("alert".to_string()),
+            sound: None,
+            tag: Some(format!("process-policy-{}-{}", action.policy_id, action.pid)),
+            data: Some(payload),
+        }).await;
+    }
+}
+
+#[cfg(unix)]
+fn renice(pid: u32, level: i32) -> Result<()> {
+    let output = std::process::Command::new("renice")
+        .args(["-n", &level.to_string(), "-p", &pid.to_string()])
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("renice failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn renice(_pid: u32, _level: i32) -> Result<()> {
+    anyhow::bail!("Changing process priority is not supported on this platform")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(duration_secs: i64) -> ProcessPolicy {
+        ProcessPolicy {
+            id: 1,
+            name: "Browsers".to_string(),
+            pattern: "chrome".to_string(),
+            max_rss_bytes: Some(8 * 1024 * 1_048_576),
+            max_cpu_percent: Some(90.0),
+            duration_secs,
+            action: "kill".to_string(),
+            nice_level: 10,
+            dry_run: true,
+            enabled: true,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn breaches_must_be_sustained_and_fire_once() {
+        let policy = policy(300);
+        assert!(breach_reason(&policy, 1_048_576, 50.0).is_none());
+        assert_eq!(breach_reason(&policy, 1_048_576, 95.0).as_deref(), Some("CPU 95% > 90%"));
+
+        let mut tracker = BreachTracker::default();
+        assert!(tracker.observe(&policy, &[42], 1_000).is_empty());
+        assert!(tracker.observe(&policy, &[42], 1_200).is_empty());
+        assert_eq!(tracker.observe(&policy, &[42], 1_300), vec![42]);
+        assert!(tracker.observe(&policy, &[42], 1_400).is_empty());
+
+        // Dropping under the limit resets the clock
+        assert!(tracker.observe(&policy, &[], 1_500).is_empty());
+        assert!(tracker.observe(&policy, &[42], 1_600).is_empty());
+        assert_eq!(tracker.observe(&policy, &[42], 1_900), vec![42]);
+    }
+}
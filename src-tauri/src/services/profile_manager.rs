@@ -0,0 +1,129 @@
+use crate::storage::ai::{AIStore, ChatMessage, Conversation};
+use crate::storage::auth::AuthManager;
+use crate::storage::profiles::{profile_user_id, Profile, ProfileStore};
+use crate::storage::temporal::{AlertRule, TemporalStore, Watchlist, WatchlistItem};
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileSwitch {
+    pub profile: Profile,
+    pub session_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchlistExport {
+    pub watchlist: Watchlist,
+    pub items: Vec<WatchlistItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationExport {
+    pub conversation: Conversation,
+    pub messages: Vec<ChatMessage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileExport {
+    pub format: String,
+    pub exported_at: i64,
+    pub profile: Profile,
+    pub watchlists: Vec<WatchlistExport>,
+    pub alert_rules: Vec<AlertRule>,
+    pub conversations: Vec<ConversationExport>,
+}
+
+pub struct ProfileManager;
+
+impl ProfileManager {
+    /// Make `profile_id` the active profile. Profiles with a PIN require it;
+    /// every attempt is logged through the auth system and a successful
+    /// switch opens a session for the profile.
+    pub fn switch(db: &Arc<Mutex<Database>>, profile_id: i64, pin: Option<&str>) -> Result<ProfileSwitch> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let store = ProfileStore::new(db_guard.conn.clone());
+        let auth = AuthManager::new(db_guard.conn.clone())?;
+        let user_id = profile_user_id(profile_id);
+
+        let profile = store.get_profile(profile_id)?
+            .ok_or_else(|| anyhow::anyhow!("Profile {} not found", profile_id))?;
+
+        if profile.has_pin {
+            let valid = match pin {
+                Some(p) => auth.verify_pin(&user_id, p)?,
+                None => false,
+            };
+            let _ = auth.log_auth_attempt(&user_id, valid, None);
+            if !valid {
+                return Err(anyhow::anyhow!("Invalid PIN for profile '{}'", profile.name));
+            }
+        }
+
+        store.set_active_profile(profile_id)?;
+        let session_id = auth.create_session(&user_id)?;
+
+        Ok(ProfileSwitch {
+            profile: Profile { active: true, ..profile },
+            session_id,
+        })
+    }
+
+    /// Set or change a profile's PIN. Changing an existing PIN requires the current one.
+    pub fn set_pin(db: &Arc<Mutex<Database>>, profile_id: i64, pin: &str, current_pin: Option<&str>) -> Result<()> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let store = ProfileStore::new(db_guard.conn.clone());
+        let auth = AuthManager::new(db_guard.conn.clone())?;
+        let user_id = profile_user_id(profile_id);
+
+        let profile = store.get_profile(profile_id)?
+            .ok_or_else(|| anyhow::anyhow!("Profile {} not found", profile_id))?;
+        if profile.has_pin {
+            let valid = match current_pin {
+                Some(p) => auth.verify_pin(&user_id, p)?,
+                None => false,
+            };
+            let _ = auth.log_auth_attempt(&user_id, valid, None);
+            if !valid {
+                return Err(anyhow::anyhow!("Current PIN is incorrect"));
+            }
+        }
+
+        auth.set_pin(&user_id, pin)
+    }
+
+    /// Everything that belongs to one profile, independent of which profile is active.
+    pub fn export(db: &Arc<Mutex<Database>>, profile_id: i64) -> Result<ProfileExport> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let profile = ProfileStore::new(db_guard.conn.clone())
+            .get_profile(profile_id)?
+            .ok_or_else(|| anyhow::anyhow!("Profile {} not found", profile_id))?;
+        let temporal = TemporalStore::new(db_guard.conn.clone());
+        let ai = AIStore::new(db_guard.conn.clone())?;
+
+        let mut watchlists = Vec::new();
+        for watchlist in temporal.list_watchlists_for(Some(profile_id))? {
+            let items = temporal.list_watchlist_items(watchlist.id)?;
+            watchlists.push(WatchlistExport { watchlist, items });
+        }
+
+        let mut conversations = Vec::new();
+        for conversation in ai.list_conversations_for(Some(profile_id))? {
+            let messages = ai.get_messages(&conversation.id)?;
+            conversations.push(ConversationExport { conversation, messages });
+        }
+
+        Ok(ProfileExport {
+            format: "mina-profile-export".to_string(),
+            exported_at: chrono::Utc::now().timestamp(),
+            alert_rules: temporal.list_alert_rules_for(Some(profile_id))?,
+            profile,
+            watchlists,
+            conversations,
+        })
+    }
+}
@@ -0,0 +1,264 @@
+use crate::storage::ai::{AIStore, PromptTemplate, TemplateExample, TemplateVariable};
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+pub const BUNDLE_FORMAT: &str = "mina-prompt-bundle";
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Shareable collection of prompt templates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplateBundle {
+    pub format: String,
+    pub format_version: u32,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub exported_at: Option<i64>,
+    pub templates: Vec<BundledTemplate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledTemplate {
+    pub name: String,
+    #[serde(default = "default_version")]
+    pub version: i64,
+    pub template: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub variables: Vec<TemplateVariable>,
+    #[serde(default)]
+    pub examples: Vec<TemplateExample>,
+}
+
+fn default_version() -> i64 {
+    1
+}
+
+/// What to do when an imported template has the same name as an existing one.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// Replace only when the incoming version is higher (the default).
+    KeepNewer,
+    Overwrite,
+    Skip,
+    /// Import under a new name, e.g. "summarize (2)".
+    Rename,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateImportReport {
+    pub imported: Vec<String>,
+    pub updated: Vec<String>,
+    pub skipped: Vec<String>,
+    pub renamed: Vec<(String, String)>,
+}
+
+impl From<PromptTemplate> for BundledTemplate {
+    fn from(t: PromptTemplate) -> Self {
+        let variables = if t.variables.is_empty() {
+            extract_variables(&t.template)
+        } else {
+            t.variables
+        };
+        BundledTemplate {
+            name: t.name,
+            version: t.version,
+            template: t.template,
+            description: t.description,
+            variables,
+            examples: t.examples,
+        }
+    }
+}
+
+pub struct PromptBundleService;
+
+impl PromptBundleService {
+    pub fn export(store: &AIStore, ids: Option<&[i64]>, name: Option<String>, author: Option<String>) -> Result<PromptTemplateBundle> {
+        let templates = match ids {
+            Some(ids) => {
+                let mut templates = Vec::new();
+                for id in ids {
+                    if let Some(t) = store.get_template_by_id(*id)? {
+                        templates.push(t);
+                    }
+                }
+                templates
+            }
+            None => store.list_templates()?,
+        };
+
+        Ok(PromptTemplateBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            format_version: BUNDLE_FORMAT_VERSION,
+            name,
+            description: None,
+            author,
+            exported_at: Some(chrono::Utc::now().timestamp()),
+            templates: templates.into_iter().map(BundledTemplate::from).collect(),
+        })
+    }
+
+    /// Load a bundle from a local path or an http(s) URL.
+    pub async fn load(path_or_url: &str) -> Result<PromptTemplateBundle> {
+        let content = if path_or_url.starts_with("http://") || path_or_url.starts_with("https://") {
+            reqwest::Client::new()
+                .get(path_or_url)
+                .timeout(std::time::Duration::from_secs(30))
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?
+        } else {
+            tokio::fs::read_to_string(path_or_url)
+                .await
+                .with_context(|| format!("Failed to read {}", path_or_url))?
+        };
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<PromptTemplateBundle> {
+        let bundle: PromptTemplateBundle = serde_json::from_str(content)
+            .context("Invalid prompt template bundle")?;
+        if bundle.format != BUNDLE_FORMAT {
+            anyhow::bail!("Unsupported bundle format: {}", bundle.format);
+        }
+        if bundle.format_version > BUNDLE_FORMAT_VERSION {
+            anyhow::bail!(
+                "Bundle format version {} is newer than supported version {}",
+                bundle.format_version,
+                BUNDLE_FORMAT_VERSION
+            );
+        }
+        Ok(bundle)
+    }
+
+    pub fn import(store: &AIStore, bundle: &PromptTemplateBundle, strategy: ConflictStrategy) -> Result<TemplateImportReport> {
+        let mut report = TemplateImportReport::default();
+
+        for incoming in &bundle.templates {
+            let name = incoming.name.trim();
+            if name.is_empty() || incoming.template.trim().is_empty() {
+                report.skipped.push(incoming.name.clone());
+                continue;
+            }
+            let variables = if incoming.variables.is_empty() {
+                extract_variables(&incoming.template)
+            } else {
+                incoming.variables.clone()
+            };
+
+            let target_name = match store.get_template(name)? {
+                None => {
+                    report.imported.push(name.to_string());
+                    name.to_string()
+                }
+                Some(existing) => match strategy {
+                    ConflictStrategy::Skip => {
+                        report.skipped.push(name.to_string());
+                        continue;
+                    }
+                    ConflictStrategy::KeepNewer if incoming.version <= existing.version => {
+                        report.skipped.push(name.to_string());
+                        continue;
+                    }
+                    ConflictStrategy::KeepNewer | ConflictStrategy::Overwrite => {
+                        report.updated.push(name.to_string());
+                        name.to_string()
+                    }
+                    ConflictStrategy::Rename => {
+                        let mut n = 2;
+                        let mut candidate = format!("{} ({})", name, n);
+                        while store.get_template(&candidate)?.is_some() {
+                            n += 1;
+                            candidate = format!("{} ({})", name, n);
+                        }
+                        report.renamed.push((name.to_string(), candidate.clone()));
+                        candidate
+                    }
+                },
+            };
+
+            store.save_template(
+                &target_name,
+                &incoming.template,
+                incoming.description.as_deref(),
+                incoming.version,
+                &variables,
+                &incoming.examples,
+            )?;
+        }
+
+        Ok(report)
+    }
+}
+
+fn placeholder_regex() -> Option<Regex> {
+    Regex::new(r"\{\{\s*([A-Za-z_][\w.]*)\s*\}\}").ok()
+}
+
+/// `{{name}}` placeholders in order of first appearance.
+pub fn extract_variables(template: &str) -> Vec<TemplateVariable> {
+    let re = match placeholder_regex() {
+        Some(re) => re,
+        None => return Vec::new(),
+    };
+    let mut seen = std::collections::HashSet::new();
+    re.captures_iter(template)
+        .filter(|caps| seen.insert(caps[1].to_string()))
+        .map(|caps| TemplateVariable {
+            name: caps[1].to_string(),
+            description: None,
+            default: None,
+        })
+        .collect()
+}
+
+/// Fill `{{name}}` placeholders from `values`. Dotted names walk into
+/// objects and arrays (`event.title`, `links.articles.0`); arrays render
+/// comma-separated, fractional numbers with two decimals, and unknown
+/// names render empty.
+pub fn render_template(template: &str, values: &serde_json::Value) -> String {
+    let re = match placeholder_regex() {
+        Some(re) => re,
+        None => return template.to_string(),
+    };
+    re.replace_all(template, |caps: &regex::Captures| {
+        lookup_variable(values, &caps[1]).map(value_to_text).unwrap_or_default()
+    })
+    .into_owned()
+}
+
+/// Placeholders of `template` that `values` has no value for.
+pub fn missing_variables(template: &str, values: &serde_json::Value) -> Vec<String> {
+    extract_variables(template)
+        .into_iter()
+        .map(|v| v.name)
+        .filter(|name| lookup_variable(values, name).map(|v| v.is_null()).unwrap_or(true))
+        .collect()
+}
+
+fn lookup_variable<'a>(values: &'a serde_json::Value, name: &str) -> Option<&'a serde_json::Value> {
+    name.split('.').try_fold(values, |value, key| match value {
+        serde_json::Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => value.get(key),
+    })
+}
+
+fn value_to_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) if n.is_f64() => format!("{:.2}", n.as_f64().unwrap_or_default()),
+        serde_json::Value::Array(items) => items.iter().map(value_to_text).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
@@ -1,3 +1,4 @@
+use crate::storage::rate_limit::RateLimitStore;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -8,9 +9,14 @@ pub struct RateLimit {
     pub window: Duration,
 }
 
+/// Per-provider request limits. When backed by a RateLimitStore, limits are
+/// token buckets persisted in the database, so they survive restarts and are
+/// the same buckets the rate limit commands show and edit. Without a store
+/// (or for providers without a bucket) it falls back to an in-memory window.
 pub struct RateLimiter {
     limits: HashMap<String, RateLimit>,
     requests: Arc<Mutex<HashMap<String, Vec<Instant>>>>,
+    store: Option<Arc<RateLimitStore>>,
 }
 
 impl RateLimiter {
@@ -18,11 +24,26 @@ impl RateLimiter {
         RateLimiter {
             limits: HashMap::new(),
             requests: Arc::new(Mutex::new(HashMap::new())),
+            store: None,
         }
     }
 
-    /// Register a rate limit for a provider
+    pub fn with_store(store: Arc<RateLimitStore>) -> Self {
+        RateLimiter {
+            store: Some(store),
+            ..Self::new()
+        }
+    }
+
+    /// Register a rate limit for a provider. With a store, this creates the
+    /// provider's bucket on first run; an existing bucket is left as is so
+    /// limits edited through the rate limit commands stick.
     pub fn register_limit(&mut self, provider: String, max_requests: u32, window_secs: u64) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.ensure_bucket(&provider, max_requests as i64, max_requests as i64, window_secs as i64) {
+                eprintln!("Failed to persist rate limit for {}: {}", provider, e);
+            }
+        }
         self.limits.insert(
             provider,
             RateLimit {
@@ -34,6 +55,10 @@ impl RateLimiter {
 
     /// Check if a request can be made for a provider
     pub fn can_make_request(&self, provider: &str) -> bool {
+        if let Some(bucket) = self.bucket(provider) {
+            return bucket.tokens > 0;
+        }
+
         let limit = match self.limits.get(provider) {
             Some(l) => l,
             None => return true, // No limit registered, allow request
@@ -59,62 +84,80 @@ impl RateLimiter {
         }
     }
 
-    /// Record a request for a provider
-    pub fn record_request(&self, provider: &str) {
+    /// Take a request slot if one is free. Checking and taking happen in one
+    /// step (a single token consume, or under the window lock) so concurrent
+    /// callers can't both take the last slot.
+    pub fn try_acquire(&self, provider: &str) -> bool {
+        if let Some(store) = &self.store {
+            match store.consume_token(provider, 1) {
+                Ok(true) => return true,
+                // consume_token also says false when there's no bucket at all
+                Ok(false) if self.bucket(provider).is_some() => return false,
+                Ok(false) => {}
+                Err(e) => eprintln!("Failed to consume rate limit token for {}: {}", provider, e),
+            }
+        }
+
+        let limit = match self.limits.get(provider) {
+            Some(l) => l,
+            None => return true, // No limit registered, allow request
+        };
+
         let mut requests = match self.requests.lock() {
             Ok(r) => r,
-            Err(_) => return, // Mutex poisoned, skip recording
+            Err(_) => return false, // Mutex poisoned, deny to be safe
         };
 
         let now = Instant::now();
-        requests
-            .entry(provider.to_string())
-            .or_insert_with(Vec::new)
-            .push(now);
+        let window_start = now - limit.window;
+        let provider_requests = requests.entry(provider.to_string()).or_default();
+        provider_requests.retain(|&timestamp| timestamp > window_start);
+        if provider_requests.len() < limit.max_requests as usize {
+            provider_requests.push(now);
+            true
+        } else {
+            false
+        }
     }
 
-    /// Wait if needed until a request can be made
-    pub async fn wait_if_needed(&self, provider: &str) {
-        while !self.can_make_request(provider) {
-            // Calculate time until next request can be made
-            let limit = match self.limits.get(provider) {
-                Some(l) => l,
-                None => return, // No limit, no need to wait
-            };
-
-            // Extract the wait time while holding the lock, then drop it before await
-            let wait_time = {
-                let requests = match self.requests.lock() {
-                    Ok(r) => r,
-                    Err(_) => return,
-                };
-
-                if let Some(provider_requests) = requests.get(provider) {
-                    if let Some(oldest) = provider_requests.first() {
-                        let elapsed = oldest.elapsed();
-                        if elapsed < limit.window {
-                            Some(limit.window - elapsed)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            };
-
-            if let Some(wait_time) = wait_time {
-                tokio::time::sleep(wait_time).await;
-            } else {
-                return;
+    /// Wait until a request slot is free and take it.
+    pub async fn acquire(&self, provider: &str) {
+        while !self.try_acquire(provider) {
+            match self.wait_time(provider) {
+                Some(wait_time) => tokio::time::sleep(wait_time).await,
+                None => return,
             }
         }
     }
 
+    /// How long until a slot may free up, or None when waiting won't help.
+    fn wait_time(&self, provider: &str) -> Option<Duration> {
+        // Persisted bucket: sleep until its next refill
+        if let Some(bucket) = self.bucket(provider) {
+            if bucket.refill_rate <= 0 || bucket.refill_interval <= 0 {
+                return None;
+            }
+            let now = chrono::Utc::now().timestamp();
+            let wait_secs = (bucket.last_refill + bucket.refill_interval - now).max(1);
+            return Some(Duration::from_secs(wait_secs as u64));
+        }
+
+        let limit = self.limits.get(provider)?;
+        let requests = self.requests.lock().ok()?;
+        let elapsed = requests.get(provider)?.first()?.elapsed();
+        if elapsed < limit.window {
+            Some(limit.window - elapsed)
+        } else {
+            None
+        }
+    }
+
     /// Get remaining requests in current window
     pub fn get_remaining_requests(&self, provider: &str) -> Option<u32> {
+        if let Some(bucket) = self.bucket(provider) {
+            return Some(bucket.tokens.max(0) as u32);
+        }
+
         let limit = self.limits.get(provider)?;
         let requests = self.requests.lock().ok()?;
         
@@ -132,7 +175,19 @@ impl RateLimiter {
         }
     }
 
-    /// Clear old requests (cleanup)
+    /// The provider's persisted bucket with pending refills applied, if any.
+    fn bucket(&self, provider: &str) -> Option<crate::storage::rate_limit::RateLimitBucket> {
+        let store = self.store.as_ref()?;
+        match store.peek_bucket(provider) {
+            Ok(bucket) => bucket,
+            Err(e) => {
+                eprintln!("Failed to read rate limit bucket for {}: {}", provider, e);
+                None
+            }
+        }
+    }
+
+    /// Clear old requests (cleanup). Persisted buckets refill lazily and need none.
     pub fn cleanup(&self) {
         let mut requests = match self.requests.lock() {
             Ok(r) => r,
@@ -160,7 +215,26 @@ impl Clone for RateLimiter {
         RateLimiter {
             limits: self.limits.clone(),
             requests: self.requests.clone(),
+            store: self.store.clone(),
         }
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acquire_takes_slots_until_the_window_is_full() {
+        let mut limiter = RateLimiter::new();
+        limiter.register_limit("test".to_string(), 2, 60);
+        let shared = limiter.clone();
+
+        assert!(limiter.try_acquire("test"));
+        assert!(shared.try_acquire("test"));
+        assert!(!limiter.try_acquire("test"));
+        assert_eq!(limiter.get_remaining_requests("test"), Some(0));
+        assert!(limiter.try_acquire("unlimited"));
+    }
+}
@@ -0,0 +1,271 @@
+use crate::services::portfolio_analyzer::PortfolioAnalyzer;
+use crate::storage::market_data::MarketDataStore;
+use crate::storage::portfolio::PortfolioStore;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllocationDrift {
+    pub target_type: String, // ticker|sector
+    pub target: String,
+    pub current_value: f64,
+    pub current_percent: f64,
+    pub target_percent: f64,
+    pub drift_percent: f64, // current - target
+    pub drift_value: f64,   // value to sell (positive) or buy (negative) to hit the target
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftReport {
+    pub portfolio_id: i64,
+    pub base_currency: String,
+    pub total_value: f64, // holdings plus available cash
+    pub cash: f64,
+    pub entries: Vec<AllocationDrift>,
+    pub max_abs_drift_percent: f64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RebalanceOptions {
+    /// Trades smaller than this (base currency) are skipped
+    #[serde(default)]
+    pub min_trade_size: f64,
+    /// Uninvested cash that can fund buys in addition to sale proceeds
+    #[serde(default)]
+    pub available_cash: f64,
+    #[serde(default)]
+    pub whole_shares: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestedTrade {
+    pub ticker: String,
+    pub action: String, // buy|sell
+    pub quantity: f64,
+    pub price: f64, // in the ticker's own currency
+    pub value: f64, // in the portfolio's base currency
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebalancePlan {
+    pub drift: DriftReport,
+    pub trades: Vec<SuggestedTrade>,
+    pub cash_after: f64,
+    pub notes: Vec<String>,
+}
+
+struct Position {
+    value: f64,
+    price: f64,
+    fx_rate: f64,
+}
+
+pub struct RebalanceAdvisor;
+
+impl RebalanceAdvisor {
+    /// Compare current weights against the portfolio's targets. Holdings not
+    /// covered by any target show up with a 0% target.
+    pub fn drift(
+        portfolio_store: &PortfolioStore,
+        market_data_store: &MarketDataStore,
+        portfolio_id: i64,
+        cash: f64,
+    ) -> Result<DriftReport> {
+        let targets = portfolio_store.list_target_allocations(portfolio_id)?;
+        if targets.is_empty() {
+            anyhow::bail!("Portfolio {} has no target allocations", portfolio_id);
+        }
+        let by_sector = targets[0].target_type == "sector";
+        let value = PortfolioAnalyzer::calculate_portfolio_value(portfolio_store, market_data_store, portfolio_id)?;
+        let total_value = value.total_value + cash.max(0.0);
+
+        let sectors = if by_sector {
+            let tickers: Vec<String> = value.holdings.iter().map(|h| h.ticker.clone()).collect();
+            market_data_store.get_sectors(&tickers)?
+        } else {
+            HashMap::new()
+        };
+
+        let mut current: HashMap<String, f64> = HashMap::new();
+        for holding in &value.holdings {
+            let key = if by_sector {
+                sectors.get(&holding.ticker).cloned().unwrap_or_else(|| "Unknown".to_string())
+            } else {
+                holding.ticker.clone()
+            };
+            *current.entry(key).or_insert(0.0) += holding.current_value;
+        }
+
+        let mut entries = Vec::new();
+        let target_type = if by_sector { "sector" } else { "ticker" };
+        let mut push = |target: &str, current_value: f64, target_percent: f64| {
+            let current_percent = percent_of(current_value, total_value);
+            entries.push(AllocationDrift {
+                target_type: target_type.to_string(),
+                target: target.to_string(),
+                current_value,
+                current_percent,
+                target_percent,
+                drift_percent: current_percent - target_percent,
+                drift_value: current_value - total_value * target_percent / 100.0,
+            });
+        };
+        for target in &targets {
+            push(&target.target, current.remove(&target.target).unwrap_or(0.0), target.weight_percent);
+        }
+        for (target, current_value) in current {
+            push(&target, current_value, 0.0);
+        }
+
+        entries.sort_by(|a, b| b.drift_percent.abs().partial_cmp(&a.drift_percent.abs()).unwrap_or(std::cmp::Ordering::Equal));
+        let max_abs_drift_percent = entries.first().map(|e| e.drift_percent.abs()).unwrap_or(0.0);
+
+        Ok(DriftReport {
+            portfolio_id,
+            base_currency: value.base_currency,
+            total_value,
+            cash: cash.max(0.0),
+            entries,
+            max_abs_drift_percent,
+        })
+    }
+
+    /// Trades that move the portfolio towards its targets. Sells run first and
+    /// fund the buys; buys are scaled down when proceeds plus available cash
+    /// don't cover them. Sector drift is spread over the sector's holdings in
+    /// proportion to their current value.
+    pub fn suggest_trades(
+        portfolio_store: &PortfolioStore,
+        market_data_store: &MarketDataStore,
+        portfolio_id: i64,
+        options: &RebalanceOptions,
+    ) -> Result<RebalancePlan> {
+        let drift = Self::drift(portfolio_store, market_data_store, portfolio_id, options.available_cash)?;
+        let value = PortfolioAnalyzer::calculate_portfolio_value(portfolio_store, market_data_store, portfolio_id)?;
+        let mut notes = Vec::new();
+
+        let positions: HashMap<String, Position> = value.holdings
+            .iter()
+            .map(|h| (h.ticker.clone(), Position { value: h.current_value, price: h.current_price, fx_rate: h.fx_rate }))
+            .collect();
+
+        // Per-ticker value change: positive = buy, negative = sell
+        let mut deltas: Vec<(String, f64)> = Vec::new();
+        let by_sector = drift.entries.first().map(|e| e.target_type == "sector").unwrap_or(false);
+        if by_sector {
+            let tickers: Vec<String> = positions.keys().cloned().collect();
+            let sectors = market_data_store.get_sectors(&tickers)?;
+            for entry in &drift.entries {
+                let members: Vec<(&String, &Position)> = positions
+                    .iter()
+                    .filter(|(t, _)| sectors.get(*t).map(|s| s.as_str()).unwrap_or("Unknown") == entry.target)
+                    .collect();
+                let sector_value: f64 = members.iter().map(|(_, p)| p.value).sum();
+                if members.is_empty() || sector_value <= 0.0 {
+                    if entry.drift_value < 0.0 {
+                        notes.push(format!("No holdings in sector {} to buy; add a ticker to reach its target", entry.target));
+                    }
+                    continue;
+                }
+                for (ticker, position) in members {
+                    deltas.push((ticker.clone(), -entry.drift_value * position.value / sector_value));
+                }
+            }
+        } else {
+            for entry in &drift.entries {
+                deltas.push((entry.target.clone(), -entry.drift_value));
+            }
+        }
+
+        let mut cash = drift.cash;
+        let mut trades = Vec::new();
+
+        // Sells first so their proceeds can fund buys
+        for (ticker, delta) in deltas.iter().filter(|(_, d)| *d < 0.0) {
+            let Some(position) = positions.get(ticker) else { continue };
+            let value = (-delta).min(position.value);
+            if let Some(trade) = Self::trade(ticker, "sell", value, position.price, position.fx_rate, options, &mut notes) {
+                cash += trade.value;
+                trades.push(trade);
+            }
+        }
+
+        let mut buys: Vec<(&String, f64)> = deltas.iter().filter(|(_, d)| *d > 0.0).map(|(t, d)| (t, *d)).collect();
+        buys.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        let wanted: f64 = buys.iter().map(|(_, d)| d).sum();
+        let scale = if wanted > cash && wanted > 0.0 {
+            notes.push(format!("Buys scaled to {:.0}% to fit available cash", cash / wanted * 100.0));
+            cash / wanted
+        } else {
+            1.0
+        };
+
+        for (ticker, delta) in buys {
+            let (price, fx_rate) = match positions.get(ticker) {
+                Some(position) => (position.price, position.fx_rate),
+                None => match market_data_store.get_price(ticker)? {
+                    // Not held yet, so no currency on record; assume the base currency
+                    Some(price) => (price.price, 1.0),
+                    None => {
+                        notes.push(format!("No price for {}; buy skipped", ticker));
+                        continue;
+                    }
+                },
+            };
+            let value = (delta * scale).min(cash);
+            if let Some(trade) = Self::trade(ticker, "buy", value, price, fx_rate, options, &mut notes) {
+                cash -= trade.value;
+                trades.push(trade);
+            }
+        }
+
+        Ok(RebalancePlan {
+            drift,
+            trades,
+            cash_after: cash,
+            notes,
+        })
+    }
+
+    fn trade(
+        ticker: &str,
+        action: &str,
+        value: f64,
+        price: f64,
+        fx_rate: f64,
+        options: &RebalanceOptions,
+        notes: &mut Vec<String>,
+    ) -> Option<SuggestedTrade> {
+        let unit_value = price * fx_rate;
+        if unit_value <= 0.0 {
+            notes.push(format!("No usable price for {}; {} skipped", ticker, action));
+            return None;
+        }
+
+        let mut quantity = value / unit_value;
+        if options.whole_shares {
+            quantity = quantity.floor();
+        }
+        let value = quantity * unit_value;
+        if quantity <= 0.0 || value < options.min_trade_size.max(0.01) {
+            return None;
+        }
+
+        Some(SuggestedTrade {
+            ticker: ticker.to_string(),
+            action: action.to_string(),
+            quantity,
+            price,
+            value,
+        })
+    }
+}
+
+fn percent_of(value: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        value / total * 100.0
+    } else {
+        0.0
+    }
+}
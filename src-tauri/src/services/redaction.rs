@@ -0,0 +1,260 @@
+use crate::providers::ollama::ChatMessage;
+use crate::storage::llm_usage::{CloudEgressRecord, LlmUsageStore};
+use crate::storage::Database;
+use anyhow::Result;
+use regex::Regex;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock};
+
+pub const CONFIG_ENABLED: &str = "redaction_enabled";
+pub const CONFIG_MODE: &str = "redaction_mode";
+pub const CONFIG_CATEGORIES: &str = "redaction_categories";
+pub const CONFIG_ENTITIES: &str = "redaction_entities";
+
+pub const CATEGORY_EMAIL: &str = "email";
+pub const CATEGORY_IP: &str = "ip";
+pub const CATEGORY_ACCOUNT: &str = "account";
+pub const CATEGORY_ENTITY: &str = "entity";
+
+/// Characters of the redacted text kept in the egress audit.
+const PREVIEW_CHARS: usize = 500;
+
+/// What gets redacted from text bound for cloud providers, and how.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedactionSettings {
+    pub enabled: bool,
+    /// "pseudonymize" swaps values for placeholders that responses can be
+    /// mapped back from; "strip" removes them for good
+    pub mode: String,
+    /// Any of email|ip|account
+    pub categories: Vec<String>,
+    /// Names (people, companies, projects) to hide wherever they appear
+    pub entities: Vec<String>,
+}
+
+impl Default for RedactionSettings {
+    fn default() -> Self {
+        RedactionSettings {
+            enabled: true,
+            mode: "pseudonymize".to_string(),
+            categories: vec![CATEGORY_EMAIL.to_string(), CATEGORY_IP.to_string(), CATEGORY_ACCOUNT.to_string()],
+            entities: Vec::new(),
+        }
+    }
+}
+
+impl RedactionSettings {
+    pub fn load(db: &Database) -> Result<Self> {
+        let defaults = Self::default();
+        let list = |value: String, separator: char| -> Vec<String> {
+            value.split(separator).map(|v| v.trim().to_string()).filter(|v| !v.is_empty()).collect()
+        };
+        Ok(RedactionSettings {
+            enabled: db.get_config(CONFIG_ENABLED)?.map(|v| v == "true").unwrap_or(defaults.enabled),
+            mode: db.get_config(CONFIG_MODE)?
+                .filter(|m| m == "strip" || m == "pseudonymize")
+                .unwrap_or(defaults.mode),
+            categories: db.get_config(CONFIG_CATEGORIES)?.map(|v| list(v, ',')).unwrap_or(defaults.categories),
+            entities: db.get_config(CONFIG_ENTITIES)?.map(|v| list(v, '\n')).unwrap_or(defaults.entities),
+        })
+    }
+
+    pub fn save(&self, db: &Database) -> Result<()> {
+        db.set_config(CONFIG_ENABLED, if self.enabled { "true" } else { "false" })?;
+        db.set_config(CONFIG_MODE, &self.mode)?;
+        db.set_config(CONFIG_CATEGORIES, &self.categories.join(","))?;
+        db.set_config(CONFIG_ENTITIES, &self.entities.join("\n"))?;
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.mode != "strip" && self.mode != "pseudonymize" {
+            anyhow::bail!("Unknown redaction mode: {}", self.mode);
+        }
+        for category in &self.categories {
+            if ![CATEGORY_EMAIL, CATEGORY_IP, CATEGORY_ACCOUNT].contains(&category.as_str()) {
+                anyhow::bail!("Unknown redaction category: {}", category);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn patterns() -> &'static [(&'static str, Regex)] {
+    static PATTERNS: OnceLock<Vec<(&'static str, Regex)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        vec![
+            (CATEGORY_EMAIL, Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").expect("valid regex")),
+            (
+                CATEGORY_IP,
+                Regex::new(r"\b(?:(?:25[0-5]|2[0-4]\d|1?\d?\d)\.){3}(?:25[0-5]|2[0-4]\d|1?\d?\d)\b|\b(?:[0-9A-Fa-f]{1,4}:){7}[0-9A-Fa-f]{1,4}\b")
+                    .expect("valid regex"),
+            ),
+            // IBANs, then card and account numbers: 8-19 digits, optionally grouped
+            (
+                CATEGORY_ACCOUNT,
+                Regex::new(r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,3})?\b|\b\d(?:[ -]?\d){7,18}\b")
+                    .expect("valid regex"),
+            ),
+        ]
+    })
+}
+
+/// Redacts one request. Pseudonyms are stable within it, so the same
+/// value always gets the same placeholder, and `restore` maps them back
+/// in the response.
+pub struct Redactor {
+    settings: RedactionSettings,
+    pseudonyms: HashMap<String, String>,
+    originals: HashMap<String, String>,
+    counts: BTreeMap<String, usize>,
+}
+
+impl Redactor {
+    pub fn new(settings: RedactionSettings) -> Self {
+        Redactor {
+            settings,
+            pseudonyms: HashMap::new(),
+            originals: HashMap::new(),
+            counts: BTreeMap::new(),
+        }
+    }
+
+    pub fn redact(&mut self, text: &str) -> String {
+        if !self.settings.enabled {
+            return text.to_string();
+        }
+        let mut text = text.to_string();
+
+        for (category, pattern) in patterns() {
+            if self.settings.categories.iter().any(|c| c == *category) {
+                text = self.replace(pattern, category, &text);
+            }
+        }
+        // After the patterns, so a configured name inside an email address
+        // doesn't split it
+        let entities: Vec<String> = self.settings.entities.clone();
+        for entity in entities {
+            let Ok(pattern) = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(&entity))) else { continue };
+            text = self.replace(&pattern, CATEGORY_ENTITY, &text);
+        }
+        text
+    }
+
+    fn replace(&mut self, pattern: &Regex, category: &str, text: &str) -> String {
+        pattern
+            .replace_all(text, |caps: &regex::Captures| {
+                let value = caps[0].to_string();
+                *self.counts.entry(category.to_string()).or_insert(0) += 1;
+                if self.settings.mode == "strip" {
+                    return format!("[{} removed]", category);
+                }
+                if let Some(placeholder) = self.pseudonyms.get(&value) {
+                    return placeholder.clone();
+                }
+                let n = self.originals.keys().filter(|p| p.starts_with(&format!("<{}_", category.to_uppercase()))).count() + 1;
+                let placeholder = format!("<{}_{}>", category.to_uppercase(), n);
+                self.pseudonyms.insert(value.clone(), placeholder.clone());
+                self.originals.insert(placeholder.clone(), value);
+                placeholder
+            })
+            .into_owned()
+    }
+
+    /// Put the original values back into a response.
+    pub fn restore(&self, text: &str) -> String {
+        let mut restored = text.to_string();
+        for (placeholder, original) in &self.originals {
+            restored = restored.replace(placeholder, original);
+        }
+        restored
+    }
+
+    pub fn counts(&self) -> &BTreeMap<String, usize> {
+        &self.counts
+    }
+}
+
+/// Applies the redaction settings to everything sent to cloud LLM
+/// providers and logs what left the machine.
+#[derive(Clone)]
+pub struct PrivacyFilter {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl PrivacyFilter {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        PrivacyFilter { conn }
+    }
+
+    /// Redact `text` for `provider` and record it in the egress audit.
+    /// Keep the returned redactor to restore the response.
+    pub fn prepare(&self, provider: &str, model: &str, purpose: &str, text: &str) -> Result<(String, Redactor)> {
+        let settings = RedactionSettings::load(&Database { conn: self.conn.clone() })?;
+        let mut redactor = Redactor::new(settings);
+        let sent = redactor.redact(text);
+        self.audit(provider, model, purpose, &sent, &redactor)?;
+        Ok((sent, redactor))
+    }
+
+    /// `prepare` for a chat: one redactor across all messages, so a value
+    /// gets the same placeholder wherever it appears.
+    pub fn prepare_messages(
+        &self,
+        provider: &str,
+        model: &str,
+        purpose: &str,
+        messages: &[ChatMessage],
+    ) -> Result<(Vec<ChatMessage>, Redactor)> {
+        let settings = RedactionSettings::load(&Database { conn: self.conn.clone() })?;
+        let mut redactor = Redactor::new(settings);
+        let sent: Vec<ChatMessage> = messages
+            .iter()
+            .map(|m| ChatMessage { role: m.role.clone(), content: redactor.redact(&m.content) })
+            .collect();
+        let text = sent.iter().map(|m| format!("{}: {}", m.role, m.content)).collect::<Vec<_>>().join("\n\n");
+        self.audit(provider, model, purpose, &text, &redactor)?;
+        Ok((sent, redactor))
+    }
+
+    fn audit(&self, provider: &str, model: &str, purpose: &str, sent: &str, redactor: &Redactor) -> Result<()> {
+        LlmUsageStore::new(self.conn.clone()).record_egress(&CloudEgressRecord {
+            id: 0,
+            provider: provider.to_string(),
+            model: model.to_string(),
+            purpose: purpose.to_string(),
+            chars_sent: sent.chars().count() as i64,
+            redactions: redactor.counts().clone(),
+            preview: sent.chars().take(PREVIEW_CHARS).collect(),
+            created_at: chrono::Utc::now().timestamp(),
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pseudonyms_are_stable_and_reversible() {
+        let mut redactor = Redactor::new(RedactionSettings {
+            entities: vec!["Project Falcon".to_string()],
+            ..RedactionSettings::default()
+        });
+        let sent = redactor.redact(
+            "Mail jane@corp.example about project falcon from 10.0.0.12; jane@corp.example owns IBAN DE89 3704 0044 0532 0130 00.",
+        );
+        assert_eq!(
+            sent,
+            "Mail <EMAIL_1> about <ENTITY_1> from <IP_1>; <EMAIL_1> owns IBAN <ACCOUNT_1>."
+        );
+        assert_eq!(redactor.counts().get(CATEGORY_EMAIL), Some(&2));
+        assert_eq!(redactor.restore("Reply to <EMAIL_1> re <ENTITY_1>"), "Reply to jane@corp.example re project falcon");
+
+        let mut strip = Redactor::new(RedactionSettings { mode: "strip".to_string(), ..RedactionSettings::default() });
+        assert_eq!(strip.redact("card 4111 1111 1111 1111"), "card [account removed]");
+    }
+}
@@ -0,0 +1,206 @@
+use crate::services::embeddings::EmbeddingService;
+use crate::storage::osint::RSSItem;
+use crate::storage::vector_store::cosine_similarity;
+use crate::storage::{OSINTStore, TemporalStore, VectorDocument, VectorStore};
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Vector collection caching article embeddings, keyed "article:<id>"
+pub const VECTOR_COLLECTION: &str = "articles";
+
+const VECTOR_WEIGHT: f64 = 0.5;
+const ENTITY_WEIGHT: f64 = 0.3;
+const EVENT_WEIGHT: f64 = 0.2;
+/// Candidates gathered per signal before scoring
+const MAX_CANDIDATES: i64 = 40;
+/// Vector neighbours below this similarity aren't candidates on their own
+const MIN_VECTOR_SIMILARITY: f32 = 0.3;
+/// Characters of an article embedded, title included
+const EMBED_CHARS: usize = 2000;
+
+/// Another article covering the same story, with what links the two.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedArticle {
+    pub id: i64,
+    pub feed_id: i64,
+    pub title: String,
+    pub url: String,
+    pub published_at: i64,
+    /// Blend of the signals below in 0..1
+    pub score: f64,
+    /// None when embeddings weren't available
+    pub vector_similarity: Option<f64>,
+    pub shared_entities: Vec<String>,
+    pub shared_events: Vec<i64>,
+}
+
+#[derive(Default)]
+struct Signals {
+    vector: Option<f64>,
+    entities: Vec<String>,
+    events: Vec<i64>,
+}
+
+pub struct RelatedArticles;
+
+impl RelatedArticles {
+    /// Articles related to `article_id`, best first. Without an embedder only
+    /// shared entities and events count.
+    pub async fn find(
+        conn: Arc<Mutex<Connection>>,
+        embedder: Option<&EmbeddingService>,
+        article_id: i64,
+        limit: usize,
+    ) -> Result<Vec<RelatedArticle>> {
+        let osint = OSINTStore::new(conn.clone());
+        let article = osint
+            .get_item(article_id)?
+            .ok_or_else(|| anyhow::anyhow!("Article {} not found", article_id))?;
+
+        let entity_count = osint.get_entities_for_article(article_id)?.len();
+        let mut signals: HashMap<i64, Signals> = HashMap::new();
+        for (id, names) in osint.articles_sharing_entities(article_id, MAX_CANDIDATES)? {
+            signals.entry(id).or_default().entities = names;
+        }
+        for (id, event_id) in TemporalStore::new(conn.clone()).articles_sharing_events(article_id)? {
+            let events = &mut signals.entry(id).or_default().events;
+            if !events.contains(&event_id) {
+                events.push(event_id);
+            }
+        }
+
+        let mut items: HashMap<i64, RSSItem> = HashMap::new();
+        if let Some(embedder) = embedder {
+            let vectors = VectorStore::new(conn.clone());
+            vectors.create_collection(VECTOR_COLLECTION, embedder.dimension() as i32)?;
+            match embedding_for(&vectors, embedder, &article).await {
+                Ok(target) => {
+                    for (doc, similarity) in vectors.search_similar(VECTOR_COLLECTION, &target, MAX_CANDIDATES as i32, MIN_VECTOR_SIMILARITY)? {
+                        let id = doc.metadata.get("article_id").and_then(|v| v.as_i64());
+                        if let Some(id) = id.filter(|id| *id != article_id) {
+                            signals.entry(id).or_default().vector = Some(similarity as f64);
+                        }
+                    }
+                    // Entity and event candidates not among the neighbours get embedded too
+                    let missing: Vec<i64> = signals.iter().filter(|(_, s)| s.vector.is_none()).map(|(id, _)| *id).collect();
+                    for id in missing {
+                        let Some(item) = osint.get_item(id)? else { continue };
+                        match embedding_for(&vectors, embedder, &item).await {
+                            Ok(embedding) => {
+                                if let Some(s) = signals.get_mut(&id) {
+                                    s.vector = Some(cosine_similarity(&target, &embedding) as f64);
+                                }
+                            }
+                            Err(e) => eprintln!("Failed to embed article {}: {}", id, e),
+                        }
+                        items.insert(id, item);
+                    }
+                }
+                Err(e) => eprintln!("Related articles for {} without embeddings: {}", article_id, e),
+            }
+        }
+
+        let mut scored: Vec<(i64, f64, Signals)> = signals
+            .into_iter()
+            .map(|(id, s)| (id, blend(&s, entity_count), s))
+            .filter(|(_, score, _)| *score > 0.0)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| b.0.cmp(&a.0)));
+
+        let mut related = Vec::new();
+        for (id, score, s) in scored {
+            if related.len() >= limit {
+                break;
+            }
+            let item = match items.remove(&id) {
+                Some(item) => item,
+                None => match osint.get_item(id)? {
+                    Some(item) => item,
+                    None => continue,
+                },
+            };
+            related.push(RelatedArticle {
+                id,
+                feed_id: item.feed_id,
+                title: item.title,
+                url: item.url,
+                published_at: item.published_at,
+                score,
+                vector_similarity: s.vector,
+                shared_entities: s.entities,
+                shared_events: s.events,
+            });
+        }
+        Ok(related)
+    }
+}
+
+/// Weighted mean of the available signals; the vector weight only counts
+/// when there is a similarity.
+fn blend(s: &Signals, entity_count: usize) -> f64 {
+    let entities = if entity_count == 0 { 0.0 } else { (s.entities.len() as f64 / entity_count as f64).min(1.0) };
+    let events = if s.events.is_empty() { 0.0 } else { 1.0 };
+    let (mut total, mut weights) = (entities * ENTITY_WEIGHT + events * EVENT_WEIGHT, ENTITY_WEIGHT + EVENT_WEIGHT);
+    if let Some(vector) = s.vector {
+        total += vector.clamp(0.0, 1.0) * VECTOR_WEIGHT;
+        weights += VECTOR_WEIGHT;
+    }
+    total / weights
+}
+
+/// The article's cached embedding, or a new one that is cached for next time.
+async fn embedding_for(vectors: &VectorStore, embedder: &EmbeddingService, item: &RSSItem) -> Result<Vec<f32>> {
+    let doc_id = format!("article:{}", item.id);
+    if let Some(doc) = vectors.get_document(&doc_id)? {
+        if doc.embedding.len() == embedder.dimension() {
+            return Ok(doc.embedding);
+        }
+    }
+
+    let text: String = format!("{}\n{}", item.title, item.content).chars().take(EMBED_CHARS).collect();
+    let embedding = embedder.generate(&text).await?;
+    vectors.insert_document(&VectorDocument {
+        id: doc_id,
+        collection: VECTOR_COLLECTION.to_string(),
+        content: item.title.clone(),
+        embedding: embedding.clone(),
+        metadata: serde_json::json!({ "article_id": item.id, "feed_id": item.feed_id }),
+        created_at: chrono::Utc::now().timestamp(),
+        expires_at: None,
+    })?;
+    Ok(embedding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[tokio::test]
+    async fn shared_events_and_entities_rank_without_embeddings() {
+        let db = test_support::test_db();
+        let osint = OSINTStore::new(db.conn.clone());
+        let target = test_support::article(&db, "Wire", "Acme bids for Beta", "Acme Corp offers to buy Beta Inc");
+        let both = test_support::article(&db, "Daily", "Beta weighs Acme offer", "Beta Inc board reviews the bid");
+        let entity_only = test_support::article(&db, "Ledger", "Acme earnings", "Acme Corp beats estimates");
+        let unrelated = test_support::article(&db, "Gazette", "Rain expected", "Storms move east");
+        for (id, names) in [(target, vec!["Acme Corp", "Beta Inc"]), (both, vec!["beta inc", "Acme Corp"]), (entity_only, vec!["Acme Corp"]), (unrelated, vec!["Weather Service"])] {
+            for name in names {
+                osint.save_extracted_entity(id, "ORG", name, 0.9, None).unwrap();
+            }
+        }
+        test_support::event(&db, &test_support::new_event("Acme bid"), &[target, both]);
+
+        let related = RelatedArticles::find(db.conn.clone(), None, target, 10).await.unwrap();
+        let ids: Vec<i64> = related.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![both, entity_only]);
+        assert_eq!(related[0].shared_entities.len(), 2);
+        assert_eq!(related[0].shared_events.len(), 1);
+        assert!(related[0].vector_similarity.is_none());
+        assert!((related[0].score - 1.0).abs() < 1e-9);
+        assert!((related[1].score - 0.5 * ENTITY_WEIGHT / (ENTITY_WEIGHT + EVENT_WEIGHT)).abs() < 1e-9);
+    }
+}
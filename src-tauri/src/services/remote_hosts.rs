@@ -0,0 +1,231 @@
+use crate::storage::devops::DevOpsStore;
+use crate::storage::remote_hosts::{NewRemoteHost, RemoteHost, RemoteHostAction, RemoteHostStore};
+use crate::storage::Database;
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::process::Command;
+
+const WOL_PORT: u16 = 9;
+const DEFAULT_BROADCAST: &str = "255.255.255.255";
+const SSH_TIMEOUT_SECS: u64 = 120;
+/// Command output kept in the action log.
+const MAX_OUTPUT_BYTES: usize = 64 * 1024;
+
+/// Parse "aa:bb:cc:dd:ee:ff" (or with dashes) into bytes.
+fn parse_mac(mac: &str) -> Result<[u8; 6]> {
+    let parts: Vec<&str> = mac.trim().split([':', '-']).collect();
+    if parts.len() != 6 {
+        anyhow::bail!("Invalid MAC address: {}", mac);
+    }
+    let mut bytes = [0u8; 6];
+    for (byte, part) in bytes.iter_mut().zip(parts) {
+        *byte = u8::from_str_radix(part, 16).map_err(|_| anyhow::anyhow!("Invalid MAC address: {}", mac))?;
+    }
+    Ok(bytes)
+}
+
+/// Six 0xFF bytes followed by the MAC repeated 16 times.
+fn magic_packet(mac: [u8; 6]) -> Vec<u8> {
+    let mut packet = vec![0xFF; 6];
+    for _ in 0..16 {
+        packet.extend_from_slice(&mac);
+    }
+    packet
+}
+
+fn truncate_output(mut output: String) -> String {
+    if output.len() > MAX_OUTPUT_BYTES {
+        let mut end = MAX_OUTPUT_BYTES;
+        while !output.is_char_boundary(end) {
+            end -= 1;
+        }
+        output.truncate(end);
+        output.push_str("\n[output truncated]");
+    }
+    output
+}
+
+/// The outcome of running an action, before it's logged.
+struct Outcome {
+    success: bool,
+    exit_code: Option<i32>,
+    output: String,
+}
+
+/// Wake, ping and run commands on home-lab machines. Every action is
+/// logged, and failures raise a devops alert for hosts that ask for it.
+pub struct RemoteHostService;
+
+impl RemoteHostService {
+    pub fn validate(host: &NewRemoteHost) -> Result<()> {
+        if host.name.trim().is_empty() {
+            anyhow::bail!("Host name cannot be empty");
+        }
+        if let Some(mac) = host.mac_address.as_deref().filter(|m| !m.is_empty()) {
+            parse_mac(mac)?;
+        }
+        // Leading dashes would be read as ssh/ping options
+        for value in [&host.address, &host.ssh_target, &host.broadcast_address].into_iter().flatten() {
+            if value.trim_start().starts_with('-') {
+                anyhow::bail!("Invalid host address: {}", value);
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn wake(db: &Arc<Mutex<Database>>, app: &AppHandle, host_id: i64) -> Result<RemoteHostAction> {
+        let host = Self::host(db, host_id)?;
+        let started = Instant::now();
+        let outcome = match Self::send_magic_packet(&host).await {
+            Ok(target) => Outcome { success: true, exit_code: None, output: format!("Magic packet sent to {}", target) },
+            Err(e) => Outcome { success: false, exit_code: None, output: e.to_string() },
+        };
+        Self::finish(db, app, &host, "wake", None, outcome, started).await
+    }
+
+    pub async fn ping(db: &Arc<Mutex<Database>>, app: &AppHandle, host_id: i64) -> Result<RemoteHostAction> {
+        let host = Self::host(db, host_id)?;
+        let address = host
+            .address
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Host {} has no address to ping", host.name))?;
+
+        let mut command = Command::new("ping");
+        if cfg!(windows) {
+            command.args(["-n", "1", "-w", "2000", &address]);
+        } else {
+            command.args(["-c", "1", "-W", "2", &address]);
+        }
+        let started = Instant::now();
+        let outcome = Self::run(command, Duration::from_secs(10)).await;
+        Self::finish(db, app, &host, "ping", None, outcome, started).await
+    }
+
+    /// Run a command over the system ssh client in batch mode, so hosts
+    /// need key-based auth and nothing ever prompts.
+    pub async fn ssh(db: &Arc<Mutex<Database>>, app: &AppHandle, host_id: i64, remote_command: &str) -> Result<RemoteHostAction> {
+        let host = Self::host(db, host_id)?;
+        let target = host
+            .ssh_target
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Host {} has no SSH target", host.name))?;
+        if remote_command.trim().is_empty() {
+            anyhow::bail!("Command cannot be empty");
+        }
+
+        let mut command = Command::new("ssh");
+        command.args(["-o", "BatchMode=yes", "-o", "ConnectTimeout=10", &target, "--", remote_command]);
+        let started = Instant::now();
+        let outcome = Self::run(command, Duration::from_secs(SSH_TIMEOUT_SECS)).await;
+        Self::finish(db, app, &host, "ssh", Some(remote_command), outcome, started).await
+    }
+
+    fn host(db: &Arc<Mutex<Database>>, host_id: i64) -> Result<RemoteHost> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        RemoteHostStore::new(db_guard.conn.clone())
+            .get_host(host_id)?
+            .ok_or_else(|| anyhow::anyhow!("Remote host {} not found", host_id))
+    }
+
+    async fn send_magic_packet(host: &RemoteHost) -> Result<String> {
+        let mac = host
+            .mac_address
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Host {} has no MAC address", host.name))?;
+        let packet = magic_packet(parse_mac(mac)?);
+        let broadcast = host.broadcast_address.as_deref().unwrap_or(DEFAULT_BROADCAST);
+        let target = format!("{}:{}", broadcast, WOL_PORT);
+
+        let socket = tokio::net::UdpSocket::bind("0.0.0.0:0").await?;
+        socket.set_broadcast(true)?;
+        socket.send_to(&packet, &target).await?;
+        Ok(target)
+    }
+
+    async fn run(mut command: Command, timeout: Duration) -> Outcome {
+        command.kill_on_drop(true);
+        match tokio::time::timeout(timeout, command.output()).await {
+            Ok(Ok(output)) => {
+                let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                if !stderr.trim().is_empty() {
+                    text.push_str(&stderr);
+                }
+                Outcome {
+                    success: output.status.success(),
+                    exit_code: output.status.code(),
+                    output: truncate_output(text),
+                }
+            }
+            Ok(Err(e)) => Outcome { success: false, exit_code: None, output: format!("Failed to run command: {}", e) },
+            Err(_) => Outcome { success: false, exit_code: None, output: format!("Timed out after {}s", timeout.as_secs()) },
+        }
+    }
+
+    async fn finish(
+        db: &Arc<Mutex<Database>>,
+        app: &AppHandle,
+        host: &RemoteHost,
+        kind: &str,
+        command: Option<&str>,
+        outcome: Outcome,
+        started: Instant,
+    ) -> Result<RemoteHostAction> {
+        let mut action = RemoteHostAction {
+            id: 0,
+            host_id: host.id,
+            action: kind.to_string(),
+            command: command.map(|c| c.to_string()),
+            success: outcome.success,
+            exit_code: outcome.exit_code,
+            output: outcome.output,
+            duration_ms: started.elapsed().as_millis() as i64,
+            created_at: chrono::Utc::now().timestamp(),
+        };
+        {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            action.id = RemoteHostStore::new(db_guard.conn.clone()).record_action(&action)?;
+            if !action.success && host.alert_on_failure {
+                let message = format!("{} on {} failed: {}", kind, host.name, action.output.lines().last().unwrap_or(""));
+                DevOpsStore::new(db_guard.conn.clone()).create_alert(
+                    &format!("Remote host {} {} failed", host.name, kind),
+                    "warning",
+                    &message,
+                    "remote_hosts",
+                )?;
+            }
+        }
+
+        let _ = crate::services::window_router::emit(app, serde_json::json!({
+            "type": "remote-host-action",
+            "data": {
+                "host": host.name,
+                "action": &action,
+            },
+            "timestamp": chrono::Utc::now().timestamp_millis(),
+        }));
+
+        Ok(action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn magic_packet_repeats_the_mac_after_a_sync_stream() {
+        let mac = parse_mac("AA-bb-cc-dd-ee-01").unwrap();
+        assert_eq!(mac, [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x01]);
+        assert!(parse_mac("aa:bb:cc").is_err());
+
+        let packet = magic_packet(mac);
+        assert_eq!(packet.len(), 102);
+        assert_eq!(&packet[..6], &[0xFF; 6]);
+        assert_eq!(&packet[96..], &mac);
+    }
+}
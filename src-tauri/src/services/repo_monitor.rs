@@ -0,0 +1,104 @@
+use crate::providers::git::GitProvider;
+use crate::storage::projects::ProjectStore;
+use crate::storage::Database;
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+
+/// Re-alert on the same repo at most once a day.
+const REALERT_AFTER_SECS: i64 = 86_400;
+
+pub struct RepoMonitor;
+
+impl RepoMonitor {
+    /// Periodically check linked project repos for commits that were never pushed
+    pub fn start_checking(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1800)); // Check every 30 minutes
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = Self::check_repos(&db, &app).await {
+                    eprintln!("Error checking project repositories: {}", e);
+                }
+            }
+        });
+    }
+
+    async fn check_repos(db: &Arc<Mutex<Database>>, app: &AppHandle) -> Result<()> {
+        let store = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            ProjectStore::new(db_guard.conn.clone())
+        };
+        let links = store.list_repo_links()?;
+        let git = GitProvider::new();
+        let now = chrono::Utc::now().timestamp();
+
+        for link in links {
+            if let Some(last) = link.last_alerted_at {
+                if now - last < REALERT_AFTER_SECS {
+                    continue;
+                }
+            }
+
+            let status = match git.get_status(&link.repo_path).await {
+                Ok(status) => status,
+                Err(e) => {
+                    eprintln!("Failed to get git status for {}: {}", link.repo_path, e);
+                    continue;
+                }
+            };
+
+            let oldest = match status.oldest_unpushed_at {
+                Some(ts) => ts,
+                None => continue,
+            };
+            let age_days = (now - oldest) / 86_400;
+            if age_days < link.unpushed_alert_days {
+                continue;
+            }
+
+            let project_name = store
+                .get_project(link.project_id)?
+                .map(|p| p.name)
+                .unwrap_or_else(|| format!("Project {}", link.project_id));
+
+            let payload = serde_json::json!({
+                "project_id": link.project_id,
+                "project_name": project_name,
+                "repo_path": link.repo_path,
+                "branch": status.branch,
+                "ahead": status.ahead,
+                "oldest_unpushed_at": oldest,
+                "age_days": age_days,
+            });
+
+            let _ = crate::services::window_router::emit(app, serde_json::json!({
+                "type": "project-repo-unpushed",
+                "data": payload,
+                "timestamp": chrono::Utc::now().timestamp_millis(),
+            }));
+
+            use crate::services::desktop_notifications::{DesktopNotificationService, NotificationOptions};
+            let _ = DesktopNotificationService::send(app, NotificationOptions {
+                title: format!("Unpushed commits: {}", project_name),
+                body: format!(
+                    "{} commit(s) on {} have not been pushed for {} day(s)",
+                    status.ahead.unwrap_or(0),
+                    status.branch,
+                    age_days
+                ),
+                icon: Some("alert".to_string()),
+                sound: None,
+                tag: Some(format!("project-repo-{}", link.project_id)),
+                data: Some(payload),
+            }).await;
+
+            store.mark_repo_alerted(link.project_id)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,445 @@
+use crate::services::data_export::DataExportService;
+use crate::storage::saved_reports::{NewSavedReport, ReportFilter, ReportSpec, SavedReport, SavedReportStore};
+use crate::storage::sql::quote_ident;
+use crate::storage::Database;
+use anyhow::Result;
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_LIMIT: i64 = 1000;
+const MAX_LIMIT: i64 = 10_000;
+const MAX_IN_VALUES: usize = 100;
+const CHECK_INTERVAL_SECS: u64 = 300;
+/// Credentials, sessions and clipboard contents are never reportable
+const HIDDEN_TABLES: &[&str] = &["api_keys", "auth_attempts", "auth_sessions", "config", "clipboard_entries", "permissions"];
+
+/// A table reports can read, for building specs in the UI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportTable {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportResult {
+    pub columns: Vec<String>,
+    /// One object per row, keyed by column
+    pub rows: Vec<Value>,
+    /// More rows matched than the limit allowed
+    pub truncated: bool,
+}
+
+/// SQL built from a validated spec: every identifier is a known column or
+/// a checked alias, every value a bound parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuiltQuery {
+    pub sql: String,
+    pub params: Vec<SqlValue>,
+    pub columns: Vec<String>,
+    pub limit: i64,
+}
+
+pub struct ReportBuilder;
+
+impl ReportBuilder {
+    /// Tables reports may read, with their columns.
+    pub fn tables(conn: &Connection) -> Result<Vec<ReportTable>> {
+        let mut stmt = conn.prepare(
+            "SELECT name, COALESCE(sql, '') LIKE 'CREATE VIRTUAL%' FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )?;
+        let entries = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        // Full-text indexes and their shadow tables aren't useful to report on
+        let virtual_tables: Vec<&String> = entries.iter().filter(|(_, v)| *v).map(|(name, _)| name).collect();
+
+        let mut tables = Vec::new();
+        for (name, is_virtual) in &entries {
+            if *is_virtual
+                || HIDDEN_TABLES.contains(&name.as_str())
+                || virtual_tables.iter().any(|v| name.starts_with(&format!("{}_", v)))
+            {
+                continue;
+            }
+            tables.push(ReportTable { name: name.clone(), columns: table_columns(conn, name)? });
+        }
+        Ok(tables)
+    }
+
+    /// Turn a spec into SQL over a table with `columns`.
+    pub fn build(spec: &ReportSpec, columns: &[String], now: i64) -> Result<BuiltQuery> {
+        let known: HashSet<&str> = columns.iter().map(|c| c.as_str()).collect();
+        let column = |name: &str| -> Result<String> {
+            if known.contains(name) {
+                Ok(quote_ident(name))
+            } else {
+                anyhow::bail!("Unknown column {} in {}", name, spec.table)
+            }
+        };
+
+        let mut select = Vec::new();
+        let mut output = Vec::new();
+        if spec.aggregations.is_empty() {
+            let picked: Vec<String> = if !spec.group_by.is_empty() {
+                spec.group_by.clone()
+            } else if !spec.columns.is_empty() {
+                spec.columns.clone()
+            } else {
+                columns.to_vec()
+            };
+            for name in picked {
+                select.push(column(&name)?);
+                output.push(name);
+            }
+        } else {
+            if !spec.columns.is_empty() {
+                anyhow::bail!("Use group_by, not columns, alongside aggregations");
+            }
+            for name in &spec.group_by {
+                select.push(column(name)?);
+                output.push(name.clone());
+            }
+            for agg in &spec.aggregations {
+                let func = agg.func.to_lowercase();
+                let expr = match (func.as_str(), agg.column.as_deref()) {
+                    ("count", None) => "COUNT(*)".to_string(),
+                    ("count", Some(c)) => format!("COUNT({})", column(c)?),
+                    ("count_distinct", Some(c)) => format!("COUNT(DISTINCT {})", column(c)?),
+                    ("sum" | "avg" | "min" | "max", Some(c)) => format!("{}({})", func.to_uppercase(), column(c)?),
+                    ("count_distinct" | "sum" | "avg" | "min" | "max", None) => anyhow::bail!("{} needs a column", func),
+                    _ => anyhow::bail!("Unknown aggregation: {}", agg.func),
+                };
+                let alias = agg
+                    .alias
+                    .clone()
+                    .unwrap_or_else(|| agg.column.as_ref().map(|c| format!("{}_{}", func, c)).unwrap_or_else(|| func.clone()));
+                if !is_identifier(&alias) {
+                    anyhow::bail!("Invalid alias: {}", alias);
+                }
+                if output.contains(&alias) {
+                    anyhow::bail!("Duplicate result column: {}", alias);
+                }
+                select.push(format!("{} AS {}", expr, quote_ident(&alias)));
+                output.push(alias);
+            }
+        }
+        if select.is_empty() {
+            anyhow::bail!("Report selects no columns");
+        }
+
+        let mut params = Vec::new();
+        let mut conditions = Vec::new();
+        for filter in &spec.filters {
+            conditions.push(filter_sql(filter, &column(&filter.column)?, &mut params, now)?);
+        }
+
+        // sql-audit: select expressions are validated, quoted columns
+        let mut sql = format!("SELECT {} FROM {}", select.join(", "), quote_ident(&spec.table));
+        if !conditions.is_empty() {
+            sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        if !spec.group_by.is_empty() {
+            let group: Vec<String> = spec.group_by.iter().map(|c| column(c)).collect::<Result<_>>()?;
+            sql.push_str(&format!(" GROUP BY {}", group.join(", ")));
+        }
+        if !spec.order_by.is_empty() {
+            let mut order = Vec::new();
+            for o in &spec.order_by {
+                let target = if output.contains(&o.column) { quote_ident(&o.column) } else { column(&o.column)? };
+                order.push(format!("{} {}", target, if o.desc { "DESC" } else { "ASC" }));
+            }
+            sql.push_str(&format!(" ORDER BY {}", order.join(", ")));
+        }
+        let limit = spec.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        // One extra row tells whether the result was cut off
+        sql.push_str(&format!(" LIMIT {}", limit + 1));
+
+        Ok(BuiltQuery { sql, params, columns: output, limit })
+    }
+
+    pub fn run(conn: &Connection, spec: &ReportSpec) -> Result<ReportResult> {
+        let columns = Self::tables(conn)?
+            .into_iter()
+            .find(|t| t.name == spec.table)
+            .map(|t| t.columns)
+            .ok_or_else(|| anyhow::anyhow!("Table {} can't be reported on", spec.table))?;
+        let query = Self::build(spec, &columns, chrono::Utc::now().timestamp())?;
+
+        let mut stmt = conn.prepare(&query.sql)?;
+        if !stmt.readonly() {
+            anyhow::bail!("Report query is not read-only");
+        }
+        let mut rows = stmt.query(rusqlite::params_from_iter(query.params.iter()))?;
+        let mut out = Vec::new();
+        while let Some(row) = rows.next()? {
+            let mut object = serde_json::Map::new();
+            for (i, name) in query.columns.iter().enumerate() {
+                object.insert(name.clone(), to_json(row.get_ref(i)?));
+            }
+            out.push(Value::Object(object));
+        }
+
+        let truncated = out.len() as i64 > query.limit;
+        out.truncate(query.limit as usize);
+        Ok(ReportResult { columns: query.columns, rows: out, truncated })
+    }
+
+    pub fn export(result: &ReportResult, format: &str) -> Result<String> {
+        match format {
+            "csv" => {
+                let headers: Vec<&str> = result.columns.iter().map(|c| c.as_str()).collect();
+                Ok(DataExportService::export_to_csv(&result.rows, &headers))
+            }
+            "json" => Ok(serde_json::to_string_pretty(&result.rows)?),
+            _ => anyhow::bail!("Unknown export format: {} (use csv or json)", format),
+        }
+    }
+
+    /// Check a report before saving: the spec must build against its
+    /// table, and scheduled reports need a folder to write to.
+    pub fn validate(conn: &Connection, report: &NewSavedReport) -> Result<()> {
+        if report.name.trim().is_empty() {
+            anyhow::bail!("Report name is required");
+        }
+        let format = report.export_format.as_deref().unwrap_or("csv");
+        if format != "csv" && format != "json" {
+            anyhow::bail!("Unknown export format: {} (use csv or json)", format);
+        }
+        if let Some(hours) = report.schedule_hours {
+            if hours < 1 {
+                anyhow::bail!("Schedule must be at least 1 hour");
+            }
+            if report.export_dir.as_deref().map(str::trim).unwrap_or("").is_empty() {
+                anyhow::bail!("Scheduled reports need an export folder");
+            }
+        }
+        let columns = Self::tables(conn)?
+            .into_iter()
+            .find(|t| t.name == report.spec.table)
+            .map(|t| t.columns)
+            .ok_or_else(|| anyhow::anyhow!("Table {} can't be reported on", report.spec.table))?;
+        Self::build(&report.spec, &columns, 0).map(|_| ())
+    }
+
+    /// Run a saved report and write it to its export folder. Returns the file.
+    pub fn run_to_file(db: &Database, report: &SavedReport) -> Result<PathBuf> {
+        let dir = report
+            .export_dir
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Report {} has no export folder", report.name))?;
+        let result = {
+            let conn = db.conn.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            Self::run(&conn, &report.spec)?
+        };
+        let contents = Self::export(&result, &report.export_format)?;
+
+        std::fs::create_dir_all(dir)?;
+        let file_name = format!(
+            "{}-{}.{}",
+            slug(&report.name),
+            chrono::Utc::now().format("%Y%m%d-%H%M"),
+            report.export_format
+        );
+        let path = Path::new(dir).join(file_name);
+        std::fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    /// Export scheduled reports whose interval has passed.
+    pub fn start_scheduler(db: Arc<Mutex<Database>>) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+            let heartbeat = crate::services::watchdog::Watchdog::register("report_scheduler", tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+
+            loop {
+                interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
+                let db_guard = match db.lock() {
+                    Ok(guard) => guard,
+                    Err(_) => continue,
+                };
+                let store = SavedReportStore::new(db_guard.conn.clone());
+                let now = chrono::Utc::now().timestamp();
+                let due = match store.list_due_reports(now) {
+                    Ok(due) => due,
+                    Err(e) => {
+                        eprintln!("Failed to list scheduled reports: {}", e);
+                        continue;
+                    }
+                };
+                for report in due {
+                    let (output, error) = match Self::run_to_file(&db_guard, &report) {
+                        Ok(path) => (Some(path.display().to_string()), None),
+                        Err(e) => {
+                            eprintln!("Scheduled report {} failed: {}", report.name, e);
+                            (None, Some(e.to_string()))
+                        }
+                    };
+                    if let Err(e) = store.record_run(report.id, now, output.as_deref(), error.as_deref()) {
+                        eprintln!("Failed to record report run: {}", e);
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn filter_sql(filter: &ReportFilter, column: &str, params: &mut Vec<SqlValue>, now: i64) -> Result<String> {
+    let op = filter.op.to_lowercase();
+    let comparison = match op.as_str() {
+        "eq" => Some("="),
+        "ne" => Some("!="),
+        "gt" => Some(">"),
+        "gte" => Some(">="),
+        "lt" => Some("<"),
+        "lte" => Some("<="),
+        _ => None,
+    };
+    if let Some(comparison) = comparison {
+        params.push(to_sql(&filter.value)?);
+        return Ok(format!("{} {} ?", column, comparison));
+    }
+
+    match op.as_str() {
+        "contains" => {
+            let needle = filter.value.as_str().ok_or_else(|| anyhow::anyhow!("contains needs a string"))?;
+            let escaped = needle.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            params.push(SqlValue::Text(format!("%{}%", escaped)));
+            Ok(format!("{} LIKE ? ESCAPE '\\'", column))
+        }
+        "in" => {
+            let values = filter.value.as_array().ok_or_else(|| anyhow::anyhow!("in needs an array"))?;
+            if values.is_empty() || values.len() > MAX_IN_VALUES {
+                anyhow::bail!("in needs 1 to {} values", MAX_IN_VALUES);
+            }
+            for value in values {
+                params.push(to_sql(value)?);
+            }
+            Ok(format!("{} IN ({})", column, vec!["?"; values.len()].join(", ")))
+        }
+        "is_null" => Ok(format!("{} IS NULL", column)),
+        "not_null" => Ok(format!("{} IS NOT NULL", column)),
+        // Unix-second columns within the last N days, so scheduled reports roll forward
+        "within_days" => {
+            let days = filter.value.as_f64().ok_or_else(|| anyhow::anyhow!("within_days needs a number"))?;
+            params.push(SqlValue::Integer(now - (days * 86400.0) as i64));
+            Ok(format!("{} >= ?", column))
+        }
+        _ => anyhow::bail!("Unknown filter operator: {}", filter.op),
+    }
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", quote_ident(table)))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(columns)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name.len() <= 64
+}
+
+fn to_sql(value: &Value) -> Result<SqlValue> {
+    match value {
+        Value::String(s) => Ok(SqlValue::Text(s.clone())),
+        Value::Bool(b) => Ok(SqlValue::Integer(*b as i64)),
+        Value::Number(n) => Ok(n.as_i64().map(SqlValue::Integer).unwrap_or_else(|| SqlValue::Real(n.as_f64().unwrap_or(0.0)))),
+        _ => anyhow::bail!("Filter values must be strings, numbers or booleans"),
+    }
+}
+
+fn to_json(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => Value::from(f),
+        ValueRef::Text(t) => Value::String(String::from_utf8_lossy(t).into_owned()),
+        ValueRef::Blob(b) => Value::String(format!("<{} bytes>", b.len())),
+    }
+}
+
+fn slug(name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-");
+    if slug.is_empty() { "report".to_string() } else { slug }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::saved_reports::{ReportAggregation, ReportOrder};
+    use crate::test_support;
+    use serde_json::json;
+
+    #[test]
+    fn specs_build_bound_queries_and_reject_unknown_names() {
+        let columns: Vec<String> = ["id", "name", "category", "created_at"].iter().map(|c| c.to_string()).collect();
+        let spec = ReportSpec {
+            table: "rss_feeds".to_string(),
+            columns: Vec::new(),
+            filters: vec![
+                ReportFilter { column: "name".to_string(), op: "contains".to_string(), value: json!("50%") },
+                ReportFilter { column: "created_at".to_string(), op: "within_days".to_string(), value: json!(7) },
+            ],
+            group_by: vec!["category".to_string()],
+            aggregations: vec![ReportAggregation { func: "count".to_string(), column: None, alias: None }],
+            order_by: vec![ReportOrder { column: "count".to_string(), desc: true }],
+            limit: Some(50),
+        };
+        let query = ReportBuilder::build(&spec, &columns, 1_000_000).unwrap();
+        assert_eq!(
+            query.sql,
+            "SELECT \"category\", COUNT(*) AS \"count\" FROM \"rss_feeds\" WHERE \"name\" LIKE ? ESCAPE '\\' AND \"created_at\" >= ? GROUP BY \"category\" ORDER BY \"count\" DESC LIMIT 51"
+        );
+        assert_eq!(query.params, vec![SqlValue::Text("%50\\%%".to_string()), SqlValue::Integer(395_200)]);
+        assert_eq!(query.columns, vec!["category", "count"]);
+
+        let mut injected = spec.clone();
+        injected.group_by = vec!["category\"; DROP TABLE rss_feeds; --".to_string()];
+        assert!(ReportBuilder::build(&injected, &columns, 0).is_err());
+        let mut bad_alias = spec.clone();
+        bad_alias.aggregations[0].alias = Some("n) FROM x; --".to_string());
+        assert!(ReportBuilder::build(&bad_alias, &columns, 0).is_err());
+    }
+
+    #[test]
+    fn reports_run_over_visible_tables_only() {
+        let db = test_support::test_db();
+        test_support::feed(&db, "Reuters");
+        test_support::feed(&db, "Bloomberg");
+        let conn = db.conn.lock().unwrap();
+
+        let spec: ReportSpec = serde_json::from_value(json!({
+            "table": "rss_feeds",
+            "columns": ["name"],
+            "filters": [{ "column": "name", "op": "in", "value": ["Reuters", "AP"] }],
+            "limit": 10
+        }))
+        .unwrap();
+        let result = ReportBuilder::run(&conn, &spec).unwrap();
+        assert_eq!(result.rows, vec![json!({ "name": "Reuters" })]);
+        assert!(!result.truncated);
+        assert_eq!(ReportBuilder::export(&result, "csv").unwrap(), "name\n\"Reuters\"\n");
+
+        let hidden: ReportSpec = serde_json::from_value(json!({ "table": "config" })).unwrap();
+        assert!(ReportBuilder::run(&conn, &hidden).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::storage::response_cache::{CachedResponse, ResponseCacheStore};
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::AppHandle;
+
+/// Past its TTL, a response is served instantly while it refreshes for up to
+/// this many TTLs. Older ones are refetched first and only served if that fails.
+const STALE_SERVE_FACTOR: i64 = 24;
+
+/// Keys with a background refresh running, so each is refreshed once at a time
+static REFRESHING: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+fn refreshing() -> &'static Mutex<HashSet<String>> {
+    REFRESHING.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// A value served from the cache or fetched just now.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cached<T> {
+    pub value: T,
+    pub provider: Option<String>,
+    pub fetched_at: i64,
+    /// Older than its TTL; a refresh is underway or the provider failed
+    pub stale: bool,
+}
+
+/// Persistent cache of provider responses with stale-while-revalidate:
+/// stale values are returned at once and refreshed in the background, with a
+/// `provider-cache-updated` message once fresh data lands.
+#[derive(Clone)]
+pub struct ResponseCache {
+    conn: Arc<Mutex<Connection>>,
+    app: Option<AppHandle>,
+}
+
+impl ResponseCache {
+    /// Without an app handle refreshes still happen, just unannounced.
+    pub fn new(conn: Arc<Mutex<Connection>>, app: Option<AppHandle>) -> Self {
+        ResponseCache { conn, app }
+    }
+
+    /// The cached response for `key` if younger than `ttl_secs`; otherwise
+    /// whatever `fetch` returns. `fetch` yields the value and the provider
+    /// that served it.
+    pub async fn get_or_fetch<T, F, Fut>(&self, key: &str, kind: &str, ttl_secs: i64, fetch: F) -> Result<Cached<T>>
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<(T, Option<String>)>> + Send + 'static,
+    {
+        let store = ResponseCacheStore::new(self.conn.clone());
+        let now = chrono::Utc::now().timestamp();
+        let cached = match store.get(key) {
+            Ok(cached) => cached.and_then(|c| decode::<T>(&c).map(|value| (c, value))),
+            Err(e) => {
+                eprintln!("Failed to read cached response {}: {}", key, e);
+                None
+            }
+        };
+
+        let ttl_secs = ttl_secs.max(1);
+        if let Some((response, value)) = cached {
+            let age = now - response.fetched_at;
+            let cached = Cached { value, provider: response.provider, fetched_at: response.fetched_at, stale: age >= ttl_secs };
+            if !cached.stale {
+                return Ok(cached);
+            }
+            if age < ttl_secs * STALE_SERVE_FACTOR {
+                self.refresh_in_background(key, kind, fetch());
+                return Ok(cached);
+            }
+            // Too old to show without trying for fresh data first
+            return match fetch().await {
+                Ok((value, provider)) => Ok(Self::store(&store, key, kind, value, provider)),
+                Err(e) => {
+                    eprintln!("Refreshing {} failed, serving the cached response: {}", key, e);
+                    Ok(cached)
+                }
+            };
+        }
+
+        let (value, provider) = fetch().await?;
+        Ok(Self::store(&store, key, kind, value, provider))
+    }
+
+    fn store<T: Serialize>(store: &ResponseCacheStore, key: &str, kind: &str, value: T, provider: Option<String>) -> Cached<T> {
+        let fetched_at = chrono::Utc::now().timestamp();
+        let saved = serde_json::to_string(&value).map_err(anyhow::Error::from).and_then(|payload| {
+            store.put(&CachedResponse {
+                cache_key: key.to_string(),
+                kind: kind.to_string(),
+                provider: provider.clone(),
+                payload,
+                fetched_at,
+            })
+        });
+        if let Err(e) = saved {
+            eprintln!("Failed to cache response {}: {}", key, e);
+        }
+        Cached { value, provider, fetched_at, stale: false }
+    }
+
+    fn refresh_in_background<T, Fut>(&self, key: &str, kind: &str, fetch: Fut)
+    where
+        T: Serialize + Send + 'static,
+        Fut: Future<Output = Result<(T, Option<String>)>> + Send + 'static,
+    {
+        let Ok(mut running) = refreshing().lock() else {
+            return;
+        };
+        if !running.insert(key.to_string()) {
+            return;
+        }
+        drop(running);
+
+        let (key, kind) = (key.to_string(), kind.to_string());
+        let (conn, app) = (self.conn.clone(), self.app.clone());
+        tauri::async_runtime::spawn(async move {
+            match fetch.await {
+                Ok((value, provider)) => {
+                    let fresh = Self::store(&ResponseCacheStore::new(conn), &key, &kind, value, provider);
+                    if let Some(app) = app {
+                        let _ = crate::services::window_router::emit(&app, serde_json::json!({
+                            "type": "provider-cache-updated",
+                            "data": { "key": key, "kind": kind, "provider": fresh.provider, "fetched_at": fresh.fetched_at, "value": fresh.value },
+                            "timestamp": chrono::Utc::now().timestamp_millis(),
+                        }));
+                    }
+                }
+                Err(e) => eprintln!("Background refresh of {} failed: {}", key, e),
+            }
+            if let Ok(mut running) = refreshing().lock() {
+                running.remove(&key);
+            }
+        });
+    }
+}
+
+/// A payload that no longer matches its type counts as a miss.
+fn decode<T: DeserializeOwned>(response: &CachedResponse) -> Option<T> {
+    serde_json::from_str(&response.payload).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn put(db: &crate::storage::Database, key: &str, payload: &str, fetched_at: i64) {
+        ResponseCacheStore::new(db.conn.clone())
+            .put(&CachedResponse {
+                cache_key: key.to_string(),
+                kind: "quote".to_string(),
+                provider: Some("Old".to_string()),
+                payload: payload.to_string(),
+                fetched_at,
+            })
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn stale_responses_are_served_then_refreshed() {
+        let db = test_support::test_db();
+        let cache = ResponseCache::new(db.conn.clone(), None);
+        let now = chrono::Utc::now().timestamp();
+        let failing = || async { Err::<(f64, Option<String>), _>(anyhow::anyhow!("provider down")) };
+
+        // Missing: fetched and stored
+        let fetched = cache.get_or_fetch("quote:A", "quote", 60, || async { Ok::<_, anyhow::Error>((1.0, Some("P".to_string()))) }).await.unwrap();
+        assert!(!fetched.stale);
+        // Fresh: the provider isn't asked
+        assert_eq!(cache.get_or_fetch("quote:A", "quote", 60, failing).await.unwrap().value, 1.0);
+
+        // Stale: the old value now, the new one once the refresh lands
+        put(&db, "quote:B", "2.0", now - 120);
+        let served = cache.get_or_fetch("quote:B", "quote", 60, || async { Ok::<_, anyhow::Error>((3.0, None)) }).await.unwrap();
+        assert!(served.stale);
+        assert_eq!(served.value, 2.0);
+        let store = ResponseCacheStore::new(db.conn.clone());
+        for _ in 0..100 {
+            if store.get("quote:B").unwrap().unwrap().payload == "3.0" {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(store.get("quote:B").unwrap().unwrap().payload, "3.0");
+
+        // Too old to serve first, but better than nothing when the provider fails
+        put(&db, "quote:C", "4.0", now - 60 * STALE_SERVE_FACTOR - 1);
+        let fallback = cache.get_or_fetch("quote:C", "quote", 60, failing).await.unwrap();
+        assert!(fallback.stale);
+        assert_eq!(fallback.value, 4.0);
+        assert!(cache.get_or_fetch("quote:D", "quote", 60, failing).await.is_err());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::services::alert_rule_engine::{AlertRuleEngine, RuleTrace};
+use crate::storage::temporal::{AlertRule, TemporalEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// A hand-made event for testing rules. Sector and industry conditions see
+/// entities written as "sector:<name>" / "industry:<name>", the form the
+/// live pipeline derives from stored fundamentals; market signal conditions
+/// see "rating:downgrade:<ticker>" and "short_interest_change_pct:<ticker>:<pct>".
+/// `languages` become "language:<code>" entities for language conditions.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyntheticEvent {
+    pub title: String,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub entities: Vec<String>,
+    #[serde(default)]
+    pub sources: Vec<String>,
+    #[serde(default)]
+    pub languages: Vec<String>,
+    #[serde(default = "default_event_type")]
+    pub event_type: String,
+    #[serde(default)]
+    pub start_ts: Option<i64>,
+    #[serde(default)]
+    pub sentiment_score: f64,
+    #[serde(default)]
+    pub novelty_score: f64,
+    #[serde(default)]
+    pub volume_score: f64,
+    #[serde(default)]
+    pub severity: f64,
+    #[serde(default = "default_confidence")]
+    pub confidence: f64,
+    #[serde(default = "default_lifecycle_state")]
+    pub lifecycle_state: String,
+    #[serde(default)]
+    pub previous_lifecycle_state: Option<String>,
+    #[serde(default)]
+    pub lifecycle_changed_at: Option<i64>,
+    #[serde(default)]
+    pub corroborated_by: i64,
+}
+
+fn default_event_type() -> String {
+    "news".to_string()
+}
+
+fn default_confidence() -> f64 {
+    0.5
+}
+
+fn default_lifecycle_state() -> String {
+    "emerging".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleTestResult {
+    pub rule_id: Option<i64>,
+    pub rule_name: String,
+    pub enabled: bool,
+    pub trace: RuleTrace,
+}
+
+pub struct RuleSandbox;
+
+impl RuleSandbox {
+    /// Run the synthetic event through each rule and trace every condition.
+    pub fn run(rules: &[AlertRule], synthetic: &SyntheticEvent) -> Vec<RuleTestResult> {
+        let event = to_event(synthetic);
+        let haystack = format!("{} {}", event.title.to_lowercase(), event.summary.to_lowercase());
+        let entities: HashSet<String> = synthetic
+            .entities
+            .iter()
+            .map(|e| e.trim().to_lowercase())
+            .chain(synthetic.languages.iter().map(|l| format!("language:{}", l.trim().to_lowercase())))
+            .collect();
+        let sources: HashSet<String> = synthetic.sources.iter().map(|s| s.trim().to_lowercase()).collect();
+
+        rules
+            .iter()
+            .map(|rule| RuleTestResult {
+                rule_id: (rule.id > 0).then_some(rule.id),
+                rule_name: rule.name.clone(),
+                enabled: rule.enabled,
+                trace: AlertRuleEngine::explain(&rule.rule_json, &haystack, &entities, &sources, &event),
+            })
+            .collect()
+    }
+
+    /// Wrap an unsaved rule definition so it can be tested like a stored one.
+    pub fn draft_rule(rule_json: Value) -> AlertRule {
+        AlertRule {
+            id: 0,
+            name: "Draft rule".to_string(),
+            enabled: true,
+            watchlist_id: None,
+            rule_json,
+            schedule: None,
+            escalation_config: None,
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+fn to_event(synthetic: &SyntheticEvent) -> TemporalEvent {
+    let now = chrono::Utc::now().timestamp();
+    let start_ts = synthetic.start_ts.unwrap_or(now);
+    TemporalEvent {
+        id: 0,
+        title: synthetic.title.clone(),
+        summary: synthetic.summary.clone(),
+        start_ts,
+        end_ts: start_ts,
+        event_type: synthetic.event_type.clone(),
+        confidence: synthetic.confidence,
+        severity: synthetic.severity,
+        novelty_score: synthetic.novelty_score,
+        volume_score: synthetic.volume_score,
+        sentiment_score: synthetic.sentiment_score,
+        cluster_key: "sandbox".to_string(),
+        created_at: now,
+        updated_at: now,
+        lifecycle_state: synthetic.lifecycle_state.clone(),
+        previous_lifecycle_state: synthetic.previous_lifecycle_state.clone(),
+        lifecycle_changed_at: synthetic.lifecycle_changed_at,
+        source_count: synthetic.sources.len() as i64,
+        corroborated_by: synthetic.corroborated_by,
+        reliability_tier_count: 0,
+        time_to_second_source: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_shows_which_condition_failed() {
+        let rule = RuleSandbox::draft_rule(serde_json::json!({
+            "logic": {
+                "operator": "AND",
+                "conditions": [
+                    { "type": "contains_keyword", "keyword": "merger" },
+                    { "type": "severity", "operator": ">=", "value": 0.8 }
+                ]
+            }
+        }));
+        let synthetic: SyntheticEvent = serde_json::from_value(serde_json::json!({
+            "title": "Merger talks confirmed",
+            "severity": 0.5
+        }))
+        .unwrap();
+
+        let results = RuleSandbox::run(&[rule], &synthetic);
+        let trace = &results[0].trace;
+        assert!(!trace.matched);
+        let group = &trace.conditions[0];
+        assert!(group.children[0].matched);
+        assert_eq!(group.children[0].matched_values, vec!["merger".to_string()]);
+        assert!(!group.children[1].matched);
+        assert_eq!(group.children[1].observed, Some(0.5));
+    }
+}
@@ -121,6 +121,23 @@ impl ScriptBridgeServer {
                 response_json
             );
             
+            stream.write_all(http_response.as_bytes()).await?;
+        } else if method == "GET" && (path == "/calendar.ics" || path.starts_with("/calendar.ics?")) {
+            let http_response = match Self::calendar_feed(&app, path).await {
+                Ok(ics) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/calendar; charset=utf-8\r\nContent-Length: {}\r\n\r\n{}",
+                    ics.len(),
+                    ics
+                ),
+                Err(e) => {
+                    let message = e.to_string();
+                    format!(
+                        "HTTP/1.1 403 Forbidden\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{}",
+                        message.len(),
+                        message
+                    )
+                }
+            };
             stream.write_all(http_response.as_bytes()).await?;
         } else {
             // 404 for other paths
@@ -131,6 +148,52 @@ impl ScriptBridgeServer {
         Ok(())
     }
 
+    /// Serve the ICS calendar when the feed is enabled and the request carries
+    /// its token. Query: `token`, optional `days` and comma-separated `kinds`.
+    async fn calendar_feed(app: &AppHandle, path: &str) -> Result<String> {
+        use crate::services::api_key_manager::APIKeyManager;
+        use crate::services::calendar_ics::{CalendarIcs, CalendarRange};
+        use crate::storage::Database;
+        use std::sync::{Arc, Mutex};
+        use tauri::Manager;
+
+        let query: HashMap<String, String> = path
+            .split_once('?')
+            .map(|(_, q)| q)
+            .unwrap_or("")
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(k, v)| (k.to_string(), urlencoding::decode(v).map(|v| v.into_owned()).unwrap_or_default()))
+            .collect();
+
+        let db = app.try_state::<Mutex<Database>>()
+            .ok_or_else(|| anyhow::anyhow!("Database not ready"))?;
+        let token = {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            CalendarIcs::feed_token(&db_guard)
+        };
+        match token {
+            Some(token) if query.get("token") == Some(&token) => {}
+            Some(_) => anyhow::bail!("Invalid calendar feed token"),
+            None => anyhow::bail!("Calendar feed is disabled"),
+        }
+
+        let kinds: Option<Vec<String>> = query
+            .get("kinds")
+            .map(|k| k.split(',').map(|s| s.to_string()).collect());
+        let kinds = CalendarIcs::parse_kinds(kinds.as_deref())?;
+        let range = CalendarRange {
+            from_ts: None,
+            to_ts: query
+                .get("days")
+                .and_then(|d| d.parse::<i64>().ok())
+                .map(|d| chrono::Utc::now().timestamp() + d.clamp(1, 365) * 86400),
+        };
+        let api_key_manager = app.try_state::<Arc<APIKeyManager>>();
+
+        CalendarIcs::build(&db, api_key_manager.as_deref().map(|m| m.as_ref()), &range, &kinds).await
+    }
+
     pub fn get_port(&self) -> u16 {
         self.port
     }
@@ -153,3 +153,35 @@ impl SentimentAnalyzer {
         analyzer.analyze(text)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{assert_golden, round4};
+    use serde_json::json;
+
+    #[test]
+    fn scores_match_golden() {
+        let analyzer = SentimentAnalyzer::new();
+        let texts = [
+            "Acme posts strong growth and record profit",
+            "Shares plunge after very weak guidance",
+            "Results did not beat expectations",
+            "Markets crash as recession fears grow",
+            "The meeting is scheduled for Tuesday",
+        ];
+        let scores: Vec<serde_json::Value> = texts
+            .iter()
+            .map(|text| json!({ "text": text, "score": round4(analyzer.analyze(text)) }))
+            .collect();
+        let series = [0.2, 0.4, 0.6];
+        assert_golden(
+            "sentiment",
+            &json!({
+                "scores": scores,
+                "aggregate": round4(SentimentAnalyzer::aggregate_sentiment(&series)),
+                "trend": round4(SentimentAnalyzer::sentiment_trend(&series)),
+            }),
+        );
+    }
+}
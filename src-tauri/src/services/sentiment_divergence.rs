@@ -0,0 +1,239 @@
+use crate::services::sentiment_analyzer::SentimentAnalyzer;
+use crate::storage::osint::{EntitySourceArticle, OSINTStore};
+use crate::storage::temporal::{NewTemporalEvent, TemporalStore};
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const CONFIG_WINDOW_HOURS: &str = "sentiment_divergence_window_hours";
+/// Gap between the most positive and most negative source's mean sentiment.
+/// Scores span [-1, 1] but a single article rarely goes beyond ±0.3.
+pub const CONFIG_MIN_SPREAD: &str = "sentiment_divergence_min_spread";
+/// Sources with fewer articles about the entity in the window are left out.
+pub const CONFIG_MIN_ARTICLES: &str = "sentiment_divergence_min_articles";
+
+pub const EVENT_TYPE: &str = "sentiment_divergence";
+const HOUR: i64 = 3600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DivergenceSettings {
+    pub window_hours: i64,
+    pub min_spread: f64,
+    pub min_articles: usize,
+}
+
+impl Default for DivergenceSettings {
+    fn default() -> Self {
+        DivergenceSettings {
+            window_hours: 48,
+            min_spread: 0.25,
+            min_articles: 2,
+        }
+    }
+}
+
+impl DivergenceSettings {
+    pub fn load(db: &Database) -> Self {
+        let defaults = Self::default();
+        let get = |key: &str| db.get_config(key).ok().flatten();
+        DivergenceSettings {
+            window_hours: get(CONFIG_WINDOW_HOURS).and_then(|v| v.parse().ok()).unwrap_or(defaults.window_hours).max(1),
+            min_spread: get(CONFIG_MIN_SPREAD).and_then(|v| v.parse().ok()).unwrap_or(defaults.min_spread),
+            min_articles: get(CONFIG_MIN_ARTICLES).and_then(|v| v.parse().ok()).unwrap_or(defaults.min_articles).max(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceSentiment {
+    pub feed_id: i64,
+    pub source: String,
+    pub articles: usize,
+    pub mean_sentiment: f64,
+}
+
+/// How sources covering one entity differ in tone over a window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityDivergence {
+    pub entity: String,
+    pub from_ts: i64,
+    pub to_ts: i64,
+    /// Most positive first
+    pub sources: Vec<SourceSentiment>,
+    /// Most positive source's mean minus the most negative one's
+    pub spread: f64,
+    pub article_ids: Vec<i64>,
+}
+
+pub struct SentimentDivergenceDetector;
+
+impl SentimentDivergenceDetector {
+    /// Per-source sentiment of every entity covered by at least two sources
+    /// with `min_articles` each in the window ending at `now`, widest spread first.
+    pub fn analyze(
+        osint_store: &OSINTStore,
+        settings: &DivergenceSettings,
+        entity: Option<&str>,
+        now: i64,
+    ) -> Result<Vec<EntityDivergence>> {
+        let from_ts = now - settings.window_hours * HOUR;
+        let articles = osint_store.entity_source_articles(from_ts, now + 1, entity)?;
+
+        let mut by_entity: BTreeMap<String, Vec<EntitySourceArticle>> = BTreeMap::new();
+        for article in articles {
+            by_entity.entry(article.entity.to_lowercase()).or_default().push(article);
+        }
+
+        let analyzer = SentimentAnalyzer::new();
+        let mut out: Vec<EntityDivergence> = by_entity
+            .into_values()
+            .filter_map(|articles| divergence(&analyzer, &articles, settings.min_articles, from_ts, now))
+            .collect();
+        out.sort_by(|a, b| b.spread.total_cmp(&a.spread));
+        Ok(out)
+    }
+
+    /// Upsert a "sentiment_divergence" event for every entity whose sources
+    /// disagree by at least `min_spread`. Returns the ids of new events.
+    pub fn detect(
+        osint_store: &OSINTStore,
+        temporal_store: &TemporalStore,
+        settings: &DivergenceSettings,
+    ) -> Result<Vec<i64>> {
+        let now = chrono::Utc::now().timestamp();
+        let mut created = Vec::new();
+        for found in Self::analyze(osint_store, settings, None, now)? {
+            if found.spread < settings.min_spread {
+                continue;
+            }
+            let (id, is_new) = temporal_store.upsert_event(&divergence_event(&found, settings), &found.article_ids)?;
+            if is_new {
+                created.push(id);
+            }
+        }
+        Ok(created)
+    }
+}
+
+fn divergence(
+    analyzer: &SentimentAnalyzer,
+    articles: &[EntitySourceArticle],
+    min_articles: usize,
+    from_ts: i64,
+    to_ts: i64,
+) -> Option<EntityDivergence> {
+    let mut by_source: HashMap<i64, (String, Vec<f64>)> = HashMap::new();
+    for article in articles {
+        let score = analyzer.analyze(&format!("{} {}", article.title, article.content));
+        by_source
+            .entry(article.feed_id)
+            .or_insert_with(|| (article.source.clone(), Vec::new()))
+            .1
+            .push(score);
+    }
+
+    let mut sources: Vec<SourceSentiment> = by_source
+        .into_iter()
+        .filter(|(_, (_, scores))| scores.len() >= min_articles)
+        .map(|(feed_id, (source, scores))| SourceSentiment {
+            feed_id,
+            source,
+            articles: scores.len(),
+            mean_sentiment: SentimentAnalyzer::aggregate_sentiment(&scores),
+        })
+        .collect();
+    if sources.len() < 2 {
+        return None;
+    }
+    sources.sort_by(|a, b| b.mean_sentiment.total_cmp(&a.mean_sentiment).then(a.feed_id.cmp(&b.feed_id)));
+
+    let spread = sources.first()?.mean_sentiment - sources.last()?.mean_sentiment;
+    Some(EntityDivergence {
+        entity: articles.first()?.entity.clone(),
+        from_ts,
+        to_ts,
+        sources,
+        spread,
+        article_ids: articles.iter().map(|a| a.article_id).collect(),
+    })
+}
+
+/// Severity is the spread scaled to [0, 1], which the
+/// "sentiment_divergence" rule condition reads back.
+fn divergence_event(found: &EntityDivergence, settings: &DivergenceSettings) -> NewTemporalEvent {
+    let date = chrono::DateTime::from_timestamp(found.to_ts, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let positive = &found.sources[0];
+    let negative = &found.sources[found.sources.len() - 1];
+    let articles: usize = found.sources.iter().map(|s| s.articles).sum();
+    let mean = found.sources.iter().map(|s| s.mean_sentiment * s.articles as f64).sum::<f64>() / articles.max(1) as f64;
+
+    NewTemporalEvent {
+        title: format!("Sources disagree on {}", found.entity),
+        summary: format!(
+            "{} is positive on {} ({:+.2} over {} articles) while {} is negative ({:+.2} over {}), {} sources in the last {}h",
+            positive.source,
+            found.entity,
+            positive.mean_sentiment,
+            positive.articles,
+            negative.source,
+            negative.mean_sentiment,
+            negative.articles,
+            found.sources.len(),
+            settings.window_hours
+        ),
+        start_ts: found.from_ts,
+        end_ts: found.to_ts,
+        event_type: EVENT_TYPE.to_string(),
+        confidence: (articles as f64 / 10.0).clamp(0.5, 1.0),
+        severity: (found.spread / 2.0).clamp(0.0, 1.0),
+        novelty_score: 0.0,
+        volume_score: articles as f64,
+        sentiment_score: mean,
+        cluster_key: format!("{}|{}|{}", EVENT_TYPE, date, found.entity.to_lowercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn opposing_sources_raise_a_divergence_event() {
+        let db = test_support::test_db();
+        let osint = OSINTStore::new(db.conn.clone());
+        let temporal = TemporalStore::new(db.conn.clone());
+        let now = chrono::Utc::now().timestamp();
+        let pr_feed = test_support::feed(&db, "Acme Newsroom");
+        let media_feed = test_support::feed(&db, "Daily Ledger");
+        let post = |feed_id: i64, title: &str, content: &str, ago: i64| {
+            let url = format!("https://example.com/{}", title.to_lowercase().replace(' ', "-"));
+            let id = osint.save_rss_item(feed_id, title, content, &url, now - ago).unwrap();
+            osint.save_extracted_entity(id, "organization", "Acme", 0.9, None).unwrap();
+        };
+        post(pr_feed, "Acme record growth", "Acme reports record profit and strong growth, an excellent quarter", 3600);
+        post(pr_feed, "Acme wins award", "Acme wins award for excellent strong innovation and record success", 7200);
+        post(media_feed, "Acme probe widens", "Regulators warn of crisis at Acme as the probe signals collapse risk", 1800);
+        post(media_feed, "Acme shares plunge", "Acme shares plunge after weak results and a bearish downgrade", 5400);
+
+        let settings = DivergenceSettings { min_spread: 0.2, ..DivergenceSettings::default() };
+        let found = SentimentDivergenceDetector::analyze(&osint, &settings, Some("acme"), now).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].sources[0].source, "Acme Newsroom");
+        assert!(found[0].spread >= settings.min_spread, "spread {}", found[0].spread);
+
+        let created = SentimentDivergenceDetector::detect(&osint, &temporal, &settings).unwrap();
+        assert_eq!(created.len(), 1);
+        let event = temporal.get_event(created[0]).unwrap().unwrap();
+        assert_eq!(event.event_type, EVENT_TYPE);
+        assert_eq!(temporal.list_event_evidence(event.id).unwrap().len(), 4);
+        // Re-running the same day updates the event instead of adding another
+        assert!(SentimentDivergenceDetector::detect(&osint, &temporal, &settings).unwrap().is_empty());
+
+        let strict = DivergenceSettings { min_articles: 3, ..settings };
+        assert!(SentimentDivergenceDetector::analyze(&osint, &strict, None, now).unwrap().is_empty());
+    }
+}
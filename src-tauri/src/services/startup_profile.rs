@@ -0,0 +1,340 @@
+use crate::commands::ollama::OllamaState;
+use crate::providers::OllamaProvider;
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::market_cache::MarketDataCache;
+use crate::services::market_data_stream::MarketDataStreamer;
+use crate::services::rate_limiter::RateLimiter;
+use crate::services::watchdog::Watchdog;
+use crate::services::{AutomationEventBus, HealthCheckService};
+use crate::storage::Database;
+use crate::ws::WsServer;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::ipc::Invoke;
+use tauri::{AppHandle, Manager};
+use tokio::sync::RwLock;
+
+/// "full" starts everything at launch, "lite" defers the optional
+/// subsystems until first use. Read at startup only.
+pub const CONFIG_PROFILE: &str = "startup_profile";
+/// JSON object overriding single subsystems of the profile, e.g.
+/// `{"ollama": true}`.
+pub const CONFIG_SUBSYSTEMS: &str = "startup_subsystems";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    /// Price streaming, price alerts, fundamentals and FX refresh
+    MarketData,
+    /// HTTP health endpoints on port 5433 and health check monitoring
+    HealthCheckServer,
+    Ollama,
+    /// System metrics pushed over WebSocket every second
+    WsBroadcast,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 4] = [
+        Subsystem::MarketData,
+        Subsystem::HealthCheckServer,
+        Subsystem::Ollama,
+        Subsystem::WsBroadcast,
+    ];
+
+    /// The subsystem a command needs running, if any.
+    fn for_command(command: &str) -> Option<Subsystem> {
+        match command {
+            "get_market_price" | "get_market_prices" | "get_chart_data" | "get_fundamentals"
+            | "refresh_fundamentals" | "create_price_alert" | "update_price_alert" => Some(Subsystem::MarketData),
+            "list_health_checks" | "check_health_check" | "get_health_check_history" | "get_health_check_uptime" => Some(Subsystem::HealthCheckServer),
+            "check_ollama_status" | "list_ollama_models" | "get_ollama_model_info" | "load_model_from_file"
+            | "chat_with_ollama" | "scan_models_folder" | "get_models_folder_path"
+            | "extract_conversation_memories" | "ask_analyst"
+            | "run_ollama_benchmark" | "get_context_usage" => {
+                Some(Subsystem::Ollama)
+            }
+            "ws_connect" | "ws_subscribe" => Some(Subsystem::WsBroadcast),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartupProfile {
+    pub name: String,
+    pub market_data: bool,
+    pub health_check_server: bool,
+    pub ollama: bool,
+    pub ws_broadcast: bool,
+}
+
+impl StartupProfile {
+    pub fn full() -> Self {
+        StartupProfile {
+            name: "full".to_string(),
+            market_data: true,
+            health_check_server: true,
+            ollama: true,
+            ws_broadcast: true,
+        }
+    }
+
+    pub fn lite() -> Self {
+        StartupProfile {
+            name: "lite".to_string(),
+            market_data: false,
+            health_check_server: false,
+            ollama: false,
+            ws_broadcast: false,
+        }
+    }
+
+    /// Profile from config, full when unset or unknown.
+    pub fn load(db: &Database) -> Self {
+        let name = db.get_config(CONFIG_PROFILE).ok().flatten().unwrap_or_default();
+        let overrides = db
+            .get_config(CONFIG_SUBSYSTEMS)
+            .ok()
+            .flatten()
+            .and_then(|v| serde_json::from_str::<serde_json::Value>(&v).ok());
+        Self::resolve(&name, overrides.as_ref())
+    }
+
+    fn resolve(name: &str, overrides: Option<&serde_json::Value>) -> Self {
+        let mut profile = match name.trim() {
+            "lite" => Self::lite(),
+            _ => Self::full(),
+        };
+        if let Some(overrides) = overrides.and_then(|v| v.as_object()) {
+            for subsystem in Subsystem::ALL {
+                let key = serde_json::to_value(subsystem)
+                    .ok()
+                    .and_then(|k| k.as_str().map(|s| s.to_string()))
+                    .unwrap_or_default();
+                if let Some(enabled) = overrides.get(&key).and_then(|v| v.as_bool()) {
+                    *profile.flag(subsystem) = enabled;
+                }
+            }
+        }
+        profile
+    }
+
+    pub fn enabled(&self, subsystem: Subsystem) -> bool {
+        match subsystem {
+            Subsystem::MarketData => self.market_data,
+            Subsystem::HealthCheckServer => self.health_check_server,
+            Subsystem::Ollama => self.ollama,
+            Subsystem::WsBroadcast => self.ws_broadcast,
+        }
+    }
+
+    fn flag(&mut self, subsystem: Subsystem) -> &mut bool {
+        match subsystem {
+            Subsystem::MarketData => &mut self.market_data,
+            Subsystem::HealthCheckServer => &mut self.health_check_server,
+            Subsystem::Ollama => &mut self.ollama,
+            Subsystem::WsBroadcast => &mut self.ws_broadcast,
+        }
+    }
+}
+
+/// What the optional subsystems need to start, built once during setup.
+pub struct SubsystemDeps {
+    pub conn: Arc<Mutex<Connection>>,
+    pub ws_server: Arc<WsServer>,
+    pub market_streamer: Arc<MarketDataStreamer>,
+    pub api_key_manager: Arc<APIKeyManager>,
+    pub rate_limiter: Arc<Mutex<RateLimiter>>,
+    pub market_cache: MarketDataCache,
+    pub event_bus: Arc<AutomationEventBus>,
+    pub health_check_service: HealthCheckService,
+    pub models_folder: Option<PathBuf>,
+}
+
+/// Starts the subsystems the profile enables at launch and the rest on
+/// first use. Managed as app state.
+pub struct Subsystems {
+    profile: StartupProfile,
+    deps: SubsystemDeps,
+    started: Mutex<HashSet<Subsystem>>,
+}
+
+impl Subsystems {
+    pub fn new(profile: StartupProfile, deps: SubsystemDeps) -> Self {
+        Subsystems {
+            profile,
+            deps,
+            started: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn profile(&self) -> &StartupProfile {
+        &self.profile
+    }
+
+    pub fn started(&self) -> Vec<Subsystem> {
+        let started = self.started.lock().map(|s| s.clone()).unwrap_or_default();
+        Subsystem::ALL.into_iter().filter(|s| started.contains(s)).collect()
+    }
+
+    pub fn start_enabled(&self, app: &AppHandle) {
+        for subsystem in Subsystem::ALL {
+            if self.profile.enabled(subsystem) {
+                self.ensure(app, subsystem);
+            } else {
+                eprintln!("MINA: {:?} deferred until first use", subsystem);
+            }
+        }
+    }
+
+    /// Start a subsystem unless it is already running.
+    pub fn ensure(&self, app: &AppHandle, subsystem: Subsystem) {
+        let first = match self.started.lock() {
+            Ok(mut started) => started.insert(subsystem),
+            Err(_) => false,
+        };
+        if first {
+            self.start(app, subsystem);
+        }
+    }
+
+    fn start(&self, app: &AppHandle, subsystem: Subsystem) {
+        let deps = &self.deps;
+        let db = || Arc::new(Mutex::new(Database { conn: deps.conn.clone() }));
+        match subsystem {
+            Subsystem::MarketData => {
+                eprintln!("MINA: Starting market data...");
+                deps.market_streamer.start_batching(app.clone());
+                deps.market_streamer.start_fetching_loop(Some(deps.api_key_manager.clone()), db());
+                crate::services::price_alert_checker::PriceAlertChecker::start_checking(
+                    db(),
+                    deps.ws_server.clone(),
+                    deps.api_key_manager.clone(),
+                    deps.rate_limiter.clone(),
+                    app.clone(),
+                    Some(deps.event_bus.clone()),
+                );
+                // Let the watchdog start these loops again if they get stuck
+                let (streamer, handle) = (deps.market_streamer.clone(), app.clone());
+                Watchdog::on_restart("market_data_batching", move || streamer.start_batching(handle.clone()));
+                let (streamer, keys, conn) = (deps.market_streamer.clone(), deps.api_key_manager.clone(), deps.conn.clone());
+                Watchdog::on_restart("market_data_fetching", move || {
+                    streamer.start_fetching_loop(Some(keys.clone()), Arc::new(Mutex::new(Database { conn: conn.clone() })))
+                });
+                let (ws, keys, limiter, bus, conn, handle) = (
+                    deps.ws_server.clone(),
+                    deps.api_key_manager.clone(),
+                    deps.rate_limiter.clone(),
+                    deps.event_bus.clone(),
+                    deps.conn.clone(),
+                    app.clone(),
+                );
+                Watchdog::on_restart("price_alert_checker", move || {
+                    crate::services::price_alert_checker::PriceAlertChecker::start_checking(
+                        Arc::new(Mutex::new(Database { conn: conn.clone() })),
+                        ws.clone(),
+                        keys.clone(),
+                        limiter.clone(),
+                        handle.clone(),
+                        Some(bus.clone()),
+                    )
+                });
+                // Keep fundamentals of held and alerted tickers fresh
+                crate::services::fundamentals::FundamentalsService::start_scheduler(
+                    db(),
+                    deps.api_key_manager.clone(),
+                    deps.rate_limiter.clone(),
+                    deps.market_cache.clone(),
+                );
+                // Short interest and analyst ratings of watchlist tickers
+                crate::services::market_signals::MarketSignalsService::start_scheduler(
+                    db(),
+                    deps.api_key_manager.clone(),
+                    deps.rate_limiter.clone(),
+                );
+                // Keep FX rates for multi-currency holdings cached
+                crate::services::fx::FxService::start_scheduler(db());
+            }
+            Subsystem::HealthCheckServer => {
+                eprintln!("MINA: Starting health check service...");
+                let service = deps.health_check_service.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = service.start().await {
+                        eprintln!("WARNING: Failed to start health check service: {}", e);
+                        eprintln!("Note: Database and Redis health checks will not be available via HTTP");
+                    } else {
+                        eprintln!("MINA: Health check service started on port {}", service.get_port());
+                    }
+                });
+                crate::services::health_checker::HealthChecker::start_checking(db());
+            }
+            Subsystem::Ollama => {
+                eprintln!("MINA: Initializing Ollama provider...");
+                match &deps.models_folder {
+                    Some(models_folder) => {
+                        let state: OllamaState = Arc::new(RwLock::new(OllamaProvider::new(models_folder.clone())));
+                        app.manage(state.clone());
+                        // Keep selected models warm and queue requests per model
+                        crate::services::ollama_lifecycle::ModelLifecycle::start(db(), app.clone(), state);
+                    }
+                    None => eprintln!("WARNING: No models folder, Ollama unavailable"),
+                }
+            }
+            Subsystem::WsBroadcast => {
+                eprintln!("MINA: Starting WebSocket broadcast...");
+                deps.ws_server.start_broadcast(app.clone());
+                let (ws, handle) = (deps.ws_server.clone(), app.clone());
+                Watchdog::on_restart("ws_broadcast", move || ws.start_broadcast(handle.clone()));
+            }
+        }
+    }
+}
+
+/// Wrap the command handler so a deferred subsystem starts right before
+/// the first command that needs it.
+pub fn lazy_init<F>(handler: F) -> impl Fn(Invoke<tauri::Wry>) -> bool + Send + Sync + 'static
+where
+    F: Fn(Invoke<tauri::Wry>) -> bool + Send + Sync + 'static,
+{
+    move |invoke: Invoke<tauri::Wry>| {
+        if let Some(subsystem) = Subsystem::for_command(invoke.message.command()) {
+            let webview = invoke.message.webview();
+            if let Some(subsystems) = webview.try_state::<Subsystems>() {
+                subsystems.ensure(webview.app_handle(), subsystem);
+            }
+        }
+        handler(invoke)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_profile_is_full() {
+        assert_eq!(StartupProfile::resolve("", None), StartupProfile::full());
+        assert_eq!(StartupProfile::resolve("heavy", None), StartupProfile::full());
+    }
+
+    #[test]
+    fn overrides_apply_to_profile() {
+        let overrides = serde_json::json!({ "ollama": true, "ws_broadcast": "yes" });
+        let profile = StartupProfile::resolve("lite", Some(&overrides));
+        assert!(profile.ollama);
+        assert!(!profile.ws_broadcast);
+        assert!(!profile.market_data);
+    }
+
+    #[test]
+    fn commands_map_to_subsystems() {
+        assert_eq!(Subsystem::for_command("get_market_prices"), Some(Subsystem::MarketData));
+        // A new or re-enabled price alert needs the checker running
+        assert_eq!(Subsystem::for_command("create_price_alert"), Some(Subsystem::MarketData));
+        assert_eq!(Subsystem::for_command("chat_with_ollama"), Some(Subsystem::Ollama));
+        assert_eq!(Subsystem::for_command("get_config"), None);
+    }
+}
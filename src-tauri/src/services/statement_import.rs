@@ -0,0 +1,375 @@
+use crate::storage::statement_imports::{
+    MappingProfile, StatementImport, StatementImportStore, StatementMapping, StatementTransaction,
+};
+use crate::storage::portfolio::Transaction;
+use crate::storage::PortfolioStore;
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+
+/// Marks exported mapping profiles so imports can reject other JSON.
+const PROFILE_FORMAT: &str = "mina-statement-mapping";
+const PROFILE_VERSION: u32 = 1;
+
+/// One data row of a statement, as it would be imported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewRow {
+    /// Line number in the file
+    pub row: usize,
+    pub transaction: Option<StatementTransaction>,
+    pub error: Option<String>,
+    /// Matches a transaction already in the portfolio
+    pub duplicate: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPreview {
+    pub mapping: StatementMapping,
+    pub file_hash: String,
+    /// The same file was imported into this portfolio before
+    pub previous_import: Option<StatementImport>,
+    pub rows: Vec<PreviewRow>,
+    pub valid: usize,
+    pub invalid: usize,
+    pub duplicates: usize,
+}
+
+/// A mapping profile as shared between installations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedProfile {
+    pub format: String,
+    pub version: u32,
+    pub name: String,
+    pub institution: Option<String>,
+    pub mapping: StatementMapping,
+}
+
+/// Split a CSV line on `delimiter`, honouring double quotes.
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// Unix seconds from YYYY-MM-DD, RFC 3339 or unix seconds.
+pub(crate) fn parse_timestamp(value: &str) -> Option<i64> {
+    if let Ok(seconds) = value.parse::<i64>() {
+        return Some(seconds);
+    }
+    if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.timestamp());
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc().timestamp())
+}
+
+fn parse_date(value: &str, format: Option<&str>) -> Option<i64> {
+    let Some(format) = format else { return parse_timestamp(value) };
+    chrono::NaiveDateTime::parse_from_str(value, format)
+        .ok()
+        .or_else(|| chrono::NaiveDate::parse_from_str(value, format).ok()?.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc().timestamp())
+}
+
+fn parse_amount(value: &str, decimal_comma: bool) -> Option<f64> {
+    let value: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+    let value = if decimal_comma {
+        value.replace('.', "").replace(',', ".")
+    } else {
+        value.replace(',', "")
+    };
+    value.trim_start_matches('+').parse().ok()
+}
+
+/// The header row and the data lines after it, with their line numbers.
+fn split_header(text: &str, skip_lines: usize) -> Result<(String, Vec<(usize, &str)>)> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .skip(skip_lines)
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+    let (_, header) = lines.next().context("CSV has no header row")?;
+    Ok((header.trim_start_matches('\u{feff}').to_string(), lines.collect()))
+}
+
+fn detect_delimiter(header: &str) -> char {
+    [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|d| header.matches(*d).count())
+        .unwrap_or(',')
+}
+
+/// Imports broker and bank CSV exports into portfolios through
+/// per-institution mapping profiles, skipping transactions already there.
+pub struct StatementImporter;
+
+impl StatementImporter {
+    /// A mapping from common column names, for files without a profile.
+    pub fn detect_mapping(text: &str) -> Result<StatementMapping> {
+        let (header, _) = split_header(text, 0)?;
+        let delimiter = detect_delimiter(&header);
+        let columns = split_csv_line(&header, delimiter);
+        let column = |names: &[&str]| columns.iter().find(|c| names.contains(&c.to_lowercase().as_str())).cloned();
+
+        let mut mapping = StatementMapping::new(
+            &column(&["ticker", "symbol"]).context("CSV has no ticker column")?,
+            &column(&["quantity", "qty", "shares", "units"]).context("CSV has no quantity column")?,
+            &column(&["price", "unit_price"]).context("CSV has no price column")?,
+            &column(&["date", "transaction_date", "trade_date"]).context("CSV has no date column")?,
+        );
+        mapping.transaction_type = column(&["type", "transaction_type", "action", "side"]);
+        mapping.fees = column(&["fees", "fee", "commission"]);
+        mapping.notes = column(&["notes", "note", "description"]);
+        mapping.delimiter = Some(delimiter);
+        // Semicolon-separated exports come from locales with decimal commas
+        mapping.decimal_comma = delimiter == ';';
+        Ok(mapping)
+    }
+
+    /// Every data row, with the transaction it maps to or why it doesn't.
+    /// Fails only when the file doesn't fit the mapping at all.
+    pub fn parse(text: &str, mapping: &StatementMapping) -> Result<Vec<PreviewRow>> {
+        let (header, lines) = split_header(text, mapping.skip_lines)?;
+        let delimiter = mapping.delimiter.unwrap_or_else(|| detect_delimiter(&header));
+        let columns: Vec<String> = split_csv_line(&header, delimiter).iter().map(|c| c.to_lowercase()).collect();
+        let find = |name: &str| columns.iter().position(|c| *c == name.to_lowercase());
+        let required = |name: &str| find(name).with_context(|| format!("CSV has no \"{}\" column", name));
+
+        let ticker = required(&mapping.ticker)?;
+        let quantity = required(&mapping.quantity)?;
+        let price = required(&mapping.price)?;
+        let date = required(&mapping.date)?;
+        let kind = mapping.transaction_type.as_deref().map(required).transpose()?;
+        let fees = mapping.fees.as_deref().map(required).transpose()?;
+        let notes = mapping.notes.as_deref().map(required).transpose()?;
+        let is_one_of = |values: &[String], value: &str| values.iter().any(|v| v.eq_ignore_ascii_case(value));
+
+        let to_transaction = |fields: &[String]| -> std::result::Result<StatementTransaction, String> {
+            let field = |i: usize| fields.get(i).map(String::as_str).unwrap_or("");
+            let amount = |i: usize, name: &str| {
+                parse_amount(field(i), mapping.decimal_comma)
+                    .ok_or_else(|| format!("invalid {} \"{}\"", name, field(i)))
+            };
+            let qty = amount(quantity, "quantity")?;
+            let transaction_type = match kind.map(field) {
+                Some(t) if is_one_of(&mapping.buy_values, t) => "buy",
+                Some(t) if is_one_of(&mapping.sell_values, t) => "sell",
+                Some(t) if !t.is_empty() => return Err(format!("unknown transaction type \"{}\"", t)),
+                _ if qty < 0.0 => "sell",
+                _ => "buy",
+            };
+            if field(ticker).is_empty() || qty == 0.0 {
+                return Err("missing ticker or quantity".to_string());
+            }
+            Ok(StatementTransaction {
+                ticker: field(ticker).to_uppercase(),
+                transaction_type: transaction_type.to_string(),
+                quantity: qty.abs(),
+                price: amount(price, "price")?.abs(),
+                transaction_date: parse_date(field(date), mapping.date_format.as_deref())
+                    .ok_or_else(|| format!("invalid date \"{}\"", field(date)))?,
+                fees: match fees.filter(|i| !field(*i).is_empty()) {
+                    Some(i) => amount(i, "fees")?.abs(),
+                    None => 0.0,
+                },
+                notes: notes.map(field).filter(|n| !n.is_empty()).map(str::to_string),
+            })
+        };
+
+        Ok(lines
+            .into_iter()
+            .map(|(row, line)| match to_transaction(&split_csv_line(line, delimiter)) {
+                Ok(t) => PreviewRow { row, transaction: Some(t), error: None, duplicate: false },
+                Err(e) => PreviewRow { row, transaction: None, error: Some(e), duplicate: false },
+            })
+            .collect())
+    }
+
+    /// The mapping to use: an explicit one, else the profile's, else detected.
+    pub fn resolve_mapping(
+        store: &StatementImportStore,
+        text: &str,
+        profile_id: Option<i64>,
+        mapping: Option<StatementMapping>,
+    ) -> Result<StatementMapping> {
+        if let Some(mapping) = mapping {
+            return Ok(mapping);
+        }
+        match profile_id {
+            Some(id) => Ok(store.get_profile(id)?.with_context(|| format!("Mapping profile {} not found", id))?.mapping),
+            None => Self::detect_mapping(text),
+        }
+    }
+
+    pub fn preview(
+        conn: &Arc<Mutex<Connection>>,
+        portfolio_id: i64,
+        text: &str,
+        mapping: StatementMapping,
+    ) -> Result<ImportPreview> {
+        let existing = PortfolioStore::new(conn.clone()).list_all_transactions(portfolio_id)?;
+        let mut rows = Self::parse(text, &mapping)?;
+        for row in &mut rows {
+            row.duplicate = row.transaction.as_ref().is_some_and(|t| existing.iter().any(|e| is_duplicate(t, e)));
+        }
+        let file_hash = format!("{:x}", Sha256::digest(text.as_bytes()));
+        Ok(ImportPreview {
+            previous_import: StatementImportStore::new(conn.clone()).find_import_by_hash(portfolio_id, &file_hash)?,
+            valid: rows.iter().filter(|r| r.transaction.is_some() && !r.duplicate).count(),
+            invalid: rows.iter().filter(|r| r.error.is_some()).count(),
+            duplicates: rows.iter().filter(|r| r.duplicate).count(),
+            mapping,
+            file_hash,
+            rows,
+        })
+    }
+
+    /// Import the valid rows that aren't duplicates. Invalid rows fail the
+    /// whole import unless `skip_invalid`.
+    pub fn commit(
+        conn: &Arc<Mutex<Connection>>,
+        portfolio_id: i64,
+        profile_id: Option<i64>,
+        file_name: &str,
+        text: &str,
+        mapping: StatementMapping,
+        skip_invalid: bool,
+    ) -> Result<StatementImport> {
+        if PortfolioStore::new(conn.clone()).get_portfolio(portfolio_id)?.is_none() {
+            anyhow::bail!("Portfolio {} not found", portfolio_id);
+        }
+        let preview = Self::preview(conn, portfolio_id, text, mapping)?;
+        if preview.invalid > 0 && !skip_invalid {
+            let errors: Vec<String> = preview
+                .rows
+                .iter()
+                .filter_map(|r| r.error.as_ref().map(|e| format!("row {}: {}", r.row, e)))
+                .take(5)
+                .collect();
+            anyhow::bail!("{} invalid rows: {}", preview.invalid, errors.join("; "));
+        }
+
+        let transactions: Vec<StatementTransaction> = preview
+            .rows
+            .iter()
+            .filter(|r| !r.duplicate)
+            .filter_map(|r| r.transaction.clone())
+            .collect();
+        let mut import = StatementImport {
+            id: 0,
+            portfolio_id,
+            profile_id,
+            file_name: file_name.to_string(),
+            file_hash: preview.file_hash,
+            rows_total: preview.rows.len() as i64,
+            rows_imported: transactions.len() as i64,
+            rows_duplicate: preview.duplicates as i64,
+            rows_invalid: preview.invalid as i64,
+            status: "committed".to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            undone_at: None,
+        };
+        import.id = StatementImportStore::new(conn.clone()).commit_import(&import, &transactions)?;
+        Ok(import)
+    }
+
+    pub fn export_profile(profile: &MappingProfile) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&SharedProfile {
+            format: PROFILE_FORMAT.to_string(),
+            version: PROFILE_VERSION,
+            name: profile.name.clone(),
+            institution: profile.institution.clone(),
+            mapping: profile.mapping.clone(),
+        })?)
+    }
+
+    /// Save a shared profile, replacing a local one of the same name.
+    pub fn import_profile(store: &StatementImportStore, json: &str) -> Result<i64> {
+        let shared: SharedProfile = serde_json::from_str(json).context("Not a mapping profile")?;
+        if shared.format != PROFILE_FORMAT {
+            anyhow::bail!("Not a mapping profile");
+        }
+        if shared.version > PROFILE_VERSION {
+            anyhow::bail!("Mapping profile version {} is newer than this app supports", shared.version);
+        }
+        store.save_profile(&shared.name, shared.institution.as_deref(), &shared.mapping)
+    }
+}
+
+/// Same ticker, direction, day, quantity and price.
+fn is_duplicate(new: &StatementTransaction, existing: &Transaction) -> bool {
+    new.ticker.eq_ignore_ascii_case(&existing.ticker)
+        && new.transaction_type == existing.transaction_type
+        && new.transaction_date.div_euclid(86_400) == existing.transaction_date.div_euclid(86_400)
+        && (new.quantity - existing.quantity).abs() < 1e-6
+        && (new.price - existing.price).abs() < 0.005
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_statement_rows_to_transactions() {
+        let csv = "\u{feff}Date;Symbol;Action;Shares;Price;Commission;Description\n\
+                   2024-03-01;aapl;Bought;10;1.170,50;1,00;\"Savings plan; March\"\n\
+                   2024-03-05;MSFT;;-2;410;;\n\
+                   2024-03-06;NVDA;Split;1;800;;\n";
+        let mapping = StatementImporter::detect_mapping(csv).unwrap();
+        assert_eq!((mapping.delimiter, mapping.decimal_comma), (Some(';'), true));
+
+        let rows = StatementImporter::parse(csv, &mapping).unwrap();
+        assert_eq!(rows[0].transaction, Some(StatementTransaction {
+            ticker: "AAPL".to_string(),
+            transaction_type: "buy".to_string(),
+            quantity: 10.0,
+            price: 1170.5,
+            transaction_date: 1_709_251_200,
+            fees: 1.0,
+            notes: Some("Savings plan; March".to_string()),
+        }));
+        let sell = rows[1].transaction.as_ref().unwrap();
+        assert_eq!((sell.transaction_type.as_str(), sell.quantity), ("sell", 2.0));
+        assert_eq!((rows[2].row, rows[2].error.as_deref()), (4, Some("unknown transaction type \"Split\"")));
+
+        // A bank export with account details above the header
+        let mut bank = StatementMapping::new("ISIN", "Stück", "Kurs", "Datum");
+        bank.date_format = Some("%d.%m.%Y".to_string());
+        bank.decimal_comma = true;
+        bank.skip_lines = 2;
+        let rows = StatementImporter::parse("Depot 1234\n\nDatum;ISIN;Stück;Kurs\n01.03.2024;US0378331005;5;170,5\n", &bank).unwrap();
+        assert_eq!(rows[0].transaction.as_ref().map(|t| (t.transaction_date, t.price)), Some((1_709_251_200, 170.5)));
+
+        let existing = Transaction {
+            id: 1,
+            portfolio_id: 1,
+            ticker: "AAPL".to_string(),
+            transaction_type: "buy".to_string(),
+            quantity: 10.0,
+            price: 1170.5,
+            transaction_date: 1_709_251_200 + 3_600,
+            fees: 0.0,
+            notes: None,
+        };
+        assert!(is_duplicate(StatementImporter::parse(csv, &mapping).unwrap()[0].transaction.as_ref().unwrap(), &existing));
+    }
+}
@@ -0,0 +1,491 @@
+use crate::storage::{Database, NewIoc, OSINTStore};
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// Namespace the STIX 2.1 spec defines for deterministic object ids.
+const STIX_NAMESPACE: Uuid = Uuid::from_u128(0x00abedb4_aa42_466c_9c01_fed23315a9b7);
+const MAX_ARTICLES_PER_OBJECT: usize = 20;
+const MAX_IOCS: i64 = 10_000;
+
+/// STIX domain object types imported as entities under their own name.
+const PASSTHROUGH_TYPES: [&str; 8] = [
+    "threat-actor",
+    "intrusion-set",
+    "campaign",
+    "malware",
+    "tool",
+    "vulnerability",
+    "attack-pattern",
+    "infrastructure",
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StixImportReport {
+    pub entities_created: usize,
+    pub entities_matched: usize,
+    pub iocs: usize,
+    pub relationships: usize,
+    /// Objects with no local counterpart (reports, notes, unsupported patterns, ...)
+    pub skipped: usize,
+}
+
+pub struct StixService;
+
+impl StixService {
+    /// A STIX 2.1 bundle of entities (all, or `entity_ids`), the
+    /// relationships between them, IOCs (all, or `ioc_ids`) as indicators,
+    /// and the articles supporting them as reports.
+    pub fn export(db: &Database, entity_ids: Option<&[i64]>, ioc_ids: Option<&[i64]>) -> Result<Value> {
+        let store = OSINTStore::new(db.conn.clone());
+        let now = stix_timestamp(chrono::Utc::now().timestamp());
+
+        let entities: Vec<_> = store
+            .list_entities(None)?
+            .into_iter()
+            .filter(|e| selected(entity_ids, e.id))
+            .collect();
+        let iocs: Vec<_> = store
+            .list_iocs(None, MAX_IOCS)?
+            .into_iter()
+            .filter(|i| selected(ioc_ids, i.id))
+            .collect();
+
+        let mut objects = Vec::new();
+        let mut stix_ids = HashMap::new();
+        // Article id to the STIX ids it supports
+        let mut supporting: BTreeMap<i64, Vec<String>> = BTreeMap::new();
+
+        for entity in &entities {
+            let object = entity_object(entity.id, &entity.entity_type, &entity.name, &entity.metadata, entity.created_at);
+            let id = object["id"].as_str().unwrap_or_default().to_string();
+            for article_id in store
+                .article_ids_for_entity(&entity.name, 0, i64::MAX)?
+                .into_iter()
+                .rev()
+                .take(MAX_ARTICLES_PER_OBJECT)
+            {
+                supporting.entry(article_id).or_default().push(id.clone());
+            }
+            stix_ids.insert(entity.id, id);
+            objects.push(object);
+        }
+
+        for rel in store.list_relationships()? {
+            let (Some(source), Some(target)) = (stix_ids.get(&rel.source_id), stix_ids.get(&rel.target_id)) else {
+                continue;
+            };
+            let created = stix_timestamp(rel.created_at);
+            objects.push(json!({
+                "type": "relationship",
+                "spec_version": "2.1",
+                "id": stix_id("relationship", &format!("mina:relationship:{}", rel.id)),
+                "created": created,
+                "modified": created,
+                "relationship_type": relationship_type(&rel.relationship_type),
+                "source_ref": source,
+                "target_ref": target,
+                "confidence": confidence(rel.strength),
+            }));
+        }
+
+        for ioc in &iocs {
+            let Some(pattern) = ioc_pattern(&ioc.ioc_type, &ioc.value) else { continue };
+            let id = stix_id("indicator", &format!("mina:ioc:{}:{}", ioc.ioc_type, ioc.value));
+            let mut object = json!({
+                "type": "indicator",
+                "spec_version": "2.1",
+                "id": id,
+                "created": stix_timestamp(ioc.first_seen),
+                "modified": stix_timestamp(ioc.last_seen),
+                "name": ioc.value,
+                "pattern": pattern,
+                "pattern_type": "stix",
+                "valid_from": stix_timestamp(ioc.first_seen),
+                "confidence": confidence(ioc.confidence),
+                "x_mina_source": ioc.source,
+            });
+            if let Some(description) = &ioc.description {
+                object["description"] = json!(description);
+            }
+            if !ioc.tags.is_empty() {
+                object["labels"] = json!(ioc.tags);
+            }
+            for article_id in store.article_ids_for_ioc(ioc.id)?.into_iter().take(MAX_ARTICLES_PER_OBJECT) {
+                supporting.entry(article_id).or_default().push(id.clone());
+            }
+            objects.push(object);
+        }
+
+        let feeds: HashMap<i64, String> = store.list_feeds()?.into_iter().map(|f| (f.id, f.name)).collect();
+        for (article_id, refs) in supporting {
+            let Some(item) = store.get_item(article_id)? else { continue };
+            let published = stix_timestamp(item.published_at);
+            let source_name = feeds.get(&item.feed_id).cloned().unwrap_or_else(|| "article".to_string());
+            objects.push(json!({
+                "type": "report",
+                "spec_version": "2.1",
+                "id": stix_id("report", &format!("mina:article:{}", item.url)),
+                "created": published,
+                "modified": published,
+                "name": item.title,
+                "published": published,
+                "report_types": ["threat-report"],
+                "object_refs": refs,
+                "external_references": [{ "source_name": source_name, "url": item.url }],
+            }));
+        }
+
+        Ok(json!({
+            "type": "bundle",
+            "id": format!("bundle--{}", Uuid::new_v4()),
+            "objects": objects,
+            "x_mina_exported_at": now,
+        }))
+    }
+
+    /// Add the entities, relationships and indicators of a STIX bundle.
+    /// Entities already known by type and name are reused, IOCs merged.
+    pub fn import(db: &Database, bundle: &Value, source: &str) -> Result<StixImportReport> {
+        let objects = match bundle.get("type").and_then(|t| t.as_str()) {
+            Some("bundle") => bundle.get("objects").and_then(|o| o.as_array()).cloned().unwrap_or_default(),
+            Some(_) => vec![bundle.clone()],
+            None => anyhow::bail!("Not a STIX object or bundle"),
+        };
+        let store = OSINTStore::new(db.conn.clone());
+        let mut report = StixImportReport::default();
+        let mut entity_ids: HashMap<String, i64> = HashMap::new();
+        let mut relationships = Vec::new();
+
+        for object in &objects {
+            let object_type = object.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+            let stix_id = object.get("id").and_then(|i| i.as_str()).unwrap_or_default();
+
+            if object_type == "relationship" {
+                relationships.push(object);
+                continue;
+            }
+            if let Some((entity_type, name)) = entity_from_object(object) {
+                let id = match store.find_entity(&entity_type, &name)? {
+                    Some(id) => {
+                        report.entities_matched += 1;
+                        id
+                    }
+                    None => {
+                        let metadata = json!({
+                            "stix_id": stix_id,
+                            "description": object.get("description"),
+                            "aliases": object.get("aliases"),
+                        });
+                        report.entities_created += 1;
+                        store.create_entity(&entity_type, &name, &metadata.to_string())?
+                    }
+                };
+                entity_ids.insert(stix_id.to_string(), id);
+                continue;
+            }
+
+            let iocs = iocs_from_object(object);
+            if iocs.is_empty() {
+                report.skipped += 1;
+                continue;
+            }
+            let description = object
+                .get("description")
+                .or_else(|| object.get("name"))
+                .and_then(|d| d.as_str())
+                .map(|d| d.to_string());
+            let tags: Vec<String> = object
+                .get("labels")
+                .and_then(|l| l.as_array())
+                .map(|l| l.iter().filter_map(|t| t.as_str().map(|t| t.to_string())).collect())
+                .unwrap_or_default();
+            let confidence = object.get("confidence").and_then(|c| c.as_f64()).map_or(0.5, |c| c / 100.0);
+            for (ioc_type, value) in iocs {
+                store.upsert_ioc(&NewIoc {
+                    ioc_type,
+                    value,
+                    description: description.clone(),
+                    source: source.to_string(),
+                    tags: tags.clone(),
+                    confidence,
+                })?;
+                report.iocs += 1;
+            }
+        }
+
+        for rel in relationships {
+            let source_id = rel.get("source_ref").and_then(|r| r.as_str()).and_then(|r| entity_ids.get(r));
+            let target_id = rel.get("target_ref").and_then(|r| r.as_str()).and_then(|r| entity_ids.get(r));
+            let (Some(source_id), Some(target_id)) = (source_id, target_id) else {
+                report.skipped += 1;
+                continue;
+            };
+            let relationship_type = rel.get("relationship_type").and_then(|t| t.as_str()).unwrap_or("related-to");
+            let strength = rel.get("confidence").and_then(|c| c.as_f64()).map_or(1.0, |c| c / 100.0);
+            store.create_relationship(*source_id, *target_id, relationship_type, strength)?;
+            report.relationships += 1;
+        }
+
+        Ok(report)
+    }
+}
+
+/// The IOC type of a raw value, if it looks like one.
+pub fn classify_ioc(value: &str) -> Option<&'static str> {
+    let value = value.trim();
+    if value.is_empty() || value.contains(char::is_whitespace) {
+        return None;
+    }
+    if value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return match value.len() {
+            32 => Some("md5"),
+            40 => Some("sha1"),
+            64 => Some("sha256"),
+            _ => None,
+        };
+    }
+    match value.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V4(_)) => return Some("ipv4"),
+        Ok(std::net::IpAddr::V6(_)) => return Some("ipv6"),
+        Err(_) => {}
+    }
+    if value.contains("://") {
+        Some("url")
+    } else if value.contains('@') {
+        Some("email")
+    } else if value.contains('.')
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        && value.chars().any(|c| c.is_ascii_alphabetic())
+    {
+        Some("domain")
+    } else {
+        None
+    }
+}
+
+fn selected(ids: Option<&[i64]>, id: i64) -> bool {
+    match ids {
+        Some(ids) => ids.contains(&id),
+        None => true,
+    }
+}
+
+fn stix_id(object_type: &str, name: &str) -> String {
+    format!("{}--{}", object_type, Uuid::new_v5(&STIX_NAMESPACE, name.as_bytes()))
+}
+
+fn stix_timestamp(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string()
+}
+
+fn confidence(value: f64) -> i64 {
+    (value.clamp(0.0, 1.0) * 100.0).round() as i64
+}
+
+/// STIX relationship types are lowercase and hyphenated.
+fn relationship_type(value: &str) -> String {
+    let normalized: String = value
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let normalized = normalized.split('-').filter(|p| !p.is_empty()).collect::<Vec<_>>().join("-");
+    if normalized.is_empty() {
+        "related-to".to_string()
+    } else {
+        normalized
+    }
+}
+
+fn entity_object(id: i64, entity_type: &str, name: &str, metadata: &str, created_at: i64) -> Value {
+    let lower = entity_type.to_lowercase();
+    let (object_type, identity_class) = match lower.as_str() {
+        "person" => ("identity", Some("individual")),
+        "organization" | "company" => ("identity", Some("organization")),
+        "country" | "location" => ("location", None),
+        t if PASSTHROUGH_TYPES.contains(&t) => (t, None),
+        _ => ("identity", Some("unknown")),
+    };
+    let created = stix_timestamp(created_at);
+    let mut object = json!({
+        "type": object_type,
+        "spec_version": "2.1",
+        "id": stix_id(object_type, &format!("mina:entity:{}", id)),
+        "created": created,
+        "modified": created,
+        "name": name,
+        "x_mina_entity_type": entity_type,
+    });
+    if let Some(class) = identity_class {
+        object["identity_class"] = json!(class);
+    }
+    if object_type == "location" {
+        object["country"] = json!(name);
+    }
+    if object_type == "malware" {
+        object["is_family"] = json!(false);
+    }
+    if let Some(description) = serde_json::from_str::<Value>(metadata)
+        .ok()
+        .and_then(|m| m.get("description").and_then(|d| d.as_str()).map(|d| d.to_string()))
+    {
+        object["description"] = json!(description);
+    }
+    object
+}
+
+/// Local entity type and name of a STIX domain object, if it maps to one.
+fn entity_from_object(object: &Value) -> Option<(String, String)> {
+    let object_type = object.get("type")?.as_str()?;
+    let name = object
+        .get("name")
+        .or_else(|| object.get("country"))
+        .or_else(|| object.get("region"))
+        .and_then(|n| n.as_str())?
+        .trim()
+        .to_string();
+    if name.is_empty() {
+        return None;
+    }
+    if let Some(entity_type) = object.get("x_mina_entity_type").and_then(|t| t.as_str()) {
+        return Some((entity_type.to_string(), name));
+    }
+    let entity_type = match object_type {
+        "identity" => match object.get("identity_class").and_then(|c| c.as_str()) {
+            Some("individual") => "person",
+            _ => "organization",
+        },
+        "location" => "location",
+        t if PASSTHROUGH_TYPES.contains(&t) => t,
+        _ => return None,
+    };
+    Some((entity_type.to_string(), name))
+}
+
+fn ioc_pattern(ioc_type: &str, value: &str) -> Option<String> {
+    let path = match ioc_type {
+        "ipv4" => "ipv4-addr:value",
+        "ipv6" => "ipv6-addr:value",
+        "domain" => "domain-name:value",
+        "url" => "url:value",
+        "email" => "email-addr:value",
+        "md5" => "file:hashes.MD5",
+        "sha1" => "file:hashes.'SHA-1'",
+        "sha256" => "file:hashes.'SHA-256'",
+        _ => return None,
+    };
+    let escaped = value.replace('\\', "\\\\").replace('\'', "\\'");
+    Some(format!("[{} = '{}']", path, escaped))
+}
+
+fn pattern_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"([a-z0-9-]+:[A-Za-z0-9_.'-]+)\s*=\s*'((?:[^'\\]|\\.)*)'").expect("valid regex")
+    })
+}
+
+fn ioc_type_for_path(path: &str) -> Option<&'static str> {
+    match path.replace('\'', "").to_lowercase().as_str() {
+        "ipv4-addr:value" => Some("ipv4"),
+        "ipv6-addr:value" => Some("ipv6"),
+        "domain-name:value" => Some("domain"),
+        "url:value" => Some("url"),
+        "email-addr:value" => Some("email"),
+        "file:hashes.md5" => Some("md5"),
+        "file:hashes.sha-1" | "file:hashes.sha1" => Some("sha1"),
+        "file:hashes.sha-256" | "file:hashes.sha256" => Some("sha256"),
+        _ => None,
+    }
+}
+
+/// IOCs in an indicator's STIX pattern or in a cyber-observable object.
+/// Only equality comparisons are understood.
+fn iocs_from_object(object: &Value) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    let mut seen = HashSet::new();
+    let mut push = |ioc_type: &str, value: &str| {
+        if !value.is_empty() && seen.insert((ioc_type.to_string(), value.to_string())) {
+            out.push((ioc_type.to_string(), value.to_string()));
+        }
+    };
+
+    match object.get("type").and_then(|t| t.as_str()).unwrap_or_default() {
+        "indicator" => {
+            // Only STIX patterns, not Snort/YARA/Sigma ones
+            let pattern = match object.get("pattern_type").and_then(|t| t.as_str()).unwrap_or("stix") {
+                "stix" => object.get("pattern").and_then(|p| p.as_str()).unwrap_or_default(),
+                _ => "",
+            };
+            for caps in pattern_regex().captures_iter(pattern) {
+                if let Some(ioc_type) = ioc_type_for_path(&caps[1]) {
+                    let value = caps[2].replace("\\'", "'").replace("\\\\", "\\");
+                    push(ioc_type, &value);
+                }
+            }
+        }
+        "file" => {
+            if let Some(hashes) = object.get("hashes").and_then(|h| h.as_object()) {
+                for (algorithm, value) in hashes {
+                    let path = format!("file:hashes.{}", algorithm);
+                    if let (Some(ioc_type), Some(value)) = (ioc_type_for_path(&path), value.as_str()) {
+                        push(ioc_type, value);
+                    }
+                }
+            }
+        }
+        observable => {
+            let path = format!("{}:value", observable);
+            if let (Some(ioc_type), Some(value)) =
+                (ioc_type_for_path(&path), object.get("value").and_then(|v| v.as_str()))
+            {
+                push(ioc_type, value);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_raw_indicators() {
+        assert_eq!(classify_ioc("203.0.113.7"), Some("ipv4"));
+        assert_eq!(classify_ioc("2001:db8::1"), Some("ipv6"));
+        assert_eq!(classify_ioc("evil-cdn.example.com"), Some("domain"));
+        assert_eq!(classify_ioc("https://evil.example/x"), Some("url"));
+        assert_eq!(classify_ioc("d41d8cd98f00b204e9800998ecf8427e"), Some("md5"));
+        assert_eq!(classify_ioc("not an ioc"), None);
+    }
+
+    #[test]
+    fn patterns_round_trip() {
+        let pattern = ioc_pattern("sha256", &"a".repeat(64)).unwrap();
+        assert_eq!(pattern, format!("[file:hashes.'SHA-256' = '{}']", "a".repeat(64)));
+
+        let indicator = json!({
+            "type": "indicator",
+            "pattern_type": "stix",
+            "pattern": "[ipv4-addr:value = '198.51.100.1'] OR [url:value = 'http://x.example/it\\'s']",
+        });
+        assert_eq!(
+            iocs_from_object(&indicator),
+            vec![
+                ("ipv4".to_string(), "198.51.100.1".to_string()),
+                ("url".to_string(), "http://x.example/it's".to_string()),
+            ]
+        );
+        assert_eq!(relationship_type("Works For"), "works-for");
+    }
+}
@@ -0,0 +1,98 @@
+use crate::storage::temporal::{EvidenceArrival, LifecycleTransition, TemporalStore};
+use anyhow::Result;
+
+pub const EMERGING: &str = "emerging";
+pub const DEVELOPING: &str = "developing";
+pub const PEAK: &str = "peak";
+pub const FADING: &str = "fading";
+pub const DORMANT: &str = "dormant";
+pub const STATES: [&str; 5] = [EMERGING, DEVELOPING, PEAK, FADING, DORMANT];
+
+const HOUR: i64 = 3600;
+/// Arrival rates are compared over windows of this length.
+const WINDOW: i64 = 6 * HOUR;
+/// A story stays emerging for its first window.
+const EMERGING_FOR: i64 = WINDOW;
+/// No new evidence for this long makes a story dormant.
+const DORMANT_AFTER: i64 = 48 * HOUR;
+/// The last window needs this multiple of the story's average rate to count as peak.
+const PEAK_RATIO: f64 = 1.5;
+
+pub struct StoryLifecycle;
+
+impl StoryLifecycle {
+    /// Reclassify events with evidence in the last `days_back` days and
+    /// record a transition for every state that changed.
+    pub fn update(temporal_store: &TemporalStore, days_back: i64) -> Result<Vec<LifecycleTransition>> {
+        let now = chrono::Utc::now().timestamp();
+        let mut transitions = Vec::new();
+        for arrival in temporal_store.evidence_arrival(now - days_back * 24 * HOUR, now, WINDOW)? {
+            let state = classify(&arrival, now);
+            if state != arrival.lifecycle_state {
+                transitions.push(temporal_store.set_lifecycle_state(arrival.event_id, state, now)?);
+            }
+        }
+        Ok(transitions)
+    }
+
+    pub fn is_state(state: &str) -> bool {
+        STATES.contains(&state)
+    }
+}
+
+/// Lifecycle state from how evidence has been arriving:
+/// - dormant: nothing new for two days
+/// - emerging: first article within the last window
+/// - peak: last window well above the story's average rate and not slowing
+/// - fading: last window empty or under half the one before
+/// - developing: anything else still receiving coverage
+pub fn classify(arrival: &EvidenceArrival, now: i64) -> &'static str {
+    if now - arrival.last_ts > DORMANT_AFTER {
+        return DORMANT;
+    }
+    if now - arrival.first_ts <= EMERGING_FOR {
+        return EMERGING;
+    }
+    if arrival.recent == 0 {
+        return FADING;
+    }
+
+    let windows = ((now - arrival.first_ts) as f64 / WINDOW as f64).max(1.0);
+    let average = (arrival.total as f64 / windows).max(1.0);
+    if arrival.recent as f64 >= PEAK_RATIO * average && arrival.recent >= arrival.prior {
+        PEAK
+    } else if (arrival.recent as f64) < arrival.prior as f64 / 2.0 {
+        FADING
+    } else {
+        DEVELOPING
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arrival(first_ago: i64, last_ago: i64, total: i64, recent: i64, prior: i64) -> EvidenceArrival {
+        EvidenceArrival {
+            event_id: 1,
+            lifecycle_state: EMERGING.to_string(),
+            first_ts: 1_000_000 - first_ago,
+            last_ts: 1_000_000 - last_ago,
+            total,
+            recent,
+            prior,
+        }
+    }
+
+    #[test]
+    fn classifies_by_arrival_rate() {
+        let now = 1_000_000;
+        assert_eq!(classify(&arrival(2 * HOUR, 0, 3, 3, 0), now), EMERGING);
+        // 24h old story, 4 windows averaging 5 articles, 12 in the last one
+        assert_eq!(classify(&arrival(24 * HOUR, 0, 20, 12, 4), now), PEAK);
+        assert_eq!(classify(&arrival(24 * HOUR, 0, 20, 5, 6), now), DEVELOPING);
+        assert_eq!(classify(&arrival(24 * HOUR, HOUR, 20, 2, 8), now), FADING);
+        assert_eq!(classify(&arrival(24 * HOUR, 10 * HOUR, 20, 0, 0), now), FADING);
+        assert_eq!(classify(&arrival(96 * HOUR, 72 * HOUR, 20, 0, 0), now), DORMANT);
+    }
+}
@@ -0,0 +1,274 @@
+use crate::providers::market_data::normalize_sector;
+use crate::services::portfolio_analyzer::{HoldingImpact, PortfolioAnalyzer};
+use crate::storage::market_data::MarketDataStore;
+use crate::storage::portfolio::PortfolioStore;
+use crate::storage::scenarios::ScenarioShock;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Rough percent price move per +100bp in rates, used when a rates shock
+/// doesn't bring its own sensitivities. Sectors not listed get -3%.
+const DEFAULT_RATE_SENSITIVITIES: &[(&str, f64)] = &[
+    ("Real Estate", -8.0),
+    ("Utilities", -6.0),
+    ("Technology", -5.0),
+    ("Communication Services", -4.0),
+    ("Consumer Cyclical", -4.0),
+    ("Healthcare", -2.0),
+    ("Consumer Defensive", -2.0),
+    ("Industrials", -3.0),
+    ("Basic Materials", -2.0),
+    ("Energy", 0.0),
+    ("Financial Services", 2.0),
+];
+const DEFAULT_RATE_SENSITIVITY: f64 = -3.0;
+
+/// Everyday names for the sector labels providers report.
+const SECTOR_ALIASES: &[(&str, &str)] = &[
+    ("tech", "Technology"),
+    ("it", "Technology"),
+    ("financials", "Financial Services"),
+    ("finance", "Financial Services"),
+    ("banks", "Financial Services"),
+    ("health", "Healthcare"),
+    ("health care", "Healthcare"),
+    ("pharma", "Healthcare"),
+    ("reit", "Real Estate"),
+    ("reits", "Real Estate"),
+    ("real estate", "Real Estate"),
+    ("telecom", "Communication Services"),
+    ("media", "Communication Services"),
+    ("consumer discretionary", "Consumer Cyclical"),
+    ("consumer staples", "Consumer Defensive"),
+    ("materials", "Basic Materials"),
+    ("oil", "Energy"),
+];
+
+/// How the shocks combined for one holding, all in percent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedShock {
+    pub ticker: String,
+    pub price_percent: f64,
+    pub rates_percent: f64,
+    pub fx_percent: f64,
+    pub total_percent: f64, // move in the portfolio's base currency
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioResult {
+    pub scenario_id: Option<i64>,
+    pub portfolio_id: i64,
+    pub base_currency: String,
+    pub portfolio_value: f64,
+    pub total_impact: f64,
+    pub impact_percent: f64,
+    pub holdings: Vec<HoldingImpact>,
+    pub applied: Vec<AppliedShock>,
+    pub unmatched_shocks: Vec<String>, // shocks that touched no holding
+}
+
+pub struct StressTestEngine;
+
+impl StressTestEngine {
+    /// Parse shorthand like "tech -15%, EURUSD -5%, rates +50bp, NVDA -20%".
+    /// Lower-case names are sectors, upper-case names tickers, six-letter
+    /// codes (or "EUR/USD") currency pairs, and "rates" an interest rate move.
+    pub fn parse_shocks(text: &str) -> Result<Vec<ScenarioShock>> {
+        let mut shocks = Vec::new();
+        for part in text.split([',', ';', '\n']).map(str::trim).filter(|p| !p.is_empty()) {
+            let (name, amount) = part
+                .rsplit_once(char::is_whitespace)
+                .ok_or_else(|| anyhow::anyhow!("Expected '<target> <change>' in '{}'", part))?;
+            let name = name.trim();
+            let amount = amount.trim().to_lowercase();
+
+            let (value, is_bp) = if let Some(bp) = amount.strip_suffix("bp").or_else(|| amount.strip_suffix("bps")) {
+                (bp.trim_start_matches('+').parse::<f64>(), true)
+            } else {
+                (amount.trim_end_matches('%').trim_start_matches('+').parse::<f64>(), false)
+            };
+            let value = value.map_err(|_| anyhow::anyhow!("Invalid change '{}' in '{}'", amount, part))?;
+
+            let lower = name.to_lowercase();
+            let pair = name.replace('/', "");
+            let shock = if matches!(lower.as_str(), "rates" | "rate" | "yields" | "interest rates") {
+                ScenarioShock {
+                    kind: "rates".to_string(),
+                    target: String::new(),
+                    // "+0.5%" in rates means 50bp
+                    change: if is_bp { value } else { value * 100.0 },
+                    sensitivities: HashMap::new(),
+                }
+            } else if is_bp {
+                anyhow::bail!("Basis points only apply to rates, got '{}'", part);
+            } else if pair.len() == 6 && pair.chars().all(|c| c.is_ascii_uppercase()) {
+                ScenarioShock { kind: "fx".to_string(), target: pair, change: value, sensitivities: HashMap::new() }
+            } else if name.chars().any(|c| c.is_lowercase()) {
+                ScenarioShock { kind: "sector".to_string(), target: canonical_sector(name), change: value, sensitivities: HashMap::new() }
+            } else {
+                ScenarioShock { kind: "ticker".to_string(), target: name.to_string(), change: value, sensitivities: HashMap::new() }
+            };
+            shocks.push(shock);
+        }
+
+        if shocks.is_empty() {
+            anyhow::bail!("No shocks given");
+        }
+        Ok(shocks)
+    }
+
+    /// Project the scenario onto the portfolio. A ticker shock replaces its
+    /// sector's shock; rates and FX effects compound on top.
+    pub fn run(
+        portfolio_store: &PortfolioStore,
+        market_data_store: &MarketDataStore,
+        portfolio_id: i64,
+        scenario_id: Option<i64>,
+        shocks: &[ScenarioShock],
+    ) -> Result<ScenarioResult> {
+        let value = PortfolioAnalyzer::calculate_portfolio_value(portfolio_store, market_data_store, portfolio_id)?;
+        let tickers: Vec<String> = value.holdings.iter().map(|h| h.ticker.clone()).collect();
+        let sectors = market_data_store.get_sectors(&tickers)?;
+        let mut matched = vec![false; shocks.len()];
+
+        let mut applied = Vec::new();
+        let mut price_changes = HashMap::new();
+        for holding in &value.holdings {
+            let sector = sectors.get(&holding.ticker).map(|s| s.as_str());
+            let mut ticker_shock = None;
+            let mut sector_shock = None;
+            let mut rates_percent = 0.0;
+            let mut fx_factor = 1.0;
+
+            for (i, shock) in shocks.iter().enumerate() {
+                match shock.kind.as_str() {
+                    "ticker" if shock.target.eq_ignore_ascii_case(&holding.ticker) => {
+                        ticker_shock = Some(shock.change);
+                        matched[i] = true;
+                    }
+                    "sector" if sector.map(|s| s.eq_ignore_ascii_case(&shock.target)).unwrap_or(false) => {
+                        sector_shock = Some(shock.change);
+                        matched[i] = true;
+                    }
+                    "rates" => {
+                        rates_percent += rate_sensitivity(shock, &holding.ticker, sector) * shock.change / 100.0;
+                        matched[i] = true;
+                    }
+                    "fx" => {
+                        if let Some(factor) = fx_factor_for(&shock.target, shock.change, &holding.currency, &value.base_currency) {
+                            fx_factor *= factor;
+                            matched[i] = true;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            let price_percent = ticker_shock.or(sector_shock).unwrap_or(0.0);
+            let local_factor = (1.0 + price_percent / 100.0) * (1.0 + rates_percent / 100.0);
+            let total_percent = (local_factor * fx_factor - 1.0) * 100.0;
+            if total_percent.abs() < 1e-12 {
+                continue;
+            }
+
+            price_changes.insert(holding.ticker.clone(), total_percent);
+            applied.push(AppliedShock {
+                ticker: holding.ticker.clone(),
+                price_percent,
+                rates_percent,
+                fx_percent: (fx_factor - 1.0) * 100.0,
+                total_percent,
+            });
+        }
+
+        let impact = PortfolioAnalyzer::analyze_price_changes(portfolio_store, market_data_store, portfolio_id, &price_changes)?;
+        let unmatched_shocks = shocks
+            .iter()
+            .zip(&matched)
+            .filter(|(_, m)| !**m)
+            .map(|(s, _)| format!("{} {} {:+}", s.kind, s.target, s.change))
+            .collect();
+
+        Ok(ScenarioResult {
+            scenario_id,
+            portfolio_id,
+            base_currency: impact.base_currency,
+            portfolio_value: impact.portfolio_value,
+            total_impact: impact.total_impact,
+            impact_percent: impact.impact_percent,
+            holdings: impact.affected_holdings,
+            applied,
+            unmatched_shocks,
+        })
+    }
+}
+
+fn canonical_sector(name: &str) -> String {
+    let lower = name.trim().to_lowercase();
+    SECTOR_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == lower)
+        .map(|(_, sector)| sector.to_string())
+        .or_else(|| normalize_sector(&name.to_uppercase()))
+        .unwrap_or_else(|| name.trim().to_string())
+}
+
+fn rate_sensitivity(shock: &ScenarioShock, ticker: &str, sector: Option<&str>) -> f64 {
+    let lookup = |key: &str| {
+        shock.sensitivities
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| *v)
+    };
+    if let Some(v) = lookup(ticker).or_else(|| sector.and_then(lookup)) {
+        return v;
+    }
+    if !shock.sensitivities.is_empty() {
+        return 0.0;
+    }
+    sector
+        .and_then(|s| DEFAULT_RATE_SENSITIVITIES.iter().find(|(name, _)| name.eq_ignore_ascii_case(s)))
+        .map(|(_, v)| *v)
+        .unwrap_or(DEFAULT_RATE_SENSITIVITY)
+}
+
+/// Value factor for a holding in `currency` seen from `base` when the
+/// `pair` rate (1 BASE in QUOTE) moves by `change` percent.
+fn fx_factor_for(pair: &str, change: f64, currency: &str, base: &str) -> Option<f64> {
+    if pair.len() != 6 {
+        return None;
+    }
+    let (pair_base, pair_quote) = pair.split_at(3);
+    let moved = 1.0 + change / 100.0;
+    if currency.eq_ignore_ascii_case(pair_base) && base.eq_ignore_ascii_case(pair_quote) {
+        Some(moved)
+    } else if currency.eq_ignore_ascii_case(pair_quote) && base.eq_ignore_ascii_case(pair_base) && moved > 0.0 {
+        Some(1.0 / moved)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mixed_shorthand() {
+        let shocks = StressTestEngine::parse_shocks("tech -15%, EURUSD -5%, rates +50bp, NVDA -20%").unwrap();
+        let kinds: Vec<&str> = shocks.iter().map(|s| s.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["sector", "fx", "rates", "ticker"]);
+        assert_eq!(shocks[0].target, "Technology");
+        assert_eq!(shocks[2].change, 50.0);
+    }
+
+    #[test]
+    fn fx_shock_direction_follows_pair() {
+        // EUR holding in a USD portfolio loses when EURUSD falls
+        assert_eq!(fx_factor_for("EURUSD", -5.0, "EUR", "USD"), Some(0.95));
+        // USD holding in a EUR portfolio gains
+        assert!(fx_factor_for("EURUSD", -5.0, "USD", "EUR").unwrap() > 1.0);
+        assert_eq!(fx_factor_for("EURUSD", -5.0, "GBP", "USD"), None);
+    }
+}
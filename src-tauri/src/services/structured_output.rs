@@ -0,0 +1,261 @@
+use crate::providers::ollama::{ChatMessage, OllamaProvider};
+use crate::services::llm_metering::LlmMeter;
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Calls per request, counting the first, before giving up on a model
+/// that keeps answering off-schema.
+const MAX_ATTEMPTS: usize = 3;
+
+/// The JSON a response was meant to be: code fences and prose around it
+/// are dropped, and common slips (trailing commas, smart quotes, brackets
+/// left open by a cut-off answer) are repaired.
+pub fn extract_json(response: &str) -> Option<Value> {
+    let trimmed = response.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Some(value);
+    }
+    let start = trimmed.find(['{', '['])?;
+    let end = trimmed.rfind(['}', ']']).filter(|end| *end > start).map(|end| end + 1).unwrap_or(trimmed.len());
+    let candidate = &trimmed[start..end];
+    serde_json::from_str(candidate)
+        .ok()
+        .or_else(|| serde_json::from_str(&repair(&trimmed[start..])).ok())
+}
+
+fn repair(text: &str) -> String {
+    let text = text.replace(['\u{201c}', '\u{201d}'], "\"");
+    let mut out = String::with_capacity(text.len());
+    let mut open = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in text.chars() {
+        if in_string {
+            out.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => open.push('}'),
+            '[' => open.push(']'),
+            '}' | ']' => {
+                // A trailing comma before the close
+                let kept = out.trim_end().len();
+                if out[..kept].ends_with(',') {
+                    out.truncate(kept - 1);
+                }
+                if open.pop() != Some(c) {
+                    // Anything after the outermost value closes is prose
+                    break;
+                }
+            }
+            _ => {}
+        }
+        out.push(c);
+        if open.is_empty() && matches!(c, '}' | ']') {
+            return out;
+        }
+    }
+
+    if in_string {
+        out.push('"');
+    }
+    let kept = out.trim_end().len();
+    if out[..kept].ends_with(',') {
+        out.truncate(kept - 1);
+    }
+    while let Some(close) = open.pop() {
+        out.push(close);
+    }
+    out
+}
+
+fn type_matches(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// Check `value` against the subset of JSON Schema the pipelines use:
+/// type, properties, required, additionalProperties: false, items, enum,
+/// minItems/maxItems, minLength, minimum/maximum. Returns one message per
+/// violation, each with the path to the offending value.
+pub fn validate(value: &Value, schema: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at("$", value, schema, &mut errors);
+    errors
+}
+
+fn validate_at(path: &str, value: &Value, schema: &Value, errors: &mut Vec<String>) {
+    let types: Vec<&str> = match &schema["type"] {
+        Value::String(t) => vec![t.as_str()],
+        Value::Array(ts) => ts.iter().filter_map(|t| t.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && !types.iter().any(|t| type_matches(value, t)) {
+        errors.push(format!("{}: expected {}", path, types.join(" or ")));
+        return;
+    }
+    if let Some(allowed) = schema["enum"].as_array() {
+        if !allowed.contains(value) {
+            errors.push(format!("{}: must be one of {}", path, Value::Array(allowed.clone())));
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            for key in schema["required"].as_array().into_iter().flatten().filter_map(|k| k.as_str()) {
+                if !object.contains_key(key) {
+                    errors.push(format!("{}: missing required property \"{}\"", path, key));
+                }
+            }
+            let properties = schema["properties"].as_object();
+            for (key, child) in object {
+                match properties.and_then(|p| p.get(key)) {
+                    Some(child_schema) => validate_at(&format!("{}.{}", path, key), child, child_schema, errors),
+                    None if schema["additionalProperties"] == Value::Bool(false) => {
+                        errors.push(format!("{}: unexpected property \"{}\"", path, key));
+                    }
+                    None => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema["minItems"].as_u64() {
+                if (items.len() as u64) < min {
+                    errors.push(format!("{}: expected at least {} items", path, min));
+                }
+            }
+            if let Some(max) = schema["maxItems"].as_u64() {
+                if items.len() as u64 > max {
+                    errors.push(format!("{}: expected at most {} items", path, max));
+                }
+            }
+            if schema["items"].is_object() {
+                for (i, item) in items.iter().enumerate() {
+                    validate_at(&format!("{}[{}]", path, i), item, &schema["items"], errors);
+                }
+            }
+        }
+        Value::String(s) => {
+            if let Some(min) = schema["minLength"].as_u64() {
+                if (s.chars().count() as u64) < min {
+                    errors.push(format!("{}: expected at least {} characters", path, min));
+                }
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            if schema["minimum"].as_f64().is_some_and(|min| n < min) {
+                errors.push(format!("{}: below minimum {}", path, schema["minimum"]));
+            }
+            if schema["maximum"].as_f64().is_some_and(|max| n > max) {
+                errors.push(format!("{}: above maximum {}", path, schema["maximum"]));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// JSON output from LLM calls: the schema constrains sampling where the
+/// backend supports it (Ollama's `format`), the answer is repaired and
+/// validated, and the model is asked again with the errors when it still
+/// doesn't match.
+pub struct StructuredOutput;
+
+impl StructuredOutput {
+    pub async fn ollama_chat<T: DeserializeOwned>(
+        meter: &LlmMeter,
+        provider: &OllamaProvider,
+        model: &str,
+        mut messages: Vec<ChatMessage>,
+        schema: &Value,
+    ) -> Result<T> {
+        let mut errors = Vec::new();
+        for _ in 0..MAX_ATTEMPTS {
+            let response = meter
+                .ollama_chat_with_format(provider, model, messages.clone(), Some(schema.clone()))
+                .await?;
+
+            errors = match extract_json(&response) {
+                Some(value) => {
+                    let violations = validate(&value, schema);
+                    if violations.is_empty() {
+                        match serde_json::from_value(value) {
+                            Ok(parsed) => return Ok(parsed),
+                            Err(e) => vec![e.to_string()],
+                        }
+                    } else {
+                        violations
+                    }
+                }
+                None => vec!["response is not JSON".to_string()],
+            };
+
+            messages.push(ChatMessage { role: "assistant".to_string(), content: response });
+            messages.push(ChatMessage {
+                role: "user".to_string(),
+                content: format!(
+                    "That response doesn't match the required JSON schema:\n- {}\n\nRespond with only JSON matching this schema:\n{}",
+                    errors.join("\n- "),
+                    schema
+                ),
+            });
+        }
+        anyhow::bail!(
+            "Model output didn't match the schema after {} attempts: {}",
+            MAX_ATTEMPTS,
+            errors.join("; ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn repairs_and_validates_model_json() {
+        let schema = json!({
+            "type": "object",
+            "required": ["summary", "tickers"],
+            "properties": {
+                "summary": { "type": "string", "minLength": 1 },
+                "tickers": { "type": "array", "items": { "type": "string" } },
+                "tone": { "enum": ["positive", "negative", "neutral"] }
+            }
+        });
+
+        let fenced = "Here you go:\n```json\n{\"summary\": \"Rates held\", \"tickers\": [\"TLT\",],}\n```";
+        let value = extract_json(fenced).unwrap();
+        assert_eq!(value, json!({ "summary": "Rates held", "tickers": ["TLT"] }));
+        assert!(validate(&value, &schema).is_empty());
+
+        // Cut off mid-answer
+        let truncated = extract_json("{\"summary\": \"Rates held\", \"tickers\": [\"TLT\", \"IE").unwrap();
+        assert_eq!(truncated["tickers"], json!(["TLT", "IE"]));
+
+        let errors = validate(&json!({ "tickers": [1], "tone": "bullish" }), &schema);
+        assert_eq!(errors, vec![
+            "$: missing required property \"summary\"".to_string(),
+            "$.tickers[0]: expected string".to_string(),
+            "$.tone: must be one of [\"positive\",\"negative\",\"neutral\"]".to_string(),
+        ]);
+    }
+}
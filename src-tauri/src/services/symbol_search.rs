@@ -0,0 +1,225 @@
+use crate::providers::market_data::MarketDataManager;
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::command_palette::fuzzy_score;
+use crate::services::rate_limiter::RateLimiter;
+use crate::storage::market_data::{MarketDataStore, SymbolInfo};
+use crate::storage::stock_news::StockNewsStore;
+use crate::storage::Database;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// How long provider results for a query are reused before asking again.
+const QUERY_TTL_SECS: i64 = 7 * 86_400;
+/// Single characters match too much to be worth a provider call.
+const MIN_PROVIDER_QUERY_LEN: usize = 2;
+const CACHE_CANDIDATES: i64 = 50;
+/// Tickers the user already tracks rank slightly above unknown ones.
+const LOCAL_BONUS: i64 = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolSearchResult {
+    pub symbol: String,
+    pub name: String,
+    pub exchange: Option<String>,
+    pub asset_type: Option<String>,
+    pub region: Option<String>,
+    pub currency: Option<String>,
+    pub source: String, // local|cache|<provider name>
+    pub score: i64,
+}
+
+/// Symbol autocomplete over local tickers, the symbol cache and provider
+/// search APIs.
+pub struct SymbolSearchService;
+
+impl SymbolSearchService {
+    /// Ranked matches for `query`. Providers are asked only when the query
+    /// hasn't been searched within the TTL or `refresh` is set; their results
+    /// are cached, so repeated keystrokes stay local.
+    pub async fn search(
+        db: &Arc<Mutex<Database>>,
+        api_key_manager: &APIKeyManager,
+        rate_limiter: Option<&RateLimiter>,
+        query: &str,
+        limit: usize,
+        refresh: bool,
+    ) -> Result<Vec<SymbolSearchResult>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let conn = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            db_guard.conn.clone()
+        };
+        let store = MarketDataStore::new(conn.clone());
+        let now = chrono::Utc::now().timestamp();
+
+        let stale = store
+            .symbol_search_time(query)?
+            .map(|searched_at| now - searched_at >= QUERY_TTL_SECS)
+            .unwrap_or(true);
+        if query.chars().count() >= MIN_PROVIDER_QUERY_LEN && (refresh || stale) {
+            let manager = MarketDataManager::new(Some(api_key_manager));
+            match manager.search_symbols(query, rate_limiter).await {
+                Ok(found) => {
+                    let symbols: Vec<SymbolInfo> = found
+                        .into_iter()
+                        .map(|m| SymbolInfo {
+                            symbol: m.symbol.to_uppercase(),
+                            name: m.name,
+                            exchange: m.exchange,
+                            asset_type: m.asset_type,
+                            region: m.region,
+                            currency: m.currency,
+                            provider: Some(m.provider),
+                            updated_at: now,
+                        })
+                        .collect();
+                    store.upsert_symbols(&symbols)?;
+                    store.record_symbol_search(query, now)?;
+                }
+                // Offline or rate limited: fall back to what's known locally
+                Err(e) => eprintln!("Symbol search for {} failed: {}", query, e),
+            }
+        }
+
+        let mut candidates: Vec<SymbolSearchResult> = StockNewsStore::new(conn)
+            .list_tickers(None)?
+            .into_iter()
+            .map(|t| SymbolSearchResult {
+                symbol: t.symbol.to_uppercase(),
+                name: t.name,
+                exchange: Some(t.exchange).filter(|e| !e.is_empty()),
+                asset_type: Some("Equity".to_string()),
+                region: None,
+                currency: None,
+                source: "local".to_string(),
+                score: 0,
+            })
+            .collect();
+        candidates.extend(store.search_symbol_directory(query, CACHE_CANDIDATES)?.into_iter().map(|s| {
+            SymbolSearchResult {
+                symbol: s.symbol,
+                name: s.name,
+                exchange: s.exchange,
+                asset_type: s.asset_type,
+                region: s.region,
+                currency: s.currency,
+                source: s.provider.unwrap_or_else(|| "cache".to_string()),
+                score: 0,
+            }
+        }));
+
+        Ok(rank_matches(query, candidates, limit))
+    }
+}
+
+/// Merge candidates by symbol (earlier ones win, later ones fill in missing
+/// metadata), drop non-matches and return the best `limit`.
+fn rank_matches(query: &str, candidates: Vec<SymbolSearchResult>, limit: usize) -> Vec<SymbolSearchResult> {
+    let mut merged: Vec<SymbolSearchResult> = Vec::new();
+    for candidate in candidates {
+        if let Some(existing) = merged.iter_mut().find(|m| m.symbol == candidate.symbol) {
+            existing.exchange = existing.exchange.take().or(candidate.exchange);
+            existing.region = existing.region.take().or(candidate.region);
+            existing.currency = existing.currency.take().or(candidate.currency);
+            continue;
+        }
+        merged.push(candidate);
+    }
+
+    let mut ranked: Vec<SymbolSearchResult> = merged
+        .into_iter()
+        .filter_map(|mut m| {
+            let bonus = if m.source == "local" { LOCAL_BONUS } else { 0 };
+            m.score = match_score(query, &m.symbol, &m.name)? + bonus;
+            Some(m)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.symbol.cmp(&b.symbol)));
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Exact symbol > symbol prefix > name prefix > name word prefix > fuzzy
+/// name match. None when the query doesn't match at all.
+fn match_score(query: &str, symbol: &str, name: &str) -> Option<i64> {
+    let query_upper = query.to_uppercase();
+    let symbol = symbol.to_uppercase();
+    if symbol == query_upper {
+        return Some(1000);
+    }
+    if symbol.starts_with(&query_upper) {
+        // AAPL before AAPL.MX for "AAP"
+        return Some(800 - (symbol.len() - query_upper.len()).min(20) as i64 * 10);
+    }
+
+    let query_lower = query.to_lowercase();
+    let name = name.to_lowercase();
+    if name.starts_with(&query_lower) {
+        return Some(600);
+    }
+    if name
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(&query_lower))
+    {
+        return Some(500);
+    }
+    if name.contains(&query_lower) {
+        return Some(400);
+    }
+    // Fuzzy matches on short queries hit nearly every name
+    if query_lower.chars().count() < 3 {
+        return None;
+    }
+    fuzzy_score(&query_lower, &name).map(|score| score.clamp(0, 80) * 4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(symbol: &str, name: &str, source: &str) -> SymbolSearchResult {
+        SymbolSearchResult {
+            symbol: symbol.to_string(),
+            name: name.to_string(),
+            exchange: None,
+            asset_type: None,
+            region: None,
+            currency: None,
+            source: source.to_string(),
+            score: 0,
+        }
+    }
+
+    #[test]
+    fn exact_and_prefix_symbols_rank_before_name_matches() {
+        let ranked = rank_matches(
+            "app",
+            vec![
+                candidate("APPN", "Appian Corporation", "Yahoo Finance"),
+                candidate("AAPL", "Apple Inc.", "local"),
+                candidate("APP", "AppLovin Corporation", "Yahoo Finance"),
+                candidate("MSFT", "Microsoft Corporation", "local"),
+            ],
+            10,
+        );
+        let symbols: Vec<&str> = ranked.iter().map(|m| m.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["APP", "APPN", "AAPL"]);
+    }
+
+    #[test]
+    fn duplicates_merge_and_keep_the_first_source() {
+        let mut cached = candidate("SAP", "SAP SE", "Alpha Vantage");
+        cached.region = Some("Germany".to_string());
+        let ranked = rank_matches("sap", vec![candidate("SAP", "SAP SE", "local"), cached], 10);
+
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].source, "local");
+        assert_eq!(ranked[0].region.as_deref(), Some("Germany"));
+        assert_eq!(ranked[0].score, 1000 + LOCAL_BONUS);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::services::api_key_manager::APIKeyManager;
+use crate::storage::sync::{SyncApplyReport, SyncChanges, SyncStore};
+use crate::storage::Database;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+
+pub const CONFIG_SYNC_URL: &str = "sync_server_url";
+pub const CONFIG_SYNC_INTERVAL_MINUTES: &str = "sync_interval_minutes";
+const CONFIG_DEVICE_ID: &str = "sync_device_id";
+const CONFIG_LAST_PUSH: &str = "sync_last_push_at";
+const CONFIG_LAST_PULL: &str = "sync_last_pull_at";
+
+/// The bearer token for the sync server lives in APIKeyManager under this provider.
+pub const SYNC_KEY_PROVIDER: &str = "sync";
+
+#[derive(Debug, Serialize)]
+struct SyncRequest<'a> {
+    device_id: &'a str,
+    since: i64,
+    changes: &'a SyncChanges,
+}
+
+/// The server stores what we pushed and answers with everything other
+/// devices pushed after `since`, plus its own clock for the next cursor.
+#[derive(Debug, Deserialize)]
+struct SyncResponse {
+    server_time: i64,
+    #[serde(default)]
+    changes: SyncChanges,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub pushed_feeds: usize,
+    pub pushed_article_states: usize,
+    pub pushed_watchlists: usize,
+    pub pulled: SyncApplyReport,
+    pub server_time: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncStatus {
+    pub server_url: Option<String>,
+    pub interval_minutes: i64,
+    pub device_id: String,
+    pub last_push_at: Option<i64>,
+    pub last_pull_at: Option<i64>,
+    pub has_token: bool,
+}
+
+pub struct SyncClient;
+
+impl SyncClient {
+    pub fn status(db: &Arc<Mutex<Database>>, api_key_manager: &APIKeyManager) -> Result<SyncStatus> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        Ok(SyncStatus {
+            server_url: db_guard.get_config(CONFIG_SYNC_URL)?.filter(|u| !u.trim().is_empty()),
+            interval_minutes: db_guard.get_config(CONFIG_SYNC_INTERVAL_MINUTES)?
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            device_id: Self::device_id(&db_guard)?,
+            last_push_at: db_guard.get_config(CONFIG_LAST_PUSH)?.and_then(|v| v.parse().ok()),
+            last_pull_at: db_guard.get_config(CONFIG_LAST_PULL)?.and_then(|v| v.parse().ok()),
+            has_token: api_key_manager.has_key(SYNC_KEY_PROVIDER)?,
+        })
+    }
+
+    /// Push local changes and pull remote ones in a single round trip.
+    pub async fn sync_once(db: &Arc<Mutex<Database>>, api_key_manager: &APIKeyManager) -> Result<SyncReport> {
+        let (url, device_id, last_push, last_pull, changes, started_at) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let url = db_guard.get_config(CONFIG_SYNC_URL)?
+                .filter(|u| !u.trim().is_empty())
+                .ok_or_else(|| anyhow::anyhow!("Sync server is not configured"))?;
+            let last_push: i64 = db_guard.get_config(CONFIG_LAST_PUSH)?.and_then(|v| v.parse().ok()).unwrap_or(0);
+            let last_pull: i64 = db_guard.get_config(CONFIG_LAST_PULL)?.and_then(|v| v.parse().ok()).unwrap_or(0);
+            let started_at = chrono::Utc::now().timestamp();
+            let changes = SyncStore::new(db_guard.conn.clone()).changes_since(last_push)?;
+            (url, Self::device_id(&db_guard)?, last_push, last_pull, changes, started_at)
+        };
+
+        let mut request = reqwest::Client::new()
+            .post(format!("{}/changes", url.trim_end_matches('/')))
+            .timeout(std::time::Duration::from_secs(30))
+            .json(&SyncRequest { device_id: &device_id, since: last_pull, changes: &changes });
+        if let Some(token) = api_key_manager.get_key_optional(SYNC_KEY_PROVIDER)? {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await.context("Failed to reach sync server")?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Sync server returned {}", response.status()));
+        }
+        let response: SyncResponse = response.json().await.context("Invalid sync server response")?;
+
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let pulled = SyncStore::new(db_guard.conn.clone()).apply(&response.changes)?;
+        // Push cursor uses our clock, pull cursor the server's, so skew between
+        // devices can't make either side miss changes
+        db_guard.set_config(CONFIG_LAST_PUSH, &started_at.max(last_push).to_string())?;
+        db_guard.set_config(CONFIG_LAST_PULL, &response.server_time.to_string())?;
+
+        Ok(SyncReport {
+            pushed_feeds: changes.feeds.len(),
+            pushed_article_states: changes.article_states.len(),
+            pushed_watchlists: changes.watchlists.len(),
+            pulled,
+            server_time: response.server_time,
+        })
+    }
+
+    /// Sync on the configured interval. An interval of 0 (the default) means manual only.
+    pub fn start_scheduler(db: Arc<Mutex<Database>>, api_key_manager: Arc<APIKeyManager>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+            let mut last_run = 0i64;
+
+            loop {
+                interval.tick().await;
+
+                let minutes = match db.lock() {
+                    Ok(db_guard) => db_guard
+                        .get_config(CONFIG_SYNC_INTERVAL_MINUTES)
+                        .ok()
+                        .flatten()
+                        .and_then(|v| v.parse::<i64>().ok())
+                        .unwrap_or(0),
+                    Err(_) => continue,
+                };
+                let now = chrono::Utc::now().timestamp();
+                if minutes <= 0 || now - last_run < minutes * 60 {
+                    continue;
+                }
+                last_run = now;
+
+                match Self::sync_once(&db, &api_key_manager).await {
+                    Ok(report) => {
+                        let _ = crate::services::window_router::emit(&app, serde_json::json!({
+                            "type": "sync-completed",
+                            "data": report,
+                            "timestamp": chrono::Utc::now().timestamp_millis(),
+                        }));
+                    }
+                    Err(e) => eprintln!("Sync failed: {}", e),
+                }
+            }
+        });
+    }
+
+    fn device_id(db: &Database) -> Result<String> {
+        if let Some(id) = db.get_config(CONFIG_DEVICE_ID)? {
+            return Ok(id);
+        }
+        let id = uuid::Uuid::new_v4().to_string();
+        db.set_config(CONFIG_DEVICE_ID, &id)?;
+        Ok(id)
+    }
+}
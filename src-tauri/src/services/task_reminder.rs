@@ -0,0 +1,71 @@
+use crate::services::desktop_notifications::{DesktopNotificationService, NotificationOptions};
+use crate::storage::projects::ProjectStore;
+use crate::storage::Database;
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+
+/// Remind this long before a task is due.
+const REMIND_AHEAD_SECS: i64 = 3600;
+
+pub struct TaskReminder;
+
+impl TaskReminder {
+    /// Start periodic due-date checks for project tasks
+    pub fn start_checking(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(300)); // Check every 5 minutes
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = Self::send_due_reminders(&db, &app).await {
+                    eprintln!("Error sending task reminders: {}", e);
+                }
+            }
+        });
+    }
+
+    async fn send_due_reminders(db: &Arc<Mutex<Database>>, app: &AppHandle) -> Result<()> {
+        let store = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            ProjectStore::new(db_guard.conn.clone())
+        };
+        let now = chrono::Utc::now().timestamp();
+        let tasks = store.list_tasks_needing_reminder(now + REMIND_AHEAD_SECS)?;
+
+        for task in tasks {
+            let due_at = task.due_at.unwrap_or(now);
+            let body = if due_at < now {
+                format!("\"{}\" is overdue", task.title)
+            } else {
+                format!("\"{}\" is due in {} min", task.title, ((due_at - now) / 60).max(1))
+            };
+
+            let _ = crate::services::window_router::emit(app, serde_json::json!({
+                "type": "project-task-due",
+                "data": task,
+                "timestamp": chrono::Utc::now().timestamp_millis(),
+            }));
+
+            let _ = DesktopNotificationService::send(app, NotificationOptions {
+                title: "Task reminder".to_string(),
+                body,
+                icon: Some("task".to_string()),
+                sound: Some("default".to_string()),
+                tag: Some(format!("project-task-{}", task.id)),
+                data: Some(serde_json::json!({
+                    "type": "project_task",
+                    "task_id": task.id,
+                    "project_id": task.project_id,
+                    "due_at": due_at,
+                })),
+            }).await;
+
+            store.mark_task_reminded(task.id)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,395 @@
+use crate::services::fx::FxService;
+use crate::storage::portfolio::PortfolioStore;
+use crate::storage::Database;
+use anyhow::Result;
+use chrono::{Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Which open lots a sale is matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LotMethod {
+    Fifo,
+    Lifo,
+    /// Highest cost first, minimizing realized gains
+    Hifo,
+}
+
+impl LotMethod {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "fifo" => Ok(LotMethod::Fifo),
+            "lifo" => Ok(LotMethod::Lifo),
+            "hifo" => Ok(LotMethod::Hifo),
+            other => Err(anyhow::anyhow!("Unknown lot matching method: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxReportFormat {
+    /// US IRS Form 8949, split into short-term (Part I) and long-term (Part II)
+    Form8949,
+    /// German Anlage KAP: per-sale listing with share gains and losses totalled separately
+    GermanKap,
+}
+
+impl TaxReportFormat {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().replace('-', "_").as_str() {
+            "form_8949" | "form8949" | "8949" => Ok(TaxReportFormat::Form8949),
+            "german_kap" | "kap" => Ok(TaxReportFormat::GermanKap),
+            other => Err(anyhow::anyhow!("Unknown tax report format: {}", other)),
+        }
+    }
+}
+
+/// One buy or sell, already converted to the report currency.
+#[derive(Debug, Clone)]
+pub struct LotTrade {
+    pub ticker: String,
+    pub is_sell: bool,
+    pub quantity: f64,
+    pub price: f64,
+    pub fees: f64,
+    pub date: i64,
+}
+
+/// A sold quantity matched against one purchase lot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealizedLot {
+    pub ticker: String,
+    pub quantity: f64,
+    pub acquired_at: Option<i64>, // None when the sale had no matching purchase
+    pub sold_at: i64,
+    pub proceeds: f64,
+    pub cost_basis: f64,
+    pub gain: f64,
+    pub long_term: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxReport {
+    pub portfolio_id: i64,
+    pub tax_year: i32,
+    pub method: LotMethod,
+    pub currency: String,
+    pub lots: Vec<RealizedLot>,
+    pub total_proceeds: f64,
+    pub total_cost_basis: f64,
+    pub total_gain: f64,
+    pub short_term_gain: f64,
+    pub long_term_gain: f64,
+    pub warnings: Vec<String>,
+}
+
+struct OpenLot {
+    quantity: f64,
+    unit_cost: f64,
+    acquired_at: i64,
+}
+
+const QUANTITY_EPSILON: f64 = 1e-9;
+const LONG_TERM_SECS: i64 = 365 * 86400;
+
+/// Match sales against purchase lots in date order. Buy fees are added to the
+/// lot's cost, sell fees deducted from proceeds. Quantity sold beyond the open
+/// position comes back as a lot with no acquisition date and zero cost basis.
+pub fn match_lots(trades: &[LotTrade], method: LotMethod) -> Vec<RealizedLot> {
+    let mut ordered: Vec<&LotTrade> = trades.iter().collect();
+    ordered.sort_by_key(|t| t.date);
+
+    let mut open: HashMap<String, Vec<OpenLot>> = HashMap::new();
+    let mut realized = Vec::new();
+
+    for trade in ordered {
+        if trade.quantity <= 0.0 {
+            continue;
+        }
+        if !trade.is_sell {
+            open.entry(trade.ticker.clone()).or_default().push(OpenLot {
+                quantity: trade.quantity,
+                unit_cost: trade.price + trade.fees / trade.quantity,
+                acquired_at: trade.date,
+            });
+            continue;
+        }
+
+        let unit_proceeds = trade.price - trade.fees / trade.quantity;
+        let lots = open.entry(trade.ticker.clone()).or_default();
+        let mut remaining = trade.quantity;
+
+        while remaining > QUANTITY_EPSILON {
+            let index = match method {
+                LotMethod::Fifo => (!lots.is_empty()).then_some(0),
+                LotMethod::Lifo => lots.len().checked_sub(1),
+                LotMethod::Hifo => lots
+                    .iter()
+                    .enumerate()
+                    .max_by(|a, b| a.1.unit_cost.partial_cmp(&b.1.unit_cost).unwrap_or(std::cmp::Ordering::Equal))
+                    .map(|(i, _)| i),
+            };
+            let Some(index) = index else {
+                realized.push(RealizedLot {
+                    ticker: trade.ticker.clone(),
+                    quantity: remaining,
+                    acquired_at: None,
+                    sold_at: trade.date,
+                    proceeds: remaining * unit_proceeds,
+                    cost_basis: 0.0,
+                    gain: remaining * unit_proceeds,
+                    long_term: false,
+                });
+                break;
+            };
+
+            let lot = &mut lots[index];
+            let quantity = remaining.min(lot.quantity);
+            let proceeds = quantity * unit_proceeds;
+            let cost_basis = quantity * lot.unit_cost;
+            realized.push(RealizedLot {
+                ticker: trade.ticker.clone(),
+                quantity,
+                acquired_at: Some(lot.acquired_at),
+                sold_at: trade.date,
+                proceeds,
+                cost_basis,
+                gain: proceeds - cost_basis,
+                long_term: trade.date - lot.acquired_at > LONG_TERM_SECS,
+            });
+
+            lot.quantity -= quantity;
+            remaining -= quantity;
+            if lot.quantity <= QUANTITY_EPSILON {
+                lots.remove(index);
+            }
+        }
+    }
+
+    realized
+}
+
+pub struct TaxLotService;
+
+impl TaxLotService {
+    /// Realized gains for sales in `tax_year`, in `currency` (the portfolio's
+    /// base currency when None). Each trade is converted at its own date's
+    /// reference rate; trades are assumed to be in their holding's currency.
+    pub async fn realized_gains(
+        db: &Arc<Mutex<Database>>,
+        portfolio_id: i64,
+        tax_year: i32,
+        method: LotMethod,
+        currency: Option<&str>,
+    ) -> Result<TaxReport> {
+        let (portfolio, holdings, transactions) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let store = PortfolioStore::new(db_guard.conn.clone());
+            let portfolio = store.get_portfolio(portfolio_id)?
+                .ok_or_else(|| anyhow::anyhow!("Portfolio {} not found", portfolio_id))?;
+            (portfolio, store.list_holdings(portfolio_id)?, store.list_all_transactions(portfolio_id)?)
+        };
+
+        let report_currency = currency
+            .map(|c| c.trim().to_uppercase())
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(|| portfolio.base_currency.clone());
+        let holding_currency: HashMap<String, String> = holdings
+            .into_iter()
+            .map(|h| (h.ticker.to_uppercase(), h.currency))
+            .collect();
+
+        let year_end = Utc.with_ymd_and_hms(tax_year + 1, 1, 1, 0, 0, 0)
+            .single()
+            .ok_or_else(|| anyhow::anyhow!("Invalid tax year {}", tax_year))?
+            .timestamp();
+
+        let mut trades = Vec::new();
+        let mut rates: HashMap<(String, String), f64> = HashMap::new();
+        for tx in transactions.into_iter().filter(|t| t.transaction_date < year_end) {
+            let is_sell = match tx.transaction_type.to_lowercase().as_str() {
+                "buy" => false,
+                "sell" => true,
+                _ => continue,
+            };
+            let ticker = tx.ticker.to_uppercase();
+            let trade_currency = holding_currency
+                .get(&ticker)
+                .cloned()
+                .unwrap_or_else(|| portfolio.base_currency.clone());
+
+            let rate = if trade_currency == report_currency {
+                1.0
+            } else {
+                let date = format_date(tx.transaction_date, "%Y-%m-%d");
+                let key = (trade_currency.clone(), date.clone());
+                match rates.get(&key) {
+                    Some(rate) => *rate,
+                    None => {
+                        let rate = FxService::get_rate(db, &trade_currency, &report_currency, Some(&date))
+                            .await?
+                            .rate;
+                        rates.insert(key, rate);
+                        rate
+                    }
+                }
+            };
+
+            trades.push(LotTrade {
+                ticker,
+                is_sell,
+                quantity: tx.quantity,
+                price: tx.price * rate,
+                fees: tx.fees * rate,
+                date: tx.transaction_date,
+            });
+        }
+
+        let lots: Vec<RealizedLot> = match_lots(&trades, method)
+            .into_iter()
+            .filter(|lot| year_of(lot.sold_at) == Some(tax_year))
+            .collect();
+        let warnings: Vec<String> = lots
+            .iter()
+            .filter(|lot| lot.acquired_at.is_none())
+            .map(|lot| format!(
+                "Sold {} more {} than held on {}; reported with zero cost basis",
+                format_quantity(lot.quantity), lot.ticker, format_date(lot.sold_at, "%Y-%m-%d")
+            ))
+            .collect();
+
+        let total_proceeds: f64 = lots.iter().map(|l| l.proceeds).sum();
+        let total_cost_basis: f64 = lots.iter().map(|l| l.cost_basis).sum();
+        let short_term_gain: f64 = lots.iter().filter(|l| !l.long_term).map(|l| l.gain).sum();
+        let long_term_gain: f64 = lots.iter().filter(|l| l.long_term).map(|l| l.gain).sum();
+
+        Ok(TaxReport {
+            portfolio_id,
+            tax_year,
+            method,
+            currency: report_currency,
+            total_proceeds,
+            total_cost_basis,
+            total_gain: total_proceeds - total_cost_basis,
+            short_term_gain,
+            long_term_gain,
+            lots,
+            warnings,
+        })
+    }
+
+    pub fn to_csv(report: &TaxReport, format: TaxReportFormat) -> String {
+        match format {
+            TaxReportFormat::Form8949 => Self::form_8949_csv(report),
+            TaxReportFormat::GermanKap => Self::german_kap_csv(report),
+        }
+    }
+
+    fn form_8949_csv(report: &TaxReport) -> String {
+        let mut csv = String::from("Part,Description,Date Acquired,Date Sold,Proceeds,Cost Basis,Code,Adjustment,Gain or Loss\n");
+        let mut lots: Vec<&RealizedLot> = report.lots.iter().collect();
+        // Part I (short-term) rows come before Part II (long-term)
+        lots.sort_by_key(|l| (l.long_term, l.sold_at));
+        for lot in lots {
+            csv.push_str(&format!(
+                "{},\"{} sh {}\",{},{},{:.2},{:.2},,,{:.2}\n",
+                if lot.long_term { "II" } else { "I" },
+                format_quantity(lot.quantity),
+                lot.ticker,
+                lot.acquired_at.map(|t| format_date(t, "%m/%d/%Y")).unwrap_or_else(|| "VARIOUS".to_string()),
+                format_date(lot.sold_at, "%m/%d/%Y"),
+                lot.proceeds,
+                lot.cost_basis,
+                lot.gain,
+            ));
+        }
+        csv
+    }
+
+    fn german_kap_csv(report: &TaxReport) -> String {
+        let mut csv = String::from("Wertpapier;Stück;Anschaffungsdatum;Veräußerungsdatum;Veräußerungserlös;Anschaffungskosten;Gewinn/Verlust;Währung\n");
+        let mut lots: Vec<&RealizedLot> = report.lots.iter().collect();
+        lots.sort_by_key(|l| l.sold_at);
+        for lot in lots {
+            csv.push_str(&format!(
+                "{};{};{};{};{};{};{};{}\n",
+                lot.ticker,
+                format_quantity(lot.quantity).replace('.', ","),
+                lot.acquired_at.map(|t| format_date(t, "%d.%m.%Y")).unwrap_or_default(),
+                format_date(lot.sold_at, "%d.%m.%Y"),
+                german_amount(lot.proceeds),
+                german_amount(lot.cost_basis),
+                german_amount(lot.gain),
+                report.currency,
+            ));
+        }
+
+        // Share gains and losses are declared separately on Anlage KAP
+        let gains: f64 = report.lots.iter().filter(|l| l.gain > 0.0).map(|l| l.gain).sum();
+        let losses: f64 = report.lots.iter().filter(|l| l.gain < 0.0).map(|l| -l.gain).sum();
+        csv.push('\n');
+        csv.push_str(&format!("Summe Gewinne aus Aktienveräußerungen;;;;;;{};{}\n", german_amount(gains), report.currency));
+        csv.push_str(&format!("Summe Verluste aus Aktienveräußerungen;;;;;;{};{}\n", german_amount(losses), report.currency));
+        csv
+    }
+}
+
+fn format_date(ts: i64, fmt: &str) -> String {
+    Utc.timestamp_opt(ts, 0)
+        .single()
+        .map(|d| d.format(fmt).to_string())
+        .unwrap_or_default()
+}
+
+fn year_of(ts: i64) -> Option<i32> {
+    Utc.timestamp_opt(ts, 0).single().map(|d| d.year())
+}
+
+fn format_quantity(quantity: f64) -> String {
+    let formatted = format!("{:.6}", quantity);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn german_amount(amount: f64) -> String {
+    format!("{:.2}", amount).replace('.', ",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(is_sell: bool, quantity: f64, price: f64, date: i64) -> LotTrade {
+        LotTrade { ticker: "AAPL".to_string(), is_sell, quantity, price, fees: 0.0, date }
+    }
+
+    #[test]
+    fn fifo_and_hifo_pick_different_lots() {
+        let trades = vec![
+            trade(false, 10.0, 100.0, 0),
+            trade(false, 10.0, 150.0, 86400),
+            trade(true, 10.0, 160.0, 2 * 86400),
+        ];
+
+        let fifo = match_lots(&trades, LotMethod::Fifo);
+        assert_eq!(fifo.len(), 1);
+        assert!((fifo[0].gain - 600.0).abs() < 1e-6);
+
+        let hifo = match_lots(&trades, LotMethod::Hifo);
+        assert!((hifo[0].gain - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn oversold_quantity_is_flagged() {
+        let trades = vec![trade(false, 5.0, 100.0, 0), trade(true, 8.0, 120.0, 86400)];
+
+        let lots = match_lots(&trades, LotMethod::Fifo);
+        assert_eq!(lots.len(), 2);
+        assert!(lots[1].acquired_at.is_none());
+        assert_eq!(lots[1].cost_basis, 0.0);
+        assert!((lots[1].quantity - 3.0).abs() < 1e-9);
+    }
+}
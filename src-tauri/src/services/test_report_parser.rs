@@ -0,0 +1,310 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParsedTestCase {
+    pub name: String,
+    pub status: String, // "passed", "failed", "skipped"
+    pub duration: Option<f64>, // seconds
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TestReportFormat {
+    JUnit,
+    LlvmLit,
+    /// libtest JSON events (`cargo test -- -Z unstable-options --format json`)
+    CargoJson,
+}
+
+impl TestReportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format.to_lowercase().as_str() {
+            "junit" | "xml" => Some(TestReportFormat::JUnit),
+            "llvm" | "lit" | "llvm-lit" => Some(TestReportFormat::LlvmLit),
+            "cargo" | "cargo-json" | "libtest" => Some(TestReportFormat::CargoJson),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TestReportFormat::JUnit => "junit",
+            TestReportFormat::LlvmLit => "llvm",
+            TestReportFormat::CargoJson => "cargo",
+        }
+    }
+
+    /// Guess the format from report content.
+    pub fn detect(content: &str) -> Self {
+        if content.contains("<testsuite") || content.contains("<testcase") {
+            TestReportFormat::JUnit
+        } else if content.lines().any(|l| cargo_event(l).is_some()) {
+            TestReportFormat::CargoJson
+        } else {
+            TestReportFormat::LlvmLit
+        }
+    }
+}
+
+pub fn parse_report(content: &str, format: TestReportFormat) -> Result<Vec<ParsedTestCase>, String> {
+    match format {
+        TestReportFormat::JUnit => parse_junit(content),
+        TestReportFormat::LlvmLit => parse_llvm_lit(content),
+        TestReportFormat::CargoJson => parse_cargo_json(content),
+    }
+}
+
+/// Parse JUnit XML (as written by cargo-nextest, jest-junit, pytest, gradle, ...).
+/// Only `<testcase>` elements and their failure/error/skipped children are read.
+pub fn parse_junit(content: &str) -> Result<Vec<ParsedTestCase>, String> {
+    let case_re = Regex::new(r"(?s)<testcase\b([^>]*?)(?:/>|>(.*?)</testcase>)")
+        .map_err(|e| e.to_string())?;
+    let attr_re = Regex::new(r#"([\w:.-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+        .map_err(|e| e.to_string())?;
+    let failure_re = Regex::new(r"(?s)<(failure|error)\b([^>]*?)(?:/>|>(.*?)</(?:failure|error)>)")
+        .map_err(|e| e.to_string())?;
+    let skipped_re = Regex::new(r"<skipped\b").map_err(|e| e.to_string())?;
+
+    let mut cases = Vec::new();
+    for caps in case_re.captures_iter(content) {
+        let attrs = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+        let body = caps.get(2).map(|m| m.as_str()).unwrap_or("");
+
+        let mut name = None;
+        let mut classname = None;
+        let mut time = None;
+        for attr in attr_re.captures_iter(attrs) {
+            let value = attr.get(2).or_else(|| attr.get(3)).map(|m| xml_unescape(m.as_str()));
+            match &attr[1] {
+                "name" => name = value,
+                "classname" => classname = value,
+                "time" => time = value.and_then(|v| v.parse::<f64>().ok()),
+                _ => {}
+            }
+        }
+
+        let name = match name {
+            Some(n) => n,
+            None => continue,
+        };
+        let full_name = match classname {
+            Some(c) if !c.is_empty() && !name.starts_with(&c) => format!("{}::{}", c, name),
+            _ => name,
+        };
+
+        let (status, error) = if let Some(failure) = failure_re.captures(body) {
+            let message = attr_re
+                .captures_iter(failure.get(2).map(|m| m.as_str()).unwrap_or(""))
+                .find(|a| &a[1] == "message")
+                .and_then(|a| a.get(2).or_else(|| a.get(3)).map(|m| xml_unescape(m.as_str())));
+            let text = failure.get(3).map(|m| xml_unescape(strip_cdata(m.as_str()).trim()));
+            let error = match (message, text) {
+                (Some(m), Some(t)) if !t.is_empty() => format!("{}\n{}", m, t),
+                (Some(m), _) => m,
+                (None, Some(t)) => t,
+                (None, None) => failure[1].to_string(),
+            };
+            ("failed".to_string(), Some(error))
+        } else if skipped_re.is_match(body) {
+            ("skipped".to_string(), None)
+        } else {
+            ("passed".to_string(), None)
+        };
+
+        cases.push(ParsedTestCase {
+            name: full_name,
+            status,
+            duration: time,
+            error,
+        });
+    }
+
+    if cases.is_empty() && !content.contains("<testsuite") {
+        return Err("No JUnit test cases found".to_string());
+    }
+    Ok(cases)
+}
+
+/// Parse LLVM `lit` console output (`PASS: Suite :: path/test.ll (1 of 20)`).
+/// Failure details come from the `TEST '...' FAILED` blocks lit prints.
+pub fn parse_llvm_lit(content: &str) -> Result<Vec<ParsedTestCase>, String> {
+    let line_re = Regex::new(
+        r"(?m)^(PASS|FAIL|XFAIL|XPASS|UNSUPPORTED|UNRESOLVED|TIMEOUT|FLAKYPASS|SKIPPED): (.+?)(?: \(\d+ of \d+\))?\s*$",
+    )
+    .map_err(|e| e.to_string())?;
+    let failed_block_re = Regex::new(r"(?s)\*{10,} TEST '(.+?)' FAILED \*{10,}\n(.*?)\n\*{10,}")
+        .map_err(|e| e.to_string())?;
+
+    let mut failure_details = std::collections::HashMap::new();
+    for block in failed_block_re.captures_iter(content) {
+        failure_details.insert(block[1].to_string(), block[2].trim().to_string());
+    }
+
+    let mut cases = Vec::new();
+    for caps in line_re.captures_iter(content) {
+        let name = caps[2].trim().to_string();
+        let status = match &caps[1] {
+            "PASS" | "XFAIL" | "FLAKYPASS" => "passed",
+            "UNSUPPORTED" | "SKIPPED" => "skipped",
+            _ => "failed",
+        };
+        let error = if status == "failed" {
+            Some(
+                failure_details
+                    .get(&name)
+                    .cloned()
+                    .unwrap_or_else(|| caps[1].to_string()),
+            )
+        } else {
+            None
+        };
+        cases.push(ParsedTestCase {
+            name,
+            status: status.to_string(),
+            duration: None,
+            error,
+        });
+    }
+
+    if cases.is_empty() {
+        return Err("No lit test results found".to_string());
+    }
+    Ok(cases)
+}
+
+/// Parse libtest's JSON event stream (`cargo test -- -Z unstable-options
+/// --format json`). Only per-test results are read; other lines (suite
+/// events, compiler output) are skipped.
+pub fn parse_cargo_json(content: &str) -> Result<Vec<ParsedTestCase>, String> {
+    let mut cases = Vec::new();
+    for event in content.lines().filter_map(cargo_event) {
+        let status = match event.get("event").and_then(|e| e.as_str()) {
+            Some("ok") => "passed",
+            Some("failed") | Some("timeout") => "failed",
+            Some("ignored") => "skipped",
+            _ => continue, // "started"
+        };
+        let name = match event.get("name").and_then(|n| n.as_str()) {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        let error = if status == "failed" {
+            let output = event
+                .get("stdout")
+                .or_else(|| event.get("message"))
+                .and_then(|o| o.as_str())
+                .map(|o| o.trim().to_string())
+                .filter(|o| !o.is_empty());
+            Some(output.unwrap_or_else(|| "failed".to_string()))
+        } else {
+            None
+        };
+        cases.push(ParsedTestCase {
+            name,
+            status: status.to_string(),
+            duration: event.get("exec_time").and_then(|t| t.as_f64()),
+            error,
+        });
+    }
+
+    if cases.is_empty() {
+        return Err("No cargo test results found".to_string());
+    }
+    Ok(cases)
+}
+
+/// A libtest JSON line describing a single test.
+fn cargo_event(line: &str) -> Option<serde_json::Value> {
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return None;
+    }
+    let event: serde_json::Value = serde_json::from_str(line).ok()?;
+    (event.get("type").and_then(|t| t.as_str()) == Some("test")).then_some(event)
+}
+
+fn strip_cdata(text: &str) -> &str {
+    text.trim()
+        .strip_prefix("<![CDATA[")
+        .and_then(|t| t.strip_suffix("]]>"))
+        .unwrap_or(text)
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_junit() {
+        let xml = r#"<?xml version="1.0"?>
+<testsuites>
+  <testsuite name="core" tests="3">
+    <testcase classname="core::math" name="adds" time="0.012"/>
+    <testcase classname="core::math" name="divides" time="0.5">
+      <failure message="assertion failed">left: 1 &amp; right: 2</failure>
+    </testcase>
+    <testcase name="ignored"><skipped/></testcase>
+  </testsuite>
+</testsuites>"#;
+        let cases = parse_junit(xml).unwrap();
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].name, "core::math::adds");
+        assert_eq!(cases[0].status, "passed");
+        assert_eq!(cases[0].duration, Some(0.012));
+        assert_eq!(cases[1].status, "failed");
+        assert_eq!(cases[1].error.as_deref(), Some("assertion failed\nleft: 1 & right: 2"));
+        assert_eq!(cases[2].status, "skipped");
+    }
+
+    #[test]
+    fn test_parse_llvm_lit() {
+        let output = "PASS: LLVM :: CodeGen/add.ll (1 of 3)
+******************** TEST 'LLVM :: CodeGen/sub.ll' FAILED ********************
+error: CHECK: expected string not found
+********************
+FAIL: LLVM :: CodeGen/sub.ll (2 of 3)
+UNSUPPORTED: LLVM :: CodeGen/gpu.ll (3 of 3)
+";
+        let cases = parse_llvm_lit(output).unwrap();
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].status, "passed");
+        assert_eq!(cases[1].name, "LLVM :: CodeGen/sub.ll");
+        assert_eq!(cases[1].error.as_deref(), Some("error: CHECK: expected string not found"));
+        assert_eq!(cases[2].status, "skipped");
+    }
+
+    #[test]
+    fn test_parse_cargo_json() {
+        let output = r#"   Compiling mina v0.1.0
+{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "started", "name": "math::adds" }
+{ "type": "test", "name": "math::adds", "event": "ok", "exec_time": 0.002 }
+{ "type": "test", "event": "started", "name": "math::divides" }
+{ "type": "test", "name": "math::divides", "event": "failed", "exec_time": 0.01, "stdout": "thread 'math::divides' panicked at src/math.rs:9:5:\nassertion failed\n" }
+{ "type": "test", "name": "math::slow", "event": "ignored" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 1, "ignored": 1, "exec_time": 0.02 }
+"#;
+        assert_eq!(TestReportFormat::detect(output), TestReportFormat::CargoJson);
+        let cases = parse_cargo_json(output).unwrap();
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].name, "math::adds");
+        assert_eq!(cases[0].status, "passed");
+        assert_eq!(cases[0].duration, Some(0.002));
+        assert_eq!(cases[1].status, "failed");
+        assert_eq!(
+            cases[1].error.as_deref(),
+            Some("thread 'math::divides' panicked at src/math.rs:9:5:\nassertion failed")
+        );
+        assert_eq!(cases[2].status, "skipped");
+    }
+}
@@ -0,0 +1,161 @@
+use crate::services::test_report_parser::{self, ParsedTestCase, TestReportFormat};
+use crate::storage::testing::{TestRun, TestingStore};
+use crate::storage::Database;
+use anyhow::{Context, Result};
+use std::sync::{Arc, Mutex};
+use tokio::process::Command;
+
+const DEFAULT_TIMEOUT_SECS: u64 = 1800;
+
+pub const CONFIG_AUTO_QUARANTINE: &str = "testing_auto_quarantine";
+pub const FLAKY_WINDOW: usize = 20;
+pub const FLAKY_MIN_FLIPS: usize = 2;
+
+struct TestRunRequest {
+    suite_id: i64,
+    program: String,
+    args: Vec<String>,
+    working_dir: Option<String>,
+    report_path: Option<String>,
+    format: Option<TestReportFormat>,
+    timeout_secs: Option<u64>,
+}
+
+pub struct TestRunner;
+
+impl TestRunner {
+    /// Run the command configured for the suite and store its results as one run.
+    pub async fn run_suite(db: Arc<Mutex<Database>>, suite_id: i64) -> Result<TestRun> {
+        let command = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            TestingStore::new(db_guard.conn.clone())
+                .get_suite_command(suite_id)?
+                .ok_or_else(|| anyhow::anyhow!("No test command configured for suite {}", suite_id))?
+        };
+        let format = match command.format.as_deref() {
+            None | Some("auto") => None,
+            Some(f) => Some(TestReportFormat::parse(f).ok_or_else(|| anyhow::anyhow!("Unknown report format: {}", f))?),
+        };
+        Self::run(db, TestRunRequest {
+            suite_id,
+            program: command.program,
+            args: command.args,
+            working_dir: command.working_dir,
+            report_path: command.report_path,
+            format,
+            timeout_secs: command.timeout_secs,
+        })
+        .await
+    }
+
+    /// Run a test command, parse its output and store the results as one run.
+    async fn run(db: Arc<Mutex<Database>>, request: TestRunRequest) -> Result<TestRun> {
+        let started_at = chrono::Utc::now().timestamp();
+        let command_line = std::iter::once(request.program.clone())
+            .chain(request.args.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut command = Command::new(&request.program);
+        command.args(&request.args).kill_on_drop(true);
+        if let Some(dir) = &request.working_dir {
+            command.current_dir(dir);
+        }
+
+        let timeout = std::time::Duration::from_secs(request.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+        let output = tokio::time::timeout(timeout, command.output())
+            .await
+            .map_err(|_| anyhow::anyhow!("Test command timed out after {}s", timeout.as_secs()))?
+            .with_context(|| format!("Failed to execute {}", request.program))?;
+
+        let content = match &request.report_path {
+            Some(path) => {
+                let path = match &request.working_dir {
+                    Some(dir) if !std::path::Path::new(path).is_absolute() => std::path::Path::new(dir).join(path),
+                    _ => std::path::PathBuf::from(path),
+                };
+                tokio::fs::read_to_string(&path)
+                    .await
+                    .with_context(|| format!("Failed to read report {}", path.display()))?
+            }
+            None => format!(
+                "{}\n{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ),
+        };
+
+        let format = request.format.unwrap_or_else(|| TestReportFormat::detect(&content));
+        let cases = test_report_parser::parse_report(&content, format)
+            .map_err(|e| anyhow::anyhow!("Failed to parse test output: {}", e))?;
+
+        Self::store_results(&db, request.suite_id, &command_line, format, output.status.code(), started_at, &cases)
+    }
+
+    /// Store an already-produced report (e.g. uploaded from CI) as a run.
+    pub fn import(db: &Arc<Mutex<Database>>, suite_id: i64, content: &str, format: Option<TestReportFormat>) -> Result<TestRun> {
+        let format = format.unwrap_or_else(|| TestReportFormat::detect(content));
+        let cases = test_report_parser::parse_report(content, format)
+            .map_err(|e| anyhow::anyhow!("Failed to parse test report: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        Self::store_results(db, suite_id, "import", format, None, now, &cases)
+    }
+
+    fn store_results(
+        db: &Arc<Mutex<Database>>,
+        suite_id: i64,
+        command_line: &str,
+        format: TestReportFormat,
+        exit_code: Option<i32>,
+        started_at: i64,
+        cases: &[ParsedTestCase],
+    ) -> Result<TestRun> {
+        let db_guard = db.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let store = TestingStore::new(db_guard.conn.clone());
+        let run = store.save_test_run(suite_id, command_line, format.name(), exit_code, started_at, cases)?;
+
+        let quarantined: std::collections::HashSet<String> = store
+            .list_quarantined(Some(suite_id))?
+            .into_iter()
+            .map(|q| q.test_name)
+            .collect();
+
+        // Surface failures on the error dashboard, one entry per run. Quarantined
+        // tests still get recorded but don't raise errors.
+        let failed_names: Vec<&str> = cases
+            .iter()
+            .filter(|c| c.status == "failed" && !quarantined.contains(&c.name))
+            .map(|c| c.name.as_str())
+            .collect();
+        if !failed_names.is_empty() {
+            let suite_name = store.get_suite_name(suite_id)?
+                .unwrap_or_else(|| format!("Suite #{}", suite_id));
+            let listed: Vec<&str> = failed_names.iter().take(20).copied().collect();
+            if let Err(e) = db_guard.save_error(
+                "Test Failure",
+                &format!("{} test(s) failed in suite '{}'", failed_names.len(), suite_name),
+                Some(&listed.join("\n")),
+                Some(&format!("TestingCenter:{}", suite_name)),
+                "error",
+            ) {
+                eprintln!("Warning: Failed to log test failures to error dashboard: {}", e);
+            }
+        }
+
+        let auto_quarantine = db_guard.get_config(CONFIG_AUTO_QUARANTINE)?
+            .map(|v| v == "true")
+            .unwrap_or(false);
+        if auto_quarantine {
+            for flaky in store.detect_flaky_tests(suite_id, FLAKY_WINDOW, FLAKY_MIN_FLIPS)? {
+                if !flaky.quarantined {
+                    let reason = format!("Auto-quarantined: {} flips in last {} runs", flaky.flips, flaky.runs);
+                    store.quarantine_test(suite_id, &flaky.name, Some(&reason), true)?;
+                }
+            }
+        }
+
+        Ok(run)
+    }
+}
@@ -0,0 +1,260 @@
+use crate::storage::temporal::{TemporalStore, ThresholdAdjustment};
+use crate::storage::Database;
+use anyhow::Result;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// Scores whose thresholds auto-tuning may move
+pub const TUNABLE_SCORES: &[&str] = &["severity", "volume", "sentiment"];
+
+/// A rule is tuned at most once per this period
+pub const TUNE_INTERVAL_SECS: i64 = 7 * 86400;
+/// Fewer labels than this since the last tuning are too few to judge by
+pub const MIN_LABELS: i64 = 5;
+/// How far one tuning moves a threshold
+pub const STEP: f64 = 0.05;
+/// More than this share of unhelpful labels makes the rule stricter
+const TIGHTEN_ABOVE: f64 = 0.5;
+/// At least this share of helpful labels lets the rule fire more often
+const LOOSEN_AT: f64 = 0.8;
+const CHECK_INTERVAL_SECS: u64 = 3600;
+
+/// A score threshold found in a rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Threshold {
+    pub score: String,
+    /// JSON pointer to the value in rule_json
+    pub path: String,
+    pub value: f64,
+    /// Raising the value makes the rule stricter (`>=`/`>`); lowering it does for `<=`/`<`
+    pub stricter_up: bool,
+}
+
+pub struct ThresholdTuner;
+
+impl ThresholdTuner {
+    /// Every tunable threshold in a rule, nested groups included. Equality
+    /// comparisons are left alone.
+    pub fn thresholds(rule_json: &Value) -> Vec<Threshold> {
+        let mut out = Vec::new();
+        collect(rule_json, String::new(), &mut out);
+        out
+    }
+
+    /// Move the rule's bounded thresholds one step according to the labels
+    /// since it was last tuned. Returns the adjustments made, if any.
+    pub fn tune_rule(store: &TemporalStore, rule_id: i64, now: i64) -> Result<Vec<ThresholdAdjustment>> {
+        let tuning = store
+            .get_rule_tuning(rule_id)?
+            .filter(|t| t.enabled)
+            .ok_or_else(|| anyhow::anyhow!("Auto-tuning is off for rule {}", rule_id))?;
+        let rule = store
+            .get_alert_rule(rule_id)?
+            .ok_or_else(|| anyhow::anyhow!("Alert rule {} not found", rule_id))?;
+
+        let (helpful, unhelpful) = store.rule_label_counts(rule_id, tuning.last_tuned_at.unwrap_or(0))?;
+        let total = helpful + unhelpful;
+        // Not enough labels yet: leave last_tuned_at so they keep adding up
+        if total < MIN_LABELS {
+            return Ok(Vec::new());
+        }
+        let direction = if unhelpful as f64 / total as f64 > TIGHTEN_ABOVE {
+            1.0
+        } else if helpful as f64 / total as f64 >= LOOSEN_AT {
+            -1.0
+        } else {
+            0.0
+        };
+
+        let mut rule_json = rule.rule_json.clone();
+        let mut adjustments = Vec::new();
+        if direction != 0.0 {
+            for threshold in Self::thresholds(&rule.rule_json) {
+                let Some(bounds) = tuning.bounds.get(&threshold.score) else {
+                    continue;
+                };
+                let delta = if threshold.stricter_up { direction * STEP } else { -direction * STEP };
+                let new_value = round(threshold.value + delta).clamp(bounds.min, bounds.max);
+                if (new_value - threshold.value).abs() < 1e-9 {
+                    continue;
+                }
+                if let Some(v) = rule_json.pointer_mut(&threshold.path) {
+                    *v = serde_json::json!(new_value);
+                }
+                adjustments.push(ThresholdAdjustment {
+                    id: 0,
+                    rule_id,
+                    score: threshold.score,
+                    path: threshold.path,
+                    old_value: threshold.value,
+                    new_value,
+                    helpful,
+                    unhelpful,
+                    tuned_at: now,
+                    reverted_at: None,
+                });
+            }
+        }
+
+        let ids = store.apply_threshold_adjustments(rule_id, &rule_json, &adjustments, now)?;
+        for (adjustment, id) in adjustments.iter_mut().zip(ids) {
+            adjustment.id = id;
+        }
+        Ok(adjustments)
+    }
+
+    /// Tune every enabled rule not tuned in the last week.
+    pub fn run_due(store: &TemporalStore, now: i64) -> Result<Vec<ThresholdAdjustment>> {
+        let mut all = Vec::new();
+        for tuning in store.list_tuned_rules()? {
+            if tuning.last_tuned_at.is_some_and(|t| now - t < TUNE_INTERVAL_SECS) {
+                continue;
+            }
+            match Self::tune_rule(store, tuning.rule_id, now) {
+                Ok(adjustments) => all.extend(adjustments),
+                Err(e) => eprintln!("Tuning alert rule {} failed: {}", tuning.rule_id, e),
+            }
+        }
+        Ok(all)
+    }
+
+    pub fn start_scheduler(db: Arc<Mutex<Database>>) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+            let heartbeat = crate::services::watchdog::Watchdog::register("threshold_tuner", tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+
+            loop {
+                interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
+                let store = match db.lock() {
+                    Ok(db_guard) => TemporalStore::new(db_guard.conn.clone()),
+                    Err(e) => {
+                        eprintln!("Threshold tuning skipped: database lock error: {}", e);
+                        continue;
+                    }
+                };
+                match Self::run_due(&store, chrono::Utc::now().timestamp()) {
+                    Ok(adjustments) if adjustments.is_empty() => {}
+                    Ok(adjustments) => eprintln!("Auto-tuning adjusted {} alert thresholds", adjustments.len()),
+                    Err(e) => eprintln!("Threshold tuning failed: {}", e),
+                }
+            }
+        });
+    }
+}
+
+fn round(v: f64) -> f64 {
+    (v * 1000.0).round() / 1000.0
+}
+
+fn collect(node: &Value, path: String, out: &mut Vec<Threshold>) {
+    let Some(obj) = node.as_object() else {
+        return;
+    };
+    if let Some(threshold) = threshold_of(obj, &path) {
+        out.push(threshold);
+    }
+    for key in ["logic", "condition"] {
+        if let Some(child) = obj.get(key) {
+            collect(child, format!("{}/{}", path, key), out);
+        }
+    }
+    for key in ["conditions", "any", "all"] {
+        if let Some(items) = obj.get(key).and_then(|v| v.as_array()) {
+            for (i, item) in items.iter().enumerate() {
+                collect(item, format!("{}/{}/{}", path, key, i), out);
+            }
+        }
+    }
+}
+
+fn threshold_of(cond: &serde_json::Map<String, Value>, path: &str) -> Option<Threshold> {
+    let value = cond.get("value").and_then(|v| v.as_f64())?;
+    let (score, stricter_up) = match cond.get("type").and_then(|v| v.as_str())? {
+        "sentiment_below" => ("sentiment", false),
+        "sentiment_above" => ("sentiment", true),
+        "volume_spike" => ("volume", true),
+        kind @ ("severity" | "volume" | "sentiment") => {
+            let stricter_up = match cond.get("operator").and_then(|v| v.as_str()).unwrap_or(">=") {
+                ">" | "gt" | ">=" | "gte" => true,
+                "<" | "lt" | "<=" | "lte" => false,
+                _ => return None,
+            };
+            (kind, stricter_up)
+        }
+        _ => return None,
+    };
+    Some(Threshold { score: score.to_string(), path: format!("{}/value", path), value, stricter_up })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::temporal::ThresholdBounds;
+    use crate::test_support;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn finds_thresholds_in_nested_and_legacy_rules() {
+        let rule = json!({"logic": {"operator": "AND", "conditions": [
+            {"type": "severity", "operator": ">=", "value": 0.6},
+            {"logic": {"operator": "OR", "conditions": [
+                {"type": "sentiment", "operator": "<=", "value": -0.4},
+                {"type": "volume", "operator": "==", "value": 2.0},
+                {"type": "novelty", "value": 0.5},
+            ]}},
+        ]}});
+        let found = ThresholdTuner::thresholds(&rule);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].path, "/logic/conditions/0/value");
+        assert!(found[0].stricter_up);
+        assert_eq!(found[1].path, "/logic/conditions/1/logic/conditions/0/value");
+        assert!(!found[1].stricter_up);
+
+        let legacy = ThresholdTuner::thresholds(&json!({"any": [{"type": "sentiment_below", "value": -0.3}]}));
+        assert_eq!(legacy[0].score, "sentiment");
+        assert_eq!(legacy[0].path, "/any/0/value");
+    }
+
+    #[test]
+    fn unhelpful_labels_tighten_within_bounds_and_revert() {
+        let db = test_support::test_db();
+        let store = TemporalStore::new(db.conn.clone());
+        let rule_json = json!({"logic": {"operator": "AND", "conditions": [
+            {"type": "severity", "operator": ">=", "value": 0.6},
+            {"type": "sentiment", "operator": "<=", "value": -0.38},
+        ]}});
+        let rule_id = test_support::alert_rule(&db, "Noisy", rule_json);
+        let bounds = HashMap::from([
+            ("severity".to_string(), ThresholdBounds { min: 0.3, max: 0.9 }),
+            ("sentiment".to_string(), ThresholdBounds { min: -0.4, max: 0.0 }),
+        ]);
+        store.set_rule_tuning(rule_id, true, &bounds).unwrap();
+
+        // One helpful, four unhelpful
+        for i in 0..5 {
+            let alert_id = test_support::alert(&db, rule_id, None);
+            store.set_alert_label(alert_id, if i == 0 { 1 } else { -1 }, None).unwrap();
+        }
+        let now = chrono::Utc::now().timestamp();
+
+        let adjustments = ThresholdTuner::run_due(&store, now).unwrap();
+        assert_eq!(adjustments.len(), 2);
+        let tuned = store.get_alert_rule(rule_id).unwrap().unwrap().rule_json;
+        assert_eq!(tuned.pointer("/logic/conditions/0/value"), Some(&json!(0.65)));
+        // Lowered past -0.4, so held at the bound
+        assert_eq!(tuned.pointer("/logic/conditions/1/value"), Some(&json!(-0.4)));
+
+        // Tuned just now, so not due again
+        assert!(ThresholdTuner::run_due(&store, now + 60).unwrap().is_empty());
+
+        let reverted = store.revert_threshold_adjustment(adjustments[0].id).unwrap();
+        assert!(reverted.reverted_at.is_some());
+        let rule_json = store.get_alert_rule(rule_id).unwrap().unwrap().rule_json;
+        assert_eq!(rule_json.pointer("/logic/conditions/0/value"), Some(&json!(0.6)));
+        assert!(store.revert_threshold_adjustment(adjustments[0].id).is_err());
+    }
+}
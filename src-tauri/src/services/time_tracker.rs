@@ -0,0 +1,152 @@
+use crate::storage::time_tracking::TimeTrackingStore;
+use crate::storage::Database;
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+use tokio::process::Command;
+
+pub const CONFIG_IDLE_MINUTES: &str = "time_tracking_idle_minutes";
+pub const CONFIG_AUTO_ENABLED: &str = "time_tracking_auto_enabled";
+
+const DEFAULT_IDLE_MINUTES: i64 = 5;
+
+pub struct TimeTracker;
+
+impl TimeTracker {
+    /// Watch for idle time (stops running timers at the last activity) and, when
+    /// permitted in config, start/stop automatic timers based on the focused app.
+    pub fn start_tracking(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = Self::tick(&db, &app).await {
+                    eprintln!("Error updating time tracking: {}", e);
+                }
+            }
+        });
+    }
+
+    async fn tick(db: &Arc<Mutex<Database>>, app: &AppHandle) -> Result<()> {
+        let (store, idle_minutes, auto_enabled) = {
+            let db_guard = db.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let idle_minutes = db_guard.get_config(CONFIG_IDLE_MINUTES)?
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or(DEFAULT_IDLE_MINUTES)
+                .max(1);
+            let auto_enabled = db_guard.get_config(CONFIG_AUTO_ENABLED)?
+                .map(|v| v == "true")
+                .unwrap_or(false);
+            (TimeTrackingStore::new(db_guard.conn.clone()), idle_minutes, auto_enabled)
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        let idle_secs = get_idle_seconds().await;
+
+        if let Some(idle) = idle_secs {
+            if idle >= idle_minutes * 60 {
+                let running = store.list_running()?;
+                if !running.is_empty() {
+                    // Close at the moment the user went idle, not now
+                    let stopped = store.stop_timers(None, None, now - idle, "idle")?;
+                    let _ = crate::services::window_router::emit(app, serde_json::json!({
+                        "type": "time-tracking-idle",
+                        "data": { "stopped": stopped, "idle_secs": idle, "entries": running },
+                        "timestamp": chrono::Utc::now().timestamp_millis(),
+                    }));
+                }
+                return Ok(());
+            }
+        }
+
+        if !auto_enabled {
+            return Ok(());
+        }
+
+        let focused = match get_focused_app().await {
+            Some(app_name) => app_name,
+            None => return Ok(()),
+        };
+        let focused_lower = focused.to_lowercase();
+        let matched_project = store
+            .list_rules()?
+            .into_iter()
+            .find(|rule| focused_lower.contains(&rule.app_pattern.to_lowercase()))
+            .map(|rule| rule.project_id);
+
+        let running_auto: Vec<i64> = store
+            .list_running()?
+            .into_iter()
+            .filter(|e| e.source == "auto")
+            .map(|e| e.project_id)
+            .collect();
+
+        for project_id in &running_auto {
+            if Some(*project_id) != matched_project {
+                store.stop_timers(Some(*project_id), Some("auto"), now, "app_switch")?;
+            }
+        }
+
+        if let Some(project_id) = matched_project {
+            if !running_auto.contains(&project_id) {
+                let entry_id = store.start_timer(project_id, "auto", Some(&focused), now)?;
+                let _ = crate::services::window_router::emit(app, serde_json::json!({
+                    "type": "time-tracking-auto-start",
+                    "data": { "entry_id": entry_id, "project_id": project_id, "app": focused },
+                    "timestamp": chrono::Utc::now().timestamp_millis(),
+                }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Seconds since the last keyboard/mouse input, where the platform exposes it.
+pub async fn get_idle_seconds() -> Option<i64> {
+    if cfg!(target_os = "macos") {
+        let output = Command::new("ioreg").args(["-c", "IOHIDSystem"]).output().await.ok()?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        // Line looks like: "HIDIdleTime" = 123456789 (nanoseconds)
+        let line = text.lines().find(|l| l.contains("\"HIDIdleTime\""))?;
+        let value = line.split('=').nth(1)?.trim().parse::<i64>().ok()?;
+        Some(value / 1_000_000_000)
+    } else if cfg!(target_os = "linux") {
+        let output = Command::new("xprintidle").output().await.ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let ms = String::from_utf8_lossy(&output.stdout).trim().parse::<i64>().ok()?;
+        Some(ms / 1000)
+    } else {
+        None
+    }
+}
+
+/// Name of the frontmost application (macOS) or active window title (Linux/X11).
+pub async fn get_focused_app() -> Option<String> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("osascript")
+            .args(["-e", "tell application \"System Events\" to get name of first application process whose frontmost is true"])
+            .output()
+            .await
+            .ok()?
+    } else if cfg!(target_os = "linux") {
+        Command::new("xdotool")
+            .args(["getactivewindow", "getwindowname"])
+            .output()
+            .await
+            .ok()?
+    } else {
+        return None;
+    };
+
+    if !output.status.success() {
+        return None;
+    }
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if name.is_empty() { None } else { Some(name) }
+}
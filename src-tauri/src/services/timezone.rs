@@ -0,0 +1,177 @@
+use crate::storage::Database;
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// IANA name such as "Europe/Berlin". Unset means UTC.
+pub const CONFIG_TIMEZONE: &str = "timezone";
+/// "HH:MM-HH:MM" in the configured timezone, e.g. "22:00-07:00".
+/// Desktop notifications are not shown in between. Unset means none.
+pub const CONFIG_QUIET_HOURS: &str = "notification_quiet_hours";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimePreferences {
+    pub timezone: String,
+    pub quiet_hours: Option<String>,
+}
+
+impl Default for TimePreferences {
+    fn default() -> Self {
+        TimePreferences { timezone: "UTC".to_string(), quiet_hours: None }
+    }
+}
+
+impl TimePreferences {
+    pub fn load(db: &Database) -> Self {
+        Self::from_values(
+            db.get_config(CONFIG_TIMEZONE).ok().flatten(),
+            db.get_config(CONFIG_QUIET_HOURS).ok().flatten(),
+        )
+    }
+
+    /// For storage code that only holds a connection.
+    pub fn from_conn(conn: &Connection) -> Self {
+        let get = |key: &str| -> Option<String> {
+            conn.query_row("SELECT value FROM config WHERE key = ?1", params![key], |row| row.get(0))
+                .optional()
+                .ok()
+                .flatten()
+        };
+        Self::from_values(get(CONFIG_TIMEZONE), get(CONFIG_QUIET_HOURS))
+    }
+
+    fn from_values(timezone: Option<String>, quiet_hours: Option<String>) -> Self {
+        TimePreferences {
+            // A name chrono-tz no longer knows falls back to UTC rather than failing every caller
+            timezone: timezone
+                .filter(|v| parse_timezone(v).is_ok())
+                .unwrap_or_else(|| Self::default().timezone),
+            quiet_hours: quiet_hours.filter(|v| !v.trim().is_empty()),
+        }
+    }
+
+    pub fn save(&self, db: &Database) -> Result<()> {
+        let tz = parse_timezone(&self.timezone)?;
+        let quiet_hours = match self.quiet_hours.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            Some(value) => {
+                QuietHours::parse(value)?;
+                value
+            }
+            None => "",
+        };
+        db.set_config(CONFIG_TIMEZONE, tz.name())?;
+        db.set_config(CONFIG_QUIET_HOURS, quiet_hours)?;
+        Ok(())
+    }
+
+    pub fn tz(&self) -> Tz {
+        parse_timezone(&self.timezone).unwrap_or(Tz::UTC)
+    }
+
+    pub fn quiet_hours(&self) -> Option<QuietHours> {
+        self.quiet_hours.as_deref().and_then(|v| QuietHours::parse(v).ok())
+    }
+
+    /// Whether desktop notifications should stay silent at `ts`.
+    pub fn is_quiet(&self, ts: i64) -> bool {
+        self.quiet_hours().map(|q| q.contains(ts, self.tz())).unwrap_or(false)
+    }
+}
+
+pub fn parse_timezone(name: &str) -> Result<Tz> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| anyhow::anyhow!("Unknown timezone: {}", name))
+}
+
+/// A daily window in local time. Windows may wrap past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn parse(value: &str) -> Result<Self> {
+        let (start, end) = value
+            .split_once('-')
+            .ok_or_else(|| anyhow::anyhow!("Quiet hours must look like 22:00-07:00"))?;
+        let time = |s: &str| {
+            NaiveTime::parse_from_str(s.trim(), "%H:%M")
+                .map_err(|_| anyhow::anyhow!("Invalid time in quiet hours: {}", s.trim()))
+        };
+        let hours = QuietHours { start: time(start)?, end: time(end)? };
+        if hours.start == hours.end {
+            anyhow::bail!("Quiet hours must not start and end at the same time");
+        }
+        Ok(hours)
+    }
+
+    /// Compares wall-clock time, so the window stays at the same local
+    /// hours on both sides of a DST change.
+    pub fn contains(&self, ts: i64, tz: Tz) -> bool {
+        let time = local_datetime(ts, tz).time();
+        if self.start < self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+pub fn local_datetime(ts: i64, tz: Tz) -> DateTime<Tz> {
+    Utc.timestamp_opt(ts, 0)
+        .single()
+        .unwrap_or_default()
+        .with_timezone(&tz)
+}
+
+pub fn local_date(ts: i64, tz: Tz) -> NaiveDate {
+    local_datetime(ts, tz).date_naive()
+}
+
+/// First instant of `date` in `tz`. Where a DST change skips midnight the
+/// day starts at the first local time that exists; where midnight repeats
+/// it starts at the earlier one.
+pub fn day_start(date: NaiveDate, tz: Tz) -> i64 {
+    let midnight = date.and_time(NaiveTime::MIN);
+    (0..=24 * 4)
+        .find_map(|step| tz.from_local_datetime(&(midnight + Duration::minutes(15 * step))).earliest())
+        .map(|t| t.timestamp())
+        .unwrap_or_else(|| midnight.and_utc().timestamp())
+}
+
+/// Start and (exclusive) end of the local day containing `ts`. Days across
+/// a DST change are 23 or 25 hours long, so never step by 86400.
+pub fn day_bounds(ts: i64, tz: Tz) -> (i64, i64) {
+    let date = local_date(ts, tz);
+    let next = date.succ_opt().unwrap_or(date);
+    (day_start(date, tz), day_start(next, tz))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_days_follow_dst_changes() {
+        let berlin = parse_timezone("Europe/Berlin").unwrap();
+        // 2024-03-31 12:00 UTC: clocks went forward at 02:00 local that night
+        let (start, end) = day_bounds(1_711_886_400, berlin);
+        assert_eq!(end - start, 23 * 3600);
+        assert_eq!(local_datetime(start, berlin).format("%Y-%m-%d %H:%M").to_string(), "2024-03-31 00:00");
+        // 2024-10-27 12:00 UTC: clocks went back
+        let (start, end) = day_bounds(1_730_030_400, berlin);
+        assert_eq!(end - start, 25 * 3600);
+
+        let quiet = QuietHours::parse("22:00-07:00").unwrap();
+        // 23:30 local in summer (21:30 UTC) and in winter (22:30 UTC)
+        assert!(quiet.contains(1_719_005_400, berlin));
+        assert!(quiet.contains(1_734_820_200, berlin));
+        // 12:00 UTC is 13:00 or 14:00 local
+        assert!(!quiet.contains(1_730_030_400, berlin));
+        assert!(QuietHours::parse("7pm-8am").is_err());
+    }
+}
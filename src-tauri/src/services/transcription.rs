@@ -0,0 +1,297 @@
+use crate::storage::{Database, NewTranscript, OSINTStore, TranscriptStore};
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+/// whisper.cpp model file (ggml), e.g. ggml-base.en.bin. Unset disables transcription.
+pub const CONFIG_WHISPER_MODEL: &str = "whisper_model_path";
+/// ffmpeg binary, when it isn't on PATH.
+pub const CONFIG_FFMPEG_PATH: &str = "ffmpeg_path";
+/// Spoken language code such as "en"; unset lets whisper detect it.
+pub const CONFIG_LANGUAGE: &str = "transcription_language";
+/// Transcribe audio enclosures of RSS items in the background.
+pub const CONFIG_PODCASTS_ENABLED: &str = "podcast_transcription_enabled";
+
+const AUDIO_EXTENSIONS: [&str; 7] = ["mp3", "m4a", "wav", "ogg", "opus", "flac", "aac"];
+const DEFAULT_FFMPEG: &str = "ffmpeg";
+/// whisper expects 16 kHz mono
+const SAMPLE_RATE: &str = "16000";
+const MAX_EPISODES_PER_RUN: i64 = 2;
+const MAX_EPISODE_BYTES: usize = 400 * 1024 * 1024;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TranscriptionResult {
+    pub transcript_id: i64,
+    pub title: String,
+    /// Same audio was transcribed before; nothing was stored
+    pub duplicate: bool,
+    pub segments: usize,
+    pub duration_ms: i64,
+}
+
+/// Local speech-to-text with whisper.cpp. Audio is decoded to 16 kHz mono
+/// PCM with ffmpeg first, so anything ffmpeg reads can be transcribed.
+#[derive(Debug, Clone)]
+pub struct Transcriber {
+    model_path: PathBuf,
+    ffmpeg: String,
+    language: Option<String>,
+}
+
+impl Transcriber {
+    /// None until a whisper model is configured.
+    pub fn from_config(db: &Database) -> Option<Self> {
+        let get = |key| db.get_config(key).ok().flatten().filter(|v: &String| !v.trim().is_empty());
+        Some(Transcriber {
+            model_path: PathBuf::from(get(CONFIG_WHISPER_MODEL)?),
+            ffmpeg: get(CONFIG_FFMPEG_PATH).unwrap_or_else(|| DEFAULT_FFMPEG.to_string()),
+            language: get(CONFIG_LANGUAGE),
+        })
+    }
+
+    pub fn is_audio(path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| AUDIO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+    }
+
+    /// Transcribe an audio file and index its segments for search. Files are
+    /// recognized by content hash, so the same recording is stored once.
+    pub async fn transcribe_file(
+        db: &Mutex<Database>,
+        path: &Path,
+        title: Option<String>,
+        article_id: Option<i64>,
+        source: Option<String>,
+    ) -> Result<TranscriptionResult> {
+        let title = title.unwrap_or_else(|| {
+            path.file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Untitled".to_string())
+        });
+        let bytes = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let file_hash = format!("{:x}", Sha256::digest(&bytes));
+        drop(bytes);
+
+        let transcriber = {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let store = TranscriptStore::new(db_guard.conn.clone());
+            if let Some(transcript_id) = store.find_by_hash(&file_hash)? {
+                return Ok(TranscriptionResult {
+                    transcript_id,
+                    title,
+                    duplicate: true,
+                    ..Default::default()
+                });
+            }
+            Self::from_config(&db_guard)
+                .ok_or_else(|| anyhow::anyhow!("No whisper model configured"))?
+        };
+
+        let owned_path = path.to_path_buf();
+        let worker = transcriber.clone();
+        let segments = tokio::task::spawn_blocking(move || {
+            let samples = worker.decode(&owned_path)?;
+            worker.transcribe_samples(&samples)
+        })
+        .await
+        .context("Transcription task failed")??;
+
+        let result = TranscriptionResult {
+            transcript_id: 0,
+            title: title.clone(),
+            duplicate: false,
+            segments: segments.len(),
+            duration_ms: segments.iter().map(|(_, end, _)| *end).max().unwrap_or(0),
+        };
+        let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        let store = TranscriptStore::new(db_guard.conn.clone());
+        let transcript_id = store.insert_transcript(&NewTranscript {
+            title,
+            source: source.unwrap_or_else(|| path.display().to_string()),
+            file_hash,
+            article_id,
+            language: transcriber.language.clone(),
+            model: transcriber
+                .model_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            segments,
+        })?;
+        Ok(TranscriptionResult { transcript_id, ..result })
+    }
+
+    /// Download and transcribe one queued podcast episode.
+    pub async fn transcribe_episode(db: &Mutex<Database>, article_id: i64) -> Result<TranscriptionResult> {
+        let (episode, title) = {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            let episode = TranscriptStore::new(db_guard.conn.clone())
+                .get_episode(article_id)?
+                .ok_or_else(|| anyhow::anyhow!("Article {} has no audio enclosure", article_id))?;
+            let title = OSINTStore::new(db_guard.conn.clone()).get_item(article_id)?.map(|item| item.title);
+            (episode, title)
+        };
+
+        let path = std::env::temp_dir().join(format!("mina-episode-{}", uuid::Uuid::new_v4()));
+        let result = match download(&episode.audio_url, &path).await {
+            Ok(()) => Self::transcribe_file(db, &path, title, Some(article_id), Some(episode.audio_url.clone())).await,
+            Err(e) => Err(e),
+        };
+        let _ = std::fs::remove_file(&path);
+
+        let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        TranscriptStore::new(db_guard.conn.clone()).finish_episode(
+            article_id,
+            result.as_ref().map(|r| r.transcript_id).map_err(|e| e.to_string()),
+        )?;
+        result
+    }
+
+    /// Transcribe queued podcast episodes, a few per tick since each one
+    /// takes minutes of CPU.
+    pub fn start_scheduler(db: Arc<Mutex<Database>>) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(15 * 60));
+
+            loop {
+                interval.tick().await;
+
+                let pending = {
+                    let Ok(db_guard) = db.lock() else { continue };
+                    let enabled = db_guard
+                        .get_config(CONFIG_PODCASTS_ENABLED)
+                        .ok()
+                        .flatten()
+                        .is_some_and(|v| v == "true");
+                    if !enabled || Self::from_config(&db_guard).is_none() {
+                        continue;
+                    }
+                    match TranscriptStore::new(db_guard.conn.clone()).pending_episodes(MAX_EPISODES_PER_RUN) {
+                        Ok(pending) => pending,
+                        Err(e) => {
+                            eprintln!("Failed to list podcast episodes: {}", e);
+                            continue;
+                        }
+                    }
+                };
+
+                for episode in pending {
+                    if let Err(e) = Self::transcribe_episode(&db, episode.article_id).await {
+                        eprintln!("Failed to transcribe episode {}: {}", episode.audio_url, e);
+                    }
+                }
+            }
+        });
+    }
+
+    fn decode(&self, path: &Path) -> Result<Vec<f32>> {
+        let output = Command::new(&self.ffmpeg)
+            .args(["-nostdin", "-loglevel", "error", "-i"])
+            .arg(path)
+            .args(["-ar", SAMPLE_RATE, "-ac", "1", "-f", "s16le", "-"])
+            .output()
+            .with_context(|| format!("Failed to run {} (is ffmpeg installed?)", self.ffmpeg))?;
+        if !output.status.success() {
+            anyhow::bail!("ffmpeg failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(pcm_s16le_to_f32(&output.stdout))
+    }
+
+    /// (start_ms, end_ms, text) of each segment whisper recognized.
+    fn transcribe_samples(&self, samples: &[f32]) -> Result<Vec<(i64, i64, String)>> {
+        use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+        let model = self.model_path.to_string_lossy();
+        let ctx = WhisperContext::new_with_params(&model, WhisperContextParameters::default())
+            .map_err(|e| anyhow::anyhow!("Failed to load whisper model {}: {}", model, e))?;
+        let mut state = ctx
+            .create_state()
+            .map_err(|e| anyhow::anyhow!("Failed to create whisper state: {}", e))?;
+
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_language(Some(self.language.as_deref().unwrap_or("auto")));
+        params.set_n_threads(std::thread::available_parallelism().map(|n| n.get() as i32).unwrap_or(4));
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_special(false);
+        params.set_print_timestamps(false);
+
+        state
+            .full(params, samples)
+            .map_err(|e| anyhow::anyhow!("Transcription failed: {}", e))?;
+
+        let count = state
+            .full_n_segments()
+            .map_err(|e| anyhow::anyhow!("Failed to read segments: {}", e))?;
+        let mut segments = Vec::with_capacity(count.max(0) as usize);
+        for i in 0..count {
+            let text = state
+                .full_get_segment_text(i)
+                .map_err(|e| anyhow::anyhow!("Failed to read segment text: {}", e))?;
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            // whisper timestamps are in centiseconds
+            let start = state.full_get_segment_t0(i).unwrap_or(0) * 10;
+            let end = state.full_get_segment_t1(i).unwrap_or(0) * 10;
+            segments.push((start, end, text.to_string()));
+        }
+        Ok(segments)
+    }
+}
+
+async fn download(url: &str, path: &Path) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30 * 60))
+        .build()?;
+    let response = client.get(url).send().await?.error_for_status()?;
+    let mut file = std::fs::File::create(path)?;
+    let mut written = 0usize;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        written += chunk.len();
+        if written > MAX_EPISODE_BYTES {
+            anyhow::bail!("Episode larger than {} bytes", MAX_EPISODE_BYTES);
+        }
+        file.write_all(&chunk)?;
+    }
+    Ok(())
+}
+
+fn pcm_s16le_to_f32(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pcm_conversion_scales_to_unit_range() {
+        let bytes = [0x00, 0x00, 0xff, 0x7f, 0x00, 0x80, 0x01];
+        let samples = pcm_s16le_to_f32(&bytes);
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples[0], 0.0);
+        assert!((samples[1] - 32767.0 / 32768.0).abs() < 1e-6);
+        assert_eq!(samples[2], -1.0);
+    }
+
+    #[test]
+    fn audio_extensions() {
+        assert!(Transcriber::is_audio(Path::new("/tmp/episode.MP3")));
+        assert!(Transcriber::is_audio(Path::new("call.m4a")));
+        assert!(!Transcriber::is_audio(Path::new("notes.pdf")));
+        assert!(!Transcriber::is_audio(Path::new("noext")));
+    }
+}
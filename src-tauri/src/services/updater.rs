@@ -0,0 +1,395 @@
+use crate::storage::Database;
+use anyhow::{Context, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+/// Release feed in the Tauri updater JSON format.
+pub const CONFIG_FEED_URL: &str = "update_feed_url";
+/// minisign public key (base64 of the .pub file) release artifacts are
+/// signed with, compiled in from MINA_UPDATE_PUBLIC_KEY at build time so
+/// nothing at runtime can swap it. Builds without it refuse updates.
+const PUBLIC_KEY: Option<&str> = option_env!("MINA_UPDATE_PUBLIC_KEY");
+/// Check the feed once a day and announce new versions.
+pub const CONFIG_AUTO_CHECK: &str = "update_auto_check";
+const CONFIG_LAST_CHECK: &str = "update_last_checked_at";
+
+const UPDATES_DIR: &str = "updates";
+const PREVIOUS_MANIFEST: &str = "previous.json";
+const MAX_ARTIFACT_BYTES: usize = 500 * 1024 * 1024;
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReleaseFeed {
+    version: String,
+    notes: Option<String>,
+    pub_date: Option<String>,
+    platforms: std::collections::HashMap<String, PlatformRelease>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PlatformRelease {
+    url: String,
+    signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub version: String,
+    pub available: bool,
+    pub notes: Option<String>,
+    pub pub_date: Option<String>,
+    pub url: Option<String>,
+    pub signature: Option<String>,
+}
+
+/// The install an update replaced, kept so it can be rolled back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviousInstall {
+    pub version: String,
+    pub backup_path: String,
+    pub install_path: String,
+    pub replaced_at: i64,
+}
+
+/// Checks the release feed, downloads and verifies signed artifacts and
+/// swaps them in, moving the running install aside for rollback.
+pub struct Updater;
+
+impl Updater {
+    pub async fn check(db: &Mutex<Database>, app: &AppHandle) -> Result<UpdateInfo> {
+        let feed_url = {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            db_guard
+                .get_config(CONFIG_FEED_URL)?
+                .filter(|u| !u.trim().is_empty())
+                .ok_or_else(|| anyhow::anyhow!("No update feed configured"))?
+        };
+
+        let feed: ReleaseFeed = reqwest::Client::new()
+            .get(&feed_url)
+            .timeout(std::time::Duration::from_secs(30))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Invalid release feed")?;
+
+        let current_version = app.package_info().version.to_string();
+        let platform = feed.platforms.get(&platform_key());
+        let info = UpdateInfo {
+            available: platform.is_some() && is_newer(&feed.version, &current_version),
+            current_version,
+            version: feed.version.clone(),
+            notes: feed.notes.clone(),
+            pub_date: feed.pub_date.clone(),
+            url: platform.map(|p| p.url.clone()),
+            signature: platform.map(|p| p.signature.clone()),
+        };
+
+        let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        db_guard.set_config(CONFIG_LAST_CHECK, &chrono::Utc::now().timestamp().to_string())?;
+        Ok(info)
+    }
+
+    /// Download, verify and install the update, keeping the running
+    /// version for `rollback`. Takes effect after a restart.
+    pub async fn apply(db: &Mutex<Database>, app: &AppHandle) -> Result<UpdateInfo> {
+        let info = Self::check(db, app).await?;
+        if !info.available {
+            anyhow::bail!("Already on the latest version ({})", info.current_version);
+        }
+        let public_key = PUBLIC_KEY
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("This build has no update public key; refusing unsigned update"))?;
+        let url = info.url.clone().unwrap_or_default();
+        let signature = info.signature.clone().unwrap_or_default();
+
+        let bytes = download(&url).await?;
+        verify_signature(&bytes, &signature, public_key)?;
+
+        let updates_dir = updates_dir(app)?;
+        let staging = updates_dir.join("staged").join(&info.version);
+        let _ = std::fs::remove_dir_all(&staging);
+        std::fs::create_dir_all(&staging)?;
+        let file_name = url
+            .rsplit('/')
+            .next()
+            .and_then(|n| n.split('?').next())
+            .filter(|n| !n.is_empty())
+            .unwrap_or("update.bin")
+            .to_string();
+        let artifact = staging.join(&file_name);
+        std::fs::write(&artifact, &bytes)?;
+        let payload = unpack(&artifact, &staging)?;
+
+        let install_path = install_target()?;
+        let backup_dir = updates_dir.join("previous");
+        let _ = std::fs::remove_dir_all(&backup_dir);
+        std::fs::create_dir_all(&backup_dir)?;
+        let backup_path = backup_dir.join(install_path.file_name().unwrap_or_default());
+
+        move_path(&install_path, &backup_path).context("Failed to set aside the current install")?;
+        if let Err(e) = move_path(&payload, &install_path) {
+            // Put the old install back so the app still starts
+            let _ = move_path(&backup_path, &install_path);
+            return Err(e.context("Failed to install update"));
+        }
+        make_executable(&install_path);
+
+        let previous = PreviousInstall {
+            version: info.current_version.clone(),
+            backup_path: backup_path.display().to_string(),
+            install_path: install_path.display().to_string(),
+            replaced_at: chrono::Utc::now().timestamp(),
+        };
+        std::fs::write(updates_dir.join(PREVIOUS_MANIFEST), serde_json::to_vec_pretty(&previous)?)?;
+        let _ = std::fs::remove_dir_all(&staging);
+        Ok(info)
+    }
+
+    pub fn previous(app: &AppHandle) -> Result<Option<PreviousInstall>> {
+        let path = updates_dir(app)?.join(PREVIOUS_MANIFEST);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&std::fs::read(path)?)?))
+    }
+
+    /// Swap the previous install back in. Takes effect after a restart.
+    pub fn rollback(app: &AppHandle) -> Result<PreviousInstall> {
+        let previous = Self::previous(app)?.ok_or_else(|| anyhow::anyhow!("No previous version to roll back to"))?;
+        let backup = PathBuf::from(&previous.backup_path);
+        let install = PathBuf::from(&previous.install_path);
+        if !backup.exists() {
+            anyhow::bail!("Previous install is missing at {}", backup.display());
+        }
+
+        let updates_dir = updates_dir(app)?;
+        let discarded = updates_dir.join("rolled-back");
+        let _ = std::fs::remove_dir_all(&discarded);
+        std::fs::create_dir_all(&discarded)?;
+        move_path(&install, &discarded.join(install.file_name().unwrap_or_default()))?;
+        move_path(&backup, &install)?;
+        make_executable(&install);
+        std::fs::remove_file(updates_dir.join(PREVIOUS_MANIFEST))?;
+        Ok(previous)
+    }
+
+    /// Daily feed check when enabled; new versions are announced as
+    /// `update-available`.
+    pub fn start_scheduler(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+
+            loop {
+                interval.tick().await;
+
+                let due = {
+                    let Ok(db_guard) = db.lock() else { continue };
+                    let enabled = db_guard
+                        .get_config(CONFIG_AUTO_CHECK)
+                        .ok()
+                        .flatten()
+                        .is_some_and(|v| v == "true");
+                    let last = db_guard
+                        .get_config(CONFIG_LAST_CHECK)
+                        .ok()
+                        .flatten()
+                        .and_then(|v| v.parse::<i64>().ok())
+                        .unwrap_or(0);
+                    enabled && chrono::Utc::now().timestamp() - last >= 86_400
+                };
+                if !due {
+                    continue;
+                }
+
+                match Self::check(&db, &app).await {
+                    Ok(info) if info.available => {
+                        let _ = crate::services::window_router::emit(&app, serde_json::json!({
+                            "type": "update-available",
+                            "data": info,
+                            "timestamp": chrono::Utc::now().timestamp_millis(),
+                        }));
+                    }
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Update check failed: {}", e),
+                }
+            }
+        });
+    }
+}
+
+fn updates_dir(app: &AppHandle) -> Result<PathBuf> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| anyhow::anyhow!("No app data dir: {}", e))?
+        .join(UPDATES_DIR);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Feed key of this build, e.g. "darwin-aarch64" or "windows-x86_64".
+fn platform_key() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        other => other,
+    };
+    format!("{}-{}", os, std::env::consts::ARCH)
+}
+
+/// The file or bundle an update replaces: the AppImage on Linux, the .app
+/// bundle on macOS, otherwise the executable itself.
+fn install_target() -> Result<PathBuf> {
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(PathBuf::from(appimage));
+    }
+    let exe = std::env::current_exe()?;
+    if cfg!(target_os = "macos") {
+        if let Some(bundle) = exe.ancestors().find(|p| p.extension().is_some_and(|e| e == "app")) {
+            return Ok(bundle.to_path_buf());
+        }
+    }
+    Ok(exe)
+}
+
+async fn download(url: &str) -> Result<Vec<u8>> {
+    use futures::StreamExt;
+
+    let response = reqwest::Client::new()
+        .get(url)
+        .timeout(std::time::Duration::from_secs(30 * 60))
+        .send()
+        .await?
+        .error_for_status()?;
+    let mut bytes = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        if bytes.len() + chunk.len() > MAX_ARTIFACT_BYTES {
+            anyhow::bail!("Update larger than {} bytes", MAX_ARTIFACT_BYTES);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+/// Check a minisign signature. Feed signatures and the configured key are
+/// base64 of the minisign text files, as produced by `tauri signer`.
+fn verify_signature(data: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    let decode = |value: &str| -> Result<String> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(value.trim())
+            .context("Invalid base64")?;
+        Ok(String::from_utf8(bytes)?)
+    };
+    let key = minisign_verify::PublicKey::decode(&decode(public_key)?)
+        .map_err(|e| anyhow::anyhow!("Invalid update public key: {}", e))?;
+    let signature = minisign_verify::Signature::decode(&decode(signature)?)
+        .map_err(|e| anyhow::anyhow!("Invalid update signature: {}", e))?;
+    key.verify(data, &signature, true)
+        .map_err(|e| anyhow::anyhow!("Update signature verification failed: {}", e))
+}
+
+/// Extract .tar.gz artifacts (macOS bundles); anything else is installed
+/// as is.
+fn unpack(artifact: &Path, staging: &Path) -> Result<PathBuf> {
+    let name = artifact.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    if !name.ends_with(".tar.gz") {
+        return Ok(artifact.to_path_buf());
+    }
+    let out = staging.join("unpacked");
+    std::fs::create_dir_all(&out)?;
+    let file = std::fs::File::open(artifact)?;
+    tar::Archive::new(flate2::read::GzDecoder::new(file))
+        .unpack(&out)
+        .context("Failed to extract update")?;
+    let mut entries = std::fs::read_dir(&out)?.filter_map(|e| e.ok()).map(|e| e.path());
+    entries.next().ok_or_else(|| anyhow::anyhow!("Update archive is empty"))
+}
+
+/// Rename, falling back to copy and delete across filesystems.
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursive(from, to).with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+    if from.is_dir() {
+        std::fs::remove_dir_all(from)?;
+    } else {
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> Result<()> {
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        std::fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+    if path.is_file() {
+        let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755));
+    }
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) {}
+
+/// Numeric comparison of dotted versions; pre-release suffixes are ignored.
+fn is_newer(candidate: &str, current: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    let (a, b) = (parse(candidate), parse(current));
+    for i in 0..a.len().max(b.len()) {
+        let (x, y) = (a.get(i).copied().unwrap_or(0), b.get(i).copied().unwrap_or(0));
+        if x != y {
+            return x > y;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_comparison() {
+        assert!(is_newer("0.2.0", "0.1.9"));
+        assert!(is_newer("v1.10.0", "1.9.3"));
+        assert!(is_newer("1.0.1", "1.0"));
+        assert!(!is_newer("1.0.0", "1.0.0"));
+        assert!(!is_newer("1.0.0-beta.2", "1.0.0"));
+        assert!(!is_newer("0.9.9", "1.0.0"));
+    }
+
+    #[test]
+    fn platform_key_uses_feed_names() {
+        let key = platform_key();
+        assert!(!key.starts_with("macos"));
+        assert!(key.ends_with(std::env::consts::ARCH));
+    }
+}
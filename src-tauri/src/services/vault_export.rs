@@ -0,0 +1,510 @@
+use crate::storage::osint::{EntitySummary, OSINTStore};
+use crate::storage::temporal::{EventCommentary, EventMatchContext, TemporalEvent, TemporalStore};
+use crate::storage::Database;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Vault folder of the last export; the scheduler keeps it in sync.
+pub const CONFIG_VAULT_PATH: &str = "vault_export_path";
+
+pub const DEFAULT_DAYS: i64 = 90;
+/// Everything is written below this folder of the vault.
+const EXPORT_DIR: &str = "MINA";
+/// Notes written by the last export, so stale ones can be removed without
+/// touching notes the exporter doesn't own.
+const MANIFEST_FILE: &str = ".mina-export.json";
+const MAX_EVENTS: i64 = 2000;
+const MAX_NOTEBOOK_ARTICLES: i32 = 500;
+const MAX_RELATED_ENTITIES: usize = 10;
+const MAX_NAME_CHARS: usize = 100;
+const SYNC_INTERVAL_SECS: u64 = 3600;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VaultExportReport {
+    pub vault_path: String,
+    pub events: usize,
+    pub entities: usize,
+    pub notebooks: usize,
+    pub written: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    /// Note ID to path relative to the export folder
+    notes: BTreeMap<String, String>,
+}
+
+struct Note {
+    id: String,
+    path: String,
+    content: String,
+}
+
+struct NotebookArticle {
+    title: String,
+    url: String,
+    published_at: i64,
+    entities: Vec<String>,
+}
+
+struct Notebook {
+    id: i64,
+    name: String,
+    articles: Vec<NotebookArticle>,
+}
+
+pub struct VaultExporter;
+
+impl VaultExporter {
+    pub fn vault_path(db: &Database) -> Option<String> {
+        db.get_config(CONFIG_VAULT_PATH).ok().flatten().filter(|p| !p.trim().is_empty())
+    }
+
+    /// Write events of the last `days` days, the entities they mention and
+    /// article folders (as notebooks) into `vault`, rewriting only notes
+    /// whose content changed.
+    pub fn export(db: &Database, vault: &Path, days: i64) -> Result<VaultExportReport> {
+        if !vault.is_dir() {
+            anyhow::bail!("Vault folder does not exist: {}", vault.display());
+        }
+        let since = chrono::Utc::now().timestamp() - days.max(1) * 86400;
+        let temporal = TemporalStore::new(db.conn.clone());
+        let osint = OSINTStore::new(db.conn.clone());
+
+        let events = temporal.list_events(MAX_EVENTS, Some(since), None)?;
+        let contexts: HashMap<i64, EventMatchContext> = temporal
+            .event_match_contexts(since)?
+            .into_iter()
+            .map(|c| (c.event_id, c))
+            .collect();
+        let mut commentary = HashMap::new();
+        for event in &events {
+            if let Some(c) = temporal.get_event_commentary(event.id)? {
+                commentary.insert(event.id, c);
+            }
+        }
+
+        let mut notebooks = Vec::new();
+        for folder in osint.list_folders()? {
+            let mut articles = Vec::new();
+            for item in osint.get_items_by_filter(None, None, None, Some(folder.id), None, MAX_NOTEBOOK_ARTICLES)? {
+                let entities = osint.get_entities_for_article(item.id)?.into_iter().map(|e| e.name).collect();
+                articles.push(NotebookArticle {
+                    title: item.title,
+                    url: item.url,
+                    published_at: item.published_at,
+                    entities,
+                });
+            }
+            notebooks.push(Notebook { id: folder.id, name: folder.name, articles });
+        }
+
+        let summaries = osint.entity_summaries(since)?;
+        let notes = build_notes(&events, &contexts, &commentary, &summaries, &notebooks);
+
+        let mut report = write_notes(&vault.join(EXPORT_DIR), &notes)?;
+        report.vault_path = vault.display().to_string();
+        report.events = notes.iter().filter(|n| n.id.starts_with("event-")).count();
+        report.entities = notes.iter().filter(|n| n.id.starts_with("entity-")).count();
+        report.notebooks = notebooks.len();
+        Ok(report)
+    }
+
+    pub fn start_scheduler(db: Arc<Mutex<Database>>) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(SYNC_INTERVAL_SECS));
+
+            loop {
+                interval.tick().await;
+                let db_guard = match db.lock() {
+                    Ok(guard) => guard,
+                    Err(_) => continue,
+                };
+                let Some(path) = Self::vault_path(&db_guard) else { continue };
+                match Self::export(&db_guard, Path::new(&path), DEFAULT_DAYS) {
+                    Ok(report) if report.written + report.removed > 0 => eprintln!(
+                        "Vault sync: {} notes written, {} removed",
+                        report.written, report.removed
+                    ),
+                    Ok(_) => {}
+                    Err(e) => eprintln!("Vault sync failed: {}", e),
+                }
+            }
+        });
+    }
+}
+
+fn build_notes(
+    events: &[TemporalEvent],
+    contexts: &HashMap<i64, EventMatchContext>,
+    commentary: &HashMap<i64, EventCommentary>,
+    summaries: &[EntitySummary],
+    notebooks: &[Notebook],
+) -> Vec<Note> {
+    let summary_by_key: HashMap<String, &EntitySummary> =
+        summaries.iter().map(|s| (s.name.to_lowercase(), s)).collect();
+    let mut names = NoteNames::default();
+
+    // Entity notes exist for entities some event or notebook mentions
+    let mut entity_events: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut entity_notebooks: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut co_mentions: HashMap<String, HashMap<String, usize>> = HashMap::new();
+
+    let mut event_notes = Vec::new();
+    for event in events {
+        let date = format_date(event.start_ts);
+        let event_name = names.claim(&format!("event-{}", event.id), &format!("{} {}", date, event.title));
+        let context = contexts.get(&event.id);
+        let mut entity_keys: Vec<String> = context
+            .map(|c| c.entities.iter().map(|e| e.to_lowercase()).collect::<HashSet<_>>().into_iter().collect())
+            .unwrap_or_default();
+        entity_keys.sort();
+        for key in &entity_keys {
+            entity_events.entry(key.clone()).or_default().push(event_name.clone());
+            let related = co_mentions.entry(key.clone()).or_default();
+            for other in entity_keys.iter().filter(|o| *o != key) {
+                *related.entry(other.clone()).or_default() += 1;
+            }
+        }
+        event_notes.push((event_name, event, entity_keys));
+    }
+    for notebook in notebooks {
+        let notebook_name = names.claim(&format!("notebook-{}", notebook.id), &notebook.name);
+        for article in &notebook.articles {
+            for entity in &article.entities {
+                let list = entity_notebooks.entry(entity.to_lowercase()).or_default();
+                if !list.contains(&notebook_name) {
+                    list.push(notebook_name.clone());
+                }
+            }
+        }
+    }
+
+    let mut entity_keys: Vec<String> = entity_events.keys().chain(entity_notebooks.keys()).cloned().collect();
+    entity_keys.sort();
+    entity_keys.dedup();
+    for key in &entity_keys {
+        let display = summary_by_key.get(key).map(|s| s.name.clone()).unwrap_or_else(|| key.clone());
+        names.claim(&entity_id(key), &display);
+    }
+
+    let mut out = Vec::new();
+    for (event_name, event, keys) in &event_notes {
+        let links: Vec<String> = keys.iter().filter_map(|k| names.link(&entity_id(k))).collect();
+        out.push(Note {
+            id: format!("event-{}", event.id),
+            path: format!("Events/{}.md", event_name),
+            content: event_note(event, contexts.get(&event.id), commentary.get(&event.id), &links),
+        });
+    }
+    for key in &entity_keys {
+        let Some(name) = names.get(&entity_id(key)) else { continue };
+        let mut related: Vec<(&String, &usize)> = co_mentions.get(key).map(|m| m.iter().collect()).unwrap_or_default();
+        related.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let related: Vec<(String, usize)> = related
+            .into_iter()
+            .filter_map(|(k, n)| names.link(&entity_id(k)).map(|l| (l, *n)))
+            .take(MAX_RELATED_ENTITIES)
+            .collect();
+        out.push(Note {
+            id: entity_id(key),
+            path: format!("Entities/{}.md", name),
+            content: entity_note(
+                &entity_id(key),
+                name,
+                summary_by_key.get(key).copied(),
+                entity_events.get(key).map(|v| v.as_slice()).unwrap_or_default(),
+                entity_notebooks.get(key).map(|v| v.as_slice()).unwrap_or_default(),
+                &related,
+            ),
+        });
+    }
+    for notebook in notebooks {
+        let id = format!("notebook-{}", notebook.id);
+        let Some(name) = names.get(&id) else { continue };
+        out.push(Note {
+            path: format!("Notebooks/{}.md", name),
+            content: notebook_note(notebook, &names),
+            id,
+        });
+    }
+    out
+}
+
+fn entity_id(key: &str) -> String {
+    format!("entity-{}", key)
+}
+
+/// File names per note ID, unique across the export.
+#[derive(Default)]
+struct NoteNames {
+    by_id: HashMap<String, String>,
+    used: HashSet<String>,
+}
+
+impl NoteNames {
+    fn claim(&mut self, id: &str, title: &str) -> String {
+        let base = sanitize_name(title);
+        let mut name = base.clone();
+        let mut n = 2;
+        while !self.used.insert(name.to_lowercase()) {
+            name = format!("{} {}", base, n);
+            n += 1;
+        }
+        self.by_id.insert(id.to_string(), name.clone());
+        name
+    }
+
+    fn get(&self, id: &str) -> Option<&String> {
+        self.by_id.get(id)
+    }
+
+    fn link(&self, id: &str) -> Option<String> {
+        self.get(id).map(|name| format!("[[{}]]", name))
+    }
+}
+
+/// A note title usable as a file name and inside `[[wiki links]]`.
+fn sanitize_name(title: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .map(|c| if "\\/:*?\"<>|#^[]".contains(c) || c.is_control() { ' ' } else { c })
+        .collect();
+    let collapsed = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    let trimmed: String = collapsed.trim_matches('.').chars().take(MAX_NAME_CHARS).collect();
+    if trimmed.trim().is_empty() {
+        "Untitled".to_string()
+    } else {
+        trimmed.trim().to_string()
+    }
+}
+
+fn format_date(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn format_datetime(ts: i64) -> String {
+    chrono::DateTime::from_timestamp(ts, 0)
+        .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        .unwrap_or_default()
+}
+
+/// YAML double-quoted scalar; JSON string escaping is a subset of it.
+fn yaml_str(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+fn yaml_list(values: &[String]) -> String {
+    format!("[{}]", values.iter().map(|v| yaml_str(v)).collect::<Vec<_>>().join(", "))
+}
+
+fn event_note(
+    event: &TemporalEvent,
+    context: Option<&EventMatchContext>,
+    commentary: Option<&EventCommentary>,
+    entity_links: &[String],
+) -> String {
+    let mut out = String::new();
+    out.push_str("---\n");
+    out.push_str(&format!("mina_id: event-{}\n", event.id));
+    out.push_str("type: event\n");
+    out.push_str(&format!("event_type: {}\n", yaml_str(&event.event_type)));
+    out.push_str(&format!("start: {}\n", format_datetime(event.start_ts)));
+    out.push_str(&format!("end: {}\n", format_datetime(event.end_ts)));
+    out.push_str(&format!("severity: {:.2}\n", event.severity));
+    out.push_str(&format!("confidence: {:.2}\n", event.confidence));
+    out.push_str(&format!("sentiment: {:.2}\n", event.sentiment_score));
+    out.push_str(&format!("lifecycle: {}\n", yaml_str(&event.lifecycle_state)));
+    out.push_str(&format!("corroborated_by: {}\n", event.corroborated_by));
+    out.push_str(&format!("entities: {}\n", yaml_list(entity_links)));
+    out.push_str("tags: [mina/event]\n");
+    out.push_str("---\n\n");
+
+    out.push_str(&format!("# {}\n\n", event.title));
+    if !event.summary.trim().is_empty() {
+        out.push_str(&format!("{}\n\n", event.summary.trim()));
+    }
+    if let Some(c) = commentary {
+        out.push_str(&format!("## What happened\n\n{}\n\n", c.what_happened.trim()));
+        out.push_str(&format!("## Why it matters\n\n{}\n\n", c.why_it_matters.trim()));
+    }
+    if !entity_links.is_empty() {
+        out.push_str("## Entities\n\n");
+        for link in entity_links {
+            out.push_str(&format!("- {}\n", link));
+        }
+        out.push('\n');
+    }
+    if let Some(context) = context.filter(|c| !c.article_urls.is_empty()) {
+        out.push_str("## Sources\n\n");
+        for (source, url) in context.sources.iter().zip(&context.article_urls) {
+            out.push_str(&format!("- [{}]({})\n", source, url));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn entity_note(
+    id: &str,
+    name: &str,
+    summary: Option<&EntitySummary>,
+    events: &[String],
+    notebooks: &[String],
+    related: &[(String, usize)],
+) -> String {
+    let mut out = String::new();
+    out.push_str("---\n");
+    out.push_str(&format!("mina_id: {}\n", yaml_str(id)));
+    out.push_str("type: entity\n");
+    if let Some(s) = summary {
+        out.push_str(&format!("entity_type: {}\n", yaml_str(&s.entity_type)));
+        out.push_str(&format!("mentions: {}\n", s.mentions));
+        out.push_str(&format!("first_seen: {}\n", format_datetime(s.first_seen)));
+        out.push_str(&format!("last_seen: {}\n", format_datetime(s.last_seen)));
+    }
+    out.push_str("tags: [mina/entity]\n");
+    out.push_str("---\n\n");
+    out.push_str(&format!("# {}\n\n", name));
+
+    if !events.is_empty() {
+        out.push_str("## Events\n\n");
+        for event in events {
+            out.push_str(&format!("- [[{}]]\n", event));
+        }
+        out.push('\n');
+    }
+    if !notebooks.is_empty() {
+        out.push_str("## Notebooks\n\n");
+        for notebook in notebooks {
+            out.push_str(&format!("- [[{}]]\n", notebook));
+        }
+        out.push('\n');
+    }
+    if !related.is_empty() {
+        out.push_str("## Related entities\n\n");
+        for (link, shared) in related {
+            out.push_str(&format!("- {} ({} shared events)\n", link, shared));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn notebook_note(notebook: &Notebook, names: &NoteNames) -> String {
+    let mut out = String::new();
+    out.push_str("---\n");
+    out.push_str(&format!("mina_id: notebook-{}\n", notebook.id));
+    out.push_str("type: notebook\n");
+    out.push_str(&format!("articles: {}\n", notebook.articles.len()));
+    out.push_str("tags: [mina/notebook]\n");
+    out.push_str("---\n\n");
+    out.push_str(&format!("# {}\n\n", notebook.name));
+
+    for article in &notebook.articles {
+        out.push_str(&format!("## [{}]({})\n\n", article.title.trim(), article.url));
+        out.push_str(&format!("Published {}", format_date(article.published_at)));
+        let links: Vec<String> = article
+            .entities
+            .iter()
+            .filter_map(|e| names.link(&entity_id(&e.to_lowercase())))
+            .collect();
+        if !links.is_empty() {
+            out.push_str(&format!(" · {}", links.join(", ")));
+        }
+        out.push_str("\n\n");
+    }
+    out
+}
+
+/// Write notes whose content changed and remove notes of the previous export
+/// that are gone or moved.
+fn write_notes(root: &Path, notes: &[Note]) -> Result<VaultExportReport> {
+    let manifest_path = root.join(MANIFEST_FILE);
+    let previous: Manifest = std::fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    let mut report = VaultExportReport::default();
+    let mut manifest = Manifest::default();
+    for note in notes {
+        let path = root.join(&note.path);
+        if std::fs::read_to_string(&path).ok().as_deref() == Some(note.content.as_str()) {
+            report.unchanged += 1;
+        } else {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            std::fs::write(&path, &note.content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            report.written += 1;
+        }
+        manifest.notes.insert(note.id.clone(), note.path.clone());
+    }
+
+    let current: HashSet<&String> = manifest.notes.values().collect();
+    for old_path in previous.notes.values().filter(|p| !current.contains(p)) {
+        let path: PathBuf = root.join(old_path);
+        if path.is_file() && std::fs::remove_file(&path).is_ok() {
+            report.removed += 1;
+        }
+    }
+
+    std::fs::create_dir_all(root).with_context(|| format!("Failed to create {}", root.display()))?;
+    std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)
+        .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_safe_for_files_and_wiki_links() {
+        assert_eq!(sanitize_name("Fed: rates [held] at 5.5%"), "Fed rates held at 5.5%");
+        assert_eq!(sanitize_name("a/b\\c#d"), "a b c d");
+        assert_eq!(sanitize_name("  ...  "), "Untitled");
+
+        let mut names = NoteNames::default();
+        assert_eq!(names.claim("entity-apple", "Apple"), "Apple");
+        assert_eq!(names.claim("notebook-1", "apple"), "apple 2");
+        assert_eq!(names.link("entity-apple").as_deref(), Some("[[Apple]]"));
+    }
+
+    #[test]
+    fn rewrites_only_changed_notes_and_removes_stale_ones() {
+        let root = std::env::temp_dir().join(format!("mina-vault-{}", uuid::Uuid::new_v4()));
+        let note = |id: &str, path: &str, content: &str| Note {
+            id: id.to_string(),
+            path: path.to_string(),
+            content: content.to_string(),
+        };
+
+        let first = write_notes(
+            &root,
+            &[note("event-1", "Events/Old.md", "a"), note("entity-x", "Entities/X.md", "x")],
+        )
+        .unwrap();
+        assert_eq!(first.written, 2);
+
+        let second = write_notes(
+            &root,
+            &[note("event-1", "Events/New.md", "a"), note("entity-x", "Entities/X.md", "x")],
+        )
+        .unwrap();
+        assert_eq!((second.written, second.unchanged, second.removed), (1, 1, 1));
+        assert!(!root.join("Events/Old.md").exists());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,354 @@
+use crate::services::api_key_manager::APIKeyManager;
+use crate::services::document_ingest::{DocumentIngest, CONFIG_WATCH_FOLDER};
+use crate::services::statement_import::{parse_timestamp, StatementImporter};
+use crate::services::transcription::Transcriber;
+use crate::storage::statement_imports::StatementImportStore;
+use crate::storage::temporal::{NewTemporalEvent, TemporalStore};
+use crate::storage::watch_folders::{WatchFolder, WatchFolderFile, WatchFolderStore};
+use crate::storage::{Database, PortfolioStore};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tauri::{AppHandle, Manager};
+
+pub const PIPELINE_DOCUMENTS: &str = "documents";
+pub const PIPELINE_PORTFOLIO_CSV: &str = "portfolio_csv";
+pub const PIPELINE_JSON_EVENTS: &str = "json_events";
+pub const PIPELINE_OLLAMA_MODELS: &str = "ollama_models";
+
+const SCAN_INTERVAL_SECS: u64 = 60;
+/// Files modified more recently may still be being written
+const SETTLE_SECS: u64 = 10;
+const MODEL_EXTENSIONS: [&str; 5] = ["gguf", "bin", "safetensors", "pt", "pth"];
+
+/// What a watch folder does with the files dropped into it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pipeline {
+    /// PDF/DOCX/text/screenshots into the document corpus, audio transcribed
+    Documents,
+    /// Broker/bank statement CSVs into a portfolio, through a mapping
+    /// profile when the folder names one
+    PortfolioCsv,
+    /// Webhook-style JSON payloads into temporal events
+    JsonEvents,
+    /// Model files announced for loading into Ollama
+    OllamaModels,
+}
+
+impl Pipeline {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            PIPELINE_DOCUMENTS => Ok(Pipeline::Documents),
+            PIPELINE_PORTFOLIO_CSV => Ok(Pipeline::PortfolioCsv),
+            PIPELINE_JSON_EVENTS => Ok(Pipeline::JsonEvents),
+            PIPELINE_OLLAMA_MODELS => Ok(Pipeline::OllamaModels),
+            other => anyhow::bail!("Unknown watch folder pipeline: {}", other),
+        }
+    }
+
+    pub fn accepts(&self, path: &Path) -> bool {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+        match self {
+            Pipeline::Documents => DocumentIngest::is_supported(path) || Transcriber::is_audio(path),
+            Pipeline::PortfolioCsv => extension == "csv",
+            Pipeline::JsonEvents => extension == "json",
+            Pipeline::OllamaModels => MODEL_EXTENSIONS.contains(&extension.as_str()),
+        }
+    }
+}
+
+/// What one scan of a folder did.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchScanReport {
+    pub folder_id: i64,
+    pub processed: usize,
+    pub duplicates: usize,
+    pub failed: usize,
+    pub files: Vec<WatchFolderFile>,
+}
+
+/// Maps watch folders to ingestion pipelines and feeds them the files that
+/// appear, tracking each handled file by content hash.
+pub struct WatchFolders;
+
+impl WatchFolders {
+    /// Check a folder definition before it's saved.
+    pub fn validate(db: &Database, path: &str, pipeline: &str, options: &Value) -> Result<()> {
+        let pipeline = Pipeline::parse(pipeline)?;
+        if !Path::new(path).is_dir() {
+            anyhow::bail!("Not a folder: {}", path);
+        }
+        if pipeline == Pipeline::PortfolioCsv {
+            let portfolio_id = options["portfolio_id"]
+                .as_i64()
+                .context("A portfolio_csv folder needs a portfolio_id option")?;
+            if PortfolioStore::new(db.conn.clone()).get_portfolio(portfolio_id)?.is_none() {
+                anyhow::bail!("Portfolio {} not found", portfolio_id);
+            }
+            if let Some(profile_id) = options["profile_id"].as_i64() {
+                if StatementImportStore::new(db.conn.clone()).get_profile(profile_id)?.is_none() {
+                    anyhow::bail!("Mapping profile {} not found", profile_id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The single document watch folder setting predates watch folders;
+    /// carry it over as a documents folder.
+    pub fn migrate_legacy(db: &Database) -> Result<()> {
+        let Some(folder) = DocumentIngest::watch_folder(db) else { return Ok(()) };
+        let path = folder.display().to_string();
+        let store = WatchFolderStore::new(db.conn.clone());
+        if store.find_folder(&path, PIPELINE_DOCUMENTS)?.is_none() {
+            store.create_folder(&path, PIPELINE_DOCUMENTS, &serde_json::json!({}))?;
+        }
+        db.set_config(CONFIG_WATCH_FOLDER, "")?;
+        Ok(())
+    }
+
+    pub fn start_scheduler(db: Arc<Mutex<Database>>, api_key_manager: Arc<APIKeyManager>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            if let Ok(db_guard) = db.lock() {
+                if let Err(e) = Self::migrate_legacy(&db_guard) {
+                    eprintln!("Failed to migrate the document watch folder: {}", e);
+                }
+            }
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(SCAN_INTERVAL_SECS));
+            // Path and modification time of files already looked at this session
+            let mut seen: HashSet<(PathBuf, SystemTime)> = HashSet::new();
+
+            loop {
+                interval.tick().await;
+                let folders = match db.lock() {
+                    Ok(guard) => WatchFolderStore::new(guard.conn.clone()).list_folders().unwrap_or_default(),
+                    Err(_) => continue,
+                };
+                for folder in folders.into_iter().filter(|f| f.enabled) {
+                    if let Err(e) = Self::scan(&db, &api_key_manager, Some(&app), &folder, &mut seen).await {
+                        eprintln!("Failed to scan watch folder {}: {}", folder.path, e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Run the folder's pipeline on every settled file it hasn't handled.
+    pub async fn scan(
+        db: &Mutex<Database>,
+        api_key_manager: &APIKeyManager,
+        app: Option<&AppHandle>,
+        folder: &WatchFolder,
+        seen: &mut HashSet<(PathBuf, SystemTime)>,
+    ) -> Result<WatchScanReport> {
+        let pipeline = Pipeline::parse(&folder.pipeline)?;
+        let store = {
+            let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+            WatchFolderStore::new(db_guard.conn.clone())
+        };
+        let entries = std::fs::read_dir(&folder.path)
+            .with_context(|| format!("Failed to read {}", folder.path))?;
+
+        let mut report = WatchScanReport { folder_id: folder.id, ..Default::default() };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(modified) = entry.metadata().and_then(|m| m.modified()) else { continue };
+            let settled = modified.elapsed().is_ok_and(|age| age.as_secs() >= SETTLE_SECS);
+            if !path.is_file() || !pipeline.accepts(&path) || !settled || !seen.insert((path.clone(), modified)) {
+                continue;
+            }
+
+            let Ok(bytes) = std::fs::read(&path) else {
+                // Locked or vanished; look again next scan
+                seen.remove(&(path, modified));
+                continue;
+            };
+            let file_hash = format!("{:x}", Sha256::digest(&bytes));
+            if store.is_handled(folder.id, &file_hash)? {
+                continue;
+            }
+
+            let (status, detail) = match Self::process(db, api_key_manager, app, pipeline, folder, &path, &file_hash).await {
+                Ok((duplicate, detail)) => (if duplicate { "duplicate" } else { "processed" }, detail),
+                Err(e) => ("failed", e.to_string()),
+            };
+            match status {
+                "processed" => report.processed += 1,
+                "duplicate" => report.duplicates += 1,
+                _ => report.failed += 1,
+            }
+            let file = WatchFolderFile {
+                folder_id: folder.id,
+                file_hash,
+                path: path.display().to_string(),
+                status: status.to_string(),
+                detail: Some(detail),
+                processed_at: chrono::Utc::now().timestamp(),
+            };
+            store.record_file(&file)?;
+            if let Some(app) = app {
+                let _ = crate::services::window_router::emit(app, serde_json::json!({
+                    "type": "watch-folder-file",
+                    "data": file,
+                    "timestamp": chrono::Utc::now().timestamp_millis(),
+                }));
+            }
+            report.files.push(file);
+        }
+
+        store.mark_scanned(folder.id, chrono::Utc::now().timestamp())?;
+        Ok(report)
+    }
+
+    /// (duplicate, what happened)
+    async fn process(
+        db: &Mutex<Database>,
+        api_key_manager: &APIKeyManager,
+        app: Option<&AppHandle>,
+        pipeline: Pipeline,
+        folder: &WatchFolder,
+        path: &Path,
+        file_hash: &str,
+    ) -> Result<(bool, String)> {
+        match pipeline {
+            Pipeline::Documents if Transcriber::is_audio(path) => {
+                let result = Transcriber::transcribe_file(db, path, None, None, None).await?;
+                Ok((result.duplicate, format!("Transcript {} ({} segments)", result.transcript_id, result.segments)))
+            }
+            Pipeline::Documents => {
+                let embedder = crate::commands::memory::embedding_service(api_key_manager, db);
+                let result = DocumentIngest::ingest(db, &embedder, path).await?;
+                Ok((result.duplicate, format!("Document {} ({} chars)", result.document_id, result.char_count)))
+            }
+            Pipeline::PortfolioCsv => {
+                let portfolio_id = folder.options["portfolio_id"]
+                    .as_i64()
+                    .context("Folder has no portfolio_id option")?;
+                let profile_id = folder.options["profile_id"].as_i64();
+                let text = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+                let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+                let mapping = StatementImporter::resolve_mapping(
+                    &StatementImportStore::new(db_guard.conn.clone()),
+                    &text,
+                    profile_id,
+                    None,
+                )?;
+                let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let import = StatementImporter::commit(&db_guard.conn, portfolio_id, profile_id, &file_name, &text, mapping, false)?;
+                Ok((
+                    import.rows_imported == 0,
+                    format!(
+                        "Import {}: {} transactions into portfolio {} ({} already there)",
+                        import.id, import.rows_imported, portfolio_id, import.rows_duplicate
+                    ),
+                ))
+            }
+            Pipeline::JsonEvents => {
+                let text = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+                let payload: Value = serde_json::from_str(&text).context("Not valid JSON")?;
+                let events = parse_events(&payload, file_hash)?;
+                let created = {
+                    let db_guard = db.lock().map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+                    let store = TemporalStore::new(db_guard.conn.clone());
+                    let mut created = Vec::new();
+                    for event in &events {
+                        let (id, is_new) = store.upsert_event(event, &[])?;
+                        if is_new {
+                            created.push((id, event.title.clone(), event.event_type.clone()));
+                        }
+                    }
+                    created
+                };
+                let new_events = created.len();
+                if let Some(bus) = app.and_then(|a| a.try_state::<Arc<crate::services::AutomationEventBus>>()) {
+                    for (event_id, title, event_type) in created {
+                        let _ = bus
+                            .emit(crate::services::AutomationEvent::TemporalEventCreated { event_id, title, event_type })
+                            .await;
+                    }
+                }
+                Ok((false, format!("{} events ({} new)", events.len(), new_events)))
+            }
+            Pipeline::OllamaModels => {
+                if let Some(app) = app {
+                    let _ = crate::services::window_router::emit(app, serde_json::json!({
+                        "type": "ollama-model-file",
+                        "data": { "path": path.display().to_string(), "folder_id": folder.id },
+                        "timestamp": chrono::Utc::now().timestamp_millis(),
+                    }));
+                }
+                Ok((false, format!(
+                    "Model file detected; load it with: ollama create <name> -f <Modelfile pointing at {}>",
+                    path.display()
+                )))
+            }
+        }
+    }
+}
+
+/// Events from a webhook-style payload: one object or an array of them,
+/// each with at least a title. An `id` makes re-sent events update rather
+/// than duplicate; without one, events are keyed by file and position.
+fn parse_events(payload: &Value, file_hash: &str) -> Result<Vec<NewTemporalEvent>> {
+    let items: Vec<&Value> = match payload {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(_) => vec![payload],
+        _ => anyhow::bail!("Expected a JSON object or array of objects"),
+    };
+    let now = chrono::Utc::now().timestamp();
+    let timestamp = |value: &Value| match value {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => parse_timestamp(s),
+        _ => None,
+    };
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let title = item["title"]
+                .as_str()
+                .filter(|t| !t.trim().is_empty())
+                .with_context(|| format!("event {}: missing title", index))?;
+            let start_ts = timestamp(&item["start_ts"]).or_else(|| timestamp(&item["timestamp"])).unwrap_or(now);
+            let cluster_key = match &item["id"] {
+                Value::String(id) => format!("watch:{}", id),
+                Value::Number(id) => format!("watch:{}", id),
+                _ => format!("watch:{}:{}", &file_hash[..16], index),
+            };
+            Ok(NewTemporalEvent {
+                title: title.trim().to_string(),
+                summary: item["summary"].as_str().unwrap_or_default().to_string(),
+                start_ts,
+                end_ts: timestamp(&item["end_ts"]).unwrap_or(start_ts),
+                event_type: item["event_type"].as_str().unwrap_or("external").to_string(),
+                confidence: item["confidence"].as_f64().unwrap_or(1.0).clamp(0.0, 1.0),
+                severity: item["severity"].as_f64().unwrap_or(0.5).clamp(0.0, 1.0),
+                novelty_score: 0.0,
+                volume_score: 0.0,
+                sentiment_score: item["sentiment"].as_f64().unwrap_or(0.0).clamp(-1.0, 1.0),
+                cluster_key,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_webhook_events() {
+        let events = parse_events(&serde_json::json!([{ "id": "evt-1", "title": "Plant outage", "timestamp": "2024-03-01" }]), "ab".repeat(32).as_str()).unwrap();
+        assert_eq!((events[0].cluster_key.as_str(), events[0].start_ts), ("watch:evt-1", 1_709_251_200));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::storage::Database;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Missed intervals after which a loop counts as stuck. Unset means 3.
+pub const CONFIG_MISSED_INTERVALS: &str = "watchdog_missed_intervals";
+const DEFAULT_MISSED_INTERVALS: i64 = 3;
+const CHECK_INTERVAL_SECS: u64 = 5;
+/// Loops ticking faster than the watchdog checks are judged at its pace
+const MIN_INTERVAL_SECS: i64 = CHECK_INTERVAL_SECS as i64;
+/// Restarts per loop before the watchdog only alerts
+const MAX_RESTARTS: u32 = 3;
+
+type Restart = Arc<dyn Fn() + Send + Sync>;
+
+struct Entry {
+    interval_secs: i64,
+    generation: u64,
+    registered_at: i64,
+    last_beat: i64,
+    beats: u64,
+    restarts: u32,
+    restart: Option<Restart>,
+    alerted: bool,
+}
+
+static LOOPS: OnceLock<Mutex<BTreeMap<String, Entry>>> = OnceLock::new();
+
+fn loops() -> &'static Mutex<BTreeMap<String, Entry>> {
+    LOOPS.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoopStatus {
+    pub name: String,
+    pub interval_secs: i64,
+    pub registered_at: i64,
+    pub last_beat: i64,
+    pub seconds_since_beat: i64,
+    pub missed_intervals: i64,
+    pub state: String, // ok|late|stuck
+    pub beats: u64,
+    pub restarts: u32,
+    pub restartable: bool,
+}
+
+/// Handed to a loop when it registers; the loop beats once per iteration.
+pub struct Heartbeat {
+    name: String,
+    generation: u64,
+}
+
+impl Heartbeat {
+    /// Record that the loop is alive. False once a restart has replaced
+    /// this loop, which should then exit.
+    pub fn beat(&self) -> bool {
+        let Ok(mut loops) = loops().lock() else {
+            return true;
+        };
+        match loops.get_mut(&self.name) {
+            Some(entry) if entry.generation == self.generation => {
+                entry.last_beat = chrono::Utc::now().timestamp();
+                entry.beats += 1;
+                entry.alerted = false;
+                true
+            }
+            Some(_) => false,
+            None => true,
+        }
+    }
+}
+
+enum Action {
+    Restart(String, Restart),
+    Alert(String, i64),
+}
+
+/// Tracks heartbeats of long-running loops and restarts, or alerts about,
+/// the ones that stop beating.
+pub struct Watchdog;
+
+impl Watchdog {
+    /// Start watching a loop that iterates every `interval`. Registering a
+    /// name again (as a restart does) supersedes the previous loop.
+    pub fn register(name: &str, interval: Duration) -> Heartbeat {
+        let now = chrono::Utc::now().timestamp();
+        let mut loops = loops().lock().unwrap_or_else(|e| e.into_inner());
+        let entry = loops.entry(name.to_string()).or_insert_with(|| Entry {
+            interval_secs: 0,
+            generation: 0,
+            registered_at: now,
+            last_beat: now,
+            beats: 0,
+            restarts: 0,
+            restart: None,
+            alerted: false,
+        });
+        entry.interval_secs = (interval.as_secs() as i64).max(MIN_INTERVAL_SECS);
+        entry.generation += 1;
+        entry.registered_at = now;
+        entry.last_beat = now;
+        entry.alerted = false;
+        Heartbeat { name: name.to_string(), generation: entry.generation }
+    }
+
+    /// How to start the loop again when it gets stuck. Loops without one
+    /// are only alerted about.
+    pub fn on_restart(name: &str, restart: impl Fn() + Send + Sync + 'static) {
+        let mut loops = loops().lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = loops.get_mut(name) {
+            entry.restart = Some(Arc::new(restart));
+        }
+    }
+
+    pub fn missed_intervals(db: &Database) -> i64 {
+        db.get_config(CONFIG_MISSED_INTERVALS)
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .filter(|n| *n >= 2)
+            .unwrap_or(DEFAULT_MISSED_INTERVALS)
+    }
+
+    /// Every watched loop with its last heartbeat.
+    pub fn status(now: i64, missed_threshold: i64) -> Vec<LoopStatus> {
+        let loops = loops().lock().unwrap_or_else(|e| e.into_inner());
+        loops
+            .iter()
+            .map(|(name, entry)| {
+                let missed = (now - entry.last_beat) / entry.interval_secs;
+                let state = if missed >= missed_threshold {
+                    "stuck"
+                } else if missed >= 1 {
+                    "late"
+                } else {
+                    "ok"
+                };
+                LoopStatus {
+                    name: name.clone(),
+                    interval_secs: entry.interval_secs,
+                    registered_at: entry.registered_at,
+                    last_beat: entry.last_beat,
+                    seconds_since_beat: now - entry.last_beat,
+                    missed_intervals: missed,
+                    state: state.to_string(),
+                    beats: entry.beats,
+                    restarts: entry.restarts,
+                    restartable: entry.restart.is_some(),
+                }
+            })
+            .collect()
+    }
+
+    /// Decide what to do about loops that missed `missed_threshold`
+    /// intervals. Restarts run after the lock is released, since they
+    /// register the loop again.
+    fn sweep(now: i64, missed_threshold: i64) -> Vec<Action> {
+        let mut loops = loops().lock().unwrap_or_else(|e| e.into_inner());
+        let mut actions = Vec::new();
+        for (name, entry) in loops.iter_mut() {
+            if (now - entry.last_beat) / entry.interval_secs < missed_threshold {
+                continue;
+            }
+            match &entry.restart {
+                Some(restart) if entry.restarts < MAX_RESTARTS => {
+                    entry.restarts += 1;
+                    entry.last_beat = now;
+                    actions.push(Action::Restart(name.clone(), restart.clone()));
+                }
+                _ if !entry.alerted => {
+                    entry.alerted = true;
+                    actions.push(Action::Alert(name.clone(), now - entry.last_beat));
+                }
+                _ => {}
+            }
+        }
+        actions
+    }
+
+    pub fn start(db: Arc<Mutex<Database>>, app: AppHandle) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+
+            loop {
+                interval.tick().await;
+                let missed = db.lock().map(|db| Self::missed_intervals(&db)).unwrap_or(DEFAULT_MISSED_INTERVALS);
+
+                for action in Self::sweep(chrono::Utc::now().timestamp(), missed) {
+                    match action {
+                        Action::Restart(name, restart) => {
+                            eprintln!("Watchdog: {} stopped beating, restarting it", name);
+                            restart();
+                        }
+                        Action::Alert(name, silent_for) => Self::alert(&app, &name, silent_for).await,
+                    }
+                }
+            }
+        });
+    }
+
+    async fn alert(app: &AppHandle, name: &str, silent_for: i64) {
+        use crate::services::desktop_notifications::{DesktopNotificationService, NotificationOptions};
+
+        eprintln!("Watchdog: {} has not beaten for {}s", name, silent_for);
+        crate::ws::publish_alert(app, "watchdog", serde_json::json!({ "loop": name, "silent_for": silent_for }));
+        let _ = DesktopNotificationService::send(app, NotificationOptions {
+            title: "Background task stuck".to_string(),
+            body: format!("{} has not responded for {} minutes.", name, (silent_for / 60).max(1)),
+            icon: Some("alert".to_string()),
+            sound: None,
+            tag: Some(format!("watchdog-{}", name)),
+            data: Some(serde_json::json!({ "type": "watchdog", "loop": name })),
+        }).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn stuck_loops_are_restarted_then_alerted_about() {
+        let name = "test-stuck-loop";
+        let first = Watchdog::register(name, Duration::from_secs(10));
+        let restarts = Arc::new(AtomicUsize::new(0));
+        let counter = restarts.clone();
+        Watchdog::on_restart(name, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Watchdog::register(name, Duration::from_secs(10));
+        });
+        let now = chrono::Utc::now().timestamp();
+        let ours = |actions: Vec<Action>| -> Vec<Action> {
+            actions
+                .into_iter()
+                .filter(|a| matches!(a, Action::Restart(n, _) | Action::Alert(n, _) if n == name))
+                .collect()
+        };
+
+        assert!(ours(Watchdog::sweep(now + 20, 3)).is_empty());
+        let status = Watchdog::status(now + 20, 3).into_iter().find(|s| s.name == name).unwrap();
+        assert_eq!(status.state, "late");
+
+        for round in 1..=MAX_RESTARTS as i64 {
+            let actions = ours(Watchdog::sweep(now + round * 100, 3));
+            assert_eq!(actions.len(), 1);
+            if let Action::Restart(_, restart) = &actions[0] {
+                restart();
+            } else {
+                panic!("expected a restart");
+            }
+        }
+        assert_eq!(restarts.load(Ordering::SeqCst), MAX_RESTARTS as usize);
+        // The replaced loop is told to stop
+        assert!(!first.beat());
+
+        let actions = ours(Watchdog::sweep(now + 1000, 3));
+        assert!(matches!(actions.as_slice(), [Action::Alert(_, _)]));
+        // Alerted once until it beats again
+        assert!(ours(Watchdog::sweep(now + 2000, 3)).is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::services::corroboration::domain_of;
+use crate::storage::temporal::{EventMatchContext, TemporalStore, WatchlistItem};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Labeled alerts needed before an item's helpful ratio drives a suggestion.
+const MIN_LABELED_ALERTS: i64 = 3;
+const PRODUCTIVE_RATIO: f64 = 0.7;
+const NOISY_RATIO: f64 = 0.3;
+const WEIGHT_STEP: f64 = 1.5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchlistItemStats {
+    pub item_id: i64,
+    pub item_type: String,
+    pub value: String,
+    pub weight: f64,
+    pub enabled: bool,
+    pub events_matched: i64,
+    pub alerts: i64,
+    pub helpful: i64,
+    pub unhelpful: i64,
+    /// Helpful share of labeled alerts, once any are labeled
+    pub helpful_ratio: Option<f64>,
+    pub suggestion: Option<String>, // retire|increase_weight|decrease_weight
+    pub suggested_weight: Option<f64>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchlistAnalytics {
+    pub watchlist_id: i64,
+    pub days: i64,
+    /// Rules tied to this watchlist. When there are none, alerts of every rule count.
+    pub linked_rules: usize,
+    pub items: Vec<WatchlistItemStats>,
+}
+
+pub struct WatchlistAnalyzer;
+
+impl WatchlistAnalyzer {
+    /// How many events each item of the watchlist matched in the last `days`
+    /// days, how the alerts on those events were labeled, and what to do
+    /// about items that never match or mostly produce noise.
+    pub fn analyze(temporal_store: &TemporalStore, watchlist_id: i64, days: i64) -> Result<WatchlistAnalytics> {
+        let now = chrono::Utc::now().timestamp();
+        let since = now - days * 86400;
+        let items = temporal_store.list_watchlist_items(watchlist_id)?;
+        let contexts = temporal_store.event_match_contexts(since)?;
+        let outcomes = temporal_store.alert_outcomes(since)?;
+
+        let linked: HashSet<i64> = temporal_store
+            .list_alert_rules_for(None)?
+            .into_iter()
+            .filter(|r| r.watchlist_id == Some(watchlist_id))
+            .map(|r| r.id)
+            .collect();
+
+        let mut stats = Vec::new();
+        for item in &items {
+            let matched: HashSet<i64> = contexts
+                .iter()
+                .filter(|c| item_matches(item, c))
+                .map(|c| c.event_id)
+                .collect();
+
+            let (mut alerts, mut helpful, mut unhelpful) = (0, 0, 0);
+            for outcome in &outcomes {
+                let on_matched = outcome.event_id.map(|id| matched.contains(&id)).unwrap_or(false);
+                if !on_matched || (!linked.is_empty() && !linked.contains(&outcome.rule_id)) {
+                    continue;
+                }
+                alerts += 1;
+                match outcome.label {
+                    Some(l) if l > 0 => helpful += 1,
+                    Some(l) if l < 0 => unhelpful += 1,
+                    _ => {}
+                }
+            }
+
+            let mut item_stats = WatchlistItemStats {
+                item_id: item.id,
+                item_type: item.item_type.clone(),
+                value: item.value.clone(),
+                weight: item.weight,
+                enabled: item.enabled,
+                events_matched: matched.len() as i64,
+                alerts,
+                helpful,
+                unhelpful,
+                helpful_ratio: if helpful + unhelpful > 0 {
+                    Some(helpful as f64 / (helpful + unhelpful) as f64)
+                } else {
+                    None
+                },
+                suggestion: None,
+                suggested_weight: None,
+                reason: None,
+            };
+            suggest(&mut item_stats, item.created_at < since, days);
+            stats.push(item_stats);
+        }
+
+        Ok(WatchlistAnalytics {
+            watchlist_id,
+            days,
+            linked_rules: linked.len(),
+            items: stats,
+        })
+    }
+}
+
+pub(crate) fn item_matches(item: &WatchlistItem, context: &EventMatchContext) -> bool {
+    let value = item.value.trim().to_lowercase();
+    if value.is_empty() {
+        return false;
+    }
+    match item.item_type.as_str() {
+        "entity" => context.entities.iter().any(|e| e.to_lowercase() == value),
+        "keyword" => {
+            context.title.to_lowercase().contains(&value) || context.summary.to_lowercase().contains(&value)
+        }
+        "domain" => {
+            let wanted = domain_of(&value).unwrap_or(value);
+            context
+                .article_urls
+                .iter()
+                .filter_map(|u| domain_of(u.as_str()))
+                .any(|d| d == wanted || d.ends_with(&format!(".{}", wanted)))
+        }
+        "source" => context.sources.iter().any(|s| s.to_lowercase() == value),
+        _ => false,
+    }
+}
+
+/// Only enabled items get suggestions; an item added within the window
+/// hasn't had the chance to match yet and is never suggested for retirement.
+fn suggest(stats: &mut WatchlistItemStats, older_than_window: bool, days: i64) {
+    if !stats.enabled {
+        return;
+    }
+    let labeled = stats.helpful + stats.unhelpful;
+    if stats.events_matched == 0 && older_than_window {
+        stats.suggestion = Some("retire".to_string());
+        stats.reason = Some(format!("No matching events in the last {} days", days));
+    } else if labeled >= MIN_LABELED_ALERTS {
+        let ratio = stats.helpful_ratio.unwrap_or(0.0);
+        if ratio >= PRODUCTIVE_RATIO {
+            stats.suggestion = Some("increase_weight".to_string());
+            stats.suggested_weight = Some(stats.weight * WEIGHT_STEP);
+            stats.reason = Some(format!("{} of {} labeled alerts were helpful", stats.helpful, labeled));
+        } else if ratio <= NOISY_RATIO {
+            stats.suggestion = Some("decrease_weight".to_string());
+            stats.suggested_weight = Some(stats.weight / WEIGHT_STEP);
+            stats.reason = Some(format!("{} of {} labeled alerts were unhelpful", stats.unhelpful, labeled));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(item_type: &str, value: &str) -> WatchlistItem {
+        WatchlistItem {
+            id: 1,
+            watchlist_id: 1,
+            item_type: item_type.to_string(),
+            value: value.to_string(),
+            weight: 1.0,
+            enabled: true,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn matches_by_item_type() {
+        let context = EventMatchContext {
+            event_id: 1,
+            title: "Chip export rules tightened".to_string(),
+            summary: String::new(),
+            entities: vec!["NVIDIA".to_string()],
+            sources: vec!["Reuters Tech".to_string()],
+            article_urls: vec!["https://www.reuters.com/tech/a".to_string()],
+        };
+        assert!(item_matches(&item("entity", "nvidia"), &context));
+        assert!(item_matches(&item("keyword", "export rules"), &context));
+        assert!(item_matches(&item("domain", "reuters.com"), &context));
+        assert!(item_matches(&item("source", "reuters tech"), &context));
+        assert!(!item_matches(&item("domain", "apnews.com"), &context));
+    }
+}
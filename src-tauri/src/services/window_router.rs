@@ -0,0 +1,290 @@
+use crate::storage::windows::{AppWindow, WindowStore};
+use anyhow::Result;
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+pub const MAIN_WINDOW: &str = "main";
+const FLUSH_INTERVAL_SECS: u64 = 2;
+
+/// Tracks every window's layout and the WebSocket message types it wants.
+/// Moves and resizes are kept in memory and written every few seconds.
+#[derive(Clone)]
+pub struct WindowRouter {
+    store: Arc<WindowStore>,
+    windows: Arc<Mutex<HashMap<String, AppWindow>>>,
+    dirty: Arc<Mutex<HashSet<String>>>,
+}
+
+impl WindowRouter {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = WindowStore::new(conn);
+        let mut windows: HashMap<String, AppWindow> = store
+            .list_windows()
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to load window layouts: {}", e);
+                Vec::new()
+            })
+            .into_iter()
+            .map(|w| (w.label.clone(), w))
+            .collect();
+        // The main window sees everything unless narrowed explicitly
+        windows.entry(MAIN_WINDOW.to_string()).or_insert_with(|| AppWindow {
+            label: MAIN_WINDOW.to_string(),
+            route: "/".to_string(),
+            title: "MINA".to_string(),
+            x: None,
+            y: None,
+            width: 1400,
+            height: 900,
+            maximized: false,
+            topics: vec!["*".to_string()],
+            open: true,
+            updated_at: 0,
+        });
+
+        WindowRouter {
+            store: Arc::new(store),
+            windows: Arc::new(Mutex::new(windows)),
+            dirty: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Put the main window back where it was and reopen detached windows
+    /// that were open at quit, then start writing layout changes.
+    pub fn restore(&self, app: &AppHandle) {
+        let windows = self.list();
+        for window in windows.iter().filter(|w| w.open) {
+            if window.label == MAIN_WINDOW {
+                if let Some(main) = app.get_webview_window(MAIN_WINDOW) {
+                    if window.updated_at > 0 {
+                        let _ = main.set_size(tauri::LogicalSize::new(window.width as f64, window.height as f64));
+                        if let (Some(x), Some(y)) = (window.x, window.y) {
+                            let _ = main.set_position(tauri::LogicalPosition::new(x as f64, y as f64));
+                        }
+                        if window.maximized {
+                            let _ = main.maximize();
+                        }
+                    }
+                }
+            } else if let Err(e) = build_window(app, window) {
+                eprintln!("Failed to restore window {}: {}", window.label, e);
+            }
+        }
+
+        let router = self.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(FLUSH_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                router.flush();
+            }
+        });
+    }
+
+    /// Open a detached window, or focus it and apply the new topics when it
+    /// is already open. Size and position come from its saved layout.
+    pub fn open(&self, app: &AppHandle, label: &str, route: &str, title: &str, topics: Vec<String>) -> Result<AppWindow> {
+        if label == MAIN_WINDOW {
+            anyhow::bail!("The main window can't be reopened");
+        }
+        let window = {
+            let mut windows = self.windows.lock()
+                .map_err(|e| anyhow::anyhow!("Window registry lock error: {}", e))?;
+            let window = windows.entry(label.to_string()).or_insert_with(|| AppWindow {
+                label: label.to_string(),
+                route: route.to_string(),
+                title: title.to_string(),
+                x: None,
+                y: None,
+                width: 900,
+                height: 700,
+                maximized: false,
+                topics: Vec::new(),
+                open: true,
+                updated_at: 0,
+            });
+            window.route = route.to_string();
+            window.title = title.to_string();
+            window.topics = topics;
+            window.open = true;
+            window.clone()
+        };
+        self.store.save_window(&window)?;
+
+        match app.get_webview_window(label) {
+            Some(existing) => {
+                let _ = existing.set_focus();
+            }
+            None => build_window(app, &window)?,
+        }
+        Ok(window)
+    }
+
+    /// Close a window and forget it should reopen; its layout is kept.
+    pub fn close(&self, app: &AppHandle, label: &str) -> Result<()> {
+        self.mark_closed(label)?;
+        if let Some(window) = app.get_webview_window(label) {
+            window.close()?;
+        }
+        Ok(())
+    }
+
+    pub fn set_topics(&self, label: &str, topics: Vec<String>) -> Result<AppWindow> {
+        let window = {
+            let mut windows = self.windows.lock()
+                .map_err(|e| anyhow::anyhow!("Window registry lock error: {}", e))?;
+            let window = windows
+                .get_mut(label)
+                .ok_or_else(|| anyhow::anyhow!("Unknown window: {}", label))?;
+            window.topics = topics;
+            window.clone()
+        };
+        self.store.save_window(&window)?;
+        Ok(window)
+    }
+
+    pub fn list(&self) -> Vec<AppWindow> {
+        let mut windows: Vec<AppWindow> = self
+            .windows
+            .lock()
+            .map(|w| w.values().cloned().collect())
+            .unwrap_or_default();
+        windows.sort_by(|a, b| a.label.cmp(&b.label));
+        windows
+    }
+
+    /// Labels of open windows subscribed to a message type.
+    pub fn targets(&self, message_type: &str) -> Vec<String> {
+        let Ok(windows) = self.windows.lock() else { return vec![MAIN_WINDOW.to_string()] };
+        windows
+            .values()
+            .filter(|w| w.open && topic_matches(&w.topics, message_type))
+            .map(|w| w.label.clone())
+            .collect()
+    }
+
+    /// Record layout changes and user-closed windows.
+    pub fn handle_event(&self, window: &tauri::Window, event: &tauri::WindowEvent) {
+        let label = window.label();
+        match event {
+            tauri::WindowEvent::Moved(position) => {
+                let scale = window.scale_factor().unwrap_or(1.0);
+                let position = position.to_logical::<i32>(scale);
+                self.update(label, |w| {
+                    w.x = Some(position.x);
+                    w.y = Some(position.y);
+                });
+            }
+            tauri::WindowEvent::Resized(size) => {
+                let scale = window.scale_factor().unwrap_or(1.0);
+                let maximized = window.is_maximized().unwrap_or(false);
+                let size = size.to_logical::<u32>(scale);
+                self.update(label, |w| {
+                    w.maximized = maximized;
+                    // Keep the restored size when maximizing
+                    if !maximized && size.width > 0 && size.height > 0 {
+                        w.width = size.width;
+                        w.height = size.height;
+                    }
+                });
+            }
+            tauri::WindowEvent::CloseRequested { .. } => {
+                if label == MAIN_WINDOW {
+                    // App is quitting: keep detached windows marked open so
+                    // they come back next launch
+                    self.flush();
+                } else if let Err(e) = self.mark_closed(label) {
+                    eprintln!("Failed to save window {}: {}", label, e);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn update(&self, label: &str, apply: impl FnOnce(&mut AppWindow)) {
+        let Ok(mut windows) = self.windows.lock() else { return };
+        let Some(window) = windows.get_mut(label) else { return };
+        apply(window);
+        if let Ok(mut dirty) = self.dirty.lock() {
+            dirty.insert(label.to_string());
+        }
+    }
+
+    fn mark_closed(&self, label: &str) -> Result<()> {
+        let window = {
+            let mut windows = self.windows.lock()
+                .map_err(|e| anyhow::anyhow!("Window registry lock error: {}", e))?;
+            let Some(window) = windows.get_mut(label) else { return Ok(()) };
+            window.open = false;
+            window.clone()
+        };
+        self.store.save_window(&window)
+    }
+
+    fn flush(&self) {
+        let labels: Vec<String> = match self.dirty.lock() {
+            Ok(mut dirty) => dirty.drain().collect(),
+            Err(_) => return,
+        };
+        for label in labels {
+            let window = self.windows.lock().ok().and_then(|w| w.get(&label).cloned());
+            if let Some(window) = window {
+                if let Err(e) = self.store.save_window(&window) {
+                    eprintln!("Failed to save window {}: {}", label, e);
+                }
+            }
+        }
+    }
+}
+
+/// Emit a `ws-message` event to the windows subscribed to its `type`.
+/// Falls back to every window before the router is set up.
+pub fn emit(app: &AppHandle, message: serde_json::Value) -> tauri::Result<()> {
+    let Some(router) = app.try_state::<WindowRouter>() else {
+        return app.emit("ws-message", message);
+    };
+    let message_type = message.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+    for label in router.targets(message_type) {
+        app.emit_to(label.as_str(), "ws-message", &message)?;
+    }
+    Ok(())
+}
+
+fn build_window(app: &AppHandle, window: &AppWindow) -> Result<()> {
+    let url = tauri::WebviewUrl::App(window.route.trim_start_matches('/').into());
+    let mut builder = tauri::WebviewWindowBuilder::new(app, &window.label, url)
+        .title(&window.title)
+        .inner_size(window.width as f64, window.height as f64)
+        .maximized(window.maximized);
+    if let (Some(x), Some(y)) = (window.x, window.y) {
+        builder = builder.position(x as f64, y as f64);
+    }
+    builder.build()?;
+    Ok(())
+}
+
+/// "*" matches everything, "project-*" every type with that prefix.
+fn topic_matches(topics: &[String], message_type: &str) -> bool {
+    topics.iter().any(|topic| {
+        topic == "*"
+            || topic == message_type
+            || topic.strip_suffix('*').is_some_and(|prefix| message_type.starts_with(prefix))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_match_exact_prefix_and_wildcard() {
+        let topics = vec!["price-alert".to_string(), "project-*".to_string()];
+        assert!(topic_matches(&topics, "price-alert"));
+        assert!(topic_matches(&topics, "project-task-due"));
+        assert!(!topic_matches(&topics, "market-data-batch"));
+        assert!(topic_matches(&["*".to_string()], "anything"));
+        assert!(!topic_matches(&[], "price-alert"));
+    }
+}
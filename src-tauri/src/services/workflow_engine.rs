@@ -167,8 +167,7 @@ impl WorkflowEngine {
         }
 
         // Emit WebSocket event for real-time updates
-        use tauri::Emitter;
-        let _ = self.app.emit("ws-message", serde_json::json!({
+        let _ = crate::services::window_router::emit(&self.app, serde_json::json!({
             "type": "workflow-execution",
             "data": {
                 "id": execution_id,
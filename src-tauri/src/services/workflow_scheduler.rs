@@ -12,12 +12,26 @@ use tokio::time::interval;
 use crate::storage::Database;
 use crate::storage::automation::{AutomationStore, Workflow};
 use crate::services::workflow_engine::WorkflowEngine;
+use crate::services::timezone::{parse_timezone, TimePreferences};
+use chrono_tz::Tz;
 
 struct ScheduledWorkflow {
     workflow_id: i64,
     cron_expr: Schedule,
     next_execution: DateTime<Utc>,
-    timezone: Option<String>,
+    /// The trigger's "timezone", else the global preference
+    timezone: Tz,
+}
+
+impl ScheduledWorkflow {
+    /// Cron fields are wall-clock times in the workflow's timezone, so
+    /// "0 9 * * *" keeps firing at 09:00 local across DST changes.
+    fn upcoming(&self) -> Option<DateTime<Utc>> {
+        self.cron_expr
+            .upcoming(self.timezone)
+            .next()
+            .map(|t| t.with_timezone(&Utc))
+    }
 }
 
 pub struct WorkflowScheduler {
@@ -101,9 +115,8 @@ impl WorkflowScheduler {
                     if let Ok(mut schedules_guard) = schedules.lock() {
                         if let Some(scheduled) = schedules_guard.get_mut(&workflow_id) {
                             // Calculate next execution
-                            scheduled.next_execution = scheduled.cron_expr
-                                .upcoming(Utc)
-                                .next()
+                            scheduled.next_execution = scheduled
+                                .upcoming()
                                 .unwrap_or(now + chrono::Duration::hours(24));
                         }
                     }
@@ -134,6 +147,7 @@ impl WorkflowScheduler {
         let store = AutomationStore::new(db_guard.conn.clone()).unwrap();
         
         let workflows = store.list_workflows().unwrap_or_default();
+        let default_tz = TimePreferences::load(&db_guard).tz();
         let mut schedules_guard = self.schedules.lock().unwrap();
         schedules_guard.clear();
 
@@ -150,19 +164,24 @@ impl WorkflowScheduler {
 
             if let Some(cron_str) = trigger_config.get("cron").and_then(|v| v.as_str()) {
                 if let Ok(schedule) = Schedule::from_str(cron_str) {
-                    let next_execution = schedule
-                        .upcoming(Utc)
-                        .next()
-                        .unwrap_or(now + chrono::Duration::hours(24));
-
-                    schedules_guard.insert(workflow.id, ScheduledWorkflow {
+                    let timezone = match trigger_config.get("timezone").and_then(|v| v.as_str()) {
+                        Some(name) => parse_timezone(name).unwrap_or_else(|e| {
+                            eprintln!("Workflow {}: {}, using {}", workflow.id, e, default_tz);
+                            default_tz
+                        }),
+                        None => default_tz,
+                    };
+                    let mut scheduled = ScheduledWorkflow {
                         workflow_id: workflow.id,
                         cron_expr: schedule,
-                        next_execution,
-                        timezone: trigger_config.get("timezone")
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string()),
-                    });
+                        next_execution: now,
+                        timezone,
+                    };
+                    scheduled.next_execution = scheduled
+                        .upcoming()
+                        .unwrap_or(now + chrono::Duration::hours(24));
+
+                    schedules_guard.insert(workflow.id, scheduled);
                 }
             }
         }
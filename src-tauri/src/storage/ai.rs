@@ -1,3 +1,4 @@
+use crate::storage::profiles::active_profile_id;
 use anyhow::{Context, Result};
 use rusqlite::{Connection, params, OptionalExtension};
 use serde::{Deserialize, Serialize};
@@ -23,6 +24,30 @@ pub struct Conversation {
     pub model: Option<String>,
 }
 
+/// Rolling summary standing in for a conversation's oldest turns once it
+/// outgrows the model's context window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationSummary {
+    pub conversation_id: String,
+    pub summary: String,
+    /// How many leading non-system messages the summary covers
+    pub summarized_messages: i64,
+    pub model: Option<String>,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedConversation {
+    pub id: String,
+    pub title: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub model: Option<String>,
+    pub message_count: i64,
+    pub archived_at: i64,
+    pub compressed_bytes: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PromptTemplate {
     pub id: i64,
@@ -30,6 +55,26 @@ pub struct PromptTemplate {
     pub template: String,
     pub description: Option<String>,
     pub created_at: i64,
+    pub version: i64,
+    pub variables: Vec<TemplateVariable>,
+    pub examples: Vec<TemplateExample>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateExample {
+    #[serde(default)]
+    pub inputs: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    pub output: Option<String>,
 }
 
 pub struct AIStore {
@@ -84,11 +129,46 @@ impl AIStore {
             [],
         )?;
 
+        // Migration: sharing metadata for template bundles
+        let _ = conn.execute("ALTER TABLE prompt_templates ADD COLUMN version INTEGER NOT NULL DEFAULT 1", []);
+        let _ = conn.execute("ALTER TABLE prompt_templates ADD COLUMN variables TEXT", []);
+        let _ = conn.execute("ALTER TABLE prompt_templates ADD COLUMN examples TEXT", []);
+
+        // Migration: chat history belongs to a user profile
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN profile_id INTEGER NOT NULL DEFAULT 1", []);
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_messages_conversation ON chat_messages(conversation_id)",
             [],
         )?;
 
+        // Archived conversations keep their messages as gzip-compressed JSON so
+        // they stay out of the chat_messages table and the main listing
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_archive (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                model TEXT,
+                message_count INTEGER NOT NULL,
+                archived_at INTEGER NOT NULL,
+                messages_gz BLOB NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS conversation_summaries (
+                conversation_id TEXT PRIMARY KEY,
+                summary TEXT NOT NULL,
+                summarized_messages INTEGER NOT NULL,
+                model TEXT,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
         Ok(())
     }
 
@@ -96,24 +176,38 @@ impl AIStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let now = chrono::Utc::now().timestamp();
+        let profile_id = active_profile_id(&conn)?;
 
         conn.execute(
-            "INSERT OR REPLACE INTO conversations (id, title, created_at, updated_at, model)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![id, title, now, now, model],
+            "INSERT OR REPLACE INTO conversations (id, title, created_at, updated_at, model, profile_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![id, title, now, now, model, profile_id],
         )?;
 
         Ok(())
     }
 
+    /// Conversations of the active profile.
     pub fn list_conversations(&self) -> Result<Vec<Conversation>> {
+        let profile_id = {
+            let conn = self.conn.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            active_profile_id(&conn)?
+        };
+        self.list_conversations_for(Some(profile_id))
+    }
+
+    /// Conversations of one profile, or of every profile when `profile_id` is None.
+    pub fn list_conversations_for(&self, profile_id: Option<i64>) -> Result<Vec<Conversation>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let mut stmt = conn.prepare(
-            "SELECT id, title, created_at, updated_at, model FROM conversations ORDER BY updated_at DESC"
+            "SELECT id, title, created_at, updated_at, model FROM conversations
+             WHERE ?1 IS NULL OR profile_id = ?1
+             ORDER BY updated_at DESC"
         )?;
 
-        let rows = stmt.query_map([], |row| {
+        let rows = stmt.query_map(params![profile_id], |row| {
             Ok(Conversation {
                 id: row.get(0)?,
                 title: row.get(1)?,
@@ -130,6 +224,27 @@ impl AIStore {
         Ok(conversations)
     }
 
+    pub fn get_conversation(&self, id: &str) -> Result<Option<Conversation>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let conversation = conn
+            .query_row(
+                "SELECT id, title, created_at, updated_at, model FROM conversations WHERE id = ?1",
+                params![id],
+                |row| {
+                    Ok(Conversation {
+                        id: row.get(0)?,
+                        title: row.get(1)?,
+                        created_at: row.get(2)?,
+                        updated_at: row.get(3)?,
+                        model: row.get(4)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(conversation)
+    }
+
     pub fn add_message(
         &self,
         conversation_id: &str,
@@ -186,6 +301,206 @@ impl AIStore {
         Ok(messages)
     }
 
+    pub fn get_summary(&self, conversation_id: &str) -> Result<Option<ConversationSummary>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT conversation_id, summary, summarized_messages, model, updated_at
+             FROM conversation_summaries WHERE conversation_id = ?1",
+            params![conversation_id],
+            |row| {
+                Ok(ConversationSummary {
+                    conversation_id: row.get(0)?,
+                    summary: row.get(1)?,
+                    summarized_messages: row.get(2)?,
+                    model: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(anyhow::Error::from)
+    }
+
+    pub fn save_summary(&self, conversation_id: &str, summary: &str, summarized_messages: i64, model: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO conversation_summaries (conversation_id, summary, summarized_messages, model, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![conversation_id, summary, summarized_messages, model, chrono::Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_summary(&self, conversation_id: &str) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM conversation_summaries WHERE conversation_id = ?1", params![conversation_id])?;
+        Ok(())
+    }
+
+    /// Move a conversation and its messages into the compressed archive.
+    /// Returns false if the conversation doesn't exist.
+    pub fn archive_conversation(&self, id: &str) -> Result<bool> {
+        let conversation = match self.get_conversation(id)? {
+            Some(c) => c,
+            None => return Ok(false),
+        };
+        let messages = self.get_messages(id)?;
+        let compressed = compress_messages(&messages)?;
+
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO conversation_archive
+             (id, title, created_at, updated_at, model, message_count, archived_at, messages_gz)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                conversation.id,
+                conversation.title,
+                conversation.created_at,
+                conversation.updated_at,
+                conversation.model,
+                messages.len() as i64,
+                now,
+                compressed
+            ],
+        )?;
+        tx.execute("DELETE FROM chat_messages WHERE conversation_id = ?1", params![id])?;
+        // Rebuilt on demand if the conversation is restored
+        tx.execute("DELETE FROM conversation_summaries WHERE conversation_id = ?1", params![id])?;
+        tx.execute("DELETE FROM conversations WHERE id = ?1", params![id])?;
+        tx.commit()?;
+
+        Ok(true)
+    }
+
+    /// Archive every conversation not updated since `before_ts`. Returns the number archived.
+    pub fn archive_conversations_before(&self, before_ts: i64) -> Result<usize> {
+        let ids: Vec<String> = {
+            let conn = self.conn.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            let mut stmt = conn.prepare("SELECT id FROM conversations WHERE updated_at < ?1")?;
+            let rows = stmt.query_map(params![before_ts], |row| row.get(0))?;
+            rows.collect::<std::result::Result<Vec<String>, _>>()?
+        };
+
+        let mut archived = 0;
+        for id in ids {
+            if self.archive_conversation(&id)? {
+                archived += 1;
+            }
+        }
+        Ok(archived)
+    }
+
+    pub fn list_archived_conversations(&self) -> Result<Vec<ArchivedConversation>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, title, created_at, updated_at, model, message_count, archived_at, LENGTH(messages_gz)
+             FROM conversation_archive
+             ORDER BY updated_at DESC"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(ArchivedConversation {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                created_at: row.get(2)?,
+                updated_at: row.get(3)?,
+                model: row.get(4)?,
+                message_count: row.get(5)?,
+                archived_at: row.get(6)?,
+                compressed_bytes: row.get(7)?,
+            })
+        })?;
+
+        let mut archived = Vec::new();
+        for row in rows {
+            archived.push(row?);
+        }
+        Ok(archived)
+    }
+
+    /// Read an archived conversation without restoring it.
+    pub fn get_archived_conversation(&self, id: &str) -> Result<Option<(Conversation, Vec<ChatMessage>)>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let row = conn
+            .query_row(
+                "SELECT id, title, created_at, updated_at, model, messages_gz FROM conversation_archive WHERE id = ?1",
+                params![id],
+                |row| {
+                    Ok((
+                        Conversation {
+                            id: row.get(0)?,
+                            title: row.get(1)?,
+                            created_at: row.get(2)?,
+                            updated_at: row.get(3)?,
+                            model: row.get(4)?,
+                        },
+                        row.get::<_, Vec<u8>>(5)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        match row {
+            Some((conversation, compressed)) => Ok(Some((conversation, decompress_messages(&compressed)?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Move an archived conversation back into the main tables, keeping
+    /// original timestamps. Returns false if it isn't archived.
+    pub fn restore_conversation(&self, id: &str) -> Result<bool> {
+        let (conversation, messages) = match self.get_archived_conversation(id)? {
+            Some(archived) => archived,
+            None => return Ok(false),
+        };
+
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        // Restored chats land in whichever profile is active
+        let profile_id = active_profile_id(&conn)?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO conversations (id, title, created_at, updated_at, model, profile_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                conversation.id,
+                conversation.title,
+                conversation.created_at,
+                conversation.updated_at,
+                conversation.model,
+                profile_id
+            ],
+        )?;
+        for message in &messages {
+            tx.execute(
+                "INSERT INTO chat_messages (conversation_id, role, content, timestamp, model, tokens)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    conversation.id,
+                    message.role,
+                    message.content,
+                    message.timestamp,
+                    message.model,
+                    message.tokens
+                ],
+            )?;
+        }
+        tx.execute("DELETE FROM conversation_archive WHERE id = ?1", params![id])?;
+        tx.commit()?;
+
+        Ok(true)
+    }
+
     pub fn create_template(&self, name: &str, template: &str, description: Option<&str>) -> Result<i64> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -204,18 +519,10 @@ impl AIStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let mut stmt = conn.prepare(
-            "SELECT id, name, template, description, created_at FROM prompt_templates ORDER BY name"
+            &format!("SELECT {} FROM prompt_templates ORDER BY name", TEMPLATE_COLUMNS)
         )?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(PromptTemplate {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                template: row.get(2)?,
-                description: row.get(3)?,
-                created_at: row.get(4)?,
-            })
-        })?;
+        let rows = stmt.query_map([], row_to_template)?;
 
         let mut templates = Vec::new();
         for row in rows {
@@ -230,21 +537,101 @@ impl AIStore {
         
         let template: Option<PromptTemplate> = conn
             .query_row(
-                "SELECT id, name, template, description, created_at FROM prompt_templates WHERE name = ?1",
+                &format!("SELECT {} FROM prompt_templates WHERE name = ?1", TEMPLATE_COLUMNS),
                 params![name],
-                |row| {
-                    Ok(PromptTemplate {
-                        id: row.get(0)?,
-                        name: row.get(1)?,
-                        template: row.get(2)?,
-                        description: row.get(3)?,
-                        created_at: row.get(4)?,
-                    })
-                },
+                row_to_template,
             )
             .optional()?;
 
         Ok(template)
     }
+
+    pub fn get_template_by_id(&self, id: i64) -> Result<Option<PromptTemplate>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let template = conn
+            .query_row(
+                &format!("SELECT {} FROM prompt_templates WHERE id = ?1", TEMPLATE_COLUMNS),
+                params![id],
+                row_to_template,
+            )
+            .optional()?;
+        Ok(template)
+    }
+
+    /// Insert or replace a template by name, including its sharing metadata.
+    pub fn save_template(
+        &self,
+        name: &str,
+        template: &str,
+        description: Option<&str>,
+        version: i64,
+        variables: &[TemplateVariable],
+        examples: &[TemplateExample],
+    ) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let timestamp = chrono::Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT INTO prompt_templates (name, template, description, created_at, version, variables, examples)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(name) DO UPDATE SET
+                template = excluded.template,
+                description = excluded.description,
+                version = excluded.version,
+                variables = excluded.variables,
+                examples = excluded.examples",
+            params![
+                name,
+                template,
+                description,
+                timestamp,
+                version,
+                serde_json::to_string(variables)?,
+                serde_json::to_string(examples)?
+            ],
+        )?;
+
+        let id = conn.query_row(
+            "SELECT id FROM prompt_templates WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )?;
+        Ok(id)
+    }
 }
 
+const TEMPLATE_COLUMNS: &str = "id, name, template, description, created_at, version, variables, examples";
+
+fn row_to_template(row: &rusqlite::Row) -> rusqlite::Result<PromptTemplate> {
+    let variables: Option<String> = row.get(6)?;
+    let examples: Option<String> = row.get(7)?;
+    Ok(PromptTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        template: row.get(2)?,
+        description: row.get(3)?,
+        created_at: row.get(4)?,
+        version: row.get(5)?,
+        variables: variables.and_then(|v| serde_json::from_str(&v).ok()).unwrap_or_default(),
+        examples: examples.and_then(|e| serde_json::from_str(&e).ok()).unwrap_or_default(),
+    })
+}
+
+fn compress_messages(messages: &[ChatMessage]) -> Result<Vec<u8>> {
+    use std::io::Write;
+    let json = serde_json::to_vec(messages)?;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&json)?;
+    Ok(encoder.finish()?)
+}
+
+fn decompress_messages(compressed: &[u8]) -> Result<Vec<ChatMessage>> {
+    use std::io::Read;
+    let mut json = Vec::new();
+    flate2::read::GzDecoder::new(compressed)
+        .read_to_end(&mut json)
+        .context("Failed to decompress archived messages")?;
+    Ok(serde_json::from_slice(&json)?)
+}
@@ -76,6 +76,69 @@ impl AnalyticsStore {
         Ok(())
     }
 
+    /// Insert many samples of one metric in a single transaction.
+    /// Each sample is (timestamp, value, metadata).
+    pub fn save_metrics(&self, metric_type: &str, samples: &[(i64, f64, Option<String>)]) -> Result<()> {
+        let mut conn = self.lock_conn()?;
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO analytics_metrics (timestamp, metric_type, value, metadata)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (timestamp, value, metadata) in samples {
+                stmt.execute(params![timestamp, metric_type, value, metadata])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn delete_metrics_before(&self, metric_type: &str, before: i64) -> Result<usize> {
+        let conn = self.lock_conn()?;
+        let count = conn.execute(
+            "DELETE FROM analytics_metrics WHERE metric_type = ?1 AND timestamp < ?2",
+            params![metric_type, before],
+        )?;
+        Ok(count)
+    }
+
+    /// Commands that took at least `threshold_ms` since `since`, grouped by
+    /// command name from the `command_duration` samples, slowest first.
+    pub fn slow_commands(&self, threshold_ms: f64, since: i64, limit: i64) -> Result<Vec<SlowCommand>> {
+        let conn = self.lock_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT json_extract(metadata, '$.command') AS command,
+                    COUNT(*),
+                    AVG(value),
+                    MAX(value),
+                    AVG(COALESCE(json_extract(metadata, '$.db_wait_ms'), 0)),
+                    AVG(COALESCE(json_extract(metadata, '$.args_bytes'), 0)),
+                    MAX(timestamp)
+             FROM analytics_metrics
+             WHERE metric_type = 'command_duration' AND value >= ?1 AND timestamp >= ?2
+             GROUP BY command
+             ORDER BY MAX(value) DESC
+             LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![threshold_ms, since, limit], |row| {
+            Ok(SlowCommand {
+                command: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                count: row.get(1)?,
+                avg_ms: row.get(2)?,
+                max_ms: row.get(3)?,
+                avg_db_wait_ms: row.get(4)?,
+                avg_args_bytes: row.get(5)?,
+                last_seen: row.get(6)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
     pub fn get_metrics(
         &self,
         metric_type: &str,
@@ -247,3 +310,14 @@ pub struct Statistics {
     pub std_dev: f64,
     pub count: usize,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SlowCommand {
+    pub command: String,
+    pub count: i64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+    pub avg_db_wait_ms: f64,
+    pub avg_args_bytes: f64,
+    pub last_seen: i64,
+}
@@ -0,0 +1,329 @@
+use anyhow::Result;
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub id: i64,
+    pub suite: String,
+    pub name: String,
+    pub metric: String, // "time", "throughput", "memory", ...
+    pub value: f64,
+    pub unit: String, // "ns/iter", "ms", "ops/s", ...
+    pub lower_is_better: bool,
+    pub context: Option<String>, // commit hash, version, machine, ...
+    pub recorded_at: i64,
+}
+
+/// A measurement as submitted by a command, a watched file or a parsed bench run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewBenchmarkResult {
+    pub suite: String,
+    pub name: String,
+    #[serde(default = "default_metric")]
+    pub metric: String,
+    pub value: f64,
+    pub unit: String,
+    #[serde(default = "default_lower_is_better")]
+    pub lower_is_better: bool,
+    #[serde(default)]
+    pub context: Option<String>,
+}
+
+fn default_metric() -> String {
+    "time".to_string()
+}
+
+fn default_lower_is_better() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkSeries {
+    pub suite: String,
+    pub name: String,
+    pub metric: String,
+    pub unit: String,
+    pub lower_is_better: bool,
+    pub samples: i64,
+    pub last_recorded_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRegression {
+    pub id: i64,
+    pub result_id: i64,
+    pub suite: String,
+    pub name: String,
+    pub metric: String,
+    pub baseline: f64,
+    pub value: f64,
+    pub change_percent: f64, // Positive means worse
+    pub threshold_percent: f64,
+    pub context: Option<String>,
+    pub detected_at: i64,
+    pub acknowledged: bool,
+}
+
+pub struct BenchmarkStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl BenchmarkStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = BenchmarkStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: BenchmarkStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS benchmark_results (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                suite TEXT NOT NULL,
+                name TEXT NOT NULL,
+                metric TEXT NOT NULL DEFAULT 'time',
+                value REAL NOT NULL,
+                unit TEXT NOT NULL,
+                lower_is_better INTEGER NOT NULL DEFAULT 1,
+                context TEXT,
+                recorded_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS benchmark_regressions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                result_id INTEGER NOT NULL,
+                suite TEXT NOT NULL,
+                name TEXT NOT NULL,
+                metric TEXT NOT NULL,
+                baseline REAL NOT NULL,
+                value REAL NOT NULL,
+                change_percent REAL NOT NULL,
+                threshold_percent REAL NOT NULL,
+                context TEXT,
+                detected_at INTEGER NOT NULL,
+                acknowledged INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY (result_id) REFERENCES benchmark_results(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_benchmark_results_series ON benchmark_results(suite, name, metric, recorded_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn record_result(&self, result: &NewBenchmarkResult) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT INTO benchmark_results (suite, name, metric, value, unit, lower_is_better, context, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                result.suite,
+                result.name,
+                result.metric,
+                result.value,
+                result.unit,
+                if result.lower_is_better { 1 } else { 0 },
+                result.context,
+                now
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Values of the `window` results recorded before `before_id`, newest first.
+    pub fn previous_values(&self, suite: &str, name: &str, metric: &str, before_id: i64, window: i64) -> Result<Vec<f64>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT value FROM benchmark_results
+             WHERE suite = ?1 AND name = ?2 AND metric = ?3 AND id < ?4
+             ORDER BY recorded_at DESC, id DESC
+             LIMIT ?5"
+        )?;
+        let rows = stmt.query_map(params![suite, name, metric, before_id, window], |row| row.get::<_, f64>(0))?;
+
+        let mut values = Vec::new();
+        for row in rows {
+            values.push(row?);
+        }
+        Ok(values)
+    }
+
+    pub fn list_results(
+        &self,
+        suite: Option<&str>,
+        name: Option<&str>,
+        metric: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<BenchmarkResult>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, suite, name, metric, value, unit, lower_is_better, context, recorded_at
+             FROM benchmark_results
+             WHERE (?1 IS NULL OR suite = ?1) AND (?2 IS NULL OR name = ?2) AND (?3 IS NULL OR metric = ?3)
+             ORDER BY recorded_at DESC, id DESC
+             LIMIT ?4"
+        )?;
+        let rows = stmt.query_map(params![suite, name, metric, limit], |row| {
+            Ok(BenchmarkResult {
+                id: row.get(0)?,
+                suite: row.get(1)?,
+                name: row.get(2)?,
+                metric: row.get(3)?,
+                value: row.get(4)?,
+                unit: row.get(5)?,
+                lower_is_better: row.get::<_, i64>(6)? != 0,
+                context: row.get(7)?,
+                recorded_at: row.get(8)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// One row per (suite, name, metric), using the unit and direction of the latest sample.
+    pub fn list_series(&self, suite: Option<&str>) -> Result<Vec<BenchmarkSeries>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT r.suite, r.name, r.metric, r.unit, r.lower_is_better, s.samples, s.last_recorded_at
+             FROM (
+                 SELECT suite, name, metric, COUNT(*) AS samples, MAX(recorded_at) AS last_recorded_at, MAX(id) AS last_id
+                 FROM benchmark_results
+                 WHERE ?1 IS NULL OR suite = ?1
+                 GROUP BY suite, name, metric
+             ) s
+             JOIN benchmark_results r ON r.id = s.last_id
+             ORDER BY r.suite, r.name, r.metric"
+        )?;
+        let rows = stmt.query_map(params![suite], |row| {
+            Ok(BenchmarkSeries {
+                suite: row.get(0)?,
+                name: row.get(1)?,
+                metric: row.get(2)?,
+                unit: row.get(3)?,
+                lower_is_better: row.get::<_, i64>(4)? != 0,
+                samples: row.get(5)?,
+                last_recorded_at: row.get(6)?,
+            })
+        })?;
+
+        let mut series = Vec::new();
+        for row in rows {
+            series.push(row?);
+        }
+        Ok(series)
+    }
+
+    pub fn create_regression(
+        &self,
+        result_id: i64,
+        result: &NewBenchmarkResult,
+        baseline: f64,
+        change_percent: f64,
+        threshold_percent: f64,
+    ) -> Result<BenchmarkRegression> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT INTO benchmark_regressions (result_id, suite, name, metric, baseline, value, change_percent, threshold_percent, context, detected_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                result_id,
+                result.suite,
+                result.name,
+                result.metric,
+                baseline,
+                result.value,
+                change_percent,
+                threshold_percent,
+                result.context,
+                now
+            ],
+        )?;
+
+        Ok(BenchmarkRegression {
+            id: conn.last_insert_rowid(),
+            result_id,
+            suite: result.suite.clone(),
+            name: result.name.clone(),
+            metric: result.metric.clone(),
+            baseline,
+            value: result.value,
+            change_percent,
+            threshold_percent,
+            context: result.context.clone(),
+            detected_at: now,
+            acknowledged: false,
+        })
+    }
+
+    pub fn list_regressions(&self, unacknowledged_only: bool, limit: i64) -> Result<Vec<BenchmarkRegression>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, result_id, suite, name, metric, baseline, value, change_percent, threshold_percent, context, detected_at, acknowledged
+             FROM benchmark_regressions
+             WHERE (?1 = 0 OR acknowledged = 0)
+             ORDER BY detected_at DESC
+             LIMIT ?2"
+        )?;
+        let rows = stmt.query_map(params![if unacknowledged_only { 1 } else { 0 }, limit], |row| {
+            Ok(BenchmarkRegression {
+                id: row.get(0)?,
+                result_id: row.get(1)?,
+                suite: row.get(2)?,
+                name: row.get(3)?,
+                metric: row.get(4)?,
+                baseline: row.get(5)?,
+                value: row.get(6)?,
+                change_percent: row.get(7)?,
+                threshold_percent: row.get(8)?,
+                context: row.get(9)?,
+                detected_at: row.get(10)?,
+                acknowledged: row.get::<_, i64>(11)? != 0,
+            })
+        })?;
+
+        let mut regressions = Vec::new();
+        for row in rows {
+            regressions.push(row?);
+        }
+        Ok(regressions)
+    }
+
+    pub fn acknowledge_regression(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE benchmark_regressions SET acknowledged = 1 WHERE id = ?1",
+            params![id],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::Result;
+use rusqlite::{Connection, params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardEntry {
+    pub id: i64,
+    pub content: String, // Redacted when sensitive
+    pub content_hash: String, // Of the redacted content when sensitive
+    pub sensitive: bool,
+    pub sensitive_kinds: Vec<String>,
+    pub char_count: i64,
+    pub created_at: i64,
+    pub last_seen_at: i64,
+}
+
+pub struct ClipboardStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ClipboardStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = ClipboardStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: ClipboardStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS clipboard_entries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                content TEXT NOT NULL,
+                content_hash TEXT NOT NULL UNIQUE,
+                sensitive INTEGER NOT NULL DEFAULT 0,
+                sensitive_kinds TEXT NOT NULL DEFAULT '[]',
+                char_count INTEGER NOT NULL,
+                created_at INTEGER NOT NULL,
+                last_seen_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_clipboard_entries_last_seen ON clipboard_entries(last_seen_at DESC)",
+            [],
+        )?;
+
+        // Migration: sensitive entries used to keep the hash of the secret itself,
+        // which can be checked against guesses. Replace it with a placeholder.
+        conn.execute(
+            "UPDATE clipboard_entries SET content_hash = 'redacted:' || id
+             WHERE sensitive = 1 AND content_hash NOT LIKE 'redacted:%'",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Insert a new entry, or bump `last_seen_at` if the same content was copied before.
+    pub fn record_entry(
+        &self,
+        content: &str,
+        content_hash: &str,
+        sensitive_kinds: &[String],
+        char_count: usize,
+    ) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        let existing: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM clipboard_entries WHERE content_hash = ?1",
+                params![content_hash],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        if let Some(id) = existing {
+            conn.execute(
+                "UPDATE clipboard_entries SET last_seen_at = ?1 WHERE id = ?2",
+                params![now, id],
+            )?;
+            return Ok(id);
+        }
+
+        let kinds_json = serde_json::to_string(sensitive_kinds)?;
+        conn.execute(
+            "INSERT INTO clipboard_entries (content, content_hash, sensitive, sensitive_kinds, char_count, created_at, last_seen_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                content,
+                content_hash,
+                if sensitive_kinds.is_empty() { 0 } else { 1 },
+                kinds_json,
+                char_count as i64,
+                now,
+                now
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn list_entries(&self, limit: i64, include_sensitive: bool) -> Result<Vec<ClipboardEntry>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, content, content_hash, sensitive, sensitive_kinds, char_count, created_at, last_seen_at
+             FROM clipboard_entries
+             WHERE (?1 = 1 OR sensitive = 0)
+             ORDER BY last_seen_at DESC
+             LIMIT ?2"
+        )?;
+
+        let rows = stmt.query_map(params![if include_sensitive { 1 } else { 0 }, limit], |row| {
+            let kinds_json: String = row.get(4)?;
+            Ok(ClipboardEntry {
+                id: row.get(0)?,
+                content: row.get(1)?,
+                content_hash: row.get(2)?,
+                sensitive: row.get::<_, i64>(3)? != 0,
+                sensitive_kinds: serde_json::from_str(&kinds_json).unwrap_or_default(),
+                char_count: row.get(5)?,
+                created_at: row.get(6)?,
+                last_seen_at: row.get(7)?,
+            })
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    }
+
+    pub fn delete_entry(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM clipboard_entries WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Delete entries last seen before `before_ts` (all entries when `None`).
+    pub fn purge(&self, before_ts: Option<i64>, sensitive_only: bool) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let deleted = conn.execute(
+            "DELETE FROM clipboard_entries
+             WHERE (?1 IS NULL OR last_seen_at < ?1)
+               AND (?2 = 0 OR sensitive = 1)",
+            params![before_ts, if sensitive_only { 1 } else { 0 }],
+        )?;
+        Ok(deleted)
+    }
+
+    /// Keep only the most recent `max_entries` rows.
+    pub fn trim(&self, max_entries: i64) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let deleted = conn.execute(
+            "DELETE FROM clipboard_entries WHERE id NOT IN (
+                SELECT id FROM clipboard_entries ORDER BY last_seen_at DESC LIMIT ?1
+             )",
+            params![max_entries],
+        )?;
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,180 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// One widget on a dashboard and where it sits on the grid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DashboardWidget {
+    pub id: String,
+    pub kind: String, // alerts_by_rule|watchlist_movers|system_metrics|...
+    #[serde(default)]
+    pub title: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    /// Widget-specific settings, e.g. the rule ids or watchlist shown
+    #[serde(default)]
+    pub config: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardLayout {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub widgets: Vec<DashboardWidget>,
+    /// The layout the dashboard opens with
+    pub is_default: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+pub struct DashboardStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl DashboardStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = DashboardStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: DashboardStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS dashboard_layouts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                description TEXT,
+                widgets_json TEXT NOT NULL DEFAULT '[]',
+                is_default INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_layout(&self, name: &str, description: Option<&str>, widgets: &[DashboardWidget]) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "INSERT INTO dashboard_layouts (name, description, widgets_json, is_default, created_at, updated_at)
+             VALUES (?1, ?2, ?3, 0, ?4, ?4)",
+            params![name, description, serde_json::to_string(widgets)?, now],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn update_layout(
+        &self,
+        id: i64,
+        name: Option<&str>,
+        description: Option<&str>,
+        widgets: Option<&[DashboardWidget]>,
+    ) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let widgets_json = widgets.map(serde_json::to_string).transpose()?;
+        let updated = conn.execute(
+            "UPDATE dashboard_layouts SET
+                name = COALESCE(?1, name),
+                description = COALESCE(?2, description),
+                widgets_json = COALESCE(?3, widgets_json),
+                updated_at = ?4
+             WHERE id = ?5",
+            params![name, description, widgets_json, chrono::Utc::now().timestamp(), id],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Dashboard layout {} not found", id);
+        }
+        Ok(())
+    }
+
+    pub fn get_layout(&self, id: i64) -> Result<Option<DashboardLayout>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, name, description, widgets_json, is_default, created_at, updated_at
+             FROM dashboard_layouts WHERE id = ?1",
+            params![id],
+            row_to_layout,
+        )
+        .optional()
+        .map_err(anyhow::Error::from)
+    }
+
+    pub fn get_layout_by_name(&self, name: &str) -> Result<Option<DashboardLayout>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, name, description, widgets_json, is_default, created_at, updated_at
+             FROM dashboard_layouts WHERE name = ?1",
+            params![name],
+            row_to_layout,
+        )
+        .optional()
+        .map_err(anyhow::Error::from)
+    }
+
+    /// The default layout first, then by name.
+    pub fn list_layouts(&self) -> Result<Vec<DashboardLayout>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, widgets_json, is_default, created_at, updated_at
+             FROM dashboard_layouts ORDER BY is_default DESC, name",
+        )?;
+        let rows = stmt.query_map([], row_to_layout)?;
+
+        let mut layouts = Vec::new();
+        for row in rows {
+            layouts.push(row?);
+        }
+        Ok(layouts)
+    }
+
+    pub fn delete_layout(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM dashboard_layouts WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Make a layout the one the dashboard opens with.
+    pub fn set_default_layout(&self, id: i64) -> Result<()> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        tx.execute("UPDATE dashboard_layouts SET is_default = 0 WHERE is_default = 1", [])?;
+        let updated = tx.execute("UPDATE dashboard_layouts SET is_default = 1 WHERE id = ?1", params![id])?;
+        if updated == 0 {
+            anyhow::bail!("Dashboard layout {} not found", id);
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+fn row_to_layout(row: &rusqlite::Row) -> rusqlite::Result<DashboardLayout> {
+    let widgets_json: String = row.get(3)?;
+    Ok(DashboardLayout {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        widgets: serde_json::from_str(&widgets_json).unwrap_or_default(),
+        is_default: row.get::<_, i32>(4)? == 1,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
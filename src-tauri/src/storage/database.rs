@@ -9,6 +9,36 @@ pub struct Database {
 
 impl Database {
     pub fn new(_app_handle: &AppHandle) -> Result<Self> {
+        let app_data_dir = Self::data_dir()?;
+
+        std::fs::create_dir_all(&app_data_dir)
+            .context("Failed to create app data directory")?;
+
+        let db_path = app_data_dir.join("reality.db");
+        Self::apply_staged_restore(&db_path)?;
+        let conn = Connection::open(&db_path)
+            .context("Failed to open database")?;
+
+        let db = Database {
+            conn: Arc::new(Mutex::new(conn)),
+        };
+
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    /// Fresh in-memory database with the base tables; store schemas are added
+    /// by creating the stores on its connection.
+    pub fn open_in_memory() -> Result<Self> {
+        let db = Database {
+            conn: Arc::new(Mutex::new(Connection::open_in_memory()?)),
+        };
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    /// Directory holding the database and other app data.
+    pub fn data_dir() -> Result<std::path::PathBuf> {
         // Get app data directory - using standard paths
         let app_data_dir = if cfg!(target_os = "macos") {
             std::env::var("HOME")
@@ -23,20 +53,33 @@ impl Database {
                 .map(|home| std::path::PathBuf::from(home).join(".local/share/mina"))
                 .context("Failed to get HOME directory")?
         };
+        Ok(app_data_dir)
+    }
 
-        std::fs::create_dir_all(&app_data_dir)
-            .context("Failed to create app data directory")?;
-
-        let db_path = app_data_dir.join("reality.db");
-        let conn = Connection::open(&db_path)
-            .context("Failed to open database")?;
-
-        let db = Database {
-            conn: Arc::new(Mutex::new(conn)),
-        };
+    /// Path a downloaded backup is written to; it replaces the database on the next launch.
+    pub fn staged_restore_path() -> Result<std::path::PathBuf> {
+        Ok(Self::data_dir()?.join("reality.db.restore"))
+    }
 
-        db.init_schema()?;
-        Ok(db)
+    /// Swap in a backup staged by a restore, keeping the previous database as
+    /// `reality.db.pre-restore`. The live connection can't be replaced while the
+    /// app runs, so this happens before the database is opened.
+    fn apply_staged_restore(db_path: &std::path::Path) -> Result<()> {
+        let staged = Self::staged_restore_path()?;
+        if !staged.exists() {
+            return Ok(());
+        }
+        if db_path.exists() {
+            std::fs::rename(db_path, db_path.with_extension("db.pre-restore"))
+                .context("Failed to move current database aside")?;
+        }
+        // WAL/SHM files belong to the old database
+        for suffix in ["-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", db_path.display(), suffix));
+        }
+        std::fs::rename(&staged, db_path).context("Failed to apply restored database")?;
+        eprintln!("MINA: Restored database from backup");
+        Ok(())
     }
 
     fn init_schema(&self) -> Result<()> {
@@ -0,0 +1,207 @@
+use crate::storage::sql;
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Size of one table or index. Byte counts come from dbstat, which only
+/// exists when SQLite was built with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectSize {
+    pub name: String,
+    pub kind: String, // table|index
+    /// The table an index belongs to; the table itself for tables
+    pub table_name: String,
+    pub rows: Option<i64>,
+    pub bytes: Option<i64>,
+    /// Free space inside the object's pages
+    pub unused_bytes: Option<i64>,
+}
+
+/// Database-wide page accounting plus every table and index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbMeasurement {
+    pub total_bytes: i64,
+    /// Pages freed by deletes that only VACUUM returns to the filesystem
+    pub freelist_bytes: i64,
+    pub objects: Vec<ObjectSize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HousekeepingRecord {
+    pub id: i64,
+    pub created_at: i64,
+    pub total_bytes: i64,
+    pub report: serde_json::Value,
+}
+
+pub struct DbHousekeepingStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl DbHousekeepingStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = DbHousekeepingStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: DbHousekeepingStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS db_housekeeping_reports (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                created_at INTEGER NOT NULL,
+                total_bytes INTEGER NOT NULL,
+                report_json TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_db_housekeeping_reports_created ON db_housekeeping_reports(created_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Count rows and, where dbstat allows, bytes of every table and index.
+    /// Row counts scan each table, so this is for the weekly job and
+    /// explicit requests, not for polling.
+    pub fn measure(&self) -> Result<DbMeasurement> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let pragma = |name: &str| -> Result<i64> {
+            // sql-audit: pragma names are the literals below
+            Ok(conn.query_row(&format!("PRAGMA {}", name), [], |row| row.get(0))?)
+        };
+        let page_size = pragma("page_size")?;
+        let total_bytes = pragma("page_count")? * page_size;
+        let freelist_bytes = pragma("freelist_count")? * page_size;
+
+        // dbstat is only there when SQLite was built with it
+        let mut sizes: HashMap<String, (i64, i64)> = HashMap::new();
+        if let Ok(mut stmt) = conn.prepare("SELECT name, SUM(pgsize), SUM(unused) FROM dbstat GROUP BY name") {
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?)))?;
+            for row in rows.flatten() {
+                sizes.insert(row.0, (row.1, row.2));
+            }
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT name, type, tbl_name FROM sqlite_master
+             WHERE type IN ('table', 'index') AND name NOT LIKE 'sqlite_%'
+             ORDER BY tbl_name, type DESC, name"
+        )?;
+        let entries = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut objects = Vec::new();
+        for (name, kind, table_name) in entries {
+            // Virtual tables (FTS) can't always be counted; their shadow tables are
+            let rows = if kind == "table" {
+                conn.query_row(&format!("SELECT COUNT(*) FROM {}", sql::quote_ident(&name)), [], |row| row.get(0))
+                    .ok()
+            } else {
+                None
+            };
+            let size = sizes.get(&name);
+            objects.push(ObjectSize {
+                name,
+                kind,
+                table_name,
+                rows,
+                bytes: size.map(|s| s.0),
+                unused_bytes: size.map(|s| s.1),
+            });
+        }
+
+        Ok(DbMeasurement { total_bytes, freelist_bytes, objects })
+    }
+
+    pub fn save_report(&self, created_at: i64, total_bytes: i64, report: &serde_json::Value) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO db_housekeeping_reports (created_at, total_bytes, report_json) VALUES (?1, ?2, ?3)",
+            params![created_at, total_bytes, report.to_string()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn latest_report(&self) -> Result<Option<HousekeepingRecord>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let record = conn
+            .query_row(
+                "SELECT id, created_at, total_bytes, report_json FROM db_housekeeping_reports
+                 ORDER BY created_at DESC, id DESC LIMIT 1",
+                [],
+                row_to_record,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    /// The oldest report at or after `since`: the baseline growth is measured against.
+    pub fn earliest_report_since(&self, since: i64) -> Result<Option<HousekeepingRecord>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let record = conn
+            .query_row(
+                "SELECT id, created_at, total_bytes, report_json FROM db_housekeeping_reports
+                 WHERE created_at >= ?1 ORDER BY created_at ASC, id ASC LIMIT 1",
+                params![since],
+                row_to_record,
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    /// Newest first.
+    pub fn list_reports(&self, limit: i64) -> Result<Vec<HousekeepingRecord>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, created_at, total_bytes, report_json FROM db_housekeeping_reports
+             ORDER BY created_at DESC, id DESC LIMIT ?1"
+        )?;
+        let rows = stmt.query_map(params![limit], row_to_record)?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            records.push(row?);
+        }
+        Ok(records)
+    }
+
+    /// Drop all but the newest `keep` reports. Returns how many were removed.
+    pub fn prune_reports(&self, keep: i64) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let removed = conn.execute(
+            "DELETE FROM db_housekeeping_reports WHERE id NOT IN (
+                SELECT id FROM db_housekeeping_reports ORDER BY created_at DESC, id DESC LIMIT ?1
+             )",
+            params![keep],
+        )?;
+        Ok(removed)
+    }
+}
+
+fn row_to_record(row: &rusqlite::Row) -> rusqlite::Result<HousekeepingRecord> {
+    let report: String = row.get(3)?;
+    Ok(HousekeepingRecord {
+        id: row.get(0)?,
+        created_at: row.get(1)?,
+        total_bytes: row.get(2)?,
+        report: serde_json::from_str(&report).unwrap_or(serde_json::Value::Null),
+    })
+}
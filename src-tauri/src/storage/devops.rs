@@ -14,6 +14,29 @@ pub struct HealthCheck {
     pub error: Option<String>,
 }
 
+/// One recorded outcome of a health check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheckRun {
+    pub id: i64,
+    pub name: String,
+    pub status: String,
+    pub response_time: Option<i64>,
+    pub error: Option<String>,
+    pub checked_at: i64,
+}
+
+/// Uptime of a health check over the runs recorded since `since`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UptimeStats {
+    pub name: String,
+    pub since: i64,
+    pub checks: i64,
+    pub healthy: i64,
+    pub uptime_percent: Option<f64>,
+    pub avg_response_time: Option<f64>,
+    pub last_failure_at: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
     pub id: i64,
@@ -103,6 +126,23 @@ impl DevOpsStore {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS health_check_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                status TEXT NOT NULL,
+                response_time INTEGER,
+                error TEXT,
+                checked_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_health_check_history_name ON health_check_history(name, checked_at)",
+            [],
+        )?;
+
         // Migration: Ensure alerts table has required columns
         let table_exists: bool = conn.query_row(
             "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='alerts'",
@@ -267,6 +307,97 @@ impl DevOpsStore {
         Ok(())
     }
 
+    /// Store a check's outcome as its current status and in its history.
+    pub fn record_health_check(
+        &self,
+        name: &str,
+        status: &str,
+        response_time: Option<i64>,
+        error: Option<&str>,
+        checked_at: i64,
+    ) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "UPDATE health_checks 
+             SET status = ?1, last_check = ?2, response_time = ?3, error = ?4
+             WHERE name = ?5",
+            params![status, checked_at, response_time, error, name],
+        )?;
+        conn.execute(
+            "INSERT INTO health_check_history (name, status, response_time, error, checked_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![name, status, response_time, error, checked_at],
+        )?;
+
+        Ok(())
+    }
+
+    /// Newest first.
+    pub fn list_health_check_history(&self, name: &str, limit: i64) -> Result<Vec<HealthCheckRun>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, status, response_time, error, checked_at FROM health_check_history
+             WHERE name = ?1 ORDER BY checked_at DESC, id DESC LIMIT ?2"
+        )?;
+
+        let rows = stmt.query_map(params![name, limit], |row| {
+            Ok(HealthCheckRun {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                status: row.get(2)?,
+                response_time: row.get(3)?,
+                error: row.get(4)?,
+                checked_at: row.get(5)?,
+            })
+        })?;
+
+        let mut runs = Vec::new();
+        for row in rows {
+            runs.push(row?);
+        }
+        Ok(runs)
+    }
+
+    pub fn health_check_uptime(&self, name: &str, since: i64) -> Result<UptimeStats> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let (checks, healthy, avg_response_time, last_failure_at): (i64, i64, Option<f64>, Option<i64>) = conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(CASE WHEN status = 'healthy' THEN 1 ELSE 0 END), 0),
+                    AVG(response_time),
+                    MAX(CASE WHEN status != 'healthy' THEN checked_at END)
+             FROM health_check_history
+             WHERE name = ?1 AND checked_at >= ?2",
+            params![name, since],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+
+        Ok(UptimeStats {
+            name: name.to_string(),
+            since,
+            checks,
+            healthy,
+            uptime_percent: (checks > 0).then(|| healthy as f64 * 100.0 / checks as f64),
+            avg_response_time,
+            last_failure_at,
+        })
+    }
+
+    /// Drop history older than `before`. Returns how many runs were removed.
+    pub fn prune_health_check_history(&self, before: i64) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let removed = conn.execute(
+            "DELETE FROM health_check_history WHERE checked_at < ?1",
+            params![before],
+        )?;
+        Ok(removed)
+    }
+
     pub fn list_health_checks(&self) -> Result<Vec<HealthCheck>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -403,3 +534,30 @@ impl DevOpsStore {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn uptime_counts_recorded_runs() {
+        let db = test_support::test_db();
+        let store = DevOpsStore::new(db.conn.clone());
+        store.create_health_check("Cache", "redis://localhost:6379").unwrap();
+        store.record_health_check("Cache", "healthy", Some(12), None, 1_000).unwrap();
+        store.record_health_check("Cache", "unhealthy", Some(30), Some("PING timeout"), 2_000).unwrap();
+        store.record_health_check("Cache", "healthy", Some(18), None, 3_000).unwrap();
+
+        let stats = store.health_check_uptime("Cache", 1_500).unwrap();
+        assert_eq!((stats.checks, stats.healthy, stats.last_failure_at), (2, 1, Some(2_000)));
+        assert_eq!(stats.uptime_percent, Some(50.0));
+        assert_eq!(stats.avg_response_time, Some(24.0));
+
+        let check = store.list_health_checks().unwrap().into_iter().find(|c| c.name == "Cache").unwrap();
+        assert_eq!((check.status.as_str(), check.last_check), ("healthy", 3_000));
+        assert_eq!(store.list_health_check_history("Cache", 10).unwrap()[0].checked_at, 3_000);
+        assert_eq!(store.prune_health_check_history(2_500).unwrap(), 2);
+        assert_eq!(store.health_check_uptime("Cache", 0).unwrap().uptime_percent, Some(100.0));
+    }
+}
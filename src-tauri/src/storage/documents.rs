@@ -0,0 +1,252 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// An ingested file (PDF, DOCX, text). The extracted text is kept
+/// separately, see `get_document_text`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Document {
+    pub id: i64,
+    pub title: String,
+    pub file_path: String,
+    pub file_hash: String, // sha256 of the file, to skip re-ingesting it
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub page_count: Option<i64>,
+    /// The text came from OCR because the file had no text layer
+    pub ocr_used: bool,
+    pub char_count: i64,
+    pub ingested_at: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewDocument {
+    pub title: String,
+    pub file_path: String,
+    pub file_hash: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub page_count: Option<i64>,
+    pub ocr_used: bool,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentEntity {
+    pub document_id: i64,
+    pub entity_type: String,
+    pub name: String,
+    pub confidence: f64,
+}
+
+pub struct DocumentStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl DocumentStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = DocumentStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: DocumentStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS documents (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                file_path TEXT NOT NULL,
+                file_hash TEXT NOT NULL UNIQUE,
+                mime_type TEXT NOT NULL,
+                size_bytes INTEGER NOT NULL,
+                page_count INTEGER,
+                ocr_used INTEGER NOT NULL DEFAULT 0,
+                content TEXT NOT NULL,
+                ingested_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS document_entities (
+                document_id INTEGER NOT NULL,
+                entity_type TEXT NOT NULL,
+                name TEXT NOT NULL,
+                confidence REAL NOT NULL,
+                PRIMARY KEY (document_id, entity_type, name),
+                FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Search index shared with TemporalStore, which normally creates it
+        conn.execute(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS fts_documents USING fts5(
+                doc_type,
+                doc_id UNINDEXED,
+                title,
+                content,
+                ts UNINDEXED
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_document_entities_name ON document_entities(name)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Store a document and add it to the search index next to articles and events.
+    pub fn insert_document(&self, doc: &NewDocument) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT INTO documents (title, file_path, file_hash, mime_type, size_bytes, page_count, ocr_used, content, ingested_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                doc.title,
+                doc.file_path,
+                doc.file_hash,
+                doc.mime_type,
+                doc.size_bytes,
+                doc.page_count,
+                doc.ocr_used as i64,
+                doc.content,
+                now
+            ],
+        )?;
+        let id = conn.last_insert_rowid();
+
+        conn.execute(
+            "INSERT INTO fts_documents (doc_type, doc_id, title, content, ts) VALUES ('document', ?1, ?2, ?3, ?4)",
+            params![id, doc.title, doc.content, now],
+        )?;
+
+        Ok(id)
+    }
+
+    pub fn find_by_hash(&self, file_hash: &str) -> Result<Option<i64>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id FROM documents WHERE file_hash = ?1",
+            params![file_hash],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn get_document(&self, id: i64) -> Result<Option<Document>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, title, file_path, file_hash, mime_type, size_bytes, page_count, ocr_used, length(content), ingested_at
+             FROM documents WHERE id = ?1",
+            params![id],
+            row_to_document,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn get_document_text(&self, id: i64) -> Result<Option<String>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row("SELECT content FROM documents WHERE id = ?1", params![id], |row| row.get(0))
+            .optional()
+            .map_err(Into::into)
+    }
+
+    pub fn list_documents(&self, limit: i64) -> Result<Vec<Document>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, title, file_path, file_hash, mime_type, size_bytes, page_count, ocr_used, length(content), ingested_at
+             FROM documents
+             ORDER BY ingested_at DESC
+             LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], row_to_document)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn delete_document(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM document_entities WHERE document_id = ?1", params![id])?;
+        conn.execute(
+            "DELETE FROM fts_documents WHERE doc_type = 'document' AND doc_id = ?1",
+            params![id],
+        )?;
+        conn.execute("DELETE FROM documents WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn save_entities(&self, document_id: i64, entities: &[(String, String, f64)]) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        for (entity_type, name, confidence) in entities {
+            conn.execute(
+                "INSERT OR IGNORE INTO document_entities (document_id, entity_type, name, confidence)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![document_id, entity_type, name, confidence],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn get_entities(&self, document_id: i64) -> Result<Vec<DocumentEntity>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT document_id, entity_type, name, confidence
+             FROM document_entities
+             WHERE document_id = ?1
+             ORDER BY confidence DESC, name",
+        )?;
+        let rows = stmt.query_map(params![document_id], |row| {
+            Ok(DocumentEntity {
+                document_id: row.get(0)?,
+                entity_type: row.get(1)?,
+                name: row.get(2)?,
+                confidence: row.get(3)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+}
+
+fn row_to_document(row: &rusqlite::Row) -> rusqlite::Result<Document> {
+    Ok(Document {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        file_path: row.get(2)?,
+        file_hash: row.get(3)?,
+        mime_type: row.get(4)?,
+        size_bytes: row.get(5)?,
+        page_count: row.get(6)?,
+        ocr_used: row.get::<_, i64>(7)? != 0,
+        char_count: row.get(8)?,
+        ingested_at: row.get(9)?,
+    })
+}
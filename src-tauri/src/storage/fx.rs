@@ -0,0 +1,138 @@
+use crate::providers::fx::FxRate;
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::sync::{Arc, Mutex};
+
+pub struct FxStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl FxStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = FxStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: FxStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS fx_rates (
+                base TEXT NOT NULL,
+                quote TEXT NOT NULL,
+                date TEXT NOT NULL,
+                rate REAL NOT NULL,
+                provider TEXT NOT NULL,
+                fetched_at INTEGER NOT NULL,
+                PRIMARY KEY (base, quote, date)
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn upsert_rates(&self, rates: &[FxRate], provider: &str) -> Result<usize> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        let tx = conn.transaction()?;
+        for rate in rates {
+            tx.execute(
+                "INSERT OR REPLACE INTO fx_rates (base, quote, date, rate, provider, fetched_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![rate.base, rate.quote, rate.date, rate.rate, provider, now],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(rates.len())
+    }
+
+    /// Latest stored rate on or before `date` (today when None). Falls back to
+    /// the inverse of the opposite pair.
+    pub fn get_rate(&self, base: &str, quote: &str, date: Option<&str>) -> Result<Option<FxRate>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Self::rate_on_or_before(&conn, base, quote, date)
+    }
+
+    pub(crate) fn rate_on_or_before(conn: &Connection, base: &str, quote: &str, date: Option<&str>) -> Result<Option<FxRate>> {
+        if base == quote {
+            return Ok(Some(FxRate {
+                base: base.to_string(),
+                quote: quote.to_string(),
+                date: date.map(|d| d.to_string()).unwrap_or_else(today),
+                rate: 1.0,
+            }));
+        }
+        let date = date.map(|d| d.to_string()).unwrap_or_else(today);
+
+        let lookup = |b: &str, q: &str| -> Result<Option<(String, f64)>> {
+            Ok(conn
+                .query_row(
+                    "SELECT date, rate FROM fx_rates
+                     WHERE base = ?1 AND quote = ?2 AND date <= ?3
+                     ORDER BY date DESC LIMIT 1",
+                    params![b, q, date],
+                    |row| Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?)),
+                )
+                .optional()?)
+        };
+
+        if let Some((date, rate)) = lookup(base, quote)? {
+            return Ok(Some(FxRate { base: base.to_string(), quote: quote.to_string(), date, rate }));
+        }
+        if let Some((date, rate)) = lookup(quote, base)? {
+            if rate > 0.0 {
+                return Ok(Some(FxRate { base: base.to_string(), quote: quote.to_string(), date, rate: 1.0 / rate }));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn get_history(&self, base: &str, quote: &str, from: &str, to: &str) -> Result<Vec<FxRate>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT base, quote, date, rate FROM fx_rates
+             WHERE base = ?1 AND quote = ?2 AND date >= ?3 AND date <= ?4
+             ORDER BY date ASC",
+        )?;
+        let rows = stmt.query_map(params![base, quote, from, to], |row| {
+            Ok(FxRate {
+                base: row.get(0)?,
+                quote: row.get(1)?,
+                date: row.get(2)?,
+                rate: row.get(3)?,
+            })
+        })?;
+
+        let mut rates = Vec::new();
+        for row in rows {
+            rates.push(row?);
+        }
+        Ok(rates)
+    }
+
+    /// When the pair was last fetched, to decide whether to ask the provider again.
+    pub fn last_fetched_at(&self, base: &str, quote: &str) -> Result<Option<i64>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Ok(conn
+            .query_row(
+                "SELECT MAX(fetched_at) FROM fx_rates WHERE base = ?1 AND quote = ?2",
+                params![base, quote],
+                |row| row.get::<_, Option<i64>>(0),
+            )?)
+    }
+}
+
+fn today() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
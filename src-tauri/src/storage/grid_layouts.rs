@@ -121,6 +121,7 @@ impl GridLayoutStore {
         params_vec.push(Box::new(now));
         params_vec.push(Box::new(id));
 
+        // sql-audit: column assignments are literals, values are bound
         let sql = format!(
             "UPDATE grid_layouts SET {} WHERE id = ?",
             updates.join(", ")
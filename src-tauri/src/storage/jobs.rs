@@ -0,0 +1,165 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// A background job run through the JobManager. `status` is one of queued,
+/// running, succeeded, failed, cancelled or interrupted (the app quit
+/// before it finished).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub name: String,
+    pub class: String,
+    pub status: String,
+    pub progress: f64, // 0..1
+    pub message: Option<String>,
+    pub params: serde_json::Value,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub created_at: i64,
+    pub started_at: Option<i64>,
+    pub finished_at: Option<i64>,
+}
+
+pub struct JobStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl JobStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = JobStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: JobStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS jobs (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                class TEXT NOT NULL,
+                status TEXT NOT NULL,
+                progress REAL NOT NULL DEFAULT 0,
+                message TEXT,
+                params TEXT NOT NULL DEFAULT '{}',
+                result TEXT,
+                error TEXT,
+                created_at INTEGER NOT NULL,
+                started_at INTEGER,
+                finished_at INTEGER
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_jobs_created_at ON jobs(created_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn save_job(&self, job: &Job) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO jobs
+             (id, name, class, status, progress, message, params, result, error, created_at, started_at, finished_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                job.id,
+                job.name,
+                job.class,
+                job.status,
+                job.progress,
+                job.message,
+                job.params.to_string(),
+                job.result.as_ref().map(|r| r.to_string()),
+                job.error,
+                job.created_at,
+                job.started_at,
+                job.finished_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_job(&self, id: &str) -> Result<Option<Job>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, name, class, status, progress, message, params, result, error, created_at, started_at, finished_at
+             FROM jobs WHERE id = ?1",
+            params![id],
+            row_to_job,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    /// Newest first, optionally only one status.
+    pub fn list_jobs(&self, status: Option<&str>, limit: i64) -> Result<Vec<Job>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, class, status, progress, message, params, result, error, created_at, started_at, finished_at
+             FROM jobs
+             WHERE (?1 IS NULL OR status = ?1)
+             ORDER BY created_at DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![status, limit], row_to_job)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Jobs left queued or running by a previous session can't resume.
+    pub fn mark_interrupted(&self) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let count = conn.execute(
+            "UPDATE jobs SET status = 'interrupted', finished_at = ?1
+             WHERE status IN ('queued', 'running')",
+            params![chrono::Utc::now().timestamp()],
+        )?;
+        Ok(count)
+    }
+
+    pub fn delete_finished_before(&self, before: i64) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let count = conn.execute(
+            "DELETE FROM jobs WHERE finished_at IS NOT NULL AND finished_at < ?1",
+            params![before],
+        )?;
+        Ok(count)
+    }
+}
+
+fn row_to_job(row: &rusqlite::Row) -> rusqlite::Result<Job> {
+    let params: String = row.get(6)?;
+    let result: Option<String> = row.get(7)?;
+    Ok(Job {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        class: row.get(2)?,
+        status: row.get(3)?,
+        progress: row.get(4)?,
+        message: row.get(5)?,
+        params: serde_json::from_str(&params).unwrap_or(serde_json::Value::Null),
+        result: result.and_then(|r| serde_json::from_str(&r).ok()),
+        error: row.get(8)?,
+        created_at: row.get(9)?,
+        started_at: row.get(10)?,
+        finished_at: row.get(11)?,
+    })
+}
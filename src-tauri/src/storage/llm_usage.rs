@@ -0,0 +1,458 @@
+use anyhow::Result;
+use rusqlite::{Connection, params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmUsageRow {
+    pub day: String, // YYYY-MM-DD (UTC)
+    pub provider: String,
+    pub model: String,
+    pub requests: i64,
+    pub tokens_in: i64,
+    pub tokens_out: i64,
+    pub avg_latency_ms: f64,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmBudget {
+    pub provider: String,
+    pub monthly_limit_usd: Option<f64>,
+    pub monthly_token_limit: Option<i64>,
+    pub action: String, // "warn" or "block"
+    pub warned_month: Option<String>,
+}
+
+/// One request sent to a cloud provider, as it left the machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudEgressRecord {
+    pub id: i64,
+    pub provider: String,
+    pub model: String,
+    pub purpose: String,
+    pub chars_sent: i64,
+    /// Values redacted per category, e.g. {"email": 2}
+    pub redactions: std::collections::BTreeMap<String, usize>,
+    /// Start of the text that was sent, after redaction
+    pub preview: String,
+    pub created_at: i64,
+}
+
+/// Where the router sent one call and why.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmRouteRecord {
+    pub id: i64,
+    pub task: String,
+    pub target: String, // "local" or "cloud"
+    pub provider: String,
+    pub model: String,
+    pub reason: String,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub cost_usd: f64,
+    /// Served by the other target after the routed one failed
+    pub fell_back: bool,
+    pub success: bool,
+    pub error: Option<String>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmRouteSummary {
+    pub task: String,
+    pub target: String,
+    pub provider: String,
+    pub model: String,
+    pub calls: i64,
+    pub fallbacks: i64,
+    pub failures: i64,
+    pub tokens_in: i64,
+    pub tokens_out: i64,
+    pub cost_usd: f64,
+}
+
+pub struct LlmUsageStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl LlmUsageStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = LlmUsageStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: LlmUsageStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS llm_usage_daily (
+                day TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                model TEXT NOT NULL,
+                requests INTEGER NOT NULL DEFAULT 0,
+                tokens_in INTEGER NOT NULL DEFAULT 0,
+                tokens_out INTEGER NOT NULL DEFAULT 0,
+                latency_ms_total INTEGER NOT NULL DEFAULT 0,
+                cost_usd REAL NOT NULL DEFAULT 0,
+                PRIMARY KEY (day, provider, model)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS llm_budgets (
+                provider TEXT PRIMARY KEY,
+                monthly_limit_usd REAL,
+                monthly_token_limit INTEGER,
+                action TEXT NOT NULL DEFAULT 'warn',
+                warned_month TEXT
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS cloud_llm_egress (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                provider TEXT NOT NULL,
+                model TEXT NOT NULL,
+                purpose TEXT NOT NULL,
+                chars_sent INTEGER NOT NULL,
+                redactions_json TEXT NOT NULL DEFAULT '{}',
+                preview TEXT NOT NULL DEFAULT '',
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS llm_route_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task TEXT NOT NULL,
+                target TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                model TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                prompt_tokens INTEGER NOT NULL DEFAULT 0,
+                completion_tokens INTEGER NOT NULL DEFAULT 0,
+                cost_usd REAL NOT NULL DEFAULT 0,
+                fell_back INTEGER NOT NULL DEFAULT 0,
+                success INTEGER NOT NULL DEFAULT 1,
+                error TEXT,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_llm_route_log_created ON llm_route_log(created_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn record_usage(
+        &self,
+        provider: &str,
+        model: &str,
+        tokens_in: i64,
+        tokens_out: i64,
+        latency_ms: i64,
+        cost_usd: f64,
+    ) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let day = chrono::Utc::now().format("%Y-%m-%d").to_string();
+
+        conn.execute(
+            "INSERT INTO llm_usage_daily (day, provider, model, requests, tokens_in, tokens_out, latency_ms_total, cost_usd)
+             VALUES (?1, ?2, ?3, 1, ?4, ?5, ?6, ?7)
+             ON CONFLICT(day, provider, model) DO UPDATE SET
+                requests = requests + 1,
+                tokens_in = tokens_in + excluded.tokens_in,
+                tokens_out = tokens_out + excluded.tokens_out,
+                latency_ms_total = latency_ms_total + excluded.latency_ms_total,
+                cost_usd = cost_usd + excluded.cost_usd",
+            params![day, provider, model, tokens_in, tokens_out, latency_ms, cost_usd],
+        )?;
+
+        Ok(())
+    }
+
+    /// Daily rows between two days (inclusive, `YYYY-MM-DD`).
+    pub fn get_usage(&self, from_day: &str, to_day: &str, provider: Option<&str>) -> Result<Vec<LlmUsageRow>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT day, provider, model, requests, tokens_in, tokens_out, latency_ms_total, cost_usd
+             FROM llm_usage_daily
+             WHERE day >= ?1 AND day <= ?2 AND (?3 IS NULL OR provider = ?3)
+             ORDER BY day DESC, provider, model"
+        )?;
+        let rows = stmt.query_map(params![from_day, to_day, provider], |row| {
+            let requests: i64 = row.get(3)?;
+            let latency_total: i64 = row.get(6)?;
+            Ok(LlmUsageRow {
+                day: row.get(0)?,
+                provider: row.get(1)?,
+                model: row.get(2)?,
+                requests,
+                tokens_in: row.get(4)?,
+                tokens_out: row.get(5)?,
+                avg_latency_ms: if requests > 0 { latency_total as f64 / requests as f64 } else { 0.0 },
+                cost_usd: row.get(7)?,
+            })
+        })?;
+
+        let mut usage = Vec::new();
+        for row in rows {
+            usage.push(row?);
+        }
+        Ok(usage)
+    }
+
+    /// (cost, tokens in + out) for a provider in a month (`YYYY-MM`).
+    pub fn month_totals(&self, provider: &str, month: &str) -> Result<(f64, i64)> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let totals = conn.query_row(
+            "SELECT COALESCE(SUM(cost_usd), 0), COALESCE(SUM(tokens_in + tokens_out), 0)
+             FROM llm_usage_daily
+             WHERE provider = ?1 AND substr(day, 1, 7) = ?2",
+            params![provider, month],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(totals)
+    }
+
+    pub fn record_egress(&self, record: &CloudEgressRecord) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO cloud_llm_egress (provider, model, purpose, chars_sent, redactions_json, preview, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                record.provider,
+                record.model,
+                record.purpose,
+                record.chars_sent,
+                serde_json::to_string(&record.redactions)?,
+                record.preview,
+                record.created_at,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Newest first.
+    pub fn list_egress(&self, provider: Option<&str>, limit: i64) -> Result<Vec<CloudEgressRecord>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, provider, model, purpose, chars_sent, redactions_json, preview, created_at
+             FROM cloud_llm_egress
+             WHERE ?1 IS NULL OR provider = ?1
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?2"
+        )?;
+        let rows = stmt.query_map(params![provider, limit], |row| {
+            let redactions: String = row.get(5)?;
+            Ok(CloudEgressRecord {
+                id: row.get(0)?,
+                provider: row.get(1)?,
+                model: row.get(2)?,
+                purpose: row.get(3)?,
+                chars_sent: row.get(4)?,
+                redactions: serde_json::from_str(&redactions).unwrap_or_default(),
+                preview: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        })?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            records.push(row?);
+        }
+        Ok(records)
+    }
+
+    pub fn record_route(&self, record: &LlmRouteRecord) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO llm_route_log (task, target, provider, model, reason, prompt_tokens, completion_tokens,
+                                        cost_usd, fell_back, success, error, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                record.task,
+                record.target,
+                record.provider,
+                record.model,
+                record.reason,
+                record.prompt_tokens,
+                record.completion_tokens,
+                record.cost_usd,
+                record.fell_back as i64,
+                record.success as i64,
+                record.error,
+                record.created_at,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Newest first.
+    pub fn list_routes(&self, task: Option<&str>, limit: i64) -> Result<Vec<LlmRouteRecord>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, task, target, provider, model, reason, prompt_tokens, completion_tokens,
+                    cost_usd, fell_back, success, error, created_at
+             FROM llm_route_log
+             WHERE ?1 IS NULL OR task = ?1
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?2"
+        )?;
+        let rows = stmt.query_map(params![task, limit], |row| {
+            Ok(LlmRouteRecord {
+                id: row.get(0)?,
+                task: row.get(1)?,
+                target: row.get(2)?,
+                provider: row.get(3)?,
+                model: row.get(4)?,
+                reason: row.get(5)?,
+                prompt_tokens: row.get(6)?,
+                completion_tokens: row.get(7)?,
+                cost_usd: row.get(8)?,
+                fell_back: row.get::<_, i64>(9)? == 1,
+                success: row.get::<_, i64>(10)? == 1,
+                error: row.get(11)?,
+                created_at: row.get(12)?,
+            })
+        })?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            records.push(row?);
+        }
+        Ok(records)
+    }
+
+    /// Calls and cost per task, target and model since `since` (unix seconds).
+    pub fn route_summary(&self, since: i64) -> Result<Vec<LlmRouteSummary>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT task, target, provider, model, COUNT(*), SUM(fell_back), SUM(1 - success),
+                    SUM(prompt_tokens), SUM(completion_tokens), SUM(cost_usd)
+             FROM llm_route_log
+             WHERE created_at >= ?1
+             GROUP BY task, target, provider, model
+             ORDER BY task, target, model"
+        )?;
+        let rows = stmt.query_map(params![since], |row| {
+            Ok(LlmRouteSummary {
+                task: row.get(0)?,
+                target: row.get(1)?,
+                provider: row.get(2)?,
+                model: row.get(3)?,
+                calls: row.get(4)?,
+                fallbacks: row.get(5)?,
+                failures: row.get(6)?,
+                tokens_in: row.get(7)?,
+                tokens_out: row.get(8)?,
+                cost_usd: row.get(9)?,
+            })
+        })?;
+
+        let mut summaries = Vec::new();
+        for row in rows {
+            summaries.push(row?);
+        }
+        Ok(summaries)
+    }
+
+    pub fn set_budget(
+        &self,
+        provider: &str,
+        monthly_limit_usd: Option<f64>,
+        monthly_token_limit: Option<i64>,
+        action: &str,
+    ) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO llm_budgets (provider, monthly_limit_usd, monthly_token_limit, action)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(provider) DO UPDATE SET
+                monthly_limit_usd = excluded.monthly_limit_usd,
+                monthly_token_limit = excluded.monthly_token_limit,
+                action = excluded.action,
+                warned_month = NULL",
+            params![provider, monthly_limit_usd, monthly_token_limit, action],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_budget(&self, provider: &str) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM llm_budgets WHERE provider = ?1", params![provider])?;
+        Ok(())
+    }
+
+    pub fn get_budget(&self, provider: &str) -> Result<Option<LlmBudget>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let budget = conn
+            .query_row(
+                "SELECT provider, monthly_limit_usd, monthly_token_limit, action, warned_month
+                 FROM llm_budgets WHERE provider = ?1",
+                params![provider],
+                row_to_budget,
+            )
+            .optional()?;
+        Ok(budget)
+    }
+
+    pub fn list_budgets(&self) -> Result<Vec<LlmBudget>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT provider, monthly_limit_usd, monthly_token_limit, action, warned_month
+             FROM llm_budgets ORDER BY provider"
+        )?;
+        let rows = stmt.query_map([], row_to_budget)?;
+
+        let mut budgets = Vec::new();
+        for row in rows {
+            budgets.push(row?);
+        }
+        Ok(budgets)
+    }
+
+    pub fn mark_budget_warned(&self, provider: &str, month: &str) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE llm_budgets SET warned_month = ?1 WHERE provider = ?2",
+            params![month, provider],
+        )?;
+        Ok(())
+    }
+}
+
+fn row_to_budget(row: &rusqlite::Row) -> rusqlite::Result<LlmBudget> {
+    Ok(LlmBudget {
+        provider: row.get(0)?,
+        monthly_limit_usd: row.get(1)?,
+        monthly_token_limit: row.get(2)?,
+        action: row.get(3)?,
+        warned_month: row.get(4)?,
+    })
+}
@@ -1,6 +1,9 @@
+use crate::services::change_feed::{self, ChangeFeed, ChangeOp};
+use crate::storage::sql;
 use anyhow::Result;
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +14,10 @@ pub struct MarketPrice {
     pub change_percent: f64,
     pub volume: i64,
     pub timestamp: i64,
+    /// Market session when the quote was served (pre_market, regular,
+    /// post_market, closed); not stored
+    #[serde(default)]
+    pub session: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +45,89 @@ pub struct MarketSnapshot {
     pub timestamp: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fundamentals {
+    pub ticker: String,
+    pub name: Option<String>,
+    pub sector: Option<String>,
+    pub industry: Option<String>,
+    pub pe_ratio: Option<f64>,
+    pub eps: Option<f64>,
+    pub market_cap: Option<f64>,
+    pub dividend_yield: Option<f64>,
+    pub provider: Option<String>,
+    pub fetched_at: i64,
+}
+
+/// A symbol learned from a provider search, kept for offline autocomplete.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolInfo {
+    pub symbol: String,
+    pub name: String,
+    pub exchange: Option<String>,
+    pub asset_type: Option<String>,
+    pub region: Option<String>,
+    pub currency: Option<String>,
+    pub provider: Option<String>,
+    pub updated_at: i64,
+}
+
+/// Short interest as of one settlement date.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortInterest {
+    pub ticker: String,
+    pub report_ts: i64,
+    pub shares_short: i64,
+    pub short_percent_float: Option<f64>,
+    pub days_to_cover: Option<f64>,
+    pub provider: Option<String>,
+    pub fetched_at: i64,
+}
+
+/// An analyst upgrade, downgrade, initiation or reiteration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalystRating {
+    pub id: i64,
+    pub ticker: String,
+    pub firm: String,
+    pub action: String, // upgrade|downgrade|initiate|maintain|reiterate
+    pub from_grade: Option<String>,
+    pub to_grade: Option<String>,
+    pub rated_at: i64,
+    pub provider: Option<String>,
+    pub fetched_at: i64,
+}
+
+/// How long a rating change counts as recent for `rating_upgrade` /
+/// `rating_downgrade` rule conditions.
+pub const RATING_CHANGE_WINDOW_SECS: i64 = 7 * 86_400;
+
+/// A user-added market holiday or early close (`early_close` as local
+/// "HH:MM"), on top of the built-in exchange calendars.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketHoliday {
+    pub id: i64,
+    pub exchange: String,
+    pub date: String, // YYYY-MM-DD
+    pub name: String,
+    pub early_close: Option<String>,
+    pub created_at: i64,
+}
+
+/// A note pinned to a ticker's chart: an event or alert marker, or text the
+/// user wrote.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartAnnotation {
+    pub id: i64,
+    pub ticker: String,
+    pub timestamp: i64,
+    pub kind: String, // event|alert|user
+    pub text: String,
+    pub event_id: Option<i64>,
+    pub alert_id: Option<i64>,
+    pub created_at: i64,
+}
+
 pub struct MarketDataStore {
     pub conn: Arc<Mutex<Connection>>,
 }
@@ -103,11 +193,130 @@ impl MarketDataStore {
             [],
         )?;
 
+        // Company fundamentals, refreshed on a schedule
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS fundamentals (
+                ticker TEXT NOT NULL PRIMARY KEY,
+                name TEXT,
+                sector TEXT,
+                industry TEXT,
+                pe_ratio REAL,
+                eps REAL,
+                market_cap REAL,
+                dividend_yield REAL,
+                provider TEXT,
+                fetched_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Symbols seen in provider searches, and when each query last went out
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS symbol_directory (
+                symbol TEXT NOT NULL PRIMARY KEY,
+                name TEXT NOT NULL,
+                exchange TEXT,
+                asset_type TEXT,
+                region TEXT,
+                currency TEXT,
+                provider TEXT,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS symbol_searches (
+                query TEXT NOT NULL PRIMARY KEY,
+                searched_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Short interest and analyst rating history of watchlist tickers
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS short_interest (
+                ticker TEXT NOT NULL,
+                report_ts INTEGER NOT NULL,
+                shares_short INTEGER NOT NULL,
+                short_percent_float REAL,
+                days_to_cover REAL,
+                provider TEXT,
+                fetched_at INTEGER NOT NULL,
+                PRIMARY KEY (ticker, report_ts)
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS analyst_ratings (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                ticker TEXT NOT NULL,
+                firm TEXT NOT NULL,
+                action TEXT NOT NULL,
+                from_grade TEXT,
+                to_grade TEXT,
+                rated_at INTEGER NOT NULL,
+                provider TEXT,
+                fetched_at INTEGER NOT NULL,
+                UNIQUE(ticker, firm, rated_at, action)
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS market_signal_fetches (
+                ticker TEXT NOT NULL PRIMARY KEY,
+                fetched_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS market_holidays (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                exchange TEXT NOT NULL,
+                date TEXT NOT NULL,
+                name TEXT NOT NULL,
+                early_close TEXT,
+                created_at INTEGER NOT NULL,
+                UNIQUE(exchange, date)
+            )",
+            [],
+        )?;
+
+        // marker_key ("event:<id>", "alert:<id>") keeps markers unique per
+        // ticker; user notes leave it NULL and never conflict
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS chart_annotations (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                ticker TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                text TEXT NOT NULL,
+                event_id INTEGER,
+                alert_id INTEGER,
+                marker_key TEXT,
+                created_at INTEGER NOT NULL,
+                UNIQUE(ticker, marker_key)
+            )",
+            [],
+        )?;
+
         // Create indexes
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_chart_annotations_ticker_ts ON chart_annotations(ticker, timestamp)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_fundamentals_sector ON fundamentals(sector)",
+            [],
+        )?;
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_price_history_ticker_ts ON price_history(ticker, timestamp DESC)",
             [],
         )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_analyst_ratings_ticker_ts ON analyst_ratings(ticker, rated_at DESC)",
+            [],
+        )?;
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_price_history_ts ON price_history(timestamp DESC)",
             [],
@@ -135,6 +344,7 @@ impl MarketDataStore {
             ],
         )?;
 
+        ChangeFeed::keys(change_feed::MARKET_PRICES, ChangeOp::Update, [price.ticker.to_uppercase()]);
         Ok(())
     }
 
@@ -156,6 +366,7 @@ impl MarketDataStore {
                         change_percent: row.get(3)?,
                         volume: row.get(4)?,
                         timestamp: row.get(5)?,
+                        session: None,
                     })
                 },
             )
@@ -167,12 +378,11 @@ impl MarketDataStore {
     pub fn get_prices(&self, tickers: &[String]) -> Result<Vec<MarketPrice>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
-        let placeholders = tickers.iter().map(|_| "?").collect::<Vec<_>>().join(",");
         let query = format!(
             "SELECT ticker, price, change, change_percent, volume, timestamp
              FROM market_prices
              WHERE ticker IN ({})",
-            placeholders
+            sql::placeholders(tickers.len())
         );
 
         let mut stmt = conn.prepare(&query)?;
@@ -186,6 +396,7 @@ impl MarketDataStore {
                     change_percent: row.get(3)?,
                     volume: row.get(4)?,
                     timestamp: row.get(5)?,
+                    session: None,
                 })
             },
         )?;
@@ -261,6 +472,39 @@ impl MarketDataStore {
         Ok(history)
     }
 
+    /// Every stored bar in the range, oldest first, for server-side downsampling.
+    pub fn get_price_history_range(&self, ticker: &str, from_ts: i64, to_ts: i64) -> Result<Vec<PriceHistory>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, ticker, timestamp, open, high, low, close, volume
+             FROM price_history
+             WHERE ticker = ?1 AND timestamp >= ?2 AND timestamp <= ?3
+             ORDER BY timestamp ASC",
+        )?;
+
+        let rows = stmt.query_map(params![ticker, from_ts, to_ts], |row| {
+            Ok(PriceHistory {
+                id: row.get(0)?,
+                ticker: row.get(1)?,
+                timestamp: row.get(2)?,
+                open: row.get(3)?,
+                high: row.get(4)?,
+                low: row.get(5)?,
+                close: row.get(6)?,
+                volume: row.get(7)?,
+            })
+        })?;
+
+        let mut history = Vec::new();
+        for row in rows {
+            history.push(row?);
+        }
+
+        Ok(history)
+    }
+
     pub fn upsert_snapshot(&self, snapshot: &MarketSnapshot) -> Result<()> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -315,4 +559,573 @@ impl MarketDataStore {
 
         Ok(snapshot)
     }
+
+    pub fn upsert_fundamentals(&self, fundamentals: &Fundamentals) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO fundamentals
+             (ticker, name, sector, industry, pe_ratio, eps, market_cap, dividend_yield, provider, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                fundamentals.ticker.to_uppercase(),
+                fundamentals.name,
+                fundamentals.sector,
+                fundamentals.industry,
+                fundamentals.pe_ratio,
+                fundamentals.eps,
+                fundamentals.market_cap,
+                fundamentals.dividend_yield,
+                fundamentals.provider,
+                fundamentals.fetched_at
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_fundamentals(&self, ticker: &str) -> Result<Option<Fundamentals>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let fundamentals = conn
+            .query_row(
+                "SELECT ticker, name, sector, industry, pe_ratio, eps, market_cap, dividend_yield, provider, fetched_at
+                 FROM fundamentals
+                 WHERE ticker = ?1",
+                params![ticker.to_uppercase()],
+                row_to_fundamentals,
+            )
+            .optional()?;
+
+        Ok(fundamentals)
+    }
+
+    pub fn list_fundamentals(&self, sector: Option<&str>) -> Result<Vec<Fundamentals>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT ticker, name, sector, industry, pe_ratio, eps, market_cap, dividend_yield, provider, fetched_at
+             FROM fundamentals
+             WHERE ?1 IS NULL OR lower(sector) = lower(?1)
+             ORDER BY ticker ASC",
+        )?;
+        let rows = stmt.query_map(params![sector], row_to_fundamentals)?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+
+        Ok(out)
+    }
+
+    /// Sector per ticker, for tickers that have one.
+    pub fn get_sectors(&self, tickers: &[String]) -> Result<std::collections::HashMap<String, String>> {
+        let mut sectors = std::collections::HashMap::new();
+        for ticker in tickers {
+            if let Some(sector) = self.get_fundamentals(ticker)?.and_then(|f| f.sector) {
+                sectors.insert(ticker.clone(), sector);
+            }
+        }
+        Ok(sectors)
+    }
+
+    /// Tickers held in portfolios or watched by price alerts whose fundamentals
+    /// are missing or older than `max_age_secs`.
+    pub fn tickers_needing_fundamentals(&self, max_age_secs: i64) -> Result<Vec<String>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let cutoff = chrono::Utc::now().timestamp() - max_age_secs;
+
+        let mut stmt = conn.prepare(
+            "SELECT t.ticker FROM (
+                 SELECT DISTINCT upper(ticker) AS ticker FROM holdings
+                 UNION
+                 SELECT DISTINCT upper(ticker) AS ticker FROM price_alerts
+             ) t
+             LEFT JOIN fundamentals f ON f.ticker = t.ticker
+             WHERE f.ticker IS NULL OR f.fetched_at < ?1
+             ORDER BY f.fetched_at ASC",
+        )?;
+        let rows = stmt.query_map(params![cutoff], |row| row.get::<_, String>(0))?;
+
+        let mut tickers = Vec::new();
+        for row in rows {
+            tickers.push(row?);
+        }
+
+        Ok(tickers)
+    }
+
+    pub fn upsert_symbols(&self, symbols: &[SymbolInfo]) -> Result<()> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        for symbol in symbols {
+            tx.execute(
+                "INSERT OR REPLACE INTO symbol_directory
+                 (symbol, name, exchange, asset_type, region, currency, provider, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    symbol.symbol.to_uppercase(),
+                    symbol.name,
+                    symbol.exchange,
+                    symbol.asset_type,
+                    symbol.region,
+                    symbol.currency,
+                    symbol.provider,
+                    symbol.updated_at
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Cached symbols starting with `query` or whose name contains it.
+    pub fn search_symbol_directory(&self, query: &str, limit: i64) -> Result<Vec<SymbolInfo>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+
+        let mut stmt = conn.prepare(
+            "SELECT symbol, name, exchange, asset_type, region, currency, provider, updated_at
+             FROM symbol_directory
+             WHERE symbol LIKE ?1 || '%' ESCAPE '\\' OR name LIKE '%' || ?1 || '%' ESCAPE '\\'
+             ORDER BY length(symbol) ASC, symbol ASC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![escaped, limit], |row| {
+            Ok(SymbolInfo {
+                symbol: row.get(0)?,
+                name: row.get(1)?,
+                exchange: row.get(2)?,
+                asset_type: row.get(3)?,
+                region: row.get(4)?,
+                currency: row.get(5)?,
+                provider: row.get(6)?,
+                updated_at: row.get(7)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// When `query` was last sent to the providers, if ever.
+    pub fn symbol_search_time(&self, query: &str) -> Result<Option<i64>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Ok(conn
+            .query_row(
+                "SELECT searched_at FROM symbol_searches WHERE query = ?1",
+                params![query.to_lowercase()],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn record_symbol_search(&self, query: &str, searched_at: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO symbol_searches (query, searched_at) VALUES (?1, ?2)",
+            params![query.to_lowercase(), searched_at],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_market_holidays(&self, exchange: Option<&str>) -> Result<Vec<MarketHoliday>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, exchange, date, name, early_close, created_at
+             FROM market_holidays
+             WHERE ?1 IS NULL OR exchange = upper(?1)
+             ORDER BY date ASC",
+        )?;
+        let rows = stmt.query_map(params![exchange], |row| {
+            Ok(MarketHoliday {
+                id: row.get(0)?,
+                exchange: row.get(1)?,
+                date: row.get(2)?,
+                name: row.get(3)?,
+                early_close: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Add or replace the holiday on `date` for an exchange.
+    pub fn upsert_market_holiday(&self, exchange: &str, date: &str, name: &str, early_close: Option<&str>) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT INTO market_holidays (exchange, date, name, early_close, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(exchange, date) DO UPDATE SET name = excluded.name, early_close = excluded.early_close",
+            params![exchange.to_uppercase(), date, name, early_close, now],
+        )?;
+        Ok(conn.query_row(
+            "SELECT id FROM market_holidays WHERE exchange = ?1 AND date = ?2",
+            params![exchange.to_uppercase(), date],
+            |row| row.get(0),
+        )?)
+    }
+
+    pub fn delete_market_holiday(&self, id: i64) -> Result<bool> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Ok(conn.execute("DELETE FROM market_holidays WHERE id = ?1", params![id])? > 0)
+    }
+
+    pub fn list_chart_annotations(&self, ticker: &str, from_ts: i64, to_ts: i64) -> Result<Vec<ChartAnnotation>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, ticker, timestamp, kind, text, event_id, alert_id, created_at
+             FROM chart_annotations
+             WHERE ticker = upper(?1) AND timestamp >= ?2 AND timestamp <= ?3
+             ORDER BY timestamp ASC, id ASC",
+        )?;
+        let rows = stmt.query_map(params![ticker.trim(), from_ts, to_ts], |row| {
+            Ok(ChartAnnotation {
+                id: row.get(0)?,
+                ticker: row.get(1)?,
+                timestamp: row.get(2)?,
+                kind: row.get(3)?,
+                text: row.get(4)?,
+                event_id: row.get(5)?,
+                alert_id: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// A user note on a ticker's chart.
+    pub fn add_chart_annotation(&self, ticker: &str, timestamp: i64, text: &str) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO chart_annotations (ticker, timestamp, kind, text, created_at)
+             VALUES (upper(?1), ?2, 'user', ?3, ?4)",
+            params![ticker.trim(), timestamp, text, chrono::Utc::now().timestamp()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Pin an event marker, or an alert marker when `alert_id` is set, to a
+    /// ticker's chart; pinning it again updates its position and text.
+    pub fn upsert_chart_marker(
+        &self,
+        ticker: &str,
+        timestamp: i64,
+        text: &str,
+        event_id: Option<i64>,
+        alert_id: Option<i64>,
+    ) -> Result<i64> {
+        let (kind, marker_key) = match (alert_id, event_id) {
+            (Some(alert_id), _) => ("alert", format!("alert:{}", alert_id)),
+            (None, Some(event_id)) => ("event", format!("event:{}", event_id)),
+            (None, None) => anyhow::bail!("A chart marker needs an event or alert"),
+        };
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO chart_annotations (ticker, timestamp, kind, text, event_id, alert_id, marker_key, created_at)
+             VALUES (upper(?1), ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(ticker, marker_key) DO UPDATE SET
+                timestamp = excluded.timestamp, text = excluded.text",
+            params![ticker.trim(), timestamp, kind, text, event_id, alert_id, marker_key, chrono::Utc::now().timestamp()],
+        )?;
+        Ok(conn.query_row(
+            "SELECT id FROM chart_annotations WHERE ticker = upper(?1) AND marker_key = ?2",
+            params![ticker.trim(), marker_key],
+            |row| row.get(0),
+        )?)
+    }
+
+    pub fn update_chart_annotation(&self, id: i64, text: &str) -> Result<bool> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Ok(conn.execute("UPDATE chart_annotations SET text = ?1 WHERE id = ?2", params![text, id])? > 0)
+    }
+
+    pub fn delete_chart_annotation(&self, id: i64) -> Result<bool> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Ok(conn.execute("DELETE FROM chart_annotations WHERE id = ?1", params![id])? > 0)
+    }
+
+    pub fn upsert_short_interest(&self, reports: &[ShortInterest]) -> Result<()> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        for report in reports {
+            tx.execute(
+                "INSERT OR REPLACE INTO short_interest
+                 (ticker, report_ts, shares_short, short_percent_float, days_to_cover, provider, fetched_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    report.ticker.to_uppercase(),
+                    report.report_ts,
+                    report.shares_short,
+                    report.short_percent_float,
+                    report.days_to_cover,
+                    report.provider,
+                    report.fetched_at
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Newest report first.
+    pub fn list_short_interest(&self, ticker: &str, limit: i64) -> Result<Vec<ShortInterest>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT ticker, report_ts, shares_short, short_percent_float, days_to_cover, provider, fetched_at
+             FROM short_interest
+             WHERE ticker = ?1
+             ORDER BY report_ts DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![ticker.to_uppercase(), limit], |row| {
+            Ok(ShortInterest {
+                ticker: row.get(0)?,
+                report_ts: row.get(1)?,
+                shares_short: row.get(2)?,
+                short_percent_float: row.get(3)?,
+                days_to_cover: row.get(4)?,
+                provider: row.get(5)?,
+                fetched_at: row.get(6)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Store rating actions not seen before. Returns how many were new.
+    pub fn insert_analyst_ratings(&self, ratings: &[AnalystRating]) -> Result<usize> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        let mut inserted = 0;
+        for rating in ratings {
+            inserted += tx.execute(
+                "INSERT OR IGNORE INTO analyst_ratings
+                 (ticker, firm, action, from_grade, to_grade, rated_at, provider, fetched_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    rating.ticker.to_uppercase(),
+                    rating.firm,
+                    rating.action,
+                    rating.from_grade,
+                    rating.to_grade,
+                    rating.rated_at,
+                    rating.provider,
+                    rating.fetched_at
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Newest first, optionally only actions at or after `since`.
+    pub fn list_analyst_ratings(&self, ticker: &str, since: Option<i64>, limit: i64) -> Result<Vec<AnalystRating>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, ticker, firm, action, from_grade, to_grade, rated_at, provider, fetched_at
+             FROM analyst_ratings
+             WHERE ticker = ?1 AND (?2 IS NULL OR rated_at >= ?2)
+             ORDER BY rated_at DESC
+             LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![ticker.to_uppercase(), since, limit], |row| {
+            Ok(AnalystRating {
+                id: row.get(0)?,
+                ticker: row.get(1)?,
+                firm: row.get(2)?,
+                action: row.get(3)?,
+                from_grade: row.get(4)?,
+                to_grade: row.get(5)?,
+                rated_at: row.get(6)?,
+                provider: row.get(7)?,
+                fetched_at: row.get(8)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Ticker for a symbol or company name, from fundamentals or the ticker list.
+    pub fn resolve_ticker(&self, entity: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Ok(conn
+            .query_row(
+                "SELECT ticker FROM fundamentals WHERE lower(ticker) = lower(?1) OR lower(name) = lower(?1)
+                 UNION ALL
+                 SELECT upper(symbol) FROM stock_tickers WHERE lower(symbol) = lower(?1) OR lower(name) = lower(?1)
+                 LIMIT 1",
+                params![entity.trim()],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn record_signal_fetch(&self, ticker: &str, fetched_at: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO market_signal_fetches (ticker, fetched_at) VALUES (?1, ?2)",
+            params![ticker.to_uppercase(), fetched_at],
+        )?;
+        Ok(())
+    }
+
+    /// Tickers on enabled watchlist items (by symbol, or by company name with
+    /// stored fundamentals) whose short interest and ratings were last
+    /// fetched before `max_age_secs` ago.
+    pub fn watchlist_tickers_needing_signals(&self, max_age_secs: i64) -> Result<Vec<String>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let cutoff = chrono::Utc::now().timestamp() - max_age_secs;
+
+        let mut stmt = conn.prepare(
+            "SELECT t.ticker FROM (
+                 SELECT upper(wi.value) AS ticker FROM watchlist_items wi
+                 WHERE wi.enabled = 1 AND wi.item_type = 'entity'
+                   AND upper(wi.value) IN (SELECT upper(symbol) FROM stock_tickers UNION SELECT ticker FROM fundamentals)
+                 UNION
+                 SELECT f.ticker FROM watchlist_items wi
+                 JOIN fundamentals f ON lower(f.name) = lower(wi.value)
+                 WHERE wi.enabled = 1 AND wi.item_type = 'entity'
+             ) t
+             LEFT JOIN market_signal_fetches s ON s.ticker = t.ticker
+             WHERE s.ticker IS NULL OR s.fetched_at < ?1
+             ORDER BY s.fetched_at ASC",
+        )?;
+        let rows = stmt.query_map(params![cutoff], |row| row.get::<_, String>(0))?;
+
+        let mut tickers = Vec::new();
+        for row in rows {
+            tickers.push(row?);
+        }
+        Ok(tickers)
+    }
+}
+
+fn row_to_fundamentals(row: &rusqlite::Row<'_>) -> rusqlite::Result<Fundamentals> {
+    Ok(Fundamentals {
+        ticker: row.get(0)?,
+        name: row.get(1)?,
+        sector: row.get(2)?,
+        industry: row.get(3)?,
+        pe_ratio: row.get(4)?,
+        eps: row.get(5)?,
+        market_cap: row.get(6)?,
+        dividend_yield: row.get(7)?,
+        provider: row.get(8)?,
+        fetched_at: row.get(9)?,
+    })
+}
+
+/// Change between the two latest short interest reports, in percent.
+pub fn short_interest_change_pct(reports: &[ShortInterest]) -> Option<f64> {
+    match reports {
+        [latest, previous, ..] if previous.shares_short > 0 => {
+            Some((latest.shares_short - previous.shares_short) as f64 / previous.shares_short as f64 * 100.0)
+        }
+        _ => None,
+    }
+}
+
+/// Rule-engine entities for the market signals of tickers among `entities`
+/// (symbols, or company names with stored fundamentals):
+/// "rating:upgrade:<ticker>" / "rating:downgrade:<ticker>" for rating changes
+/// at or after `since`, and "short_interest_change_pct:<ticker>:<pct>".
+/// Takes the locked connection; tables that don't exist yet yield nothing.
+pub fn market_signal_entities(conn: &Connection, entities: &HashSet<String>, since: i64) -> Vec<String> {
+    let mut out = Vec::new();
+    let (Ok(mut ticker_stmt), Ok(mut rating_stmt), Ok(mut short_stmt)) = (
+        conn.prepare("SELECT ticker FROM fundamentals WHERE lower(ticker) = ?1 OR lower(name) = ?1"),
+        conn.prepare(
+            "SELECT DISTINCT action FROM analyst_ratings
+             WHERE ticker = ?1 AND rated_at >= ?2 AND action IN ('upgrade', 'downgrade')",
+        ),
+        conn.prepare("SELECT shares_short FROM short_interest WHERE ticker = ?1 ORDER BY report_ts DESC LIMIT 2"),
+    ) else {
+        return out;
+    };
+
+    let mut tickers: Vec<String> = Vec::new();
+    for entity in entities.iter().filter(|e| !e.contains(':')) {
+        let resolved: Vec<String> = ticker_stmt
+            .query_map(params![entity], |row| row.get::<_, String>(0))
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default();
+        // Bare symbols without fundamentals still count
+        let candidates = if resolved.is_empty() { vec![entity.to_uppercase()] } else { resolved };
+        for ticker in candidates {
+            if !tickers.contains(&ticker) {
+                tickers.push(ticker);
+            }
+        }
+    }
+
+    for ticker in tickers {
+        if let Ok(rows) = rating_stmt.query_map(params![ticker, since], |row| row.get::<_, String>(0)) {
+            for action in rows.filter_map(|r| r.ok()) {
+                out.push(format!("rating:{}:{}", action, ticker.to_lowercase()));
+            }
+        }
+        let shares: Vec<i64> = short_stmt
+            .query_map(params![ticker], |row| row.get::<_, i64>(0))
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default();
+        if let &[latest, previous] = shares.as_slice() {
+            if previous > 0 {
+                let pct = (latest - previous) as f64 / previous as f64 * 100.0;
+                out.push(format!("short_interest_change_pct:{}:{:.2}", ticker.to_lowercase(), pct));
+            }
+        }
+    }
+    out
 }
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use rusqlite::{Connection, params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Memory {
+    pub id: i64,
+    pub content: String,
+    pub kind: String, // "fact" or "preference"
+    pub source_conversation_id: Option<String>,
+    #[serde(skip)]
+    pub embedding: Vec<f32>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_used_at: Option<i64>,
+    pub use_count: i64,
+}
+
+pub struct MemoryStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl MemoryStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = MemoryStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: MemoryStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS ai_memories (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                content TEXT NOT NULL,
+                kind TEXT NOT NULL DEFAULT 'fact',
+                source_conversation_id TEXT,
+                embedding TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                last_used_at INTEGER,
+                use_count INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn add_memory(
+        &self,
+        content: &str,
+        kind: &str,
+        source_conversation_id: Option<&str>,
+        embedding: &[f32],
+    ) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT INTO ai_memories (content, kind, source_conversation_id, embedding, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+            params![content, kind, source_conversation_id, serde_json::to_string(embedding)?, now],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn update_memory(&self, id: i64, content: &str, kind: Option<&str>, embedding: &[f32]) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        let updated = conn.execute(
+            "UPDATE ai_memories
+             SET content = ?1, kind = COALESCE(?2, kind), embedding = ?3, updated_at = ?4
+             WHERE id = ?5",
+            params![content, kind, serde_json::to_string(embedding)?, now, id],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Memory {} not found", id);
+        }
+        Ok(())
+    }
+
+    pub fn delete_memory(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM ai_memories WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn get_memory(&self, id: i64) -> Result<Option<Memory>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let memory = conn
+            .query_row(
+                "SELECT id, content, kind, source_conversation_id, embedding, created_at, updated_at, last_used_at, use_count
+                 FROM ai_memories WHERE id = ?1",
+                params![id],
+                row_to_memory,
+            )
+            .optional()?;
+        Ok(memory)
+    }
+
+    pub fn list_memories(&self) -> Result<Vec<Memory>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, content, kind, source_conversation_id, embedding, created_at, updated_at, last_used_at, use_count
+             FROM ai_memories
+             ORDER BY updated_at DESC"
+        )?;
+        let rows = stmt.query_map([], row_to_memory)?;
+
+        let mut memories = Vec::new();
+        for row in rows {
+            memories.push(row?);
+        }
+        Ok(memories)
+    }
+
+    pub fn mark_used(&self, ids: &[i64]) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        for id in ids {
+            conn.execute(
+                "UPDATE ai_memories SET last_used_at = ?1, use_count = use_count + 1 WHERE id = ?2",
+                params![now, id],
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn row_to_memory(row: &rusqlite::Row) -> rusqlite::Result<Memory> {
+    let embedding_json: String = row.get(4)?;
+    Ok(Memory {
+        id: row.get(0)?,
+        content: row.get(1)?,
+        kind: row.get(2)?,
+        source_conversation_id: row.get(3)?,
+        embedding: serde_json::from_str(&embedding_json).unwrap_or_default(),
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+        last_used_at: row.get(7)?,
+        use_count: row.get(8)?,
+    })
+}
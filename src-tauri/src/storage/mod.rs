@@ -23,18 +23,47 @@ pub mod api_keys;
 pub mod grid_layouts;
 pub mod price_alerts;
 pub mod portfolio_performance;
+pub mod clipboard;
+pub mod time_tracking;
+pub mod benchmarks;
+pub mod memories;
+pub mod llm_usage;
+pub mod profiles;
+pub mod sync;
+pub mod fx;
+pub mod scenarios;
+pub mod documents;
+pub mod transcripts;
+pub mod jobs;
+pub mod outbox;
+pub mod windows;
+pub mod palette;
+pub mod schema_integrity;
+pub mod search_index;
+pub mod dashboards;
+pub mod process_policies;
+pub mod remote_hosts;
+pub mod ssh_tunnels;
+pub mod model_benchmarks;
+pub mod watch_folders;
+pub mod statement_imports;
+pub mod db_housekeeping;
+pub mod saved_reports;
+pub mod sql;
+pub mod response_cache;
+pub mod tags;
 
 pub use database::{Database, ErrorRecord};
 pub use migrations::MigrationManager;
 pub use auth::{AuthManager, AuthAttempt};
 pub use vector_store::{VectorStore, VectorDocument, CollectionStats};
-pub use analytics::{AnalyticsStore, AnalyticsMetrics, Statistics};
+pub use analytics::{AnalyticsStore, AnalyticsMetrics, Statistics, SlowCommand};
 pub use rate_limit::{RateLimitStore, RateLimitBucket};
 pub use migration_tracking::{MigrationTracker, MigrationRecord};
-pub use ai::{AIStore, ChatMessage, Conversation, PromptTemplate};
+pub use ai::{AIStore, ChatMessage, Conversation, ConversationSummary, ArchivedConversation, PromptTemplate, TemplateVariable, TemplateExample};
 pub use automation::{AutomationStore, Script, Workflow, WorkflowExecution};
 pub use devops::{DevOpsStore, HealthCheck, Alert, PrometheusMetric};
-pub use osint::{OSINTStore, RSSFeed, RSSItem, Entity, EntityRelationship};
+pub use osint::{OSINTStore, RSSFeed, RSSItem, Entity, EntityRelationship, Ioc, NewIoc, ArticleImageText};
 pub use temporal::{
     TemporalStore,
     TemporalEvent,
@@ -51,15 +80,43 @@ pub use temporal::{
     FeatureValue,
     AlertLabel,
     AlertEscalation,
+    EventCommentary,
 };
-pub use testing::{TestingStore, TestSuite, TestResult, TestSuiteStats};
-pub use projects::{ProjectStore, Project};
+pub use testing::{TestingStore, TestSuite, TestResult, TestSuiteStats, TestRun, FlakyTest, QuarantinedTest};
+pub use projects::{ProjectStore, Project, ProjectRepoLink, ProjectTask, ProjectTaskLink, TaskBoardColumn};
+pub use tags::{TagStore, Tag, TagUsage};
 pub use stock_news::{StockNewsStore, StockTicker, StockNewsItem, StockNewsTicker};
-pub use market_data::{MarketDataStore, MarketPrice, PriceHistory, MarketSnapshot};
+pub use market_data::{MarketDataStore, MarketPrice, PriceHistory, MarketSnapshot, SymbolInfo, MarketHoliday, ShortInterest, AnalystRating, ChartAnnotation};
 pub use portfolio::{PortfolioStore, Portfolio, Holding, Transaction};
 pub use economic_calendar::{EconomicCalendarStore, EconomicEvent, EventImpactHistory};
 pub use messaging::{MessagingStore, MessagingConversation, Message, MessageAttachment};
 pub use grid_layouts::{GridLayoutStore, GridLayoutData};
 pub use price_alerts::{PriceAlertStore, PriceAlert};
 pub use portfolio_performance::{PortfolioPerformanceStore, PortfolioSnapshot};
+pub use clipboard::{ClipboardStore, ClipboardEntry};
+pub use time_tracking::{TimeTrackingStore, TimeEntry, TimeSummaryRow, AutoTrackingRule};
+pub use benchmarks::{BenchmarkStore, BenchmarkResult, BenchmarkRegression, BenchmarkSeries, NewBenchmarkResult};
+pub use memories::{MemoryStore, Memory};
+pub use llm_usage::{LlmUsageStore, LlmUsageRow, LlmBudget};
+pub use profiles::{ProfileStore, Profile};
+pub use sync::{SyncStore, SyncChanges};
+pub use fx::FxStore;
+pub use scenarios::{ScenarioStore, StressScenario, ScenarioShock, ScenarioRun};
+pub use documents::{DocumentStore, Document, DocumentEntity, NewDocument};
+pub use transcripts::{TranscriptStore, Transcript, TranscriptSegment, NewTranscript, PodcastEpisode};
+pub use jobs::{JobStore, Job};
+pub use outbox::{OutboxStore, OutboxMessage};
+pub use windows::{WindowStore, AppWindow};
+pub use palette::{PaletteStore, RecentTarget};
+pub use dashboards::{DashboardStore, DashboardLayout, DashboardWidget};
+pub use process_policies::{ProcessPolicyStore, ProcessPolicy, ProcessPolicyAction};
+pub use remote_hosts::{RemoteHostStore, RemoteHost, RemoteHostAction};
+pub use ssh_tunnels::{SshTunnelStore, SshTunnel};
+pub use model_benchmarks::{ModelBenchmarkStore, ModelBenchmarkResult, ModelBenchmarkSummary};
+pub use watch_folders::{WatchFolderStore, WatchFolder, WatchFolderFile};
+pub use statement_imports::{StatementImportStore, StatementMapping, MappingProfile, StatementImport, StatementTransaction};
+pub use db_housekeeping::DbHousekeepingStore;
+pub use saved_reports::{SavedReportStore, SavedReport, NewSavedReport, ReportSpec};
+pub use response_cache::{ResponseCacheStore, CachedResponse};
+pub use schema_integrity::{SchemaIntegrity, SchemaReport, SchemaDrift};
 
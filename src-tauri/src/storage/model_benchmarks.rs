@@ -0,0 +1,190 @@
+use anyhow::Result;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// One benchmark prompt run against one model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelBenchmarkResult {
+    pub id: i64,
+    /// Groups the results of one benchmark run
+    pub run_id: String,
+    pub model: String,
+    pub prompt_id: String,
+    pub task: String, // summarization|extraction|general
+    pub tokens_per_sec: Option<f64>,
+    /// Time to the full response, excluding model load
+    pub latency_ms: i64,
+    pub load_ms: i64,
+    pub completion_tokens: i64,
+    /// Memory the loaded model took, when Ollama reported it
+    pub memory_bytes: Option<i64>,
+    /// Whether the response passed the prompt's quality check
+    pub passed: bool,
+    pub error: Option<String>,
+    pub created_at: i64,
+}
+
+/// A model's results for one task in its latest benchmark run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelBenchmarkSummary {
+    pub model: String,
+    pub task: String,
+    pub run_id: String,
+    pub prompts: i64,
+    /// Share of prompts whose response passed its quality check
+    pub quality: f64,
+    pub avg_tokens_per_sec: Option<f64>,
+    pub avg_latency_ms: f64,
+    pub memory_bytes: Option<i64>,
+    pub benchmarked_at: i64,
+}
+
+pub struct ModelBenchmarkStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ModelBenchmarkStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = ModelBenchmarkStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: ModelBenchmarkStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS model_benchmark_results (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_id TEXT NOT NULL,
+                model TEXT NOT NULL,
+                prompt_id TEXT NOT NULL,
+                task TEXT NOT NULL,
+                tokens_per_sec REAL,
+                latency_ms INTEGER NOT NULL DEFAULT 0,
+                load_ms INTEGER NOT NULL DEFAULT 0,
+                completion_tokens INTEGER NOT NULL DEFAULT 0,
+                memory_bytes INTEGER,
+                passed INTEGER NOT NULL DEFAULT 0,
+                error TEXT,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_model_benchmark_results_model ON model_benchmark_results(model, created_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn record_result(&self, result: &ModelBenchmarkResult) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO model_benchmark_results
+                (run_id, model, prompt_id, task, tokens_per_sec, latency_ms, load_ms, completion_tokens,
+                 memory_bytes, passed, error, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                result.run_id,
+                result.model,
+                result.prompt_id,
+                result.task,
+                result.tokens_per_sec,
+                result.latency_ms,
+                result.load_ms,
+                result.completion_tokens,
+                result.memory_bytes,
+                result.passed as i32,
+                result.error,
+                result.created_at,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Newest first, optionally for one model.
+    pub fn list_results(&self, model: Option<&str>, limit: i64) -> Result<Vec<ModelBenchmarkResult>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, run_id, model, prompt_id, task, tokens_per_sec, latency_ms, load_ms, completion_tokens,
+                    memory_bytes, passed, error, created_at
+             FROM model_benchmark_results
+             WHERE ?1 IS NULL OR model = ?1
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![model, limit], |row| {
+            Ok(ModelBenchmarkResult {
+                id: row.get(0)?,
+                run_id: row.get(1)?,
+                model: row.get(2)?,
+                prompt_id: row.get(3)?,
+                task: row.get(4)?,
+                tokens_per_sec: row.get(5)?,
+                latency_ms: row.get(6)?,
+                load_ms: row.get(7)?,
+                completion_tokens: row.get(8)?,
+                memory_bytes: row.get(9)?,
+                passed: row.get::<_, i32>(10)? == 1,
+                error: row.get(11)?,
+                created_at: row.get(12)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Per-task results of each model's latest run, optionally for one task.
+    /// Failed requests count against quality but not towards speed.
+    pub fn list_summaries(&self, task: Option<&str>) -> Result<Vec<ModelBenchmarkSummary>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT r.model, r.task, r.run_id, COUNT(*), AVG(r.passed),
+                    AVG(r.tokens_per_sec),
+                    AVG(CASE WHEN r.error IS NULL THEN r.latency_ms END),
+                    MAX(r.memory_bytes), MAX(r.created_at)
+             FROM model_benchmark_results r
+             WHERE (?1 IS NULL OR r.task = ?1)
+               AND r.run_id = (
+                   SELECT run_id FROM model_benchmark_results
+                   WHERE model = r.model
+                   ORDER BY created_at DESC, id DESC LIMIT 1
+               )
+             GROUP BY r.model, r.task, r.run_id
+             ORDER BY r.model, r.task",
+        )?;
+        let rows = stmt.query_map(params![task], |row| {
+            Ok(ModelBenchmarkSummary {
+                model: row.get(0)?,
+                task: row.get(1)?,
+                run_id: row.get(2)?,
+                prompts: row.get(3)?,
+                quality: row.get(4)?,
+                avg_tokens_per_sec: row.get(5)?,
+                avg_latency_ms: row.get::<_, Option<f64>>(6)?.unwrap_or(0.0),
+                memory_bytes: row.get(7)?,
+                benchmarked_at: row.get(8)?,
+            })
+        })?;
+
+        let mut summaries = Vec::new();
+        for row in rows {
+            summaries.push(row?);
+        }
+        Ok(summaries)
+    }
+}
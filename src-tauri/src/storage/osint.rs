@@ -1,6 +1,10 @@
+use crate::services::change_feed::{self, ChangeFeed, ChangeOp};
+use crate::storage::profiles::{active_profile_id, active_profile_shares_articles};
+use crate::storage::search_index::detect_language;
 use anyhow::Result;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +16,9 @@ pub struct RSSFeed {
     pub reliability: f64,
     pub last_fetch: Option<i64>,
     pub created_at: i64,
+    /// Language the feed publishes in (ISO 639-1); its articles are tagged
+    /// with it instead of a detected one
+    pub language: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +34,36 @@ pub struct RSSItem {
     pub favorite: bool,
     pub saved: bool,
     pub folder_id: Option<i64>,
+    /// The feed's language, else detected from the text; None when unknown
+    pub language: Option<String>,
+}
+
+/// Read articles not marked favorite or saved, published before ?1, that no
+/// event cites as evidence and no task or transcript links to.
+const CLEANUP_CANDIDATES: &str = "SELECT i.id FROM rss_items i
+     WHERE i.read = 1 AND i.favorite = 0 AND i.saved = 0 AND i.published_at < ?1
+       AND NOT EXISTS (SELECT 1 FROM temporal_event_evidence e WHERE e.rss_item_id = i.id)
+       AND NOT EXISTS (SELECT 1 FROM project_task_links l WHERE l.target_type = 'article' AND l.target_id = i.id)
+       AND NOT EXISTS (SELECT 1 FROM transcripts t WHERE t.article_id = i.id)";
+
+/// Rows keyed by article that go with it when it is cleaned up
+const ARTICLE_DEPENDENTS: &[&str] = &[
+    "DELETE FROM extracted_entities WHERE article_id = ?1",
+    "DELETE FROM ioc_articles WHERE article_id = ?1",
+    "DELETE FROM article_ocr WHERE article_id = ?1",
+    "DELETE FROM article_image_text WHERE article_id = ?1",
+    "DELETE FROM podcast_episodes WHERE article_id = ?1",
+    "DELETE FROM tag_attachments WHERE object_type = 'article' AND object_id = ?1",
+    "DELETE FROM fts_documents WHERE doc_type = 'rss_item' AND doc_id = ?1",
+    "DELETE FROM vector_documents WHERE id = 'article:' || ?1",
+];
+
+/// What a cleanup of read articles would remove.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleCleanupPreview {
+    pub count: i64,
+    pub oldest_published_at: Option<i64>,
+    pub newest_published_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,6 +104,87 @@ pub struct EntityRelationship {
     pub created_at: i64,
 }
 
+/// Articles mentioning an entity on one UTC day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityDayCount {
+    pub entity: String,
+    pub day_start: i64,
+    pub count: i64,
+}
+
+/// An article mentioning an entity, with the feed it came from.
+#[derive(Debug, Clone)]
+pub struct EntitySourceArticle {
+    pub entity: String,
+    pub article_id: i64,
+    pub feed_id: i64,
+    pub source: String,
+    pub title: String,
+    pub content: String,
+    pub published_at: i64,
+}
+
+/// An indicator of compromise. `ioc_type` is one of ipv4, ipv6, domain,
+/// url, email, md5, sha1, sha256.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ioc {
+    pub id: i64,
+    pub ioc_type: String,
+    pub value: String,
+    pub description: Option<String>,
+    pub source: String,
+    pub tags: Vec<String>,
+    pub confidence: f64,
+    pub first_seen: i64,
+    pub last_seen: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewIoc {
+    pub ioc_type: String,
+    pub value: String,
+    pub description: Option<String>,
+    pub source: String,
+    pub tags: Vec<String>,
+    pub confidence: f64,
+}
+
+/// Text recognized in an image embedded in a saved article.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArticleImageText {
+    pub article_id: i64,
+    pub image_url: String,
+    pub text: String,
+    pub engine: String, // "tesseract" or the vision model name
+    pub processed_at: i64,
+}
+
+/// Mentions of one entity, names compared case-insensitively.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntitySummary {
+    pub name: String,
+    pub entity_type: String,
+    pub mentions: i64,
+    pub first_seen: i64,
+    pub last_seen: i64,
+}
+
+/// Fetch outcomes and stored-item counts of one feed over a window, the raw
+/// input of the data quality report.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeedQualityCounts {
+    pub feed_id: i64,
+    pub fetches: i64,
+    pub fetch_failures: i64,
+    pub parse_failures: i64,
+    pub last_error: Option<String>,
+    pub items: i64,
+    pub empty_items: i64,
+    /// Items whose title an earlier item in the window already had
+    pub duplicate_items: i64,
+    pub last_item_at: Option<i64>,
+}
+
 pub struct OSINTStore {
     pub conn: Arc<Mutex<Connection>>,
 }
@@ -107,6 +225,13 @@ impl OSINTStore {
             [],
         ));
 
+        // Migration: feeds record the profile that added them
+        let _ = conn.execute("ALTER TABLE rss_feeds ADD COLUMN profile_id INTEGER NOT NULL DEFAULT 1", []);
+        // Migration: modification time used by sync conflict resolution
+        let _ = conn.execute("ALTER TABLE rss_feeds ADD COLUMN updated_at INTEGER", []);
+        // Migration: expected language for language filtering
+        let _ = conn.execute("ALTER TABLE rss_feeds ADD COLUMN language TEXT", []);
+
         conn.execute(
             "CREATE TABLE IF NOT EXISTS rss_items (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -131,6 +256,8 @@ impl OSINTStore {
         let _ = conn.execute("ALTER TABLE rss_items ADD COLUMN favorite INTEGER NOT NULL DEFAULT 0", []);
         let _ = conn.execute("ALTER TABLE rss_items ADD COLUMN saved INTEGER NOT NULL DEFAULT 0", []);
         let _ = conn.execute("ALTER TABLE rss_items ADD COLUMN folder_id INTEGER", []);
+        let _ = conn.execute("ALTER TABLE rss_items ADD COLUMN state_updated_at INTEGER", []);
+        let _ = conn.execute("ALTER TABLE rss_items ADD COLUMN language TEXT", []);
 
         conn.execute(
             "CREATE TABLE IF NOT EXISTS article_folders (
@@ -142,6 +269,20 @@ impl OSINTStore {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS rss_item_archive (
+                id INTEGER PRIMARY KEY,
+                feed_id INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                url TEXT NOT NULL,
+                published_at INTEGER NOT NULL,
+                fetched_at INTEGER NOT NULL,
+                archived_at INTEGER NOT NULL,
+                content_gz BLOB NOT NULL
+            )",
+            [],
+        )?;
+
         conn.execute(
             "CREATE TABLE IF NOT EXISTS extracted_entities (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -222,6 +363,88 @@ impl OSINTStore {
             [],
         )?;
 
+        // Indicators of compromise, e.g. from STIX bundles or MISP feeds
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS iocs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                ioc_type TEXT NOT NULL,
+                value TEXT NOT NULL,
+                description TEXT,
+                source TEXT NOT NULL,
+                tags TEXT NOT NULL DEFAULT '[]',
+                confidence REAL NOT NULL DEFAULT 0.5,
+                first_seen INTEGER NOT NULL,
+                last_seen INTEGER NOT NULL,
+                UNIQUE(ioc_type, value)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS ioc_articles (
+                ioc_id INTEGER NOT NULL,
+                article_id INTEGER NOT NULL,
+                PRIMARY KEY (ioc_id, article_id),
+                FOREIGN KEY (ioc_id) REFERENCES iocs(id) ON DELETE CASCADE,
+                FOREIGN KEY (article_id) REFERENCES rss_items(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // One row per article whose images went through OCR, so articles
+        // whose images yielded nothing aren't retried every run
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS article_ocr (
+                article_id INTEGER PRIMARY KEY,
+                image_count INTEGER NOT NULL,
+                processed_at INTEGER NOT NULL,
+                FOREIGN KEY (article_id) REFERENCES rss_items(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS article_image_text (
+                article_id INTEGER NOT NULL,
+                image_url TEXT NOT NULL,
+                text TEXT NOT NULL,
+                engine TEXT NOT NULL,
+                processed_at INTEGER NOT NULL,
+                PRIMARY KEY (article_id, image_url),
+                FOREIGN KEY (article_id) REFERENCES rss_items(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // One row per feed per fetch run: ok|http_error|fetch_error|parse_error
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS feed_fetch_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                feed_id INTEGER NOT NULL,
+                fetched_at INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                items INTEGER NOT NULL DEFAULT 0,
+                error TEXT,
+                FOREIGN KEY (feed_id) REFERENCES rss_feeds(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_feed_fetch_log_feed_ts ON feed_fetch_log(feed_id, fetched_at)",
+            [],
+        )?;
+
+        // Issues last notified per feed, so quality alerts fire on changes only
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS feed_quality_flags (
+                feed_id INTEGER PRIMARY KEY,
+                issues TEXT NOT NULL,
+                flagged_at INTEGER NOT NULL,
+                FOREIGN KEY (feed_id) REFERENCES rss_feeds(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
         // Don't initialize default feeds here - do it lazily on first access
         // This prevents hanging during app startup
         // Default feeds will be added when the first feed list is requested
@@ -277,11 +500,12 @@ impl OSINTStore {
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let now = chrono::Utc::now().timestamp();
         let rel = reliability.unwrap_or(0.5);
+        let profile_id = active_profile_id(&conn)?;
 
         conn.execute(
-            "INSERT OR IGNORE INTO rss_feeds (url, name, enabled, reliability, created_at)
-             VALUES (?1, ?2, 1, ?3, ?4)",
-            params![url, name, rel, now],
+            "INSERT OR IGNORE INTO rss_feeds (url, name, enabled, reliability, created_at, profile_id, updated_at)
+             VALUES (?1, ?2, 1, ?3, ?4, ?5, ?4)",
+            params![url, name, rel, now, profile_id],
         )?;
 
         // Get the ID
@@ -310,10 +534,67 @@ impl OSINTStore {
         if let Some(e) = enabled {
             conn.execute("UPDATE rss_feeds SET enabled = ?1 WHERE id = ?2", params![if e { 1i64 } else { 0i64 }, id])?;
         }
+        conn.execute(
+            "UPDATE rss_feeds SET updated_at = ?1 WHERE id = ?2",
+            params![chrono::Utc::now().timestamp(), id],
+        )?;
 
         Ok(())
     }
 
+    /// Set or clear a feed's expected language. Setting it retags the feed's
+    /// existing articles; clearing it queues them for `detect_item_languages`.
+    pub fn set_feed_language(&self, id: i64, language: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        let updated = conn.execute(
+            "UPDATE rss_feeds SET language = ?1, updated_at = ?2 WHERE id = ?3",
+            params![language, now, id],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Feed {} not found", id);
+        }
+        match language {
+            Some(language) => {
+                conn.execute("UPDATE rss_items SET language = ?1 WHERE feed_id = ?2", params![language, id])?;
+            }
+            None => {
+                conn.execute("UPDATE rss_items SET language = NULL WHERE feed_id = ?1", params![id])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Detect the language of up to `limit` articles that have none, e.g.
+    /// from before languages were tracked. Returns how many were looked at;
+    /// articles too short to tell are marked "" so they aren't retried.
+    pub fn detect_item_languages(&self, limit: i64) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT i.id, i.title, i.content, f.language
+             FROM rss_items i
+             JOIN rss_feeds f ON f.id = i.feed_id
+             WHERE i.language IS NULL
+             LIMIT ?1"
+        )?;
+        let items = stmt
+            .query_map(params![limit], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, Option<String>>(3)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        for (id, title, content, feed_language) in &items {
+            let language = feed_language
+                .clone()
+                .or_else(|| detect_language(&format!("{} {}", title, content)).map(str::to_string))
+                .unwrap_or_default();
+            conn.execute("UPDATE rss_items SET language = ?1 WHERE id = ?2", params![language, id])?;
+        }
+        Ok(items.len())
+    }
+
     pub fn update_feed_last_fetch(&self, id: i64) -> Result<()> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -335,11 +616,15 @@ impl OSINTStore {
         
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        // Profiles that opted out of the shared corpus only see their own feeds
+        let (profile_id, shared) = active_profile_shares_articles(&conn)?;
         let mut stmt = conn.prepare(
-            "SELECT id, url, name, enabled, reliability, last_fetch, created_at FROM rss_feeds ORDER BY reliability DESC, name"
+            "SELECT id, url, name, enabled, reliability, last_fetch, created_at, language FROM rss_feeds
+             WHERE ?1 = 1 OR profile_id = ?2
+             ORDER BY reliability DESC, name"
         )?;
 
-        let rows = stmt.query_map([], |row| {
+        let rows = stmt.query_map(params![if shared { 1 } else { 0 }, profile_id], |row| {
             Ok(RSSFeed {
                 id: row.get(0)?,
                 url: row.get(1)?,
@@ -348,6 +633,7 @@ impl OSINTStore {
                 reliability: row.get(4)?,
                 last_fetch: row.get(5)?,
                 created_at: row.get(6)?,
+                language: row.get(7)?,
             })
         })?;
 
@@ -369,12 +655,18 @@ impl OSINTStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let fetched_at = chrono::Utc::now().timestamp();
+        let feed_language: Option<String> = conn
+            .query_row("SELECT language FROM rss_feeds WHERE id = ?1", params![feed_id], |row| row.get(0))
+            .optional()?
+            .flatten();
+        let language = feed_language.or_else(|| detect_language(&format!("{} {}", title, content)).map(str::to_string));
 
         conn.execute(
-            "INSERT OR IGNORE INTO rss_items (feed_id, title, content, url, published_at, fetched_at, read, favorite, saved)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, 0, 0)",
-            params![feed_id, title, content, url, published_at, fetched_at],
+            "INSERT OR IGNORE INTO rss_items (feed_id, title, content, url, published_at, fetched_at, read, favorite, saved, language)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, 0, 0, ?7)",
+            params![feed_id, title, content, url, published_at, fetched_at, language],
         )?;
+        let inserted = conn.changes() > 0;
 
         // Get the ID
         let id: i64 = conn.query_row(
@@ -383,24 +675,28 @@ impl OSINTStore {
             |row| row.get(0),
         )?;
 
+        if inserted {
+            ChangeFeed::ids(change_feed::ARTICLES, ChangeOp::Insert, [id]);
+        }
         Ok(id)
     }
 
     pub fn get_recent_items(&self, limit: i32) -> Result<Vec<RSSItem>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let (profile_id, shared) = active_profile_shares_articles(&conn)?;
         // Get items ordered by feed reliability and recency
         let mut stmt = conn.prepare(
             "SELECT i.id, i.feed_id, i.title, i.content, i.url, i.published_at, i.fetched_at, 
-                    i.read, i.favorite, i.saved, i.folder_id
+                    i.read, i.favorite, i.saved, i.folder_id, i.language
              FROM rss_items i
              JOIN rss_feeds f ON i.feed_id = f.id
-             WHERE f.enabled = 1
+             WHERE f.enabled = 1 AND (?2 = 1 OR f.profile_id = ?3)
              ORDER BY f.reliability DESC, i.published_at DESC
              LIMIT ?1"
         )?;
 
-        let rows = stmt.query_map(params![limit], |row| {
+        let rows = stmt.query_map(params![limit, if shared { 1 } else { 0 }, profile_id], |row| {
             Ok(RSSItem {
                 id: row.get(0)?,
                 feed_id: row.get(1)?,
@@ -413,6 +709,7 @@ impl OSINTStore {
                 favorite: row.get::<_, i64>(8)? == 1,
                 saved: row.get::<_, i64>(9)? == 1,
                 folder_id: row.get(10)?,
+                language: row.get::<_, Option<String>>(11)?.filter(|l| !l.is_empty()),
             })
         })?;
 
@@ -427,7 +724,7 @@ impl OSINTStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let mut stmt = conn.prepare(
-            "SELECT id, feed_id, title, content, url, published_at, fetched_at, read, favorite, saved, folder_id
+            "SELECT id, feed_id, title, content, url, published_at, fetched_at, read, favorite, saved, folder_id, language
              FROM rss_items WHERE id = ?1"
         )?;
 
@@ -444,6 +741,7 @@ impl OSINTStore {
                 favorite: row.get::<_, i64>(8)? == 1,
                 saved: row.get::<_, i64>(9)? == 1,
                 folder_id: row.get(10)?,
+                language: row.get::<_, Option<String>>(11)?.filter(|l| !l.is_empty()),
             })
         }) {
             Ok(item) => Ok(Some(item)),
@@ -456,9 +754,10 @@ impl OSINTStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         conn.execute(
-            "UPDATE rss_items SET read = ?1 WHERE id = ?2",
-            params![if read { 1i64 } else { 0i64 }, id],
+            "UPDATE rss_items SET read = ?1, state_updated_at = ?2 WHERE id = ?3",
+            params![if read { 1i64 } else { 0i64 }, chrono::Utc::now().timestamp(), id],
         )?;
+        ChangeFeed::ids(change_feed::ARTICLES, ChangeOp::Update, [id]);
         Ok(())
     }
 
@@ -472,9 +771,10 @@ impl OSINTStore {
         )?;
         let new_value = if current == 1 { 0 } else { 1 };
         conn.execute(
-            "UPDATE rss_items SET favorite = ?1 WHERE id = ?2",
-            params![new_value, id],
+            "UPDATE rss_items SET favorite = ?1, state_updated_at = ?2 WHERE id = ?3",
+            params![new_value, chrono::Utc::now().timestamp(), id],
         )?;
+        ChangeFeed::ids(change_feed::ARTICLES, ChangeOp::Update, [id]);
         Ok(new_value == 1)
     }
 
@@ -488,9 +788,10 @@ impl OSINTStore {
         )?;
         let new_value = if current == 1 { 0 } else { 1 };
         conn.execute(
-            "UPDATE rss_items SET saved = ?1 WHERE id = ?2",
-            params![new_value, id],
+            "UPDATE rss_items SET saved = ?1, state_updated_at = ?2 WHERE id = ?3",
+            params![new_value, chrono::Utc::now().timestamp(), id],
         )?;
+        ChangeFeed::ids(change_feed::ARTICLES, ChangeOp::Update, [id]);
         Ok(new_value == 1)
     }
 
@@ -549,18 +850,82 @@ impl OSINTStore {
         Ok(())
     }
 
+    /// How many articles `cleanup_read_articles` would remove for this cutoff.
+    pub fn preview_article_cleanup(&self, published_before: i64) -> Result<ArticleCleanupPreview> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let preview = conn.query_row(
+            &format!(
+                "SELECT COUNT(*), MIN(published_at), MAX(published_at) FROM rss_items WHERE id IN ({})",
+                CLEANUP_CANDIDATES
+            ),
+            params![published_before],
+            |row| {
+                Ok(ArticleCleanupPreview {
+                    count: row.get(0)?,
+                    oldest_published_at: row.get(1)?,
+                    newest_published_at: row.get(2)?,
+                })
+            },
+        )?;
+        Ok(preview)
+    }
+
+    /// Remove read, non-favorite articles published before the cutoff in one
+    /// transaction, keeping a compressed copy in rss_item_archive unless
+    /// `delete` is set. Returns the number removed.
+    pub fn cleanup_read_articles(&self, published_before: i64, delete: bool) -> Result<usize> {
+        use std::io::Write;
+
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        let ids: Vec<i64> = {
+            let mut stmt = tx.prepare(CLEANUP_CANDIDATES)?;
+            let rows = stmt.query_map(params![published_before], |row| row.get(0))?;
+            rows.collect::<Result<Vec<i64>, _>>()?
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        for id in &ids {
+            if !delete {
+                let (feed_id, title, content, url, published_at, fetched_at): (i64, String, String, String, i64, i64) = tx.query_row(
+                    "SELECT feed_id, title, content, url, published_at, fetched_at FROM rss_items WHERE id = ?1",
+                    params![id],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?)),
+                )?;
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(content.as_bytes())?;
+                tx.execute(
+                    "INSERT OR REPLACE INTO rss_item_archive (id, feed_id, title, url, published_at, fetched_at, archived_at, content_gz)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![id, feed_id, title, url, published_at, fetched_at, now, encoder.finish()?],
+                )?;
+            }
+            for statement in ARTICLE_DEPENDENTS {
+                tx.execute(statement, params![id])?;
+            }
+            tx.execute("DELETE FROM rss_items WHERE id = ?1", params![id])?;
+        }
+        tx.commit()?;
+
+        ChangeFeed::ids(change_feed::ARTICLES, ChangeOp::Delete, ids.iter().copied());
+        Ok(ids.len())
+    }
+
     pub fn get_items_by_filter(
         &self,
         favorite: Option<bool>,
         saved: Option<bool>,
         read: Option<bool>,
         folder_id: Option<i64>,
+        tag: Option<&str>,
         limit: i32,
     ) -> Result<Vec<RSSItem>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let mut query = "SELECT i.id, i.feed_id, i.title, i.content, i.url, i.published_at, i.fetched_at, 
-                                i.read, i.favorite, i.saved, i.folder_id
+                                i.read, i.favorite, i.saved, i.folder_id, i.language
                          FROM rss_items i
                          JOIN rss_feeds f ON i.feed_id = f.id
                          WHERE f.enabled = 1".to_string();
@@ -582,6 +947,13 @@ impl OSINTStore {
             query.push_str(" AND i.folder_id = ?");
             params_vec.push(Box::new(fid));
         }
+        if let Some(t) = tag {
+            query.push_str(
+                " AND i.id IN (SELECT ta.object_id FROM tag_attachments ta JOIN tags t ON t.id = ta.tag_id
+                               WHERE ta.object_type = 'article' AND t.name = ? COLLATE NOCASE)",
+            );
+            params_vec.push(Box::new(t.to_string()));
+        }
 
         query.push_str(" ORDER BY f.reliability DESC, i.published_at DESC LIMIT ?");
         params_vec.push(Box::new(limit));
@@ -602,6 +974,7 @@ impl OSINTStore {
                 favorite: row.get::<_, i64>(8)? == 1,
                 saved: row.get::<_, i64>(9)? == 1,
                 folder_id: row.get(10)?,
+                language: row.get::<_, Option<String>>(11)?.filter(|l| !l.is_empty()),
             })
         })?;
 
@@ -637,6 +1010,64 @@ impl OSINTStore {
         Ok(id)
     }
 
+    /// Replace everything extracted from an article with `entities`
+    /// (type, name, confidence, context) in one transaction, so readers
+    /// never see the article without entities. Returns (removed, inserted).
+    pub fn replace_extracted_entities(
+        &self,
+        article_id: i64,
+        entities: &[(String, String, f64, String)],
+    ) -> Result<(usize, usize)> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        let tx = conn.transaction()?;
+        let removed = tx.execute("DELETE FROM extracted_entities WHERE article_id = ?1", params![article_id])?;
+        let mut inserted = 0;
+        for (entity_type, name, confidence, context) in entities {
+            inserted += tx.execute(
+                "INSERT OR IGNORE INTO extracted_entities (article_id, entity_type, name, confidence, context, extracted_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![article_id, entity_type, name, confidence, context, now],
+            )?;
+        }
+        tx.commit()?;
+        Ok((removed, inserted))
+    }
+
+    /// Articles published in [from_ts, to_ts) with an id above `after_id`,
+    /// as (id, title, content) in id order, for walking a range in pages.
+    pub fn articles_after(&self, from_ts: i64, to_ts: i64, after_id: i64, limit: i64) -> Result<Vec<(i64, String, String)>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, title, content FROM rss_items
+             WHERE published_at >= ?1 AND published_at < ?2 AND id > ?3
+             ORDER BY id ASC
+             LIMIT ?4",
+        )?;
+        let rows = stmt.query_map(params![from_ts, to_ts, after_id, limit], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?;
+
+        let mut articles = Vec::new();
+        for row in rows {
+            articles.push(row?);
+        }
+        Ok(articles)
+    }
+
+    pub fn count_articles_between(&self, from_ts: i64, to_ts: i64) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Ok(conn.query_row(
+            "SELECT COUNT(*) FROM rss_items WHERE published_at >= ?1 AND published_at < ?2",
+            params![from_ts, to_ts],
+            |row| row.get(0),
+        )?)
+    }
+
     pub fn get_entities_for_article(&self, article_id: i64) -> Result<Vec<ExtractedEntity>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -666,6 +1097,147 @@ impl OSINTStore {
         Ok(entities)
     }
 
+    /// Other articles mentioning entities of this one, with the shared entity
+    /// names, most shared first.
+    pub fn articles_sharing_entities(&self, article_id: i64, limit: i64) -> Result<Vec<(i64, Vec<String>)>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "WITH shared AS (
+                SELECT DISTINCT o.article_id, s.name
+                FROM extracted_entities s
+                JOIN extracted_entities o ON o.name = s.name COLLATE NOCASE AND o.article_id != s.article_id
+                WHERE s.article_id = ?1
+             ),
+             top AS (
+                SELECT article_id FROM shared GROUP BY article_id ORDER BY COUNT(*) DESC, article_id DESC LIMIT ?2
+             )
+             SELECT shared.article_id, shared.name FROM shared JOIN top ON top.article_id = shared.article_id
+             ORDER BY shared.article_id, shared.name",
+        )?;
+        let rows = stmt.query_map(params![article_id, limit], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+
+        let mut out: Vec<(i64, Vec<String>)> = Vec::new();
+        for row in rows {
+            let (id, name) = row?;
+            match out.last_mut() {
+                Some((last, names)) if *last == id => names.push(name),
+                _ => out.push((id, vec![name])),
+            }
+        }
+        Ok(out)
+    }
+
+    /// Distinct articles per entity and UTC day published since `from_ts`,
+    /// optionally for one entity (case-insensitive).
+    pub fn daily_entity_counts(&self, from_ts: i64, entity: Option<&str>) -> Result<Vec<EntityDayCount>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT MIN(ee.name), (ri.published_at / 86400) * 86400 AS day_start, COUNT(DISTINCT ri.id)
+             FROM extracted_entities ee
+             JOIN rss_items ri ON ri.id = ee.article_id
+             WHERE ri.published_at >= ?1 AND (?2 IS NULL OR lower(ee.name) = lower(?2))
+             GROUP BY lower(ee.name), day_start
+             ORDER BY day_start ASC",
+        )?;
+        let rows = stmt.query_map(params![from_ts, entity], |row| {
+            Ok(EntityDayCount {
+                entity: row.get(0)?,
+                day_start: row.get(1)?,
+                count: row.get(2)?,
+            })
+        })?;
+
+        let mut counts = Vec::new();
+        for row in rows {
+            counts.push(row?);
+        }
+        Ok(counts)
+    }
+
+    /// Articles mentioning each entity, and when, for articles published since `from_ts`.
+    pub fn entity_summaries(&self, from_ts: i64) -> Result<Vec<EntitySummary>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT MIN(ee.name), MIN(ee.entity_type), COUNT(DISTINCT ri.id), MIN(ri.published_at), MAX(ri.published_at)
+             FROM extracted_entities ee
+             JOIN rss_items ri ON ri.id = ee.article_id
+             WHERE ri.published_at >= ?1
+             GROUP BY lower(ee.name)
+             ORDER BY COUNT(DISTINCT ri.id) DESC",
+        )?;
+        let rows = stmt.query_map(params![from_ts], |row| {
+            Ok(EntitySummary {
+                name: row.get(0)?,
+                entity_type: row.get(1)?,
+                mentions: row.get(2)?,
+                first_seen: row.get(3)?,
+                last_seen: row.get(4)?,
+            })
+        })?;
+
+        let mut summaries = Vec::new();
+        for row in rows {
+            summaries.push(row?);
+        }
+        Ok(summaries)
+    }
+
+    /// Ids of articles mentioning an entity published in [from_ts, to_ts).
+    pub fn article_ids_for_entity(&self, entity: &str, from_ts: i64, to_ts: i64) -> Result<Vec<i64>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT ri.id
+             FROM extracted_entities ee
+             JOIN rss_items ri ON ri.id = ee.article_id
+             WHERE lower(ee.name) = lower(?1) AND ri.published_at >= ?2 AND ri.published_at < ?3
+             ORDER BY ri.id ASC",
+        )?;
+        let rows = stmt.query_map(params![entity, from_ts, to_ts], |row| row.get(0))?;
+
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(row?);
+        }
+        Ok(ids)
+    }
+
+    /// Articles published in [from_ts, to_ts) with each entity they mention,
+    /// optionally only for one entity. Entity names keep their first spelling.
+    pub fn entity_source_articles(&self, from_ts: i64, to_ts: i64, entity: Option<&str>) -> Result<Vec<EntitySourceArticle>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT MIN(ee.name), ri.id, f.id, f.name, ri.title, ri.content, ri.published_at
+             FROM extracted_entities ee
+             JOIN rss_items ri ON ri.id = ee.article_id
+             JOIN rss_feeds f ON f.id = ri.feed_id
+             WHERE ri.published_at >= ?1 AND ri.published_at < ?2 AND (?3 IS NULL OR lower(ee.name) = lower(?3))
+             GROUP BY lower(ee.name), ri.id
+             ORDER BY ri.published_at ASC",
+        )?;
+        let rows = stmt.query_map(params![from_ts, to_ts, entity], |row| {
+            Ok(EntitySourceArticle {
+                entity: row.get(0)?,
+                article_id: row.get(1)?,
+                feed_id: row.get(2)?,
+                source: row.get(3)?,
+                title: row.get(4)?,
+                content: row.get(5)?,
+                published_at: row.get(6)?,
+            })
+        })?;
+
+        let mut articles = Vec::new();
+        for row in rows {
+            articles.push(row?);
+        }
+        Ok(articles)
+    }
+
     pub fn create_entity(&self, entity_type: &str, name: &str, metadata: &str) -> Result<i64> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -740,4 +1312,390 @@ impl OSINTStore {
 
         Ok(conn.last_insert_rowid())
     }
+
+    /// Entity with this type and name (case-insensitive), if any.
+    pub fn find_entity(&self, entity_type: &str, name: &str) -> Result<Option<i64>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id FROM entities WHERE entity_type = ?1 AND lower(name) = lower(?2) ORDER BY id LIMIT 1",
+            params![entity_type, name],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn list_relationships(&self) -> Result<Vec<EntityRelationship>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, source_id, target_id, relationship_type, strength, created_at
+             FROM entity_relationships ORDER BY id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(EntityRelationship {
+                id: row.get(0)?,
+                source_id: row.get(1)?,
+                target_id: row.get(2)?,
+                relationship_type: row.get(3)?,
+                strength: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Insert an IOC or, when it's known, refresh its last_seen and merge
+    /// tags. Returns its id.
+    pub fn upsert_ioc(&self, ioc: &NewIoc) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        let existing: Option<(i64, String)> = conn
+            .query_row(
+                "SELECT id, tags FROM iocs WHERE ioc_type = ?1 AND value = ?2",
+                params![ioc.ioc_type, ioc.value],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        match existing {
+            Some((id, tags_json)) => {
+                let mut tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
+                for tag in &ioc.tags {
+                    if !tags.contains(tag) {
+                        tags.push(tag.clone());
+                    }
+                }
+                conn.execute(
+                    "UPDATE iocs SET last_seen = ?1, tags = ?2, description = COALESCE(?3, description)
+                     WHERE id = ?4",
+                    params![now, serde_json::to_string(&tags)?, ioc.description, id],
+                )?;
+                Ok(id)
+            }
+            None => {
+                conn.execute(
+                    "INSERT INTO iocs (ioc_type, value, description, source, tags, confidence, first_seen, last_seen)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+                    params![
+                        ioc.ioc_type,
+                        ioc.value,
+                        ioc.description,
+                        ioc.source,
+                        serde_json::to_string(&ioc.tags)?,
+                        ioc.confidence,
+                        now
+                    ],
+                )?;
+                Ok(conn.last_insert_rowid())
+            }
+        }
+    }
+
+    pub fn list_iocs(&self, ioc_type: Option<&str>, limit: i64) -> Result<Vec<Ioc>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, ioc_type, value, description, source, tags, confidence, first_seen, last_seen
+             FROM iocs
+             WHERE (?1 IS NULL OR ioc_type = ?1)
+             ORDER BY last_seen DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![ioc_type, limit], row_to_ioc)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn get_ioc(&self, id: i64) -> Result<Option<Ioc>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, ioc_type, value, description, source, tags, confidence, first_seen, last_seen
+             FROM iocs WHERE id = ?1",
+            params![id],
+            row_to_ioc,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn delete_ioc(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM ioc_articles WHERE ioc_id = ?1", params![id])?;
+        conn.execute("DELETE FROM iocs WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn link_ioc_article(&self, ioc_id: i64, article_id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR IGNORE INTO ioc_articles (ioc_id, article_id) VALUES (?1, ?2)",
+            params![ioc_id, article_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn article_ids_for_ioc(&self, ioc_id: i64) -> Result<Vec<i64>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare("SELECT article_id FROM ioc_articles WHERE ioc_id = ?1 ORDER BY article_id")?;
+        let rows = stmt.query_map(params![ioc_id], |row| row.get(0))?;
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(row?);
+        }
+        Ok(ids)
+    }
+
+    pub fn iocs_for_article(&self, article_id: i64) -> Result<Vec<Ioc>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT i.id, i.ioc_type, i.value, i.description, i.source, i.tags, i.confidence, i.first_seen, i.last_seen
+             FROM iocs i
+             JOIN ioc_articles ia ON ia.ioc_id = i.id
+             WHERE ia.article_id = ?1
+             ORDER BY i.id",
+        )?;
+        let rows = stmt.query_map(params![article_id], row_to_ioc)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn count_iocs(&self, source: Option<&str>) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT COUNT(*) FROM iocs WHERE (?1 IS NULL OR source = ?1)",
+            params![source],
+            |row| row.get(0),
+        )
+        .map_err(Into::into)
+    }
+
+    /// Saved articles with inline images that haven't been through OCR yet.
+    /// Returns (id, url, content).
+    pub fn articles_pending_ocr(&self, limit: i64) -> Result<Vec<(i64, String, String)>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, url, content FROM rss_items
+             WHERE saved = 1
+               AND content LIKE '%<img%'
+               AND id NOT IN (SELECT article_id FROM article_ocr)
+             ORDER BY published_at DESC
+             LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Replace the OCR results of an article and re-index it so the
+    /// recognized text is searchable along with the article body.
+    pub fn save_article_image_text(&self, article_id: i64, image_count: i64, texts: &[ArticleImageText]) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        conn.execute("DELETE FROM article_image_text WHERE article_id = ?1", params![article_id])?;
+        for t in texts {
+            conn.execute(
+                "INSERT OR REPLACE INTO article_image_text (article_id, image_url, text, engine, processed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![article_id, t.image_url, t.text, t.engine, t.processed_at],
+            )?;
+        }
+        conn.execute(
+            "INSERT OR REPLACE INTO article_ocr (article_id, image_count, processed_at) VALUES (?1, ?2, ?3)",
+            params![article_id, image_count, now],
+        )?;
+
+        let article: Option<(String, String, i64)> = conn
+            .query_row(
+                "SELECT title, content, published_at FROM rss_items WHERE id = ?1",
+                params![article_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+        if let Some((title, content, ts)) = article {
+            let mut indexed = content;
+            for t in texts {
+                indexed.push(' ');
+                indexed.push_str(&t.text);
+            }
+            conn.execute(
+                "DELETE FROM fts_documents WHERE doc_type = 'rss_item' AND doc_id = ?1",
+                params![article_id],
+            )?;
+            conn.execute(
+                "INSERT INTO fts_documents (doc_type, doc_id, title, content, ts) VALUES ('rss_item', ?1, ?2, ?3, ?4)",
+                params![article_id, title, indexed, ts],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn record_feed_fetch(&self, feed_id: i64, status: &str, items: i64, error: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO feed_fetch_log (feed_id, fetched_at, status, items, error) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![feed_id, chrono::Utc::now().timestamp(), status, items, error],
+        )?;
+        Ok(())
+    }
+
+    /// Per-feed fetch outcomes since `since` and quality counts of the items
+    /// first stored since then. Feeds with neither are left out.
+    pub fn feed_quality_counts(&self, since: i64) -> Result<Vec<FeedQualityCounts>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut counts: HashMap<i64, FeedQualityCounts> = HashMap::new();
+
+        let mut stmt = conn.prepare(
+            "SELECT feed_id, COUNT(*),
+                    SUM(CASE WHEN status IN ('http_error', 'fetch_error') THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN status = 'parse_error' THEN 1 ELSE 0 END),
+                    (SELECT l2.error FROM feed_fetch_log l2
+                     WHERE l2.feed_id = l.feed_id AND l2.error IS NOT NULL
+                     ORDER BY l2.fetched_at DESC LIMIT 1)
+             FROM feed_fetch_log l
+             WHERE fetched_at >= ?1
+             GROUP BY feed_id",
+        )?;
+        let rows = stmt.query_map(params![since], |row| {
+            Ok(FeedQualityCounts {
+                feed_id: row.get(0)?,
+                fetches: row.get(1)?,
+                fetch_failures: row.get(2)?,
+                parse_failures: row.get(3)?,
+                last_error: row.get(4)?,
+                ..Default::default()
+            })
+        })?;
+        for row in rows {
+            let row = row?;
+            counts.insert(row.feed_id, row);
+        }
+
+        // Empty items carry the no-content placeholder the fetcher writes
+        let mut stmt = conn.prepare(
+            "SELECT i.feed_id, COUNT(*),
+                    SUM(CASE WHEN trim(i.content) = '' OR i.content LIKE '%class=\"no-content\"%' THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN EXISTS (
+                        SELECT 1 FROM rss_items o
+                        WHERE o.fetched_at >= ?1 AND o.id < i.id AND lower(o.title) = lower(i.title)
+                    ) THEN 1 ELSE 0 END)
+             FROM rss_items i
+             WHERE i.fetched_at >= ?1
+             GROUP BY i.feed_id",
+        )?;
+        let rows = stmt.query_map(params![since], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?, row.get::<_, i64>(3)?))
+        })?;
+        for row in rows {
+            let (feed_id, items, empty_items, duplicate_items) = row?;
+            let entry = counts.entry(feed_id).or_insert_with(|| FeedQualityCounts { feed_id, ..Default::default() });
+            entry.items = items;
+            entry.empty_items = empty_items;
+            entry.duplicate_items = duplicate_items;
+        }
+
+        // Newest item regardless of the window, for staleness
+        let mut stmt = conn.prepare("SELECT feed_id, MAX(fetched_at) FROM rss_items GROUP BY feed_id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?;
+        for row in rows {
+            let (feed_id, last_item_at) = row?;
+            if let Some(entry) = counts.get_mut(&feed_id) {
+                entry.last_item_at = Some(last_item_at);
+            } else {
+                counts.insert(feed_id, FeedQualityCounts { feed_id, last_item_at: Some(last_item_at), ..Default::default() });
+            }
+        }
+
+        Ok(counts.into_values().collect())
+    }
+
+    pub fn feed_quality_flag(&self, feed_id: i64) -> Result<Option<String>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Ok(conn
+            .query_row("SELECT issues FROM feed_quality_flags WHERE feed_id = ?1", params![feed_id], |row| row.get(0))
+            .optional()?)
+    }
+
+    /// Remember the issues last notified for a feed; None clears them.
+    pub fn set_feed_quality_flag(&self, feed_id: i64, issues: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        match issues {
+            Some(issues) => conn.execute(
+                "INSERT OR REPLACE INTO feed_quality_flags (feed_id, issues, flagged_at) VALUES (?1, ?2, ?3)",
+                params![feed_id, issues, chrono::Utc::now().timestamp()],
+            )?,
+            None => conn.execute("DELETE FROM feed_quality_flags WHERE feed_id = ?1", params![feed_id])?,
+        };
+        Ok(())
+    }
+
+    pub fn get_article_image_text(&self, article_id: i64) -> Result<Vec<ArticleImageText>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT article_id, image_url, text, engine, processed_at
+             FROM article_image_text
+             WHERE article_id = ?1
+             ORDER BY image_url",
+        )?;
+        let rows = stmt.query_map(params![article_id], |row| {
+            Ok(ArticleImageText {
+                article_id: row.get(0)?,
+                image_url: row.get(1)?,
+                text: row.get(2)?,
+                engine: row.get(3)?,
+                processed_at: row.get(4)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+}
+
+fn row_to_ioc(row: &rusqlite::Row) -> rusqlite::Result<Ioc> {
+    let tags: String = row.get(5)?;
+    Ok(Ioc {
+        id: row.get(0)?,
+        ioc_type: row.get(1)?,
+        value: row.get(2)?,
+        description: row.get(3)?,
+        source: row.get(4)?,
+        tags: serde_json::from_str(&tags).unwrap_or_default(),
+        confidence: row.get(6)?,
+        first_seen: row.get(7)?,
+        last_seen: row.get(8)?,
+    })
 }
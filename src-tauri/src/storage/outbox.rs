@@ -0,0 +1,282 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// An outbound notification waiting to be delivered. `status` is pending,
+/// sending, sent or dead (gave up after too many attempts).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxMessage {
+    pub id: i64,
+    /// Unique per logical send; enqueueing the same key twice is a no-op
+    pub idempotency_key: String,
+    pub alert_id: i64,
+    pub escalation_id: Option<i64>,
+    pub channel: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub sent_at: Option<i64>,
+}
+
+/// Durable outbox for alert notifications. A message is written before it
+/// is sent, so a crash mid-send leaves it queued for the retry worker
+/// rather than lost.
+pub struct OutboxStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl OutboxStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = OutboxStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: OutboxStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS notification_outbox (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                idempotency_key TEXT NOT NULL UNIQUE,
+                alert_id INTEGER NOT NULL,
+                escalation_id INTEGER,
+                channel TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                attempts INTEGER NOT NULL DEFAULT 0,
+                next_attempt_at INTEGER NOT NULL,
+                last_error TEXT,
+                created_at INTEGER NOT NULL,
+                sent_at INTEGER
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_outbox_due ON notification_outbox(status, next_attempt_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Record an escalation and queue its notification in one transaction.
+    /// Returns None when a message with this key already exists.
+    pub fn enqueue_escalation(
+        &self,
+        idempotency_key: &str,
+        alert_id: i64,
+        escalation_level: i32,
+        channel: &str,
+        payload: &serde_json::Value,
+    ) -> Result<Option<OutboxMessage>> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        let tx = conn.transaction()?;
+
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO notification_outbox
+             (idempotency_key, alert_id, channel, payload, status, attempts, next_attempt_at, created_at)
+             VALUES (?1, ?2, ?3, ?4, 'pending', 0, ?5, ?5)",
+            params![idempotency_key, alert_id, channel, payload.to_string(), now],
+        )?;
+        if inserted == 0 {
+            return Ok(None);
+        }
+        let id = tx.last_insert_rowid();
+
+        tx.execute(
+            "INSERT INTO alert_escalations (alert_id, escalated_at, escalation_level, channel, sent)
+             VALUES (?1, ?2, ?3, ?4, 0)",
+            params![alert_id, now, escalation_level, channel],
+        )?;
+        let escalation_id = tx.last_insert_rowid();
+        tx.execute(
+            "UPDATE notification_outbox SET escalation_id = ?1 WHERE id = ?2",
+            params![escalation_id, id],
+        )?;
+        tx.commit()?;
+
+        Ok(Some(OutboxMessage {
+            id,
+            idempotency_key: idempotency_key.to_string(),
+            alert_id,
+            escalation_id: Some(escalation_id),
+            channel: channel.to_string(),
+            payload: payload.clone(),
+            status: "pending".to_string(),
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            created_at: now,
+            sent_at: None,
+        }))
+    }
+
+    pub fn get_message(&self, id: i64) -> Result<Option<OutboxMessage>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, idempotency_key, alert_id, escalation_id, channel, payload, status, attempts,
+                    next_attempt_at, last_error, created_at, sent_at
+             FROM notification_outbox WHERE id = ?1",
+            params![id],
+            row_to_message,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn find_by_key(&self, idempotency_key: &str) -> Result<Option<OutboxMessage>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, idempotency_key, alert_id, escalation_id, channel, payload, status, attempts,
+                    next_attempt_at, last_error, created_at, sent_at
+             FROM notification_outbox WHERE idempotency_key = ?1",
+            params![idempotency_key],
+            row_to_message,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    /// Pending messages whose retry time has come, oldest first.
+    pub fn due_messages(&self, now: i64, limit: i64) -> Result<Vec<OutboxMessage>> {
+        self.query(
+            "SELECT id, idempotency_key, alert_id, escalation_id, channel, payload, status, attempts,
+                    next_attempt_at, last_error, created_at, sent_at
+             FROM notification_outbox
+             WHERE status = 'pending' AND next_attempt_at <= ?1
+             ORDER BY next_attempt_at ASC
+             LIMIT ?2",
+            params![now, limit],
+        )
+    }
+
+    /// Newest first, optionally only one status.
+    pub fn list_messages(&self, status: Option<&str>, limit: i64) -> Result<Vec<OutboxMessage>> {
+        self.query(
+            "SELECT id, idempotency_key, alert_id, escalation_id, channel, payload, status, attempts,
+                    next_attempt_at, last_error, created_at, sent_at
+             FROM notification_outbox
+             WHERE (?1 IS NULL OR status = ?1)
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?2",
+            params![status, limit],
+        )
+    }
+
+    /// Move a pending message to sending. False when another worker got it
+    /// first.
+    pub fn claim(&self, id: i64) -> Result<bool> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let updated = conn.execute(
+            "UPDATE notification_outbox SET status = 'sending', attempts = attempts + 1
+             WHERE id = ?1 AND status = 'pending'",
+            params![id],
+        )?;
+        Ok(updated == 1)
+    }
+
+    pub fn mark_sent(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE notification_outbox SET status = 'sent', sent_at = ?1, last_error = NULL WHERE id = ?2",
+            params![chrono::Utc::now().timestamp(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Requeue for `next_attempt_at`, or dead-letter when that is None.
+    pub fn mark_failed(&self, id: i64, error: &str, next_attempt_at: Option<i64>) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        match next_attempt_at {
+            Some(at) => conn.execute(
+                "UPDATE notification_outbox SET status = 'pending', next_attempt_at = ?1, last_error = ?2 WHERE id = ?3",
+                params![at, error, id],
+            )?,
+            None => conn.execute(
+                "UPDATE notification_outbox SET status = 'dead', last_error = ?1 WHERE id = ?2",
+                params![error, id],
+            )?,
+        };
+        Ok(())
+    }
+
+    /// Give a dead-lettered message a fresh set of attempts.
+    pub fn requeue(&self, id: i64) -> Result<bool> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let updated = conn.execute(
+            "UPDATE notification_outbox SET status = 'pending', attempts = 0, next_attempt_at = ?1
+             WHERE id = ?2 AND status = 'dead'",
+            params![chrono::Utc::now().timestamp(), id],
+        )?;
+        Ok(updated == 1)
+    }
+
+    /// Messages left in sending by a crash may or may not have gone out;
+    /// send them again rather than drop them.
+    pub fn reset_in_flight(&self) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let count = conn.execute(
+            "UPDATE notification_outbox SET status = 'pending' WHERE status = 'sending'",
+            [],
+        )?;
+        Ok(count)
+    }
+
+    pub fn delete_sent_before(&self, before: i64) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let count = conn.execute(
+            "DELETE FROM notification_outbox WHERE status = 'sent' AND sent_at < ?1",
+            params![before],
+        )?;
+        Ok(count)
+    }
+
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<OutboxMessage>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map(params, row_to_message)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+}
+
+fn row_to_message(row: &rusqlite::Row) -> rusqlite::Result<OutboxMessage> {
+    let payload: String = row.get(5)?;
+    Ok(OutboxMessage {
+        id: row.get(0)?,
+        idempotency_key: row.get(1)?,
+        alert_id: row.get(2)?,
+        escalation_id: row.get(3)?,
+        channel: row.get(4)?,
+        payload: serde_json::from_str(&payload).unwrap_or(serde_json::Value::Null),
+        status: row.get(6)?,
+        attempts: row.get(7)?,
+        next_attempt_at: row.get(8)?,
+        last_error: row.get(9)?,
+        created_at: row.get(10)?,
+        sent_at: row.get(11)?,
+    })
+}
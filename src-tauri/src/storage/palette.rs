@@ -0,0 +1,103 @@
+use anyhow::Result;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Something opened from the UI that the command palette offers again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentTarget {
+    pub kind: String, // event|entity|project|script|workflow|...
+    pub target_id: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub visit_count: i64,
+    pub visited_at: i64,
+}
+
+pub struct PaletteStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl PaletteStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = PaletteStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: PaletteStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS palette_recents (
+                kind TEXT NOT NULL,
+                target_id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                subtitle TEXT,
+                visit_count INTEGER NOT NULL DEFAULT 1,
+                visited_at INTEGER NOT NULL,
+                PRIMARY KEY (kind, target_id)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_palette_recents_visited ON palette_recents(visited_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn record_visit(&self, kind: &str, target_id: &str, title: &str, subtitle: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO palette_recents (kind, target_id, title, subtitle, visit_count, visited_at)
+             VALUES (?1, ?2, ?3, ?4, 1, ?5)
+             ON CONFLICT(kind, target_id) DO UPDATE SET
+                title = excluded.title,
+                subtitle = excluded.subtitle,
+                visit_count = visit_count + 1,
+                visited_at = excluded.visited_at",
+            params![kind, target_id, title, subtitle, chrono::Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    /// Most recently visited first.
+    pub fn list_recent(&self, limit: i64) -> Result<Vec<RecentTarget>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT kind, target_id, title, subtitle, visit_count, visited_at
+             FROM palette_recents
+             ORDER BY visited_at DESC
+             LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], |row| {
+            Ok(RecentTarget {
+                kind: row.get(0)?,
+                target_id: row.get(1)?,
+                title: row.get(2)?,
+                subtitle: row.get(3)?,
+                visit_count: row.get(4)?,
+                visited_at: row.get(5)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn clear(&self) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Ok(conn.execute("DELETE FROM palette_recents", [])?)
+    }
+}
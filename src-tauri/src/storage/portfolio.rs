@@ -8,6 +8,7 @@ pub struct Portfolio {
     pub id: i64,
     pub name: String,
     pub created_at: i64,
+    pub base_currency: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +19,7 @@ pub struct Holding {
     pub quantity: f64,
     pub purchase_price: f64,
     pub purchase_date: i64,
+    pub currency: String, // currency the ticker trades and was bought in
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +35,16 @@ pub struct Transaction {
     pub notes: Option<String>,
 }
 
+/// Desired share of a portfolio, either for one ticker or for a whole sector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetAllocation {
+    pub portfolio_id: i64,
+    pub target_type: String, // ticker|sector
+    pub target: String,
+    pub weight_percent: f64,
+    pub updated_at: i64,
+}
+
 pub struct PortfolioStore {
     pub conn: Arc<Mutex<Connection>>,
 }
@@ -91,6 +103,23 @@ impl PortfolioStore {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS target_allocations (
+                portfolio_id INTEGER NOT NULL,
+                target_type TEXT NOT NULL,
+                target TEXT NOT NULL,
+                weight_percent REAL NOT NULL,
+                updated_at INTEGER NOT NULL,
+                PRIMARY KEY (portfolio_id, target_type, target),
+                FOREIGN KEY (portfolio_id) REFERENCES portfolios(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Multi-currency: portfolios value in their base currency, holdings keep their own
+        let _ = conn.execute("ALTER TABLE portfolios ADD COLUMN base_currency TEXT NOT NULL DEFAULT 'USD'", []);
+        let _ = conn.execute("ALTER TABLE holdings ADD COLUMN currency TEXT NOT NULL DEFAULT 'USD'", []);
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_holdings_portfolio ON holdings(portfolio_id)",
             [],
@@ -128,7 +157,7 @@ impl PortfolioStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let mut stmt = conn.prepare(
-            "SELECT id, name, created_at FROM portfolios ORDER BY created_at DESC",
+            "SELECT id, name, created_at, base_currency FROM portfolios ORDER BY created_at DESC",
         )?;
 
         let rows = stmt.query_map([], |row| {
@@ -136,6 +165,7 @@ impl PortfolioStore {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 created_at: row.get(2)?,
+                base_currency: row.get(3)?,
             })
         })?;
 
@@ -153,13 +183,14 @@ impl PortfolioStore {
 
         let portfolio = conn
             .query_row(
-                "SELECT id, name, created_at FROM portfolios WHERE id = ?1",
+                "SELECT id, name, created_at, base_currency FROM portfolios WHERE id = ?1",
                 params![id],
                 |row| {
                     Ok(Portfolio {
                         id: row.get(0)?,
                         name: row.get(1)?,
                         created_at: row.get(2)?,
+                        base_currency: row.get(3)?,
                     })
                 },
             )
@@ -175,15 +206,16 @@ impl PortfolioStore {
         quantity: f64,
         purchase_price: f64,
         purchase_date: i64,
+        currency: &str,
     ) -> Result<i64> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let now = chrono::Utc::now().timestamp();
 
         conn.execute(
-            "INSERT OR REPLACE INTO holdings (portfolio_id, ticker, quantity, purchase_price, purchase_date, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![portfolio_id, ticker, quantity, purchase_price, purchase_date, now],
+            "INSERT OR REPLACE INTO holdings (portfolio_id, ticker, quantity, purchase_price, purchase_date, created_at, currency)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![portfolio_id, ticker, quantity, purchase_price, purchase_date, now, currency.to_uppercase()],
         )?;
 
         Ok(conn.last_insert_rowid())
@@ -193,7 +225,7 @@ impl PortfolioStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let mut stmt = conn.prepare(
-            "SELECT id, portfolio_id, ticker, quantity, purchase_price, purchase_date
+            "SELECT id, portfolio_id, ticker, quantity, purchase_price, purchase_date, currency
              FROM holdings
              WHERE portfolio_id = ?1
              ORDER BY ticker",
@@ -207,6 +239,7 @@ impl PortfolioStore {
                 quantity: row.get(3)?,
                 purchase_price: row.get(4)?,
                 purchase_date: row.get(5)?,
+                currency: row.get(6)?,
             })
         })?;
 
@@ -222,7 +255,7 @@ impl PortfolioStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let mut stmt = conn.prepare(
-            "SELECT id, portfolio_id, ticker, quantity, purchase_price, purchase_date
+            "SELECT id, portfolio_id, ticker, quantity, purchase_price, purchase_date, currency
              FROM holdings
              WHERE ticker = ?1
              ORDER BY portfolio_id",
@@ -236,6 +269,7 @@ impl PortfolioStore {
                 quantity: row.get(3)?,
                 purchase_price: row.get(4)?,
                 purchase_date: row.get(5)?,
+                currency: row.get(6)?,
             })
         })?;
 
@@ -273,6 +307,45 @@ impl PortfolioStore {
         Ok(())
     }
 
+    pub fn set_base_currency(&self, portfolio_id: i64, currency: &str) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE portfolios SET base_currency = ?1 WHERE id = ?2",
+            params![currency.to_uppercase(), portfolio_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_holding_currency(&self, holding_id: i64, currency: &str) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE holdings SET currency = ?1 WHERE id = ?2",
+            params![currency.to_uppercase(), holding_id],
+        )?;
+        Ok(())
+    }
+
+    /// Distinct (holding currency, portfolio base currency) pairs that need FX rates.
+    pub fn currency_pairs(&self) -> Result<Vec<(String, String)>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT h.currency, p.base_currency
+             FROM holdings h
+             JOIN portfolios p ON p.id = h.portfolio_id
+             WHERE h.currency != p.base_currency",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+
+        let mut pairs = Vec::new();
+        for row in rows {
+            pairs.push(row?);
+        }
+        Ok(pairs)
+    }
+
     pub fn delete_holding(&self, holding_id: i64) -> Result<()> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -314,6 +387,40 @@ impl PortfolioStore {
         Ok(conn.last_insert_rowid())
     }
 
+    /// Every transaction of a portfolio, oldest first (for lot matching).
+    pub fn list_all_transactions(&self, portfolio_id: i64) -> Result<Vec<Transaction>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, portfolio_id, ticker, transaction_type, quantity, price, transaction_date, fees, notes
+             FROM transactions
+             WHERE portfolio_id = ?1
+             ORDER BY transaction_date ASC, id ASC",
+        )?;
+
+        let rows = stmt.query_map(params![portfolio_id], |row| {
+            Ok(Transaction {
+                id: row.get(0)?,
+                portfolio_id: row.get(1)?,
+                ticker: row.get(2)?,
+                transaction_type: row.get(3)?,
+                quantity: row.get(4)?,
+                price: row.get(5)?,
+                transaction_date: row.get(6)?,
+                fees: row.get(7)?,
+                notes: row.get(8)?,
+            })
+        })?;
+
+        let mut transactions = Vec::new();
+        for row in rows {
+            transactions.push(row?);
+        }
+
+        Ok(transactions)
+    }
+
     pub fn list_transactions(&self, portfolio_id: i64, limit: Option<i64>) -> Result<Vec<Transaction>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -349,6 +456,53 @@ impl PortfolioStore {
         Ok(transactions)
     }
 
+    /// Replace a portfolio's targets. Ticker targets are stored upper-cased.
+    pub fn set_target_allocations(&self, portfolio_id: i64, targets: &[(String, String, f64)]) -> Result<()> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM target_allocations WHERE portfolio_id = ?1", params![portfolio_id])?;
+        for (target_type, target, weight_percent) in targets {
+            let target = if target_type == "ticker" { target.to_uppercase() } else { target.clone() };
+            tx.execute(
+                "INSERT OR REPLACE INTO target_allocations (portfolio_id, target_type, target, weight_percent, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![portfolio_id, target_type, target, weight_percent, now],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    pub fn list_target_allocations(&self, portfolio_id: i64) -> Result<Vec<TargetAllocation>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT portfolio_id, target_type, target, weight_percent, updated_at
+             FROM target_allocations
+             WHERE portfolio_id = ?1
+             ORDER BY weight_percent DESC",
+        )?;
+        let rows = stmt.query_map(params![portfolio_id], |row| {
+            Ok(TargetAllocation {
+                portfolio_id: row.get(0)?,
+                target_type: row.get(1)?,
+                target: row.get(2)?,
+                weight_percent: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        })?;
+
+        let mut targets = Vec::new();
+        for row in rows {
+            targets.push(row?);
+        }
+        Ok(targets)
+    }
+
     pub fn delete_portfolio(&self, id: i64) -> Result<()> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -13,6 +13,8 @@ pub struct PriceAlert {
     pub triggered: bool,
     pub triggered_at: Option<i64>,
     pub enabled: bool,
+    /// Only fire during the regular trading session of the ticker's market
+    pub regular_hours_only: bool,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -61,6 +63,11 @@ impl PriceAlertStore {
             [],
         )?;
 
+        let _ = conn.execute(
+            "ALTER TABLE price_alerts ADD COLUMN regular_hours_only INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_price_alerts_ticker ON price_alerts(ticker)",
             [],
@@ -84,15 +91,16 @@ impl PriceAlertStore {
         ticker: &str,
         condition: &str,
         target_price: f64,
+        regular_hours_only: bool,
     ) -> Result<i64> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let now = chrono::Utc::now().timestamp();
 
         conn.execute(
-            "INSERT INTO price_alerts (ticker, condition, target_price, enabled, created_at, updated_at)
-             VALUES (?1, ?2, ?3, 1, ?4, ?5)",
-            params![ticker, condition, target_price, now, now],
+            "INSERT INTO price_alerts (ticker, condition, target_price, enabled, regular_hours_only, created_at, updated_at)
+             VALUES (?1, ?2, ?3, 1, ?4, ?5, ?6)",
+            params![ticker, condition, target_price, regular_hours_only as i64, now, now],
         )?;
 
         Ok(conn.last_insert_rowid())
@@ -103,7 +111,7 @@ impl PriceAlertStore {
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
 
         conn.query_row(
-            "SELECT id, ticker, condition, target_price, current_price, triggered, triggered_at, enabled, created_at, updated_at
+            "SELECT id, ticker, condition, target_price, current_price, triggered, triggered_at, enabled, regular_hours_only, created_at, updated_at
              FROM price_alerts WHERE id = ?1",
             params![id],
             |row| {
@@ -116,8 +124,9 @@ impl PriceAlertStore {
                     triggered: row.get::<_, i64>(5)? == 1,
                     triggered_at: row.get(6)?,
                     enabled: row.get::<_, i64>(7)? == 1,
-                    created_at: row.get(8)?,
-                    updated_at: row.get(9)?,
+                    regular_hours_only: row.get::<_, i64>(8)? == 1,
+                    created_at: row.get(9)?,
+                    updated_at: row.get(10)?,
                 })
             },
         )
@@ -133,7 +142,7 @@ impl PriceAlertStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
 
-        let mut sql = "SELECT id, ticker, condition, target_price, current_price, triggered, triggered_at, enabled, created_at, updated_at
+        let mut sql = "SELECT id, ticker, condition, target_price, current_price, triggered, triggered_at, enabled, regular_hours_only, created_at, updated_at
                        FROM price_alerts WHERE 1=1".to_string();
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
@@ -159,8 +168,9 @@ impl PriceAlertStore {
                 triggered: row.get::<_, i64>(5)? == 1,
                 triggered_at: row.get(6)?,
                 enabled: row.get::<_, i64>(7)? == 1,
-                created_at: row.get(8)?,
-                updated_at: row.get(9)?,
+                regular_hours_only: row.get::<_, i64>(8)? == 1,
+                created_at: row.get(9)?,
+                updated_at: row.get(10)?,
             })
         })?;
 
@@ -204,6 +214,7 @@ impl PriceAlertStore {
         condition: Option<&str>,
         target_price: Option<f64>,
         enabled: Option<bool>,
+        regular_hours_only: Option<bool>,
     ) -> Result<()> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -224,6 +235,10 @@ impl PriceAlertStore {
             updates.push("enabled = ?");
             params_vec.push(Box::new(if e { 1 } else { 0 }));
         }
+        if let Some(r) = regular_hours_only {
+            updates.push("regular_hours_only = ?");
+            params_vec.push(Box::new(if r { 1 } else { 0 }));
+        }
 
         if updates.is_empty() {
             return Ok(());
@@ -233,6 +248,7 @@ impl PriceAlertStore {
         params_vec.push(Box::new(now));
         params_vec.push(Box::new(id));
 
+        // sql-audit: column assignments are literals, values are bound
         let sql = format!("UPDATE price_alerts SET {} WHERE id = ?", updates.join(", "));
         let mut stmt = conn.prepare(&sql)?;
         stmt.execute(rusqlite::params_from_iter(params_vec.iter().map(|p| p.as_ref())))?;
@@ -0,0 +1,303 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// A limit the process guardian enforces on processes whose name matches
+/// `pattern`. A process is acted on once it has exceeded a limit for
+/// `duration_secs` straight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessPolicy {
+    pub id: i64,
+    pub name: String,
+    /// Case-insensitive regex matched against the process name
+    pub pattern: String,
+    pub max_rss_bytes: Option<u64>,
+    /// Percent of one core, so a busy multi-threaded process can exceed 100
+    pub max_cpu_percent: Option<f32>,
+    pub duration_secs: i64,
+    pub action: String, // kill|nice|notify
+    /// Niceness applied by the "nice" action
+    pub nice_level: i32,
+    /// Audit mode: record and alert on what would happen without acting
+    pub dry_run: bool,
+    pub enabled: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewProcessPolicy {
+    pub name: String,
+    pub pattern: String,
+    pub max_rss_bytes: Option<u64>,
+    pub max_cpu_percent: Option<f32>,
+    pub duration_secs: i64,
+    pub action: String,
+    #[serde(default = "default_nice_level")]
+    pub nice_level: i32,
+    #[serde(default = "default_true")]
+    pub dry_run: bool,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_nice_level() -> i32 {
+    10
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// One time a policy triggered, and what was (or would have been) done.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessPolicyAction {
+    pub id: i64,
+    pub policy_id: i64,
+    pub policy_name: String,
+    pub pid: u32,
+    pub process_name: String,
+    pub action: String,
+    pub reason: String,
+    pub rss_bytes: u64,
+    pub cpu_percent: f32,
+    pub dry_run: bool,
+    pub success: bool,
+    pub error: Option<String>,
+    pub created_at: i64,
+}
+
+pub struct ProcessPolicyStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ProcessPolicyStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = ProcessPolicyStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: ProcessPolicyStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS process_policies (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                pattern TEXT NOT NULL,
+                max_rss_bytes INTEGER,
+                max_cpu_percent REAL,
+                duration_secs INTEGER NOT NULL DEFAULT 0,
+                action TEXT NOT NULL DEFAULT 'notify',
+                nice_level INTEGER NOT NULL DEFAULT 10,
+                dry_run INTEGER NOT NULL DEFAULT 1,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS process_policy_actions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                policy_id INTEGER NOT NULL,
+                policy_name TEXT NOT NULL,
+                pid INTEGER NOT NULL,
+                process_name TEXT NOT NULL,
+                action TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                rss_bytes INTEGER NOT NULL,
+                cpu_percent REAL NOT NULL,
+                dry_run INTEGER NOT NULL,
+                success INTEGER NOT NULL,
+                error TEXT,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_process_policy_actions_created ON process_policy_actions(created_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_policy(&self, policy: &NewProcessPolicy) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "INSERT INTO process_policies
+                (name, pattern, max_rss_bytes, max_cpu_percent, duration_secs, action, nice_level, dry_run, enabled, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?10)",
+            params![
+                policy.name,
+                policy.pattern,
+                policy.max_rss_bytes.map(|b| b as i64),
+                policy.max_cpu_percent,
+                policy.duration_secs,
+                policy.action,
+                policy.nice_level,
+                policy.dry_run as i32,
+                policy.enabled as i32,
+                now,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn update_policy(&self, id: i64, policy: &NewProcessPolicy) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let updated = conn.execute(
+            "UPDATE process_policies SET
+                name = ?1, pattern = ?2, max_rss_bytes = ?3, max_cpu_percent = ?4, duration_secs = ?5,
+                action = ?6, nice_level = ?7, dry_run = ?8, enabled = ?9, updated_at = ?10
+             WHERE id = ?11",
+            params![
+                policy.name,
+                policy.pattern,
+                policy.max_rss_bytes.map(|b| b as i64),
+                policy.max_cpu_percent,
+                policy.duration_secs,
+                policy.action,
+                policy.nice_level,
+                policy.dry_run as i32,
+                policy.enabled as i32,
+                chrono::Utc::now().timestamp(),
+                id,
+            ],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Process policy {} not found", id);
+        }
+        Ok(())
+    }
+
+    pub fn get_policy(&self, id: i64) -> Result<Option<ProcessPolicy>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, name, pattern, max_rss_bytes, max_cpu_percent, duration_secs, action, nice_level,
+                    dry_run, enabled, created_at, updated_at
+             FROM process_policies WHERE id = ?1",
+            params![id],
+            row_to_policy,
+        )
+        .optional()
+        .map_err(anyhow::Error::from)
+    }
+
+    pub fn list_policies(&self, enabled_only: bool) -> Result<Vec<ProcessPolicy>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, pattern, max_rss_bytes, max_cpu_percent, duration_secs, action, nice_level,
+                    dry_run, enabled, created_at, updated_at
+             FROM process_policies
+             WHERE ?1 = 0 OR enabled = 1
+             ORDER BY name",
+        )?;
+        let rows = stmt.query_map(params![enabled_only as i32], row_to_policy)?;
+
+        let mut policies = Vec::new();
+        for row in rows {
+            policies.push(row?);
+        }
+        Ok(policies)
+    }
+
+    pub fn delete_policy(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM process_policies WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn record_action(&self, action: &ProcessPolicyAction) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO process_policy_actions
+                (policy_id, policy_name, pid, process_name, action, reason, rss_bytes, cpu_percent, dry_run, success, error, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                action.policy_id,
+                action.policy_name,
+                action.pid,
+                action.process_name,
+                action.action,
+                action.reason,
+                action.rss_bytes as i64,
+                action.cpu_percent,
+                action.dry_run as i32,
+                action.success as i32,
+                action.error,
+                action.created_at,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Newest first, optionally for one policy.
+    pub fn list_actions(&self, policy_id: Option<i64>, limit: i64) -> Result<Vec<ProcessPolicyAction>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, policy_id, policy_name, pid, process_name, action, reason, rss_bytes, cpu_percent,
+                    dry_run, success, error, created_at
+             FROM process_policy_actions
+             WHERE ?1 IS NULL OR policy_id = ?1
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![policy_id, limit], |row| {
+            Ok(ProcessPolicyAction {
+                id: row.get(0)?,
+                policy_id: row.get(1)?,
+                policy_name: row.get(2)?,
+                pid: row.get(3)?,
+                process_name: row.get(4)?,
+                action: row.get(5)?,
+                reason: row.get(6)?,
+                rss_bytes: row.get::<_, i64>(7)? as u64,
+                cpu_percent: row.get(8)?,
+                dry_run: row.get::<_, i32>(9)? == 1,
+                success: row.get::<_, i32>(10)? == 1,
+                error: row.get(11)?,
+                created_at: row.get(12)?,
+            })
+        })?;
+
+        let mut actions = Vec::new();
+        for row in rows {
+            actions.push(row?);
+        }
+        Ok(actions)
+    }
+}
+
+fn row_to_policy(row: &rusqlite::Row) -> rusqlite::Result<ProcessPolicy> {
+    Ok(ProcessPolicy {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        pattern: row.get(2)?,
+        max_rss_bytes: row.get::<_, Option<i64>>(3)?.map(|b| b as u64),
+        max_cpu_percent: row.get(4)?,
+        duration_secs: row.get(5)?,
+        action: row.get(6)?,
+        nice_level: row.get(7)?,
+        dry_run: row.get::<_, i32>(8)? == 1,
+        enabled: row.get::<_, i32>(9)? == 1,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+    })
+}
@@ -0,0 +1,200 @@
+use anyhow::Result;
+use rusqlite::{Connection, params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+pub const DEFAULT_PROFILE_ID: i64 = 1;
+pub const CONFIG_ACTIVE_PROFILE: &str = "active_profile_id";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: i64,
+    pub name: String,
+    pub share_articles: bool, // false = only sees feeds added under this profile
+    pub has_pin: bool,
+    pub active: bool,
+    pub created_at: i64,
+}
+
+/// Profile whose watchlists, alert rules and chats the app currently shows.
+/// Falls back to the default profile when nothing has been switched yet.
+pub(crate) fn active_profile_id(conn: &Connection) -> Result<i64> {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT value FROM config WHERE key = ?1",
+            params![CONFIG_ACTIVE_PROFILE],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(value.and_then(|v| v.parse::<i64>().ok()).unwrap_or(DEFAULT_PROFILE_ID))
+}
+
+/// Whether the active profile reads from the shared article corpus.
+/// Defaults to true so a missing profiles table never hides articles.
+pub(crate) fn active_profile_shares_articles(conn: &Connection) -> Result<(i64, bool)> {
+    let profile_id = active_profile_id(conn)?;
+    let shared = conn
+        .query_row(
+            "SELECT share_articles FROM profiles WHERE id = ?1",
+            params![profile_id],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|v| v == 1)
+        .unwrap_or(true);
+    Ok((profile_id, shared))
+}
+
+/// The key AuthManager stores a profile's PIN under.
+pub fn profile_user_id(profile_id: i64) -> String {
+    format!("profile_{}", profile_id)
+}
+
+pub struct ProfileStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ProfileStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = ProfileStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: ProfileStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS profiles (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                share_articles INTEGER NOT NULL DEFAULT 1,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Everything created before profiles existed belongs to the default one
+        conn.execute(
+            "INSERT OR IGNORE INTO profiles (id, name, share_articles, created_at) VALUES (?1, 'Default', 1, ?2)",
+            params![DEFAULT_PROFILE_ID, chrono::Utc::now().timestamp()],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_profile(&self, name: &str, share_articles: bool) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "INSERT INTO profiles (name, share_articles, created_at) VALUES (?1, ?2, ?3)",
+            params![name, if share_articles { 1 } else { 0 }, now],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn update_profile(&self, id: i64, name: Option<&str>, share_articles: Option<bool>) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        if let Some(n) = name {
+            conn.execute("UPDATE profiles SET name = ?1 WHERE id = ?2", params![n, id])?;
+        }
+        if let Some(s) = share_articles {
+            conn.execute(
+                "UPDATE profiles SET share_articles = ?1 WHERE id = ?2",
+                params![if s { 1 } else { 0 }, id],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn list_profiles(&self) -> Result<Vec<Profile>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let active = active_profile_id(&conn)?;
+        let mut stmt = conn.prepare(
+            "SELECT p.id, p.name, p.share_articles, p.created_at,
+                    EXISTS(SELECT 1 FROM config c WHERE c.key = 'pin_profile_' || p.id)
+             FROM profiles p
+             ORDER BY p.id ASC"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let id: i64 = row.get(0)?;
+            Ok(Profile {
+                id,
+                name: row.get(1)?,
+                share_articles: row.get::<_, i64>(2)? == 1,
+                has_pin: row.get::<_, i64>(4)? == 1,
+                active: id == active,
+                created_at: row.get(3)?,
+            })
+        })?;
+
+        let mut profiles = Vec::new();
+        for row in rows {
+            profiles.push(row?);
+        }
+        Ok(profiles)
+    }
+
+    pub fn get_profile(&self, id: i64) -> Result<Option<Profile>> {
+        Ok(self.list_profiles()?.into_iter().find(|p| p.id == id))
+    }
+
+    pub fn get_active_profile_id(&self) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        active_profile_id(&conn)
+    }
+
+    pub fn set_active_profile(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO config (key, value, updated_at) VALUES (?1, ?2, ?3)",
+            params![CONFIG_ACTIVE_PROFILE, id.to_string(), chrono::Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a profile together with its watchlists, alert rules and chats.
+    /// Its private feeds are handed to the default profile so no articles are
+    /// lost. The default profile can't be deleted.
+    pub fn delete_profile(&self, id: i64) -> Result<()> {
+        if id == DEFAULT_PROFILE_ID {
+            return Err(anyhow::anyhow!("The default profile cannot be deleted"));
+        }
+
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM watchlist_items WHERE watchlist_id IN (SELECT id FROM watchlists WHERE profile_id = ?1)",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM alert_rules WHERE profile_id = ?1", params![id])?;
+        tx.execute("DELETE FROM watchlists WHERE profile_id = ?1", params![id])?;
+        tx.execute(
+            "DELETE FROM chat_messages WHERE conversation_id IN (SELECT id FROM conversations WHERE profile_id = ?1)",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM conversations WHERE profile_id = ?1", params![id])?;
+        tx.execute(
+            "UPDATE rss_feeds SET profile_id = ?1 WHERE profile_id = ?2",
+            params![DEFAULT_PROFILE_ID, id],
+        )?;
+        tx.execute("DELETE FROM config WHERE key = ?1", params![format!("pin_{}", profile_user_id(id))])?;
+        tx.execute(
+            "DELETE FROM config WHERE key = ?1 AND value = ?2",
+            params![CONFIG_ACTIVE_PROFILE, id.to_string()],
+        )?;
+        tx.execute("DELETE FROM profiles WHERE id = ?1", params![id])?;
+
+        tx.commit()?;
+        Ok(())
+    }
+}
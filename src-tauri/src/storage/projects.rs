@@ -13,6 +13,49 @@ pub struct Project {
     pub updated_at: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectRepoLink {
+    pub project_id: i64,
+    pub repo_path: String,
+    pub unpushed_alert_days: i64,
+    pub last_alerted_at: Option<i64>,
+    pub linked_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTask {
+    pub id: i64,
+    pub project_id: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,   // "backlog", "todo", "in_progress", "review", "done"
+    pub priority: String, // "low", "medium", "high", "urgent"
+    pub due_at: Option<i64>,
+    pub tags: Vec<String>,
+    pub position: i64,
+    pub links: Vec<ProjectTaskLink>,
+    pub reminder_sent_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub completed_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectTaskLink {
+    pub target_type: String, // "event", "article", "alert"
+    pub target_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskBoardColumn {
+    pub status: String,
+    pub tasks: Vec<ProjectTask>,
+}
+
+pub const TASK_STATUSES: &[&str] = &["backlog", "todo", "in_progress", "review", "done"];
+pub const TASK_PRIORITIES: &[&str] = &["low", "medium", "high", "urgent"];
+pub const TASK_LINK_TYPES: &[&str] = &["event", "article", "alert"];
+
 pub struct ProjectStore {
     conn: Arc<Mutex<Connection>>,
 }
@@ -47,6 +90,59 @@ impl ProjectStore {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS project_repos (
+                project_id INTEGER PRIMARY KEY,
+                repo_path TEXT NOT NULL,
+                unpushed_alert_days INTEGER NOT NULL DEFAULT 3,
+                last_alerted_at INTEGER,
+                linked_at INTEGER NOT NULL,
+                FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS project_tasks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                project_id INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT,
+                status TEXT NOT NULL DEFAULT 'todo',
+                priority TEXT NOT NULL DEFAULT 'medium',
+                due_at INTEGER,
+                tags TEXT NOT NULL DEFAULT '[]',
+                position INTEGER NOT NULL DEFAULT 0,
+                reminder_sent_at INTEGER,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                completed_at INTEGER,
+                FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS project_task_links (
+                task_id INTEGER NOT NULL,
+                target_type TEXT NOT NULL,
+                target_id INTEGER NOT NULL,
+                PRIMARY KEY (task_id, target_type, target_id),
+                FOREIGN KEY (task_id) REFERENCES project_tasks(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_project_tasks_project_status ON project_tasks(project_id, status, position)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_project_tasks_due ON project_tasks(due_at)",
+            [],
+        )?;
+
         Ok(())
     }
 
@@ -89,45 +185,32 @@ impl ProjectStore {
         Ok(())
     }
 
-    pub fn list_projects(&self, project_type: Option<&str>) -> Result<Vec<Project>> {
+    /// Projects, newest first, optionally of one type and/or carrying a tag.
+    pub fn list_projects(&self, project_type: Option<&str>, tag: Option<&str>) -> Result<Vec<Project>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, project_type, content, created_at, updated_at FROM projects
+             WHERE (?1 IS NULL OR project_type = ?1)
+               AND (?2 IS NULL OR id IN (
+                    SELECT ta.object_id FROM tag_attachments ta JOIN tags t ON t.id = ta.tag_id
+                    WHERE ta.object_type = 'project' AND t.name = ?2 COLLATE NOCASE))
+             ORDER BY updated_at DESC"
+        )?;
+        let rows = stmt.query_map(params![project_type, tag], |row| {
+            Ok(Project {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                project_type: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        })?;
+
         let mut projects = Vec::new();
-        
-        if let Some(pt) = project_type {
-            let mut stmt = conn.prepare(
-                "SELECT id, name, project_type, content, created_at, updated_at FROM projects WHERE project_type = ?1 ORDER BY updated_at DESC"
-            )?;
-            let rows = stmt.query_map(params![pt], |row| {
-                Ok(Project {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    project_type: row.get(2)?,
-                    content: row.get(3)?,
-                    created_at: row.get(4)?,
-                    updated_at: row.get(5)?,
-                })
-            })?;
-            for row in rows {
-                projects.push(row?);
-            }
-        } else {
-            let mut stmt = conn.prepare(
-                "SELECT id, name, project_type, content, created_at, updated_at FROM projects ORDER BY updated_at DESC"
-            )?;
-            let rows = stmt.query_map([], |row| {
-                Ok(Project {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    project_type: row.get(2)?,
-                    content: row.get(3)?,
-                    created_at: row.get(4)?,
-                    updated_at: row.get(5)?,
-                })
-            })?;
-            for row in rows {
-                projects.push(row?);
-            }
+        for row in rows {
+            projects.push(row?);
         }
         Ok(projects)
     }
@@ -159,8 +242,407 @@ impl ProjectStore {
     pub fn delete_project(&self, id: i64) -> Result<()> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM project_repos WHERE project_id = ?1", params![id])?;
+        conn.execute(
+            "DELETE FROM project_task_links WHERE task_id IN (SELECT id FROM project_tasks WHERE project_id = ?1)",
+            params![id],
+        )?;
+        conn.execute("DELETE FROM project_tasks WHERE project_id = ?1", params![id])?;
         conn.execute("DELETE FROM projects WHERE id = ?1", params![id])?;
         Ok(())
     }
+
+    pub fn link_repo(&self, project_id: i64, repo_path: &str, unpushed_alert_days: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        conn.execute(
+            "INSERT INTO project_repos (project_id, repo_path, unpushed_alert_days, last_alerted_at, linked_at)
+             VALUES (?1, ?2, ?3, NULL, ?4)
+             ON CONFLICT(project_id) DO UPDATE SET
+                repo_path = excluded.repo_path,
+                unpushed_alert_days = excluded.unpushed_alert_days,
+                last_alerted_at = NULL",
+            params![project_id, repo_path, unpushed_alert_days, now],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn unlink_repo(&self, project_id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM project_repos WHERE project_id = ?1", params![project_id])?;
+        Ok(())
+    }
+
+    pub fn get_repo_link(&self, project_id: i64) -> Result<Option<ProjectRepoLink>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let link = conn
+            .query_row(
+                "SELECT project_id, repo_path, unpushed_alert_days, last_alerted_at, linked_at
+                 FROM project_repos WHERE project_id = ?1",
+                params![project_id],
+                |row| {
+                    Ok(ProjectRepoLink {
+                        project_id: row.get(0)?,
+                        repo_path: row.get(1)?,
+                        unpushed_alert_days: row.get(2)?,
+                        last_alerted_at: row.get(3)?,
+                        linked_at: row.get(4)?,
+                    })
+                },
+            )
+            .optional()?;
+
+        Ok(link)
+    }
+
+    pub fn list_repo_links(&self) -> Result<Vec<ProjectRepoLink>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT project_id, repo_path, unpushed_alert_days, last_alerted_at, linked_at
+             FROM project_repos ORDER BY linked_at DESC"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(ProjectRepoLink {
+                project_id: row.get(0)?,
+                repo_path: row.get(1)?,
+                unpushed_alert_days: row.get(2)?,
+                last_alerted_at: row.get(3)?,
+                linked_at: row.get(4)?,
+            })
+        })?;
+
+        let mut links = Vec::new();
+        for row in rows {
+            links.push(row?);
+        }
+        Ok(links)
+    }
+
+    pub fn mark_repo_alerted(&self, project_id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "UPDATE project_repos SET last_alerted_at = ?1 WHERE project_id = ?2",
+            params![now, project_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn create_task(
+        &self,
+        project_id: i64,
+        title: &str,
+        description: Option<&str>,
+        status: &str,
+        priority: &str,
+        due_at: Option<i64>,
+        tags: &[String],
+    ) -> Result<i64> {
+        validate_task_fields(Some(status), Some(priority))?;
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        // New tasks go to the bottom of their column
+        let position: i64 = conn.query_row(
+            "SELECT COALESCE(MAX(position), -1) + 1 FROM project_tasks WHERE project_id = ?1 AND status = ?2",
+            params![project_id, status],
+            |row| row.get(0),
+        )?;
+
+        conn.execute(
+            "INSERT INTO project_tasks (project_id, title, description, status, priority, due_at, tags, position, created_at, updated_at, completed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                project_id,
+                title,
+                description,
+                status,
+                priority,
+                due_at,
+                serde_json::to_string(tags)?,
+                position,
+                now,
+                now,
+                if status == "done" { Some(now) } else { None }
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Update task fields. `due_at` uses a nested option so callers can clear it.
+    pub fn update_task(
+        &self,
+        id: i64,
+        title: Option<&str>,
+        description: Option<&str>,
+        priority: Option<&str>,
+        due_at: Option<Option<i64>>,
+        tags: Option<&[String]>,
+    ) -> Result<()> {
+        validate_task_fields(None, priority)?;
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        if let Some(t) = title {
+            conn.execute("UPDATE project_tasks SET title = ?1 WHERE id = ?2", params![t, id])?;
+        }
+        if let Some(d) = description {
+            conn.execute("UPDATE project_tasks SET description = ?1 WHERE id = ?2", params![d, id])?;
+        }
+        if let Some(p) = priority {
+            conn.execute("UPDATE project_tasks SET priority = ?1 WHERE id = ?2", params![p, id])?;
+        }
+        if let Some(due) = due_at {
+            // Moving the due date re-arms the reminder
+            conn.execute(
+                "UPDATE project_tasks SET due_at = ?1, reminder_sent_at = NULL WHERE id = ?2",
+                params![due, id],
+            )?;
+        }
+        if let Some(tags) = tags {
+            conn.execute(
+                "UPDATE project_tasks SET tags = ?1 WHERE id = ?2",
+                params![serde_json::to_string(tags)?, id],
+            )?;
+        }
+        conn.execute("UPDATE project_tasks SET updated_at = ?1 WHERE id = ?2", params![now, id])?;
+
+        Ok(())
+    }
+
+    /// Move a task to a column and position, shifting the other cards in that column down.
+    pub fn move_task(&self, id: i64, status: &str, position: Option<i64>) -> Result<()> {
+        validate_task_fields(Some(status), None)?;
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        let tx = conn.transaction()?;
+
+        let project_id: i64 = tx.query_row(
+            "SELECT project_id FROM project_tasks WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )?;
+
+        let position = match position {
+            Some(p) => p.max(0),
+            None => tx.query_row(
+                "SELECT COALESCE(MAX(position), -1) + 1 FROM project_tasks WHERE project_id = ?1 AND status = ?2 AND id != ?3",
+                params![project_id, status, id],
+                |row| row.get(0),
+            )?,
+        };
+
+        tx.execute(
+            "UPDATE project_tasks SET position = position + 1
+             WHERE project_id = ?1 AND status = ?2 AND position >= ?3 AND id != ?4",
+            params![project_id, status, position, id],
+        )?;
+        tx.execute(
+            "UPDATE project_tasks SET
+                status = ?1,
+                position = ?2,
+                updated_at = ?3,
+                completed_at = CASE WHEN ?1 = 'done' THEN COALESCE(completed_at, ?3) ELSE NULL END
+             WHERE id = ?4",
+            params![status, position, now, id],
+        )?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn delete_task(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM project_task_links WHERE task_id = ?1", params![id])?;
+        conn.execute("DELETE FROM project_tasks WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn get_task(&self, id: i64) -> Result<Option<ProjectTask>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let task = conn
+            .query_row(
+                &format!("SELECT {} FROM project_tasks WHERE id = ?1", TASK_COLUMNS),
+                params![id],
+                row_to_task,
+            )
+            .optional()?;
+
+        match task {
+            Some(mut task) => {
+                task.links = load_task_links(&conn, task.id)?;
+                Ok(Some(task))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn list_tasks(&self, project_id: i64, status: Option<&str>, tag: Option<&str>) -> Result<Vec<ProjectTask>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM project_tasks
+             WHERE project_id = ?1 AND (?2 IS NULL OR status = ?2)
+             ORDER BY status, position, id",
+            TASK_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![project_id, status], row_to_task)?;
+
+        let mut tasks = Vec::new();
+        for row in rows {
+            let mut task = row?;
+            if let Some(t) = tag {
+                if !task.tags.iter().any(|existing| existing.eq_ignore_ascii_case(t)) {
+                    continue;
+                }
+            }
+            task.links = load_task_links(&conn, task.id)?;
+            tasks.push(task);
+        }
+        Ok(tasks)
+    }
+
+    /// All tasks of a project grouped into the fixed board columns.
+    pub fn get_board(&self, project_id: i64) -> Result<Vec<TaskBoardColumn>> {
+        let tasks = self.list_tasks(project_id, None, None)?;
+        let mut columns: Vec<TaskBoardColumn> = TASK_STATUSES
+            .iter()
+            .map(|status| TaskBoardColumn {
+                status: status.to_string(),
+                tasks: Vec::new(),
+            })
+            .collect();
+
+        for task in tasks {
+            if let Some(column) = columns.iter_mut().find(|c| c.status == task.status) {
+                column.tasks.push(task);
+            }
+        }
+        for column in &mut columns {
+            column.tasks.sort_by_key(|t| (t.position, t.id));
+        }
+        Ok(columns)
+    }
+
+    pub fn link_task(&self, task_id: i64, target_type: &str, target_id: i64) -> Result<()> {
+        if !TASK_LINK_TYPES.contains(&target_type) {
+            return Err(anyhow::anyhow!("Invalid link type: {}", target_type));
+        }
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR IGNORE INTO project_task_links (task_id, target_type, target_id) VALUES (?1, ?2, ?3)",
+            params![task_id, target_type, target_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn unlink_task(&self, task_id: i64, target_type: &str, target_id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "DELETE FROM project_task_links WHERE task_id = ?1 AND target_type = ?2 AND target_id = ?3",
+            params![task_id, target_type, target_id],
+        )?;
+        Ok(())
+    }
+
+    /// Open tasks due before `due_before` whose reminder has not been sent yet.
+    pub fn list_tasks_needing_reminder(&self, due_before: i64) -> Result<Vec<ProjectTask>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM project_tasks
+             WHERE due_at IS NOT NULL AND due_at <= ?1 AND status != 'done' AND reminder_sent_at IS NULL
+             ORDER BY due_at",
+            TASK_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![due_before], row_to_task)?;
+
+        let mut tasks = Vec::new();
+        for row in rows {
+            tasks.push(row?);
+        }
+        Ok(tasks)
+    }
+
+    pub fn mark_task_reminded(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "UPDATE project_tasks SET reminder_sent_at = ?1 WHERE id = ?2",
+            params![now, id],
+        )?;
+        Ok(())
+    }
 }
 
+const TASK_COLUMNS: &str = "id, project_id, title, description, status, priority, due_at, tags, position, reminder_sent_at, created_at, updated_at, completed_at";
+
+fn row_to_task(row: &rusqlite::Row) -> rusqlite::Result<ProjectTask> {
+    let tags_json: String = row.get(7)?;
+    Ok(ProjectTask {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        title: row.get(2)?,
+        description: row.get(3)?,
+        status: row.get(4)?,
+        priority: row.get(5)?,
+        due_at: row.get(6)?,
+        tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+        position: row.get(8)?,
+        links: Vec::new(),
+        reminder_sent_at: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+        completed_at: row.get(12)?,
+    })
+}
+
+fn load_task_links(conn: &Connection, task_id: i64) -> Result<Vec<ProjectTaskLink>> {
+    let mut stmt = conn.prepare(
+        "SELECT target_type, target_id FROM project_task_links WHERE task_id = ?1 ORDER BY target_type, target_id"
+    )?;
+    let rows = stmt.query_map(params![task_id], |row| {
+        Ok(ProjectTaskLink {
+            target_type: row.get(0)?,
+            target_id: row.get(1)?,
+        })
+    })?;
+
+    let mut links = Vec::new();
+    for row in rows {
+        links.push(row?);
+    }
+    Ok(links)
+}
+
+fn validate_task_fields(status: Option<&str>, priority: Option<&str>) -> Result<()> {
+    if let Some(s) = status {
+        if !TASK_STATUSES.contains(&s) {
+            return Err(anyhow::anyhow!("Invalid task status: {}", s));
+        }
+    }
+    if let Some(p) = priority {
+        if !TASK_PRIORITIES.contains(&p) {
+            return Err(anyhow::anyhow!("Invalid task priority: {}", p));
+        }
+    }
+    Ok(())
+}
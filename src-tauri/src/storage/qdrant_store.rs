@@ -27,8 +27,9 @@ impl QdrantStore {
     /// api_key: Optional API key for authentication
     pub async fn new(url: Option<&str>, api_key: Option<&str>) -> Result<Self> {
         let url = url.unwrap_or("http://localhost:6333");
-        
-        let mut config = QdrantConfig::from_url(url);
+        let url = crate::services::ssh_tunnels::TunnelManager::route_url("qdrant", url);
+
+        let mut config = QdrantConfig::from_url(&url);
         if let Some(key) = api_key {
             config.api_key = Some(key.to_string());
         }
@@ -94,137 +94,149 @@ impl RateLimitStore {
         Ok(bucket)
     }
 
+    /// Register a bucket if it doesn't exist yet. Existing buckets keep their
+    /// persisted tokens and any limits changed since they were created.
+    pub fn ensure_bucket(
+        &self,
+        name: &str,
+        capacity: i64,
+        refill_rate: i64,
+        refill_interval: i64,
+    ) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = Self::now()?;
+
+        conn.execute(
+            "INSERT OR IGNORE INTO rate_limit_buckets
+             (name, capacity, tokens, refill_rate, refill_interval, last_refill)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![name, capacity, capacity, refill_rate, refill_interval, now],
+        )?;
+
+        Ok(())
+    }
+
+    /// Bucket state as of now, with pending refills applied but not written back.
+    pub fn peek_bucket(&self, name: &str) -> Result<Option<RateLimitBucket>> {
+        let now = Self::now()?;
+        Ok(self.get_bucket(name)?.map(|b| Self::refilled(b, now)))
+    }
+
+    /// All buckets as of now, with pending refills applied but not written back.
     pub fn list_buckets(&self) -> Result<Vec<RateLimitBucket>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = Self::now()?;
         let mut stmt = conn.prepare(
             "SELECT name, capacity, tokens, refill_rate, refill_interval, last_refill
-             FROM rate_limit_buckets"
+             FROM rate_limit_buckets
+             ORDER BY name"
         )?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(RateLimitBucket {
-                name: row.get(0)?,
-                capacity: row.get(1)?,
-                tokens: row.get(2)?,
-                refill_rate: row.get(3)?,
-                refill_interval: row.get(4)?,
-                last_refill: row.get(5)?,
-            })
-        })?;
+        let rows = stmt.query_map([], Self::row_to_bucket)?;
 
         let mut buckets = Vec::new();
         for row in rows {
-            buckets.push(row?);
+            buckets.push(Self::refilled(row?, now));
         }
         Ok(buckets)
     }
 
+    /// Take `amount` tokens if available. Refills and consumption are written
+    /// in the same statement, so a crash never loses or double-spends tokens.
+    /// Returns false if the bucket doesn't exist or is short on tokens.
     pub fn consume_token(&self, name: &str, amount: i64) -> Result<bool> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .context("System time before UNIX epoch")?
-            .as_secs() as i64;
+        let now = Self::now()?;
 
-        // Get bucket and refill if needed
-        let bucket: Option<RateLimitBucket> = conn
+        let bucket = conn
             .query_row(
                 "SELECT name, capacity, tokens, refill_rate, refill_interval, last_refill
                  FROM rate_limit_buckets WHERE name = ?1",
                 params![name],
-                |row| {
-                    Ok(RateLimitBucket {
-                        name: row.get(0)?,
-                        capacity: row.get(1)?,
-                        tokens: row.get(2)?,
-                        refill_rate: row.get(3)?,
-                        refill_interval: row.get(4)?,
-                        last_refill: row.get(5)?,
-                    })
-                },
+                Self::row_to_bucket,
             )
             .optional()?;
 
-        if let Some(mut bucket) = bucket {
-            // Refill tokens if interval has passed
-            let elapsed = now - bucket.last_refill;
-            if elapsed >= bucket.refill_interval {
-                let refills = elapsed / bucket.refill_interval;
-                bucket.tokens = (bucket.tokens + bucket.refill_rate * refills).min(bucket.capacity);
-                bucket.last_refill = now;
-            }
-
-            // Check if we have enough tokens
-            if bucket.tokens >= amount {
-                bucket.tokens -= amount;
-                
-                // Update bucket
-                conn.execute(
-                    "UPDATE rate_limit_buckets 
-                     SET tokens = ?1, last_refill = ?2 
-                     WHERE name = ?3",
-                    params![bucket.tokens, bucket.last_refill, name],
-                )?;
-                
-                Ok(true)
-            } else {
-                // Update last_refill even if we can't consume
-                conn.execute(
-                    "UPDATE rate_limit_buckets SET last_refill = ?1 WHERE name = ?2",
-                    params![now, name],
-                )?;
-                Ok(false)
-            }
-        } else {
-            Ok(false)
+        let mut bucket = match bucket {
+            Some(b) => Self::refilled(b, now),
+            None => return Ok(false),
+        };
+
+        let consumed = bucket.tokens >= amount;
+        if consumed {
+            bucket.tokens -= amount;
         }
+
+        conn.execute(
+            "UPDATE rate_limit_buckets
+             SET tokens = ?1, last_refill = ?2
+             WHERE name = ?3",
+            params![bucket.tokens, bucket.last_refill, name],
+        )?;
+
+        Ok(consumed)
     }
 
     pub fn refill_bucket(&self, name: &str) -> Result<()> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .context("System time before UNIX epoch")?
-            .as_secs() as i64;
+        let now = Self::now()?;
 
-        let bucket: Option<RateLimitBucket> = conn
+        let bucket = conn
             .query_row(
                 "SELECT name, capacity, tokens, refill_rate, refill_interval, last_refill
                  FROM rate_limit_buckets WHERE name = ?1",
                 params![name],
-                |row| {
-                    Ok(RateLimitBucket {
-                        name: row.get(0)?,
-                        capacity: row.get(1)?,
-                        tokens: row.get(2)?,
-                        refill_rate: row.get(3)?,
-                        refill_interval: row.get(4)?,
-                        last_refill: row.get(5)?,
-                    })
-                },
+                Self::row_to_bucket,
             )
             .optional()?;
 
-        if let Some(mut bucket) = bucket {
-            let elapsed = now - bucket.last_refill;
-            if elapsed >= bucket.refill_interval {
-                let refills = elapsed / bucket.refill_interval;
-                bucket.tokens = (bucket.tokens + bucket.refill_rate * refills).min(bucket.capacity);
-                bucket.last_refill = now;
-
-                conn.execute(
-                    "UPDATE rate_limit_buckets 
-                     SET tokens = ?1, last_refill = ?2 
-                     WHERE name = ?3",
-                    params![bucket.tokens, bucket.last_refill, name],
-                )?;
-            }
+        if let Some(bucket) = bucket {
+            let bucket = Self::refilled(bucket, now);
+            conn.execute(
+                "UPDATE rate_limit_buckets 
+                 SET tokens = ?1, last_refill = ?2 
+                 WHERE name = ?3",
+                params![bucket.tokens, bucket.last_refill, name],
+            )?;
         }
 
         Ok(())
     }
-}
 
+    /// Apply whole refill intervals elapsed since `last_refill`. The refill
+    /// clock only advances by whole intervals so partial progress isn't lost.
+    fn refilled(mut bucket: RateLimitBucket, now: i64) -> RateLimitBucket {
+        if bucket.refill_interval <= 0 {
+            return bucket;
+        }
+        let elapsed = now - bucket.last_refill;
+        if elapsed >= bucket.refill_interval {
+            let refills = elapsed / bucket.refill_interval;
+            bucket.tokens = (bucket.tokens + bucket.refill_rate * refills).min(bucket.capacity);
+            bucket.last_refill += refills * bucket.refill_interval;
+        }
+        bucket
+    }
+
+    fn row_to_bucket(row: &rusqlite::Row<'_>) -> rusqlite::Result<RateLimitBucket> {
+        Ok(RateLimitBucket {
+            name: row.get(0)?,
+            capacity: row.get(1)?,
+            tokens: row.get(2)?,
+            refill_rate: row.get(3)?,
+            refill_interval: row.get(4)?,
+            last_refill: row.get(5)?,
+        })
+    }
+
+    fn now() -> Result<i64> {
+        Ok(SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("System time before UNIX epoch")?
+            .as_secs() as i64)
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// A machine the devops tools can wake, ping and run commands on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteHost {
+    pub id: i64,
+    pub name: String,
+    /// For wake-on-LAN, e.g. "aa:bb:cc:dd:ee:ff"
+    pub mac_address: Option<String>,
+    /// Hostname or IP to ping
+    pub address: Option<String>,
+    /// Host alias from ~/.ssh/config, or "user@host"
+    pub ssh_target: Option<String>,
+    /// Broadcast address for magic packets; 255.255.255.255 when unset
+    pub broadcast_address: Option<String>,
+    /// Raise a devops alert when an action on this host fails
+    pub alert_on_failure: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewRemoteHost {
+    pub name: String,
+    pub mac_address: Option<String>,
+    pub address: Option<String>,
+    pub ssh_target: Option<String>,
+    pub broadcast_address: Option<String>,
+    #[serde(default)]
+    pub alert_on_failure: bool,
+}
+
+/// One wake, ping or SSH command run against a host.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteHostAction {
+    pub id: i64,
+    pub host_id: i64,
+    pub action: String, // wake|ping|ssh
+    pub command: Option<String>,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub output: String,
+    pub duration_ms: i64,
+    pub created_at: i64,
+}
+
+pub struct RemoteHostStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl RemoteHostStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = RemoteHostStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: RemoteHostStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS remote_hosts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                mac_address TEXT,
+                address TEXT,
+                ssh_target TEXT,
+                broadcast_address TEXT,
+                alert_on_failure INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS remote_host_actions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                host_id INTEGER NOT NULL,
+                action TEXT NOT NULL,
+                command TEXT,
+                success INTEGER NOT NULL,
+                exit_code INTEGER,
+                output TEXT NOT NULL DEFAULT '',
+                duration_ms INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (host_id) REFERENCES remote_hosts(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_remote_host_actions_host ON remote_host_actions(host_id, created_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_host(&self, host: &NewRemoteHost) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "INSERT INTO remote_hosts (name, mac_address, address, ssh_target, broadcast_address, alert_on_failure, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+            params![
+                host.name,
+                host.mac_address,
+                host.address,
+                host.ssh_target,
+                host.broadcast_address,
+                host.alert_on_failure as i32,
+                now,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn update_host(&self, id: i64, host: &NewRemoteHost) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let updated = conn.execute(
+            "UPDATE remote_hosts SET
+                name = ?1, mac_address = ?2, address = ?3, ssh_target = ?4, broadcast_address = ?5,
+                alert_on_failure = ?6, updated_at = ?7
+             WHERE id = ?8",
+            params![
+                host.name,
+                host.mac_address,
+                host.address,
+                host.ssh_target,
+                host.broadcast_address,
+                host.alert_on_failure as i32,
+                chrono::Utc::now().timestamp(),
+                id,
+            ],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Remote host {} not found", id);
+        }
+        Ok(())
+    }
+
+    pub fn get_host(&self, id: i64) -> Result<Option<RemoteHost>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, name, mac_address, address, ssh_target, broadcast_address, alert_on_failure, created_at, updated_at
+             FROM remote_hosts WHERE id = ?1",
+            params![id],
+            row_to_host,
+        )
+        .optional()
+        .map_err(anyhow::Error::from)
+    }
+
+    pub fn list_hosts(&self) -> Result<Vec<RemoteHost>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, mac_address, address, ssh_target, broadcast_address, alert_on_failure, created_at, updated_at
+             FROM remote_hosts ORDER BY name",
+        )?;
+        let rows = stmt.query_map([], row_to_host)?;
+
+        let mut hosts = Vec::new();
+        for row in rows {
+            hosts.push(row?);
+        }
+        Ok(hosts)
+    }
+
+    pub fn delete_host(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM remote_host_actions WHERE host_id = ?1", params![id])?;
+        conn.execute("DELETE FROM remote_hosts WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn record_action(&self, action: &RemoteHostAction) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO remote_host_actions (host_id, action, command, success, exit_code, output, duration_ms, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                action.host_id,
+                action.action,
+                action.command,
+                action.success as i32,
+                action.exit_code,
+                action.output,
+                action.duration_ms,
+                action.created_at,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Newest first, optionally for one host.
+    pub fn list_actions(&self, host_id: Option<i64>, limit: i64) -> Result<Vec<RemoteHostAction>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, host_id, action, command, success, exit_code, output, duration_ms, created_at
+             FROM remote_host_actions
+             WHERE ?1 IS NULL OR host_id = ?1
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![host_id, limit], |row| {
+            Ok(RemoteHostAction {
+                id: row.get(0)?,
+                host_id: row.get(1)?,
+                action: row.get(2)?,
+                command: row.get(3)?,
+                success: row.get::<_, i32>(4)? == 1,
+                exit_code: row.get(5)?,
+                output: row.get(6)?,
+                duration_ms: row.get(7)?,
+                created_at: row.get(8)?,
+            })
+        })?;
+
+        let mut actions = Vec::new();
+        for row in rows {
+            actions.push(row?);
+        }
+        Ok(actions)
+    }
+}
+
+fn row_to_host(row: &rusqlite::Row) -> rusqlite::Result<RemoteHost> {
+    Ok(RemoteHost {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        mac_address: row.get(2)?,
+        address: row.get(3)?,
+        ssh_target: row.get(4)?,
+        broadcast_address: row.get(5)?,
+        alert_on_failure: row.get::<_, i32>(6)? == 1,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
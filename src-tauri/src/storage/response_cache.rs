@@ -0,0 +1,142 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Responses nobody asked for again in this long are dropped on the next write
+const MAX_AGE_SECS: i64 = 30 * 86400;
+
+/// A provider response as last fetched. `payload` is the serialized value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub cache_key: String,
+    pub kind: String, // quote|history|news
+    pub provider: Option<String>,
+    pub payload: String,
+    pub fetched_at: i64,
+}
+
+pub struct ResponseCacheStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ResponseCacheStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = ResponseCacheStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: ResponseCacheStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_response_cache (
+                cache_key TEXT PRIMARY KEY,
+                kind TEXT NOT NULL,
+                provider TEXT,
+                payload TEXT NOT NULL,
+                fetched_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_provider_response_cache_fetched ON provider_response_cache(fetched_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, cache_key: &str) -> Result<Option<CachedResponse>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let response = conn
+            .query_row(
+                "SELECT cache_key, kind, provider, payload, fetched_at FROM provider_response_cache WHERE cache_key = ?1",
+                params![cache_key],
+                |row| {
+                    Ok(CachedResponse {
+                        cache_key: row.get(0)?,
+                        kind: row.get(1)?,
+                        provider: row.get(2)?,
+                        payload: row.get(3)?,
+                        fetched_at: row.get(4)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(response)
+    }
+
+    /// Store a response, replacing the previous one for the key.
+    pub fn put(&self, response: &CachedResponse) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO provider_response_cache (cache_key, kind, provider, payload, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(cache_key) DO UPDATE SET
+                kind = excluded.kind, provider = excluded.provider,
+                payload = excluded.payload, fetched_at = excluded.fetched_at",
+            params![response.cache_key, response.kind, response.provider, response.payload, response.fetched_at],
+        )?;
+        conn.execute(
+            "DELETE FROM provider_response_cache WHERE fetched_at < ?1",
+            params![response.fetched_at - MAX_AGE_SECS],
+        )?;
+        Ok(())
+    }
+
+    /// Drop responses matching every given filter; keys are "kind:TICKER[:...]".
+    /// Returns how many were removed.
+    pub fn clear(&self, kind: Option<&str>, ticker: Option<&str>, provider: Option<&str>) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let removed = conn.execute(
+            "DELETE FROM provider_response_cache
+             WHERE (?1 IS NULL OR kind = ?1)
+               AND (?2 IS NULL OR cache_key = kind || ':' || ?2 OR cache_key LIKE kind || ':' || ?2 || ':%')
+               AND (?3 IS NULL OR provider = ?3 COLLATE NOCASE)",
+            params![kind, ticker.map(|t| t.to_uppercase()), provider],
+        )?;
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn response(key: &str, kind: &str, provider: &str, fetched_at: i64) -> CachedResponse {
+        CachedResponse {
+            cache_key: key.to_string(),
+            kind: kind.to_string(),
+            provider: Some(provider.to_string()),
+            payload: "{}".to_string(),
+            fetched_at,
+        }
+    }
+
+    #[test]
+    fn clear_matches_ticker_keys_and_old_rows_are_pruned() {
+        let db = test_support::test_db();
+        let store = ResponseCacheStore::new(db.conn.clone());
+        let now = chrono::Utc::now().timestamp();
+        store.put(&response("quote:AAPL", "quote", "Yahoo", now - MAX_AGE_SECS - 10)).unwrap();
+        store.put(&response("history:AAPL:0:100:1d", "history", "Yahoo", now)).unwrap();
+        store.put(&response("history:AAPLX:0:100:1d", "history", "Yahoo", now)).unwrap();
+        store.put(&response("quote:MSFT", "quote", "Polygon", now)).unwrap();
+
+        // Written long ago, dropped by the later writes
+        assert!(store.get("quote:AAPL").unwrap().is_none());
+        assert_eq!(store.clear(None, Some("aapl"), None).unwrap(), 1);
+        assert!(store.get("history:AAPLX:0:100:1d").unwrap().is_some());
+        assert_eq!(store.clear(Some("quote"), None, Some("yahoo")).unwrap(), 0);
+        assert_eq!(store.clear(None, None, None).unwrap(), 2);
+    }
+}
@@ -0,0 +1,244 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// One condition on a column. `value` is a scalar, an array for "in", a
+/// number of days for "within_days" and ignored for "is_null"/"not_null".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportFilter {
+    pub column: String,
+    pub op: String, // eq|ne|gt|gte|lt|lte|contains|in|is_null|not_null|within_days
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportAggregation {
+    pub func: String, // count|count_distinct|sum|avg|min|max
+    /// Unset only for count, meaning COUNT(*)
+    #[serde(default)]
+    pub column: Option<String>,
+    /// Result column name; defaults to e.g. "sum_amount"
+    #[serde(default)]
+    pub alias: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportOrder {
+    /// A table column, group-by column or aggregation alias
+    pub column: String,
+    #[serde(default)]
+    pub desc: bool,
+}
+
+/// A read-only query over one table, built and validated server-side.
+/// Without aggregations it selects `columns` (all when empty); with them it
+/// selects the `group_by` columns plus the aggregates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportSpec {
+    pub table: String,
+    #[serde(default)]
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub filters: Vec<ReportFilter>,
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    #[serde(default)]
+    pub aggregations: Vec<ReportAggregation>,
+    #[serde(default)]
+    pub order_by: Vec<ReportOrder>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedReport {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub spec: ReportSpec,
+    /// Run and export every this many hours; unset for manual runs only
+    pub schedule_hours: Option<i64>,
+    pub export_format: String, // csv|json
+    /// Folder scheduled exports are written to
+    pub export_dir: Option<String>,
+    pub last_run_at: Option<i64>,
+    pub last_output: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewSavedReport {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub spec: ReportSpec,
+    #[serde(default)]
+    pub schedule_hours: Option<i64>,
+    #[serde(default)]
+    pub export_format: Option<String>,
+    #[serde(default)]
+    pub export_dir: Option<String>,
+}
+
+pub struct SavedReportStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+const REPORT_COLUMNS: &str = "id, name, description, spec_json, schedule_hours, export_format, export_dir,
+    last_run_at, last_output, last_error, created_at, updated_at";
+
+impl SavedReportStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = SavedReportStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: SavedReportStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS saved_reports (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                description TEXT,
+                spec_json TEXT NOT NULL,
+                schedule_hours INTEGER,
+                export_format TEXT NOT NULL DEFAULT 'csv',
+                export_dir TEXT,
+                last_run_at INTEGER,
+                last_output TEXT,
+                last_error TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Create, or update the report with `id`.
+    pub fn save_report(&self, id: Option<i64>, report: &NewSavedReport) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        let spec_json = serde_json::to_string(&report.spec)?;
+        let format = report.export_format.as_deref().unwrap_or("csv");
+
+        match id {
+            Some(id) => {
+                let updated = conn.execute(
+                    "UPDATE saved_reports SET name = ?1, description = ?2, spec_json = ?3, schedule_hours = ?4,
+                        export_format = ?5, export_dir = ?6, updated_at = ?7
+                     WHERE id = ?8",
+                    params![report.name, report.description, spec_json, report.schedule_hours, format, report.export_dir, now, id],
+                )?;
+                if updated == 0 {
+                    anyhow::bail!("Report {} not found", id);
+                }
+                Ok(id)
+            }
+            None => {
+                conn.execute(
+                    "INSERT INTO saved_reports (name, description, spec_json, schedule_hours, export_format, export_dir, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+                    params![report.name, report.description, spec_json, report.schedule_hours, format, report.export_dir, now],
+                )?;
+                Ok(conn.last_insert_rowid())
+            }
+        }
+    }
+
+    pub fn get_report(&self, id: i64) -> Result<Option<SavedReport>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let report = conn
+            .query_row(
+                &format!("SELECT {} FROM saved_reports WHERE id = ?1", REPORT_COLUMNS),
+                params![id],
+                row_to_report,
+            )
+            .optional()?;
+        Ok(report)
+    }
+
+    pub fn list_reports(&self) -> Result<Vec<SavedReport>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM saved_reports ORDER BY name", REPORT_COLUMNS))?;
+        let rows = stmt.query_map([], row_to_report)?;
+
+        let mut reports = Vec::new();
+        for row in rows {
+            reports.push(row?);
+        }
+        Ok(reports)
+    }
+
+    /// Scheduled reports whose interval has passed since their last run.
+    pub fn list_due_reports(&self, now: i64) -> Result<Vec<SavedReport>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM saved_reports
+             WHERE schedule_hours > 0 AND export_dir IS NOT NULL
+               AND COALESCE(last_run_at, 0) + schedule_hours * 3600 <= ?1
+             ORDER BY id",
+            REPORT_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![now], row_to_report)?;
+
+        let mut reports = Vec::new();
+        for row in rows {
+            reports.push(row?);
+        }
+        Ok(reports)
+    }
+
+    pub fn record_run(&self, id: i64, ran_at: i64, output: Option<&str>, error: Option<&str>) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE saved_reports SET last_run_at = ?1, last_output = COALESCE(?2, last_output), last_error = ?3
+             WHERE id = ?4",
+            params![ran_at, output, error, id],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_report(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM saved_reports WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+}
+
+fn row_to_report(row: &rusqlite::Row) -> rusqlite::Result<SavedReport> {
+    let spec_json: String = row.get(3)?;
+    let spec = serde_json::from_str(&spec_json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    Ok(SavedReport {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        spec,
+        schedule_hours: row.get(4)?,
+        export_format: row.get(5)?,
+        export_dir: row.get(6)?,
+        last_run_at: row.get(7)?,
+        last_output: row.get(8)?,
+        last_error: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+    })
+}
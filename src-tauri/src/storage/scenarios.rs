@@ -0,0 +1,235 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// One shock in a stress scenario.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioShock {
+    pub kind: String, // ticker|sector|fx|rates
+    /// Ticker, sector name, currency pair ("EURUSD") or empty for rates
+    #[serde(default)]
+    pub target: String,
+    /// Percent change, or basis points for rates
+    pub change: f64,
+    /// Rates only: percent price change per +100bp, keyed by ticker or sector.
+    /// Falls back to the built-in sector sensitivities when empty.
+    #[serde(default)]
+    pub sensitivities: HashMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressScenario {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub shocks: Vec<ScenarioShock>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// A stored evaluation of a scenario against a portfolio, for comparing over time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioRun {
+    pub id: i64,
+    pub scenario_id: i64,
+    pub portfolio_id: i64,
+    pub portfolio_value: f64,
+    pub total_impact: f64,
+    pub impact_percent: f64,
+    pub result: serde_json::Value,
+    pub run_at: i64,
+}
+
+pub struct ScenarioStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ScenarioStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = ScenarioStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: ScenarioStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS stress_scenarios (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                description TEXT,
+                shocks TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS scenario_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                scenario_id INTEGER NOT NULL,
+                portfolio_id INTEGER NOT NULL,
+                portfolio_value REAL NOT NULL,
+                total_impact REAL NOT NULL,
+                impact_percent REAL NOT NULL,
+                result TEXT NOT NULL,
+                run_at INTEGER NOT NULL,
+                FOREIGN KEY (scenario_id) REFERENCES stress_scenarios(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_scenario_runs_scenario ON scenario_runs(scenario_id, portfolio_id, run_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Create a scenario, or replace the shocks of the one with the same name.
+    pub fn save_scenario(&self, name: &str, description: Option<&str>, shocks: &[ScenarioShock]) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        let shocks_json = serde_json::to_string(shocks)?;
+
+        conn.execute(
+            "INSERT INTO stress_scenarios (name, description, shocks, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(name) DO UPDATE SET
+                description = excluded.description,
+                shocks = excluded.shocks,
+                updated_at = excluded.updated_at",
+            params![name, description, shocks_json, now],
+        )?;
+
+        Ok(conn.query_row(
+            "SELECT id FROM stress_scenarios WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )?)
+    }
+
+    pub fn get_scenario(&self, id: i64) -> Result<Option<StressScenario>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let row = conn
+            .query_row(
+                "SELECT id, name, description, shocks, created_at, updated_at
+                 FROM stress_scenarios WHERE id = ?1",
+                params![id],
+                row_to_scenario,
+            )
+            .optional()?;
+        row.map(parse_scenario).transpose()
+    }
+
+    pub fn list_scenarios(&self) -> Result<Vec<StressScenario>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, shocks, created_at, updated_at
+             FROM stress_scenarios ORDER BY name ASC",
+        )?;
+        let rows = stmt.query_map([], row_to_scenario)?;
+
+        let mut scenarios = Vec::new();
+        for row in rows {
+            scenarios.push(parse_scenario(row?)?);
+        }
+        Ok(scenarios)
+    }
+
+    pub fn delete_scenario(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM scenario_runs WHERE scenario_id = ?1", params![id])?;
+        conn.execute("DELETE FROM stress_scenarios WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn record_run(
+        &self,
+        scenario_id: i64,
+        portfolio_id: i64,
+        portfolio_value: f64,
+        total_impact: f64,
+        impact_percent: f64,
+        result: &serde_json::Value,
+    ) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO scenario_runs (scenario_id, portfolio_id, portfolio_value, total_impact, impact_percent, result, run_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                scenario_id,
+                portfolio_id,
+                portfolio_value,
+                total_impact,
+                impact_percent,
+                result.to_string(),
+                chrono::Utc::now().timestamp(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Past runs of a scenario for one portfolio, newest first.
+    pub fn list_runs(&self, scenario_id: i64, portfolio_id: i64, limit: Option<i64>) -> Result<Vec<ScenarioRun>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let limit = limit.unwrap_or(50).clamp(1, 1000);
+        let mut stmt = conn.prepare(
+            "SELECT id, scenario_id, portfolio_id, portfolio_value, total_impact, impact_percent, result, run_at
+             FROM scenario_runs
+             WHERE scenario_id = ?1 AND portfolio_id = ?2
+             ORDER BY run_at DESC, id DESC
+             LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![scenario_id, portfolio_id, limit], |row| {
+            let result: String = row.get(6)?;
+            Ok(ScenarioRun {
+                id: row.get(0)?,
+                scenario_id: row.get(1)?,
+                portfolio_id: row.get(2)?,
+                portfolio_value: row.get(3)?,
+                total_impact: row.get(4)?,
+                impact_percent: row.get(5)?,
+                result: serde_json::from_str(&result).unwrap_or(serde_json::Value::Null),
+                run_at: row.get(7)?,
+            })
+        })?;
+
+        let mut runs = Vec::new();
+        for row in rows {
+            runs.push(row?);
+        }
+        Ok(runs)
+    }
+}
+
+type ScenarioRow = (i64, String, Option<String>, String, i64, i64);
+
+fn row_to_scenario(row: &rusqlite::Row) -> rusqlite::Result<ScenarioRow> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?))
+}
+
+fn parse_scenario((id, name, description, shocks, created_at, updated_at): ScenarioRow) -> Result<StressScenario> {
+    Ok(StressScenario {
+        id,
+        name,
+        description,
+        shocks: serde_json::from_str(&shocks)?,
+        created_at,
+        updated_at,
+    })
+}
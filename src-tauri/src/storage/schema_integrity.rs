@@ -0,0 +1,443 @@
+use super::*;
+use super::sql::quote_ident;
+use anyhow::Result;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// One difference between a table in the database and the shape the stores
+/// create today.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaDrift {
+    pub table: String,
+    pub column: Option<String>,
+    pub kind: String, // missing_table|missing_column|type_mismatch|extra_column
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+    pub repair: String, // create_table|add_column|rebuild|none
+    pub status: String, // pending|repaired|failed
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaReport {
+    pub checked_tables: usize,
+    pub drifts: Vec<SchemaDrift>,
+    pub checked_at: i64,
+}
+
+impl SchemaReport {
+    /// Drifts a repair didn't fix or that need a guided rebuild.
+    pub fn unresolved(&self) -> Vec<&SchemaDrift> {
+        self.drifts
+            .iter()
+            .filter(|d| d.repair != "none" && d.status != "repaired")
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ColumnInfo {
+    name: String,
+    decl_type: String,
+    not_null: bool,
+    default: Option<String>,
+    pk: bool,
+}
+
+#[derive(Debug, Clone)]
+struct TableShape {
+    name: String,
+    sql: String,
+    columns: Vec<ColumnInfo>,
+    /// CREATE INDEX / CREATE TRIGGER statements on the table
+    dependents: Vec<String>,
+}
+
+/// Create every store's tables on `conn`, in the order the app does at
+/// startup. Stores opened lazily by commands are included.
+pub fn init_all_stores(conn: Arc<Mutex<Connection>>) -> Result<()> {
+    RateLimitStore::new(conn.clone())?;
+    let _ = TestingStore::new(conn.clone());
+    let _ = AnalyticsStore::new(conn.clone());
+    let _ = VectorStore::new(conn.clone());
+    let _ = ProfileStore::new(conn.clone());
+    AIStore::new(conn.clone())?;
+    AutomationStore::new(conn.clone())?;
+    let _ = DevOpsStore::new(conn.clone());
+    let _ = ClipboardStore::new(conn.clone());
+    let _ = OSINTStore::new(conn.clone());
+    let _ = TemporalStore::new(conn.clone());
+    let _ = FxStore::new(conn.clone());
+    let _ = ProjectStore::new(conn.clone());
+    let _ = TagStore::new(conn.clone());
+    let _ = TimeTrackingStore::new(conn.clone());
+    let _ = BenchmarkStore::new(conn.clone());
+    let _ = MigrationTracker::new(conn.clone());
+    StockNewsStore::new(conn.clone()).init_schema()?;
+
+    AuthManager::new(conn.clone())?;
+    api_keys::APIKeyStore::new(conn.clone())?;
+    let _ = MarketDataStore::new(conn.clone());
+    let _ = PortfolioStore::new(conn.clone());
+    let _ = EconomicCalendarStore::new(conn.clone());
+    let _ = MessagingStore::new(conn.clone());
+    let _ = GridLayoutStore::new(conn.clone());
+    let _ = PriceAlertStore::new(conn.clone());
+    let _ = PortfolioPerformanceStore::new(conn.clone());
+    let _ = MemoryStore::new(conn.clone());
+    let _ = LlmUsageStore::new(conn.clone());
+    let _ = ScenarioStore::new(conn.clone());
+    let _ = DocumentStore::new(conn.clone());
+    let _ = TranscriptStore::new(conn.clone());
+    let _ = JobStore::new(conn.clone());
+    let _ = OutboxStore::new(conn.clone());
+    let _ = WindowStore::new(conn.clone());
+    let _ = PaletteStore::new(conn.clone());
+    let _ = DashboardStore::new(conn.clone());
+    let _ = ProcessPolicyStore::new(conn.clone());
+    let _ = RemoteHostStore::new(conn.clone());
+    let _ = SshTunnelStore::new(conn.clone());
+    let _ = ModelBenchmarkStore::new(conn.clone());
+    let _ = WatchFolderStore::new(conn.clone());
+    let _ = StatementImportStore::new(conn.clone());
+    let _ = DbHousekeepingStore::new(conn.clone());
+    let _ = SavedReportStore::new(conn);
+    Ok(())
+}
+
+/// Startup check that compares the real tables with a reference database
+/// built in memory by the same stores, so databases from old builds are
+/// fixed up front instead of failing at query time.
+pub struct SchemaIntegrity;
+
+impl SchemaIntegrity {
+    pub fn verify(conn: &Arc<Mutex<Connection>>) -> Result<SchemaReport> {
+        let expected = Self::expected()?;
+        let conn = conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let actual = read_shapes(&conn)?;
+        Ok(compare(&expected, &actual))
+    }
+
+    /// Create missing tables and add missing columns. Tables listed in
+    /// `rebuild` are recreated with the expected shape and their rows copied
+    /// over; that covers drift `ALTER TABLE` can't fix.
+    pub fn repair(conn: &Arc<Mutex<Connection>>, rebuild: &[String]) -> Result<SchemaReport> {
+        let expected = Self::expected()?;
+        let conn = conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let actual = read_shapes(&conn)?;
+        let mut report = compare(&expected, &actual);
+
+        for table in rebuild {
+            let Some(shape) = expected.get(table) else {
+                anyhow::bail!("Unknown table: {}", table);
+            };
+            let Some(current) = actual.get(table) else { continue };
+            let columns: Vec<String> = current.columns.iter().map(|c| c.name.clone()).collect();
+            let result = rebuild_table(&conn, shape, &columns);
+            for drift in report.drifts.iter_mut().filter(|d| &d.table == table && d.repair != "create_table") {
+                apply_result(drift, &result);
+            }
+        }
+
+        for drift in report.drifts.iter_mut().filter(|d| d.status == "pending") {
+            let Some(shape) = expected.get(&drift.table) else { continue };
+            let result = match drift.repair.as_str() {
+                "create_table" => create_table(&conn, shape),
+                "add_column" => {
+                    let column = shape
+                        .columns
+                        .iter()
+                        .find(|c| Some(&c.name) == drift.column.as_ref())
+                        .expect("drift column comes from the expected shape");
+                    // sql-audit: column definitions come from our own CREATE statements
+                    conn.execute(&format!("ALTER TABLE {} ADD COLUMN {}", quote_ident(&shape.name), column_def(column)), [])
+                        .map(|_| ())
+                        .map_err(Into::into)
+                }
+                _ => continue,
+            };
+            apply_result(drift, &result);
+            if result.is_err() && drift.repair == "add_column" {
+                drift.repair = "rebuild".to_string();
+            }
+        }
+        Ok(report)
+    }
+
+    fn expected() -> Result<BTreeMap<String, TableShape>> {
+        let reference = Database::open_in_memory()?;
+        init_all_stores(reference.conn.clone())?;
+        let conn = reference.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        read_shapes(&conn)
+    }
+}
+
+fn apply_result(drift: &mut SchemaDrift, result: &Result<()>) {
+    match result {
+        Ok(()) => {
+            drift.status = "repaired".to_string();
+            drift.error = None;
+        }
+        Err(e) => {
+            drift.status = "failed".to_string();
+            drift.error = Some(e.to_string());
+        }
+    }
+}
+
+fn compare(expected: &BTreeMap<String, TableShape>, actual: &BTreeMap<String, TableShape>) -> SchemaReport {
+    let mut drifts = Vec::new();
+    for (name, shape) in expected {
+        let Some(current) = actual.get(name) else {
+            drifts.push(drift(name, None, "missing_table", None, None, "create_table"));
+            continue;
+        };
+        for column in &shape.columns {
+            match current.columns.iter().find(|c| c.name == column.name) {
+                None => {
+                    let repair = if can_add_column(column) { "add_column" } else { "rebuild" };
+                    drifts.push(drift(name, Some(&column.name), "missing_column", Some(column_def(column)), None, repair));
+                }
+                Some(found) if !found.decl_type.eq_ignore_ascii_case(&column.decl_type) || found.pk != column.pk => {
+                    drifts.push(drift(
+                        name,
+                        Some(&column.name),
+                        "type_mismatch",
+                        Some(column_def(column)),
+                        Some(column_def(found)),
+                        "rebuild",
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        for column in current.columns.iter().filter(|c| !shape.columns.iter().any(|e| e.name == c.name)) {
+            drifts.push(drift(name, Some(&column.name), "extra_column", None, Some(column_def(column)), "none"));
+        }
+    }
+    SchemaReport {
+        checked_tables: expected.len(),
+        drifts,
+        checked_at: chrono::Utc::now().timestamp(),
+    }
+}
+
+fn drift(
+    table: &str,
+    column: Option<&str>,
+    kind: &str,
+    expected: Option<String>,
+    actual: Option<String>,
+    repair: &str,
+) -> SchemaDrift {
+    SchemaDrift {
+        table: table.to_string(),
+        column: column.map(|c| c.to_string()),
+        kind: kind.to_string(),
+        expected,
+        actual,
+        repair: repair.to_string(),
+        status: if repair == "none" { "ignored" } else { "pending" }.to_string(),
+        error: None,
+    }
+}
+
+fn read_shapes(conn: &Connection) -> Result<BTreeMap<String, TableShape>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, sql FROM sqlite_master
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND sql IS NOT NULL
+         ORDER BY name",
+    )?;
+    let tables: Vec<(String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // FTS shadow tables come and go with their virtual table
+    let virtual_tables: Vec<String> = tables
+        .iter()
+        .filter(|(_, sql)| sql.to_uppercase().starts_with("CREATE VIRTUAL TABLE"))
+        .map(|(name, _)| format!("{}_", name))
+        .collect();
+
+    let mut shapes = BTreeMap::new();
+    for (name, sql) in tables {
+        if virtual_tables.iter().any(|prefix| name.starts_with(prefix.as_str())) {
+            continue;
+        }
+        let mut stmt = conn.prepare("SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1)")?;
+        let columns = stmt
+            .query_map(params![name], |row| {
+                Ok(ColumnInfo {
+                    name: row.get(0)?,
+                    decl_type: row.get(1)?,
+                    not_null: row.get::<_, i64>(2)? != 0,
+                    default: row.get(3)?,
+                    pk: row.get::<_, i64>(4)? != 0,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut stmt = conn.prepare(
+            "SELECT sql FROM sqlite_master
+             WHERE type IN ('index', 'trigger') AND tbl_name = ?1 AND sql IS NOT NULL
+             ORDER BY name",
+        )?;
+        let dependents = stmt
+            .query_map(params![name], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        shapes.insert(name.clone(), TableShape { name, sql, columns, dependents });
+    }
+    Ok(shapes)
+}
+
+/// SQLite can only add columns that are nullable or have a constant default.
+fn can_add_column(column: &ColumnInfo) -> bool {
+    if column.pk {
+        return false;
+    }
+    match &column.default {
+        Some(default) => !default.starts_with('(') && !default.to_uppercase().starts_with("CURRENT_"),
+        None => !column.not_null,
+    }
+}
+
+fn column_def(column: &ColumnInfo) -> String {
+    let mut def = quote_ident(&column.name);
+    if !column.decl_type.is_empty() {
+        def.push(' ');
+        def.push_str(&column.decl_type);
+    }
+    if column.not_null {
+        def.push_str(" NOT NULL");
+    }
+    if let Some(default) = &column.default {
+        def.push_str(" DEFAULT ");
+        def.push_str(default);
+    }
+    def
+}
+
+fn create_table(conn: &Connection, shape: &TableShape) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(&shape.sql, [])?;
+    for sql in &shape.dependents {
+        tx.execute(sql, [])?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Recreate a table with the expected shape, copying the columns both
+/// versions share. Rolls back if rows don't fit the new constraints.
+fn rebuild_table(conn: &Connection, shape: &TableShape, existing_columns: &[String]) -> Result<()> {
+    let foreign_keys: i64 = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+    // Keep other tables' foreign keys pointing at the original name
+    conn.execute_batch("PRAGMA foreign_keys = OFF; PRAGMA legacy_alter_table = ON;")?;
+
+    let result = (|| -> Result<()> {
+        let old = format!("{}__rebuild", shape.name);
+        let shared: Vec<String> = shape
+            .columns
+            .iter()
+            .filter(|c| existing_columns.contains(&c.name))
+            .map(|c| quote_ident(&c.name))
+            .collect();
+
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(&format!("ALTER TABLE {} RENAME TO {}", quote_ident(&shape.name), quote_ident(&old)), [])?;
+        tx.execute(&shape.sql, [])?;
+        if !shared.is_empty() {
+            let columns = shared.join(", ");
+            tx.execute(
+                // sql-audit: columns are quoted names shared by both shapes
+                &format!("INSERT INTO {} ({}) SELECT {} FROM {}", quote_ident(&shape.name), columns, columns, quote_ident(&old)),
+                [],
+            )?;
+        }
+        tx.execute(&format!("DROP TABLE {}", quote_ident(&old)), [])?;
+        for sql in &shape.dependents {
+            tx.execute(sql, [])?;
+        }
+        tx.commit()?;
+        Ok(())
+    })();
+
+    // sql-audit: restores the integer read from the pragma above
+    conn.execute_batch(&format!("PRAGMA legacy_alter_table = OFF; PRAGMA foreign_keys = {};", foreign_keys))?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(conn: &Arc<Mutex<Connection>>) -> BTreeMap<String, TableShape> {
+        read_shapes(&conn.lock().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn drift_is_repaired_and_rebuilds_keep_rows() {
+        let db = Database::open_in_memory().unwrap();
+        db.conn
+            .lock()
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE palette_recents (
+                    kind TEXT NOT NULL,
+                    target_id TEXT NOT NULL,
+                    title TEXT NOT NULL,
+                    visited_at TEXT NOT NULL,
+                    PRIMARY KEY (kind, target_id)
+                 );
+                 INSERT INTO palette_recents VALUES ('event', '7', 'Rate decision', 1700000000);",
+            )
+            .unwrap();
+
+        let report = SchemaIntegrity::verify(&db.conn).unwrap();
+        let find = |kind: &str, column: Option<&str>| {
+            report
+                .drifts
+                .iter()
+                .find(|d| d.kind == kind && d.column.as_deref() == column && (column.is_some() || d.table == "jobs"))
+                .cloned()
+        };
+        assert_eq!(find("missing_table", None).unwrap().repair, "create_table");
+        assert_eq!(find("missing_column", Some("subtitle")).unwrap().repair, "add_column");
+        assert_eq!(find("type_mismatch", Some("visited_at")).unwrap().repair, "rebuild");
+
+        let report = SchemaIntegrity::repair(&db.conn, &[]).unwrap();
+        let unresolved = report.unresolved();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].column.as_deref(), Some("visited_at"));
+        assert!(shape(&db.conn).contains_key("jobs"));
+
+        let report = SchemaIntegrity::repair(&db.conn, &["palette_recents".to_string()]).unwrap();
+        assert!(report.unresolved().is_empty(), "{:?}", report.unresolved());
+        let title: String = db
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT title FROM palette_recents WHERE target_id = '7'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(title, "Rate decision");
+    }
+
+    #[test]
+    fn only_constant_defaults_can_be_added() {
+        let column = |not_null, default: Option<&str>| ColumnInfo {
+            name: "c".to_string(),
+            decl_type: "INTEGER".to_string(),
+            not_null,
+            default: default.map(|d| d.to_string()),
+            pk: false,
+        };
+        assert!(can_add_column(&column(false, None)));
+        assert!(can_add_column(&column(true, Some("0"))));
+        assert!(!can_add_column(&column(true, None)));
+        assert!(!can_add_column(&column(false, Some("CURRENT_TIMESTAMP"))));
+    }
+}
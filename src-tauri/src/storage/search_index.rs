@@ -0,0 +1,364 @@
+//! Tokenizer and stop-word configuration of the `fts_documents` search
+//! index, plus the optional trigram index used for substring matches
+//! (German compounds, partial tickers).
+
+use crate::storage::Database;
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// unicode61|porter|ascii
+pub const CONFIG_TOKENIZER: &str = "fts_tokenizer";
+/// 0 keeps diacritics, 1 and 2 fold them (2 also folds combining marks)
+pub const CONFIG_REMOVE_DIACRITICS: &str = "fts_remove_diacritics";
+/// Comma-separated languages whose built-in stop words are dropped from queries
+pub const CONFIG_STOP_WORD_LANGUAGES: &str = "fts_stop_word_languages";
+/// Comma-separated extra stop words
+pub const CONFIG_CUSTOM_STOP_WORDS: &str = "fts_custom_stop_words";
+/// "true" to keep a trigram index next to the token index
+pub const CONFIG_TRIGRAM: &str = "fts_trigram_index";
+/// Comma-separated ISO 639-1 codes; articles in other languages don't form
+/// events. Empty allows every language.
+pub const CONFIG_EVENT_LANGUAGES: &str = "event_allowed_languages";
+
+pub const TRIGRAM_TABLE: &str = "fts_documents_trigram";
+pub const TOKENIZERS: [&str; 3] = ["unicode61", "porter", "ascii"];
+/// Languages with a built-in stop-word list.
+pub const LANGUAGES: [&str; 4] = ["en", "de", "fr", "es"];
+
+const STOP_WORDS_EN: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "in", "is", "it", "its", "of", "on",
+    "or", "that", "the", "to", "was", "were", "will", "with",
+];
+const STOP_WORDS_DE: &[&str] = &[
+    "aber", "als", "am", "an", "auch", "auf", "aus", "bei", "das", "dass", "dem", "den", "der", "des", "die", "ein",
+    "eine", "einem", "einen", "einer", "es", "für", "hat", "im", "in", "ist", "mit", "nach", "nicht", "oder", "sich",
+    "sie", "sind", "und", "von", "vor", "war", "wird", "zu", "zum", "zur",
+];
+const STOP_WORDS_FR: &[&str] = &[
+    "au", "aux", "avec", "ce", "dans", "de", "des", "du", "elle", "en", "est", "et", "il", "la", "le", "les", "leur",
+    "mais", "ou", "par", "pas", "pour", "qui", "sa", "se", "son", "sur", "un", "une",
+];
+const STOP_WORDS_ES: &[&str] = &[
+    "al", "como", "con", "de", "del", "el", "en", "es", "la", "las", "lo", "los", "más", "para", "pero", "por",
+    "que", "se", "su", "sus", "un", "una", "y",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchIndexSettings {
+    pub tokenizer: String,
+    pub remove_diacritics: u8,
+    pub stop_word_languages: Vec<String>,
+    pub custom_stop_words: Vec<String>,
+    pub trigram: bool,
+}
+
+impl Default for SearchIndexSettings {
+    /// What the index was created with before it was configurable.
+    fn default() -> Self {
+        SearchIndexSettings {
+            tokenizer: "unicode61".to_string(),
+            remove_diacritics: 1,
+            stop_word_languages: Vec::new(),
+            custom_stop_words: Vec::new(),
+            trigram: false,
+        }
+    }
+}
+
+impl SearchIndexSettings {
+    pub fn load(db: &Database) -> Result<Self> {
+        let conn = db.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        Ok(Self::from_conn(&conn))
+    }
+
+    /// Settings from the config table; defaults for anything unset.
+    pub fn from_conn(conn: &Connection) -> Self {
+        let get = |key: &str| -> Option<String> {
+            conn.query_row("SELECT value FROM config WHERE key = ?1", params![key], |row| row.get(0))
+                .optional()
+                .ok()
+                .flatten()
+        };
+        let list = |value: Option<String>| -> Vec<String> {
+            value
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_lowercase())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let defaults = Self::default();
+        SearchIndexSettings {
+            tokenizer: get(CONFIG_TOKENIZER)
+                .filter(|t| TOKENIZERS.contains(&t.as_str()))
+                .unwrap_or(defaults.tokenizer),
+            remove_diacritics: get(CONFIG_REMOVE_DIACRITICS)
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v <= 2)
+                .unwrap_or(defaults.remove_diacritics),
+            stop_word_languages: list(get(CONFIG_STOP_WORD_LANGUAGES)),
+            custom_stop_words: list(get(CONFIG_CUSTOM_STOP_WORDS)),
+            trigram: get(CONFIG_TRIGRAM).map(|v| v == "true").unwrap_or(defaults.trigram),
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !TOKENIZERS.contains(&self.tokenizer.as_str()) {
+            anyhow::bail!("Unknown tokenizer: {} (expected one of {})", self.tokenizer, TOKENIZERS.join(", "));
+        }
+        if self.remove_diacritics > 2 {
+            anyhow::bail!("remove_diacritics must be 0, 1 or 2");
+        }
+        if let Some(language) = self.stop_word_languages.iter().find(|l| !LANGUAGES.contains(&l.as_str())) {
+            anyhow::bail!("No stop words for language: {} (expected one of {})", language, LANGUAGES.join(", "));
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, db: &Database) -> Result<()> {
+        self.validate()?;
+        db.set_config(CONFIG_TOKENIZER, &self.tokenizer)?;
+        db.set_config(CONFIG_REMOVE_DIACRITICS, &self.remove_diacritics.to_string())?;
+        db.set_config(CONFIG_STOP_WORD_LANGUAGES, &self.stop_word_languages.join(","))?;
+        db.set_config(CONFIG_CUSTOM_STOP_WORDS, &self.custom_stop_words.join(","))?;
+        db.set_config(CONFIG_TRIGRAM, if self.trigram { "true" } else { "false" })?;
+        Ok(())
+    }
+
+    /// The FTS5 `tokenize` option for the token index.
+    pub fn tokenize_option(&self) -> String {
+        let base = match self.tokenizer.as_str() {
+            "ascii" => "ascii".to_string(),
+            _ => format!("unicode61 remove_diacritics {}", self.remove_diacritics),
+        };
+        if self.tokenizer == "porter" {
+            format!("porter {}", base)
+        } else {
+            base
+        }
+    }
+
+    pub fn stop_words(&self) -> HashSet<String> {
+        let mut words: HashSet<String> = self.custom_stop_words.iter().cloned().collect();
+        for language in &self.stop_word_languages {
+            words.extend(stop_word_list(language).iter().map(|w| w.to_string()));
+        }
+        words
+    }
+}
+
+fn stop_word_list(language: &str) -> &'static [&'static str] {
+    match language {
+        "en" => STOP_WORDS_EN,
+        "de" => STOP_WORDS_DE,
+        "fr" => STOP_WORDS_FR,
+        "es" => STOP_WORDS_ES,
+        _ => &[],
+    }
+}
+
+/// Two lowercase letters, e.g. "en".
+pub fn is_language_code(code: &str) -> bool {
+    code.len() == 2 && code.chars().all(|c| c.is_ascii_lowercase())
+}
+
+/// Languages allowed to form events; empty allows every language.
+pub fn event_languages(conn: &Connection) -> Vec<String> {
+    conn.query_row("SELECT value FROM config WHERE key = ?1", params![CONFIG_EVENT_LANGUAGES], |row| row.get::<_, String>(0))
+        .optional()
+        .ok()
+        .flatten()
+        .map(|v| v.split(',').map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// The language in LANGUAGES whose stop words dominate `text`. None when
+/// too few stop words appear to tell, as in short headlines, or on a tie.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+    let mut scores: Vec<(&'static str, usize)> = LANGUAGES
+        .iter()
+        .map(|language| {
+            let list = stop_word_list(language);
+            (*language, words.iter().filter(|w| list.contains(&w.as_str())).count())
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.cmp(&a.1));
+    match scores.as_slice() {
+        [(language, best), (_, second), ..] if *best >= 3 && best > second => Some(language),
+        _ => None,
+    }
+}
+
+/// Drop stop words from a plain word query. Queries using FTS5 syntax
+/// (quotes, prefixes, columns, groups or operators) are left as they are,
+/// and so is a query made only of stop words.
+pub fn prepare_query(query: &str, stop_words: &HashSet<String>) -> String {
+    if stop_words.is_empty() || uses_fts_syntax(query) {
+        return query.to_string();
+    }
+    let kept: Vec<&str> = query
+        .split_whitespace()
+        .filter(|term| !stop_words.contains(&term.to_lowercase()))
+        .collect();
+    if kept.is_empty() {
+        query.to_string()
+    } else {
+        kept.join(" ")
+    }
+}
+
+pub fn uses_fts_syntax(query: &str) -> bool {
+    query.contains(['"', '*', ':', '(', ')', '^', '+'])
+        || query
+            .split_whitespace()
+            .any(|term| matches!(term, "AND" | "OR" | "NOT" | "NEAR"))
+}
+
+/// Trigram query matching every term as a substring, or None when a term
+/// is too short for trigrams to find it.
+pub fn trigram_query(query: &str) -> Option<String> {
+    if uses_fts_syntax(query) {
+        return None;
+    }
+    let terms: Vec<&str> = query.split_whitespace().collect();
+    if terms.is_empty() || terms.iter().any(|t| t.chars().count() < 3) {
+        return None;
+    }
+    Some(terms.iter().map(|t| format!("\"{}\"", t)).collect::<Vec<_>>().join(" AND "))
+}
+
+pub fn trigram_index_exists(conn: &Connection) -> Result<bool> {
+    Ok(conn
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            params![TRIGRAM_TABLE],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some())
+}
+
+/// Drop and recreate the search tables with `settings`. Their content is
+/// gone afterwards; rebuild the index to fill them.
+pub fn recreate_index(conn: &Connection, settings: &SearchIndexSettings) -> Result<()> {
+    settings.validate()?;
+    conn.execute("DROP TABLE IF EXISTS fts_documents", [])?;
+    conn.execute(&format!("DROP TABLE IF EXISTS {}", TRIGRAM_TABLE), [])?;
+    // sql-audit: the tokenizer option is built from validated settings
+    conn.execute(
+        &format!(
+            "CREATE VIRTUAL TABLE fts_documents USING fts5(
+                doc_type,
+                doc_id UNINDEXED,
+                title,
+                content,
+                ts UNINDEXED,
+                tokenize = '{}'
+            )",
+            settings.tokenize_option()
+        ),
+        [],
+    )?;
+    if settings.trigram {
+        conn.execute(
+            &format!(
+                "CREATE VIRTUAL TABLE {} USING fts5(
+                    doc_type UNINDEXED,
+                    doc_id UNINDEXED,
+                    title,
+                    content,
+                    ts UNINDEXED,
+                    tokenize = 'trigram'
+                )",
+                TRIGRAM_TABLE
+            ),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Copy the token index into the trigram index, when there is one. Stores
+/// that add single documents only write the token index, so this runs at
+/// the end of every rebuild.
+pub fn sync_trigram_index(conn: &Connection) -> Result<()> {
+    if !trigram_index_exists(conn)? {
+        return Ok(());
+    }
+    conn.execute(&format!("DELETE FROM {}", TRIGRAM_TABLE), [])?;
+    conn.execute(
+        &format!(
+            "INSERT INTO {} (doc_type, doc_id, title, content, ts)
+             SELECT doc_type, doc_id, title, content, ts FROM fts_documents",
+            TRIGRAM_TABLE
+        ),
+        [],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stop_words_are_dropped_from_plain_queries_only() {
+        let settings = SearchIndexSettings {
+            stop_word_languages: vec!["de".to_string()],
+            custom_stop_words: vec!["reuters".to_string()],
+            ..Default::default()
+        };
+        let stop_words = settings.stop_words();
+        assert_eq!(prepare_query("Die Zinsen der EZB", &stop_words), "Zinsen EZB");
+        assert_eq!(prepare_query("Reuters Bund", &stop_words), "Bund");
+        assert_eq!(prepare_query("\"die Bank\"", &stop_words), "\"die Bank\"");
+        assert_eq!(prepare_query("und", &stop_words), "und");
+    }
+
+    #[test]
+    fn detects_language_from_stop_words() {
+        assert_eq!(detect_language("The central bank said it will hold rates for the rest of the year"), Some("en"));
+        assert_eq!(detect_language("Die Zentralbank hat die Zinsen nicht erhöht, sagte sie am Montag"), Some("de"));
+        assert_eq!(detect_language("La banque centrale a maintenu les taux dans la zone euro"), Some("fr"));
+        assert_eq!(detect_language("EZB: Lagarde"), None);
+    }
+
+    #[test]
+    fn trigram_index_finds_compound_parts() {
+        let conn = Connection::open_in_memory().unwrap();
+        let settings = SearchIndexSettings { remove_diacritics: 2, trigram: true, ..Default::default() };
+        assert_eq!(settings.tokenize_option(), "unicode61 remove_diacritics 2");
+        recreate_index(&conn, &settings).unwrap();
+        conn.execute(
+            "INSERT INTO fts_documents (doc_type, doc_id, title, content, ts)
+             VALUES ('rss_item', 1, 'Krankenversicherung wird teurer', 'Beiträge steigen', 0)",
+            [],
+        )
+        .unwrap();
+        sync_trigram_index(&conn).unwrap();
+
+        let count = |table: &str, query: &str| -> i64 {
+            conn.query_row(
+                &format!("SELECT COUNT(*) FROM {} WHERE {} MATCH ?1", table, table),
+                params![query],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(count("fts_documents", "versicherung"), 0);
+        assert_eq!(count(TRIGRAM_TABLE, &trigram_query("versicherung").unwrap()), 1);
+        // Diacritics are folded in the token index
+        assert_eq!(count("fts_documents", "beitrage"), 1);
+        assert!(trigram_query("EU").is_none());
+    }
+}
@@ -0,0 +1,142 @@
+//! Building blocks for statements whose shape depends on input. Values are
+//! always bound as parameters; only `IN` list placeholders, quoted
+//! identifiers and constants may be spliced into SQL text. The audit test
+//! below fails the build on any other `format!`-built query unless it
+//! carries a `// sql-audit: <why it is safe>` comment.
+
+/// "?,?,?" for an `IN (...)` list of `n` bound values.
+pub fn placeholders(n: usize) -> String {
+    vec!["?"; n].join(",")
+}
+
+/// A table or column name quoted for SQL, for names that come from the
+/// schema rather than from code.
+pub fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const SQL_KEYWORDS: [&str; 8] = ["SELECT", "INSERT", "UPDATE", "DELETE", "WHERE", "CREATE", "DROP", "PRAGMA"];
+    const SAFE_CALLS: [&str; 4] = ["placeholders(", "sql::placeholders(", "quote_ident(", "sql::quote_ident("];
+    /// Lines above a statement searched for its `// sql-audit:` comment
+    const AUDIT_WINDOW: usize = 3;
+
+    #[test]
+    fn helpers_quote_and_count() {
+        assert_eq!(placeholders(3), "?,?,?");
+        assert_eq!(placeholders(0), "");
+        assert_eq!(quote_ident("odd\"name"), "\"odd\"\"name\"");
+    }
+
+    /// Every `format!` whose template is SQL may only interpolate
+    /// constants and the helpers above, or must say why it is safe.
+    #[test]
+    fn no_values_are_formatted_into_sql() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut checked = 0;
+        let mut violations = Vec::new();
+        for dir in ["storage", "services", "commands"] {
+            for entry in std::fs::read_dir(src.join(dir)).unwrap() {
+                let path = entry.unwrap().path();
+                if path.extension().and_then(|e| e.to_str()) != Some("rs") {
+                    continue;
+                }
+                let text = std::fs::read_to_string(&path).unwrap();
+                // Tests may build whatever SQL they like
+                let code = text.split("#[cfg(test)]").next().unwrap_or("");
+                for (line, args) in sql_format_calls(code) {
+                    checked += 1;
+                    let audited = code.lines().skip(line.saturating_sub(AUDIT_WINDOW + 1)).take(AUDIT_WINDOW + 1)
+                        .any(|l| l.trim_start().starts_with("// sql-audit:"));
+                    if !audited && !args.iter().all(|a| is_safe_arg(a)) {
+                        violations.push(format!("{}:{} interpolates {:?}", path.display(), line, args));
+                    }
+                }
+            }
+        }
+        assert!(checked > 0, "the audit found no SQL to check");
+        assert!(violations.is_empty(), "values formatted into SQL; bind them instead:\n{}", violations.join("\n"));
+    }
+
+    fn is_safe_arg(arg: &str) -> bool {
+        let is_const = arg.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+            && arg.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+        is_const || SAFE_CALLS.iter().any(|call| arg.starts_with(call))
+    }
+
+    /// (1-based line, interpolated arguments) of each `format!` with an SQL
+    /// template in `code`.
+    fn sql_format_calls(code: &str) -> Vec<(usize, Vec<String>)> {
+        let mut calls = Vec::new();
+        let mut from = 0;
+        while let Some(found) = code[from..].find("format!(") {
+            let start = from + found + "format!(".len();
+            let Some((end, parts)) = split_call(&code[start..]) else { break };
+            from = start + end;
+
+            let Some(template) = parts.first() else { continue };
+            if !SQL_KEYWORDS.iter().any(|k| template.contains(k)) || !template.contains('{') {
+                continue;
+            }
+            let named = |name: &str| parts[1..].iter().any(|a| a.split('=').next().map(str::trim) == Some(name));
+            let mut args: Vec<String> = parts[1..]
+                .iter()
+                .map(|a| a.split_once('=').map(|(_, v)| v).unwrap_or(a).trim().to_string())
+                .collect();
+            // Inline captures like {bucket}
+            for hole in template.split('{').skip(1) {
+                let name = hole.split(['}', ':']).next().unwrap_or("");
+                if name.starts_with(|c: char| c.is_alphabetic() || c == '_') && !named(name) {
+                    args.push(name.to_string());
+                }
+            }
+            calls.push((code[..start].matches('\n').count() + 1, args));
+        }
+        calls
+    }
+
+    /// Split the arguments of a call starting right after its `(` at top
+    /// level commas. Returns the index of the closing `)` and the arguments.
+    fn split_call(body: &str) -> Option<(usize, Vec<String>)> {
+        let chars: Vec<(usize, char)> = body.char_indices().collect();
+        let mut parts = vec![String::new()];
+        let (mut depth, mut in_string, mut i) = (0, false, 0);
+        while i < chars.len() {
+            let (at, c) = chars[i];
+            let mut width = 1;
+            match c {
+                '\\' if in_string => width = 2,
+                '"' => in_string = !in_string,
+                // Char literals such as '"' or '\''
+                '\'' if !in_string => {
+                    let len = if chars.get(i + 1).map(|c| c.1) == Some('\\') { 4 } else { 3 };
+                    if chars.get(i + len - 1).map(|c| c.1) == Some('\'') {
+                        width = len;
+                    }
+                }
+                '(' | '[' | '{' if !in_string => depth += 1,
+                ')' | ']' | '}' if !in_string => {
+                    if depth == 0 {
+                        let parts = parts.into_iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect();
+                        return Some((at, parts));
+                    }
+                    depth -= 1;
+                }
+                ',' if !in_string && depth == 0 => {
+                    parts.push(String::new());
+                    i += 1;
+                    continue;
+                }
+                _ => {}
+            }
+            let last = parts.last_mut()?;
+            last.extend(chars[i..(i + width).min(chars.len())].iter().map(|c| c.1));
+            i += width;
+        }
+        None
+    }
+}
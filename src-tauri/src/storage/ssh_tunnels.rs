@@ -0,0 +1,185 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// An SSH local port-forward: 127.0.0.1:`local_port` on this machine
+/// reaches `remote_host`:`remote_port` as seen from `ssh_target`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshTunnel {
+    pub id: i64,
+    pub name: String,
+    /// Host alias from ~/.ssh/config, or "user@host"
+    pub ssh_target: String,
+    pub local_port: u16,
+    pub remote_host: String,
+    pub remote_port: u16,
+    /// Client routed through the tunnel while it's up: postgres|redis|qdrant
+    pub service: Option<String>,
+    /// Health check whose URL is routed through the tunnel
+    pub health_check: Option<String>,
+    /// Connect at startup
+    pub auto_start: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewSshTunnel {
+    pub name: String,
+    pub ssh_target: String,
+    pub local_port: u16,
+    #[serde(default = "default_remote_host")]
+    pub remote_host: String,
+    pub remote_port: u16,
+    pub service: Option<String>,
+    pub health_check: Option<String>,
+    #[serde(default)]
+    pub auto_start: bool,
+}
+
+fn default_remote_host() -> String {
+    "localhost".to_string()
+}
+
+pub struct SshTunnelStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SshTunnelStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = SshTunnelStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: SshTunnelStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS ssh_tunnels (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                ssh_target TEXT NOT NULL,
+                local_port INTEGER NOT NULL UNIQUE,
+                remote_host TEXT NOT NULL DEFAULT 'localhost',
+                remote_port INTEGER NOT NULL,
+                service TEXT,
+                health_check TEXT,
+                auto_start INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_tunnel(&self, tunnel: &NewSshTunnel) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "INSERT INTO ssh_tunnels
+                (name, ssh_target, local_port, remote_host, remote_port, service, health_check, auto_start, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9)",
+            params![
+                tunnel.name,
+                tunnel.ssh_target,
+                tunnel.local_port,
+                tunnel.remote_host,
+                tunnel.remote_port,
+                tunnel.service,
+                tunnel.health_check,
+                tunnel.auto_start as i32,
+                now,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn update_tunnel(&self, id: i64, tunnel: &NewSshTunnel) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let updated = conn.execute(
+            "UPDATE ssh_tunnels SET
+                name = ?1, ssh_target = ?2, local_port = ?3, remote_host = ?4, remote_port = ?5,
+                service = ?6, health_check = ?7, auto_start = ?8, updated_at = ?9
+             WHERE id = ?10",
+            params![
+                tunnel.name,
+                tunnel.ssh_target,
+                tunnel.local_port,
+                tunnel.remote_host,
+                tunnel.remote_port,
+                tunnel.service,
+                tunnel.health_check,
+                tunnel.auto_start as i32,
+                chrono::Utc::now().timestamp(),
+                id,
+            ],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("SSH tunnel {} not found", id);
+        }
+        Ok(())
+    }
+
+    pub fn get_tunnel(&self, id: i64) -> Result<Option<SshTunnel>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, name, ssh_target, local_port, remote_host, remote_port, service, health_check,
+                    auto_start, created_at, updated_at
+             FROM ssh_tunnels WHERE id = ?1",
+            params![id],
+            row_to_tunnel,
+        )
+        .optional()
+        .map_err(anyhow::Error::from)
+    }
+
+    pub fn list_tunnels(&self) -> Result<Vec<SshTunnel>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, ssh_target, local_port, remote_host, remote_port, service, health_check,
+                    auto_start, created_at, updated_at
+             FROM ssh_tunnels ORDER BY name",
+        )?;
+        let rows = stmt.query_map([], row_to_tunnel)?;
+
+        let mut tunnels = Vec::new();
+        for row in rows {
+            tunnels.push(row?);
+        }
+        Ok(tunnels)
+    }
+
+    pub fn delete_tunnel(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM ssh_tunnels WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+}
+
+fn row_to_tunnel(row: &rusqlite::Row) -> rusqlite::Result<SshTunnel> {
+    Ok(SshTunnel {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        ssh_target: row.get(2)?,
+        local_port: row.get(3)?,
+        remote_host: row.get(4)?,
+        remote_port: row.get(5)?,
+        service: row.get(6)?,
+        health_check: row.get(7)?,
+        auto_start: row.get::<_, i32>(8)? == 1,
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+    })
+}
@@ -0,0 +1,382 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+fn default_buy_values() -> Vec<String> {
+    ["buy", "bought", "b", "kauf"].iter().map(|v| v.to_string()).collect()
+}
+
+fn default_sell_values() -> Vec<String> {
+    ["sell", "sold", "s", "verkauf"].iter().map(|v| v.to_string()).collect()
+}
+
+/// How one institution's CSV export maps onto transactions. Column names
+/// are matched case-insensitively against the header row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatementMapping {
+    pub ticker: String,
+    pub quantity: String,
+    pub price: String,
+    pub date: String,
+    #[serde(default)]
+    pub transaction_type: Option<String>,
+    #[serde(default)]
+    pub fees: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Detected from the header (`,` or `;`) when unset
+    #[serde(default)]
+    pub delimiter: Option<char>,
+    /// Amounts like 1.234,56
+    #[serde(default)]
+    pub decimal_comma: bool,
+    /// chrono format of the date column, e.g. "%d.%m.%Y"; unset accepts
+    /// YYYY-MM-DD, RFC 3339 and unix seconds
+    #[serde(default)]
+    pub date_format: Option<String>,
+    /// Lines before the header row, e.g. account details
+    #[serde(default)]
+    pub skip_lines: usize,
+    /// Values of the type column meaning buy/sell; without a type column
+    /// negative quantities are sells
+    #[serde(default = "default_buy_values")]
+    pub buy_values: Vec<String>,
+    #[serde(default = "default_sell_values")]
+    pub sell_values: Vec<String>,
+}
+
+impl StatementMapping {
+    pub fn new(ticker: &str, quantity: &str, price: &str, date: &str) -> Self {
+        StatementMapping {
+            ticker: ticker.to_string(),
+            quantity: quantity.to_string(),
+            price: price.to_string(),
+            date: date.to_string(),
+            transaction_type: None,
+            fees: None,
+            notes: None,
+            delimiter: None,
+            decimal_comma: false,
+            date_format: None,
+            skip_lines: 0,
+            buy_values: default_buy_values(),
+            sell_values: default_sell_values(),
+        }
+    }
+}
+
+/// A saved mapping for one bank or broker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MappingProfile {
+    pub id: i64,
+    pub name: String,
+    pub institution: Option<String>,
+    pub mapping: StatementMapping,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// One committed statement import, undoable as a whole.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatementImport {
+    pub id: i64,
+    pub portfolio_id: i64,
+    pub profile_id: Option<i64>,
+    pub file_name: String,
+    pub file_hash: String,
+    pub rows_total: i64,
+    pub rows_imported: i64,
+    pub rows_duplicate: i64,
+    pub rows_invalid: i64,
+    pub status: String, // committed|undone
+    pub created_at: i64,
+    pub undone_at: Option<i64>,
+}
+
+/// A transaction to insert as part of an import.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatementTransaction {
+    pub ticker: String,
+    pub transaction_type: String, // buy|sell
+    pub quantity: f64,
+    pub price: f64,
+    pub transaction_date: i64,
+    pub fees: f64,
+    pub notes: Option<String>,
+}
+
+pub struct StatementImportStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl StatementImportStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = StatementImportStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: StatementImportStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS statement_mapping_profiles (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                institution TEXT,
+                mapping_json TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS statement_imports (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                portfolio_id INTEGER NOT NULL,
+                profile_id INTEGER,
+                file_name TEXT NOT NULL,
+                file_hash TEXT NOT NULL,
+                rows_total INTEGER NOT NULL DEFAULT 0,
+                rows_imported INTEGER NOT NULL DEFAULT 0,
+                rows_duplicate INTEGER NOT NULL DEFAULT 0,
+                rows_invalid INTEGER NOT NULL DEFAULT 0,
+                status TEXT NOT NULL DEFAULT 'committed',
+                created_at INTEGER NOT NULL,
+                undone_at INTEGER
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS statement_import_transactions (
+                import_id INTEGER NOT NULL,
+                transaction_id INTEGER NOT NULL,
+                PRIMARY KEY (import_id, transaction_id),
+                FOREIGN KEY (import_id) REFERENCES statement_imports(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_statement_imports_portfolio ON statement_imports(portfolio_id, created_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn save_profile(&self, name: &str, institution: Option<&str>, mapping: &StatementMapping) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "INSERT INTO statement_mapping_profiles (name, institution, mapping_json, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(name) DO UPDATE SET
+                institution = excluded.institution,
+                mapping_json = excluded.mapping_json,
+                updated_at = excluded.updated_at",
+            params![name, institution, serde_json::to_string(mapping)?, now],
+        )?;
+        let id = conn.query_row(
+            "SELECT id FROM statement_mapping_profiles WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )?;
+        Ok(id)
+    }
+
+    pub fn get_profile(&self, id: i64) -> Result<Option<MappingProfile>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let profile = conn
+            .query_row(
+                "SELECT id, name, institution, mapping_json, created_at, updated_at
+                 FROM statement_mapping_profiles WHERE id = ?1",
+                params![id],
+                row_to_profile,
+            )
+            .optional()?;
+        Ok(profile)
+    }
+
+    pub fn list_profiles(&self) -> Result<Vec<MappingProfile>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, institution, mapping_json, created_at, updated_at
+             FROM statement_mapping_profiles ORDER BY name"
+        )?;
+        let rows = stmt.query_map([], row_to_profile)?;
+
+        let mut profiles = Vec::new();
+        for row in rows {
+            profiles.push(row?);
+        }
+        Ok(profiles)
+    }
+
+    pub fn delete_profile(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM statement_mapping_profiles WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Insert the transactions and record the import in one SQL transaction.
+    pub fn commit_import(&self, import: &StatementImport, transactions: &[StatementTransaction]) -> Result<i64> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO statement_imports (portfolio_id, profile_id, file_name, file_hash, rows_total, rows_imported,
+                                            rows_duplicate, rows_invalid, status, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'committed', ?9)",
+            params![
+                import.portfolio_id,
+                import.profile_id,
+                import.file_name,
+                import.file_hash,
+                import.rows_total,
+                transactions.len() as i64,
+                import.rows_duplicate,
+                import.rows_invalid,
+                now,
+            ],
+        )?;
+        let import_id = tx.last_insert_rowid();
+
+        for t in transactions {
+            tx.execute(
+                "INSERT INTO transactions (portfolio_id, ticker, transaction_type, quantity, price, transaction_date, fees, notes, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![import.portfolio_id, t.ticker, t.transaction_type, t.quantity, t.price, t.transaction_date, t.fees, t.notes, now],
+            )?;
+            tx.execute(
+                "INSERT INTO statement_import_transactions (import_id, transaction_id) VALUES (?1, ?2)",
+                params![import_id, tx.last_insert_rowid()],
+            )?;
+        }
+        tx.commit()?;
+        Ok(import_id)
+    }
+
+    /// Delete the transactions an import created. Returns how many.
+    pub fn undo_import(&self, id: i64) -> Result<usize> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let tx = conn.transaction()?;
+        let status: Option<String> = tx
+            .query_row("SELECT status FROM statement_imports WHERE id = ?1", params![id], |row| row.get(0))
+            .optional()?;
+        match status.as_deref() {
+            None => anyhow::bail!("Import {} not found", id),
+            Some("undone") => anyhow::bail!("Import {} was already undone", id),
+            _ => {}
+        }
+        let deleted = tx.execute(
+            "DELETE FROM transactions
+             WHERE id IN (SELECT transaction_id FROM statement_import_transactions WHERE import_id = ?1)",
+            params![id],
+        )?;
+        tx.execute(
+            "UPDATE statement_imports SET status = 'undone', undone_at = ?1 WHERE id = ?2",
+            params![chrono::Utc::now().timestamp(), id],
+        )?;
+        tx.commit()?;
+        Ok(deleted)
+    }
+
+    pub fn get_import(&self, id: i64) -> Result<Option<StatementImport>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let import = conn
+            .query_row(
+                "SELECT id, portfolio_id, profile_id, file_name, file_hash, rows_total, rows_imported,
+                        rows_duplicate, rows_invalid, status, created_at, undone_at
+                 FROM statement_imports WHERE id = ?1",
+                params![id],
+                row_to_import,
+            )
+            .optional()?;
+        Ok(import)
+    }
+
+    /// The latest committed import of the same file into a portfolio.
+    pub fn find_import_by_hash(&self, portfolio_id: i64, file_hash: &str) -> Result<Option<StatementImport>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let import = conn
+            .query_row(
+                "SELECT id, portfolio_id, profile_id, file_name, file_hash, rows_total, rows_imported,
+                        rows_duplicate, rows_invalid, status, created_at, undone_at
+                 FROM statement_imports
+                 WHERE portfolio_id = ?1 AND file_hash = ?2 AND status = 'committed'
+                 ORDER BY created_at DESC LIMIT 1",
+                params![portfolio_id, file_hash],
+                row_to_import,
+            )
+            .optional()?;
+        Ok(import)
+    }
+
+    /// Newest first.
+    pub fn list_imports(&self, portfolio_id: Option<i64>, limit: i64) -> Result<Vec<StatementImport>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, portfolio_id, profile_id, file_name, file_hash, rows_total, rows_imported,
+                    rows_duplicate, rows_invalid, status, created_at, undone_at
+             FROM statement_imports
+             WHERE ?1 IS NULL OR portfolio_id = ?1
+             ORDER BY created_at DESC, id DESC
+             LIMIT ?2"
+        )?;
+        let rows = stmt.query_map(params![portfolio_id, limit], row_to_import)?;
+
+        let mut imports = Vec::new();
+        for row in rows {
+            imports.push(row?);
+        }
+        Ok(imports)
+    }
+}
+
+fn row_to_profile(row: &rusqlite::Row) -> rusqlite::Result<MappingProfile> {
+    let mapping: String = row.get(3)?;
+    Ok(MappingProfile {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        institution: row.get(2)?,
+        mapping: serde_json::from_str(&mapping)
+            .unwrap_or_else(|_| StatementMapping::new("ticker", "quantity", "price", "date")),
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+fn row_to_import(row: &rusqlite::Row) -> rusqlite::Result<StatementImport> {
+    Ok(StatementImport {
+        id: row.get(0)?,
+        portfolio_id: row.get(1)?,
+        profile_id: row.get(2)?,
+        file_name: row.get(3)?,
+        file_hash: row.get(4)?,
+        rows_total: row.get(5)?,
+        rows_imported: row.get(6)?,
+        rows_duplicate: row.get(7)?,
+        rows_invalid: row.get(8)?,
+        status: row.get(9)?,
+        created_at: row.get(10)?,
+        undone_at: row.get(11)?,
+    })
+}
@@ -1,8 +1,14 @@
+use crate::storage::sql;
 use anyhow::Result;
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 
+/// BM25 weights of the indexed columns (title, content): a hit in the
+/// headline counts for more than one in the body.
+const TITLE_WEIGHT: f64 = 10.0;
+const CONTENT_WEIGHT: f64 = 1.0;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StockTicker {
     pub id: i64,
@@ -108,6 +114,44 @@ impl StockNewsStore {
             [],
         )?;
 
+        // Full-text index over title and content, kept in sync by triggers
+        let fts_exists = conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'stock_news_fts'",
+                [],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        conn.execute(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS stock_news_fts USING fts5(
+                title,
+                content,
+                content = 'stock_news',
+                content_rowid = 'id',
+                tokenize = 'porter unicode61 remove_diacritics 2'
+            )",
+            [],
+        )?;
+        conn.execute_batch(
+            "CREATE TRIGGER IF NOT EXISTS stock_news_fts_insert AFTER INSERT ON stock_news BEGIN
+                INSERT INTO stock_news_fts (rowid, title, content) VALUES (new.id, new.title, new.content);
+             END;
+             CREATE TRIGGER IF NOT EXISTS stock_news_fts_delete AFTER DELETE ON stock_news BEGIN
+                INSERT INTO stock_news_fts (stock_news_fts, rowid, title, content)
+                VALUES ('delete', old.id, old.title, old.content);
+             END;
+             CREATE TRIGGER IF NOT EXISTS stock_news_fts_update AFTER UPDATE OF title, content ON stock_news BEGIN
+                INSERT INTO stock_news_fts (stock_news_fts, rowid, title, content)
+                VALUES ('delete', old.id, old.title, old.content);
+                INSERT INTO stock_news_fts (rowid, title, content) VALUES (new.id, new.title, new.content);
+             END;",
+        )?;
+        // News stored before the index existed
+        if !fts_exists {
+            conn.execute("INSERT INTO stock_news_fts (stock_news_fts) VALUES ('rebuild')", [])?;
+        }
+
         Ok(())
     }
 
@@ -152,17 +196,12 @@ impl StockNewsStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
 
-        let query = if let Some(index) = index_name {
-            format!(
-                "SELECT id, symbol, name, exchange, index_name, created_at FROM stock_tickers WHERE index_name = '{}' ORDER BY symbol",
-                index
-            )
-        } else {
-            "SELECT id, symbol, name, exchange, index_name, created_at FROM stock_tickers ORDER BY symbol".to_string()
-        };
-
-        let mut stmt = conn.prepare(&query)?;
-        let rows = stmt.query_map([], |row| {
+        let mut stmt = conn.prepare(
+            "SELECT id, symbol, name, exchange, index_name, created_at FROM stock_tickers
+             WHERE ?1 IS NULL OR index_name = ?1
+             ORDER BY symbol",
+        )?;
+        let rows = stmt.query_map(params![index_name], |row| {
             Ok(StockTicker {
                 id: row.get(0)?,
                 symbol: row.get(1)?,
@@ -292,7 +331,6 @@ impl StockNewsStore {
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
 
         let query = if let Some(ref ticker_list) = tickers {
-            let ticker_placeholders = ticker_list.iter().map(|_| "?").collect::<Vec<_>>().join(",");
             if let Some(_since_ts) = since {
                 format!(
                     "SELECT DISTINCT n.id, n.title, n.content, n.url, n.source, n.source_id, 
@@ -302,7 +340,7 @@ impl StockNewsStore {
                      WHERE nt.ticker IN ({}) AND n.published_at >= ?
                      ORDER BY n.published_at DESC
                      LIMIT ?",
-                    ticker_placeholders
+                    sql::placeholders(ticker_list.len())
                 )
             } else {
                 format!(
@@ -313,7 +351,7 @@ impl StockNewsStore {
                      WHERE nt.ticker IN ({})
                      ORDER BY n.published_at DESC
                      LIMIT ?",
-                    ticker_placeholders
+                    sql::placeholders(ticker_list.len())
                 )
             }
         } else if let Some(_since_ts) = since {
@@ -386,37 +424,41 @@ impl StockNewsStore {
         Ok(news_items)
     }
 
+    /// Full-text search ranked by BM25, best match first. Plain words must
+    /// all occur; FTS5 syntax is passed through for phrases ("rate cut"),
+    /// prefixes (semi*) and AND/OR/NOT. With `tickers`, only news tagged
+    /// with one of them is searched.
     pub fn search_news(&self, query: &str, tickers: Option<Vec<String>>, limit: i32) -> Result<Vec<StockNewsItem>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
-        let search_term = format!("%{}%", query);
-
-        let sql = if let Some(ref ticker_list) = tickers {
-            let ticker_placeholders = ticker_list.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-            format!(
-                "SELECT DISTINCT n.id, n.title, n.content, n.url, n.source, n.source_id, 
-                        n.published_at, n.fetched_at, n.sentiment, n.relevance_score, n.created_at
-                 FROM stock_news n
-                 JOIN stock_news_tickers nt ON n.id = nt.news_id
-                 WHERE (n.title LIKE ? OR n.content LIKE ?) AND nt.ticker IN ({})
-                 ORDER BY n.published_at DESC
-                 LIMIT ?",
-                ticker_placeholders
-            )
-        } else {
-            "SELECT id, title, content, url, source, source_id, published_at, fetched_at, 
-                    sentiment, relevance_score, created_at
-             FROM stock_news
-             WHERE title LIKE ? OR content LIKE ?
-             ORDER BY published_at DESC
-             LIMIT ?".to_string()
+        let match_query = fts_match_query(query);
+        if match_query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ticker_filter = match &tickers {
+            Some(ticker_list) if !ticker_list.is_empty() => format!(
+                "AND n.id IN (SELECT news_id FROM stock_news_tickers WHERE ticker IN ({}))",
+                sql::placeholders(ticker_list.len())
+            ),
+            _ => String::new(),
         };
+        // sql-audit: the ticker filter holds only placeholders
+        let sql = format!(
+            "SELECT n.id, n.title, n.content, n.url, n.source, n.source_id,
+                    n.published_at, n.fetched_at, n.sentiment, n.relevance_score, n.created_at
+             FROM stock_news_fts
+             JOIN stock_news n ON n.id = stock_news_fts.rowid
+             WHERE stock_news_fts MATCH ? {}
+             ORDER BY bm25(stock_news_fts, {}, {}), n.published_at DESC
+             LIMIT ?",
+            ticker_filter, TITLE_WEIGHT, CONTENT_WEIGHT
+        );
 
         let mut stmt = conn.prepare(&sql)?;
 
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
-        params_vec.push(Box::new(search_term.clone()));
-        params_vec.push(Box::new(search_term));
+        params_vec.push(Box::new(match_query));
         if let Some(ref ticker_list) = tickers {
             for ticker in ticker_list {
                 params_vec.push(Box::new(ticker.clone()));
@@ -523,6 +565,19 @@ impl StockNewsStore {
 }
 
 // S&P 500 tickers (top 50 for initial implementation, can be expanded)
+/// FTS5 query for user input. Plain words are quoted so punctuation such
+/// as in "S&P" or "e-commerce" can't break the query syntax.
+fn fts_match_query(query: &str) -> String {
+    if crate::storage::search_index::uses_fts_syntax(query) {
+        return query.trim().to_string();
+    }
+    query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn get_sp500_tickers() -> Vec<(String, String, String)> {
     vec![
         ("AAPL".to_string(), "Apple Inc.".to_string(), "NASDAQ".to_string()),
@@ -624,3 +679,55 @@ fn get_dax_tickers() -> Vec<(String, String, String)> {
     ]
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn index_name_is_bound_not_spliced() {
+        let db = test_support::test_db();
+        let store = StockNewsStore::new(db.conn.clone());
+        store.create_ticker("SAP", "SAP SE", "XETRA", "DAX").unwrap();
+        store.create_ticker("AAPL", "Apple Inc.", "NASDAQ", "S&P 500").unwrap();
+
+        let symbols = |index: Option<&str>| -> Vec<String> {
+            store.list_tickers(index).unwrap().into_iter().map(|t| t.symbol).collect()
+        };
+        assert_eq!(symbols(Some("DAX")), vec!["SAP"]);
+        assert_eq!(symbols(None).len(), 2);
+        assert!(symbols(Some("DAX' OR '1'='1")).is_empty());
+    }
+
+    #[test]
+    fn search_ranks_matches_and_filters_by_ticker() {
+        let db = test_support::test_db();
+        let store = StockNewsStore::new(db.conn.clone());
+        let news = |title: &str, content: &str, ticker: &str| {
+            let url = format!("https://news.example/{}", title.to_lowercase().replace(' ', "-"));
+            let id = store.create_news_item(title, content, &url, "Wire", None, 1_700_000_000).unwrap();
+            store.associate_ticker(id, ticker, 1.0).unwrap();
+            id
+        };
+        let body_only = news("Chipmakers rally", "Nvidia reported earnings above estimates.", "NVDA");
+        let headline = news("Nvidia earnings beat", "Data center revenue doubled.", "NVDA");
+        let other = news("Apple earnings miss", "Services growth slowed at S&P heavyweight.", "AAPL");
+
+        let ids = |query: &str, tickers: Option<Vec<String>>| -> Vec<i64> {
+            store.search_news(query, tickers, 10).unwrap().into_iter().map(|n| n.id).collect()
+        };
+        // Headline hits rank first; stemming matches "earning" to "earnings"
+        assert_eq!(ids("nvidia earning", None), vec![headline, body_only]);
+        assert_eq!(ids("\"earnings beat\"", None), vec![headline]);
+        assert_eq!(ids("earnings NOT nvidia", None), vec![other]);
+        assert_eq!(ids("earnings", Some(vec!["AAPL".to_string()])), vec![other]);
+        // Punctuation in plain words is not query syntax
+        assert_eq!(ids("S&P", None), vec![other]);
+
+        // Updates and deletes reach the index
+        db.conn.lock().unwrap().execute("UPDATE stock_news SET title = 'Nvidia guidance' WHERE id = ?1", params![headline]).unwrap();
+        assert_eq!(ids("guidance", None), vec![headline]);
+        db.conn.lock().unwrap().execute("DELETE FROM stock_news WHERE id = ?1", params![other]).unwrap();
+        assert!(ids("apple", None).is_empty());
+    }
+}
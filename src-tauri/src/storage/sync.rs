@@ -0,0 +1,286 @@
+use crate::storage::profiles::active_profile_id;
+use anyhow::Result;
+use rusqlite::{Connection, params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedFeed {
+    pub url: String,
+    pub name: String,
+    pub enabled: bool,
+    pub reliability: f64,
+    pub updated_at: i64,
+}
+
+/// Read/favorite/saved state of an article, keyed by its URL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedArticleState {
+    pub url: String,
+    pub feed_url: String,
+    pub title: String,
+    pub published_at: i64,
+    pub read: bool,
+    pub favorite: bool,
+    pub saved: bool,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedWatchlistItem {
+    pub item_type: String,
+    pub value: String,
+    pub weight: f64,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedWatchlist {
+    pub name: String,
+    pub items: Vec<SyncedWatchlistItem>,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncChanges {
+    #[serde(default)]
+    pub feeds: Vec<SyncedFeed>,
+    #[serde(default)]
+    pub article_states: Vec<SyncedArticleState>,
+    #[serde(default)]
+    pub watchlists: Vec<SyncedWatchlist>,
+}
+
+impl SyncChanges {
+    pub fn is_empty(&self) -> bool {
+        self.feeds.is_empty() && self.article_states.is_empty() && self.watchlists.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncApplyReport {
+    pub applied: usize,
+    pub skipped: usize, // local copy was newer, or the article isn't known here
+}
+
+/// Reads and applies the state that the sync subsystem keeps consistent
+/// across devices. Conflicts are resolved by timestamp: the newer side wins,
+/// ties keep the local copy. Deletions are not propagated.
+pub struct SyncStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SyncStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        SyncStore { conn }
+    }
+
+    /// Everything modified locally after `since`.
+    pub fn changes_since(&self, since: i64) -> Result<SyncChanges> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT url, name, enabled, reliability, COALESCE(updated_at, created_at)
+             FROM rss_feeds
+             WHERE COALESCE(updated_at, created_at) > ?1"
+        )?;
+        let feeds = stmt
+            .query_map(params![since], |row| {
+                Ok(SyncedFeed {
+                    url: row.get(0)?,
+                    name: row.get(1)?,
+                    enabled: row.get::<_, i64>(2)? == 1,
+                    reliability: row.get(3)?,
+                    updated_at: row.get(4)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT i.url, f.url, i.title, i.published_at, i.read, i.favorite, i.saved, i.state_updated_at
+             FROM rss_items i
+             JOIN rss_feeds f ON i.feed_id = f.id
+             WHERE i.state_updated_at > ?1"
+        )?;
+        let article_states = stmt
+            .query_map(params![since], |row| {
+                Ok(SyncedArticleState {
+                    url: row.get(0)?,
+                    feed_url: row.get(1)?,
+                    title: row.get(2)?,
+                    published_at: row.get(3)?,
+                    read: row.get::<_, i64>(4)? == 1,
+                    favorite: row.get::<_, i64>(5)? == 1,
+                    saved: row.get::<_, i64>(6)? == 1,
+                    updated_at: row.get(7)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let profile_id = active_profile_id(&conn)?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, COALESCE(updated_at, created_at)
+             FROM watchlists
+             WHERE profile_id = ?1 AND COALESCE(updated_at, created_at) > ?2"
+        )?;
+        let changed: Vec<(i64, String, i64)> = stmt
+            .query_map(params![profile_id, since], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut item_stmt = conn.prepare(
+            "SELECT item_type, value, weight, enabled FROM watchlist_items WHERE watchlist_id = ?1"
+        )?;
+        let mut watchlists = Vec::new();
+        for (id, name, updated_at) in changed {
+            let items = item_stmt
+                .query_map(params![id], |row| {
+                    Ok(SyncedWatchlistItem {
+                        item_type: row.get(0)?,
+                        value: row.get(1)?,
+                        weight: row.get(2)?,
+                        enabled: row.get::<_, i64>(3)? == 1,
+                    })
+                })?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            watchlists.push(SyncedWatchlist { name, items, updated_at });
+        }
+
+        Ok(SyncChanges { feeds, article_states, watchlists })
+    }
+
+    /// Apply changes pulled from the server. Feeds go first so article
+    /// states can be attached to feeds that only just arrived.
+    pub fn apply(&self, changes: &SyncChanges) -> Result<SyncApplyReport> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let profile_id = active_profile_id(&conn)?;
+        let tx = conn.transaction()?;
+        let mut report = SyncApplyReport::default();
+
+        for feed in &changes.feeds {
+            let local: Option<i64> = tx
+                .query_row(
+                    "SELECT COALESCE(updated_at, created_at) FROM rss_feeds WHERE url = ?1",
+                    params![feed.url],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            match local {
+                Some(ts) if ts >= feed.updated_at => report.skipped += 1,
+                Some(_) => {
+                    tx.execute(
+                        "UPDATE rss_feeds SET name = ?1, enabled = ?2, reliability = ?3, updated_at = ?4 WHERE url = ?5",
+                        params![feed.name, if feed.enabled { 1 } else { 0 }, feed.reliability, feed.updated_at, feed.url],
+                    )?;
+                    report.applied += 1;
+                }
+                None => {
+                    tx.execute(
+                        "INSERT INTO rss_feeds (url, name, enabled, reliability, created_at, profile_id, updated_at)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?5)",
+                        params![feed.url, feed.name, if feed.enabled { 1 } else { 0 }, feed.reliability, feed.updated_at, profile_id],
+                    )?;
+                    report.applied += 1;
+                }
+            }
+        }
+
+        for state in &changes.article_states {
+            let local: Option<(i64, Option<i64>)> = tx
+                .query_row(
+                    "SELECT id, state_updated_at FROM rss_items WHERE url = ?1",
+                    params![state.url],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            match local {
+                Some((_, Some(ts))) if ts >= state.updated_at => report.skipped += 1,
+                Some((id, _)) => {
+                    tx.execute(
+                        "UPDATE rss_items SET read = ?1, favorite = ?2, saved = ?3, state_updated_at = ?4 WHERE id = ?5",
+                        params![
+                            if state.read { 1 } else { 0 },
+                            if state.favorite { 1 } else { 0 },
+                            if state.saved { 1 } else { 0 },
+                            state.updated_at,
+                            id
+                        ],
+                    )?;
+                    report.applied += 1;
+                }
+                None if state.saved || state.favorite => {
+                    // Keep saved articles even if this device never fetched them;
+                    // the content fills in if the feed still carries the item
+                    let feed_id: Option<i64> = tx
+                        .query_row("SELECT id FROM rss_feeds WHERE url = ?1", params![state.feed_url], |row| row.get(0))
+                        .optional()?;
+                    match feed_id {
+                        Some(feed_id) => {
+                            tx.execute(
+                                "INSERT INTO rss_items (feed_id, title, content, url, published_at, fetched_at, read, favorite, saved, state_updated_at)
+                                 VALUES (?1, ?2, '', ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                                params![
+                                    feed_id,
+                                    state.title,
+                                    state.url,
+                                    state.published_at,
+                                    chrono::Utc::now().timestamp(),
+                                    if state.read { 1 } else { 0 },
+                                    if state.favorite { 1 } else { 0 },
+                                    if state.saved { 1 } else { 0 },
+                                    state.updated_at
+                                ],
+                            )?;
+                            report.applied += 1;
+                        }
+                        None => report.skipped += 1,
+                    }
+                }
+                None => report.skipped += 1,
+            }
+        }
+
+        for watchlist in &changes.watchlists {
+            let local: Option<(i64, i64)> = tx
+                .query_row(
+                    "SELECT id, COALESCE(updated_at, created_at) FROM watchlists WHERE name = ?1",
+                    params![watchlist.name],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            let watchlist_id = match local {
+                Some((_, ts)) if ts >= watchlist.updated_at => {
+                    report.skipped += 1;
+                    continue;
+                }
+                Some((id, _)) => id,
+                None => {
+                    tx.execute(
+                        "INSERT INTO watchlists (name, created_at, profile_id, updated_at) VALUES (?1, ?2, ?3, ?2)",
+                        params![watchlist.name, watchlist.updated_at, profile_id],
+                    )?;
+                    tx.last_insert_rowid()
+                }
+            };
+
+            // The newer side's item set replaces ours wholesale
+            tx.execute("DELETE FROM watchlist_items WHERE watchlist_id = ?1", params![watchlist_id])?;
+            for item in &watchlist.items {
+                tx.execute(
+                    "INSERT OR REPLACE INTO watchlist_items (watchlist_id, item_type, value, weight, enabled, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![watchlist_id, item.item_type, item.value, item.weight, if item.enabled { 1 } else { 0 }, watchlist.updated_at],
+                )?;
+            }
+            tx.execute(
+                "UPDATE watchlists SET updated_at = ?1 WHERE id = ?2",
+                params![watchlist.updated_at, watchlist_id],
+            )?;
+            report.applied += 1;
+        }
+
+        tx.commit()?;
+        Ok(report)
+    }
+}
@@ -0,0 +1,341 @@
+use anyhow::Result;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Kinds of object a tag can be attached to
+pub const TAG_OBJECT_TYPES: &[&str] = &["article", "event", "alert", "project", "note"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub color: Option<String>,
+    pub created_at: i64,
+}
+
+/// How much a tag is used: attachments per object type and when it was last attached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagUsage {
+    pub tag: Tag,
+    pub total: i64,
+    pub by_type: HashMap<String, i64>,
+    pub last_used_at: Option<i64>,
+}
+
+pub struct TagStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl TagStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = TagStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: TagStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let existed = table_exists(&conn, "tags")?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                color TEXT,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS tag_attachments (
+                tag_id INTEGER NOT NULL,
+                object_type TEXT NOT NULL,
+                object_id INTEGER NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (tag_id, object_type, object_id),
+                FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tag_attachments_object ON tag_attachments(object_type, object_id)",
+            [],
+        )?;
+
+        // Article folders predate tags: carry them over once, as tags on the same articles
+        if !existed && table_exists(&conn, "article_folders")? {
+            let now = chrono::Utc::now().timestamp();
+            conn.execute(
+                "INSERT OR IGNORE INTO tags (name, color, created_at) SELECT name, color, created_at FROM article_folders",
+                [],
+            )?;
+            conn.execute(
+                "INSERT OR IGNORE INTO tag_attachments (tag_id, object_type, object_id, created_at)
+                 SELECT t.id, 'article', i.id, ?1
+                 FROM rss_items i
+                 JOIN article_folders f ON f.id = i.folder_id
+                 JOIN tags t ON t.name = f.name",
+                params![now],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn create_tag(&self, name: &str, color: Option<&str>) -> Result<i64> {
+        let name = clean_name(name)?;
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO tags (name, color, created_at) VALUES (?1, ?2, ?3)",
+            params![name, color, chrono::Utc::now().timestamp()],
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::SqliteFailure(err, _) if err.code == rusqlite::ErrorCode::ConstraintViolation => {
+                anyhow::anyhow!("A tag named {} already exists", name)
+            }
+            e => e.into(),
+        })?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Rename or recolor a tag. Attachments follow it.
+    pub fn update_tag(&self, id: i64, name: Option<&str>, color: Option<&str>) -> Result<()> {
+        let name = name.map(clean_name).transpose()?;
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let updated = conn.execute(
+            "UPDATE tags SET name = COALESCE(?1, name), color = COALESCE(?2, color) WHERE id = ?3",
+            params![name, color, id],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Tag {} not found", id);
+        }
+        Ok(())
+    }
+
+    /// Delete a tag and detach it from everything.
+    pub fn delete_tag(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM tag_attachments WHERE tag_id = ?1", params![id])?;
+        conn.execute("DELETE FROM tags WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn list_tags(&self) -> Result<Vec<Tag>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare("SELECT id, name, color, created_at FROM tags ORDER BY name COLLATE NOCASE")?;
+        let rows = stmt.query_map([], row_to_tag)?;
+        let mut tags = Vec::new();
+        for row in rows {
+            tags.push(row?);
+        }
+        Ok(tags)
+    }
+
+    /// Attach a tag by name, creating the tag if it doesn't exist yet.
+    /// Returns the tag's id.
+    pub fn attach(&self, object_type: &str, object_id: i64, tag_name: &str) -> Result<i64> {
+        check_object_type(object_type)?;
+        let name = clean_name(tag_name)?;
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "INSERT OR IGNORE INTO tags (name, created_at) VALUES (?1, ?2)",
+            params![name, now],
+        )?;
+        let tag_id: i64 = conn.query_row(
+            "SELECT id FROM tags WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )?;
+        conn.execute(
+            "INSERT OR IGNORE INTO tag_attachments (tag_id, object_type, object_id, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![tag_id, object_type, object_id, now],
+        )?;
+        Ok(tag_id)
+    }
+
+    pub fn detach(&self, object_type: &str, object_id: i64, tag_id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "DELETE FROM tag_attachments WHERE tag_id = ?1 AND object_type = ?2 AND object_id = ?3",
+            params![tag_id, object_type, object_id],
+        )?;
+        Ok(())
+    }
+
+    /// Drop every tag from an object, e.g. once it has been deleted.
+    pub fn detach_all(&self, object_type: &str, object_id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "DELETE FROM tag_attachments WHERE object_type = ?1 AND object_id = ?2",
+            params![object_type, object_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn tags_for(&self, object_type: &str, object_id: i64) -> Result<Vec<Tag>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.name, t.color, t.created_at
+             FROM tags t JOIN tag_attachments ta ON ta.tag_id = t.id
+             WHERE ta.object_type = ?1 AND ta.object_id = ?2
+             ORDER BY t.name COLLATE NOCASE",
+        )?;
+        let rows = stmt.query_map(params![object_type, object_id], row_to_tag)?;
+        let mut tags = Vec::new();
+        for row in rows {
+            tags.push(row?);
+        }
+        Ok(tags)
+    }
+
+    /// Objects of one type carrying a tag, newest attachment first.
+    pub fn tagged_objects(&self, object_type: &str, tag_name: &str) -> Result<Vec<i64>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT ta.object_id FROM tag_attachments ta JOIN tags t ON t.id = ta.tag_id
+             WHERE ta.object_type = ?1 AND t.name = ?2 COLLATE NOCASE
+             ORDER BY ta.created_at DESC",
+        )?;
+        let rows = stmt.query_map(params![object_type, tag_name.trim()], |row| row.get(0))?;
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(row?);
+        }
+        Ok(ids)
+    }
+
+    /// Every tag with how often it is attached, most used first.
+    pub fn usage_stats(&self) -> Result<Vec<TagUsage>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.name, t.color, t.created_at, ta.object_type, COUNT(ta.object_id), MAX(ta.created_at)
+             FROM tags t LEFT JOIN tag_attachments ta ON ta.tag_id = t.id
+             GROUP BY t.id, ta.object_type",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row_to_tag(row)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, i64>(5)?,
+                row.get::<_, Option<i64>>(6)?,
+            ))
+        })?;
+
+        let mut usage: Vec<TagUsage> = Vec::new();
+        for row in rows {
+            let (tag, object_type, count, last_used_at) = row?;
+            let index = match usage.iter().position(|u| u.tag.id == tag.id) {
+                Some(index) => index,
+                None => {
+                    usage.push(TagUsage { tag, total: 0, by_type: HashMap::new(), last_used_at: None });
+                    usage.len() - 1
+                }
+            };
+            let entry = &mut usage[index];
+            if let Some(object_type) = object_type {
+                entry.total += count;
+                entry.by_type.insert(object_type, count);
+                entry.last_used_at = entry.last_used_at.max(last_used_at);
+            }
+        }
+        usage.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.tag.name.to_lowercase().cmp(&b.tag.name.to_lowercase())));
+        Ok(usage)
+    }
+}
+
+fn table_exists(conn: &Connection, name: &str) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+        params![name],
+        |row| row.get(0),
+    )?)
+}
+
+fn clean_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("Tag name cannot be empty");
+    }
+    Ok(name.to_string())
+}
+
+fn check_object_type(object_type: &str) -> Result<()> {
+    if !TAG_OBJECT_TYPES.contains(&object_type) {
+        anyhow::bail!("Cannot tag a {} (use {})", object_type, TAG_OBJECT_TYPES.join(", "));
+    }
+    Ok(())
+}
+
+fn row_to_tag(row: &rusqlite::Row) -> rusqlite::Result<Tag> {
+    Ok(Tag {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        color: row.get(2)?,
+        created_at: row.get(3)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn attach_creates_tags_case_insensitively_and_counts_usage() {
+        let db = test_support::test_db();
+        let store = TagStore::new(db.conn.clone());
+
+        let macro_id = store.attach("article", 1, "Macro").unwrap();
+        assert_eq!(store.attach("event", 7, " macro ").unwrap(), macro_id);
+        store.attach("event", 8, "macro").unwrap();
+        store.attach("alert", 3, "Urgent").unwrap();
+        store.create_tag("Unused", Some("#999")).unwrap();
+        assert!(store.create_tag("URGENT", None).is_err());
+        assert!(store.attach("folder", 1, "Macro").is_err());
+
+        assert_eq!(store.tagged_objects("event", "MACRO").unwrap().len(), 2);
+        let stats = store.usage_stats().unwrap();
+        assert_eq!(stats[0].tag.name, "Macro");
+        assert_eq!(stats[0].total, 3);
+        assert_eq!(stats[0].by_type.get("event"), Some(&2));
+        assert_eq!(stats.last().unwrap().total, 0);
+
+        store.delete_tag(macro_id).unwrap();
+        assert!(store.tags_for("article", 1).unwrap().is_empty());
+        assert_eq!(store.list_tags().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn article_folders_become_tags() {
+        let db = test_support::test_db();
+        let article_id = test_support::article(&db, "Wire", "Rates hold", "Central bank holds");
+        let osint = crate::storage::OSINTStore::new(db.conn.clone());
+        let folder_id = osint.create_folder("Research", Some("#0af")).unwrap();
+        osint.set_folder(article_id, Some(folder_id)).unwrap();
+        // As in a database from before tags
+        db.conn.lock().unwrap().execute_batch("DROP TABLE tag_attachments; DROP TABLE tags;").unwrap();
+
+        let store = TagStore::new(db.conn.clone());
+        let tags = store.tags_for("article", article_id).unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "Research");
+        assert_eq!(tags[0].color.as_deref(), Some("#0af"));
+    }
+}
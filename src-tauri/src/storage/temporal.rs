@@ -1,3 +1,6 @@
+use crate::storage::profiles::active_profile_id;
+use crate::services::change_feed::{self, ChangeFeed, ChangeOp};
+use crate::storage::{search_index, sql};
 use anyhow::Result;
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
@@ -21,6 +24,111 @@ pub struct TemporalEvent {
     pub cluster_key: String,
     pub created_at: i64,
     pub updated_at: i64,
+    pub lifecycle_state: String, // emerging|developing|peak|fading|dormant
+    pub previous_lifecycle_state: Option<String>,
+    pub lifecycle_changed_at: Option<i64>,
+    pub source_count: i64, // distinct feeds among the evidence
+    pub corroborated_by: i64, // independent sources, i.e. distinct article domains
+    pub reliability_tier_count: i64,
+    /// Seconds from the first article to the first one from another domain
+    pub time_to_second_source: Option<i64>,
+}
+
+/// An event produced by a detector rather than the news clustering pass.
+/// `cluster_key` identifies it so re-running the detector updates it in place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTemporalEvent {
+    pub title: String,
+    pub summary: String,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub event_type: String,
+    pub confidence: f64,
+    pub severity: f64,
+    pub novelty_score: f64,
+    pub volume_score: f64,
+    pub sentiment_score: f64,
+    pub cluster_key: String,
+}
+
+/// How fast evidence has been arriving for one event, as input to the
+/// lifecycle classification.
+#[derive(Debug, Clone)]
+pub struct EvidenceArrival {
+    pub event_id: i64,
+    pub lifecycle_state: String,
+    pub first_ts: i64,
+    pub last_ts: i64,
+    pub total: i64,
+    /// Articles published in the last window
+    pub recent: i64,
+    /// Articles published in the window before that
+    pub prior: i64,
+}
+
+/// One evidence article of an event with where it came from.
+#[derive(Debug, Clone)]
+pub struct EvidenceSource {
+    pub event_id: i64,
+    pub feed_id: i64,
+    pub article_url: String,
+    pub feed_url: String,
+    pub reliability: f64,
+    pub published_at: i64,
+}
+
+/// A news event with how much evidence it had when its summary was last
+/// written, for deciding whether the story has moved on since.
+#[derive(Debug, Clone)]
+pub struct SummaryCandidate {
+    pub event_id: i64,
+    pub severity: f64,
+    pub evidence_count: i64,
+    /// Unset until the summary has been regenerated once
+    pub summarized_evidence_count: Option<i64>,
+    pub summarized_severity: Option<f64>,
+}
+
+/// An evidence article with its text and feed, for summary selection.
+#[derive(Debug, Clone)]
+pub struct SummaryEvidence {
+    pub rss_item_id: i64,
+    pub feed_id: i64,
+    pub source: String,
+    pub reliability: f64,
+    pub title: String,
+    pub content: String,
+    pub weight: f64,
+    pub published_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryRevision {
+    pub id: i64,
+    pub event_id: i64,
+    pub revision: i64,
+    pub summary: String,
+    pub evidence_count: i64,
+    pub severity: f64,
+    pub reason: String,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Corroboration {
+    pub source_count: i64,
+    pub corroborated_by: i64,
+    pub reliability_tier_count: i64,
+    pub time_to_second_source: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleTransition {
+    pub id: i64,
+    pub event_id: i64,
+    pub from_state: Option<String>,
+    pub to_state: String,
+    pub changed_at: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +139,18 @@ pub struct TemporalEventEvidence {
     pub snippet: Option<String>,
 }
 
+/// An evidence article as shown next to an alert.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceSnippet {
+    pub rss_item_id: i64,
+    pub title: String,
+    pub url: String,
+    pub source: String,
+    pub published_at: i64,
+    pub weight: f64,
+    pub snippet: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Watchlist {
     pub id: i64,
@@ -61,6 +181,74 @@ pub struct AlertRule {
     pub created_at: i64,
 }
 
+/// Holds back alerts about one noisy entity: "mute" drops them until the
+/// override expires, "raise_threshold" only lets through events whose
+/// scores reach `min_scores` (e.g. {"severity": 0.8}).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityAlertOverride {
+    pub id: i64,
+    /// Lowercased entity name as extracted from articles
+    pub entity: String,
+    /// Only this rule; unset for every rule
+    pub rule_id: Option<i64>,
+    pub action: String, // mute|raise_threshold
+    pub min_scores: HashMap<String, f64>,
+    pub reason: Option<String>,
+    /// Unset means until deleted
+    pub expires_at: Option<i64>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewEntityAlertOverride {
+    pub entity: String,
+    #[serde(default)]
+    pub rule_id: Option<i64>,
+    pub action: String,
+    #[serde(default)]
+    pub min_scores: HashMap<String, f64>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Lasts this many days; unset until deleted
+    #[serde(default)]
+    pub days: Option<i64>,
+}
+
+/// Range auto-tuning keeps one score threshold within.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdBounds {
+    pub min: f64,
+    pub max: f64,
+}
+
+/// A rule's adaptive threshold settings. Only scores with bounds
+/// (severity|volume|sentiment) are tuned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleTuning {
+    pub rule_id: i64,
+    pub enabled: bool,
+    pub bounds: HashMap<String, ThresholdBounds>,
+    pub last_tuned_at: Option<i64>,
+    pub updated_at: i64,
+}
+
+/// One threshold change made by auto-tuning.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdAdjustment {
+    pub id: i64,
+    pub rule_id: i64,
+    pub score: String,
+    /// JSON pointer to the value in the rule's rule_json
+    pub path: String,
+    pub old_value: f64,
+    pub new_value: f64,
+    /// Labels on the rule's alerts since the previous tuning
+    pub helpful: i64,
+    pub unhelpful: i64,
+    pub tuned_at: i64,
+    pub reverted_at: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Alert {
     pub id: i64,
@@ -87,6 +275,26 @@ pub struct BacktestReport {
     pub by_rule_unhelpful: HashMap<i64, i64>,
 }
 
+/// What a watchlist item can match against on one event.
+#[derive(Debug, Clone, Default)]
+pub struct EventMatchContext {
+    pub event_id: i64,
+    pub title: String,
+    pub summary: String,
+    pub entities: Vec<String>,
+    pub sources: Vec<String>,
+    pub article_urls: Vec<String>,
+}
+
+/// A fired alert and the feedback it got, if any.
+#[derive(Debug, Clone)]
+pub struct AlertOutcome {
+    pub alert_id: i64,
+    pub rule_id: i64,
+    pub event_id: Option<i64>,
+    pub label: Option<i64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertLabel {
     pub alert_id: i64,
@@ -134,6 +342,53 @@ pub struct FeatureValue {
     pub value: f64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventCommentary {
+    pub event_id: i64,
+    pub model: String,
+    pub what_happened: String,
+    pub why_it_matters: String,
+    pub affected_tickers: Vec<String>,
+    pub created_at: i64,
+}
+
+/// Searchable summary of an event moved to the compressed archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedEvent {
+    pub event_id: i64,
+    pub title: String,
+    pub summary: String,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub event_type: String,
+    pub evidence_count: i64,
+    pub archived_at: i64,
+    pub compressed_bytes: i64,
+}
+
+/// Everything an archived event takes with it, restored together.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedEventDetail {
+    pub event: TemporalEvent,
+    pub evidence: Vec<TemporalEventEvidence>,
+    pub commentary: Option<EventCommentary>,
+    pub transitions: Vec<LifecycleTransition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventStorageUsage {
+    pub events: i64,
+    pub evidence_links: i64,
+    pub transitions: i64,
+    pub commentaries: i64,
+    pub oldest_event_ts: Option<i64>,
+    pub archived_events: i64,
+    pub archive_bytes: i64,
+    pub oldest_archived_ts: Option<i64>,
+    /// On-disk size of the live event tables, when SQLite reports it
+    pub live_table_bytes: Option<i64>,
+}
+
 pub struct TemporalStore {
     pub conn: Arc<Mutex<Connection>>,
 }
@@ -223,6 +478,76 @@ impl TemporalStore {
             [],
         )?;
 
+        // Migration: watchlists and rules belong to a user profile
+        let _ = conn.execute("ALTER TABLE watchlists ADD COLUMN profile_id INTEGER NOT NULL DEFAULT 1", []);
+        let _ = conn.execute("ALTER TABLE alert_rules ADD COLUMN profile_id INTEGER NOT NULL DEFAULT 1", []);
+        // Migration: modification time used by sync conflict resolution
+        let _ = conn.execute("ALTER TABLE watchlists ADD COLUMN updated_at INTEGER", []);
+        // Migration: story lifecycle derived from evidence arrival rate
+        let _ = conn.execute("ALTER TABLE temporal_events ADD COLUMN lifecycle_state TEXT NOT NULL DEFAULT 'emerging'", []);
+        let _ = conn.execute("ALTER TABLE temporal_events ADD COLUMN previous_lifecycle_state TEXT", []);
+        let _ = conn.execute("ALTER TABLE temporal_events ADD COLUMN lifecycle_changed_at INTEGER", []);
+        // Migration: cross-source corroboration
+        let _ = conn.execute("ALTER TABLE temporal_events ADD COLUMN source_count INTEGER NOT NULL DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE temporal_events ADD COLUMN corroborated_by INTEGER NOT NULL DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE temporal_events ADD COLUMN reliability_tier_count INTEGER NOT NULL DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE temporal_events ADD COLUMN time_to_second_source INTEGER", []);
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS temporal_event_transitions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                event_id INTEGER NOT NULL,
+                from_state TEXT,
+                to_state TEXT NOT NULL,
+                changed_at INTEGER NOT NULL,
+                FOREIGN KEY (event_id) REFERENCES temporal_events(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_temporal_event_transitions_event ON temporal_event_transitions(event_id, changed_at)",
+            [],
+        )?;
+
+        // Every summary an event has had; revision 1 is the one written at first evidence
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS temporal_event_summary_revisions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                event_id INTEGER NOT NULL,
+                revision INTEGER NOT NULL,
+                summary TEXT NOT NULL,
+                evidence_count INTEGER NOT NULL,
+                severity REAL NOT NULL,
+                reason TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                UNIQUE(event_id, revision),
+                FOREIGN KEY (event_id) REFERENCES temporal_events(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Events past the retention period keep a searchable summary here and
+        // the rest (event, evidence links, commentary, transitions) as
+        // gzip-compressed JSON until restored
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS temporal_event_archive (
+                event_id INTEGER PRIMARY KEY,
+                title TEXT NOT NULL,
+                summary TEXT NOT NULL,
+                start_ts INTEGER NOT NULL,
+                end_ts INTEGER NOT NULL,
+                event_type TEXT NOT NULL,
+                evidence_count INTEGER NOT NULL,
+                archived_at INTEGER NOT NULL,
+                detail_gz BLOB NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_temporal_event_archive_start_ts ON temporal_event_archive(start_ts)",
+            [],
+        )?;
+
         conn.execute(
             "CREATE TABLE IF NOT EXISTS alerts (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -268,6 +593,23 @@ impl TemporalStore {
             [],
         )?;
 
+        // Issue created for an alert in Jira or Linear
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS alert_issues (
+                alert_id INTEGER PRIMARY KEY,
+                provider TEXT NOT NULL,
+                issue_id TEXT NOT NULL,
+                issue_key TEXT NOT NULL,
+                issue_url TEXT,
+                status TEXT,
+                closed INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                synced_at INTEGER,
+                FOREIGN KEY (alert_id) REFERENCES alerts(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
         conn.execute(
             "CREATE TABLE IF NOT EXISTS feature_definitions (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -304,6 +646,19 @@ impl TemporalStore {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS temporal_event_commentary (
+                event_id INTEGER PRIMARY KEY,
+                model TEXT NOT NULL,
+                what_happened TEXT NOT NULL,
+                why_it_matters TEXT NOT NULL,
+                affected_tickers TEXT NOT NULL DEFAULT '[]',
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (event_id) REFERENCES temporal_events(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_temporal_events_start_ts ON temporal_events(start_ts)",
             [],
@@ -387,6 +742,52 @@ impl TemporalStore {
             "CREATE INDEX IF NOT EXISTS idx_alerts_fired_at ON alerts(fired_at)",
             [],
         )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS alert_entity_overrides (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                entity TEXT NOT NULL,
+                rule_id INTEGER,
+                action TEXT NOT NULL,
+                min_scores_json TEXT NOT NULL DEFAULT '{}',
+                reason TEXT,
+                expires_at INTEGER,
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (rule_id) REFERENCES alert_rules(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS alert_rule_tuning (
+                rule_id INTEGER PRIMARY KEY,
+                enabled INTEGER NOT NULL DEFAULT 0,
+                bounds_json TEXT NOT NULL DEFAULT '{}',
+                last_tuned_at INTEGER,
+                updated_at INTEGER NOT NULL,
+                FOREIGN KEY (rule_id) REFERENCES alert_rules(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS alert_threshold_adjustments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                rule_id INTEGER NOT NULL,
+                score TEXT NOT NULL,
+                path TEXT NOT NULL,
+                old_value REAL NOT NULL,
+                new_value REAL NOT NULL,
+                helpful INTEGER NOT NULL,
+                unhelpful INTEGER NOT NULL,
+                tuned_at INTEGER NOT NULL,
+                reverted_at INTEGER,
+                FOREIGN KEY (rule_id) REFERENCES alert_rules(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_threshold_adjustments_rule ON alert_threshold_adjustments(rule_id, tuned_at)",
+            [],
+        )?;
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_watchlist_items_watchlist ON watchlist_items(watchlist_id)",
             [],
@@ -405,75 +806,60 @@ impl TemporalStore {
     pub fn list_events(&self, limit: i64, from_ts: Option<i64>, to_ts: Option<i64>) -> Result<Vec<TemporalEvent>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
-        let row_mapper = |row: &rusqlite::Row<'_>| {
-            Ok(TemporalEvent {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                summary: row.get(2)?,
-                start_ts: row.get(3)?,
-                end_ts: row.get(4)?,
-                event_type: row.get(5)?,
-                confidence: row.get(6)?,
-                severity: row.get(7)?,
-                novelty_score: row.get(8)?,
-                volume_score: row.get(9)?,
-                sentiment_score: row.get(10)?,
-                cluster_key: row.get(11)?,
-                created_at: row.get(12)?,
-                updated_at: row.get(13)?,
-            })
-        };
-
         let mut out: Vec<TemporalEvent> = Vec::new();
 
         match (from_ts, to_ts) {
             (Some(f), Some(t)) => {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, summary, start_ts, end_ts, event_type, confidence, severity, novelty_score, volume_score, sentiment_score, cluster_key, created_at, updated_at
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {}
                      FROM temporal_events
                      WHERE end_ts >= ?1 AND start_ts <= ?2
                      ORDER BY start_ts DESC
                      LIMIT ?3",
-                )?;
-                let rows = stmt.query_map(params![f, t, limit], row_mapper)?;
+                    EVENT_COLUMNS
+                ))?;
+                let rows = stmt.query_map(params![f, t, limit], row_to_event)?;
                 for r in rows {
                     out.push(r?);
                 }
             }
             (Some(f), None) => {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, summary, start_ts, end_ts, event_type, confidence, severity, novelty_score, volume_score, sentiment_score, cluster_key, created_at, updated_at
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {}
                      FROM temporal_events
                      WHERE end_ts >= ?1
                      ORDER BY start_ts DESC
                      LIMIT ?2",
-                )?;
-                let rows = stmt.query_map(params![f, limit], row_mapper)?;
+                    EVENT_COLUMNS
+                ))?;
+                let rows = stmt.query_map(params![f, limit], row_to_event)?;
                 for r in rows {
                     out.push(r?);
                 }
             }
             (None, Some(t)) => {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, summary, start_ts, end_ts, event_type, confidence, severity, novelty_score, volume_score, sentiment_score, cluster_key, created_at, updated_at
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {}
                      FROM temporal_events
                      WHERE start_ts <= ?1
                      ORDER BY start_ts DESC
                      LIMIT ?2",
-                )?;
-                let rows = stmt.query_map(params![t, limit], row_mapper)?;
+                    EVENT_COLUMNS
+                ))?;
+                let rows = stmt.query_map(params![t, limit], row_to_event)?;
                 for r in rows {
                     out.push(r?);
                 }
             }
             (None, None) => {
-                let mut stmt = conn.prepare(
-                    "SELECT id, title, summary, start_ts, end_ts, event_type, confidence, severity, novelty_score, volume_score, sentiment_score, cluster_key, created_at, updated_at
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {}
                      FROM temporal_events
                      ORDER BY start_ts DESC
                      LIMIT ?1",
-                )?;
-                let rows = stmt.query_map(params![limit], row_mapper)?;
+                    EVENT_COLUMNS
+                ))?;
+                let rows = stmt.query_map(params![limit], row_to_event)?;
                 for r in rows {
                     out.push(r?);
                 }
@@ -483,31 +869,140 @@ impl TemporalStore {
         Ok(out)
     }
 
+    /// `list_events` narrowed to events carrying a tag.
+    pub fn list_tagged_events(&self, tag: &str, limit: i64, from_ts: Option<i64>, to_ts: Option<i64>) -> Result<Vec<TemporalEvent>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}
+             FROM temporal_events
+             WHERE (?1 IS NULL OR end_ts >= ?1) AND (?2 IS NULL OR start_ts <= ?2)
+               AND id IN (SELECT ta.object_id FROM tag_attachments ta JOIN tags t ON t.id = ta.tag_id
+                          WHERE ta.object_type = 'event' AND t.name = ?3 COLLATE NOCASE)
+             ORDER BY start_ts DESC
+             LIMIT ?4",
+            EVENT_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![from_ts, to_ts, tag.trim(), limit], row_to_event)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Events in a time range linked to a ticker or entity name, either through
+    /// an extracted entity of their evidence or by mentioning it, oldest first.
+    pub fn list_events_for_ticker(&self, ticker: &str, from_ts: i64, to_ts: i64, limit: i64) -> Result<Vec<TemporalEvent>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let ticker = ticker.trim().to_uppercase();
+        let pattern = format!("%{}%", ticker);
+
+        let linked = format!(
+            "SELECT {} FROM temporal_events e
+             WHERE e.end_ts >= ?1 AND e.start_ts <= ?2
+               AND (upper(e.title) LIKE ?3 OR upper(e.summary) LIKE ?3 OR EXISTS (
+                    SELECT 1 FROM temporal_event_evidence te
+                    JOIN extracted_entities ee ON ee.article_id = te.rss_item_id
+                    WHERE te.event_id = e.id AND upper(ee.name) = ?4))
+             ORDER BY e.start_ts ASC
+             LIMIT ?5",
+            EVENT_COLUMNS
+        );
+        // Entity extraction may not have created its table yet; match on text alone then
+        let has_entities: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='extracted_entities'",
+            [],
+            |row| row.get(0),
+        )?;
+        let mut stmt = if has_entities {
+            conn.prepare(&linked)?
+        } else {
+            conn.prepare(&format!(
+                "SELECT {} FROM temporal_events e
+                 WHERE e.end_ts >= ?1 AND e.start_ts <= ?2
+                   AND (upper(e.title) LIKE ?3 OR upper(e.summary) LIKE ?3 OR ?4 = '')
+                 ORDER BY e.start_ts ASC
+                 LIMIT ?5",
+                EVENT_COLUMNS
+            ))?
+        };
+
+        let rows = stmt.query_map(params![from_ts, to_ts, pattern, ticker, limit], row_to_event)?;
+
+        let mut events = Vec::new();
+        for row in rows {
+            events.push(row?);
+        }
+        Ok(events)
+    }
+
+    /// Insert or update an event by its cluster key and link its evidence
+    /// articles. Returns the event id and whether it was newly created.
+    pub fn upsert_event(&self, event: &NewTemporalEvent, evidence_ids: &[i64]) -> Result<(i64, bool)> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+
+        let tx = conn.transaction()?;
+        let existing: Option<i64> = tx
+            .query_row(
+                "SELECT id FROM temporal_events WHERE cluster_key = ?1",
+                params![event.cluster_key],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let id = match existing {
+            Some(id) => {
+                tx.execute(
+                    "UPDATE temporal_events
+                     SET title = ?1, summary = ?2, start_ts = ?3, end_ts = ?4, event_type = ?5, confidence = ?6,
+                         severity = ?7, novelty_score = ?8, volume_score = ?9, sentiment_score = ?10, updated_at = ?11
+                     WHERE id = ?12",
+                    params![
+                        event.title, event.summary, event.start_ts, event.end_ts, event.event_type, event.confidence,
+                        event.severity, event.novelty_score, event.volume_score, event.sentiment_score, now, id
+                    ],
+                )?;
+                id
+            }
+            None => {
+                tx.execute(
+                    "INSERT INTO temporal_events
+                     (title, summary, start_ts, end_ts, event_type, confidence, severity, novelty_score, volume_score, sentiment_score, cluster_key, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?12)",
+                    params![
+                        event.title, event.summary, event.start_ts, event.end_ts, event.event_type, event.confidence,
+                        event.severity, event.novelty_score, event.volume_score, event.sentiment_score, event.cluster_key, now
+                    ],
+                )?;
+                tx.last_insert_rowid()
+            }
+        };
+
+        for rss_item_id in evidence_ids {
+            tx.execute(
+                "INSERT OR IGNORE INTO temporal_event_evidence (event_id, rss_item_id, weight, snippet)
+                 VALUES (?1, ?2, 1.0, NULL)",
+                params![id, rss_item_id],
+            )?;
+        }
+        tx.commit()?;
+
+        let op = if existing.is_none() { ChangeOp::Insert } else { ChangeOp::Update };
+        ChangeFeed::ids(change_feed::EVENTS, op, [id]);
+        Ok((id, existing.is_none()))
+    }
+
     pub fn get_event(&self, id: i64) -> Result<Option<TemporalEvent>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         conn.query_row(
-            "SELECT id, title, summary, start_ts, end_ts, event_type, confidence, severity, novelty_score, volume_score, sentiment_score, cluster_key, created_at, updated_at
-             FROM temporal_events WHERE id = ?1",
+            &format!("SELECT {} FROM temporal_events WHERE id = ?1", EVENT_COLUMNS),
             params![id],
-            |row| {
-                Ok(TemporalEvent {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    summary: row.get(2)?,
-                    start_ts: row.get(3)?,
-                    end_ts: row.get(4)?,
-                    event_type: row.get(5)?,
-                    confidence: row.get(6)?,
-                    severity: row.get(7)?,
-                    novelty_score: row.get(8)?,
-                    volume_score: row.get(9)?,
-                    sentiment_score: row.get(10)?,
-                    cluster_key: row.get(11)?,
-                    created_at: row.get(12)?,
-                    updated_at: row.get(13)?,
-                })
-            },
+            row_to_event,
         )
         .optional()
         .map_err(Into::into)
@@ -537,28 +1032,19 @@ impl TemporalStore {
         Ok(out)
     }
 
-    pub fn create_watchlist(&self, name: &str) -> Result<i64> {
+    /// (article, event) pairs for other articles that are evidence of the same
+    /// events as this one.
+    pub fn articles_sharing_events(&self, rss_item_id: i64) -> Result<Vec<(i64, i64)>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
-        let now = chrono::Utc::now().timestamp();
-        conn.execute(
-            "INSERT INTO watchlists (name, created_at) VALUES (?1, ?2)",
-            params![name, now],
+        let mut stmt = conn.prepare(
+            "SELECT o.rss_item_id, o.event_id
+             FROM temporal_event_evidence s
+             JOIN temporal_event_evidence o ON o.event_id = s.event_id AND o.rss_item_id != s.rss_item_id
+             WHERE s.rss_item_id = ?1
+             ORDER BY o.weight DESC",
         )?;
-        Ok(conn.last_insert_rowid())
-    }
-
-    pub fn list_watchlists(&self) -> Result<Vec<Watchlist>> {
-        let conn = self.conn.lock()
-            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
-        let mut stmt = conn.prepare("SELECT id, name, created_at FROM watchlists ORDER BY name ASC")?;
-        let rows = stmt.query_map([], |row| {
-            Ok(Watchlist {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                created_at: row.get(2)?,
-            })
-        })?;
+        let rows = stmt.query_map(params![rss_item_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
         let mut out = Vec::new();
         for r in rows {
             out.push(r?);
@@ -566,8 +1052,408 @@ impl TemporalStore {
         Ok(out)
     }
 
-    pub fn add_watchlist_item(
-        &self,
+    /// The `limit` strongest evidence articles of an event with their feed.
+    pub fn evidence_snippets(&self, event_id: i64, limit: i64) -> Result<Vec<EvidenceSnippet>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT te.rss_item_id, i.title, i.url, f.name, i.published_at, te.weight, te.snippet
+             FROM temporal_event_evidence te
+             JOIN rss_items i ON i.id = te.rss_item_id
+             JOIN rss_feeds f ON f.id = i.feed_id
+             WHERE te.event_id = ?1
+             ORDER BY te.weight DESC, i.published_at ASC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![event_id, limit], |row| {
+            Ok(EvidenceSnippet {
+                rss_item_id: row.get(0)?,
+                title: row.get(1)?,
+                url: row.get(2)?,
+                source: row.get(3)?,
+                published_at: row.get(4)?,
+                weight: row.get(5)?,
+                snippet: row.get(6)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Evidence arrival counts for events that are not yet dormant or ended
+    /// after `active_since`. `recent` covers [now - window, now], `prior`
+    /// the window before it.
+    pub fn evidence_arrival(&self, active_since: i64, now: i64, window: i64) -> Result<Vec<EvidenceArrival>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT e.id, e.lifecycle_state, MIN(r.published_at), MAX(r.published_at), COUNT(*),
+                    SUM(CASE WHEN r.published_at > ?2 - ?3 AND r.published_at <= ?2 THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN r.published_at > ?2 - 2 * ?3 AND r.published_at <= ?2 - ?3 THEN 1 ELSE 0 END)
+             FROM temporal_events e
+             JOIN temporal_event_evidence te ON te.event_id = e.id
+             JOIN rss_items r ON r.id = te.rss_item_id
+             WHERE e.lifecycle_state != 'dormant' OR e.end_ts >= ?1
+             GROUP BY e.id",
+        )?;
+        let rows = stmt.query_map(params![active_since, now, window], |row| {
+            Ok(EvidenceArrival {
+                event_id: row.get(0)?,
+                lifecycle_state: row.get(1)?,
+                first_ts: row.get(2)?,
+                last_ts: row.get(3)?,
+                total: row.get(4)?,
+                recent: row.get(5)?,
+                prior: row.get(6)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Evidence articles with their feed, for events ending after `active_since`.
+    pub fn evidence_sources(&self, active_since: i64) -> Result<Vec<EvidenceSource>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT te.event_id, r.feed_id, r.url, f.url, f.reliability, r.published_at
+             FROM temporal_events e
+             JOIN temporal_event_evidence te ON te.event_id = e.id
+             JOIN rss_items r ON r.id = te.rss_item_id
+             JOIN rss_feeds f ON f.id = r.feed_id
+             WHERE e.end_ts >= ?1
+             ORDER BY te.event_id, r.published_at ASC",
+        )?;
+        let rows = stmt.query_map(params![active_since], |row| {
+            Ok(EvidenceSource {
+                event_id: row.get(0)?,
+                feed_id: row.get(1)?,
+                article_url: row.get(2)?,
+                feed_url: row.get(3)?,
+                reliability: row.get(4)?,
+                published_at: row.get(5)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// News events ending after `active_since` with their evidence count now
+    /// and when the summary was last regenerated.
+    pub fn summary_candidates(&self, active_since: i64) -> Result<Vec<SummaryCandidate>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT e.id, e.severity, COUNT(*), r.evidence_count, r.severity
+             FROM temporal_events e
+             JOIN temporal_event_evidence te ON te.event_id = e.id
+             LEFT JOIN temporal_event_summary_revisions r ON r.event_id = e.id
+                AND r.revision = (SELECT MAX(revision) FROM temporal_event_summary_revisions WHERE event_id = e.id)
+             WHERE e.end_ts >= ?1 AND e.event_type = 'news'
+             GROUP BY e.id",
+        )?;
+        let rows = stmt.query_map(params![active_since], |row| {
+            Ok(SummaryCandidate {
+                event_id: row.get(0)?,
+                severity: row.get(1)?,
+                evidence_count: row.get(2)?,
+                summarized_evidence_count: row.get(3)?,
+                summarized_severity: row.get(4)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// All evidence articles of an event with their text and feed, oldest first.
+    pub fn summary_evidence(&self, event_id: i64) -> Result<Vec<SummaryEvidence>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT te.rss_item_id, f.id, f.name, f.reliability, i.title, i.content, te.weight, i.published_at
+             FROM temporal_event_evidence te
+             JOIN rss_items i ON i.id = te.rss_item_id
+             JOIN rss_feeds f ON f.id = i.feed_id
+             WHERE te.event_id = ?1
+             ORDER BY i.published_at ASC, te.rss_item_id ASC",
+        )?;
+        let rows = stmt.query_map(params![event_id], |row| {
+            Ok(SummaryEvidence {
+                rss_item_id: row.get(0)?,
+                feed_id: row.get(1)?,
+                source: row.get(2)?,
+                reliability: row.get(3)?,
+                title: row.get(4)?,
+                content: row.get(5)?,
+                weight: row.get(6)?,
+                published_at: row.get(7)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Replace an event's summary and add it to the revision history. The
+    /// first regeneration also records the original summary as revision 1.
+    pub fn record_summary_revision(
+        &self,
+        event_id: i64,
+        summary: &str,
+        evidence_count: i64,
+        severity: f64,
+        reason: &str,
+    ) -> Result<SummaryRevision> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        let tx = conn.transaction()?;
+        let last: i64 = tx.query_row(
+            "SELECT COALESCE(MAX(revision), 0) FROM temporal_event_summary_revisions WHERE event_id = ?1",
+            params![event_id],
+            |row| row.get(0),
+        )?;
+        if last == 0 {
+            tx.execute(
+                "INSERT INTO temporal_event_summary_revisions
+                 (event_id, revision, summary, evidence_count, severity, reason, created_at)
+                 SELECT id, 1, summary, 1, severity, 'initial', created_at FROM temporal_events WHERE id = ?1",
+                params![event_id],
+            )?;
+        }
+        let revision = last.max(1) + 1;
+        tx.execute(
+            "INSERT INTO temporal_event_summary_revisions
+             (event_id, revision, summary, evidence_count, severity, reason, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![event_id, revision, summary, evidence_count, severity, reason, now],
+        )?;
+        let id = tx.last_insert_rowid();
+        tx.execute(
+            "UPDATE temporal_events SET summary = ?1, updated_at = ?2 WHERE id = ?3",
+            params![summary, now, event_id],
+        )?;
+        tx.commit()?;
+
+        ChangeFeed::ids(change_feed::EVENTS, ChangeOp::Update, [event_id]);
+        Ok(SummaryRevision {
+            id,
+            event_id,
+            revision,
+            summary: summary.to_string(),
+            evidence_count,
+            severity,
+            reason: reason.to_string(),
+            created_at: now,
+        })
+    }
+
+    /// An event's summaries, newest first.
+    pub fn list_summary_revisions(&self, event_id: i64) -> Result<Vec<SummaryRevision>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, event_id, revision, summary, evidence_count, severity, reason, created_at
+             FROM temporal_event_summary_revisions
+             WHERE event_id = ?1
+             ORDER BY revision DESC",
+        )?;
+        let rows = stmt.query_map(params![event_id], |row| {
+            Ok(SummaryRevision {
+                id: row.get(0)?,
+                event_id: row.get(1)?,
+                revision: row.get(2)?,
+                summary: row.get(3)?,
+                evidence_count: row.get(4)?,
+                severity: row.get(5)?,
+                reason: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn set_corroboration(&self, event_id: i64, corroboration: &Corroboration) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE temporal_events
+             SET source_count = ?1, corroborated_by = ?2, reliability_tier_count = ?3, time_to_second_source = ?4
+             WHERE id = ?5",
+            params![
+                corroboration.source_count,
+                corroboration.corroborated_by,
+                corroboration.reliability_tier_count,
+                corroboration.time_to_second_source,
+                event_id
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Move an event to a new lifecycle state and record the transition.
+    pub fn set_lifecycle_state(&self, event_id: i64, to_state: &str, changed_at: i64) -> Result<LifecycleTransition> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        let from_state: Option<String> = tx
+            .query_row(
+                "SELECT lifecycle_state FROM temporal_events WHERE id = ?1",
+                params![event_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        tx.execute(
+            "UPDATE temporal_events
+             SET previous_lifecycle_state = lifecycle_state, lifecycle_state = ?1, lifecycle_changed_at = ?2
+             WHERE id = ?3",
+            params![to_state, changed_at, event_id],
+        )?;
+        tx.execute(
+            "INSERT INTO temporal_event_transitions (event_id, from_state, to_state, changed_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![event_id, from_state, to_state, changed_at],
+        )?;
+        let id = tx.last_insert_rowid();
+        tx.commit()?;
+
+        Ok(LifecycleTransition {
+            id,
+            event_id,
+            from_state,
+            to_state: to_state.to_string(),
+            changed_at,
+        })
+    }
+
+    pub fn list_lifecycle_transitions(&self, event_id: i64) -> Result<Vec<LifecycleTransition>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, event_id, from_state, to_state, changed_at
+             FROM temporal_event_transitions
+             WHERE event_id = ?1
+             ORDER BY changed_at ASC, id ASC",
+        )?;
+        let rows = stmt.query_map(params![event_id], |row| {
+            Ok(LifecycleTransition {
+                id: row.get(0)?,
+                event_id: row.get(1)?,
+                from_state: row.get(2)?,
+                to_state: row.get(3)?,
+                changed_at: row.get(4)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Events currently in any of `states`, most recently changed first.
+    pub fn list_events_by_state(&self, states: &[String], limit: i64) -> Result<Vec<TemporalEvent>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        if states.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}
+             FROM temporal_events
+             WHERE lifecycle_state IN ({})
+             ORDER BY COALESCE(lifecycle_changed_at, updated_at) DESC
+             LIMIT ?",
+            EVENT_COLUMNS,
+            sql::placeholders(states.len())
+        ))?;
+
+        let mut values: Vec<&dyn rusqlite::ToSql> = Vec::new();
+        for state in states {
+            values.push(state);
+        }
+        values.push(&limit);
+        let rows = stmt.query_map(values.as_slice(), row_to_event)?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn create_watchlist(&self, name: &str) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        let profile_id = active_profile_id(&conn)?;
+        conn.execute(
+            "INSERT INTO watchlists (name, created_at, profile_id, updated_at) VALUES (?1, ?2, ?3, ?2)",
+            params![name, now, profile_id],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Watchlists of the active profile.
+    pub fn list_watchlists(&self) -> Result<Vec<Watchlist>> {
+        let profile_id = {
+            let conn = self.conn.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            active_profile_id(&conn)?
+        };
+        self.list_watchlists_for(Some(profile_id))
+    }
+
+    /// Watchlists of one profile, or of every profile when `profile_id` is None.
+    pub fn list_watchlists_for(&self, profile_id: Option<i64>) -> Result<Vec<Watchlist>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, created_at FROM watchlists
+             WHERE ?1 IS NULL OR profile_id = ?1
+             ORDER BY name ASC",
+        )?;
+        let rows = stmt.query_map(params![profile_id], |row| {
+            Ok(Watchlist {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    pub fn add_watchlist_item(
+        &self,
         watchlist_id: i64,
         item_type: &str,
         value: &str,
@@ -582,7 +1468,12 @@ impl TemporalStore {
              VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             params![watchlist_id, item_type, value, weight, if enabled { 1 } else { 0 }, now],
         )?;
-        Ok(conn.last_insert_rowid())
+        let item_id = conn.last_insert_rowid();
+        conn.execute(
+            "UPDATE watchlists SET updated_at = ?1 WHERE id = ?2",
+            params![now, watchlist_id],
+        )?;
+        Ok(item_id)
     }
 
     pub fn list_watchlist_items(&self, watchlist_id: i64) -> Result<Vec<WatchlistItem>> {
@@ -611,6 +1502,106 @@ impl TemporalStore {
         Ok(out)
     }
 
+    /// Entities, feed names and article URLs of every event ending after `since`.
+    pub fn event_match_contexts(&self, since: i64) -> Result<Vec<EventMatchContext>> {
+        self.match_contexts("e.end_ts >= ?1", since)
+    }
+
+    /// Match context of a single event.
+    pub fn event_match_context(&self, event_id: i64) -> Result<Option<EventMatchContext>> {
+        Ok(self.match_contexts("e.id = ?1", event_id)?.into_iter().next())
+    }
+
+    /// `filter` is a condition on `temporal_events e` with `param` as ?1.
+    fn match_contexts(&self, filter: &'static str, param: i64) -> Result<Vec<EventMatchContext>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut contexts: HashMap<i64, EventMatchContext> = HashMap::new();
+
+        // sql-audit: filter is a literal condition
+        let mut stmt = conn.prepare(&format!("SELECT e.id, e.title, e.summary FROM temporal_events e WHERE {}", filter))?;
+        let rows = stmt.query_map(params![param], |row| {
+            Ok(EventMatchContext {
+                event_id: row.get(0)?,
+                title: row.get(1)?,
+                summary: row.get(2)?,
+                ..Default::default()
+            })
+        })?;
+        for row in rows {
+            let context = row?;
+            contexts.insert(context.event_id, context);
+        }
+
+        // sql-audit: filter is a literal condition
+        let mut stmt = conn.prepare(&format!(
+            "SELECT DISTINCT te.event_id, f.name, i.url
+             FROM temporal_events e
+             JOIN temporal_event_evidence te ON te.event_id = e.id
+             JOIN rss_items i ON i.id = te.rss_item_id
+             JOIN rss_feeds f ON f.id = i.feed_id
+             WHERE {}",
+            filter
+        ))?;
+        let rows = stmt.query_map(params![param], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+        })?;
+        for row in rows {
+            let (event_id, source, url) = row?;
+            if let Some(context) = contexts.get_mut(&event_id) {
+                context.sources.push(source);
+                context.article_urls.push(url);
+            }
+        }
+
+        // Entity extraction may not have created its table yet
+        // sql-audit: filter is a literal condition
+        if let Ok(mut stmt) = conn.prepare(&format!(
+            "SELECT DISTINCT te.event_id, ee.name
+             FROM temporal_events e
+             JOIN temporal_event_evidence te ON te.event_id = e.id
+             JOIN extracted_entities ee ON ee.article_id = te.rss_item_id
+             WHERE {}",
+            filter
+        )) {
+            let rows = stmt.query_map(params![param], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?;
+            for row in rows {
+                let (event_id, entity) = row?;
+                if let Some(context) = contexts.get_mut(&event_id) {
+                    context.entities.push(entity);
+                }
+            }
+        }
+
+        Ok(contexts.into_values().collect())
+    }
+
+    /// Alerts fired since `since` with their helpful/unhelpful label.
+    pub fn alert_outcomes(&self, since: i64) -> Result<Vec<AlertOutcome>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT a.id, a.rule_id, a.event_id, l.label
+             FROM alerts a
+             LEFT JOIN alert_labels l ON l.alert_id = a.id
+             WHERE a.fired_at >= ?1",
+        )?;
+        let rows = stmt.query_map(params![since], |row| {
+            Ok(AlertOutcome {
+                alert_id: row.get(0)?,
+                rule_id: row.get(1)?,
+                event_id: row.get(2)?,
+                label: row.get(3)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
     pub fn create_alert_rule(
         &self,
         name: &str,
@@ -624,38 +1615,303 @@ impl TemporalStore {
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let now = chrono::Utc::now().timestamp();
         let escalation_config_str = escalation_config.map(|v| v.to_string());
+        let profile_id = active_profile_id(&conn)?;
+        conn.execute(
+            "INSERT INTO alert_rules (name, enabled, watchlist_id, rule_json, schedule, escalation_config, created_at, profile_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![name, if enabled { 1 } else { 0 }, watchlist_id, rule_json.to_string(), schedule, escalation_config_str, now, profile_id],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Set the rule's per-channel message templates, kept under "templates"
+    /// in its escalation config. `None` removes them.
+    pub fn set_alert_rule_templates(&self, rule_id: i64, templates: Option<&Value>) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let current: Option<String> = conn
+            .query_row(
+                "SELECT escalation_config FROM alert_rules WHERE id = ?1",
+                params![rule_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .ok_or_else(|| anyhow::anyhow!("Alert rule {} not found", rule_id))?;
+        let mut config = current
+            .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+            .filter(|v| v.is_object())
+            .unwrap_or_else(|| serde_json::json!({}));
+        match templates {
+            Some(t) => config["templates"] = t.clone(),
+            None => {
+                if let Some(map) = config.as_object_mut() {
+                    map.remove("templates");
+                }
+            }
+        }
+        conn.execute(
+            "UPDATE alert_rules SET escalation_config = ?1 WHERE id = ?2",
+            params![config.to_string(), rule_id],
+        )?;
+        Ok(())
+    }
+
+    /// Alert rules of the active profile.
+    pub fn create_entity_override(&self, entry: &NewEntityAlertOverride) -> Result<i64> {
+        use crate::services::alert_rule_engine::OVERRIDE_SCORES;
+
+        let entity = entry.entity.trim().to_lowercase();
+        if entity.is_empty() {
+            anyhow::bail!("Entity is required");
+        }
+        match entry.action.as_str() {
+            "mute" => {}
+            "raise_threshold" => {
+                if entry.min_scores.is_empty() {
+                    anyhow::bail!("raise_threshold needs at least one minimum score");
+                }
+                if let Some(name) = entry.min_scores.keys().find(|k| !OVERRIDE_SCORES.contains(&k.as_str())) {
+                    anyhow::bail!("Unknown score {} (use {})", name, OVERRIDE_SCORES.join(", "));
+                }
+            }
+            other => anyhow::bail!("Unknown override action: {} (use mute or raise_threshold)", other),
+        }
+        if entry.days.is_some_and(|d| d < 1) {
+            anyhow::bail!("Overrides must last at least one day");
+        }
+
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
         conn.execute(
-            "INSERT INTO alert_rules (name, enabled, watchlist_id, rule_json, schedule, escalation_config, created_at)
+            "INSERT INTO alert_entity_overrides (entity, rule_id, action, min_scores_json, reason, expires_at, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![name, if enabled { 1 } else { 0 }, watchlist_id, rule_json.to_string(), schedule, escalation_config_str, now],
+            params![
+                entity,
+                entry.rule_id,
+                entry.action,
+                serde_json::to_string(&entry.min_scores)?,
+                entry.reason,
+                entry.days.map(|d| now + d * 86400),
+                now
+            ],
         )?;
         Ok(conn.last_insert_rowid())
     }
 
+    /// Entity overrides, newest first. Expired ones only with `include_expired`.
+    pub fn list_entity_overrides(&self, include_expired: bool) -> Result<Vec<EntityAlertOverride>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = if include_expired { i64::MIN } else { chrono::Utc::now().timestamp() };
+        active_entity_overrides(&conn, now)
+    }
+
+    pub fn delete_entity_override(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM alert_entity_overrides WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Turn auto-tuning of a rule on or off and set its bounds. Keeps when it
+    /// was last tuned.
+    pub fn set_rule_tuning(&self, rule_id: i64, enabled: bool, bounds: &HashMap<String, ThresholdBounds>) -> Result<()> {
+        use crate::services::threshold_tuner::TUNABLE_SCORES;
+
+        for (score, b) in bounds {
+            if !TUNABLE_SCORES.contains(&score.as_str()) {
+                anyhow::bail!("Unknown score {} (use {})", score, TUNABLE_SCORES.join(", "));
+            }
+            if b.min > b.max {
+                anyhow::bail!("Bounds for {} need min <= max", score);
+            }
+        }
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM alert_rules WHERE id = ?1)",
+            params![rule_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            anyhow::bail!("Alert rule {} not found", rule_id);
+        }
+        conn.execute(
+            "INSERT INTO alert_rule_tuning (rule_id, enabled, bounds_json, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(rule_id) DO UPDATE SET
+                enabled = excluded.enabled, bounds_json = excluded.bounds_json, updated_at = excluded.updated_at",
+            params![rule_id, enabled, serde_json::to_string(bounds)?, chrono::Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_rule_tuning(&self, rule_id: i64) -> Result<Option<RuleTuning>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT rule_id, enabled, bounds_json, last_tuned_at, updated_at FROM alert_rule_tuning WHERE rule_id = ?1",
+            params![rule_id],
+            row_to_rule_tuning,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    /// Rules with auto-tuning switched on.
+    pub fn list_tuned_rules(&self) -> Result<Vec<RuleTuning>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT t.rule_id, t.enabled, t.bounds_json, t.last_tuned_at, t.updated_at
+             FROM alert_rule_tuning t JOIN alert_rules r ON r.id = t.rule_id
+             WHERE t.enabled = 1
+             ORDER BY t.rule_id",
+        )?;
+        let rows = stmt.query_map([], row_to_rule_tuning)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// (helpful, unhelpful) labels on a rule's alerts fired after `since`.
+    pub fn rule_label_counts(&self, rule_id: i64, since: i64) -> Result<(i64, i64)> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT COALESCE(SUM(l.label = 1), 0), COALESCE(SUM(l.label = -1), 0)
+             FROM alert_labels l JOIN alerts a ON a.id = l.alert_id
+             WHERE a.rule_id = ?1 AND a.fired_at > ?2",
+            params![rule_id, since],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(Into::into)
+    }
+
+    /// Store the tuned rule_json with its logged adjustments and mark the
+    /// rule tuned at `tuned_at`, all at once. Returns the adjustment ids.
+    pub fn apply_threshold_adjustments(
+        &self,
+        rule_id: i64,
+        rule_json: &Value,
+        adjustments: &[ThresholdAdjustment],
+        tuned_at: i64,
+    ) -> Result<Vec<i64>> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        let mut ids = Vec::new();
+        if !adjustments.is_empty() {
+            tx.execute(
+                "UPDATE alert_rules SET rule_json = ?1 WHERE id = ?2",
+                params![rule_json.to_string(), rule_id],
+            )?;
+            for a in adjustments {
+                tx.execute(
+                    "INSERT INTO alert_threshold_adjustments
+                        (rule_id, score, path, old_value, new_value, helpful, unhelpful, tuned_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![rule_id, a.score, a.path, a.old_value, a.new_value, a.helpful, a.unhelpful, tuned_at],
+                )?;
+                ids.push(tx.last_insert_rowid());
+            }
+        }
+        tx.execute(
+            "UPDATE alert_rule_tuning SET last_tuned_at = ?1 WHERE rule_id = ?2",
+            params![tuned_at, rule_id],
+        )?;
+        tx.commit()?;
+        Ok(ids)
+    }
+
+    /// Logged adjustments, newest first, of one rule or all.
+    pub fn list_threshold_adjustments(&self, rule_id: Option<i64>, limit: i64) -> Result<Vec<ThresholdAdjustment>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, rule_id, score, path, old_value, new_value, helpful, unhelpful, tuned_at, reverted_at
+             FROM alert_threshold_adjustments
+             WHERE ?1 IS NULL OR rule_id = ?1
+             ORDER BY tuned_at DESC, id DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![rule_id, limit], row_to_threshold_adjustment)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Put a threshold back to its value before the adjustment. Refused when
+    /// the threshold has been changed since, by tuning or by hand.
+    pub fn revert_threshold_adjustment(&self, id: i64) -> Result<ThresholdAdjustment> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        let adjustment = tx
+            .query_row(
+                "SELECT id, rule_id, score, path, old_value, new_value, helpful, unhelpful, tuned_at, reverted_at
+                 FROM alert_threshold_adjustments WHERE id = ?1",
+                params![id],
+                row_to_threshold_adjustment,
+            )
+            .optional()?
+            .ok_or_else(|| anyhow::anyhow!("Adjustment {} not found", id))?;
+        if adjustment.reverted_at.is_some() {
+            anyhow::bail!("Adjustment {} was already reverted", id);
+        }
+
+        let rule_json: String = tx.query_row(
+            "SELECT rule_json FROM alert_rules WHERE id = ?1",
+            params![adjustment.rule_id],
+            |row| row.get(0),
+        )?;
+        let mut rule_json: Value = serde_json::from_str(&rule_json)?;
+        let current = rule_json
+            .pointer_mut(&adjustment.path)
+            .ok_or_else(|| anyhow::anyhow!("The rule no longer has this threshold"))?;
+        if current.as_f64().map_or(true, |v| (v - adjustment.new_value).abs() > 1e-9) {
+            anyhow::bail!("The threshold was changed after this adjustment; revert the later change first");
+        }
+        *current = serde_json::json!(adjustment.old_value);
+
+        let now = chrono::Utc::now().timestamp();
+        tx.execute(
+            "UPDATE alert_rules SET rule_json = ?1 WHERE id = ?2",
+            params![rule_json.to_string(), adjustment.rule_id],
+        )?;
+        tx.execute(
+            "UPDATE alert_threshold_adjustments SET reverted_at = ?1 WHERE id = ?2",
+            params![now, id],
+        )?;
+        tx.commit()?;
+        Ok(ThresholdAdjustment { reverted_at: Some(now), ..adjustment })
+    }
+
     pub fn list_alert_rules(&self) -> Result<Vec<AlertRule>> {
+        let profile_id = {
+            let conn = self.conn.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            active_profile_id(&conn)?
+        };
+        self.list_alert_rules_for(Some(profile_id))
+    }
+
+    /// Alert rules of one profile, or of every profile when `profile_id` is None.
+    pub fn list_alert_rules_for(&self, profile_id: Option<i64>) -> Result<Vec<AlertRule>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let mut stmt = conn.prepare(
             "SELECT id, name, enabled, watchlist_id, rule_json, schedule, escalation_config, created_at
-             FROM alert_rules ORDER BY created_at DESC",
+             FROM alert_rules
+             WHERE ?1 IS NULL OR profile_id = ?1
+             ORDER BY created_at DESC",
         )?;
-        let rows = stmt.query_map([], |row| {
-            let rule_json_str: String = row.get(4)?;
-            let rule_json: Value = serde_json::from_str(&rule_json_str).unwrap_or(Value::Null);
-            let escalation_config_str: Option<String> = row.get(6)?;
-            let escalation_config = escalation_config_str
-                .and_then(|s| serde_json::from_str(&s).ok());
-            Ok(AlertRule {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                enabled: row.get::<_, i64>(2)? == 1,
-                watchlist_id: row.get(3)?,
-                rule_json,
-                schedule: row.get(5)?,
-                escalation_config,
-                created_at: row.get(7)?,
-            })
-        })?;
+        let rows = stmt.query_map(params![profile_id], row_to_alert_rule)?;
         let mut out = Vec::new();
         for r in rows {
             out.push(r?);
@@ -663,23 +1919,36 @@ impl TemporalStore {
         Ok(out)
     }
 
+    pub fn get_alert_rule(&self, id: i64) -> Result<Option<AlertRule>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, name, enabled, watchlist_id, rule_json, schedule, escalation_config, created_at
+             FROM alert_rules WHERE id = ?1",
+            params![id],
+            row_to_alert_rule,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn get_alert(&self, id: i64) -> Result<Option<Alert>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, rule_id, fired_at, event_id, payload_json, status, snoozed_until
+             FROM alerts WHERE id = ?1",
+            params![id],
+            row_to_alert,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
     pub fn list_alerts(&self, limit: i64, from_ts: Option<i64>, to_ts: Option<i64>) -> Result<Vec<Alert>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
-        let row_mapper = |row: &rusqlite::Row<'_>| {
-            let payload_str: String = row.get(4)?;
-            let payload_json: Value = serde_json::from_str(&payload_str)
-                .unwrap_or_else(|_| Value::Null);
-            Ok(Alert {
-                id: row.get(0)?,
-                rule_id: row.get(1)?,
-                fired_at: row.get(2)?,
-                event_id: row.get(3)?,
-                payload_json,
-                status: row.get(5)?,
-                snoozed_until: row.get(6)?,
-            })
-        };
+        let row_mapper = row_to_alert;
 
         let mut out: Vec<Alert> = Vec::new();
         match (from_ts, to_ts) {
@@ -735,7 +2004,28 @@ impl TemporalStore {
                 }
             }
         }
-
+
+        Ok(out)
+    }
+
+    /// `list_alerts` narrowed to alerts carrying a tag.
+    pub fn list_tagged_alerts(&self, tag: &str, limit: i64, from_ts: Option<i64>, to_ts: Option<i64>) -> Result<Vec<Alert>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, rule_id, fired_at, event_id, payload_json, status, snoozed_until
+             FROM alerts
+             WHERE (?1 IS NULL OR fired_at >= ?1) AND (?2 IS NULL OR fired_at <= ?2)
+               AND id IN (SELECT ta.object_id FROM tag_attachments ta JOIN tags t ON t.id = ta.tag_id
+                          WHERE ta.object_type = 'alert' AND t.name = ?3 COLLATE NOCASE)
+             ORDER BY fired_at DESC
+             LIMIT ?4",
+        )?;
+        let rows = stmt.query_map(params![from_ts, to_ts, tag.trim(), limit], row_to_alert)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
         Ok(out)
     }
 
@@ -746,6 +2036,7 @@ impl TemporalStore {
             "UPDATE alerts SET status = ?1, snoozed_until = ?2 WHERE id = ?3",
             params![status, snoozed_until, alert_id],
         )?;
+        ChangeFeed::ids(change_feed::ALERTS, ChangeOp::Update, [alert_id]);
         Ok(())
     }
 
@@ -767,13 +2058,31 @@ impl TemporalStore {
         Ok(())
     }
 
-    fn create_alert_if_new(&self, rule_id: i64, event_id: Option<i64>, payload_json: &Value) -> Result<Option<Alert>> {
+    /// Insert the alert unless it repeats one already raised. Rules watching a
+    /// lifecycle transition pass the time of the transition (`transition_at`)
+    /// and alert once per transition, however long it stays in the match
+    /// window and whatever became of the earlier alert.
+    fn create_alert_if_new(&self, rule_id: i64, event_id: Option<i64>, transition_at: Option<i64>, payload_json: &Value) -> Result<Option<Alert>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let now = chrono::Utc::now().timestamp();
 
-        // Avoid spamming duplicates: if an alert exists for same rule+event in last 6 hours and not resolved, skip.
-        if let Some(eid) = event_id {
+        if let (Some(eid), Some(changed_at)) = (event_id, transition_at) {
+            let existing: Option<i64> = conn
+                .query_row(
+                    "SELECT id FROM alerts
+                     WHERE rule_id = ?1 AND event_id = ?2
+                       AND json_extract(payload_json, '$.lifecycle.changed_at') = ?3
+                     LIMIT 1",
+                    params![rule_id, eid, changed_at],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if existing.is_some() {
+                return Ok(None);
+            }
+        } else if let Some(eid) = event_id {
+            // Avoid spamming duplicates: if an alert exists for same rule+event in last 6 hours and not resolved, skip.
             let existing: Option<i64> = conn
                 .query_row(
                     "SELECT id FROM alerts
@@ -795,6 +2104,7 @@ impl TemporalStore {
             params![rule_id, now, event_id, payload_json.to_string()],
         )?;
         let id = conn.last_insert_rowid();
+        ChangeFeed::ids(change_feed::ALERTS, ChangeOp::Insert, [id]);
         Ok(Some(Alert {
             id,
             rule_id,
@@ -836,41 +2146,30 @@ impl TemporalStore {
                 created_at: row.get(7)?,
             })
         })?;
+        // Time conditions are read in the rule's timezone, else the global one
+        let timezone = crate::services::timezone::TimePreferences::from_conn(&conn).timezone;
         let mut rules: Vec<AlertRule> = Vec::new();
         for r in rule_rows {
-            rules.push(r?);
+            let mut rule = r?;
+            rule.rule_json = crate::services::alert_rule_engine::AlertRuleEngine::with_timezone(&rule.rule_json, &timezone);
+            rules.push(rule);
         }
 
         if rules.is_empty() {
             return Ok(Vec::new());
         }
+        let overrides = active_entity_overrides(&conn, chrono::Utc::now().timestamp())?;
 
         // Load recent events
-        let mut events_stmt = conn.prepare(
-            "SELECT id, title, summary, start_ts, end_ts, event_type, confidence, severity, novelty_score, volume_score, sentiment_score, cluster_key, created_at, updated_at
+        let mut events_stmt = conn.prepare(&format!(
+            "SELECT {}
              FROM temporal_events
              WHERE end_ts >= ?1
              ORDER BY start_ts DESC
              LIMIT ?2",
-        )?;
-        let event_rows = events_stmt.query_map(params![from_ts, limit_events], |row| {
-            Ok(TemporalEvent {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                summary: row.get(2)?,
-                start_ts: row.get(3)?,
-                end_ts: row.get(4)?,
-                event_type: row.get(5)?,
-                confidence: row.get(6)?,
-                severity: row.get(7)?,
-                novelty_score: row.get(8)?,
-                volume_score: row.get(9)?,
-                sentiment_score: row.get(10)?,
-                cluster_key: row.get(11)?,
-                created_at: row.get(12)?,
-                updated_at: row.get(13)?,
-            })
-        })?;
+            EVENT_COLUMNS
+        ))?;
+        let event_rows = events_stmt.query_map(params![from_ts, limit_events], row_to_event)?;
 
         let mut created: Vec<Alert> = Vec::new();
 
@@ -889,6 +2188,43 @@ impl TemporalStore {
             for e in ent_rows {
                 entities.insert(e?.to_lowercase());
             }
+            // Sector/industry of entities with stored fundamentals, for sector rules.
+            // The fundamentals table only exists once market data has been used.
+            if let Ok(mut sector_stmt) = conn.prepare(
+                "SELECT sector, industry FROM fundamentals WHERE lower(ticker) = ?1 OR lower(name) = ?1",
+            ) {
+                let mut resolved: Vec<String> = Vec::new();
+                for entity in &entities {
+                    let rows = sector_stmt.query_map(params![entity], |row| {
+                        Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?))
+                    })?;
+                    for row in rows {
+                        let (sector, industry) = row?;
+                        if let Some(sector) = sector {
+                            resolved.push(format!("sector:{}", sector.to_lowercase()));
+                        }
+                        if let Some(industry) = industry {
+                            resolved.push(format!("industry:{}", industry.to_lowercase()));
+                        }
+                    }
+                }
+                entities.extend(resolved);
+            }
+            // Recent rating changes and short interest moves of mentioned tickers
+            let signal_since = chrono::Utc::now().timestamp() - crate::storage::market_data::RATING_CHANGE_WINDOW_SECS;
+            let signals = crate::storage::market_data::market_signal_entities(&conn, &entities, signal_since);
+            entities.extend(signals);
+            // Languages of the evidence articles, for language rules
+            let mut lang_stmt = conn.prepare(
+                "SELECT DISTINCT i.language
+                 FROM temporal_event_evidence te
+                 JOIN rss_items i ON i.id = te.rss_item_id
+                 WHERE te.event_id = ?1 AND COALESCE(i.language, '') != ''",
+            )?;
+            let lang_rows = lang_stmt.query_map(params![event.id], |row| row.get::<_, String>(0))?;
+            for language in lang_rows {
+                entities.insert(format!("language:{}", language?.to_lowercase()));
+            }
 
             // Sources for event (rss_feeds.name)
             let mut src_stmt = conn.prepare(
@@ -911,12 +2247,77 @@ impl TemporalStore {
                     continue;
                 }
                 if rule_matches_mvp(&rule.rule_json, &haystack, &entities, &sources, &event) {
-                    let payload = serde_json::json!({
+                    // A muted entity, or one whose raised threshold this event misses
+                    if crate::services::alert_rule_engine::AlertRuleEngine::entity_override_reason(
+                        &overrides, rule.id, &entities, &event,
+                    )
+                    .is_some()
+                    {
+                        continue;
+                    }
+                    let mut payload = serde_json::json!({
                         "rule": { "id": rule.id, "name": rule.name },
                         "event": { "id": event.id, "title": event.title, "start_ts": event.start_ts, "end_ts": event.end_ts },
-                        "scores": { "sentiment": event.sentiment_score, "novelty": event.novelty_score, "volume": event.volume_score }
+                        "scores": {
+                            "sentiment": event.sentiment_score,
+                            "novelty": event.novelty_score,
+                            "volume": event.volume_score,
+                            "severity": event.severity,
+                            "confidence": event.confidence
+                        },
+                        "lifecycle": {
+                            "state": event.lifecycle_state,
+                            "previous": event.previous_lifecycle_state,
+                            "changed_at": event.lifecycle_changed_at
+                        },
+                        "corroboration": {
+                            "sources": event.source_count,
+                            "corroborated_by": event.corroborated_by,
+                            "reliability_tiers": event.reliability_tier_count,
+                            "time_to_second_source": event.time_to_second_source
+                        }
+                    });
+                    // Keep why the rule matched for "why did I get this?"
+                    let trace = crate::services::alert_rule_engine::AlertRuleEngine::explain(
+                        &rule.rule_json, &haystack, &entities, &sources, &event,
+                    );
+                    payload["explanation"] = serde_json::json!({
+                        "reasons": trace.reasons(),
+                        "highlights": trace.highlights(),
+                        "trace": trace,
                     });
-                    if let Some(alert) = self.create_alert_if_new(rule.id, Some(event.id), &payload)? {
+                    let commentary = conn
+                        .query_row(
+                            "SELECT event_id, model, what_happened, why_it_matters, affected_tickers, created_at
+                             FROM temporal_event_commentary WHERE event_id = ?1",
+                            params![event.id],
+                            row_to_commentary,
+                        )
+                        .optional()?;
+                    if let Some(commentary) = commentary {
+                        payload["commentary"] = serde_json::to_value(&commentary)?;
+                    }
+                    // Earliest articles, for links in alert messages
+                    let mut url_stmt = conn.prepare(
+                        "SELECT i.url
+                         FROM temporal_event_evidence te
+                         JOIN rss_items i ON i.id = te.rss_item_id
+                         WHERE te.event_id = ?1
+                         ORDER BY i.published_at ASC
+                         LIMIT 5",
+                    )?;
+                    let url_rows = url_stmt.query_map(params![event.id], |row| row.get::<_, String>(0))?;
+                    let mut articles = Vec::new();
+                    for url in url_rows {
+                        articles.push(url?);
+                    }
+                    payload["links"] = serde_json::json!({ "articles": articles });
+                    let transition_at = if crate::services::alert_rule_engine::AlertRuleEngine::has_condition(&rule.rule_json, "lifecycle_transition") {
+                        event.lifecycle_changed_at
+                    } else {
+                        None
+                    };
+                    if let Some(alert) = self.create_alert_if_new(rule.id, Some(event.id), transition_at, &payload)? {
                         // Trigger escalation check for new alert
                         if let Err(e) = self.check_alert_escalation(&alert, &rule) {
                             eprintln!("Failed to check escalation for alert {}: {}", alert.id, e);
@@ -935,11 +2336,14 @@ impl TemporalStore {
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         conn.execute("DELETE FROM fts_documents", [])?;
 
-        // Index RSS items
+        // Index RSS items, with any text OCR'd from their images
         let mut inserted = 0i64;
         if let Some(ts) = from_ts {
             let mut stmt = conn.prepare(
-                "SELECT id, title, content, published_at FROM rss_items WHERE published_at >= ?1 ORDER BY published_at DESC",
+                "SELECT id, title,
+                        content || COALESCE((SELECT ' ' || group_concat(text, ' ') FROM article_image_text WHERE article_id = rss_items.id), ''),
+                        published_at
+                 FROM rss_items WHERE published_at >= ?1 ORDER BY published_at DESC",
             )?;
             let rows = stmt.query_map(params![ts], |row| {
                 Ok((
@@ -959,7 +2363,10 @@ impl TemporalStore {
             }
         } else {
             let mut stmt = conn.prepare(
-                "SELECT id, title, content, published_at FROM rss_items ORDER BY published_at DESC",
+                "SELECT id, title,
+                        content || COALESCE((SELECT ' ' || group_concat(text, ' ') FROM article_image_text WHERE article_id = rss_items.id), ''),
+                        published_at
+                 FROM rss_items ORDER BY published_at DESC",
             )?;
             let rows = stmt.query_map([], |row| {
                 Ok((
@@ -995,32 +2402,112 @@ impl TemporalStore {
             inserted += 1;
         }
 
+        // Archived events stay searchable by their summary
+        let mut archive_stmt = conn.prepare(
+            "SELECT event_id, title, summary, start_ts FROM temporal_event_archive ORDER BY start_ts DESC",
+        )?;
+        let archive_rows = archive_stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, i64>(3)?))
+        })?;
+        for r in archive_rows {
+            let (id, title, summary, ts) = r?;
+            conn.execute(
+                "INSERT INTO fts_documents (doc_type, doc_id, title, content, ts) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params!["archived_event", id, title, summary, ts],
+            )?;
+            inserted += 1;
+        }
+
+        // Ingested documents; the table only exists once DocumentStore ran
+        if let Ok(mut doc_stmt) = conn.prepare("SELECT id, title, content, ingested_at FROM documents") {
+            let doc_rows = doc_stmt.query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, i64>(3)?))
+            })?;
+            for r in doc_rows {
+                let (id, title, content, ts) = r?;
+                conn.execute(
+                    "INSERT INTO fts_documents (doc_type, doc_id, title, content, ts) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params!["document", id, title, content, ts],
+                )?;
+                inserted += 1;
+            }
+        }
+
+        // Transcript segments, one row each so hits carry their timestamp
+        if let Ok(mut seg_stmt) = conn.prepare(
+            "SELECT s.id, t.title, s.text, t.created_at
+             FROM transcript_segments s
+             JOIN transcripts t ON t.id = s.transcript_id",
+        ) {
+            let seg_rows = seg_stmt.query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, i64>(3)?))
+            })?;
+            for r in seg_rows {
+                let (id, title, text, ts) = r?;
+                conn.execute(
+                    "INSERT INTO fts_documents (doc_type, doc_id, title, content, ts) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params!["transcript_segment", id, title, text, ts],
+                )?;
+                inserted += 1;
+            }
+        }
+
+        search_index::sync_trigram_index(&conn)?;
         Ok(inserted)
     }
 
+    /// Full-text search with the configured stop words removed. With a
+    /// trigram index, substring matches (parts of compounds) are added.
     pub fn search(&self, query: &str, limit: i64) -> Result<Vec<Value>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
-        let mut stmt = conn.prepare(
-            "SELECT doc_type, doc_id, title, snippet(fts_documents, 3, '[', ']', '…', 12) as snippet, ts
-             FROM fts_documents
-             WHERE fts_documents MATCH ?1
-             ORDER BY ts DESC
-             LIMIT ?2",
-        )?;
-        let rows = stmt.query_map(params![query, limit], |row| {
-            Ok(serde_json::json!({
-                "doc_type": row.get::<_, String>(0)?,
-                "doc_id": row.get::<_, i64>(1)?,
-                "title": row.get::<_, String>(2)?,
-                "snippet": row.get::<_, String>(3)?,
-                "ts": row.get::<_, i64>(4)?,
-            }))
-        })?;
+        let settings = search_index::SearchIndexSettings::from_conn(&conn);
+        let query = search_index::prepare_query(query, &settings.stop_words());
+
         let mut out = Vec::new();
-        for r in rows {
-            out.push(r?);
+        let mut seen: HashSet<(String, i64)> = HashSet::new();
+        let mut run = |table: &str, query: &str| -> Result<()> {
+            // sql-audit: table is one of the two index tables below
+            let mut stmt = conn.prepare(&format!(
+                "SELECT doc_type, doc_id, title, snippet({0}, 3, '[', ']', '…', 12) as snippet, ts
+                 FROM {0}
+                 WHERE {0} MATCH ?1
+                 ORDER BY ts DESC
+                 LIMIT ?2",
+                table
+            ))?;
+            let rows = stmt.query_map(params![query, limit], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            })?;
+            for r in rows {
+                let (doc_type, doc_id, title, snippet, ts) = r?;
+                if seen.insert((doc_type.clone(), doc_id)) {
+                    out.push(serde_json::json!({
+                        "doc_type": doc_type,
+                        "doc_id": doc_id,
+                        "title": title,
+                        "snippet": snippet,
+                        "ts": ts,
+                    }));
+                }
+            }
+            Ok(())
+        };
+        run("fts_documents", &query)?;
+        if search_index::trigram_index_exists(&conn)? {
+            if let Some(trigram_query) = search_index::trigram_query(&query) {
+                run(search_index::TRIGRAM_TABLE, &trigram_query)?;
+            }
         }
+
+        out.sort_by(|a, b| b["ts"].as_i64().cmp(&a["ts"].as_i64()));
+        out.truncate(limit.max(0) as usize);
         Ok(out)
     }
 
@@ -1037,13 +2524,18 @@ impl TemporalStore {
         // - For each rss_item in range, find its top extracted entity (by confidence)
         // - Cluster key = YYYY-MM-DD + '|' + top_entity_name (or 'misc')
         // - Upsert event per cluster key, attach evidence rows
+        // Articles in languages outside the allowed list stay out of events;
+        // those of unknown language are kept
+        let allowed = search_index::event_languages(&conn);
+        let allowed = if allowed.is_empty() { String::new() } else { format!(",{},", allowed.join(",")) };
         let mut stmt = conn.prepare(
             "SELECT id, title, content, published_at
              FROM rss_items
              WHERE published_at >= ?1
+               AND (?2 = '' OR COALESCE(language, '') = '' OR instr(?2, ',' || language || ',') > 0)
              ORDER BY published_at DESC",
         )?;
-        let rows = stmt.query_map(params![from_ts], |row| {
+        let rows = stmt.query_map(params![from_ts, allowed], |row| {
             Ok((
                 row.get::<_, i64>(0)?,
                 row.get::<_, String>(1)?,
@@ -1102,6 +2594,7 @@ impl TemporalStore {
                      WHERE id = ?5",
                     params![new_end, new_start, now, sentiment_score, eid],
                 )?;
+                ChangeFeed::ids(change_feed::EVENTS, ChangeOp::Update, [eid]);
                 eid
             } else {
                 let summary = summarize_light(&title, &content);
@@ -1113,7 +2606,9 @@ impl TemporalStore {
                     params![event_title, summary, published_at, published_at, sentiment_score, cluster_key, now, now],
                 )?;
                 touched_events += 1;
-                conn.last_insert_rowid()
+                let eid = conn.last_insert_rowid();
+                ChangeFeed::ids(change_feed::EVENTS, ChangeOp::Insert, [eid]);
+                eid
             };
 
             // Evidence link (idempotent)
@@ -1494,7 +2989,10 @@ impl TemporalStore {
     // - alerts_count(<days>)
     // - events_count(<days>)
     // - avg_sentiment(<days>)
-    // Materializes daily buckets into feature_values.
+    // - fx_close(EURUSD) or "eurusd close": daily FX reference rate
+    // Materializes daily buckets into feature_values. Buckets are calendar
+    // days in the configured timezone (23 or 25 hours across DST changes),
+    // stored at the day's local midnight; today's bucket is partial.
     pub fn compute_feature_mvp(&self, feature_id: i64, days_back: i64) -> Result<i64> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -1514,34 +3012,46 @@ impl TemporalStore {
             },
         )?;
 
+        let tz = crate::services::timezone::TimePreferences::from_conn(&conn).tz();
+        let today = crate::services::timezone::local_date(chrono::Utc::now().timestamp(), tz);
+        let first_day = today - chrono::Duration::days(days_back - 1);
+
         // Clear existing values for this feature in the computed range (simple strategy)
-        let now = chrono::Utc::now().timestamp();
-        let from_ts = now - days_back * 24 * 3600;
+        let from_ts = crate::services::timezone::day_start(first_day, tz);
         conn.execute(
             "DELETE FROM feature_values WHERE feature_id = ?1 AND ts >= ?2",
             params![feature_id, from_ts],
         )?;
 
-        let mut inserted = 0i64;
-        for day in (0..days_back).rev() {
-            let day_end = now - day * 24 * 3600;
-            let day_start = day_end - 24 * 3600;
+        let fx_pair = parse_fx_expression(&def.expression);
 
-            let value = if def.expression.starts_with("alerts_count") {
+        let mut inserted = 0i64;
+        for date in first_day.iter_days().take(days_back as usize) {
+            let day_start = crate::services::timezone::day_start(date, tz);
+            let day_end = crate::services::timezone::day_start(date + chrono::Duration::days(1), tz);
+
+            let value = if let Some((base, quote)) = &fx_pair {
+                // Days without a stored rate are skipped rather than recorded as 0
+                let date = date.format("%Y-%m-%d").to_string();
+                match crate::storage::fx::FxStore::rate_on_or_before(&conn, base, quote, Some(&date)).ok().flatten() {
+                    Some(rate) => rate.rate,
+                    None => continue,
+                }
+            } else if def.expression.starts_with("alerts_count") {
                 conn.query_row(
-                    "SELECT COUNT(*) FROM alerts WHERE fired_at BETWEEN ?1 AND ?2",
+                    "SELECT COUNT(*) FROM alerts WHERE fired_at >= ?1 AND fired_at < ?2",
                     params![day_start, day_end],
                     |row| row.get::<_, i64>(0),
                 )? as f64
             } else if def.expression.starts_with("events_count") {
                 conn.query_row(
-                    "SELECT COUNT(*) FROM temporal_events WHERE start_ts BETWEEN ?1 AND ?2",
+                    "SELECT COUNT(*) FROM temporal_events WHERE start_ts >= ?1 AND start_ts < ?2",
                     params![day_start, day_end],
                     |row| row.get::<_, i64>(0),
                 )? as f64
             } else if def.expression.starts_with("avg_sentiment") {
                 conn.query_row(
-                    "SELECT COALESCE(AVG(sentiment_score), 0.0) FROM temporal_events WHERE start_ts BETWEEN ?1 AND ?2",
+                    "SELECT COALESCE(AVG(sentiment_score), 0.0) FROM temporal_events WHERE start_ts >= ?1 AND start_ts < ?2",
                     params![day_start, day_end],
                     |row| row.get::<_, f64>(0),
                 )?
@@ -1552,7 +3062,7 @@ impl TemporalStore {
             conn.execute(
                 "INSERT INTO feature_values (feature_id, ts, subject_type, subject_value, value)
                  VALUES (?1, ?2, 'global', 'global', ?3)",
-                params![feature_id, day_end, value],
+                params![feature_id, day_start, value],
             )?;
             inserted += 1;
         }
@@ -1561,6 +3071,23 @@ impl TemporalStore {
     }
 }
 
+/// "fx_close(EURUSD)" or "eurusd close" -> ("EUR", "USD").
+fn parse_fx_expression(expression: &str) -> Option<(String, String)> {
+    let expr = expression.trim().to_uppercase();
+    let pair = if let Some(inner) = expr.strip_prefix("FX_CLOSE(").and_then(|r| r.strip_suffix(')')) {
+        inner.trim().replace('/', "")
+    } else if let Some(pair) = expr.strip_suffix(" CLOSE") {
+        pair.trim().replace('/', "")
+    } else {
+        return None;
+    };
+    if pair.len() == 6 && pair.chars().all(|c| c.is_ascii_alphabetic()) {
+        Some((pair[..3].to_string(), pair[3..].to_string()))
+    } else {
+        None
+    }
+}
+
 fn truncate(s: &str, max: usize) -> String {
     let mut out = s.trim().to_string();
     if out.len() > max {
@@ -1702,6 +3229,21 @@ pub struct AlertEscalation {
     pub error_message: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertIssue {
+    pub alert_id: i64,
+    pub provider: String, // jira|linear
+    /// Provider's internal ID, used for status lookups
+    pub issue_id: String,
+    /// Human-facing key, e.g. "OPS-123"
+    pub issue_key: String,
+    pub issue_url: Option<String>,
+    pub status: Option<String>,
+    pub closed: bool,
+    pub created_at: i64,
+    pub synced_at: Option<i64>,
+}
+
 impl TemporalStore {
     pub fn create_escalation(
         &self,
@@ -1761,6 +3303,567 @@ impl TemporalStore {
 
         Ok(escalations)
     }
+
+    pub fn save_alert_issue(&self, issue: &AlertIssue) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO alert_issues
+             (alert_id, provider, issue_id, issue_key, issue_url, status, closed, created_at, synced_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                issue.alert_id,
+                issue.provider,
+                issue.issue_id,
+                issue.issue_key,
+                issue.issue_url,
+                issue.status,
+                if issue.closed { 1 } else { 0 },
+                issue.created_at,
+                issue.synced_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_alert_issue(&self, alert_id: i64) -> Result<Option<AlertIssue>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT alert_id, provider, issue_id, issue_key, issue_url, status, closed, created_at, synced_at
+             FROM alert_issues WHERE alert_id = ?1",
+            params![alert_id],
+            row_to_alert_issue,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    /// Issues not yet closed, for status sync.
+    pub fn list_open_alert_issues(&self) -> Result<Vec<AlertIssue>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT alert_id, provider, issue_id, issue_key, issue_url, status, closed, created_at, synced_at
+             FROM alert_issues WHERE closed = 0
+             ORDER BY created_at ASC",
+        )?;
+        let rows = stmt.query_map([], row_to_alert_issue)?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn update_alert_issue_status(&self, alert_id: i64, status: &str, closed: bool) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE alert_issues SET status = ?1, closed = ?2, synced_at = ?3 WHERE alert_id = ?4",
+            params![status, if closed { 1 } else { 0 }, chrono::Utc::now().timestamp(), alert_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn save_event_commentary(&self, commentary: &EventCommentary) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO temporal_event_commentary
+             (event_id, model, what_happened, why_it_matters, affected_tickers, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                commentary.event_id,
+                commentary.model,
+                commentary.what_happened,
+                commentary.why_it_matters,
+                serde_json::to_string(&commentary.affected_tickers)?,
+                commentary.created_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_event_commentary(&self, event_id: i64) -> Result<Option<EventCommentary>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let commentary = conn
+            .query_row(
+                "SELECT event_id, model, what_happened, why_it_matters, affected_tickers, created_at
+                 FROM temporal_event_commentary WHERE event_id = ?1",
+                params![event_id],
+                row_to_commentary,
+            )
+            .optional()?;
+        Ok(commentary)
+    }
+
+    /// High-severity events since `from_ts` without commentary. Events the MVP
+    /// builder hasn't scored (severity 0) qualify on article volume instead.
+    pub fn list_events_needing_commentary(
+        &self,
+        from_ts: i64,
+        min_severity: f64,
+        min_volume: f64,
+        limit: i64,
+    ) -> Result<Vec<TemporalEvent>> {
+        let ids: Vec<i64> = {
+            let conn = self.conn.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            let mut stmt = conn.prepare(
+                "SELECT e.id
+                 FROM temporal_events e
+                 LEFT JOIN temporal_event_commentary c ON c.event_id = e.id
+                 WHERE c.event_id IS NULL
+                   AND e.end_ts >= ?1
+                   AND (e.severity >= ?2 OR e.volume_score >= ?3)
+                 ORDER BY e.severity DESC, e.volume_score DESC
+                 LIMIT ?4",
+            )?;
+            let rows = stmt.query_map(params![from_ts, min_severity, min_volume, limit], |row| row.get(0))?;
+            rows.collect::<Result<Vec<i64>, _>>()?
+        };
+
+        let mut events = Vec::new();
+        for id in ids {
+            if let Some(event) = self.get_event(id)? {
+                events.push(event);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Recent events that have commentary, newest first, for briefings.
+    /// Events with commentary still running at `from_ts`, and starting
+    /// before `to_ts` when given.
+    pub fn list_commented_events(&self, from_ts: i64, to_ts: Option<i64>, limit: i64) -> Result<Vec<(TemporalEvent, EventCommentary)>> {
+        let ids: Vec<i64> = {
+            let conn = self.conn.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            let mut stmt = conn.prepare(
+                "SELECT e.id
+                 FROM temporal_events e
+                 JOIN temporal_event_commentary c ON c.event_id = e.id
+                 WHERE e.end_ts >= ?1 AND (?3 IS NULL OR e.start_ts < ?3)
+                 ORDER BY e.start_ts DESC
+                 LIMIT ?2",
+            )?;
+            let rows = stmt.query_map(params![from_ts, limit, to_ts], |row| row.get(0))?;
+            rows.collect::<Result<Vec<i64>, _>>()?
+        };
+
+        let mut out = Vec::new();
+        for id in ids {
+            if let (Some(event), Some(commentary)) = (self.get_event(id)?, self.get_event_commentary(id)?) {
+                out.push((event, commentary));
+            }
+        }
+        Ok(out)
+    }
+
+    // =========================
+    // Retention / archive
+    // =========================
+
+    /// Move up to `limit` events that ended before `before_ts` into the
+    /// compressed archive. Returns the number archived.
+    pub fn archive_events_before(&self, before_ts: i64, limit: i64) -> Result<usize> {
+        let ids: Vec<i64> = {
+            let conn = self.conn.lock()
+                .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+            let mut stmt = conn.prepare(
+                "SELECT id FROM temporal_events WHERE end_ts < ?1 ORDER BY end_ts ASC LIMIT ?2",
+            )?;
+            let rows = stmt.query_map(params![before_ts, limit], |row| row.get(0))?;
+            rows.collect::<Result<Vec<i64>, _>>()?
+        };
+
+        let mut archived = 0;
+        for id in ids {
+            if self.archive_event(id)? {
+                archived += 1;
+            }
+        }
+        Ok(archived)
+    }
+
+    /// Returns false if the event doesn't exist.
+    pub fn archive_event(&self, event_id: i64) -> Result<bool> {
+        let event = match self.get_event(event_id)? {
+            Some(event) => event,
+            None => return Ok(false),
+        };
+        let detail = ArchivedEventDetail {
+            evidence: self.list_event_evidence(event_id)?,
+            commentary: self.get_event_commentary(event_id)?,
+            transitions: self.list_lifecycle_transitions(event_id)?,
+            event,
+        };
+        let compressed = compress_detail(&detail)?;
+
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO temporal_event_archive
+             (event_id, title, summary, start_ts, end_ts, event_type, evidence_count, archived_at, detail_gz)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                event_id,
+                detail.event.title,
+                detail.event.summary,
+                detail.event.start_ts,
+                detail.event.end_ts,
+                detail.event.event_type,
+                detail.evidence.len() as i64,
+                now,
+                compressed
+            ],
+        )?;
+        tx.execute("DELETE FROM temporal_event_evidence WHERE event_id = ?1", params![event_id])?;
+        tx.execute("DELETE FROM temporal_event_commentary WHERE event_id = ?1", params![event_id])?;
+        tx.execute("DELETE FROM temporal_event_transitions WHERE event_id = ?1", params![event_id])?;
+        tx.execute("DELETE FROM temporal_event_summary_revisions WHERE event_id = ?1", params![event_id])?;
+        tx.execute("DELETE FROM temporal_events WHERE id = ?1", params![event_id])?;
+        tx.execute(
+            "DELETE FROM fts_documents WHERE doc_type = 'temporal_event' AND doc_id = ?1",
+            params![event_id],
+        )?;
+        tx.execute(
+            "INSERT INTO fts_documents (doc_type, doc_id, title, content, ts) VALUES ('archived_event', ?1, ?2, ?3, ?4)",
+            params![event_id, detail.event.title, detail.event.summary, detail.event.start_ts],
+        )?;
+        tx.commit()?;
+
+        ChangeFeed::ids(change_feed::EVENTS, ChangeOp::Delete, [event_id]);
+        Ok(true)
+    }
+
+    /// Archived event summaries, newest first, optionally filtered by a
+    /// substring of the title or summary.
+    pub fn list_archived_events(&self, query: Option<&str>, limit: i64) -> Result<Vec<ArchivedEvent>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let pattern = format!("%{}%", query.unwrap_or("").trim().to_lowercase());
+        let mut stmt = conn.prepare(
+            "SELECT event_id, title, summary, start_ts, end_ts, event_type, evidence_count, archived_at, LENGTH(detail_gz)
+             FROM temporal_event_archive
+             WHERE lower(title) LIKE ?1 OR lower(summary) LIKE ?1
+             ORDER BY start_ts DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![pattern, limit], |row| {
+            Ok(ArchivedEvent {
+                event_id: row.get(0)?,
+                title: row.get(1)?,
+                summary: row.get(2)?,
+                start_ts: row.get(3)?,
+                end_ts: row.get(4)?,
+                event_type: row.get(5)?,
+                evidence_count: row.get(6)?,
+                archived_at: row.get(7)?,
+                compressed_bytes: row.get(8)?,
+            })
+        })?;
+
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Read an archived event without restoring it.
+    pub fn get_archived_event(&self, event_id: i64) -> Result<Option<ArchivedEventDetail>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let compressed: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT detail_gz FROM temporal_event_archive WHERE event_id = ?1",
+                params![event_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        compressed.map(|c| decompress_detail(&c)).transpose()
+    }
+
+    /// Move an archived event back into the live tables under its original
+    /// id. Returns false if it isn't archived.
+    pub fn restore_event(&self, event_id: i64) -> Result<bool> {
+        let detail = match self.get_archived_event(event_id)? {
+            Some(detail) => detail,
+            None => return Ok(false),
+        };
+        let event = &detail.event;
+
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let tx = conn.transaction()?;
+        let clash: Option<i64> = tx
+            .query_row(
+                "SELECT id FROM temporal_events WHERE cluster_key = ?1",
+                params![event.cluster_key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(clash) = clash {
+            anyhow::bail!("Event {} has since been rebuilt with the same cluster key", clash);
+        }
+
+        tx.execute(
+            "INSERT INTO temporal_events
+             (id, title, summary, start_ts, end_ts, event_type, confidence, severity, novelty_score, volume_score,
+              sentiment_score, cluster_key, created_at, updated_at, lifecycle_state, previous_lifecycle_state,
+              lifecycle_changed_at, source_count, corroborated_by, reliability_tier_count, time_to_second_source)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
+            params![
+                event.id, event.title, event.summary, event.start_ts, event.end_ts, event.event_type,
+                event.confidence, event.severity, event.novelty_score, event.volume_score,
+                event.sentiment_score, event.cluster_key, event.created_at, event.updated_at,
+                event.lifecycle_state, event.previous_lifecycle_state, event.lifecycle_changed_at,
+                event.source_count, event.corroborated_by, event.reliability_tier_count, event.time_to_second_source
+            ],
+        )?;
+        for evidence in &detail.evidence {
+            tx.execute(
+                "INSERT OR IGNORE INTO temporal_event_evidence (event_id, rss_item_id, weight, snippet)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![event.id, evidence.rss_item_id, evidence.weight, evidence.snippet],
+            )?;
+        }
+        if let Some(commentary) = &detail.commentary {
+            tx.execute(
+                "INSERT OR REPLACE INTO temporal_event_commentary
+                 (event_id, model, what_happened, why_it_matters, affected_tickers, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    event.id,
+                    commentary.model,
+                    commentary.what_happened,
+                    commentary.why_it_matters,
+                    serde_json::to_string(&commentary.affected_tickers)?,
+                    commentary.created_at
+                ],
+            )?;
+        }
+        for transition in &detail.transitions {
+            tx.execute(
+                "INSERT INTO temporal_event_transitions (event_id, from_state, to_state, changed_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![event.id, transition.from_state, transition.to_state, transition.changed_at],
+            )?;
+        }
+        tx.execute("DELETE FROM temporal_event_archive WHERE event_id = ?1", params![event_id])?;
+        tx.execute(
+            "DELETE FROM fts_documents WHERE doc_type = 'archived_event' AND doc_id = ?1",
+            params![event_id],
+        )?;
+        tx.execute(
+            "INSERT INTO fts_documents (doc_type, doc_id, title, content, ts) VALUES ('temporal_event', ?1, ?2, ?3, ?4)",
+            params![event.id, event.title, event.summary, event.start_ts],
+        )?;
+        tx.commit()?;
+
+        Ok(true)
+    }
+
+    pub fn storage_usage(&self) -> Result<EventStorageUsage> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let count = |table: &str| -> Result<i64> {
+            // sql-audit: table names are the literals below
+            Ok(conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))?)
+        };
+
+        let (oldest_event_ts, oldest_archived_ts, archive_bytes): (Option<i64>, Option<i64>, i64) = conn.query_row(
+            "SELECT (SELECT MIN(start_ts) FROM temporal_events),
+                    (SELECT MIN(start_ts) FROM temporal_event_archive),
+                    (SELECT COALESCE(SUM(LENGTH(detail_gz)), 0) FROM temporal_event_archive)",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        // dbstat is only there when SQLite was built with it
+        let live_table_bytes = conn
+            .query_row(
+                "SELECT SUM(pgsize) FROM dbstat
+                 WHERE name IN ('temporal_events', 'temporal_event_evidence', 'temporal_event_commentary', 'temporal_event_transitions')",
+                [],
+                |row| row.get::<_, Option<i64>>(0),
+            )
+            .ok()
+            .flatten();
+
+        Ok(EventStorageUsage {
+            events: count("temporal_events")?,
+            evidence_links: count("temporal_event_evidence")?,
+            transitions: count("temporal_event_transitions")?,
+            commentaries: count("temporal_event_commentary")?,
+            oldest_event_ts,
+            archived_events: count("temporal_event_archive")?,
+            archive_bytes,
+            oldest_archived_ts,
+            live_table_bytes,
+        })
+    }
+}
+
+const EVENT_COLUMNS: &str = "id, title, summary, start_ts, end_ts, event_type, confidence, severity, novelty_score, volume_score, sentiment_score, cluster_key, created_at, updated_at, lifecycle_state, previous_lifecycle_state, lifecycle_changed_at, source_count, corroborated_by, reliability_tier_count, time_to_second_source";
+
+fn row_to_event(row: &rusqlite::Row) -> rusqlite::Result<TemporalEvent> {
+    Ok(TemporalEvent {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        summary: row.get(2)?,
+        start_ts: row.get(3)?,
+        end_ts: row.get(4)?,
+        event_type: row.get(5)?,
+        confidence: row.get(6)?,
+        severity: row.get(7)?,
+        novelty_score: row.get(8)?,
+        volume_score: row.get(9)?,
+        sentiment_score: row.get(10)?,
+        cluster_key: row.get(11)?,
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+        lifecycle_state: row.get(14)?,
+        previous_lifecycle_state: row.get(15)?,
+        lifecycle_changed_at: row.get(16)?,
+        source_count: row.get(17)?,
+        corroborated_by: row.get(18)?,
+        reliability_tier_count: row.get(19)?,
+        time_to_second_source: row.get(20)?,
+    })
+}
+
+fn compress_detail(detail: &ArchivedEventDetail) -> Result<Vec<u8>> {
+    use std::io::Write;
+    let json = serde_json::to_vec(detail)?;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&json)?;
+    Ok(encoder.finish()?)
+}
+
+fn decompress_detail(compressed: &[u8]) -> Result<ArchivedEventDetail> {
+    use std::io::Read;
+    let mut json = Vec::new();
+    flate2::read::GzDecoder::new(compressed)
+        .read_to_end(&mut json)
+        .map_err(|e| anyhow::anyhow!("Failed to decompress archived event: {}", e))?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// Overrides that haven't expired by `now`, newest first.
+fn active_entity_overrides(conn: &Connection, now: i64) -> Result<Vec<EntityAlertOverride>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, entity, rule_id, action, min_scores_json, reason, expires_at, created_at
+         FROM alert_entity_overrides
+         WHERE expires_at IS NULL OR expires_at > ?1
+         ORDER BY created_at DESC, id DESC",
+    )?;
+    let rows = stmt.query_map(params![now], |row| {
+        let min_scores_json: String = row.get(4)?;
+        Ok(EntityAlertOverride {
+            id: row.get(0)?,
+            entity: row.get(1)?,
+            rule_id: row.get(2)?,
+            action: row.get(3)?,
+            min_scores: serde_json::from_str(&min_scores_json).unwrap_or_default(),
+            reason: row.get(5)?,
+            expires_at: row.get(6)?,
+            created_at: row.get(7)?,
+        })
+    })?;
+    let mut out = Vec::new();
+    for r in rows {
+        out.push(r?);
+    }
+    Ok(out)
+}
+
+fn row_to_rule_tuning(row: &rusqlite::Row) -> rusqlite::Result<RuleTuning> {
+    let bounds_json: String = row.get(2)?;
+    Ok(RuleTuning {
+        rule_id: row.get(0)?,
+        enabled: row.get::<_, i64>(1)? == 1,
+        bounds: serde_json::from_str(&bounds_json).unwrap_or_default(),
+        last_tuned_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
 }
 
+fn row_to_threshold_adjustment(row: &rusqlite::Row) -> rusqlite::Result<ThresholdAdjustment> {
+    Ok(ThresholdAdjustment {
+        id: row.get(0)?,
+        rule_id: row.get(1)?,
+        score: row.get(2)?,
+        path: row.get(3)?,
+        old_value: row.get(4)?,
+        new_value: row.get(5)?,
+        helpful: row.get(6)?,
+        unhelpful: row.get(7)?,
+        tuned_at: row.get(8)?,
+        reverted_at: row.get(9)?,
+    })
+}
+
+fn row_to_alert_rule(row: &rusqlite::Row) -> rusqlite::Result<AlertRule> {
+    let rule_json_str: String = row.get(4)?;
+    let rule_json: Value = serde_json::from_str(&rule_json_str).unwrap_or(Value::Null);
+    let escalation_config_str: Option<String> = row.get(6)?;
+    let escalation_config = escalation_config_str
+        .and_then(|s| serde_json::from_str(&s).ok());
+    Ok(AlertRule {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        enabled: row.get::<_, i64>(2)? == 1,
+        watchlist_id: row.get(3)?,
+        rule_json,
+        schedule: row.get(5)?,
+        escalation_config,
+        created_at: row.get(7)?,
+    })
+}
+
+fn row_to_alert(row: &rusqlite::Row) -> rusqlite::Result<Alert> {
+    let payload_str: String = row.get(4)?;
+    let payload_json: Value = serde_json::from_str(&payload_str)
+        .unwrap_or_else(|_| Value::Null);
+    Ok(Alert {
+        id: row.get(0)?,
+        rule_id: row.get(1)?,
+        fired_at: row.get(2)?,
+        event_id: row.get(3)?,
+        payload_json,
+        status: row.get(5)?,
+        snoozed_until: row.get(6)?,
+    })
+}
+
+fn row_to_commentary(row: &rusqlite::Row) -> rusqlite::Result<EventCommentary> {
+    let tickers: String = row.get(4)?;
+    Ok(EventCommentary {
+        event_id: row.get(0)?,
+        model: row.get(1)?,
+        what_happened: row.get(2)?,
+        why_it_matters: row.get(3)?,
+        affected_tickers: serde_json::from_str(&tickers).unwrap_or_default(),
+        created_at: row.get(5)?,
+    })
+}
 
+fn row_to_alert_issue(row: &rusqlite::Row) -> rusqlite::Result<AlertIssue> {
+    Ok(AlertIssue {
+        alert_id: row.get(0)?,
+        provider: row.get(1)?,
+        issue_id: row.get(2)?,
+        issue_key: row.get(3)?,
+        issue_url: row.get(4)?,
+        status: row.get(5)?,
+        closed: row.get::<_, i64>(6)? == 1,
+        created_at: row.get(7)?,
+        synced_at: row.get(8)?,
+    })
+}
@@ -11,6 +11,20 @@ pub struct TestSuite {
     pub created_at: i64,
 }
 
+/// The command a suite runs. Runs only ever execute the command configured
+/// for their suite.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCommand {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub working_dir: Option<String>,
+    /// Read results from this file instead of stdout (e.g. a JUnit XML report).
+    pub report_path: Option<String>,
+    pub format: Option<String>, // "junit", "llvm", "cargo", None to detect
+    pub timeout_secs: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestResult {
     pub id: i64,
@@ -20,6 +34,65 @@ pub struct TestResult {
     pub duration: Option<f64>,
     pub error: Option<String>,
     pub executed_at: i64,
+    pub run_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestRun {
+    pub id: i64,
+    pub suite_id: i64,
+    pub command: String,
+    pub format: String, // "junit", "llvm", "cargo"
+    pub exit_code: Option<i32>,
+    pub passed: i64,
+    pub failed: i64,
+    pub skipped: i64,
+    pub duration: f64,
+    pub started_at: i64,
+    pub finished_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlakyTest {
+    pub name: String,
+    pub runs: usize,
+    pub passes: usize,
+    pub failures: usize,
+    pub flips: usize,
+    pub flake_rate: f64, // flips / (runs - 1)
+    pub last_status: String,
+    pub quarantined: bool,
+    /// Suites the test ran in within the window, this one included
+    pub suite_ids: Vec<i64>,
+    /// Consecutive results ending in last_status
+    pub current_streak: usize,
+    pub longest_failure_streak: usize,
+    /// How much failures depend on the 6-hour block of the (local) day the
+    /// test ran in, as a correlation ratio in 0..1
+    pub time_of_day_correlation: Option<f64>,
+    /// Start hour of the block failing most often
+    pub peak_failure_hour: Option<u32>,
+    /// Pearson correlation of failures with how many other runs overlapped
+    /// the test's run; positive when it fails more under parallel load
+    pub parallelism_correlation: Option<f64>,
+}
+
+/// One pass/fail execution in a test's history.
+struct FlakySample {
+    failed: bool,
+    suite_id: i64,
+    hour: Option<u32>,
+    /// Other runs overlapping this one; None for results saved outside a run
+    concurrent_runs: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedTest {
+    pub suite_id: i64,
+    pub test_name: String,
+    pub reason: Option<String>,
+    pub automatic: bool,
+    pub quarantined_at: i64,
 }
 
 pub struct TestingStore {
@@ -63,11 +136,52 @@ impl TestingStore {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS test_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                suite_id INTEGER NOT NULL,
+                command TEXT NOT NULL,
+                format TEXT NOT NULL,
+                exit_code INTEGER,
+                passed INTEGER NOT NULL DEFAULT 0,
+                failed INTEGER NOT NULL DEFAULT 0,
+                skipped INTEGER NOT NULL DEFAULT 0,
+                duration REAL NOT NULL DEFAULT 0,
+                started_at INTEGER NOT NULL,
+                finished_at INTEGER NOT NULL,
+                FOREIGN KEY (suite_id) REFERENCES test_suites(id)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS test_quarantine (
+                suite_id INTEGER NOT NULL,
+                test_name TEXT NOT NULL,
+                reason TEXT,
+                automatic INTEGER NOT NULL DEFAULT 0,
+                quarantined_at INTEGER NOT NULL,
+                PRIMARY KEY (suite_id, test_name),
+                FOREIGN KEY (suite_id) REFERENCES test_suites(id)
+            )",
+            [],
+        )?;
+
+        // Migration: results ingested from a run point back at it
+        let _ = conn.execute("ALTER TABLE test_results ADD COLUMN run_id INTEGER", []);
+        // Migration: suites carry the command their runs execute
+        let _ = conn.execute("ALTER TABLE test_suites ADD COLUMN command_json TEXT", []);
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_test_results_suite ON test_results(suite_id)",
             [],
         )?;
 
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_test_runs_suite ON test_runs(suite_id, started_at)",
+            [],
+        )?;
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_test_results_executed ON test_results(executed_at)",
             [],
@@ -120,6 +234,38 @@ impl TestingStore {
         Ok(suites)
     }
 
+    /// Set or clear (None) the command a suite's runs execute.
+    pub fn set_suite_command(&self, suite_id: i64, command: Option<&TestCommand>) -> Result<()> {
+        let command_json = command.map(serde_json::to_string).transpose()?;
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let updated = conn.execute(
+            "UPDATE test_suites SET command_json = ?1 WHERE id = ?2",
+            params![command_json, suite_id],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Test suite {} not found", suite_id);
+        }
+        Ok(())
+    }
+
+    pub fn get_suite_command(&self, suite_id: i64) -> Result<Option<TestCommand>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let command_json: Option<String> = conn
+            .query_row(
+                "SELECT command_json FROM test_suites WHERE id = ?1",
+                params![suite_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        Ok(match command_json {
+            Some(json) => Some(serde_json::from_str(&json)?),
+            None => None,
+        })
+    }
+
     pub fn save_test_result(
         &self,
         suite_id: i64,
@@ -145,7 +291,7 @@ impl TestingStore {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
         let mut stmt = conn.prepare(
-            "SELECT id, suite_id, name, status, duration, error, executed_at
+            "SELECT id, suite_id, name, status, duration, error, executed_at, run_id
              FROM test_results
              WHERE suite_id = ?1
              ORDER BY executed_at DESC"
@@ -160,6 +306,7 @@ impl TestingStore {
                 duration: row.get(4)?,
                 error: row.get(5)?,
                 executed_at: row.get(6)?,
+                run_id: row.get(7)?,
             })
         })?;
 
@@ -170,6 +317,244 @@ impl TestingStore {
         Ok(results)
     }
 
+    /// Store a completed run and all of its parsed results in one transaction.
+    pub fn save_test_run(
+        &self,
+        suite_id: i64,
+        command: &str,
+        format: &str,
+        exit_code: Option<i32>,
+        started_at: i64,
+        cases: &[crate::services::test_report_parser::ParsedTestCase],
+    ) -> Result<TestRun> {
+        let mut conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let finished_at = chrono::Utc::now().timestamp();
+        let passed = cases.iter().filter(|c| c.status == "passed").count() as i64;
+        let failed = cases.iter().filter(|c| c.status == "failed").count() as i64;
+        let skipped = cases.iter().filter(|c| c.status == "skipped").count() as i64;
+        let duration: f64 = cases.iter().filter_map(|c| c.duration).sum();
+
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO test_runs (suite_id, command, format, exit_code, passed, failed, skipped, duration, started_at, finished_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![suite_id, command, format, exit_code, passed, failed, skipped, duration, started_at, finished_at],
+        )?;
+        let run_id = tx.last_insert_rowid();
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO test_results (suite_id, name, status, duration, error, executed_at, run_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+            )?;
+            for case in cases {
+                stmt.execute(params![suite_id, case.name, case.status, case.duration, case.error, finished_at, run_id])?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(TestRun {
+            id: run_id,
+            suite_id,
+            command: command.to_string(),
+            format: format.to_string(),
+            exit_code,
+            passed,
+            failed,
+            skipped,
+            duration,
+            started_at,
+            finished_at,
+        })
+    }
+
+    pub fn list_test_runs(&self, suite_id: i64, limit: i64) -> Result<Vec<TestRun>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, suite_id, command, format, exit_code, passed, failed, skipped, duration, started_at, finished_at
+             FROM test_runs
+             WHERE suite_id = ?1
+             ORDER BY started_at DESC
+             LIMIT ?2"
+        )?;
+
+        let rows = stmt.query_map(params![suite_id, limit], |row| {
+            Ok(TestRun {
+                id: row.get(0)?,
+                suite_id: row.get(1)?,
+                command: row.get(2)?,
+                format: row.get(3)?,
+                exit_code: row.get(4)?,
+                passed: row.get(5)?,
+                failed: row.get(6)?,
+                skipped: row.get(7)?,
+                duration: row.get(8)?,
+                started_at: row.get(9)?,
+                finished_at: row.get(10)?,
+            })
+        })?;
+
+        let mut runs = Vec::new();
+        for row in rows {
+            runs.push(row?);
+        }
+        Ok(runs)
+    }
+
+    /// Find tests of a suite that alternate between passing and failing within
+    /// their last `window` executions, following each test by name across every
+    /// suite it runs in. A test needs at least `min_flips` status changes to be
+    /// flagged; streaks and time-of-day/parallelism correlations come along to
+    /// tell flakiness from breakage and point at its cause.
+    pub fn detect_flaky_tests(&self, suite_id: i64, window: usize, min_flips: usize) -> Result<Vec<FlakyTest>> {
+        let quarantined: std::collections::HashSet<String> = self
+            .list_quarantined(Some(suite_id))?
+            .into_iter()
+            .map(|q| q.test_name)
+            .collect();
+
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT r.name, r.status, r.suite_id, r.executed_at,
+                    CASE WHEN run.id IS NULL THEN NULL ELSE
+                        (SELECT COUNT(*) FROM test_runs other
+                         WHERE other.id != run.id
+                           AND other.started_at <= run.finished_at
+                           AND other.finished_at >= run.started_at)
+                    END
+             FROM test_results r
+             LEFT JOIN test_runs run ON run.id = r.run_id
+             WHERE r.status IN ('passed', 'failed')
+               AND r.name IN (SELECT name FROM test_results WHERE suite_id = ?1)
+             ORDER BY r.name, r.executed_at, r.id"
+        )?;
+        let rows = stmt.query_map(params![suite_id], |row| {
+            let executed_at: i64 = row.get(3)?;
+            Ok((
+                row.get::<_, String>(0)?,
+                FlakySample {
+                    failed: row.get::<_, String>(1)? == "failed",
+                    suite_id: row.get(2)?,
+                    hour: chrono::DateTime::from_timestamp(executed_at, 0)
+                        .map(|t| chrono::Timelike::hour(&t.with_timezone(&chrono::Local))),
+                    concurrent_runs: row.get(4)?,
+                },
+            ))
+        })?;
+
+        let mut history: std::collections::BTreeMap<String, Vec<FlakySample>> = std::collections::BTreeMap::new();
+        for row in rows {
+            let (name, sample) = row?;
+            history.entry(name).or_default().push(sample);
+        }
+
+        let mut flaky = Vec::new();
+        for (name, samples) in history {
+            let recent = &samples[samples.len().saturating_sub(window.max(2))..];
+            if recent.len() < 2 {
+                continue;
+            }
+            let failures = recent.iter().filter(|s| s.failed).count();
+            let passes = recent.len() - failures;
+            let flips = recent.windows(2).filter(|w| w[0].failed != w[1].failed).count();
+            if passes == 0 || failures == 0 || flips < min_flips {
+                continue;
+            }
+
+            let last_failed = recent[recent.len() - 1].failed;
+            let current_streak = recent.iter().rev().take_while(|s| s.failed == last_failed).count();
+            let longest_failure_streak = recent
+                .split(|s| !s.failed)
+                .map(|streak| streak.len())
+                .max()
+                .unwrap_or(0);
+
+            let mut suite_ids: Vec<i64> = recent.iter().map(|s| s.suite_id).collect();
+            suite_ids.sort_unstable();
+            suite_ids.dedup();
+
+            let outcomes: Vec<f64> = recent.iter().map(|s| if s.failed { 1.0 } else { 0.0 }).collect();
+            let blocks: Vec<Option<u32>> = recent.iter().map(|s| s.hour.map(|h| h / 6)).collect();
+            let (time_of_day_correlation, peak_block) = correlation_ratio(&blocks, &outcomes);
+            let (load, load_outcomes): (Vec<f64>, Vec<f64>) = recent
+                .iter()
+                .zip(&outcomes)
+                .filter_map(|(s, outcome)| s.concurrent_runs.map(|c| (c as f64, *outcome)))
+                .unzip();
+
+            flaky.push(FlakyTest {
+                quarantined: quarantined.contains(&name),
+                name,
+                runs: recent.len(),
+                passes,
+                failures,
+                flips,
+                flake_rate: flips as f64 / (recent.len() - 1) as f64,
+                last_status: if last_failed { "failed" } else { "passed" }.to_string(),
+                suite_ids,
+                current_streak,
+                longest_failure_streak,
+                time_of_day_correlation,
+                peak_failure_hour: peak_block.map(|b| b * 6),
+                parallelism_correlation: pearson(&load, &load_outcomes),
+            });
+        }
+
+        flaky.sort_by(|a, b| b.flake_rate.partial_cmp(&a.flake_rate).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(flaky)
+    }
+
+    pub fn quarantine_test(&self, suite_id: i64, test_name: &str, reason: Option<&str>, automatic: bool) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "INSERT OR REPLACE INTO test_quarantine (suite_id, test_name, reason, automatic, quarantined_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![suite_id, test_name, reason, if automatic { 1 } else { 0 }, now],
+        )?;
+        Ok(())
+    }
+
+    pub fn unquarantine_test(&self, suite_id: i64, test_name: &str) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "DELETE FROM test_quarantine WHERE suite_id = ?1 AND test_name = ?2",
+            params![suite_id, test_name],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_quarantined(&self, suite_id: Option<i64>) -> Result<Vec<QuarantinedTest>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT suite_id, test_name, reason, automatic, quarantined_at
+             FROM test_quarantine
+             WHERE (?1 IS NULL OR suite_id = ?1)
+             ORDER BY quarantined_at DESC"
+        )?;
+        let rows = stmt.query_map(params![suite_id], |row| {
+            Ok(QuarantinedTest {
+                suite_id: row.get(0)?,
+                test_name: row.get(1)?,
+                reason: row.get(2)?,
+                automatic: row.get::<_, i64>(3)? != 0,
+                quarantined_at: row.get(4)?,
+            })
+        })?;
+
+        let mut tests = Vec::new();
+        for row in rows {
+            tests.push(row?);
+        }
+        Ok(tests)
+    }
+
     pub fn get_suite_name(&self, suite_id: i64) -> Result<Option<String>> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -222,6 +607,52 @@ impl TestingStore {
     }
 }
 
+/// Correlation ratio (eta) of `values` over the groups they fall in, with the
+/// group whose mean is highest. Ungrouped values are left out; None without
+/// variance or with fewer than two groups.
+fn correlation_ratio(groups: &[Option<u32>], values: &[f64]) -> (Option<f64>, Option<u32>) {
+    let mut by_group: std::collections::BTreeMap<u32, (f64, usize)> = std::collections::BTreeMap::new();
+    for (group, value) in groups.iter().zip(values) {
+        if let Some(group) = group {
+            let entry = by_group.entry(*group).or_insert((0.0, 0));
+            entry.0 += value;
+            entry.1 += 1;
+        }
+    }
+    let means: Vec<(u32, f64)> = by_group.iter().map(|(group, (sum, count))| (*group, sum / *count as f64)).collect();
+    let peak = means.iter().max_by(|a, b| a.1.total_cmp(&b.1)).map(|(group, _)| *group);
+    if means.len() < 2 {
+        return (None, peak);
+    }
+
+    let grouped: Vec<f64> = groups.iter().zip(values).filter(|(g, _)| g.is_some()).map(|(_, v)| *v).collect();
+    let mean = grouped.iter().sum::<f64>() / grouped.len() as f64;
+    let total: f64 = grouped.iter().map(|v| (v - mean).powi(2)).sum();
+    if total == 0.0 {
+        return (None, peak);
+    }
+    let between: f64 = means
+        .iter()
+        .map(|(group, group_mean)| by_group[group].1 as f64 * (group_mean - mean).powi(2))
+        .sum();
+    (Some((between / total).sqrt()), peak)
+}
+
+fn pearson(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() < 2 {
+        return None;
+    }
+    let n = xs.len() as f64;
+    let (mean_x, mean_y) = (xs.iter().sum::<f64>() / n, ys.iter().sum::<f64>() / n);
+    let cov: f64 = xs.iter().zip(ys).map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let var_x: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+    let var_y: f64 = ys.iter().map(|y| (y - mean_y).powi(2)).sum();
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+    Some(cov / (var_x * var_y).sqrt())
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TestSuiteStats {
     pub total: usize,
@@ -230,3 +661,43 @@ pub struct TestSuiteStats {
     pub duration: f64,
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[test]
+    fn flaky_tests_are_tracked_across_suites() {
+        let db = test_support::test_db();
+        let store = TestingStore::new(db.conn.clone());
+        let unit = store.create_suite("unit", "unit").unwrap();
+        let ci = store.create_suite("ci", "integration").unwrap();
+        for (suite_id, status) in [(unit, "passed"), (ci, "failed"), (unit, "passed"), (ci, "failed"), (ci, "failed")] {
+            store.save_test_result(suite_id, "net::retries", status, None, None).unwrap();
+        }
+        store.save_test_result(unit, "math::adds", "passed", None, None).unwrap();
+
+        let flaky = store.detect_flaky_tests(unit, 20, 2).unwrap();
+        assert_eq!(flaky.len(), 1);
+        let test = &flaky[0];
+        assert_eq!(test.name, "net::retries");
+        assert_eq!(test.flips, 3);
+        assert_eq!(test.suite_ids, vec![unit, ci]);
+        assert_eq!(test.last_status, "failed");
+        assert_eq!(test.current_streak, 2);
+        assert_eq!(test.longest_failure_streak, 2);
+        // Not run as part of a run, so there's no load to correlate with
+        assert!(test.parallelism_correlation.is_none());
+    }
+
+    #[test]
+    fn correlations_pick_out_the_failing_block() {
+        let groups = [Some(0), Some(0), Some(3), Some(3)];
+        let (eta, peak) = correlation_ratio(&groups, &[0.0, 0.0, 1.0, 1.0]);
+        assert_eq!(peak, Some(3));
+        assert!((eta.unwrap() - 1.0).abs() < 1e-9);
+        assert!((pearson(&[0.0, 1.0, 2.0], &[0.0, 0.0, 1.0]).unwrap() - 0.866).abs() < 1e-3);
+        assert!(pearson(&[1.0, 1.0], &[0.0, 1.0]).is_none());
+    }
+}
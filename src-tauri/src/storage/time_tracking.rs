@@ -0,0 +1,266 @@
+use anyhow::Result;
+use rusqlite::{Connection, params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeEntry {
+    pub id: i64,
+    pub project_id: i64,
+    pub started_at: i64,
+    pub ended_at: Option<i64>,
+    pub duration_secs: i64,
+    pub source: String, // "manual", "auto"
+    pub note: Option<String>,
+    pub stopped_reason: Option<String>, // "manual", "idle", "app_switch"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeSummaryRow {
+    pub period_start: String,
+    pub project_id: i64,
+    pub project_name: String,
+    pub total_secs: i64,
+    pub entry_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoTrackingRule {
+    pub id: i64,
+    pub project_id: i64,
+    pub app_pattern: String,
+    pub created_at: i64,
+}
+
+pub struct TimeTrackingStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl TimeTrackingStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = TimeTrackingStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: TimeTrackingStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS time_entries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                project_id INTEGER NOT NULL,
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER,
+                source TEXT NOT NULL DEFAULT 'manual',
+                note TEXT,
+                stopped_reason TEXT,
+                FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS time_tracking_rules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                project_id INTEGER NOT NULL,
+                app_pattern TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_time_entries_project_started ON time_entries(project_id, started_at)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_time_entries_open ON time_entries(ended_at) WHERE ended_at IS NULL",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Start a timer for a project. Returns the existing entry if one is already running.
+    pub fn start_timer(&self, project_id: i64, source: &str, note: Option<&str>, started_at: i64) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        let running: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM time_entries WHERE project_id = ?1 AND ended_at IS NULL",
+                params![project_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(id) = running {
+            return Ok(id);
+        }
+
+        conn.execute(
+            "INSERT INTO time_entries (project_id, started_at, source, note) VALUES (?1, ?2, ?3, ?4)",
+            params![project_id, started_at, source, note],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Stop running timers (for one project or all). Returns the number of entries closed.
+    pub fn stop_timers(&self, project_id: Option<i64>, source: Option<&str>, ended_at: i64, reason: &str) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let stopped = conn.execute(
+            "UPDATE time_entries
+             SET ended_at = MAX(started_at, ?1), stopped_reason = ?2
+             WHERE ended_at IS NULL
+               AND (?3 IS NULL OR project_id = ?3)
+               AND (?4 IS NULL OR source = ?4)",
+            params![ended_at, reason, project_id, source],
+        )?;
+        Ok(stopped)
+    }
+
+    pub fn list_running(&self) -> Result<Vec<TimeEntry>> {
+        let now = chrono::Utc::now().timestamp();
+        self.query_entries(
+            "SELECT id, project_id, started_at, ended_at, source, note, stopped_reason
+             FROM time_entries WHERE ended_at IS NULL ORDER BY started_at",
+            params![],
+            now,
+        )
+    }
+
+    pub fn list_entries(&self, project_id: Option<i64>, from_ts: Option<i64>, to_ts: Option<i64>) -> Result<Vec<TimeEntry>> {
+        let now = chrono::Utc::now().timestamp();
+        self.query_entries(
+            "SELECT id, project_id, started_at, ended_at, source, note, stopped_reason
+             FROM time_entries
+             WHERE (?1 IS NULL OR project_id = ?1)
+               AND (?2 IS NULL OR started_at >= ?2)
+               AND (?3 IS NULL OR started_at <= ?3)
+             ORDER BY started_at DESC",
+            params![project_id, from_ts, to_ts],
+            now,
+        )
+    }
+
+    pub fn delete_entry(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM time_entries WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Aggregate closed and running entries per project per day (`daily`) or ISO week (`weekly`).
+    /// Buckets are computed in UTC on the entry start time.
+    pub fn summarize(&self, period: &str, from_ts: i64, to_ts: i64, project_id: Option<i64>) -> Result<Vec<TimeSummaryRow>> {
+        let bucket = match period {
+            "daily" => "strftime('%Y-%m-%d', e.started_at, 'unixepoch')",
+            "weekly" => "strftime('%Y-%m-%d', e.started_at, 'unixepoch', 'weekday 0', '-6 days')",
+            _ => return Err(anyhow::anyhow!("Invalid period: {}. Use 'daily' or 'weekly'", period)),
+        };
+
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        // sql-audit: bucket is one of the two expressions above
+        let sql = format!(
+            "SELECT {bucket} AS period_start, e.project_id, COALESCE(p.name, 'Project ' || e.project_id),
+                    SUM(COALESCE(e.ended_at, ?1) - e.started_at), COUNT(*)
+             FROM time_entries e
+             LEFT JOIN projects p ON p.id = e.project_id
+             WHERE e.started_at >= ?2 AND e.started_at <= ?3
+               AND (?4 IS NULL OR e.project_id = ?4)
+             GROUP BY period_start, e.project_id
+             ORDER BY period_start, e.project_id",
+            bucket = bucket
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params![now, from_ts, to_ts, project_id], |row| {
+            Ok(TimeSummaryRow {
+                period_start: row.get(0)?,
+                project_id: row.get(1)?,
+                project_name: row.get(2)?,
+                total_secs: row.get(3)?,
+                entry_count: row.get(4)?,
+            })
+        })?;
+
+        let mut summary = Vec::new();
+        for row in rows {
+            summary.push(row?);
+        }
+        Ok(summary)
+    }
+
+    pub fn add_rule(&self, project_id: i64, app_pattern: &str) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "INSERT INTO time_tracking_rules (project_id, app_pattern, created_at) VALUES (?1, ?2, ?3)",
+            params![project_id, app_pattern, now],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn delete_rule(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM time_tracking_rules WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn list_rules(&self) -> Result<Vec<AutoTrackingRule>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, project_id, app_pattern, created_at FROM time_tracking_rules ORDER BY id"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(AutoTrackingRule {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                app_pattern: row.get(2)?,
+                created_at: row.get(3)?,
+            })
+        })?;
+
+        let mut rules = Vec::new();
+        for row in rows {
+            rules.push(row?);
+        }
+        Ok(rules)
+    }
+
+    fn query_entries(&self, sql: &str, params: &[&dyn rusqlite::ToSql], now: i64) -> Result<Vec<TimeEntry>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map(params, |row| {
+            let started_at: i64 = row.get(2)?;
+            let ended_at: Option<i64> = row.get(3)?;
+            Ok(TimeEntry {
+                id: row.get(0)?,
+                project_id: row.get(1)?,
+                started_at,
+                ended_at,
+                duration_secs: (ended_at.unwrap_or(now) - started_at).max(0),
+                source: row.get(4)?,
+                note: row.get(5)?,
+                stopped_reason: row.get(6)?,
+            })
+        })?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    }
+}
@@ -0,0 +1,355 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// A transcribed audio file or podcast episode. Segments carry the
+/// timestamps, see `get_segments`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transcript {
+    pub id: i64,
+    pub title: String,
+    pub source: String, // file path or episode URL
+    pub file_hash: String,
+    pub article_id: Option<i64>, // the RSS item, for podcast episodes
+    pub language: Option<String>,
+    pub model: String,
+    pub duration_ms: i64,
+    pub segment_count: i64,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub id: i64,
+    pub transcript_id: i64,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewTranscript {
+    pub title: String,
+    pub source: String,
+    pub file_hash: String,
+    pub article_id: Option<i64>,
+    pub language: Option<String>,
+    pub model: String,
+    /// (start_ms, end_ms, text)
+    pub segments: Vec<(i64, i64, String)>,
+}
+
+/// Audio enclosure of an RSS item waiting to be transcribed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PodcastEpisode {
+    pub article_id: i64,
+    pub audio_url: String,
+    pub status: String, // pending, done, failed
+    pub transcript_id: Option<i64>,
+    pub error: Option<String>,
+    pub updated_at: i64,
+}
+
+pub struct TranscriptStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl TranscriptStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = TranscriptStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: TranscriptStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS transcripts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                source TEXT NOT NULL,
+                file_hash TEXT NOT NULL UNIQUE,
+                article_id INTEGER,
+                language TEXT,
+                model TEXT NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS transcript_segments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                transcript_id INTEGER NOT NULL,
+                start_ms INTEGER NOT NULL,
+                end_ms INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                FOREIGN KEY (transcript_id) REFERENCES transcripts(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS podcast_episodes (
+                article_id INTEGER PRIMARY KEY,
+                audio_url TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                transcript_id INTEGER,
+                error TEXT,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Search index shared with TemporalStore, which normally creates it
+        conn.execute(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS fts_documents USING fts5(
+                doc_type,
+                doc_id UNINDEXED,
+                title,
+                content,
+                ts UNINDEXED
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_transcript_segments_transcript ON transcript_segments(transcript_id, start_ms)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Store a transcript and index each segment, so search hits point at
+    /// the moment something was said.
+    pub fn insert_transcript(&self, transcript: &NewTranscript) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        let duration_ms = transcript.segments.iter().map(|(_, end, _)| *end).max().unwrap_or(0);
+
+        conn.execute(
+            "INSERT INTO transcripts (title, source, file_hash, article_id, language, model, duration_ms, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                transcript.title,
+                transcript.source,
+                transcript.file_hash,
+                transcript.article_id,
+                transcript.language,
+                transcript.model,
+                duration_ms,
+                now
+            ],
+        )?;
+        let id = conn.last_insert_rowid();
+
+        for (start_ms, end_ms, text) in &transcript.segments {
+            conn.execute(
+                "INSERT INTO transcript_segments (transcript_id, start_ms, end_ms, text) VALUES (?1, ?2, ?3, ?4)",
+                params![id, start_ms, end_ms, text],
+            )?;
+            let segment_id = conn.last_insert_rowid();
+            conn.execute(
+                "INSERT INTO fts_documents (doc_type, doc_id, title, content, ts) VALUES ('transcript_segment', ?1, ?2, ?3, ?4)",
+                params![segment_id, transcript.title, text, now],
+            )?;
+        }
+
+        Ok(id)
+    }
+
+    pub fn find_by_hash(&self, file_hash: &str) -> Result<Option<i64>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id FROM transcripts WHERE file_hash = ?1",
+            params![file_hash],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn get_transcript(&self, id: i64) -> Result<Option<Transcript>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT t.id, t.title, t.source, t.file_hash, t.article_id, t.language, t.model, t.duration_ms,
+                    (SELECT COUNT(*) FROM transcript_segments s WHERE s.transcript_id = t.id), t.created_at
+             FROM transcripts t WHERE t.id = ?1",
+            params![id],
+            row_to_transcript,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn list_transcripts(&self, limit: i64) -> Result<Vec<Transcript>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.title, t.source, t.file_hash, t.article_id, t.language, t.model, t.duration_ms,
+                    (SELECT COUNT(*) FROM transcript_segments s WHERE s.transcript_id = t.id), t.created_at
+             FROM transcripts t
+             ORDER BY t.created_at DESC
+             LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], row_to_transcript)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn get_segments(&self, transcript_id: i64) -> Result<Vec<TranscriptSegment>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, transcript_id, start_ms, end_ms, text
+             FROM transcript_segments
+             WHERE transcript_id = ?1
+             ORDER BY start_ms",
+        )?;
+        let rows = stmt.query_map(params![transcript_id], row_to_segment)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn get_segment(&self, id: i64) -> Result<Option<TranscriptSegment>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT id, transcript_id, start_ms, end_ms, text FROM transcript_segments WHERE id = ?1",
+            params![id],
+            row_to_segment,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn delete_transcript(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "DELETE FROM fts_documents WHERE doc_type = 'transcript_segment'
+               AND doc_id IN (SELECT id FROM transcript_segments WHERE transcript_id = ?1)",
+            params![id],
+        )?;
+        conn.execute("DELETE FROM transcript_segments WHERE transcript_id = ?1", params![id])?;
+        conn.execute(
+            "UPDATE podcast_episodes SET transcript_id = NULL, status = 'pending' WHERE transcript_id = ?1",
+            params![id],
+        )?;
+        conn.execute("DELETE FROM transcripts WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Remember an RSS item's audio enclosure. Already known episodes keep their status.
+    pub fn queue_episode(&self, article_id: i64, audio_url: &str) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR IGNORE INTO podcast_episodes (article_id, audio_url, status, updated_at)
+             VALUES (?1, ?2, 'pending', ?3)",
+            params![article_id, audio_url, chrono::Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    pub fn pending_episodes(&self, limit: i64) -> Result<Vec<PodcastEpisode>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT article_id, audio_url, status, transcript_id, error, updated_at
+             FROM podcast_episodes
+             WHERE status = 'pending'
+             ORDER BY updated_at DESC
+             LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], row_to_episode)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn get_episode(&self, article_id: i64) -> Result<Option<PodcastEpisode>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT article_id, audio_url, status, transcript_id, error, updated_at
+             FROM podcast_episodes WHERE article_id = ?1",
+            params![article_id],
+            row_to_episode,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn finish_episode(&self, article_id: i64, result: std::result::Result<i64, String>) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        match result {
+            Ok(transcript_id) => conn.execute(
+                "UPDATE podcast_episodes SET status = 'done', transcript_id = ?2, error = NULL, updated_at = ?3
+                 WHERE article_id = ?1",
+                params![article_id, transcript_id, now],
+            )?,
+            Err(error) => conn.execute(
+                "UPDATE podcast_episodes SET status = 'failed', error = ?2, updated_at = ?3 WHERE article_id = ?1",
+                params![article_id, error, now],
+            )?,
+        };
+        Ok(())
+    }
+}
+
+fn row_to_transcript(row: &rusqlite::Row) -> rusqlite::Result<Transcript> {
+    Ok(Transcript {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        source: row.get(2)?,
+        file_hash: row.get(3)?,
+        article_id: row.get(4)?,
+        language: row.get(5)?,
+        model: row.get(6)?,
+        duration_ms: row.get(7)?,
+        segment_count: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+fn row_to_segment(row: &rusqlite::Row) -> rusqlite::Result<TranscriptSegment> {
+    Ok(TranscriptSegment {
+        id: row.get(0)?,
+        transcript_id: row.get(1)?,
+        start_ms: row.get(2)?,
+        end_ms: row.get(3)?,
+        text: row.get(4)?,
+    })
+}
+
+fn row_to_episode(row: &rusqlite::Row) -> rusqlite::Result<PodcastEpisode> {
+    Ok(PodcastEpisode {
+        article_id: row.get(0)?,
+        audio_url: row.get(1)?,
+        status: row.get(2)?,
+        transcript_id: row.get(3)?,
+        error: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
@@ -1,9 +1,13 @@
 use anyhow::Result;
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 
+/// Collection holding image embeddings, apart from the text collections
+/// since the vectors live in a different space.
+pub const IMAGE_COLLECTION: &str = "images";
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VectorDocument {
     pub id: String,
@@ -245,6 +249,47 @@ impl VectorStore {
         Ok(())
     }
 
+    /// A document kept in SQLite. Documents whose vector lives in Qdrant
+    /// come back as None.
+    pub fn get_document(&self, id: &str) -> Result<Option<VectorDocument>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let row = conn
+            .query_row(
+                "SELECT id, collection, content, embedding, metadata, created_at, expires_at
+                 FROM vector_documents WHERE id = ?1",
+                params![id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                        row.get::<_, i64>(5)?,
+                        row.get::<_, Option<i64>>(6)?,
+                    ))
+                },
+            )
+            .optional()?;
+
+        let Some((id, collection, content, embedding_json, metadata_json, created_at, expires_at)) = row else {
+            return Ok(None);
+        };
+        let Ok(embedding) = serde_json::from_str::<Vec<f32>>(&embedding_json) else {
+            return Ok(None);
+        };
+        Ok(Some(VectorDocument {
+            id,
+            collection,
+            content,
+            embedding,
+            metadata: serde_json::from_str(&metadata_json).unwrap_or_default(),
+            created_at,
+            expires_at,
+        }))
+    }
+
     pub fn search_similar(
         &self,
         collection: &str,
@@ -337,6 +382,47 @@ impl VectorStore {
         Ok(results)
     }
 
+    /// Index an image embedding. `source` is the image's file path or URL
+    /// and `model` the embedding model, which searches must match.
+    pub fn index_image(
+        &self,
+        id: &str,
+        source: &str,
+        model: &str,
+        embedding: Vec<f32>,
+        mut metadata: serde_json::Value,
+    ) -> Result<()> {
+        self.create_collection(IMAGE_COLLECTION, embedding.len() as i32)?;
+        if let Some(obj) = metadata.as_object_mut() {
+            obj.insert("model".to_string(), serde_json::json!(model));
+        } else {
+            metadata = serde_json::json!({ "model": model });
+        }
+        self.insert_document(&VectorDocument {
+            id: id.to_string(),
+            collection: IMAGE_COLLECTION.to_string(),
+            content: source.to_string(),
+            embedding,
+            metadata,
+            created_at: chrono::Utc::now().timestamp(),
+            expires_at: None,
+        })
+    }
+
+    /// Reverse image lookup: indexed images closest to `query_embedding`
+    /// that were embedded with the same model.
+    pub fn search_similar_images(
+        &self,
+        model: &str,
+        query_embedding: &[f32],
+        limit: i32,
+        min_similarity: f32,
+    ) -> Result<Vec<(VectorDocument, f32)>> {
+        let mut results = self.search_similar(IMAGE_COLLECTION, query_embedding, limit, min_similarity)?;
+        results.retain(|(doc, _)| doc.metadata.get("model").and_then(|m| m.as_str()) == Some(model));
+        Ok(results)
+    }
+
     pub fn delete_expired(&self) -> Result<usize> {
         let conn = self.conn.lock()
             .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
@@ -380,7 +466,14 @@ pub struct CollectionStats {
     pub expired: usize,
 }
 
-fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+/// Id of an image in `IMAGE_COLLECTION`, from its content so the same
+/// image found in several places is indexed once.
+pub fn image_document_id(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    format!("image:{:x}", Sha256::digest(bytes))
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() {
         return 0.0;
     }
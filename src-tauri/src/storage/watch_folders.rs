@@ -0,0 +1,265 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// A folder whose new files are fed through one ingestion pipeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchFolder {
+    pub id: i64,
+    pub path: String,
+    pub pipeline: String, // documents|portfolio_csv|json_events|ollama_models
+    pub enabled: bool,
+    /// Pipeline settings, e.g. {"portfolio_id": 1, "profile_id": 2} for portfolio_csv
+    pub options: serde_json::Value,
+    pub last_scan_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// A file a watch folder has handled, by content hash, so renames and
+/// restarts don't process it twice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchFolderFile {
+    pub folder_id: i64,
+    pub file_hash: String,
+    pub path: String,
+    pub status: String, // processed|duplicate|failed
+    pub detail: Option<String>,
+    pub processed_at: i64,
+}
+
+pub struct WatchFolderStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl WatchFolderStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = WatchFolderStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: WatchFolderStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS watch_folders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                path TEXT NOT NULL,
+                pipeline TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                options_json TEXT NOT NULL DEFAULT '{}',
+                last_scan_at INTEGER,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                UNIQUE(path, pipeline)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS watch_folder_files (
+                folder_id INTEGER NOT NULL,
+                file_hash TEXT NOT NULL,
+                path TEXT NOT NULL,
+                status TEXT NOT NULL,
+                detail TEXT,
+                processed_at INTEGER NOT NULL,
+                PRIMARY KEY (folder_id, file_hash),
+                FOREIGN KEY (folder_id) REFERENCES watch_folders(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_watch_folder_files_processed ON watch_folder_files(folder_id, processed_at)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_folder(&self, path: &str, pipeline: &str, options: &serde_json::Value) -> Result<i64> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "INSERT INTO watch_folders (path, pipeline, enabled, options_json, created_at, updated_at)
+             VALUES (?1, ?2, 1, ?3, ?4, ?4)",
+            params![path, pipeline, options.to_string(), now],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn update_folder(&self, id: i64, path: &str, options: &serde_json::Value, enabled: bool) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let updated = conn.execute(
+            "UPDATE watch_folders SET path = ?1, options_json = ?2, enabled = ?3, updated_at = ?4 WHERE id = ?5",
+            params![path, options.to_string(), enabled as i64, chrono::Utc::now().timestamp(), id],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Watch folder {} not found", id);
+        }
+        Ok(())
+    }
+
+    pub fn set_enabled(&self, id: i64, enabled: bool) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE watch_folders SET enabled = ?1, updated_at = ?2 WHERE id = ?3",
+            params![enabled as i64, chrono::Utc::now().timestamp(), id],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_folder(&self, id: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM watch_folder_files WHERE folder_id = ?1", params![id])?;
+        conn.execute("DELETE FROM watch_folders WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn get_folder(&self, id: i64) -> Result<Option<WatchFolder>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let folder = conn
+            .query_row(
+                "SELECT id, path, pipeline, enabled, options_json, last_scan_at, created_at, updated_at
+                 FROM watch_folders WHERE id = ?1",
+                params![id],
+                row_to_folder,
+            )
+            .optional()?;
+        Ok(folder)
+    }
+
+    pub fn find_folder(&self, path: &str, pipeline: &str) -> Result<Option<WatchFolder>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let folder = conn
+            .query_row(
+                "SELECT id, path, pipeline, enabled, options_json, last_scan_at, created_at, updated_at
+                 FROM watch_folders WHERE path = ?1 AND pipeline = ?2",
+                params![path, pipeline],
+                row_to_folder,
+            )
+            .optional()?;
+        Ok(folder)
+    }
+
+    pub fn list_folders(&self) -> Result<Vec<WatchFolder>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, path, pipeline, enabled, options_json, last_scan_at, created_at, updated_at
+             FROM watch_folders ORDER BY pipeline, path"
+        )?;
+        let rows = stmt.query_map([], row_to_folder)?;
+
+        let mut folders = Vec::new();
+        for row in rows {
+            folders.push(row?);
+        }
+        Ok(folders)
+    }
+
+    pub fn mark_scanned(&self, id: i64, scanned_at: i64) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "UPDATE watch_folders SET last_scan_at = ?1 WHERE id = ?2",
+            params![scanned_at, id],
+        )?;
+        Ok(())
+    }
+
+    pub fn is_handled(&self, folder_id: i64, file_hash: &str) -> Result<bool> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let found: Option<i64> = conn
+            .query_row(
+                "SELECT 1 FROM watch_folder_files WHERE folder_id = ?1 AND file_hash = ?2",
+                params![folder_id, file_hash],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(found.is_some())
+    }
+
+    pub fn record_file(&self, file: &WatchFolderFile) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT INTO watch_folder_files (folder_id, file_hash, path, status, detail, processed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(folder_id, file_hash) DO UPDATE SET
+                path = excluded.path,
+                status = excluded.status,
+                detail = excluded.detail,
+                processed_at = excluded.processed_at",
+            params![file.folder_id, file.file_hash, file.path, file.status, file.detail, file.processed_at],
+        )?;
+        Ok(())
+    }
+
+    /// Newest first.
+    pub fn list_files(&self, folder_id: i64, status: Option<&str>, limit: i64) -> Result<Vec<WatchFolderFile>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT folder_id, file_hash, path, status, detail, processed_at
+             FROM watch_folder_files
+             WHERE folder_id = ?1 AND (?2 IS NULL OR status = ?2)
+             ORDER BY processed_at DESC
+             LIMIT ?3"
+        )?;
+        let rows = stmt.query_map(params![folder_id, status, limit], |row| {
+            Ok(WatchFolderFile {
+                folder_id: row.get(0)?,
+                file_hash: row.get(1)?,
+                path: row.get(2)?,
+                status: row.get(3)?,
+                detail: row.get(4)?,
+                processed_at: row.get(5)?,
+            })
+        })?;
+
+        let mut files = Vec::new();
+        for row in rows {
+            files.push(row?);
+        }
+        Ok(files)
+    }
+
+    /// Forget failed files so the next scan retries them. Returns how many.
+    pub fn clear_failed(&self, folder_id: i64) -> Result<usize> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let cleared = conn.execute(
+            "DELETE FROM watch_folder_files WHERE folder_id = ?1 AND status = 'failed'",
+            params![folder_id],
+        )?;
+        Ok(cleared)
+    }
+}
+
+fn row_to_folder(row: &rusqlite::Row) -> rusqlite::Result<WatchFolder> {
+    let options: String = row.get(4)?;
+    Ok(WatchFolder {
+        id: row.get(0)?,
+        path: row.get(1)?,
+        pipeline: row.get(2)?,
+        enabled: row.get::<_, i64>(3)? == 1,
+        options: serde_json::from_str(&options).unwrap_or(serde_json::Value::Object(Default::default())),
+        last_scan_at: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
@@ -0,0 +1,137 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// A window and the layout it is restored with. `topics` are the WebSocket
+/// message types routed to it ("*" for everything).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppWindow {
+    pub label: String,
+    /// Frontend route the window shows, e.g. "/alerts"
+    pub route: String,
+    pub title: String,
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    pub topics: Vec<String>,
+    /// Reopened on next launch
+    pub open: bool,
+    pub updated_at: i64,
+}
+
+pub struct WindowStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl WindowStore {
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        let store = WindowStore { conn };
+        if let Err(e) = store.init_schema() {
+            eprintln!("WARNING: WindowStore schema initialization failed: {}", e);
+        }
+        store
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS app_windows (
+                label TEXT PRIMARY KEY,
+                route TEXT NOT NULL,
+                title TEXT NOT NULL,
+                x INTEGER,
+                y INTEGER,
+                width INTEGER NOT NULL,
+                height INTEGER NOT NULL,
+                maximized INTEGER NOT NULL DEFAULT 0,
+                topics TEXT NOT NULL DEFAULT '[]',
+                open INTEGER NOT NULL DEFAULT 1,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn save_window(&self, window: &AppWindow) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO app_windows
+             (label, route, title, x, y, width, height, maximized, topics, open, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                window.label,
+                window.route,
+                window.title,
+                window.x,
+                window.y,
+                window.width,
+                window.height,
+                if window.maximized { 1 } else { 0 },
+                serde_json::to_string(&window.topics)?,
+                if window.open { 1 } else { 0 },
+                chrono::Utc::now().timestamp()
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_window(&self, label: &str) -> Result<Option<AppWindow>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.query_row(
+            "SELECT label, route, title, x, y, width, height, maximized, topics, open, updated_at
+             FROM app_windows WHERE label = ?1",
+            params![label],
+            row_to_window,
+        )
+        .optional()
+        .map_err(Into::into)
+    }
+
+    pub fn list_windows(&self) -> Result<Vec<AppWindow>> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        let mut stmt = conn.prepare(
+            "SELECT label, route, title, x, y, width, height, maximized, topics, open, updated_at
+             FROM app_windows ORDER BY label",
+        )?;
+        let rows = stmt.query_map([], row_to_window)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    pub fn delete_window(&self, label: &str) -> Result<()> {
+        let conn = self.conn.lock()
+            .map_err(|e| anyhow::anyhow!("Database lock poisoned: {}", e))?;
+        conn.execute("DELETE FROM app_windows WHERE label = ?1", params![label])?;
+        Ok(())
+    }
+}
+
+fn row_to_window(row: &rusqlite::Row) -> rusqlite::Result<AppWindow> {
+    let topics: String = row.get(8)?;
+    Ok(AppWindow {
+        label: row.get(0)?,
+        route: row.get(1)?,
+        title: row.get(2)?,
+        x: row.get(3)?,
+        y: row.get(4)?,
+        width: row.get(5)?,
+        height: row.get(6)?,
+        maximized: row.get::<_, i64>(7)? == 1,
+        topics: serde_json::from_str(&topics).unwrap_or_default(),
+        open: row.get::<_, i64>(9)? == 1,
+        updated_at: row.get(10)?,
+    })
+}
@@ -0,0 +1,160 @@
+//! Shared fixtures for unit tests: an in-memory database with every store's
+//! schema, factories for common rows and golden-file assertions.
+
+use crate::storage::schema_integrity::init_all_stores;
+use crate::storage::temporal::NewTemporalEvent;
+use crate::storage::{
+    Database, MarketDataStore, MarketPrice, MigrationManager, OSINTStore, PortfolioStore, TemporalEvent, TemporalStore,
+};
+use rusqlite::params;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Fixed timestamp fixtures use, so golden output doesn't depend on the clock.
+pub const FIXTURE_TS: i64 = 1_700_000_000;
+
+/// In-memory database with all store schemas and migrations applied.
+pub fn test_db() -> Database {
+    let db = Database::open_in_memory().expect("open in-memory database");
+    init_all_stores(db.conn.clone()).expect("create store schemas");
+    MigrationManager::new()
+        .migrate(&db.conn.lock().unwrap())
+        .expect("apply migrations");
+    db
+}
+
+pub fn feed(db: &Database, name: &str) -> i64 {
+    OSINTStore::new(db.conn.clone())
+        .create_feed(&format!("https://{}.example/feed", name.to_lowercase().replace(' ', "-")), name, None)
+        .expect("create feed")
+}
+
+/// An article in a feed named after its domain.
+pub fn article(db: &Database, source: &str, title: &str, content: &str) -> i64 {
+    let feed_id = feed(db, source);
+    let url = format!("https://{}.example/{}", source.to_lowercase(), title.to_lowercase().replace(' ', "-"));
+    OSINTStore::new(db.conn.clone())
+        .save_rss_item(feed_id, title, content, &url, FIXTURE_TS)
+        .expect("save article")
+}
+
+/// A new event with neutral scores; adjust fields before passing it to `event`.
+pub fn new_event(title: &str) -> NewTemporalEvent {
+    NewTemporalEvent {
+        title: title.to_string(),
+        summary: String::new(),
+        start_ts: FIXTURE_TS,
+        end_ts: FIXTURE_TS + 3600,
+        event_type: "news".to_string(),
+        confidence: 0.5,
+        severity: 0.5,
+        novelty_score: 0.5,
+        volume_score: 0.5,
+        sentiment_score: 0.0,
+        cluster_key: format!("test:{}", title.to_lowercase()),
+    }
+}
+
+/// Store an event with the given articles as evidence.
+pub fn event(db: &Database, event: &NewTemporalEvent, article_ids: &[i64]) -> TemporalEvent {
+    let store = TemporalStore::new(db.conn.clone());
+    let (id, _) = store.upsert_event(event, article_ids).expect("upsert event");
+    store.get_event(id).expect("load event").expect("event exists")
+}
+
+pub fn alert_rule(db: &Database, name: &str, rule_json: serde_json::Value) -> i64 {
+    TemporalStore::new(db.conn.clone())
+        .create_alert_rule(name, true, None, &rule_json, None, None)
+        .expect("create alert rule")
+}
+
+/// A fired alert, bypassing the rule engine.
+pub fn alert(db: &Database, rule_id: i64, event_id: Option<i64>) -> i64 {
+    let conn = db.conn.lock().unwrap();
+    conn.execute(
+        "INSERT INTO alerts (rule_id, fired_at, event_id, payload_json, status) VALUES (?1, ?2, ?3, '{}', 'new')",
+        params![rule_id, FIXTURE_TS, event_id],
+    )
+    .expect("insert alert");
+    conn.last_insert_rowid()
+}
+
+/// A USD portfolio from (ticker, quantity, purchase price) rows.
+pub fn portfolio(db: &Database, name: &str, holdings: &[(&str, f64, f64)]) -> i64 {
+    let store = PortfolioStore::new(db.conn.clone());
+    let id = store.create_portfolio(name).expect("create portfolio");
+    for (ticker, quantity, price) in holdings {
+        store
+            .add_holding(id, ticker, *quantity, *price, FIXTURE_TS, "USD")
+            .expect("add holding");
+    }
+    id
+}
+
+pub fn price(db: &Database, ticker: &str, price: f64) {
+    MarketDataStore::new(db.conn.clone())
+        .upsert_price(&MarketPrice {
+            ticker: ticker.to_string(),
+            price,
+            change: 0.0,
+            change_percent: 0.0,
+            volume: 0,
+            timestamp: FIXTURE_TS,
+            session: None,
+        })
+        .expect("upsert price");
+}
+
+/// Round for golden output so float noise doesn't fail comparisons.
+pub fn round4(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+/// Compare `actual` with `testdata/golden/<name>.json`. Run with
+/// `UPDATE_GOLDEN=1` to write the file from the current output instead.
+pub fn assert_golden<T: Serialize>(name: &str, actual: &T) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata/golden")
+        .join(format!("{}.json", name));
+    let actual = serde_json::to_value(actual).expect("serialize golden output");
+
+    if std::env::var("UPDATE_GOLDEN").is_ok_and(|v| v == "1") {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+        return;
+    }
+    let expected: serde_json::Value = match std::fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).expect("parse golden file"),
+        Err(_) => panic!("Missing golden file {}; run with UPDATE_GOLDEN=1 to create it", path.display()),
+    };
+    assert_eq!(
+        actual,
+        expected,
+        "Output differs from {}; run with UPDATE_GOLDEN=1 if the change is intended",
+        path.display()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factories_link_articles_events_and_alerts() {
+        let db = test_db();
+        let first = article(&db, "Reuters", "Acme agrees merger", "Acme and Globex agree terms");
+        let second = article(&db, "Bloomberg", "Globex confirms deal", "Globex board approves");
+        let stored = event(&db, &new_event("Acme Globex merger"), &[first, second]);
+        let rule_id = alert_rule(&db, "Mergers", serde_json::json!({ "any": [{ "type": "contains_keyword", "keyword": "merger" }] }));
+        alert(&db, rule_id, Some(stored.id));
+
+        let conn = db.conn.lock().unwrap();
+        let evidence: i64 = conn
+            .query_row("SELECT COUNT(*) FROM temporal_event_evidence WHERE event_id = ?1", [stored.id], |row| row.get(0))
+            .unwrap();
+        let alerts: i64 = conn
+            .query_row("SELECT COUNT(*) FROM alerts WHERE event_id = ?1", [stored.id], |row| row.get(0))
+            .unwrap();
+        assert_eq!((evidence, alerts), (2, 1));
+    }
+}
@@ -1,14 +1,16 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
+use std::time::Instant;
 use tokio::time::{interval, Duration};
-use tauri::{Manager, Emitter};
+use tauri::Manager;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WsMessage {
     SystemMetrics(crate::SystemMetrics),
     ProcessUpdate(crate::providers::process::ProcessInfo),
+    ProcessBatch(Vec<crate::providers::process::ProcessInfo>),
     NetworkUpdate(crate::providers::network::NetworkInterface),
     Error(crate::storage::ErrorRecord),
     ConfigUpdate { key: String, value: String },
@@ -18,20 +20,297 @@ pub enum WsMessage {
     MarketDataBatch(Vec<crate::storage::MarketPrice>),
     Message(crate::storage::messaging::Message),
     MessageTyping { conversation_id: i64, sender: String },
+    OllamaModelState(crate::services::ollama_lifecycle::ModelLoadState),
+    Alert(serde_json::Value),
     Ping,
     Pong,
 }
 
+/// Messages kept per topic for clients catching up after a reconnect.
+const HISTORY_PER_TOPIC: usize = 200;
+/// Batch window for clients that negotiate a compact encoding without
+/// choosing one.
+pub const DEFAULT_BATCH_MS: u64 = 250;
+/// Resolution of the broadcast loops; intervals below it can't be honoured
+pub const TICK_MS: u64 = 100;
+/// JSON of `BroadcastIntervals`
+pub const CONFIG_BROADCAST_INTERVALS: &str = "ws_broadcast_intervals";
+
+/// How often each family of data is pushed, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BroadcastIntervals {
+    pub system_metrics_ms: u64,
+    /// Process list snapshots, only while someone subscribes to "processes"
+    pub processes_ms: u64,
+    /// While any streamed ticker's market is in a session
+    pub market_data_ms: u64,
+    /// While the markets of all streamed tickers are closed
+    pub market_data_closed_ms: u64,
+    /// 0 sends each alert as it fires; otherwise they're held and sent together
+    pub alerts_ms: u64,
+}
+
+impl Default for BroadcastIntervals {
+    fn default() -> Self {
+        BroadcastIntervals {
+            system_metrics_ms: 2000,
+            processes_ms: 5000,
+            market_data_ms: 1000,
+            market_data_closed_ms: 30_000,
+            alerts_ms: 0,
+        }
+    }
+}
+
+impl BroadcastIntervals {
+    pub fn load(db: &crate::storage::Database) -> Self {
+        db.get_config(CONFIG_BROADCAST_INTERVALS)
+            .ok()
+            .flatten()
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, db: &crate::storage::Database) -> Result<(), String> {
+        self.validate()?;
+        let json = serde_json::to_string(self).map_err(|e| format!("Failed to encode intervals: {}", e))?;
+        db.set_config(CONFIG_BROADCAST_INTERVALS, &json)
+            .map_err(|e| format!("Failed to save intervals: {}", e))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let periodic = [
+            ("system_metrics_ms", self.system_metrics_ms),
+            ("processes_ms", self.processes_ms),
+            ("market_data_ms", self.market_data_ms),
+            ("market_data_closed_ms", self.market_data_closed_ms),
+        ];
+        for (name, ms) in periodic {
+            if ms < TICK_MS {
+                return Err(format!("{} must be at least {}ms", name, TICK_MS));
+            }
+        }
+        if self.alerts_ms != 0 && self.alerts_ms < TICK_MS {
+            return Err(format!("alerts_ms must be 0 or at least {}ms", TICK_MS));
+        }
+        Ok(())
+    }
+}
+
+/// Whether a family last sent at `last` is due again, marking it sent if so.
+pub fn due(last: &mut Option<Instant>, every_ms: u64) -> bool {
+    let now = Instant::now();
+    if last.is_some_and(|at| now.duration_since(at) < Duration::from_millis(every_ms)) {
+        return false;
+    }
+    *last = Some(now);
+    true
+}
+
+/// How a client wants its messages delivered. Anything but JSON is batched
+/// and sent as one base64 payload per batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WsEncoding {
+    #[default]
+    Json,
+    Msgpack,
+    Gzip,
+}
+
+impl WsEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WsEncoding::Json => "json",
+            WsEncoding::Msgpack => "msgpack",
+            WsEncoding::Gzip => "gzip",
+        }
+    }
+
+    /// Encode a batch of frontend events: as-is for JSON, otherwise as a
+    /// base64 string of MessagePack or gzipped JSON.
+    pub fn encode(&self, events: Vec<serde_json::Value>) -> Result<serde_json::Value, String> {
+        use base64::Engine;
+        use std::io::Write;
+
+        let bytes = match self {
+            WsEncoding::Json => return Ok(serde_json::Value::Array(events)),
+            WsEncoding::Msgpack => rmp_serde::to_vec_named(&events)
+                .map_err(|e| format!("Failed to encode MessagePack: {}", e))?,
+            WsEncoding::Gzip => {
+                let json = serde_json::to_vec(&events).map_err(|e| format!("Failed to encode JSON: {}", e))?;
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+                encoder.write_all(&json).map_err(|e| format!("Failed to compress: {}", e))?;
+                encoder.finish().map_err(|e| format!("Failed to compress: {}", e))?
+            }
+        };
+        Ok(serde_json::Value::String(base64::engine::general_purpose::STANDARD.encode(bytes)))
+    }
+}
+
+/// Drop updates a later one in the same batch supersedes: all but the last
+/// system metrics sample and process list, and all but the last quote per
+/// ticker.
+pub fn coalesce(envelopes: Vec<WsEnvelope>) -> Vec<WsEnvelope> {
+    let mut seen_metrics = false;
+    let mut seen_processes = false;
+    let mut seen_tickers = std::collections::HashSet::new();
+    let mut kept: Vec<WsEnvelope> = envelopes
+        .into_iter()
+        .rev()
+        .filter(|envelope| match &envelope.message {
+            WsMessage::SystemMetrics(_) => !std::mem::replace(&mut seen_metrics, true),
+            WsMessage::ProcessBatch(_) => !std::mem::replace(&mut seen_processes, true),
+            WsMessage::MarketData(price) => seen_tickers.insert(price.ticker.clone()),
+            _ => true,
+        })
+        .collect();
+    kept.reverse();
+    kept
+}
+
+/// A message as delivered to connections: numbered so clients can ask for
+/// what they missed. Keepalives aren't numbered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WsEnvelope {
+    pub seq: Option<u64>,
+    pub topic: String,
+    pub timestamp: i64,
+    pub message: WsMessage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WsReplay {
+    /// Oldest first
+    pub messages: Vec<WsEnvelope>,
+    pub latest_seq: u64,
+    /// False when messages after `since_seq` were already evicted, so the
+    /// client should refetch state instead
+    pub complete: bool,
+}
+
+#[derive(Default)]
+struct TopicHistory {
+    messages: VecDeque<WsEnvelope>,
+    /// Highest sequence number dropped from this topic
+    evicted_through: u64,
+}
+
+/// Bounded per-topic ring buffers with one sequence shared by all topics.
+#[derive(Default)]
+struct History {
+    latest_seq: u64,
+    topics: HashMap<String, TopicHistory>,
+}
+
+impl History {
+    fn record(&mut self, topic: &str, message: WsMessage) -> WsEnvelope {
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        // Process lists are large and stale by the next snapshot; not worth replaying
+        if matches!(message, WsMessage::Ping | WsMessage::Pong | WsMessage::ProcessBatch(_)) {
+            return WsEnvelope { seq: None, topic: topic.to_string(), timestamp, message };
+        }
+
+        self.latest_seq += 1;
+        let envelope = WsEnvelope { seq: Some(self.latest_seq), topic: topic.to_string(), timestamp, message };
+        let history = self.topics.entry(topic.to_string()).or_default();
+        history.messages.push_back(envelope.clone());
+        while history.messages.len() > HISTORY_PER_TOPIC {
+            if let Some(evicted) = history.messages.pop_front() {
+                history.evicted_through = evicted.seq.unwrap_or(history.evicted_through);
+            }
+        }
+        envelope
+    }
+
+    /// Messages after `since_seq` on the given topics ("*" for all).
+    fn replay(&self, topics: &[String], since_seq: u64) -> WsReplay {
+        let all = topics.iter().any(|t| t == "*");
+        let mut complete = true;
+        let mut messages = Vec::new();
+        for (topic, history) in &self.topics {
+            if !all && !topics.contains(topic) {
+                continue;
+            }
+            if history.evicted_through > since_seq {
+                complete = false;
+            }
+            messages.extend(history.messages.iter().filter(|m| m.seq > Some(since_seq)).cloned());
+        }
+        messages.sort_by_key(|m| m.seq);
+        WsReplay { messages, latest_seq: self.latest_seq, complete }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct WsConnection {
     pub id: String,
     pub topics: Vec<String>,
-    pub sender: broadcast::Sender<WsMessage>,
+    pub sender: broadcast::Sender<WsEnvelope>,
 }
 
 pub struct WsServer {
     connections: Arc<Mutex<HashMap<String, WsConnection>>>,
     system_metrics_tx: Arc<Mutex<Option<broadcast::Sender<WsMessage>>>>,
+    history: Arc<Mutex<History>>,
+    intervals: Arc<Mutex<BroadcastIntervals>>,
+    /// Alerts held until the next alerts interval
+    pending_alerts: Arc<Mutex<Vec<serde_json::Value>>>,
+}
+
+/// Number a message, remember it and deliver it to the connections
+/// subscribed to its topic. Returns how many connections it was sent to.
+fn dispatch(
+    connections: &Mutex<HashMap<String, WsConnection>>,
+    history: &Mutex<History>,
+    topic: &str,
+    message: WsMessage,
+) -> Result<usize, String> {
+    let envelope = history.lock()
+        .map_err(|e| format!("Failed to lock history: {}", e))?
+        .record(topic, message);
+    let conns = connections.lock()
+        .map_err(|e| format!("Failed to lock connections: {}", e))?;
+    let mut count = 0;
+    for conn in conns.values() {
+        if conn.topics.iter().any(|t| t == topic || t == "*") && conn.sender.send(envelope.clone()).is_ok() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn has_subscribers(connections: &Mutex<HashMap<String, WsConnection>>, topic: &str) -> bool {
+    connections
+        .lock()
+        .map(|conns| conns.values().any(|c| c.topics.iter().any(|t| t == topic || t == "*")))
+        .unwrap_or(false)
+}
+
+/// Publish a fired alert through the app's WebSocket server, if it runs.
+/// `source` says which alert system fired it ("temporal", "price").
+pub fn publish_alert(app: &tauri::AppHandle, source: &str, alert: serde_json::Value) {
+    if let Some(server) = app.try_state::<Mutex<Arc<WsServer>>>() {
+        if let Ok(server) = server.lock() {
+            let _ = server.publish_alert(serde_json::json!({ "source": source, "alert": alert }));
+        }
+    }
+}
+
+/// Keepalive to every connection, whatever its topics.
+fn ping_all(connections: &Mutex<HashMap<String, WsConnection>>) {
+    let envelope = WsEnvelope {
+        seq: None,
+        topic: String::new(),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        message: WsMessage::Ping,
+    };
+    if let Ok(conns) = connections.lock() {
+        for conn in conns.values() {
+            let _ = conn.sender.send(envelope.clone());
+        }
+    }
 }
 
 impl WsServer {
@@ -39,9 +318,36 @@ impl WsServer {
         WsServer {
             connections: Arc::new(Mutex::new(HashMap::new())),
             system_metrics_tx: Arc::new(Mutex::new(None)),
+            history: Arc::new(Mutex::new(History::default())),
+            intervals: Arc::new(Mutex::new(BroadcastIntervals::default())),
+            pending_alerts: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
+    pub fn broadcast_intervals(&self) -> BroadcastIntervals {
+        self.intervals.lock().map(|i| *i).unwrap_or_default()
+    }
+
+    /// Takes effect on the broadcast loops' next tick.
+    pub fn set_broadcast_intervals(&self, intervals: BroadcastIntervals) -> Result<(), String> {
+        intervals.validate()?;
+        *self.intervals.lock().map_err(|e| format!("Failed to lock intervals: {}", e))? = intervals;
+        Ok(())
+    }
+
+    /// Send a fired alert on the "alerts" topic, now or with the next batch
+    /// depending on the alerts interval.
+    pub fn publish_alert(&self, alert: serde_json::Value) -> Result<(), String> {
+        if self.broadcast_intervals().alerts_ms == 0 {
+            return self.publish("alerts", WsMessage::Alert(alert));
+        }
+        self.pending_alerts
+            .lock()
+            .map_err(|e| format!("Failed to lock pending alerts: {}", e))?
+            .push(alert);
+        Ok(())
+    }
+
     pub fn start_broadcast(&self, app: tauri::AppHandle) {
         let (tx, _) = broadcast::channel::<WsMessage>(1000);
         if let Ok(mut tx_guard) = self.system_metrics_tx.lock() {
@@ -51,14 +357,43 @@ impl WsServer {
         }
 
         let connections = self.connections.clone();
+        let history = self.history.clone();
+        let intervals = self.intervals.clone();
+        let pending_alerts = self.pending_alerts.clone();
         let app_handle = app.clone();
 
         // Use Tauri's async runtime to spawn the task
         tauri::async_runtime::spawn(async move {
-            let mut interval = interval(Duration::from_secs(1));
+            let mut interval = interval(Duration::from_millis(TICK_MS));
+            let (mut last_metrics, mut last_processes, mut last_alerts) = (None, None, None);
+            let heartbeat = crate::services::watchdog::Watchdog::register("ws_broadcast", Duration::from_millis(TICK_MS));
             
             loop {
                 interval.tick().await;
+                if !heartbeat.beat() {
+                    break;
+                }
+                let every = intervals.lock().map(|i| *i).unwrap_or_default();
+
+                if every.alerts_ms > 0 && due(&mut last_alerts, every.alerts_ms) {
+                    let alerts = pending_alerts.lock().map(|mut p| std::mem::take(&mut *p)).unwrap_or_default();
+                    for alert in alerts {
+                        let _ = dispatch(&connections, &history, "alerts", WsMessage::Alert(alert));
+                    }
+                }
+
+                if due(&mut last_processes, every.processes_ms) && has_subscribers(&connections, "processes") {
+                    if let Some(processes) = app_handle.try_state::<std::sync::Mutex<crate::providers::ProcessProvider>>() {
+                        if let Ok(mut provider) = processes.try_lock() {
+                            provider.refresh();
+                            let _ = dispatch(&connections, &history, "processes", WsMessage::ProcessBatch(provider.get_processes()));
+                        }
+                    }
+                }
+
+                if !due(&mut last_metrics, every.system_metrics_ms) {
+                    continue;
+                }
 
                 // Fetch and broadcast system metrics
                 if let Some(metrics_guard) = app_handle.try_state::<std::sync::Mutex<crate::providers::SystemProvider>>() {
@@ -132,42 +467,23 @@ impl WsServer {
                         }
 
                         // Emit Tauri event for frontend
-                        let _ = app_handle.emit("ws-message", serde_json::json!({
+                        let _ = crate::services::window_router::emit(&app_handle, serde_json::json!({
                             "type": "system-metrics",
                             "data": system_metrics,
                             "timestamp": chrono::Utc::now().timestamp_millis(),
                         }));
 
                         // Send to all connections subscribed to system-metrics or *
-                        if let Ok(conns) = connections.lock() {
-                            for conn in conns.values() {
-                                if conn.topics.contains(&"system-metrics".to_string()) || 
-                                   conn.topics.contains(&"*".to_string()) {
-                                    let _ = conn.sender.send(msg.clone());
-                                }
-                            }
-                        }
+                        let _ = dispatch(&connections, &history, "system-metrics", msg);
                     } else {
                         // If we can't lock the provider, send a ping to keep connections alive
-                        let msg = WsMessage::Ping;
-                        let _ = tx.send(msg.clone());
-                        
-                        if let Ok(conns) = connections.lock() {
-                            for conn in conns.values() {
-                                let _ = conn.sender.send(msg.clone());
-                            }
-                        }
+                        let _ = tx.send(WsMessage::Ping);
+                        ping_all(&connections);
                     }
                 } else {
                     // If we can't get the provider state, send a ping to keep connections alive
-                    let msg = WsMessage::Ping;
-                    let _ = tx.send(msg.clone());
-                    
-                    if let Ok(conns) = connections.lock() {
-                        for conn in conns.values() {
-                            let _ = conn.sender.send(msg.clone());
-                        }
-                    }
+                    let _ = tx.send(WsMessage::Ping);
+                    ping_all(&connections);
                 }
             }
         });
@@ -185,14 +501,15 @@ impl WsServer {
     }
 
     pub fn publish(&self, topic: &str, message: WsMessage) -> Result<(), String> {
-        let conns = self.connections.lock()
-            .map_err(|e| format!("Failed to lock connections: {}", e))?;
-        for conn in conns.values() {
-            if conn.topics.contains(&topic.to_string()) || conn.topics.contains(&"*".to_string()) {
-                let _ = conn.sender.send(message.clone());
-            }
-        }
-        Ok(())
+        dispatch(&self.connections, &self.history, topic, message).map(|_| ())
+    }
+
+    /// Messages on `topics` numbered after `since_seq`, for a client that
+    /// reconnected and wants to catch up.
+    pub fn replay(&self, topics: &[String], since_seq: u64) -> Result<WsReplay, String> {
+        let history = self.history.lock()
+            .map_err(|e| format!("Failed to lock history: {}", e))?;
+        Ok(history.replay(topics, since_seq))
     }
 
     pub fn get_connection_count(&self) -> usize {
@@ -216,8 +533,8 @@ impl WsServer {
     }
 
     /// Add a new connection and return a receiver for messages
-    pub fn add_connection(&self, id: String, topics: Vec<String>) -> Result<broadcast::Receiver<WsMessage>, String> {
-        let (tx, rx) = broadcast::channel::<WsMessage>(1000);
+    pub fn add_connection(&self, id: String, topics: Vec<String>) -> Result<broadcast::Receiver<WsEnvelope>, String> {
+        let (tx, rx) = broadcast::channel::<WsEnvelope>(1000);
         let conn = WsConnection {
             id: id.clone(),
             topics,
@@ -244,17 +561,7 @@ impl WsServer {
 
     /// Broadcast a message to all connections subscribed to a specific topic
     pub fn broadcast_to_topic(&self, topic: &str, message: WsMessage) -> Result<usize, String> {
-        let conns = self.connections.lock()
-            .map_err(|e| format!("Failed to lock connections: {}", e))?;
-        let mut count = 0;
-        for conn in conns.values() {
-            if conn.topics.contains(&topic.to_string()) || conn.topics.contains(&"*".to_string()) {
-                if conn.sender.send(message.clone()).is_ok() {
-                    count += 1;
-                }
-            }
-        }
-        Ok(count)
+        dispatch(&self.connections, &self.history, topic, message)
     }
 
     /// Get connection status
@@ -282,3 +589,92 @@ impl Default for WsServer {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update() -> WsMessage {
+        WsMessage::ConfigUpdate { key: "theme".to_string(), value: "dark".to_string() }
+    }
+
+    #[test]
+    fn replay_returns_missed_messages_and_flags_evicted_ones() {
+        let mut history = History::default();
+        assert_eq!(history.record("market-data", WsMessage::Ping).seq, None);
+        for _ in 0..3 {
+            history.record("config", update());
+            history.record("market-data", update());
+        }
+        let replay = history.replay(&["market-data".to_string()], 2);
+        let seqs: Vec<Option<u64>> = replay.messages.iter().map(|m| m.seq).collect();
+        assert_eq!(seqs, vec![Some(4), Some(6)]);
+        assert_eq!(replay.latest_seq, 6);
+        assert!(replay.complete);
+
+        for _ in 0..HISTORY_PER_TOPIC {
+            history.record("market-data", update());
+        }
+        let replay = history.replay(&["*".to_string()], 2);
+        assert!(!replay.complete);
+        assert_eq!(replay.messages.len(), HISTORY_PER_TOPIC + 2);
+    }
+
+    #[test]
+    fn broadcast_intervals_are_validated_and_paced() {
+        let intervals: BroadcastIntervals = serde_json::from_str(r#"{"processes_ms": 10000}"#).unwrap();
+        assert_eq!(intervals.processes_ms, 10_000);
+        assert_eq!(intervals.system_metrics_ms, BroadcastIntervals::default().system_metrics_ms);
+        assert!(BroadcastIntervals { market_data_ms: 50, ..intervals }.validate().is_err());
+        assert!(BroadcastIntervals { alerts_ms: 0, ..intervals }.validate().is_ok());
+
+        let server = WsServer::new();
+        server.set_broadcast_intervals(BroadcastIntervals { alerts_ms: 500, ..intervals }).unwrap();
+        server.publish_alert(serde_json::json!({ "id": 1 })).unwrap();
+        assert_eq!(server.pending_alerts.lock().unwrap().len(), 1);
+
+        let mut last = None;
+        assert!(due(&mut last, 60_000));
+        assert!(!due(&mut last, 60_000));
+        assert!(due(&mut last, 0));
+
+        let mut history = History::default();
+        assert_eq!(history.record("processes", WsMessage::ProcessBatch(Vec::new())).seq, None);
+    }
+
+    #[test]
+    fn batches_keep_the_latest_quote_per_ticker_and_round_trip_gzip() {
+        let quote = |ticker: &str, price: f64| WsEnvelope {
+            seq: None,
+            topic: "market-data".to_string(),
+            timestamp: 0,
+            message: WsMessage::MarketData(crate::storage::MarketPrice {
+                ticker: ticker.to_string(),
+                price,
+                change: 0.0,
+                change_percent: 0.0,
+                volume: 0,
+                timestamp: 0,
+                session: None,
+            }),
+        };
+        let kept = coalesce(vec![quote("NVDA", 1.0), quote("AAPL", 2.0), quote("NVDA", 3.0)]);
+        let prices: Vec<(String, f64)> = kept
+            .iter()
+            .filter_map(|e| match &e.message {
+                WsMessage::MarketData(p) => Some((p.ticker.clone(), p.price)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(prices, vec![("AAPL".to_string(), 2.0), ("NVDA".to_string(), 3.0)]);
+
+        use base64::Engine;
+        use std::io::Read;
+        let events = vec![serde_json::json!({ "type": "ping" })];
+        let encoded = WsEncoding::Gzip.encode(events.clone()).unwrap();
+        let bytes = base64::engine::general_purpose::STANDARD.decode(encoded.as_str().unwrap()).unwrap();
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(&bytes[..]).read_to_string(&mut json).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<serde_json::Value>>(&json).unwrap(), events);
+    }
+}
@@ -59,14 +59,14 @@ export const marketDataCommands: Command[] = [
       const now = Math.floor(Date.now() / 1000);
       const fromTs = now - days * 24 * 3600;
 
-      const data = await invoke<any[]>("get_chart_data", {
+      const data = await invoke<{ points: any[] }>("get_chart_data", {
         ticker,
         fromTs,
         toTs: now,
         interval,
       });
 
-      console.log(`Retrieved ${data.length} data points for ${ticker}`);
+      console.log(`Retrieved ${data.points.length} data points for ${ticker}`);
     },
     autocomplete: (args) => {
       if (args.length === 1) {
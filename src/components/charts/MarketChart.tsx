@@ -45,6 +45,12 @@ interface OHLCVData {
   volume: number;
 }
 
+interface ChartData {
+  points: OHLCVData[];
+  source_points: number;
+  downsampled: boolean;
+}
+
 export default function MarketChart({
   ticker,
   timeframe = "1d",
@@ -154,15 +160,17 @@ export default function MarketChart({
 
         // Load price data (will be implemented in backend)
         // For now, use mock data structure
-        const priceDataResult = await invoke<OHLCVData[]>("get_chart_data", {
+        const priceDataResult = await invoke<ChartData>("get_chart_data", {
           ticker,
           fromTs,
           toTs: now,
           interval: timeframe,
-        }).catch(() => {
-          // Fallback: return empty array if command doesn't exist yet
-          return [];
-        });
+        })
+          .then((data) => data.points)
+          .catch(() => {
+            // Fallback: return empty array if the data can't be loaded
+            return [] as OHLCVData[];
+          });
 
         if (priceDataResult && priceDataResult.length > 0) {
           setPriceData(priceDataResult);
@@ -291,12 +299,13 @@ export default function MarketChart({
               if (compTicker === ticker) continue;
               
               try {
-                const compData = await invoke<OHLCVData[]>("get_chart_data", {
+                const compData = (await invoke<ChartData>("get_chart_data", {
                   ticker: compTicker,
                   fromTs,
                   toTs: now,
                   interval: timeframe,
-                });
+                  mode: "lttb",
+                })).points;
                 
                 if (compData && compData.length > 0) {
                   // Normalize to percentage change for comparison
@@ -32,6 +32,16 @@ interface PromptTemplate {
   created_at: number;
 }
 
+interface ContextUsage {
+  model: string;
+  context_length: number;
+  budget_tokens: number;
+  used_tokens: number;
+  percent: number;
+  summarized_messages: number;
+  summary_tokens: number;
+}
+
 interface OllamaModel {
   name: string;
   size: number;
@@ -53,6 +63,7 @@ export default function AIConsciousness() {
   const [modelsFolder, setModelsFolder] = useState<string>("");
   const [availableModelFiles, setAvailableModelFiles] = useState<string[]>([]);
   const [isSending, setIsSending] = useState(false);
+  const [contextUsage, setContextUsage] = useState<ContextUsage | null>(null);
   
   // Modal states
   const [showConversationModal, setShowConversationModal] = useState(false);
@@ -173,6 +184,7 @@ export default function AIConsciousness() {
       });
       await loadConversations();
       setSelectedConversation(id);
+      setContextUsage(null);
       setConversationTitle("");
       setShowConversationModal(false);
       errorHandler.showSuccess("Conversation created successfully");
@@ -215,8 +227,8 @@ export default function AIConsciousness() {
         conversationId: selectedConversation,
       });
 
-      // Prepare messages for Ollama (last 10 messages for context)
-      const recentMessages = history.slice(-10).map(msg => ({
+      // Older turns that don't fit the context window are summarized server-side
+      const historyMessages = history.map(msg => ({
         role: msg.role,
         content: msg.content,
       }));
@@ -224,7 +236,8 @@ export default function AIConsciousness() {
       // Call Ollama
       const aiResponse = await invoke<string>("chat_with_ollama", {
         model: selectedModel,
-        messages: recentMessages,
+        messages: historyMessages,
+        conversationId: selectedConversation,
       });
 
       // Add AI response
@@ -237,6 +250,11 @@ export default function AIConsciousness() {
       });
 
       await loadMessages(selectedConversation);
+      setContextUsage(await invoke<ContextUsage>("get_context_usage", {
+        model: selectedModel,
+        messages: [...historyMessages, { role: "assistant", content: aiResponse }],
+        conversationId: selectedConversation,
+      }));
     } catch (error) {
       errorHandler.showError("Failed to send message", error);
       setInputMessage(userMessage); // Restore message on error
@@ -432,7 +450,10 @@ export default function AIConsciousness() {
               {conversations.map((conv) => (
                 <button
                   key={conv.id}
-                  onClick={() => setSelectedConversation(conv.id)}
+                  onClick={() => {
+                    setSelectedConversation(conv.id);
+                    setContextUsage(null);
+                  }}
                   className={`w-full text-left p-2 rounded glass-card transition-all ${
                     selectedConversation === conv.id
                       ? "border-2 border-neon-cyan"
@@ -489,6 +510,15 @@ export default function AIConsciousness() {
                 <div className="mb-4 p-2 glass-card rounded flex items-center gap-2">
                   <Cpu className="w-4 h-4 text-neon-cyan" />
                   <span className="text-sm text-gray-300">Model: <span className="text-neon-cyan">{selectedModel}</span></span>
+                  {contextUsage && contextUsage.model === selectedModel && (
+                    <span
+                      className={`ml-auto text-xs ${contextUsage.used_tokens > contextUsage.budget_tokens ? "text-neon-amber" : "text-gray-400"}`}
+                      title={`${contextUsage.used_tokens} / ${contextUsage.context_length} tokens`}
+                    >
+                      Context {Math.round(contextUsage.percent)}%
+                      {contextUsage.summarized_messages > 0 && ` · ${contextUsage.summarized_messages} earlier messages summarized`}
+                    </span>
+                  )}
                 </div>
               )}
               <div className="flex-1 overflow-y-auto space-y-4 mb-4">
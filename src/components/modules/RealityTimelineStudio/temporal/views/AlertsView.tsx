@@ -38,6 +38,16 @@ interface Alert {
   snoozed_until?: number | null;
 }
 
+interface AlertExplanation {
+  reasons: string[];
+  highlights: string[];
+}
+
+const explanationOf = (alert: Alert): AlertExplanation | null => {
+  const explanation = alert.payload_json?.explanation as AlertExplanation | undefined;
+  return explanation && Array.isArray(explanation.reasons) ? explanation : null;
+};
+
 const defaultRuleJson = {
   any: [{ type: "contains_keyword", keyword: "breaking" }],
   all: [],
@@ -242,6 +252,15 @@ export default function AlertsView() {
                     <span className="text-xs text-gray-500">{new Date(a.fired_at * 1000).toLocaleString()}</span>
                   </div>
                   <div className="text-xs text-gray-500 mt-1">status: {a.status}</div>
+                  {explanationOf(a) && explanationOf(a)!.reasons.length > 0 && (
+                    <div className="text-xs text-gray-400 mt-1">
+                      Why: {explanationOf(a)!.reasons.map((reason) => (
+                        <span key={reason} className="inline-block mr-2 px-1.5 py-0.5 rounded bg-neon-amber/10 text-neon-amber">
+                          {reason}
+                        </span>
+                      ))}
+                    </div>
+                  )}
                   <div className="flex items-center gap-2 mt-2 flex-wrap">
                     <Button variant="secondary" onClick={() => ack(a.id)}>
                       <Check className="w-4 h-4 mr-2" />
@@ -344,7 +363,7 @@ export default function AlertsView() {
               className="w-full bg-black/50 border border-white/10 rounded-lg px-3 py-2 text-xs text-white font-mono h-32"
             />
             <p className="text-xs text-gray-500 mt-1">
-              Configure escalation levels with delay_minutes and channels (email, sms, webhook, push)
+              Configure escalation levels with delay_minutes and channels (email, sms, webhook, push, slack, telegram)
             </p>
           </div>
           <div className="flex justify-end gap-2">
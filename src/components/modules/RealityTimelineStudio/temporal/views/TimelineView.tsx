@@ -22,6 +22,13 @@ interface TemporalEvent {
   volume_score: number;
   sentiment_score: number;
   cluster_key: string;
+  lifecycle_state: string;
+  previous_lifecycle_state?: string | null;
+  lifecycle_changed_at?: number | null;
+  source_count: number;
+  corroborated_by: number;
+  reliability_tier_count: number;
+  time_to_second_source?: number | null;
 }
 
 interface TemporalEvidence {